@@ -29,7 +29,7 @@ public partial class ComplaintsView : Control
         }
 
         if (_list != null) _list.ItemSelected += OnItemSelected;
-        if (_btnResolveRefund != null) _btnResolveRefund.Pressed += () => ResolveSelected("refund", 100.0); // Example amount
+        if (_btnResolveRefund != null) _btnResolveRefund.Pressed += () => ResolveSelected("monetary_relief", 100.0); // Example amount
         if (_btnResolveExplain != null) _btnResolveExplain.Pressed += () => ResolveSelected("explanation_only", 0.0);
     }
 
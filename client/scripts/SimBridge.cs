@@ -7,6 +7,9 @@ using System.Text.Json.Serialization;
 
 public partial class SimBridge : Node
 {
+    // Must match PROTOCOL_VERSION in core/src/protocol.rs
+    private const uint ProtocolVersion = 2;
+
     private Process _simProcess;
     private bool _isRunning = false;
 
@@ -54,6 +57,13 @@ public partial class SimBridge : Node
             ReadOutputAsync();
             ReadErrorAsync();
             GD.Print("SimRunner started successfully.");
+
+            // Handshake; sim-runner refuses a mismatched client and exits
+            WriteJson(new JsonObject
+            {
+                ["type"] = "get_protocol_version",
+                ["client_version"] = ProtocolVersion
+            });
         }
         catch (Exception e)
         {
@@ -107,6 +117,26 @@ public partial class SimBridge : Node
                 return;
             }
 
+            // Handshake reply; not a state update
+            if (root.TryGetProperty("protocol_version", out _))
+            {
+                return;
+            }
+
+            // Command result; the state after it rides along when accepted
+            if (root.TryGetProperty("accepted", out var acceptedElement))
+            {
+                if (!acceptedElement.GetBoolean())
+                {
+                    var errors = root.GetProperty("errors").ToString();
+                    GD.PrintErr($"Command rejected: {errors}");
+                    EmitSignal(SignalName.SimulationError, errors);
+                    return;
+                }
+                root = root.GetProperty("state");
+                json = root.GetRawText();
+            }
+
             EmitSignal(SignalName.StateUpdated, json);
             
             if (root.TryGetProperty("tick", out var tickElement))
@@ -0,0 +1,379 @@
+//! Adverse media screening (Phase 4.38).
+//!
+//! A news feed publishes a few stories a day, each about fraud, corruption
+//! or another financial crime. Some are about one of our customers, some
+//! about a business our customers have paid lately, and the rest about
+//! strangers — a share of whom happen to share a customer's or
+//! counterparty's name. Stories print a name, sometimes only an initial,
+//! and for people a country and sometimes a year of birth.
+//!
+//! Screening scores every customer and recent counterparty whose name is
+//! close enough against each story. The match score weighs the name with
+//! whichever identifiers the screened entity has on file; a detail the
+//! story leaves out earns half its weight, so namesakes in thin stories
+//! escalate while a mismatched year of birth clears them. Escalated hits
+//! feed the customer's AML risk rating, and strong hits on severe stories
+//! raise an alert and bring their EDD review forward. The story's subject
+//! is ground truth, kept on every match for false positive analysis.
+
+use crate::{
+    config::AdverseMediaConfig,
+    error::SimResult,
+    event::SimEvent,
+    name_generator::NameGenerator,
+    rng::SubsystemRng,
+    store::{
+        adverse_media::{AdverseMediaArticleRow, AdverseMediaMatchRow, ScreeningSubjectRow},
+        SimStore,
+    },
+    types::{RunId, Tick},
+};
+use std::collections::BTreeMap;
+
+/// Countries printed in stories about strangers abroad.
+const STRANGER_COUNTRIES: &[&str] = &["MX", "GB", "CN", "IN", "NG", "RU", "BR", "AE", "DE", "PH"];
+
+/// The trading name behind a counterparty reference. Deterministic, so the
+/// feed and screening agree on it.
+pub fn counterparty_name(counterparty: &str) -> String {
+    let hash = counterparty
+        .bytes()
+        .fold(0u64, |acc, b| acc.wrapping_mul(31).wrapping_add(b as u64));
+    NameGenerator::generate_business_name(&mut SubsystemRng::new(hash, 0))
+}
+
+/// Token-wise name similarity in 0..1: a matching word scores 1 and an
+/// initial matching a word's first letter scores 0.5, over the longer
+/// name's word count.
+pub fn name_similarity(a: &str, b: &str) -> f64 {
+    let words = |name: &str| -> Vec<String> {
+        name.split_whitespace()
+            .map(|w| w.trim_end_matches('.').to_lowercase())
+            .filter(|w| !w.is_empty())
+            .collect()
+    };
+    let (a, b) = (words(a), words(b));
+    if a.is_empty() || b.is_empty() {
+        return 0.0;
+    }
+    let score = |x: &str, y: &str| {
+        if x == y {
+            1.0
+        } else if (x.len() == 1 && y.starts_with(x)) || (y.len() == 1 && x.starts_with(y)) {
+            0.5
+        } else {
+            0.0
+        }
+    };
+    let total: f64 = a
+        .iter()
+        .map(|x| b.iter().map(|y| score(x, y)).fold(0.0, f64::max))
+        .sum();
+    total / a.len().max(b.len()) as f64
+}
+
+pub struct AdverseMedia {
+    run_id: RunId,
+    config: AdverseMediaConfig,
+}
+
+impl AdverseMedia {
+    pub fn new(run_id: RunId, config: AdverseMediaConfig) -> Self {
+        Self { run_id, config }
+    }
+
+    pub fn enabled(&self) -> bool {
+        self.config.enabled
+    }
+
+    pub fn config(&self) -> &AdverseMediaConfig {
+        &self.config
+    }
+
+    /// Publish today's stories and screen customers and counterparties
+    /// against them.
+    pub fn process(&self, store: &SimStore, tick: Tick, rng: &mut SubsystemRng) -> SimResult<Vec<SimEvent>> {
+        let subjects = store.adverse_media_subjects(&self.run_id)?;
+        let mut paid_by: BTreeMap<String, Vec<String>> = BTreeMap::new();
+        for (customer_id, counterparty) in store.recent_counterparties(
+            &self.run_id,
+            tick.saturating_sub(self.config.counterparty_lookback_ticks),
+        )? {
+            paid_by.entry(counterparty).or_default().push(customer_id);
+        }
+        let counterparties: Vec<(String, String)> = paid_by
+            .keys()
+            .map(|cp| (cp.clone(), counterparty_name(cp)))
+            .collect();
+
+        let articles = self.publish(&subjects, &counterparties, tick, rng);
+        let mut events = Vec::new();
+        let mut candidates = 0;
+        for article in &articles {
+            store.insert_adverse_media_article(&self.run_id, article)?;
+
+            let mut matches = Vec::new();
+            for subject in &subjects {
+                let sim = name_similarity(&article.name, &subject.name);
+                if sim < self.config.candidate_name_score {
+                    continue;
+                }
+                let birth_year = (!subject.business).then_some(subject.birth_year);
+                let score = self.match_score(article, sim, Some(&subject.country), birth_year);
+                matches.push(("customer", subject.customer_id.clone(), subject.customer_id.clone(), score));
+            }
+            if article.subject_type == "business" {
+                for (counterparty, name) in &counterparties {
+                    let sim = name_similarity(&article.name, name);
+                    if sim < self.config.candidate_name_score {
+                        continue;
+                    }
+                    let score = self.match_score(article, sim, None, None);
+                    for customer_id in &paid_by[counterparty] {
+                        matches.push(("counterparty", counterparty.clone(), customer_id.clone(), score));
+                    }
+                }
+            }
+
+            candidates += matches.len();
+            for (entity_kind, entity_id, customer_id, score) in matches {
+                events.extend(self.record_match(store, article, entity_kind, entity_id, customer_id, score, tick)?);
+            }
+        }
+
+        let escalated = events.len();
+        events.push(SimEvent::AdverseMediaScreened {
+            tick,
+            articles: articles.len() as i64,
+            candidates: candidates as i64,
+            escalated: escalated as i64,
+        });
+        Ok(events)
+    }
+
+    /// Today's stories, about customers, counterparties and strangers.
+    fn publish(
+        &self,
+        subjects: &[ScreeningSubjectRow],
+        counterparties: &[(String, String)],
+        tick: Tick,
+        rng: &mut SubsystemRng,
+    ) -> Vec<AdverseMediaArticleRow> {
+        let cfg = &self.config;
+        let mut count = cfg.articles_per_tick.floor() as usize;
+        if rng.chance(cfg.articles_per_tick.fract()) {
+            count += 1;
+        }
+
+        let mut articles = Vec::new();
+        for i in 0..count {
+            let Some(category) = cfg
+                .categories
+                .get(rng.next_u64_below(cfg.categories.len().max(1) as u64) as usize)
+            else {
+                break;
+            };
+            let mut article = AdverseMediaArticleRow {
+                article_id: format!("am-{tick}-{i}"),
+                tick,
+                subject_kind: "stranger".into(),
+                subject_id: None,
+                subject_type: "individual".into(),
+                name: String::new(),
+                country: None,
+                birth_year: None,
+                category: category.category.clone(),
+                severity: category.severity,
+            };
+
+            let roll = rng.next_f64();
+            if roll < cfg.customer_subject_rate && !subjects.is_empty() {
+                let subject = &subjects[rng.next_u64_below(subjects.len() as u64) as usize];
+                article.subject_kind = "customer".into();
+                article.subject_id = Some(subject.customer_id.clone());
+                article.name = subject.name.clone();
+                article.country = Some(subject.country.clone());
+                if subject.business {
+                    article.subject_type = "business".into();
+                } else {
+                    if rng.chance(cfg.name_variant_rate) {
+                        article.name = initialled(&subject.name);
+                    }
+                    article.birth_year = subject.birth_year.filter(|_| rng.chance(cfg.detail_rate));
+                }
+            } else if roll < cfg.customer_subject_rate + cfg.counterparty_subject_rate
+                && !counterparties.is_empty()
+            {
+                let (counterparty, name) = &counterparties[rng.next_u64_below(counterparties.len() as u64) as usize];
+                article.subject_kind = "counterparty".into();
+                article.subject_id = Some(counterparty.clone());
+                article.subject_type = "business".into();
+                article.name = name.clone();
+            } else {
+                self.stranger(&mut article, subjects, counterparties, rng);
+            }
+            articles.push(article);
+        }
+        articles
+    }
+
+    /// Fill in a story about someone we do not bank, who may share a
+    /// customer's or counterparty's name.
+    fn stranger(
+        &self,
+        article: &mut AdverseMediaArticleRow,
+        subjects: &[ScreeningSubjectRow],
+        counterparties: &[(String, String)],
+        rng: &mut SubsystemRng,
+    ) {
+        let business = rng.chance(0.5);
+        let namesake = rng.chance(self.config.namesake_rate);
+        article.name = match (business, namesake) {
+            (true, true) if !counterparties.is_empty() => {
+                counterparties[rng.next_u64_below(counterparties.len() as u64) as usize].1.clone()
+            }
+            (false, true) if !subjects.is_empty() => {
+                subjects[rng.next_u64_below(subjects.len() as u64) as usize].name.clone()
+            }
+            (true, _) => NameGenerator::generate_business_name(rng),
+            (false, _) => NameGenerator::generate_full_name(rng),
+        };
+        if business {
+            article.subject_type = "business".into();
+        } else {
+            article.country = Some(if rng.chance(0.6) {
+                "US".into()
+            } else {
+                STRANGER_COUNTRIES[rng.next_u64_below(STRANGER_COUNTRIES.len() as u64) as usize].into()
+            });
+            if rng.chance(self.config.detail_rate) {
+                article.birth_year = Some(1940 + rng.next_u64_below(66) as u32);
+            }
+        }
+    }
+
+    /// Weighted match score over the identifiers the screened entity has
+    /// on file, normalised to 0..1. A detail the story leaves out earns
+    /// half its weight; one it gets wrong earns none.
+    fn match_score(
+        &self,
+        article: &AdverseMediaArticleRow,
+        name_similarity: f64,
+        country: Option<&str>,
+        birth_year: Option<Option<u32>>,
+    ) -> f64 {
+        let cfg = &self.config;
+        let detail = |printed: Option<bool>| match printed {
+            None => 0.5,
+            Some(true) => 1.0,
+            Some(false) => 0.0,
+        };
+        let mut score = cfg.name_weight * name_similarity;
+        let mut weight = cfg.name_weight;
+        if let Some(country) = country {
+            score += cfg.country_weight * detail(article.country.as_deref().map(|c| c == country));
+            weight += cfg.country_weight;
+        }
+        if let Some(birth_year) = birth_year {
+            score += cfg.birth_year_weight * detail(article.birth_year.map(|y| Some(y) == birth_year));
+            weight += cfg.birth_year_weight;
+        }
+        if weight > 0.0 { score / weight } else { 0.0 }
+    }
+
+    /// Record one scored candidate. An escalated hit goes on the
+    /// customer's AML screening record; a strong hit on a severe story also
+    /// raises an alert and brings their EDD review forward.
+    #[allow(clippy::too_many_arguments)]
+    fn record_match(
+        &self,
+        store: &SimStore,
+        article: &AdverseMediaArticleRow,
+        entity_kind: &str,
+        entity_id: String,
+        customer_id: String,
+        score: f64,
+        tick: Tick,
+    ) -> SimResult<Option<SimEvent>> {
+        let cfg = &self.config;
+        let escalated = score >= cfg.match_threshold;
+        let edd_triggered = escalated
+            && entity_kind == "customer"
+            && score >= cfg.edd_match_score
+            && article.severity >= cfg.edd_severity;
+        store.insert_adverse_media_match(&self.run_id, &AdverseMediaMatchRow {
+            article_id: article.article_id.clone(),
+            entity_kind: entity_kind.to_string(),
+            true_match: article.subject_id.as_deref() == Some(entity_id.as_str()),
+            entity_id: entity_id.clone(),
+            customer_id: customer_id.clone(),
+            tick,
+            match_score: score,
+            escalated,
+            edd_triggered,
+        })?;
+        if !escalated {
+            return Ok(None);
+        }
+
+        let mut impact = article.severity * score * cfg.risk_impact_scale;
+        if entity_kind == "counterparty" {
+            impact *= cfg.counterparty_impact_share;
+        }
+        let details = serde_json::json!({
+            "article_id": article.article_id,
+            "printed_name": article.name,
+            "entity_kind": entity_kind,
+            "category": article.category,
+            "severity": article.severity,
+            "match_score": score,
+        })
+        .to_string();
+        store.insert_aml_screening_result(
+            &self.run_id,
+            &format!("aml-media-{}-{entity_id}-{customer_id}", article.article_id),
+            &customer_id,
+            tick as i64,
+            "adverse_media",
+            if score >= 0.95 { "exact_match" } else { "fuzzy_match" },
+            score,
+            Some(&article.article_id),
+            &details,
+            impact,
+        )?;
+        if edd_triggered {
+            store.insert_aml_alert(
+                &self.run_id,
+                &format!("aml-alert-media-{customer_id}-{}", article.article_id),
+                &customer_id,
+                tick as i64,
+                "adverse_media",
+                "high",
+                &format!("Adverse media: {} story naming {}", article.category, article.name),
+                &details,
+            )?;
+            store.expedite_edd_review(&self.run_id, &customer_id, tick + cfg.edd_due_ticks, tick)?;
+        }
+
+        Ok(Some(SimEvent::AdverseMediaHit {
+            tick,
+            customer_id,
+            article_id: article.article_id.clone(),
+            entity_kind: entity_kind.to_string(),
+            category: article.category.clone(),
+            match_score: score,
+            edd_triggered,
+        }))
+    }
+}
+
+/// "Jane Doe" as "J. Doe".
+fn initialled(name: &str) -> String {
+    match name.split_once(' ') {
+        Some((first, rest)) => first
+            .chars()
+            .next()
+            .map_or_else(|| name.to_string(), |initial| format!("{initial}. {rest}")),
+        None => name.to_string(),
+    }
+}
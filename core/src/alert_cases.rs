@@ -0,0 +1,88 @@
+//! Alert-to-case grouping (Phase 4.60).
+//!
+//! A new AML alert joins an open case instead of opening one of its own
+//! when the case already holds an alert on the same customer, or on a
+//! customer linked to them by strong enough relationships, raised within
+//! the grouping window. Repeat hits on one subject become one case to
+//! work, and case counts, sizes and totals measure real investigations
+//! rather than alert noise.
+//!
+//! The window is measured back from the alert to the case's newest
+//! alert, so a case stays open to new alerts as long as they keep
+//! coming. A case closes once none of its alerts is still open or under
+//! investigation.
+
+use crate::{
+    config::AlertGroupingConfig,
+    error::SimResult,
+    event::SimEvent,
+    store::SimStore,
+    types::{RunId, Tick},
+};
+
+pub struct AlertCases {
+    run_id: RunId,
+    config: AlertGroupingConfig,
+}
+
+impl AlertCases {
+    pub fn new(run_id: RunId, config: AlertGroupingConfig) -> Self {
+        Self { run_id, config }
+    }
+
+    pub fn enabled(&self) -> bool {
+        self.config.enabled
+    }
+
+    fn window(&self, alert_type: &str) -> Tick {
+        self.config.type_windows.get(alert_type).copied().unwrap_or(self.config.window_ticks)
+    }
+
+    /// Put every alert not yet in a case into one, oldest first.
+    pub fn group(&self, store: &SimStore, tick: Tick) -> SimResult<Vec<SimEvent>> {
+        let mut events = Vec::new();
+        for alert in store.ungrouped_alerts(&self.run_id)? {
+            let since = alert.tick.saturating_sub(self.window(&alert.alert_type));
+            match store.open_alert_case_for(
+                &self.run_id,
+                &alert.customer_id,
+                self.config.min_relationship_strength,
+                since,
+            )? {
+                Some(case_id) => {
+                    store.attach_alert_to_case(&self.run_id, &case_id, &alert)?;
+                    events.push(SimEvent::AlertGroupedIntoCase {
+                        tick,
+                        case_id,
+                        alert_id: alert.alert_id,
+                        customer_id: alert.customer_id,
+                    });
+                }
+                None => {
+                    let case_id = format!("case-{}", alert.alert_id);
+                    store.open_alert_case(&self.run_id, &case_id, &alert)?;
+                    events.push(SimEvent::AlertCaseOpened {
+                        tick,
+                        case_id,
+                        alert_id: alert.alert_id,
+                        customer_id: alert.customer_id,
+                    });
+                }
+            }
+        }
+        Ok(events)
+    }
+
+    /// Close the cases whose alerts have all been worked.
+    pub fn close_worked(&self, store: &SimStore, tick: Tick) -> SimResult<Vec<SimEvent>> {
+        Ok(store
+            .close_worked_alert_cases(&self.run_id, tick)?
+            .into_iter()
+            .map(|case| SimEvent::AlertCaseClosed {
+                tick,
+                case_id: case.case_id,
+                alert_count: case.alert_count as u32,
+            })
+            .collect())
+    }
+}
@@ -0,0 +1,188 @@
+//! Alert disposition QA (Phase 4.73).
+//!
+//! Every QA cycle a QA function re-reviews a sample of the SAR decisions
+//! made since the last one: the alerts filed on, defensively or not, and
+//! those missed. QA judges whether a filing was called for, as the
+//! reportable score says, but only as reliably as its reviewers; a
+//! disagreement is QA's finding, not proof of an error.
+//!
+//! The sample is the configured share of the cycle's decisions, capped
+//! at what QA has capacity to review, and each review costs QA time
+//! booked into opex. When an alert type's reviews in a cycle disagree
+//! often enough, a retraining work item opens for it. The item runs its
+//! course, its cost is booked on completion, and the next cycles' rates
+//! show whether the desk improved.
+
+use crate::{
+    config::AlertQaConfig,
+    error::SimResult,
+    event::SimEvent,
+    rng::SubsystemRng,
+    store::{
+        alert_qa::{AlertQaCycleRow, AlertQaReviewRow, QaCandidate, QaRetrainingRow},
+        SimStore,
+    },
+    types::{RunId, Tick},
+};
+use std::collections::BTreeMap;
+
+pub struct AlertQa {
+    run_id: RunId,
+    config: AlertQaConfig,
+    /// Alert score at or above which a filing is called for.
+    reportable_score: f64,
+}
+
+impl AlertQa {
+    pub fn new(run_id: RunId, config: AlertQaConfig, reportable_score: f64) -> Self {
+        Self { run_id, config, reportable_score }
+    }
+
+    pub fn enabled(&self) -> bool {
+        self.config.enabled
+    }
+
+    /// Re-review a sample of the cycle's decisions, if a cycle ends at
+    /// `tick`, and open retraining where an alert type fares badly.
+    pub fn review(&self, store: &SimStore, tick: Tick, rng: &mut SubsystemRng) -> SimResult<Vec<SimEvent>> {
+        let c = &self.config;
+        if tick == 0 || !tick.is_multiple_of(c.interval_ticks) {
+            return Ok(Vec::new());
+        }
+        let start = tick.saturating_sub(c.interval_ticks) + 1;
+        let mut candidates = store.unreviewed_sar_decisions(&self.run_id, start, tick)?;
+        let population = candidates.len();
+        let wanted = (population as f64 * c.sample_rate).ceil() as usize;
+        let sample_size = wanted.min(c.review_capacity).min(population);
+        // Partial Fisher–Yates: the first sample_size are a uniform sample
+        for i in 0..sample_size {
+            let j = i + rng.next_u64_below((population - i) as u64) as usize;
+            candidates.swap(i, j);
+        }
+        candidates.truncate(sample_size);
+
+        let mut events = Vec::new();
+        let mut by_type: BTreeMap<String, Vec<AlertQaReviewRow>> = BTreeMap::new();
+        for candidate in candidates {
+            let row = self.re_review(candidate, tick, rng);
+            store.insert_alert_qa_review(&self.run_id, &row)?;
+            by_type.entry(row.alert_type.clone()).or_default().push(row);
+        }
+
+        let disagreements = by_type.values().flatten().filter(|r| r.error_kind.is_some()).count();
+        let disagreement_rate = (sample_size > 0).then(|| disagreements as f64 / sample_size as f64);
+        let previous = store.latest_alert_qa_cycle(&self.run_id)?.and_then(|r| r.disagreement_rate);
+        let cycle = AlertQaCycleRow {
+            tick,
+            population: population as i64,
+            sampled: sample_size as i64,
+            disagreements: disagreements as i64,
+            disagreement_rate,
+            disagreement_trend: disagreement_rate.zip(previous).map(|(now, before)| now - before),
+        };
+        store.insert_alert_qa_cycle(&self.run_id, &cycle)?;
+        if wanted > sample_size {
+            tracing::debug!("alert QA short of capacity: {sample_size} of {wanted} reviews wanted");
+        }
+        events.push(SimEvent::AlertQaCycleCompleted {
+            tick,
+            population: cycle.population,
+            sampled: cycle.sampled,
+            disagreements: cycle.disagreements,
+            disagreement_rate,
+            disagreement_trend: cycle.disagreement_trend,
+        });
+
+        for (alert_type, reviews) in by_type {
+            if let Some(event) = self.maybe_retrain(store, &alert_type, &reviews, tick)? {
+                events.push(event);
+            }
+        }
+        Ok(events)
+    }
+
+    /// Complete the retraining due by `tick`.
+    pub fn complete_retraining(&self, store: &SimStore, tick: Tick) -> SimResult<Vec<SimEvent>> {
+        Ok(store
+            .complete_due_qa_retrainings(&self.run_id, tick)?
+            .into_iter()
+            .map(|item| SimEvent::QaRetrainingCompleted {
+                tick,
+                retraining_id: item.retraining_id,
+                alert_type: item.alert_type,
+                cost: item.cost,
+            })
+            .collect())
+    }
+
+    /// QA's view of one decision.
+    fn re_review(&self, candidate: QaCandidate, tick: Tick, rng: &mut SubsystemRng) -> AlertQaReviewRow {
+        let desk_filed = candidate.decision != "missed";
+        let called_for = candidate.alert_score >= self.reportable_score;
+        let qa_filed = if rng.chance(self.config.reviewer_accuracy) { called_for } else { !called_for };
+        let error_kind = match (desk_filed, qa_filed) {
+            (true, false) => Some("over_filing".to_string()),
+            (false, true) => Some("under_filing".to_string()),
+            _ => None,
+        };
+        AlertQaReviewRow {
+            alert_id: candidate.alert_id,
+            cycle_tick: tick,
+            customer_id: candidate.customer_id,
+            alert_type: candidate.alert_type,
+            alert_score: candidate.alert_score,
+            decision: candidate.decision,
+            desk_filed,
+            qa_filed,
+            error_kind,
+            cost: self.config.cost_per_review,
+        }
+    }
+
+    /// Open retraining on the alert type if enough of its reviews this
+    /// cycle disagreed and none is already under way.
+    fn maybe_retrain(
+        &self,
+        store: &SimStore,
+        alert_type: &str,
+        reviews: &[AlertQaReviewRow],
+        tick: Tick,
+    ) -> SimResult<Option<SimEvent>> {
+        let c = &self.config;
+        let count = |kind: &str| reviews.iter().filter(|r| r.error_kind.as_deref() == Some(kind)).count();
+        let (over, under) = (count("over_filing"), count("under_filing"));
+        let rate = (over + under) as f64 / reviews.len() as f64;
+        if reviews.len() < c.retraining_min_reviews
+            || rate < c.retraining_threshold
+            || store.qa_retraining_open_for(&self.run_id, alert_type)?
+        {
+            return Ok(None);
+        }
+        let error_kind = match over.cmp(&under) {
+            std::cmp::Ordering::Greater => "over_filing",
+            std::cmp::Ordering::Less => "under_filing",
+            std::cmp::Ordering::Equal => "mixed",
+        };
+        let item = QaRetrainingRow {
+            retraining_id: format!("qa-rt-{alert_type}-{tick}"),
+            alert_type: alert_type.to_string(),
+            error_kind: error_kind.into(),
+            disagreement_rate: rate,
+            opened_tick: tick,
+            due_tick: tick + c.retraining_ticks,
+            status: "open".into(),
+            completed_tick: None,
+            cost: c.retraining_cost,
+        };
+        store.insert_qa_retraining(&self.run_id, &item)?;
+        tracing::info!("QA retraining opened on {alert_type} alerts: {error_kind} ({:.0}% disagreement)", rate * 100.0);
+        Ok(Some(SimEvent::QaRetrainingOpened {
+            tick,
+            retraining_id: item.retraining_id,
+            alert_type: item.alert_type,
+            error_kind: item.error_kind,
+            disagreement_rate: rate,
+            due_tick: item.due_tick,
+        }))
+    }
+}
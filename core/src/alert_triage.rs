@@ -0,0 +1,143 @@
+//! Alert triage (Phase 4.85).
+//!
+//! The player works AML alerts by hand, through three commands:
+//!   - `assign_alert` puts an alert under investigation by an analyst.
+//!     The desk no longer files or clears it; the analyst owns it.
+//!   - `close_alert` closes it with a disposition: `false_positive`,
+//!     `suspicious` (suspicious, but not filed on) or `sar_filed`, which
+//!     files the SAR there and then. Closing a reportable alert without a
+//!     filing is a missed SAR when filing decisions are recorded.
+//!   - `escalate_alert_to_case` marks the alert's case escalated, opening
+//!     one if the alert is in none, and puts the alert under
+//!     investigation.
+//!
+//! Closing and escalating take a note, assigning may; each is kept
+//! against the alert with the analyst assigned at the time. Only alerts still open or under
+//! investigation can be worked; one the desk filed or cleared before
+//! the command landed is rejected with an `alert_triage_rejected` event.
+//!
+//! At the end of every tick the alert queue's size and age and the day's
+//! closures by disposition are written to `alert_aging`. An alert held
+//! under investigation still counts against the SAR deadline.
+
+use crate::{
+    config::AlertTriageConfig,
+    error::SimResult,
+    event::SimEvent,
+    store::SimStore,
+    transaction_monitoring_subsystem::AMLAlert,
+    types::{RunId, Tick},
+};
+
+/// How an analyst can close an alert.
+pub const DISPOSITIONS: &[&str] = &["false_positive", "suspicious", "sar_filed"];
+
+/// The alert if it is open or under investigation; otherwise why it
+/// cannot be worked.
+pub fn workable(alert: Option<AMLAlert>, alert_id: &str) -> Result<AMLAlert, String> {
+    match alert {
+        None => Err(format!("no alert {alert_id}")),
+        Some(alert) if alert.status == "open" || alert.status == "investigating" => Ok(alert),
+        Some(alert) => Err(format!("alert {alert_id} is {}", alert.status)),
+    }
+}
+
+pub struct AlertTriage {
+    run_id: RunId,
+    config: AlertTriageConfig,
+}
+
+impl AlertTriage {
+    pub fn new(run_id: RunId, config: AlertTriageConfig) -> Self {
+        Self { run_id, config }
+    }
+
+    pub fn enabled(&self) -> bool {
+        self.config.enabled
+    }
+
+    /// The alert, if it can still be worked; otherwise the rejection.
+    pub fn load(&self, store: &SimStore, alert_id: &str, tick: Tick) -> SimResult<Result<AMLAlert, SimEvent>> {
+        let alert = store.aml_alert(&self.run_id, alert_id)?;
+        Ok(workable(alert, alert_id).map_err(|reason| SimEvent::AlertTriageRejected {
+            tick,
+            alert_id: alert_id.into(),
+            reason,
+        }))
+    }
+
+    pub fn assign(
+        &self,
+        store: &SimStore,
+        alert_id: &str,
+        analyst: &str,
+        note: Option<&str>,
+        tick: Tick,
+    ) -> SimResult<SimEvent> {
+        if let Err(rejected) = self.load(store, alert_id, tick)? {
+            return Ok(rejected);
+        }
+        store.assign_alert(&self.run_id, alert_id, analyst)?;
+        if let Some(note) = note {
+            store.insert_alert_note(&self.run_id, alert_id, tick, Some(analyst), "assign", note)?;
+        }
+        Ok(SimEvent::AlertAssigned { tick, alert_id: alert_id.into(), analyst: analyst.into() })
+    }
+
+    /// Resolve an alert as `false_positive` or `suspicious`.
+    pub fn close(
+        &self,
+        store: &SimStore,
+        alert: &AMLAlert,
+        disposition: &str,
+        note: &str,
+        tick: Tick,
+    ) -> SimResult<SimEvent> {
+        store.close_alert(&self.run_id, &alert.alert_id, disposition, tick, note)?;
+        self.note(store, &alert.alert_id, "close", note, tick)?;
+        Ok(SimEvent::AlertClosed {
+            tick,
+            alert_id: alert.alert_id.clone(),
+            disposition: disposition.into(),
+        })
+    }
+
+    /// Keep an analyst's note against the alert, under whoever has it.
+    pub fn note(&self, store: &SimStore, alert_id: &str, action: &str, note: &str, tick: Tick) -> SimResult<()> {
+        let analyst = store.alert_assignee(&self.run_id, alert_id)?;
+        store.insert_alert_note(&self.run_id, alert_id, tick, analyst.as_deref(), action, note)
+    }
+
+    pub fn escalate(&self, store: &SimStore, alert_id: &str, note: &str, tick: Tick) -> SimResult<Vec<SimEvent>> {
+        let alert = match self.load(store, alert_id, tick)? {
+            Ok(alert) => alert,
+            Err(rejected) => return Ok(vec![rejected]),
+        };
+        let mut events = Vec::new();
+        let case_id = match store.alert_case_of(&self.run_id, alert_id)? {
+            Some(case_id) => case_id,
+            None => {
+                let case_id = format!("case-{alert_id}");
+                store.open_alert_case(&self.run_id, &case_id, &alert)?;
+                events.push(SimEvent::AlertCaseOpened {
+                    tick,
+                    case_id: case_id.clone(),
+                    alert_id: alert_id.into(),
+                    customer_id: alert.customer_id.clone(),
+                });
+                case_id
+            }
+        };
+        store.escalate_alert_case(&self.run_id, &case_id, tick)?;
+        store.start_alert_investigation(&self.run_id, alert_id)?;
+        self.note(store, alert_id, "escalate", note, tick)?;
+        events.push(SimEvent::AlertEscalated { tick, alert_id: alert_id.into(), case_id });
+        Ok(events)
+    }
+
+    /// Write the queue's metrics at the end of `tick`.
+    pub fn record_aging(&self, store: &SimStore, tick: Tick) -> SimResult<()> {
+        let aging = store.alert_aging_at(&self.run_id, tick, self.config.aged_after_ticks)?;
+        store.insert_alert_aging(&self.run_id, &aging)
+    }
+}
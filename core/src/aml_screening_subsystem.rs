@@ -7,21 +7,28 @@
 //!   4. Calculates customer AML risk ratings
 //!   5. Generates AML alerts for high-risk matches
 //!   6. Computes weekly AML metrics
+//!   7. Screens customers and counterparties against adverse media (Phase 4.38)
+//!
+//! Phase 4.89: sanctions hits are scored by `name_matching` and kept with
+//! an explanation of the score. The player sets how weak a match still
+//! counts as a hit with the sanctions sensitivity dial.
 
 use crate::{
+    adverse_media::AdverseMedia,
+    command::PlayerCommand,
+    config::{AdverseMediaConfig, SanctionsScreeningConfig},
     error::SimResult,
     event::SimEvent,
+    name_matching::{self, ListedName, NameMatcher, ScreenedName},
     rng::SubsystemRng,
-    store::SimStore,
-    subsystem::SimSubsystem,
+    store::{OFACWatchlistRow, SimStore},
+    subsystem::{SimSubsystem, SubsystemManifest},
     types::{RunId, Tick},
 };
 use std::any::Any;
 
 // ── Constants ────────────────────────────────────────────────────────────────
 
-const OFAC_EXACT_MATCH_THRESHOLD: f64 = 0.95;
-const OFAC_FUZZY_MATCH_THRESHOLD: f64 = 0.80;
 const PEP_NAME_MATCH_THRESHOLD: f64 = 0.85;
 const RISK_RATING_THRESHOLD_HIGH: f64 = 0.60;
 const RISK_RATING_THRESHOLD_CRITICAL: f64 = 0.80;
@@ -30,128 +37,235 @@ const METRICS_INTERVAL: u64 = 7; // Weekly
 
 // ── Subsystem ────────────────────────────────────────────────────────────────
 
+/// A watchlist entry with its aliases, countries and year of birth
+/// unpacked.
+struct Listing<'a> {
+    entry: &'a OFACWatchlistRow,
+    aliases: Vec<String>,
+    countries: Vec<String>,
+    birth_year: Option<u32>,
+}
+
+impl<'a> Listing<'a> {
+    fn new(entry: &'a OFACWatchlistRow) -> Self {
+        let list = |json: &Option<String>| -> Vec<String> {
+            json.as_deref().and_then(|j| serde_json::from_str(j).ok()).unwrap_or_default()
+        };
+        Self {
+            entry,
+            aliases: list(&entry.aliases),
+            countries: list(&entry.country_codes),
+            birth_year: entry.date_of_birth.as_deref().and_then(|d| d.get(..4)?.parse().ok()),
+        }
+    }
+
+    fn listed_name(&self) -> ListedName<'_> {
+        ListedName {
+            name: &self.entry.full_name,
+            aliases: &self.aliases,
+            countries: &self.countries,
+            birth_year: self.birth_year,
+        }
+    }
+}
+
 pub struct AMLScreeningSubsystem {
     run_id: RunId,
+    adverse_media: AdverseMedia,
+    /// Phase 4.89: sanctions name matching, its sensitivity set by the
+    /// player.
+    sanctions: SanctionsScreeningConfig,
     store: SimStore,
 }
 
 impl AMLScreeningSubsystem {
-    pub fn new(run_id: RunId, store: SimStore) -> Self {
-        Self { run_id, store }
+    pub fn new(
+        run_id: RunId,
+        adverse_media: AdverseMediaConfig,
+        sanctions: SanctionsScreeningConfig,
+        store: SimStore,
+    ) -> Self {
+        Self {
+            adverse_media: AdverseMedia::new(run_id.clone(), adverse_media),
+            run_id,
+            sanctions,
+            store,
+        }
     }
 
-    /// Screen new customers against OFAC sanctions list.
-    fn screen_ofac(
-        &self,
-        tick: Tick,
-        rng: &mut SubsystemRng,
-    ) -> SimResult<Vec<SimEvent>> {
-        let mut events = Vec::new();
-
-        // Get customers onboarded in last 7 days (new customers)
-        let recent_customers = self.store.get_customers_onboarded_in_window(
-            &self.run_id,
-            tick.saturating_sub(7) as i64,
-            tick as i64,
-        )?;
-
-        for customer in recent_customers {
-            // Generate synthetic customer name for matching (name not stored in DB)
-            let customer_name = self.generate_customer_name(&customer.customer_id, rng);
-
-            // Check against OFAC watchlist
-            let watchlist = self.store.get_ofac_watchlist()?;
-
-            for entry in &watchlist {
-                // Calculate name match score (simplified fuzzy matching)
-                let match_score = self.calculate_name_match_score(
-                    &customer_name,
-                    &entry.full_name,
-                    rng,
-                );
-
-                if match_score >= OFAC_FUZZY_MATCH_THRESHOLD {
-                    let match_type = if match_score >= OFAC_EXACT_MATCH_THRESHOLD {
-                        "exact_match"
-                    } else {
-                        "fuzzy_match"
-                    };
-
-                    let screening_id = format!(
-                        "aml-ofac-{}-{}-{}",
-                        customer.customer_id,
-                        entry.entity_id,
-                        tick
-                    );
+    /// Match score a sanctions hit needs at the current sensitivity.
+    pub fn match_threshold(&self) -> f64 {
+        let cfg = &self.sanctions;
+        cfg.strict_match_threshold - cfg.sensitivity * (cfg.strict_match_threshold - cfg.lenient_match_threshold)
+    }
 
-                    let details = serde_json::json!({
-                        "customer_name": customer_name,
-                        "watchlist_name": entry.full_name,
-                        "program": entry.program,
-                        "match_score": match_score,
-                    }).to_string();
+    /// Apply player changes to the sanctions sensitivity (Phase 4.89).
+    fn apply_player_commands(&mut self, tick: Tick, events_in: &[SimEvent]) -> SimResult<Vec<SimEvent>> {
+        let mut events = Vec::new();
+        for event in events_in {
+            let SimEvent::PlayerCommandReceived { command_id, .. } = event else {
+                continue;
+            };
+            if let Some(PlayerCommand::SetSanctionsSensitivity { sensitivity }) =
+                self.store.get_player_command(&self.run_id, command_id)?
+            {
+                self.sanctions.sensitivity = sensitivity.clamp(0.0, 1.0);
+                tracing::info!("sanctions hits need a match score of {:.2}", self.match_threshold());
+                events.push(SimEvent::SanctionsSensitivityChanged {
+                    tick,
+                    sensitivity: self.sanctions.sensitivity,
+                    match_threshold: self.match_threshold(),
+                });
+            }
+        }
+        Ok(events)
+    }
 
-                    self.store.insert_aml_screening_result(
-                        &self.run_id,
-                        &screening_id,
-                        &customer.customer_id,
-                        tick as i64,
-                        "ofac_sanctions",
-                        match_type,
-                        match_score,
-                        Some(&entry.entity_id),
-                        &details,
-                        if entry.program == "SDN" { 0.50 } else { 0.30 },
-                    )?;
+    /// The listed party a customer really is, if any, and the name they
+    /// give. Deterministic in the customer id, so every screening of the
+    /// customer agrees.
+    fn listed_party<'l>(&self, customer_id: &str, listings: &'l [Listing]) -> Option<(&'l Listing<'l>, &'l str)> {
+        let hash = customer_id.bytes().fold(0u64, |acc, b| acc.wrapping_mul(31).wrapping_add(b as u64));
+        let mut rng = SubsystemRng::new(hash, 0);
+        if listings.is_empty() || !rng.chance(self.sanctions.listed_party_rate) {
+            return None;
+        }
+        let listing = &listings[rng.next_u64_below(listings.len() as u64) as usize];
+        let name = if !listing.aliases.is_empty() && rng.chance(self.sanctions.alias_use_rate) {
+            &listing.aliases[rng.next_u64_below(listing.aliases.len() as u64) as usize]
+        } else {
+            &listing.entry.full_name
+        };
+        Some((listing, name))
+    }
 
-                    // Generate critical alert for SDN matches
-                    if entry.program == "SDN" && match_score >= OFAC_EXACT_MATCH_THRESHOLD {
-                        let alert_id = format!("aml-alert-ofac-{}-{}", customer.customer_id, tick);
+    /// Screen new customers against the OFAC sanctions list, keeping each
+    /// one's best match if it scores a hit.
+    fn screen_ofac(&self, tick: Tick) -> SimResult<Vec<SimEvent>> {
+        let mut events = Vec::new();
+        let matcher = NameMatcher::new(&self.sanctions);
+        let threshold = self.match_threshold();
+        let watchlist = self.store.get_ofac_watchlist()?;
+        let listings: Vec<Listing> = watchlist.iter().map(Listing::new).collect();
+
+        // Customers onboarded in last 7 days (new customers)
+        let subjects = self.store.screening_subjects_onboarded(&self.run_id, tick.saturating_sub(7), tick)?;
+
+        for subject in subjects {
+            // A listed party gives a listed name and the listing's details;
+            // everyone else a synthetic name (namesakes included) and their own
+            let listed = self.listed_party(&subject.customer_id, &listings);
+            let customer_name = match listed {
+                Some((_, name)) => name.to_string(),
+                None => self.generate_customer_name(&subject.customer_id),
+            };
+            let screened = match listed {
+                Some((listing, _)) => ScreenedName {
+                    name: &customer_name,
+                    country: listing.countries.first().map(String::as_str),
+                    birth_year: listing.birth_year,
+                },
+                None => ScreenedName {
+                    name: &customer_name,
+                    country: Some(&subject.country),
+                    birth_year: subject.birth_year,
+                },
+            };
 
-                        self.store.insert_aml_alert(
-                            &self.run_id,
-                            &alert_id,
-                            &customer.customer_id,
-                            tick as i64,
-                            "sanctions_hit",
-                            "critical",
-                            &format!("OFAC SDN exact match: {}", entry.full_name),
-                            &details,
-                        )?;
+            let best = listings
+                .iter()
+                .filter_map(|l| matcher.score(&screened, &l.listed_name()).map(|m| (l, m)))
+                .max_by(|a, b| a.1.score.total_cmp(&b.1.score));
+            let Some((listing, name_match)) = best.filter(|(_, m)| m.score >= threshold) else {
+                continue;
+            };
+            let entry = listing.entry;
+            let match_score = name_match.score;
+            let match_type = if match_score >= self.sanctions.exact_match_threshold {
+                "exact_match"
+            } else if name_match.via_alias {
+                "alias_match"
+            } else {
+                "fuzzy_match"
+            };
+            let true_match = listed.is_some_and(|(l, _)| l.entry.entity_id == entry.entity_id);
+
+            let screening_id = format!(
+                "aml-ofac-{}-{}-{}",
+                subject.customer_id,
+                entry.entity_id,
+                tick
+            );
+
+            let details = serde_json::json!({
+                "customer_name": customer_name,
+                "watchlist_name": entry.full_name,
+                "matched_name": name_match.matched_name,
+                "program": entry.program,
+                "match_score": match_score,
+                "name_score": name_match.name_score,
+                "match_threshold": threshold,
+            }).to_string();
+
+            self.store.insert_aml_screening_result(
+                &self.run_id,
+                &screening_id,
+                &subject.customer_id,
+                tick as i64,
+                "ofac_sanctions",
+                match_type,
+                match_score,
+                Some(&entry.entity_id),
+                &details,
+                if entry.program == "SDN" { 0.50 } else { 0.30 },
+            )?;
+            self.store.annotate_screening_result(
+                &self.run_id,
+                &screening_id,
+                &name_match.explanation,
+                Some(true_match),
+            )?;
 
-                        events.push(SimEvent::AMLAlertGenerated {
-                            tick,
-                            alert_id,
-                            alert_type: "sanctions_hit".to_string(),
-                            customer_id: customer.customer_id.clone(),
-                            severity: "critical".to_string(),
-                            risk_score: match_score,
-                        });
-                    }
+            // Generate critical alert for SDN matches
+            if entry.program == "SDN" && match_type == "exact_match" {
+                let alert_id = format!("aml-alert-ofac-{}-{}", subject.customer_id, tick);
 
-                    events.push(SimEvent::AMLScreeningHit {
-                        tick,
-                        screening_id,
-                        screening_type: "ofac_sanctions".to_string(),
-                        customer_id: customer.customer_id.clone(),
-                        match_type: match_type.to_string(),
-                        match_score,
-                    });
+                self.store.insert_aml_alert(
+                    &self.run_id,
+                    &alert_id,
+                    &subject.customer_id,
+                    tick as i64,
+                    "sanctions_hit",
+                    "critical",
+                    &format!("OFAC SDN exact match: {}", entry.full_name),
+                    &details,
+                )?;
 
-                    break; // Only record first match per customer
-                }
+                events.push(SimEvent::AMLAlertGenerated {
+                    tick,
+                    alert_id,
+                    alert_type: "sanctions_hit".to_string(),
+                    customer_id: subject.customer_id.clone(),
+                    severity: "critical".to_string(),
+                    risk_score: match_score,
+                });
             }
+
+            events.push(SimEvent::AMLScreeningHit {
+                tick,
+                screening_id,
+                screening_type: "ofac_sanctions".to_string(),
+                customer_id: subject.customer_id.clone(),
+                match_type: match_type.to_string(),
+                match_score,
+            });
         }
 
         Ok(events)
     }
 
     /// Identify Politically Exposed Persons (PEPs).
-    fn screen_pep(
-        &self,
-        tick: Tick,
-        rng: &mut SubsystemRng,
-    ) -> SimResult<Vec<SimEvent>> {
+    fn screen_pep(&self, tick: Tick) -> SimResult<Vec<SimEvent>> {
         let mut events = Vec::new();
 
         // Get recently onboarded customers
@@ -163,17 +277,13 @@ impl AMLScreeningSubsystem {
 
         for customer in recent_customers {
             // Generate synthetic customer name for matching
-            let customer_name = self.generate_customer_name(&customer.customer_id, rng);
+            let customer_name = self.generate_customer_name(&customer.customer_id);
 
             // Check against PEP registry
             let pep_registry = self.store.get_pep_registry()?;
 
             for pep in &pep_registry {
-                let match_score = self.calculate_name_match_score(
-                    &customer_name,
-                    &pep.full_name,
-                    rng,
-                );
+                let match_score = name_matching::token_similarity(&customer_name, &pep.full_name);
 
                 if match_score >= PEP_NAME_MATCH_THRESHOLD {
                     let screening_id = format!(
@@ -364,6 +474,7 @@ impl AMLScreeningSubsystem {
             let mut sanctions_risk = 0.0;
             let mut pep_risk = 0.0;
             let mut jurisdiction_risk = 0.0;
+            let mut adverse_media_risk = 0.0;
 
             // Aggregate screening results from last 90 days
             let screenings = self.store.get_customer_aml_screenings(
@@ -384,6 +495,9 @@ impl AMLScreeningSubsystem {
                     "jurisdiction_risk" => {
                         jurisdiction_risk = f64::max(jurisdiction_risk, screening.risk_impact);
                     }
+                    "adverse_media" => {
+                        adverse_media_risk = f64::max(adverse_media_risk, screening.risk_impact);
+                    }
                     _ => {}
                 }
             }
@@ -398,6 +512,7 @@ impl AMLScreeningSubsystem {
             let mut risk_score = sanctions_risk * 2.0  // Sanctions are most critical
                 + pep_risk * 1.5
                 + jurisdiction_risk * 1.2
+                + adverse_media_risk * 1.2
                 + transaction_risk * 0.8
                 + behavioral_risk * 0.5;
 
@@ -450,6 +565,7 @@ impl AMLScreeningSubsystem {
                         "sanctions_risk": sanctions_risk,
                         "pep_risk": pep_risk,
                         "jurisdiction_risk": jurisdiction_risk,
+                        "adverse_media_risk": adverse_media_risk,
                     }).to_string(),
                 )?;
 
@@ -509,8 +625,8 @@ impl AMLScreeningSubsystem {
     }
 
     /// Generate a synthetic customer name from customer_id for screening.
-    /// Uses deterministic RNG to ensure reproducibility.
-    fn generate_customer_name(&self, customer_id: &str, rng: &mut SubsystemRng) -> String {
+    /// Deterministic in the customer id, to ensure reproducibility.
+    fn generate_customer_name(&self, customer_id: &str) -> String {
         const FIRST_NAMES: &[&str] = &[
             "John", "Maria", "Chen", "Ahmed", "Sofia", "Nikolai", "Elena", "Jean",
             "Alexander", "Li", "Carlos", "Anna", "David", "Yuki", "Mohammed",
@@ -526,57 +642,36 @@ impl AMLScreeningSubsystem {
 
         format!("{} {}", FIRST_NAMES[first_idx], LAST_NAMES[last_idx])
     }
-
-    /// Simplified fuzzy name matching with deterministic noise.
-    fn calculate_name_match_score(
-        &self,
-        name1: &str,
-        name2: &str,
-        rng: &mut SubsystemRng,
-    ) -> f64 {
-        let n1 = name1.to_lowercase();
-        let n2 = name2.to_lowercase();
-
-        // Exact match
-        if n1 == n2 {
-            return 1.0;
-        }
-
-        // Simple heuristic: count matching words
-        let words1: Vec<&str> = n1.split_whitespace().collect();
-        let words2: Vec<&str> = n2.split_whitespace().collect();
-
-        if words1.is_empty() || words2.is_empty() {
-            return 0.0;
-        }
-
-        let mut matching_words = 0;
-        for w1 in &words1 {
-            for w2 in &words2 {
-                if w1 == w2 || w1.starts_with(w2) || w2.starts_with(w1) {
-                    matching_words += 1;
-                    break;
-                }
-            }
-        }
-
-        let base_score = matching_words as f64 / words1.len().max(words2.len()) as f64;
-
-        // Add deterministic noise
-        let noise = rng.next_f64() * 0.05;
-        (base_score + noise).min(1.0)
-    }
 }
 
+const MANIFEST: SubsystemManifest = SubsystemManifest {
+    version: 1,
+    produces: &[
+        "adverse_media_hit", "adverse_media_screened", "aml_alert_generated",
+        "aml_metrics_computed", "aml_risk_rating_computed", "aml_screening_hit",
+        "sanctions_sensitivity_changed",
+    ],
+    consumes: &["player_command_received"],
+    tables: &[
+        "adverse_media_article", "adverse_media_match", "aml_alert", "aml_metrics",
+        "aml_screening_result", "customer_aml_risk", "edd_review",
+    ],
+    requires: &[],
+};
+
 impl SimSubsystem for AMLScreeningSubsystem {
     fn name(&self) -> &'static str {
         "aml_screening"
     }
 
+    fn manifest(&self) -> SubsystemManifest {
+        MANIFEST
+    }
+
     fn update(
         &mut self,
         tick: Tick,
-        _events_in: &[SimEvent],
+        events_in: &[SimEvent],
         rng: &mut SubsystemRng,
     ) -> SimResult<Vec<SimEvent>> {
         let mut events = Vec::new();
@@ -585,15 +680,23 @@ impl SimSubsystem for AMLScreeningSubsystem {
             return Ok(events);
         }
 
+        // 0. Phase 4.89: the player's sanctions sensitivity
+        events.extend(self.apply_player_commands(tick, events_in)?);
+
         // 1. OFAC sanctions screening (daily for new customers)
-        events.extend(self.screen_ofac(tick, rng)?);
+        events.extend(self.screen_ofac(tick)?);
 
         // 2. PEP screening (daily for new customers)
-        events.extend(self.screen_pep(tick, rng)?);
+        events.extend(self.screen_pep(tick)?);
 
         // 3. Jurisdiction risk assessment (daily for new international customers)
         events.extend(self.assess_jurisdiction_risk(tick)?);
 
+        // 3b. Phase 4.38: adverse media screening (daily)
+        if self.adverse_media.enabled() {
+            events.extend(self.adverse_media.process(&self.store, tick, rng)?);
+        }
+
         // 4. Calculate customer risk ratings (monthly)
         events.extend(self.calculate_risk_ratings(tick, rng)?);
 
@@ -603,6 +706,24 @@ impl SimSubsystem for AMLScreeningSubsystem {
         Ok(events)
     }
 
+    fn save_state(&self) -> SimResult<Option<serde_json::Value>> {
+        Ok(Some(serde_json::json!({ "sanctions_sensitivity": self.sanctions.sensitivity })))
+    }
+
+    fn load_state(&mut self, state: serde_json::Value) -> SimResult<()> {
+        self.sanctions.sensitivity = serde_json::from_value(state["sanctions_sensitivity"].clone())?;
+        Ok(())
+    }
+
+    fn replay(&mut self, _tick: Tick, events: &[SimEvent]) -> SimResult<()> {
+        for event in events {
+            if let SimEvent::SanctionsSensitivityChanged { sensitivity, .. } = event {
+                self.sanctions.sensitivity = *sensitivity;
+            }
+        }
+        Ok(())
+    }
+
     fn as_any(&self) -> &dyn Any {
         self
     }
@@ -0,0 +1,213 @@
+//! Authorized push payment fraud (Phase 4.91).
+//!
+//! RTP and FedNow settle within the tick a payment is sent, and once
+//! sent it is final: no recall brings it back. Scammers know it. Now and
+//! then one gets a retail customer on the line — posing as the bank,
+//! selling something that does not exist, offering an investment, or
+//! courting them — and some customers are talked into pushing a large
+//! share of their balance to the scammer's account on an instant rail.
+//! The customer authorized the payment, so nothing looks amiss.
+//!
+//! Most victims realise within a couple of weeks and report it, claiming
+//! reimbursement. The fraud desk decides each claim a few ticks later
+//! under the bank's reimbursement policy, which the player sets: pay
+//! nobody, pay only vulnerable customers, or pay everyone. A paid claim
+//! leaves the customer an excess and is capped; the bank books the rest
+//! as a loss. A declined claim leaves the customer to bear it and may
+//! prompt a complaint.
+
+use crate::{
+    command::PlayerCommand,
+    config::AppFraudConfig,
+    error::SimResult,
+    event::SimEvent,
+    rng::SubsystemRng,
+    store::{app_fraud::AppScamPaymentRow, AccountRow, SimStore},
+    types::{RunId, Tick},
+};
+use std::collections::{HashMap, HashSet};
+use uuid::Uuid;
+
+/// Reimbursement policies, least generous first.
+pub const POLICIES: &[&str] = &["none", "vulnerable", "full"];
+
+pub struct AppFraud {
+    run_id: RunId,
+    config: AppFraudConfig,
+}
+
+impl AppFraud {
+    pub fn new(run_id: RunId, config: AppFraudConfig) -> Self {
+        Self { run_id, config }
+    }
+
+    pub fn enabled(&self) -> bool {
+        self.config.enabled
+    }
+
+    pub fn policy(&self) -> &str {
+        &self.config.reimbursement_policy
+    }
+
+    /// Decide claims from now on under `policy`.
+    pub fn set_policy(&mut self, policy: &str) {
+        self.config.reimbursement_policy = policy.to_string();
+    }
+
+    /// What a claim for `amount` gets under the current policy.
+    pub fn reimbursement(&self, amount: f64, vulnerable: bool) -> f64 {
+        let c = &self.config;
+        let covered = match c.reimbursement_policy.as_str() {
+            "full" => true,
+            "vulnerable" => vulnerable,
+            _ => false,
+        };
+        if !covered {
+            return 0.0;
+        }
+        (amount - c.excess).clamp(0.0, c.reimbursement_cap)
+    }
+
+    /// Let today's scammers at the customers and send the payments of
+    /// those who fall for it.
+    pub fn book_scams(
+        &self,
+        store: &SimStore,
+        accounts: &[AccountRow],
+        tick: Tick,
+        rng: &mut SubsystemRng,
+    ) -> SimResult<Vec<SimEvent>> {
+        let c = &self.config;
+        if c.scam_types.is_empty() || c.rails.is_empty() {
+            return Ok(Vec::new());
+        }
+        let targets: HashSet<String> = store.customers_in_segments(&self.run_id, &c.segments)?.into_iter().collect();
+        let mut first_account: HashMap<&str, &AccountRow> = HashMap::new();
+        for acct in accounts.iter().filter(|a| targets.contains(&a.customer_id)) {
+            first_account.entry(acct.customer_id.as_str()).or_insert(acct);
+        }
+        let mut victims: Vec<&AccountRow> = first_account.into_values().collect();
+        victims.sort_by(|a, b| a.account_id.cmp(&b.account_id));
+
+        let (mut payments, mut total) = (0, 0.0);
+        for acct in victims {
+            if !rng.chance(c.scam_rate) || !rng.chance(c.fall_rate) {
+                continue;
+            }
+            let scam_type = &c.scam_types[rng.next_u64_below(c.scam_types.len() as u64) as usize];
+            let rail_id = &c.rails[rng.next_u64_below(c.rails.len() as u64) as usize];
+            let share = c.share_min + (c.share_max - c.share_min) * rng.next_f64();
+            let amount = (acct.balance * share * 100.0).floor() / 100.0;
+            if amount < c.min_payment {
+                continue;
+            }
+            let payee = format!("payee-{:08x}", rng.next_u64() as u32);
+            let report_tick = rng.chance(c.report_rate).then(|| {
+                let spread = c.report_max_ticks.saturating_sub(c.report_min_ticks);
+                tick + c.report_min_ticks + rng.next_u64_below(spread + 1)
+            });
+            let row = AppScamPaymentRow {
+                txn_id: Uuid::new_v4().to_string(),
+                customer_id: acct.customer_id.clone(),
+                account_id: acct.account_id.clone(),
+                tick,
+                amount,
+                rail_id: rail_id.clone(),
+                scam_type: scam_type.clone(),
+                payee,
+                vulnerable: store.customer_is_vulnerable(&self.run_id, &acct.customer_id, c.vulnerable_age)?,
+                report_tick,
+                claim_status: None,
+                decided_tick: None,
+                policy: None,
+                reimbursed: 0.0,
+            };
+            store.insert_transaction_with_rail(
+                &self.run_id,
+                &row.txn_id,
+                &row.account_id,
+                tick,
+                amount,
+                "debit",
+                "p2p_transfer",
+                Some(&row.payee),
+                rail_id,
+                "settled",
+            )?;
+            store.update_account_balance(&self.run_id, &row.account_id, -amount)?;
+            store.insert_app_scam_payment(&self.run_id, &row)?;
+            payments += 1;
+            total += amount;
+        }
+
+        if payments == 0 {
+            return Ok(Vec::new());
+        }
+        Ok(vec![SimEvent::AppScamPaymentsBooked { tick, payments, amount: total }])
+    }
+
+    /// Apply the player's policy changes, open today's claims and decide
+    /// those the desk has come to.
+    pub fn process(&mut self, store: &SimStore, tick: Tick, commands: &[PlayerCommand]) -> SimResult<Vec<SimEvent>> {
+        let mut events = Vec::new();
+        for command in commands {
+            let PlayerCommand::SetAppReimbursementPolicy { policy } = command else {
+                continue;
+            };
+            self.set_policy(policy);
+            tracing::info!("APP scam claims now reimbursed under the {policy} policy");
+            events.push(SimEvent::AppReimbursementPolicyChanged { tick, policy: policy.clone() });
+        }
+
+        for mut row in store.app_scams_reported_on(&self.run_id, tick)? {
+            row.claim_status = Some("pending".into());
+            store.update_app_claim(&self.run_id, &row)?;
+            events.push(SimEvent::AppScamReported {
+                tick,
+                txn_id: row.txn_id,
+                customer_id: row.customer_id,
+                account_id: row.account_id,
+                scam_type: row.scam_type,
+                amount: row.amount,
+            });
+        }
+
+        let Some(reported_by) = tick.checked_sub(self.config.decision_ticks) else {
+            return Ok(events);
+        };
+        for mut row in store.app_claims_due(&self.run_id, reported_by)? {
+            let reimbursed = self.reimbursement(row.amount, row.vulnerable);
+            if reimbursed > 0.0 {
+                store.insert_transaction_with_rail(
+                    &self.run_id,
+                    &Uuid::new_v4().to_string(),
+                    &row.account_id,
+                    tick,
+                    reimbursed,
+                    "credit",
+                    "app_reimbursement",
+                    None,
+                    "ACH",
+                    "settled",
+                )?;
+                store.update_account_balance(&self.run_id, &row.account_id, reimbursed)?;
+            }
+            let outcome = if reimbursed > 0.0 { "reimbursed" } else { "declined" };
+            row.claim_status = Some(outcome.into());
+            row.decided_tick = Some(tick);
+            row.policy = Some(self.policy().to_string());
+            row.reimbursed = reimbursed;
+            store.update_app_claim(&self.run_id, &row)?;
+            events.push(SimEvent::AppClaimDecided {
+                tick,
+                txn_id: row.txn_id,
+                customer_id: row.customer_id,
+                account_id: row.account_id,
+                policy: self.policy().to_string(),
+                outcome: outcome.to_string(),
+                reimbursed,
+            });
+        }
+        Ok(events)
+    }
+}
@@ -0,0 +1,229 @@
+//! ATM network (Phase 4.92).
+//!
+//! Cash withdrawals routed to the ATM channel are made at one of the
+//! bank's ATMs: usually the customer's own, otherwise any in service.
+//! The ATM is recorded on the transaction, so each device's withdrawals,
+//! and the devices each card was used at, can be read back tick by tick.
+//!
+//! ATMs break. A device fault is logged as an incident against the
+//! `atm_fleet` component naming the ATM, and the ATM is out of service
+//! until the incident is resolved — by the incident subsystem, or by a
+//! field engineer `repair_ticks` later, whichever comes first. A P0 or
+//! P1 against the whole fleet takes every ATM out; with none in service
+//! the ATM channel is down for the day.
+//!
+//! Now and then a cash-out crew hits one ATM with a batch of cloned
+//! cards, then moves on to others with the same cards before they are
+//! stopped. The fraud desk alerts every card used at
+//! `multi_atm_threshold` or more ATMs in a tick (`ATM_MULTI_DEVICE`).
+
+use crate::{
+    channel::{Channel, ChannelDay},
+    config::AtmNetworkConfig,
+    error::SimResult,
+    event::SimEvent,
+    rng::SubsystemRng,
+    store::{
+        atm::{AtmCashOutRow, AtmRow},
+        AccountRow, SimStore,
+    },
+    types::{RunId, Tick},
+};
+use uuid::Uuid;
+
+/// Fraud alert type for a card used at many ATMs in one tick.
+pub const MULTI_DEVICE: &str = "ATM_MULTI_DEVICE";
+
+/// The fleet as the tick found it.
+pub struct AtmDay {
+    fleet: Vec<String>,
+    in_service: Vec<String>,
+}
+
+impl AtmDay {
+    pub fn in_service(&self) -> &[String] {
+        &self.in_service
+    }
+}
+
+pub struct AtmNetwork {
+    run_id: RunId,
+    config: AtmNetworkConfig,
+}
+
+impl AtmNetwork {
+    pub fn new(run_id: RunId, config: AtmNetworkConfig) -> Self {
+        Self { run_id, config }
+    }
+
+    pub fn enabled(&self) -> bool {
+        self.config.enabled
+    }
+
+    /// Start the tick: site the fleet on first use, bring repaired ATMs
+    /// back into service and let today's faults take others out.
+    pub fn open_day(&self, store: &SimStore, tick: Tick, rng: &mut SubsystemRng) -> SimResult<(AtmDay, Vec<SimEvent>)> {
+        let c = &self.config;
+        let mut atms = store.atms(&self.run_id)?;
+        if atms.is_empty() && !c.locations.is_empty() {
+            for n in 0..c.atms {
+                let row = AtmRow {
+                    atm_id: format!("atm-{:03}", n + 1),
+                    location: c.locations[n % c.locations.len()].clone(),
+                    status: "in_service".into(),
+                    incident_id: None,
+                    out_since: None,
+                };
+                store.upsert_atm(&self.run_id, &row)?;
+                atms.push(row);
+            }
+        }
+
+        let mut events = Vec::new();
+        for atm in &mut atms {
+            if let (Some(incident_id), Some(out_since)) = (atm.incident_id.clone(), atm.out_since) {
+                if !store.incident_resolved(&self.run_id, &incident_id)? {
+                    if tick < out_since + c.repair_ticks {
+                        continue;
+                    }
+                    store.resolve_incident(&self.run_id, &incident_id, tick)?;
+                }
+                atm.status = "in_service".into();
+                atm.incident_id = None;
+                atm.out_since = None;
+                store.upsert_atm(&self.run_id, atm)?;
+                events.push(SimEvent::AtmBackInService { tick, atm_id: atm.atm_id.clone(), incident_id });
+                continue;
+            }
+            if !rng.chance(c.fault_rate) {
+                continue;
+            }
+            let incident_id = format!("inc-atm_fleet-{}-{tick}", atm.atm_id);
+            store.insert_incident(
+                &self.run_id,
+                &incident_id,
+                "atm_fleet",
+                tick,
+                "P3",
+                &format!("ATM {} out of service — device fault", atm.atm_id),
+                tick + c.repair_ticks,
+            )?;
+            atm.status = "out_of_service".into();
+            atm.incident_id = Some(incident_id.clone());
+            atm.out_since = Some(tick);
+            store.upsert_atm(&self.run_id, atm)?;
+            tracing::info!("{} out of service ({incident_id})", atm.atm_id);
+            events.push(SimEvent::AtmOutOfService { tick, atm_id: atm.atm_id.clone(), incident_id });
+        }
+
+        let in_service = if store.atm_fleet_down(&self.run_id)? {
+            Vec::new()
+        } else {
+            atms.iter().filter(|a| a.status == "in_service").map(|a| a.atm_id.clone()).collect()
+        };
+        let fleet = atms.into_iter().map(|a| a.atm_id).collect();
+        Ok((AtmDay { fleet, in_service }, events))
+    }
+
+    /// The ATM a customer withdraws at: their usual one while it is in
+    /// service and habit holds, otherwise any in service.
+    pub fn pick<'a>(&self, day: &'a AtmDay, customer_id: &str, rng: &mut SubsystemRng) -> Option<&'a str> {
+        if day.in_service.is_empty() {
+            return None;
+        }
+        let hash = customer_id.bytes().fold(0u64, |h, b| h.wrapping_mul(31).wrapping_add(b as u64));
+        let home = &day.fleet[(hash % day.fleet.len() as u64) as usize];
+        if day.in_service.contains(home) && rng.chance(self.config.home_rate) {
+            return Some(home);
+        }
+        Some(&day.in_service[rng.next_u64_below(day.in_service.len() as u64) as usize])
+    }
+
+    /// Now and then a crew hits one ATM with cloned cards, then takes
+    /// each card on to other ATMs.
+    pub fn cash_out(
+        &self,
+        store: &SimStore,
+        day: &AtmDay,
+        mut channels: Option<&mut ChannelDay>,
+        accounts: &[AccountRow],
+        tick: Tick,
+        rng: &mut SubsystemRng,
+    ) -> SimResult<Vec<SimEvent>> {
+        let c = &self.config;
+        if day.in_service.is_empty() || !rng.chance(c.cash_out_rate) {
+            return Ok(Vec::new());
+        }
+        let target = day.in_service[rng.next_u64_below(day.in_service.len() as u64) as usize].clone();
+        let cash_out_id = format!("cashout-{tick}-{target}");
+        let needed = c.cash_out_amount * c.cash_out_withdrawals as f64;
+        let mut candidates: Vec<&AccountRow> = accounts.iter().filter(|a| a.balance >= needed).collect();
+
+        let (mut cards, mut withdrawals, mut total) = (0, 0, 0.0);
+        while (cards as usize) < c.cash_out_cards && !candidates.is_empty() {
+            let card = candidates.swap_remove(rng.next_u64_below(candidates.len() as u64) as usize);
+            let mut others: Vec<&String> = day.in_service.iter().filter(|a| **a != target).collect();
+            let mut route = vec![target.clone()];
+            while route.len() < c.cash_out_withdrawals && !others.is_empty() {
+                route.push(others.swap_remove(rng.next_u64_below(others.len() as u64) as usize).clone());
+            }
+            for atm_id in route {
+                let row = AtmCashOutRow {
+                    txn_id: Uuid::new_v4().to_string(),
+                    cash_out_id: cash_out_id.clone(),
+                    tick,
+                    target_atm: target.clone(),
+                    atm_id,
+                    account_id: card.account_id.clone(),
+                    amount: c.cash_out_amount,
+                };
+                store.insert_transaction_with_rail(
+                    &self.run_id,
+                    &row.txn_id,
+                    &row.account_id,
+                    tick,
+                    row.amount,
+                    "debit",
+                    "cash_withdrawal",
+                    None,
+                    "ACH",
+                    "settled",
+                )?;
+                store.set_transaction_channel(&self.run_id, &row.txn_id, Channel::Atm.as_str())?;
+                store.set_transaction_atm(&self.run_id, &row.txn_id, &row.atm_id)?;
+                store.update_account_balance(&self.run_id, &row.account_id, -row.amount)?;
+                store.insert_atm_cash_out(&self.run_id, &row)?;
+                if let Some(day) = channels.as_deref_mut() {
+                    day.complete(Channel::Atm);
+                }
+                withdrawals += 1;
+                total += row.amount;
+            }
+            cards += 1;
+        }
+
+        if cards == 0 {
+            return Ok(Vec::new());
+        }
+        tracing::warn!("cash-out at {target}: {cards} cloned cards, {withdrawals} withdrawals");
+        Ok(vec![SimEvent::AtmCashOut { tick, cash_out_id, atm_id: target, cards, withdrawals, amount: total }])
+    }
+
+    /// Alert every card used at too many ATMs today.
+    pub fn detect(&self, store: &SimStore, tick: Tick) -> SimResult<Vec<SimEvent>> {
+        let mut events = Vec::new();
+        for (account_id, _) in store.cards_at_many_atms(&self.run_id, tick, self.config.multi_atm_threshold)? {
+            let alert_id = format!("fraud-atm-{account_id}-{tick}");
+            store.insert_fraud_alert(&self.run_id, &alert_id, tick as i64, MULTI_DEVICE, "account", &account_id, 0.85, "high")?;
+            events.push(SimEvent::FraudAlertGenerated {
+                tick,
+                alert_id,
+                alert_type: MULTI_DEVICE.to_string(),
+                entity_id: account_id,
+                fraud_score: 0.85,
+                severity: "high".to_string(),
+            });
+        }
+        Ok(events)
+    }
+}
@@ -0,0 +1,157 @@
+//! Role-based command authorization (Phase 4.46).
+//!
+//! A front-end with several seats sends each command with the role of
+//! the player at that seat: analyst, manager or CCO. Some commands need
+//! more than an analyst: refunds above a write-off limit, risk dials set
+//! outside their comfort zone, and any command the scenario reserves for
+//! a role. A command sent without enough authority is refused before it
+//! changes anything, and every decision a gate made, allowed or not, is
+//! written to `command_authorization` for the audit trail.
+
+use crate::{
+    config::{AuthorizationConfig, DialConfig},
+    error::SimResult,
+    store::{authorization::AuthorizationAuditRow, SimStore},
+    types::{RunId, Tick},
+};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::{collections::HashMap, fmt, str::FromStr};
+
+/// Seats in ascending authority.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum Role {
+    Analyst,
+    Manager,
+    /// Chief compliance officer.
+    Cco,
+}
+
+impl Role {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Analyst => "analyst",
+            Self::Manager => "manager",
+            Self::Cco => "cco",
+        }
+    }
+}
+
+impl fmt::Display for Role {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for Role {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "analyst" => Ok(Self::Analyst),
+            "manager" => Ok(Self::Manager),
+            "cco" => Ok(Self::Cco),
+            other => Err(anyhow::anyhow!("unknown role '{other}'")),
+        }
+    }
+}
+
+/// The role a gate asked for and why.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Requirement {
+    pub role: Role,
+    pub reason: String,
+}
+
+/// The scenario's gates, with each dial's comfort zone.
+pub struct Authorizer {
+    run_id: RunId,
+    config: AuthorizationConfig,
+    comfort_zones: HashMap<String, (f64, f64)>,
+}
+
+impl Authorizer {
+    pub fn new(run_id: RunId, config: AuthorizationConfig, dials: &[DialConfig]) -> Self {
+        let comfort_zones = dials
+            .iter()
+            .map(|d| (d.dial_id.clone(), (d.comfort_zone_min, d.comfort_zone_max)))
+            .collect();
+        Self { run_id, config, comfort_zones }
+    }
+
+    /// Role of a command that names none.
+    pub fn default_role(&self) -> Role {
+        self.config.default_role
+    }
+
+    /// The most senior role any gate asks of `cmd` with this payload, or
+    /// None when no gate applies.
+    pub fn requirement(&self, cmd: &str, payload: &serde_json::Value) -> Option<Requirement> {
+        let mut gates = Vec::new();
+        if let Some(&role) = self.config.command_roles.get(cmd) {
+            gates.push(Requirement { role, reason: format!("{cmd} is reserved for {role}") });
+        }
+        // bulk_resolve is checked against its largest single refund.
+        if matches!(cmd, "resolve_complaint" | "bulk_resolve") {
+            let refund = payload["refund"].as_f64().unwrap_or(0.0);
+            let limits = [
+                (Role::Manager, self.config.analyst_refund_limit),
+                (Role::Cco, self.config.manager_refund_limit),
+            ];
+            if let Some((role, limit)) = limits.into_iter().rev().find(|(_, limit)| refund > *limit) {
+                gates.push(Requirement {
+                    role,
+                    reason: format!("refund ${refund:.2} is over the ${limit:.2} write-off limit"),
+                });
+            }
+        }
+        if cmd == "set_risk_dial" {
+            let dial_id = payload["dial_id"].as_str().unwrap_or_default();
+            let value = payload["new_value"].as_f64().unwrap_or_default();
+            if let Some(&(min, max)) = self.comfort_zones.get(dial_id) {
+                if value < min || value > max {
+                    gates.push(Requirement {
+                        role: self.config.dial_outside_comfort_zone,
+                        reason: format!("{dial_id} {value} is outside its comfort zone {min}–{max}"),
+                    });
+                }
+            }
+        }
+        gates.into_iter().filter(|g| g.role > Role::Analyst).max_by_key(|g| g.role)
+    }
+
+    /// Whether `role` may send `cmd` with this payload. A decision a gate
+    /// made is written to the audit log; the refusal, if any, is returned.
+    pub fn check(
+        &self,
+        store: &SimStore,
+        cmd: &str,
+        payload: &serde_json::Value,
+        role: Option<Role>,
+        tick: Tick,
+    ) -> SimResult<Option<Requirement>> {
+        let Some(required) = self.requirement(cmd, payload) else {
+            return Ok(None);
+        };
+        let role = role.unwrap_or(self.config.default_role);
+        let allowed = role >= required.role;
+        if allowed {
+            tracing::info!("{cmd} by {role}: {}", required.reason);
+        } else {
+            tracing::warn!("refused {cmd} by {role}, needs {}: {}", required.role, required.reason);
+        }
+        store.insert_authorization_audit(
+            &self.run_id,
+            &AuthorizationAuditRow {
+                tick,
+                command: cmd.to_string(),
+                role,
+                required_role: required.role,
+                allowed,
+                reason: required.reason.clone(),
+            },
+        )?;
+        Ok((!allowed).then_some(required))
+    }
+}
@@ -0,0 +1,269 @@
+//! Business email compromise (Phase 4.53).
+//!
+//! Business customers pay their vendors' invoices by wire, mostly to a
+//! handful of regular vendors and now and then to one they have never
+//! paid. Rarely, a fraudster posing as a vendor sends a spoofed invoice,
+//! and some customers pay it — a large wire to a payee nobody has seen
+//! before.
+//!
+//! BEC_NEW_PAYEE looks at each day's outgoing wires from the configured
+//! segments: a payee the account has not paid in the lookback, and an
+//! amount well above anything it paid there, raises a fraud alert. From
+//! then on the clock is running. A recall sent the same day usually gets
+//! the money back; each day of delay cuts the odds, and past the window
+//! the funds are gone. The fraud desk recalls alerted wires on its own
+//! after a few days, and the player can recall any wire sooner. Recalling
+//! a genuine payment returns nothing.
+
+use crate::{
+    command::PlayerCommand,
+    config::BecConfig,
+    error::SimResult,
+    event::SimEvent,
+    rng::SubsystemRng,
+    store::{
+        bec::{InvoiceWireRow, WireRecallRow},
+        AccountRow, SimStore,
+    },
+    types::{RunId, Tick},
+};
+use std::collections::{HashMap, HashSet};
+use uuid::Uuid;
+
+pub const ALERT_TYPE: &str = "bec_new_payee";
+
+pub struct BusinessEmailCompromise {
+    run_id: RunId,
+    config: BecConfig,
+}
+
+impl BusinessEmailCompromise {
+    pub fn new(run_id: RunId, config: BecConfig) -> Self {
+        Self { run_id, config }
+    }
+
+    pub fn enabled(&self) -> bool {
+        self.config.enabled
+    }
+
+    /// BEC_NEW_PAYEE score for a new-payee wire of `amount` from an
+    /// account whose largest debit in the lookback was `largest_debit`,
+    /// or `None` if the amount is not anomalous. With no debits at all
+    /// the wire scores as if exactly at the multiple.
+    pub fn score(&self, amount: f64, largest_debit: f64) -> Option<f64> {
+        let c = &self.config;
+        let ratio = if largest_debit > 0.0 { amount / largest_debit } else { c.amount_multiple };
+        if ratio < c.amount_multiple {
+            return None;
+        }
+        Some((c.base_score + c.score_per_doubling * (ratio / c.amount_multiple).log2()).min(1.0))
+    }
+
+    /// Chance a recall sent `ticks_elapsed` after the wire gets the money
+    /// back.
+    pub fn recovery_chance(&self, ticks_elapsed: Tick) -> f64 {
+        let c = &self.config;
+        if ticks_elapsed > c.recall_window_ticks {
+            return 0.0;
+        }
+        c.recall_recovery_rate * c.recall_decay.powi(ticks_elapsed as i32)
+    }
+
+    /// Deliver today's invoices to business customers and wire the ones
+    /// they pay.
+    pub fn book_invoices(
+        &self,
+        store: &SimStore,
+        accounts: &[AccountRow],
+        tick: Tick,
+        rng: &mut SubsystemRng,
+    ) -> SimResult<Vec<SimEvent>> {
+        let c = &self.config;
+        let businesses: HashSet<String> = store.customers_in_segments(&self.run_id, &c.segments)?.into_iter().collect();
+        let mut first_account: HashMap<&str, &AccountRow> = HashMap::new();
+        for acct in accounts.iter().filter(|a| businesses.contains(&a.customer_id)) {
+            first_account.entry(acct.customer_id.as_str()).or_insert(acct);
+        }
+        let mut payers: Vec<&AccountRow> = first_account.into_values().collect();
+        payers.sort_by(|a, b| a.account_id.cmp(&b.account_id));
+
+        let (mut spoofed_invoices, mut wires, mut total) = (0, 0, 0.0);
+        for acct in payers {
+            let invoice = if rng.chance(c.spoofed_invoice_rate) {
+                spoofed_invoices += 1;
+                rng.chance(c.fall_rate).then_some((true, c.spoofed_share_min, c.spoofed_share_max))
+            } else if rng.chance(c.vendor_invoice_rate) {
+                Some((false, c.vendor_share_min, c.vendor_share_max))
+            } else {
+                None
+            };
+            let Some((spoofed, low, high)) = invoice else {
+                continue;
+            };
+            let counterparty = if !spoofed && !rng.chance(c.new_vendor_share) && c.regular_vendors > 0 {
+                format!("vendor-{}-{}", acct.account_id, rng.next_u64_below(c.regular_vendors as u64))
+            } else {
+                format!("payee-{:08x}", rng.next_u64() as u32)
+            };
+            let share = low + (high - low) * rng.next_f64();
+            let amount = (acct.balance * share * 100.0).floor() / 100.0;
+            if amount < c.min_invoice {
+                continue;
+            }
+            let row = InvoiceWireRow {
+                txn_id: Uuid::new_v4().to_string(),
+                customer_id: acct.customer_id.clone(),
+                account_id: acct.account_id.clone(),
+                tick,
+                amount,
+                counterparty,
+                spoofed,
+            };
+            store.insert_transaction_with_rail(
+                &self.run_id,
+                &row.txn_id,
+                &row.account_id,
+                tick,
+                amount,
+                "debit",
+                "invoice_payment",
+                Some(&row.counterparty),
+                "wire",
+                "settled",
+            )?;
+            store.update_account_balance(&self.run_id, &row.account_id, -amount)?;
+            store.insert_invoice_wire(&self.run_id, &row)?;
+            wires += 1;
+            total += amount;
+        }
+
+        if spoofed_invoices == 0 && wires == 0 {
+            return Ok(Vec::new());
+        }
+        Ok(vec![SimEvent::InvoiceWiresBooked { tick, spoofed_invoices, wires, amount: total }])
+    }
+
+    /// Run BEC_NEW_PAYEE over today's outgoing wires, then send the
+    /// player's recalls and any the desk has come to.
+    pub fn process(
+        &self,
+        store: &SimStore,
+        tick: Tick,
+        commands: &[PlayerCommand],
+        rng: &mut SubsystemRng,
+    ) -> SimResult<Vec<SimEvent>> {
+        let c = &self.config;
+        let mut events = Vec::new();
+
+        for wire in store.new_payee_wires(&self.run_id, tick, tick.saturating_sub(c.lookback_ticks))? {
+            if !c.segments.contains(&wire.segment) {
+                continue;
+            }
+            let Some(score) = self.score(wire.amount, wire.largest_debit) else {
+                continue;
+            };
+            let alert_id = format!("fraud-bec-{}", wire.txn_id);
+            let severity = if score >= 0.8 { "high" } else { "medium" };
+            store.insert_fraud_alert(
+                &self.run_id,
+                &alert_id,
+                tick as i64,
+                ALERT_TYPE,
+                "transaction",
+                &wire.txn_id,
+                score,
+                severity,
+            )?;
+            store.upsert_wire_recall(
+                &self.run_id,
+                &WireRecallRow {
+                    txn_id: wire.txn_id.clone(),
+                    account_id: wire.account_id,
+                    wire_tick: tick,
+                    amount: wire.amount,
+                    alert_id: Some(alert_id.clone()),
+                    alerted_tick: Some(tick),
+                    recall_tick: None,
+                    outcome: None,
+                    recovered: 0.0,
+                },
+            )?;
+            events.push(SimEvent::FraudAlertGenerated {
+                tick,
+                alert_id,
+                alert_type: ALERT_TYPE.to_string(),
+                entity_id: wire.txn_id,
+                fraud_score: score,
+                severity: severity.to_string(),
+            });
+        }
+
+        for command in commands {
+            let PlayerCommand::RecallWire { txn_id } = command else {
+                continue;
+            };
+            events.push(self.recall(store, txn_id, tick, rng)?);
+        }
+
+        if let Some(delay) = c.desk_recall_ticks {
+            if tick >= delay {
+                for wire in store.unrecalled_wires_alerted_by(&self.run_id, tick - delay)? {
+                    events.push(self.recall(store, &wire.txn_id, tick, rng)?);
+                }
+            }
+        }
+        Ok(events)
+    }
+
+    /// Ask the receiving bank to return a wire, crediting the account if
+    /// it does.
+    fn recall(&self, store: &SimStore, txn_id: &str, tick: Tick, rng: &mut SubsystemRng) -> SimResult<SimEvent> {
+        let rejected = |reason: &str| {
+            tracing::warn!("recall of wire {txn_id} rejected: {reason}");
+            SimEvent::WireRecallRejected { tick, txn_id: txn_id.to_string(), reason: reason.to_string() }
+        };
+        let Some(mut row) = store.wire_recall(&self.run_id, txn_id)?.or(store.outgoing_wire(&self.run_id, txn_id)?)
+        else {
+            return Ok(rejected("not an outgoing wire"));
+        };
+        if row.recall_tick.is_some() {
+            return Ok(rejected("already recalled"));
+        }
+
+        let ticks_elapsed = tick - row.wire_tick;
+        let spoofed = store.invoice_wire(&self.run_id, txn_id)?.is_some_and(|w| w.spoofed);
+        let outcome = if !spoofed {
+            "not_fraud"
+        } else if rng.chance(self.recovery_chance(ticks_elapsed)) {
+            "recovered"
+        } else {
+            "funds_gone"
+        };
+        if outcome == "recovered" {
+            store.insert_transaction_with_rail(
+                &self.run_id,
+                &Uuid::new_v4().to_string(),
+                &row.account_id,
+                tick,
+                row.amount,
+                "credit",
+                "wire_recall",
+                None,
+                "wire",
+                "settled",
+            )?;
+            store.update_account_balance(&self.run_id, &row.account_id, row.amount)?;
+            row.recovered = row.amount;
+        }
+        row.recall_tick = Some(tick);
+        row.outcome = Some(outcome.to_string());
+        store.upsert_wire_recall(&self.run_id, &row)?;
+        Ok(SimEvent::WireRecallResolved {
+            tick,
+            txn_id: txn_id.to_string(),
+            ticks_elapsed,
+            outcome: outcome.to_string(),
+            recovered: row.recovered,
+        })
+    }
+}
@@ -0,0 +1,381 @@
+//! Branch Network subsystem — Phase 4.3.
+//!
+//! Seeds one branch per state market with enough customers, assigns every
+//! customer a home branch by state (customers in markets without a branch
+//! are served by the "br-DIGITAL" channel), and sizes branch staff from
+//! the customer count.
+//!
+//! Player commands:
+//!   - OpenBranch  { state_code } — customers in that state move from
+//!     digital to the new branch and receive a churn-reducing life event.
+//!     Codes missing from `state_config` are rejected.
+//!   - CloseBranch { branch_id }  — customers fall back to digital, take a
+//!     satisfaction hit and a churn-raising life event.
+//!
+//! Every `report_interval_ticks` a per-branch performance report is
+//! written: customers, staff, cash operations, complaint rate, churn,
+//! projected acquisitions and operating cost. Each open branch then
+//! onboards its projected acquisitions as new customers in its market, so
+//! closing a branch stops acquisition there and opening one starts it.
+//! Reported operating cost flows into opex (EconomicsSubsystem).
+//!
+//! Execution: every tick, after RiskAppetite (reports read this tick's
+//!   complaints and churn).
+//! Depends on: customer.state_code (CustomerSubsystem), transactions,
+//!   complaint, churn life events (ChurnSubsystem reads them).
+
+use crate::{
+    churn_subsystem::LifeEvent,
+    command::PlayerCommand,
+    config::BranchNetworkConfig,
+    error::SimResult,
+    event::SimEvent,
+    rng::SubsystemRng,
+    store::{branch::BranchPerformanceRow, SimStore},
+    subsystem::{SimSubsystem, SubsystemManifest},
+    types::{RunId, Tick},
+};
+
+/// Pseudo-branch serving customers whose market has no open branch.
+pub const DIGITAL_BRANCH_ID: &str = "br-DIGITAL";
+
+pub struct BranchSubsystem {
+    run_id:      RunId,
+    config:      BranchNetworkConfig,
+    store:       SimStore,
+    initialized: bool,
+}
+
+impl BranchSubsystem {
+    pub fn new(run_id: RunId, config: BranchNetworkConfig, store: SimStore) -> Self {
+        Self { run_id, config, store, initialized: false }
+    }
+
+    fn staff_for(&self, customers: usize) -> i64 {
+        (customers.div_ceil(self.config.customers_per_staff.max(1)) as i64)
+            .max(self.config.min_staff)
+    }
+
+    /// Open a branch in `state_code` and move that market's digital
+    /// customers onto it. Returns the new branch id and customers assigned.
+    fn open_branch(&self, state_code: &str, tick: Tick) -> SimResult<(String, usize)> {
+        let n = self.store.branches_in_state(&self.run_id, state_code)? + 1;
+        let branch_id = format!("br-{state_code}-{n:02}");
+        let customers = self.store.active_customers_in_state(&self.run_id, state_code)?;
+
+        self.store.insert_branch(
+            &self.run_id, &branch_id, state_code, self.staff_for(customers.len()), tick,
+        )?;
+        for customer_id in &customers {
+            self.store.assign_customer_branch(&self.run_id, customer_id, &branch_id, tick)?;
+        }
+        Ok((branch_id, customers.len()))
+    }
+
+    /// Tick 1: seed branches in every market above the size threshold.
+    fn seed_network(&self, tick: Tick) -> SimResult<Vec<SimEvent>> {
+        let mut out = Vec::new();
+        for (state_code, count) in self.store.active_customer_counts_by_state(&self.run_id)? {
+            if count < self.config.min_customers_for_branch {
+                for customer_id in self.store.active_customers_in_state(&self.run_id, &state_code)? {
+                    self.store.assign_customer_branch(
+                        &self.run_id, &customer_id, DIGITAL_BRANCH_ID, tick,
+                    )?;
+                }
+                continue;
+            }
+            let (branch_id, assigned) = self.open_branch(&state_code, tick)?;
+            out.push(SimEvent::BranchOpened {
+                tick,
+                branch_id,
+                state_code,
+                customers_assigned: assigned as i64,
+            });
+        }
+        tracing::info!("seeded {} branches", out.len());
+        Ok(out)
+    }
+
+    /// Apply a branch life event (churn driver) to every affected customer.
+    fn apply_life_event(
+        &self,
+        customers:  &[String],
+        event_type: &str,
+        delta:      f64,
+        tick:       Tick,
+    ) -> SimResult<()> {
+        for customer_id in customers {
+            self.store.insert_life_event(&self.run_id, &LifeEvent {
+                customer_id:        customer_id.clone(),
+                event_type:         event_type.to_string(),
+                tick_occurred:      tick,
+                tick_expires:       tick + self.config.network_change_effect_ticks,
+                active:             true,
+                churn_risk_delta:   delta,
+                behavioral_changes: serde_json::Value::Null,
+            })?;
+        }
+        Ok(())
+    }
+
+    fn handle_open(&self, state_code: &str, tick: Tick) -> SimResult<Vec<SimEvent>> {
+        if !self.store.is_known_state(state_code)? {
+            return Ok(vec![SimEvent::BranchCommandRejected {
+                tick,
+                command: "open_branch".into(),
+                reason: format!("{state_code} is not a known state"),
+            }]);
+        }
+        let open_in_state = self
+            .store
+            .open_branches(&self.run_id)?
+            .into_iter()
+            .any(|b| b.state_code == state_code);
+        if open_in_state {
+            return Ok(vec![SimEvent::BranchCommandRejected {
+                tick,
+                command: "open_branch".into(),
+                reason: format!("{state_code} already has an open branch"),
+            }]);
+        }
+
+        let (branch_id, assigned) = self.open_branch(state_code, tick)?;
+        let customers = self.store.branch_customers(&self.run_id, &branch_id)?;
+        self.apply_life_event(&customers, "branch_opened", -self.config.open_churn_relief, tick)?;
+
+        tracing::info!("opened {branch_id} ({assigned} customers)");
+        Ok(vec![SimEvent::BranchOpened {
+            tick,
+            branch_id,
+            state_code: state_code.to_string(),
+            customers_assigned: assigned as i64,
+        }])
+    }
+
+    fn handle_close(&self, branch_id: &str, tick: Tick) -> SimResult<Vec<SimEvent>> {
+        let branch = match self.store.get_branch(&self.run_id, branch_id)? {
+            Some(b) if b.status == "open" => b,
+            _ => {
+                return Ok(vec![SimEvent::BranchCommandRejected {
+                    tick,
+                    command: "close_branch".into(),
+                    reason: format!("{branch_id} is not an open branch"),
+                }]);
+            }
+        };
+
+        let customers = self.store.branch_customers(&self.run_id, branch_id)?;
+        self.store.close_branch(&self.run_id, branch_id, tick)?;
+        for customer_id in &customers {
+            self.store.assign_customer_branch(&self.run_id, customer_id, DIGITAL_BRANCH_ID, tick)?;
+            self.store.update_customer_satisfaction(
+                &self.run_id, customer_id, -self.config.closure_satisfaction_hit,
+            )?;
+        }
+        self.apply_life_event(&customers, "branch_closed", self.config.closure_churn_delta, tick)?;
+
+        tracing::warn!(
+            "closed {branch_id}, {} customers moved to digital",
+            customers.len(),
+        );
+        Ok(vec![SimEvent::BranchClosed {
+            tick,
+            branch_id: branch_id.to_string(),
+            state_code: branch.state_code,
+            customers_affected: customers.len() as i64,
+        }])
+    }
+
+    /// Onboard a branch's projected acquisitions as new customers, each
+    /// modelled on a randomly chosen existing customer of the branch.
+    fn acquire_customers(
+        &self,
+        branch_id:  &str,
+        state_code: &str,
+        projected:  f64,
+        tick:       Tick,
+        rng:        &mut SubsystemRng,
+    ) -> SimResult<Option<SimEvent>> {
+        let book = self.store.branch_customers(&self.run_id, branch_id)?;
+        if book.is_empty() {
+            return Ok(None);
+        }
+        let count = projected.floor() as usize + rng.chance(projected.fract()) as usize;
+        for n in 0..count {
+            let template = &book[rng.next_u64_below(book.len() as u64) as usize];
+            let customer_id = format!("c-{branch_id}-{tick}-{n}");
+            let account_id = format!("a-{branch_id}-{tick}-{n}");
+            self.store.insert_branch_acquisition(
+                &self.run_id, template, &customer_id, &account_id, branch_id, tick,
+            )?;
+        }
+        if count == 0 {
+            return Ok(None);
+        }
+        Ok(Some(SimEvent::BranchCustomersAcquired {
+            tick,
+            branch_id: branch_id.to_string(),
+            state_code: state_code.to_string(),
+            customers_acquired: count as i64,
+        }))
+    }
+
+    /// Write the per-branch performance report for the period ending at
+    /// `tick`, then onboard each open branch's acquisitions.
+    fn report(&self, tick: Tick, rng: &mut SubsystemRng) -> SimResult<Vec<SimEvent>> {
+        let mut out = Vec::new();
+        let start = tick.saturating_sub(self.config.report_interval_ticks);
+        let mut branches: Vec<(String, String, i64)> = self
+            .store
+            .open_branches(&self.run_id)?
+            .into_iter()
+            .map(|b| (b.branch_id, b.state_code, b.staff_count))
+            .collect();
+        branches.push((DIGITAL_BRANCH_ID.to_string(), String::new(), 0));
+
+        let mut worst: Option<(String, f64)> = None;
+        let mut acquisitions = Vec::new();
+        for (branch_id, state_code, staff) in branches {
+            let customers = self.store.branch_customers(&self.run_id, &branch_id)?.len();
+            let is_digital = branch_id == DIGITAL_BRANCH_ID;
+
+            // Re-size staff to the current book.
+            let staff_count = if is_digital { 0 } else { self.staff_for(customers) };
+            if !is_digital && staff_count != staff {
+                self.store.set_branch_staff(&self.run_id, &branch_id, staff_count)?;
+            }
+
+            let (cash_txn_count, cash_volume, complaints_opened, churned_count) =
+                self.store.branch_activity(&self.run_id, &branch_id, start, tick)?;
+            let complaint_rate = if customers > 0 {
+                complaints_opened as f64 * 100.0 / customers as f64
+            } else {
+                0.0
+            };
+            let projected_acquisitions = if is_digital {
+                0.0
+            } else {
+                self.config.acquisitions_per_branch_month * (1.0 - complaint_rate / 100.0).max(0.0)
+            };
+            let operating_cost = if is_digital {
+                0.0
+            } else {
+                self.config.occupancy_monthly_cost
+                    + staff_count as f64 * self.config.staff_monthly_cost
+            };
+
+            if !is_digital && worst.as_ref().is_none_or(|(_, r)| complaint_rate > *r) {
+                worst = Some((branch_id.clone(), complaint_rate));
+            }
+            if !is_digital {
+                acquisitions.push((branch_id.clone(), state_code, projected_acquisitions));
+            }
+
+            self.store.insert_branch_performance(&self.run_id, tick, &BranchPerformanceRow {
+                branch_id,
+                customer_count: customers as i64,
+                staff_count,
+                cash_txn_count,
+                cash_volume,
+                complaints_opened,
+                complaint_rate,
+                churned_count,
+                projected_acquisitions,
+                operating_cost,
+            })?;
+        }
+
+        let open_count = self.store.branch_count(&self.run_id, "open")?;
+        tracing::debug!("performance report for {open_count} branches");
+        out.push(SimEvent::BranchPerformanceReported {
+            tick,
+            branch_count: open_count,
+            highest_complaint_branch: worst.map(|(b, _)| b),
+        });
+
+        for (branch_id, state_code, projected) in acquisitions {
+            out.extend(self.acquire_customers(&branch_id, &state_code, projected, tick, rng)?);
+        }
+        Ok(out)
+    }
+}
+
+const MANIFEST: SubsystemManifest = SubsystemManifest {
+    version: 1,
+    produces: &[
+        "branch_closed", "branch_command_rejected", "branch_customers_acquired", "branch_opened",
+        "branch_performance_reported",
+    ],
+    consumes: &["player_command_received"],
+    tables: &[
+        "account", "branch", "branch_performance", "customer", "customer_branch", "life_event",
+    ],
+    requires: &[],
+};
+
+impl SimSubsystem for BranchSubsystem {
+    fn name(&self) -> &'static str {
+        "branch"
+    }
+
+    fn manifest(&self) -> SubsystemManifest {
+        MANIFEST
+    }
+
+    fn update(
+        &mut self,
+        tick:      Tick,
+        events_in: &[SimEvent],
+        rng:       &mut SubsystemRng,
+    ) -> SimResult<Vec<SimEvent>> {
+        let mut out = Vec::new();
+
+        if !self.config.enabled || tick == 0 {
+            return Ok(out);
+        }
+
+        if !self.initialized {
+            self.initialized = true;
+            out.extend(self.seed_network(tick)?);
+        }
+
+        for event in events_in {
+            if let SimEvent::PlayerCommandReceived { command_id, .. } = event {
+                match self.store.get_player_command(&self.run_id, command_id)? {
+                    Some(PlayerCommand::OpenBranch { state_code }) => {
+                        out.extend(self.handle_open(&state_code, tick)?);
+                    }
+                    Some(PlayerCommand::CloseBranch { branch_id }) => {
+                        out.extend(self.handle_close(&branch_id, tick)?);
+                    }
+                    _ => {}
+                }
+            }
+        }
+
+        if tick.is_multiple_of(self.config.report_interval_ticks) {
+            out.extend(self.report(tick, rng)?);
+        }
+
+        Ok(out)
+    }
+
+    fn save_state(&self) -> SimResult<Option<serde_json::Value>> {
+        Ok(Some(serde_json::json!({ "initialized": self.initialized })))
+    }
+
+    fn load_state(&mut self, state: serde_json::Value) -> SimResult<()> {
+        self.initialized = state["initialized"].as_bool().unwrap_or_default();
+        Ok(())
+    }
+
+    // The network is seeded on the first tick played while enabled
+    fn replay(&mut self, _tick: Tick, _events: &[SimEvent]) -> SimResult<()> {
+        self.initialized |= self.config.enabled;
+        Ok(())
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+}
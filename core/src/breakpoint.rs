@@ -0,0 +1,210 @@
+//! Pause-on-event breakpoints (Phase 4.74).
+//!
+//! A player, or a test harness driving the engine, registers conditions
+//! such as "the first SAR is filed" or "any account balance falls below
+//! -$10k". The engine checks them at the end of every tick, after the
+//! scenario scripts; when one fires it logs a `BreakpointHit` event and
+//! `run_ticks` stops after that tick, leaving the clock paused, so the
+//! caller sees the state that tripped it.
+//!
+//! An event condition fires on every tick that logs the event. A balance
+//! or KPI condition fires when it becomes true and not again until it has
+//! been false, so stepping on from a hit does not stop at once. A `once`
+//! breakpoint is removed when it fires. Breakpoints belong to the engine
+//! and are not saved with the run.
+
+use crate::{
+    engine::event_type_name,
+    error::{SimError, SimResult},
+    event::SimEvent,
+    state_mirror::StateMirror,
+    store::SimStore,
+    types::Tick,
+};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// The headline KPIs a condition can watch, as named in `UiState`.
+pub const KPI_NAMES: &[&str] = &[
+    "active_customers",
+    "churned_customers",
+    "complaint_count",
+    "sla_breaches",
+    "backlog",
+    "nim",
+    "efficiency_ratio",
+    "pre_tax_profit",
+    "resilience",
+];
+
+/// What a breakpoint waits for, tagged by `kind`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum BreakpointCondition {
+    /// A tick logs an event of this type, e.g. `sar_filed`.
+    Event { event_type: String },
+    /// Some account's balance is below `amount`.
+    BalanceBelow { amount: f64 },
+    /// A headline KPI is below `value`.
+    KpiBelow { metric: String, value: f64 },
+    /// A headline KPI is above `value`.
+    KpiAbove { metric: String, value: f64 },
+    /// The clock reaches `tick`.
+    AtTick { tick: Tick },
+}
+
+/// A registered condition.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct Breakpoint {
+    /// Chosen by the caller; setting an id again replaces it.
+    pub id: String,
+    pub condition: BreakpointCondition,
+    /// Remove the breakpoint once it fires.
+    #[serde(default)]
+    pub once: bool,
+}
+
+/// A breakpoint that stopped the clock.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct BreakpointHit {
+    pub breakpoint_id: String,
+    pub tick: Tick,
+    /// What tripped it, e.g. the account and its balance.
+    pub detail: String,
+}
+
+/// sim-runner → client after `set_breakpoint`, `clear_breakpoint` and
+/// `list_breakpoints`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct BreakpointList {
+    pub breakpoints: Vec<Breakpoint>,
+}
+
+#[derive(Debug)]
+struct Armed {
+    breakpoint: Breakpoint,
+    /// False while a state condition that fired is still true.
+    armed: bool,
+}
+
+#[derive(Debug, Default)]
+pub struct Breakpoints {
+    registered: Vec<Armed>,
+}
+
+impl Breakpoints {
+    pub fn is_empty(&self) -> bool {
+        self.registered.is_empty()
+    }
+
+    /// Register a breakpoint, replacing any with its id.
+    pub fn set(&mut self, breakpoint: Breakpoint) -> SimResult<()> {
+        let refuse = |reason: String| Err(SimError::command("set_breakpoint", reason));
+        if breakpoint.id.trim().is_empty() {
+            return refuse("breakpoint id must not be empty".into());
+        }
+        match &breakpoint.condition {
+            BreakpointCondition::Event { event_type } if event_type.trim().is_empty() => {
+                return refuse("event_type must not be empty".into());
+            }
+            BreakpointCondition::KpiBelow { metric, .. } | BreakpointCondition::KpiAbove { metric, .. }
+                if !KPI_NAMES.contains(&metric.as_str()) =>
+            {
+                return refuse(format!("unknown KPI {metric:?}; expected one of {}", KPI_NAMES.join(", ")));
+            }
+            _ => {}
+        }
+        let armed = Armed { breakpoint, armed: true };
+        match self.registered.iter_mut().find(|a| a.breakpoint.id == armed.breakpoint.id) {
+            Some(existing) => *existing = armed,
+            None => self.registered.push(armed),
+        }
+        Ok(())
+    }
+
+    /// Remove a breakpoint; false if none had the id.
+    pub fn clear(&mut self, id: &str) -> bool {
+        let before = self.registered.len();
+        self.registered.retain(|a| a.breakpoint.id != id);
+        self.registered.len() < before
+    }
+
+    /// Every registered breakpoint, in the order set.
+    pub fn list(&self) -> Vec<Breakpoint> {
+        self.registered.iter().map(|a| a.breakpoint.clone()).collect()
+    }
+
+    /// The breakpoints the tick just run trips, dropping those set `once`.
+    pub fn check(
+        &mut self,
+        store: &SimStore,
+        run_id: &str,
+        tick: Tick,
+        tick_events: &[SimEvent],
+        kpis: &StateMirror,
+    ) -> SimResult<Vec<BreakpointHit>> {
+        let mut hits = Vec::new();
+        // Looked up at most once a tick, and only if asked for
+        let mut lowest_balance = None;
+        for entry in &mut self.registered {
+            let detail = match &entry.breakpoint.condition {
+                BreakpointCondition::Event { event_type } => {
+                    let count = tick_events.iter().filter(|e| event_type_name(e) == event_type).count();
+                    if count > 0 {
+                        hits.push(BreakpointHit {
+                            breakpoint_id: entry.breakpoint.id.clone(),
+                            tick,
+                            detail: format!("{count} {event_type} event(s)"),
+                        });
+                    }
+                    continue;
+                }
+                BreakpointCondition::BalanceBelow { amount } => {
+                    if lowest_balance.is_none() {
+                        lowest_balance = Some(store.lowest_account_balance(run_id)?);
+                    }
+                    lowest_balance
+                        .clone()
+                        .flatten()
+                        .filter(|(_, balance)| balance < amount)
+                        .map(|(account_id, balance)| format!("account {account_id} balance {balance:.2} < {amount:.2}"))
+                }
+                BreakpointCondition::KpiBelow { metric, value } => kpi_value(kpis, metric)
+                    .filter(|now| now < value)
+                    .map(|now| format!("{metric} {now} < {value}")),
+                BreakpointCondition::KpiAbove { metric, value } => kpi_value(kpis, metric)
+                    .filter(|now| now > value)
+                    .map(|now| format!("{metric} {now} > {value}")),
+                BreakpointCondition::AtTick { tick: at } => (tick == *at).then(|| format!("tick {tick}")),
+            };
+            match detail {
+                Some(detail) if entry.armed => {
+                    entry.armed = false;
+                    hits.push(BreakpointHit { breakpoint_id: entry.breakpoint.id.clone(), tick, detail });
+                }
+                Some(_) => {}
+                None => entry.armed = true,
+            }
+        }
+        self.registered
+            .retain(|a| !(a.breakpoint.once && hits.iter().any(|h| h.breakpoint_id == a.breakpoint.id)));
+        Ok(hits)
+    }
+}
+
+/// A headline KPI by its `UiState` name; None for resilience before the
+/// stress index has run.
+fn kpi_value(kpis: &StateMirror, metric: &str) -> Option<f64> {
+    match metric {
+        "active_customers" => Some(kpis.active_customers as f64),
+        "churned_customers" => Some(kpis.churned_customers as f64),
+        "complaint_count" => Some(kpis.complaint_count as f64),
+        "sla_breaches" => Some(kpis.sla_breaches as f64),
+        "backlog" => Some(kpis.backlog as f64),
+        "nim" => Some(kpis.nim),
+        "efficiency_ratio" => Some(kpis.efficiency_ratio),
+        "pre_tax_profit" => Some(kpis.pre_tax_profit),
+        "resilience" => kpis.resilience,
+        _ => None,
+    }
+}
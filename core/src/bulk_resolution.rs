@@ -0,0 +1,133 @@
+//! Bulk complaint resolution (Phase 4.47).
+//!
+//! A burst of complaints about the same thing is closed in one
+//! `bulk_resolve` command instead of one `resolve_complaint` each. The
+//! command names a resolution template, configured in
+//! `complaint_triggers.json` or sent inline, and a filter over the open
+//! complaints. Every matched complaint is planned first: the template's
+//! refund rule either prices it or skips it with a reason. The plan is
+//! then applied in one store transaction, so a failure part way closes
+//! nothing.
+
+use crate::{
+    complaint_subsystem::ComplaintRecord,
+    config::{RefundRule, ResolutionTemplate},
+    engine::SimEngine,
+    error::SimResult,
+    types::Tick,
+};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// Which open complaints a `bulk_resolve` covers; unset fields match
+/// anything.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ComplaintFilter {
+    /// Issue category, as in `complaint.issue`.
+    #[serde(default)]
+    pub issue: Option<String>,
+    #[serde(default)]
+    pub priority: Option<String>,
+    /// Ticks since the complaint was opened, inclusive.
+    #[serde(default)]
+    pub min_age_ticks: Option<Tick>,
+    #[serde(default)]
+    pub max_age_ticks: Option<Tick>,
+}
+
+impl ComplaintFilter {
+    pub fn matches(&self, complaint: &ComplaintRecord, tick: Tick) -> bool {
+        let age = tick.saturating_sub(complaint.tick_opened);
+        self.issue.as_ref().is_none_or(|issue| *issue == complaint.issue)
+            && self.priority.as_ref().is_none_or(|priority| *priority == complaint.priority)
+            && self.min_age_ticks.is_none_or(|min| age >= min)
+            && self.max_age_ticks.is_none_or(|max| age <= max)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct AppliedResolution {
+    pub complaint_id: String,
+    pub refund: f64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct SkippedComplaint {
+    pub complaint_id: String,
+    pub reason: String,
+}
+
+/// What a `bulk_resolve` closed and what it left open, and why.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct BulkResolution {
+    /// Empty for an inline template.
+    pub template_id: String,
+    pub resolution_code: String,
+    /// Open complaints the filter matched.
+    pub matched: usize,
+    pub applied: Vec<AppliedResolution>,
+    pub skipped: Vec<SkippedComplaint>,
+    pub total_refunded: f64,
+}
+
+impl BulkResolution {
+    /// Work out each matching complaint's refund, or why it is skipped,
+    /// without closing anything.
+    pub fn plan(engine: &SimEngine, filter: &ComplaintFilter, template: &ResolutionTemplate) -> SimResult<Self> {
+        let tick = engine.clock.current_tick;
+        let mut plan = Self {
+            template_id: template.template_id.clone(),
+            resolution_code: template.resolution_code.clone(),
+            matched: 0,
+            applied: Vec::new(),
+            skipped: Vec::new(),
+            total_refunded: 0.0,
+        };
+        for complaint in engine.store.open_complaints(&engine.run_id)? {
+            if !filter.matches(&complaint, tick) {
+                continue;
+            }
+            plan.matched += 1;
+            let refund = match template.refund {
+                RefundRule::Nothing => Ok(0.0),
+                RefundRule::Fixed { amount } => Ok(amount),
+                RefundRule::FeeUpTo { max } => match engine.store.complaint_fee(&engine.run_id, &complaint)? {
+                    Some(fee) if fee <= max => Ok(fee),
+                    Some(fee) => Err(format!("fee ${fee:.2} is over ${max:.2}")),
+                    None => Err("no fee to refund".to_string()),
+                },
+            };
+            match refund {
+                Ok(refund) => {
+                    plan.total_refunded += refund;
+                    plan.applied.push(AppliedResolution { complaint_id: complaint.complaint_id, refund });
+                }
+                Err(reason) => plan.skipped.push(SkippedComplaint { complaint_id: complaint.complaint_id, reason }),
+            }
+        }
+        Ok(plan)
+    }
+
+    /// The largest refund on any one complaint, which is what the
+    /// write-off limits are checked against.
+    pub fn largest_refund(&self) -> f64 {
+        self.applied.iter().map(|a| a.refund).fold(0.0, f64::max)
+    }
+
+    /// Close every planned complaint, all or none.
+    pub fn apply(&self, engine: &SimEngine) -> SimResult<()> {
+        let tick = engine.clock.current_tick;
+        engine.store.atomically(|| {
+            for applied in &self.applied {
+                engine.store_close_complaint_direct(
+                    &engine.run_id,
+                    &applied.complaint_id,
+                    tick,
+                    &self.resolution_code,
+                    applied.refund,
+                )?;
+            }
+            Ok(())
+        })
+    }
+}
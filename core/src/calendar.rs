@@ -0,0 +1,215 @@
+//! Business-day calendar (Phase 4.41).
+//!
+//! Tick 1 is the scenario's start date and every tick after it is the
+//! next calendar day in the bank's time zone. Weekends, US federal
+//! holidays (a Saturday holiday observed the Friday before, a Sunday one
+//! the Monday after) and any extra closures are not business days.
+//!
+//! Complaint SLAs run in business days. Batch rails settle, roll and
+//! return on banking days; real-time rails run every day. Regulatory
+//! deadlines stay in calendar days but one landing on a closure moves to
+//! the next business day. Rail cutoffs are set in Eastern time, the Fed's
+//! clock, and shift by the bank's offset from it.
+//!
+//! With the calendar off every day is a business day, which is how the
+//! sim ran before it.
+
+use crate::{config::BusinessCalendarConfig, types::Tick};
+use chrono::{Datelike, Duration, NaiveDate, Weekday};
+use std::collections::BTreeMap;
+
+/// UTC offset of the Fed's processing day, which rail cutoffs are set in.
+pub const RAIL_UTC_OFFSET_HOURS: i64 = -5;
+
+/// Years of holidays computed past the start date.
+const HORIZON_YEARS: i32 = 30;
+
+pub struct BusinessCalendar {
+    enabled: bool,
+    start: NaiveDate,
+    utc_offset_hours: i64,
+    weekend: Vec<Weekday>,
+    /// Closure date → name.
+    holidays: BTreeMap<NaiveDate, String>,
+}
+
+impl BusinessCalendar {
+    /// Errors on a start date or extra closure that is not `YYYY-MM-DD`
+    /// and on a weekend day that names no weekday, which would otherwise
+    /// move the run's dates or closures without a trace.
+    pub fn new(config: &BusinessCalendarConfig) -> anyhow::Result<Self> {
+        let start = parse_date("start_date", &config.start_date)?;
+        let weekend = config
+            .weekend_days
+            .iter()
+            .map(|d| {
+                d.parse::<Weekday>()
+                    .map_err(|_| anyhow::anyhow!("calendar weekend_days: unknown day {d:?}"))
+            })
+            .collect::<anyhow::Result<_>>()?;
+
+        let mut holidays = BTreeMap::new();
+        if config.federal_holidays {
+            for year in start.year() - 1..=start.year() + HORIZON_YEARS {
+                for (date, name) in federal_holidays(year) {
+                    holidays.insert(date, name.to_string());
+                }
+            }
+        }
+        for date in &config.extra_holidays {
+            let date = parse_date("extra_holidays", date)?;
+            holidays.entry(date).or_insert_with(|| "Bank closure".to_string());
+        }
+
+        Ok(Self {
+            enabled: config.enabled,
+            start,
+            utc_offset_hours: config.utc_offset_hours,
+            weekend,
+            holidays,
+        })
+    }
+
+    pub fn enabled(&self) -> bool {
+        self.enabled
+    }
+
+    /// Calendar date of a tick; tick 0 is the day before the start date.
+    pub fn date(&self, tick: Tick) -> NaiveDate {
+        self.start + Duration::days(tick as i64 - 1)
+    }
+
+    /// The holiday closing the bank on this tick, if any.
+    pub fn holiday(&self, tick: Tick) -> Option<&str> {
+        if !self.enabled {
+            return None;
+        }
+        self.holidays.get(&self.date(tick)).map(String::as_str)
+    }
+
+    pub fn is_business_day(&self, tick: Tick) -> bool {
+        if !self.enabled {
+            return true;
+        }
+        let date = self.date(tick);
+        !self.weekend.contains(&date.weekday()) && !self.holidays.contains_key(&date)
+    }
+
+    /// The first business day at or after `tick`.
+    pub fn roll_forward(&self, tick: Tick) -> Tick {
+        let mut t = tick;
+        while !self.is_business_day(t) {
+            t += 1;
+        }
+        t
+    }
+
+    /// The tick `days` business days after `tick`; zero days is the first
+    /// business day at or after it.
+    pub fn add_business_days(&self, tick: Tick, days: u64) -> Tick {
+        if !self.enabled {
+            return tick + days;
+        }
+        let mut t = tick;
+        let mut left = days;
+        while left > 0 {
+            t += 1;
+            if self.is_business_day(t) {
+                left -= 1;
+            }
+        }
+        self.roll_forward(t)
+    }
+
+    /// Every tick whose `days`-business-day offset lands on `tick`: what
+    /// a batch with that lag picks up today. Nothing on a closure, and a
+    /// closure's backlog on the next business day. With the calendar off,
+    /// just `tick - days`.
+    pub fn due_from(&self, tick: Tick, days: u64) -> Vec<Tick> {
+        if !self.enabled {
+            return vec![tick.saturating_sub(days)];
+        }
+        if !self.is_business_day(tick) {
+            return Vec::new();
+        }
+        let mut origins = Vec::new();
+        let mut origin = tick.saturating_sub(days);
+        loop {
+            let lands = self.add_business_days(origin, days);
+            if lands == tick {
+                origins.push(origin);
+            } else if lands < tick {
+                break;
+            }
+            if origin == 0 {
+                break;
+            }
+            origin -= 1;
+        }
+        origins.reverse();
+        origins
+    }
+
+    /// Business days in `(from, to]`.
+    pub fn business_days_between(&self, from: Tick, to: Tick) -> u64 {
+        (from + 1..=to).filter(|t| self.is_business_day(*t)).count() as u64
+    }
+
+    /// A rail cutoff set in Eastern time, as an hour of the bank's own
+    /// day. With the calendar off the bank keeps Eastern time.
+    pub fn local_cutoff_hour(&self, rail_cutoff_hour: Tick) -> Tick {
+        if !self.enabled {
+            return rail_cutoff_hour;
+        }
+        let shift = self.utc_offset_hours - RAIL_UTC_OFFSET_HOURS;
+        (rail_cutoff_hour as i64 + shift).clamp(0, 24) as Tick
+    }
+}
+
+fn parse_date(field: &str, value: &str) -> anyhow::Result<NaiveDate> {
+    NaiveDate::parse_from_str(value, "%Y-%m-%d")
+        .map_err(|_| anyhow::anyhow!("calendar {field}: {value:?} is not a YYYY-MM-DD date"))
+}
+
+/// US federal holidays observed in `year`, with weekend dates moved to
+/// the Friday before or Monday after. New Year's Day of the next year can
+/// be observed on this year's 31 December.
+pub fn federal_holidays(year: i32) -> Vec<(NaiveDate, &'static str)> {
+    let fixed = |month, day| NaiveDate::from_ymd_opt(year, month, day).unwrap();
+    let mut holidays = vec![
+        (observed(fixed(1, 1)), "New Year's Day"),
+        (nth_weekday(year, 1, Weekday::Mon, 3), "Martin Luther King Jr. Day"),
+        (nth_weekday(year, 2, Weekday::Mon, 3), "Washington's Birthday"),
+        (last_weekday(year, 5, Weekday::Mon), "Memorial Day"),
+        (observed(fixed(6, 19)), "Juneteenth"),
+        (observed(fixed(7, 4)), "Independence Day"),
+        (nth_weekday(year, 9, Weekday::Mon, 1), "Labor Day"),
+        (nth_weekday(year, 10, Weekday::Mon, 2), "Columbus Day"),
+        (observed(fixed(11, 11)), "Veterans Day"),
+        (nth_weekday(year, 11, Weekday::Thu, 4), "Thanksgiving Day"),
+        (observed(fixed(12, 25)), "Christmas Day"),
+    ];
+    holidays.retain(|(date, _)| date.year() == year || date.month() == 12);
+    let next_new_year = observed(NaiveDate::from_ymd_opt(year + 1, 1, 1).unwrap());
+    if next_new_year.year() == year {
+        holidays.push((next_new_year, "New Year's Day"));
+    }
+    holidays
+}
+
+fn observed(date: NaiveDate) -> NaiveDate {
+    match date.weekday() {
+        Weekday::Sat => date - Duration::days(1),
+        Weekday::Sun => date + Duration::days(1),
+        _ => date,
+    }
+}
+
+fn nth_weekday(year: i32, month: u32, weekday: Weekday, n: u8) -> NaiveDate {
+    NaiveDate::from_weekday_of_month_opt(year, month, weekday, n).unwrap()
+}
+
+fn last_weekday(year: i32, month: u32, weekday: Weekday) -> NaiveDate {
+    NaiveDate::from_weekday_of_month_opt(year, month, weekday, 5)
+        .unwrap_or_else(|| nth_weekday(year, month, weekday, 4))
+}
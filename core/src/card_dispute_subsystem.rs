@@ -3,33 +3,59 @@
 //! Generates disputes from settled card authorizations, models the complete
 //! dispute lifecycle, detects friendly fraud, and integrates with Economics
 //! (chargeback losses) and Complaint (rejected disputes) subsystems.
+//! A staffed dispute desk takes investigation and decision steps only as
+//! far as its capacity goes; the rest wait (Phase 4.80).
+//! With merchants on, a purchase is disputed at its merchant's rate and
+//! the dispute is filed against the merchant; merchants' chargeback rates
+//! are checked weekly (Phase 4.93).
 //!
 //! Execution order: After ReconciliationSubsystem, before ComplaintSubsystem.
 
 use crate::{
+    case_documents::CaseDocuments,
+    config::{CaseDocumentConfig, EvidenceBundleConfig, MerchantConfig},
     error::SimResult,
     event::SimEvent,
+    evidence_bundle::EvidenceBundles,
+    merchant::Merchants,
+    queue::QueueKind,
     rng::SubsystemRng,
-    store::SimStore,
-    subsystem::SimSubsystem,
+    store::{NewDispute, SimStore},
+    subsystem::{SimSubsystem, SubsystemManifest},
     types::{RunId, Tick},
 };
-use std::any::Any;
+use std::{any::Any, collections::BTreeMap};
 
 // Constants
 const DISPUTE_GENERATION_RATE: f64 = 0.008; // 0.8% of settled auths
 const PROVISIONAL_CREDIT_THRESHOLD_DAYS: i64 = 10; // Issue credit after 10 days
 const METRICS_INTERVAL: i64 = 7; // Weekly chargeback metrics
 const FRIENDLY_FRAUD_THRESHOLD: f64 = 0.70; // Auto-reject above 70%
+const FILING_WINDOW_TICKS: f64 = 31.0; // Ticks an auth sits in the 30-60 day window
 
 pub struct CardDisputeSubsystem {
     run_id: RunId,
+    /// Phase 4.31: supporting documents attached at filing.
+    documents: CaseDocuments,
+    /// Phase 4.59: the evidence bundle kept with each dispute.
+    evidence: EvidenceBundles,
+    /// Phase 4.93: per-merchant dispute rates and chargeback monitoring.
+    merchants: Merchants,
     store: SimStore,
 }
 
 impl CardDisputeSubsystem {
-    pub fn new(run_id: RunId, store: SimStore) -> Self {
-        Self { run_id, store }
+    pub fn new(
+        run_id: RunId,
+        documents: CaseDocumentConfig,
+        evidence: EvidenceBundleConfig,
+        merchants: MerchantConfig,
+        store: SimStore,
+    ) -> Self {
+        let documents = CaseDocuments::new(run_id.clone(), documents);
+        let evidence = EvidenceBundles::new(run_id.clone(), evidence);
+        let merchants = Merchants::new(run_id.clone(), merchants);
+        Self { run_id, documents, evidence, merchants, store }
     }
 
     /// Generate new disputes from settled authorizations.
@@ -51,9 +77,21 @@ impl CardDisputeSubsystem {
             window_end,
         )?;
 
+        // Phase 4.93: the merchants purchases were made at
+        let merchants: BTreeMap<String, _> = if self.merchants.enabled() {
+            self.store.merchants(&self.run_id)?.into_iter().map(|m| (m.merchant_id.clone(), m)).collect()
+        } else {
+            BTreeMap::new()
+        };
+
         for auth in settled_auths {
-            // Probabilistic dispute generation
-            if !rng.chance(DISPUTE_GENERATION_RATE) {
+            // Probabilistic dispute generation; a merchant's rate is
+            // spread over the days the auth is open to dispute
+            let rate = match auth.merchant_id.as_ref().and_then(|id| merchants.get(id)) {
+                Some(merchant) => self.merchants.dispute_rate(merchant, auth.tick_authorized) / FILING_WINDOW_TICKS,
+                None => DISPUTE_GENERATION_RATE,
+            };
+            if !rng.chance(rate) {
                 continue;
             }
 
@@ -82,19 +120,19 @@ impl CardDisputeSubsystem {
             let settled_amount = auth.cleared_amount.unwrap_or(auth.amount);
 
             // Create dispute
-            self.store.insert_dispute(
-                &self.run_id,
-                &dispute_id,
-                &auth.authorization_id,
-                &auth.account_id,
-                &customer_id,
-                tick as i64,
-                settled_amount,
-                auth.merchant_name.as_deref().unwrap_or("unknown"),
-                merchant_category,
-                &reason,
-                fraud_score,
-            )?;
+            self.store.insert_dispute(&self.run_id, &NewDispute {
+                dispute_id: dispute_id.clone(),
+                authorization_id: auth.authorization_id.clone(),
+                account_id: auth.account_id.clone(),
+                customer_id: customer_id.clone(),
+                tick_filed: tick as i64,
+                amount: settled_amount,
+                merchant_name: auth.merchant_name.clone().unwrap_or_else(|| "unknown".into()),
+                merchant_category: merchant_category.to_string(),
+                reason: reason.clone(),
+                friendly_fraud_score: fraud_score,
+                merchant_id: auth.merchant_id.clone(),
+            })?;
 
             events.push(SimEvent::DisputeFiled {
                 tick,
@@ -105,6 +143,18 @@ impl CardDisputeSubsystem {
                 reason: reason.clone(),
             });
 
+            // Phase 4.31: the dispute form and its supporting evidence
+            if self.documents.enabled() {
+                let dispute = self.store.get_dispute(&self.run_id, &dispute_id)?;
+                events.push(self.documents.for_dispute(&self.store, &dispute, tick, rng)?);
+            }
+
+            // Phase 4.59: the evidence the dispute is filed on
+            if self.evidence.enabled() {
+                let dispute = self.store.get_dispute(&self.run_id, &dispute_id)?;
+                events.push(self.evidence.for_dispute(&self.store, &dispute, tick)?);
+            }
+
             // Immediately transition to investigating
             self.store
                 .update_dispute_status(&self.run_id, &dispute_id, "investigating")?;
@@ -281,11 +331,27 @@ impl CardDisputeSubsystem {
         let mut events = Vec::new();
 
         let active = self.store.get_active_disputes(&self.run_id)?;
+        let capacity = self.store.staffing_capacity(&self.run_id, tick, QueueKind::Cases)?;
+        let mut worked = 0;
 
         for dispute in active {
             let config = self.store.get_dispute_config(&dispute.reason)?;
             let days_since_filed = (tick as i64) - dispute.tick_filed;
 
+            // Phase 4.80: investigation and decision steps are desk work;
+            // once the staff are spent they wait for the next tick
+            let desk_step = match dispute.status.as_str() {
+                "investigating" => days_since_filed >= config.investigation_duration_ticks,
+                "under_review" => days_since_filed >= config.investigation_duration_ticks + 10,
+                _ => false,
+            };
+            if desk_step {
+                if capacity.is_some_and(|c| worked >= c) {
+                    continue;
+                }
+                worked += 1;
+            }
+
             // investigating → resolved_rejected (if high fraud score)
             if dispute.status == "investigating"
                 && dispute.friendly_fraud_score > FRIENDLY_FRAUD_THRESHOLD
@@ -336,6 +402,9 @@ impl CardDisputeSubsystem {
                 self.resolve_dispute(&dispute.dispute_id, tick, outcome, customer_won, &mut events)?;
             }
         }
+        if capacity.is_some() {
+            self.store.record_staffing_work(&self.run_id, tick, QueueKind::Cases, worked)?;
+        }
 
         Ok(events)
     }
@@ -426,11 +495,30 @@ impl CardDisputeSubsystem {
     }
 }
 
+const MANIFEST: SubsystemManifest = SubsystemManifest {
+    version: 3,
+    produces: &[
+        "case_documents_generated", "chargeback_issued", "chargeback_metrics_computed",
+        "dispute_filed", "dispute_resolved", "dispute_status_changed", "evidence_bundle_assembled",
+        "friendly_fraud_detected", "merchant_chargeback_alert", "provisional_credit_issued",
+    ],
+    consumes: &[],
+    tables: &[
+        "account", "card_dispute", "case_document", "chargeback_metrics", "dispute_timeline",
+        "early_warning_alert", "evidence_bundle", "merchant", "staffing_snapshot",
+    ],
+    requires: &["payment_hub"],
+};
+
 impl SimSubsystem for CardDisputeSubsystem {
     fn name(&self) -> &'static str {
         "card_dispute"
     }
 
+    fn manifest(&self) -> SubsystemManifest {
+        MANIFEST
+    }
+
     fn update(
         &mut self,
         tick: Tick,
@@ -451,6 +539,11 @@ impl SimSubsystem for CardDisputeSubsystem {
         // 4. Compute weekly metrics
         events.extend(self.compute_metrics(tick)?);
 
+        // 5. Phase 4.93: weekly merchant chargeback monitoring
+        if self.merchants.enabled() && (tick as i64) % METRICS_INTERVAL == 0 {
+            events.extend(self.merchants.monitor(&self.store, tick)?);
+        }
+
         Ok(events)
     }
 
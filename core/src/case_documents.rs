@@ -0,0 +1,174 @@
+//! Supporting documents for disputes and complaints (Phase 4.31).
+//!
+//! When a case is filed it gets one record per document it requires, with
+//! the document's structured fields so the UI can render the evidence. A
+//! document may never have been collected; it is then recorded as missing
+//! rather than left out, and exams find the gap instead of assuming the
+//! file is complete.
+
+use crate::{
+    complaint_subsystem::ComplaintRecord,
+    config::CaseDocumentConfig,
+    error::SimResult,
+    event::SimEvent,
+    rng::SubsystemRng,
+    store::{case_document::CaseDocumentRow, DisputeRow, SimStore},
+    types::{RunId, Tick},
+};
+use serde_json::{json, Value};
+
+/// Reasons where the customer claims they never made the charge.
+const FRAUD_REASONS: &[&str] = &["unauthorized_charge", "card_stolen", "merchant_fraud"];
+
+/// Reasons the customer should first have taken up with the merchant.
+const MERCHANT_REASONS: &[&str] = &[
+    "service_not_rendered",
+    "defective_product",
+    "not_as_described",
+    "cancelled_subscription",
+    "credit_not_received",
+];
+
+const ID_TYPES: &[&str] = &["drivers_license", "passport", "state_id"];
+
+pub struct CaseDocuments {
+    run_id: RunId,
+    config: CaseDocumentConfig,
+}
+
+impl CaseDocuments {
+    pub fn new(run_id: RunId, config: CaseDocumentConfig) -> Self {
+        Self { run_id, config }
+    }
+
+    pub fn enabled(&self) -> bool {
+        self.config.enabled
+    }
+
+    /// A dispute needs the signed dispute form and the statement showing
+    /// the charge, plus an ID copy for fraud claims and the customer's
+    /// correspondence with the merchant for merchant disputes.
+    pub fn for_dispute(
+        &self,
+        store:   &SimStore,
+        dispute: &DisputeRow,
+        tick:    Tick,
+        rng:     &mut SubsystemRng,
+    ) -> SimResult<SimEvent> {
+        let mut documents = vec![
+            ("dispute_form", json!({
+                "dispute_id": dispute.dispute_id,
+                "authorization_id": dispute.authorization_id,
+                "amount": dispute.amount,
+                "merchant_name": dispute.merchant_name,
+                "reason": dispute.reason,
+                "tick_signed": tick,
+            })),
+            ("statement", self.statement(store, &dispute.account_id, tick)?),
+        ];
+        if FRAUD_REASONS.contains(&dispute.reason.as_str()) {
+            documents.push(("id_copy", id_copy(&dispute.customer_id, tick, rng)));
+        }
+        if MERCHANT_REASONS.contains(&dispute.reason.as_str()) {
+            documents.push(("merchant_correspondence", json!({
+                "merchant_name": dispute.merchant_name,
+                "tick_contacted": tick.saturating_sub(rng.next_u64_below(14) + 1),
+            })));
+        }
+        self.attach(store, "dispute", &dispute.dispute_id, &dispute.customer_id, documents, tick, rng)
+    }
+
+    /// A complaint needs its recorded narrative, the statement for the
+    /// account it concerns, and the referral when a regulator sent it.
+    pub fn for_complaint(
+        &self,
+        store:     &SimStore,
+        complaint: &ComplaintRecord,
+        tick:      Tick,
+        rng:       &mut SubsystemRng,
+    ) -> SimResult<SimEvent> {
+        let mut documents = vec![("complaint_narrative", json!({
+            "complaint_id": complaint.complaint_id,
+            "product": complaint.product,
+            "issue": complaint.issue,
+            "priority": complaint.priority,
+            "channel": complaint.channel,
+        }))];
+        if let Some(account_id) = &complaint.account_id {
+            documents.push(("statement", self.statement(store, account_id, tick)?));
+        }
+        if complaint.channel == "regulator" {
+            documents.push(("regulator_referral", json!({
+                "complaint_id": complaint.complaint_id,
+                "regulator": "CFPB",
+                "tick_received": tick,
+            })));
+        }
+        self.attach(store, "complaint", &complaint.complaint_id, &complaint.customer_id, documents, tick, rng)
+    }
+
+    /// The 30 days to `tick` for the account.
+    fn statement(&self, store: &SimStore, account_id: &str, tick: Tick) -> SimResult<Value> {
+        Ok(json!({
+            "account_id": account_id,
+            "period_start": tick.saturating_sub(30),
+            "period_end": tick,
+            "closing_balance": store.account_balance(&self.run_id, account_id)?,
+        }))
+    }
+
+    /// Record each document as on file or, with the configured chance,
+    /// missing.
+    #[allow(clippy::too_many_arguments)]
+    fn attach(
+        &self,
+        store:       &SimStore,
+        case_type:   &str,
+        case_id:     &str,
+        customer_id: &str,
+        documents:   Vec<(&str, Value)>,
+        tick:        Tick,
+        rng:         &mut SubsystemRng,
+    ) -> SimResult<SimEvent> {
+        let mut on_file = Vec::new();
+        let mut missing = Vec::new();
+        for (doc_type, content) in documents {
+            let collected = !rng.chance(self.config.missing_probability);
+            store.insert_case_document(&self.run_id, &CaseDocumentRow {
+                document_id:  format!("doc-{case_id}-{doc_type}"),
+                case_type:    case_type.into(),
+                case_id:      case_id.into(),
+                customer_id:  customer_id.into(),
+                doc_type:     doc_type.into(),
+                tick_created: tick,
+                status:       if collected { "on_file" } else { "missing" }.into(),
+                content:      collected.then(|| content.to_string()),
+            })?;
+            if collected {
+                on_file.push(doc_type.to_string());
+            } else {
+                missing.push(doc_type.to_string());
+            }
+        }
+        if !missing.is_empty() {
+            tracing::debug!("{case_type} {case_id} filed without {}", missing.join(", "));
+        }
+        Ok(SimEvent::CaseDocumentsGenerated {
+            tick,
+            case_type: case_type.into(),
+            case_id: case_id.into(),
+            customer_id: customer_id.into(),
+            on_file,
+            missing,
+        })
+    }
+}
+
+fn id_copy(customer_id: &str, tick: Tick, rng: &mut SubsystemRng) -> Value {
+    json!({
+        "customer_id": customer_id,
+        "id_type": ID_TYPES[rng.next_u64_below(ID_TYPES.len() as u64) as usize],
+        "id_last4": format!("{:04}", rng.next_u64_below(10_000)),
+        "expires_tick": tick + 365 + rng.next_u64_below(365 * 4),
+    })
+}
@@ -0,0 +1,275 @@
+//! Case management subsystem — Phase 4.86.
+//!
+//! Fraud alerts, AML alerts, screening hits, disputes and complaints are
+//! each worked on their own desk. An investigation case pulls together
+//! the ones on one customer, or on a relationship cluster when
+//! `min_relationship_strength` is set, so an analyst works them as one:
+//!   - An AML alert, fraud alert or screening hit joins the open case on
+//!     its customer's cluster, or opens one.
+//!   - A dispute or complaint joins the open case if there is one; on its
+//!     own it opens none.
+//!
+//! Each tick links the items raised since the previous tick that are in
+//! no case yet, so items written after the subsystem ran, by later
+//! subsystems or between ticks, are linked on the next.
+//!
+//! Player commands:
+//!   - AssignCase { case_id, analyst, note } — the case's analyst.
+//!   - CloseCase { case_id, disposition, note } — closes the case as
+//!     `unfounded` or `confirmed` and its items still open with it:
+//!     alerts as false positives or suspicious, screening hits as false
+//!     positives or confirmed matches, complaints under
+//!     `complaint_resolution_code`. Disputes keep to the card network's
+//!     timeline, and an AML alert at or above the reportable score is
+//!     left for the SAR desk's filing decision; both stay open.
+//!
+//! A case nobody closes closes as `worked` once its items have all been
+//! closed on their own desks. A case's consolidated timeline is the
+//! `investigation_case` entity timeline.
+//!
+//! Execution: every tick, after Complaint, so the day's alerts, hits,
+//! disputes and complaints have all been raised.
+
+use crate::{
+    command::PlayerCommand,
+    config::{CaseManagementConfig, SarDecisionConfig},
+    error::SimResult,
+    event::SimEvent,
+    rng::SubsystemRng,
+    store::{
+        case_management::{CaseItemRow, InvestigationCaseRow},
+        SimStore,
+    },
+    subsystem::{SimSubsystem, SubsystemManifest},
+    types::{RunId, Tick},
+};
+
+/// How an analyst can close a case.
+pub const DISPOSITIONS: &[&str] = &["unfounded", "confirmed"];
+
+/// The case if it is still open; otherwise why it cannot be worked.
+pub fn workable(case: Option<InvestigationCaseRow>, case_id: &str) -> Result<InvestigationCaseRow, String> {
+    match case {
+        None => Err(format!("no case {case_id}")),
+        Some(case) if case.status == "open" => Ok(case),
+        Some(_) => Err(format!("case {case_id} is closed")),
+    }
+}
+
+pub struct CaseSubsystem {
+    run_id: RunId,
+    config: CaseManagementConfig,
+    sar:    SarDecisionConfig,
+    store:  SimStore,
+}
+
+impl CaseSubsystem {
+    pub fn new(run_id: RunId, config: CaseManagementConfig, sar: SarDecisionConfig, store: SimStore) -> Self {
+        Self { run_id, config, sar, store }
+    }
+
+    /// The case, if it can still be worked; otherwise the rejection.
+    fn load(&self, case_id: &str, tick: Tick) -> SimResult<Result<InvestigationCaseRow, SimEvent>> {
+        let case = self.store.investigation_case(&self.run_id, case_id)?;
+        Ok(workable(case, case_id).map_err(|reason| SimEvent::InvestigationCaseRejected {
+            tick,
+            case_id: case_id.into(),
+            reason,
+        }))
+    }
+
+    /// Link the items raised since the previous tick into cases.
+    fn link_items(&self, tick: Tick) -> SimResult<Vec<SimEvent>> {
+        let mut events = Vec::new();
+        for (item, opens) in self.store.unlinked_case_items(&self.run_id, tick.saturating_sub(1))? {
+            let open_case = self.store.open_investigation_case_for(
+                &self.run_id,
+                &item.customer_id,
+                self.config.min_relationship_strength,
+            )?;
+            let case_id = match open_case {
+                Some(case_id) => case_id,
+                None if opens => {
+                    let case_id = format!("ic-{}", item.item_id);
+                    self.store.open_investigation_case(&self.run_id, &case_id, &item.customer_id, tick)?;
+                    events.push(SimEvent::InvestigationCaseOpened {
+                        tick,
+                        case_id: case_id.clone(),
+                        customer_id: item.customer_id.clone(),
+                        item_type: item.item_type.clone(),
+                        item_id: item.item_id.clone(),
+                    });
+                    case_id
+                }
+                None => continue,
+            };
+            self.store.link_case_item(&self.run_id, &case_id, &item, tick)?;
+        }
+        Ok(events)
+    }
+
+    fn assign(&self, case_id: &str, analyst: &str, note: Option<&str>, tick: Tick) -> SimResult<SimEvent> {
+        if let Err(rejected) = self.load(case_id, tick)? {
+            return Ok(rejected);
+        }
+        self.store.assign_investigation_case(&self.run_id, case_id, analyst)?;
+        if let Some(note) = note {
+            self.store.insert_case_note(&self.run_id, case_id, tick, Some(analyst), "assign", note)?;
+        }
+        tracing::info!("case {case_id} assigned to {analyst}");
+        Ok(SimEvent::InvestigationCaseAssigned { tick, case_id: case_id.into(), analyst: analyst.into() })
+    }
+
+    fn close(&self, case_id: &str, disposition: &str, note: &str, tick: Tick) -> SimResult<SimEvent> {
+        let case = match self.load(case_id, tick)? {
+            Ok(case) => case,
+            Err(rejected) => return Ok(rejected),
+        };
+        let analyst = case.assigned_to.as_deref();
+        let mut items_closed = 0;
+        for item in self.store.case_items(&self.run_id, case_id)? {
+            if !item.open {
+                continue;
+            }
+            if let Some(item_disposition) = self.close_item(&item, disposition, analyst, note, tick)? {
+                self.store
+                    .set_case_item_disposition(&self.run_id, &item.item_type, &item.item_id, &item_disposition)?;
+                items_closed += 1;
+            }
+        }
+        self.store.close_investigation_case(&self.run_id, case_id, disposition, tick)?;
+        self.store.insert_case_note(&self.run_id, case_id, tick, analyst, "close", note)?;
+        tracing::info!("case {case_id} closed as {disposition}, closing {items_closed} items");
+        Ok(SimEvent::InvestigationCaseClosed {
+            tick,
+            case_id: case_id.into(),
+            disposition: disposition.into(),
+            items_closed,
+        })
+    }
+
+    /// Close an open item with its case; the disposition set on it, or
+    /// None for an item left to its own desk.
+    fn close_item(
+        &self,
+        item: &CaseItemRow,
+        disposition: &str,
+        analyst: Option<&str>,
+        note: &str,
+        tick: Tick,
+    ) -> SimResult<Option<String>> {
+        let confirmed = disposition == "confirmed";
+        let item_disposition = match item.item_type.as_str() {
+            "aml_alert" => {
+                let Some(alert) = self.store.aml_alert(&self.run_id, &item.item_id)? else {
+                    return Ok(None);
+                };
+                if alert.alert_score >= self.sar.reportable_score {
+                    return Ok(None);
+                }
+                let status = if confirmed { "suspicious" } else { "false_positive" };
+                self.store.close_alert(&self.run_id, &item.item_id, status, tick, note)?;
+                self.store.insert_alert_note(&self.run_id, &item.item_id, tick, analyst, "close", note)?;
+                status
+            }
+            "fraud_alert" => {
+                let status = if confirmed { "resolved" } else { "false_positive" };
+                self.store.resolve_fraud_alert(&self.run_id, &item.item_id, status, tick)?;
+                status
+            }
+            "screening_hit" => {
+                let status = if confirmed { "confirmed" } else { "false_positive" };
+                self.store.review_screening_hit(&self.run_id, &item.item_id, status, tick, note)?;
+                status
+            }
+            "complaint" => {
+                let code = &self.config.complaint_resolution_code;
+                self.store.close_complaint(&self.run_id, &item.item_id, tick, code, 0.0)?;
+                code
+            }
+            _ => return Ok(None),
+        };
+        Ok(Some(item_disposition.into()))
+    }
+
+    /// Close the open cases whose items were all closed on their own
+    /// desks.
+    fn close_worked(&self, tick: Tick) -> SimResult<Vec<SimEvent>> {
+        let mut events = Vec::new();
+        for case in self.store.worked_investigation_cases(&self.run_id)? {
+            self.store.close_investigation_case(&self.run_id, &case.case_id, "worked", tick)?;
+            events.push(SimEvent::InvestigationCaseClosed {
+                tick,
+                case_id: case.case_id,
+                disposition: "worked".into(),
+                items_closed: 0,
+            });
+        }
+        Ok(events)
+    }
+}
+
+const MANIFEST: SubsystemManifest = SubsystemManifest {
+    version: 1,
+    produces: &[
+        "investigation_case_assigned", "investigation_case_closed", "investigation_case_opened",
+        "investigation_case_rejected",
+    ],
+    consumes: &["player_command_received"],
+    tables: &[
+        "alert_note", "aml_alert", "aml_screening_result", "case_item", "case_note", "complaint", "complaint_backlog",
+        "fraud_alert", "investigation_case",
+    ],
+    requires: &[],
+};
+
+impl SimSubsystem for CaseSubsystem {
+    fn name(&self) -> &'static str {
+        "case"
+    }
+
+    fn manifest(&self) -> SubsystemManifest {
+        MANIFEST
+    }
+
+    fn update(
+        &mut self,
+        tick:      Tick,
+        events_in: &[SimEvent],
+        _rng:      &mut SubsystemRng,
+    ) -> SimResult<Vec<SimEvent>> {
+        let mut out = Vec::new();
+
+        if !self.config.enabled || tick == 0 {
+            return Ok(out);
+        }
+
+        // 1. The player's case commands, on the cases as they stood.
+        for event in events_in {
+            let SimEvent::PlayerCommandReceived { command_id, .. } = event else {
+                continue;
+            };
+            match self.store.get_player_command(&self.run_id, command_id)? {
+                Some(PlayerCommand::AssignCase { case_id, analyst, note }) => {
+                    out.push(self.assign(&case_id, &analyst, note.as_deref(), tick)?);
+                }
+                Some(PlayerCommand::CloseCase { case_id, disposition, note }) => {
+                    out.push(self.close(&case_id, &disposition, &note, tick)?);
+                }
+                _ => {}
+            }
+        }
+
+        // 2. New items into cases.
+        out.extend(self.link_items(tick)?);
+
+        // 3. Cases whose items were all worked elsewhere.
+        out.extend(self.close_worked(tick)?);
+
+        Ok(out)
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+}
@@ -0,0 +1,222 @@
+//! Service channels (Phase 4.90).
+//!
+//! Every customer-initiated debit goes through a channel: cash is drawn
+//! at an ATM or over the counter at a branch, and payments are sent
+//! online, in the mobile app, at a branch or through the call center.
+//! Card purchases are made at the merchant and carry none. The channel
+//! is recorded on the transaction.
+//!
+//! Each channel runs on a system component. While that component has an
+//! open incident the channel is down (P0, P1) or degraded, turning away
+//! `degraded_failure_rate` of attempts. A customer turned away tries
+//! another working channel with `fallback_rate`, or gives up: the
+//! transaction is not booked and `ChannelTransactionAbandoned` may
+//! prompt a complaint about the channel.
+//!
+//! Each tick's transactions completed, diverted and abandoned on every
+//! channel, and their cost to serve, are written to `channel_activity`;
+//! the economics subsystem books the cost as opex.
+
+use crate::{
+    config::ChannelConfig,
+    error::SimResult,
+    rng::SubsystemRng,
+    store::{channel::ChannelActivityRow, SimStore},
+    types::{RunId, Tick},
+};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Channel {
+    Branch,
+    Atm,
+    Online,
+    Mobile,
+    CallCenter,
+}
+
+impl Channel {
+    pub const ALL: [Channel; 5] = [Self::Branch, Self::Atm, Self::Online, Self::Mobile, Self::CallCenter];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Branch => "branch",
+            Self::Atm => "atm",
+            Self::Online => "online",
+            Self::Mobile => "mobile",
+            Self::CallCenter => "call_center",
+        }
+    }
+
+    /// The `system_component` the channel runs on: tellers post to core
+    /// banking, and ATMs switch through the card processor.
+    pub fn component(&self) -> &'static str {
+        match self {
+            Self::Branch => "core_banking",
+            Self::Atm => "card_processor",
+            Self::Online => "online_banking",
+            Self::Mobile => "mobile_banking",
+            Self::CallCenter => "customer_service",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ChannelStatus {
+    Up,
+    Degraded,
+    Down,
+}
+
+impl ChannelStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Up => "up",
+            Self::Degraded => "degraded",
+            Self::Down => "down",
+        }
+    }
+}
+
+/// One tick's channel status and counts, filled in as transactions are
+/// routed.
+pub struct ChannelDay {
+    status: BTreeMap<Channel, ChannelStatus>,
+    rows: BTreeMap<Channel, ChannelActivityRow>,
+}
+
+impl ChannelDay {
+    pub fn status(&self, channel: Channel) -> ChannelStatus {
+        self.status.get(&channel).copied().unwrap_or(ChannelStatus::Up)
+    }
+
+    /// Phase 4.92: take a channel down for the day whatever its
+    /// component's state, as when no ATM is in service.
+    pub fn take_down(&mut self, channel: Channel) {
+        self.status.insert(channel, ChannelStatus::Down);
+        self.rows.get_mut(&channel).unwrap().status = ChannelStatus::Down.as_str().into();
+    }
+
+    /// Count a transaction the bank put through a channel itself, without
+    /// routing it.
+    pub fn complete(&mut self, channel: Channel) {
+        self.rows.get_mut(&channel).unwrap().completed += 1;
+    }
+}
+
+pub struct Channels {
+    run_id: RunId,
+    config: ChannelConfig,
+}
+
+impl Channels {
+    pub fn new(run_id: RunId, config: ChannelConfig) -> Self {
+        Self { run_id, config }
+    }
+
+    pub fn enabled(&self) -> bool {
+        self.config.enabled
+    }
+
+    /// Start the tick: each channel is as bad as the worst incident open
+    /// on its component.
+    pub fn open_day(&self, store: &SimStore, tick: Tick) -> SimResult<ChannelDay> {
+        let mut status = BTreeMap::new();
+        for incident in store.get_active_incidents(&self.run_id)? {
+            let severity = match incident.severity.as_str() {
+                "P0" | "P1" => ChannelStatus::Down,
+                _ => ChannelStatus::Degraded,
+            };
+            for channel in Channel::ALL.into_iter().filter(|c| c.component() == incident.component_id) {
+                let worst = status.entry(channel).or_insert(severity);
+                *worst = (*worst).max(severity);
+            }
+        }
+        let rows = Channel::ALL
+            .into_iter()
+            .map(|channel| {
+                let row = ChannelActivityRow {
+                    tick,
+                    channel: channel.as_str().into(),
+                    status: status.get(&channel).unwrap_or(&ChannelStatus::Up).as_str().into(),
+                    completed: 0,
+                    diverted: 0,
+                    abandoned: 0,
+                    cost: 0.0,
+                };
+                (channel, row)
+            })
+            .collect();
+        Ok(ChannelDay { status, rows })
+    }
+
+    /// Pick the channel a cash withdrawal or a payment goes through.
+    /// `Err` carries the channel that turned the customer away when they
+    /// gave up; `Ok(None)` when the mix is empty.
+    pub fn route(
+        &self,
+        day: &mut ChannelDay,
+        cash: bool,
+        rng: &mut SubsystemRng,
+    ) -> Result<Option<Channel>, Channel> {
+        let mix = if cash { &self.config.cash_mix } else { &self.config.payment_mix };
+        let Some(chosen) = pick(mix.iter().map(|(c, w)| (*c, *w)), rng) else {
+            return Ok(None);
+        };
+        let refused = match day.status(chosen) {
+            ChannelStatus::Up => false,
+            ChannelStatus::Degraded => rng.chance(self.config.degraded_failure_rate),
+            ChannelStatus::Down => true,
+        };
+        if !refused {
+            day.rows.get_mut(&chosen).unwrap().completed += 1;
+            return Ok(Some(chosen));
+        }
+
+        let working = mix
+            .iter()
+            .filter(|(c, _)| **c != chosen && day.status(**c) == ChannelStatus::Up)
+            .map(|(c, w)| (*c, *w));
+        let fallback = pick(working, rng).filter(|_| rng.chance(self.config.fallback_rate));
+        let row = day.rows.get_mut(&chosen).unwrap();
+        match fallback {
+            Some(fallback) => {
+                row.diverted += 1;
+                day.rows.get_mut(&fallback).unwrap().completed += 1;
+                Ok(Some(fallback))
+            }
+            None => {
+                row.abandoned += 1;
+                Err(chosen)
+            }
+        }
+    }
+
+    /// End the tick: cost the completed transactions and write the day.
+    pub fn close_day(&self, store: &SimStore, mut day: ChannelDay) -> SimResult<()> {
+        for (channel, row) in &mut day.rows {
+            row.cost = row.completed as f64 * self.config.cost_per_transaction.get(channel).copied().unwrap_or(0.0);
+            store.insert_channel_activity(&self.run_id, row)?;
+        }
+        Ok(())
+    }
+}
+
+/// A weighted draw; None when no weight is positive.
+fn pick(weights: impl Iterator<Item = (Channel, f64)>, rng: &mut SubsystemRng) -> Option<Channel> {
+    let weights: Vec<_> = weights.filter(|(_, w)| *w > 0.0).collect();
+    let total: f64 = weights.iter().map(|(_, w)| w).sum();
+    if total <= 0.0 {
+        return None;
+    }
+    let mut roll = rng.next_f64() * total;
+    for (channel, weight) in &weights {
+        if roll < *weight {
+            return Some(*channel);
+        }
+        roll -= weight;
+    }
+    weights.last().map(|(c, _)| *c)
+}
@@ -0,0 +1,393 @@
+//! Check fraud (Phase 4.54).
+//!
+//! Business customers write checks that clear against their accounts on
+//! the check rail, numbered in sequence from their checkbook. Now and then
+//! a washed check turns up instead: payee altered and amount raised, on a
+//! copy of a check already paid or a blank stolen from further on in the
+//! book. Customers also deposit checks drawn on other banks at the ATM or
+//! a branch, and fraud rings deposit counterfeits at the ATM, most printed
+//! with serials no real checkbook uses, and cash them out as soon as the
+//! funds land and any deposit hold lifts.
+//! During a fraud wave both typologies multiply.
+//!
+//! Two rules run over each day's items: DUPLICATE_SERIAL (the drawer has
+//! already had a check with this serial) and SERIAL_OUT_OF_RANGE (a
+//! presented check well past the drawer's last serial, or a deposited one
+//! outside the range real checkbooks use). A flagged item raises a fraud
+//! alert and waits for the check desk, which reviews a fixed number a day,
+//! oldest first; a flagged deposit is held until then.
+//!
+//! The return deadlines decide who eats the loss. A presented check the
+//! desk returns by the midnight deadline goes back to the depositary bank;
+//! one returned late, or one nobody flagged that the customer finds on
+//! their statement, the bank has to make good. A counterfeit deposit comes
+//! back from the paying bank a few days later and is charged back to the
+//! account; whatever the ring has already withdrawn is the bank's loss,
+//! unless the paying bank missed its own deadline and keeps it.
+
+use crate::{
+    config::CheckFraudConfig,
+    deposit_holds::DepositHolds,
+    error::SimResult,
+    event::SimEvent,
+    rng::SubsystemRng,
+    store::{check_fraud::CheckItemRow, AccountRow, SimStore},
+    types::{RunId, Tick},
+};
+use std::collections::{HashMap, HashSet};
+use uuid::Uuid;
+
+pub const DUPLICATE_SERIAL: &str = "check_duplicate_serial";
+pub const SERIAL_OUT_OF_RANGE: &str = "check_serial_out_of_range";
+
+pub struct CheckFraud {
+    run_id: RunId,
+    config: CheckFraudConfig,
+}
+
+impl CheckFraud {
+    pub fn new(run_id: RunId, config: CheckFraudConfig) -> Self {
+        Self { run_id, config }
+    }
+
+    pub fn enabled(&self) -> bool {
+        self.config.enabled
+    }
+
+    /// Whether a fraud wave is under way at `tick`.
+    pub fn in_wave(&self, tick: Tick) -> bool {
+        self.config.wave_start_tick.is_some_and(|start| tick >= start && tick < start + self.config.wave_ticks)
+    }
+
+    /// Clear today's checks: those written by business customers, and
+    /// those deposited, genuine or not.
+    pub fn book_checks(
+        &self,
+        store: &SimStore,
+        accounts: &[AccountRow],
+        tick: Tick,
+        rng: &mut SubsystemRng,
+    ) -> SimResult<Vec<SimEvent>> {
+        let c = &self.config;
+        let wave = self.in_wave(tick);
+        let multiplier = if wave { c.wave_multiplier } else { 1.0 };
+        let writers: HashSet<String> =
+            store.customers_in_segments(&self.run_id, &c.writer_segments)?.into_iter().collect();
+        let mut first_account: HashMap<&str, &AccountRow> = HashMap::new();
+        for acct in accounts.iter().filter(|a| writers.contains(&a.customer_id)) {
+            first_account.entry(acct.customer_id.as_str()).or_insert(acct);
+        }
+        let mut writers: Vec<&AccountRow> = first_account.into_values().collect();
+        writers.sort_by(|a, b| a.account_id.cmp(&b.account_id));
+
+        let mut items = Vec::new();
+        for acct in writers {
+            let last = store.last_check_serial(&self.run_id, &acct.account_id, tick + 1, true)?;
+            let share = c.check_share_min + (c.check_share_max - c.check_share_min) * rng.next_f64();
+            let written = (acct.balance * share * 100.0).floor() / 100.0;
+            if rng.chance(c.check_rate) && written >= c.min_check {
+                let serial = last.map_or(c.first_serial, |s| s + 1);
+                let payee = format!("vendor-{}-{}", acct.account_id, rng.next_u64_below(10));
+                items.push(self.item(acct, tick, "presented", &acct.account_id, serial, payee, written, "genuine"));
+            }
+            if rng.chance(c.washed_rate * multiplier) {
+                let last = last.unwrap_or(c.first_serial - 1);
+                let serial = if last >= c.first_serial && rng.chance(c.washed_copy_share) {
+                    last
+                } else {
+                    last + 1 + rng.next_u64_below(2 * c.serial_tolerance.max(1) as u64) as i64
+                };
+                let raise = c.washed_raise_min + (c.washed_raise_max - c.washed_raise_min) * rng.next_f64();
+                let amount = (written.max(c.min_check) * raise * 100.0).floor() / 100.0;
+                let payee = format!("payee-{:08x}", rng.next_u64() as u32);
+                items.push(self.item(acct, tick, "presented", &acct.account_id, serial, payee, amount, "washed"));
+            }
+        }
+        for acct in accounts {
+            if rng.chance(c.deposit_rate) {
+                let drawer = format!("drawer-{}", rng.next_u64_below(500));
+                let serial = c.serial_min + rng.next_u64_below((c.serial_max - c.serial_min + 1) as u64) as i64;
+                let amount = self.amount(c.deposit_amount_min, c.deposit_amount_max, rng);
+                let mut item = self.item(acct, tick, "deposited", &drawer, serial, acct.account_id.clone(), amount, "genuine");
+                if rng.chance(c.branch_share) {
+                    item.channel = "branch".to_string();
+                }
+                items.push(item);
+            }
+            if rng.chance(c.counterfeit_rate * multiplier) {
+                let drawer = format!("drawer-{}", rng.next_u64_below(500));
+                let serial = if rng.chance(c.counterfeit_off_range_share) {
+                    c.serial_max + 1 + rng.next_u64_below(c.serial_max as u64) as i64
+                } else {
+                    c.serial_min + rng.next_u64_below((c.serial_max - c.serial_min + 1) as u64) as i64
+                };
+                let amount = self.amount(c.counterfeit_amount_min, c.counterfeit_amount_max, rng);
+                items.push(self.item(acct, tick, "deposited", &drawer, serial, acct.account_id.clone(), amount, "counterfeit"));
+            }
+        }
+        if items.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let (mut presented, mut deposited, mut fraudulent, mut total) = (0, 0, 0, 0.0);
+        for item in &items {
+            let (direction, category, counterparty) = if item.side == "presented" {
+                presented += 1;
+                ("debit", "check_paid", &item.payee)
+            } else {
+                deposited += 1;
+                ("credit", "check_deposit", &item.drawer)
+            };
+            store.insert_transaction_with_rail(
+                &self.run_id,
+                &item.txn_id,
+                &item.account_id,
+                tick,
+                item.amount,
+                direction,
+                category,
+                Some(counterparty),
+                "check",
+                "settled",
+            )?;
+            let delta = if direction == "debit" { -item.amount } else { item.amount };
+            store.update_account_balance(&self.run_id, &item.account_id, delta)?;
+            store.insert_check_item(&self.run_id, item)?;
+            fraudulent += (item.typology != "genuine") as i64;
+            total += item.amount;
+        }
+        Ok(vec![SimEvent::CheckItemsBooked { tick, presented, deposited, fraudulent, amount: total, wave }])
+    }
+
+    fn amount(&self, low: f64, high: f64, rng: &mut SubsystemRng) -> f64 {
+        ((low + (high - low) * rng.next_f64()) * 100.0).floor() / 100.0
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn item(
+        &self,
+        acct: &AccountRow,
+        tick: Tick,
+        side: &str,
+        drawer: &str,
+        serial: i64,
+        payee: String,
+        amount: f64,
+        typology: &str,
+    ) -> CheckItemRow {
+        let (channel, deadline) = if side == "presented" {
+            ("clearing", tick + self.config.paying_deadline_ticks)
+        } else {
+            ("atm", tick + self.config.deposit_return_ticks)
+        };
+        CheckItemRow {
+            item_id: Uuid::new_v4().to_string(),
+            account_id: acct.account_id.clone(),
+            tick,
+            side: side.to_string(),
+            channel: channel.to_string(),
+            drawer: drawer.to_string(),
+            serial,
+            payee,
+            amount,
+            typology: typology.to_string(),
+            txn_id: Uuid::new_v4().to_string(),
+            rule: None,
+            alert_id: None,
+            deadline_tick: deadline,
+            status: "paid".to_string(),
+            resolved_tick: None,
+            loss_bearer: None,
+            loss: 0.0,
+        }
+    }
+
+    /// The serial rule `item` breaks, if any.
+    pub fn rule(&self, store: &SimStore, item: &CheckItemRow) -> SimResult<Option<&'static str>> {
+        let c = &self.config;
+        if store.check_serial_seen(&self.run_id, &item.drawer, item.serial, &item.item_id)? {
+            return Ok(Some(DUPLICATE_SERIAL));
+        }
+        let out_of_range = if item.side == "presented" {
+            let last = store.last_check_serial(&self.run_id, &item.drawer, item.tick, false)?;
+            item.serial < c.first_serial || item.serial > last.unwrap_or(c.first_serial - 1) + c.serial_tolerance
+        } else {
+            item.serial < c.serial_min || item.serial > c.serial_max
+        };
+        Ok(out_of_range.then_some(SERIAL_OUT_OF_RANGE))
+    }
+
+    /// Run the serial rules over today's items, let the desk work through
+    /// its queue, then settle the items whose deadlines have come.
+    pub fn process(&self, store: &SimStore, tick: Tick, rng: &mut SubsystemRng) -> SimResult<Vec<SimEvent>> {
+        let c = &self.config;
+        let mut events = Vec::new();
+
+        for mut item in store.check_items_at(&self.run_id, tick)? {
+            let Some(rule) = self.rule(store, &item)? else {
+                continue;
+            };
+            let score = if rule == DUPLICATE_SERIAL { 0.9 } else { 0.75 };
+            let severity = if score >= 0.8 { "high" } else { "medium" };
+            let alert_id = format!("fraud-check-{}", item.item_id);
+            store.insert_fraud_alert(
+                &self.run_id,
+                &alert_id,
+                tick as i64,
+                rule,
+                "transaction",
+                &item.txn_id,
+                score,
+                severity,
+            )?;
+            events.push(SimEvent::FraudAlertGenerated {
+                tick,
+                alert_id: alert_id.clone(),
+                alert_type: rule.to_string(),
+                entity_id: item.txn_id.clone(),
+                fraud_score: score,
+                severity: severity.to_string(),
+            });
+            item.rule = Some(rule.to_string());
+            item.alert_id = Some(alert_id);
+            item.status = "pending_review".to_string();
+            store.update_check_item(&self.run_id, &item)?;
+        }
+
+        for item in store.check_items_in_status(&self.run_id, "pending_review")?.into_iter().take(c.review_capacity) {
+            events.push(self.review(store, item, tick)?);
+        }
+
+        for mut item in store.outstanding_fraudulent_checks(&self.run_id)? {
+            if item.side == "presented" {
+                if tick > item.deadline_tick {
+                    // The customer finds it on their statement
+                    self.refund(store, &item, tick)?;
+                    events.push(self.resolve(store, item, tick, "reimbursed", Some("bank"), None)?);
+                }
+                continue;
+            }
+            if tick >= item.deadline_tick {
+                if rng.chance(c.late_return_rate) {
+                    events.push(self.resolve(store, item, tick, "late_return", Some("paying_bank"), None)?);
+                } else {
+                    let shortfall = self.charge_back(store, &item, tick)?;
+                    let bearer = (shortfall > 0.0).then_some("bank");
+                    events.push(self.resolve(store, item, tick, "returned", bearer, Some(shortfall))?);
+                }
+            } else if item.status == "paid"
+                && tick >= item.tick + c.cash_out_ticks
+                && store.active_deposit_hold(&self.run_id, &item.txn_id)?.is_none()
+            {
+                let balance = store.account_balance(&self.run_id, &item.account_id)?;
+                let cash = item.amount.min(balance.max(0.0));
+                if cash > 0.0 {
+                    store.insert_transaction_with_rail(
+                        &self.run_id,
+                        &Uuid::new_v4().to_string(),
+                        &item.account_id,
+                        tick,
+                        cash,
+                        "debit",
+                        "cash_withdrawal",
+                        None,
+                        "ACH",
+                        "settled",
+                    )?;
+                    store.update_account_balance(&self.run_id, &item.account_id, -cash)?;
+                }
+                item.status = "cashed_out".to_string();
+                store.update_check_item(&self.run_id, &item)?;
+            }
+        }
+        Ok(events)
+    }
+
+    /// The desk's decision on a flagged item.
+    fn review(&self, store: &SimStore, item: CheckItemRow, tick: Tick) -> SimResult<SimEvent> {
+        if item.typology == "genuine" {
+            return self.resolve(store, item, tick, "cleared", None, None);
+        }
+        if item.side == "deposited" {
+            let shortfall = self.charge_back(store, &item, tick)?;
+            let bearer = (shortfall > 0.0).then_some("bank");
+            return self.resolve(store, item, tick, "returned", bearer, Some(shortfall));
+        }
+        self.refund(store, &item, tick)?;
+        if tick <= item.deadline_tick {
+            self.resolve(store, item, tick, "returned", Some("depositary_bank"), None)
+        } else {
+            self.resolve(store, item, tick, "reimbursed", Some("bank"), None)
+        }
+    }
+
+    /// Put a fraudulent presented check's amount back in the account.
+    fn refund(&self, store: &SimStore, item: &CheckItemRow, tick: Tick) -> SimResult<()> {
+        store.insert_transaction_with_rail(
+            &self.run_id,
+            &Uuid::new_v4().to_string(),
+            &item.account_id,
+            tick,
+            item.amount,
+            "credit",
+            "check_return",
+            Some(&item.payee),
+            "check",
+            "settled",
+        )?;
+        store.update_account_balance(&self.run_id, &item.account_id, item.amount)
+    }
+
+    /// Reverse a returned deposit out of the account, as far as its
+    /// balance goes, lifting any hold on it; returns the shortfall.
+    fn charge_back(&self, store: &SimStore, item: &CheckItemRow, tick: Tick) -> SimResult<f64> {
+        DepositHolds::lift(store, &self.run_id, &item.txn_id, tick)?;
+        let balance = store.account_balance(&self.run_id, &item.account_id)?;
+        let taken = item.amount.min(balance.max(0.0));
+        if taken > 0.0 {
+            store.insert_transaction_with_rail(
+                &self.run_id,
+                &Uuid::new_v4().to_string(),
+                &item.account_id,
+                tick,
+                taken,
+                "debit",
+                "check_return",
+                Some(&item.drawer),
+                "check",
+                "settled",
+            )?;
+            store.update_account_balance(&self.run_id, &item.account_id, -taken)?;
+        }
+        Ok(item.amount - taken)
+    }
+
+    /// Close `item` out; the loss is its whole amount unless given.
+    fn resolve(
+        &self,
+        store: &SimStore,
+        mut item: CheckItemRow,
+        tick: Tick,
+        outcome: &str,
+        loss_bearer: Option<&str>,
+        loss: Option<f64>,
+    ) -> SimResult<SimEvent> {
+        item.loss = if loss_bearer.is_some() { loss.unwrap_or(item.amount) } else { 0.0 };
+        item.loss_bearer = loss_bearer.map(str::to_string);
+        item.status = outcome.to_string();
+        item.resolved_tick = Some(tick);
+        store.update_check_item(&self.run_id, &item)?;
+        if item.loss_bearer.as_deref() == Some("bank") {
+            tracing::warn!("check {} {outcome}: bank loses ${:.2}", item.item_id, item.loss);
+        }
+        Ok(SimEvent::CheckFraudResolved {
+            tick,
+            item_id: item.item_id,
+            side: item.side,
+            typology: item.typology,
+            outcome: outcome.to_string(),
+            loss_bearer: item.loss_bearer,
+            loss: item.loss,
+        })
+    }
+}
@@ -0,0 +1,155 @@
+//! Churn prediction evaluation (Phase 4.64).
+//!
+//! Every churn score carries a 30- and a 90-day prediction. Once a score
+//! batch's horizon has elapsed, the evaluation checks those predictions
+//! against who actually churned, segment by segment:
+//!
+//! - calibration: the mean prediction against the realized churn rate,
+//! - Brier score: the mean squared error of the predictions,
+//! - AUC: the chance a churner was scored above a stayer, ties counting
+//!   half; the ranking quality, whatever the calibration.
+//!
+//! A segment's first evaluation at a horizon is its baseline. A later
+//! batch whose AUC fell, or whose Brier score rose, past the tolerance is
+//! flagged as drift: the model's accuracy has decayed since the run
+//! began. Segments too small to judge are recorded but never flagged.
+
+use crate::{
+    config::ChurnEvaluationConfig,
+    error::SimResult,
+    event::SimEvent,
+    store::{
+        churn_evaluation::{ChurnOutcome, ChurnPredictionEvalRow},
+        SimStore,
+    },
+    types::{RunId, Tick},
+};
+
+/// Horizons the churn score predicts over, in ticks.
+pub const HORIZONS: &[Tick] = &[30, 90];
+
+pub struct ChurnEvaluation {
+    run_id: RunId,
+    config: ChurnEvaluationConfig,
+}
+
+impl ChurnEvaluation {
+    pub fn new(run_id: RunId, config: ChurnEvaluationConfig) -> Self {
+        Self { run_id, config }
+    }
+
+    pub fn enabled(&self) -> bool {
+        self.config.enabled
+    }
+
+    /// Judge every score batch whose horizon had elapsed by `tick`.
+    pub fn evaluate(&self, store: &SimStore, tick: Tick) -> SimResult<Vec<SimEvent>> {
+        let mut events = Vec::new();
+        for &horizon in HORIZONS {
+            for score_tick in store.unevaluated_churn_score_ticks(&self.run_id, horizon, tick)? {
+                let outcomes = store.churn_outcomes(&self.run_id, score_tick, horizon)?;
+                let mut drifting = 0;
+                for segment in outcomes.chunk_by(|a, b| a.segment == b.segment) {
+                    let mut row = metrics(segment, score_tick, horizon, tick);
+                    if let Some(event) = self.check_drift(store, &mut row)? {
+                        drifting += 1;
+                        events.push(event);
+                    }
+                    store.insert_churn_prediction_eval(&self.run_id, &row)?;
+                }
+                events.push(SimEvent::ChurnPredictionsEvaluated {
+                    tick,
+                    score_tick,
+                    horizon,
+                    customers: outcomes.len() as u32,
+                    churned: outcomes.iter().filter(|o| o.churned).count() as u32,
+                    drifting_segments: drifting,
+                });
+            }
+        }
+        Ok(events)
+    }
+
+    /// Hold `row` to its segment's baseline, flagging it if it scored
+    /// worse by more than the tolerance.
+    fn check_drift(&self, store: &SimStore, row: &mut ChurnPredictionEvalRow) -> SimResult<Option<SimEvent>> {
+        let min_customers = self.config.min_customers as i64;
+        if row.customers < min_customers {
+            return Ok(None);
+        }
+        let Some(baseline) = store.churn_eval_baseline(&self.run_id, &row.segment, row.horizon, min_customers)?
+        else {
+            return Ok(None);
+        };
+        let auc_drop = match (baseline.auc, row.auc) {
+            (Some(before), Some(now)) => before - now,
+            _ => 0.0,
+        };
+        let brier_rise = row.brier_score - baseline.brier_score;
+        row.drift = auc_drop > self.config.auc_drop_tolerance || brier_rise > self.config.brier_rise_tolerance;
+        if !row.drift {
+            return Ok(None);
+        }
+        tracing::warn!(
+            "churn model drifting in {} at {}d: AUC down {auc_drop:.3}, Brier up {brier_rise:.3} since tick {}",
+            row.segment,
+            row.horizon,
+            baseline.score_tick,
+        );
+        Ok(Some(SimEvent::ChurnModelDriftDetected {
+            tick: row.eval_tick,
+            segment: row.segment.clone(),
+            horizon: row.horizon,
+            score_tick: row.score_tick,
+            auc: row.auc,
+            baseline_auc: baseline.auc,
+            brier_score: row.brier_score,
+            baseline_brier_score: baseline.brier_score,
+        }))
+    }
+}
+
+/// One segment's metrics for a score batch.
+fn metrics(outcomes: &[ChurnOutcome], score_tick: Tick, horizon: Tick, tick: Tick) -> ChurnPredictionEvalRow {
+    let customers = outcomes.len() as f64;
+    let churned = outcomes.iter().filter(|o| o.churned).count();
+    let outcome = |o: &ChurnOutcome| if o.churned { 1.0 } else { 0.0 };
+    let mean_predicted = outcomes.iter().fold(0.0, |sum, o| sum + o.predicted) / customers;
+    let realized_rate = churned as f64 / customers;
+    let brier_score = outcomes.iter().fold(0.0, |sum, o| sum + (o.predicted - outcome(o)).powi(2)) / customers;
+    ChurnPredictionEvalRow {
+        score_tick,
+        horizon,
+        segment: outcomes[0].segment.clone(),
+        eval_tick: tick,
+        customers: outcomes.len() as i64,
+        churned: churned as i64,
+        mean_predicted,
+        realized_rate,
+        calibration_gap: mean_predicted - realized_rate,
+        brier_score,
+        auc: auc(outcomes),
+        drift: false,
+    }
+}
+
+/// Chance a churner was scored above a stayer, ties counting half: the
+/// Mann-Whitney U over the number of pairs. None without both.
+fn auc(outcomes: &[ChurnOutcome]) -> Option<f64> {
+    let churners = outcomes.iter().filter(|o| o.churned).count() as f64;
+    let stayers = outcomes.len() as f64 - churners;
+    if churners == 0.0 || stayers == 0.0 {
+        return None;
+    }
+    let mut ranked: Vec<&ChurnOutcome> = outcomes.iter().collect();
+    ranked.sort_by(|a, b| a.predicted.total_cmp(&b.predicted));
+    // Tied predictions share the mean of the ranks they span.
+    let mut next_rank = 1.0;
+    let mut churner_ranks = 0.0;
+    for tied in ranked.chunk_by(|a, b| a.predicted == b.predicted) {
+        let rank = next_rank + (tied.len() as f64 - 1.0) / 2.0;
+        churner_ranks += rank * tied.iter().filter(|o| o.churned).count() as f64;
+        next_rank += tied.len() as f64;
+    }
+    Some((churner_ranks - churners * (churners + 1.0) / 2.0) / (churners * stayers))
+}
@@ -6,17 +6,20 @@
 //!   3. Tracks churn component contributions for analysis
 //!   4. Triggers actual churn when risk exceeds threshold + coin flip
 //!   5. Records churn cohorts for post-mortem analysis
+//!   6. Offers the hardship program after qualifying life events (Phase 4.30)
+//!   7. Judges past predictions once their horizons elapse (Phase 4.64)
 //!
 //! Execution: every 30 ticks (monthly).
 //! Depends on: customer, complaint, transaction, offer subsystems.
 
 use crate::{
+    churn_evaluation::ChurnEvaluation,
     config::SimConfig,
     error::SimResult,
     event::SimEvent,
     rng::SubsystemRng,
-    store::SimStore,
-    subsystem::SimSubsystem,
+    store::{hardship::HardshipEnrollmentRow, SimStore},
+    subsystem::{SimSubsystem, SubsystemManifest},
     types::{RunId, Tick},
 };
 use serde::{Deserialize, Serialize};
@@ -67,6 +70,8 @@ pub struct CustomerChurnInputs {
     pub product_count: usize,
     pub has_active_retention_offer: bool,
     pub active_life_event_delta: f64,
+    /// Enrolled in the hardship program (Phase 4.30).
+    pub in_hardship_program: bool,
 }
 
 #[derive(Debug, Clone)]
@@ -87,14 +92,17 @@ pub struct ChurnSubsystem {
     run_id: RunId,
     config: SimConfig,
     store: SimStore,
+    evaluation: ChurnEvaluation,
 }
 
 impl ChurnSubsystem {
     pub fn new(run_id: RunId, config: SimConfig, store: SimStore) -> Self {
+        let evaluation = ChurnEvaluation::new(run_id.clone(), config.churn_evaluation.clone());
         Self {
             run_id,
             config,
             store,
+            evaluation,
         }
     }
 
@@ -184,6 +192,13 @@ impl ChurnSubsystem {
             1.0
         };
 
+        // Hardship program relief (Phase 4.30)
+        let hardship_relief = if customer.in_hardship_program && self.config.hardship.enabled {
+            self.config.hardship.churn_risk_relief
+        } else {
+            0.0
+        };
+
         let additive_risk = base_rate
             + satisfaction_component
             + fee_burden_component
@@ -192,7 +207,8 @@ impl ChurnSubsystem {
             + inactivity_component
             + product_depth_bonus
             + retention_offer_bonus
-            + customer.active_life_event_delta;
+            + customer.active_life_event_delta
+            - hardship_relief;
 
         let churn_risk = (additive_risk * life_event_multiplier).clamp(0.0, 1.0);
 
@@ -251,6 +267,37 @@ impl ChurnSubsystem {
         events
     }
 
+    /// Phase 4.30: offer the hardship program to a customer hit by a
+    /// qualifying life event. Already enrolled customers stay as they are.
+    fn offer_hardship(&self, event: &LifeEvent, tick: Tick, rng: &mut SubsystemRng) -> SimResult<Option<SimEvent>> {
+        let program = &self.config.hardship;
+        if !program.eligible_life_events.contains(&event.event_type)
+            || self.store.in_hardship_program(&self.run_id, &event.customer_id, tick)?
+            || !rng.chance(program.enrollment_probability)
+        {
+            return Ok(None);
+        }
+        let tick_expires = tick + program.duration_ticks;
+        self.store.insert_hardship_enrollment(&self.run_id, &HardshipEnrollmentRow {
+            customer_id: event.customer_id.clone(),
+            tick_enrolled: tick,
+            tick_expires,
+            life_event: event.event_type.clone(),
+        })?;
+        self.store.update_customer_satisfaction(
+            &self.run_id,
+            &event.customer_id,
+            program.enrollment_satisfaction_delta,
+        )?;
+        tracing::debug!("{} enrolled in hardship program after {}", event.customer_id, event.event_type);
+        Ok(Some(SimEvent::HardshipEnrolled {
+            tick,
+            customer_id: event.customer_id.clone(),
+            life_event: event.event_type.clone(),
+            tick_expires,
+        }))
+    }
+
     fn should_churn(&self, score: &ChurnScore, rng: &mut SubsystemRng) -> bool {
         let t = &self.config.churn_model.churn_thresholds;
 
@@ -318,11 +365,29 @@ impl ChurnSubsystem {
     }
 }
 
+const MANIFEST: SubsystemManifest = SubsystemManifest {
+    version: 2,
+    produces: &[
+        "churn_model_drift_detected", "churn_predictions_evaluated", "customer_churned",
+        "hardship_enrolled", "life_event_occurred",
+    ],
+    consumes: &[],
+    tables: &[
+        "account", "churn_aggregate", "churn_cohort", "churn_prediction_eval", "customer",
+        "customer_churn_score", "hardship_enrollment", "life_event",
+    ],
+    requires: &[],
+};
+
 impl SimSubsystem for ChurnSubsystem {
     fn name(&self) -> &'static str {
         "churn"
     }
 
+    fn manifest(&self) -> SubsystemManifest {
+        MANIFEST
+    }
+
     fn update(
         &mut self,
         tick: Tick,
@@ -374,8 +439,8 @@ impl SimSubsystem for ChurnSubsystem {
                         churn_risk: score.churn_risk,
                     });
 
-                    log::info!(
-                        "tick={tick} churn: {} churned (risk={:.3}, driver=cohort:{})",
+                    tracing::info!(
+                        "{} churned (risk={:.3}, driver=cohort:{})",
                         customer_record.customer_id,
                         score.churn_risk,
                         cohort_id,
@@ -398,12 +463,16 @@ impl SimSubsystem for ChurnSubsystem {
                         duration,
                     });
 
-                    log::debug!(
-                        "tick={tick} churn: life_event={} for {} (delta={:.2})",
+                    tracing::debug!(
+                        "life_event={} for {} (delta={:.2})",
                         event.event_type,
                         event.customer_id,
                         event.churn_risk_delta,
                     );
+
+                    if self.config.hardship.enabled {
+                        out.extend(self.offer_hardship(&event, tick, rng)?);
+                    }
                 }
             }
 
@@ -421,7 +490,11 @@ impl SimSubsystem for ChurnSubsystem {
                 }
             }
 
-            log::debug!("tick={tick} churn: score update complete");
+            if self.evaluation.enabled() {
+                out.extend(self.evaluation.evaluate(&self.store, tick)?);
+            }
+
+            tracing::debug!("score update complete");
         }
 
         Ok(out)
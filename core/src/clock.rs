@@ -1,6 +1,7 @@
 //! Simulation clock — owns tick state, speed control, and pause.
 
 use crate::types::{RunId, Tick};
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -9,6 +10,10 @@ pub struct SimClock {
     pub current_tick: Tick,
     pub speed: SimSpeed,
     pub paused: bool,
+    /// The player paused the run with `PlayerCommand::Pause`: ticks are
+    /// refused until they resume. `paused` only says no tick is running.
+    #[serde(default)]
+    pub held: bool,
 }
 
 impl SimClock {
@@ -18,6 +23,7 @@ impl SimClock {
             current_tick: 0,
             speed: SimSpeed::Normal,
             paused: true,
+            held: false,
         }
     }
 
@@ -49,7 +55,7 @@ impl SimClock {
     }
 }
 
-#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum SimSpeed {
     Normal,      // 1 tick/step  (1 day per ~3 real seconds)
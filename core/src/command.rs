@@ -30,6 +30,229 @@ pub enum PlayerCommand {
         dial_id: String,
         new_value: f64,
     },
+    // ── Phase 4.3 ─────────────────────────────────
+    OpenBranch {
+        state_code: String,
+    },
+    CloseBranch {
+        branch_id: String,
+    },
+    // ── Phase 4.4 ─────────────────────────────────
+    RespondFairLendingFinding {
+        finding_id: String,
+        response: String, // "remediate" | "justify"
+    },
+    // ── Phase 4.5 ─────────────────────────────────
+    LaunchOffer {
+        offer_id: String,
+        exclude_zip_prefixes: Vec<String>,
+    },
+    // ── Phase 4.6 ─────────────────────────────────
+    ClaimNetworkRecovery {
+        invoice_id: String,
+    },
+    // ── Phase 4.10 ────────────────────────────────
+    SetAchOriginationLimits {
+        daily_count: u32,
+        daily_amount: f64,
+    },
+    // ── Phase 4.11 ────────────────────────────────
+    SetLinkVerificationStrictness {
+        strictness: f64, // share of new links verified by micro-deposit, 0.0–1.0
+    },
+    // ── Phase 4.12 ────────────────────────────────
+    SetCustomerLimit {
+        limit_type: String, // "daily_transfer" | "cash_withdrawal" | "p2p_send" | "wire"
+        scope: String,      // "global" | "tier:<composite_risk>" | "product:<product_id>"
+        amount: f64,
+    },
+    // ── Phase 4.16 ────────────────────────────────
+    SetSarThreshold {
+        threshold: f64, // alert score at or above which a SAR is filed, 0–100
+    },
+    // ── Phase 4.17 ────────────────────────────────
+    OpenTuningReview {
+        rule_id: String,   // monitoring rule the review covers, e.g. "STRUCT_9K"
+        rationale: String, // documented reason for the proposed tuning
+    },
+    TuneMonitoringRule {
+        rule_id: String,
+        change: String,              // "suppress" | "raise_threshold" | "exclude_customer" | "restore"
+        threshold_multiplier: f64,   // raise_threshold only, at least 1.0
+        customer_id: Option<String>, // exclude_customer only
+        review_id: Option<String>,   // approved tuning review documenting the change
+    },
+    // ── Phase 4.29 ────────────────────────────────
+    SetComplaintSlaPolicy {
+        priority: Option<String>, // None matches every priority
+        product: Option<String>,  // None matches every product
+        channel: Option<String>,  // "phone" | "branch" | "online" | "regulator"; None matches all
+        resolve_days: u64,
+        effective_tick: Tick, // applies to complaints opened from this tick
+    },
+    // ── Phase 4.32 ────────────────────────────────
+    SetContactPolicy {
+        honor_do_not_contact: bool,           // false contacts do-not-contact customers anyway
+        max_contacts_per_window: Option<u32>, // per customer per fatigue window; None is uncapped
+    },
+    // ── Phase 4.34 ────────────────────────────────
+    SetCreditHealthFloor {
+        min_score: f64, // 0..1; credit offers skip customers scoring below it
+    },
+    // ── Phase 4.37 ────────────────────────────────
+    SetVisaRestrictionPolicy {
+        level: String,    // "none" | "outbound" | "full"
+        grace_ticks: u64, // ticks lapsed before the restriction applies
+    },
+    // ── Phase 4.52 ────────────────────────────────
+    SetDepositRatePremium {
+        premium: f64, // annual rate paid over the usual deposit rate, e.g. 0.01
+    },
+    DrawEmergencyLiquidity {
+        amount: f64, // discount window credit to draw during a deposit run
+    },
+    // ── Phase 4.53 ────────────────────────────────
+    RecallWire {
+        txn_id: String, // outgoing wire to ask the receiving bank to return
+    },
+    // ── Phase 4.55 ────────────────────────────────
+    SetDepositHoldPolicy {
+        class: Option<String>, // "atm" | "branch" | "new_account" | "large_item"; None matches all
+        risk: Option<String>,  // "standard" | "elevated"; None matches both
+        hold_ticks: Tick,      // ticks funds above the next-day amount stay held
+    },
+    // ── Phase 4.62 ────────────────────────────────
+    SignOffCloseItem {
+        period: String, // quarter closing, e.g. "Q1-Y1"
+        item: String,   // "accruals" | "reconciliation" | "disputes" | "pnl"
+    },
+    PostCloseAdjustment {
+        period: String, // quarter the adjustment belongs to
+        amount: f64,    // signed; positive raises profit
+        reason: String,
+    },
+    // ── Phase 4.80 ────────────────────────────────
+    SetStaffing {
+        queue: crate::queue::QueueKind,
+        ftes: f64, // full-time case workers on the queue; 0 leaves it unworked
+    },
+    // ── Phase 4.85 ────────────────────────────────
+    AssignAlert {
+        alert_id: String,
+        analyst: String,
+        note: Option<String>,
+    },
+    CloseAlert {
+        alert_id: String,
+        disposition: String, // "false_positive" | "suspicious" | "sar_filed"
+        note: String,
+    },
+    EscalateAlertToCase {
+        alert_id: String,
+        note: String,
+    },
+
+    // ── Phase 4.86 ────────────────────────────────
+    AssignCase {
+        case_id: String,
+        analyst: String,
+        note: Option<String>,
+    },
+    CloseCase {
+        case_id: String,
+        disposition: String, // "unfounded" | "confirmed"
+        note: String,
+    },
+    // ── Phase 4.89 ────────────────────────────────
+    SetSanctionsSensitivity {
+        sensitivity: f64, // 0..1; higher flags weaker name matches, trading false positives for misses
+    },
+    // ── Phase 4.91 ────────────────────────────────
+    SetAppReimbursementPolicy {
+        policy: String, // "none" | "vulnerable" | "full": which scam victims' claims the bank pays
+    },
+}
+
+impl PlayerCommand {
+    /// Every command's `cmd` tag, in declaration order.
+    pub const NAMES: &'static [&'static str] = &[
+        "pause",
+        "resume",
+        "set_speed",
+        "close_complaint",
+        "set_product_fee",
+        "set_risk_dial",
+        "open_branch",
+        "close_branch",
+        "respond_fair_lending_finding",
+        "launch_offer",
+        "claim_network_recovery",
+        "set_ach_origination_limits",
+        "set_link_verification_strictness",
+        "set_customer_limit",
+        "set_sar_threshold",
+        "open_tuning_review",
+        "tune_monitoring_rule",
+        "set_complaint_sla_policy",
+        "set_contact_policy",
+        "set_credit_health_floor",
+        "set_visa_restriction_policy",
+        "set_deposit_rate_premium",
+        "draw_emergency_liquidity",
+        "recall_wire",
+        "set_deposit_hold_policy",
+        "sign_off_close_item",
+        "post_close_adjustment",
+        "set_staffing",
+        "assign_alert",
+        "close_alert",
+        "escalate_alert_to_case",
+        "assign_case",
+        "close_case",
+        "set_sanctions_sensitivity",
+        "set_app_reimbursement_policy",
+    ];
+
+    /// The command's `cmd` tag.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Pause => "pause",
+            Self::Resume => "resume",
+            Self::SetSpeed { .. } => "set_speed",
+            Self::CloseComplaint { .. } => "close_complaint",
+            Self::SetProductFee { .. } => "set_product_fee",
+            Self::SetRiskDial { .. } => "set_risk_dial",
+            Self::OpenBranch { .. } => "open_branch",
+            Self::CloseBranch { .. } => "close_branch",
+            Self::RespondFairLendingFinding { .. } => "respond_fair_lending_finding",
+            Self::LaunchOffer { .. } => "launch_offer",
+            Self::ClaimNetworkRecovery { .. } => "claim_network_recovery",
+            Self::SetAchOriginationLimits { .. } => "set_ach_origination_limits",
+            Self::SetLinkVerificationStrictness { .. } => "set_link_verification_strictness",
+            Self::SetCustomerLimit { .. } => "set_customer_limit",
+            Self::SetSarThreshold { .. } => "set_sar_threshold",
+            Self::OpenTuningReview { .. } => "open_tuning_review",
+            Self::TuneMonitoringRule { .. } => "tune_monitoring_rule",
+            Self::SetComplaintSlaPolicy { .. } => "set_complaint_sla_policy",
+            Self::SetContactPolicy { .. } => "set_contact_policy",
+            Self::SetCreditHealthFloor { .. } => "set_credit_health_floor",
+            Self::SetVisaRestrictionPolicy { .. } => "set_visa_restriction_policy",
+            Self::SetDepositRatePremium { .. } => "set_deposit_rate_premium",
+            Self::DrawEmergencyLiquidity { .. } => "draw_emergency_liquidity",
+            Self::RecallWire { .. } => "recall_wire",
+            Self::SetDepositHoldPolicy { .. } => "set_deposit_hold_policy",
+            Self::SignOffCloseItem { .. } => "sign_off_close_item",
+            Self::PostCloseAdjustment { .. } => "post_close_adjustment",
+            Self::SetStaffing { .. } => "set_staffing",
+            Self::AssignAlert { .. } => "assign_alert",
+            Self::CloseAlert { .. } => "close_alert",
+            Self::EscalateAlertToCase { .. } => "escalate_alert_to_case",
+            Self::AssignCase { .. } => "assign_case",
+            Self::CloseCase { .. } => "close_case",
+            Self::SetSanctionsSensitivity { .. } => "set_sanctions_sensitivity",
+            Self::SetAppReimbursementPolicy { .. } => "set_app_reimbursement_policy",
+        }
+    }
 }
 
 /// A queued player command with its submission tick.
@@ -0,0 +1,537 @@
+//! Typed results for IPC commands (Phase 4.45).
+//!
+//! Every `command` message is answered with a `CommandResult` saying
+//! whether it was accepted. A rejected command changed nothing and says
+//! why: a code and one message per bad field. An accepted one says what
+//! it was logged as, which entities it touched, and the state after it.
+//!
+//! `resolve_complaint` closes a complaint on the spot, and `bulk_resolve`
+//! every open complaint a filter matches (see `bulk_resolution`). Any
+//! other `cmd` is a `PlayerCommand` tag, with the payload as its fields, and is queued
+//! for the next tick. Checks here are the ones a form could make: fields
+//! present and typed, values from the known set and in range, the
+//! complaint or alert still open. Then the sender's role must be senior enough
+//! (see `authorization`). Rules that depend on the run's state, such as
+//! a fee's regulatory ceiling or a dial's risk appetite, stay with the
+//! owning subsystem, which logs a `*_rejected` event when the command
+//! lands.
+
+use crate::{
+    alert_triage, app_fraud, case_subsystem,
+    authorization::Role,
+    bulk_resolution::{BulkResolution, ComplaintFilter},
+    command::PlayerCommand,
+    complaint_sla, customer_limits, deposit_holds,
+    engine::SimEngine,
+    error::{SimError, SimResult},
+    config::{RefundRule, ResolutionTemplate},
+    fair_lending_subsystem, pricing_subsystem,
+    protocol::UiState,
+    quarter_close, rule_tuning, visa,
+};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Map, Value};
+
+/// Why a command was rejected.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum RejectionCode {
+    /// No command has that name.
+    UnknownCommand,
+    /// The payload is not an object, or a field is missing or mistyped.
+    InvalidPayload,
+    /// A value is out of range or not one of the known options.
+    ValidationFailed,
+    /// The command names an entity the run does not have, or one that is
+    /// already closed.
+    NotFound,
+    /// The sender's role is below what the command needs.
+    Unauthorized,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct ValidationError {
+    /// Payload field at fault; "payload" or "cmd" when it is the message
+    /// as a whole.
+    pub field: String,
+    pub message: String,
+}
+
+impl ValidationError {
+    fn new(field: &str, message: impl Into<String>) -> Self {
+        Self {
+            field: field.to_string(),
+            message: message.into(),
+        }
+    }
+}
+
+/// sim-runner → client after `command`.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct CommandResult {
+    pub command: String,
+    pub accepted: bool,
+    /// Set when rejected.
+    pub code: Option<RejectionCode>,
+    /// Empty when accepted.
+    pub errors: Vec<ValidationError>,
+    /// Log id of a queued command, which applies on the next tick. None
+    /// for `resolve_complaint` and `bulk_resolve`, which apply at once,
+    /// and rejections.
+    pub command_id: Option<String>,
+    /// Complaints, products, branches and the like the command names.
+    pub entity_ids: Vec<String>,
+    /// What an accepted `bulk_resolve` closed and skipped.
+    pub bulk: Option<BulkResolution>,
+    /// State after the command; None when rejected.
+    pub state: Option<UiState>,
+}
+
+impl CommandResult {
+    /// Check and apply one command sent by `role`, or the run's default
+    /// role when None. Rejections are an `Ok` result with `accepted`
+    /// false; `Err` is left for the engine failing.
+    pub fn apply(engine: &mut SimEngine, cmd: &str, payload: &Value, role: Option<Role>) -> SimResult<Self> {
+        let fields = match payload {
+            Value::Object(fields) => fields.clone(),
+            Value::Null => Map::new(),
+            _ => {
+                let error = ValidationError::new("payload", "must be an object");
+                return Ok(Self::rejected(cmd, RejectionCode::InvalidPayload, vec![error]));
+            }
+        };
+        if cmd == "resolve_complaint" {
+            return resolve_complaint(engine, fields, payload, role);
+        }
+        if cmd == "bulk_resolve" {
+            return bulk_resolve(engine, fields, role);
+        }
+        if !PlayerCommand::NAMES.contains(&cmd) {
+            let error = ValidationError::new("cmd", format!("no command '{cmd}'"));
+            return Ok(Self::rejected(cmd, RejectionCode::UnknownCommand, vec![error]));
+        }
+
+        let mut fields = fields;
+        fields.insert("cmd".into(), cmd.into());
+        let command: PlayerCommand = match serde_json::from_value(Value::Object(fields)) {
+            Ok(command) => command,
+            Err(e) => return Ok(Self::rejected(cmd, RejectionCode::InvalidPayload, vec![payload_error(&e)])),
+        };
+        let errors = field_errors(&command);
+        if !errors.is_empty() {
+            return Ok(Self::rejected(cmd, RejectionCode::ValidationFailed, errors));
+        }
+        if let PlayerCommand::CloseComplaint { complaint_id, resolution_code } = &command {
+            if let Some(rejected) = check_complaint(engine, cmd, complaint_id, "resolution_code", resolution_code)? {
+                return Ok(rejected);
+            }
+        }
+        if let PlayerCommand::AssignAlert { alert_id, .. }
+        | PlayerCommand::CloseAlert { alert_id, .. }
+        | PlayerCommand::EscalateAlertToCase { alert_id, .. } = &command
+        {
+            if let Some(rejected) = check_alert(engine, cmd, alert_id)? {
+                return Ok(rejected);
+            }
+        }
+        if let PlayerCommand::AssignCase { case_id, .. } | PlayerCommand::CloseCase { case_id, .. } = &command {
+            if let Some(rejected) = check_case(engine, cmd, case_id)? {
+                return Ok(rejected);
+            }
+        }
+        if let Some(refused) = authorize(engine, cmd, payload, role)? {
+            return Ok(refused);
+        }
+
+        let entity_ids = entity_ids(&command);
+        let command_id = engine.submit_command(command)?;
+        Ok(Self::accepted(cmd, Some(command_id), entity_ids))
+    }
+
+    /// Attach the state after an accepted command.
+    pub fn with_state(mut self, engine: &SimEngine, summary_only: bool) -> SimResult<Self> {
+        if self.accepted {
+            self.state = Some(UiState::build(engine, summary_only)?);
+        }
+        Ok(self)
+    }
+
+    /// A rejection as a recoverable `SimError::Command`, for callers that
+    /// answer with a state or an error.
+    pub fn into_result(self) -> SimResult<Self> {
+        if self.accepted {
+            return Ok(self);
+        }
+        let reason = self
+            .errors
+            .iter()
+            .map(|e| format!("{}: {}", e.field, e.message))
+            .collect::<Vec<_>>()
+            .join("; ");
+        Err(SimError::command(self.command, reason))
+    }
+
+    fn accepted(cmd: &str, command_id: Option<String>, entity_ids: Vec<String>) -> Self {
+        Self {
+            command: cmd.to_string(),
+            accepted: true,
+            code: None,
+            errors: Vec::new(),
+            command_id,
+            entity_ids,
+            bulk: None,
+            state: None,
+        }
+    }
+
+    fn rejected(cmd: &str, code: RejectionCode, errors: Vec<ValidationError>) -> Self {
+        Self {
+            command: cmd.to_string(),
+            accepted: false,
+            code: Some(code),
+            errors,
+            command_id: None,
+            entity_ids: Vec::new(),
+            bulk: None,
+            state: None,
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct ResolveComplaint {
+    complaint_id: String,
+    resolution: String,
+    refund: f64,
+}
+
+fn resolve_complaint(
+    engine: &mut SimEngine,
+    fields: Map<String, Value>,
+    payload: &Value,
+    role: Option<Role>,
+) -> SimResult<CommandResult> {
+    let cmd = "resolve_complaint";
+    let ResolveComplaint { complaint_id, resolution, refund } = match serde_json::from_value(Value::Object(fields)) {
+        Ok(payload) => payload,
+        Err(e) => return Ok(CommandResult::rejected(cmd, RejectionCode::InvalidPayload, vec![payload_error(&e)])),
+    };
+    if !refund.is_finite() || refund < 0.0 {
+        let error = ValidationError::new("refund", "must be zero or more");
+        return Ok(CommandResult::rejected(cmd, RejectionCode::ValidationFailed, vec![error]));
+    }
+    if let Some(rejected) = check_complaint(engine, cmd, &complaint_id, "resolution", &resolution)? {
+        return Ok(rejected);
+    }
+    if let Some(refused) = authorize(engine, cmd, payload, role)? {
+        return Ok(refused);
+    }
+    engine.store_close_complaint_direct(&engine.run_id, &complaint_id, engine.clock.current_tick, &resolution, refund)?;
+    Ok(CommandResult::accepted(cmd, None, vec![complaint_id]))
+}
+
+#[derive(Deserialize)]
+struct BulkResolve {
+    #[serde(default)]
+    filter: ComplaintFilter,
+    /// A configured template's id, or a template object.
+    template: Value,
+}
+
+fn bulk_resolve(engine: &SimEngine, fields: Map<String, Value>, role: Option<Role>) -> SimResult<CommandResult> {
+    let cmd = "bulk_resolve";
+    let invalid = |e: ValidationError| Ok(CommandResult::rejected(cmd, RejectionCode::InvalidPayload, vec![e]));
+    let BulkResolve { filter, template } = match serde_json::from_value(Value::Object(fields)) {
+        Ok(payload) => payload,
+        Err(e) => return invalid(payload_error(&e)),
+    };
+    let template = match template {
+        Value::String(template_id) => match engine.resolution_template(&template_id) {
+            Some(template) => template.clone(),
+            None => {
+                let error = ValidationError::new("template", format!("no template '{template_id}'"));
+                return Ok(CommandResult::rejected(cmd, RejectionCode::ValidationFailed, vec![error]));
+            }
+        },
+        inline => match serde_json::from_value::<ResolutionTemplate>(inline) {
+            Ok(template) => template,
+            Err(e) => return invalid(ValidationError::new("template", e.to_string())),
+        },
+    };
+    let errors = bulk_errors(engine, &filter, &template);
+    if !errors.is_empty() {
+        return Ok(CommandResult::rejected(cmd, RejectionCode::ValidationFailed, errors));
+    }
+
+    let plan = BulkResolution::plan(engine, &filter, &template)?;
+    if let Some(refused) = authorize(engine, cmd, &json!({ "refund": plan.largest_refund() }), role)? {
+        return Ok(refused);
+    }
+    plan.apply(engine)?;
+    let entity_ids = plan.applied.iter().map(|a| a.complaint_id.clone()).collect();
+    let mut result = CommandResult::accepted(cmd, None, entity_ids);
+    result.bulk = Some(plan);
+    Ok(result)
+}
+
+fn bulk_errors(engine: &SimEngine, filter: &ComplaintFilter, template: &ResolutionTemplate) -> Vec<ValidationError> {
+    let mut errors = Vec::new();
+    if !engine.has_resolution_code(&template.resolution_code) {
+        let message = format!("unknown resolution code '{}'", template.resolution_code);
+        errors.push(ValidationError::new("template.resolution_code", message));
+    }
+    let amount = match template.refund {
+        RefundRule::Nothing => 0.0,
+        RefundRule::Fixed { amount } => amount,
+        RefundRule::FeeUpTo { max } => max,
+    };
+    if !amount.is_finite() || amount < 0.0 {
+        errors.push(ValidationError::new("template.refund", "must be zero or more"));
+    }
+    if filter.priority.as_deref().is_some_and(|p| !complaint_sla::PRIORITIES.contains(&p)) {
+        errors.push(ValidationError::new("filter.priority", "unknown priority"));
+    }
+    if let (Some(min), Some(max)) = (filter.min_age_ticks, filter.max_age_ticks) {
+        if min > max {
+            errors.push(ValidationError::new("filter.min_age_ticks", "must not exceed max_age_ticks"));
+        }
+    }
+    errors
+}
+
+/// A rejection unless the resolution code is configured and the
+/// complaint is open.
+fn check_complaint(
+    engine: &SimEngine,
+    cmd: &str,
+    complaint_id: &str,
+    resolution_field: &str,
+    resolution: &str,
+) -> SimResult<Option<CommandResult>> {
+    if !engine.has_resolution_code(resolution) {
+        let error = ValidationError::new(resolution_field, format!("unknown resolution code '{resolution}'"));
+        return Ok(Some(CommandResult::rejected(cmd, RejectionCode::ValidationFailed, vec![error])));
+    }
+    let reason = match engine.store.get_complaint(&engine.run_id, complaint_id) {
+        Ok(complaint) if complaint.status == "open" => return Ok(None),
+        Ok(complaint) => format!("complaint {complaint_id} is {}", complaint.status),
+        Err(SimError::Store(rusqlite::Error::QueryReturnedNoRows)) => format!("no complaint {complaint_id}"),
+        Err(e) => return Err(e),
+    };
+    let error = ValidationError::new("complaint_id", reason);
+    Ok(Some(CommandResult::rejected(cmd, RejectionCode::NotFound, vec![error])))
+}
+
+/// A rejection unless the alert is open or under investigation.
+fn check_alert(engine: &SimEngine, cmd: &str, alert_id: &str) -> SimResult<Option<CommandResult>> {
+    let alert = engine.store.aml_alert(&engine.run_id, alert_id)?;
+    Ok(alert_triage::workable(alert, alert_id).err().map(|reason| {
+        CommandResult::rejected(cmd, RejectionCode::NotFound, vec![ValidationError::new("alert_id", reason)])
+    }))
+}
+
+/// A rejection unless the investigation case is open.
+fn check_case(engine: &SimEngine, cmd: &str, case_id: &str) -> SimResult<Option<CommandResult>> {
+    let case = engine.store.investigation_case(&engine.run_id, case_id)?;
+    Ok(case_subsystem::workable(case, case_id).err().map(|reason| {
+        CommandResult::rejected(cmd, RejectionCode::NotFound, vec![ValidationError::new("case_id", reason)])
+    }))
+}
+
+/// A refusal when the run gates commands by role and `role` falls short.
+fn authorize(engine: &SimEngine, cmd: &str, payload: &Value, role: Option<Role>) -> SimResult<Option<CommandResult>> {
+    let Some(authorizer) = engine.authorizer() else {
+        return Ok(None);
+    };
+    let refused = authorizer.check(&engine.store, cmd, payload, role, engine.clock.current_tick)?;
+    Ok(refused.map(|required| {
+        let message = format!("needs {}: {}", required.role, required.reason);
+        CommandResult::rejected(cmd, RejectionCode::Unauthorized, vec![ValidationError::new("role", message)])
+    }))
+}
+
+/// Name the field serde stopped at when it can tell.
+fn payload_error(e: &serde_json::Error) -> ValidationError {
+    let message = e.to_string();
+    let field = message
+        .strip_prefix("missing field `")
+        .and_then(|rest| rest.split('`').next())
+        .unwrap_or("payload");
+    ValidationError::new(field, message.clone())
+}
+
+/// Values outside what the command's owner would ever accept.
+fn field_errors(command: &PlayerCommand) -> Vec<ValidationError> {
+    let mut errors = Vec::new();
+    let mut check = |ok: bool, field: &str, message: &str| {
+        if !ok {
+            errors.push(ValidationError::new(field, message));
+        }
+    };
+    let one_of = |value: &str, options: &[&str]| options.contains(&value);
+    let unit = |value: f64| (0.0..=1.0).contains(&value);
+    let non_negative = |value: f64| value.is_finite() && value >= 0.0;
+
+    match command {
+        PlayerCommand::Pause | PlayerCommand::Resume | PlayerCommand::SetSpeed { .. } => {}
+        PlayerCommand::CloseComplaint { complaint_id, .. } => {
+            check(!complaint_id.is_empty(), "complaint_id", "is required");
+        }
+        PlayerCommand::SetProductFee { product_id, fee_type, new_value } => {
+            check(!product_id.is_empty(), "product_id", "is required");
+            check(one_of(fee_type, pricing_subsystem::FEE_TYPES), "fee_type", "unknown fee type");
+            check(non_negative(*new_value), "new_value", "must be zero or more");
+        }
+        PlayerCommand::SetRiskDial { dial_id, new_value } => {
+            check(!dial_id.is_empty(), "dial_id", "is required");
+            check(new_value.is_finite(), "new_value", "must be a number");
+        }
+        PlayerCommand::OpenBranch { state_code } => {
+            check(!state_code.is_empty(), "state_code", "is required");
+        }
+        PlayerCommand::CloseBranch { branch_id } => {
+            check(!branch_id.is_empty(), "branch_id", "is required");
+        }
+        PlayerCommand::RespondFairLendingFinding { finding_id, response } => {
+            check(!finding_id.is_empty(), "finding_id", "is required");
+            check(one_of(response, fair_lending_subsystem::RESPONSES), "response", "must be remediate or justify");
+        }
+        PlayerCommand::LaunchOffer { offer_id, .. } => {
+            check(!offer_id.is_empty(), "offer_id", "is required");
+        }
+        PlayerCommand::ClaimNetworkRecovery { invoice_id } => {
+            check(!invoice_id.is_empty(), "invoice_id", "is required");
+        }
+        PlayerCommand::SetAchOriginationLimits { daily_amount, .. } => {
+            check(non_negative(*daily_amount), "daily_amount", "must be zero or more");
+        }
+        PlayerCommand::SetLinkVerificationStrictness { strictness } => {
+            check(unit(*strictness), "strictness", "must be between 0 and 1");
+        }
+        PlayerCommand::SetCustomerLimit { limit_type, scope, amount } => {
+            check(one_of(limit_type, customer_limits::LIMIT_TYPES), "limit_type", "unknown limit type");
+            check(
+                customer_limits::valid_scope(scope),
+                "scope",
+                "must be global, tier:<tier> or product:<product_id>",
+            );
+            check(non_negative(*amount), "amount", "must be zero or more");
+        }
+        PlayerCommand::SetSarThreshold { threshold } => {
+            check((0.0..=100.0).contains(threshold), "threshold", "must be between 0 and 100");
+        }
+        PlayerCommand::OpenTuningReview { rule_id, rationale } => {
+            check(one_of(rule_id, rule_tuning::TUNABLE_RULES), "rule_id", "not a tunable rule");
+            check(!rationale.trim().is_empty(), "rationale", "is required");
+        }
+        PlayerCommand::TuneMonitoringRule { rule_id, change, threshold_multiplier, customer_id, .. } => {
+            check(one_of(rule_id, rule_tuning::TUNABLE_RULES), "rule_id", "not a tunable rule");
+            check(one_of(change, rule_tuning::CHANGES), "change", "unknown change");
+            if change == "raise_threshold" {
+                check(*threshold_multiplier >= 1.0, "threshold_multiplier", "must be at least 1");
+            }
+            if change == "exclude_customer" {
+                check(customer_id.is_some(), "customer_id", "is required to exclude a customer");
+            }
+        }
+        PlayerCommand::SetComplaintSlaPolicy { priority, channel, resolve_days, .. } => {
+            let known = |value: &Option<String>, options| value.as_deref().is_none_or(|v| one_of(v, options));
+            check(known(priority, complaint_sla::PRIORITIES), "priority", "unknown priority");
+            check(known(channel, complaint_sla::CHANNELS), "channel", "unknown channel");
+            check(*resolve_days >= 1, "resolve_days", "must be at least 1");
+        }
+        PlayerCommand::SetContactPolicy { max_contacts_per_window, .. } => {
+            check(*max_contacts_per_window != Some(0), "max_contacts_per_window", "cap must allow a contact");
+        }
+        PlayerCommand::SetCreditHealthFloor { min_score } => {
+            check(unit(*min_score), "min_score", "must be between 0 and 1");
+        }
+        PlayerCommand::SetVisaRestrictionPolicy { level, .. } => {
+            check(one_of(level, visa::LEVELS), "level", "must be none, outbound or full");
+        }
+        PlayerCommand::SetDepositRatePremium { premium } => {
+            check(unit(*premium), "premium", "must be between 0 and 1");
+        }
+        PlayerCommand::DrawEmergencyLiquidity { amount } => {
+            check(amount.is_finite() && *amount > 0.0, "amount", "must be more than zero");
+        }
+        PlayerCommand::RecallWire { txn_id } => {
+            check(!txn_id.is_empty(), "txn_id", "is required");
+        }
+        PlayerCommand::SetDepositHoldPolicy { class, risk, .. } => {
+            let known = |value: &Option<String>, options| value.as_deref().is_none_or(|v| one_of(v, options));
+            check(known(class, deposit_holds::CLASSES), "class", "unknown class");
+            check(known(risk, deposit_holds::RISKS), "risk", "unknown risk");
+        }
+        PlayerCommand::SignOffCloseItem { period, item } => {
+            check(!period.is_empty(), "period", "is required");
+            check(one_of(item, quarter_close::CHECKLIST), "item", "not a close checklist item");
+        }
+        PlayerCommand::PostCloseAdjustment { period, amount, reason } => {
+            check(!period.is_empty(), "period", "is required");
+            check(amount.is_finite() && *amount != 0.0, "amount", "must be a non-zero amount");
+            check(!reason.trim().is_empty(), "reason", "is required");
+        }
+        PlayerCommand::SetStaffing { ftes, .. } => {
+            check(non_negative(*ftes), "ftes", "must be zero or more");
+        }
+        PlayerCommand::AssignAlert { alert_id, analyst, .. } => {
+            check(!alert_id.is_empty(), "alert_id", "is required");
+            check(!analyst.trim().is_empty(), "analyst", "is required");
+        }
+        PlayerCommand::CloseAlert { alert_id, disposition, note } => {
+            check(!alert_id.is_empty(), "alert_id", "is required");
+            check(
+                one_of(disposition, alert_triage::DISPOSITIONS),
+                "disposition",
+                "must be false_positive, suspicious or sar_filed",
+            );
+            check(!note.trim().is_empty(), "note", "is required");
+        }
+        PlayerCommand::EscalateAlertToCase { alert_id, note } => {
+            check(!alert_id.is_empty(), "alert_id", "is required");
+            check(!note.trim().is_empty(), "note", "is required");
+        }
+        PlayerCommand::AssignCase { case_id, analyst, .. } => {
+            check(!case_id.is_empty(), "case_id", "is required");
+            check(!analyst.trim().is_empty(), "analyst", "is required");
+        }
+        PlayerCommand::CloseCase { case_id, disposition, note } => {
+            check(!case_id.is_empty(), "case_id", "is required");
+            check(one_of(disposition, case_subsystem::DISPOSITIONS), "disposition", "must be unfounded or confirmed");
+            check(!note.trim().is_empty(), "note", "is required");
+        }
+        PlayerCommand::SetSanctionsSensitivity { sensitivity } => {
+            check(unit(*sensitivity), "sensitivity", "must be between 0 and 1");
+        }
+        PlayerCommand::SetAppReimbursementPolicy { policy } => {
+            check(one_of(policy, app_fraud::POLICIES), "policy", "must be none, vulnerable or full");
+        }
+    }
+    errors
+}
+
+/// The ids of the entities a command acts on.
+fn entity_ids(command: &PlayerCommand) -> Vec<String> {
+    let ids: Vec<&String> = match command {
+        PlayerCommand::CloseComplaint { complaint_id, .. } => vec![complaint_id],
+        PlayerCommand::SetProductFee { product_id, .. } => vec![product_id],
+        PlayerCommand::SetRiskDial { dial_id, .. } => vec![dial_id],
+        PlayerCommand::CloseBranch { branch_id } => vec![branch_id],
+        PlayerCommand::RespondFairLendingFinding { finding_id, .. } => vec![finding_id],
+        PlayerCommand::LaunchOffer { offer_id, .. } => vec![offer_id],
+        PlayerCommand::ClaimNetworkRecovery { invoice_id } => vec![invoice_id],
+        PlayerCommand::RecallWire { txn_id } => vec![txn_id],
+        PlayerCommand::AssignAlert { alert_id, .. }
+        | PlayerCommand::CloseAlert { alert_id, .. }
+        | PlayerCommand::EscalateAlertToCase { alert_id, .. } => vec![alert_id],
+        PlayerCommand::AssignCase { case_id, .. } | PlayerCommand::CloseCase { case_id, .. } => vec![case_id],
+        PlayerCommand::OpenTuningReview { rule_id, .. } => vec![rule_id],
+        PlayerCommand::TuneMonitoringRule { rule_id, customer_id, review_id, .. } => {
+            [Some(rule_id), customer_id.as_ref(), review_id.as_ref()].into_iter().flatten().collect()
+        }
+        _ => Vec::new(),
+    };
+    ids.into_iter().cloned().collect()
+}
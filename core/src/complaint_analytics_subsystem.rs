@@ -15,9 +15,10 @@ use crate::{
     config::SimConfig,
     error::SimResult,
     event::SimEvent,
+    queue_aging::QueueAging,
     rng::SubsystemRng,
     store::SimStore,
-    subsystem::SimSubsystem,
+    subsystem::{SimSubsystem, SubsystemManifest},
     types::{RunId, Tick},
 };
 use serde::{Deserialize, Serialize};
@@ -475,15 +476,49 @@ impl ComplaintAnalyticsSubsystem {
             }
         }
 
+        // Warning 3: Aged backlog growing week on week, on any desk
+        if let Some(threshold) = ew_config.aged_backlog_growth_warning {
+            let aging = QueueAging::query(&self.store, &self.run_id, tick)?;
+            for desk in aging.desks {
+                let growth = desk.aged.saturating_sub(desk.aged_week_ago);
+                if growth >= threshold {
+                    alerts.push(EarlyWarningAlert {
+                        alert_type: "aged_backlog_growth".into(),
+                        severity: if growth >= 2 * threshold { "high" } else { "medium" }.into(),
+                        segment: None,
+                        metric_name: format!("aged_backlog_{}", desk.desk.as_str()),
+                        current_value: desk.aged as f64,
+                        threshold_value: threshold as f64,
+                        delta_pct: growth as f64 / desk.aged_week_ago.max(1) as f64 * 100.0,
+                    });
+                }
+            }
+        }
+
         Ok(alerts)
     }
 }
 
+const MANIFEST: SubsystemManifest = SubsystemManifest {
+    version: 1,
+    produces: &["complaint_warning_fired"],
+    consumes: &[],
+    tables: &[
+        "complaint_pattern", "complaint_root_cause", "early_warning_alert", "repeat_complainer",
+        "resolution_effectiveness", "sla_performance_snapshot",
+    ],
+    requires: &["complaint"],
+};
+
 impl SimSubsystem for ComplaintAnalyticsSubsystem {
     fn name(&self) -> &'static str {
         "complaint_analytics"
     }
 
+    fn manifest(&self) -> SubsystemManifest {
+        MANIFEST
+    }
+
     fn update(
         &mut self,
         tick: Tick,
@@ -501,8 +536,8 @@ impl SimSubsystem for ComplaintAnalyticsSubsystem {
         for pattern in patterns {
             self.store
                 .insert_complaint_pattern(&self.run_id, tick, &pattern)?;
-            log::info!(
-                "tick={tick} analytics: detected {} in {} (severity={:.2})",
+            tracing::info!(
+                "detected {} in {} (severity={:.2})",
                 pattern.pattern_type,
                 pattern.issue_category,
                 pattern.severity_score,
@@ -544,8 +579,8 @@ impl SimSubsystem for ComplaintAnalyticsSubsystem {
                 segment: alert.segment.clone(),
             });
 
-            log::warn!(
-                "tick={tick} WARNING: {} ({}) - {} = {:.2} (threshold {:.2})",
+            tracing::warn!(
+                "WARNING: {} ({}) - {} = {:.2} (threshold {:.2})",
                 alert.alert_type,
                 alert.severity,
                 alert.metric_name,
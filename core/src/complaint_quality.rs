@@ -0,0 +1,149 @@
+//! Complaint response quality (Phase 4.65).
+//!
+//! Closing a complaint is not the end of it. The tick after each closure
+//! the desk's response is scored for quality, between 0 and 1, from:
+//!
+//! - the skill of the analysts working the desk,
+//! - the time spent on it, against the ticks a thorough response takes,
+//! - whether the resolution suits the issue: an explanation is no answer
+//!   to an unauthorized transaction.
+//!
+//! The worse the response, the likelier the customer comes back. They
+//! may go to the regulator, which sends the complaint back on its own
+//! channel and weighs on the UDAAP score. Failing that, they may reopen
+//! it with the bank. Either way it returns to the queue as if newly
+//! opened, with an SLA as long as the first, and is scored again when
+//! next closed. Every reporting period a desk
+//! performance report sums up the closures scored, with the trend in
+//! their quality from the last report.
+
+use crate::{
+    complaint_subsystem::ComplaintRecord,
+    config::ComplaintQualityConfig,
+    error::SimResult,
+    event::SimEvent,
+    rng::SubsystemRng,
+    store::{
+        complaint_quality::{ComplaintQualityRow, DeskPerformanceRow},
+        SimStore,
+    },
+    types::{RunId, Tick},
+};
+
+pub struct ComplaintQuality {
+    run_id: RunId,
+    config: ComplaintQualityConfig,
+}
+
+impl ComplaintQuality {
+    pub fn new(run_id: RunId, config: ComplaintQualityConfig) -> Self {
+        Self { run_id, config }
+    }
+
+    pub fn enabled(&self) -> bool {
+        self.config.enabled
+    }
+
+    /// Score every closure not yet scored, then see whether the customer
+    /// accepts it.
+    pub fn review(&self, store: &SimStore, tick: Tick, rng: &mut SubsystemRng) -> SimResult<Vec<SimEvent>> {
+        let mut events = Vec::new();
+        for complaint in store.unscored_complaint_closures(&self.run_id)? {
+            let mut row = self.score(&complaint, tick);
+            let shortfall = 1.0 - row.quality;
+            if rng.chance(self.config.max_escalation_probability * shortfall) {
+                row.outcome = "escalated".into();
+                self.reopen(store, &complaint, tick, "regulator")?;
+                store.adjust_udaap_score(&self.run_id, self.config.escalation_udaap_impact, tick)?;
+                tracing::warn!("{} escalated to the regulator (quality {:.2})", complaint.complaint_id, row.quality);
+                events.push(SimEvent::ComplaintEscalatedToRegulator {
+                    tick,
+                    complaint_id: complaint.complaint_id.clone(),
+                    customer_id: complaint.customer_id.clone(),
+                    quality: row.quality,
+                });
+            } else if rng.chance(self.config.max_reopen_probability * shortfall) {
+                row.outcome = "reopened".into();
+                self.reopen(store, &complaint, tick, &complaint.channel)?;
+                tracing::debug!("{} reopened (quality {:.2})", complaint.complaint_id, row.quality);
+                events.push(SimEvent::ComplaintReopened {
+                    tick,
+                    complaint_id: complaint.complaint_id.clone(),
+                    customer_id: complaint.customer_id.clone(),
+                    quality: row.quality,
+                });
+            }
+            store.insert_complaint_quality(&self.run_id, &row)?;
+        }
+        Ok(events)
+    }
+
+    /// Sum up the closures scored since the last report, if one is due.
+    pub fn report(&self, store: &SimStore, tick: Tick) -> SimResult<Vec<SimEvent>> {
+        if tick == 0 || !tick.is_multiple_of(self.config.report_interval_ticks) {
+            return Ok(Vec::new());
+        }
+        let start = tick.saturating_sub(self.config.report_interval_ticks) + 1;
+        let scored = store.complaint_quality_scored(&self.run_id, start, tick)?;
+        let count = |outcome: &str| scored.iter().filter(|r| r.outcome == outcome).count() as i64;
+        let mean = |total: f64| (!scored.is_empty()).then(|| total / scored.len() as f64);
+        let avg_quality = mean(scored.iter().fold(0.0, |sum, r| sum + r.quality));
+        let previous = store.latest_desk_performance_report(&self.run_id)?.and_then(|r| r.avg_quality);
+        let row = DeskPerformanceRow {
+            tick,
+            closures: scored.len() as i64,
+            avg_quality,
+            quality_trend: avg_quality.zip(previous).map(|(now, before)| now - before),
+            poor_quality_count: scored.iter().filter(|r| r.quality < self.config.poor_quality_threshold).count()
+                as i64,
+            reopened_count: count("reopened"),
+            escalated_count: count("escalated"),
+            avg_handling_ticks: mean(scored.iter().fold(0.0, |sum, r| sum + r.handling_ticks as f64)),
+        };
+        store.insert_desk_performance_report(&self.run_id, &row)?;
+        tracing::debug!("desk performance: {} closures, quality trend {:?}", row.closures, row.quality_trend);
+        Ok(vec![SimEvent::DeskPerformanceReported {
+            tick,
+            closures: row.closures,
+            avg_quality: row.avg_quality,
+            quality_trend: row.quality_trend,
+            reopened: row.reopened_count,
+            escalated: row.escalated_count,
+        }])
+    }
+
+    /// One closure's quality.
+    fn score(&self, complaint: &ComplaintRecord, tick: Tick) -> ComplaintQualityRow {
+        let c = &self.config;
+        let tick_closed = complaint.tick_closed.unwrap_or(tick);
+        let handling_ticks = tick_closed.saturating_sub(complaint.tick_opened);
+        let time_score = (handling_ticks as f64 / c.thorough_handling_ticks.max(1) as f64).min(1.0);
+        let resolution_code = complaint.resolution_code.clone().unwrap_or_default();
+        let appropriate = c
+            .appropriate_resolutions
+            .get(&complaint.issue)
+            .is_none_or(|codes| codes.contains(&resolution_code));
+        let quality = (c.skill_weight * c.analyst_skill
+            + c.time_weight * time_score
+            + c.appropriateness_weight * if appropriate { 1.0 } else { 0.0 })
+        .clamp(0.0, 1.0);
+        ComplaintQualityRow {
+            complaint_id: complaint.complaint_id.clone(),
+            tick_closed,
+            scored_tick: tick,
+            resolution_code,
+            analyst_skill: c.analyst_skill,
+            handling_ticks,
+            time_score,
+            appropriate,
+            quality,
+            outcome: "stands".into(),
+        }
+    }
+
+    /// Back in the queue from `tick`, with an SLA as long as the last.
+    fn reopen(&self, store: &SimStore, complaint: &ComplaintRecord, tick: Tick, channel: &str) -> SimResult<()> {
+        let window = complaint.sla_due_tick.saturating_sub(complaint.tick_opened);
+        store.reopen_complaint(&self.run_id, &complaint.complaint_id, tick, tick + window, channel)
+    }
+}
@@ -0,0 +1,100 @@
+//! Complaint SLA policy (Phase 4.29).
+//!
+//! A complaint's resolve deadline is fixed when it opens, from the policy
+//! in force that tick: the most specific rule matching its priority,
+//! product and channel, or the trigger's own deadline when none does.
+//! Regulatory limits in scope then cap it. The player can schedule policy
+//! changes from an effective tick, but never one that a regulatory limit
+//! covers and it would exceed.
+
+use crate::config::{ComplaintSlaConfig, SlaPolicyRule};
+use crate::types::Tick;
+
+pub const PRIORITIES: &[&str] = &["low", "standard", "high", "urgent"];
+pub const CHANNELS: &[&str] = &["phone", "branch", "online", "regulator"];
+
+impl SlaPolicyRule {
+    fn matches(&self, priority: &str, product: &str, channel: &str) -> bool {
+        self.priority.as_deref().is_none_or(|p| p == priority)
+            && self.product.as_deref().is_none_or(|p| p == product)
+            && self.channel.as_deref().is_none_or(|c| c == channel)
+    }
+
+    /// Whether every complaint `other` applies to is also in this scope.
+    fn covers(&self, other: &SlaPolicyRule) -> bool {
+        fn field(mine: &Option<String>, theirs: &Option<String>) -> bool {
+            mine.is_none() || mine == theirs
+        }
+        field(&self.priority, &other.priority)
+            && field(&self.product, &other.product)
+            && field(&self.channel, &other.channel)
+    }
+
+    fn specificity(&self) -> usize {
+        [&self.priority, &self.product, &self.channel]
+            .iter()
+            .filter(|f| f.is_some())
+            .count()
+    }
+}
+
+/// Configured policy plus the player's scheduled changes.
+pub struct SlaPolicies {
+    /// (effective tick, rule); configured rules are effective from tick 0.
+    rules: Vec<(Tick, SlaPolicyRule)>,
+    /// How many of `rules` came from config; the player's follow.
+    configured: usize,
+    regulatory_limits: Vec<SlaPolicyRule>,
+}
+
+impl SlaPolicies {
+    pub fn new(config: &ComplaintSlaConfig) -> Self {
+        Self {
+            rules: config.policies.iter().map(|r| (0, r.clone())).collect(),
+            configured: config.policies.len(),
+            regulatory_limits: config.regulatory_limits.clone(),
+        }
+    }
+
+    /// Add a rule that applies to complaints opened from `effective_tick`.
+    pub fn schedule(&mut self, rule: SlaPolicyRule, effective_tick: Tick) {
+        self.rules.push((effective_tick, rule));
+    }
+
+    /// The player's scheduled rules, in the order they were set.
+    pub fn scheduled(&self) -> &[(Tick, SlaPolicyRule)] {
+        &self.rules[self.configured..]
+    }
+
+    /// Tightest regulatory limit covering the rule's whole scope, if any.
+    pub fn regulatory_cap(&self, rule: &SlaPolicyRule) -> Option<u64> {
+        self.regulatory_limits
+            .iter()
+            .filter(|limit| limit.covers(rule))
+            .map(|limit| limit.resolve_days)
+            .min()
+    }
+
+    /// Resolve days for a complaint opened at `tick`. The most specific
+    /// rule in force wins, the later effective tick breaking ties.
+    pub fn resolve_days(
+        &self,
+        priority: &str,
+        product: &str,
+        channel: &str,
+        tick: Tick,
+        trigger_days: u64,
+    ) -> u64 {
+        let policy_days = self
+            .rules
+            .iter()
+            .filter(|(effective, rule)| *effective <= tick && rule.matches(priority, product, channel))
+            .max_by_key(|(effective, rule)| (rule.specificity(), *effective))
+            .map_or(trigger_days, |(_, rule)| rule.resolve_days);
+        self.regulatory_limits
+            .iter()
+            .filter(|limit| limit.matches(priority, product, channel))
+            .map(|limit| limit.resolve_days)
+            .fold(policy_days, u64::min)
+    }
+}
@@ -1,24 +1,44 @@
 //! Complaint & Service subsystem — Phase 1C.
 //!
-//! Listens for FeeCharged and SLABreached events, generates complaints
-//! probabilistically, tracks SLA aging, and processes player resolutions.
+//! Listens for FeeCharged, SLABreached, PaymentRolledPastCutoff,
+//! unauthorized AchReturnReceived and ContactPreferenceViolated events,
+//! generates complaints probabilistically, tracks SLA aging, and
+//! processes player resolutions.
+//! Deadlines come from the SLA policy in force when a complaint opens
+//! (Phase 4.29), so a later policy change never moves an open one.
+//! A breach on an urgent complaint scars the customer's satisfaction
+//! baseline (Phase 4.33). Each closure is scored for quality, and a poor
+//! one may come back reopened or by way of the regulator (Phase 4.65).
+//! When the complaint desk is staffed, it closes as many as its capacity
+//! allows each tick, earliest SLA first (Phase 4.80).
+//! A transaction given up on because its channel was out can prompt a
+//! complaint about that channel (Phase 4.90). A scam victim whose
+//! reimbursement claim is declined may complain too (Phase 4.91).
 //!
 //! DESIGN RULE: Complaints are LEADING indicators. They fire BEFORE churn.
 //! A high complaint rate this quarter predicts high churn next quarter.
 
 use crate::{
-    config::{ComplaintTrigger, ResolutionCode, SimConfig},
-    error::SimResult,
+    calendar::BusinessCalendar,
+    case_documents::CaseDocuments,
+    channel::Channel,
+    command::PlayerCommand,
+    complaint_quality::ComplaintQuality,
+    complaint_sla::{self, SlaPolicies},
+    config::{ComplaintPropensity, ComplaintTrigger, ResolutionCode, SimConfig, SlaPolicyRule},
+    error::{SimError, SimResult},
     event::SimEvent,
+    queue::QueueKind,
     rng::SubsystemRng,
+    satisfaction::SatisfactionDynamics,
     store::SimStore,
-    subsystem::SimSubsystem,
+    subsystem::{SimSubsystem, SubsystemManifest},
     types::{RunId, Tick},
 };
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
 pub struct ComplaintRecord {
     pub complaint_id: String,
     pub customer_id: String,
@@ -34,19 +54,36 @@ pub struct ComplaintRecord {
     pub resolution_code: Option<String>,
     pub amount_refunded: f64,
     pub udaap_flag: bool,
+    /// "phone" | "branch" | "online" | "regulator" (Phase 4.29)
+    pub channel: String,
+    /// The service channel the complaint is about, where known (Phase
+    /// 4.90); `channel` is how it reached the bank.
+    #[serde(default)]
+    pub service_channel: Option<String>,
 }
 
 pub struct ComplaintSubsystem {
     run_id: RunId,
     store: SimStore,
     trigger_map: HashMap<String, Vec<ComplaintTrigger>>,
-    // Retained for Phase 1E player-command wiring.
-    #[allow(dead_code)]
+    /// Phase 4.51: scales each trigger's probability.
+    propensity: ComplaintPropensity,
+    sla_policies: SlaPolicies,
+    /// Phase 4.31: supporting documents attached at filing.
+    documents: CaseDocuments,
+    /// Phase 4.33: scars from breaches, diminishing goodwill.
+    satisfaction: SatisfactionDynamics,
+    /// Phase 4.41: SLAs count business days.
+    calendar: BusinessCalendar,
+    /// Phase 4.65: closure quality and the desk performance report.
+    quality: ComplaintQuality,
     resolution_codes: HashMap<String, ResolutionCode>,
+    /// Phase 4.80: resolution the staffed desk closes complaints under.
+    desk_resolution_code: String,
 }
 
 impl ComplaintSubsystem {
-    pub fn new(run_id: RunId, config: SimConfig, store: SimStore) -> Self {
+    pub fn new(run_id: RunId, config: SimConfig, store: SimStore) -> SimResult<Self> {
         let mut trigger_map: HashMap<String, Vec<ComplaintTrigger>> = HashMap::new();
         for trigger in config.complaint_triggers {
             trigger_map
@@ -54,28 +91,45 @@ impl ComplaintSubsystem {
                 .or_default()
                 .push(trigger);
         }
+        let sla_policies = SlaPolicies::new(&config.complaint_sla);
+        let documents = CaseDocuments::new(run_id.clone(), config.case_documents);
+        let satisfaction = SatisfactionDynamics::new(run_id.clone(), config.satisfaction_dynamics);
+        let calendar = BusinessCalendar::new(&config.calendar)?;
+        let quality = ComplaintQuality::new(run_id.clone(), config.complaint_quality);
         let resolution_codes = config.resolution_codes;
-        Self {
+        let desk_resolution_code = config.staffing.complaint_resolution_code;
+        Ok(Self {
             run_id,
             store,
             trigger_map,
+            propensity: config.complaint_propensity,
+            sla_policies,
+            documents,
+            satisfaction,
+            calendar,
+            quality,
             resolution_codes,
-        }
+            desk_resolution_code,
+        })
     }
 
     /// Returns a cloned trigger if a complaint should fire for this event.
     /// Returns owned (not reference) to avoid borrow conflicts in update().
+    /// `customer_factor` is the propensity for the customer's product and
+    /// segment.
     fn should_trigger_complaint(
         &self,
         event: &SimEvent,
+        customer_factor: f64,
         rng: &mut SubsystemRng,
     ) -> Option<ComplaintTrigger> {
+        let mut fires = |trigger: &ComplaintTrigger| rng.chance(self.propensity.chance(trigger, customer_factor));
         match event {
             SimEvent::FeeCharged { fee_type, .. } => {
                 let triggers = self.trigger_map.get("fee_charged")?;
                 for trigger in triggers {
                     if let Some(ref ft) = trigger.fee_type {
-                        if ft == fee_type && rng.chance(trigger.probability) {
+                        if ft == fee_type && fires(trigger) {
                             return Some(trigger.clone());
                         }
                     }
@@ -85,22 +139,155 @@ impl ComplaintSubsystem {
             SimEvent::SLABreached { .. } => {
                 let triggers = self.trigger_map.get("sla_breach")?;
                 for trigger in triggers {
-                    if trigger.prior_breach && rng.chance(trigger.probability) {
+                    if trigger.prior_breach && fires(trigger) {
                         return Some(trigger.clone());
                     }
                 }
                 None
             }
+            SimEvent::PaymentRolledPastCutoff { .. } => {
+                let triggers = self.trigger_map.get("payment_delayed")?;
+                triggers
+                    .iter()
+                    .find(|trigger| fires(trigger))
+                    .cloned()
+            }
+            SimEvent::AchReturnReceived { handling, amount, .. } if handling == "dispute" => {
+                let triggers = self.trigger_map.get("transaction_dispute")?;
+                triggers
+                    .iter()
+                    .filter(|trigger| trigger.amount_threshold.is_none_or(|min| *amount >= min))
+                    .find(|trigger| fires(trigger))
+                    .cloned()
+            }
+            SimEvent::ContactPreferenceViolated { .. } => {
+                let triggers = self.trigger_map.get("contact_violation")?;
+                triggers
+                    .iter()
+                    .find(|trigger| fires(trigger))
+                    .cloned()
+            }
+            // Phase 4.37: a full restriction is a frozen account
+            SimEvent::AccountRestricted { level, .. } => {
+                let key = if level == "full" { "account_frozen" } else { "account_restricted" };
+                let triggers = self.trigger_map.get(key)?;
+                triggers
+                    .iter()
+                    .find(|trigger| fires(trigger))
+                    .cloned()
+            }
+            // Phase 4.55: the longer and harder a hold bites, the likelier
+            SimEvent::FundsHeld { pressure, .. } => {
+                let triggers = self.trigger_map.get("funds_held")?;
+                triggers
+                    .iter()
+                    .find(|trigger| rng.chance((self.propensity.chance(trigger, customer_factor) * pressure).min(1.0)))
+                    .cloned()
+            }
+            // Phase 4.90
+            SimEvent::ChannelTransactionAbandoned { .. } => {
+                let triggers = self.trigger_map.get("channel_outage")?;
+                triggers
+                    .iter()
+                    .find(|trigger| fires(trigger))
+                    .cloned()
+            }
+            // Phase 4.91: a scam victim the bank would not reimburse
+            SimEvent::AppClaimDecided { outcome, .. } if outcome == "declined" => {
+                let triggers = self.trigger_map.get("app_claim_declined")?;
+                triggers
+                    .iter()
+                    .find(|trigger| fires(trigger))
+                    .cloned()
+            }
+            _ => None,
+        }
+    }
+
+    /// Who a complaint prompted by `event` would come from: the
+    /// customer, the account it is about if any, and the product.
+    /// None for events that never prompt one, and for a contact
+    /// violation against a customer with no accounts left.
+    fn complaint_subject(&self, event: &SimEvent) -> SimResult<Option<(String, Option<String>, String)>> {
+        let subject = match event {
+            SimEvent::FeeCharged {
+                customer_id,
+                account_id,
+                ..
+            }
+            | SimEvent::AccountRestricted {
+                customer_id,
+                account_id,
+                ..
+            }
+            | SimEvent::FundsHeld {
+                customer_id,
+                account_id,
+                ..
+            }
+            | SimEvent::ChannelTransactionAbandoned {
+                customer_id,
+                account_id,
+                ..
+            }
+            | SimEvent::AppClaimDecided {
+                customer_id,
+                account_id,
+                ..
+            } => {
+                let prod = self.store.account_product(&self.run_id, account_id)?;
+                (customer_id.clone(), Some(account_id.clone()), prod)
+            }
+            SimEvent::SLABreached {
+                customer_id,
+                complaint_id,
+                ..
+            } => {
+                let c = self.store.get_complaint(&self.run_id, complaint_id)?;
+                (customer_id.clone(), None, c.product)
+            }
+            SimEvent::PaymentRolledPastCutoff { account_id, .. }
+            | SimEvent::AchReturnReceived { account_id, .. } => {
+                let customer_id = self.store.get_account_customer_id(&self.run_id, account_id)?;
+                let prod = self.store.account_product(&self.run_id, account_id)?;
+                (customer_id, Some(account_id.clone()), prod)
+            }
+            // Phase 4.32: about the contact, not an account. A customer
+            // who has closed every account since is gone.
+            SimEvent::ContactPreferenceViolated { customer_id, .. } => {
+                let account_id = match self.store.customer_primary_account(&self.run_id, customer_id) {
+                    Ok(account_id) => account_id,
+                    Err(SimError::Store(rusqlite::Error::QueryReturnedNoRows)) => return Ok(None),
+                    Err(e) => return Err(e),
+                };
+                let prod = self.store.account_product(&self.run_id, &account_id)?;
+                (customer_id.clone(), None, prod)
+            }
+            _ => return Ok(None),
+        };
+        Ok(Some(subject))
+    }
+
+    /// The service channel a complaint prompted by `event` is about:
+    /// the channel that turned the customer away, or the ATM an ATM fee
+    /// was charged at (Phase 4.90).
+    fn service_channel(event: &SimEvent) -> Option<String> {
+        match event {
+            SimEvent::ChannelTransactionAbandoned { channel, .. } => Some(channel.clone()),
+            SimEvent::FeeCharged { fee_type, .. } if fee_type == "atm" => Some(Channel::Atm.as_str().into()),
             _ => None,
         }
     }
 
+    #[allow(clippy::too_many_arguments)]
     fn make_complaint(
+        &self,
         complaint_id: String,
         customer_id: &str,
         account_id: Option<&str>,
         product: &str,
         trigger: &ComplaintTrigger,
+        service_channel: Option<String>,
         tick: Tick,
     ) -> ComplaintRecord {
         ComplaintRecord {
@@ -113,12 +300,94 @@ impl ComplaintSubsystem {
             issue: trigger.issue_category.clone(),
             priority: trigger.priority.clone(),
             status: "open".to_string(),
-            sla_due_tick: tick + trigger.sla_resolve_days,
+            sla_due_tick: self.calendar.add_business_days(
+                tick,
+                self.sla_policies.resolve_days(
+                    &trigger.priority,
+                    product,
+                    &trigger.channel,
+                    tick,
+                    trigger.sla_resolve_days,
+                ),
+            ),
             sla_breached: false,
             resolution_code: None,
             amount_refunded: 0.0,
             udaap_flag: trigger.issue_category == "fee_dispute",
+            channel: trigger.channel.clone(),
+            service_channel,
+        }
+    }
+
+    /// Apply the player's SLA policy changes (Phase 4.29).
+    fn apply_player_commands(&mut self, tick: Tick, events_in: &[SimEvent]) -> SimResult<Vec<SimEvent>> {
+        let mut events = Vec::new();
+        for event in events_in {
+            let SimEvent::PlayerCommandReceived { command_id, .. } = event else {
+                continue;
+            };
+            if let Some(PlayerCommand::SetComplaintSlaPolicy {
+                priority,
+                product,
+                channel,
+                resolve_days,
+                effective_tick,
+            }) = self.store.get_player_command(&self.run_id, command_id)?
+            {
+                let rule = SlaPolicyRule {
+                    priority,
+                    product,
+                    channel,
+                    resolve_days,
+                };
+                events.push(self.set_sla_policy(rule, effective_tick, tick)?);
+            }
+        }
+        Ok(events)
+    }
+
+    /// Phase 4.29: schedule a policy rule unless it is malformed,
+    /// backdated, or would relax a regulatory deadline.
+    fn set_sla_policy(&mut self, rule: SlaPolicyRule, effective_tick: Tick, tick: Tick) -> SimResult<SimEvent> {
+        let cap = self.sla_policies.regulatory_cap(&rule);
+        let reason = if rule.priority.as_deref().is_some_and(|p| !complaint_sla::PRIORITIES.contains(&p)) {
+            Some("unknown priority".to_string())
+        } else if rule.channel.as_deref().is_some_and(|c| !complaint_sla::CHANNELS.contains(&c)) {
+            Some("unknown channel".to_string())
+        } else if rule.resolve_days == 0 {
+            Some("resolve days must be at least 1".to_string())
+        } else if effective_tick < tick {
+            Some("effective tick is in the past".to_string())
+        } else {
+            cap.filter(|cap| rule.resolve_days > *cap)
+                .map(|cap| format!("regulation requires resolution within {cap} days"))
+        };
+        if let Some(reason) = reason {
+            tracing::warn!("rejected complaint SLA policy {rule:?}: {reason}");
+            return Ok(SimEvent::ComplaintSlaPolicyRejected {
+                tick,
+                priority: rule.priority,
+                product: rule.product,
+                channel: rule.channel,
+                reason,
+            });
         }
+
+        self.store.insert_sla_policy(&self.run_id, tick, &rule, effective_tick)?;
+        tracing::info!(
+            "complaint SLA of {} days from tick {effective_tick} for {rule:?}",
+            rule.resolve_days
+        );
+        let event = SimEvent::ComplaintSlaPolicySet {
+            tick,
+            priority: rule.priority.clone(),
+            product: rule.product.clone(),
+            channel: rule.channel.clone(),
+            resolve_days: rule.resolve_days,
+            effective_tick,
+        };
+        self.sla_policies.schedule(rule, effective_tick);
+        Ok(event)
     }
 
     fn process_sla_aging(&self, tick: Tick) -> SimResult<Vec<SimEvent>> {
@@ -138,13 +407,42 @@ impl ComplaintSubsystem {
                     customer_id: complaint.customer_id.clone(),
                     days_overdue: (tick.saturating_sub(complaint.sla_due_tick)) as i32,
                 });
+                if self.satisfaction.scars_on_breach(&complaint.priority) {
+                    events.push(self.satisfaction.scar(
+                        &self.store,
+                        &complaint.customer_id,
+                        &complaint.complaint_id,
+                        tick,
+                    )?);
+                }
             }
         }
         Ok(events)
     }
 
-    /// Resolve a complaint via player command. Wired in Phase 1E.
-    #[allow(dead_code)]
+    /// Phase 4.80: the staffed desk closes up to `capacity` complaints
+    /// filed before this tick, earliest SLA due first.
+    fn work_queue(&self, capacity: usize, tick: Tick, rng: &mut SubsystemRng) -> SimResult<Vec<SimEvent>> {
+        let mut open: Vec<ComplaintRecord> = self
+            .store
+            .open_complaints(&self.run_id)?
+            .into_iter()
+            .filter(|c| c.tick_opened < tick)
+            .collect();
+        // Stable, so complaints due together go oldest first
+        open.sort_by_key(|c| c.sla_due_tick);
+        open.truncate(capacity);
+
+        let mut events = Vec::new();
+        for complaint in &open {
+            events.extend(self.process_resolution(&complaint.complaint_id, &self.desk_resolution_code, tick, rng)?);
+        }
+        self.store
+            .record_staffing_work(&self.run_id, tick, QueueKind::Complaints, open.len())?;
+        Ok(events)
+    }
+
+    /// Resolve a complaint, for the staffed desk or a player command.
     fn process_resolution(
         &self,
         complaint_id: &str,
@@ -154,23 +452,27 @@ impl ComplaintSubsystem {
     ) -> SimResult<Vec<SimEvent>> {
         let complaint = self.store.get_complaint(&self.run_id, complaint_id)?;
         if complaint.status != "open" {
-            log::warn!("Attempted to resolve non-open complaint {complaint_id}");
+            tracing::warn!("Attempted to resolve non-open complaint {complaint_id}");
             return Ok(vec![]);
         }
 
         let resolution = self
             .resolution_codes
             .get(resolution_code)
-            .ok_or_else(|| anyhow::anyhow!("Unknown resolution code: {resolution_code}"))?;
+            .ok_or_else(|| {
+                SimError::command("close_complaint", format!("unknown resolution code: {resolution_code}"))
+            })?;
 
         let refund = resolution.avg_amount_refunded;
         self.store
             .close_complaint(&self.run_id, complaint_id, tick, resolution_code, refund)?;
 
-        self.store.update_customer_satisfaction(
-            &self.run_id,
+        self.satisfaction.goodwill(
+            &self.store,
             &complaint.customer_id,
+            "complaint_resolution",
             resolution.satisfaction_delta,
+            tick,
         )?;
         self.store.adjust_customer_churn_risk(
             &self.run_id,
@@ -201,52 +503,80 @@ impl ComplaintSubsystem {
     }
 }
 
+const MANIFEST: SubsystemManifest = SubsystemManifest {
+    version: 5,
+    produces: &[
+        "case_documents_generated", "complaint_escalated_to_regulator", "complaint_filed",
+        "complaint_reopened", "complaint_resolved", "complaint_sla_policy_rejected",
+        "complaint_sla_policy_set", "desk_performance_reported", "satisfaction_scarred",
+        "sla_breached",
+    ],
+    consumes: &[
+        "account_restricted", "ach_return_received", "app_claim_decided", "channel_transaction_abandoned",
+        "contact_preference_violated", "fee_charged", "funds_held", "payment_rolled_past_cutoff",
+        "player_command_received", "sla_breached",
+    ],
+    tables: &[
+        "case_document", "complaint", "complaint_aggregate", "complaint_quality",
+        "complaint_sla_policy", "customer", "desk_performance_report", "interaction",
+        "regulatory_score", "satisfaction_gesture", "satisfaction_scar", "staffing_snapshot",
+    ],
+    requires: &[],
+};
+
 impl SimSubsystem for ComplaintSubsystem {
     fn name(&self) -> &'static str {
         "complaint"
     }
 
+    fn manifest(&self) -> SubsystemManifest {
+        MANIFEST
+    }
+
     fn update(
         &mut self,
         tick: Tick,
         events_in: &[SimEvent],
         rng: &mut SubsystemRng,
     ) -> SimResult<Vec<SimEvent>> {
-        let mut out_events = Vec::new();
+        // 1. Policy changes first, so one effective this tick applies to
+        // the complaints it opens.
+        let mut out_events = self.apply_player_commands(tick, events_in)?;
 
-        // 1. Generate complaints from triggering events.
+        // 2. Generate complaints from triggering events.
         for event in events_in {
-            let Some(trigger) = self.should_trigger_complaint(event, rng) else {
+            // Phase 4.51: product and segment factors need the customer
+            // before the trigger is rolled
+            let mut subject = None;
+            let mut customer_factor = 1.0;
+            if self.propensity.by_customer() {
+                let Some(found) = self.complaint_subject(event)? else {
+                    continue;
+                };
+                let segment = self.store.customer_segment(&self.run_id, &found.0)?;
+                customer_factor = self.propensity.customer_factor(&found.2, &segment);
+                subject = Some(found);
+            }
+            let Some(trigger) = self.should_trigger_complaint(event, customer_factor, rng) else {
                 continue;
             };
-
-            let (customer_id, account_id, product) = match event {
-                SimEvent::FeeCharged {
-                    customer_id,
-                    account_id,
-                    ..
-                } => {
-                    let prod = self.store.account_product(&self.run_id, account_id)?;
-                    (customer_id.clone(), Some(account_id.clone()), prod)
-                }
-                SimEvent::SLABreached {
-                    customer_id,
-                    complaint_id,
-                    ..
-                } => {
-                    let c = self.store.get_complaint(&self.run_id, complaint_id)?;
-                    (customer_id.clone(), None, c.product)
-                }
-                _ => continue,
+            let subject = match subject {
+                Some(subject) => subject,
+                None => match self.complaint_subject(event)? {
+                    Some(subject) => subject,
+                    None => continue,
+                },
             };
+            let (customer_id, account_id, product) = subject;
 
             let complaint_id = format!("cmp-{tick:08x}-{:016x}", rng.next_u64());
-            let complaint = Self::make_complaint(
+            let complaint = self.make_complaint(
                 complaint_id,
                 &customer_id,
                 account_id.as_deref(),
                 &product,
                 &trigger,
+                Self::service_channel(event),
                 tick,
             );
 
@@ -254,6 +584,13 @@ impl SimSubsystem for ComplaintSubsystem {
             self.store
                 .update_customer_satisfaction(&self.run_id, &customer_id, -0.03)?;
 
+            // Phase 4.31: the complaint record and its supporting evidence
+            let documents = if self.documents.enabled() {
+                Some(self.documents.for_complaint(&self.store, &complaint, tick, rng)?)
+            } else {
+                None
+            };
+
             out_events.push(SimEvent::ComplaintFiled {
                 tick,
                 complaint_id: complaint.complaint_id,
@@ -261,12 +598,26 @@ impl SimSubsystem for ComplaintSubsystem {
                 issue: trigger.issue_category,
                 priority: trigger.priority,
             });
+            out_events.extend(documents);
         }
 
-        // 2. SLA aging and breach detection.
+        // 3. Phase 4.80: the desk works the queue as far as its staff
+        // allow; what it leaves ages into breaches below.
+        if let Some(capacity) = self.store.staffing_capacity(&self.run_id, tick, QueueKind::Complaints)? {
+            out_events.extend(self.work_queue(capacity, tick, rng)?);
+        }
+
+        // 3b. SLA aging and breach detection.
         out_events.extend(self.process_sla_aging(tick)?);
 
-        // 3. Player resolution commands (wired in Phase 1E; stub here).
+        // Phase 4.65: score the closures since last tick; the poor ones
+        // may come straight back.
+        if self.quality.enabled() {
+            out_events.extend(self.quality.review(&self.store, tick, rng)?);
+            out_events.extend(self.quality.report(&self.store, tick)?);
+        }
+
+        // 4. Player resolution commands (wired in Phase 1E; stub here).
         for event in events_in {
             if let SimEvent::PlayerCommandReceived { command_type, .. } = event {
                 if command_type == "close_complaint" {
@@ -275,13 +626,13 @@ impl SimSubsystem for ComplaintSubsystem {
             }
         }
 
-        // 4. Periodic complaint aggregate (every 7 ticks).
+        // 5. Periodic complaint aggregate (every 7 ticks).
         if tick.is_multiple_of(7) {
             let agg = self.store.compute_complaint_aggregate(&self.run_id, tick)?;
             self.store
                 .save_complaint_aggregate(&self.run_id, tick, &agg)?;
-            log::debug!(
-                "tick={tick} complaint: opened={} closed={} breached={} backlog={}",
+            tracing::debug!(
+                "opened={} closed={} breached={} backlog={}",
                 agg.complaints_opened,
                 agg.complaints_closed,
                 agg.sla_breaches,
@@ -292,6 +643,34 @@ impl SimSubsystem for ComplaintSubsystem {
         Ok(out_events)
     }
 
+    // Player SLA policies are the only state not in the store
+    fn save_state(&self) -> SimResult<Option<serde_json::Value>> {
+        Ok(Some(serde_json::json!({ "sla_policies": self.sla_policies.scheduled() })))
+    }
+
+    fn load_state(&mut self, state: serde_json::Value) -> SimResult<()> {
+        let scheduled: Vec<(Tick, SlaPolicyRule)> = serde_json::from_value(state["sla_policies"].clone())?;
+        for (effective_tick, rule) in scheduled {
+            self.sla_policies.schedule(rule, effective_tick);
+        }
+        Ok(())
+    }
+
+    fn replay(&mut self, _tick: Tick, events: &[SimEvent]) -> SimResult<()> {
+        for event in events {
+            if let SimEvent::ComplaintSlaPolicySet { priority, product, channel, resolve_days, effective_tick, .. } = event {
+                let rule = SlaPolicyRule {
+                    priority: priority.clone(),
+                    product: product.clone(),
+                    channel: channel.clone(),
+                    resolve_days: *resolve_days,
+                };
+                self.sla_policies.schedule(rule, *effective_tick);
+            }
+        }
+        Ok(())
+    }
+
     fn as_any(&self) -> &dyn std::any::Any {
         self
     }
@@ -1,6 +1,6 @@
-use crate::types::Tick;
+use crate::{authorization::Role, channel::Channel, event::EconomicPhase, queue::QueueKind, types::Tick};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ProductConfig {
@@ -60,6 +60,13 @@ pub struct ComplaintTrigger {
     pub priority: String,
     pub sla_acknowledge_days: u64,
     pub sla_resolve_days: u64,
+    /// Intake channel of the complaints this trigger raises (Phase 4.29).
+    #[serde(default = "default_complaint_channel")]
+    pub channel: String,
+}
+
+fn default_complaint_channel() -> String {
+    "phone".into()
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -70,6 +77,93 @@ pub struct ResolutionCode {
     pub avg_amount_refunded: f64,
 }
 
+/// A named way of closing many complaints at once with `bulk_resolve`
+/// (Phase 4.47).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ResolutionTemplate {
+    /// Empty on a template sent inline with the command.
+    #[serde(default)]
+    pub template_id: String,
+    pub resolution_code: String,
+    #[serde(default)]
+    pub refund: RefundRule,
+}
+
+/// How much a template refunds each complaint it closes.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "rule", rename_all = "snake_case")]
+pub enum RefundRule {
+    #[default]
+    Nothing,
+    /// The same amount on every complaint.
+    Fixed { amount: f64 },
+    /// The fee that prompted the complaint, when it is at most `max`.
+    /// Complaints with no fee, or a larger one, are skipped.
+    FeeUpTo { max: f64 },
+}
+
+/// How much more or less likely complaints are for a product, customer
+/// segment or issue (Phase 4.51). A trigger's probability is multiplied
+/// by the factor for the account's product, the customer's segment and
+/// the trigger's issue, capped at 1. Anything not listed is 1.0, so a
+/// scenario raises overdraft disputes or one product's complaints
+/// without touching the triggers themselves.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ComplaintPropensity {
+    #[serde(default)]
+    pub products: HashMap<String, f64>,
+    #[serde(default)]
+    pub segments: HashMap<String, f64>,
+    #[serde(default)]
+    pub issues: HashMap<String, f64>,
+}
+
+impl ComplaintPropensity {
+    /// Whether any factor depends on who the complaint is from, which
+    /// needs the account looked up before the trigger is rolled.
+    pub fn by_customer(&self) -> bool {
+        !self.products.is_empty() || !self.segments.is_empty()
+    }
+
+    /// Product and segment factor for one customer's account.
+    pub fn customer_factor(&self, product: &str, segment: &str) -> f64 {
+        self.products.get(product).copied().unwrap_or(1.0) * self.segments.get(segment).copied().unwrap_or(1.0)
+    }
+
+    /// Probability that `trigger` fires for a customer with
+    /// `customer_factor`.
+    pub fn chance(&self, trigger: &ComplaintTrigger, customer_factor: f64) -> f64 {
+        let issue = self.issues.get(&trigger.issue_category).copied().unwrap_or(1.0);
+        (trigger.probability * customer_factor * issue).min(1.0)
+    }
+
+    /// Reject negative factors and products, segments or issues the
+    /// scenario does not have, which would otherwise be silently ignored.
+    fn validate(
+        &self,
+        products: &HashMap<String, ProductConfig>,
+        segments: &HashMap<String, SegmentConfig>,
+        triggers: &[ComplaintTrigger],
+    ) -> anyhow::Result<()> {
+        let known = [
+            ("products", &self.products, products.keys().map(String::as_str).collect::<Vec<_>>()),
+            ("segments", &self.segments, segments.keys().map(String::as_str).collect()),
+            ("issues", &self.issues, triggers.iter().map(|t| t.issue_category.as_str()).collect()),
+        ];
+        for (table, factors, names) in known {
+            for (name, factor) in factors {
+                if !names.contains(&name.as_str()) {
+                    anyhow::bail!("complaint propensity {table}: unknown {name}");
+                }
+                if !(factor.is_finite() && *factor >= 0.0) {
+                    anyhow::bail!("complaint propensity {table}.{name} must be a non-negative number, got {factor}");
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
 #[derive(Debug, Clone, Deserialize)]
 struct SegmentsFile {
     segments: Vec<SegmentConfig>,
@@ -79,6 +173,10 @@ struct SegmentsFile {
 struct ComplaintConfigFile {
     triggers: Vec<ComplaintTrigger>,
     resolution_codes: Vec<ResolutionCode>,
+    #[serde(default)]
+    resolution_templates: Vec<ResolutionTemplate>,
+    #[serde(default)]
+    propensity: ComplaintPropensity,
 }
 
 // ── Phase 2.2: Offer catalog ───────────────────────────────────────
@@ -90,6 +188,9 @@ pub struct OfferConfig {
     pub label: String,
     pub product_id: Option<String>,
     pub bonus_amount: f64,
+    /// Credit line extended on take-up; credit offers only (Phase 4.34).
+    #[serde(default)]
+    pub credit_limit: f64,
     pub requirements: OfferRequirements,
     pub eligibility: OfferEligibility,
     pub cost_model: OfferCostModel,
@@ -117,6 +218,8 @@ pub struct OfferEligibility {
     pub min_churn_risk: Option<f64>,
     #[serde(default)]
     pub max_churn_risk: Option<f64>,
+    #[serde(default)]
+    pub exclude_zip_prefixes: Vec<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -296,6 +399,10 @@ pub struct EarlyWarningConfig {
     pub repeat_complainer_threshold: u32,
     pub segment_concentration_warning: f64,
     pub issue_type_concentration_warning: f64,
+    /// Phase 4.48: warn when a desk's aged backlog (14+ ticks old) grew
+    /// by at least this many items in a week. None turns it off.
+    #[serde(default)]
+    pub aged_backlog_growth_warning: Option<u64>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -405,7 +512,12 @@ pub struct PaymentRailConfig {
     pub fraud_risk_multiplier: f64,
     pub operational_risk_base: f64,
     pub batch_window_ticks: Option<Tick>,
+    /// Hour of the processing day (0–23) at or after which newly initiated
+    /// items miss the day's window; `None` processes everything same-day.
     pub cutoff_time_tick: Option<Tick>,
+    /// Phase 4.91: final once sent; no recall can bring a payment back.
+    #[serde(default)]
+    pub irrevocable: bool,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -572,215 +684,3222 @@ pub struct ReputationConfig {
     pub max_onboarding_penalty: f64,
 }
 
-#[derive(Debug, Clone)]
-pub struct SimConfig {
-    pub segments: HashMap<String, SegmentConfig>,
-    pub initial_population: usize,
-    pub complaint_triggers: Vec<ComplaintTrigger>,
-    pub resolution_codes: HashMap<String, ResolutionCode>,
-    pub products: HashMap<String, ProductConfig>,
-    pub fee_constraints: HashMap<String, FeeConstraint>,
-    pub impact_formulas: HashMap<String, FeeImpactFormula>,
-    pub offers: HashMap<String, OfferConfig>,
-    pub churn_model: ChurnModelConfig,
-    pub segment_economics: SegmentEconomicsConfig,
-    pub complaint_analytics: ComplaintAnalyticsConfig,
-    pub risk_appetite: RiskAppetiteConfig,
-    pub payment_hub: PaymentHubConfig,
-    pub reconciliation: ReconciliationConfig,
-    pub identity_address: IdentityAddressConfig,
-    pub incident: IncidentConfig,
-    pub regulatory_exam: RegulatoryExamConfig,
-    pub reputation: ReputationConfig,
+// ── Phase 4.1: Credit bureau config ───────────────────────────────
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CreditBureauConfig {
+    pub enabled: bool,
+    /// How often account status is furnished to the bureau (in ticks).
+    pub furnish_interval_ticks: Tick,
+    /// Probability a single furnished record is inaccurate.
+    pub furnishing_error_rate: f64,
+    /// Probability the customer notices and disputes an inaccurate record.
+    pub dispute_probability: f64,
+    /// FCRA investigation window from dispute receipt (in ticks).
+    pub investigation_window_ticks: Tick,
+    /// Daily probability that operations corrects an open dispute.
+    pub daily_correction_rate: f64,
+    /// Penalty booked when a dispute passes its window uncorrected.
+    pub late_correction_penalty: f64,
 }
 
-impl SimConfig {
-    /// Load from the data/ directory.
-    /// In tests, use SimConfig::default_test().
-    pub fn load(data_dir: &str) -> anyhow::Result<Self> {
-        let path = format!("{data_dir}/segments/segments.json");
-        let content = std::fs::read_to_string(&path)
-            .map_err(|e| anyhow::anyhow!("Cannot read {path}: {e}"))?;
-        let file: SegmentsFile = serde_json::from_str(&content)?;
-        let segments = file
-            .segments
-            .into_iter()
-            .map(|s| (s.id.clone(), s))
-            .collect();
+// ── Phase 4.2: Protected funds config ─────────────────────────────
 
-        let complaint_path = format!("{data_dir}/complaints/complaint_triggers.json");
-        let complaint_content = std::fs::read_to_string(&complaint_path)
-            .map_err(|e| anyhow::anyhow!("Cannot read {complaint_path}: {e}"))?;
-        let complaint_file: ComplaintConfigFile = serde_json::from_str(&complaint_content)?;
-        let resolution_codes = complaint_file
-            .resolution_codes
-            .into_iter()
-            .map(|r| (r.code.clone(), r))
-            .collect();
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProtectedFundsConfig {
+    pub enabled: bool,
+    /// Fraction of accounts enrolled as federal benefit recipients.
+    pub beneficiary_share: f64,
+    /// Day of the 30-tick month on which benefits are deposited.
+    pub benefit_payment_day: Tick,
+    /// Monthly benefit for recipients without a payroll history.
+    pub default_monthly_benefit: f64,
+    /// Lookback window for the protected amount (two months under 31 CFR 212).
+    pub lookback_ticks: Tick,
+    /// When false, the fee engine ignores protection and violations are booked.
+    pub enforce_protection: bool,
+    /// Daily probability a recipient account receives a garnishment or setoff.
+    pub garnishment_daily_rate: f64,
+    /// UDAAP score increase per protected-funds violation.
+    pub violation_udaap_impact: f64,
+}
 
-        let product_path = format!("{data_dir}/products/product_catalog.json");
-        let product_content = std::fs::read_to_string(&product_path)
-            .map_err(|e| anyhow::anyhow!("Cannot read {product_path}: {e}"))?;
-        let product_file: ProductCatalogFile = serde_json::from_str(&product_content)?;
-        let products = product_file
-            .products
-            .into_iter()
-            .map(|p| (p.product_id.clone(), p))
-            .collect();
+// ── Phase 4.3: Branch network config ──────────────────────────────
 
-        let fee_path = format!("{data_dir}/products/fee_constraints.json");
-        let fee_content = std::fs::read_to_string(&fee_path)
-            .map_err(|e| anyhow::anyhow!("Cannot read {fee_path}: {e}"))?;
-        let fee_file: FeeConstraintsFile = serde_json::from_str(&fee_content)?;
-        let fee_constraints = fee_file
-            .fee_types
-            .into_iter()
-            .map(|f| (f.fee_type.clone(), f))
-            .collect();
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BranchNetworkConfig {
+    pub enabled: bool,
+    /// Minimum active customers in a state for a branch to be seeded.
+    pub min_customers_for_branch: i64,
+    /// Customers served per branch staff member.
+    pub customers_per_staff: usize,
+    /// Minimum staff at any open branch.
+    pub min_staff: i64,
+    /// Monthly loaded cost per branch staff member.
+    pub staff_monthly_cost: f64,
+    /// Monthly occupancy cost per open branch.
+    pub occupancy_monthly_cost: f64,
+    /// New accounts per month a branch is expected to bring in.
+    pub acquisitions_per_branch_month: f64,
+    /// Churn-risk delta applied to customers of a closed branch.
+    pub closure_churn_delta: f64,
+    /// Satisfaction hit applied to customers of a closed branch.
+    pub closure_satisfaction_hit: f64,
+    /// Churn-risk reduction for customers gaining a new branch.
+    pub open_churn_relief: f64,
+    /// How long an open/close life event stays active (in ticks).
+    pub network_change_effect_ticks: Tick,
+    /// How often the per-branch performance report is written (in ticks).
+    pub report_interval_ticks: Tick,
+}
 
-        let offer_path = format!("{data_dir}/offers/offer_catalog.json");
-        let offer_content = std::fs::read_to_string(&offer_path)
-            .map_err(|e| anyhow::anyhow!("Cannot read {offer_path}: {e}"))?;
-        let offer_file: OfferCatalogFile = serde_json::from_str(&offer_content)?;
-        let offers = offer_file
-            .offers
-            .into_iter()
-            .map(|o| (o.offer_id.clone(), o))
-            .collect();
+// ── Phase 4.4: Fair lending config ────────────────────────────────
 
-        let churn_path = format!("{data_dir}/churn/churn_model_config.json");
-        let churn_content = std::fs::read_to_string(&churn_path)
-            .map_err(|e| anyhow::anyhow!("Cannot read {churn_path}: {e}"))?;
-        let churn_model: ChurnModelConfig = serde_json::from_str(&churn_content)?;
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FairLendingConfig {
+    pub enabled: bool,
+    /// Share of zips generated as majority-minority geographies.
+    pub high_minority_zip_share: f64,
+    /// Zip income below this fraction of the state median is LMI.
+    pub lmi_income_ratio: f64,
+    /// How often disparity metrics are computed (in ticks).
+    pub review_interval_ticks: Tick,
+    /// Minimum customers in each group for a metric to be evaluated.
+    pub min_group_size: i64,
+    /// Adverse ratio (protected vs control) that raises a finding.
+    pub adverse_ratio_threshold: f64,
+    /// Adverse ratio at or above which a finding is "major".
+    pub major_ratio_threshold: f64,
+    /// Ticks the player has to respond to a finding.
+    pub response_window_ticks: Tick,
+    /// UDAAP score increase when a finding goes unanswered.
+    pub escalation_udaap_impact: f64,
+    /// UDAAP score decrease when a finding is remediated.
+    pub remediation_udaap_relief: f64,
+    /// Cost of remediating a finding (customer restitution and program
+    /// changes), booked to opex in the quarter it is remediated.
+    pub remediation_cost: f64,
+}
 
-        let seg_econ_path = format!("{data_dir}/economics/segment_economics_config.json");
-        let seg_econ_content = std::fs::read_to_string(&seg_econ_path)
-            .map_err(|e| anyhow::anyhow!("Cannot read {seg_econ_path}: {e}"))?;
-        let seg_econ_file: SegmentEconomicsFile = serde_json::from_str(&seg_econ_content)?;
-        let segment_economics = SegmentEconomicsConfig {
-            cost_allocation_model: seg_econ_file.cost_allocation_model,
-            clv_model: seg_econ_file.clv_model,
-            profitability_metrics: seg_econ_file.profitability_metrics,
-            revenue_attribution: seg_econ_file.revenue_attribution,
-        };
+// ── Phase 4.5: Offer guardrail config ─────────────────────────────
 
-        let complaint_analytics_path = format!("{data_dir}/complaints/analytics_config.json");
-        let complaint_analytics_content = std::fs::read_to_string(&complaint_analytics_path)
-            .map_err(|e| anyhow::anyhow!("Cannot read {complaint_analytics_path}: {e}"))?;
-        let complaint_analytics_file: ComplaintAnalyticsFile =
-            serde_json::from_str(&complaint_analytics_content)?;
-        let complaint_analytics = ComplaintAnalyticsConfig {
-            pattern_detection: complaint_analytics_file.pattern_detection,
-            root_cause_tracking: complaint_analytics_file.root_cause_tracking,
-            resolution_effectiveness: complaint_analytics_file.resolution_effectiveness,
-            sla_performance: complaint_analytics_file.sla_performance,
-            early_warning_indicators: complaint_analytics_file.early_warning_indicators,
-            cost_analysis: complaint_analytics_file.cost_analysis,
-        };
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OfferGuardrailConfig {
+    pub enabled: bool,
+    /// Exclusion disparity (protected vs control zips) that triggers a warning.
+    pub warn_ratio: f64,
+    /// Exclusion disparity at which the launch is blocked.
+    pub block_ratio: f64,
+    /// UDAAP score increase when a launch proceeds over a warning.
+    pub warn_udaap_impact: f64,
+}
 
-        let risk_path = format!("{data_dir}/risk/risk_appetite_config.json");
-        let risk_content = std::fs::read_to_string(&risk_path)
-            .map_err(|e| anyhow::anyhow!("Cannot read {risk_path}: {e}"))?;
-        let risk_file: RiskAppetiteFile = serde_json::from_str(&risk_content)?;
-        let risk_appetite = RiskAppetiteConfig {
-            dials: risk_file.dials,
-            constraints: risk_file.constraints,
-            risk_profile_scoring: risk_file.risk_profile_scoring,
-            board_pressure: risk_file.board_pressure,
-        };
+// ── Phase 4.6: Card network invoice config ────────────────────────
 
-        let payment_path = format!("{data_dir}/payment/payment_rails_config.json");
-        let payment_content = std::fs::read_to_string(&payment_path)
-            .map_err(|e| anyhow::anyhow!("Cannot read {payment_path}: {e}"))?;
-        let payment_file: PaymentHubFile = serde_json::from_str(&payment_content)?;
-        let payment_hub = PaymentHubConfig {
-            rails: payment_file.rails,
-            interchange_fee_rate: payment_file.interchange_fee_rate,
-            auth_expiry_ticks: payment_file.auth_expiry_ticks,
-        };
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NetworkInvoiceConfig {
+    pub enabled: bool,
+    /// How often the card network invoices (in ticks).
+    pub invoice_interval_ticks: Tick,
+    /// Network assessment rate on settled card volume.
+    pub assessment_rate: f64,
+    /// Network fee per card dispute filed.
+    pub dispute_fee: f64,
+    /// Probability that an invoice line contains a network error.
+    pub network_error_rate: f64,
+    /// Smallest rate error, as a fraction of the correct amount.
+    pub min_error_pct: f64,
+    /// Largest rate error, as a fraction of the correct amount.
+    pub max_error_pct: f64,
+    /// Ticks after receipt during which an error can be claimed back.
+    pub recovery_window_ticks: Tick,
+    /// Probability the network accepts a recovery claim.
+    pub recovery_success_rate: f64,
+}
 
-        let identity_address = {
-            let ia_path = format!("{data_dir}/identity/identity_address.json");
-            let ia_content = std::fs::read_to_string(&ia_path)
-                .map_err(|e| anyhow::anyhow!("Cannot read {ia_path}: {e}"))?;
-            let ia_file: IdentityAddressFile = serde_json::from_str(&ia_content)?;
-            IdentityAddressConfig {
-                regions: ia_file.regions,
-                synthetic_identity_rate: ia_file.rates.synthetic_identity_rate,
-                homeless_rate: ia_file.rates.homeless_rate,
-                po_box_rate: ia_file.rates.po_box_rate,
-                cmra_rate: ia_file.rates.cmra_rate,
-                address_sharing_alert_threshold: ia_file.rates.address_sharing_alert_threshold,
-                voip_rate: ia_file.rates.voip_rate,
-                international_customer_rate: ia_file.rates.international_customer_rate,
-            }
-        };
+// ── Phase 4.7: Settlement account config ──────────────────────────
 
-        Ok(Self {
-            segments,
-            initial_population: 500,
-            complaint_triggers: complaint_file.triggers,
-            resolution_codes,
-            products,
-            fee_constraints,
-            impact_formulas: fee_file.impact_formulas,
-            offers,
-            churn_model,
-            segment_economics,
-            complaint_analytics,
-            risk_appetite,
-            payment_hub,
-            reconciliation: {
-                let recon_path =
-                    format!("{data_dir}/reconciliation/recon_queue_config.json");
-                let recon_content = std::fs::read_to_string(&recon_path)
-                    .map_err(|e| anyhow::anyhow!("Cannot read {recon_path}: {e}"))?;
-                let recon_file: ReconFile = serde_json::from_str(&recon_content)?;
-                ReconciliationConfig {
-                    queue_configs: recon_file.queue_configs,
-                    enable_auto_clear: recon_file.enable_auto_clear,
-                    metrics_frequency_ticks: recon_file.metrics_frequency_ticks,
-                }
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SettlementAccountConfig {
+    pub enabled: bool,
+    /// Trailing window used to project each rail's daily outflow (in ticks).
+    pub projection_window_ticks: Tick,
+    /// Extra funding on top of the projected outflow.
+    pub funding_buffer_pct: f64,
+    /// Penalty rate charged per tick on borrowed shortfall.
+    pub penalty_rate: f64,
+    /// Flat fee per borrowing event.
+    pub borrowing_fee: f64,
+    /// Shortfall, as a fraction of the funded amount, that raises an alert.
+    pub alert_shortfall_pct: f64,
+}
+
+// ── Phase 4.8: Payment cutoff config ──────────────────────────────
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PaymentCutoffConfig {
+    pub enabled: bool,
+    /// First hour of the day customers initiate payments.
+    pub initiation_start_hour: u64,
+    /// Hour after which no new payments are initiated (exclusive).
+    pub initiation_end_hour: u64,
+}
+
+// ── Phase 4.9: ACH return config ──────────────────────────────────
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AchReturnCode {
+    /// NACHA return reason code, e.g. "R01".
+    pub code: String,
+    pub description: String,
+    /// Relative generation weight among returned items.
+    pub weight: f64,
+    /// "represent" | "customer_contact" | "dispute" | "close_origination"
+    pub handling: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AchReturnConfig {
+    pub enabled: bool,
+    /// Probability a settled ACH item is returned.
+    pub return_rate: f64,
+    /// Ticks after initiation that the return arrives.
+    pub return_delay_ticks: Tick,
+    /// Ticks after a represent-eligible return that the item is re-presented.
+    pub represent_delay_ticks: Tick,
+    /// Probability a re-presented item is collected.
+    pub represent_success_rate: f64,
+    /// Satisfaction change from the contact a customer_contact return requires.
+    pub contact_satisfaction_delta: f64,
+    /// Ticks the originator has to answer an unauthorized-return dispute.
+    pub dispute_review_ticks: Tick,
+    /// Probability the originator proves authorization and the debit is reinstated.
+    pub originator_contest_rate: f64,
+    pub codes: Vec<AchReturnCode>,
+}
+
+/// The return reason code taxonomy shared by the live and test configs.
+fn default_ach_return_codes() -> Vec<AchReturnCode> {
+    [
+        ("R01", "Insufficient funds", 0.40, "represent"),
+        ("R02", "Account closed", 0.12, "customer_contact"),
+        ("R03", "No account / unable to locate account", 0.08, "customer_contact"),
+        ("R04", "Invalid account number", 0.05, "customer_contact"),
+        ("R05", "Unauthorized debit to consumer account", 0.02, "dispute"),
+        ("R07", "Authorization revoked by customer", 0.04, "close_origination"),
+        ("R08", "Payment stopped", 0.05, "customer_contact"),
+        ("R09", "Uncollected funds", 0.06, "represent"),
+        ("R10", "Customer advises not authorized", 0.12, "dispute"),
+        ("R16", "Account frozen", 0.02, "close_origination"),
+        ("R29", "Corporate customer advises not authorized", 0.04, "dispute"),
+    ]
+    .into_iter()
+    .map(|(code, description, weight, handling)| AchReturnCode {
+        code: code.into(),
+        description: description.into(),
+        weight,
+        handling: handling.into(),
+    })
+    .collect()
+}
+
+// ── Phase 4.10: Customer-originated ACH config ────────────────────
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AchOriginationConfig {
+    pub enabled: bool,
+    /// Daily probability an account pays a bill to an external biller.
+    pub bill_pay_daily_rate: f64,
+    /// Daily probability an account pulls funds in from an external account.
+    pub external_transfer_daily_rate: f64,
+    /// Daily probability an account is taken over and drained into a
+    /// burst of unauthorized external transfers.
+    pub takeover_daily_rate: f64,
+    /// Transfer attempts in one takeover burst.
+    pub takeover_burst_size: u32,
+    /// Default per-customer limit on originated entries per day.
+    pub daily_count_limit: u32,
+    /// Default per-customer limit on originated dollars per day.
+    pub daily_amount_limit: f64,
+    /// Satisfaction change when a legitimate entry is declined by a limit.
+    pub declined_satisfaction_delta: f64,
+    /// Ticks after origination that a return arrives.
+    pub return_delay_ticks: Tick,
+    /// Probability a legitimate entry is returned R02/R03/R04.
+    pub administrative_return_rate: f64,
+    /// Probability a legitimate external transfer is returned R01.
+    pub nsf_return_rate: f64,
+    /// Probability a legitimate external transfer is disputed as unauthorized.
+    pub unauthorized_return_rate: f64,
+    /// Ticks between ODFI return-rate reviews.
+    pub review_interval_ticks: Tick,
+    /// Days of originated debit entries each review covers.
+    pub review_lookback_ticks: Tick,
+    /// NACHA unauthorized return-rate threshold (0.5%).
+    pub unauthorized_threshold: f64,
+    /// NACHA administrative return-rate threshold (3%).
+    pub administrative_threshold: f64,
+    /// NACHA overall return-rate threshold (15%).
+    pub overall_threshold: f64,
+    /// Network fine assessed for each review that breaches a threshold.
+    pub network_fine: f64,
+}
+
+// ── Phase 4.11: External account linking config ───────────────────
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExternalLinkConfig {
+    pub enabled: bool,
+    /// Default share of new links sent through micro-deposit verification
+    /// rather than instant verification (0.0–1.0). Adjustable by the player.
+    pub verification_strictness: f64,
+    /// Ticks before the customer can confirm the micro-deposit amounts.
+    pub micro_deposit_delay_ticks: Tick,
+    /// Probability a genuine customer confirms the micro-deposits.
+    pub micro_deposit_confirm_rate: f64,
+    /// Probability a fraudster's link passes instant verification.
+    pub instant_bypass_rate: f64,
+    /// Probability a fraudster's link passes micro-deposit verification.
+    pub micro_deposit_bypass_rate: f64,
+    /// Daily probability a customer without payroll links an account to
+    /// switch their direct deposit here.
+    pub dd_switch_daily_rate: f64,
+    /// Daily probability a taken-over account links a mule account.
+    pub mule_link_daily_rate: f64,
+    /// Ticks a verified mule link is used to drain the account.
+    pub drain_window_ticks: Tick,
+    /// Satisfaction change when a genuine link fails verification.
+    pub failed_link_satisfaction_delta: f64,
+}
+
+// ── Phase 4.12: Customer limits config ────────────────────────────
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CustomerLimitRule {
+    /// "daily_transfer" | "cash_withdrawal" | "p2p_send" | "wire"
+    pub limit_type: String,
+    /// "global", "tier:<composite_risk>" or "product:<product_id>".
+    pub scope: String,
+    /// Daily cap in dollars; for wires, the cap on a single wire.
+    pub amount: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CustomerLimitConfig {
+    pub enabled: bool,
+    /// Satisfaction change when a genuine attempt is declined by a limit.
+    pub declined_satisfaction_delta: f64,
+    pub rules: Vec<CustomerLimitRule>,
+}
+
+/// Global limits, tightened for high and critical risk tiers.
+fn default_customer_limit_rules() -> Vec<CustomerLimitRule> {
+    [
+        ("daily_transfer", "global", 10_000.0),
+        ("cash_withdrawal", "global", 1_000.0),
+        ("p2p_send", "global", 2_500.0),
+        ("wire", "global", 25_000.0),
+        ("daily_transfer", "tier:high", 5_000.0),
+        ("cash_withdrawal", "tier:high", 500.0),
+        ("p2p_send", "tier:high", 1_000.0),
+        ("wire", "tier:high", 10_000.0),
+        ("daily_transfer", "tier:critical", 2_500.0),
+        ("cash_withdrawal", "tier:critical", 300.0),
+        ("p2p_send", "tier:critical", 500.0),
+        ("wire", "tier:critical", 5_000.0),
+    ]
+    .into_iter()
+    .map(|(limit_type, scope, amount)| CustomerLimitRule {
+        limit_type: limit_type.into(),
+        scope: scope.into(),
+        amount,
+    })
+    .collect()
+}
+
+// ── Phase 4.13: Risk lifecycle config ─────────────────────────────
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RiskTierServicing {
+    /// "low" | "medium" | "high" | "critical"
+    pub tier: String,
+    /// Ticks between the customer's periodic reviews.
+    pub review_interval: Tick,
+    /// Analyst cost of one review, booked into opex.
+    pub review_cost: f64,
+    /// Scales transaction monitoring thresholds; below 1.0 alerts sooner.
+    pub monitoring_multiplier: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RiskLifecycleConfig {
+    pub enabled: bool,
+    /// Ticks between behavior re-scores.
+    pub rescore_interval: Tick,
+    /// Window of behavior signals counted at each re-score.
+    pub signal_lookback_ticks: Tick,
+    /// Behavior risk added per AML alert in the window.
+    pub alert_weight: f64,
+    /// Behavior risk added per customer limit decline in the window.
+    pub decline_weight: f64,
+    /// Behavior risk added per unauthorized originated entry in the window.
+    pub unauthorized_weight: f64,
+    /// Behavior risk at which the customer is at least medium, high or
+    /// critical.
+    pub medium_threshold: f64,
+    pub high_threshold: f64,
+    pub critical_threshold: f64,
+    pub tiers: Vec<RiskTierServicing>,
+}
+
+/// Reviews yearly for low risk down to monthly for critical, with
+/// monitoring thresholds tightened as the tier rises.
+fn default_risk_tier_servicing() -> Vec<RiskTierServicing> {
+    [
+        ("low", 365, 50.0, 1.0),
+        ("medium", 180, 100.0, 0.9),
+        ("high", 90, 400.0, 0.75),
+        ("critical", 30, 750.0, 0.5),
+    ]
+    .into_iter()
+    .map(|(tier, review_interval, review_cost, monitoring_multiplier)| RiskTierServicing {
+        tier: tier.into(),
+        review_interval,
+        review_cost,
+        monitoring_multiplier,
+    })
+    .collect()
+}
+
+// ── Phase 4.14: SSN validation config ─────────────────────────────
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SsnValidationConfig {
+    pub enabled: bool,
+    /// Synthetic-identity score added for an SSN outside the ranges the
+    /// SSA has ever issued.
+    pub invalid_range_weight: f64,
+    /// Added when the SSN's issuance era does not fit the date of birth.
+    pub issuance_mismatch_weight: f64,
+    /// Added when the SSN is on the death master file.
+    pub deceased_weight: f64,
+    /// Genuine death master file entries seeded alongside the population.
+    pub death_master_file_size: usize,
+}
+
+// ── Phase 4.15: Identity verification config ──────────────────────
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IdvVendorProfile {
+    pub name: String,
+    /// Chance the vendor catches a mismatched element; also how rarely it
+    /// returns partial matches on genuine data.
+    pub accuracy: f64,
+    /// Fee charged per applicant check, booked into opex.
+    pub cost_per_check: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IdvConfig {
+    pub enabled: bool,
+    /// Name of the vendor applicants are checked with.
+    pub vendor: String,
+    pub vendors: Vec<IdvVendorProfile>,
+}
+
+/// A cheap vendor that misses much, a standard one, and a premium one.
+fn default_idv_vendors() -> Vec<IdvVendorProfile> {
+    [("budget", 0.55, 0.40), ("standard", 0.85, 1.50), ("premium", 0.97, 4.00)]
+        .into_iter()
+        .map(|(name, accuracy, cost_per_check)| IdvVendorProfile {
+            name: name.into(),
+            accuracy,
+            cost_per_check,
+        })
+        .collect()
+}
+
+// ── Phase 4.16: SAR decision config ───────────────────────────────
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SarDecisionConfig {
+    pub enabled: bool,
+    /// Alert score at or above which a SAR is filed; the player's dial.
+    pub filing_threshold: f64,
+    /// Alert score at or above which the activity is genuinely reportable.
+    /// Filings below it are defensive; reportable alerts left unfiled past
+    /// the 30-day deadline are missed and surface at the next exam.
+    pub reportable_score: f64,
+    /// Investigation and narrative cost of one filing, booked into opex.
+    pub cost_per_filing: f64,
+}
+
+// ── Phase 4.17: Rule tuning config ────────────────────────────────
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RuleTuningConfig {
+    pub enabled: bool,
+    /// Ticks a tuning review takes from opening to approval.
+    pub review_ticks: Tick,
+    /// Analyst cost of one tuning review, booked into opex on approval.
+    pub review_cost: f64,
+}
+
+// ── Phase 4.18: Coverage assessment config ────────────────────────
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CoverageAssessmentConfig {
+    pub enabled: bool,
+    /// Ticks between assessments; each looks back over one interval.
+    pub interval_ticks: Tick,
+}
+
+// ── Phase 4.25: Time acceleration config ──────────────────────────
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TimeAccelerationConfig {
+    pub enabled: bool,
+    /// Every account runs daily up to and including this tick.
+    pub coarse_after_tick: Tick,
+    /// Ticks per coarse step: 7 for weekly, 30 for monthly.
+    pub coarse_step_ticks: Tick,
+    /// Composite risk tiers stepped coarsely. Customers in other tiers,
+    /// with an open alert, or receiving protected benefits stay daily.
+    pub coarse_risk_tiers: Vec<String>,
+}
+
+impl TimeAccelerationConfig {
+    /// Whether coarse accounts are processed this tick.
+    pub fn is_step_tick(&self, tick: Tick) -> bool {
+        tick > self.coarse_after_tick && (tick - self.coarse_after_tick).is_multiple_of(self.coarse_step_ticks)
+    }
+}
+
+// ── Phase 4.28: Event log retention config ────────────────────────
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EventLogRetentionConfig {
+    pub enabled: bool,
+    /// Trailing ticks kept as individual event_log rows.
+    pub detail_ticks: Tick,
+    /// How often older ticks are compacted into the archive.
+    pub compact_interval_ticks: Tick,
+    /// Also write each compacted tick as a JSON line under this directory.
+    pub export_dir: Option<String>,
+}
+
+// ── Phase 4.29: Complaint SLA policy config ───────────────────────
+
+/// A resolve deadline for the complaints in its scope. A field left as
+/// None matches every value.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SlaPolicyRule {
+    /// "low" | "standard" | "high" | "urgent"
+    pub priority: Option<String>,
+    pub product: Option<String>,
+    /// "phone" | "branch" | "online" | "regulator"
+    pub channel: Option<String>,
+    /// Ticks from opening until the complaint breaches.
+    pub resolve_days: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ComplaintSlaConfig {
+    /// Bank policy. The most specific rule in scope sets the deadline;
+    /// with none, the trigger's own `sla_resolve_days` applies.
+    pub policies: Vec<SlaPolicyRule>,
+    /// Regulatory maximums. Every one in scope caps the deadline, and
+    /// the player cannot set a policy that would exceed one.
+    pub regulatory_limits: Vec<SlaPolicyRule>,
+}
+
+/// Reg E error resolution for urgent disputes, and the CFPB's 15-day
+/// response window for complaints referred by a regulator.
+fn default_sla_regulatory_limits() -> Vec<SlaPolicyRule> {
+    vec![
+        SlaPolicyRule {
+            priority: Some("urgent".into()),
+            product: None,
+            channel: None,
+            resolve_days: 10,
+        },
+        SlaPolicyRule {
+            priority: None,
+            product: None,
+            channel: Some("regulator".into()),
+            resolve_days: 15,
+        },
+    ]
+}
+
+// ── Phase 4.30: Hardship program config ───────────────────────────
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HardshipConfig {
+    pub enabled: bool,
+    /// Life events that qualify a customer for the program.
+    pub eligible_life_events: Vec<String>,
+    /// Chance a qualifying customer takes up the offer.
+    pub enrollment_probability: f64,
+    pub duration_ticks: Tick,
+    /// Fee types waived while enrolled, as in FeeCharged ("overdraft").
+    pub waived_fee_types: Vec<String>,
+    /// Payment flexibility: no bank setoffs against an enrolled
+    /// customer's accounts.
+    pub suspend_setoffs: bool,
+    /// Taken off an enrolled customer's churn risk at each rescore.
+    pub churn_risk_relief: f64,
+    /// Satisfaction change on enrolling.
+    pub enrollment_satisfaction_delta: f64,
+}
+
+// ── Phase 4.31: Case document config ──────────────────────────────
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CaseDocumentConfig {
+    pub enabled: bool,
+    /// Chance each required document is never collected for its case.
+    pub missing_probability: f64,
+}
+
+// ── Phase 4.32: Contact preference and fatigue config ─────────────
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ContactFatigueConfig {
+    pub enabled: bool,
+    /// Share of customers who ask not to be contacted at onboarding.
+    pub do_not_contact_rate: f64,
+    /// Trailing window contacts are counted over for fatigue and caps.
+    pub window_ticks: Tick,
+    /// Response rate multiplier for each earlier contact in the window.
+    pub response_decay: f64,
+    /// Response rate multiplier when the campaign's channel is not the
+    /// customer's preferred one.
+    pub off_channel_response_factor: f64,
+    /// Contacts in the window a customer takes before each further one
+    /// costs satisfaction.
+    pub fatigue_threshold: u32,
+    pub fatigue_satisfaction_delta: f64,
+    /// Base response rates by campaign type, before fatigue.
+    pub offer_response_rate: f64,
+    pub survey_response_rate: f64,
+    pub collections_response_rate: f64,
+    /// Satisfaction survey: share of active customers sampled each run.
+    pub survey_interval_ticks: Tick,
+    pub survey_sample_rate: f64,
+    /// Collections calls to overdrawn customers.
+    pub collections_interval_ticks: Tick,
+}
+
+// ── Phase 4.33: Satisfaction dynamics config ──────────────────────
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SatisfactionDynamicsConfig {
+    pub enabled: bool,
+    /// Satisfaction each segment settles back to when nothing happens.
+    pub segment_baselines: HashMap<String, f64>,
+    /// Baseline for segments not listed.
+    pub default_baseline: f64,
+    /// Share of the gap to the baseline closed at each monthly pass.
+    pub monthly_reversion: f64,
+    /// Trailing window goodwill gestures count as repeats over.
+    pub goodwill_window_ticks: Tick,
+    /// Goodwill multiplier for each earlier gesture in the window.
+    pub goodwill_decay: f64,
+    /// Complaint priorities whose SLA breach scars the customer.
+    pub scar_priorities: Vec<String>,
+    /// Baseline lost for good with each scar.
+    pub scar_severity: f64,
+    /// Lowest a baseline can be scarred to.
+    pub baseline_floor: f64,
+}
+
+// ── Phase 4.34: Financial health config ───────────────────────────
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FinancialHealthConfig {
+    pub enabled: bool,
+    /// Trailing window each monthly score looks back over.
+    pub lookback_ticks: Tick,
+    /// Component weights; they sum to 1 so the score runs 0..1.
+    pub volatility_weight: f64,
+    pub overdraft_weight: f64,
+    pub payroll_weight: f64,
+    /// Daily net-flow volatility (relative to average daily inflow) at
+    /// which the volatility component bottoms out.
+    pub volatility_saturation: f64,
+    /// Overdraft fees in the window at which that component bottoms out.
+    pub overdraft_saturation: u32,
+    /// Offer types that extend credit and are gated on health.
+    pub credit_offer_types: Vec<String>,
+    /// Lowest score a credit offer goes to until the player changes it.
+    pub default_min_credit_score: f64,
+    /// Below this a customer is low-health: each credit offer made to
+    /// one adds `low_health_udaap_impact` to the UDAAP score.
+    pub low_health_threshold: f64,
+    pub low_health_udaap_impact: f64,
+    /// Monthly default probability on a credit line at a score of 0;
+    /// it falls with the square of the score's distance below 1.
+    pub max_monthly_default_rate: f64,
+    /// Share of the credit limit lost on default.
+    pub loss_given_default: f64,
+    /// Score lost per unit of high-risk spend share, as categorized by
+    /// transaction enrichment (Phase 4.35); nothing when enrichment is off.
+    pub high_risk_spend_penalty: f64,
+}
+
+// ── Phase 4.35: Transaction enrichment config ─────────────────────
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MerchantCategoryConfig {
+    pub category: String,
+    /// Share of merchants that really are in this category.
+    pub merchant_share: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TransactionEnrichmentConfig {
+    pub enabled: bool,
+    /// Chance the enrichment labels a merchant with its true category;
+    /// otherwise it picks one of the others.
+    pub accuracy: f64,
+    pub categories: Vec<MerchantCategoryConfig>,
+    /// Categories whose spend intensity the HIGH_RISK_MCC rule watches.
+    pub high_risk_categories: Vec<String>,
+    /// Trailing window spend intensity is measured over.
+    pub intensity_lookback_ticks: Tick,
+    /// Share of a customer's merchant spend in high-risk categories that
+    /// alerts, once that spend reaches `intensity_min_spend`.
+    pub intensity_threshold: f64,
+    pub intensity_min_spend: f64,
+    pub intensity_alert_score: f64,
+}
+
+/// The merchant category mix shared by the live and test configs.
+fn default_merchant_categories() -> Vec<MerchantCategoryConfig> {
+    [
+        ("groceries", 0.22),
+        ("dining", 0.16),
+        ("retail", 0.18),
+        ("utilities", 0.10),
+        ("rent", 0.06),
+        ("travel", 0.06),
+        ("healthcare", 0.06),
+        ("entertainment", 0.08),
+        ("gambling", 0.04),
+        ("crypto", 0.04),
+    ]
+    .into_iter()
+    .map(|(category, merchant_share)| MerchantCategoryConfig {
+        category: category.into(),
+        merchant_share,
+    })
+    .collect()
+}
+
+// ── Phase 4.36: Remittance corridor config ───────────────────────
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RemittanceCorridorConfig {
+    /// Destination country (ISO 3166 alpha-2).
+    pub country: String,
+    /// Corridor AML risk rating: low | medium | high.
+    pub risk_rating: String,
+    /// Typical amount of one family remittance down the corridor.
+    pub typical_amount: f64,
+    /// Ticks between a legitimate sender's remittances.
+    pub interval_ticks: Tick,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RemittanceConfig {
+    pub enabled: bool,
+    pub corridors: Vec<RemittanceCorridorConfig>,
+    /// Share of international customers who remit home. Customers whose
+    /// citizenship has no corridor never remit.
+    pub participation_rate: f64,
+    /// Share of remitters who are really layering funds through the
+    /// account instead.
+    pub layering_rate: f64,
+    /// Family beneficiaries a legitimate sender pays, at most.
+    pub max_family_beneficiaries: u32,
+    /// Ticks between layering cycles; each cycle takes in a wire of
+    /// `layering_inflow_multiple` times the home corridor's typical
+    /// amount and fans it out to `layering_fanout` fresh beneficiaries
+    /// across medium- and high-risk corridors.
+    pub layering_interval_ticks: Tick,
+    pub layering_inflow_multiple: f64,
+    pub layering_fanout: u32,
+    /// Trailing window REMIT_LAYERING looks at.
+    pub lookback_ticks: Tick,
+    /// Fan-out that alerts: this many distinct beneficiaries, or this
+    /// many distinct corridors...
+    pub min_beneficiaries: i64,
+    pub min_corridors: i64,
+    /// ...while remittances out are at least this share of all credits
+    /// in over the window.
+    pub pass_through_threshold: f64,
+    pub alert_score: f64,
+    /// Added to the alert score for the riskiest corridor used.
+    pub medium_risk_uplift: f64,
+    pub high_risk_uplift: f64,
+}
+
+/// The corridor table shared by the live and test configs. Sanctioned
+/// destinations have no corridor.
+fn default_remittance_corridors() -> Vec<RemittanceCorridorConfig> {
+    [
+        ("MX", "medium", 350.0, 30),
+        ("IN", "low", 450.0, 30),
+        ("PH", "medium", 300.0, 30),
+        ("CN", "medium", 600.0, 60),
+        ("BR", "medium", 350.0, 30),
+        ("GB", "low", 700.0, 60),
+        ("CA", "low", 500.0, 60),
+        ("DE", "low", 650.0, 60),
+        ("NG", "high", 400.0, 30),
+        ("AE", "high", 1200.0, 30),
+        ("RU", "high", 500.0, 30),
+    ]
+    .into_iter()
+    .map(|(country, risk_rating, typical_amount, interval_ticks)| RemittanceCorridorConfig {
+        country: country.into(),
+        risk_rating: risk_rating.into(),
+        typical_amount,
+        interval_ticks,
+    })
+    .collect()
+}
+
+// ── Phase 4.37: Visa expiry config ───────────────────────────────
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VisaValidityConfig {
+    pub visa_status: String,
+    /// How long a visa of this type is issued for.
+    pub validity_ticks: Tick,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VisaExpiryConfig {
+    pub enabled: bool,
+    pub validity: Vec<VisaValidityConfig>,
+    /// Ticks before expiry the bank opens a documentation refresh.
+    pub refresh_lead_ticks: Tick,
+    /// Share of holders whose visa is renewed; the rest never send new
+    /// documents.
+    pub renewal_rate: f64,
+    /// Renewing customers send their documents at a uniform point from
+    /// the refresh request until this long after expiry.
+    pub late_submission_ticks: Tick,
+    /// Ticks the bank takes to verify submitted documents.
+    pub processing_ticks: Tick,
+    /// Cost of working one refresh, booked to opex on completion.
+    pub refresh_cost: f64,
+    /// Restriction once documentation has been lapsed `grace_ticks`:
+    /// none | outbound | full. The player can change both.
+    pub restriction_level: String,
+    pub grace_ticks: Tick,
+    /// Satisfaction hit when a restriction lands, by level.
+    pub outbound_satisfaction_delta: f64,
+    pub full_satisfaction_delta: f64,
+    /// Further hit when the customer's documents were already with the
+    /// bank.
+    pub pending_satisfaction_delta: f64,
+    /// Satisfaction hit per transaction the restriction blocks.
+    pub blocked_satisfaction_delta: f64,
+}
+
+/// Visa validity periods shared by the live and test configs.
+fn default_visa_validity() -> Vec<VisaValidityConfig> {
+    [("H1B", 1095), ("L1", 730), ("F1", 365), ("B1/B2", 180)]
+        .into_iter()
+        .map(|(visa_status, validity_ticks)| VisaValidityConfig {
+            visa_status: visa_status.into(),
+            validity_ticks,
+        })
+        .collect()
+}
+
+// ── Phase 4.38: Adverse media config ─────────────────────────────
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AdverseMediaCategoryConfig {
+    pub category: String,
+    /// How damaging a story of this kind is, 0..1.
+    pub severity: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AdverseMediaConfig {
+    pub enabled: bool,
+    pub categories: Vec<AdverseMediaCategoryConfig>,
+    /// Mean stories the feed publishes per tick.
+    pub articles_per_tick: f64,
+    /// Share of stories about one of our customers, and about a business
+    /// our customers have paid recently. The rest are about strangers who
+    /// may share a name with either.
+    pub customer_subject_rate: f64,
+    pub counterparty_subject_rate: f64,
+    /// Share of stories about strangers that happen to share the name of
+    /// a customer or counterparty.
+    pub namesake_rate: f64,
+    /// Share of stories about a customer that print only their first
+    /// initial.
+    pub name_variant_rate: f64,
+    /// Share of stories about a person that give their year of birth.
+    pub detail_rate: f64,
+    /// Counterparties paid within this many ticks are screened.
+    pub counterparty_lookback_ticks: Tick,
+    /// Name similarity below which a customer or counterparty is not a
+    /// candidate at all.
+    pub candidate_name_score: f64,
+    /// Match score weights for name, country and year of birth. A detail
+    /// the story leaves out earns half its weight.
+    pub name_weight: f64,
+    pub country_weight: f64,
+    pub birth_year_weight: f64,
+    /// Match score at which a hit is escalated into the customer's risk
+    /// rating.
+    pub match_threshold: f64,
+    /// A customer hit at least this strong about a story at least this
+    /// severe raises an alert and brings their EDD review forward to
+    /// `edd_due_ticks` out.
+    pub edd_match_score: f64,
+    pub edd_severity: f64,
+    pub edd_due_ticks: Tick,
+    /// Risk impact of an escalated hit per unit of severity x score; a
+    /// hit through a counterparty carries this share of it.
+    pub risk_impact_scale: f64,
+    pub counterparty_impact_share: f64,
+}
+
+/// Story categories shared by the live and test configs.
+fn default_adverse_media_categories() -> Vec<AdverseMediaCategoryConfig> {
+    [
+        ("fraud", 0.70),
+        ("corruption", 0.80),
+        ("money_laundering", 0.90),
+        ("sanctions_evasion", 1.00),
+        ("tax_evasion", 0.50),
+        ("organised_crime", 0.90),
+    ]
+    .into_iter()
+    .map(|(category, severity)| AdverseMediaCategoryConfig {
+        category: category.into(),
+        severity,
+    })
+    .collect()
+}
+
+// ── Phase 4.39: Correspondent banking config ─────────────────────
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RespondentBankConfig {
+    pub bank_id: String,
+    pub country: String,
+    /// low | medium | high
+    pub risk_rating: String,
+    /// Mean wires the respondent clears through us per tick.
+    pub wires_per_tick: f64,
+    pub typical_amount: f64,
+    /// Ordering customers the respondent declares to us.
+    pub declared_originators: u32,
+    /// Ground truth: downstream banks quietly clearing through the
+    /// respondent's account, and their share of its wires.
+    pub nested_banks: u32,
+    pub nested_share: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CorrespondentDueDiligenceConfig {
+    /// low | medium | high
+    pub risk_rating: String,
+    /// Ticks between due diligence refreshes.
+    pub interval_ticks: Tick,
+    /// Cost of working one refresh, booked to opex on completion.
+    pub cost: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CorrespondentBankingConfig {
+    pub enabled: bool,
+    pub respondents: Vec<RespondentBankConfig>,
+    /// Countries nested banks sit in, assigned round-robin.
+    pub nested_countries: Vec<String>,
+    /// Customers behind each nested bank.
+    pub nested_originators: u32,
+    /// Share of nested wires with the ordering institution stripped, so
+    /// they read as the respondent's own customers.
+    pub stripping_rate: f64,
+    /// Clearing fee earned per wire.
+    pub clearing_fee: f64,
+    pub due_diligence: Vec<CorrespondentDueDiligenceConfig>,
+    /// Share of questionnaires the respondent returns, within
+    /// `response_ticks` of the request.
+    pub response_rate: f64,
+    pub response_ticks: Tick,
+    /// A refresh still open this long after the request is overdue and the
+    /// relationship is suspended until one completes.
+    pub overdue_ticks: Tick,
+    /// Share of questionnaires from a respondent with nested banks that
+    /// disclose them.
+    pub disclosure_rate: f64,
+    /// CORR_NESTED: window of wires reviewed per respondent. Alerts when
+    /// third-party ordering institutions reach `min_third_party_share` of
+    /// wires or distinct originators reach `originator_excess` times the
+    /// declared base.
+    pub lookback_ticks: Tick,
+    pub min_third_party_share: f64,
+    pub originator_excess: f64,
+    pub alert_score: f64,
+    /// Score uplifts for medium- and high-risk respondents, and for nested
+    /// activity the respondent never disclosed.
+    pub medium_risk_uplift: f64,
+    pub high_risk_uplift: f64,
+    pub undisclosed_uplift: f64,
+}
+
+/// Respondent banks shared by the live and test configs.
+fn default_respondent_banks() -> Vec<RespondentBankConfig> {
+    [
+        ("RESP-GB-01", "GB", "low", 4.0, 25_000.0, 40, 0, 0.0),
+        ("RESP-MX-01", "MX", "medium", 3.0, 15_000.0, 30, 1, 0.15),
+        ("RESP-AE-01", "AE", "medium", 2.0, 40_000.0, 20, 2, 0.30),
+        ("RESP-PA-01", "PA", "high", 2.0, 30_000.0, 15, 3, 0.50),
+    ]
+    .into_iter()
+    .map(
+        |(bank_id, country, risk_rating, wires_per_tick, typical_amount, declared_originators, nested_banks, nested_share)| {
+            RespondentBankConfig {
+                bank_id: bank_id.into(),
+                country: country.into(),
+                risk_rating: risk_rating.into(),
+                wires_per_tick,
+                typical_amount,
+                declared_originators,
+                nested_banks,
+                nested_share,
+            }
+        },
+    )
+    .collect()
+}
+
+/// Refreshes yearly for low-risk respondents down to quarterly for high.
+fn default_correspondent_due_diligence() -> Vec<CorrespondentDueDiligenceConfig> {
+    [("low", 365, 500.0), ("medium", 180, 1_500.0), ("high", 90, 4_000.0)]
+        .into_iter()
+        .map(|(risk_rating, interval_ticks, cost)| CorrespondentDueDiligenceConfig {
+            risk_rating: risk_rating.into(),
+            interval_ticks,
+            cost,
+        })
+        .collect()
+}
+
+// ── Phase 4.40: Cross-border fan-in/fan-out config ───────────────
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CrossBorderConfig {
+    pub enabled: bool,
+    /// Share of international customers whose account is a funnel:
+    /// collecting wires from many foreign senders and dispersing them
+    /// domestically.
+    pub funnel_rate: f64,
+    /// Share of the rest who receive occasional support from home.
+    pub inbound_rate: f64,
+    /// Countries funnel senders wire from.
+    pub source_countries: Vec<String>,
+    /// Ticks between funnel collections; each brings in `funnel_senders`
+    /// wires of around `funnel_wire_amount` from fresh foreign senders.
+    pub funnel_interval_ticks: Tick,
+    pub funnel_senders: u32,
+    pub funnel_wire_amount: f64,
+    /// Ticks a funnel holds a collection before dispersing it, less
+    /// `funnel_retention`, across `funnel_payees` fresh domestic payees.
+    pub dispersal_lag_ticks: Tick,
+    pub funnel_retention: f64,
+    pub funnel_payees: u32,
+    /// Ticks between support wires home, of around `inbound_amount`
+    /// from one of at most `max_inbound_senders` relatives, with
+    /// `inbound_forward_share` passed on to family here.
+    pub inbound_interval_ticks: Tick,
+    pub inbound_amount: f64,
+    pub max_inbound_senders: u32,
+    pub inbound_forward_share: f64,
+    /// XB_FUNNEL: rolling window reviewed per customer. Alerts on at
+    /// least `min_senders` foreign senders in and `min_payees` domestic
+    /// payees out, with at least `dispersal_ratio` of the inflow gone
+    /// back out.
+    pub lookback_ticks: Tick,
+    pub min_senders: i64,
+    pub min_payees: i64,
+    pub dispersal_ratio: f64,
+    pub alert_score: f64,
+    /// Added to the alert score for the riskiest source country on the
+    /// high-risk jurisdiction list: 'elevated'/'medium', or
+    /// 'high'/'critical'.
+    pub elevated_uplift: f64,
+    pub high_risk_uplift: f64,
+}
+
+/// Funnel source countries shared by the live and test configs: a mix of
+/// ordinary corridors and listed jurisdictions.
+fn default_cross_border_source_countries() -> Vec<String> {
+    ["MX", "CN", "NG", "AE", "GB", "PA", "CY", "VE", "RU"]
+        .into_iter()
+        .map(String::from)
+        .collect()
+}
+
+// ── Phase 4.41: Business-day calendar config ─────────────────────
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BusinessCalendarConfig {
+    pub enabled: bool,
+    /// Date of tick 1, `YYYY-MM-DD`.
+    pub start_date: String,
+    /// The bank's time zone, for display, and its UTC offset. Rail
+    /// cutoffs are set in Eastern time and shift by the difference.
+    pub time_zone: String,
+    pub utc_offset_hours: i64,
+    /// Days the bank is closed every week (`Sat`, `Sun`, ...).
+    pub weekend_days: Vec<String>,
+    /// Close on observed US federal holidays.
+    pub federal_holidays: bool,
+    /// Further closures, `YYYY-MM-DD`.
+    pub extra_holidays: Vec<String>,
+}
+
+// ── Phase 4.42: Feature flags ────────────────────────────────────
+
+/// Per-run switches for ablation studies; see `feature_flags`.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct FeatureFlagsConfig {
+    /// Names of subsystems (`offer`, `card_dispute`, ...) left out of
+    /// the run.
+    #[serde(default)]
+    pub disabled_subsystems: Vec<String>,
+    /// Phase 4.77: if set, the only subsystems besides the core ones
+    /// that run. One also listed as disabled stays off.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub enabled_subsystems: Option<Vec<String>>,
+}
+
+// ── Phase 4.46: Command authorization ────────────────────────────
+
+/// Which seat may send what; see `authorization`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AuthorizationConfig {
+    pub enabled: bool,
+    /// Role of a command that names none.
+    pub default_role: Role,
+    /// Largest complaint refund an analyst may write off.
+    pub analyst_refund_limit: f64,
+    /// Largest refund a manager may write off; above it needs the CCO.
+    pub manager_refund_limit: f64,
+    /// Role needed to set a risk dial outside its comfort zone.
+    pub dial_outside_comfort_zone: Role,
+    /// Lowest role for a command, by `cmd`. Unlisted commands are open
+    /// to analysts.
+    #[serde(default)]
+    pub command_roles: HashMap<String, Role>,
+}
+
+// ── Phase 4.52: Deposit run config ───────────────────────────────
+
+/// A confidence shock and the run on deposits it sets off; see
+/// `deposit_run`. Rates are annual fractions, like the base rate.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DepositRunConfig {
+    pub enabled: bool,
+    /// Tick a scripted shock hits, if any.
+    pub shock_tick: Option<Tick>,
+    /// A shock also hits when the reputation score falls below this.
+    pub reputation_trigger: f64,
+    /// Ticks after a run ends before another can start.
+    pub cooldown_ticks: Tick,
+    /// Balance per account covered by deposit insurance.
+    pub insured_limit: f64,
+    /// Share of uninsured deposits leaving on the first tick of a run of
+    /// severity 1.
+    pub initial_run_rate: f64,
+    /// Growth of the run rate per tick at severity 1 with no premium.
+    pub acceleration: f64,
+    pub max_run_rate: f64,
+    /// A run is over once its rate falls below this.
+    pub calm_run_rate: f64,
+    /// Chance an insured depositor empties their account in a tick, as a
+    /// share of the run rate.
+    pub insured_flight_share: f64,
+    /// Each point (0.01) of deposit rate premium divides the run's growth
+    /// by 1 + this.
+    pub rate_sensitivity: f64,
+    /// Highest premium the player may pay over the usual deposit rate.
+    pub max_rate_premium: f64,
+    /// Liquid assets and capital held against deposits outside a run.
+    pub liquid_asset_ratio: f64,
+    pub capital_ratio: f64,
+    /// Liquidity coverage and capital ratio below which a run feeds on
+    /// itself.
+    pub min_liquidity_coverage: f64,
+    pub min_capital_ratio: f64,
+    /// Reputation score that neither calms nor feeds a run.
+    pub neutral_reputation: f64,
+    /// Severity added by each driver at its worst.
+    pub reputation_weight: f64,
+    pub liquidity_weight: f64,
+    pub capital_weight: f64,
+    /// Most the discount window lends, as a share of deposits.
+    pub emergency_capacity: f64,
+    /// Interest on discount window credit, and on the penalty credit
+    /// that covers a shortfall.
+    pub emergency_rate: f64,
+    pub penalty_rate: f64,
+}
+
+// ── Phase 4.53: Business email compromise config ─────────────────
+
+/// Spoofed invoices sent to business customers and the fraud desk's
+/// answer to the wires they pay; see `bec`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BecConfig {
+    pub enabled: bool,
+    /// Segments whose customers are businesses paying invoices by wire.
+    pub segments: Vec<String>,
+    /// Chance per business customer per tick of a spoofed invoice
+    /// arriving, and of the customer paying it.
+    pub spoofed_invoice_rate: f64,
+    pub fall_rate: f64,
+    /// Chance per tick of a genuine invoice, from one of the customer's
+    /// `regular_vendors` or, `new_vendor_share` of the time, from a
+    /// vendor never paid before.
+    pub vendor_invoice_rate: f64,
+    pub regular_vendors: u32,
+    pub new_vendor_share: f64,
+    /// Invoice amounts as shares of the paying account's balance; none
+    /// smaller than `min_invoice` is paid.
+    pub spoofed_share_min: f64,
+    pub spoofed_share_max: f64,
+    pub vendor_share_min: f64,
+    pub vendor_share_max: f64,
+    pub min_invoice: f64,
+    /// BEC_NEW_PAYEE: a wire to a payee the account has not paid in the
+    /// lookback, at least `amount_multiple` times its largest debit there.
+    /// Scores `base_score` at the multiple, plus `score_per_doubling` for
+    /// each doubling beyond.
+    pub lookback_ticks: Tick,
+    pub amount_multiple: f64,
+    pub base_score: f64,
+    pub score_per_doubling: f64,
+    /// Chance a recall on the day of the wire gets the money back,
+    /// multiplied by `recall_decay` for each tick of delay; none once
+    /// `recall_window_ticks` have passed.
+    pub recall_recovery_rate: f64,
+    pub recall_decay: f64,
+    pub recall_window_ticks: Tick,
+    /// Ticks the fraud desk takes to recall an alerted wire on its own;
+    /// `None` leaves every recall to the player.
+    pub desk_recall_ticks: Option<Tick>,
+}
+
+// ── Phase 4.54: Check fraud config ───────────────────────────────
+
+/// Checks written by business customers and deposited at the ATM, the
+/// washed and counterfeit items among them, and the return deadlines
+/// that decide who eats the loss; see `check_fraud`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CheckFraudConfig {
+    pub enabled: bool,
+    /// Segments whose customers write checks.
+    pub writer_segments: Vec<String>,
+    /// Chance per writer per tick of a check being presented, for a share
+    /// of the balance; none smaller than `min_check`.
+    pub check_rate: f64,
+    pub check_share_min: f64,
+    pub check_share_max: f64,
+    pub min_check: f64,
+    /// Serial of the first check in every checkbook.
+    pub first_serial: i64,
+    /// Chance per writer per tick of a paid check coming back washed:
+    /// payee altered and amount raised by a factor in the range.
+    pub washed_rate: f64,
+    pub washed_raise_min: f64,
+    pub washed_raise_max: f64,
+    /// Share of washed checks that copy a check already paid; the rest
+    /// are blanks stolen from further on in the book.
+    pub washed_copy_share: f64,
+    /// Chance per account per tick of depositing a genuine check, and of a
+    /// counterfeit one being deposited at the ATM by a fraud ring.
+    pub deposit_rate: f64,
+    pub counterfeit_rate: f64,
+    pub deposit_amount_min: f64,
+    pub deposit_amount_max: f64,
+    pub counterfeit_amount_min: f64,
+    pub counterfeit_amount_max: f64,
+    /// Serials genuine deposited checks fall between; this share of
+    /// counterfeits are printed with one outside it.
+    pub serial_min: i64,
+    pub serial_max: i64,
+    pub counterfeit_off_range_share: f64,
+    /// SERIAL_OUT_OF_RANGE: a check drawn on a writer more than this many
+    /// serials past the last one the writer used.
+    pub serial_tolerance: i64,
+    /// Ticks after a counterfeit deposit before the ring cashes it out.
+    pub cash_out_ticks: Tick,
+    /// Alerts the check desk can review per tick, oldest first.
+    pub review_capacity: usize,
+    /// Paying bank's midnight deadline: ticks after presentment to return
+    /// an item drawn on a customer. Past it the bank owns the loss.
+    pub paying_deadline_ticks: Tick,
+    /// Ticks the other bank takes to return a counterfeit deposited here,
+    /// and the chance it misses its own deadline and eats the loss.
+    pub deposit_return_ticks: Tick,
+    pub late_return_rate: f64,
+    /// A fraud wave multiplies the washed and counterfeit rates for
+    /// `wave_ticks` from `wave_start_tick`.
+    pub wave_start_tick: Option<Tick>,
+    pub wave_ticks: Tick,
+    pub wave_multiplier: f64,
+    /// Share of genuine deposits made at a branch rather than the ATM.
+    pub branch_share: f64,
+}
+
+// ── Phase 4.55: Deposit hold config ──────────────────────────────
+
+/// One funds-availability rule: ticks a check deposit's funds above the
+/// next-day amount stay on hold. `class` is the deposit channel ("atm" |
+/// "branch") or an exception ("new_account" | "large_item"), `risk`
+/// "standard" | "elevated"; `None` matches all.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HoldRule {
+    pub class: Option<String>,
+    pub risk: Option<String>,
+    pub hold_ticks: Tick,
+}
+
+/// Reg CC style funds availability for check deposits; see
+/// `deposit_holds`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DepositHoldConfig {
+    pub enabled: bool,
+    /// Available the tick after deposit whatever the hold.
+    pub next_day_amount: f64,
+    /// Bank policy. For each class a deposit falls in, the most specific
+    /// rule in scope sets the hold; the longest of those applies.
+    pub policies: Vec<HoldRule>,
+    /// Statutory maximums. Every one in scope caps the hold, and the
+    /// player cannot set a policy that would exceed one.
+    pub statutory_limits: Vec<HoldRule>,
+    /// Accounts opened fewer ticks ago than this are new accounts.
+    pub new_account_ticks: Tick,
+    /// Deposits above this are large items.
+    pub large_item_threshold: f64,
+    /// An account with a deposit returned in this many ticks is
+    /// elevated risk.
+    pub risk_lookback_ticks: Tick,
+    /// How much a hold hurts customers in each segment; a hold's
+    /// pressure is its ticks times this (1.0 for segments not listed).
+    pub liquidity_sensitivity: HashMap<String, f64>,
+    /// Satisfaction lost per unit of hold pressure.
+    pub satisfaction_per_pressure: f64,
+}
+
+/// Bank hold policy: a day for most check deposits, two at the ATM, and
+/// longer for elevated risk and the exception holds.
+fn default_hold_policies() -> Vec<HoldRule> {
+    let rule = |class: Option<&str>, risk: Option<&str>, hold_ticks| HoldRule {
+        class: class.map(Into::into),
+        risk: risk.map(Into::into),
+        hold_ticks,
+    };
+    vec![
+        rule(None, None, 1),
+        rule(Some("atm"), None, 2),
+        rule(Some("atm"), Some("elevated"), 5),
+        rule(Some("branch"), Some("elevated"), 5),
+        rule(Some("new_account"), None, 5),
+        rule(Some("large_item"), None, 5),
+    ]
+}
+
+/// Reg CC: checks available by the second business day, and no
+/// exception hold longer than a reasonable period of five more.
+fn default_hold_statutory_limits() -> Vec<HoldRule> {
+    vec![
+        HoldRule { class: Some("atm".into()), risk: Some("standard".into()), hold_ticks: 2 },
+        HoldRule { class: Some("branch".into()), risk: Some("standard".into()), hold_ticks: 2 },
+        HoldRule { class: None, risk: None, hold_ticks: 7 },
+    ]
+}
+
+// ── Phase 4.57: Event stream config ──────────────────────────────
+
+/// Appends every event-log entry to a JSONL file or named pipe as it is
+/// logged; see `event_stream`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EventStreamConfig {
+    pub enabled: bool,
+    pub path: String,
+    /// Start a new file once the current one would grow past this many
+    /// bytes; None never rotates. Named pipes never rotate.
+    pub rotate_bytes: Option<u64>,
+    /// Rotated files kept as `<path>.1` (newest) to `<path>.<n>`.
+    pub keep_files: usize,
+}
+
+// ── Phase 4.59: Evidence bundle config ───────────────────────────
+
+/// What goes into the evidence bundle kept with each SAR and dispute
+/// filing; see `evidence_bundle`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EvidenceBundleConfig {
+    pub enabled: bool,
+    /// Ticks of account activity before the alert or dispute collected.
+    pub lookback_ticks: Tick,
+    /// Most transactions listed in a bundle, largest first; the window's
+    /// count and total cover all of them.
+    pub max_transactions: usize,
+    /// Share of quality checks a bundle must pass; below it the filing
+    /// is deficient and exams cite it.
+    pub min_quality: f64,
+}
+
+// ── Phase 4.60: Alert grouping config ────────────────────────────
+
+/// Groups AML alerts into investigation cases; see `alert_cases`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AlertGroupingConfig {
+    pub enabled: bool,
+    /// A new alert joins an open case in its cluster whose last alert
+    /// came at most this many ticks before it.
+    pub window_ticks: Tick,
+    /// Windows for particular alert types, in place of `window_ticks`.
+    pub type_windows: HashMap<String, Tick>,
+    /// Customers linked, directly or through others, by relationships at
+    /// least this strong share cases; None groups by customer alone.
+    pub min_relationship_strength: Option<f64>,
+}
+
+// ── Phase 4.61: Operational risk config ──────────────────────────
+
+/// The loss event record and the operational risk capital estimated
+/// from it; see `operational_risk`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OperationalRiskConfig {
+    pub enabled: bool,
+    /// Ticks of loss history the loss component averages over.
+    pub lookback_ticks: Tick,
+    /// Share of annual gross income held as the business indicator
+    /// component.
+    pub bic_coefficient: f64,
+    /// Multiple of average annual losses taken as the loss component.
+    pub loss_multiplier: f64,
+}
+
+// ── Phase 4.62: Quarter close config ─────────────────────────────
+
+/// Accruals, checklist and sign-off deadline of the quarter-end close;
+/// see `quarter_close`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QuarterCloseConfig {
+    pub enabled: bool,
+    /// Ticks after the quarter's end the checklist may take before the
+    /// close is overdue and adjustments to it are late.
+    pub signoff_ticks: Tick,
+    /// Share of outstanding provisional credit reserved against disputes
+    /// the bank may lose.
+    pub provisional_credit_reserve_rate: f64,
+}
+
+// ── Phase 4.63: Opex allocation config ───────────────────────────
+
+/// A shared cost pool and the driver that spreads it over segments.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OpexPoolConfig {
+    pub pool: String,
+    /// Quarterly cost for the whole bank; a sampled run carries its share.
+    pub quarterly_cost: f64,
+    /// 'customers' | 'transactions' | 'headcount'
+    pub driver: String,
+    /// For 'headcount': the desk's heads working each segment. Segments
+    /// not listed have none.
+    pub headcount: HashMap<String, f64>,
+}
+
+/// Spreads shared opex into segment P&L by pool in place of the flat
+/// customer-share charge; see `opex_allocation`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OpexAllocationConfig {
+    pub enabled: bool,
+    pub pools: Vec<OpexPoolConfig>,
+}
+
+impl OpexAllocationConfig {
+    /// Reject unknown drivers, heads in segments the scenario does not
+    /// have, and negative costs or heads, which would otherwise skew
+    /// every segment's margin without a trace.
+    pub fn validate(&self, segments: &HashMap<String, SegmentConfig>) -> anyhow::Result<()> {
+        for pool in &self.pools {
+            if !crate::opex_allocation::DRIVERS.contains(&pool.driver.as_str()) {
+                anyhow::bail!("opex pool {}: unknown driver {}", pool.pool, pool.driver);
+            }
+            if !(pool.quarterly_cost.is_finite() && pool.quarterly_cost >= 0.0) {
+                anyhow::bail!("opex pool {}: quarterly_cost must be a non-negative number", pool.pool);
+            }
+            for (segment, heads) in &pool.headcount {
+                if !segments.contains_key(segment) {
+                    anyhow::bail!("opex pool {}: unknown segment {segment}", pool.pool);
+                }
+                if !(heads.is_finite() && *heads >= 0.0) {
+                    anyhow::bail!("opex pool {}.{segment}: headcount must be a non-negative number", pool.pool);
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+// ── Phase 4.64: Churn evaluation config ──────────────────────────
+
+/// Judging churn predictions once their horizons elapse; see
+/// `churn_evaluation`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChurnEvaluationConfig {
+    pub enabled: bool,
+    /// Scored customers a segment needs before its evaluation is held to
+    /// the baseline, or can set it.
+    pub min_customers: u32,
+    /// Fall in AUC from the segment's baseline that counts as drift.
+    pub auc_drop_tolerance: f64,
+    /// Rise in Brier score from the baseline that counts as drift.
+    pub brier_rise_tolerance: f64,
+}
+
+// ── Phase 4.65: Complaint quality config ─────────────────────────
+
+/// Scoring each complaint closure and the customer's response to it; see
+/// `complaint_quality`. The three weights should sum to 1.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ComplaintQualityConfig {
+    pub enabled: bool,
+    /// Skill of the analysts working the desk, 0–1.
+    pub analyst_skill: f64,
+    /// Ticks a thorough response takes; closing sooner scores less.
+    pub thorough_handling_ticks: u64,
+    /// Resolution codes that suit each issue. An issue not listed takes
+    /// any code.
+    pub appropriate_resolutions: HashMap<String, Vec<String>>,
+    pub skill_weight: f64,
+    pub time_weight: f64,
+    pub appropriateness_weight: f64,
+    /// Quality under which a closure counts as poor in the desk report.
+    pub poor_quality_threshold: f64,
+    /// Chance a closure of quality 0 is reopened; falls linearly to 0 at
+    /// quality 1.
+    pub max_reopen_probability: f64,
+    /// Chance a closure of quality 0 is escalated to the regulator.
+    pub max_escalation_probability: f64,
+    /// UDAAP score added per escalation.
+    pub escalation_udaap_impact: f64,
+    /// Ticks between desk performance reports.
+    pub report_interval_ticks: u64,
+}
+
+// ── Phase 4.69: Stress index config ─────────────────────────────
+
+/// The composite stress index; see `stress_index`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StressIndexConfig {
+    pub enabled: bool,
+    /// Open complaints per 1,000 active customers.
+    pub backlog_per_1k: PressureBand,
+    /// Free capital over deposits.
+    pub capital_ratio: PressureBand,
+    /// Reputation score, 0–100.
+    pub reputation: PressureBand,
+    /// Branch customers over what their staff can serve.
+    pub staffing_utilization: PressureBand,
+    /// Open fair lending findings plus recent exam findings.
+    pub open_findings: PressureBand,
+    pub weights: StressWeights,
+    /// Exam findings count as open for this many ticks after they are
+    /// raised.
+    pub finding_lookback_ticks: u64,
+    /// Resilience under which the bank is strained.
+    pub strained_below: f64,
+    /// Resilience under which the bank is critical.
+    pub critical_below: f64,
+}
+
+/// A reading's calm value, where it puts no pressure on, and its edge,
+/// where the pressure is full. `edge` may sit either side of `calm`.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct PressureBand {
+    pub calm: f64,
+    pub edge: f64,
+}
+
+/// Relative weight of each component; need not sum to 1.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StressWeights {
+    pub backlog: f64,
+    pub capital: f64,
+    pub reputation: f64,
+    pub staffing: f64,
+    pub findings: f64,
+}
+
+// ── Phase 4.68: Scenario script config ──────────────────────────
+
+/// Rhai scripts run at the end of every tick; see `scenario_script`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScenarioScriptConfig {
+    pub enabled: bool,
+    /// Run in this order each tick.
+    pub scripts: Vec<ScenarioScript>,
+    /// Rhai operations one script may take in a tick before it is
+    /// stopped.
+    pub max_operations: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScenarioScript {
+    /// Names the script in events and in its `once` keys.
+    pub name: String,
+    pub source: String,
+}
+
+impl ScenarioScript {
+    /// Every `*.rhai` file in `dir` by file name, each named by its stem.
+    /// A missing directory holds no scripts.
+    pub fn load_dir(dir: &str) -> anyhow::Result<Vec<Self>> {
+        let Ok(entries) = std::fs::read_dir(dir) else {
+            return Ok(Vec::new());
+        };
+        let mut paths = Vec::new();
+        for entry in entries {
+            let path = entry?.path();
+            if path.extension().is_some_and(|ext| ext == "rhai") {
+                paths.push(path);
+            }
+        }
+        paths.sort();
+        paths
+            .into_iter()
+            .map(|path| {
+                let source = std::fs::read_to_string(&path)
+                    .map_err(|e| anyhow::anyhow!("Cannot read {}: {e}", path.display()))?;
+                let name = path.file_stem().unwrap_or_default().to_string_lossy().into_owned();
+                Ok(Self { name, source })
+            })
+            .collect()
+    }
+}
+
+// ── Phase 4.73: Alert QA config ─────────────────────────────────
+
+/// Re-reviewing a sample of SAR decisions; see `alert_qa`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AlertQaConfig {
+    pub enabled: bool,
+    /// Ticks between QA cycles; each samples the decisions since the last.
+    pub interval_ticks: Tick,
+    /// Share of a cycle's decisions QA sets out to re-review.
+    pub sample_rate: f64,
+    /// Most reviews QA can do in a cycle.
+    pub review_capacity: usize,
+    /// Chance a QA reviewer judges correctly whether a filing was called
+    /// for.
+    pub reviewer_accuracy: f64,
+    /// QA time per review, booked into opex.
+    pub cost_per_review: f64,
+    /// Disagreement rate on an alert type's reviews in one cycle at which
+    /// retraining opens for it.
+    pub retraining_threshold: f64,
+    /// Fewest reviews of an alert type in a cycle to judge it on.
+    pub retraining_min_reviews: usize,
+    /// Ticks a retraining work item takes, and its cost on completion.
+    pub retraining_ticks: Tick,
+    pub retraining_cost: f64,
+}
+
+// ── Phase 4.77: Macro regime config ─────────────────────────────
+
+/// Where the economy starts a run; see `scenario`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct MacroRegimeConfig {
+    pub economic_phase: EconomicPhase,
+    /// Annual fraction, kept between `MIN_BASE_RATE` and `MAX_BASE_RATE`.
+    pub base_rate: f64,
+    /// Ticks before the phase first turns.
+    pub phase_ticks: Tick,
+}
+
+impl Default for MacroRegimeConfig {
+    fn default() -> Self {
+        Self {
+            economic_phase: EconomicPhase::Expansion,
+            base_rate: 0.05,
+            phase_ticks: 360, // 4 quarters to start
+        }
+    }
+}
+
+// ── Phase 4.80: Staffing config ─────────────────────────────────
+
+/// Case workers on the work queues; see `staffing_subsystem`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StaffingConfig {
+    pub enabled: bool,
+    /// Starting headcount of each staffed queue. A queue left out is not
+    /// staffed until the player puts FTEs on it, and its owner works
+    /// everything due as before.
+    pub queues: Vec<QueueStaffing>,
+    /// Loaded cost of one FTE for a day.
+    pub fte_daily_cost: f64,
+    /// Overtime pay over the regular rate, e.g. 1.5 for time and a half.
+    pub overtime_premium: f64,
+    /// Most overtime a backlogged queue can draw, as a share of its
+    /// regular capacity.
+    pub max_overtime_share: f64,
+    /// Resolution code the desk closes a complaint under.
+    pub complaint_resolution_code: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QueueStaffing {
+    pub queue: QueueKind,
+    pub ftes: f64,
+    /// Items one FTE works in a day.
+    pub items_per_fte: f64,
+}
+
+/// Headcount a full-size bank starts each queue with.
+fn default_queue_staffing() -> Vec<QueueStaffing> {
+    let queue = |queue, ftes, items_per_fte| QueueStaffing { queue, ftes, items_per_fte };
+    vec![
+        queue(QueueKind::Complaints, 4.0, 6.0),
+        queue(QueueKind::Alerts, 1.0, 10.0),
+        queue(QueueKind::Exceptions, 1.0, 8.0),
+        queue(QueueKind::Cases, 2.0, 15.0),
+    ]
+}
+
+// ── Phase 4.81: Regulatory enforcement config ───────────────────
+
+/// What regulators do with exam findings; see `enforcement`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EnforcementConfig {
+    /// Needs `regulatory_exam` on too.
+    pub enabled: bool,
+    /// Rise in the UDAAP score since the last exam, per 1,000 active
+    /// customers, that brings the CFPB in for a targeted exam early.
+    pub udaap_exam_rise_per_1k: f64,
+    /// Rise per 1,000 customers over the cycle that is a major UDAAP
+    /// finding, and one that is critical.
+    pub udaap_major_rise_per_1k: f64,
+    pub udaap_critical_rise_per_1k: f64,
+    /// Complaints and SARs an exam tests from its cycle.
+    pub sample_size: usize,
+    /// Fewest sampled items the examiners will draw a conclusion from.
+    pub min_sample: usize,
+    /// Share of a sample found mishandled that is a major finding; twice
+    /// this is critical.
+    pub sample_failure_share: f64,
+    /// Ticks after an MRA, and after a consent order, before an exam can
+    /// find it remediated.
+    pub mra_remediation_ticks: Tick,
+    pub consent_order_remediation_ticks: Tick,
+    /// A consent order's penalty on a repeat of the finding behind an
+    /// earlier one, as a multiple of the finding's fine.
+    pub repeat_penalty_multiplier: f64,
+    /// How far back a mandated lookback reaches.
+    pub lookback_ticks: Tick,
+    /// Ticks a lookback takes to work through its items.
+    pub lookback_review_ticks: Tick,
+    pub lookback_cost_per_item: f64,
+}
+
+// ── Phase 4.82: Lending config ──────────────────────────────────
+
+/// Loans and credit lines; see `lending_subsystem`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LendingConfig {
+    pub enabled: bool,
+    pub products: Vec<LoanProductConfig>,
+    /// Chance a month that an eligible customer with no loan open applies,
+    /// for one of the products at random.
+    pub monthly_application_rate: f64,
+    /// Ticks between payments.
+    pub payment_interval_ticks: Tick,
+    /// Most a loan's annualised payment can be as a share of income.
+    pub max_dti: f64,
+    /// Chance a borrower at the lowest credit score skips a payment they
+    /// could afford; falls with the square of the score's distance from
+    /// 300 toward 850.
+    pub max_miss_rate: f64,
+    /// Days past due at which a loan is charged off.
+    pub charge_off_dpd: i64,
+    /// Share of a charged-off balance recovered, netted from the loss.
+    pub recovery_rate: f64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LoanKind {
+    /// Fixed payments amortizing the amount over `term_payments`.
+    Installment,
+    /// Revolving line: a monthly minimum of interest plus a share of
+    /// the balance.
+    Line,
+}
+
+impl LoanKind {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Installment => "installment",
+            Self::Line => "line",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LoanProductConfig {
+    pub product_id: String,
+    pub kind: LoanKind,
+    pub annual_rate: f64,
+    pub min_credit_score: i64,
+    /// Amount lent, or the line's limit, as a share of annual income, up
+    /// to `max_amount`.
+    pub income_multiple: f64,
+    pub max_amount: f64,
+    /// Installment: number of payments.
+    pub term_payments: u32,
+    /// Line: share of the limit drawn at opening.
+    pub draw_share: f64,
+    /// Line: principal due each payment as a share of the balance, and
+    /// the least principal due while the balance covers it.
+    pub min_payment_share: f64,
+    pub min_payment: f64,
+}
+
+/// The loan products a full-size bank offers.
+fn default_loan_products() -> Vec<LoanProductConfig> {
+    vec![
+        LoanProductConfig {
+            product_id: "personal_loan".into(),
+            kind: LoanKind::Installment,
+            annual_rate: 0.11,
+            min_credit_score: 640,
+            income_multiple: 0.2,
+            max_amount: 25_000.0,
+            term_payments: 36,
+            draw_share: 1.0,
+            min_payment_share: 0.0,
+            min_payment: 0.0,
+        },
+        LoanProductConfig {
+            product_id: "credit_line".into(),
+            kind: LoanKind::Line,
+            annual_rate: 0.18,
+            min_credit_score: 600,
+            income_multiple: 0.1,
+            max_amount: 10_000.0,
+            term_payments: 0,
+            draw_share: 0.5,
+            min_payment_share: 0.03,
+            min_payment: 25.0,
+        },
+    ]
+}
+
+// ── Phase 4.83: Rate environment config ─────────────────────────
+
+/// The base rate path, the yield curve and how deposits and assets
+/// reprice; see `macro_subsystem` and `economics_subsystem`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RateEnvironmentConfig {
+    pub enabled: bool,
+    /// Size of one hike or cut.
+    pub rate_step: f64,
+    /// The odds of each quarter's rate decision, by economic phase.
+    pub regime_moves: Vec<RegimeRateMoves>,
+    /// Long rate over the base rate when the base rate is at neutral.
+    pub term_spread: f64,
+    pub neutral_rate: f64,
+    /// Share of the base rate's distance from neutral the long end does
+    /// not follow, so the curve flattens as rates rise and steepens as
+    /// they fall.
+    pub curve_flattening: f64,
+    /// Share of a move in the base rate from where the run started that
+    /// each deposit product passes on to its posted rate. A product not
+    /// listed passes on `default_deposit_beta`.
+    pub deposit_betas: HashMap<String, f64>,
+    pub default_deposit_beta: f64,
+    /// Share of deposits lent or invested; the rest sits in reserves
+    /// earning nothing.
+    pub earning_asset_share: f64,
+    /// Share of earning assets at fixed long rates, repricing evenly over
+    /// `asset_repricing_ticks`; the rest floats with the base rate.
+    pub fixed_asset_share: f64,
+    pub asset_repricing_ticks: Tick,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RegimeRateMoves {
+    pub phase: EconomicPhase,
+    /// Chance a quarter's decision is a hike, and that it is a cut;
+    /// otherwise the rate holds.
+    pub hike_chance: f64,
+    pub cut_chance: f64,
+}
+
+/// Hikes through expansions, cuts through contractions.
+fn default_regime_moves() -> Vec<RegimeRateMoves> {
+    let moves = |phase, hike_chance, cut_chance| RegimeRateMoves { phase, hike_chance, cut_chance };
+    vec![
+        moves(EconomicPhase::Expansion, 0.5, 0.05),
+        moves(EconomicPhase::Peak, 0.15, 0.15),
+        moves(EconomicPhase::Contraction, 0.05, 0.6),
+        moves(EconomicPhase::Trough, 0.05, 0.25),
+    ]
+}
+
+/// Basic and student checking barely move; premium and business accounts
+/// hold balances worth competing for.
+fn default_deposit_betas() -> HashMap<String, f64> {
+    [
+        ("basic_checking", 0.05),
+        ("student_checking", 0.05),
+        ("standard_checking", 0.15),
+        ("premium_checking", 0.35),
+        ("business_checking", 0.25),
+    ]
+    .into_iter()
+    .map(|(product, beta)| (product.to_string(), beta))
+    .collect()
+}
+
+// ── Phase 4.85: Alert triage config ─────────────────────────────
+
+/// Analysts working AML alerts by hand, and the alert queue's daily
+/// aging metrics; see `alert_triage`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AlertTriageConfig {
+    pub enabled: bool,
+    /// Alerts still open or under investigation this many ticks after
+    /// they were raised count as aged.
+    pub aged_after_ticks: Tick,
+}
+
+// ── Phase 4.86: Case management config ──────────────────────────
+
+/// Investigation cases linking alerts, screening hits, disputes and
+/// complaints; see `case_subsystem`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CaseManagementConfig {
+    pub enabled: bool,
+    /// Customers linked, directly or through others, by relationships at
+    /// least this strong share cases; None keeps a case to one customer.
+    pub min_relationship_strength: Option<f64>,
+    /// Resolution code a closed case closes its open complaints under.
+    pub complaint_resolution_code: String,
+}
+
+// ── Phase 4.87: Money mule network config ───────────────────────
+
+/// Mule networks seeded into the customer base; see `mule_network`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MuleNetworkConfig {
+    pub enabled: bool,
+    /// Daily probability a new network is seeded while fewer than
+    /// `max_networks` are active.
+    pub seed_daily_rate: f64,
+    pub max_networks: u32,
+    /// Mules recruited per network, drawn between the two inclusive.
+    pub min_mules: u32,
+    pub max_mules: u32,
+    /// Ticks between a mule's rounds of `credits_per_cycle` inbound
+    /// credits of around `credit_amount` each, from fresh senders.
+    pub cycle_interval_ticks: Tick,
+    pub credits_per_cycle: u32,
+    pub credit_amount: f64,
+    /// Ticks a mule holds a round before forwarding it, less `mule_cut`,
+    /// to the controller; 0 forwards it the same tick.
+    pub forward_lag_ticks: Tick,
+    pub mule_cut: f64,
+    /// Strength of the relationship recorded between the controller and
+    /// each mule.
+    pub relationship_strength: f64,
+    /// Ticks a network runs before it dissolves.
+    pub lifetime_ticks: Tick,
+}
+
+// ── Phase 4.88: Structuring config ──────────────────────────────
+
+/// Cash structuring by a share of risky customers; see `structuring`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StructuringConfig {
+    pub enabled: bool,
+    /// Composite risk levels a customer is labeled at; each is labeled
+    /// once, a structurer with probability `structurer_rate`.
+    pub risk_levels: Vec<String>,
+    pub structurer_rate: f64,
+    /// Ticks between the starts of a structurer's episodes. Each makes
+    /// `deposits_per_episode` cash deposits of `min_deposit` up to
+    /// `max_deposit`, spread over `episode_spread_ticks` and the
+    /// customer's accounts.
+    pub episode_interval_ticks: Tick,
+    pub deposits_per_episode: u32,
+    pub episode_spread_ticks: Tick,
+    pub min_deposit: f64,
+    pub max_deposit: f64,
+}
+
+// ── Phase 4.89: Sanctions screening config ──────────────────────
+
+/// Sanctions name matching; see `name_matching`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SanctionsScreeningConfig {
+    /// Name similarity below which a listed party is not a candidate.
+    pub candidate_name_score: f64,
+    /// Scale on an alias's name score against the listed name's.
+    pub alias_discount: f64,
+    /// Match score weights for name, country and year of birth. A detail
+    /// either side leaves out earns half its weight.
+    pub name_weight: f64,
+    pub country_weight: f64,
+    pub birth_year_weight: f64,
+    /// Years of birth this far apart still agree.
+    pub birth_year_tolerance: u32,
+    /// The false positive dial: at sensitivity 0 a hit needs
+    /// `strict_match_threshold`, at 1 only `lenient_match_threshold`.
+    /// The player moves it with `SetSanctionsSensitivity`.
+    pub sensitivity: f64,
+    pub strict_match_threshold: f64,
+    pub lenient_match_threshold: f64,
+    /// Hits scoring at least this are exact matches, and on the SDN list
+    /// raise a critical alert.
+    pub exact_match_threshold: f64,
+    /// Share of new customers who are a listed party, screened under its
+    /// name or an alias, its country and its year of birth. Ground
+    /// truth, kept on every hit.
+    pub listed_party_rate: f64,
+    /// Share of listed parties who give an alias rather than the listed
+    /// name.
+    pub alias_use_rate: f64,
+}
+
+// ── Phase 4.90: Service channel config ──────────────────────────
+
+/// The channel each customer transaction goes through; see `channel`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChannelConfig {
+    pub enabled: bool,
+    /// Relative shares of cash withdrawals by channel, and of payments
+    /// other than card purchases.
+    pub cash_mix: BTreeMap<Channel, f64>,
+    pub payment_mix: BTreeMap<Channel, f64>,
+    /// Cost to serve one completed transaction, by channel.
+    pub cost_per_transaction: BTreeMap<Channel, f64>,
+    /// Share of attempts a degraded channel turns away; a down channel
+    /// turns all of them away.
+    pub degraded_failure_rate: f64,
+    /// Chance a customer turned away tries another channel of the same
+    /// mix rather than giving up.
+    pub fallback_rate: f64,
+}
+
+// ── Phase 4.91: Authorized push payment fraud config ─────────────
+
+/// Customers talked into sending instant payments to scammers, and the
+/// bank's answer to their reimbursement claims; see `app_fraud`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AppFraudConfig {
+    pub enabled: bool,
+    /// Segments whose customers scammers approach.
+    pub segments: Vec<String>,
+    /// Chance per customer per tick of a scammer making contact, and of
+    /// the customer paying.
+    pub scam_rate: f64,
+    pub fall_rate: f64,
+    /// Scripts the scammer runs, drawn evenly: e.g. purchase,
+    /// impersonation, investment, romance.
+    pub scam_types: Vec<String>,
+    /// Instant rails the payment goes out on, drawn evenly.
+    pub rails: Vec<String>,
+    /// Payment as a share of the account's balance; none smaller than
+    /// `min_payment` is sent.
+    pub share_min: f64,
+    pub share_max: f64,
+    pub min_payment: f64,
+    /// Share of victims who realise and report, between
+    /// `report_min_ticks` and `report_max_ticks` after paying.
+    pub report_rate: f64,
+    pub report_min_ticks: Tick,
+    pub report_max_ticks: Tick,
+    /// Ticks the fraud desk takes to decide a reported claim.
+    pub decision_ticks: Tick,
+    /// 'none' | 'vulnerable' | 'full': which claims the bank reimburses.
+    /// The player moves it with `SetAppReimbursementPolicy`.
+    pub reimbursement_policy: String,
+    /// Customers flagged vulnerable, or at least this old when they
+    /// opened, count as vulnerable.
+    pub vulnerable_age: i64,
+    /// Each reimbursement leaves the customer `excess` and pays at most
+    /// `reimbursement_cap`.
+    pub excess: f64,
+    pub reimbursement_cap: f64,
+}
+
+// ── Phase 4.92: ATM network config ──────────────────────────────
+
+/// The ATMs cash withdrawals on the ATM channel are made at, their
+/// faults, and the cash-out crews that hit them; see `atm`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AtmNetworkConfig {
+    pub enabled: bool,
+    /// ATMs in the fleet, sited at `locations` in turn.
+    pub atms: usize,
+    pub locations: Vec<String>,
+    /// Share of a customer's withdrawals made at their usual ATM while
+    /// it is in service.
+    pub home_rate: f64,
+    /// Chance per ATM per tick of a device fault, and ticks before a
+    /// field engineer has it back in service.
+    pub fault_rate: f64,
+    pub repair_ticks: Tick,
+    /// Chance per tick of a cash-out crew hitting one ATM with
+    /// `cash_out_cards` cloned cards.
+    pub cash_out_rate: f64,
+    pub cash_out_cards: usize,
+    /// Withdrawals of `cash_out_amount` per cloned card: the first at
+    /// the ATM hit, the rest at others before the card is stopped.
+    pub cash_out_withdrawals: usize,
+    pub cash_out_amount: f64,
+    /// Distinct ATMs one card is used at in a tick that raise
+    /// `ATM_MULTI_DEVICE`.
+    pub multi_atm_threshold: usize,
+}
+
+// ── Phase 4.93: Merchant config ──────────────────────────────────
+
+/// The merchants card purchases are made at, how often their purchases
+/// are disputed, and the monitoring of their chargeback rates; see
+/// `merchant`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MerchantConfig {
+    pub enabled: bool,
+    /// Merchants accepting the bank's cards, each of a category drawn
+    /// by weight.
+    pub count: usize,
+    pub categories: BTreeMap<String, f64>,
+    /// Risk tiers drawn by weight, and each tier's fraud rate: the
+    /// chance a settled purchase there is disputed.
+    pub risk_tiers: BTreeMap<String, f64>,
+    pub fraud_rates: BTreeMap<String, f64>,
+    /// Chance per tick of a merchant going bad (breached, or taking
+    /// orders it never ships) for `bad_merchant_ticks`. Purchases made
+    /// there meanwhile are disputed `bad_merchant_multiplier` times as
+    /// often.
+    pub bad_merchant_rate: f64,
+    pub bad_merchant_ticks: Tick,
+    pub bad_merchant_multiplier: f64,
+    /// Each week, a merchant whose disputes filed over the last
+    /// `chargeback_window_ticks` are at least
+    /// `chargeback_alert_min_disputes` and `chargeback_alert_rate` of its
+    /// purchases in the window raises an early warning.
+    pub chargeback_window_ticks: Tick,
+    pub chargeback_alert_rate: f64,
+    pub chargeback_alert_min_disputes: i64,
+}
+
+// ── Phase 4.94: Device telemetry config ──────────────────────────
+
+/// Logins from customers' devices, account takeovers from fraudsters'
+/// devices, and the device signals fraud detection scores; see `device`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeviceTelemetryConfig {
+    pub enabled: bool,
+    /// Share of customers banking online or in the app. Each logs in
+    /// with `login_rate` a tick, and always on a tick they pay
+    /// digitally; a login is from a new device with `new_device_rate`.
+    pub digital_rate: f64,
+    pub login_rate: f64,
+    pub new_device_rate: f64,
+    /// Chance per tick of a fraudster taking over a digital customer's
+    /// account from one of `farm_devices` devices and sending
+    /// `takeover_share` of its balance out.
+    pub takeover_rate: f64,
+    pub farm_devices: usize,
+    pub takeover_share: f64,
+    /// A payment of `high_value_amount` or more from a device first seen
+    /// within `new_device_ticks` is alerted; so is a device reaching
+    /// `shared_device_threshold` customers.
+    pub high_value_amount: f64,
+    pub new_device_ticks: Tick,
+    pub shared_device_threshold: i64,
+}
+
+#[derive(Debug, Clone)]
+pub struct SimConfig {
+    pub segments: HashMap<String, SegmentConfig>,
+    pub initial_population: usize,
+    pub complaint_triggers: Vec<ComplaintTrigger>,
+    pub resolution_codes: HashMap<String, ResolutionCode>,
+    /// Phase 4.47: templates for `bulk_resolve`, by id.
+    pub resolution_templates: HashMap<String, ResolutionTemplate>,
+    /// Phase 4.51: per-product, segment and issue complaint rates.
+    pub complaint_propensity: ComplaintPropensity,
+    pub products: HashMap<String, ProductConfig>,
+    pub fee_constraints: HashMap<String, FeeConstraint>,
+    pub impact_formulas: HashMap<String, FeeImpactFormula>,
+    pub offers: HashMap<String, OfferConfig>,
+    pub churn_model: ChurnModelConfig,
+    pub segment_economics: SegmentEconomicsConfig,
+    pub complaint_analytics: ComplaintAnalyticsConfig,
+    pub risk_appetite: RiskAppetiteConfig,
+    pub payment_hub: PaymentHubConfig,
+    pub reconciliation: ReconciliationConfig,
+    pub identity_address: IdentityAddressConfig,
+    pub incident: IncidentConfig,
+    pub regulatory_exam: RegulatoryExamConfig,
+    pub reputation: ReputationConfig,
+    pub credit_bureau: CreditBureauConfig,
+    pub protected_funds: ProtectedFundsConfig,
+    pub branch_network: BranchNetworkConfig,
+    pub fair_lending: FairLendingConfig,
+    pub offer_guardrail: OfferGuardrailConfig,
+    pub network_invoice: NetworkInvoiceConfig,
+    pub settlement_accounts: SettlementAccountConfig,
+    pub payment_cutoff: PaymentCutoffConfig,
+    pub ach_returns: AchReturnConfig,
+    pub ach_origination: AchOriginationConfig,
+    pub external_links: ExternalLinkConfig,
+    pub customer_limits: CustomerLimitConfig,
+    pub risk_lifecycle: RiskLifecycleConfig,
+    pub ssn_validation: SsnValidationConfig,
+    pub idv: IdvConfig,
+    pub sar_decision: SarDecisionConfig,
+    pub rule_tuning: RuleTuningConfig,
+    pub coverage_assessment: CoverageAssessmentConfig,
+    pub time_acceleration: TimeAccelerationConfig,
+    /// Share of the bank simulated in detail (Phase 4.26). 1.0 is the
+    /// whole population; see `with_population_scale`.
+    pub population_scale: f64,
+    pub event_log_retention: EventLogRetentionConfig,
+    pub complaint_sla: ComplaintSlaConfig,
+    pub hardship: HardshipConfig,
+    pub case_documents: CaseDocumentConfig,
+    pub contact_fatigue: ContactFatigueConfig,
+    pub satisfaction_dynamics: SatisfactionDynamicsConfig,
+    pub financial_health: FinancialHealthConfig,
+    pub transaction_enrichment: TransactionEnrichmentConfig,
+    pub remittance: RemittanceConfig,
+    pub visa_expiry: VisaExpiryConfig,
+    pub adverse_media: AdverseMediaConfig,
+    pub correspondent_banking: CorrespondentBankingConfig,
+    pub cross_border: CrossBorderConfig,
+    pub calendar: BusinessCalendarConfig,
+    pub feature_flags: FeatureFlagsConfig,
+    pub authorization: AuthorizationConfig,
+    pub deposit_run: DepositRunConfig,
+    pub bec: BecConfig,
+    pub check_fraud: CheckFraudConfig,
+    pub deposit_holds: DepositHoldConfig,
+    pub event_stream: EventStreamConfig,
+    pub evidence_bundles: EvidenceBundleConfig,
+    pub alert_grouping: AlertGroupingConfig,
+    pub operational_risk: OperationalRiskConfig,
+    pub quarter_close: QuarterCloseConfig,
+    pub opex_allocation: OpexAllocationConfig,
+    pub churn_evaluation: ChurnEvaluationConfig,
+    pub complaint_quality: ComplaintQualityConfig,
+    pub scenario_scripts: ScenarioScriptConfig,
+    pub stress_index: StressIndexConfig,
+    pub alert_qa: AlertQaConfig,
+    pub macro_regime: MacroRegimeConfig,
+    pub staffing: StaffingConfig,
+    pub enforcement: EnforcementConfig,
+    pub lending: LendingConfig,
+    pub rate_environment: RateEnvironmentConfig,
+    pub alert_triage: AlertTriageConfig,
+    pub case_management: CaseManagementConfig,
+    pub mule_networks: MuleNetworkConfig,
+    pub structuring: StructuringConfig,
+    pub sanctions_screening: SanctionsScreeningConfig,
+    pub channels: ChannelConfig,
+    pub app_fraud: AppFraudConfig,
+    pub atm_network: AtmNetworkConfig,
+    pub merchants: MerchantConfig,
+    pub devices: DeviceTelemetryConfig,
+}
+
+impl SimConfig {
+    /// Load from the data/ directory.
+    /// In tests, use SimConfig::default_test().
+    pub fn load(data_dir: &str) -> anyhow::Result<Self> {
+        let path = format!("{data_dir}/segments/segments.json");
+        let content = std::fs::read_to_string(&path)
+            .map_err(|e| anyhow::anyhow!("Cannot read {path}: {e}"))?;
+        let file: SegmentsFile = serde_json::from_str(&content)?;
+        let segments = file
+            .segments
+            .into_iter()
+            .map(|s| (s.id.clone(), s))
+            .collect();
+
+        let complaint_path = format!("{data_dir}/complaints/complaint_triggers.json");
+        let complaint_content = std::fs::read_to_string(&complaint_path)
+            .map_err(|e| anyhow::anyhow!("Cannot read {complaint_path}: {e}"))?;
+        let complaint_file: ComplaintConfigFile = serde_json::from_str(&complaint_content)?;
+        let resolution_codes = complaint_file
+            .resolution_codes
+            .into_iter()
+            .map(|r| (r.code.clone(), r))
+            .collect();
+        let resolution_templates = complaint_file
+            .resolution_templates
+            .into_iter()
+            .map(|t| (t.template_id.clone(), t))
+            .collect();
+
+        let product_path = format!("{data_dir}/products/product_catalog.json");
+        let product_content = std::fs::read_to_string(&product_path)
+            .map_err(|e| anyhow::anyhow!("Cannot read {product_path}: {e}"))?;
+        let product_file: ProductCatalogFile = serde_json::from_str(&product_content)?;
+        let products = product_file
+            .products
+            .into_iter()
+            .map(|p| (p.product_id.clone(), p))
+            .collect();
+        complaint_file.propensity.validate(&products, &segments, &complaint_file.triggers)?;
+
+        let fee_path = format!("{data_dir}/products/fee_constraints.json");
+        let fee_content = std::fs::read_to_string(&fee_path)
+            .map_err(|e| anyhow::anyhow!("Cannot read {fee_path}: {e}"))?;
+        let fee_file: FeeConstraintsFile = serde_json::from_str(&fee_content)?;
+        let fee_constraints = fee_file
+            .fee_types
+            .into_iter()
+            .map(|f| (f.fee_type.clone(), f))
+            .collect();
+
+        let offer_path = format!("{data_dir}/offers/offer_catalog.json");
+        let offer_content = std::fs::read_to_string(&offer_path)
+            .map_err(|e| anyhow::anyhow!("Cannot read {offer_path}: {e}"))?;
+        let offer_file: OfferCatalogFile = serde_json::from_str(&offer_content)?;
+        let offers = offer_file
+            .offers
+            .into_iter()
+            .map(|o| (o.offer_id.clone(), o))
+            .collect();
+
+        let churn_path = format!("{data_dir}/churn/churn_model_config.json");
+        let churn_content = std::fs::read_to_string(&churn_path)
+            .map_err(|e| anyhow::anyhow!("Cannot read {churn_path}: {e}"))?;
+        let churn_model: ChurnModelConfig = serde_json::from_str(&churn_content)?;
+
+        let seg_econ_path = format!("{data_dir}/economics/segment_economics_config.json");
+        let seg_econ_content = std::fs::read_to_string(&seg_econ_path)
+            .map_err(|e| anyhow::anyhow!("Cannot read {seg_econ_path}: {e}"))?;
+        let seg_econ_file: SegmentEconomicsFile = serde_json::from_str(&seg_econ_content)?;
+        let segment_economics = SegmentEconomicsConfig {
+            cost_allocation_model: seg_econ_file.cost_allocation_model,
+            clv_model: seg_econ_file.clv_model,
+            profitability_metrics: seg_econ_file.profitability_metrics,
+            revenue_attribution: seg_econ_file.revenue_attribution,
+        };
+
+        let complaint_analytics_path = format!("{data_dir}/complaints/analytics_config.json");
+        let complaint_analytics_content = std::fs::read_to_string(&complaint_analytics_path)
+            .map_err(|e| anyhow::anyhow!("Cannot read {complaint_analytics_path}: {e}"))?;
+        let complaint_analytics_file: ComplaintAnalyticsFile =
+            serde_json::from_str(&complaint_analytics_content)?;
+        let complaint_analytics = ComplaintAnalyticsConfig {
+            pattern_detection: complaint_analytics_file.pattern_detection,
+            root_cause_tracking: complaint_analytics_file.root_cause_tracking,
+            resolution_effectiveness: complaint_analytics_file.resolution_effectiveness,
+            sla_performance: complaint_analytics_file.sla_performance,
+            early_warning_indicators: complaint_analytics_file.early_warning_indicators,
+            cost_analysis: complaint_analytics_file.cost_analysis,
+        };
+
+        let risk_path = format!("{data_dir}/risk/risk_appetite_config.json");
+        let risk_content = std::fs::read_to_string(&risk_path)
+            .map_err(|e| anyhow::anyhow!("Cannot read {risk_path}: {e}"))?;
+        let risk_file: RiskAppetiteFile = serde_json::from_str(&risk_content)?;
+        let risk_appetite = RiskAppetiteConfig {
+            dials: risk_file.dials,
+            constraints: risk_file.constraints,
+            risk_profile_scoring: risk_file.risk_profile_scoring,
+            board_pressure: risk_file.board_pressure,
+        };
+
+        let payment_path = format!("{data_dir}/payment/payment_rails_config.json");
+        let payment_content = std::fs::read_to_string(&payment_path)
+            .map_err(|e| anyhow::anyhow!("Cannot read {payment_path}: {e}"))?;
+        let payment_file: PaymentHubFile = serde_json::from_str(&payment_content)?;
+        let payment_hub = PaymentHubConfig {
+            rails: payment_file.rails,
+            interchange_fee_rate: payment_file.interchange_fee_rate,
+            auth_expiry_ticks: payment_file.auth_expiry_ticks,
+        };
+
+        let identity_address = {
+            let ia_path = format!("{data_dir}/identity/identity_address.json");
+            let ia_content = std::fs::read_to_string(&ia_path)
+                .map_err(|e| anyhow::anyhow!("Cannot read {ia_path}: {e}"))?;
+            let ia_file: IdentityAddressFile = serde_json::from_str(&ia_content)?;
+            IdentityAddressConfig {
+                regions: ia_file.regions,
+                synthetic_identity_rate: ia_file.rates.synthetic_identity_rate,
+                homeless_rate: ia_file.rates.homeless_rate,
+                po_box_rate: ia_file.rates.po_box_rate,
+                cmra_rate: ia_file.rates.cmra_rate,
+                address_sharing_alert_threshold: ia_file.rates.address_sharing_alert_threshold,
+                voip_rate: ia_file.rates.voip_rate,
+                international_customer_rate: ia_file.rates.international_customer_rate,
+            }
+        };
+
+        // The same $45k a quarter the flat charge spreads, split by what
+        // drives it
+        let opex_allocation = OpexAllocationConfig {
+            enabled: true,
+            pools: vec![
+                OpexPoolConfig {
+                    pool: "branch_network".into(),
+                    quarterly_cost: 20_000.0,
+                    driver: "customers".into(),
+                    headcount: HashMap::new(),
+                },
+                OpexPoolConfig {
+                    pool: "payment_operations".into(),
+                    quarterly_cost: 15_000.0,
+                    driver: "transactions".into(),
+                    headcount: HashMap::new(),
+                },
+                OpexPoolConfig {
+                    pool: "service_desk".into(),
+                    quarterly_cost: 10_000.0,
+                    driver: "headcount".into(),
+                    headcount: [
+                        ("mass_market".into(), 5.0),
+                        ("mid_tier".into(), 3.0),
+                        ("student".into(), 1.0),
+                        ("small_business".into(), 3.0),
+                    ]
+                    .into(),
+                },
+            ],
+        };
+        opex_allocation.validate(&segments)?;
+
+        Ok(Self {
+            segments,
+            initial_population: 500,
+            complaint_triggers: complaint_file.triggers,
+            resolution_codes,
+            resolution_templates,
+            complaint_propensity: complaint_file.propensity,
+            products,
+            fee_constraints,
+            impact_formulas: fee_file.impact_formulas,
+            offers,
+            churn_model,
+            segment_economics,
+            complaint_analytics,
+            risk_appetite,
+            payment_hub,
+            reconciliation: {
+                let recon_path =
+                    format!("{data_dir}/reconciliation/recon_queue_config.json");
+                let recon_content = std::fs::read_to_string(&recon_path)
+                    .map_err(|e| anyhow::anyhow!("Cannot read {recon_path}: {e}"))?;
+                let recon_file: ReconFile = serde_json::from_str(&recon_content)?;
+                ReconciliationConfig {
+                    queue_configs: recon_file.queue_configs,
+                    enable_auto_clear: recon_file.enable_auto_clear,
+                    metrics_frequency_ticks: recon_file.metrics_frequency_ticks,
+                }
+            },
+            identity_address,
+            incident: IncidentConfig {
+                enabled: true,
+                severity_weights: vec![
+                    ("P0".into(), 0.05),
+                    ("P1".into(), 0.15),
+                    ("P2".into(), 0.30),
+                    ("P3".into(), 0.50),
+                ],
+                sla_deadlines: vec![
+                    ("P0".into(), 0),
+                    ("P1".into(), 0),
+                    ("P2".into(), 1),
+                    ("P3".into(), 3),
+                ],
+                cascading_failures_enabled: true,
+                metrics_interval_ticks: 7,
+            },
+            regulatory_exam: RegulatoryExamConfig {
+                enabled: true,
+                exam_interval_ticks: 90,
+                exam_duration_ticks: 14,
+                examiners: vec!["OCC".into(), "CFPB".into(), "FDIC".into()],
+                fine_minor: 5_000.0,
+                fine_moderate: 50_000.0,
+                fine_major: 250_000.0,
+                fine_critical: 1_000_000.0,
+                mou_critical_threshold: 2,
+            },
+            reputation: ReputationConfig {
+                enabled: true,
+                initial_score: 75.0,
+                recovery_per_tick: 0.05,
+                fine_impact_per_1k: 0.1,
+                mou_impact: 10.0,
+                sar_late_impact: 2.0,
+                sla_breach_impact: 0.5,
+                onboarding_penalty_threshold: 40.0,
+                max_onboarding_penalty: 0.50,
+            },
+            credit_bureau: CreditBureauConfig {
+                enabled: true,
+                furnish_interval_ticks: 30,
+                furnishing_error_rate: 0.01,
+                dispute_probability: 0.40,
+                investigation_window_ticks: 30,
+                daily_correction_rate: 0.06,
+                late_correction_penalty: 2_500.0,
+            },
+            protected_funds: ProtectedFundsConfig {
+                enabled: true,
+                beneficiary_share: 0.12,
+                benefit_payment_day: 3,
+                default_monthly_benefit: 1_900.0,
+                lookback_ticks: 60,
+                enforce_protection: true,
+                garnishment_daily_rate: 0.002,
+                violation_udaap_impact: 0.5,
+            },
+            branch_network: BranchNetworkConfig {
+                enabled: true,
+                min_customers_for_branch: 25,
+                customers_per_staff: 150,
+                min_staff: 3,
+                staff_monthly_cost: 5_500.0,
+                occupancy_monthly_cost: 12_000.0,
+                acquisitions_per_branch_month: 6.0,
+                closure_churn_delta: 0.10,
+                closure_satisfaction_hit: 0.08,
+                open_churn_relief: 0.04,
+                network_change_effect_ticks: 90,
+                report_interval_ticks: 30,
+            },
+            fair_lending: FairLendingConfig {
+                enabled: true,
+                high_minority_zip_share: 0.25,
+                lmi_income_ratio: 0.8,
+                review_interval_ticks: 90,
+                min_group_size: 20,
+                adverse_ratio_threshold: 1.25,
+                major_ratio_threshold: 1.5,
+                response_window_ticks: 30,
+                escalation_udaap_impact: 1.0,
+                remediation_udaap_relief: 0.25,
+                remediation_cost: 40_000.0,
+            },
+            offer_guardrail: OfferGuardrailConfig {
+                enabled: true,
+                warn_ratio: 1.25,
+                block_ratio: 2.0,
+                warn_udaap_impact: 0.25,
+            },
+            network_invoice: NetworkInvoiceConfig {
+                enabled: true,
+                invoice_interval_ticks: 30,
+                assessment_rate: 0.0014,
+                dispute_fee: 25.0,
+                network_error_rate: 0.08,
+                min_error_pct: 0.02,
+                max_error_pct: 0.12,
+                recovery_window_ticks: 60,
+                recovery_success_rate: 0.85,
+            },
+            settlement_accounts: SettlementAccountConfig {
+                enabled: true,
+                projection_window_ticks: 7,
+                funding_buffer_pct: 0.10,
+                penalty_rate: 0.0005,
+                borrowing_fee: 150.0,
+                alert_shortfall_pct: 0.25,
+            },
+            payment_cutoff: PaymentCutoffConfig {
+                enabled: true,
+                initiation_start_hour: 6,
+                initiation_end_hour: 22,
+            },
+            ach_returns: AchReturnConfig {
+                enabled: true,
+                return_rate: 0.015,
+                return_delay_ticks: 2,
+                represent_delay_ticks: 2,
+                represent_success_rate: 0.55,
+                contact_satisfaction_delta: -0.02,
+                dispute_review_ticks: 10,
+                originator_contest_rate: 0.20,
+                codes: default_ach_return_codes(),
+            },
+            ach_origination: AchOriginationConfig {
+                enabled: true,
+                bill_pay_daily_rate: 0.04,
+                external_transfer_daily_rate: 0.01,
+                takeover_daily_rate: 0.000_02,
+                takeover_burst_size: 6,
+                daily_count_limit: 5,
+                daily_amount_limit: 5_000.0,
+                declined_satisfaction_delta: -0.02,
+                return_delay_ticks: 2,
+                administrative_return_rate: 0.005,
+                nsf_return_rate: 0.03,
+                unauthorized_return_rate: 0.001,
+                review_interval_ticks: 30,
+                review_lookback_ticks: 60,
+                unauthorized_threshold: 0.005,
+                administrative_threshold: 0.03,
+                overall_threshold: 0.15,
+                network_fine: 25_000.0,
+            },
+            external_links: ExternalLinkConfig {
+                enabled: true,
+                verification_strictness: 0.5,
+                micro_deposit_delay_ticks: 2,
+                micro_deposit_confirm_rate: 0.85,
+                instant_bypass_rate: 0.90,
+                micro_deposit_bypass_rate: 0.20,
+                dd_switch_daily_rate: 0.002,
+                mule_link_daily_rate: 0.000_05,
+                drain_window_ticks: 3,
+                failed_link_satisfaction_delta: -0.03,
+            },
+            customer_limits: CustomerLimitConfig {
+                enabled: true,
+                declined_satisfaction_delta: -0.02,
+                rules: default_customer_limit_rules(),
+            },
+            risk_lifecycle: RiskLifecycleConfig {
+                enabled: true,
+                rescore_interval: 7,
+                signal_lookback_ticks: 30,
+                alert_weight: 0.25,
+                decline_weight: 0.05,
+                unauthorized_weight: 0.35,
+                medium_threshold: 0.30,
+                high_threshold: 0.60,
+                critical_threshold: 0.90,
+                tiers: default_risk_tier_servicing(),
+            },
+            ssn_validation: SsnValidationConfig {
+                enabled: true,
+                invalid_range_weight: 0.40,
+                issuance_mismatch_weight: 0.25,
+                deceased_weight: 0.50,
+                death_master_file_size: 200,
+            },
+            idv: IdvConfig {
+                enabled: true,
+                vendor: "standard".into(),
+                vendors: default_idv_vendors(),
+            },
+            sar_decision: SarDecisionConfig {
+                enabled: true,
+                filing_threshold: 85.0,
+                reportable_score: 85.0,
+                cost_per_filing: 250.0,
+            },
+            rule_tuning: RuleTuningConfig {
+                enabled: true,
+                review_ticks: 14,
+                review_cost: 2_500.0,
+            },
+            coverage_assessment: CoverageAssessmentConfig {
+                enabled: true,
+                interval_ticks: 30,
+            },
+            time_acceleration: TimeAccelerationConfig {
+                enabled: false, // opt-in for long experiments
+                coarse_after_tick: 365,
+                coarse_step_ticks: 7,
+                coarse_risk_tiers: vec!["low".into()],
+            },
+            population_scale: 1.0,
+            event_log_retention: EventLogRetentionConfig {
+                enabled: false, // opt-in: full history is the replay default
+                detail_ticks: 365,
+                compact_interval_ticks: 30,
+                export_dir: None,
+            },
+            complaint_sla: ComplaintSlaConfig {
+                policies: Vec::new(),
+                regulatory_limits: default_sla_regulatory_limits(),
+            },
+            hardship: HardshipConfig {
+                enabled: true,
+                eligible_life_events: vec!["job_change".into(), "medical_emergency".into(), "divorce".into()],
+                enrollment_probability: 0.40,
+                duration_ticks: 90,
+                waived_fee_types: vec!["overdraft".into()],
+                suspend_setoffs: true,
+                churn_risk_relief: 0.10,
+                enrollment_satisfaction_delta: 0.05,
+            },
+            case_documents: CaseDocumentConfig {
+                enabled: true,
+                missing_probability: 0.05,
+            },
+            contact_fatigue: ContactFatigueConfig {
+                enabled: true,
+                do_not_contact_rate: 0.08,
+                window_ticks: 30,
+                response_decay: 0.75,
+                off_channel_response_factor: 0.5,
+                fatigue_threshold: 3,
+                fatigue_satisfaction_delta: -0.02,
+                offer_response_rate: 0.30,
+                survey_response_rate: 0.25,
+                collections_response_rate: 0.40,
+                survey_interval_ticks: 30,
+                survey_sample_rate: 0.25,
+                collections_interval_ticks: 7,
+            },
+            satisfaction_dynamics: SatisfactionDynamicsConfig {
+                enabled: true,
+                segment_baselines: [
+                    ("mass_market".into(), 0.70),
+                    ("mid_tier".into(), 0.75),
+                    ("student".into(), 0.65),
+                    ("small_business".into(), 0.70),
+                ]
+                .into(),
+                default_baseline: 0.70,
+                monthly_reversion: 0.15,
+                goodwill_window_ticks: 180,
+                goodwill_decay: 0.5,
+                scar_priorities: vec!["urgent".into()],
+                scar_severity: 0.08,
+                baseline_floor: 0.30,
+            },
+            financial_health: FinancialHealthConfig {
+                enabled: true,
+                lookback_ticks: 90,
+                volatility_weight: 0.35,
+                overdraft_weight: 0.40,
+                payroll_weight: 0.25,
+                volatility_saturation: 3.0,
+                overdraft_saturation: 6,
+                credit_offer_types: vec!["credit_line".into()],
+                default_min_credit_score: 0.5,
+                low_health_threshold: 0.4,
+                low_health_udaap_impact: 0.05,
+                max_monthly_default_rate: 0.10,
+                loss_given_default: 0.85,
+                high_risk_spend_penalty: 0.5,
             },
-            identity_address,
-            incident: IncidentConfig {
+            transaction_enrichment: TransactionEnrichmentConfig {
                 enabled: true,
-                severity_weights: vec![
-                    ("P0".into(), 0.05),
-                    ("P1".into(), 0.15),
-                    ("P2".into(), 0.30),
-                    ("P3".into(), 0.50),
-                ],
-                sla_deadlines: vec![
-                    ("P0".into(), 0),
-                    ("P1".into(), 0),
-                    ("P2".into(), 1),
-                    ("P3".into(), 3),
-                ],
-                cascading_failures_enabled: true,
-                metrics_interval_ticks: 7,
+                accuracy: 0.92,
+                categories: default_merchant_categories(),
+                high_risk_categories: vec!["gambling".into(), "crypto".into()],
+                intensity_lookback_ticks: 30,
+                intensity_threshold: 0.30,
+                intensity_min_spend: 250.0,
+                intensity_alert_score: 60.0,
             },
-            regulatory_exam: RegulatoryExamConfig {
+            remittance: RemittanceConfig {
                 enabled: true,
-                exam_interval_ticks: 90,
-                exam_duration_ticks: 14,
-                examiners: vec!["OCC".into(), "CFPB".into(), "FDIC".into()],
-                fine_minor: 5_000.0,
-                fine_moderate: 50_000.0,
-                fine_major: 250_000.0,
-                fine_critical: 1_000_000.0,
-                mou_critical_threshold: 2,
+                corridors: default_remittance_corridors(),
+                participation_rate: 0.60,
+                layering_rate: 0.05,
+                max_family_beneficiaries: 2,
+                layering_interval_ticks: 7,
+                layering_inflow_multiple: 8.0,
+                layering_fanout: 5,
+                lookback_ticks: 30,
+                min_beneficiaries: 4,
+                min_corridors: 2,
+                pass_through_threshold: 0.50,
+                alert_score: 55.0,
+                medium_risk_uplift: 10.0,
+                high_risk_uplift: 25.0,
             },
-            reputation: ReputationConfig {
+            visa_expiry: VisaExpiryConfig {
                 enabled: true,
-                initial_score: 75.0,
-                recovery_per_tick: 0.05,
-                fine_impact_per_1k: 0.1,
-                mou_impact: 10.0,
-                sar_late_impact: 2.0,
-                sla_breach_impact: 0.5,
-                onboarding_penalty_threshold: 40.0,
-                max_onboarding_penalty: 0.50,
+                validity: default_visa_validity(),
+                refresh_lead_ticks: 60,
+                renewal_rate: 0.85,
+                late_submission_ticks: 30,
+                processing_ticks: 10,
+                refresh_cost: 20.0,
+                restriction_level: "outbound".into(),
+                grace_ticks: 30,
+                outbound_satisfaction_delta: -0.05,
+                full_satisfaction_delta: -0.15,
+                pending_satisfaction_delta: -0.10,
+                blocked_satisfaction_delta: -0.01,
+            },
+            adverse_media: AdverseMediaConfig {
+                enabled: true,
+                categories: default_adverse_media_categories(),
+                articles_per_tick: 2.0,
+                customer_subject_rate: 0.25,
+                counterparty_subject_rate: 0.20,
+                namesake_rate: 0.30,
+                name_variant_rate: 0.25,
+                detail_rate: 0.70,
+                counterparty_lookback_ticks: 30,
+                candidate_name_score: 0.50,
+                name_weight: 0.50,
+                country_weight: 0.20,
+                birth_year_weight: 0.30,
+                match_threshold: 0.75,
+                edd_match_score: 0.90,
+                edd_severity: 0.70,
+                edd_due_ticks: 14,
+                risk_impact_scale: 0.40,
+                counterparty_impact_share: 0.50,
+            },
+            correspondent_banking: CorrespondentBankingConfig {
+                enabled: true,
+                respondents: default_respondent_banks(),
+                nested_countries: vec!["VE".into(), "RU".into(), "MM".into(), "SY".into()],
+                nested_originators: 20,
+                stripping_rate: 0.60,
+                clearing_fee: 15.0,
+                due_diligence: default_correspondent_due_diligence(),
+                response_rate: 0.90,
+                response_ticks: 14,
+                overdue_ticks: 30,
+                disclosure_rate: 0.50,
+                lookback_ticks: 30,
+                min_third_party_share: 0.05,
+                originator_excess: 1.25,
+                alert_score: 50.0,
+                medium_risk_uplift: 10.0,
+                high_risk_uplift: 25.0,
+                undisclosed_uplift: 15.0,
+            },
+            cross_border: CrossBorderConfig {
+                enabled: true,
+                funnel_rate: 0.02,
+                inbound_rate: 0.25,
+                source_countries: default_cross_border_source_countries(),
+                funnel_interval_ticks: 10,
+                funnel_senders: 6,
+                funnel_wire_amount: 1_500.0,
+                dispersal_lag_ticks: 2,
+                funnel_retention: 0.08,
+                funnel_payees: 5,
+                inbound_interval_ticks: 30,
+                inbound_amount: 600.0,
+                max_inbound_senders: 2,
+                inbound_forward_share: 0.30,
+                lookback_ticks: 30,
+                min_senders: 5,
+                min_payees: 4,
+                dispersal_ratio: 0.70,
+                alert_score: 55.0,
+                elevated_uplift: 10.0,
+                high_risk_uplift: 25.0,
+            },
+            calendar: {
+                let calendar_path = format!("{data_dir}/calendar/business_calendar.json");
+                let calendar_content = std::fs::read_to_string(&calendar_path)
+                    .map_err(|e| anyhow::anyhow!("Cannot read {calendar_path}: {e}"))?;
+                let calendar: BusinessCalendarConfig = serde_json::from_str(&calendar_content)?;
+                crate::calendar::BusinessCalendar::new(&calendar)?;
+                calendar
+            },
+            feature_flags: {
+                let flags_path = format!("{data_dir}/features/feature_flags.json");
+                let flags_content = std::fs::read_to_string(&flags_path)
+                    .map_err(|e| anyhow::anyhow!("Cannot read {flags_path}: {e}"))?;
+                serde_json::from_str(&flags_content)?
+            },
+            authorization: {
+                let auth_path = format!("{data_dir}/features/authorization.json");
+                let auth_content = std::fs::read_to_string(&auth_path)
+                    .map_err(|e| anyhow::anyhow!("Cannot read {auth_path}: {e}"))?;
+                serde_json::from_str(&auth_content)?
+            },
+            deposit_run: DepositRunConfig {
+                enabled: true,
+                shock_tick: None,
+                reputation_trigger: 35.0,
+                cooldown_ticks: 180,
+                insured_limit: 250_000.0,
+                initial_run_rate: 0.02,
+                acceleration: 1.25,
+                max_run_rate: 0.40,
+                calm_run_rate: 0.002,
+                insured_flight_share: 0.02,
+                rate_sensitivity: 0.2,
+                max_rate_premium: 0.05,
+                liquid_asset_ratio: 0.15,
+                capital_ratio: 0.10,
+                min_liquidity_coverage: 0.10,
+                min_capital_ratio: 0.08,
+                neutral_reputation: 75.0,
+                reputation_weight: 0.25,
+                liquidity_weight: 0.30,
+                capital_weight: 0.20,
+                emergency_capacity: 0.30,
+                emergency_rate: 0.055,
+                penalty_rate: 0.15,
+            },
+            bec: BecConfig {
+                enabled: true,
+                segments: vec!["small_business".into()],
+                spoofed_invoice_rate: 0.002,
+                fall_rate: 0.35,
+                vendor_invoice_rate: 0.05,
+                regular_vendors: 5,
+                new_vendor_share: 0.2,
+                spoofed_share_min: 0.4,
+                spoofed_share_max: 0.9,
+                vendor_share_min: 0.05,
+                vendor_share_max: 0.25,
+                min_invoice: 250.0,
+                lookback_ticks: 60,
+                amount_multiple: 1.5,
+                base_score: 0.6,
+                score_per_doubling: 0.1,
+                recall_recovery_rate: 0.85,
+                recall_decay: 0.6,
+                recall_window_ticks: 10,
+                desk_recall_ticks: Some(3),
+            },
+            check_fraud: CheckFraudConfig {
+                enabled: true,
+                writer_segments: vec!["small_business".into()],
+                check_rate: 0.05,
+                check_share_min: 0.02,
+                check_share_max: 0.15,
+                min_check: 50.0,
+                first_serial: 1001,
+                washed_rate: 0.002,
+                washed_raise_min: 2.0,
+                washed_raise_max: 5.0,
+                washed_copy_share: 0.6,
+                deposit_rate: 0.01,
+                counterfeit_rate: 0.0005,
+                deposit_amount_min: 100.0,
+                deposit_amount_max: 2_500.0,
+                counterfeit_amount_min: 1_500.0,
+                counterfeit_amount_max: 4_500.0,
+                serial_min: 1001,
+                serial_max: 9999,
+                counterfeit_off_range_share: 0.7,
+                serial_tolerance: 50,
+                cash_out_ticks: 1,
+                review_capacity: 20,
+                paying_deadline_ticks: 1,
+                deposit_return_ticks: 5,
+                late_return_rate: 0.05,
+                wave_start_tick: None,
+                wave_ticks: 14,
+                wave_multiplier: 10.0,
+                branch_share: 0.5,
+            },
+            deposit_holds: DepositHoldConfig {
+                enabled: true,
+                next_day_amount: 225.0,
+                policies: default_hold_policies(),
+                statutory_limits: default_hold_statutory_limits(),
+                new_account_ticks: 30,
+                large_item_threshold: 5_525.0,
+                risk_lookback_ticks: 90,
+                liquidity_sensitivity: [
+                    ("mass_market".into(), 1.0),
+                    ("small_business".into(), 0.6),
+                    ("premium".into(), 0.1),
+                ]
+                .into(),
+                satisfaction_per_pressure: 0.01,
+            },
+            event_stream: EventStreamConfig {
+                enabled: false, // opt-in: sim-runner --event-stream
+                path: "events.jsonl".into(),
+                rotate_bytes: Some(100 * 1024 * 1024),
+                keep_files: 5,
+            },
+            evidence_bundles: EvidenceBundleConfig {
+                enabled: true,
+                lookback_ticks: 30,
+                max_transactions: 25,
+                min_quality: 0.75,
+            },
+            alert_grouping: AlertGroupingConfig {
+                enabled: true,
+                window_ticks: 14,
+                type_windows: [("sanctions_hit".into(), 30), ("velocity".into(), 7)].into(),
+                min_relationship_strength: Some(0.7),
+            },
+            operational_risk: OperationalRiskConfig {
+                enabled: true,
+                lookback_ticks: 365,
+                bic_coefficient: 0.12,
+                loss_multiplier: 15.0,
+            },
+            quarter_close: QuarterCloseConfig {
+                enabled: true,
+                signoff_ticks: 15,
+                provisional_credit_reserve_rate: 0.35,
+            },
+            opex_allocation,
+            churn_evaluation: ChurnEvaluationConfig {
+                enabled: true,
+                min_customers: 20,
+                auc_drop_tolerance: 0.10,
+                brier_rise_tolerance: 0.05,
+            },
+            complaint_quality: ComplaintQualityConfig {
+                enabled: true,
+                analyst_skill: 0.7,
+                thorough_handling_ticks: 3,
+                appropriate_resolutions: [
+                    ("fee_dispute", &["monetary_relief", "explanation_only", "no_error_found"][..]),
+                    ("unauthorized_transaction", &["monetary_relief", "no_error_found"]),
+                    ("service_failure", &["monetary_relief", "non_monetary_relief"]),
+                    ("missing_payment", &["monetary_relief", "non_monetary_relief", "no_error_found"]),
+                    ("unwanted_contact", &["non_monetary_relief", "explanation_only"]),
+                    ("account_access", &["non_monetary_relief", "explanation_only"]),
+                ]
+                .into_iter()
+                .map(|(issue, codes)| (issue.to_string(), codes.iter().map(|c| c.to_string()).collect()))
+                .collect(),
+                skill_weight: 0.4,
+                time_weight: 0.3,
+                appropriateness_weight: 0.3,
+                poor_quality_threshold: 0.5,
+                max_reopen_probability: 0.40,
+                max_escalation_probability: 0.15,
+                escalation_udaap_impact: 1.0,
+                report_interval_ticks: 30,
+            },
+            scenario_scripts: ScenarioScriptConfig {
+                enabled: true,
+                scripts: ScenarioScript::load_dir(&format!("{data_dir}/scripts"))?,
+                max_operations: 100_000,
+            },
+            stress_index: StressIndexConfig {
+                enabled: true,
+                backlog_per_1k: PressureBand { calm: 5.0, edge: 40.0 },
+                capital_ratio: PressureBand { calm: 0.12, edge: 0.08 },
+                reputation: PressureBand { calm: 80.0, edge: 40.0 },
+                staffing_utilization: PressureBand { calm: 0.8, edge: 1.25 },
+                open_findings: PressureBand { calm: 0.0, edge: 6.0 },
+                weights: StressWeights {
+                    backlog: 0.25,
+                    capital: 0.25,
+                    reputation: 0.2,
+                    staffing: 0.15,
+                    findings: 0.15,
+                },
+                finding_lookback_ticks: 180,
+                strained_below: 60.0,
+                critical_below: 30.0,
+            },
+            alert_qa: AlertQaConfig {
+                enabled: true,
+                interval_ticks: 30,
+                sample_rate: 0.1,
+                review_capacity: 40,
+                reviewer_accuracy: 0.95,
+                cost_per_review: 120.0,
+                retraining_threshold: 0.25,
+                retraining_min_reviews: 4,
+                retraining_ticks: 14,
+                retraining_cost: 6_000.0,
+            },
+            macro_regime: MacroRegimeConfig::default(),
+            staffing: StaffingConfig {
+                enabled: true,
+                queues: default_queue_staffing(),
+                fte_daily_cost: 420.0,
+                overtime_premium: 1.5,
+                max_overtime_share: 0.25,
+                complaint_resolution_code: "explanation_only".into(),
+            },
+            enforcement: EnforcementConfig {
+                enabled: true,
+                udaap_exam_rise_per_1k: 150.0,
+                udaap_major_rise_per_1k: 100.0,
+                udaap_critical_rise_per_1k: 250.0,
+                sample_size: 25,
+                min_sample: 5,
+                sample_failure_share: 0.25,
+                mra_remediation_ticks: 60,
+                consent_order_remediation_ticks: 150,
+                repeat_penalty_multiplier: 2.0,
+                lookback_ticks: 180,
+                lookback_review_ticks: 60,
+                lookback_cost_per_item: 150.0,
+            },
+            lending: LendingConfig {
+                enabled: true,
+                products: default_loan_products(),
+                monthly_application_rate: 0.02,
+                payment_interval_ticks: 30,
+                max_dti: 0.36,
+                max_miss_rate: 0.25,
+                charge_off_dpd: 120,
+                recovery_rate: 0.1,
+            },
+            rate_environment: RateEnvironmentConfig {
+                enabled: true,
+                rate_step: 0.0025,
+                regime_moves: default_regime_moves(),
+                term_spread: 0.015,
+                neutral_rate: 0.03,
+                curve_flattening: 0.5,
+                deposit_betas: default_deposit_betas(),
+                default_deposit_beta: 0.2,
+                earning_asset_share: 0.5,
+                fixed_asset_share: 0.5,
+                asset_repricing_ticks: 360,
+            },
+            alert_triage: AlertTriageConfig {
+                enabled: true,
+                aged_after_ticks: 20,
+            },
+            case_management: CaseManagementConfig {
+                enabled: true,
+                min_relationship_strength: Some(0.7),
+                complaint_resolution_code: "explanation_only".into(),
+            },
+            mule_networks: MuleNetworkConfig {
+                enabled: true,
+                seed_daily_rate: 0.02,
+                max_networks: 3,
+                min_mules: 3,
+                max_mules: 8,
+                cycle_interval_ticks: 7,
+                credits_per_cycle: 3,
+                credit_amount: 1_200.0,
+                forward_lag_ticks: 1,
+                mule_cut: 0.10,
+                relationship_strength: 0.8,
+                lifetime_ticks: 120,
+            },
+            structuring: StructuringConfig {
+                enabled: true,
+                risk_levels: vec!["high".into(), "critical".into()],
+                structurer_rate: 0.25,
+                episode_interval_ticks: 30,
+                deposits_per_episode: 4,
+                episode_spread_ticks: 5,
+                min_deposit: 8_500.0,
+                max_deposit: 9_900.0,
+            },
+            sanctions_screening: SanctionsScreeningConfig {
+                candidate_name_score: 0.80,
+                alias_discount: 0.95,
+                name_weight: 0.60,
+                country_weight: 0.20,
+                birth_year_weight: 0.20,
+                birth_year_tolerance: 1,
+                sensitivity: 0.5,
+                strict_match_threshold: 0.90,
+                lenient_match_threshold: 0.55,
+                exact_match_threshold: 0.95,
+                listed_party_rate: 0.002,
+                alias_use_rate: 0.4,
+            },
+            channels: ChannelConfig {
+                enabled: true,
+                cash_mix: BTreeMap::from([(Channel::Atm, 0.80), (Channel::Branch, 0.20)]),
+                payment_mix: BTreeMap::from([
+                    (Channel::Mobile, 0.45),
+                    (Channel::Online, 0.35),
+                    (Channel::Branch, 0.10),
+                    (Channel::CallCenter, 0.10),
+                ]),
+                cost_per_transaction: BTreeMap::from([
+                    (Channel::Branch, 4.00),
+                    (Channel::Atm, 0.85),
+                    (Channel::Online, 0.20),
+                    (Channel::Mobile, 0.10),
+                    (Channel::CallCenter, 3.50),
+                ]),
+                degraded_failure_rate: 0.30,
+                fallback_rate: 0.60,
+            },
+            app_fraud: AppFraudConfig {
+                enabled: true,
+                segments: vec!["mass_market".into(), "premium".into()],
+                scam_rate: 0.0005,
+                fall_rate: 0.4,
+                scam_types: vec!["purchase".into(), "impersonation".into(), "investment".into(), "romance".into()],
+                rails: vec!["RTP".into(), "FedNow".into()],
+                share_min: 0.2,
+                share_max: 0.8,
+                min_payment: 100.0,
+                report_rate: 0.8,
+                report_min_ticks: 1,
+                report_max_ticks: 14,
+                decision_ticks: 5,
+                reimbursement_policy: "vulnerable".into(),
+                vulnerable_age: 65,
+                excess: 100.0,
+                reimbursement_cap: 85_000.0,
+            },
+            atm_network: AtmNetworkConfig {
+                enabled: true,
+                atms: 40,
+                locations: vec!["branch".into(), "retail".into(), "transit".into()],
+                home_rate: 0.7,
+                fault_rate: 0.01,
+                repair_ticks: 2,
+                cash_out_rate: 0.01,
+                cash_out_cards: 6,
+                cash_out_withdrawals: 3,
+                cash_out_amount: 400.0,
+                multi_atm_threshold: 3,
+            },
+            merchants: MerchantConfig {
+                enabled: true,
+                count: 100,
+                categories: BTreeMap::from([
+                    ("retail".into(), 0.40),
+                    ("grocery".into(), 0.20),
+                    ("travel".into(), 0.10),
+                    ("digital_goods".into(), 0.15),
+                    ("subscription".into(), 0.15),
+                ]),
+                risk_tiers: BTreeMap::from([("low".into(), 0.70), ("medium".into(), 0.25), ("high".into(), 0.05)]),
+                fraud_rates: BTreeMap::from([("low".into(), 0.005), ("medium".into(), 0.012), ("high".into(), 0.03)]),
+                bad_merchant_rate: 0.01,
+                bad_merchant_ticks: 14,
+                bad_merchant_multiplier: 8.0,
+                chargeback_window_ticks: 30,
+                chargeback_alert_rate: 0.01,
+                chargeback_alert_min_disputes: 3,
+            },
+            devices: DeviceTelemetryConfig {
+                enabled: true,
+                digital_rate: 0.6,
+                login_rate: 0.3,
+                new_device_rate: 0.005,
+                takeover_rate: 0.05,
+                farm_devices: 3,
+                takeover_share: 0.8,
+                high_value_amount: 1000.0,
+                new_device_ticks: 3,
+                shared_device_threshold: 3,
             },
         })
     }
@@ -860,6 +3979,7 @@ impl SimConfig {
             priority: "standard".into(),
             sla_acknowledge_days: 2,
             sla_resolve_days: 15,
+            channel: "phone".into(),
         }];
 
         let resolution_codes = [
@@ -884,6 +4004,26 @@ impl SimConfig {
         ]
         .into();
 
+        let resolution_templates = [
+            (
+                "refund_small_fees".into(),
+                ResolutionTemplate {
+                    template_id: "refund_small_fees".into(),
+                    resolution_code: "monetary_relief".into(),
+                    refund: RefundRule::FeeUpTo { max: 35.0 },
+                },
+            ),
+            (
+                "explain_policy".into(),
+                ResolutionTemplate {
+                    template_id: "explain_policy".into(),
+                    resolution_code: "explanation_only".into(),
+                    refund: RefundRule::Nothing,
+                },
+            ),
+        ]
+        .into();
+
         let products = [(
             "basic_checking".into(),
             ProductConfig {
@@ -999,6 +4139,7 @@ impl SimConfig {
                 label: "$100 Sign-Up Bonus".into(),
                 product_id: Some("basic_checking".into()),
                 bonus_amount: 100.0,
+                credit_limit: 0.0,
                 requirements: OfferRequirements {
                     min_direct_deposit: 500.0,
                     min_balance: 100.0,
@@ -1012,6 +4153,7 @@ impl SimConfig {
                     max_existing_products: None, // allow customers with their first account
                     min_churn_risk: None,
                     max_churn_risk: None,
+                    exclude_zip_prefixes: vec![],
                 },
                 cost_model: OfferCostModel {
                     bonus_paid_on_completion: true,
@@ -1137,6 +4279,7 @@ impl SimConfig {
                 repeat_complainer_threshold: 3,
                 segment_concentration_warning: 0.60,
                 issue_type_concentration_warning: 0.50,
+                aged_backlog_growth_warning: None, // disabled by default in tests (opt-in)
             },
             cost_analysis: ComplaintCostConfig {
                 average_handle_time_minutes: [("standard".into(), 35)].into(),
@@ -1257,7 +4400,8 @@ impl SimConfig {
                     fraud_risk_multiplier: 0.5,
                     operational_risk_base: 0.001,
                     batch_window_ticks: Some(4),
-                    cutoff_time_tick: None,
+                    cutoff_time_tick: Some(16),
+                    irrevocable: false,
                 },
                 PaymentRailConfig {
                     rail_id: "wire".into(),
@@ -1268,6 +4412,7 @@ impl SimConfig {
                     operational_risk_base: 0.002,
                     batch_window_ticks: None,
                     cutoff_time_tick: Some(18),
+                    irrevocable: false,
                 },
                 PaymentRailConfig {
                     rail_id: "RTP".into(),
@@ -1278,6 +4423,7 @@ impl SimConfig {
                     operational_risk_base: 0.0015,
                     batch_window_ticks: None,
                     cutoff_time_tick: None,
+                    irrevocable: true,
                 },
                 PaymentRailConfig {
                     rail_id: "card".into(),
@@ -1288,6 +4434,7 @@ impl SimConfig {
                     operational_risk_base: 0.0012,
                     batch_window_ticks: Some(1),
                     cutoff_time_tick: None,
+                    irrevocable: false,
                 },
             ],
             interchange_fee_rate: 0.025,
@@ -1303,6 +4450,8 @@ impl SimConfig {
             initial_population: 50,
             complaint_triggers: triggers,
             resolution_codes,
+            resolution_templates,
+            complaint_propensity: ComplaintPropensity::default(),
             products,
             fee_constraints,
             impact_formulas,
@@ -1407,6 +4556,798 @@ impl SimConfig {
                 onboarding_penalty_threshold: 40.0,
                 max_onboarding_penalty: 0.50,
             },
+            credit_bureau: CreditBureauConfig {
+                enabled: false, // disabled by default in tests (opt-in)
+                furnish_interval_ticks: 30,
+                furnishing_error_rate: 0.01,
+                dispute_probability: 0.40,
+                investigation_window_ticks: 30,
+                daily_correction_rate: 0.06,
+                late_correction_penalty: 2_500.0,
+            },
+            protected_funds: ProtectedFundsConfig {
+                enabled: false, // disabled by default in tests (opt-in)
+                beneficiary_share: 0.12,
+                benefit_payment_day: 3,
+                default_monthly_benefit: 1_900.0,
+                lookback_ticks: 60,
+                enforce_protection: true,
+                garnishment_daily_rate: 0.002,
+                violation_udaap_impact: 0.5,
+            },
+            branch_network: BranchNetworkConfig {
+                enabled: false, // disabled by default in tests (opt-in)
+                min_customers_for_branch: 25,
+                customers_per_staff: 150,
+                min_staff: 3,
+                staff_monthly_cost: 5_500.0,
+                occupancy_monthly_cost: 12_000.0,
+                acquisitions_per_branch_month: 6.0,
+                closure_churn_delta: 0.10,
+                closure_satisfaction_hit: 0.08,
+                open_churn_relief: 0.04,
+                network_change_effect_ticks: 90,
+                report_interval_ticks: 30,
+            },
+            fair_lending: FairLendingConfig {
+                enabled: false, // disabled by default in tests (opt-in)
+                high_minority_zip_share: 0.25,
+                lmi_income_ratio: 0.8,
+                review_interval_ticks: 90,
+                min_group_size: 20,
+                adverse_ratio_threshold: 1.25,
+                major_ratio_threshold: 1.5,
+                response_window_ticks: 30,
+                escalation_udaap_impact: 1.0,
+                remediation_udaap_relief: 0.25,
+                remediation_cost: 40_000.0,
+            },
+            offer_guardrail: OfferGuardrailConfig {
+                enabled: false, // disabled by default in tests (opt-in)
+                warn_ratio: 1.25,
+                block_ratio: 2.0,
+                warn_udaap_impact: 0.25,
+            },
+            network_invoice: NetworkInvoiceConfig {
+                enabled: false, // disabled by default in tests (opt-in)
+                invoice_interval_ticks: 30,
+                assessment_rate: 0.0014,
+                dispute_fee: 25.0,
+                network_error_rate: 0.08,
+                min_error_pct: 0.02,
+                max_error_pct: 0.12,
+                recovery_window_ticks: 60,
+                recovery_success_rate: 0.85,
+            },
+            settlement_accounts: SettlementAccountConfig {
+                enabled: false, // disabled by default in tests (opt-in)
+                projection_window_ticks: 7,
+                funding_buffer_pct: 0.10,
+                penalty_rate: 0.0005,
+                borrowing_fee: 150.0,
+                alert_shortfall_pct: 0.25,
+            },
+            payment_cutoff: PaymentCutoffConfig {
+                enabled: false, // disabled by default in tests (opt-in)
+                initiation_start_hour: 6,
+                initiation_end_hour: 22,
+            },
+            ach_returns: AchReturnConfig {
+                enabled: false, // disabled by default in tests (opt-in)
+                return_rate: 0.015,
+                return_delay_ticks: 2,
+                represent_delay_ticks: 2,
+                represent_success_rate: 0.55,
+                contact_satisfaction_delta: -0.02,
+                dispute_review_ticks: 10,
+                originator_contest_rate: 0.20,
+                codes: default_ach_return_codes(),
+            },
+            ach_origination: AchOriginationConfig {
+                enabled: false, // disabled by default in tests (opt-in)
+                bill_pay_daily_rate: 0.04,
+                external_transfer_daily_rate: 0.01,
+                takeover_daily_rate: 0.000_02,
+                takeover_burst_size: 6,
+                daily_count_limit: 5,
+                daily_amount_limit: 5_000.0,
+                declined_satisfaction_delta: -0.02,
+                return_delay_ticks: 2,
+                administrative_return_rate: 0.005,
+                nsf_return_rate: 0.03,
+                unauthorized_return_rate: 0.001,
+                review_interval_ticks: 30,
+                review_lookback_ticks: 60,
+                unauthorized_threshold: 0.005,
+                administrative_threshold: 0.03,
+                overall_threshold: 0.15,
+                network_fine: 25_000.0,
+            },
+            external_links: ExternalLinkConfig {
+                enabled: false, // disabled by default in tests (opt-in)
+                verification_strictness: 0.5,
+                micro_deposit_delay_ticks: 2,
+                micro_deposit_confirm_rate: 0.85,
+                instant_bypass_rate: 0.90,
+                micro_deposit_bypass_rate: 0.20,
+                dd_switch_daily_rate: 0.002,
+                mule_link_daily_rate: 0.000_05,
+                drain_window_ticks: 3,
+                failed_link_satisfaction_delta: -0.03,
+            },
+            customer_limits: CustomerLimitConfig {
+                enabled: false, // disabled by default in tests (opt-in)
+                declined_satisfaction_delta: -0.02,
+                rules: default_customer_limit_rules(),
+            },
+            risk_lifecycle: RiskLifecycleConfig {
+                enabled: false, // disabled by default in tests (opt-in)
+                rescore_interval: 7,
+                signal_lookback_ticks: 30,
+                alert_weight: 0.25,
+                decline_weight: 0.05,
+                unauthorized_weight: 0.35,
+                medium_threshold: 0.30,
+                high_threshold: 0.60,
+                critical_threshold: 0.90,
+                tiers: default_risk_tier_servicing(),
+            },
+            ssn_validation: SsnValidationConfig {
+                enabled: false, // disabled by default in tests (opt-in)
+                invalid_range_weight: 0.40,
+                issuance_mismatch_weight: 0.25,
+                deceased_weight: 0.50,
+                death_master_file_size: 200,
+            },
+            idv: IdvConfig {
+                enabled: false, // disabled by default in tests (opt-in)
+                vendor: "standard".into(),
+                vendors: default_idv_vendors(),
+            },
+            sar_decision: SarDecisionConfig {
+                enabled: false, // disabled by default in tests (opt-in)
+                filing_threshold: 85.0,
+                reportable_score: 85.0,
+                cost_per_filing: 250.0,
+            },
+            rule_tuning: RuleTuningConfig {
+                enabled: false, // disabled by default in tests (opt-in)
+                review_ticks: 14,
+                review_cost: 2_500.0,
+            },
+            coverage_assessment: CoverageAssessmentConfig {
+                enabled: false, // disabled by default in tests (opt-in)
+                interval_ticks: 30,
+            },
+            time_acceleration: TimeAccelerationConfig {
+                enabled: false, // disabled by default in tests (opt-in)
+                coarse_after_tick: 365,
+                coarse_step_ticks: 7,
+                coarse_risk_tiers: vec!["low".into()],
+            },
+            population_scale: 1.0,
+            event_log_retention: EventLogRetentionConfig {
+                enabled: false, // disabled by default in tests (opt-in)
+                detail_ticks: 365,
+                compact_interval_ticks: 30,
+                export_dir: None,
+            },
+            complaint_sla: ComplaintSlaConfig {
+                policies: Vec::new(),
+                regulatory_limits: default_sla_regulatory_limits(),
+            },
+            hardship: HardshipConfig {
+                enabled: false, // disabled by default in tests (opt-in)
+                eligible_life_events: vec!["job_change".into(), "medical_emergency".into(), "divorce".into()],
+                enrollment_probability: 0.40,
+                duration_ticks: 90,
+                waived_fee_types: vec!["overdraft".into()],
+                suspend_setoffs: true,
+                churn_risk_relief: 0.10,
+                enrollment_satisfaction_delta: 0.05,
+            },
+            case_documents: CaseDocumentConfig {
+                enabled: false, // disabled by default in tests (opt-in)
+                missing_probability: 0.05,
+            },
+            contact_fatigue: ContactFatigueConfig {
+                enabled: false, // disabled by default in tests (opt-in)
+                do_not_contact_rate: 0.08,
+                window_ticks: 30,
+                response_decay: 0.75,
+                off_channel_response_factor: 0.5,
+                fatigue_threshold: 3,
+                fatigue_satisfaction_delta: -0.02,
+                offer_response_rate: 0.30,
+                survey_response_rate: 0.25,
+                collections_response_rate: 0.40,
+                survey_interval_ticks: 30,
+                survey_sample_rate: 0.25,
+                collections_interval_ticks: 7,
+            },
+            satisfaction_dynamics: SatisfactionDynamicsConfig {
+                enabled: false, // disabled by default in tests (opt-in)
+                segment_baselines: [
+                    ("mass_market".into(), 0.70),
+                    ("mid_tier".into(), 0.75),
+                    ("student".into(), 0.65),
+                    ("small_business".into(), 0.70),
+                ]
+                .into(),
+                default_baseline: 0.70,
+                monthly_reversion: 0.15,
+                goodwill_window_ticks: 180,
+                goodwill_decay: 0.5,
+                scar_priorities: vec!["urgent".into()],
+                scar_severity: 0.08,
+                baseline_floor: 0.30,
+            },
+            financial_health: FinancialHealthConfig {
+                enabled: false, // disabled by default in tests (opt-in)
+                lookback_ticks: 90,
+                volatility_weight: 0.35,
+                overdraft_weight: 0.40,
+                payroll_weight: 0.25,
+                volatility_saturation: 3.0,
+                overdraft_saturation: 6,
+                credit_offer_types: vec!["credit_line".into()],
+                default_min_credit_score: 0.5,
+                low_health_threshold: 0.4,
+                low_health_udaap_impact: 0.05,
+                max_monthly_default_rate: 0.10,
+                loss_given_default: 0.85,
+                high_risk_spend_penalty: 0.5,
+            },
+            transaction_enrichment: TransactionEnrichmentConfig {
+                enabled: false, // disabled by default in tests (opt-in)
+                accuracy: 0.92,
+                categories: default_merchant_categories(),
+                high_risk_categories: vec!["gambling".into(), "crypto".into()],
+                intensity_lookback_ticks: 30,
+                intensity_threshold: 0.30,
+                intensity_min_spend: 250.0,
+                intensity_alert_score: 60.0,
+            },
+            remittance: RemittanceConfig {
+                enabled: false, // disabled by default in tests (opt-in)
+                corridors: default_remittance_corridors(),
+                participation_rate: 0.60,
+                layering_rate: 0.05,
+                max_family_beneficiaries: 2,
+                layering_interval_ticks: 7,
+                layering_inflow_multiple: 8.0,
+                layering_fanout: 5,
+                lookback_ticks: 30,
+                min_beneficiaries: 4,
+                min_corridors: 2,
+                pass_through_threshold: 0.50,
+                alert_score: 55.0,
+                medium_risk_uplift: 10.0,
+                high_risk_uplift: 25.0,
+            },
+            visa_expiry: VisaExpiryConfig {
+                enabled: false, // disabled by default in tests (opt-in)
+                validity: default_visa_validity(),
+                refresh_lead_ticks: 60,
+                renewal_rate: 0.85,
+                late_submission_ticks: 30,
+                processing_ticks: 10,
+                refresh_cost: 20.0,
+                restriction_level: "outbound".into(),
+                grace_ticks: 30,
+                outbound_satisfaction_delta: -0.05,
+                full_satisfaction_delta: -0.15,
+                pending_satisfaction_delta: -0.10,
+                blocked_satisfaction_delta: -0.01,
+            },
+            adverse_media: AdverseMediaConfig {
+                enabled: false, // disabled by default in tests (opt-in)
+                categories: default_adverse_media_categories(),
+                articles_per_tick: 2.0,
+                customer_subject_rate: 0.25,
+                counterparty_subject_rate: 0.20,
+                namesake_rate: 0.30,
+                name_variant_rate: 0.25,
+                detail_rate: 0.70,
+                counterparty_lookback_ticks: 30,
+                candidate_name_score: 0.50,
+                name_weight: 0.50,
+                country_weight: 0.20,
+                birth_year_weight: 0.30,
+                match_threshold: 0.75,
+                edd_match_score: 0.90,
+                edd_severity: 0.70,
+                edd_due_ticks: 14,
+                risk_impact_scale: 0.40,
+                counterparty_impact_share: 0.50,
+            },
+            correspondent_banking: CorrespondentBankingConfig {
+                enabled: false, // disabled by default in tests (opt-in)
+                respondents: default_respondent_banks(),
+                nested_countries: vec!["VE".into(), "RU".into(), "MM".into(), "SY".into()],
+                nested_originators: 20,
+                stripping_rate: 0.60,
+                clearing_fee: 15.0,
+                due_diligence: default_correspondent_due_diligence(),
+                response_rate: 0.90,
+                response_ticks: 14,
+                overdue_ticks: 30,
+                disclosure_rate: 0.50,
+                lookback_ticks: 30,
+                min_third_party_share: 0.05,
+                originator_excess: 1.25,
+                alert_score: 50.0,
+                medium_risk_uplift: 10.0,
+                high_risk_uplift: 25.0,
+                undisclosed_uplift: 15.0,
+            },
+            cross_border: CrossBorderConfig {
+                enabled: false, // disabled by default in tests (opt-in)
+                funnel_rate: 0.02,
+                inbound_rate: 0.25,
+                source_countries: default_cross_border_source_countries(),
+                funnel_interval_ticks: 10,
+                funnel_senders: 6,
+                funnel_wire_amount: 1_500.0,
+                dispersal_lag_ticks: 2,
+                funnel_retention: 0.08,
+                funnel_payees: 5,
+                inbound_interval_ticks: 30,
+                inbound_amount: 600.0,
+                max_inbound_senders: 2,
+                inbound_forward_share: 0.30,
+                lookback_ticks: 30,
+                min_senders: 5,
+                min_payees: 4,
+                dispersal_ratio: 0.70,
+                alert_score: 55.0,
+                elevated_uplift: 10.0,
+                high_risk_uplift: 25.0,
+            },
+            calendar: BusinessCalendarConfig {
+                enabled: false, // disabled by default in tests (opt-in)
+                start_date: "2025-01-01".into(),
+                time_zone: "America/New_York".into(),
+                utc_offset_hours: -5,
+                weekend_days: vec!["Sat".into(), "Sun".into()],
+                federal_holidays: true,
+                extra_holidays: Vec::new(),
+            },
+            feature_flags: FeatureFlagsConfig::default(), // every subsystem on
+            authorization: AuthorizationConfig {
+                enabled: false, // disabled by default in tests (opt-in)
+                default_role: Role::Analyst,
+                analyst_refund_limit: 250.0,
+                manager_refund_limit: 2_500.0,
+                dial_outside_comfort_zone: Role::Manager,
+                command_roles: [
+                    ("set_sar_threshold".into(), Role::Cco),
+                    ("tune_monitoring_rule".into(), Role::Manager),
+                ]
+                .into(),
+            },
+            deposit_run: DepositRunConfig {
+                enabled: false, // disabled by default in tests (opt-in)
+                shock_tick: None,
+                reputation_trigger: 35.0,
+                cooldown_ticks: 180,
+                insured_limit: 250_000.0,
+                initial_run_rate: 0.02,
+                acceleration: 1.25,
+                max_run_rate: 0.40,
+                calm_run_rate: 0.002,
+                insured_flight_share: 0.02,
+                rate_sensitivity: 0.2,
+                max_rate_premium: 0.05,
+                liquid_asset_ratio: 0.15,
+                capital_ratio: 0.10,
+                min_liquidity_coverage: 0.10,
+                min_capital_ratio: 0.08,
+                neutral_reputation: 75.0,
+                reputation_weight: 0.25,
+                liquidity_weight: 0.30,
+                capital_weight: 0.20,
+                emergency_capacity: 0.30,
+                emergency_rate: 0.055,
+                penalty_rate: 0.15,
+            },
+            bec: BecConfig {
+                enabled: false, // disabled by default in tests (opt-in)
+                segments: vec!["small_business".into()],
+                spoofed_invoice_rate: 0.002,
+                fall_rate: 0.35,
+                vendor_invoice_rate: 0.05,
+                regular_vendors: 5,
+                new_vendor_share: 0.2,
+                spoofed_share_min: 0.4,
+                spoofed_share_max: 0.9,
+                vendor_share_min: 0.05,
+                vendor_share_max: 0.25,
+                min_invoice: 250.0,
+                lookback_ticks: 60,
+                amount_multiple: 1.5,
+                base_score: 0.6,
+                score_per_doubling: 0.1,
+                recall_recovery_rate: 0.85,
+                recall_decay: 0.6,
+                recall_window_ticks: 10,
+                desk_recall_ticks: None,
+            },
+            check_fraud: CheckFraudConfig {
+                enabled: false, // disabled by default in tests (opt-in)
+                writer_segments: vec!["small_business".into()],
+                check_rate: 0.05,
+                check_share_min: 0.02,
+                check_share_max: 0.15,
+                min_check: 50.0,
+                first_serial: 1001,
+                washed_rate: 0.002,
+                washed_raise_min: 2.0,
+                washed_raise_max: 5.0,
+                washed_copy_share: 0.6,
+                deposit_rate: 0.01,
+                counterfeit_rate: 0.0005,
+                deposit_amount_min: 100.0,
+                deposit_amount_max: 2_500.0,
+                counterfeit_amount_min: 1_500.0,
+                counterfeit_amount_max: 4_500.0,
+                serial_min: 1001,
+                serial_max: 9999,
+                counterfeit_off_range_share: 0.7,
+                serial_tolerance: 50,
+                cash_out_ticks: 1,
+                review_capacity: 20,
+                paying_deadline_ticks: 1,
+                deposit_return_ticks: 2,
+                late_return_rate: 0.05,
+                wave_start_tick: None,
+                wave_ticks: 14,
+                wave_multiplier: 10.0,
+                branch_share: 0.5,
+            },
+            deposit_holds: DepositHoldConfig {
+                enabled: false, // disabled by default in tests (opt-in)
+                next_day_amount: 225.0,
+                policies: default_hold_policies(),
+                statutory_limits: default_hold_statutory_limits(),
+                new_account_ticks: 30,
+                large_item_threshold: 5_525.0,
+                risk_lookback_ticks: 90,
+                liquidity_sensitivity: [
+                    ("mass_market".into(), 1.0),
+                    ("small_business".into(), 0.6),
+                    ("premium".into(), 0.1),
+                ]
+                .into(),
+                satisfaction_per_pressure: 0.01,
+            },
+            event_stream: EventStreamConfig {
+                enabled: false, // disabled by default in tests (opt-in)
+                path: "events.jsonl".into(),
+                rotate_bytes: None,
+                keep_files: 5,
+            },
+            evidence_bundles: EvidenceBundleConfig {
+                enabled: false, // disabled by default in tests (opt-in)
+                lookback_ticks: 30,
+                max_transactions: 25,
+                min_quality: 0.75,
+            },
+            alert_grouping: AlertGroupingConfig {
+                enabled: false, // disabled by default in tests (opt-in)
+                window_ticks: 14,
+                type_windows: HashMap::new(),
+                min_relationship_strength: Some(0.7),
+            },
+            operational_risk: OperationalRiskConfig {
+                enabled: false, // disabled by default in tests (opt-in)
+                lookback_ticks: 365,
+                bic_coefficient: 0.12,
+                loss_multiplier: 15.0,
+            },
+            quarter_close: QuarterCloseConfig {
+                enabled: false, // disabled by default in tests (opt-in)
+                signoff_ticks: 15,
+                provisional_credit_reserve_rate: 0.35,
+            },
+            opex_allocation: OpexAllocationConfig {
+                enabled: false, // disabled by default in tests (opt-in)
+                pools: vec![
+                    OpexPoolConfig {
+                        pool: "branch_network".into(),
+                        quarterly_cost: 20_000.0,
+                        driver: "customers".into(),
+                        headcount: HashMap::new(),
+                    },
+                    OpexPoolConfig {
+                        pool: "payment_operations".into(),
+                        quarterly_cost: 15_000.0,
+                        driver: "transactions".into(),
+                        headcount: HashMap::new(),
+                    },
+                    OpexPoolConfig {
+                        pool: "service_desk".into(),
+                        quarterly_cost: 10_000.0,
+                        driver: "headcount".into(),
+                        headcount: [
+                            ("mass_market".into(), 5.0),
+                            ("small_business".into(), 3.0),
+                            ("premium".into(), 2.0),
+                        ]
+                        .into(),
+                    },
+                ],
+            },
+            churn_evaluation: ChurnEvaluationConfig {
+                enabled: false, // disabled by default in tests (opt-in)
+                min_customers: 5,
+                auc_drop_tolerance: 0.10,
+                brier_rise_tolerance: 0.05,
+            },
+            complaint_quality: ComplaintQualityConfig {
+                enabled: false, // disabled by default in tests (opt-in)
+                analyst_skill: 0.7,
+                thorough_handling_ticks: 3,
+                appropriate_resolutions: [("fee_dispute".into(), vec!["monetary_relief".into()])].into(),
+                skill_weight: 0.4,
+                time_weight: 0.3,
+                appropriateness_weight: 0.3,
+                poor_quality_threshold: 0.5,
+                max_reopen_probability: 0.40,
+                max_escalation_probability: 0.15,
+                escalation_udaap_impact: 1.0,
+                report_interval_ticks: 30,
+            },
+            scenario_scripts: ScenarioScriptConfig {
+                enabled: false, // disabled by default in tests (opt-in)
+                scripts: Vec::new(),
+                max_operations: 100_000,
+            },
+            stress_index: StressIndexConfig {
+                enabled: false, // disabled by default in tests (opt-in)
+                backlog_per_1k: PressureBand { calm: 5.0, edge: 40.0 },
+                capital_ratio: PressureBand { calm: 0.12, edge: 0.08 },
+                reputation: PressureBand { calm: 80.0, edge: 40.0 },
+                staffing_utilization: PressureBand { calm: 0.8, edge: 1.25 },
+                open_findings: PressureBand { calm: 0.0, edge: 6.0 },
+                weights: StressWeights {
+                    backlog: 0.25,
+                    capital: 0.25,
+                    reputation: 0.2,
+                    staffing: 0.15,
+                    findings: 0.15,
+                },
+                finding_lookback_ticks: 180,
+                strained_below: 60.0,
+                critical_below: 30.0,
+            },
+            alert_qa: AlertQaConfig {
+                enabled: false, // disabled by default in tests (opt-in)
+                interval_ticks: 30,
+                sample_rate: 0.1,
+                review_capacity: 40,
+                reviewer_accuracy: 0.95,
+                cost_per_review: 120.0,
+                retraining_threshold: 0.25,
+                retraining_min_reviews: 4,
+                retraining_ticks: 14,
+                retraining_cost: 6_000.0,
+            },
+            macro_regime: MacroRegimeConfig::default(),
+            staffing: StaffingConfig {
+                enabled: false, // disabled by default in tests (opt-in)
+                queues: default_queue_staffing(),
+                fte_daily_cost: 420.0,
+                overtime_premium: 1.5,
+                max_overtime_share: 0.25,
+                complaint_resolution_code: "explanation_only".into(),
+            },
+            enforcement: EnforcementConfig {
+                enabled: false, // disabled by default in tests (opt-in)
+                udaap_exam_rise_per_1k: 150.0,
+                udaap_major_rise_per_1k: 100.0,
+                udaap_critical_rise_per_1k: 250.0,
+                sample_size: 25,
+                min_sample: 5,
+                sample_failure_share: 0.25,
+                mra_remediation_ticks: 60,
+                consent_order_remediation_ticks: 150,
+                repeat_penalty_multiplier: 2.0,
+                lookback_ticks: 180,
+                lookback_review_ticks: 60,
+                lookback_cost_per_item: 150.0,
+            },
+            lending: LendingConfig {
+                enabled: false, // disabled by default in tests (opt-in)
+                products: default_loan_products(),
+                monthly_application_rate: 0.02,
+                payment_interval_ticks: 30,
+                max_dti: 0.36,
+                max_miss_rate: 0.25,
+                charge_off_dpd: 120,
+                recovery_rate: 0.1,
+            },
+            rate_environment: RateEnvironmentConfig {
+                enabled: false, // disabled by default in tests (opt-in)
+                rate_step: 0.0025,
+                regime_moves: default_regime_moves(),
+                term_spread: 0.015,
+                neutral_rate: 0.03,
+                curve_flattening: 0.5,
+                deposit_betas: default_deposit_betas(),
+                default_deposit_beta: 0.2,
+                earning_asset_share: 0.5,
+                fixed_asset_share: 0.5,
+                asset_repricing_ticks: 360,
+            },
+            alert_triage: AlertTriageConfig {
+                enabled: false, // disabled by default in tests (opt-in)
+                aged_after_ticks: 20,
+            },
+            case_management: CaseManagementConfig {
+                enabled: false, // disabled by default in tests (opt-in)
+                min_relationship_strength: Some(0.7),
+                complaint_resolution_code: "explanation_only".into(),
+            },
+            mule_networks: MuleNetworkConfig {
+                enabled: false, // disabled by default in tests (opt-in)
+                seed_daily_rate: 0.02,
+                max_networks: 3,
+                min_mules: 3,
+                max_mules: 8,
+                cycle_interval_ticks: 7,
+                credits_per_cycle: 3,
+                credit_amount: 1_200.0,
+                forward_lag_ticks: 1,
+                mule_cut: 0.10,
+                relationship_strength: 0.8,
+                lifetime_ticks: 120,
+            },
+            structuring: StructuringConfig {
+                enabled: false, // disabled by default in tests (opt-in)
+                risk_levels: vec!["high".into(), "critical".into()],
+                structurer_rate: 0.25,
+                episode_interval_ticks: 30,
+                deposits_per_episode: 4,
+                episode_spread_ticks: 5,
+                min_deposit: 8_500.0,
+                max_deposit: 9_900.0,
+            },
+            sanctions_screening: SanctionsScreeningConfig {
+                candidate_name_score: 0.80,
+                alias_discount: 0.95,
+                name_weight: 0.60,
+                country_weight: 0.20,
+                birth_year_weight: 0.20,
+                birth_year_tolerance: 1,
+                sensitivity: 0.5,
+                strict_match_threshold: 0.90,
+                lenient_match_threshold: 0.55,
+                exact_match_threshold: 0.95,
+                listed_party_rate: 0.0, // no listed parties in tests (opt-in)
+                alias_use_rate: 0.4,
+            },
+            channels: ChannelConfig {
+                enabled: false, // disabled by default in tests (opt-in)
+                cash_mix: BTreeMap::from([(Channel::Atm, 0.80), (Channel::Branch, 0.20)]),
+                payment_mix: BTreeMap::from([
+                    (Channel::Mobile, 0.45),
+                    (Channel::Online, 0.35),
+                    (Channel::Branch, 0.10),
+                    (Channel::CallCenter, 0.10),
+                ]),
+                cost_per_transaction: BTreeMap::from([
+                    (Channel::Branch, 4.00),
+                    (Channel::Atm, 0.85),
+                    (Channel::Online, 0.20),
+                    (Channel::Mobile, 0.10),
+                    (Channel::CallCenter, 3.50),
+                ]),
+                degraded_failure_rate: 0.30,
+                fallback_rate: 0.60,
+            },
+            app_fraud: AppFraudConfig {
+                enabled: false, // disabled by default in tests (opt-in)
+                segments: vec!["mass_market".into(), "premium".into()],
+                scam_rate: 0.0005,
+                fall_rate: 0.4,
+                scam_types: vec!["purchase".into(), "impersonation".into(), "investment".into(), "romance".into()],
+                rails: vec!["RTP".into(), "FedNow".into()],
+                share_min: 0.2,
+                share_max: 0.8,
+                min_payment: 100.0,
+                report_rate: 0.8,
+                report_min_ticks: 1,
+                report_max_ticks: 14,
+                decision_ticks: 5,
+                reimbursement_policy: "vulnerable".into(),
+                vulnerable_age: 65,
+                excess: 100.0,
+                reimbursement_cap: 85_000.0,
+            },
+            atm_network: AtmNetworkConfig {
+                enabled: false, // disabled by default in tests (opt-in)
+                atms: 40,
+                locations: vec!["branch".into(), "retail".into(), "transit".into()],
+                home_rate: 0.7,
+                fault_rate: 0.01,
+                repair_ticks: 2,
+                cash_out_rate: 0.01,
+                cash_out_cards: 6,
+                cash_out_withdrawals: 3,
+                cash_out_amount: 400.0,
+                multi_atm_threshold: 3,
+            },
+            merchants: MerchantConfig {
+                enabled: false, // disabled by default in tests (opt-in)
+                count: 100,
+                categories: BTreeMap::from([
+                    ("retail".into(), 0.40),
+                    ("grocery".into(), 0.20),
+                    ("travel".into(), 0.10),
+                    ("digital_goods".into(), 0.15),
+                    ("subscription".into(), 0.15),
+                ]),
+                risk_tiers: BTreeMap::from([("low".into(), 0.70), ("medium".into(), 0.25), ("high".into(), 0.05)]),
+                fraud_rates: BTreeMap::from([("low".into(), 0.005), ("medium".into(), 0.012), ("high".into(), 0.03)]),
+                bad_merchant_rate: 0.01,
+                bad_merchant_ticks: 14,
+                bad_merchant_multiplier: 8.0,
+                chargeback_window_ticks: 30,
+                chargeback_alert_rate: 0.01,
+                chargeback_alert_min_disputes: 3,
+            },
+            devices: DeviceTelemetryConfig {
+                enabled: false, // disabled by default in tests (opt-in)
+                digital_rate: 0.6,
+                login_rate: 0.3,
+                new_device_rate: 0.005,
+                takeover_rate: 0.05,
+                farm_devices: 3,
+                takeover_share: 0.8,
+                high_value_amount: 1000.0,
+                new_device_ticks: 3,
+                shared_device_threshold: 3,
+            },
+        }
+    }
+
+    /// Simulate only `scale` of the population. The sample is stratified
+    /// by segment, and every threshold counted in customers shrinks with
+    /// it so branches, fair-lending groups and complaint clusters still
+    /// form. Fixed costs that do not grow with the book are charged pro
+    /// rata, so dividing any count or dollar figure by the scale gives
+    /// the full-bank estimate; ratios need no adjustment.
+    pub fn with_population_scale(mut self, scale: f64) -> anyhow::Result<Self> {
+        if !(scale > 0.0 && scale <= 1.0) {
+            anyhow::bail!("population scale must be in (0, 1], got {scale}");
+        }
+        let scaled = |count: f64| (count * scale).ceil().max(1.0);
+
+        self.population_scale = scale;
+        self.initial_population = scaled(self.initial_population as f64) as usize;
+
+        let branches = &mut self.branch_network;
+        branches.min_customers_for_branch = scaled(branches.min_customers_for_branch as f64) as i64;
+        branches.customers_per_staff = scaled(branches.customers_per_staff as f64) as usize;
+        branches.staff_monthly_cost *= scale;
+        branches.occupancy_monthly_cost *= scale;
+        branches.acquisitions_per_branch_month *= scale;
+
+        self.fair_lending.min_group_size = scaled(self.fair_lending.min_group_size as f64) as i64;
+        let patterns = &mut self.complaint_analytics.pattern_detection;
+        patterns.cluster_threshold_count = scaled(patterns.cluster_threshold_count as f64) as u32;
+        self.churn_evaluation.min_customers = scaled(self.churn_evaluation.min_customers as f64) as u32;
+        // A tuning review is bank-wide, not per customer
+        self.rule_tuning.review_cost *= scale;
+        // Fewer decisions to sample; retraining is bank-wide
+        self.alert_qa.review_capacity = scaled(self.alert_qa.review_capacity as f64) as usize;
+        self.alert_qa.retraining_min_reviews = scaled(self.alert_qa.retraining_min_reviews as f64) as usize;
+        self.alert_qa.retraining_cost *= scale;
+        // Case workers shrink with the queues they work
+        for queue in &mut self.staffing.queues {
+            queue.ftes *= scale;
         }
+        Ok(self)
     }
 }
@@ -0,0 +1,276 @@
+//! Correspondent banking (Phase 4.39).
+//!
+//! A handful of respondent banks clear wires through accounts they hold
+//! with us. Each declares the customers it sends for, but some quietly let
+//! downstream (nested) banks clear through the same account. Most nested
+//! wires have the ordering institution stripped from the payment message,
+//! so they arrive looking like the respondent's own customers; the rest
+//! show the nested bank and its country.
+//!
+//! None of this traffic touches a customer account, so customer-level
+//! monitoring never sees it. CORR_NESTED works on what is left: each
+//! respondent's aggregated flow, alerting on third-party ordering
+//! institutions or on more distinct originators than the respondent
+//! declared. Nested activity the respondent has never disclosed scores
+//! higher.
+//!
+//! Each relationship carries a due diligence refresh at its risk rating's
+//! cadence. The respondent returns the questionnaire — or does not — and
+//! sometimes admits to its nested banks. A refresh left unanswered past
+//! `overdue_ticks` suspends the relationship: its wires are rejected until
+//! a fresh questionnaire comes back.
+
+use crate::{
+    config::{CorrespondentBankingConfig, CorrespondentDueDiligenceConfig, RespondentBankConfig},
+    error::SimResult,
+    event::SimEvent,
+    rng::SubsystemRng,
+    store::{
+        correspondent::{
+            CorrespondentDdReviewRow, CorrespondentFlowRow, CorrespondentRelationshipRow,
+            CorrespondentWireRow,
+        },
+        SimStore,
+    },
+    types::{RunId, Tick},
+};
+use uuid::Uuid;
+
+pub struct Correspondent {
+    run_id: RunId,
+    config: CorrespondentBankingConfig,
+}
+
+impl Correspondent {
+    pub fn new(run_id: RunId, config: CorrespondentBankingConfig) -> Self {
+        Self { run_id, config }
+    }
+
+    pub fn enabled(&self) -> bool {
+        self.config.enabled
+    }
+
+    pub fn config(&self) -> &CorrespondentBankingConfig {
+        &self.config
+    }
+
+    fn due_diligence(&self, risk_rating: &str) -> Option<&CorrespondentDueDiligenceConfig> {
+        self.config.due_diligence.iter().find(|d| d.risk_rating == risk_rating)
+    }
+
+    fn dd_interval(&self, risk_rating: &str) -> Tick {
+        self.due_diligence(risk_rating).map_or(365, |d| d.interval_ticks)
+    }
+
+    /// Open new relationships, move due diligence on, then clear today's
+    /// wires.
+    pub fn process(&self, store: &SimStore, tick: Tick, rng: &mut SubsystemRng) -> SimResult<Vec<SimEvent>> {
+        let mut events = Vec::new();
+
+        let existing = store.correspondent_relationships(&self.run_id)?;
+        for respondent in &self.config.respondents {
+            if existing.iter().any(|r| r.bank_id == respondent.bank_id) {
+                continue;
+            }
+            // Stagger the first refresh across the cadence
+            let interval = self.dd_interval(&respondent.risk_rating);
+            store.insert_correspondent_relationship(&self.run_id, &CorrespondentRelationshipRow {
+                bank_id: respondent.bank_id.clone(),
+                country: respondent.country.clone(),
+                risk_rating: respondent.risk_rating.clone(),
+                declared_originators: respondent.declared_originators,
+                nested_banks: respondent.nested_banks,
+                nested_disclosed: false,
+                status: "active".into(),
+                next_dd_tick: tick + 1 + rng.next_u64_below(interval.max(1)),
+                opened_tick: tick,
+            })?;
+        }
+
+        let mut wires: i64 = 0;
+        let mut amount = 0.0;
+        let mut rejected: i64 = 0;
+        let relationships = store.correspondent_relationships(&self.run_id)?;
+        for (index, respondent) in self.config.respondents.iter().enumerate() {
+            let Some(mut relationship) = relationships.iter().find(|r| r.bank_id == respondent.bank_id).cloned()
+            else {
+                continue;
+            };
+            let before = relationship.clone();
+            events.extend(self.refresh_due_diligence(store, &mut relationship, tick, rng)?);
+            if relationship != before {
+                store.update_correspondent_relationship(&self.run_id, &relationship)?;
+            }
+
+            for wire in self.clear_wires(store, respondent, index, &relationship, tick, rng)? {
+                if wire.status == "rejected" {
+                    rejected += 1;
+                } else {
+                    wires += 1;
+                    amount += wire.amount;
+                }
+            }
+        }
+
+        if wires + rejected > 0 {
+            events.push(SimEvent::CorrespondentWiresCleared {
+                tick,
+                wires,
+                amount,
+                rejected,
+            });
+        }
+        Ok(events)
+    }
+
+    /// Request a refresh when one falls due, complete it when the
+    /// questionnaire comes back, and suspend the relationship when it is
+    /// overdue.
+    fn refresh_due_diligence(
+        &self,
+        store: &SimStore,
+        relationship: &mut CorrespondentRelationshipRow,
+        tick: Tick,
+        rng: &mut SubsystemRng,
+    ) -> SimResult<Vec<SimEvent>> {
+        let cfg = &self.config;
+        let mut events = Vec::new();
+        let Some(review) = store.open_correspondent_dd_review(&self.run_id, &relationship.bank_id)? else {
+            if tick >= relationship.next_dd_tick {
+                let review_id = format!("cdd-{}-{tick}", relationship.bank_id);
+                let responds_tick = rng
+                    .chance(cfg.response_rate)
+                    .then(|| tick + 1 + rng.next_u64_below(cfg.response_ticks.max(1)));
+                store.insert_correspondent_dd_review(&self.run_id, &CorrespondentDdReviewRow {
+                    review_id: review_id.clone(),
+                    bank_id: relationship.bank_id.clone(),
+                    requested_tick: tick,
+                    responds_tick,
+                    completed_tick: None,
+                    outcome: None,
+                    cost: 0.0,
+                })?;
+                events.push(SimEvent::CorrespondentDueDiligenceRequested {
+                    tick,
+                    bank_id: relationship.bank_id.clone(),
+                    review_id,
+                });
+            }
+            return Ok(events);
+        };
+
+        if review.responds_tick.is_some_and(|t| t <= tick) {
+            let disclosed = relationship.nested_banks > 0 && rng.chance(cfg.disclosure_rate);
+            let outcome = if disclosed { "nested_disclosed" } else { "satisfactory" };
+            let cost = self.due_diligence(&relationship.risk_rating).map_or(0.0, |d| d.cost);
+            store.close_correspondent_dd_review(&self.run_id, &review.review_id, outcome, cost, tick)?;
+            let reinstated = relationship.status == "suspended";
+            relationship.nested_disclosed |= disclosed;
+            relationship.status = "active".into();
+            relationship.next_dd_tick = tick + self.dd_interval(&relationship.risk_rating);
+            events.push(SimEvent::CorrespondentDueDiligenceCompleted {
+                tick,
+                bank_id: relationship.bank_id.clone(),
+                review_id: review.review_id,
+                outcome: outcome.into(),
+                reinstated,
+            });
+        } else if tick >= review.requested_tick + cfg.overdue_ticks {
+            // Close the lapsed refresh and ask again straight away
+            store.close_correspondent_dd_review(&self.run_id, &review.review_id, "overdue", 0.0, tick)?;
+            relationship.next_dd_tick = tick;
+            if relationship.status != "suspended" {
+                relationship.status = "suspended".into();
+                tracing::info!("correspondent {} suspended: due diligence overdue", relationship.bank_id);
+                events.push(SimEvent::CorrespondentRelationshipSuspended {
+                    tick,
+                    bank_id: relationship.bank_id.clone(),
+                    review_id: review.review_id,
+                });
+            }
+        }
+        Ok(events)
+    }
+
+    /// Today's wires from one respondent: its own customers' and, hidden
+    /// among them, its nested banks'. A suspended relationship's wires are
+    /// rejected.
+    fn clear_wires(
+        &self,
+        store: &SimStore,
+        respondent: &RespondentBankConfig,
+        index: usize,
+        relationship: &CorrespondentRelationshipRow,
+        tick: Tick,
+        rng: &mut SubsystemRng,
+    ) -> SimResult<Vec<CorrespondentWireRow>> {
+        let cfg = &self.config;
+        let mut count = respondent.wires_per_tick.floor() as usize;
+        if rng.chance(respondent.wires_per_tick.fract()) {
+            count += 1;
+        }
+
+        let mut wires = Vec::new();
+        for _ in 0..count {
+            let mut wire = CorrespondentWireRow {
+                wire_id: Uuid::new_v4().to_string(),
+                bank_id: respondent.bank_id.clone(),
+                tick,
+                amount: respondent.typical_amount * (0.5 + rng.next_f64()),
+                originator: format!(
+                    "{}-c{}",
+                    respondent.bank_id,
+                    rng.next_u64_below(respondent.declared_originators.max(1) as u64)
+                ),
+                ordering_institution: respondent.bank_id.clone(),
+                originator_country: respondent.country.clone(),
+                nested_bank: None,
+                status: "cleared".into(),
+                fee: cfg.clearing_fee,
+            };
+            if respondent.nested_banks > 0 && rng.chance(respondent.nested_share) {
+                let n = rng.next_u64_below(respondent.nested_banks as u64) as usize;
+                let nested_bank = format!("{}-N{n}", respondent.bank_id);
+                wire.originator =
+                    format!("{nested_bank}-c{}", rng.next_u64_below(cfg.nested_originators.max(1) as u64));
+                if !rng.chance(cfg.stripping_rate) {
+                    wire.ordering_institution = nested_bank.clone();
+                    if !cfg.nested_countries.is_empty() {
+                        wire.originator_country = cfg.nested_countries[(index + n) % cfg.nested_countries.len()].clone();
+                    }
+                }
+                wire.nested_bank = Some(nested_bank);
+            }
+            if relationship.status == "suspended" {
+                wire.status = "rejected".into();
+                wire.fee = 0.0;
+            }
+            store.insert_correspondent_wire(&self.run_id, &wire)?;
+            wires.push(wire);
+        }
+        Ok(wires)
+    }
+
+    /// CORR_NESTED score for a respondent's window of flow, or None when
+    /// it reads as the respondent's declared business. The respondent's
+    /// risk rating lifts the score, as does nested activity it has never
+    /// disclosed.
+    pub fn nested_score(&self, flow: &CorrespondentFlowRow, relationship: &CorrespondentRelationshipRow) -> Option<f64> {
+        let cfg = &self.config;
+        if flow.wires == 0 {
+            return None;
+        }
+        let third_party_share = flow.third_party_wires as f64 / flow.wires as f64;
+        let excess = flow.distinct_originators as f64 / relationship.declared_originators.max(1) as f64;
+        if third_party_share < cfg.min_third_party_share && excess < cfg.originator_excess {
+            return None;
+        }
+        let uplift = match relationship.risk_rating.as_str() {
+            "high" => cfg.high_risk_uplift,
+            "medium" => cfg.medium_risk_uplift,
+            _ => 0.0,
+        };
+        let undisclosed = if relationship.nested_disclosed { 0.0 } else { cfg.undisclosed_uplift };
+        Some(cfg.alert_score + uplift + undisclosed)
+    }
+}
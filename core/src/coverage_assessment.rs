@@ -0,0 +1,40 @@
+//! Scenario coverage assessment (Phase 4.18).
+//!
+//! Every interval, transaction monitoring compares the rules in force
+//! against the typologies actually running in the simulation. The player
+//! sees only the rule inventory: which rules are active or suppressed and
+//! which typologies they are meant to catch. Whether a typology is
+//! present, and so whether a gap is letting it through, is ground truth
+//! kept for the post-run analysis export.
+
+use crate::{error::SimResult, store::SimStore, types::Tick};
+
+/// Typologies assessed, with the rules meant to catch each. Nothing in
+/// the rule inventory addresses trade-based laundering or account
+/// takeover.
+pub const TYPOLOGIES: &[(&str, &[&str])] = &[
+    ("structuring", &["STRUCT_9K"]),
+    ("funnel", &["VEL_50K_7D", "VEL_100K_30D"]),
+    ("tbml", &[]),
+    ("mule", &["RAPID_MOVE"]),
+    ("ato", &[]),
+];
+
+/// Ground truth: instances of the typology running in the window. The
+/// simulation does not generate funnel accounts or trade-based
+/// laundering yet, so those are never present.
+pub fn occurrences(
+    store: &SimStore,
+    run_id: &str,
+    typology: &str,
+    start_tick: Tick,
+    end_tick: Tick,
+) -> SimResult<i64> {
+    match typology {
+        "structuring" => store.structuring_customers_between(run_id, start_tick, end_tick),
+        "mule" => Ok(store.mule_links_between(run_id, start_tick, end_tick)?
+            + store.active_mules_between(run_id, start_tick, end_tick)?),
+        "ato" => store.takeover_customers_between(run_id, start_tick, end_tick),
+        _ => Ok(0),
+    }
+}
@@ -0,0 +1,277 @@
+//! Credit Bureau Reporting subsystem — Phase 4.1.
+//!
+//! Furnishes account status to a simulated consumer reporting agency on a
+//! monthly cycle. A small fraction of furnished records are inaccurate;
+//! customers who notice file an FCRA dispute, which is opened as a
+//! "credit_reporting" complaint with an SLA equal to the 30-day
+//! investigation window. Disputes not corrected inside the window book a
+//! late-correction penalty into opex (and the linked complaint breaches its
+//! SLA, which the reputation subsystem already picks up). A player who
+//! resolves the linked complaint first also closes out the dispute; the
+//! player's resolution is never overwritten.
+//!
+//! Deposit accounts and loans are both furnished. A loan is reported by
+//! its delinquency bucket while open, and once more as paid off or
+//! charged off in the cycle after it closes.
+//!
+//! Execution: every tick, before Complaint (so dispute complaints are
+//!   aged by the complaint subsystem on the tick they are filed).
+//! Depends on: account + customer tables (active accounts and balances),
+//!   loan table (loan balances and buckets).
+
+use crate::{
+    complaint_subsystem::ComplaintRecord,
+    config::CreditBureauConfig,
+    error::SimResult,
+    event::SimEvent,
+    rng::SubsystemRng,
+    store::SimStore,
+    subsystem::{SimSubsystem, SubsystemManifest},
+    types::{RunId, Tick},
+};
+
+/// Statuses a furnisher can report for a deposit tradeline.
+const REPORTABLE_STATUSES: &[&str] = &["current", "negative_balance", "charged_off"];
+
+/// Statuses a furnisher can report for a loan tradeline.
+const LOAN_STATUSES: &[&str] = &["current", "1_29", "30_59", "60_89", "90_plus", "paid_off", "charged_off"];
+
+/// One tradeline as it stands on a furnishing cycle.
+struct Tradeline {
+    /// 'deposit' | 'loan'
+    kind:        &'static str,
+    /// The deposit account, or the loan id.
+    id:          String,
+    customer_id: String,
+    /// The deposit account a dispute's complaint is filed against.
+    account_id:  String,
+    product_id:  String,
+    actual:      String,
+    balance:     f64,
+    statuses:    &'static [&'static str],
+}
+
+pub struct CreditBureauSubsystem {
+    run_id: RunId,
+    config: CreditBureauConfig,
+    store:  SimStore,
+}
+
+impl CreditBureauSubsystem {
+    pub fn new(run_id: RunId, config: CreditBureauConfig, store: SimStore) -> Self {
+        Self { run_id, config, store }
+    }
+
+    /// Monthly furnishing cycle: report every active account and every
+    /// open or newly closed loan, and open disputes for the inaccurate
+    /// records customers notice.
+    fn furnish(&self, tick: Tick, rng: &mut SubsystemRng) -> SimResult<Vec<SimEvent>> {
+        let mut out = Vec::new();
+        let mut accounts_reported = 0i64;
+        let mut loans_reported = 0i64;
+        let mut errors = 0i64;
+
+        let deposits = self.store.active_accounts(&self.run_id)?.into_iter().map(|account| Tradeline {
+            kind:        "deposit",
+            id:          account.account_id.clone(),
+            actual:      if account.balance < 0.0 { "negative_balance" } else { "current" }.into(),
+            customer_id: account.customer_id,
+            account_id:  account.account_id,
+            product_id:  account.product_id,
+            balance:     account.balance,
+            statuses:    REPORTABLE_STATUSES,
+        });
+        let closed_after = tick.saturating_sub(self.config.furnish_interval_ticks);
+        let loans = self.store.furnishable_loans(&self.run_id, closed_after)?.into_iter().map(|loan| Tradeline {
+            kind:        "loan",
+            actual:      if loan.status == "open" { loan.bucket } else { loan.status },
+            id:          loan.loan_id,
+            customer_id: loan.customer_id,
+            account_id:  loan.account_id,
+            product_id:  loan.product_id,
+            balance:     loan.balance,
+            statuses:    LOAN_STATUSES,
+        });
+
+        for line in deposits.chain(loans) {
+            let reported_status = if rng.chance(self.config.furnishing_error_rate) {
+                let wrong: Vec<&str> = line
+                    .statuses
+                    .iter()
+                    .copied()
+                    .filter(|s| *s != line.actual)
+                    .collect();
+                wrong[rng.next_u64_below(wrong.len() as u64) as usize]
+            } else {
+                line.actual.as_str()
+            };
+
+            let furnishing_id = self.store.insert_bureau_furnishing(
+                &self.run_id, tick, line.kind, &line.id, &line.customer_id,
+                &line.actual, reported_status, line.balance,
+            )?;
+            if line.kind == "loan" {
+                loans_reported += 1;
+            } else {
+                accounts_reported += 1;
+            }
+
+            if reported_status == line.actual {
+                continue;
+            }
+            errors += 1;
+
+            if !rng.chance(self.config.dispute_probability) {
+                continue;
+            }
+
+            let dispute_id = format!("bd-{tick:08x}-{:016x}", rng.next_u64());
+            let complaint_id = format!("cmp-{tick:08x}-{:016x}", rng.next_u64());
+            let due_tick = tick + self.config.investigation_window_ticks;
+
+            self.store.insert_complaint(&self.run_id, &ComplaintRecord {
+                complaint_id:    complaint_id.clone(),
+                customer_id:     line.customer_id.clone(),
+                account_id:      Some(line.account_id.clone()),
+                tick_opened:     tick,
+                tick_closed:     None,
+                product:         line.product_id.clone(),
+                issue:           "credit_reporting".into(),
+                priority:        "high".into(),
+                status:          "open".into(),
+                sla_due_tick:    due_tick,
+                sla_breached:    false,
+                resolution_code: None,
+                amount_refunded: 0.0,
+                udaap_flag:      false,
+                channel:         "online".into(),
+                service_channel: None,
+            })?;
+            self.store.insert_bureau_dispute(
+                &self.run_id, &dispute_id, furnishing_id, &line.customer_id,
+                &line.id, &complaint_id, tick, due_tick,
+            )?;
+            self.store
+                .update_customer_satisfaction(&self.run_id, &line.customer_id, -0.05)?;
+
+            out.push(SimEvent::ComplaintFiled {
+                tick,
+                complaint_id: complaint_id.clone(),
+                customer_id:  line.customer_id.clone(),
+                issue:        "credit_reporting".into(),
+                priority:     "high".into(),
+            });
+            out.push(SimEvent::BureauDisputeFiled {
+                tick,
+                dispute_id,
+                customer_id: line.customer_id,
+                account_id:  line.id,
+                complaint_id,
+                due_tick,
+            });
+        }
+
+        out.push(SimEvent::BureauFurnished {
+            tick,
+            accounts_reported,
+            loans_reported,
+            error_count: errors,
+        });
+        tracing::info!("bureau furnishing: {accounts_reported} accounts, {loans_reported} loans, {errors} inaccurate");
+
+        Ok(out)
+    }
+
+    /// Work open disputes: correct some, penalise those past the window.
+    fn process_disputes(&self, tick: Tick, rng: &mut SubsystemRng) -> SimResult<Vec<SimEvent>> {
+        let mut out = Vec::new();
+
+        for dispute in self.store.open_bureau_disputes(&self.run_id)? {
+            if dispute.filed_tick == tick {
+                continue;
+            }
+
+            // The player already resolved the linked complaint
+            let player_resolved =
+                self.store.get_complaint(&self.run_id, &dispute.complaint_id)?.status != "open";
+
+            if player_resolved || rng.chance(self.config.daily_correction_rate) {
+                self.store.resolve_bureau_dispute(&self.run_id, &dispute.dispute_id, tick)?;
+                if !player_resolved {
+                    self.store.close_complaint(
+                        &self.run_id, &dispute.complaint_id, tick, "non_monetary_relief", 0.0,
+                    )?;
+                }
+                out.push(SimEvent::BureauDisputeCorrected {
+                    tick,
+                    dispute_id:  dispute.dispute_id,
+                    customer_id: dispute.customer_id,
+                    days_open:   tick - dispute.filed_tick,
+                    on_time:     !dispute.overdue,
+                });
+            } else if !dispute.overdue && tick > dispute.due_tick {
+                let penalty = self.config.late_correction_penalty;
+                self.store
+                    .mark_bureau_dispute_overdue(&self.run_id, &dispute.dispute_id, penalty)?;
+                tracing::warn!(
+                    "bureau dispute {} past FCRA window (due {}), penalty ${penalty:.0}",
+                    dispute.dispute_id, dispute.due_tick,
+                );
+                out.push(SimEvent::BureauCorrectionOverdue {
+                    tick,
+                    dispute_id:  dispute.dispute_id,
+                    customer_id: dispute.customer_id,
+                    penalty,
+                });
+            }
+        }
+
+        Ok(out)
+    }
+}
+
+const MANIFEST: SubsystemManifest = SubsystemManifest {
+    version: 2,
+    produces: &[
+        "bureau_correction_overdue", "bureau_dispute_corrected", "bureau_dispute_filed",
+        "bureau_furnished", "complaint_filed",
+    ],
+    consumes: &[],
+    tables: &["bureau_dispute", "bureau_furnishing", "complaint", "customer"],
+    requires: &[],
+};
+
+impl SimSubsystem for CreditBureauSubsystem {
+    fn name(&self) -> &'static str {
+        "credit_bureau"
+    }
+
+    fn manifest(&self) -> SubsystemManifest {
+        MANIFEST
+    }
+
+    fn update(
+        &mut self,
+        tick:       Tick,
+        _events_in: &[SimEvent],
+        rng:        &mut SubsystemRng,
+    ) -> SimResult<Vec<SimEvent>> {
+        let mut out = Vec::new();
+
+        if !self.config.enabled || tick == 0 {
+            return Ok(out);
+        }
+
+        if tick.is_multiple_of(self.config.furnish_interval_ticks) {
+            out.extend(self.furnish(tick, rng)?);
+        }
+
+        out.extend(self.process_disputes(tick, rng)?);
+
+        Ok(out)
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+}
@@ -0,0 +1,365 @@
+//! Cross-border fan-in/fan-out (Phase 4.40).
+//!
+//! Each international customer is profiled once, on their first live
+//! tick with an account. A small share run their account as a funnel:
+//! every collection cycle a handful of fresh senders abroad wire in, and
+//! a couple of days later the money, less a cut, goes back out to fresh
+//! payees here as instant transfers. A larger share receive support from
+//! home now and then, from one or two relatives, and pass some of it on
+//! to family here.
+//!
+//! XB_FUNNEL looks at each customer's rolling window of foreign wires in
+//! — the typology's and remittance layering's inbound wires alike —
+//! against their domestic transfers out. Fan-in from many senders, fan-out
+//! to many payees and most of the inflow gone back out is a funnel;
+//! source countries on the high-risk jurisdiction list lift the score.
+//! Each hit is kept as a finding with the transactions behind it, which
+//! the SAR narrative quotes.
+
+use crate::{
+    config::CrossBorderConfig,
+    error::SimResult,
+    event::SimEvent,
+    rng::SubsystemRng,
+    store::{
+        cross_border::{
+            CrossBorderActivityRow, CrossBorderEvidenceRow, CrossBorderFindingRow,
+            CrossBorderProfileRow, CrossBorderTransferRow,
+        },
+        AccountRow, HighRiskJurisdictionRow, SimStore,
+    },
+    types::{RunId, Tick},
+};
+use std::collections::HashMap;
+use uuid::Uuid;
+
+pub struct CrossBorder {
+    run_id: RunId,
+    config: CrossBorderConfig,
+}
+
+impl CrossBorder {
+    pub fn new(run_id: RunId, config: CrossBorderConfig) -> Self {
+        Self { run_id, config }
+    }
+
+    pub fn enabled(&self) -> bool {
+        self.config.enabled
+    }
+
+    pub fn config(&self) -> &CrossBorderConfig {
+        &self.config
+    }
+
+    /// Profile new international customers, then book the collections,
+    /// support wires and dispersals falling due today.
+    pub fn process(
+        &self,
+        store: &SimStore,
+        accounts: &[AccountRow],
+        tick: Tick,
+        rng: &mut SubsystemRng,
+    ) -> SimResult<Vec<SimEvent>> {
+        let mut first_account: HashMap<&str, &str> = HashMap::new();
+        for acct in accounts {
+            first_account
+                .entry(acct.customer_id.as_str())
+                .or_insert(acct.account_id.as_str());
+        }
+        self.enroll(store, &first_account, tick, rng)?;
+
+        let mut inbound = Vec::new();
+        let mut domestic = Vec::new();
+        for mut profile in store.cross_border_profiles_due(&self.run_id, tick)? {
+            // Closed and restricted accounts move nothing; a restricted
+            // funnel picks up again once lifted
+            if !accounts.iter().any(|a| a.account_id == profile.account_id) {
+                continue;
+            }
+            if profile.disperse_tick.is_some_and(|t| t <= tick) {
+                domestic.extend(self.disperse(store, &mut profile, tick, rng)?);
+            }
+            if profile.next_tick <= tick {
+                if profile.pattern == "funnel" {
+                    inbound.extend(self.collect(store, &mut profile, tick, rng)?);
+                } else {
+                    let (wire, forwarded) = self.support(store, &profile, tick, rng)?;
+                    inbound.push(wire);
+                    domestic.extend(forwarded);
+                    profile.next_tick = tick + self.config.inbound_interval_ticks.max(1);
+                }
+            }
+            store.update_cross_border_profile(&self.run_id, &profile)?;
+        }
+
+        if inbound.is_empty() && domestic.is_empty() {
+            return Ok(Vec::new());
+        }
+        Ok(vec![SimEvent::CrossBorderTransfersBooked {
+            tick,
+            inbound_wires: inbound.len() as i64,
+            inflow: inbound.iter().sum(),
+            domestic_transfers: domestic.len() as i64,
+            outflow: domestic.iter().sum(),
+        }])
+    }
+
+    /// Decide, once, how each new international customer moves money
+    /// across the border.
+    fn enroll(
+        &self,
+        store: &SimStore,
+        first_account: &HashMap<&str, &str>,
+        tick: Tick,
+        rng: &mut SubsystemRng,
+    ) -> SimResult<()> {
+        let cfg = &self.config;
+        for (customer_id, country) in store.unprofiled_cross_border_customers(&self.run_id)? {
+            let Some(account_id) = first_account.get(customer_id.as_str()) else {
+                continue;
+            };
+            let (pattern, interval) = if rng.chance(cfg.funnel_rate) {
+                ("funnel", cfg.funnel_interval_ticks)
+            } else if rng.chance(cfg.inbound_rate) {
+                ("inbound", cfg.inbound_interval_ticks)
+            } else {
+                ("none", 0)
+            };
+            store.insert_cross_border_profile(&self.run_id, &CrossBorderProfileRow {
+                customer_id,
+                account_id: account_id.to_string(),
+                home_country: country,
+                pattern: pattern.into(),
+                // Stagger the first cycle across the interval
+                next_tick: tick + 1 + rng.next_u64_below(interval.max(1)),
+                disperse_tick: None,
+                pending: 0.0,
+                tick_enrolled: tick,
+            })?;
+        }
+        Ok(())
+    }
+
+    /// One funnel collection: wires in from fresh senders abroad, held
+    /// until the dispersal lag has passed. Returns the amounts received.
+    fn collect(
+        &self,
+        store: &SimStore,
+        profile: &mut CrossBorderProfileRow,
+        tick: Tick,
+        rng: &mut SubsystemRng,
+    ) -> SimResult<Vec<f64>> {
+        let cfg = &self.config;
+        let mut received = Vec::new();
+        for _ in 0..cfg.funnel_senders {
+            let country = if cfg.source_countries.is_empty() {
+                profile.home_country.clone()
+            } else {
+                cfg.source_countries[rng.next_u64_below(cfg.source_countries.len() as u64) as usize].clone()
+            };
+            let sender = format!("xb-{country}-{}", rng.next_u64_below(100_000));
+            let amount = cfg.funnel_wire_amount * (0.7 + rng.next_f64() * 0.6);
+            self.transfer(store, profile, "inbound", &sender, &country, amount, tick)?;
+            received.push(amount);
+        }
+        profile.pending += received.iter().sum::<f64>() * (1.0 - cfg.funnel_retention);
+        profile.disperse_tick = Some(tick + cfg.dispersal_lag_ticks);
+        profile.next_tick = tick + cfg.funnel_interval_ticks.max(1);
+        Ok(received)
+    }
+
+    /// Send a funnel's held funds out to fresh domestic payees, as far as
+    /// the balance covers them. Returns the amounts sent.
+    fn disperse(
+        &self,
+        store: &SimStore,
+        profile: &mut CrossBorderProfileRow,
+        tick: Tick,
+        rng: &mut SubsystemRng,
+    ) -> SimResult<Vec<f64>> {
+        let payees = self.config.funnel_payees.max(1);
+        let total = profile.pending.min(store.account_balance(&self.run_id, &profile.account_id)?.max(0.0));
+        profile.pending = 0.0;
+        profile.disperse_tick = None;
+        if total <= 0.0 {
+            return Ok(Vec::new());
+        }
+        let mut sent = Vec::new();
+        let mut remaining = total;
+        for n in 0..payees {
+            let amount = if n + 1 == payees {
+                remaining
+            } else {
+                (total / payees as f64 * (0.8 + rng.next_f64() * 0.4)).min(remaining)
+            };
+            if amount <= 0.0 {
+                break;
+            }
+            let payee = format!("p2p-{}", rng.next_u64_below(1_000_000));
+            self.transfer(store, profile, "domestic", &payee, "US", amount, tick)?;
+            remaining -= amount;
+            sent.push(amount);
+        }
+        Ok(sent)
+    }
+
+    /// A support wire from a relative at home, with a share passed on to
+    /// family here. Returns the amount received and any amount passed on.
+    fn support(
+        &self,
+        store: &SimStore,
+        profile: &CrossBorderProfileRow,
+        tick: Tick,
+        rng: &mut SubsystemRng,
+    ) -> SimResult<(f64, Option<f64>)> {
+        let cfg = &self.config;
+        let relative = rng.next_u64_below(cfg.max_inbound_senders.max(1) as u64);
+        let sender = format!("xb-{}-{}-{relative}", profile.home_country, profile.customer_id);
+        let amount = cfg.inbound_amount * (0.8 + rng.next_f64() * 0.4);
+        self.transfer(store, profile, "inbound", &sender, &profile.home_country, amount, tick)?;
+
+        let forward = amount * cfg.inbound_forward_share;
+        if forward <= 0.0 {
+            return Ok((amount, None));
+        }
+        let family = format!("p2p-{}-{relative}", profile.customer_id);
+        self.transfer(store, profile, "domestic", &family, "US", forward, tick)?;
+        Ok((amount, Some(forward)))
+    }
+
+    /// Book a foreign wire in or a domestic transfer out on the account
+    /// and record it.
+    #[allow(clippy::too_many_arguments)]
+    fn transfer(
+        &self,
+        store: &SimStore,
+        profile: &CrossBorderProfileRow,
+        direction: &str,
+        counterparty: &str,
+        country: &str,
+        amount: f64,
+        tick: Tick,
+    ) -> SimResult<()> {
+        let txn_id = Uuid::new_v4().to_string();
+        let (txn_direction, category, rail, delta) = if direction == "inbound" {
+            ("credit", "international_wire", "wire", amount)
+        } else {
+            ("debit", "p2p_transfer", "RTP", -amount)
+        };
+        store.insert_transaction_with_rail(
+            &self.run_id,
+            &txn_id,
+            &profile.account_id,
+            tick,
+            amount,
+            txn_direction,
+            category,
+            Some(counterparty),
+            rail,
+            "settled",
+        )?;
+        store.update_account_balance(&self.run_id, &profile.account_id, delta)?;
+        store.insert_cross_border_transfer(&self.run_id, &CrossBorderTransferRow {
+            txn_id,
+            customer_id: profile.customer_id.clone(),
+            account_id: profile.account_id.clone(),
+            tick,
+            direction: direction.to_string(),
+            counterparty: counterparty.to_string(),
+            country: country.to_string(),
+            amount,
+        })
+    }
+
+    /// Domestic transfers out as a share of foreign wires in; 0 with
+    /// nothing in.
+    pub fn dispersal(activity: &CrossBorderActivityRow) -> f64 {
+        if activity.inflow > 0.0 {
+            activity.outflow / activity.inflow
+        } else {
+            0.0
+        }
+    }
+
+    /// The activity's source countries on the high-risk jurisdiction list.
+    pub fn listed_countries<'a>(
+        activity: &CrossBorderActivityRow,
+        jurisdictions: &'a [HighRiskJurisdictionRow],
+    ) -> Vec<&'a HighRiskJurisdictionRow> {
+        jurisdictions
+            .iter()
+            .filter(|j| activity.countries.contains(&j.country_code))
+            .collect()
+    }
+
+    /// XB_FUNNEL score for a customer's window of activity, or None when
+    /// it does not both fan in and fan out. Fan-in alone is a customer
+    /// with family abroad; fan-out alone never touches the border. The
+    /// riskiest listed source country lifts the score.
+    pub fn funnel_score(
+        &self,
+        activity: &CrossBorderActivityRow,
+        jurisdictions: &[HighRiskJurisdictionRow],
+    ) -> Option<f64> {
+        let cfg = &self.config;
+        if activity.senders < cfg.min_senders
+            || activity.payees < cfg.min_payees
+            || Self::dispersal(activity) < cfg.dispersal_ratio
+        {
+            return None;
+        }
+        let uplift = Self::listed_countries(activity, jurisdictions)
+            .iter()
+            .map(|j| match j.risk_level.as_str() {
+                "critical" | "high" => cfg.high_risk_uplift,
+                _ => cfg.elevated_uplift,
+            })
+            .fold(0.0, f64::max);
+        Some(cfg.alert_score + uplift)
+    }
+
+    /// SAR narrative for an XB_FUNNEL finding, quoting its window totals
+    /// and the transactions behind it.
+    pub fn narrative(finding: &CrossBorderFindingRow, evidence: &[CrossBorderEvidenceRow]) -> String {
+        let wires_in = evidence.iter().filter(|e| e.direction == "inbound").count();
+        let transfers_out = evidence.iter().filter(|e| e.direction == "domestic").count();
+        let share = if finding.inflow > 0.0 {
+            finding.outflow / finding.inflow * 100.0
+        } else {
+            0.0
+        };
+        let mut narrative = format!(
+            "Customer {} received ${:.2} in {} international wires from {} senders in {} \
+             between days {} and {}, and sent ${:.2} ({:.0}% of it) on to {} domestic payees \
+             in {} instant transfers.",
+            finding.customer_id,
+            finding.inflow,
+            wires_in,
+            finding.senders,
+            finding.countries.join(", "),
+            finding.window_start,
+            finding.tick,
+            finding.outflow,
+            share,
+            finding.payees,
+            transfers_out,
+        );
+        if !finding.high_risk_countries.is_empty() {
+            narrative.push_str(&format!(
+                " Funds originated in high-risk jurisdictions: {}.",
+                finding.high_risk_countries.join(", ")
+            ));
+        }
+        if let Some(largest) = evidence
+            .iter()
+            .filter(|e| e.direction == "inbound")
+            .max_by(|a, b| a.amount.total_cmp(&b.amount))
+        {
+            narrative.push_str(&format!(
+                " Largest wire in: ${:.2} from {} ({}) on day {}.",
+                largest.amount, largest.counterparty, largest.country, largest.tick
+            ));
+        }
+        narrative
+    }
+}
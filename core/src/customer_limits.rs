@@ -0,0 +1,91 @@
+//! Customer-level limits (Phase 4.12).
+//!
+//! Limits are keyed by type and scope. A rule applies globally, to one
+//! composite risk tier, or to one product; every rule in scope applies,
+//! so a customer's effective limit is the tightest of them. The
+//! transaction generators check each attempt against a `DailyLimits`
+//! tracker built at the start of the tick and decline anything over.
+
+use crate::config::CustomerLimitRule;
+use std::collections::HashMap;
+
+/// Limit types the generators enforce. Wires are capped per wire; the
+/// rest are daily totals. P2P sends are the customer's RTP payments.
+pub const LIMIT_TYPES: &[&str] = &["daily_transfer", "cash_withdrawal", "p2p_send", "wire"];
+
+/// Whether `scope` is one of "global", "tier:<tier>" or "product:<id>".
+pub fn valid_scope(scope: &str) -> bool {
+    scope == "global"
+        || scope.strip_prefix("tier:").is_some_and(|t| !t.is_empty())
+        || scope.strip_prefix("product:").is_some_and(|p| !p.is_empty())
+}
+
+/// One tick's view of the limits and what each customer has used.
+pub struct DailyLimits {
+    rules: Vec<CustomerLimitRule>,
+    /// customer_id → composite risk tier
+    tiers: HashMap<String, String>,
+    used: HashMap<(String, String), f64>,
+}
+
+impl DailyLimits {
+    pub fn new(rules: Vec<CustomerLimitRule>, tiers: HashMap<String, String>) -> Self {
+        Self {
+            rules,
+            tiers,
+            used: HashMap::new(),
+        }
+    }
+
+    /// Tightest limit in scope for the customer, if any rule applies.
+    pub fn limit_for(&self, customer_id: &str, product_id: &str, limit_type: &str) -> Option<f64> {
+        let tier = self.tiers.get(customer_id).map(String::as_str);
+        self.rules
+            .iter()
+            .filter(|r| r.limit_type == limit_type)
+            .filter(|r| match r.scope.split_once(':') {
+                Some(("tier", t)) => Some(t) == tier,
+                Some(("product", p)) => p == product_id,
+                _ => r.scope == "global",
+            })
+            .map(|r| r.amount)
+            .reduce(f64::min)
+    }
+
+    /// Dollars left today under the customer's limit, if any.
+    pub fn remaining(&self, customer_id: &str, product_id: &str, limit_type: &str) -> Option<f64> {
+        let limit = self.limit_for(customer_id, product_id, limit_type)?;
+        let used = self
+            .used
+            .get(&(customer_id.to_string(), limit_type.to_string()))
+            .copied()
+            .unwrap_or(0.0);
+        Some((limit - used).max(0.0))
+    }
+
+    /// Record `amount` against the limit. Returns the limit when the
+    /// attempt would go over it, leaving usage unchanged.
+    pub fn try_use(
+        &mut self,
+        customer_id: &str,
+        product_id: &str,
+        limit_type: &str,
+        amount: f64,
+    ) -> Result<(), f64> {
+        let Some(limit) = self.limit_for(customer_id, product_id, limit_type) else {
+            return Ok(());
+        };
+        if limit_type == "wire" {
+            return if amount > limit { Err(limit) } else { Ok(()) };
+        }
+        let used = self
+            .used
+            .entry((customer_id.to_string(), limit_type.to_string()))
+            .or_default();
+        if *used + amount > limit {
+            return Err(limit);
+        }
+        *used += amount;
+        Ok(())
+    }
+}
@@ -1,20 +1,31 @@
 use crate::{
+    command::PlayerCommand,
     config::{RegionPool, SegmentConfig, SimConfig},
-    error::SimResult,
+    error::{SimError, SimResult},
     event::SimEvent,
+    idv,
     name_generator::NameGenerator,
+    risk_lifecycle,
     rng::SubsystemRng,
+    satisfaction::SatisfactionDynamics,
+    ssn_validation,
     store::{
+        idv_check::IdvCheckRow,
+        risk_lifecycle::{EddReviewRow, RiskTierRow, RiskTierTransitionRow},
+        ssn_validation::SsnValidationRow,
+        visa::VisaRestrictionPolicy,
         AuthorizedSignerRow, BusinessEntityRow, CustodialAccountRow,
         CustomerAddressRow, CustomerBeneficiaryRow, CustomerIdentityRow,
         CustomerInternationalRow, CustomerPhoneRow, CustomerRelationshipRow,
         CustomerRiskScoreRow, DbaRegistrationRow, JointOwnershipRow, SimStore,
         TrustAccountRow, TrustBeneficiaryRow,
     },
-    subsystem::SimSubsystem,
+    subsystem::{SimSubsystem, SubsystemManifest},
     types::{RunId, Tick},
+    visa::VisaLifecycle,
 };
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 
 pub const CHURN_THRESHOLD: f64 = 0.85;
 pub const SATISFACTION_DECAY_PER_TICK: f64 = 0.0002;
@@ -48,15 +59,23 @@ pub struct CustomerSubsystem {
     config: SimConfig,
     store: SimStore,
     initialized: bool,
+    /// Phase 4.33: monthly reversion toward each customer's baseline.
+    satisfaction: SatisfactionDynamics,
+    /// Phase 4.37: visa documentation refresh and lapse restrictions.
+    visa: VisaLifecycle,
 }
 
 impl CustomerSubsystem {
     pub fn new(run_id: RunId, config: SimConfig, store: SimStore) -> Self {
+        let satisfaction = SatisfactionDynamics::new(run_id.clone(), config.satisfaction_dynamics.clone());
+        let visa = VisaLifecycle::new(run_id.clone(), config.visa_expiry.clone());
         Self {
             run_id,
             config,
             store,
             initialized: false,
+            satisfaction,
+            visa,
         }
     }
 
@@ -67,9 +86,17 @@ impl CustomerSubsystem {
     ) -> SimResult<Vec<(CustomerRecord, String)>> {
         let n = self.config.initial_population;
         let mut customers = Vec::with_capacity(n);
+        // A small sample drawn segment by segment would drift from the
+        // configured mix, so a scaled run fixes each segment's quota
+        let stratified = (self.config.population_scale < 1.0)
+            .then(|| self.stratified_segments(n, rng))
+            .transpose()?;
 
         for i in 0..n {
-            let seg = self.pick_segment(rng);
+            let seg = match &stratified {
+                Some(segments) => segments[i],
+                None => self.pick_segment(rng),
+            };
             let income_band = self.pick_income_band(seg, rng);
             let has_payroll = rng.chance(seg.payroll_probability);
             let payroll_amount = if has_payroll {
@@ -161,11 +188,6 @@ impl CustomerSubsystem {
         // Serial: 0001-9999
         let serial = (rng.next_u64_below(9999) + 1) as u16;
 
-        let ssn_area   = format!("{area:03}");
-        let ssn_group  = format!("{group:02}");
-        let ssn_serial = format!("{serial:04}");
-        let ssn_full   = format!("{ssn_area}-{ssn_group}-{ssn_serial}");
-
         // ~2% are synthetic identity fraudsters
         let (ssn_status, identity_type) = if rng.next_f64() < cfg.synthetic_identity_rate {
             ("synthetic", "synthetic")
@@ -173,9 +195,113 @@ impl CustomerSubsystem {
             ("valid", "natural_person")
         };
 
+        // Phase 4.14: some synthetic identities are built on implausible SSNs
+        let (area, group) = match (identity_type, self.synthetic_ssn_pattern(idx)) {
+            ("synthetic", "post_randomization") => (773 + (idx % 127) as u16, group),
+            ("synthetic", "invalid_group") => (area, 0),
+            _ => (area, group),
+        };
+
+        let ssn_area   = format!("{area:03}");
+        let ssn_group  = format!("{group:02}");
+        let ssn_serial = format!("{serial:04}");
+        let ssn_full   = format!("{ssn_area}-{ssn_group}-{ssn_serial}");
+
         (ssn_full, ssn_area, ssn_group, ssn_serial, ssn_status, identity_type)
     }
 
+    /// Phase 4.14: how a synthetic identity's SSN is obtained, cycling
+    /// through a deceased person's SSN, a post-2011 area, an impossible
+    /// group and a clean-looking SSN. "clean" when validation is off.
+    fn synthetic_ssn_pattern(&self, idx: usize) -> &'static str {
+        if !self.config.ssn_validation.enabled {
+            return "clean";
+        }
+        match idx % 4 {
+            0 => "deceased",
+            1 => "post_randomization",
+            2 => "invalid_group",
+            _ => "clean",
+        }
+    }
+
+    /// Phase 4.14: seed the death master file with genuine entries.
+    fn seed_death_master_file(&self) -> SimResult<()> {
+        for i in 0..self.config.ssn_validation.death_master_file_size {
+            let ssn = format!("{:03}-{:02}-{:04}", 1 + i % 665, 1 + i % 99, 9999 - i % 9999);
+            let death_year = SIM_BASE_YEAR - 1 - (i % 40) as i32;
+            self.store.insert_death_master_file_entry(&self.run_id, &ssn, death_year)?;
+        }
+        Ok(())
+    }
+
+    /// Phase 4.14: validate the SSN against the issuance tables and the
+    /// death master file at onboarding screening.
+    fn validate_ssn(&self, identity: &CustomerIdentityRow, tick: Tick) -> SimResult<Option<SimEvent>> {
+        let cfg = &self.config.ssn_validation;
+        let birth_year = identity.date_of_birth[..4].parse().unwrap_or(SIM_BASE_YEAR);
+        let mut findings = ssn_validation::validate(
+            identity.ssn_area.parse().unwrap_or(0),
+            identity.ssn_group.parse().unwrap_or(0),
+            identity.ssn_serial.parse().unwrap_or(0),
+            birth_year,
+        );
+        findings.deceased = self.store.on_death_master_file(&self.run_id, &identity.ssn_full)?;
+
+        let row = SsnValidationRow {
+            customer_id: identity.customer_id.clone(),
+            tick,
+            issues: findings.issues().into_iter().map(String::from).collect(),
+            synthetic_score: findings.synthetic_score(cfg),
+        };
+        self.store.insert_ssn_validation(&self.run_id, &row)?;
+        if findings.is_clean() {
+            return Ok(None);
+        }
+        Ok(Some(SimEvent::SsnValidationFlagged {
+            tick,
+            customer_id: row.customer_id,
+            issues: row.issues.join(","),
+            synthetic_score: row.synthetic_score,
+        }))
+    }
+
+    /// Phase 4.15: run the applicant past the configured IDV vendor and
+    /// record the match codes. Returns the decline event if any element
+    /// came back as no match, after closing out the applicant.
+    fn verify_identity(
+        &self,
+        identity: &CustomerIdentityRow,
+        tick: Tick,
+        rng: &mut SubsystemRng,
+    ) -> SimResult<Option<SimEvent>> {
+        let vendor = idv::vendor_profile(&self.config.idv);
+        let codes = idv::check(vendor, identity.identity_type == "synthetic", rng);
+        let decision = idv::decision(&codes);
+        let [name_match, ssn_match, dob_match, address_match] = codes.map(String::from);
+        self.store.insert_idv_check(&self.run_id, &IdvCheckRow {
+            customer_id: identity.customer_id.clone(),
+            tick,
+            vendor: vendor.name.clone(),
+            name_match,
+            ssn_match,
+            dob_match,
+            address_match,
+            decision: decision.to_string(),
+            cost: vendor.cost_per_check,
+        })?;
+        if decision == "pass" {
+            return Ok(None);
+        }
+        self.store.decline_applicant(&self.run_id, &identity.customer_id, tick)?;
+        Ok(Some(SimEvent::ApplicantDeclined {
+            tick,
+            customer_id: identity.customer_id.clone(),
+            vendor: vendor.name.clone(),
+            match_codes: codes.join(","),
+        }))
+    }
+
     /// Generate a date-of-birth string (YYYY-MM-DD) consistent with the
     /// segment's expected age range.
     fn generate_dob(
@@ -420,6 +546,50 @@ impl CustomerSubsystem {
         &self.config.segments[keys.last().unwrap().as_str()]
     }
 
+    /// `n` segments in proportion to their population shares (largest
+    /// remainder), shuffled so segments do not cluster by customer id.
+    fn stratified_segments<'a>(
+        &'a self,
+        n: usize,
+        rng: &mut SubsystemRng,
+    ) -> SimResult<Vec<&'a SegmentConfig>> {
+        if self.config.segments.is_empty() {
+            return Err(SimError::generator("customer", "no segments to sample from"));
+        }
+        let mut keys: Vec<&String> = self.config.segments.keys().collect();
+        keys.sort();
+        let total: f64 = keys.iter().map(|k| self.config.segments[k.as_str()].population_share).sum();
+
+        let mut quotas: Vec<(usize, f64, &SegmentConfig)> = keys
+            .iter()
+            .map(|k| {
+                let seg = &self.config.segments[k.as_str()];
+                let exact = n as f64 * seg.population_share / total;
+                (exact.floor() as usize, exact.fract(), seg)
+            })
+            .collect();
+        let mut short = n - quotas.iter().map(|q| q.0).sum::<usize>();
+        let mut by_remainder: Vec<usize> = (0..quotas.len()).collect();
+        by_remainder.sort_by(|&a, &b| quotas[b].1.total_cmp(&quotas[a].1));
+        for idx in by_remainder.into_iter().cycle() {
+            if short == 0 {
+                break;
+            }
+            quotas[idx].0 += 1;
+            short -= 1;
+        }
+
+        let mut segments: Vec<&SegmentConfig> = quotas
+            .into_iter()
+            .flat_map(|(count, _, seg)| std::iter::repeat_n(seg, count))
+            .collect();
+        for i in (1..segments.len()).rev() {
+            let j = rng.next_u64_below(i as u64 + 1) as usize;
+            segments.swap(i, j);
+        }
+        Ok(segments)
+    }
+
     fn pick_income_band(&self, seg: &SegmentConfig, rng: &mut SubsystemRng) -> String {
         let roll = rng.next_f64();
         let mut cum = 0.0;
@@ -998,13 +1168,192 @@ impl CustomerSubsystem {
 
         (primary, secondary)
     }
+
+    // ─────────────────────────────────────────────────────────────────────────
+    // Phase 4.13: Risk-tiered lifecycle
+    // ─────────────────────────────────────────────────────────────────────────
+
+    /// Re-score behavior risk, escalate tiers the signals warrant, and run
+    /// the EDD reviews that fall due.
+    fn review_risk_lifecycle(&self, tick: Tick) -> SimResult<Vec<SimEvent>> {
+        let cfg = &self.config.risk_lifecycle;
+        let mut events = Vec::new();
+
+        if cfg.rescore_interval > 0 && tick.is_multiple_of(cfg.rescore_interval) {
+            let signals = self
+                .store
+                .behavior_signals_since(&self.run_id, tick.saturating_sub(cfg.signal_lookback_ticks))?;
+            for row in self.store.customer_risk_tier_rows(&self.run_id)? {
+                let behavior = signals
+                    .get(&row.customer_id)
+                    .map_or(0.0, |s| risk_lifecycle::behavior_risk(s, cfg));
+                self.store.update_behavior_risk(&self.run_id, &row.customer_id, behavior)?;
+
+                // Every customer has one review on the books at their tier's cadence
+                let scheduled = match self.store.scheduled_edd_review(&self.run_id, &row.customer_id)? {
+                    Some(review) => review,
+                    None => {
+                        let review = self.edd_review(&row.customer_id, &row.tier, tick);
+                        self.store.insert_edd_review(&self.run_id, &review)?;
+                        review
+                    }
+                };
+
+                let target = risk_lifecycle::higher_tier(
+                    &row.base_tier,
+                    risk_lifecycle::behavior_tier(behavior, cfg),
+                );
+                if risk_lifecycle::tier_rank(target) <= risk_lifecycle::tier_rank(&row.tier) {
+                    continue;
+                }
+                events.push(self.change_tier(
+                    &row.customer_id,
+                    &row.tier,
+                    target,
+                    "behavior_escalation",
+                    behavior,
+                    tick,
+                )?);
+
+                // A higher tier brings the review forward to its own cadence
+                let due = tick + self.review_interval(target);
+                if due < scheduled.due_tick {
+                    self.store.reschedule_edd_review(&self.run_id, &scheduled.review_id, target, due)?;
+                }
+            }
+        }
+
+        let due = self.store.due_edd_reviews(&self.run_id, tick)?;
+        if due.is_empty() {
+            return Ok(events);
+        }
+        let rows: HashMap<String, RiskTierRow> = self
+            .store
+            .customer_risk_tier_rows(&self.run_id)?
+            .into_iter()
+            .map(|row| (row.customer_id.clone(), row))
+            .collect();
+        for review in due {
+            let Some(row) = rows.get(&review.customer_id) else {
+                continue;
+            };
+            // Reviews step the customer down only as far as their current behavior allows
+            let target = risk_lifecycle::higher_tier(
+                &row.base_tier,
+                risk_lifecycle::behavior_tier(row.behavior_risk, cfg),
+            );
+            let downgrade = risk_lifecycle::tier_rank(target) < risk_lifecycle::tier_rank(&row.tier);
+            let (outcome, tier) = if downgrade {
+                events.push(self.change_tier(
+                    &row.customer_id,
+                    &row.tier,
+                    target,
+                    "review_downgrade",
+                    row.behavior_risk,
+                    tick,
+                )?);
+                ("downgraded", target)
+            } else {
+                ("retained", row.tier.as_str())
+            };
+            let cost = risk_lifecycle::servicing(cfg, &row.tier).map_or(0.0, |s| s.review_cost);
+            self.store.complete_edd_review(&self.run_id, &review, outcome, cost, tick)?;
+
+            let next = self.edd_review(&row.customer_id, tier, tick);
+            self.store.insert_edd_review(&self.run_id, &next)?;
+            events.push(SimEvent::EddReviewCompleted {
+                tick,
+                review_id: review.review_id,
+                customer_id: row.customer_id.clone(),
+                outcome: outcome.to_string(),
+                next_due_tick: next.due_tick,
+            });
+        }
+
+        Ok(events)
+    }
+
+    fn review_interval(&self, tier: &str) -> Tick {
+        risk_lifecycle::servicing(&self.config.risk_lifecycle, tier).map_or(365, |s| s.review_interval)
+    }
+
+    /// A review due one interval after `tick` at the tier's cadence.
+    fn edd_review(&self, customer_id: &str, tier: &str, tick: Tick) -> EddReviewRow {
+        EddReviewRow {
+            review_id: format!("edd-{customer_id}-{tick}"),
+            customer_id: customer_id.to_string(),
+            tier: tier.to_string(),
+            trigger: "scheduled".to_string(),
+            due_tick: tick + self.review_interval(tier),
+            completed_tick: None,
+            outcome: None,
+            cost: 0.0,
+        }
+    }
+
+    fn change_tier(
+        &self,
+        customer_id: &str,
+        from_tier: &str,
+        to_tier: &str,
+        reason: &str,
+        behavior_risk: f64,
+        tick: Tick,
+    ) -> SimResult<SimEvent> {
+        let override_kind = if reason == "behavior_escalation" { "escalated" } else { "de-escalated" };
+        self.store.transition_risk_tier(
+            &self.run_id,
+            &RiskTierTransitionRow {
+                tick,
+                customer_id: customer_id.to_string(),
+                from_tier: from_tier.to_string(),
+                to_tier: to_tier.to_string(),
+                reason: reason.to_string(),
+                behavior_risk,
+            },
+            override_kind,
+        )?;
+        tracing::info!("{customer_id} risk tier {from_tier} → {to_tier} ({reason})");
+        Ok(SimEvent::RiskTierChanged {
+            tick,
+            customer_id: customer_id.to_string(),
+            from_tier: from_tier.to_string(),
+            to_tier: to_tier.to_string(),
+            reason: reason.to_string(),
+        })
+    }
 }
 
+const MANIFEST: SubsystemManifest = SubsystemManifest {
+    version: 1,
+    produces: &[
+        "account_restricted", "account_restriction_lifted", "applicant_declined",
+        "customer_identity_created", "customer_onboarded", "edd_review_completed",
+        "risk_tier_changed", "ssn_validation_flagged", "visa_documentation_lapsed",
+        "visa_documents_renewed", "visa_refresh_requested", "visa_restriction_policy_rejected",
+        "visa_restriction_policy_set",
+    ],
+    consumes: &["fee_charged", "player_command_received"],
+    tables: &[
+        "account", "authorized_signer", "business_entity", "custodial_account", "customer",
+        "customer_address", "customer_beneficiary", "customer_identity", "customer_international",
+        "customer_phone", "customer_relationship", "customer_risk_score", "dba_registration",
+        "death_master_file", "edd_review", "idv_check", "joint_ownership", "risk_tier_transition",
+        "satisfaction_gesture", "satisfaction_scar", "ssn_validation", "trust_account",
+        "trust_beneficiary", "visa_document", "visa_refresh_item", "visa_restriction_policy",
+    ],
+    requires: &[],
+};
+
 impl SimSubsystem for CustomerSubsystem {
     fn name(&self) -> &'static str {
         "customer"
     }
 
+    fn manifest(&self) -> SubsystemManifest {
+        MANIFEST
+    }
+
     fn update(
         &mut self,
         tick: Tick,
@@ -1018,8 +1367,13 @@ impl SimSubsystem for CustomerSubsystem {
             self.initialized = true;
             let population = self.generate_initial_population(rng, tick)?;
             let mut onboarded = 0usize;
+            let mut declined = 0usize;
+            if self.config.ssn_validation.enabled {
+                self.seed_death_master_file()?;
+            }
 
             for (customer, account_id) in population {
+                let applicant = onboarded + declined;
                 self.store.insert_customer(&self.run_id, &customer)?;
                 self.store.insert_account(
                     &self.run_id,
@@ -1032,12 +1386,16 @@ impl SimSubsystem for CustomerSubsystem {
 
                 // ── Phase 3.5-prep: Generate identity attributes ──────────
                 let region = self.pick_region(rng).clone();
-                let seg = self.config.segments.get(&customer.segment)
-                    .expect("segment must exist in config");
+                let seg = self.config.segments.get(&customer.segment).ok_or_else(|| {
+                    SimError::generator(
+                        "customer",
+                        format!("segment '{}' is not in config", customer.segment),
+                    )
+                })?;
 
                 // SSN / identity
                 let (ssn_full, ssn_area, ssn_group, ssn_serial, ssn_status, identity_type) =
-                    self.generate_ssn(onboarded, &region, rng);
+                    self.generate_ssn(applicant, &region, rng);
 
                 let (date_of_birth, age_at_open) = self.generate_dob(seg, tick, rng);
 
@@ -1057,6 +1415,25 @@ impl SimSubsystem for CustomerSubsystem {
                 };
                 self.store.insert_customer_identity(&identity_row)?;
 
+                // Phase 4.14: onboarding SSN screening
+                if self.config.ssn_validation.enabled {
+                    if identity_type == "synthetic" && self.synthetic_ssn_pattern(applicant) == "deceased" {
+                        let death_year = SIM_BASE_YEAR - 1 - (applicant % 20) as i32;
+                        self.store.insert_death_master_file_entry(&self.run_id, &ssn_full, death_year)?;
+                    }
+                    out_events.extend(self.validate_ssn(&identity_row, tick)?);
+                }
+
+                // Phase 4.15: vendor identity verification; declined
+                // applicants get no further profile
+                if self.config.idv.enabled {
+                    if let Some(event) = self.verify_identity(&identity_row, tick, rng)? {
+                        out_events.push(event);
+                        declined += 1;
+                        continue;
+                    }
+                }
+
                 // Address
                 let addr_row = self.generate_address(seg, &region, tick, &customer.customer_id, rng);
                 let is_shelter = addr_row.address_type == "homeless_shelter";
@@ -1228,7 +1605,10 @@ impl SimSubsystem for CustomerSubsystem {
 
                 onboarded += 1;
             }
-            log::info!("tick=0 customer: onboarded {onboarded} customers with full profile");
+            tracing::info!(
+                "onboarded {onboarded} customers with full profile, \
+                 {declined} declined at identity verification"
+            );
             return Ok(out_events);
         }
 
@@ -1252,11 +1632,14 @@ impl SimSubsystem for CustomerSubsystem {
             }
         }
 
-        // Apply satisfaction decay every 30 ticks (monthly).
+        // Apply satisfaction decay every 30 ticks (monthly). Phase 4.33:
+        // with dynamics on, revert toward each customer's baseline instead.
         if tick.is_multiple_of(30) {
             let active = self.store.active_customers(&self.run_id)?;
-            for mut c in active {
-                if c.satisfaction > 0.6 {
+            if self.satisfaction.enabled() {
+                self.satisfaction.revert(&self.store, active)?;
+            } else {
+                for mut c in active.into_iter().filter(|c| c.satisfaction > 0.6) {
                     c.satisfaction = (c.satisfaction - SATISFACTION_DECAY_PER_TICK * 30.0).max(0.0);
                     self.store.update_customer_churn_satisfaction(
                         &self.run_id,
@@ -1268,10 +1651,46 @@ impl SimSubsystem for CustomerSubsystem {
             }
         }
 
+        // Phase 4.13: behavior re-scores and EDD reviews
+        if self.config.risk_lifecycle.enabled {
+            out_events.extend(self.review_risk_lifecycle(tick)?);
+        }
+
+        // Phase 4.37: restriction policy changes, then visa timelines
+        if self.visa.enabled() {
+            for event in events_in {
+                let SimEvent::PlayerCommandReceived { command_id, .. } = event else {
+                    continue;
+                };
+                if let Some(PlayerCommand::SetVisaRestrictionPolicy { level, grace_ticks }) =
+                    self.store.get_player_command(&self.run_id, command_id)?
+                {
+                    let policy = VisaRestrictionPolicy { level, grace_ticks };
+                    out_events.push(self.visa.set_policy(&self.store, policy, tick)?);
+                }
+            }
+            out_events.extend(self.visa.process(&self.store, tick, rng)?);
+        }
+
         Ok(out_events)
     }
 
 
+    fn save_state(&self) -> SimResult<Option<serde_json::Value>> {
+        Ok(Some(serde_json::json!({ "initialized": self.initialized })))
+    }
+
+    fn load_state(&mut self, state: serde_json::Value) -> SimResult<()> {
+        self.initialized = state["initialized"].as_bool().unwrap_or_default();
+        Ok(())
+    }
+
+    // The initial population is generated on the first tick played
+    fn replay(&mut self, _tick: Tick, _events: &[SimEvent]) -> SimResult<()> {
+        self.initialized = true;
+        Ok(())
+    }
+
     fn as_any(&self) -> &dyn std::any::Any {
         self
     }
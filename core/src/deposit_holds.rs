@@ -0,0 +1,209 @@
+//! Deposit holds (Phase 4.55).
+//!
+//! Funds from a check deposit are not all available at once. The first
+//! `next_day_amount` is; the rest stays on hold for as many ticks as the
+//! bank's policy sets for the deposit's classes: the channel it came in
+//! through, plus the exception classes for a new account and a large
+//! item. Each class takes the most specific rule matching it and the
+//! account's risk (elevated after a recent returned deposit), and the
+//! longest of those is the hold. Statutory limits in scope then cap it;
+//! the player can change the policy, but never past a limit.
+//!
+//! A hold keeps a fraud ring from cashing out a counterfeit before the
+//! paying bank returns it, but every customer waiting on their money
+//! feels it: a hold's pressure, its length times the segment's liquidity
+//! sensitivity, costs satisfaction and may prompt a complaint.
+
+use crate::{
+    config::{DepositHoldConfig, HoldRule},
+    error::SimResult,
+    event::SimEvent,
+    store::{deposit_holds::DepositHoldRow, SimStore},
+    types::{RunId, Tick},
+};
+
+pub const CLASSES: &[&str] = &["atm", "branch", "new_account", "large_item"];
+pub const RISKS: &[&str] = &["standard", "elevated"];
+
+impl HoldRule {
+    fn matches(&self, class: &str, risk: &str) -> bool {
+        self.class.as_deref().is_none_or(|c| c == class) && self.risk.as_deref().is_none_or(|r| r == risk)
+    }
+
+    /// Whether every deposit `other` applies to is also in this scope.
+    fn covers(&self, other: &HoldRule) -> bool {
+        (self.class.is_none() || self.class == other.class) && (self.risk.is_none() || self.risk == other.risk)
+    }
+
+    fn specificity(&self) -> usize {
+        [&self.class, &self.risk].iter().filter(|f| f.is_some()).count()
+    }
+}
+
+pub struct DepositHolds {
+    run_id: RunId,
+    config: DepositHoldConfig,
+}
+
+impl DepositHolds {
+    pub fn new(run_id: RunId, config: DepositHoldConfig) -> Self {
+        Self { run_id, config }
+    }
+
+    pub fn enabled(&self) -> bool {
+        self.config.enabled
+    }
+
+    /// Tightest statutory limit covering the rule's whole scope, if any.
+    pub fn statutory_cap(&self, rule: &HoldRule) -> Option<Tick> {
+        self.config
+            .statutory_limits
+            .iter()
+            .filter(|limit| limit.covers(rule))
+            .map(|limit| limit.hold_ticks)
+            .min()
+    }
+
+    /// Hold for one class of deposit. The most specific rule wins, the
+    /// player's later changes breaking ties; no rule means no hold.
+    /// `player` is the player's rules with the tick each was set.
+    pub fn hold_ticks(&self, class: &str, risk: &str, player: &[(Tick, HoldRule)]) -> Tick {
+        let policy = self
+            .config
+            .policies
+            .iter()
+            .map(|rule| (0, rule))
+            .chain(player.iter().map(|(tick, rule)| (*tick + 1, rule)))
+            .filter(|(_, rule)| rule.matches(class, risk))
+            .max_by_key(|(set, rule)| (rule.specificity(), *set))
+            .map_or(0, |(_, rule)| rule.hold_ticks);
+        self.config
+            .statutory_limits
+            .iter()
+            .filter(|limit| limit.matches(class, risk))
+            .map(|limit| limit.hold_ticks)
+            .fold(policy, Tick::min)
+    }
+
+    /// Phase 4.55: adopt a player's hold rule unless it is malformed or
+    /// would hold longer than the law allows.
+    pub fn set_policy(&self, store: &SimStore, rule: HoldRule, tick: Tick) -> SimResult<SimEvent> {
+        let reason = if rule.class.as_deref().is_some_and(|c| !CLASSES.contains(&c)) {
+            Some("unknown class".to_string())
+        } else if rule.risk.as_deref().is_some_and(|r| !RISKS.contains(&r)) {
+            Some("unknown risk".to_string())
+        } else {
+            self.statutory_cap(&rule)
+                .filter(|cap| rule.hold_ticks > *cap)
+                .map(|cap| format!("regulation allows holds of at most {cap} days"))
+        };
+        if let Some(reason) = reason {
+            tracing::warn!("rejected deposit hold policy {rule:?}: {reason}");
+            return Ok(SimEvent::DepositHoldPolicyRejected {
+                tick,
+                class: rule.class,
+                risk: rule.risk,
+                hold_ticks: rule.hold_ticks,
+                reason,
+            });
+        }
+
+        store.insert_deposit_hold_policy(&self.run_id, tick, &rule)?;
+        tracing::info!("deposit hold of {} days for {rule:?}", rule.hold_ticks);
+        Ok(SimEvent::DepositHoldPolicySet {
+            tick,
+            class: rule.class,
+            risk: rule.risk,
+            hold_ticks: rule.hold_ticks,
+        })
+    }
+
+    /// Make held funds whose release tick has come available.
+    pub fn release(&self, store: &SimStore, tick: Tick) -> SimResult<()> {
+        for hold in store.deposit_holds_due(&self.run_id, tick)? {
+            store.update_available_balance(&self.run_id, &hold.account_id, hold.held_amount)?;
+            store.release_deposit_hold(&self.run_id, &hold.txn_id, tick)?;
+        }
+        Ok(())
+    }
+
+    /// Lift the hold on a deposit early, as when it is charged back.
+    pub fn lift(store: &SimStore, run_id: &str, txn_id: &str, tick: Tick) -> SimResult<()> {
+        if let Some(hold) = store.active_deposit_hold(run_id, txn_id)? {
+            store.update_available_balance(run_id, &hold.account_id, hold.held_amount)?;
+            store.release_deposit_hold(run_id, txn_id, tick)?;
+        }
+        Ok(())
+    }
+
+    /// Hold today's check deposits.
+    pub fn place(&self, store: &SimStore, tick: Tick) -> SimResult<Vec<SimEvent>> {
+        let c = &self.config;
+        let player = store.deposit_hold_policies(&self.run_id)?;
+        let mut events = Vec::new();
+        for item in store.check_items_at(&self.run_id, tick)?.into_iter().filter(|i| i.side == "deposited") {
+            let held_amount = item.amount - c.next_day_amount;
+            if held_amount <= 0.0 {
+                continue;
+            }
+            let since = tick.saturating_sub(c.risk_lookback_ticks);
+            let risk = if store.returned_check_deposits_since(&self.run_id, &item.account_id, since)? > 0 {
+                "elevated"
+            } else {
+                "standard"
+            };
+            let mut classes = vec![item.channel.as_str()];
+            if store.account_open_tick(&self.run_id, &item.account_id)? + c.new_account_ticks > tick {
+                classes.push("new_account");
+            }
+            if item.amount > c.large_item_threshold {
+                classes.push("large_item");
+            }
+            let (mut class, mut hold_ticks) = (classes[0], 0);
+            for candidate in classes {
+                let ticks = self.hold_ticks(candidate, risk, &player);
+                if ticks > hold_ticks {
+                    (class, hold_ticks) = (candidate, ticks);
+                }
+            }
+            if hold_ticks == 0 {
+                continue;
+            }
+
+            let customer_id = store.get_account_customer_id(&self.run_id, &item.account_id)?;
+            let segment = store.customer_segment(&self.run_id, &customer_id)?;
+            let pressure = hold_ticks as f64 * c.liquidity_sensitivity.get(&segment).copied().unwrap_or(1.0);
+            store.update_available_balance(&self.run_id, &item.account_id, -held_amount)?;
+            store.update_customer_satisfaction(&self.run_id, &customer_id, -c.satisfaction_per_pressure * pressure)?;
+            store.insert_deposit_hold(
+                &self.run_id,
+                &DepositHoldRow {
+                    txn_id: item.txn_id.clone(),
+                    account_id: item.account_id.clone(),
+                    customer_id: customer_id.clone(),
+                    tick,
+                    class: class.to_string(),
+                    risk: risk.to_string(),
+                    amount: item.amount,
+                    held_amount,
+                    hold_ticks,
+                    release_tick: tick + hold_ticks,
+                    released_tick: None,
+                    pressure,
+                },
+            )?;
+            events.push(SimEvent::FundsHeld {
+                tick,
+                customer_id,
+                account_id: item.account_id,
+                txn_id: item.txn_id,
+                held_amount,
+                hold_ticks,
+                class: class.to_string(),
+                risk: risk.to_string(),
+                pressure,
+            });
+        }
+        Ok(events)
+    }
+}
@@ -0,0 +1,310 @@
+//! Macroprudential deposit run scenario (Phase 4.52).
+//!
+//! Outside a run the bank holds liquid assets and capital at fixed shares
+//! of its deposits. A confidence shock — scripted for a tick, or set off
+//! by the reputation score falling below a trigger — starts a run: each
+//! tick a share of every uninsured balance (above the insured limit) is
+//! withdrawn, and insured depositors occasionally empty their accounts.
+//! That share grows every tick by the configured acceleration, scaled by
+//! severity: reputation below neutral, liquidity coverage below its floor
+//! and capital below its minimum ratio each make the run worse. Capital
+//! held against operational risk does not count toward that ratio.
+//!
+//! Withdrawals are paid from liquid assets; once those run out the gap
+//! is borrowed at the penalty rate. The player can pay a premium on the
+//! deposit rate, which slows the run's growth at a cost to NII, or draw
+//! discount window credit up to a share of deposits, which restores
+//! coverage at a lower rate than the penalty. During a run both costs
+//! come out of capital. The run is over once its rate falls below calm;
+//! borrowing is then repaid and the balance sheet resets to its usual
+//! ratios.
+
+use crate::{
+    command::PlayerCommand,
+    config::DepositRunConfig,
+    error::SimResult,
+    event::SimEvent,
+    rng::SubsystemRng,
+    store::{
+        deposit_run::{DepositRunRow, LiquidityPositionRow},
+        SimStore,
+    },
+    types::{RunId, Tick},
+};
+use uuid::Uuid;
+
+pub struct DepositRun {
+    run_id: RunId,
+    config: DepositRunConfig,
+}
+
+impl DepositRun {
+    pub fn new(run_id: RunId, config: DepositRunConfig) -> Self {
+        Self { run_id, config }
+    }
+
+    pub fn enabled(&self) -> bool {
+        self.config.enabled
+    }
+
+    /// How much worse than its base pace a run goes: 1 plus a share of
+    /// each driver's weight.
+    pub fn severity(&self, position: &LiquidityPositionRow, reputation: f64) -> f64 {
+        let c = &self.config;
+        let below = |value: f64, floor: f64| {
+            if floor > 0.0 {
+                ((floor - value) / floor).clamp(0.0, 1.0)
+            } else {
+                0.0
+            }
+        };
+        let free_capital = position.capital - position.op_risk_capital;
+        let capital_ratio = if position.deposits > 0.0 { free_capital / position.deposits } else { 1.0 };
+        let reputation_gap = if c.neutral_reputation > 0.0 {
+            ((c.neutral_reputation - reputation) / c.neutral_reputation).clamp(-1.0, 1.0)
+        } else {
+            0.0
+        };
+        1.0 + c.reputation_weight * reputation_gap
+            + c.liquidity_weight * below(position.coverage(), c.min_liquidity_coverage)
+            + c.capital_weight * below(capital_ratio, c.min_capital_ratio)
+    }
+
+    /// Apply the player's `commands`, start a run if a shock hits, move
+    /// any run on a tick, and record the day's position.
+    pub fn process(
+        &self,
+        store: &SimStore,
+        tick: Tick,
+        commands: &[PlayerCommand],
+        rng: &mut SubsystemRng,
+    ) -> SimResult<Vec<SimEvent>> {
+        let c = &self.config;
+        let mut events = Vec::new();
+
+        let balances = store.deposit_balances(&self.run_id)?;
+        let deposits: f64 = balances.iter().map(|(_, b)| b).sum();
+        let uninsured: f64 = balances.iter().map(|(_, b)| (b - c.insured_limit).max(0.0)).sum();
+        let previous = store.latest_liquidity_position(&self.run_id)?;
+        let mut position = previous.clone().unwrap_or(LiquidityPositionRow {
+            tick,
+            deposits,
+            uninsured_deposits: uninsured,
+            liquid_assets: 0.0,
+            emergency_borrowing: 0.0,
+            penalty_borrowing: 0.0,
+            capital: 0.0,
+            op_risk_capital: 0.0,
+            rate_premium: 0.0,
+            run_rate: 0.0,
+            severity: 1.0,
+            outflow: 0.0,
+            premium_cost: 0.0,
+            liquidity_cost: 0.0,
+        });
+        position.tick = tick;
+        position.deposits = deposits;
+        position.uninsured_deposits = uninsured;
+        position.outflow = 0.0;
+        // Phase 4.61: the latest quarter's operational risk capital
+        position.op_risk_capital = store.latest_op_risk_capital(&self.run_id)?.map_or(0.0, |c| c.capital);
+
+        let runs = store.deposit_runs(&self.run_id)?;
+        let mut run = runs.last().filter(|r| r.ended_tick.is_none()).cloned();
+        if run.is_none() {
+            position.liquid_assets = c.liquid_asset_ratio * deposits;
+            position.capital = c.capital_ratio * deposits;
+            position.run_rate = 0.0;
+        }
+
+        for command in commands {
+            events.push(self.apply(command, &mut position, run.as_mut(), tick));
+        }
+
+        let reputation = store.latest_reputation_score(&self.run_id)?;
+        let cooled = runs.last().and_then(|r| r.ended_tick).is_none_or(|ended| tick >= ended + c.cooldown_ticks);
+        let mut started = false;
+        if run.is_none() && cooled {
+            let trigger = if c.shock_tick == Some(tick) {
+                Some("scheduled")
+            } else if reputation < c.reputation_trigger {
+                Some("reputation")
+            } else {
+                None
+            };
+            if let Some(trigger) = trigger {
+                position.run_rate = (c.initial_run_rate * self.severity(&position, reputation)).min(c.max_run_rate);
+                let row = DepositRunRow {
+                    shock_tick: tick,
+                    trigger: trigger.to_string(),
+                    reputation_score: reputation,
+                    ended_tick: None,
+                    peak_run_rate: position.run_rate,
+                    total_outflow: 0.0,
+                    uninsured_outflow: 0.0,
+                    emergency_drawn: 0.0,
+                    shortfall: 0.0,
+                };
+                store.insert_deposit_run(&self.run_id, &row)?;
+                tracing::warn!("deposit run started at tick {tick}: {trigger}, reputation {reputation:.1}");
+                events.push(SimEvent::DepositRunStarted {
+                    tick,
+                    trigger: trigger.to_string(),
+                    reputation_score: reputation,
+                    run_rate: position.run_rate,
+                    uninsured_deposits: uninsured,
+                });
+                run = Some(row);
+                started = true;
+            }
+        }
+
+        position.severity = self.severity(&position, reputation);
+        if let Some(mut row) = run {
+            if !started {
+                let response = 1.0 + c.rate_sensitivity * position.rate_premium / 0.01;
+                position.run_rate = (position.run_rate * c.acceleration * position.severity / response).min(c.max_run_rate);
+            }
+            if position.run_rate < c.calm_run_rate {
+                let repaid = position.emergency_borrowing + position.penalty_borrowing;
+                row.ended_tick = Some(tick);
+                events.push(SimEvent::DepositRunEnded {
+                    tick,
+                    shock_tick: row.shock_tick,
+                    total_outflow: row.total_outflow,
+                    peak_run_rate: row.peak_run_rate,
+                    repaid,
+                });
+                position.liquid_assets = (position.liquid_assets - repaid).max(0.0);
+                position.emergency_borrowing = 0.0;
+                position.penalty_borrowing = 0.0;
+                position.run_rate = 0.0;
+            } else {
+                events.extend(self.withdraw(store, &balances, &mut position, &mut row, tick, rng)?);
+            }
+            store.update_deposit_run(&self.run_id, &row)?;
+            run = row.ended_tick.is_none().then_some(row);
+        }
+
+        position.premium_cost = position.deposits * position.rate_premium / 365.0;
+        position.liquidity_cost = (position.emergency_borrowing * c.emergency_rate
+            + position.penalty_borrowing * c.penalty_rate)
+            / 365.0;
+        if run.is_some() {
+            position.capital -= position.premium_cost + position.liquidity_cost;
+        }
+        store.insert_liquidity_position(&self.run_id, &position)?;
+        Ok(events)
+    }
+
+    /// One premium change or discount window draw.
+    fn apply(
+        &self,
+        command: &PlayerCommand,
+        position: &mut LiquidityPositionRow,
+        run: Option<&mut DepositRunRow>,
+        tick: Tick,
+    ) -> SimEvent {
+        let rejected = |command: &str, reason: String| {
+            tracing::warn!("{command} rejected: {reason}");
+            SimEvent::DepositRunCommandRejected { tick, command: command.to_string(), reason }
+        };
+        match command {
+            PlayerCommand::SetDepositRatePremium { premium } => {
+                if *premium > self.config.max_rate_premium {
+                    let reason = format!("premium above the {:.2}% ceiling", self.config.max_rate_premium * 100.0);
+                    return rejected(command.name(), reason);
+                }
+                position.rate_premium = *premium;
+                SimEvent::DepositRatePremiumSet { tick, premium: *premium }
+            }
+            PlayerCommand::DrawEmergencyLiquidity { amount } => {
+                let Some(run) = run else {
+                    return rejected(command.name(), "no deposit run under way".into());
+                };
+                let capacity = self.config.emergency_capacity * position.deposits - position.emergency_borrowing;
+                if *amount > capacity {
+                    return rejected(command.name(), format!("discount window has ${:.0} left", capacity.max(0.0)));
+                }
+                position.liquid_assets += amount;
+                position.emergency_borrowing += amount;
+                run.emergency_drawn += amount;
+                SimEvent::EmergencyLiquidityDrawn { tick, amount: *amount, outstanding: position.emergency_borrowing }
+            }
+            other => rejected(other.name(), "not a deposit run command".into()),
+        }
+    }
+
+    /// Withdraw the day's share of every balance, paying it from liquid
+    /// assets and borrowing any shortfall.
+    fn withdraw(
+        &self,
+        store: &SimStore,
+        balances: &[(String, f64)],
+        position: &mut LiquidityPositionRow,
+        run: &mut DepositRunRow,
+        tick: Tick,
+        rng: &mut SubsystemRng,
+    ) -> SimResult<Vec<SimEvent>> {
+        let c = &self.config;
+        let mut events = Vec::new();
+        let (mut outflow, mut uninsured_outflow) = (0.0, 0.0);
+        for (account_id, balance) in balances {
+            let above_limit = (balance - c.insured_limit).max(0.0);
+            let amount = if rng.chance(position.run_rate * c.insured_flight_share) {
+                *balance
+            } else {
+                above_limit * position.run_rate
+            };
+            let amount = (amount * 100.0).floor() / 100.0;
+            if amount <= 0.0 {
+                continue;
+            }
+            store.insert_transaction_with_rail(
+                &self.run_id,
+                &Uuid::new_v4().to_string(),
+                account_id,
+                tick,
+                amount,
+                "debit",
+                "deposit_withdrawal",
+                None,
+                "wire",
+                "settled",
+            )?;
+            store.update_account_balance(&self.run_id, account_id, -amount)?;
+            outflow += amount;
+            uninsured_outflow += amount.min(above_limit);
+        }
+
+        if outflow > position.liquid_assets {
+            let shortfall = outflow - position.liquid_assets;
+            position.liquid_assets = 0.0;
+            position.penalty_borrowing += shortfall;
+            run.shortfall += shortfall;
+            events.push(SimEvent::LiquidityShortfall {
+                tick,
+                shortfall,
+                penalty_borrowing: position.penalty_borrowing,
+            });
+        } else {
+            position.liquid_assets -= outflow;
+        }
+        position.deposits -= outflow;
+        position.uninsured_deposits -= uninsured_outflow;
+        position.outflow = outflow;
+        run.total_outflow += outflow;
+        run.uninsured_outflow += uninsured_outflow;
+        run.peak_run_rate = run.peak_run_rate.max(position.run_rate);
+
+        events.push(SimEvent::DepositOutflow {
+            tick,
+            outflow,
+            uninsured_outflow,
+            run_rate: position.run_rate,
+            severity: position.severity,
+            liquidity_coverage: position.coverage(),
+        });
+        Ok(events)
+    }
+}
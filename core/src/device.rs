@@ -0,0 +1,276 @@
+//! Device and IP telemetry (Phase 4.94).
+//!
+//! A share of customers bank online or in the app. Each tick some of them
+//! log in, and every one who pays digitally does; the login is from one
+//! of their devices, now and then a new one, and from their home IP
+//! address. Each customer's devices are kept in `customer_device` with
+//! the tick each was first seen and how many customers have used it, and
+//! a payment sent online or in the app carries the device it came from.
+//!
+//! Fraudsters take accounts over from a handful of devices of their own.
+//! A takeover is a login to a digital customer's account from one of
+//! them, through a proxy, followed by a transfer of most of the balance
+//! out on an instant rail. Going from victim to victim, their devices end
+//! up shared by many customers.
+//!
+//! The fraud desk reads both signals daily: a payment of
+//! `high_value_amount` or more from a device the customer first used
+//! within `new_device_ticks` (`DEVICE_NEW_HIGH_VALUE`, high when the
+//! device is also shared), and a device reaching
+//! `shared_device_threshold` customers (`DEVICE_SHARED`). Both feed the
+//! behavioural component of the monthly account fraud score.
+
+use crate::{
+    channel::{Channel, ChannelDay},
+    config::DeviceTelemetryConfig,
+    error::SimResult,
+    event::SimEvent,
+    rng::SubsystemRng,
+    store::{device::DeviceSessionRow, AccountRow, SimStore},
+    types::{RunId, Tick},
+};
+use std::collections::{BTreeMap, HashMap};
+use uuid::Uuid;
+
+/// Fraud alert type for a high-value payment from a new device.
+pub const NEW_DEVICE_HIGH_VALUE: &str = "DEVICE_NEW_HIGH_VALUE";
+/// Fraud alert type for a device used by many customers.
+pub const SHARED_DEVICE: &str = "DEVICE_SHARED";
+
+/// Alert scores: a new device alone, and added when it is also shared.
+const NEW_DEVICE_SCORE: f64 = 0.60;
+const SHARED_DEVICE_SCORE: f64 = 0.30;
+/// Monthly account score: the behavioural component for a shared
+/// device, and for a high-value payment from a new one in the month.
+const SHARED_COMPONENT: f64 = 0.15;
+const NEW_DEVICE_COMPONENT: f64 = 0.15;
+
+pub struct Devices {
+    run_id: RunId,
+    config: DeviceTelemetryConfig,
+}
+
+impl Devices {
+    pub fn new(run_id: RunId, config: DeviceTelemetryConfig) -> Self {
+        Self { run_id, config }
+    }
+
+    pub fn enabled(&self) -> bool {
+        self.config.enabled
+    }
+
+    /// Whether a customer banks online or in the app.
+    pub fn digital(&self, customer_id: &str) -> bool {
+        (hash(customer_id) % 1000) as f64 / 1000.0 < self.config.digital_rate
+    }
+
+    /// Log today's logins, stamp each digital payment with the device it
+    /// came from, and let a fraudster take an account over.
+    pub fn log_sessions(
+        &self,
+        store: &SimStore,
+        accounts: &[AccountRow],
+        channels: Option<&mut ChannelDay>,
+        tick: Tick,
+        rng: &mut SubsystemRng,
+    ) -> SimResult<Vec<SimEvent>> {
+        let c = &self.config;
+        let mut payments: HashMap<String, Vec<String>> = HashMap::new();
+        for (txn_id, customer_id) in store.digital_payments(&self.run_id, tick)? {
+            payments.entry(customer_id).or_default().push(txn_id);
+        }
+        let mut devices: HashMap<String, Vec<String>> = HashMap::new();
+        for row in store.customer_devices(&self.run_id)? {
+            if !is_farm(&row.device_id) {
+                devices.entry(row.customer_id).or_default().push(row.device_id);
+            }
+        }
+        let mut customers: BTreeMap<&str, &AccountRow> = BTreeMap::new();
+        for acct in accounts {
+            if self.digital(&acct.customer_id) || payments.contains_key(&acct.customer_id) {
+                customers.entry(acct.customer_id.as_str()).or_insert(acct);
+            }
+        }
+
+        let (mut sessions, mut new_devices) = (0, 0);
+        for &customer_id in customers.keys() {
+            let paid = payments.get(customer_id);
+            if paid.is_none() && !rng.chance(c.login_rate) {
+                continue;
+            }
+            let own = devices.get(customer_id).map(Vec::as_slice).unwrap_or_default();
+            let new_device = own.is_empty() || rng.chance(c.new_device_rate);
+            let device_id = if new_device {
+                let device_id = format!("dev-{:012x}", rng.next_u64() >> 16);
+                let fingerprint = format!("fp-{:016x}", rng.next_u64());
+                store.register_customer_device(&self.run_id, customer_id, &device_id, &fingerprint, tick)?;
+                new_devices += 1;
+                device_id
+            } else {
+                let device_id = own[rng.next_u64_below(own.len() as u64) as usize].clone();
+                store.touch_customer_device(&self.run_id, customer_id, &device_id, tick)?;
+                device_id
+            };
+            store.insert_device_session(&self.run_id, &DeviceSessionRow {
+                session_id: format!("sess-{tick}-{sessions:05}"),
+                tick,
+                customer_id: customer_id.to_string(),
+                device_id: device_id.clone(),
+                ip_address: home_ip(customer_id),
+                new_device,
+                takeover: false,
+            })?;
+            for txn_id in paid.into_iter().flatten() {
+                store.set_transaction_device(&self.run_id, txn_id, &device_id)?;
+            }
+            sessions += 1;
+        }
+
+        let mut events = Vec::new();
+        if c.farm_devices > 0 && rng.chance(c.takeover_rate) {
+            let victims: Vec<&AccountRow> = customers
+                .values()
+                .copied()
+                .filter(|a| self.digital(&a.customer_id) && a.balance * c.takeover_share >= c.high_value_amount)
+                .collect();
+            if !victims.is_empty() {
+                let acct = victims[rng.next_u64_below(victims.len() as u64) as usize];
+                let farm = rng.next_u64_below(c.farm_devices as u64) + 1;
+                let device_id = format!("dev-farm-{farm:02}");
+                let first_login = !store
+                    .customer_devices(&self.run_id)?
+                    .iter()
+                    .any(|d| d.customer_id == acct.customer_id && d.device_id == device_id);
+                if first_login {
+                    store.register_customer_device(&self.run_id, &acct.customer_id, &device_id, &format!("fp-farm-{farm:02}"), tick)?;
+                    new_devices += 1;
+                } else {
+                    store.touch_customer_device(&self.run_id, &acct.customer_id, &device_id, tick)?;
+                }
+                store.insert_device_session(&self.run_id, &DeviceSessionRow {
+                    session_id: format!("sess-{tick}-{sessions:05}"),
+                    tick,
+                    customer_id: acct.customer_id.clone(),
+                    device_id: device_id.clone(),
+                    ip_address: format!("203.0.113.{farm}"),
+                    new_device: first_login,
+                    takeover: true,
+                })?;
+                sessions += 1;
+
+                let txn_id = Uuid::new_v4().to_string();
+                let amount = (acct.balance * c.takeover_share * 100.0).floor() / 100.0;
+                let payee = format!("ext-{:08x}", rng.next_u64() as u32);
+                store.insert_transaction_with_rail(
+                    &self.run_id,
+                    &txn_id,
+                    &acct.account_id,
+                    tick,
+                    amount,
+                    "debit",
+                    "external_transfer_out",
+                    Some(&payee),
+                    "RTP",
+                    "settled",
+                )?;
+                store.set_transaction_channel(&self.run_id, &txn_id, Channel::Online.as_str())?;
+                store.set_transaction_device(&self.run_id, &txn_id, &device_id)?;
+                store.update_account_balance(&self.run_id, &acct.account_id, -amount)?;
+                if let Some(day) = channels {
+                    day.complete(Channel::Online);
+                }
+                tracing::warn!("account takeover: {} from {device_id}, ${amount:.2} out", acct.account_id);
+                events.push(SimEvent::AccountTakeover {
+                    tick,
+                    customer_id: acct.customer_id.clone(),
+                    account_id: acct.account_id.clone(),
+                    device_id,
+                    txn_id,
+                    amount,
+                });
+            }
+        }
+
+        if sessions > 0 {
+            events.insert(0, SimEvent::DeviceSessionsLogged { tick, sessions, new_devices });
+        }
+        Ok(events)
+    }
+
+    /// Alert today's high-value payments from new devices, and devices
+    /// that reached the sharing threshold today.
+    pub fn detect(&self, store: &SimStore, tick: Tick) -> SimResult<Vec<SimEvent>> {
+        let c = &self.config;
+        let mut events = Vec::new();
+        for payment in store.device_payments(&self.run_id, tick, tick, c.high_value_amount)? {
+            if payment.first_seen_tick + c.new_device_ticks < tick {
+                continue;
+            }
+            let shared = payment.shared_count >= c.shared_device_threshold;
+            let (score, severity) =
+                if shared { (NEW_DEVICE_SCORE + SHARED_DEVICE_SCORE, "high") } else { (NEW_DEVICE_SCORE, "medium") };
+            let alert_id = format!("fraud-device-{}", payment.txn_id);
+            store.insert_fraud_alert(&self.run_id, &alert_id, tick as i64, NEW_DEVICE_HIGH_VALUE, "account", &payment.account_id, score, severity)?;
+            events.push(SimEvent::FraudAlertGenerated {
+                tick,
+                alert_id,
+                alert_type: NEW_DEVICE_HIGH_VALUE.to_string(),
+                entity_id: payment.account_id,
+                fraud_score: score,
+                severity: severity.to_string(),
+            });
+        }
+        for (device_id, customers) in store.devices_shared_on(&self.run_id, tick, c.shared_device_threshold)? {
+            let alert_id = format!("fraud-devshare-{device_id}-{tick}");
+            tracing::info!("{device_id} now used by {customers} customers");
+            store.insert_fraud_alert(&self.run_id, &alert_id, tick as i64, SHARED_DEVICE, "device", &device_id, 0.70, "high")?;
+            events.push(SimEvent::FraudAlertGenerated {
+                tick,
+                alert_id,
+                alert_type: SHARED_DEVICE.to_string(),
+                entity_id: device_id,
+                fraud_score: 0.70,
+                severity: "high".to_string(),
+            });
+        }
+        Ok(events)
+    }
+
+    /// Each customer's behavioural component of the account fraud score
+    /// at `tick`: for a shared device, and for a high-value payment from
+    /// a new device over the last 30 ticks. Customers with neither are
+    /// left out.
+    pub fn score_components(&self, store: &SimStore, tick: Tick) -> SimResult<HashMap<String, f64>> {
+        let c = &self.config;
+        let mut components: HashMap<String, f64> = HashMap::new();
+        for row in store.customer_devices(&self.run_id)? {
+            if row.shared_count >= c.shared_device_threshold {
+                components.insert(row.customer_id, SHARED_COMPONENT);
+            }
+        }
+        let mut flagged: Vec<String> = Vec::new();
+        for payment in store.device_payments(&self.run_id, tick.saturating_sub(29), tick, c.high_value_amount)? {
+            if payment.tick <= payment.first_seen_tick + c.new_device_ticks && !flagged.contains(&payment.customer_id) {
+                flagged.push(payment.customer_id);
+            }
+        }
+        for customer_id in flagged {
+            *components.entry(customer_id).or_default() += NEW_DEVICE_COMPONENT;
+        }
+        Ok(components)
+    }
+}
+
+fn is_farm(device_id: &str) -> bool {
+    device_id.starts_with("dev-farm-")
+}
+
+fn hash(s: &str) -> u64 {
+    s.bytes().fold(0u64, |h, b| h.wrapping_mul(31).wrapping_add(b as u64))
+}
+
+/// The customer's home broadband address.
+fn home_ip(customer_id: &str) -> String {
+    let h = hash(customer_id).wrapping_mul(0x9e37_79b9_7f4a_7c15);
+    format!("10.{}.{}.{}", (h >> 40) as u8, (h >> 48) as u8, ((h >> 56) as u8).max(1))
+}
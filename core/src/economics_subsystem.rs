@@ -7,15 +7,20 @@
 //! Execution: runs every 90 ticks (quarterly).
 //! Depends on: daily_aggregate (from transaction subsystem),
 //!             complaint_aggregate (from complaint subsystem),
-//!             macro_state (for interest rates).
+//!             macro_rate_history (for interest rates).
 
 use crate::{
+    command::PlayerCommand,
     config::SimConfig,
-    error::SimResult,
+    deposit_run::DepositRun,
+    error::{SimError, SimResult},
     event::SimEvent,
+    operational_risk::OperationalRisk,
+    opex_allocation::OpexAllocation,
+    quarter_close::QuarterClose,
     rng::SubsystemRng,
     store::SimStore,
-    subsystem::SimSubsystem,
+    subsystem::{SimSubsystem, SubsystemManifest},
     types::{RunId, Tick},
 };
 use serde::{Deserialize, Serialize};
@@ -23,7 +28,7 @@ use std::collections::HashMap;
 
 pub const ECONOMICS_UPDATE_INTERVAL: Tick = 90; // quarterly
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
 pub struct PnLSnapshot {
     pub tick: Tick,
     pub period: String,
@@ -41,6 +46,19 @@ pub struct PnLSnapshot {
     // KPIs
     pub nim: f64,
     pub efficiency_ratio: f64,
+    // Operational risk (Phase 4.61): losses on record for the quarter,
+    // some of them (check fraud, penalties) also among the costs above,
+    // and the capital estimated against them; both 0 while disabled
+    pub operational_loss: f64,
+    pub op_risk_capital: f64,
+    // Quarter close (Phase 4.62): net accruals and late adjustments the
+    // close booked, already in pre_tax_profit; 0 while disabled
+    pub accrual_adjustment: f64,
+    // APP fraud (Phase 4.91): scam claims reimbursed, a cost taken from
+    // pre_tax_profit, and what victims of the quarter's declined or
+    // partly paid claims bore themselves; both 0 while disabled
+    pub app_fraud_loss: f64,
+    pub app_customer_loss: f64,
     // Context
     pub avg_deposits: f64,
     pub avg_loans: f64,
@@ -73,6 +91,11 @@ pub struct SegmentPnL {
     pub avg_cost_per_customer: f64,
     pub below_target_margin: bool,
     pub cross_subsidy_recipient: bool,
+    // Hardship program (Phase 4.30). Waived fees are revenue given up,
+    // already missing from fee_income rather than counted as a cost.
+    pub hardship_enrollments: i64,
+    pub hardship_active: i64,
+    pub hardship_fees_waived: f64,
 }
 
 impl SegmentPnL {
@@ -101,6 +124,9 @@ impl SegmentPnL {
             avg_cost_per_customer: 0.0,
             below_target_margin: false,
             cross_subsidy_recipient: false,
+            hardship_enrollments: 0,
+            hardship_active: 0,
+            hardship_fees_waived: 0.0,
         }
     }
 }
@@ -117,16 +143,73 @@ pub struct EconomicsSubsystem {
     config: SimConfig,
     store: SimStore,
     quarter_number: u32,
+    /// Phase 4.52: liquidity position and deposit runs.
+    deposit_run: DepositRun,
+    /// Phase 4.61: loss event record and operational risk capital.
+    operational_risk: OperationalRisk,
+    /// Phase 4.62: quarter-end close.
+    quarter_close: QuarterClose,
+    /// Phase 4.63: opex allocation rules.
+    opex_allocation: OpexAllocation,
 }
 
 impl EconomicsSubsystem {
     pub fn new(run_id: RunId, config: SimConfig, store: SimStore) -> Self {
+        let deposit_run = DepositRun::new(run_id.clone(), config.deposit_run.clone());
+        let operational_risk = OperationalRisk::new(
+            run_id.clone(),
+            config.operational_risk.clone(),
+            config.fair_lending.remediation_cost,
+        );
+        let quarter_close = QuarterClose::new(
+            run_id.clone(),
+            config.quarter_close.clone(),
+            config.payment_hub.interchange_fee_rate,
+        );
+        let opex_allocation = OpexAllocation::new(
+            run_id.clone(),
+            config.opex_allocation.clone(),
+            config.population_scale,
+        );
         Self {
             run_id,
             config,
             store,
             quarter_number: 0,
+            deposit_run,
+            operational_risk,
+            quarter_close,
+            opex_allocation,
+        }
+    }
+
+    /// Phase 4.83: yield on the assets deposits fund, per dollar of
+    /// deposits. The floating book earns the quarter's base rate; the
+    /// fixed book the long rates of the window it has repriced over.
+    fn asset_yield(&self, avg_rate: f64, tick: Tick) -> SimResult<f64> {
+        let rates = &self.config.rate_environment;
+        let repricing_start = (tick + 1).saturating_sub(rates.asset_repricing_ticks.max(1));
+        let fixed_yield = self.store.avg_macro_long_rate(&self.run_id, repricing_start, tick)?;
+        Ok(rates.earning_asset_share
+            * (rates.fixed_asset_share * fixed_yield + (1.0 - rates.fixed_asset_share) * avg_rate))
+    }
+
+    /// Phase 4.83: rate paid on deposits, weighted by balance. Each
+    /// product's posted rate moves by its beta times the base rate's move
+    /// since the run started, never below zero.
+    fn deposit_cost_rate(&self, avg_rate: f64, deposits: f64) -> SimResult<f64> {
+        if deposits <= 0.0 {
+            return Ok(0.0);
+        }
+        let rates = &self.config.rate_environment;
+        let rate_move = avg_rate - self.config.macro_regime.base_rate;
+        let mut cost = 0.0;
+        for (product_id, balance) in self.store.account_balances_by_product(&self.run_id)? {
+            let posted = self.config.products.get(&product_id).map_or(0.0, |p| p.interest_rate);
+            let beta = rates.deposit_betas.get(&product_id).copied().unwrap_or(rates.default_deposit_beta);
+            cost += balance * (posted + beta * rate_move).max(0.0);
         }
+        Ok(cost / deposits)
     }
 
     fn compute_pnl(&mut self, tick: Tick) -> SimResult<PnLSnapshot> {
@@ -152,27 +235,62 @@ impl EconomicsSubsystem {
             .store
             .avg_macro_base_rate(&self.run_id, quarter_start, quarter_end)?;
 
-        // Simplified NII: deposits earn 0.5× base rate as spread
-        let deposit_rate = avg_rate * 0.5;
-        let nii = avg_deposits * deposit_rate * (90.0 / 365.0);
+        // Deposit rate premium paid to hold depositors, and interest on
+        // emergency borrowing, during deposit runs (Phase 4.52)
+        let (rate_premium_cost, liquidity_cost) =
+            self.store
+                .deposit_run_costs_between(&self.run_id, quarter_start, quarter_end)?;
+
+        // Simplified NII: deposits earn 0.5× base rate as spread, less
+        // any premium paid over the usual deposit rate. With the rate
+        // environment on (Phase 4.83), the spread is what the assets
+        // deposits fund yield over what each product pays.
+        let spread = if self.config.rate_environment.enabled {
+            self.asset_yield(avg_rate, quarter_end)? - self.deposit_cost_rate(avg_rate, avg_deposits)?
+        } else {
+            avg_rate * 0.5
+        };
+        let nii = avg_deposits * spread * (90.0 / 365.0) - rate_premium_cost;
 
-        // Fee Income from daily_aggregate
+        // Fee Income from daily_aggregate, plus clearing fees charged to
+        // respondent banks (Phase 4.39)
         let fee_income = self
             .store
-            .sum_fee_income(&self.run_id, quarter_start, quarter_end)?;
+            .sum_fee_income(&self.run_id, quarter_start, quarter_end)?
+            + self
+                .store
+                .correspondent_fees_between(&self.run_id, quarter_start, quarter_end)?;
+
+        // Phase 4.82: interest received on loans
+        let nii = nii + self.store.loan_interest_between(&self.run_id, quarter_start, quarter_end)?;
 
         let gross_income = nii + fee_income;
 
         // ── Costs ──────────────────────────────────────────────
 
-        let credit_loss = 0.0; // Phase 2+
-        let fraud_loss = 0.0; // Phase 3
+        // Phase 4.34: defaulted credit lines; Phase 4.82: loan charge-offs
+        let credit_loss = self
+            .store
+            .sum_credit_losses(&self.run_id, quarter_start, quarter_end)?
+            + self
+                .store
+                .loan_charge_offs_between(&self.run_id, quarter_start, quarter_end)?;
+        // Phase 4.54: check fraud the bank could not pass on
+        let fraud_loss = self
+            .store
+            .check_fraud_losses_between(&self.run_id, quarter_start, quarter_end)?;
+        // Phase 4.91: APP scam claims the bank reimbursed
+        let (app_fraud_loss, app_customer_loss) =
+            self.store
+                .app_fraud_losses_between(&self.run_id, quarter_start, quarter_end)?;
 
         // Operating expenses
         let staff_count = 20;
         let loaded_cost = 85000.0;
         let overhead_multiplier = 1.8;
-        let quarterly_staff_cost = (staff_count as f64 * loaded_cost * overhead_multiplier) / 4.0;
+        // A sampled run carries only its share of the head-office team
+        let quarterly_staff_cost = (staff_count as f64 * loaded_cost * overhead_multiplier) / 4.0
+            * self.config.population_scale;
 
         // Complaint handling cost: $50 per complaint
         let complaint_count =
@@ -185,11 +303,108 @@ impl EconomicsSubsystem {
             self.store
                 .sum_offer_bonuses_paid(&self.run_id, quarter_start, quarter_end)?;
 
-        let opex = quarterly_staff_cost + complaint_cost + offer_bonus_cost;
+        // Penalty cost of covering settlement account shortfalls
+        let settlement_borrowing_cost = self.store.settlement_borrowing_cost_between(
+            &self.run_id,
+            quarter_start,
+            quarter_end,
+        )?;
+
+        // FCRA late-correction penalties on bureau disputes
+        let bureau_penalty_cost =
+            self.store
+                .bureau_penalty_between(&self.run_id, quarter_start, quarter_end)?;
+
+        // Branch occupancy and staffing from the monthly branch reports
+        let branch_operating_cost =
+            self.store
+                .branch_operating_cost_between(&self.run_id, quarter_start, quarter_end)?;
+
+        // Fair lending findings remediated this quarter
+        let remediation_cost = self
+            .store
+            .fair_lending_remediations_between(&self.run_id, quarter_start, quarter_end)?
+            as f64
+            * self.config.fair_lending.remediation_cost;
+
+        // Network fines from ODFI return-rate reviews
+        let odfi_fine_cost =
+            self.store
+                .odfi_fines_between(&self.run_id, quarter_start, quarter_end)?;
+
+        // Periodic and EDD customer reviews completed this quarter
+        let edd_review_cost =
+            self.store
+                .edd_review_cost_between(&self.run_id, quarter_start, quarter_end)?;
+
+        // Identity verification vendor fees for applicants checked this quarter
+        let idv_cost =
+            self.store
+                .idv_cost_between(&self.run_id, quarter_start, quarter_end)?;
+
+        // SAR investigation and filing costs this quarter
+        let sar_filing_cost =
+            self.store
+                .sar_filing_cost_between(&self.run_id, quarter_start, quarter_end)?;
+
+        // Monitoring rule tuning reviews approved this quarter
+        let tuning_review_cost =
+            self.store
+                .tuning_review_cost_between(&self.run_id, quarter_start, quarter_end)?;
+
+        // Alert QA reviews and retraining completed this quarter
+        let alert_qa_cost =
+            self.store
+                .alert_qa_cost_between(&self.run_id, quarter_start, quarter_end)?;
+
+        // Visa documentation refreshes worked this quarter
+        let visa_refresh_cost =
+            self.store
+                .visa_refresh_cost_between(&self.run_id, quarter_start, quarter_end)?;
+
+        // Correspondent due diligence refreshes completed this quarter
+        let correspondent_dd_cost =
+            self.store
+                .correspondent_dd_cost_between(&self.run_id, quarter_start, quarter_end)?;
+
+        // Case workers on the work queues, overtime included
+        let queue_staffing_cost =
+            self.store
+                .staffing_cost_between(&self.run_id, quarter_start, quarter_end)?;
+
+        // Consent order penalties and mandated lookbacks issued this quarter
+        let enforcement_cost =
+            self.store
+                .enforcement_cost_between(&self.run_id, quarter_start, quarter_end)?;
+
+        // Phase 4.90: cost to serve transactions through each channel
+        let channel_cost =
+            self.store
+                .channel_cost_between(&self.run_id, quarter_start, quarter_end)?;
+
+        let opex = quarterly_staff_cost
+            + complaint_cost
+            + offer_bonus_cost
+            + settlement_borrowing_cost
+            + bureau_penalty_cost
+            + branch_operating_cost
+            + remediation_cost
+            + odfi_fine_cost
+            + edd_review_cost
+            + idv_cost
+            + sar_filing_cost
+            + tuning_review_cost
+            + alert_qa_cost
+            + visa_refresh_cost
+            + correspondent_dd_cost
+            + queue_staffing_cost
+            + enforcement_cost
+            + channel_cost
+            + liquidity_cost;
 
         // ── Bottom Line ────────────────────────────────────────
 
-        let pre_tax_profit = gross_income - credit_loss - fraud_loss - opex;
+        let pre_tax_profit = gross_income - credit_loss - fraud_loss - app_fraud_loss - opex;
 
         // ── KPIs ───────────────────────────────────────────────
 
@@ -207,6 +422,15 @@ impl EconomicsSubsystem {
             0.0
         };
 
+        // Phase 4.61: losses booked at the closing tick are recorded next
+        // tick, so the quarter's run one tick behind it
+        let operational_loss = if self.operational_risk.enabled() {
+            self.store
+                .loss_total_between(&self.run_id, tick - 90, tick - 1)?
+        } else {
+            0.0
+        };
+
         // ── Context ────────────────────────────────────────────
 
         let customer_count = self.store.customer_count(&self.run_id, "active")?;
@@ -225,8 +449,14 @@ impl EconomicsSubsystem {
             pre_tax_profit,
             nim,
             efficiency_ratio,
+            operational_loss,
+            op_risk_capital: 0.0, // estimated once the quarter's P&L is known
+            accrual_adjustment: 0.0, // booked by the quarter close
+            app_fraud_loss,
+            app_customer_loss,
             avg_deposits,
-            avg_loans: 0.0, // Phase 2+
+            // Principal outstanding at quarter end (Phase 4.82)
+            avg_loans: self.store.loan_balance_outstanding(&self.run_id)?,
             customer_count,
             active_accounts,
         })
@@ -243,6 +473,21 @@ impl EconomicsSubsystem {
 
         let mut seg_keys: Vec<&String> = self.config.segments.keys().collect();
         seg_keys.sort();
+
+        // Phase 4.63: shared opex split pool by pool over the segments
+        // that have customers to carry it
+        let allocated = if self.opex_allocation.enabled() {
+            let mut carrying = Vec::new();
+            for segment in &seg_keys {
+                if self.store.segment_customer_count(&self.run_id, segment, "active")? > 0 {
+                    carrying.push(segment.as_str());
+                }
+            }
+            Some(self.opex_allocation.allocate(&self.store, tick, quarter_start, quarter_end, &carrying)?)
+        } else {
+            None
+        };
+
         for segment_name in seg_keys {
             let pnl = self.compute_single_segment_pnl(
                 segment_name,
@@ -250,6 +495,7 @@ impl EconomicsSubsystem {
                 quarter_start,
                 quarter_end,
                 total_nii,
+                allocated.as_ref().map(|a| a.get(segment_name).copied().unwrap_or(0.0)),
             )?;
             result.insert(segment_name.clone(), pnl);
         }
@@ -264,6 +510,7 @@ impl EconomicsSubsystem {
         quarter_start: Tick,
         quarter_end: Tick,
         total_nii: f64,
+        allocated_opex: Option<f64>,
     ) -> SimResult<SegmentPnL> {
         let econ = &self.config.segment_economics;
 
@@ -291,10 +538,13 @@ impl EconomicsSubsystem {
         };
         let nii = total_nii * balance_share;
 
+        // Quarter totals folded tick by tick; see store::segment_activity
+        let activity = self
+            .store
+            .segment_activity(&self.run_id, segment, quarter_start, quarter_end)?;
+
         // Fee income: direct attribution
-        let fee_income =
-            self.store
-                .segment_fee_income(&self.run_id, segment, quarter_start, quarter_end)?;
+        let fee_income = activity.fee_income;
 
         // Interchange income
         let avg_swipes = econ
@@ -319,10 +569,7 @@ impl EconomicsSubsystem {
             .copied()
             .unwrap_or(85.0);
 
-        let new_customers =
-            self.store
-                .segment_new_customers(&self.run_id, segment, quarter_start, quarter_end)?;
-        let acquisition_cost = new_customers as f64 * acq_cost_per;
+        let acquisition_cost = activity.new_customers as f64 * acq_cost_per;
 
         let svc_cost_per = econ
             .cost_allocation_model
@@ -332,9 +579,11 @@ impl EconomicsSubsystem {
             .unwrap_or(4.50);
         let servicing_cost = active_customers as f64 * svc_cost_per * 3.0;
 
-        let complaints =
-            self.store
-                .segment_complaints(&self.run_id, segment, quarter_start, quarter_end)?;
+        let complaints = SegmentComplaints {
+            standard: activity.complaints_standard,
+            high: activity.complaints_high,
+            urgent: activity.complaints_urgent,
+        };
         let complaint_cost = self.compute_segment_complaint_cost(
             &complaints,
             &econ
@@ -342,26 +591,21 @@ impl EconomicsSubsystem {
                 .complaint_handling_cost_per_complaint,
         );
 
-        let retention_cost = self.store.segment_retention_offer_cost(
-            &self.run_id,
-            segment,
-            quarter_start,
-            quarter_end,
-        )?;
+        let retention_cost = activity.retention_cost;
 
-        let churned = self.store.segment_churned_customers(
-            &self.run_id,
-            segment,
-            quarter_start,
-            quarter_end,
-        )?;
-        let churn_replacement_cost = churned as f64
+        let churn_replacement_cost = activity.churned_customers as f64
             * acq_cost_per
             * econ.cost_allocation_model.churn_replacement_cost_multiplier;
 
-        let total_active = self.store.total_active_customers(&self.run_id)?.max(1);
-        let customer_share = active_customers as f64 / total_active as f64;
-        let allocated_opex = 45_000.0 * customer_share;
+        // Without allocation rules, a flat charge by customer share
+        let allocated_opex = match allocated_opex {
+            Some(amount) => amount,
+            None => {
+                let total_active = self.store.total_active_customers(&self.run_id)?.max(1);
+                let customer_share = active_customers as f64 / total_active as f64;
+                45_000.0 * customer_share * self.config.population_scale
+            }
+        };
 
         let total_cost = acquisition_cost
             + servicing_cost
@@ -415,6 +659,9 @@ impl EconomicsSubsystem {
             avg_cost_per_customer,
             below_target_margin,
             cross_subsidy_recipient: false, // set by analyze_cross_subsidies
+            hardship_enrollments: activity.hardship_enrollments,
+            hardship_active: self.store.segment_hardship_active(&self.run_id, segment, tick)?,
+            hardship_fees_waived: activity.hardship_fees_waived,
         })
     }
 
@@ -481,28 +728,138 @@ impl EconomicsSubsystem {
     }
 }
 
+const MANIFEST: SubsystemManifest = SubsystemManifest {
+    version: 8,
+    produces: &[
+        "quarterly_pnl_computed",
+        // Phase 4.52
+        "deposit_run_started", "deposit_outflow", "liquidity_shortfall", "deposit_run_ended",
+        "deposit_rate_premium_set", "emergency_liquidity_drawn", "deposit_run_command_rejected",
+        // Phase 4.61
+        "op_risk_capital_estimated", "operational_loss_recorded",
+        // Phase 4.62
+        "close_adjustment_posted", "close_item_signed_off", "quarter_close_command_rejected",
+        "quarter_close_opened", "quarter_close_overdue", "quarter_closed",
+    ],
+    consumes: &["player_command_received"],
+    tables: &[
+        "app_scam_payment", "authorization", "card_dispute", "channel_activity", "check_item",
+        "close_adjustment", "close_checklist_item", "cross_subsidy_analysis", "customer_offer", "deposit_run", "liquidity_position", "loss_event",
+        "op_risk_capital", "opex_allocation", "opex_allocation_rule", "pnl_snapshot", "quarter_close",
+        "recon_exception", "segment_activity", "segment_pnl", "transactions",
+    ],
+    requires: &[],
+};
+
 impl SimSubsystem for EconomicsSubsystem {
     fn name(&self) -> &'static str {
         "economics"
     }
 
+    fn manifest(&self) -> SubsystemManifest {
+        MANIFEST
+    }
+
     fn update(
         &mut self,
         tick: Tick,
-        _events_in: &[SimEvent],
-        _rng: &mut SubsystemRng,
+        events_in: &[SimEvent],
+        rng: &mut SubsystemRng,
     ) -> SimResult<Vec<SimEvent>> {
+        // The previous tick is complete; fold it so period totals never rescan it
+        if tick > 0 {
+            let mut segments: Vec<&str> = self.config.segments.keys().map(String::as_str).collect();
+            segments.sort();
+            self.store.fold_segment_activity(&self.run_id, tick - 1, &segments)?;
+        }
+
+        let mut events = Vec::new();
+
+        // Phase 4.61: losses booked since the last look
+        if self.operational_risk.enabled() {
+            events.extend(self.operational_risk.record_losses(&self.store, tick)?);
+        }
+
+        let mut deposit_run_commands = Vec::new();
+        let mut close_commands = Vec::new();
+        if self.deposit_run.enabled() || self.quarter_close.enabled() {
+            for event in events_in {
+                let SimEvent::PlayerCommandReceived { command_id, .. } = event else {
+                    continue;
+                };
+                match self.store.get_player_command(&self.run_id, command_id)? {
+                    Some(
+                        command @ (PlayerCommand::SetDepositRatePremium { .. }
+                        | PlayerCommand::DrawEmergencyLiquidity { .. }),
+                    ) => deposit_run_commands.push(command),
+                    Some(
+                        command @ (PlayerCommand::SignOffCloseItem { .. }
+                        | PlayerCommand::PostCloseAdjustment { .. }),
+                    ) => close_commands.push(command),
+                    _ => {}
+                }
+            }
+        }
+
+        // Phase 4.52: premium changes and discount window draws, then the
+        // day's liquidity position and any run on deposits
+        if self.deposit_run.enabled() {
+            events.extend(self.deposit_run.process(&self.store, tick, &deposit_run_commands, rng)?);
+        }
+
+        // Phase 4.62: sign-offs and adjustments to open closes
+        if self.quarter_close.enabled() {
+            events.extend(self.quarter_close.process(&self.store, tick, &close_commands)?);
+        }
+
         if !tick.is_multiple_of(ECONOMICS_UPDATE_INTERVAL) || tick == 0 {
-            return Ok(vec![]);
+            return Ok(events);
         }
 
         let quarter_start = tick.saturating_sub(89);
         let quarter_end = tick;
 
-        let pnl = self.compute_pnl(tick)?;
+        let mut pnl = self.compute_pnl(tick)?;
+        // Phase 4.61: capital against operational risk, from the last
+        // year of gross income including this quarter's
+        if self.operational_risk.enabled() {
+            let mut gross_incomes: Vec<f64> = self
+                .store
+                .all_pnl_snapshots(&self.run_id)?
+                .iter()
+                .map(|p| p.gross_income)
+                .collect();
+            gross_incomes.push(pnl.gross_income);
+            let capital = self.operational_risk.estimate_capital(&self.store, tick, &gross_incomes)?;
+            self.store.insert_op_risk_capital(&self.run_id, &capital)?;
+            pnl.op_risk_capital = capital.capital;
+            events.push(SimEvent::OpRiskCapitalEstimated {
+                tick,
+                business_indicator: capital.business_indicator,
+                annual_loss: capital.annual_loss,
+                ilm: capital.ilm,
+                capital: capital.capital,
+            });
+        }
+        // Phase 4.62: the close books its accruals into the quarter's
+        // profit and opens the sign-off checklist
+        if self.quarter_close.enabled() {
+            events.extend(self.quarter_close.open(&self.store, tick, &mut pnl)?);
+        }
+        // Every ratio is guarded, so a NaN or infinity here means a
+        // corrupt input upstream; persisting it would poison every KPI after
+        if ![pnl.nii, pnl.fee_income, pnl.opex, pnl.pre_tax_profit, pnl.nim, pnl.efficiency_ratio]
+            .iter()
+            .all(|v| v.is_finite())
+        {
+            return Err(SimError::InvariantViolation {
+                tick,
+                invariant: format!("{} P&L figures must be finite", pnl.period),
+            });
+        }
         self.store.insert_pnl_snapshot(&self.run_id, &pnl)?;
 
-        log::info!(
+        tracing::info!(
             "{}: NII=${:.0} Fees=${:.0} OPEX=${:.0} Profit=${:.0} NIM={:.2}% Eff={:.1}%",
             pnl.period,
             pnl.nii,
@@ -522,7 +879,7 @@ impl SimSubsystem for EconomicsSubsystem {
             self.store.insert_segment_pnl(&self.run_id, seg_pnl)?;
 
             if seg_pnl.below_target_margin {
-                log::warn!(
+                tracing::warn!(
                     "{} {}: margin {:.1}% below target",
                     pnl.period,
                     segment,
@@ -531,14 +888,30 @@ impl SimSubsystem for EconomicsSubsystem {
             }
         }
 
-        Ok(vec![SimEvent::QuarterlyPnLComputed {
+        events.push(SimEvent::QuarterlyPnLComputed {
             tick,
             period: pnl.period.clone(),
             gross_income: pnl.gross_income,
             pre_tax_profit: pnl.pre_tax_profit,
             nim: pnl.nim,
             efficiency_ratio: pnl.efficiency_ratio,
-        }])
+        });
+        Ok(events)
+    }
+
+    fn save_state(&self) -> SimResult<Option<serde_json::Value>> {
+        Ok(Some(serde_json::json!({ "quarter_number": self.quarter_number })))
+    }
+
+    fn load_state(&mut self, state: serde_json::Value) -> SimResult<()> {
+        self.quarter_number = serde_json::from_value(state["quarter_number"].clone())?;
+        Ok(())
+    }
+
+    fn replay(&mut self, _tick: Tick, events: &[SimEvent]) -> SimResult<()> {
+        let closed = events.iter().filter(|e| matches!(e, SimEvent::QuarterlyPnLComputed { .. }));
+        self.quarter_number += closed.count() as u32;
+        Ok(())
     }
 
     fn as_any(&self) -> &dyn std::any::Any {
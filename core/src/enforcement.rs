@@ -0,0 +1,298 @@
+//! Regulatory enforcement (Phase 4.81).
+//!
+//! Gives exam findings consequences beyond the fine. An exam also tests
+//! the cycle itself: a sample of the complaints closed and SARs filed,
+//! the fee change history, and how far the UDAAP risk score has risen
+//! since the last exam, per 1,000 active customers. A rise past
+//! `udaap_exam_rise_per_1k` brings the CFPB in early for a targeted exam.
+//!
+//! When an exam closes, each major finding becomes a matter requiring
+//! attention (MRA) and each critical one a consent order carrying the
+//! finding's fine as its penalty. A major or critical finding in the
+//! category of an action still open escalates it to a consent order,
+//! with the penalty multiplied if it was already one. A consent order
+//! imposes a constraint until lifted:
+//!   - fee_cap: UDAAP findings in a cycle that raised fees; Pricing holds
+//!     every fee at its soft limit.
+//!   - complaint_lookback / sar_lookback: complaint and SAR findings; the
+//!     complaints opened, or alerts raised, over `lookback_ticks` are
+//!     re-reviewed over `lookback_review_ticks`, costed per item.
+//!
+//! An exam that no longer finds the category lifts the action once its
+//! remediation period has run and its lookback is done. Penalties and
+//! lookback costs are booked into opex in the quarter issued.
+
+use crate::{
+    config::EnforcementConfig,
+    error::SimResult,
+    event::SimEvent,
+    rng::SubsystemRng,
+    store::{enforcement::EnforcementActionRow, SimStore},
+    types::{RunId, Tick},
+};
+
+/// A finding the exam's own testing turned up, before it is fined.
+pub struct TestedFinding {
+    pub category:    &'static str,
+    pub severity:    &'static str,
+    pub description: &'static str,
+}
+
+/// A finding as recorded against an exam.
+pub struct RecordedFinding<'a> {
+    pub finding_id:  &'a str,
+    pub category:    &'a str,
+    pub severity:    &'a str,
+    pub fine_amount: f64,
+}
+
+pub struct Enforcement {
+    run_id: RunId,
+    config: EnforcementConfig,
+}
+
+impl Enforcement {
+    pub fn new(run_id: RunId, config: EnforcementConfig) -> Self {
+        Self { run_id, config }
+    }
+
+    pub fn enabled(&self) -> bool {
+        self.config.enabled
+    }
+
+    /// Rise in the UDAAP score since the last exam closed, per 1,000
+    /// active customers.
+    fn udaap_rise_per_1k(&self, store: &SimStore) -> SimResult<f64> {
+        let score = store.get_udaap_score(&self.run_id)?;
+        let baseline = store.last_exam_udaap_score(&self.run_id)?.unwrap_or(0.0);
+        let customers = store.total_active_customers(&self.run_id)?.max(1) as f64;
+        Ok((score - baseline) * 1000.0 / customers)
+    }
+
+    /// Whether UDAAP risk has risen enough to bring the CFPB in before
+    /// the next scheduled exam.
+    pub fn udaap_exam_due(&self, store: &SimStore) -> SimResult<bool> {
+        Ok(self.config.enabled && self.udaap_rise_per_1k(store)? >= self.config.udaap_exam_rise_per_1k)
+    }
+
+    /// Test the cycle `start..=end`: sample its complaints and SARs,
+    /// read its fee changes and weigh the UDAAP rise.
+    pub fn test_cycle(
+        &self,
+        store: &SimStore,
+        exam_id: &str,
+        start: Tick,
+        end: Tick,
+        rng: &mut SubsystemRng,
+    ) -> SimResult<Vec<TestedFinding>> {
+        let mut findings = Vec::new();
+
+        let complaints = store.exam_complaint_population(&self.run_id, start, end)?;
+        if let Some(severity) = self.sample(store, exam_id, "complaints", complaints, rng)? {
+            findings.push(TestedFinding {
+                category: "complaint_handling",
+                severity,
+                description: "Sampled complaints closed late or without redress for fee harm",
+            });
+        }
+
+        let sars = store.exam_sar_population(&self.run_id, start, end)?;
+        if let Some(severity) = self.sample(store, exam_id, "sars", sars, rng)? {
+            findings.push(TestedFinding {
+                category: "sar_quality",
+                severity,
+                description: "Sampled SARs filed after their deadline",
+            });
+        }
+
+        let (changes, increases) = store.fee_changes_between(&self.run_id, start, end)?;
+        store.insert_exam_sample(
+            &self.run_id, exam_id, "fee_changes", changes as usize, changes as usize, increases as usize,
+        )?;
+
+        let rise = self.udaap_rise_per_1k(store)?;
+        let severity = if rise >= self.config.udaap_critical_rise_per_1k {
+            Some("critical")
+        } else if rise >= self.config.udaap_major_rise_per_1k {
+            Some("major")
+        } else {
+            None
+        };
+        if let Some(severity) = severity {
+            findings.push(TestedFinding {
+                category: "udaap",
+                severity,
+                description: if increases > 0 {
+                    "Fee increases and complaint handling pose unfair or deceptive practice risk"
+                } else {
+                    "Complaint handling and customer harm pose unfair or deceptive practice risk"
+                },
+            });
+        }
+
+        Ok(findings)
+    }
+
+    /// Sample up to `sample_size` of a population of failed flags, record
+    /// the test, and say what finding it warrants.
+    fn sample(
+        &self,
+        store: &SimStore,
+        exam_id: &str,
+        name: &str,
+        mut population: Vec<bool>,
+        rng: &mut SubsystemRng,
+    ) -> SimResult<Option<&'static str>> {
+        let available = population.len();
+        let size = self.config.sample_size.min(available);
+        // Partial Fisher–Yates: the first size are a uniform sample
+        for i in 0..size {
+            let j = i + rng.next_u64_below((available - i) as u64) as usize;
+            population.swap(i, j);
+        }
+        let failed = population[..size].iter().filter(|f| **f).count();
+        store.insert_exam_sample(&self.run_id, exam_id, name, available, size, failed)?;
+
+        if size < self.config.min_sample.max(1) {
+            return Ok(None);
+        }
+        let share = failed as f64 / size as f64;
+        Ok(if share >= 2.0 * self.config.sample_failure_share {
+            Some("critical")
+        } else if share >= self.config.sample_failure_share {
+            Some("major")
+        } else {
+            None
+        })
+    }
+
+    /// Act on an exam closing at `tick` over the cycle from `cycle_start`:
+    /// escalate open actions whose finding recurred, issue MRAs and
+    /// consent orders on new major and critical findings, lift what the
+    /// exam found remediated, and keep the UDAAP score as the next
+    /// exam's baseline.
+    pub fn act_on_exam(
+        &self,
+        store: &SimStore,
+        exam_id: &str,
+        findings: &[RecordedFinding],
+        cycle_start: Tick,
+        tick: Tick,
+    ) -> SimResult<Vec<SimEvent>> {
+        let c = &self.config;
+        let mut events = Vec::new();
+        let open = store.open_enforcement_actions(&self.run_id)?;
+        let (_, fee_increases) = store.fee_changes_between(&self.run_id, cycle_start, tick)?;
+
+        for f in findings.iter().filter(|f| matches!(f.severity, "major" | "critical")) {
+            let prior = open.iter().find(|a| a.category == f.category);
+            let consent_order = f.severity == "critical" || prior.is_some();
+            let (kind, remediation_ticks) = if consent_order {
+                ("consent_order", c.consent_order_remediation_ticks)
+            } else {
+                ("mra", c.mra_remediation_ticks)
+            };
+            let penalty = match prior {
+                _ if !consent_order => 0.0,
+                Some(p) if p.kind == "consent_order" => f.fine_amount * c.repeat_penalty_multiplier,
+                _ => f.fine_amount,
+            };
+            let constraint_kind = if consent_order { constraint_for(f.category, fee_increases) } else { None };
+            let lookback_items = match constraint_kind {
+                Some(kind) => store.lookback_population(&self.run_id, kind, tick.saturating_sub(c.lookback_ticks), tick)?,
+                None => 0,
+            };
+
+            let action = EnforcementActionRow {
+                action_id: format!("ea-{}", f.finding_id),
+                exam_id: exam_id.into(),
+                finding_id: f.finding_id.into(),
+                kind: kind.into(),
+                category: f.category.into(),
+                tick_issued: tick,
+                remediation_due_tick: tick + remediation_ticks,
+                constraint_kind: constraint_kind.map(Into::into),
+                penalty,
+                lookback_items,
+                lookback_reviewed: 0,
+                lookback_cost: lookback_items as f64 * c.lookback_cost_per_item,
+                status: "open".into(),
+                tick_closed: None,
+                superseded_by: None,
+            };
+            store.insert_enforcement_action(&self.run_id, &action)?;
+            if let Some(p) = prior {
+                store.close_enforcement_action(&self.run_id, &p.action_id, tick, "escalated", Some(&action.action_id))?;
+            }
+            tracing::warn!(
+                "{kind} {} issued on {} finding{}",
+                action.action_id,
+                f.category,
+                prior.map(|p| format!(", escalating {}", p.action_id)).unwrap_or_default(),
+            );
+            events.push(SimEvent::EnforcementActionIssued {
+                tick,
+                action_id: action.action_id,
+                exam_id: exam_id.into(),
+                kind: kind.into(),
+                category: f.category.into(),
+                constraint: action.constraint_kind,
+                penalty,
+                supersedes: prior.map(|p| p.action_id.clone()),
+            });
+        }
+
+        for action in &open {
+            let recurred = findings.iter().any(|f| f.category == action.category);
+            let lookback_done = action.lookback_reviewed >= action.lookback_items;
+            if recurred || tick < action.remediation_due_tick || !lookback_done {
+                continue;
+            }
+            store.close_enforcement_action(&self.run_id, &action.action_id, tick, "lifted", None)?;
+            tracing::info!("{} {} lifted: {} remediated", action.kind, action.action_id, action.category);
+            events.push(SimEvent::EnforcementActionLifted {
+                tick,
+                action_id: action.action_id.clone(),
+                kind: action.kind.clone(),
+                category: action.category.clone(),
+            });
+        }
+
+        store.set_exam_udaap_score(&self.run_id, exam_id, store.get_udaap_score(&self.run_id)?)?;
+        Ok(events)
+    }
+
+    /// Work each mandated lookback through its items evenly over
+    /// `lookback_review_ticks`.
+    pub fn work_lookbacks(&self, store: &SimStore, tick: Tick) -> SimResult<Vec<SimEvent>> {
+        let mut events = Vec::new();
+        for action in store.open_enforcement_actions(&self.run_id)? {
+            if action.lookback_reviewed >= action.lookback_items {
+                continue;
+            }
+            let per_tick = (action.lookback_items as f64 / self.config.lookback_review_ticks.max(1) as f64).ceil() as i64;
+            let reviewed = (action.lookback_reviewed + per_tick).min(action.lookback_items);
+            store.record_lookback_progress(&self.run_id, &action.action_id, reviewed)?;
+            if reviewed == action.lookback_items {
+                events.push(SimEvent::LookbackCompleted {
+                    tick,
+                    action_id: action.action_id,
+                    items: action.lookback_items,
+                });
+            }
+        }
+        Ok(events)
+    }
+}
+
+/// What a consent order on `category` imposes.
+fn constraint_for(category: &str, fee_increases: i64) -> Option<&'static str> {
+    match category {
+        "udaap" if fee_increases > 0 => Some("fee_cap"),
+        "udaap" | "complaint_handling" | "complaint_sla" => Some("complaint_lookback"),
+        "sar_timeliness" | "sar_missed_filing" | "sar_quality" | "undocumented_suppression" | "filing_quality" => {
+            Some("sar_lookback")
+        }
+        _ => None,
+    }
+}
@@ -19,17 +19,19 @@
 
 use crate::{
     clock::SimClock,
-    config::ResolutionCode,
-    error::SimResult,
+    config::{ResolutionCode, ResolutionTemplate},
+    error::{SimError, SimResult},
     event::{EventLogEntry, SimEvent},
     macro_subsystem::MacroSubsystem,
     rng::{RngBank, SubsystemSlot},
+    scenario::ScenarioFile,
     snapshot::{SimSnapshot, SNAPSHOT_INTERVAL},
+    state_mirror::StateMirror,
     store::SimStore,
-    subsystem::SimSubsystem,
+    subsystem::{SimSubsystem, SubsystemInfo},
     types::{RunId, Tick},
 };
-use std::collections::HashMap;
+use std::{cell::RefCell, collections::HashMap};
 
 pub struct SimEngine {
     pub run_id: RunId,
@@ -38,8 +40,33 @@ pub struct SimEngine {
     seed: u64,
     subsystems: Vec<(SubsystemSlot, Box<dyn SimSubsystem>)>,
     pub store: SimStore,
+    /// Phase 4.21: where the event log and snapshots go; None keeps them
+    /// in `store`.
+    backend: Option<Box<dyn crate::store::backend::StoreBackend>>,
     resolution_codes: HashMap<String, ResolutionCode>,
+    /// Phase 4.47: `bulk_resolve` templates by id.
+    resolution_templates: HashMap<String, ResolutionTemplate>,
     pending_commands: Vec<SimEvent>,
+    /// Set by the first fatal error; the run never ticks again after it.
+    halted: Option<(Tick, String)>,
+    population_scale: f64,
+    /// None keeps every event as its own event_log row.
+    event_log_retention: Option<crate::config::EventLogRetentionConfig>,
+    /// Phase 4.57: None writes events to the store only.
+    event_stream: Option<crate::event_stream::EventStream>,
+    /// Phase 4.33: None applies satisfaction deltas as given.
+    satisfaction: Option<crate::satisfaction::SatisfactionDynamics>,
+    /// Phase 4.46: None lets any seat send any command.
+    authorizer: Option<crate::authorization::Authorizer>,
+    /// Phase 4.66: the headline KPIs, kept current as events are logged.
+    /// A cell so closures made through `&self` can move the backlog.
+    mirror: RefCell<StateMirror>,
+    /// Phase 4.68: None runs no scenario scripts.
+    scripts: Option<crate::scenario_script::ScenarioScripts>,
+    /// Phase 4.74: checked at the end of every tick.
+    breakpoints: crate::breakpoint::Breakpoints,
+    /// The breakpoints the last tick tripped.
+    breakpoint_hits: Vec<crate::breakpoint::BreakpointHit>,
 }
 
 impl SimEngine {
@@ -50,9 +77,21 @@ impl SimEngine {
             seed,
             subsystems: Vec::new(),
             store,
+            backend: None,
             run_id,
             resolution_codes: HashMap::new(),
+            resolution_templates: HashMap::new(),
             pending_commands: Vec::new(),
+            halted: None,
+            population_scale: 1.0,
+            event_log_retention: None,
+            event_stream: None,
+            satisfaction: None,
+            authorizer: None,
+            mirror: RefCell::default(),
+            scripts: None,
+            breakpoints: Default::default(),
+            breakpoint_hits: Vec::new(),
         }
     }
 
@@ -64,7 +103,34 @@ impl SimEngine {
         store: &SimStore,
         data_dir: &str,
     ) -> anyhow::Result<Self> {
-        let config = crate::config::SimConfig::load(data_dir)?;
+        Self::build_with_population_scale(run_id, seed, store, data_dir, 1.0)
+    }
+
+    /// Like `build`, simulating only `scale` of the population; see
+    /// `SimConfig::with_population_scale`.
+    pub fn build_with_population_scale(
+        run_id: RunId,
+        seed: u64,
+        store: &SimStore,
+        data_dir: &str,
+        scale: f64,
+    ) -> anyhow::Result<Self> {
+        Self::build_with_scenario(run_id, seed, store, data_dir, scale, &ScenarioFile::default())
+    }
+
+    /// Phase 4.77: like `build_with_population_scale`, with `scenario`
+    /// applied over the data directory's config before it is scaled.
+    pub fn build_with_scenario(
+        run_id: RunId,
+        seed: u64,
+        store: &SimStore,
+        data_dir: &str,
+        scale: f64,
+        scenario: &ScenarioFile,
+    ) -> anyhow::Result<Self> {
+        let mut config = crate::config::SimConfig::load(data_dir)?;
+        scenario.apply(&mut config)?;
+        let config = config.with_population_scale(scale)?;
 
         // Each subsystem needs its own store connection for concurrent access
         let store_customer = store.reopen()?;
@@ -84,14 +150,45 @@ impl SimEngine {
         let store_transaction_monitoring = store.reopen()?;
         let store_regulatory_exam = store.reopen()?;
         let store_reputation = store.reopen()?;
+        let store_credit_bureau = store.reopen()?;
+        let store_branch = store.reopen()?;
+        let store_fair_lending = store.reopen()?;
+        let store_incident = store.reopen()?;
+        let store_outreach = store.reopen()?;
+        let store_staffing = store.reopen()?;
+        let store_lending = store.reopen()?;
+        let store_case = store.reopen()?;
+        let store_macro = store.reopen()?;
 
         let mut engine = SimEngine::new(run_id.clone(), seed, store.reopen()?);
         engine.resolution_codes = config.resolution_codes.clone();
+        engine.resolution_templates = config.resolution_templates.clone();
+        engine.population_scale = config.population_scale;
+        engine.set_event_log_retention(config.event_log_retention.clone());
+        engine.set_event_stream(&config.event_stream)?;
+        engine.satisfaction = config.satisfaction_dynamics.enabled.then(|| {
+            crate::satisfaction::SatisfactionDynamics::new(run_id.clone(), config.satisfaction_dynamics.clone())
+        });
+        engine.authorizer = config.authorization.enabled.then(|| {
+            crate::authorization::Authorizer::new(
+                run_id.clone(),
+                config.authorization.clone(),
+                &config.risk_appetite.dials,
+            )
+        });
 
         // EXECUTION ORDER — fixed, documented, never reordered.
         // Phase 0: engine internals (no subsystem)
         // Phase 1A:
-        engine.register(SubsystemSlot::Macro, Box::new(MacroSubsystem::new()));
+        engine.register(
+            SubsystemSlot::Macro,
+            Box::new(MacroSubsystem::new(
+                run_id.clone(),
+                &config.macro_regime,
+                config.rate_environment.clone(),
+                store_macro,
+            )),
+        );
         // Phase 1B:
         engine.register(
             SubsystemSlot::Customer,
@@ -101,6 +198,16 @@ impl SimEngine {
                 store_customer,
             )),
         );
+        // Phase 4.32: Outreach (after Customer for CustomerOnboarded, before
+        // Offer so this tick's contact policy governs this tick's launches)
+        engine.register(
+            SubsystemSlot::Outreach,
+            Box::new(crate::outreach_subsystem::OutreachSubsystem::new(
+                run_id.clone(),
+                config.contact_fatigue.clone(),
+                store_outreach,
+            )),
+        );
         // Phase 2.2: Offer (after Customer so it sees CustomerOnboarded events)
         engine.register(
             SubsystemSlot::Offer,
@@ -123,6 +230,24 @@ impl SimEngine {
             SubsystemSlot::Transaction,
             Box::new(crate::transaction_subsystem::TransactionSubsystem::new(
                 run_id.clone(),
+                config.protected_funds.clone(),
+                config.ach_origination.clone(),
+                config.external_links.clone(),
+                config.customer_limits.clone(),
+                config.time_acceleration.clone(),
+                config.hardship.clone(),
+                config.remittance.clone(),
+                config.visa_expiry.clone(),
+                config.cross_border.clone(),
+                config.bec.clone(),
+                config.check_fraud.clone(),
+                config.deposit_holds.clone(),
+                config.mule_networks.clone(),
+                config.structuring.clone(),
+                config.channels.clone(),
+                config.app_fraud.clone(),
+                config.atm_network.clone(),
+                config.devices.clone(),
                 store_txn,
             )),
         );
@@ -133,7 +258,22 @@ impl SimEngine {
             Box::new(crate::payment_hub_subsystem::PaymentHubSubsystem::new(
                 run_id.clone(),
                 config.payment_hub.clone(),
+                config.payment_cutoff.clone(),
+                config.ach_returns.clone(),
+                config.ach_origination.clone(),
+                config.correspondent_banking.clone(),
+                config.calendar.clone(),
+                config.merchants.clone(),
                 store_payment_hub,
+            )?),
+        );
+        // Phase 4.80: Staffing (before the subsystems that work its queues)
+        engine.register(
+            SubsystemSlot::Staffing,
+            Box::new(crate::staffing_subsystem::StaffingSubsystem::new(
+                run_id.clone(),
+                config.staffing.clone(),
+                store_staffing,
             )),
         );
         // Phase 3.2: Reconciliation (after PaymentHub)
@@ -142,6 +282,9 @@ impl SimEngine {
             Box::new(crate::reconciliation_subsystem::ReconciliationSubsystem::new(
                 run_id.clone(),
                 config.reconciliation.clone(),
+                config.network_invoice.clone(),
+                config.settlement_accounts.clone(),
+                config.payment_hub.rails.clone(),
                 store_recon,
             )),
         );
@@ -150,6 +293,9 @@ impl SimEngine {
             SubsystemSlot::CardDispute,
             Box::new(crate::card_dispute_subsystem::CardDisputeSubsystem::new(
                 run_id.clone(),
+                config.case_documents.clone(),
+                config.evidence_bundles.clone(),
+                config.merchants.clone(),
                 store_card_dispute,
             )),
         );
@@ -158,6 +304,12 @@ impl SimEngine {
             SubsystemSlot::FraudDetection,
             Box::new(crate::fraud_detection_subsystem::FraudDetectionSubsystem::new(
                 run_id.clone(),
+                config.bec.clone(),
+                config.check_fraud.clone(),
+                config.app_fraud.clone(),
+                config.atm_network.clone(),
+                config.devices.clone(),
+                &config.payment_hub.rails,
                 store_fraud_detection,
             )),
         );
@@ -166,6 +318,8 @@ impl SimEngine {
             SubsystemSlot::AMLScreening,
             Box::new(crate::aml_screening_subsystem::AMLScreeningSubsystem::new(
                 run_id.clone(),
+                config.adverse_media.clone(),
+                config.sanctions_screening.clone(),
                 store_aml_screening,
             )),
         );
@@ -174,7 +328,38 @@ impl SimEngine {
             SubsystemSlot::TransactionMonitoring,
             Box::new(crate::transaction_monitoring_subsystem::TransactionMonitoringSubsystem::new(
                 run_id.clone(),
+                config.risk_lifecycle.clone(),
+                config.sar_decision.clone(),
+                config.rule_tuning.clone(),
+                config.coverage_assessment.clone(),
+                config.transaction_enrichment.clone(),
+                config.remittance.clone(),
+                config.correspondent_banking.clone(),
+                config.cross_border.clone(),
+                config.calendar.clone(),
+                config.evidence_bundles.clone(),
+                config.alert_grouping.clone(),
+                config.alert_qa.clone(),
+                config.alert_triage.clone(),
                 store_transaction_monitoring,
+            )?),
+        );
+        // Phase 4.1: Credit Bureau (before Complaint — files FCRA disputes as complaints)
+        engine.register(
+            SubsystemSlot::CreditBureau,
+            Box::new(crate::credit_bureau_subsystem::CreditBureauSubsystem::new(
+                run_id.clone(),
+                config.credit_bureau.clone(),
+                store_credit_bureau,
+            )),
+        );
+        // Phase 4.82: Lending (after the day's transactions land, before Economics)
+        engine.register(
+            SubsystemSlot::Lending,
+            Box::new(crate::lending_subsystem::LendingSubsystem::new(
+                run_id.clone(),
+                config.lending.clone(),
+                store_lending,
             )),
         );
         engine.register(
@@ -183,6 +368,16 @@ impl SimEngine {
                 run_id.clone(),
                 config.clone(),
                 store_complaint,
+            )?),
+        );
+        // Phase 4.86: Case management (after Complaint — links the day's alerts, hits, disputes and complaints)
+        engine.register(
+            SubsystemSlot::Case,
+            Box::new(crate::case_subsystem::CaseSubsystem::new(
+                run_id.clone(),
+                config.case_management.clone(),
+                config.sar_decision.clone(),
+                store_case,
             )),
         );
         // Phase 2.1: Pricing (runs before Economics so fee changes affect same tick's P&L)
@@ -223,12 +418,40 @@ impl SimEngine {
                 store_risk_appetite,
             )),
         );
+        // Phase 4.3: Branch network (after RiskAppetite — reports read this tick's complaints)
+        engine.register(
+            SubsystemSlot::Branch,
+            Box::new(crate::branch_subsystem::BranchSubsystem::new(
+                run_id.clone(),
+                config.branch_network.clone(),
+                store_branch,
+            )),
+        );
+        // Phase 4.4: Fair lending (after Branch — reviews read this tick's fees, closures, offers)
+        engine.register(
+            SubsystemSlot::FairLending,
+            Box::new(crate::fair_lending_subsystem::FairLendingSubsystem::new(
+                run_id.clone(),
+                config.fair_lending.clone(),
+                store_fair_lending,
+            )),
+        );
+        // Phase 3.3: Incident & Outage (after the channel traffic it degrades)
+        engine.register(
+            SubsystemSlot::Incident,
+            Box::new(crate::incident_subsystem::IncidentSubsystem::new(
+                run_id.clone(),
+                config.incident.clone(),
+                store_incident,
+            )),
+        );
         // Phase 3.6: Regulatory Exam (after TransactionMonitoring — reads SAR events)
         engine.register(
             SubsystemSlot::RegulatoryExam,
             Box::new(crate::regulatory_exam_subsystem::RegulatoryExamSubsystem::new(
                 run_id.clone(),
                 config.regulatory_exam.clone(),
+                config.enforcement.clone(),
                 store_regulatory_exam,
             )),
         );
@@ -238,9 +461,19 @@ impl SimEngine {
             Box::new(crate::reputation_subsystem::ReputationSubsystem::new(
                 run_id.clone(),
                 config.reputation.clone(),
+                crate::stress_index::StressIndex::new(
+                    run_id.clone(),
+                    config.stress_index.clone(),
+                    config.branch_network.customers_per_staff,
+                ),
                 store_reputation,
             )),
         );
+        engine.mirror = StateMirror::load(&engine.store, &engine.run_id)?.into();
+        engine.scripts = (config.scenario_scripts.enabled && !config.scenario_scripts.scripts.is_empty())
+            .then(|| crate::scenario_script::ScenarioScripts::new(&engine.run_id, &config.scenario_scripts, &engine.store))
+            .transpose()?;
+        engine.apply_feature_flags(&config.feature_flags)?;
         Ok(engine)
     }
 
@@ -267,6 +500,36 @@ impl SimEngine {
         Self::build_test_with_config(run_id, seed, config)
     }
 
+    /// Phase 4.81: test build with short exam cycles and enforcement on.
+    pub fn build_test_with_enforcement(run_id: RunId, seed: u64) -> SimResult<Self> {
+        let mut config = crate::config::SimConfig::default_test();
+        config.regulatory_exam.enabled = true;
+        config.regulatory_exam.exam_interval_ticks = 20;
+        config.regulatory_exam.exam_duration_ticks = 5;
+        config.enforcement.enabled = true;
+        config.enforcement.mra_remediation_ticks = 10;
+        config.enforcement.consent_order_remediation_ticks = 30;
+        config.enforcement.lookback_ticks = 20;
+        config.enforcement.lookback_review_ticks = 5;
+        Self::build_test_with_config(run_id, seed, config)
+    }
+
+    /// Phase 4.82: test build with lending on and customers applying
+    /// often enough to build a book within a test run.
+    pub fn build_test_with_lending(run_id: RunId, seed: u64) -> SimResult<Self> {
+        let mut config = crate::config::SimConfig::default_test();
+        config.lending.enabled = true;
+        config.lending.monthly_application_rate = 0.5;
+        Self::build_test_with_config(run_id, seed, config)
+    }
+
+    /// Phase 4.83: test build with the rate environment on.
+    pub fn build_test_with_rate_environment(run_id: RunId, seed: u64) -> SimResult<Self> {
+        let mut config = crate::config::SimConfig::default_test();
+        config.rate_environment.enabled = true;
+        Self::build_test_with_config(run_id, seed, config)
+    }
+
     /// Test-only build with reputation subsystem enabled.
     pub fn build_test_with_reputation(run_id: RunId, seed: u64) -> SimResult<Self> {
         let mut config = crate::config::SimConfig::default_test();
@@ -274,13 +537,900 @@ impl SimEngine {
         Self::build_test_with_config(run_id, seed, config)
     }
 
+    /// Test-only build with credit bureau furnishing enabled.
+    pub fn build_test_with_credit_bureau(run_id: RunId, seed: u64) -> SimResult<Self> {
+        let mut config = crate::config::SimConfig::default_test();
+        config.credit_bureau.enabled = true;
+        // Short cycle and a noisy furnisher so tests see disputes quickly
+        config.credit_bureau.furnish_interval_ticks = 10;
+        config.credit_bureau.furnishing_error_rate = 0.30;
+        config.credit_bureau.dispute_probability = 0.80;
+        config.credit_bureau.investigation_window_ticks = 10;
+        Self::build_test_with_config(run_id, seed, config)
+    }
+
+    /// Test-only build with benefit deposits and protected-funds checks enabled.
+    /// `enforce_protection = false` lets tests observe violations.
+    pub fn build_test_with_protected_funds(
+        run_id: RunId,
+        seed: u64,
+        enforce_protection: bool,
+    ) -> SimResult<Self> {
+        let mut config = crate::config::SimConfig::default_test();
+        config.protected_funds.enabled = true;
+        config.protected_funds.enforce_protection = enforce_protection;
+        // Enrol most accounts so a small test population sees overdrafts
+        config.protected_funds.beneficiary_share = 0.80;
+        config.protected_funds.garnishment_daily_rate = 0.05;
+        Self::build_test_with_config(run_id, seed, config)
+    }
+
+    /// Test-only build with the branch network enabled.
+    pub fn build_test_with_branches(run_id: RunId, seed: u64) -> SimResult<Self> {
+        let mut config = crate::config::SimConfig::default_test();
+        config.branch_network.enabled = true;
+        // The 50-customer test population is spread thin across states
+        config.branch_network.min_customers_for_branch = 3;
+        // Overdraft complaints in the test defaults would zero out every
+        // branch's projected acquisitions
+        config.complaint_triggers.clear();
+        Self::build_test_with_config(run_id, seed, config)
+    }
+
+    /// Build a test engine with fair lending reviews enabled on a monthly
+    /// cadence and sensitive thresholds for the 50-customer population.
+    pub fn build_test_with_fair_lending(run_id: RunId, seed: u64) -> SimResult<Self> {
+        let mut config = crate::config::SimConfig::default_test();
+        config.fair_lending.enabled = true;
+        config.fair_lending.review_interval_ticks = 30;
+        config.fair_lending.min_group_size = 3;
+        config.fair_lending.adverse_ratio_threshold = 1.05;
+        config.fair_lending.response_window_ticks = 10;
+        Self::build_test_with_config(run_id, seed, config)
+    }
+
+    /// Build a test engine with the offer targeting guardrail enabled.
+    /// Fair lending supplies the zip demographics; an inactive cross-sell
+    /// offer ("campaign_cash_50") is available for LaunchOffer.
+    pub fn build_test_with_offer_guardrail(run_id: RunId, seed: u64) -> SimResult<Self> {
+        let mut config = crate::config::SimConfig::default_test();
+        config.fair_lending.enabled = true;
+        config.offer_guardrail.enabled = true;
+        add_test_campaign(&mut config);
+        Self::build_test_with_config(run_id, seed, config)
+    }
+
+    /// Build a test engine with card network invoicing enabled. Every
+    /// invoice line carries a network error so reconciliation has work.
+    pub fn build_test_with_network_invoices(run_id: RunId, seed: u64) -> SimResult<Self> {
+        let mut config = crate::config::SimConfig::default_test();
+        config.network_invoice.enabled = true;
+        config.network_invoice.network_error_rate = 1.0;
+        Self::build_test_with_config(run_id, seed, config)
+    }
+
+    /// Build a test engine with per-rail settlement accounts funded at
+    /// exactly the projected outflow (no buffer), so shortfalls occur.
+    pub fn build_test_with_settlement_accounts(run_id: RunId, seed: u64) -> SimResult<Self> {
+        let mut config = crate::config::SimConfig::default_test();
+        config.settlement_accounts.enabled = true;
+        config.settlement_accounts.funding_buffer_pct = 0.0;
+        Self::build_test_with_config(run_id, seed, config)
+    }
+
+    /// Build a test engine with rail cutoffs enforced. Every payment that
+    /// rolls to the next day draws a "missing payment" complaint.
+    pub fn build_test_with_payment_cutoffs(run_id: RunId, seed: u64) -> SimResult<Self> {
+        let mut config = crate::config::SimConfig::default_test();
+        config.payment_cutoff.enabled = true;
+        config.complaint_triggers.push(crate::config::ComplaintTrigger {
+            event_type: "payment_delayed".into(),
+            fee_type: None,
+            amount_threshold: None,
+            prior_breach: false,
+            probability: 1.0,
+            issue_category: "missing_payment".into(),
+            priority: "standard".into(),
+            sla_acknowledge_days: 1,
+            sla_resolve_days: 10,
+            channel: "phone".into(),
+        });
+        Self::build_test_with_config(run_id, seed, config)
+    }
+
+    /// Build a test engine with a high ACH return rate. Every unauthorized
+    /// return draws an "unauthorized transaction" complaint.
+    pub fn build_test_with_ach_returns(run_id: RunId, seed: u64) -> SimResult<Self> {
+        let mut config = crate::config::SimConfig::default_test();
+        config.ach_returns.enabled = true;
+        config.ach_returns.return_rate = 0.25;
+        config.complaint_triggers.push(crate::config::ComplaintTrigger {
+            event_type: "transaction_dispute".into(),
+            fee_type: None,
+            amount_threshold: None,
+            prior_breach: false,
+            probability: 1.0,
+            issue_category: "unauthorized_transaction".into(),
+            priority: "urgent".into(),
+            sla_acknowledge_days: 0,
+            sla_resolve_days: 3,
+            channel: "phone".into(),
+        });
+        Self::build_test_with_config(run_id, seed, config)
+    }
+
+    /// Build a test engine with customer-originated ACH enabled.
+    pub fn build_test_with_ach_origination(run_id: RunId, seed: u64) -> SimResult<Self> {
+        let mut config = crate::config::SimConfig::default_test();
+        config.ach_origination.enabled = true;
+        config.ach_origination.bill_pay_daily_rate = 0.30;
+        config.ach_origination.external_transfer_daily_rate = 0.20;
+        Self::build_test_with_config(run_id, seed, config)
+    }
+
+    /// Build a test engine whose customer-originated ACH includes frequent
+    /// account takeovers, pushing unauthorized returns past the ODFI limit.
+    pub fn build_test_with_ach_takeovers(run_id: RunId, seed: u64) -> SimResult<Self> {
+        let mut config = crate::config::SimConfig::default_test();
+        config.ach_origination.enabled = true;
+        config.ach_origination.bill_pay_daily_rate = 0.30;
+        config.ach_origination.external_transfer_daily_rate = 0.20;
+        config.ach_origination.takeover_daily_rate = 0.01;
+        Self::build_test_with_config(run_id, seed, config)
+    }
+
+    /// Build a test engine with external account linking on top of
+    /// customer-originated ACH, with frequent mule links.
+    pub fn build_test_with_external_links(run_id: RunId, seed: u64) -> SimResult<Self> {
+        let mut config = crate::config::SimConfig::default_test();
+        config.ach_origination.enabled = true;
+        config.ach_origination.bill_pay_daily_rate = 0.30;
+        config.ach_origination.external_transfer_daily_rate = 0.20;
+        config.external_links.enabled = true;
+        config.external_links.dd_switch_daily_rate = 0.05;
+        config.external_links.mule_link_daily_rate = 0.02;
+        Self::build_test_with_config(run_id, seed, config)
+    }
+
+    /// Build a test engine with customer limits enforced.
+    pub fn build_test_with_customer_limits(run_id: RunId, seed: u64) -> SimResult<Self> {
+        let mut config = crate::config::SimConfig::default_test();
+        config.customer_limits.enabled = true;
+        Self::build_test_with_config(run_id, seed, config)
+    }
+
+    /// Build a test engine with the risk lifecycle on. Customer limits are
+    /// enforced too, so limit declines feed behavior re-scores.
+    pub fn build_test_with_risk_lifecycle(run_id: RunId, seed: u64) -> SimResult<Self> {
+        let mut config = crate::config::SimConfig::default_test();
+        config.customer_limits.enabled = true;
+        config.risk_lifecycle.enabled = true;
+        Self::build_test_with_config(run_id, seed, config)
+    }
+
+    /// Build a test engine with onboarding SSN validation on and enough
+    /// synthetic identities to cover each implausible-SSN pattern.
+    pub fn build_test_with_ssn_validation(run_id: RunId, seed: u64) -> SimResult<Self> {
+        let mut config = crate::config::SimConfig::default_test();
+        config.ssn_validation.enabled = true;
+        config.identity_address.synthetic_identity_rate = 0.30;
+        Self::build_test_with_config(run_id, seed, config)
+    }
+
+    /// Build a test engine with onboarding IDV checks run by `vendor` and
+    /// enough synthetic identities to compare vendors.
+    pub fn build_test_with_idv_vendor(run_id: RunId, seed: u64, vendor: &str) -> SimResult<Self> {
+        let mut config = crate::config::SimConfig::default_test();
+        config.idv.enabled = true;
+        config.idv.vendor = vendor.into();
+        config.identity_address.synthetic_identity_rate = 0.30;
+        Self::build_test_with_config(run_id, seed, config)
+    }
+
+    /// Build a test engine with SAR decision tracking on and a 60-tick
+    /// exam cycle to surface missed filings.
+    pub fn build_test_with_sar_decisions(run_id: RunId, seed: u64) -> SimResult<Self> {
+        let mut config = crate::config::SimConfig::default_test();
+        config.sar_decision.enabled = true;
+        config.regulatory_exam.enabled = true;
+        config.regulatory_exam.exam_interval_ticks = 60;
+        config.regulatory_exam.exam_duration_ticks = 7;
+        Self::build_test_with_config(run_id, seed, config)
+    }
+
+    /// Build a test engine with the rule tuning workflow on and a 60-tick
+    /// exam cycle to review the tunings in force.
+    pub fn build_test_with_rule_tuning(run_id: RunId, seed: u64) -> SimResult<Self> {
+        let mut config = crate::config::SimConfig::default_test();
+        config.rule_tuning.enabled = true;
+        config.regulatory_exam.enabled = true;
+        config.regulatory_exam.exam_interval_ticks = 60;
+        config.regulatory_exam.exam_duration_ticks = 7;
+        Self::build_test_with_config(run_id, seed, config)
+    }
+
+    /// Build a test engine with the monthly coverage assessment on and
+    /// the rule tuning workflow to suppress rules with.
+    pub fn build_test_with_coverage_assessment(run_id: RunId, seed: u64) -> SimResult<Self> {
+        let mut config = crate::config::SimConfig::default_test();
+        config.coverage_assessment.enabled = true;
+        config.rule_tuning.enabled = true;
+        Self::build_test_with_config(run_id, seed, config)
+    }
+
+    /// Build a test engine that switches customers in `coarse_risk_tiers`
+    /// to weekly steps after tick 20.
+    pub fn build_test_with_time_acceleration(
+        run_id: RunId,
+        seed: u64,
+        coarse_risk_tiers: &[&str],
+    ) -> SimResult<Self> {
+        let mut config = crate::config::SimConfig::default_test();
+        config.time_acceleration.enabled = true;
+        config.time_acceleration.coarse_risk_tiers = coarse_risk_tiers.iter().map(|t| t.to_string()).collect();
+        config.time_acceleration.coarse_after_tick = 20;
+        config.time_acceleration.coarse_step_ticks = 7;
+        Self::build_test_with_config(run_id, seed, config)
+    }
+
+    /// Build a test engine simulating `scale` of the test population.
+    pub fn build_test_with_population_scale(run_id: RunId, seed: u64, scale: f64) -> SimResult<Self> {
+        let config = crate::config::SimConfig::default_test().with_population_scale(scale)?;
+        Self::build_test_with_config(run_id, seed, config)
+    }
+
+    /// Build a test engine that keeps `detail_ticks` of events in detail,
+    /// compacting every 10 ticks.
+    pub fn build_test_with_event_log_retention(
+        run_id: RunId,
+        seed: u64,
+        detail_ticks: Tick,
+        export_dir: Option<String>,
+    ) -> SimResult<Self> {
+        let mut config = crate::config::SimConfig::default_test();
+        config.event_log_retention.enabled = true;
+        config.event_log_retention.detail_ticks = detail_ticks;
+        config.event_log_retention.compact_interval_ticks = 10;
+        config.event_log_retention.export_dir = export_dir;
+        Self::build_test_with_config(run_id, seed, config)
+    }
+
+    /// Build a test engine with the hardship program on. Every customer
+    /// has a job change at each monthly rescore and enrolls if not
+    /// already enrolled.
+    pub fn build_test_with_hardship(run_id: RunId, seed: u64) -> SimResult<Self> {
+        let mut config = crate::config::SimConfig::default_test();
+        config.hardship.enabled = true;
+        config.hardship.enrollment_probability = 1.0;
+        for event in &mut config.churn_model.life_events {
+            if event.event_type == "job_change" {
+                event.probability_per_year = 365.0;
+            }
+        }
+        Self::build_test_with_config(run_id, seed, config)
+    }
+
+    /// Build a test engine that documents disputes and complaints, each
+    /// required document missing with `missing_probability`, with exams
+    /// opening every 20 ticks for 5 ticks.
+    pub fn build_test_with_case_documents(run_id: RunId, seed: u64, missing_probability: f64) -> SimResult<Self> {
+        let mut config = crate::config::SimConfig::default_test();
+        config.case_documents.enabled = true;
+        config.case_documents.missing_probability = missing_probability;
+        config.regulatory_exam.enabled = true;
+        config.regulatory_exam.exam_interval_ticks = 20;
+        config.regulatory_exam.exam_duration_ticks = 5;
+        Self::build_test_with_config(run_id, seed, config)
+    }
+
+    /// Build a test engine with contact preferences and fatigue on, and
+    /// the launchable "campaign_cash_50" offer. A quarter of customers ask
+    /// not to be contacted, and every contact against that draws an
+    /// "unwanted contact" complaint.
+    pub fn build_test_with_contact_fatigue(run_id: RunId, seed: u64) -> SimResult<Self> {
+        let mut config = crate::config::SimConfig::default_test();
+        config.contact_fatigue.enabled = true;
+        config.contact_fatigue.do_not_contact_rate = 0.25;
+        add_test_campaign(&mut config);
+        config.complaint_triggers.push(crate::config::ComplaintTrigger {
+            event_type: "contact_violation".into(),
+            fee_type: None,
+            amount_threshold: None,
+            prior_breach: false,
+            probability: 1.0,
+            issue_category: "unwanted_contact".into(),
+            priority: "standard".into(),
+            sla_acknowledge_days: 2,
+            sla_resolve_days: 15,
+            channel: "phone".into(),
+        });
+        Self::build_test_with_config(run_id, seed, config)
+    }
+
+    /// Build a test engine with satisfaction dynamics on. Some overdraft
+    /// fees also draw urgent complaints, which breach (and scar) if left
+    /// open.
+    pub fn build_test_with_satisfaction_dynamics(run_id: RunId, seed: u64) -> SimResult<Self> {
+        let mut config = crate::config::SimConfig::default_test();
+        config.satisfaction_dynamics.enabled = true;
+        config.complaint_triggers.push(crate::config::ComplaintTrigger {
+            event_type: "fee_charged".into(),
+            fee_type: Some("overdraft".into()),
+            amount_threshold: None,
+            prior_breach: false,
+            probability: 0.05,
+            issue_category: "fee_escalation".into(),
+            priority: "urgent".into(),
+            sla_acknowledge_days: 1,
+            sla_resolve_days: 5,
+            channel: "phone".into(),
+        });
+        Self::build_test_with_config(run_id, seed, config)
+    }
+
+    /// Build a test engine with financial health scoring on and an
+    /// inactive $1,000 credit line offer ("credit_line_1000") to launch.
+    pub fn build_test_with_financial_health(run_id: RunId, seed: u64) -> SimResult<Self> {
+        let mut config = crate::config::SimConfig::default_test();
+        config.financial_health.enabled = true;
+        let mut credit = config.offers["signup_bonus_100"].clone();
+        credit.offer_id = "credit_line_1000".into();
+        credit.label = "$1,000 Credit Line".into();
+        credit.offer_type = "credit_line".into();
+        credit.bonus_amount = 0.0;
+        credit.credit_limit = 1000.0;
+        credit.requirements.new_to_bank_only = false;
+        credit.requirements.min_direct_deposit = 0.0;
+        credit.requirements.min_balance = 0.0;
+        credit.eligibility.target_segments.clear();
+        credit.fraud_risk.bonus_seeker_probability = 0.0;
+        credit.active = false;
+        config.offers.insert(credit.offer_id.clone(), credit);
+        Self::build_test_with_config(run_id, seed, config)
+    }
+
+    /// Build a test engine with transaction enrichment labelling merchants
+    /// at `accuracy`, and financial health scoring on. Gambling merchants
+    /// are common enough that some customers cross the HIGH_RISK_MCC
+    /// threshold.
+    pub fn build_test_with_transaction_enrichment(run_id: RunId, seed: u64, accuracy: f64) -> SimResult<Self> {
+        let mut config = crate::config::SimConfig::default_test();
+        config.transaction_enrichment.enabled = true;
+        config.transaction_enrichment.accuracy = accuracy;
+        for category in &mut config.transaction_enrichment.categories {
+            if category.category == "gambling" {
+                category.merchant_share = 0.20;
+            }
+        }
+        config.financial_health.enabled = true;
+        Self::build_test_with_config(run_id, seed, config)
+    }
+
+    /// Build a test engine with remittance corridors on. Half of new
+    /// customers are international, all of them with a corridor remit,
+    /// and a quarter of those layer funds.
+    pub fn build_test_with_remittance(run_id: RunId, seed: u64) -> SimResult<Self> {
+        let mut config = crate::config::SimConfig::default_test();
+        config.remittance.enabled = true;
+        config.remittance.participation_rate = 1.0;
+        config.remittance.layering_rate = 0.25;
+        config.identity_address.international_customer_rate = 0.5;
+        Self::build_test_with_config(run_id, seed, config)
+    }
+
+    /// Build a test engine with visa expiry on and lapsed documentation
+    /// restricted at `level` after 5 ticks. Half of customers are
+    /// international and visas run 90 ticks, with refreshes opened 20
+    /// ticks ahead, so expiries, renewals and lapses all fall inside a
+    /// short run. Restrictions draw complaints at the live rates.
+    pub fn build_test_with_visa_expiry(run_id: RunId, seed: u64, level: &str) -> SimResult<Self> {
+        let mut config = crate::config::SimConfig::default_test();
+        config.identity_address.international_customer_rate = 0.5;
+        config.visa_expiry.enabled = true;
+        for visa in &mut config.visa_expiry.validity {
+            visa.validity_ticks = 90;
+        }
+        config.visa_expiry.refresh_lead_ticks = 20;
+        config.visa_expiry.late_submission_ticks = 20;
+        config.visa_expiry.grace_ticks = 5;
+        config.visa_expiry.restriction_level = level.to_string();
+        for (event_type, probability, issue, priority) in [
+            ("account_restricted", 0.30, "account_access", "high"),
+            ("account_frozen", 0.80, "service_failure", "urgent"),
+        ] {
+            config.complaint_triggers.push(crate::config::ComplaintTrigger {
+                event_type: event_type.into(),
+                fee_type: None,
+                amount_threshold: None,
+                prior_breach: false,
+                probability,
+                issue_category: issue.into(),
+                priority: priority.into(),
+                sla_acknowledge_days: 1,
+                sla_resolve_days: 7,
+                channel: "phone".into(),
+            });
+        }
+        Self::build_test_with_config(run_id, seed, config)
+    }
+
+    /// Build a test engine with adverse media screening and the risk
+    /// lifecycle on. The feed runs at three times the live rate so hits,
+    /// namesakes and EDD triggers all turn up in a short run.
+    pub fn build_test_with_adverse_media(run_id: RunId, seed: u64) -> SimResult<Self> {
+        let mut config = crate::config::SimConfig::default_test();
+        config.risk_lifecycle.enabled = true;
+        config.adverse_media.enabled = true;
+        config.adverse_media.articles_per_tick = 6.0;
+        Self::build_test_with_config(run_id, seed, config)
+    }
+
+    /// Build a test engine with correspondent banking enabled, on short
+    /// due diligence cadences with respondents that often miss them.
+    pub fn build_test_with_correspondent_banking(run_id: RunId, seed: u64) -> SimResult<Self> {
+        let mut config = crate::config::SimConfig::default_test();
+        config.correspondent_banking.enabled = true;
+        config.correspondent_banking.response_rate = 0.50;
+        config.correspondent_banking.overdue_ticks = 10;
+        for dd in &mut config.correspondent_banking.due_diligence {
+            dd.interval_ticks = 20;
+        }
+        Self::build_test_with_config(run_id, seed, config)
+    }
+
+    /// Build a test engine with cross-border funnels on. Half of new
+    /// customers are international; a tenth of those run funnels and half
+    /// of the rest receive support wires from home.
+    pub fn build_test_with_cross_border(run_id: RunId, seed: u64) -> SimResult<Self> {
+        let mut config = crate::config::SimConfig::default_test();
+        config.cross_border.enabled = true;
+        config.cross_border.funnel_rate = 0.10;
+        config.cross_border.inbound_rate = 0.50;
+        config.identity_address.international_customer_rate = 0.5;
+        Self::build_test_with_config(run_id, seed, config)
+    }
+
+    /// Build a test engine on the business-day calendar, with cutoffs
+    /// and ACH returns on so batch rails have something to hold over a
+    /// weekend. Tick 1 is New Year's Day 2025, a Wednesday.
+    pub fn build_test_with_business_calendar(run_id: RunId, seed: u64) -> SimResult<Self> {
+        let mut config = crate::config::SimConfig::default_test();
+        config.calendar.enabled = true;
+        config.payment_cutoff.enabled = true;
+        config.ach_returns.enabled = true;
+        config.ach_returns.return_rate = 0.25;
+        Self::build_test_with_config(run_id, seed, config)
+    }
+
+    /// Build a test engine with the named subsystems disabled.
+    pub fn build_test_with_feature_flags(run_id: RunId, seed: u64, disabled: &[&str]) -> SimResult<Self> {
+        let mut config = crate::config::SimConfig::default_test();
+        config.feature_flags.disabled_subsystems = disabled.iter().map(|s| s.to_string()).collect();
+        Self::build_test_with_config(run_id, seed, config)
+    }
+
+    /// Build a test engine with role gates on commands.
+    pub fn build_test_with_authorization(run_id: RunId, seed: u64) -> SimResult<Self> {
+        let mut config = crate::config::SimConfig::default_test();
+        config.authorization.enabled = true;
+        Self::build_test_with_config(run_id, seed, config)
+    }
+
+    /// Build a test engine that warns when a desk's aged backlog grows by
+    /// `threshold` items in a week.
+    pub fn build_test_with_aged_backlog_warning(run_id: RunId, seed: u64, threshold: u64) -> SimResult<Self> {
+        let mut config = crate::config::SimConfig::default_test();
+        config.complaint_analytics.early_warning_indicators.aged_backlog_growth_warning = Some(threshold);
+        Self::build_test_with_config(run_id, seed, config)
+    }
+
+    /// Build a test engine whose complaint triggers are scaled by
+    /// `propensity`.
+    pub fn build_test_with_complaint_propensity(
+        run_id: RunId,
+        seed: u64,
+        propensity: crate::config::ComplaintPropensity,
+    ) -> SimResult<Self> {
+        let mut config = crate::config::SimConfig::default_test();
+        config.complaint_propensity = propensity;
+        Self::build_test_with_config(run_id, seed, config)
+    }
+
+    /// Build a test engine with the deposit run scenario on and a shock
+    /// scripted for tick 10. The test population's balances are small, so
+    /// the insured limit is cut to $10k to leave most of them uninsured.
+    pub fn build_test_with_deposit_run(run_id: RunId, seed: u64) -> SimResult<Self> {
+        let mut config = crate::config::SimConfig::default_test();
+        config.deposit_run.enabled = true;
+        config.deposit_run.shock_tick = Some(10);
+        config.deposit_run.insured_limit = 10_000.0;
+        Self::build_test_with_config(run_id, seed, config)
+    }
+
+    /// Build a test engine with business email compromise on. Invoices
+    /// arrive far more often than live, and every spoofed one is paid, so
+    /// the test population's handful of businesses sees both kinds; the
+    /// desk recalls alerted wires after `desk_recall_ticks`, if set.
+    pub fn build_test_with_bec(run_id: RunId, seed: u64, desk_recall_ticks: Option<Tick>) -> SimResult<Self> {
+        let mut config = crate::config::SimConfig::default_test();
+        config.bec.enabled = true;
+        config.bec.spoofed_invoice_rate = 0.03;
+        config.bec.fall_rate = 1.0;
+        config.bec.vendor_invoice_rate = 0.20;
+        config.bec.desk_recall_ticks = desk_recall_ticks;
+        Self::build_test_with_config(run_id, seed, config)
+    }
+
+    /// Build a test engine with check fraud on and the check rail added.
+    /// Checks are written and deposited far more often than live, with a
+    /// fraud wave from tick 10 to 19; the desk reviews `review_capacity`
+    /// flagged items a tick.
+    pub fn build_test_with_check_fraud(run_id: RunId, seed: u64, review_capacity: usize) -> SimResult<Self> {
+        let mut config = crate::config::SimConfig::default_test();
+        config.payment_hub.rails.push(crate::config::PaymentRailConfig {
+            rail_id: "check".into(),
+            rail_type: "check".into(),
+            latency_type: "batch".into(),
+            settlement_delay_ticks: 1,
+            fraud_risk_multiplier: 1.8,
+            operational_risk_base: 0.002,
+            batch_window_ticks: Some(1),
+            cutoff_time_tick: None,
+            irrevocable: false,
+        });
+        config.check_fraud.enabled = true;
+        config.check_fraud.check_rate = 0.30;
+        config.check_fraud.washed_rate = 0.02;
+        config.check_fraud.deposit_rate = 0.05;
+        config.check_fraud.counterfeit_rate = 0.01;
+        config.check_fraud.wave_start_tick = Some(10);
+        config.check_fraud.wave_ticks = 10;
+        config.check_fraud.review_capacity = review_capacity;
+        Self::build_test_with_config(run_id, seed, config)
+    }
+
+    /// Build a test engine with check fraud as in
+    /// `build_test_with_check_fraud` (desk reviewing 20 a tick) and
+    /// deposit holds on. Every hold policy is `hold_ticks`, statutory
+    /// limits permitting; accounts are new for their first 10 ticks and
+    /// deposits over $2,000 are large items. Holds can prompt complaints.
+    pub fn build_test_with_deposit_holds(run_id: RunId, seed: u64, hold_ticks: Tick) -> SimResult<Self> {
+        let mut config = crate::config::SimConfig::default_test();
+        config.payment_hub.rails.push(crate::config::PaymentRailConfig {
+            rail_id: "check".into(),
+            rail_type: "check".into(),
+            latency_type: "batch".into(),
+            settlement_delay_ticks: 1,
+            fraud_risk_multiplier: 1.8,
+            operational_risk_base: 0.002,
+            batch_window_ticks: Some(1),
+            cutoff_time_tick: None,
+            irrevocable: false,
+        });
+        config.check_fraud.enabled = true;
+        config.check_fraud.check_rate = 0.30;
+        config.check_fraud.washed_rate = 0.02;
+        config.check_fraud.deposit_rate = 0.05;
+        config.check_fraud.counterfeit_rate = 0.01;
+        config.check_fraud.wave_start_tick = Some(10);
+        config.check_fraud.wave_ticks = 10;
+        config.check_fraud.review_capacity = 20;
+        config.deposit_holds.enabled = true;
+        for rule in &mut config.deposit_holds.policies {
+            rule.hold_ticks = hold_ticks;
+        }
+        config.deposit_holds.new_account_ticks = 10;
+        config.deposit_holds.large_item_threshold = 2_000.0;
+        config.complaint_triggers.push(crate::config::ComplaintTrigger {
+            event_type: "funds_held".into(),
+            fee_type: None,
+            amount_threshold: None,
+            prior_breach: false,
+            probability: 0.05,
+            issue_category: "account_access".into(),
+            priority: "standard".into(),
+            sla_acknowledge_days: 1,
+            sla_resolve_days: 10,
+            channel: "phone".into(),
+        });
+        Self::build_test_with_config(run_id, seed, config)
+    }
+
+    /// Build a test engine streaming its events to `path`, rotating at
+    /// `rotate_bytes` and keeping two rotated files.
+    pub fn build_test_with_event_stream(
+        run_id: RunId,
+        seed: u64,
+        path: &str,
+        rotate_bytes: Option<u64>,
+    ) -> SimResult<Self> {
+        let mut config = crate::config::SimConfig::default_test();
+        config.event_stream.enabled = true;
+        config.event_stream.path = path.into();
+        config.event_stream.rotate_bytes = rotate_bytes;
+        config.event_stream.keep_files = 2;
+        Self::build_test_with_config(run_id, seed, config)
+    }
+
+    /// Build a test engine that keeps an evidence bundle with every SAR
+    /// and dispute, with case documents going missing at
+    /// `missing_probability`, SAR decisions on and a 20-tick exam cycle.
+    pub fn build_test_with_evidence_bundles(run_id: RunId, seed: u64, missing_probability: f64) -> SimResult<Self> {
+        let mut config = crate::config::SimConfig::default_test();
+        config.evidence_bundles.enabled = true;
+        config.case_documents.enabled = true;
+        config.case_documents.missing_probability = missing_probability;
+        config.sar_decision.enabled = true;
+        config.regulatory_exam.enabled = true;
+        config.regulatory_exam.exam_interval_ticks = 20;
+        config.regulatory_exam.exam_duration_ticks = 5;
+        Self::build_test_with_config(run_id, seed, config)
+    }
+
+    /// Build a test engine grouping AML alerts into cases within
+    /// `window_ticks`, across relationships of strength 0.7 or more.
+    pub fn build_test_with_alert_grouping(run_id: RunId, seed: u64, window_ticks: Tick) -> SimResult<Self> {
+        let mut config = crate::config::SimConfig::default_test();
+        config.alert_grouping.enabled = true;
+        config.alert_grouping.window_ticks = window_ticks;
+        Self::build_test_with_config(run_id, seed, config)
+    }
+
+    /// Build a test engine recording operational losses and estimating
+    /// the capital held against them each quarter, with an exam every 20
+    /// ticks so fines are booked.
+    pub fn build_test_with_operational_risk(run_id: RunId, seed: u64) -> SimResult<Self> {
+        let mut config = crate::config::SimConfig::default_test();
+        config.operational_risk.enabled = true;
+        config.regulatory_exam.enabled = true;
+        config.regulatory_exam.exam_interval_ticks = 20;
+        config.regulatory_exam.exam_duration_ticks = 5;
+        Self::build_test_with_config(run_id, seed, config)
+    }
+
+    /// Build a test engine that closes each quarter: accruals booked into
+    /// the P&L and a checklist opened for sign-off.
+    pub fn build_test_with_quarter_close(run_id: RunId, seed: u64) -> SimResult<Self> {
+        let mut config = crate::config::SimConfig::default_test();
+        config.quarter_close.enabled = true;
+        Self::build_test_with_config(run_id, seed, config)
+    }
+
+    /// Build a test engine spreading shared opex into segment P&L by the
+    /// test default's allocation rules.
+    pub fn build_test_with_opex_allocation(run_id: RunId, seed: u64) -> SimResult<Self> {
+        let mut config = crate::config::SimConfig::default_test();
+        config.opex_allocation.enabled = true;
+        Self::build_test_with_config(run_id, seed, config)
+    }
+
+    /// Build a test engine that judges churn predictions once their
+    /// horizons elapse.
+    pub fn build_test_with_churn_evaluation(run_id: RunId, seed: u64) -> SimResult<Self> {
+        let mut config = crate::config::SimConfig::default_test();
+        config.churn_evaluation.enabled = true;
+        Self::build_test_with_config(run_id, seed, config)
+    }
+
+    /// Build a test engine that scores complaint closures and reopens or
+    /// escalates the poor ones.
+    pub fn build_test_with_complaint_quality(run_id: RunId, seed: u64) -> SimResult<Self> {
+        let mut config = crate::config::SimConfig::default_test();
+        config.complaint_quality.enabled = true;
+        Self::build_test_with_config(run_id, seed, config)
+    }
+
+    /// Build a test engine that scores the composite stress index each
+    /// tick, with the reputation model it reads.
+    pub fn build_test_with_stress_index(run_id: RunId, seed: u64) -> SimResult<Self> {
+        let mut config = crate::config::SimConfig::default_test();
+        config.reputation.enabled = true;
+        config.stress_index.enabled = true;
+        Self::build_test_with_config(run_id, seed, config)
+    }
+
+    /// Build a test engine recording SAR decisions with QA re-reviewing
+    /// every one each month. Its reviewers never err, so a disagreement is
+    /// a real disposition error.
+    pub fn build_test_with_alert_qa(run_id: RunId, seed: u64) -> SimResult<Self> {
+        let mut config = crate::config::SimConfig::default_test();
+        config.sar_decision.enabled = true;
+        config.alert_qa.enabled = true;
+        config.alert_qa.sample_rate = 1.0;
+        config.alert_qa.reviewer_accuracy = 1.0;
+        Self::build_test_with_config(run_id, seed, config)
+    }
+
+    /// Build a test engine whose alerts the player triages, with SAR
+    /// decisions recorded so a closure without a filing can be missed.
+    pub fn build_test_with_alert_triage(run_id: RunId, seed: u64) -> SimResult<Self> {
+        let mut config = crate::config::SimConfig::default_test();
+        config.sar_decision.enabled = true;
+        config.alert_triage.enabled = true;
+        Self::build_test_with_config(run_id, seed, config)
+    }
+
+    /// Build a test engine that links alerts, hits, disputes and
+    /// complaints into investigation cases.
+    pub fn build_test_with_case_management(run_id: RunId, seed: u64) -> SimResult<Self> {
+        let mut config = crate::config::SimConfig::default_test();
+        config.case_management.enabled = true;
+        Self::build_test_with_config(run_id, seed, config)
+    }
+
+    /// Build a test engine with mule networks recruited early and often:
+    /// at most two at a time, of three to five mules.
+    pub fn build_test_with_mule_networks(run_id: RunId, seed: u64) -> SimResult<Self> {
+        let mut config = crate::config::SimConfig::default_test();
+        config.mule_networks.enabled = true;
+        config.mule_networks.seed_daily_rate = 0.25;
+        config.mule_networks.max_networks = 2;
+        config.mule_networks.max_mules = 5;
+        config.mule_networks.lifetime_ticks = 30;
+        Self::build_test_with_config(run_id, seed, config)
+    }
+
+    /// Build a test engine where half of medium, high and critical risk
+    /// customers structure cash, an episode every ten ticks, and the rule
+    /// tuning workflow to suppress STRUCT_9K with.
+    pub fn build_test_with_structuring(run_id: RunId, seed: u64) -> SimResult<Self> {
+        let mut config = crate::config::SimConfig::default_test();
+        config.structuring.enabled = true;
+        config.rule_tuning.enabled = true;
+        config.structuring.risk_levels = vec!["medium".into(), "high".into(), "critical".into()];
+        config.structuring.structurer_rate = 0.5;
+        config.structuring.episode_interval_ticks = 10;
+        Self::build_test_with_config(run_id, seed, config)
+    }
+
+    /// Build a test engine that routes debits through service channels,
+    /// where every transaction abandoned on a failing channel raises a
+    /// complaint.
+    pub fn build_test_with_channels(run_id: RunId, seed: u64) -> SimResult<Self> {
+        let mut config = crate::config::SimConfig::default_test();
+        config.channels.enabled = true;
+        config.complaint_triggers.push(crate::config::ComplaintTrigger {
+            event_type: "channel_outage".into(),
+            fee_type: None,
+            amount_threshold: None,
+            prior_breach: false,
+            probability: 1.0,
+            issue_category: "service_failure".into(),
+            priority: "standard".into(),
+            sla_acknowledge_days: 1,
+            sla_resolve_days: 10,
+            channel: "phone".into(),
+        });
+        Self::build_test_with_config(run_id, seed, config)
+    }
+
+    /// Build a test engine where scammers reach far more customers than
+    /// live, every one of them pays and reports it within three ticks,
+    /// and the desk decides claims under `policy` two ticks later. Every
+    /// declined claim raises a complaint.
+    pub fn build_test_with_app_fraud(run_id: RunId, seed: u64, policy: &str) -> SimResult<Self> {
+        let mut config = crate::config::SimConfig::default_test();
+        config.payment_hub.rails.push(crate::config::PaymentRailConfig {
+            rail_id: "FedNow".into(),
+            rail_type: "FedNow".into(),
+            latency_type: "instant".into(),
+            settlement_delay_ticks: 0,
+            fraud_risk_multiplier: 2.0,
+            operational_risk_base: 0.0015,
+            batch_window_ticks: None,
+            cutoff_time_tick: None,
+            irrevocable: true,
+        });
+        config.app_fraud.enabled = true;
+        config.app_fraud.scam_rate = 0.02;
+        config.app_fraud.fall_rate = 1.0;
+        config.app_fraud.report_rate = 1.0;
+        config.app_fraud.report_max_ticks = 3;
+        config.app_fraud.decision_ticks = 2;
+        config.app_fraud.reimbursement_policy = policy.to_string();
+        config.complaint_triggers.push(crate::config::ComplaintTrigger {
+            event_type: "app_claim_declined".into(),
+            fee_type: None,
+            amount_threshold: None,
+            prior_breach: false,
+            probability: 1.0,
+            issue_category: "unauthorized_transaction".into(),
+            priority: "high".into(),
+            sla_acknowledge_days: 1,
+            sla_resolve_days: 15,
+            channel: "phone".into(),
+        });
+        Self::build_test_with_config(run_id, seed, config)
+    }
+
+    /// Build a test engine that withdraws cash at a fleet of eight ATMs
+    /// which fault often, hit by a cash-out crew about one tick in four.
+    pub fn build_test_with_atm_network(run_id: RunId, seed: u64) -> SimResult<Self> {
+        let mut config = crate::config::SimConfig::default_test();
+        config.channels.enabled = true;
+        config.atm_network.enabled = true;
+        config.atm_network.atms = 8;
+        config.atm_network.fault_rate = 0.05;
+        config.atm_network.cash_out_rate = 0.25;
+        config.atm_network.cash_out_cards = 4;
+        Self::build_test_with_config(run_id, seed, config)
+    }
+
+    /// Build a test engine whose card purchases are made at twenty
+    /// merchants disputed at 2–8%, a few of which go bad for three weeks
+    /// and see their purchases disputed ten times as often.
+    pub fn build_test_with_merchants(run_id: RunId, seed: u64) -> SimResult<Self> {
+        let mut config = crate::config::SimConfig::default_test();
+        config.merchants.enabled = true;
+        config.merchants.count = 20;
+        for (tier, rate) in [("low", 0.02), ("medium", 0.04), ("high", 0.08)] {
+            config.merchants.fraud_rates.insert(tier.into(), rate);
+        }
+        config.merchants.bad_merchant_rate = 0.003;
+        config.merchants.bad_merchant_ticks = 21;
+        config.merchants.bad_merchant_multiplier = 10.0;
+        config.merchants.chargeback_alert_rate = 0.15;
+        Self::build_test_with_config(run_id, seed, config)
+    }
+
+    /// Build a test engine whose digital customers log in from their
+    /// devices and pay through channels, with an account takeover from
+    /// one of two fraudster devices about one tick in three.
+    pub fn build_test_with_devices(run_id: RunId, seed: u64) -> SimResult<Self> {
+        let mut config = crate::config::SimConfig::default_test();
+        config.channels.enabled = true;
+        config.devices.enabled = true;
+        config.devices.takeover_rate = 0.3;
+        config.devices.farm_devices = 2;
+        Self::build_test_with_config(run_id, seed, config)
+    }
+
+    /// Build a test engine where one new customer in fifty is a listed
+    /// party, among enough customers for some namesakes.
+    pub fn build_test_with_sanctions_screening(run_id: RunId, seed: u64) -> SimResult<Self> {
+        let mut config = crate::config::SimConfig::default_test();
+        config.initial_population = 400;
+        config.sanctions_screening.listed_party_rate = 0.02;
+        Self::build_test_with_config(run_id, seed, config)
+    }
+
+    /// Build a test engine with case workers staffing every queue at the
+    /// test population's size.
+    pub fn build_test_with_staffing(run_id: RunId, seed: u64) -> SimResult<Self> {
+        let mut config = crate::config::SimConfig::default_test();
+        config.staffing.enabled = true;
+        for queue in &mut config.staffing.queues {
+            queue.ftes = 1.0;
+        }
+        Self::build_test_with_config(run_id, seed, config)
+    }
+
+    /// Phase 4.77: build a test engine with `scenario` applied over the
+    /// test config.
+    pub fn build_test_with_scenario(run_id: RunId, seed: u64, scenario: &ScenarioFile) -> SimResult<Self> {
+        let mut config = crate::config::SimConfig::default_test();
+        scenario.apply(&mut config)?;
+        Self::build_test_with_config(run_id, seed, config)
+    }
+
     fn build_test_with_config(run_id: RunId, seed: u64, config: crate::config::SimConfig) -> SimResult<Self> {
         // Use a temp file so reopen() works (in-memory doesn't share across connections)
         let temp_path = format!("./test_{}.db", uuid::Uuid::new_v4());
         let store = SimStore::open(&temp_path)?;
         store.migrate()?;
         store.insert_run(&run_id, seed, "0.1.0-test")?;
+        Self::build_with_config(run_id, seed, &store, config)
+    }
 
+    /// Build on an open, migrated store that already holds the run, with
+    /// the given config. The store must be reopenable (a file or a
+    /// shared-cache memory URI) so each subsystem gets its own connection.
+    pub fn build_with_config(
+        run_id: RunId,
+        seed: u64,
+        store: &SimStore,
+        config: crate::config::SimConfig,
+    ) -> SimResult<Self> {
         let store_customer = store.reopen()?;
         let store_txn = store.reopen()?;
         let store_complaint = store.reopen()?;
@@ -299,11 +1449,41 @@ impl SimEngine {
         let store_transaction_monitoring = store.reopen()?;
         let store_regulatory_exam = store.reopen()?;
         let store_reputation = store.reopen()?;
+        let store_credit_bureau = store.reopen()?;
+        let store_branch = store.reopen()?;
+        let store_fair_lending = store.reopen()?;
+        let store_outreach = store.reopen()?;
+        let store_staffing = store.reopen()?;
+        let store_lending = store.reopen()?;
+        let store_case = store.reopen()?;
+        let store_macro = store.reopen()?;
 
         let mut engine = SimEngine::new(run_id.clone(), seed, store.reopen()?);
         engine.resolution_codes = config.resolution_codes.clone();
+        engine.resolution_templates = config.resolution_templates.clone();
+        engine.population_scale = config.population_scale;
+        engine.set_event_log_retention(config.event_log_retention.clone());
+        engine.set_event_stream(&config.event_stream)?;
+        engine.satisfaction = config.satisfaction_dynamics.enabled.then(|| {
+            crate::satisfaction::SatisfactionDynamics::new(run_id.clone(), config.satisfaction_dynamics.clone())
+        });
+        engine.authorizer = config.authorization.enabled.then(|| {
+            crate::authorization::Authorizer::new(
+                run_id.clone(),
+                config.authorization.clone(),
+                &config.risk_appetite.dials,
+            )
+        });
 
-        engine.register(SubsystemSlot::Macro, Box::new(MacroSubsystem::new()));
+        engine.register(
+            SubsystemSlot::Macro,
+            Box::new(MacroSubsystem::new(
+                run_id.clone(),
+                &config.macro_regime,
+                config.rate_environment.clone(),
+                store_macro,
+            )),
+        );
         engine.register(
             SubsystemSlot::Customer,
             Box::new(crate::customer_subsystem::CustomerSubsystem::new(
@@ -312,6 +1492,15 @@ impl SimEngine {
                 store_customer,
             )),
         );
+        // Phase 4.32: Outreach (after Customer, before Offer)
+        engine.register(
+            SubsystemSlot::Outreach,
+            Box::new(crate::outreach_subsystem::OutreachSubsystem::new(
+                run_id.clone(),
+                config.contact_fatigue.clone(),
+                store_outreach,
+            )),
+        );
         // Phase 2.2: Offer (after Customer)
         engine.register(
             SubsystemSlot::Offer,
@@ -334,6 +1523,24 @@ impl SimEngine {
             SubsystemSlot::Transaction,
             Box::new(crate::transaction_subsystem::TransactionSubsystem::new(
                 run_id.clone(),
+                config.protected_funds.clone(),
+                config.ach_origination.clone(),
+                config.external_links.clone(),
+                config.customer_limits.clone(),
+                config.time_acceleration.clone(),
+                config.hardship.clone(),
+                config.remittance.clone(),
+                config.visa_expiry.clone(),
+                config.cross_border.clone(),
+                config.bec.clone(),
+                config.check_fraud.clone(),
+                config.deposit_holds.clone(),
+                config.mule_networks.clone(),
+                config.structuring.clone(),
+                config.channels.clone(),
+                config.app_fraud.clone(),
+                config.atm_network.clone(),
+                config.devices.clone(),
                 store_txn,
             )),
         );
@@ -343,7 +1550,22 @@ impl SimEngine {
             Box::new(crate::payment_hub_subsystem::PaymentHubSubsystem::new(
                 run_id.clone(),
                 config.payment_hub.clone(),
+                config.payment_cutoff.clone(),
+                config.ach_returns.clone(),
+                config.ach_origination.clone(),
+                config.correspondent_banking.clone(),
+                config.calendar.clone(),
+                config.merchants.clone(),
                 store_payment_hub,
+            )?),
+        );
+        // Phase 4.80: Staffing (before the subsystems that work its queues)
+        engine.register(
+            SubsystemSlot::Staffing,
+            Box::new(crate::staffing_subsystem::StaffingSubsystem::new(
+                run_id.clone(),
+                config.staffing.clone(),
+                store_staffing,
             )),
         );
         // Phase 3.2: Reconciliation (after PaymentHub)
@@ -352,6 +1574,9 @@ impl SimEngine {
             Box::new(crate::reconciliation_subsystem::ReconciliationSubsystem::new(
                 run_id.clone(),
                 config.reconciliation.clone(),
+                config.network_invoice.clone(),
+                config.settlement_accounts.clone(),
+                config.payment_hub.rails.clone(),
                 store_recon,
             )),
         );
@@ -360,6 +1585,9 @@ impl SimEngine {
             SubsystemSlot::CardDispute,
             Box::new(crate::card_dispute_subsystem::CardDisputeSubsystem::new(
                 run_id.clone(),
+                config.case_documents.clone(),
+                config.evidence_bundles.clone(),
+                config.merchants.clone(),
                 store_card_dispute,
             )),
         );
@@ -368,6 +1596,12 @@ impl SimEngine {
             SubsystemSlot::FraudDetection,
             Box::new(crate::fraud_detection_subsystem::FraudDetectionSubsystem::new(
                 run_id.clone(),
+                config.bec.clone(),
+                config.check_fraud.clone(),
+                config.app_fraud.clone(),
+                config.atm_network.clone(),
+                config.devices.clone(),
+                &config.payment_hub.rails,
                 store_fraud_detection,
             )),
         );
@@ -376,6 +1610,8 @@ impl SimEngine {
             SubsystemSlot::AMLScreening,
             Box::new(crate::aml_screening_subsystem::AMLScreeningSubsystem::new(
                 run_id.clone(),
+                config.adverse_media.clone(),
+                config.sanctions_screening.clone(),
                 store_aml_screening,
             )),
         );
@@ -384,7 +1620,38 @@ impl SimEngine {
             SubsystemSlot::TransactionMonitoring,
             Box::new(crate::transaction_monitoring_subsystem::TransactionMonitoringSubsystem::new(
                 run_id.clone(),
+                config.risk_lifecycle.clone(),
+                config.sar_decision.clone(),
+                config.rule_tuning.clone(),
+                config.coverage_assessment.clone(),
+                config.transaction_enrichment.clone(),
+                config.remittance.clone(),
+                config.correspondent_banking.clone(),
+                config.cross_border.clone(),
+                config.calendar.clone(),
+                config.evidence_bundles.clone(),
+                config.alert_grouping.clone(),
+                config.alert_qa.clone(),
+                config.alert_triage.clone(),
                 store_transaction_monitoring,
+            )?),
+        );
+        // Phase 4.1: Credit Bureau (before Complaint — files FCRA disputes as complaints)
+        engine.register(
+            SubsystemSlot::CreditBureau,
+            Box::new(crate::credit_bureau_subsystem::CreditBureauSubsystem::new(
+                run_id.clone(),
+                config.credit_bureau.clone(),
+                store_credit_bureau,
+            )),
+        );
+        // Phase 4.82: Lending (after the day's transactions land, before Economics)
+        engine.register(
+            SubsystemSlot::Lending,
+            Box::new(crate::lending_subsystem::LendingSubsystem::new(
+                run_id.clone(),
+                config.lending.clone(),
+                store_lending,
             )),
         );
         engine.register(
@@ -393,6 +1660,16 @@ impl SimEngine {
                 run_id.clone(),
                 config.clone(),
                 store_complaint,
+            )?),
+        );
+        // Phase 4.86: Case management (after Complaint — links the day's alerts, hits, disputes and complaints)
+        engine.register(
+            SubsystemSlot::Case,
+            Box::new(crate::case_subsystem::CaseSubsystem::new(
+                run_id.clone(),
+                config.case_management.clone(),
+                config.sar_decision.clone(),
+                store_case,
             )),
         );
         // Phase 2.1: Pricing (runs before Economics)
@@ -432,6 +1709,24 @@ impl SimEngine {
                 store_risk_appetite,
             )),
         );
+        // Phase 4.3: Branch network
+        engine.register(
+            SubsystemSlot::Branch,
+            Box::new(crate::branch_subsystem::BranchSubsystem::new(
+                run_id.clone(),
+                config.branch_network.clone(),
+                store_branch,
+            )),
+        );
+        // Phase 4.4: Fair lending (after Branch — reviews read this tick's fees, closures, offers)
+        engine.register(
+            SubsystemSlot::FairLending,
+            Box::new(crate::fair_lending_subsystem::FairLendingSubsystem::new(
+                run_id.clone(),
+                config.fair_lending.clone(),
+                store_fair_lending,
+            )),
+        );
         // Phase 3.3: Incident & Outage (after all other subsystems)
         engine.register(
             SubsystemSlot::Incident,
@@ -447,6 +1742,7 @@ impl SimEngine {
             Box::new(crate::regulatory_exam_subsystem::RegulatoryExamSubsystem::new(
                 run_id.clone(),
                 config.regulatory_exam.clone(),
+                config.enforcement.clone(),
                 store_regulatory_exam,
             )),
         );
@@ -454,48 +1750,392 @@ impl SimEngine {
         engine.register(
             SubsystemSlot::Reputation,
             Box::new(crate::reputation_subsystem::ReputationSubsystem::new(
-                run_id,
+                run_id.clone(),
                 config.reputation,
+                crate::stress_index::StressIndex::new(
+                    run_id,
+                    config.stress_index,
+                    config.branch_network.customers_per_staff,
+                ),
                 store_reputation,
             )),
         );
+        engine.mirror = StateMirror::load(&engine.store, &engine.run_id)?.into();
+        engine.scripts = (config.scenario_scripts.enabled && !config.scenario_scripts.scripts.is_empty())
+            .then(|| crate::scenario_script::ScenarioScripts::new(&engine.run_id, &config.scenario_scripts, &engine.store))
+            .transpose()?;
+        engine.apply_feature_flags(&config.feature_flags)?;
+        Ok(engine)
+    }
+
+    /// Phase 4.76: pick up a stored run where it stopped, after a crash
+    /// or on purpose. Builds the engine as `build_with_scenario` does,
+    /// with the run's own seed and stored scenario, then restores it; see
+    /// `restore`.
+    pub fn resume(run_id: RunId, store: &SimStore, data_dir: &str, scale: f64) -> anyhow::Result<Self> {
+        let run = Self::stored_run(&run_id, store)?;
+        let scenario = run.provenance.scenario.unwrap_or_default();
+        let mut engine = Self::build_with_scenario(run_id, run.seed, store, data_dir, scale, &scenario)?;
+        engine.restore()?;
+        Ok(engine)
+    }
+
+    /// Like `resume`, with the config given rather than loaded.
+    pub fn resume_with_config(
+        run_id: RunId,
+        store: &SimStore,
+        config: crate::config::SimConfig,
+    ) -> SimResult<Self> {
+        let seed = Self::stored_run(&run_id, store)?.seed;
+        let mut engine = Self::build_with_config(run_id, seed, store, config)?;
+        engine.restore()?;
         Ok(engine)
     }
 
+    fn stored_run(run_id: &str, store: &SimStore) -> SimResult<crate::provenance::RunDescription> {
+        store
+            .describe_run(run_id)?
+            .ok_or_else(|| anyhow::anyhow!("no run '{run_id}' in the store").into())
+    }
+
+    /// Bring a freshly built engine up to the last tick its run logged.
+    ///
+    /// Subsystems load their state from the latest snapshot at or before
+    /// that tick, then replay the events they logged on every tick after
+    /// it. RNG streams are derived from the seed and the tick, so setting
+    /// the clock is all they need. Commands sent after the last tick are
+    /// queued for the next one again.
+    ///
+    /// A tick cut short by a crash is not rolled back: what it wrote
+    /// stays and the run carries on from the tick after. Breakpoints are
+    /// not stored and must be set again.
+    fn restore(&mut self) -> SimResult<()> {
+        let Some(last_tick) = self.store.last_logged_tick(&self.run_id)? else {
+            return Ok(());
+        };
+        let snapshot = self.snapshot_at(last_tick)?;
+        let from = snapshot.as_ref().map_or(0, |s| s.tick);
+        if let Some(mut snapshot) = snapshot {
+            for (_, subsystem) in &mut self.subsystems {
+                if let Some(state) = snapshot.subsystems.remove(subsystem.name()) {
+                    subsystem.load_state(state)?;
+                }
+            }
+        }
+
+        for tick in from + 1..=last_tick {
+            let entries = event_log(&self.backend, &self.store).events_for_tick(&self.run_id, tick)?;
+            for (_, subsystem) in &mut self.subsystems {
+                let events = entries
+                    .iter()
+                    .filter(|e| e.subsystem == subsystem.name())
+                    .map(|e| serde_json::from_str(&e.payload))
+                    .collect::<Result<Vec<SimEvent>, _>>()?;
+                subsystem.replay(tick, &events)?;
+            }
+        }
+
+        self.clock.current_tick = last_tick;
+        self.clock.pause();
+        for command in self.store.clock_commands(&self.run_id)? {
+            self.apply_clock_command(&command);
+        }
+        for (command_id, command_type) in self.store.player_commands_at(&self.run_id, last_tick)? {
+            self.pending_commands.push(SimEvent::PlayerCommandReceived {
+                tick: last_tick,
+                command_id: command_id.to_string(),
+                command_type,
+            });
+        }
+        if let Some(scripts) = &mut self.scripts {
+            for payload in self.store.logged_event_payloads(&self.run_id, "scenario_script_failed")? {
+                if let SimEvent::ScenarioScriptFailed { script, .. } = serde_json::from_str(&payload)? {
+                    scripts.mark_failed(&script);
+                }
+            }
+        }
+        tracing::info!("resumed {} at tick {last_tick} (snapshot at tick {from})", self.run_id);
+        Ok(())
+    }
+
     /// Register a subsystem. Call in the documented execution order.
     pub fn register(&mut self, slot: SubsystemSlot, subsystem: Box<dyn SimSubsystem>) {
         self.subsystems.push((slot, subsystem));
     }
 
-    /// Submit a player command to be processed on the next tick.
-    pub fn submit_command(&mut self, cmd: crate::command::PlayerCommand) -> SimResult<()> {
-        let command_id =
-            self.store
-                .store_player_command(&self.run_id, self.clock.current_tick, &cmd)?;
+    /// Phase 4.42: drop the subsystems the scenario disables, once all
+    /// are registered, and record the flags on the run.
+    pub fn apply_feature_flags(&mut self, flags: &crate::config::FeatureFlagsConfig) -> SimResult<()> {
+        flags.validate(&self.subsystems())?;
+        self.subsystems.retain(|(_, subsystem)| !flags.is_disabled(subsystem.name()));
+        self.store.record_feature_flags(&self.run_id, flags)
+    }
 
-        let cmd_type = match &cmd {
-            crate::command::PlayerCommand::Pause => "pause",
-            crate::command::PlayerCommand::Resume => "resume",
-            crate::command::PlayerCommand::SetSpeed { .. } => "set_speed",
-            crate::command::PlayerCommand::CloseComplaint { .. } => "close_complaint",
-            crate::command::PlayerCommand::SetProductFee { .. } => "set_product_fee",
-            crate::command::PlayerCommand::SetRiskDial { .. } => "set_risk_dial",
-        };
+    /// Submit a player command to be processed on the next tick. Returns
+    /// the id it was logged under.
+    ///
+    /// Pause, resume and speed changes are the clock's, and take effect
+    /// at once; they are logged like any other command.
+    pub fn submit_command(&mut self, cmd: crate::command::PlayerCommand) -> SimResult<String> {
+        let command_id = self
+            .store
+            .store_player_command(&self.run_id, self.clock.current_tick, &cmd)?
+            .to_string();
+        self.apply_clock_command(&cmd);
 
         self.pending_commands.push(SimEvent::PlayerCommandReceived {
             tick: self.clock.current_tick,
-            command_id: command_id.to_string(),
-            command_type: cmd_type.to_string(),
+            command_id: command_id.clone(),
+            command_type: cmd.name().to_string(),
         });
 
-        Ok(())
+        Ok(command_id)
+    }
+
+    fn apply_clock_command(&mut self, cmd: &crate::command::PlayerCommand) {
+        use crate::command::PlayerCommand;
+        match cmd {
+            PlayerCommand::Pause => self.clock.held = true,
+            PlayerCommand::Resume => self.clock.held = false,
+            PlayerCommand::SetSpeed { speed } => self.clock.set_speed(*speed),
+            _ => {}
+        }
+    }
+
+    /// The run's command role gates; None when every seat may send
+    /// anything.
+    pub fn authorizer(&self) -> Option<&crate::authorization::Authorizer> {
+        self.authorizer.as_ref()
+    }
+
+    /// Whether `code` is one of the configured complaint resolution codes.
+    pub fn has_resolution_code(&self, code: &str) -> bool {
+        self.resolution_codes.contains_key(code)
+    }
+
+    /// A configured `bulk_resolve` template.
+    pub fn resolution_template(&self, template_id: &str) -> Option<&ResolutionTemplate> {
+        self.resolution_templates.get(template_id)
     }
 
     /// Advance one tick. This is the core simulation step.
+    ///
+    /// A fatal error halts the run: the tick may be half-written, so every
+    /// later call fails with `RunHalted` instead of building on it.
     pub fn tick(&mut self) -> SimResult<Vec<SimEvent>> {
+        if let Some((tick, reason)) = &self.halted {
+            return Err(SimError::RunHalted {
+                tick: *tick,
+                reason: reason.clone(),
+            });
+        }
+        let result = self.step();
+        if let Err(e) = &result {
+            if e.is_fatal() {
+                tracing::error!(run_id = %self.run_id, tick = self.clock.current_tick, "run halted: {e}");
+                self.halted = Some((self.clock.current_tick, e.to_string()));
+            }
+        }
+        result
+    }
+
+    /// The tick and reason a fatal error stopped the run at, if it has.
+    pub fn halted(&self) -> Option<(Tick, &str)> {
+        self.halted.as_ref().map(|(tick, reason)| (*tick, reason.as_str()))
+    }
+
+    /// Compact the event log as `retention` says from the next tick on;
+    /// a disabled config keeps every event in detail.
+    pub fn set_event_log_retention(&mut self, retention: crate::config::EventLogRetentionConfig) {
+        self.event_log_retention = retention.enabled.then_some(retention);
+    }
+
+    /// Stream every event logged from now on as `stream` says; a disabled
+    /// config stops streaming.
+    pub fn set_event_stream(&mut self, stream: &crate::config::EventStreamConfig) -> SimResult<()> {
+        self.event_stream = crate::event_stream::EventStream::open(stream)?;
+        Ok(())
+    }
+
+    /// Phase 4.21: log events and save snapshots to `backend` from now
+    /// on instead of the SQLite store. Set it before the first tick.
+    pub fn set_backend(&mut self, backend: Box<dyn crate::store::backend::StoreBackend>) {
+        self.backend = Some(backend);
+    }
+
+    /// The seed this run was built with.
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    /// Share of the population this run simulates; divide counts and
+    /// dollar totals by it for full-bank figures.
+    pub fn population_scale(&self) -> f64 {
+        self.population_scale
+    }
+
+    /// The headline KPIs as of the last event logged; see `state_mirror`.
+    pub fn state_mirror(&self) -> StateMirror {
+        self.mirror.borrow().clone()
+    }
+
+    /// Reload the KPI mirror from the store, after writing to it other
+    /// than through a tick or a direct closure.
+    pub fn resync_state_mirror(&mut self) -> SimResult<()> {
+        *self.mirror.get_mut() = StateMirror::load(&self.store, &self.run_id)?;
+        Ok(())
+    }
+
+    /// Phase 4.68: run the scenario scripts over the tick just run and
+    /// carry out what they ask for.
+    fn run_scenario_scripts(&mut self, tick: Tick, tick_events: &mut Vec<SimEvent>) -> SimResult<()> {
+        let mut event_counts: HashMap<String, i64> = HashMap::new();
+        for event in tick_events.iter() {
+            *event_counts.entry(event_type_name(event).to_string()).or_default() += 1;
+        }
+        let kpis = self.mirror.get_mut().clone();
+        let Some(scripts) = &mut self.scripts else {
+            return Ok(());
+        };
+        let runs = scripts.run(tick, &kpis, event_counts);
+
+        let mut events = Vec::new();
+        for run in runs {
+            for key in &run.spent {
+                self.store.spend_script_once_key(&self.run_id, &run.script, key, tick)?;
+            }
+            match run.outcome {
+                Ok(actions) => {
+                    for action in actions {
+                        events.extend(self.apply_script_action(tick, &run.script, action)?);
+                    }
+                }
+                Err(error) => {
+                    tracing::warn!(script = %run.script, "scenario script failed and is dropped: {error}");
+                    events.push(SimEvent::ScenarioScriptFailed { tick, script: run.script, error });
+                }
+            }
+        }
+        for event in &events {
+            let entry = EventLogEntry {
+                id: None,
+                run_id: self.run_id.clone(),
+                tick,
+                subsystem: "scenario_script".to_string(),
+                event_type: event_type_name(event).to_string(),
+                payload: serde_json::to_string(event)?,
+            };
+            event_log(&self.backend, &self.store).append_event(&entry)?;
+            if let Some(stream) = &mut self.event_stream {
+                stream.append(&entry)?;
+            }
+            self.mirror.get_mut().observe(&self.store, &self.run_id, event)?;
+        }
+        tick_events.extend(events);
+        Ok(())
+    }
+
+    /// Phase 4.74: log a `BreakpointHit` for each breakpoint the tick
+    /// trips; `run_ticks` stops once any has.
+    fn check_breakpoints(&mut self, tick: Tick, tick_events: &mut Vec<SimEvent>) -> SimResult<()> {
+        let hits = self.breakpoints.check(&self.store, &self.run_id, tick, tick_events, self.mirror.get_mut())?;
+        for hit in hits {
+            tracing::info!(breakpoint = %hit.breakpoint_id, "breakpoint hit: {}", hit.detail);
+            let event = SimEvent::BreakpointHit {
+                tick,
+                breakpoint_id: hit.breakpoint_id.clone(),
+                detail: hit.detail.clone(),
+            };
+            let entry = EventLogEntry {
+                id: None,
+                run_id: self.run_id.clone(),
+                tick,
+                subsystem: "engine".to_string(),
+                event_type: event_type_name(&event).to_string(),
+                payload: serde_json::to_string(&event)?,
+            };
+            event_log(&self.backend, &self.store).append_event(&entry)?;
+            if let Some(stream) = &mut self.event_stream {
+                stream.append(&entry)?;
+            }
+            tick_events.push(event);
+            self.breakpoint_hits.push(hit);
+        }
+        Ok(())
+    }
+
+    /// Register a breakpoint, replacing any with its id; see `breakpoint`.
+    pub fn set_breakpoint(&mut self, breakpoint: crate::breakpoint::Breakpoint) -> SimResult<()> {
+        self.breakpoints.set(breakpoint)
+    }
+
+    /// Remove a breakpoint; false if none had the id.
+    pub fn clear_breakpoint(&mut self, id: &str) -> bool {
+        self.breakpoints.clear(id)
+    }
+
+    /// Every registered breakpoint, in the order set.
+    pub fn breakpoints(&self) -> Vec<crate::breakpoint::Breakpoint> {
+        self.breakpoints.list()
+    }
+
+    /// The breakpoints the last tick tripped, which stopped `run_ticks`
+    /// there; empty if it tripped none.
+    pub fn breakpoint_hits(&self) -> &[crate::breakpoint::BreakpointHit] {
+        &self.breakpoint_hits
+    }
+
+    fn apply_script_action(
+        &mut self,
+        tick: Tick,
+        script: &str,
+        action: crate::scenario_script::ScriptAction,
+    ) -> SimResult<Vec<SimEvent>> {
+        use crate::scenario_script::ScriptAction;
+        let fired = |action: &str, name: String, detail: String| SimEvent::ScenarioScriptFired {
+            tick,
+            script: script.to_string(),
+            action: action.to_string(),
+            name,
+            detail,
+        };
+        Ok(match action {
+            ScriptAction::Command { cmd, payload } => {
+                let result = crate::command_result::CommandResult::apply(self, &cmd, &payload, None)?;
+                let command_id = result.command_id.clone();
+                let detail = match result.into_result() {
+                    Err(e) => e.to_string(),
+                    Ok(_) => command_id.map_or("applied".into(), |id| format!("queued as {id}")),
+                };
+                vec![fired("command", cmd, detail)]
+            }
+            ScriptAction::RegulatorInquiry { reason } => {
+                if let Some(exam) = self.store.get_open_exam(&self.run_id)? {
+                    let detail = format!("exam already open; {reason}");
+                    return Ok(vec![fired("regulator_inquiry", exam.exam_id, detail)]);
+                }
+                let exam_id = format!("exam-inquiry-{tick}");
+                self.store.insert_regulatory_exam(&self.run_id, &exam_id, tick, "regulator", "inquiry")?;
+                vec![
+                    SimEvent::RegulatoryExamStarted {
+                        tick,
+                        exam_id: exam_id.clone(),
+                        examiner: "regulator".into(),
+                        scope: "inquiry".into(),
+                    },
+                    fired("regulator_inquiry", exam_id, reason),
+                ]
+            }
+            ScriptAction::Emit { name, detail } => vec![fired("emit", name, detail)],
+        })
+    }
+
+    fn step(&mut self) -> SimResult<Vec<SimEvent>> {
         assert!(!self.clock.paused, "tick() called on paused engine");
 
         let current_tick = self.clock.advance();
+        // Everything logged during the tick carries the run and tick
+        let _tick_span = tracing::info_span!("tick", run_id = %self.run_id, tick = current_tick).entered();
         let mut tick_events: Vec<SimEvent> = vec![SimEvent::TickStarted { tick: current_tick }];
 
         // Inject any pending player commands into this tick's event stream
@@ -506,6 +2146,7 @@ impl SimEngine {
         // Execute each subsystem in registration order.
         // Each subsystem sees all events emitted so far this tick.
         for (slot, subsystem) in &mut self.subsystems {
+            let _subsystem_span = tracing::info_span!("subsystem", subsystem = subsystem.name()).entered();
             let mut rng = self.rng_bank.for_subsystem_at_tick(*slot, current_tick);
             let new_events = subsystem.update(current_tick, &tick_events, &mut rng)?;
 
@@ -519,11 +2160,26 @@ impl SimEngine {
                     event_type: event_type_name(event).to_string(),
                     payload: serde_json::to_string(event)?,
                 };
-                self.store.append_event(&entry)?;
+                event_log(&self.backend, &self.store).append_event(&entry)?;
+                if let Some(stream) = &mut self.event_stream {
+                    stream.append(&entry)?;
+                }
+                self.mirror.get_mut().observe(&self.store, &self.run_id, event)?;
             }
 
             tick_events.extend(new_events);
         }
+        self.mirror.get_mut().sync_backlog(&self.store, &self.run_id)?;
+        if self.scripts.is_some() {
+            self.run_scenario_scripts(current_tick, &mut tick_events)?;
+        }
+        self.breakpoint_hits.clear();
+        if !self.breakpoints.is_empty() {
+            self.check_breakpoints(current_tick, &mut tick_events)?;
+        }
+        if let Some(stream) = &mut self.event_stream {
+            stream.flush()?;
+        }
 
         tick_events.push(SimEvent::TickCompleted { tick: current_tick });
 
@@ -532,11 +2188,27 @@ impl SimEngine {
             self.take_snapshot(current_tick)?;
         }
 
+        if let Some(retention) = &self.event_log_retention {
+            if current_tick > retention.detail_ticks
+                && current_tick.is_multiple_of(retention.compact_interval_ticks.max(1))
+            {
+                self.store.compact_event_log(
+                    &self.run_id,
+                    current_tick - retention.detail_ticks,
+                    retention.export_dir.as_deref(),
+                )?;
+            }
+        }
+
         Ok(tick_events)
     }
 
     /// Run n ticks in a loop. Used for testing and fast-forward.
+    /// Refused while the player has the run paused.
     pub fn run_ticks(&mut self, n: u64) -> SimResult<()> {
+        if self.clock.held {
+            return Err(SimError::command("tick", "the run is paused; send resume first"));
+        }
         // Emit RunInitialized at tick 0 so seed differences are observable.
         if self.clock.current_tick == 0 {
             let init_event = SimEvent::RunInitialized {
@@ -551,20 +2223,73 @@ impl SimEngine {
                 event_type: event_type_name(&init_event).to_string(),
                 payload: serde_json::to_string(&init_event)?,
             };
-            self.store.append_event(&entry)?;
+            event_log(&self.backend, &self.store).append_event(&entry)?;
+            if let Some(stream) = &mut self.event_stream {
+                stream.append(&entry)?;
+                stream.flush()?;
+            }
         }
         self.clock.resume();
+        let mut result = Ok(());
         for _ in 0..n {
-            self.tick()?;
+            result = self.tick().map(drop);
+            if result.is_err() || !self.breakpoint_hits.is_empty() {
+                break;
+            }
         }
         self.clock.pause();
-        Ok(())
+        result
+    }
+
+    /// Advance `steps` steps at the clock's speed: a tick each at normal
+    /// speed, a week of ticks accelerated, a month fast-forward.
+    pub fn run_steps(&mut self, steps: u64) -> SimResult<()> {
+        self.run_ticks(steps * self.clock.ticks_per_real_second() as u64)
     }
 
     /// Query events for a specific tick from the store.
     /// Used by the determinism test and replay tooling.
     pub fn store_events_for_tick(&self, run_id: &str, tick: Tick) -> SimResult<Vec<EventLogEntry>> {
-        self.store.events_for_tick(run_id, tick)
+        event_log(&self.backend, &self.store).events_for_tick(run_id, tick)
+    }
+
+    /// Phase 4.19: the post-run analysis report as of the current tick.
+    /// Used by sim-runner at run end.
+    pub fn post_mortem(&self) -> SimResult<crate::post_mortem::PostMortem> {
+        crate::post_mortem::PostMortem::build(&self.store, &self.run_id, self.clock.current_tick)
+    }
+
+    /// Phase 4.58: everything that happened to a customer, account or
+    /// dispute case so far, oldest first. Used by `get_entity_timeline`.
+    pub fn get_entity_timeline(
+        &self,
+        entity_type: crate::entity_timeline::EntityType,
+        entity_id: &str,
+    ) -> SimResult<crate::entity_timeline::EntityTimeline> {
+        crate::entity_timeline::EntityTimeline::query(&self.store, &self.run_id, entity_type, entity_id, None, None)
+    }
+
+    /// The registered subsystems in execution order, each with the
+    /// events and tables it declares. Used by `describe_engine`.
+    pub fn subsystems(&self) -> Vec<SubsystemInfo> {
+        let strings = |items: &[&str]| items.iter().map(|s| s.to_string()).collect();
+        self.subsystems
+            .iter()
+            .enumerate()
+            .map(|(order, (slot, subsystem))| {
+                let manifest = subsystem.manifest();
+                SubsystemInfo {
+                    name: subsystem.name().to_string(),
+                    slot: slot.name().to_string(),
+                    order,
+                    version: manifest.version,
+                    produces: strings(manifest.produces),
+                    consumes: strings(manifest.consumes),
+                    tables: strings(manifest.tables),
+                    requires: strings(manifest.requires),
+                }
+            })
+            .collect()
     }
 
     /// Query the MacroSubsystem's current state.
@@ -578,17 +2303,33 @@ impl SimEngine {
     }
 
     fn take_snapshot(&self, tick: Tick) -> SimResult<()> {
+        let mut subsystems = std::collections::BTreeMap::new();
+        for (_, subsystem) in &self.subsystems {
+            if let Some(state) = subsystem.save_state()? {
+                subsystems.insert(subsystem.name().to_string(), state);
+            }
+        }
         let snapshot = SimSnapshot {
             run_id: self.run_id.clone(),
             tick,
             clock: self.clock.clone(),
+            subsystems,
         };
-        let json = serde_json::to_string(&snapshot)?;
-        self.store.save_snapshot(&self.run_id, tick, &json)?;
-        log::debug!("Snapshot saved at tick {tick}");
+        let json = snapshot.to_json()?;
+        event_log(&self.backend, &self.store).save_snapshot(&self.run_id, tick, &json)?;
+        tracing::debug!("Snapshot saved at tick {tick}");
         Ok(())
     }
 
+    /// The latest snapshot at or before `tick`, upgraded to the current
+    /// layout. Errors if it was written by a newer crate.
+    pub fn snapshot_at(&self, tick: Tick) -> SimResult<Option<SimSnapshot>> {
+        event_log(&self.backend, &self.store)
+            .latest_snapshot_before(&self.run_id, tick)?
+            .map(|(_, json)| SimSnapshot::from_json(&json))
+            .transpose()
+    }
+
     // ── Complaint wrapper methods (for tests and sim-runner) ──────────────────
 
     pub fn store_complaint_count(&self, run_id: &str) -> SimResult<i64> {
@@ -610,7 +2351,8 @@ impl SimEngine {
         self.store.customer_satisfaction(run_id, customer_id)
     }
 
-    /// Close a complaint and apply the resolution's satisfaction delta directly.
+    /// Close a complaint and apply the resolution's satisfaction delta directly
+    /// (with diminishing returns on repeat goodwill when Phase 4.33 dynamics are on).
     /// Used by tests and the future UI layer for player-initiated resolutions.
     pub fn store_close_complaint_direct(
         &self,
@@ -623,12 +2365,26 @@ impl SimEngine {
         let complaint = self.store.get_complaint(run_id, complaint_id)?;
         self.store
             .close_complaint(run_id, complaint_id, tick, resolution_code, amount_refunded)?;
+        if run_id == self.run_id {
+            self.mirror.borrow_mut().sync_backlog(&self.store, run_id)?;
+        }
         if let Some(rc) = self.resolution_codes.get(resolution_code) {
-            self.store.update_customer_satisfaction(
-                run_id,
-                &complaint.customer_id,
-                rc.satisfaction_delta,
-            )?;
+            match &self.satisfaction {
+                Some(dynamics) => {
+                    dynamics.goodwill(
+                        &self.store,
+                        &complaint.customer_id,
+                        "complaint_resolution",
+                        rc.satisfaction_delta,
+                        tick,
+                    )?;
+                }
+                None => self.store.update_customer_satisfaction(
+                    run_id,
+                    &complaint.customer_id,
+                    rc.satisfaction_delta,
+                )?,
+            }
             self.store.adjust_customer_churn_risk(
                 run_id,
                 &complaint.customer_id,
@@ -878,11 +2634,306 @@ impl SimEngine {
     pub fn store_latest_reputation_score(&self, run_id: &str) -> SimResult<f64> {
         self.store.latest_reputation_score(run_id)
     }
+
+    // Phase 4.1: Credit Bureau test helpers
+
+    pub fn store_bureau_furnishing_count(&self, run_id: &str, errors_only: bool) -> SimResult<i64> {
+        self.store.bureau_furnishing_count(run_id, errors_only)
+    }
+
+    pub fn store_bureau_dispute_count(&self, run_id: &str) -> SimResult<i64> {
+        self.store.bureau_dispute_count(run_id)
+    }
+
+    pub fn store_bureau_penalty_total(&self, run_id: &str) -> SimResult<f64> {
+        self.store.bureau_penalty_total(run_id)
+    }
+
+    // Phase 4.2: Protected Funds test helpers
+
+    pub fn store_benefit_recipient_count(&self, run_id: &str) -> SimResult<i64> {
+        self.store.benefit_recipient_count(run_id)
+    }
+
+    pub fn store_benefit_deposit_count(&self, run_id: &str) -> SimResult<i64> {
+        self.store.benefit_deposit_count(run_id)
+    }
+
+    pub fn store_protected_funds_event_count(&self, run_id: &str, outcome: &str) -> SimResult<i64> {
+        self.store.protected_funds_event_count(run_id, outcome)
+    }
+
+    // Phase 4.3: Branch network helpers
+
+    pub fn store_branch_count(&self, run_id: &str, status: &str) -> SimResult<i64> {
+        self.store.branch_count(run_id, status)
+    }
+
+    pub fn store_open_branches(&self, run_id: &str) -> SimResult<Vec<crate::store::branch::BranchRow>> {
+        self.store.open_branches(run_id)
+    }
+
+    pub fn store_customer_branch_count(&self, run_id: &str, branch_id: &str) -> SimResult<i64> {
+        self.store.customer_branch_count(run_id, branch_id)
+    }
+
+    /// Latest monthly per-branch performance report.
+    pub fn store_branch_performance_report(
+        &self,
+        run_id: &str,
+    ) -> SimResult<Vec<crate::store::branch::BranchPerformanceRow>> {
+        self.store.latest_branch_performance(run_id)
+    }
+
+    pub fn store_branch_acquisition_count(&self, run_id: &str, branch_id: &str) -> SimResult<i64> {
+        self.store.branch_acquisition_count(run_id, branch_id)
+    }
+
+    // Phase 4.4: Fair lending test helpers
+
+    pub fn store_zip_demographics_count(&self, run_id: &str) -> SimResult<i64> {
+        self.store.zip_demographics_count(run_id)
+    }
+
+    pub fn store_fair_lending_review_count(&self, run_id: &str) -> SimResult<i64> {
+        self.store.fair_lending_review_count(run_id)
+    }
+
+    pub fn store_fair_lending_finding_count(&self, run_id: &str, status: &str) -> SimResult<i64> {
+        self.store.fair_lending_finding_count(run_id, status)
+    }
+
+    pub fn store_open_fair_lending_findings(
+        &self,
+        run_id: &str,
+    ) -> SimResult<Vec<crate::store::fair_lending::FairLendingFindingRow>> {
+        self.store.open_fair_lending_findings(run_id)
+    }
+
+    // Phase 4.5: Offer guardrail test helpers
+
+    pub fn store_offer_match_count(&self, run_id: &str, offer_id: &str) -> SimResult<i64> {
+        self.store.offer_match_count(run_id, offer_id)
+    }
+
+    pub fn store_offer_guardrail_assessment_count(&self, run_id: &str, outcome: &str) -> SimResult<i64> {
+        self.store.offer_guardrail_assessment_count(run_id, outcome)
+    }
+
+    pub fn store_zip_codes_by_group(&self, run_id: &str, majority_minority: bool) -> SimResult<Vec<String>> {
+        self.store.zip_codes_by_group(run_id, majority_minority)
+    }
+
+    // Phase 4.6: Card network invoice test helpers
+
+    pub fn store_network_invoice_count(&self, run_id: &str, status: Option<&str>) -> SimResult<i64> {
+        self.store.network_invoice_count(run_id, status)
+    }
+
+    pub fn store_open_network_invoice_exceptions(
+        &self,
+        run_id: &str,
+    ) -> SimResult<Vec<crate::store::network_invoice::NetworkInvoiceRow>> {
+        self.store.open_network_invoice_exceptions(run_id)
+    }
+
+    pub fn store_network_recovered_total(&self, run_id: &str) -> SimResult<f64> {
+        self.store.network_recovered_total(run_id)
+    }
+
+    // Phase 4.7: Settlement account test helpers
+
+    pub fn store_settlement_account_count(&self, run_id: &str) -> SimResult<i64> {
+        self.store.settlement_account_count(run_id)
+    }
+
+    pub fn store_settlement_positions(
+        &self,
+        run_id: &str,
+        rail_id: &str,
+    ) -> SimResult<Vec<crate::store::settlement::SettlementPositionRow>> {
+        self.store.settlement_positions(run_id, rail_id)
+    }
+
+    pub fn store_settlement_account_totals(&self, run_id: &str, rail_id: &str) -> SimResult<(f64, f64)> {
+        self.store.settlement_account_totals(run_id, rail_id)
+    }
+
+    pub fn store_settlement_alert_count(&self, run_id: &str, rail_id: &str) -> SimResult<i64> {
+        self.store.settlement_alert_count(run_id, rail_id)
+    }
+
+    // Phase 4.8: Payment cutoff test helpers
+
+    pub fn store_cutoff_rolls(
+        &self,
+        run_id: &str,
+        rail_id: &str,
+    ) -> SimResult<Vec<crate::store::payment_cutoff::CutoffRollRow>> {
+        self.store.cutoff_rolls(run_id, rail_id)
+    }
+
+    pub fn store_transaction_settlement_status(&self, run_id: &str, txn_id: &str) -> SimResult<String> {
+        self.store.transaction_settlement_status(run_id, txn_id)
+    }
+
+    // Phase 4.9: ACH return test helpers
+
+    pub fn store_ach_returns(&self, run_id: &str) -> SimResult<Vec<crate::store::ach_return::AchReturnRow>> {
+        self.store.ach_returns(run_id)
+    }
+
+    pub fn store_ach_disputes(&self, run_id: &str) -> SimResult<Vec<crate::store::ach_return::AchDisputeRow>> {
+        self.store.ach_disputes(run_id)
+    }
+
+    pub fn store_ach_origination_block_count(&self, run_id: &str) -> SimResult<i64> {
+        self.store.ach_origination_block_count(run_id)
+    }
+
+    pub fn store_interaction_count_by_type(&self, run_id: &str, interaction_type: &str) -> SimResult<i64> {
+        self.store.interaction_count_by_type(run_id, interaction_type)
+    }
+
+    // Phase 4.10: Customer-originated ACH test helpers
+
+    pub fn store_originated_ach_entries(
+        &self,
+        run_id: &str,
+    ) -> SimResult<Vec<crate::store::originated_ach::OriginatedAchRow>> {
+        self.store.originated_ach_entries(run_id)
+    }
+
+    pub fn store_odfi_return_reviews(
+        &self,
+        run_id: &str,
+    ) -> SimResult<Vec<crate::store::originated_ach::OdfiReturnReviewRow>> {
+        self.store.odfi_return_reviews(run_id)
+    }
+
+    // Phase 4.11: External account linking test helpers
+
+    pub fn store_external_links(
+        &self,
+        run_id: &str,
+    ) -> SimResult<Vec<crate::store::external_link::ExternalLinkRow>> {
+        self.store.external_links(run_id)
+    }
+
+    // Phase 4.12: Customer limit test helpers
+
+    pub fn store_limit_declines(
+        &self,
+        run_id: &str,
+    ) -> SimResult<Vec<crate::store::customer_limit::LimitDeclineRow>> {
+        self.store.limit_declines(run_id)
+    }
+
+    // Phase 4.13: Risk lifecycle test helpers
+
+    pub fn store_risk_tier_transitions(
+        &self,
+        run_id: &str,
+    ) -> SimResult<Vec<crate::store::risk_lifecycle::RiskTierTransitionRow>> {
+        self.store.risk_tier_transitions(run_id)
+    }
+
+    pub fn store_edd_reviews(
+        &self,
+        run_id: &str,
+    ) -> SimResult<Vec<crate::store::risk_lifecycle::EddReviewRow>> {
+        self.store.edd_reviews(run_id)
+    }
+
+    // Phase 4.14: SSN validation test helpers
+
+    pub fn store_ssn_validations(
+        &self,
+        run_id: &str,
+    ) -> SimResult<Vec<crate::store::ssn_validation::SsnValidationRow>> {
+        self.store.ssn_validations(run_id)
+    }
+
+    // Phase 4.15: identity verification test helpers
+
+    pub fn store_idv_checks(
+        &self,
+        run_id: &str,
+    ) -> SimResult<Vec<crate::store::idv_check::IdvCheckRow>> {
+        self.store.idv_checks(run_id)
+    }
+
+    // Phase 4.16: SAR decision test helpers
+
+    pub fn store_sar_decisions(
+        &self,
+        run_id: &str,
+    ) -> SimResult<Vec<crate::store::sar_decision::SarDecisionRow>> {
+        self.store.sar_decisions(run_id)
+    }
+
+    pub fn store_sar_quality_metrics(
+        &self,
+        run_id: &str,
+    ) -> SimResult<Vec<crate::store::sar_decision::SarQualityMetricsRow>> {
+        self.store.sar_quality_metrics(run_id)
+    }
+
+    // Phase 4.17: rule tuning test helpers
+
+    pub fn store_tuning_reviews(
+        &self,
+        run_id: &str,
+    ) -> SimResult<Vec<crate::store::rule_tuning::TuningReviewRow>> {
+        self.store.tuning_reviews(run_id)
+    }
+
+    pub fn store_rule_tunings(
+        &self,
+        run_id: &str,
+    ) -> SimResult<Vec<crate::store::rule_tuning::RuleTuningRow>> {
+        self.store.rule_tunings(run_id)
+    }
+
+    // Phase 4.18: coverage assessment test helpers
+
+    pub fn store_coverage_assessments(
+        &self,
+        run_id: &str,
+    ) -> SimResult<Vec<crate::store::coverage_assessment::CoverageAssessmentRow>> {
+        self.store.coverage_assessments(run_id)
+    }
+}
+
+/// The engine's event log and snapshot store: the backend if one is
+/// set, else the SQLite store. Takes the fields so the tick loop can
+/// call it while it holds the subsystems.
+fn event_log<'a>(
+    backend: &'a Option<Box<dyn crate::store::backend::StoreBackend>>,
+    store: &'a SimStore,
+) -> &'a dyn crate::store::backend::StoreBackend {
+    match backend {
+        Some(backend) => backend.as_ref(),
+        None => store,
+    }
+}
+
+/// Add the inactive "campaign_cash_50" cross-sell offer, launchable to
+/// existing customers, to a test config.
+fn add_test_campaign(config: &mut crate::config::SimConfig) {
+    let mut campaign = config.offers["signup_bonus_100"].clone();
+    campaign.offer_id = "campaign_cash_50".into();
+    campaign.label = "$50 Cross-Sell Bonus".into();
+    campaign.bonus_amount = 50.0;
+    campaign.requirements.new_to_bank_only = false;
+    campaign.eligibility.target_segments.clear();
+    campaign.active = false;
+    config.offers.insert(campaign.offer_id.clone(), campaign);
 }
 
 /// Extract a stable string name from a SimEvent variant.
 /// Used for the event_type column in event_log.
-fn event_type_name(event: &SimEvent) -> &'static str {
+pub(crate) fn event_type_name(event: &SimEvent) -> &'static str {
     match event {
         SimEvent::TickStarted { .. } => "tick_started",
         SimEvent::TickCompleted { .. } => "tick_completed",
@@ -958,5 +3009,194 @@ fn event_type_name(event: &SimEvent) -> &'static str {
         SimEvent::MOUReceived { .. } => "mou_received",
         // Phase 3.6: Reputation
         SimEvent::ReputationUpdated { .. } => "reputation_updated",
+        // Phase 4.1: Credit Bureau
+        SimEvent::BureauFurnished { .. } => "bureau_furnished",
+        SimEvent::BureauDisputeFiled { .. } => "bureau_dispute_filed",
+        SimEvent::BureauDisputeCorrected { .. } => "bureau_dispute_corrected",
+        SimEvent::BureauCorrectionOverdue { .. } => "bureau_correction_overdue",
+        // Phase 4.2: Protected Funds
+        SimEvent::ProtectedFundsViolation { .. } => "protected_funds_violation",
+        SimEvent::ProtectedDebitBlocked { .. } => "protected_debit_blocked",
+        // Phase 4.3: Branch Network
+        SimEvent::BranchOpened { .. } => "branch_opened",
+        SimEvent::BranchClosed { .. } => "branch_closed",
+        SimEvent::BranchCommandRejected { .. } => "branch_command_rejected",
+        SimEvent::BranchPerformanceReported { .. } => "branch_performance_reported",
+        SimEvent::BranchCustomersAcquired { .. } => "branch_customers_acquired",
+        // Phase 4.4: Fair Lending
+        SimEvent::FairLendingReviewCompleted { .. } => "fair_lending_review_completed",
+        SimEvent::FairLendingFindingRaised { .. } => "fair_lending_finding_raised",
+        SimEvent::FairLendingFindingResolved { .. } => "fair_lending_finding_resolved",
+        SimEvent::FairLendingFindingEscalated { .. } => "fair_lending_finding_escalated",
+        // Phase 4.5: Offer Guardrail
+        SimEvent::OfferGuardrailAssessed { .. } => "offer_guardrail_assessed",
+        SimEvent::OfferLaunched { .. } => "offer_launched",
+        // Phase 4.6: Card Network Invoices
+        SimEvent::NetworkInvoiceReceived { .. } => "network_invoice_received",
+        SimEvent::NetworkInvoiceDiscrepancy { .. } => "network_invoice_discrepancy",
+        SimEvent::NetworkRecoveryResolved { .. } => "network_recovery_resolved",
+        // Phase 4.7: Settlement Accounts
+        SimEvent::SettlementShortfall { .. } => "settlement_shortfall",
+        // Phase 4.8: Payment Cutoffs
+        SimEvent::PaymentRolledPastCutoff { .. } => "payment_rolled_past_cutoff",
+        // Phase 4.9: ACH Returns
+        SimEvent::AchReturnReceived { .. } => "ach_return_received",
+        SimEvent::AchRepresentmentResolved { .. } => "ach_representment_resolved",
+        SimEvent::AchDisputeResolved { .. } => "ach_dispute_resolved",
+        // Phase 4.10: Customer-originated ACH
+        SimEvent::AchOriginationLimitsChanged { .. } => "ach_origination_limits_changed",
+        SimEvent::AchOriginationDeclined { .. } => "ach_origination_declined",
+        SimEvent::OriginatedAchReturned { .. } => "originated_ach_returned",
+        SimEvent::OdfiReturnRateBreached { .. } => "odfi_return_rate_breached",
+        // Phase 4.11: External account linking
+        SimEvent::LinkVerificationStrictnessChanged { .. } => "link_verification_strictness_changed",
+        SimEvent::ExternalAccountLinked { .. } => "external_account_linked",
+        SimEvent::ExternalLinkFailed { .. } => "external_link_failed",
+        // Phase 4.12: Customer limits
+        SimEvent::CustomerLimitSet { .. } => "customer_limit_set",
+        SimEvent::CustomerLimitRejected { .. } => "customer_limit_rejected",
+        SimEvent::CustomerLimitDeclined { .. } => "customer_limit_declined",
+        // Phase 4.13: Risk lifecycle
+        SimEvent::RiskTierChanged { .. } => "risk_tier_changed",
+        SimEvent::EddReviewCompleted { .. } => "edd_review_completed",
+        // Phase 4.14: SSN validation
+        SimEvent::SsnValidationFlagged { .. } => "ssn_validation_flagged",
+        // Phase 4.15: identity verification
+        SimEvent::ApplicantDeclined { .. } => "applicant_declined",
+        // Phase 4.16: SAR decisions
+        SimEvent::SarThresholdChanged { .. } => "sar_threshold_changed",
+        SimEvent::SarFilingMissed { .. } => "sar_filing_missed",
+        SimEvent::SarQualityComputed { .. } => "sar_quality_computed",
+        // Phase 4.17: rule tuning
+        SimEvent::TuningReviewOpened { .. } => "tuning_review_opened",
+        SimEvent::TuningReviewApproved { .. } => "tuning_review_approved",
+        SimEvent::MonitoringRuleTuned { .. } => "monitoring_rule_tuned",
+        SimEvent::RuleTuningRejected { .. } => "rule_tuning_rejected",
+        // Phase 4.18: coverage assessment
+        SimEvent::CoverageAssessed { .. } => "coverage_assessed",
+        // Phase 4.29: complaint SLA policy
+        SimEvent::ComplaintSlaPolicySet { .. } => "complaint_sla_policy_set",
+        SimEvent::ComplaintSlaPolicyRejected { .. } => "complaint_sla_policy_rejected",
+        // Phase 4.30: hardship program
+        SimEvent::HardshipEnrolled { .. } => "hardship_enrolled",
+        // Phase 4.31: case documents
+        SimEvent::CaseDocumentsGenerated { .. } => "case_documents_generated",
+        // Phase 4.32: contact preferences
+        SimEvent::ContactPolicySet { .. } => "contact_policy_set",
+        SimEvent::ContactPolicyRejected { .. } => "contact_policy_rejected",
+        SimEvent::ContactPreferenceViolated { .. } => "contact_preference_violated",
+        SimEvent::OutreachCampaignRun { .. } => "outreach_campaign_run",
+        SimEvent::SatisfactionScarred { .. } => "satisfaction_scarred",
+        SimEvent::FinancialHealthScored { .. } => "financial_health_scored",
+        SimEvent::CreditHealthFloorSet { .. } => "credit_health_floor_set",
+        SimEvent::CreditHealthFloorRejected { .. } => "credit_health_floor_rejected",
+        SimEvent::CreditOfferScreened { .. } => "credit_offer_screened",
+        SimEvent::CreditLineDefaulted { .. } => "credit_line_defaulted",
+        SimEvent::MerchantsCategorized { .. } => "merchants_categorized",
+        // Phase 4.36: Remittance corridors
+        SimEvent::RemittanceCorridorOpened { .. } => "remittance_corridor_opened",
+        SimEvent::RemittancesSent { .. } => "remittances_sent",
+        // Phase 4.37: Visa expiry
+        SimEvent::VisaRefreshRequested { .. } => "visa_refresh_requested",
+        SimEvent::VisaDocumentsRenewed { .. } => "visa_documents_renewed",
+        SimEvent::VisaDocumentationLapsed { .. } => "visa_documentation_lapsed",
+        SimEvent::AccountRestricted { .. } => "account_restricted",
+        SimEvent::AccountRestrictionLifted { .. } => "account_restriction_lifted",
+        SimEvent::RestrictedTransactionBlocked { .. } => "restricted_transaction_blocked",
+        SimEvent::VisaRestrictionPolicySet { .. } => "visa_restriction_policy_set",
+        SimEvent::VisaRestrictionPolicyRejected { .. } => "visa_restriction_policy_rejected",
+        // Phase 4.38: Adverse media
+        SimEvent::AdverseMediaHit { .. } => "adverse_media_hit",
+        SimEvent::AdverseMediaScreened { .. } => "adverse_media_screened",
+        // Phase 4.39: Correspondent banking
+        SimEvent::CorrespondentWiresCleared { .. } => "correspondent_wires_cleared",
+        SimEvent::CorrespondentDueDiligenceRequested { .. } => "correspondent_due_diligence_requested",
+        SimEvent::CorrespondentDueDiligenceCompleted { .. } => "correspondent_due_diligence_completed",
+        SimEvent::CorrespondentRelationshipSuspended { .. } => "correspondent_relationship_suspended",
+        SimEvent::CrossBorderTransfersBooked { .. } => "cross_border_transfers_booked",
+        SimEvent::CrossBorderFunnelFinding { .. } => "cross_border_funnel_finding",
+        // Phase 4.52: Deposit run
+        SimEvent::DepositRunStarted { .. } => "deposit_run_started",
+        SimEvent::DepositOutflow { .. } => "deposit_outflow",
+        SimEvent::LiquidityShortfall { .. } => "liquidity_shortfall",
+        SimEvent::DepositRunEnded { .. } => "deposit_run_ended",
+        SimEvent::DepositRatePremiumSet { .. } => "deposit_rate_premium_set",
+        SimEvent::EmergencyLiquidityDrawn { .. } => "emergency_liquidity_drawn",
+        SimEvent::DepositRunCommandRejected { .. } => "deposit_run_command_rejected",
+        // Phase 4.53: Business email compromise
+        SimEvent::InvoiceWiresBooked { .. } => "invoice_wires_booked",
+        SimEvent::WireRecallResolved { .. } => "wire_recall_resolved",
+        SimEvent::WireRecallRejected { .. } => "wire_recall_rejected",
+        // Phase 4.54: Check fraud
+        SimEvent::CheckItemsBooked { .. } => "check_items_booked",
+        SimEvent::CheckFraudResolved { .. } => "check_fraud_resolved",
+        // Phase 4.55: Deposit holds
+        SimEvent::FundsHeld { .. } => "funds_held",
+        SimEvent::DepositHoldPolicySet { .. } => "deposit_hold_policy_set",
+        SimEvent::DepositHoldPolicyRejected { .. } => "deposit_hold_policy_rejected",
+        // Phase 4.59: Evidence bundles
+        SimEvent::EvidenceBundleAssembled { .. } => "evidence_bundle_assembled",
+        // Phase 4.60: Alert cases
+        SimEvent::AlertCaseOpened { .. } => "alert_case_opened",
+        SimEvent::AlertGroupedIntoCase { .. } => "alert_grouped_into_case",
+        SimEvent::AlertCaseClosed { .. } => "alert_case_closed",
+        // Phase 4.61: Operational risk
+        SimEvent::OperationalLossRecorded { .. } => "operational_loss_recorded",
+        SimEvent::OpRiskCapitalEstimated { .. } => "op_risk_capital_estimated",
+        SimEvent::QuarterCloseOpened { .. } => "quarter_close_opened",
+        SimEvent::CloseItemSignedOff { .. } => "close_item_signed_off",
+        SimEvent::CloseAdjustmentPosted { .. } => "close_adjustment_posted",
+        SimEvent::QuarterCloseOverdue { .. } => "quarter_close_overdue",
+        SimEvent::QuarterClosed { .. } => "quarter_closed",
+        SimEvent::QuarterCloseCommandRejected { .. } => "quarter_close_command_rejected",
+        SimEvent::ChurnPredictionsEvaluated { .. } => "churn_predictions_evaluated",
+        SimEvent::ChurnModelDriftDetected { .. } => "churn_model_drift_detected",
+        SimEvent::ComplaintReopened { .. } => "complaint_reopened",
+        SimEvent::ComplaintEscalatedToRegulator { .. } => "complaint_escalated_to_regulator",
+        SimEvent::DeskPerformanceReported { .. } => "desk_performance_reported",
+        SimEvent::ScenarioScriptFired { .. } => "scenario_script_fired",
+        SimEvent::ScenarioScriptFailed { .. } => "scenario_script_failed",
+        SimEvent::StressIndexComputed { .. } => "stress_index_computed",
+        SimEvent::ResilienceLevelChanged { .. } => "resilience_level_changed",
+        SimEvent::AlertQaCycleCompleted { .. } => "alert_qa_cycle_completed",
+        SimEvent::QaRetrainingOpened { .. } => "qa_retraining_opened",
+        SimEvent::QaRetrainingCompleted { .. } => "qa_retraining_completed",
+        SimEvent::BreakpointHit { .. } => "breakpoint_hit",
+        SimEvent::StaffingChanged { .. } => "staffing_changed",
+        SimEvent::StaffingRejected { .. } => "staffing_rejected",
+        SimEvent::AlertCleared { .. } => "alert_cleared",
+        SimEvent::EnforcementActionIssued { .. } => "enforcement_action_issued",
+        SimEvent::EnforcementActionLifted { .. } => "enforcement_action_lifted",
+        SimEvent::LookbackCompleted { .. } => "lookback_completed",
+        SimEvent::LoanOriginated { .. } => "loan_originated",
+        SimEvent::LoanApplicationDeclined { .. } => "loan_application_declined",
+        SimEvent::LoanDelinquencyChanged { .. } => "loan_delinquency_changed",
+        SimEvent::LoanChargedOff { .. } => "loan_charged_off",
+        SimEvent::LoanPaidOff { .. } => "loan_paid_off",
+        SimEvent::AlertAssigned { .. } => "alert_assigned",
+        SimEvent::AlertClosed { .. } => "alert_closed",
+        SimEvent::AlertEscalated { .. } => "alert_escalated",
+        SimEvent::AlertTriageRejected { .. } => "alert_triage_rejected",
+        SimEvent::InvestigationCaseOpened { .. } => "investigation_case_opened",
+        SimEvent::InvestigationCaseAssigned { .. } => "investigation_case_assigned",
+        SimEvent::InvestigationCaseClosed { .. } => "investigation_case_closed",
+        SimEvent::InvestigationCaseRejected { .. } => "investigation_case_rejected",
+        SimEvent::MuleNetworkSeeded { .. } => "mule_network_seeded",
+        SimEvent::MuleTransfersBooked { .. } => "mule_transfers_booked",
+        SimEvent::MuleNetworkDissolved { .. } => "mule_network_dissolved",
+        SimEvent::SanctionsSensitivityChanged { .. } => "sanctions_sensitivity_changed",
+        SimEvent::ChannelTransactionAbandoned { .. } => "channel_transaction_abandoned",
+        // Phase 4.91: Authorized push payment fraud
+        SimEvent::AppScamPaymentsBooked { .. } => "app_scam_payments_booked",
+        SimEvent::AppScamReported { .. } => "app_scam_reported",
+        SimEvent::AppClaimDecided { .. } => "app_claim_decided",
+        SimEvent::AppReimbursementPolicyChanged { .. } => "app_reimbursement_policy_changed",
+        SimEvent::AtmOutOfService { .. } => "atm_out_of_service",
+        SimEvent::AtmBackInService { .. } => "atm_back_in_service",
+        SimEvent::AtmCashOut { .. } => "atm_cash_out",
+        SimEvent::MerchantTurnedBad { .. } => "merchant_turned_bad",
+        SimEvent::MerchantChargebackAlert { .. } => "merchant_chargeback_alert",
+        SimEvent::DeviceSessionsLogged { .. } => "device_sessions_logged",
+        SimEvent::AccountTakeover { .. } => "account_takeover",
     }
 }
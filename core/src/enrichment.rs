@@ -0,0 +1,143 @@
+//! Transaction categorization enrichment (Phase 4.35).
+//!
+//! Each tick, merchants paid for the first time are categorized. Every
+//! merchant really belongs to one spend category, drawn from the
+//! configured mix; the enrichment labels it with that category with
+//! probability `accuracy` and with one of the others otherwise. Everything
+//! downstream sees only the enriched label:
+//!   - HIGH_RISK_MCC alerts on customers whose gambling and crypto share
+//!     of merchant spend crosses the threshold
+//!   - financial health docks customers for their high-risk spend share
+//!
+//! The true category is ground truth, kept for analysis of the export.
+
+use crate::{
+    config::TransactionEnrichmentConfig,
+    error::SimResult,
+    event::SimEvent,
+    rng::SubsystemRng,
+    store::{enrichment::MerchantCategoryRow, SimStore},
+    types::{RunId, Tick},
+};
+use std::collections::BTreeMap;
+
+/// A customer's merchant spend over the intensity window.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SpendIntensity {
+    pub total_spend: f64,
+    pub high_risk_spend: f64,
+}
+
+impl SpendIntensity {
+    /// High-risk share of merchant spend; 0 with no spend.
+    pub fn share(&self) -> f64 {
+        if self.total_spend > 0.0 {
+            self.high_risk_spend / self.total_spend
+        } else {
+            0.0
+        }
+    }
+}
+
+pub struct TransactionEnrichment {
+    run_id: RunId,
+    config: TransactionEnrichmentConfig,
+}
+
+impl TransactionEnrichment {
+    pub fn new(run_id: RunId, config: TransactionEnrichmentConfig) -> Self {
+        Self { run_id, config }
+    }
+
+    pub fn enabled(&self) -> bool {
+        self.config.enabled
+    }
+
+    pub fn config(&self) -> &TransactionEnrichmentConfig {
+        &self.config
+    }
+
+    /// Categorize the merchants first paid this tick.
+    pub fn enrich(&self, store: &SimStore, tick: Tick, rng: &mut SubsystemRng) -> SimResult<Option<SimEvent>> {
+        let merchants = store.uncategorized_merchants(&self.run_id, tick)?;
+        if merchants.is_empty() {
+            return Ok(None);
+        }
+        for counterparty in &merchants {
+            let true_category = self.true_category(rng);
+            let enriched_category = self.label(&true_category, rng);
+            store.insert_merchant_category(&self.run_id, &MerchantCategoryRow {
+                counterparty: counterparty.clone(),
+                true_category,
+                enriched_category,
+                tick_first_seen: tick,
+            })?;
+        }
+        tracing::debug!("enrichment: {} merchants categorized", merchants.len());
+        Ok(Some(SimEvent::MerchantsCategorized {
+            tick,
+            merchants: merchants.len() as i64,
+        }))
+    }
+
+    /// Draw a merchant's real category from the configured mix.
+    fn true_category(&self, rng: &mut SubsystemRng) -> String {
+        let total: f64 = self.config.categories.iter().map(|c| c.merchant_share).sum();
+        let mut roll = rng.next_f64() * total;
+        for category in &self.config.categories {
+            if roll < category.merchant_share {
+                return category.category.clone();
+            }
+            roll -= category.merchant_share;
+        }
+        self.config.categories.last().map(|c| c.category.clone()).unwrap_or_default()
+    }
+
+    /// The enrichment's label: the truth, or one of the other categories.
+    fn label(&self, true_category: &str, rng: &mut SubsystemRng) -> String {
+        if rng.chance(self.config.accuracy) {
+            return true_category.to_string();
+        }
+        let others: Vec<&str> = self
+            .config
+            .categories
+            .iter()
+            .map(|c| c.category.as_str())
+            .filter(|c| *c != true_category)
+            .collect();
+        if others.is_empty() {
+            return true_category.to_string();
+        }
+        others[rng.next_u64_below(others.len() as u64) as usize].to_string()
+    }
+
+    pub fn is_high_risk(&self, category: &str) -> bool {
+        self.config.high_risk_categories.iter().any(|c| c == category)
+    }
+
+    /// Merchant spend by customer over the intensity window ending at
+    /// `tick`, for one customer or all of them, on enriched categories.
+    pub fn intensity(
+        &self,
+        store: &SimStore,
+        customer_id: Option<&str>,
+        tick: Tick,
+    ) -> SimResult<BTreeMap<String, SpendIntensity>> {
+        let start = (tick + 1).saturating_sub(self.config.intensity_lookback_ticks);
+        let mut out: BTreeMap<String, SpendIntensity> = BTreeMap::new();
+        for row in store.merchant_spend_by_category(&self.run_id, customer_id, start, tick)? {
+            let entry = out.entry(row.customer_id).or_default();
+            entry.total_spend += row.amount;
+            if self.is_high_risk(&row.category) {
+                entry.high_risk_spend += row.amount;
+            }
+        }
+        Ok(out)
+    }
+
+    /// Whether the customer's spend crosses the HIGH_RISK_MCC thresholds.
+    pub fn is_intense(&self, spend: &SpendIntensity) -> bool {
+        spend.high_risk_spend >= self.config.intensity_min_spend
+            && spend.share() >= self.config.intensity_threshold
+    }
+}
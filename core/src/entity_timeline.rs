@@ -0,0 +1,131 @@
+//! Entity timelines (Phase 4.58).
+//!
+//! Everything that happened to one customer, account or dispute case, in
+//! the order it happened: transactions, complaints, alerts, notes and
+//! status changes, merged from their tables by one SQL query. An
+//! investigator's timeline view renders it directly instead of stitching
+//! queue pages and account history together on the client.
+//!
+//! A customer's timeline takes in all their accounts; an account's holds
+//! only what names that account, so customer-level AML alerts and
+//! contacts stay off it. A case's timeline is the dispute, its status
+//! changes and its documents. An investigation case's (Phase 4.86) is
+//! the alerts, screening hits, disputes and complaints linked into it,
+//! with their status changes and notes, and the case's own opening,
+//! notes and closing.
+
+use crate::{
+    error::{SimError, SimResult},
+    store::SimStore,
+    types::Tick,
+};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum EntityType {
+    Customer,
+    Account,
+    /// A card dispute, as on the cases desk.
+    Case,
+    /// An investigation case across the desks (Phase 4.86).
+    InvestigationCase,
+}
+
+impl EntityType {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Customer => "customer",
+            Self::Account => "account",
+            Self::Case => "case",
+            Self::InvestigationCase => "investigation_case",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum TimelineKind {
+    Transaction,
+    /// A complaint filed.
+    Complaint,
+    /// A fraud or AML alert raised.
+    Alert,
+    /// A dispute case filed.
+    Case,
+    /// Opened, closed, resolved, re-tiered or reported in a SAR.
+    StatusChange,
+    /// A customer contact, a case document or an investigator's note.
+    Note,
+}
+
+impl TimelineKind {
+    pub(crate) fn parse(s: &str) -> Option<Self> {
+        Some(match s {
+            "transaction" => Self::Transaction,
+            "complaint" => Self::Complaint,
+            "alert" => Self::Alert,
+            "case" => Self::Case,
+            "status_change" => Self::StatusChange,
+            "note" => Self::Note,
+            _ => return None,
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct TimelineEntry {
+    pub tick: Tick,
+    pub kind: TimelineKind,
+    /// Table the entry came from, such as "transactions" or "aml_alert".
+    pub source: String,
+    /// Id of the row in `source`: a transaction, complaint, alert,
+    /// screening, dispute, document or case id.
+    pub ref_id: String,
+    /// The account involved, when there is one.
+    pub account_id: Option<String>,
+    pub summary: String,
+    /// Transaction, alert trigger, disputed, refunded or reported amount.
+    pub amount: Option<f64>,
+}
+
+/// sim-runner → client after `get_entity_timeline`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct EntityTimeline {
+    pub entity_type: EntityType,
+    pub entity_id: String,
+    pub from_tick: Option<Tick>,
+    pub to_tick: Option<Tick>,
+    /// Oldest first. Within a tick, openings come first and closures
+    /// last, with transactions, alerts, complaints, cases, status changes
+    /// and notes in between.
+    pub entries: Vec<TimelineEntry>,
+}
+
+impl EntityTimeline {
+    /// The entity's timeline, limited to ticks `from_tick..=to_tick` when
+    /// given. An id the run never had is a command error.
+    pub fn query(
+        store: &SimStore,
+        run_id: &str,
+        entity_type: EntityType,
+        entity_id: &str,
+        from_tick: Option<Tick>,
+        to_tick: Option<Tick>,
+    ) -> SimResult<Self> {
+        if !store.timeline_entity_exists(run_id, entity_type, entity_id)? {
+            return Err(SimError::command(
+                "get_entity_timeline",
+                format!("unknown {} {entity_id}", entity_type.as_str()),
+            ));
+        }
+        Ok(Self {
+            entity_type,
+            entity_id: entity_id.to_string(),
+            from_tick,
+            to_tick,
+            entries: store.entity_timeline(run_id, entity_type, entity_id, from_tick, to_tick)?,
+        })
+    }
+}
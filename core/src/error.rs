@@ -1,13 +1,27 @@
+use crate::types::Tick;
 use thiserror::Error;
 
 #[derive(Error, Debug)]
 pub enum SimError {
-    #[error("Database error: {0}")]
-    Database(#[from] rusqlite::Error),
+    #[error("Store error: {0}")]
+    Store(#[from] rusqlite::Error),
 
     #[error("Serialization error: {0}")]
     Serialization(#[from] serde_json::Error),
 
+    /// Population or scenario generation could not produce a valid entity,
+    /// usually because the loaded config is inconsistent.
+    #[error("Generator '{generator}' failed: {reason}")]
+    Generator { generator: String, reason: String },
+
+    /// A player or IPC command was rejected before it changed anything.
+    #[error("Command '{command}' rejected: {reason}")]
+    Command { command: String, reason: String },
+
+    /// Simulation state broke a rule it must always hold.
+    #[error("Invariant violated at tick {tick}: {invariant}")]
+    InvariantViolation { tick: Tick, invariant: String },
+
     #[error("Invalid tick: expected {expected}, got {actual}")]
     TickMismatch { expected: u64, actual: u64 },
 
@@ -20,8 +34,101 @@ pub enum SimError {
     #[error("Determinism violation: state diverged at tick {tick}")]
     DeterminismViolation { tick: u64 },
 
+    /// The run stopped on a fatal error and will not tick again.
+    #[error("Run halted at tick {tick}: {reason}")]
+    RunHalted { tick: Tick, reason: String },
+
+    /// A stored snapshot was written in a layout this crate cannot read.
+    #[error(
+        "Snapshot version {found} (written by crate {crate_version}) is not supported; \
+         this crate reads versions 1 to {supported}"
+    )]
+    SnapshotVersion { found: u32, supported: u32, crate_version: String },
+
+    /// Phase 4.77: a scenario file was refused; `at` names the field,
+    /// or the line for a syntax error.
+    #[error("Scenario invalid at {at}: {reason}")]
+    Scenario { at: String, reason: String },
+
     #[error(transparent)]
     Other(#[from] anyhow::Error),
 }
 
+/// Whether a run can carry on after an error.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Severity {
+    /// Nothing was applied; report it and keep going.
+    Recoverable,
+    /// State may be partly written or untrustworthy; the run must stop.
+    Fatal,
+}
+
+impl SimError {
+    pub fn command(command: impl Into<String>, reason: impl Into<String>) -> Self {
+        Self::Command {
+            command: command.into(),
+            reason: reason.into(),
+        }
+    }
+
+    pub fn generator(generator: impl Into<String>, reason: impl Into<String>) -> Self {
+        Self::Generator {
+            generator: generator.into(),
+            reason: reason.into(),
+        }
+    }
+
+    pub fn scenario(at: impl Into<String>, reason: impl Into<String>) -> Self {
+        Self::Scenario {
+            at: at.into(),
+            reason: reason.into(),
+        }
+    }
+
+    /// Unclassified errors are fatal: a failure nobody anticipated is not
+    /// safe to tick past.
+    pub fn severity(&self) -> Severity {
+        match self {
+            Self::Command { .. }
+            | Self::Serialization(_)
+            | Self::SnapshotVersion { .. }
+            | Self::Scenario { .. } => {
+                Severity::Recoverable
+            }
+            Self::Store(_)
+            | Self::Generator { .. }
+            | Self::InvariantViolation { .. }
+            | Self::TickMismatch { .. }
+            | Self::SubsystemNotFound { .. }
+            | Self::RunNotInitialized
+            | Self::DeterminismViolation { .. }
+            | Self::RunHalted { .. }
+            | Self::Other(_) => Severity::Fatal,
+        }
+    }
+
+    pub fn is_fatal(&self) -> bool {
+        self.severity() == Severity::Fatal
+    }
+
+    /// Stable machine-readable code, sent to IPC clients as `code`.
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::Store(_) => "store_error",
+            Self::Serialization(_) => "serialization_error",
+            Self::Generator { .. } => "generator_error",
+            Self::Command { .. } => "command_error",
+            Self::InvariantViolation { .. } => "invariant_violation",
+            Self::TickMismatch { .. } => "tick_mismatch",
+            Self::SubsystemNotFound { .. } => "subsystem_not_found",
+            Self::RunNotInitialized => "run_not_initialized",
+            Self::DeterminismViolation { .. } => "determinism_violation",
+            Self::RunHalted { .. } => "run_halted",
+            Self::SnapshotVersion { .. } => "snapshot_version_unsupported",
+            Self::Scenario { .. } => "scenario_invalid",
+            Self::Other(_) => "internal_error",
+        }
+    }
+}
+
 pub type SimResult<T> = Result<T, SimError>;
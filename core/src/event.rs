@@ -482,6 +482,1194 @@ pub enum SimEvent {
         delta: f64,
         primary_driver: String,
     },
+
+    // ── Phase 4.1: Credit Bureau ──────────────────────────────────
+    BureauFurnished {
+        tick: Tick,
+        accounts_reported: i64,
+        loans_reported: i64,
+        error_count: i64,
+    },
+    BureauDisputeFiled {
+        tick: Tick,
+        dispute_id: String,
+        customer_id: String,
+        account_id: String,
+        complaint_id: String,
+        due_tick: Tick,
+    },
+    BureauDisputeCorrected {
+        tick: Tick,
+        dispute_id: String,
+        customer_id: String,
+        days_open: u64,
+        on_time: bool,
+    },
+    BureauCorrectionOverdue {
+        tick: Tick,
+        dispute_id: String,
+        customer_id: String,
+        penalty: f64,
+    },
+
+    // ── Phase 4.2: Protected Funds ────────────────────────────────
+    ProtectedFundsViolation {
+        tick: Tick,
+        customer_id: String,
+        account_id: String,
+        debit_type: String,
+        amount: f64,
+    },
+    ProtectedDebitBlocked {
+        tick: Tick,
+        customer_id: String,
+        account_id: String,
+        debit_type: String,
+        amount_blocked: f64,
+    },
+
+    // ── Phase 4.3: Branch Network ─────────────────────────────────
+    BranchOpened {
+        tick: Tick,
+        branch_id: String,
+        state_code: String,
+        customers_assigned: i64,
+    },
+    BranchClosed {
+        tick: Tick,
+        branch_id: String,
+        state_code: String,
+        customers_affected: i64,
+    },
+    BranchCommandRejected {
+        tick: Tick,
+        command: String,
+        reason: String,
+    },
+    BranchPerformanceReported {
+        tick: Tick,
+        branch_count: i64,
+        highest_complaint_branch: Option<String>,
+    },
+    BranchCustomersAcquired {
+        tick: Tick,
+        branch_id: String,
+        state_code: String,
+        customers_acquired: i64,
+    },
+
+    // ── Phase 4.4: Fair Lending ───────────────────────────────────
+    FairLendingReviewCompleted {
+        tick: Tick,
+        metrics_evaluated: i64,
+        findings_raised: i64,
+    },
+    FairLendingFindingRaised {
+        tick: Tick,
+        finding_id: String,
+        metric: String,
+        adverse_ratio: f64,
+        severity: String,
+    },
+    FairLendingFindingResolved {
+        tick: Tick,
+        finding_id: String,
+        response: String,
+    },
+    FairLendingFindingEscalated {
+        tick: Tick,
+        finding_id: String,
+        metric: String,
+    },
+
+    // ── Phase 4.5: Offer Guardrail ────────────────────────────────
+    OfferGuardrailAssessed {
+        tick: Tick,
+        offer_id: String,
+        outcome: String,
+        disparity_ratio: Option<f64>,
+    },
+    OfferLaunched {
+        tick: Tick,
+        offer_id: String,
+        customers_targeted: i64,
+    },
+
+    // ── Phase 4.6: Card Network Invoices ──────────────────────────
+    NetworkInvoiceReceived {
+        tick: Tick,
+        lines: i64,
+        invoiced_total: f64,
+        discrepancies: i64,
+    },
+    NetworkInvoiceDiscrepancy {
+        tick: Tick,
+        invoice_id: String,
+        line_type: String,
+        delta_amount: f64,
+        exception_id: String,
+    },
+    NetworkRecoveryResolved {
+        tick: Tick,
+        invoice_id: String,
+        outcome: String,
+        recovered_amount: f64,
+    },
+
+    // ── Phase 4.7: Settlement Accounts ────────────────────────────
+    SettlementShortfall {
+        tick: Tick,
+        rail_id: String,
+        shortfall: f64,
+        borrowing_cost: f64,
+        alert: bool,
+    },
+
+    // ── Phase 4.8: Payment Cutoffs ────────────────────────────────
+    PaymentRolledPastCutoff {
+        tick: Tick,
+        txn_id: String,
+        account_id: String,
+        rail_id: String,
+        amount: f64,
+        processing_tick: Tick,
+    },
+
+    // ── Phase 4.9: ACH Returns ────────────────────────────────────
+    AchReturnReceived {
+        tick: Tick,
+        return_id: String,
+        txn_id: String,
+        account_id: String,
+        return_code: String,
+        handling: String,
+        amount: f64,
+    },
+    AchRepresentmentResolved {
+        tick: Tick,
+        return_id: String,
+        collected: bool,
+    },
+    AchDisputeResolved {
+        tick: Tick,
+        dispute_id: String,
+        outcome: String,
+    },
+
+    // ── Phase 4.10: Customer-Originated ACH ───────────────────────
+    AchOriginationLimitsChanged {
+        tick: Tick,
+        daily_count: u32,
+        daily_amount: f64,
+    },
+    AchOriginationDeclined {
+        tick: Tick,
+        entry_id: String,
+        customer_id: String,
+        entry_type: String,
+        reason: String, // "daily_count" | "daily_amount"
+        amount: f64,
+    },
+    OriginatedAchReturned {
+        tick: Tick,
+        entry_id: String,
+        account_id: String,
+        return_code: String,
+        amount: f64,
+    },
+    OdfiReturnRateBreached {
+        tick: Tick,
+        breached: String,
+        unauthorized_rate: f64,
+        administrative_rate: f64,
+        overall_rate: f64,
+        network_fine: f64,
+    },
+
+    // ── Phase 4.11: External Account Linking ──────────────────────
+    LinkVerificationStrictnessChanged {
+        tick: Tick,
+        strictness: f64,
+    },
+    ExternalAccountLinked {
+        tick: Tick,
+        link_id: String,
+        customer_id: String,
+        purpose: String, // "transfer" | "direct_deposit" | "mule"
+        method: String,  // "instant" | "micro_deposit"
+    },
+    ExternalLinkFailed {
+        tick: Tick,
+        link_id: String,
+        customer_id: String,
+        purpose: String,
+        method: String,
+    },
+
+    // ── Phase 4.12: Customer Limits ───────────────────────────────
+    CustomerLimitSet {
+        tick: Tick,
+        limit_type: String,
+        scope: String,
+        amount: f64,
+    },
+    CustomerLimitRejected {
+        tick: Tick,
+        limit_type: String,
+        scope: String,
+        reason: String,
+    },
+    CustomerLimitDeclined {
+        tick: Tick,
+        customer_id: String,
+        account_id: String,
+        limit_type: String,
+        amount: f64,
+        limit_amount: f64,
+    },
+
+    // ── Phase 4.13: Risk Lifecycle ────────────────────────────────
+    RiskTierChanged {
+        tick: Tick,
+        customer_id: String,
+        from_tier: String,
+        to_tier: String,
+        reason: String,
+    },
+    EddReviewCompleted {
+        tick: Tick,
+        review_id: String,
+        customer_id: String,
+        outcome: String,
+        next_due_tick: Tick,
+    },
+
+    // ── Phase 4.14: SSN Validation ────────────────────────────────
+    SsnValidationFlagged {
+        tick: Tick,
+        customer_id: String,
+        /// Comma-separated findings, e.g. "invalid_group,deceased".
+        issues: String,
+        synthetic_score: f64,
+    },
+
+    // ── Phase 4.15: Identity Verification ─────────────────────────
+    /// An applicant failed the vendor check and was not onboarded.
+    ApplicantDeclined {
+        tick: Tick,
+        customer_id: String,
+        vendor: String,
+        /// Match codes in name/SSN/DOB/address order, comma-separated.
+        match_codes: String,
+    },
+
+    // ── Phase 4.16: SAR Decisions ─────────────────────────────────
+    SarThresholdChanged {
+        tick: Tick,
+        threshold: f64,
+    },
+    /// A reportable alert went unfiled past the SAR deadline.
+    SarFilingMissed {
+        tick: Tick,
+        alert_id: String,
+        customer_id: String,
+        alert_score: f64,
+    },
+    SarQualityComputed {
+        tick: Tick,
+        sars_filed: i64,
+        defensive_filings: i64,
+        missed_filings: i64,
+        /// Share of filings that were reportable; None when none were filed.
+        sar_quality: Option<f64>,
+    },
+
+    // ── Phase 4.17: Rule Tuning ───────────────────────────────────
+    TuningReviewOpened {
+        tick: Tick,
+        review_id: String,
+        rule_id: String,
+        due_tick: Tick,
+    },
+    TuningReviewApproved {
+        tick: Tick,
+        review_id: String,
+        rule_id: String,
+    },
+    MonitoringRuleTuned {
+        tick: Tick,
+        tuning_id: String,
+        rule_id: String,
+        change: String,
+        /// Whether the change cited an approved review of the rule.
+        documented: bool,
+    },
+    RuleTuningRejected {
+        tick: Tick,
+        rule_id: String,
+        change: String,
+        reason: String,
+    },
+
+    // ── Phase 4.18: Coverage Assessment ───────────────────────────
+    /// The player-visible side of a coverage assessment: the rule
+    /// inventory only. Which typologies are actually present stays in
+    /// the `coverage_assessment` table for the post-run export.
+    CoverageAssessed {
+        tick: Tick,
+        active_rules: Vec<String>,
+        suppressed_rules: Vec<String>,
+        /// Typologies at least one active rule is meant to catch.
+        typologies_addressed: Vec<String>,
+    },
+
+    // ── Phase 4.29: Complaint SLA Policy ──────────────────────────
+    ComplaintSlaPolicySet {
+        tick: Tick,
+        priority: Option<String>,
+        product: Option<String>,
+        channel: Option<String>,
+        resolve_days: u64,
+        effective_tick: Tick,
+    },
+    ComplaintSlaPolicyRejected {
+        tick: Tick,
+        priority: Option<String>,
+        product: Option<String>,
+        channel: Option<String>,
+        reason: String,
+    },
+
+    // ── Phase 4.30: Hardship Program ──────────────────────────────
+    HardshipEnrolled {
+        tick: Tick,
+        customer_id: String,
+        life_event: String,
+        tick_expires: Tick,
+    },
+
+    // ── Phase 4.31: Case Documents ────────────────────────────────
+    /// Documents generated for a newly filed dispute or complaint;
+    /// `missing` were required but never collected.
+    CaseDocumentsGenerated {
+        tick: Tick,
+        case_type: String,
+        case_id: String,
+        customer_id: String,
+        on_file: Vec<String>,
+        missing: Vec<String>,
+    },
+
+    // ── Phase 4.32: Contact Preferences ───────────────────────────
+    ContactPolicySet {
+        tick: Tick,
+        honor_do_not_contact: bool,
+        max_contacts_per_window: Option<u32>,
+    },
+    ContactPolicyRejected {
+        tick: Tick,
+        reason: String,
+    },
+    /// A campaign contacted a customer who asked not to be contacted.
+    ContactPreferenceViolated {
+        tick: Tick,
+        customer_id: EntityId,
+        campaign_type: String,
+        campaign_id: String,
+    },
+    /// One campaign's reach: contacted, held back by do-not-contact
+    /// (`suppressed`) or the frequency cap (`throttled`), and responses.
+    OutreachCampaignRun {
+        tick: Tick,
+        campaign_type: String,
+        campaign_id: String,
+        contacted: i64,
+        suppressed: i64,
+        throttled: i64,
+        responded: i64,
+    },
+
+    // ── Phase 4.33: Satisfaction Dynamics ─────────────────────────
+    /// A breached complaint permanently lowered the satisfaction the
+    /// customer reverts to.
+    SatisfactionScarred {
+        tick: Tick,
+        customer_id: EntityId,
+        complaint_id: String,
+        baseline: f64,
+    },
+
+    // ── Phase 4.34: Financial Health ──────────────────────────────
+    /// Monthly health scoring of the active book.
+    FinancialHealthScored {
+        tick: Tick,
+        customers_scored: i64,
+        average_score: f64,
+        low_health: i64,
+    },
+    CreditHealthFloorSet {
+        tick: Tick,
+        min_score: f64,
+    },
+    CreditHealthFloorRejected {
+        tick: Tick,
+        reason: String,
+    },
+    /// A credit offer launch held back customers below the health floor.
+    CreditOfferScreened {
+        tick: Tick,
+        offer_id: String,
+        min_score: f64,
+        eligible: i64,
+        screened_out: i64,
+    },
+    CreditLineDefaulted {
+        tick: Tick,
+        customer_id: EntityId,
+        offer_id: String,
+        health_score: f64,
+        loss_amount: f64,
+    },
+
+    // ── Phase 4.35: Transaction Enrichment ────────────────────────
+    /// Merchants paid for the first time this tick were categorized.
+    MerchantsCategorized {
+        tick: Tick,
+        merchants: i64,
+    },
+
+    // ── Phase 4.36: Remittance Corridors ──────────────────────────
+    /// An international customer started remitting down a corridor.
+    RemittanceCorridorOpened {
+        tick: Tick,
+        customer_id: String,
+        corridor: String,
+        risk_rating: String,
+    },
+    /// Remittance wires sent today, across all corridors.
+    RemittancesSent {
+        tick: Tick,
+        transfers: i64,
+        amount: f64,
+    },
+
+    // ── Phase 4.37: Visa Expiry ───────────────────────────────────
+    /// A documentation refresh work item opened ahead of visa expiry.
+    VisaRefreshRequested {
+        tick: Tick,
+        customer_id: EntityId,
+        item_id: String,
+        expiry_tick: Tick,
+    },
+    /// Renewed visa documents verified; the expiry rolls forward.
+    VisaDocumentsRenewed {
+        tick: Tick,
+        customer_id: EntityId,
+        expiry_tick: Tick,
+    },
+    /// Visa documentation expired without a verified renewal.
+    VisaDocumentationLapsed {
+        tick: Tick,
+        customer_id: EntityId,
+        /// The customer's documents were already with the bank.
+        documents_pending: bool,
+    },
+    AccountRestricted {
+        tick: Tick,
+        customer_id: EntityId,
+        account_id: EntityId,
+        level: String,
+        documents_pending: bool,
+    },
+    AccountRestrictionLifted {
+        tick: Tick,
+        customer_id: EntityId,
+        /// "renewed" | "policy"
+        reason: String,
+    },
+    /// A debit refused because the customer is restricted.
+    RestrictedTransactionBlocked {
+        tick: Tick,
+        customer_id: EntityId,
+        account_id: EntityId,
+        channel: String,
+        amount: f64,
+    },
+    VisaRestrictionPolicySet {
+        tick: Tick,
+        level: String,
+        grace_ticks: Tick,
+    },
+    VisaRestrictionPolicyRejected {
+        tick: Tick,
+        reason: String,
+    },
+
+    // ── Phase 4.38: Adverse Media ─────────────────────────────────
+    /// A screening hit on a news story escalated into a customer's risk:
+    /// on the customer themselves, or on a counterparty they have paid.
+    AdverseMediaHit {
+        tick: Tick,
+        customer_id: EntityId,
+        article_id: String,
+        entity_kind: String,
+        category: String,
+        match_score: f64,
+        edd_triggered: bool,
+    },
+    /// Today's feed screened against customers and counterparties.
+    AdverseMediaScreened {
+        tick: Tick,
+        articles: i64,
+        candidates: i64,
+        escalated: i64,
+    },
+
+    // ── Phase 4.39: Correspondent Banking ─────────────────────────
+    /// Respondent bank wires cleared through us today, and those rejected
+    /// under a suspended relationship.
+    CorrespondentWiresCleared {
+        tick: Tick,
+        wires: i64,
+        amount: f64,
+        rejected: i64,
+    },
+    /// A respondent's periodic due diligence refresh fell due.
+    CorrespondentDueDiligenceRequested {
+        tick: Tick,
+        bank_id: String,
+        review_id: String,
+    },
+    /// The respondent returned its questionnaire. Outcome: satisfactory or
+    /// nested_disclosed; a suspended relationship is reinstated.
+    CorrespondentDueDiligenceCompleted {
+        tick: Tick,
+        bank_id: String,
+        review_id: String,
+        outcome: String,
+        reinstated: bool,
+    },
+    /// Due diligence went overdue; the respondent's wires are rejected
+    /// until a refresh completes.
+    CorrespondentRelationshipSuspended {
+        tick: Tick,
+        bank_id: String,
+        review_id: String,
+    },
+
+    // ── Phase 4.40: Cross-Border Fan-In/Fan-Out ───────────────────
+    /// Foreign wires in and domestic transfers out booked today by
+    /// cross-border funnels and support wires.
+    CrossBorderTransfersBooked {
+        tick: Tick,
+        inbound_wires: i64,
+        inflow: f64,
+        domestic_transfers: i64,
+        outflow: f64,
+    },
+    /// XB_FUNNEL recorded a finding, with its evidence, behind an alert.
+    CrossBorderFunnelFinding {
+        tick: Tick,
+        finding_id: String,
+        alert_id: String,
+        customer_id: String,
+        senders: i64,
+        payees: i64,
+    },
+
+    // ── Phase 4.52: Deposit Run ───────────────────────────────────
+    /// A confidence shock set off a run on deposits. Trigger: scheduled
+    /// or reputation.
+    DepositRunStarted {
+        tick: Tick,
+        trigger: String,
+        reputation_score: f64,
+        run_rate: f64,
+        uninsured_deposits: f64,
+    },
+    /// Deposits withdrawn today during a run, and what drove the pace.
+    DepositOutflow {
+        tick: Tick,
+        outflow: f64,
+        uninsured_outflow: f64,
+        run_rate: f64,
+        severity: f64,
+        liquidity_coverage: f64,
+    },
+    /// Withdrawals exceeded liquid assets; the gap was borrowed at the
+    /// penalty rate.
+    LiquidityShortfall {
+        tick: Tick,
+        shortfall: f64,
+        penalty_borrowing: f64,
+    },
+    /// The run rate fell below calm; borrowing is repaid.
+    DepositRunEnded {
+        tick: Tick,
+        shock_tick: Tick,
+        total_outflow: f64,
+        peak_run_rate: f64,
+        repaid: f64,
+    },
+    DepositRatePremiumSet {
+        tick: Tick,
+        premium: f64,
+    },
+    EmergencyLiquidityDrawn {
+        tick: Tick,
+        amount: f64,
+        outstanding: f64,
+    },
+    DepositRunCommandRejected {
+        tick: Tick,
+        command: String,
+        reason: String,
+    },
+
+    // ── Phase 4.53: Business Email Compromise ────────────────────
+    /// Invoices business customers paid by wire today, and how many
+    /// spoofed invoices arrived, paid or not.
+    InvoiceWiresBooked {
+        tick: Tick,
+        spoofed_invoices: i64,
+        wires: i64,
+        amount: f64,
+    },
+    /// The receiving bank answered a recall. Outcome: recovered,
+    /// funds_gone or not_fraud.
+    WireRecallResolved {
+        tick: Tick,
+        txn_id: String,
+        ticks_elapsed: Tick,
+        outcome: String,
+        recovered: f64,
+    },
+    WireRecallRejected {
+        tick: Tick,
+        txn_id: String,
+        reason: String,
+    },
+
+    // ── Phase 4.54: Check Fraud ──────────────────────────────────
+    /// The day's checks cleared: presented against customers and deposited
+    /// by them, with how many were fraudulent (not known to the desk).
+    CheckItemsBooked {
+        tick: Tick,
+        presented: i64,
+        deposited: i64,
+        fraudulent: i64,
+        amount: f64,
+        wave: bool,
+    },
+    /// A check item was settled for good. Outcome: cleared, returned,
+    /// reimbursed or late_return; loss_bearer is who ate any loss.
+    CheckFraudResolved {
+        tick: Tick,
+        item_id: String,
+        side: String,
+        typology: String,
+        outcome: String,
+        loss_bearer: Option<String>,
+        loss: f64,
+    },
+
+    // ── Phase 4.55: Deposit Holds ────────────────────────────────
+    /// Part of a check deposit is on hold until `tick + hold_ticks`.
+    /// `class` is the one that set the hold; pressure is how hard the
+    /// customer feels it.
+    FundsHeld {
+        tick: Tick,
+        customer_id: String,
+        account_id: String,
+        txn_id: String,
+        held_amount: f64,
+        hold_ticks: Tick,
+        class: String,
+        risk: String,
+        pressure: f64,
+    },
+    DepositHoldPolicySet {
+        tick: Tick,
+        class: Option<String>,
+        risk: Option<String>,
+        hold_ticks: Tick,
+    },
+    DepositHoldPolicyRejected {
+        tick: Tick,
+        class: Option<String>,
+        risk: Option<String>,
+        hold_ticks: Tick,
+        reason: String,
+    },
+
+    // ── Phase 4.59: Evidence Bundles ──────────────────────────────
+    /// The evidence bundle kept with a SAR or dispute filing; `gaps`
+    /// are the quality checks it failed.
+    EvidenceBundleAssembled {
+        tick: Tick,
+        filing_type: String,
+        filing_id: String,
+        customer_id: String,
+        quality: f64,
+        gaps: Vec<String>,
+    },
+
+    // ── Phase 4.60: Alert Cases ───────────────────────────────────
+    /// An alert with no open case in its cluster and window opened one.
+    AlertCaseOpened {
+        tick: Tick,
+        case_id: String,
+        alert_id: String,
+        customer_id: String,
+    },
+    /// An alert joined an open case instead of opening its own.
+    AlertGroupedIntoCase {
+        tick: Tick,
+        case_id: String,
+        alert_id: String,
+        customer_id: String,
+    },
+    AlertCaseClosed {
+        tick: Tick,
+        case_id: String,
+        alert_count: u32,
+    },
+
+    // ── Phase 4.61: Operational Risk ──
+    OperationalLossRecorded {
+        tick: Tick,
+        basel_category: String,
+        source: String,
+        source_id: String,
+        amount: f64,
+    },
+    OpRiskCapitalEstimated {
+        tick: Tick,
+        business_indicator: f64,
+        annual_loss: f64,
+        ilm: f64,
+        capital: f64,
+    },
+
+    // ── Phase 4.62: Quarter Close ──
+    QuarterCloseOpened {
+        tick: Tick,
+        period: String,
+        accrual_adjustment: f64,
+        due_tick: Tick,
+    },
+    CloseItemSignedOff {
+        tick: Tick,
+        period: String,
+        item: String,
+    },
+    CloseAdjustmentPosted {
+        tick: Tick,
+        period: String,
+        amount: f64,
+        late: bool,
+    },
+    QuarterCloseOverdue {
+        tick: Tick,
+        period: String,
+        pending_items: u32,
+    },
+    QuarterClosed {
+        tick: Tick,
+        period: String,
+        overdue: bool,
+    },
+    QuarterCloseCommandRejected {
+        tick: Tick,
+        command: String,
+        reason: String,
+    },
+
+    // ── Phase 4.64: Churn prediction evaluation ──
+    ChurnPredictionsEvaluated {
+        tick: Tick,
+        score_tick: Tick,
+        horizon: Tick,
+        customers: u32,
+        churned: u32,
+        drifting_segments: u32,
+    },
+    ChurnModelDriftDetected {
+        tick: Tick,
+        segment: String,
+        horizon: Tick,
+        score_tick: Tick,
+        auc: Option<f64>,
+        baseline_auc: Option<f64>,
+        brier_score: f64,
+        baseline_brier_score: f64,
+    },
+
+    // ── Phase 4.65: Complaint response quality ──
+    ComplaintReopened {
+        tick: Tick,
+        complaint_id: String,
+        customer_id: String,
+        quality: f64,
+    },
+    ComplaintEscalatedToRegulator {
+        tick: Tick,
+        complaint_id: String,
+        customer_id: String,
+        quality: f64,
+    },
+    DeskPerformanceReported {
+        tick: Tick,
+        closures: i64,
+        avg_quality: Option<f64>,
+        quality_trend: Option<f64>,
+        reopened: i64,
+        escalated: i64,
+    },
+
+    // ── Phase 4.68: Scenario scripting hooks ──
+    ScenarioScriptFired {
+        tick: Tick,
+        script: String,
+        action: String, // "command" | "regulator_inquiry" | "emit"
+        name: String,   // the command, the exam opened, or the emitted name
+        detail: String,
+    },
+    ScenarioScriptFailed {
+        tick: Tick,
+        script: String,
+        error: String,
+    },
+
+    // ── Phase 4.69: Composite stress index ──
+    StressIndexComputed {
+        tick: Tick,
+        stress: f64,
+        resilience: f64,
+        level: String,  // "steady" | "strained" | "critical"
+        driver: String, // the component under the most pressure
+    },
+    ResilienceLevelChanged {
+        tick: Tick,
+        from: String,
+        to: String,
+        resilience: f64,
+    },
+
+    // ── Phase 4.73: Alert disposition QA ──
+    AlertQaCycleCompleted {
+        tick: Tick,
+        population: i64,
+        sampled: i64,
+        disagreements: i64,
+        disagreement_rate: Option<f64>,
+        disagreement_trend: Option<f64>,
+    },
+    QaRetrainingOpened {
+        tick: Tick,
+        retraining_id: String,
+        alert_type: String,
+        error_kind: String, // "over_filing" | "under_filing" | "mixed"
+        disagreement_rate: f64,
+        due_tick: Tick,
+    },
+    QaRetrainingCompleted {
+        tick: Tick,
+        retraining_id: String,
+        alert_type: String,
+        cost: f64,
+    },
+
+    // ── Phase 4.74: Pause-on-event breakpoints ──
+    BreakpointHit {
+        tick: Tick,
+        breakpoint_id: String,
+        detail: String,
+    },
+
+    // ── Phase 4.80: Staffing ──
+    StaffingChanged {
+        tick: Tick,
+        queue: String,
+        old_ftes: f64,
+        new_ftes: f64,
+    },
+    StaffingRejected {
+        tick: Tick,
+        queue: String,
+        reason: String,
+    },
+    /// An analyst closed a monitoring alert too weak to report.
+    AlertCleared {
+        tick: Tick,
+        alert_id: String,
+        alert_score: f64,
+    },
+
+    // ── Phase 4.81: Regulatory enforcement ──
+    /// An MRA or consent order issued on an exam finding.
+    EnforcementActionIssued {
+        tick: Tick,
+        action_id: String,
+        exam_id: String,
+        /// "mra" | "consent_order"
+        kind: String,
+        category: String,
+        /// "fee_cap" | "complaint_lookback" | "sar_lookback"
+        constraint: Option<String>,
+        penalty: f64,
+        /// The action a repeat finding escalated, if any.
+        supersedes: Option<String>,
+    },
+    /// An exam found the action remediated and lifted it.
+    EnforcementActionLifted {
+        tick: Tick,
+        action_id: String,
+        kind: String,
+        category: String,
+    },
+    LookbackCompleted {
+        tick: Tick,
+        action_id: String,
+        items: i64,
+    },
+
+    // ── Phase 4.82: Lending ──
+    LoanOriginated {
+        tick: Tick,
+        loan_id: String,
+        customer_id: String,
+        product_id: String,
+        /// "installment" | "line"
+        kind: String,
+        /// Amount lent, or the line's limit.
+        amount: f64,
+        annual_rate: f64,
+    },
+    /// Underwriting turned an application down.
+    LoanApplicationDeclined {
+        tick: Tick,
+        customer_id: String,
+        product_id: String,
+        reason: String,
+    },
+    /// A loan moved between delinquency buckets.
+    LoanDelinquencyChanged {
+        tick: Tick,
+        loan_id: String,
+        customer_id: String,
+        from_bucket: String,
+        to_bucket: String,
+        days_past_due: i64,
+    },
+    LoanChargedOff {
+        tick: Tick,
+        loan_id: String,
+        customer_id: String,
+        balance: f64,
+        loss: f64,
+    },
+    LoanPaidOff {
+        tick: Tick,
+        loan_id: String,
+        customer_id: String,
+    },
+
+    // ── Phase 4.85: Alert triage ──
+    AlertAssigned {
+        tick: Tick,
+        alert_id: String,
+        analyst: String,
+    },
+    /// An analyst closed an alert; a `sar_filed` close also logs the
+    /// filing's own events.
+    AlertClosed {
+        tick: Tick,
+        alert_id: String,
+        /// "false_positive" | "suspicious" | "sar_filed"
+        disposition: String,
+    },
+    AlertEscalated {
+        tick: Tick,
+        alert_id: String,
+        case_id: String,
+    },
+    /// A triage command named an alert that was no longer open.
+    AlertTriageRejected {
+        tick: Tick,
+        alert_id: String,
+        reason: String,
+    },
+
+    // ── Phase 4.86: Case management ──
+    /// An alert or screening hit on a customer with no open case opened
+    /// one.
+    InvestigationCaseOpened {
+        tick: Tick,
+        case_id: String,
+        customer_id: String,
+        /// "aml_alert" | "fraud_alert" | "screening_hit"
+        item_type: String,
+        item_id: String,
+    },
+    InvestigationCaseAssigned {
+        tick: Tick,
+        case_id: String,
+        analyst: String,
+    },
+    /// A case closed: by an analyst as unfounded or confirmed, closing
+    /// `items_closed` of its items with it, or as worked once its items
+    /// were all closed on their own desks.
+    InvestigationCaseClosed {
+        tick: Tick,
+        case_id: String,
+        /// "unfounded" | "confirmed" | "worked"
+        disposition: String,
+        items_closed: u32,
+    },
+    /// A case command named a case that was no longer open.
+    InvestigationCaseRejected {
+        tick: Tick,
+        case_id: String,
+        reason: String,
+    },
+
+    // ── Phase 4.87: Money mule networks ──
+    /// A controller recruited `mules` customers into a new network.
+    MuleNetworkSeeded {
+        tick: Tick,
+        network_id: String,
+        controller_id: String,
+        mules: u32,
+    },
+    /// Credits into mules, forwards on to controllers and controllers'
+    /// wires abroad booked today by mule networks.
+    MuleTransfersBooked {
+        tick: Tick,
+        inbound_credits: i64,
+        inflow: f64,
+        forwards: i64,
+        forwarded: f64,
+        cashed_out: f64,
+    },
+    /// A network reached the end of its lifetime and stopped moving money.
+    MuleNetworkDissolved {
+        tick: Tick,
+        network_id: String,
+    },
+
+    // ── Phase 4.89: Sanctions name matching ──
+    /// The player moved the sanctions false positive dial.
+    SanctionsSensitivityChanged {
+        tick: Tick,
+        sensitivity: f64,
+        /// Match score a sanctions hit now needs.
+        match_threshold: f64,
+    },
+
+    // ── Phase 4.90: Service channels ──
+    /// A customer turned away by a degraded or down channel gave up on
+    /// the transaction rather than use another.
+    ChannelTransactionAbandoned {
+        tick: Tick,
+        customer_id: String,
+        account_id: String,
+        channel: String,
+        amount: f64,
+    },
+
+    // ── Phase 4.91: Authorized push payment fraud ──
+    /// Instant payments customers were talked into sending scammers today.
+    AppScamPaymentsBooked {
+        tick: Tick,
+        payments: i64,
+        amount: f64,
+    },
+    /// A victim reported a scam payment and claimed reimbursement.
+    AppScamReported {
+        tick: Tick,
+        txn_id: String,
+        customer_id: String,
+        account_id: String,
+        scam_type: String,
+        amount: f64,
+    },
+    /// The fraud desk decided a reimbursement claim under `policy`.
+    /// Outcome: reimbursed or declined.
+    AppClaimDecided {
+        tick: Tick,
+        txn_id: String,
+        customer_id: String,
+        account_id: String,
+        policy: String,
+        outcome: String,
+        reimbursed: f64,
+    },
+    AppReimbursementPolicyChanged {
+        tick: Tick,
+        policy: String,
+    },
+
+    // ── Phase 4.92: ATM network ──
+    /// A device fault took an ATM out of service; `incident_id` is the
+    /// incident logged against the ATM fleet.
+    AtmOutOfService {
+        tick: Tick,
+        atm_id: String,
+        incident_id: String,
+    },
+    AtmBackInService {
+        tick: Tick,
+        atm_id: String,
+        incident_id: String,
+    },
+    /// A crew hit `atm_id` with cloned cards, and took them on to other
+    /// ATMs.
+    AtmCashOut {
+        tick: Tick,
+        cash_out_id: String,
+        atm_id: String,
+        cards: i64,
+        withdrawals: i64,
+        amount: f64,
+    },
+
+    // ── Phase 4.93: Merchants ──
+    /// A merchant went bad; its purchases up to `until` are disputed far
+    /// more often.
+    MerchantTurnedBad {
+        tick: Tick,
+        merchant_id: String,
+        until: Tick,
+    },
+    /// A merchant's disputes over the monitoring window ran over the
+    /// alert rate; `rate` is disputes over authorizations.
+    MerchantChargebackAlert {
+        tick: Tick,
+        merchant_id: String,
+        disputes: i64,
+        authorizations: i64,
+        rate: f64,
+    },
+
+    // ── Phase 4.94: Device telemetry ──
+    /// Today's logins, `new_devices` of them from a device the customer
+    /// had not used before.
+    DeviceSessionsLogged {
+        tick: Tick,
+        sessions: i64,
+        new_devices: i64,
+    },
+    /// A fraudster logged in to the customer's account from `device_id`
+    /// and sent `amount` out.
+    AccountTakeover {
+        tick: Tick,
+        customer_id: String,
+        account_id: String,
+        device_id: String,
+        txn_id: String,
+        amount: f64,
+    },
 }
 
 
@@ -495,6 +1683,15 @@ pub enum EconomicPhase {
 }
 
 impl EconomicPhase {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Expansion => "expansion",
+            Self::Peak => "peak",
+            Self::Contraction => "contraction",
+            Self::Trough => "trough",
+        }
+    }
+
     pub fn fraud_multiplier(&self) -> f64 {
         match self {
             Self::Expansion => 1.0,
@@ -0,0 +1,102 @@
+//! Streaming event export (Phase 4.57).
+//!
+//! Appends every event-log entry to a JSONL file as the engine logs it,
+//! one `EventLogEntry` per line, so external pipelines can tail a run
+//! without querying SQLite mid-run. Lines are flushed at the end of each
+//! tick, so a reader never sees part of a tick's events.
+//!
+//! A regular file is rotated by size: once the next line would take it
+//! past `rotate_bytes`, it moves to `<path>.1`, older files shift up one
+//! and the oldest past `keep_files` is dropped. The path can also be a
+//! named pipe, which is never rotated; opening one blocks until a reader
+//! opens the other end.
+
+use crate::{config::EventStreamConfig, error::SimResult, event::EventLogEntry};
+use std::{
+    fs::{File, OpenOptions},
+    io::{BufWriter, Write},
+    path::Path,
+};
+
+pub struct EventStream {
+    path: String,
+    rotate_bytes: Option<u64>,
+    keep_files: usize,
+    out: BufWriter<File>,
+    /// Bytes in the current file, written or found there on opening.
+    written: u64,
+}
+
+impl EventStream {
+    /// Open the stream for appending; None when the config is disabled.
+    pub fn open(config: &EventStreamConfig) -> SimResult<Option<Self>> {
+        if !config.enabled {
+            return Ok(None);
+        }
+        let (out, written) = Self::open_file(&config.path)?;
+        let rotatable = std::fs::metadata(&config.path).is_ok_and(|m| m.is_file());
+        tracing::info!("streaming events to {}", config.path);
+        Ok(Some(Self {
+            path: config.path.clone(),
+            rotate_bytes: config.rotate_bytes.filter(|_| rotatable),
+            keep_files: config.keep_files,
+            out,
+            written,
+        }))
+    }
+
+    fn open_file(path: &str) -> SimResult<(BufWriter<File>, u64)> {
+        let file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .map_err(|e| anyhow::anyhow!("Cannot open {path}: {e}"))?;
+        let written = file.metadata().map_or(0, |m| m.len());
+        Ok((BufWriter::new(file), written))
+    }
+
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+
+    pub fn append(&mut self, entry: &EventLogEntry) -> SimResult<()> {
+        let line = serde_json::to_string(entry)? + "\n";
+        let len = line.len() as u64;
+        if self.rotate_bytes.is_some_and(|max| self.written > 0 && self.written + len > max) {
+            self.rotate()?;
+        }
+        self.out
+            .write_all(line.as_bytes())
+            .map_err(|e| anyhow::anyhow!("Cannot write {}: {e}", self.path))?;
+        self.written += len;
+        Ok(())
+    }
+
+    pub fn flush(&mut self) -> SimResult<()> {
+        self.out.flush().map_err(|e| anyhow::anyhow!("Cannot write {}: {e}", self.path))?;
+        Ok(())
+    }
+
+    /// Shift `<path>.n` up one, oldest dropped, move the current file to
+    /// `<path>.1` and start a new one.
+    fn rotate(&mut self) -> SimResult<()> {
+        self.flush()?;
+        let rename = |from: &str, to: &str| {
+            std::fs::rename(from, to).map_err(|e| anyhow::anyhow!("Cannot rotate {from} to {to}: {e}"))
+        };
+        if self.keep_files == 0 {
+            std::fs::remove_file(&self.path).map_err(|e| anyhow::anyhow!("Cannot remove {}: {e}", self.path))?;
+        } else {
+            for n in (1..self.keep_files).rev() {
+                let older = format!("{}.{n}", self.path);
+                if Path::new(&older).exists() {
+                    rename(&older, &format!("{}.{}", self.path, n + 1))?;
+                }
+            }
+            rename(&self.path, &format!("{}.1", self.path))?;
+        }
+        (self.out, self.written) = Self::open_file(&self.path)?;
+        tracing::debug!("rotated event stream {}", self.path);
+        Ok(())
+    }
+}
@@ -0,0 +1,218 @@
+//! Evidence bundles for SAR and dispute filings (Phase 4.59).
+//!
+//! Every SAR and every dispute is filed with a bundle of the evidence
+//! behind it: the account activity around the alert or dispute, the
+//! customer's KYC record, the SARs already filed on them, and the alerts
+//! and notes on file. The bundle is kept as JSON beside the filing, so a
+//! filing can be audited from what the bank actually had in hand.
+//!
+//! Each bundle is checked for the gaps an examiner would look for: no
+//! activity to support the filing, a reported amount the activity does
+//! not add up to, an incomplete KYC record, and dispute documents never
+//! collected. Bundles passing too few checks are deficient, and the
+//! regulatory exam cites deficient filings.
+
+use crate::{
+    config::EvidenceBundleConfig,
+    entity_timeline::{EntityType, TimelineEntry, TimelineKind},
+    error::SimResult,
+    event::SimEvent,
+    store::{DisputeRow, SimStore},
+    transaction_monitoring_subsystem::{AMLAlert, SuspiciousActivityReport},
+    types::{RunId, Tick},
+};
+use serde::{Deserialize, Serialize};
+
+/// The customer's KYC record as of filing. Names, tax ids and street
+/// addresses are left out; the filing's subject id points to them.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct KycRecord {
+    pub segment: String,
+    pub risk_band: String,
+    pub open_tick: Tick,
+    pub identity_type: String,
+    pub ssn_status: String,
+    pub date_of_birth: String,
+    pub city: String,
+    pub state: String,
+    pub zip_code: String,
+    pub address_type: String,
+    pub verification_status: String,
+}
+
+/// A SAR filed on the customer before this filing.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PriorSar {
+    pub sar_id: String,
+    pub filing_tick: Tick,
+    pub activity_type: String,
+    pub suspicious_amount: f64,
+    pub filing_status: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EvidenceBundle {
+    pub bundle_id: String,
+    /// "sar" | "dispute"
+    pub filing_type: String,
+    /// The SAR or dispute id.
+    pub filing_id: String,
+    /// The alert the SAR was filed on, or the dispute itself.
+    pub source_id: String,
+    pub customer_id: String,
+    /// The disputed account; None for SARs, which cover every account.
+    pub account_id: Option<String>,
+    pub tick: Tick,
+    /// First tick of activity collected; the window runs to `tick`.
+    pub window_start: Tick,
+    /// Count and total of every transaction in the window.
+    pub transaction_count: usize,
+    pub transaction_total: f64,
+    /// The largest of them, largest first.
+    pub transactions: Vec<TimelineEntry>,
+    pub kyc: Option<KycRecord>,
+    pub prior_sars: Vec<PriorSar>,
+    /// Alerts raised in the window, the filing's own among them.
+    pub alerts: Vec<TimelineEntry>,
+    /// Contacts, investigator notes and case documents on file.
+    pub notes: Vec<TimelineEntry>,
+    /// Share of quality checks passed.
+    pub quality: f64,
+    /// The checks failed: "no_transactions" | "amount_unsupported" |
+    /// "kyc_incomplete" | "documents_missing".
+    pub gaps: Vec<String>,
+}
+
+pub struct EvidenceBundles {
+    run_id: RunId,
+    config: EvidenceBundleConfig,
+}
+
+impl EvidenceBundles {
+    pub fn new(run_id: RunId, config: EvidenceBundleConfig) -> Self {
+        Self { run_id, config }
+    }
+
+    pub fn enabled(&self) -> bool {
+        self.config.enabled
+    }
+
+    /// A SAR's bundle covers the customer's activity from the lookback
+    /// before the alert up to filing. The activity must add up to the
+    /// amount reported.
+    pub fn for_sar(
+        &self,
+        store: &SimStore,
+        sar:   &SuspiciousActivityReport,
+        alert: &AMLAlert,
+        tick:  Tick,
+    ) -> SimResult<SimEvent> {
+        let window_start = alert.tick.saturating_sub(self.config.lookback_ticks);
+        let entries =
+            store.entity_timeline(&self.run_id, EntityType::Customer, &alert.customer_id, Some(window_start), Some(tick))?;
+        let mut bundle = self.collect(store, "sar", &sar.sar_id, &alert.alert_id, &alert.customer_id, tick, window_start, entries)?;
+        bundle.prior_sars = store.prior_sars(&self.run_id, &alert.customer_id, tick, &sar.sar_id)?;
+
+        let checks = [
+            (bundle.transaction_count > 0, "no_transactions"),
+            (bundle.transaction_total >= sar.suspicious_amount, "amount_unsupported"),
+            (bundle.kyc.is_some(), "kyc_incomplete"),
+        ];
+        self.file(store, bundle, &checks)
+    }
+
+    /// A dispute's bundle covers the disputed account's activity over the
+    /// lookback before filing, which takes in the account's dispute notes
+    /// and documents. Every document the case requires must be on file.
+    pub fn for_dispute(&self, store: &SimStore, dispute: &DisputeRow, tick: Tick) -> SimResult<SimEvent> {
+        let window_start = (dispute.tick_filed as Tick).saturating_sub(self.config.lookback_ticks);
+        let entries =
+            store.entity_timeline(&self.run_id, EntityType::Account, &dispute.account_id, Some(window_start), Some(tick))?;
+        let mut bundle = self.collect(
+            store,
+            "dispute",
+            &dispute.dispute_id,
+            &dispute.dispute_id,
+            &dispute.customer_id,
+            tick,
+            window_start,
+            entries,
+        )?;
+        bundle.account_id = Some(dispute.account_id.clone());
+
+        let documents = store.case_documents(&self.run_id, "dispute", &dispute.dispute_id)?;
+        let checks = [
+            (bundle.transaction_count > 0, "no_transactions"),
+            (bundle.kyc.is_some(), "kyc_incomplete"),
+            (!documents.is_empty() && documents.iter().all(|d| d.status != "missing"), "documents_missing"),
+        ];
+        self.file(store, bundle, &checks)
+    }
+
+    /// The bundle's contents from the timeline entries in its window.
+    #[allow(clippy::too_many_arguments)]
+    fn collect(
+        &self,
+        store:        &SimStore,
+        filing_type:  &str,
+        filing_id:    &str,
+        source_id:    &str,
+        customer_id:  &str,
+        tick:         Tick,
+        window_start: Tick,
+        entries:      Vec<TimelineEntry>,
+    ) -> SimResult<EvidenceBundle> {
+        let of_kind = |kind| entries.iter().filter(move |e: &&TimelineEntry| e.kind == kind).cloned();
+        let mut transactions: Vec<TimelineEntry> = of_kind(TimelineKind::Transaction).collect();
+        let transaction_count = transactions.len();
+        let transaction_total = transactions.iter().fold(0.0, |sum, t| sum + t.amount.unwrap_or(0.0));
+        transactions.sort_by(|a, b| b.amount.unwrap_or(0.0).total_cmp(&a.amount.unwrap_or(0.0)));
+        transactions.truncate(self.config.max_transactions);
+
+        Ok(EvidenceBundle {
+            bundle_id: format!("evb-{filing_id}"),
+            filing_type: filing_type.into(),
+            filing_id: filing_id.into(),
+            source_id: source_id.into(),
+            customer_id: customer_id.into(),
+            account_id: None,
+            tick,
+            window_start,
+            transaction_count,
+            transaction_total,
+            transactions,
+            kyc: store.kyc_record(&self.run_id, customer_id)?,
+            prior_sars: Vec::new(),
+            alerts: of_kind(TimelineKind::Alert).collect(),
+            notes: of_kind(TimelineKind::Note).collect(),
+            quality: 0.0,
+            gaps: Vec::new(),
+        })
+    }
+
+    /// Score the bundle on its checks and keep it with the filing.
+    fn file(&self, store: &SimStore, mut bundle: EvidenceBundle, checks: &[(bool, &str)]) -> SimResult<SimEvent> {
+        let passed = checks.iter().filter(|(ok, _)| *ok).count();
+        bundle.quality = passed as f64 / checks.len() as f64;
+        bundle.gaps = checks.iter().filter(|(ok, _)| !ok).map(|(_, gap)| gap.to_string()).collect();
+        let deficient = bundle.quality < self.config.min_quality;
+        store.insert_evidence_bundle(&self.run_id, &bundle, deficient)?;
+
+        if deficient {
+            tracing::debug!(
+                "{} {} filed on a deficient evidence bundle: {}",
+                bundle.filing_type,
+                bundle.filing_id,
+                bundle.gaps.join(", ")
+            );
+        }
+        Ok(SimEvent::EvidenceBundleAssembled {
+            tick: bundle.tick,
+            filing_type: bundle.filing_type,
+            filing_id: bundle.filing_id,
+            customer_id: bundle.customer_id,
+            quality: bundle.quality,
+            gaps: bundle.gaps,
+        })
+    }
+}
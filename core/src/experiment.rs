@@ -0,0 +1,313 @@
+//! Headless Monte Carlo experiments (Phase 4.20).
+//!
+//! An `Experiment` runs one or more scenarios across a set of seeds on a
+//! pool of worker threads and collects whatever the metric closure
+//! extracts from each finished run into a tidy frame: one row per
+//! scenario, seed and metric. Every run gets its own shared-cache
+//! in-memory store, so nothing touches disk and runs never contend,
+//! unless `out_dir` asks for a database file per run to keep.
+//!
+//! ```no_run
+//! use fincrime_core::{config::SimConfig, experiment::{Experiment, Scenario}};
+//!
+//! let frame = Experiment::new(|engine| {
+//!     let active = engine.store.customer_count(&engine.run_id, "active")?;
+//!     Ok(vec![("active_customers".to_string(), active as f64)])
+//! })
+//! .scenario(Scenario::new("baseline", SimConfig::default_test(), 90))
+//! .seeds(1..=20)
+//! .threads(4)
+//! .run();
+//! println!("{}", frame.to_csv());
+//! ```
+
+use crate::{
+    command::PlayerCommand,
+    config::SimConfig,
+    engine::SimEngine,
+    error::SimResult,
+    store::SimStore,
+    types::Tick,
+};
+use std::{
+    path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Mutex,
+    },
+};
+
+/// Named metric values extracted from a finished run.
+pub type Metrics = Vec<(String, f64)>;
+
+/// A config, a run length and the player commands to play into it.
+#[derive(Debug, Clone)]
+pub struct Scenario {
+    pub name: String,
+    pub config: SimConfig,
+    pub ticks: Tick,
+    /// Commands and the tick they take effect on.
+    pub commands: Vec<(Tick, PlayerCommand)>,
+}
+
+impl Scenario {
+    pub fn new(name: impl Into<String>, config: SimConfig, ticks: Tick) -> Self {
+        Self {
+            name: name.into(),
+            config,
+            ticks,
+            commands: Vec::new(),
+        }
+    }
+
+    pub fn command(mut self, tick: Tick, command: PlayerCommand) -> Self {
+        self.commands.push((tick, command));
+        self
+    }
+}
+
+pub struct Experiment<F> {
+    scenarios: Vec<Scenario>,
+    seeds: Vec<u64>,
+    threads: usize,
+    out_dir: Option<PathBuf>,
+    metrics: F,
+}
+
+impl<F> Experiment<F>
+where
+    F: Fn(&SimEngine) -> SimResult<Metrics> + Sync,
+{
+    pub fn new(metrics: F) -> Self {
+        Self {
+            scenarios: Vec::new(),
+            seeds: Vec::new(),
+            threads: std::thread::available_parallelism().map_or(1, |n| n.get()),
+            out_dir: None,
+            metrics,
+        }
+    }
+
+    pub fn scenario(mut self, scenario: Scenario) -> Self {
+        self.scenarios.push(scenario);
+        self
+    }
+
+    pub fn seeds(mut self, seeds: impl IntoIterator<Item = u64>) -> Self {
+        self.seeds.extend(seeds);
+        self
+    }
+
+    /// Worker threads; defaults to the available parallelism.
+    pub fn threads(mut self, threads: usize) -> Self {
+        self.threads = threads.max(1);
+        self
+    }
+
+    /// Keep each run in `<dir>/<scenario>-<seed>.db`, replacing any file
+    /// already there, instead of in memory. The directory must exist.
+    pub fn out_dir(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.out_dir = Some(dir.into());
+        self
+    }
+
+    /// Run every scenario against every seed. Rows come back in scenario,
+    /// seed and metric order whatever order the workers finish in; a run
+    /// that fails is recorded in `failures` and the rest carry on.
+    pub fn run(&self) -> ResultsFrame {
+        let jobs: Vec<(usize, u64)> = (0..self.scenarios.len())
+            .flat_map(|s| self.seeds.iter().map(move |&seed| (s, seed)))
+            .collect();
+        let next = AtomicUsize::new(0);
+        let results = Mutex::new(Vec::with_capacity(jobs.len()));
+
+        std::thread::scope(|scope| {
+            for _ in 0..self.threads.min(jobs.len()) {
+                scope.spawn(|| {
+                    while let Some(&(s, seed)) = jobs.get(next.fetch_add(1, Ordering::Relaxed)) {
+                        let outcome = run_one(&self.scenarios[s], seed, self.out_dir.as_deref(), &self.metrics);
+                        results.lock().unwrap().push((s, seed, outcome));
+                    }
+                });
+            }
+        });
+
+        let mut results = results.into_inner().unwrap();
+        results.sort_by_key(|&(s, seed, _)| (s, seed));
+        let mut frame = ResultsFrame::default();
+        for (s, seed, outcome) in results {
+            let scenario = &self.scenarios[s].name;
+            match outcome {
+                Ok(metrics) => frame.rows.extend(metrics.into_iter().map(|(metric, value)| ResultRow {
+                    scenario: scenario.clone(),
+                    seed,
+                    metric,
+                    value,
+                })),
+                Err(e) => frame.failures.push(RunFailure {
+                    scenario: scenario.clone(),
+                    seed,
+                    error: e.to_string(),
+                }),
+            }
+        }
+        frame
+    }
+}
+
+/// Build one run on its own store, play the scenario through and extract
+/// its metrics.
+fn run_one<F>(scenario: &Scenario, seed: u64, out_dir: Option<&Path>, metrics: &F) -> SimResult<Metrics>
+where
+    F: Fn(&SimEngine) -> SimResult<Metrics>,
+{
+    let run_id = format!("exp-{}-{seed}", scenario.name);
+    let db = match out_dir {
+        Some(dir) => {
+            let path = run_db_path(dir, &scenario.name, seed);
+            // A fresh file; migrating an old one again would fail
+            if path.exists() {
+                std::fs::remove_file(&path).map_err(|e| anyhow::anyhow!("Cannot replace {}: {e}", path.display()))?;
+            }
+            path.to_string_lossy().into_owned()
+        }
+        None => format!("file:exp_{}?mode=memory&cache=shared", uuid::Uuid::new_v4()),
+    };
+    let store = SimStore::open(&db)?;
+    store.migrate()?;
+    store.insert_run(&run_id, seed, env!("CARGO_PKG_VERSION"))?;
+    let mut engine = SimEngine::build_with_config(run_id, seed, &store, scenario.config.clone())?;
+
+    let mut commands: Vec<_> = scenario.commands.iter().collect();
+    commands.sort_by_key(|(tick, _)| *tick);
+    for (tick, command) in commands {
+        // A command submitted now is picked up by the next tick
+        let submit_at = tick.saturating_sub(1).min(scenario.ticks);
+        engine.run_ticks(submit_at.saturating_sub(engine.clock.current_tick))?;
+        engine.submit_command(command.clone())?;
+    }
+    engine.run_ticks(scenario.ticks.saturating_sub(engine.clock.current_tick))?;
+    metrics(&engine)
+}
+
+/// Where `Experiment::out_dir` keeps the run of `scenario` from `seed`.
+pub fn run_db_path(dir: &Path, scenario: &str, seed: u64) -> PathBuf {
+    dir.join(format!("{scenario}-{seed}.db"))
+}
+
+/// One metric value from one run.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ResultRow {
+    pub scenario: String,
+    pub seed: u64,
+    pub metric: String,
+    pub value: f64,
+}
+
+#[derive(Debug, Clone)]
+pub struct RunFailure {
+    pub scenario: String,
+    pub seed: u64,
+    pub error: String,
+}
+
+/// Summary statistics of one metric across a scenario's seeds.
+#[derive(Debug, Clone)]
+pub struct MetricSummary {
+    pub scenario: String,
+    pub metric: String,
+    pub runs: usize,
+    pub mean: f64,
+    /// Sample standard deviation; 0.0 for a single run.
+    pub std_dev: f64,
+    pub min: f64,
+    pub max: f64,
+}
+
+/// Tidy experiment results: one row per scenario, seed and metric.
+#[derive(Debug, Clone, Default)]
+pub struct ResultsFrame {
+    pub rows: Vec<ResultRow>,
+    pub failures: Vec<RunFailure>,
+}
+
+impl ResultsFrame {
+    /// Values of one metric for one scenario, in seed order.
+    pub fn values(&self, scenario: &str, metric: &str) -> Vec<f64> {
+        self.rows
+            .iter()
+            .filter(|r| r.scenario == scenario && r.metric == metric)
+            .map(|r| r.value)
+            .collect()
+    }
+
+    /// Per scenario and metric, in first-seen order.
+    pub fn summarize(&self) -> Vec<MetricSummary> {
+        let mut keys: Vec<(&str, &str)> = Vec::new();
+        for row in &self.rows {
+            let key = (row.scenario.as_str(), row.metric.as_str());
+            if !keys.contains(&key) {
+                keys.push(key);
+            }
+        }
+        keys.into_iter()
+            .map(|(scenario, metric)| {
+                let values = self.values(scenario, metric);
+                let runs = values.len();
+                let mean = values.iter().sum::<f64>() / runs as f64;
+                let variance = if runs > 1 {
+                    values.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / (runs - 1) as f64
+                } else {
+                    0.0
+                };
+                MetricSummary {
+                    scenario: scenario.to_string(),
+                    metric: metric.to_string(),
+                    runs,
+                    mean,
+                    std_dev: variance.sqrt(),
+                    min: values.iter().copied().fold(f64::INFINITY, f64::min),
+                    max: values.iter().copied().fold(f64::NEG_INFINITY, f64::max),
+                }
+            })
+            .collect()
+    }
+
+    /// The rows as CSV with a `scenario,seed,metric,value` header.
+    pub fn to_csv(&self) -> String {
+        let mut csv = String::from("scenario,seed,metric,value\n");
+        for row in &self.rows {
+            csv.push_str(&format!("{},{},{},{}\n", row.scenario, row.seed, row.metric, row.value));
+        }
+        csv
+    }
+
+    /// One line per run with a column per metric, in first-seen order;
+    /// a metric a run did not report is left empty.
+    pub fn to_wide_csv(&self) -> String {
+        let mut metrics: Vec<&str> = Vec::new();
+        let mut runs: Vec<(&str, u64)> = Vec::new();
+        for row in &self.rows {
+            if !metrics.contains(&row.metric.as_str()) {
+                metrics.push(&row.metric);
+            }
+            if runs.last() != Some(&(row.scenario.as_str(), row.seed)) {
+                runs.push((&row.scenario, row.seed));
+            }
+        }
+        let mut csv = format!("scenario,seed,{}\n", metrics.join(","));
+        for (scenario, seed) in runs {
+            let values: Vec<String> = metrics
+                .iter()
+                .map(|&metric| {
+                    self.rows
+                        .iter()
+                        .find(|r| r.scenario == scenario && r.seed == seed && r.metric == metric)
+                        .map_or(String::new(), |r| r.value.to_string())
+                })
+                .collect();
+            csv.push_str(&format!("{scenario},{seed},{}\n", values.join(",")));
+        }
+        csv
+    }
+}
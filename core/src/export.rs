@@ -0,0 +1,319 @@
+//! Run data export (Phase 4.79).
+//!
+//! The SQLite store suits the engine; pandas and R want flat files.
+//! `SimStore::export_table` writes one run's rows of a table to CSV or,
+//! built with the `parquet` feature, Parquet, and `export_run` writes the
+//! tables most analyses start from: transactions, complaints, the
+//! quarterly P&L, churn scores and the event log.
+//!
+//! CSV follows RFC 4180: a header line, fields with a comma, quote or
+//! line break quoted, NULL left empty and blobs written as hex. Parquet
+//! columns take the table's declared type (INTEGER, REAL, TEXT or BLOB,
+//! by SQLite's affinity rules), are all nullable, and are GZIP
+//! compressed. Rows come out in the order they were written.
+//!
+//! `event_log` is exported from both tiers: ticks compacted into the
+//! archive come first, without the `id` and `created_at` the archive
+//! drops, then the detailed rows.
+
+use crate::{error::SimResult, store::SimStore};
+use rusqlite::types::Value;
+use serde::Serialize;
+use std::{
+    fmt,
+    fs::File,
+    io::{BufWriter, Write},
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+
+/// The tables `export_run` writes.
+pub const EXPORT_TABLES: &[&str] = &["transactions", "complaint", "pnl_snapshot", "customer_churn_score", "event_log"];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ExportFormat {
+    Csv,
+    Parquet,
+}
+
+impl ExportFormat {
+    pub fn extension(self) -> &'static str {
+        match self {
+            Self::Csv => "csv",
+            Self::Parquet => "parquet",
+        }
+    }
+}
+
+impl fmt::Display for ExportFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.extension())
+    }
+}
+
+impl FromStr for ExportFormat {
+    type Err = anyhow::Error;
+
+    /// Parquet is refused here, before any run, by a build without it.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "csv" => Ok(Self::Csv),
+            "parquet" if cfg!(feature = "parquet") => Ok(Self::Parquet),
+            "parquet" => Err(no_parquet()),
+            other => Err(anyhow::anyhow!("unknown export format '{other}'; expected csv or parquet")),
+        }
+    }
+}
+
+fn no_parquet() -> anyhow::Error {
+    anyhow::anyhow!("built without Parquet support; rebuild with `--features parquet`")
+}
+
+/// How a column is written, from its declared type.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ColumnKind {
+    Integer,
+    Real,
+    Text,
+    Blob,
+}
+
+impl ColumnKind {
+    /// SQLite's affinity rules, with NUMERIC and untyped columns, which
+    /// may hold anything, written as text.
+    pub fn from_declared(declared: &str) -> Self {
+        let declared = declared.to_ascii_uppercase();
+        if declared.contains("INT") {
+            Self::Integer
+        } else if ["CHAR", "CLOB", "TEXT"].iter().any(|t| declared.contains(t)) {
+            Self::Text
+        } else if declared.contains("BLOB") {
+            Self::Blob
+        } else if ["REAL", "FLOA", "DOUB"].iter().any(|t| declared.contains(t)) {
+            Self::Real
+        } else {
+            Self::Text
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Column {
+    pub name: String,
+    pub kind: ColumnKind,
+}
+
+/// One table written by `export_run`.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ExportedTable {
+    pub table: String,
+    pub path: PathBuf,
+    pub rows: usize,
+}
+
+/// Write each of `EXPORT_TABLES` for the run to
+/// `<dir>/<run_id>.<table>.<format>`, creating `dir` if need be.
+pub fn export_run(store: &SimStore, run_id: &str, format: ExportFormat, dir: &Path) -> SimResult<Vec<ExportedTable>> {
+    std::fs::create_dir_all(dir).map_err(|e| anyhow::anyhow!("Cannot create {}: {e}", dir.display()))?;
+    EXPORT_TABLES
+        .iter()
+        .map(|&table| {
+            let path = dir.join(format!("{run_id}.{table}.{}", format.extension()));
+            let rows = store.export_table(run_id, table, format, &path)?;
+            Ok(ExportedTable {
+                table: table.to_string(),
+                path,
+                rows,
+            })
+        })
+        .collect()
+}
+
+/// Streams rows to a file in one format.
+pub(crate) enum TableWriter {
+    Csv(BufWriter<File>),
+    #[cfg(feature = "parquet")]
+    Parquet(Box<parquet_table::ParquetTable>),
+}
+
+impl TableWriter {
+    pub(crate) fn create(format: ExportFormat, path: &Path, columns: &[Column]) -> SimResult<Self> {
+        let file = File::create(path).map_err(|e| anyhow::anyhow!("Cannot create {}: {e}", path.display()))?;
+        match format {
+            ExportFormat::Csv => {
+                let mut out = BufWriter::new(file);
+                let header: Vec<String> = columns.iter().map(|c| csv_field(&c.name)).collect();
+                writeln!(out, "{}", header.join(",")).map_err(anyhow::Error::from)?;
+                Ok(Self::Csv(out))
+            }
+            #[cfg(feature = "parquet")]
+            ExportFormat::Parquet => Ok(Self::Parquet(Box::new(parquet_table::ParquetTable::create(file, columns)?))),
+            #[cfg(not(feature = "parquet"))]
+            ExportFormat::Parquet => Err(no_parquet().into()),
+        }
+    }
+
+    pub(crate) fn push(&mut self, row: &[Value]) -> SimResult<()> {
+        match self {
+            Self::Csv(out) => {
+                let fields: Vec<String> = row.iter().map(|v| csv_field(&text(v))).collect();
+                writeln!(out, "{}", fields.join(",")).map_err(anyhow::Error::from)?;
+                Ok(())
+            }
+            #[cfg(feature = "parquet")]
+            Self::Parquet(table) => table.push(row),
+        }
+    }
+
+    pub(crate) fn finish(self) -> SimResult<()> {
+        match self {
+            Self::Csv(mut out) => Ok(out.flush().map_err(anyhow::Error::from)?),
+            #[cfg(feature = "parquet")]
+            Self::Parquet(table) => table.finish(),
+        }
+    }
+}
+
+/// A value as text: NULL empty, numbers as Rust prints them, blobs as hex.
+fn text(value: &Value) -> String {
+    match value {
+        Value::Null => String::new(),
+        Value::Integer(i) => i.to_string(),
+        Value::Real(f) => f.to_string(),
+        Value::Text(s) => s.clone(),
+        Value::Blob(bytes) => bytes.iter().map(|b| format!("{b:02x}")).collect(),
+    }
+}
+
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+#[cfg(feature = "parquet")]
+mod parquet_table {
+    use super::{text, Column, ColumnKind};
+    use crate::error::SimResult;
+    use arrow_array::{
+        builder::{ArrayBuilder, BinaryBuilder, Float64Builder, Int64Builder, StringBuilder},
+        ArrayRef, RecordBatch,
+    };
+    use arrow_schema::{DataType, Field, Schema, SchemaRef};
+    use parquet::{
+        arrow::ArrowWriter,
+        basic::{Compression, GzipLevel},
+        file::properties::WriterProperties,
+    };
+    use rusqlite::types::Value;
+    use std::{fs::File, sync::Arc};
+
+    /// Rows buffered before a record batch is written.
+    const BATCH_ROWS: usize = 8192;
+
+    pub(crate) struct ParquetTable {
+        writer: ArrowWriter<File>,
+        schema: SchemaRef,
+        builders: Vec<Builder>,
+        buffered: usize,
+    }
+
+    enum Builder {
+        Integer(Int64Builder),
+        Real(Float64Builder),
+        Text(StringBuilder),
+        Blob(BinaryBuilder),
+    }
+
+    impl Builder {
+        fn finish(&mut self) -> ArrayRef {
+            match self {
+                Self::Integer(b) => ArrayBuilder::finish(b),
+                Self::Real(b) => ArrayBuilder::finish(b),
+                Self::Text(b) => ArrayBuilder::finish(b),
+                Self::Blob(b) => ArrayBuilder::finish(b),
+            }
+        }
+    }
+
+    impl ParquetTable {
+        pub(crate) fn create(file: File, columns: &[Column]) -> SimResult<Self> {
+            let fields: Vec<Field> = columns
+                .iter()
+                .map(|c| {
+                    let data_type = match c.kind {
+                        ColumnKind::Integer => DataType::Int64,
+                        ColumnKind::Real => DataType::Float64,
+                        ColumnKind::Text => DataType::Utf8,
+                        ColumnKind::Blob => DataType::Binary,
+                    };
+                    Field::new(&c.name, data_type, true)
+                })
+                .collect();
+            let schema = Arc::new(Schema::new(fields));
+            let properties = WriterProperties::builder()
+                .set_compression(Compression::GZIP(GzipLevel::default()))
+                .build();
+            let writer = ArrowWriter::try_new(file, schema.clone(), Some(properties)).map_err(anyhow::Error::from)?;
+            let builders = columns
+                .iter()
+                .map(|c| match c.kind {
+                    ColumnKind::Integer => Builder::Integer(Int64Builder::new()),
+                    ColumnKind::Real => Builder::Real(Float64Builder::new()),
+                    ColumnKind::Text => Builder::Text(StringBuilder::new()),
+                    ColumnKind::Blob => Builder::Blob(BinaryBuilder::new()),
+                })
+                .collect();
+            Ok(Self {
+                writer,
+                schema,
+                builders,
+                buffered: 0,
+            })
+        }
+
+        /// A value that does not fit its column's type is converted where
+        /// SQLite would, and written as null where it would not.
+        pub(crate) fn push(&mut self, row: &[Value]) -> SimResult<()> {
+            for (builder, value) in self.builders.iter_mut().zip(row) {
+                match (builder, value) {
+                    (Builder::Integer(b), Value::Integer(i)) => b.append_value(*i),
+                    (Builder::Integer(b), Value::Real(f)) => b.append_value(*f as i64),
+                    (Builder::Integer(b), _) => b.append_null(),
+                    (Builder::Real(b), Value::Real(f)) => b.append_value(*f),
+                    (Builder::Real(b), Value::Integer(i)) => b.append_value(*i as f64),
+                    (Builder::Real(b), _) => b.append_null(),
+                    (Builder::Text(b), Value::Null) => b.append_null(),
+                    (Builder::Text(b), value) => b.append_value(text(value)),
+                    (Builder::Blob(b), Value::Null) => b.append_null(),
+                    (Builder::Blob(b), Value::Blob(bytes)) => b.append_value(bytes),
+                    (Builder::Blob(b), value) => b.append_value(text(value).as_bytes()),
+                }
+            }
+            self.buffered += 1;
+            if self.buffered == BATCH_ROWS {
+                self.flush()?;
+            }
+            Ok(())
+        }
+
+        fn flush(&mut self) -> SimResult<()> {
+            let arrays = self.builders.iter_mut().map(Builder::finish).collect();
+            let batch = RecordBatch::try_new(self.schema.clone(), arrays).map_err(anyhow::Error::from)?;
+            self.writer.write(&batch).map_err(anyhow::Error::from)?;
+            self.buffered = 0;
+            Ok(())
+        }
+
+        pub(crate) fn finish(mut self) -> SimResult<()> {
+            if self.buffered > 0 {
+                self.flush()?;
+            }
+            self.writer.close().map_err(anyhow::Error::from)?;
+            Ok(())
+        }
+    }
+}
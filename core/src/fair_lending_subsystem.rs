@@ -0,0 +1,333 @@
+//! Fair Lending subsystem — Phase 4.4.
+//!
+//! On first update, generates census-like demographics for every zip in
+//! the customer address book: population, median income (relative to the
+//! state median), minority share, LMI flag and a majority-minority flag.
+//!
+//! Every `review_interval_ticks` it compares outcomes for customers in
+//! majority-minority zips (protected) against all other zips (control):
+//!   - fee_burden       — overdraft fees per customer   (protected / control)
+//!   - account_closure  — closures per customer         (protected / control)
+//!   - offer_targeting  — offers per customer           (control / protected)
+//!
+//! An adverse ratio above `adverse_ratio_threshold` raises a finding the
+//! player must answer with RespondFairLendingFinding within
+//! `response_window_ticks`:
+//!   - "remediate" — lowers the UDAAP score by `remediation_udaap_relief`
+//!     at a `remediation_cost` booked to opex (EconomicsSubsystem)
+//!   - "justify"   — documents a business justification, no score change
+//!
+//! Findings left unanswered are escalated and raise the UDAAP score.
+//!
+//! Execution: every tick, after Branch (reviews read this tick's fees,
+//!   closures and offers).
+//! Depends on: customer_address (CustomerSubsystem), transactions,
+//!   customer.close_tick (ChurnSubsystem), customer_offer (OfferSubsystem),
+//!   regulatory_score (PricingSubsystem).
+
+use crate::{
+    command::PlayerCommand,
+    config::FairLendingConfig,
+    error::SimResult,
+    event::SimEvent,
+    rng::SubsystemRng,
+    store::{
+        fair_lending::{FairLendingFindingRow, FairLendingGroupMetrics, ZipDemographicsRow},
+        SimStore,
+    },
+    subsystem::{SimSubsystem, SubsystemManifest},
+    types::{RunId, Tick},
+};
+
+/// Used when a state is missing from `state_config`.
+const FALLBACK_STATE_MEDIAN_INCOME: f64 = 75_000.0;
+
+/// Ways the player can answer a finding.
+pub const RESPONSES: &[&str] = &["remediate", "justify"];
+
+pub struct FairLendingSubsystem {
+    run_id:      RunId,
+    config:      FairLendingConfig,
+    store:       SimStore,
+    initialized: bool,
+}
+
+impl FairLendingSubsystem {
+    pub fn new(run_id: RunId, config: FairLendingConfig, store: SimStore) -> Self {
+        Self { run_id, config, store, initialized: false }
+    }
+
+    /// Generate demographics for every zip customers live in.
+    fn generate_demographics(&self, rng: &mut SubsystemRng) -> SimResult<usize> {
+        let zips = self.store.customer_zip_codes(&self.run_id)?;
+        for (zip_code, state_code) in &zips {
+            let state_median = self
+                .store
+                .state_median_income(state_code)?
+                .unwrap_or(FALLBACK_STATE_MEDIAN_INCOME);
+
+            // Majority-minority zips skew lower-income, mirroring census patterns.
+            let high_minority = rng.chance(self.config.high_minority_zip_share);
+            let (minority_share, income_factor) = if high_minority {
+                (0.50 + 0.45 * rng.next_f64(), 0.55 + 0.50 * rng.next_f64())
+            } else {
+                (0.05 + 0.40 * rng.next_f64(), 0.75 + 0.70 * rng.next_f64())
+            };
+            let median_income = (state_median * income_factor).round();
+
+            self.store.insert_zip_demographics(&self.run_id, &ZipDemographicsRow {
+                zip_code:          zip_code.clone(),
+                state_code:        state_code.clone(),
+                population:        5_000 + rng.next_u64_below(40_000) as i64,
+                median_income,
+                minority_share,
+                lmi_flag:          median_income < state_median * self.config.lmi_income_ratio,
+                majority_minority: minority_share >= 0.5,
+            })?;
+        }
+        Ok(zips.len())
+    }
+
+    /// Compute the three disparity metrics and raise findings.
+    fn review(&self, tick: Tick) -> SimResult<Vec<SimEvent>> {
+        let start = tick.saturating_sub(self.config.review_interval_ticks);
+        let protected = self.store.fair_lending_group_metrics(&self.run_id, start, tick, true)?;
+        let control = self.store.fair_lending_group_metrics(&self.run_id, start, tick, false)?;
+
+        if protected.customers < self.config.min_group_size
+            || control.customers < self.config.min_group_size
+        {
+            tracing::debug!(
+                "groups too small ({} protected, {} control)",
+                protected.customers, control.customers,
+            );
+            return Ok(vec![SimEvent::FairLendingReviewCompleted {
+                tick,
+                metrics_evaluated: 0,
+                findings_raised: 0,
+            }]);
+        }
+
+        let rate = |m: &FairLendingGroupMetrics, v: f64| v / m.customers as f64;
+        let metrics = [
+            (
+                "fee_burden",
+                rate(&protected, protected.overdraft_fees),
+                rate(&control, control.overdraft_fees),
+                false,
+            ),
+            (
+                "account_closure",
+                rate(&protected, protected.closures as f64),
+                rate(&control, control.closures as f64),
+                false,
+            ),
+            (
+                "offer_targeting",
+                rate(&protected, protected.offers as f64),
+                rate(&control, control.offers as f64),
+                true, // fewer offers is the adverse outcome
+            ),
+        ];
+
+        let mut out = Vec::new();
+        let mut evaluated = 0i64;
+        let open = self.store.open_fair_lending_findings(&self.run_id)?;
+        for (metric, protected_value, control_value, inverse) in metrics {
+            let (num, den) = if inverse {
+                (control_value, protected_value)
+            } else {
+                (protected_value, control_value)
+            };
+            if den <= 0.0 {
+                continue; // no baseline to compare against this period
+            }
+            evaluated += 1;
+
+            let adverse_ratio = num / den;
+            let flagged = adverse_ratio > self.config.adverse_ratio_threshold;
+            self.store.insert_fair_lending_review(
+                &self.run_id, tick, metric, protected_value, control_value, adverse_ratio, flagged,
+            )?;
+
+            // One open finding per metric; a repeat disparity stays on the existing one.
+            if !flagged || open.iter().any(|f| f.metric == metric) {
+                continue;
+            }
+            let severity = if adverse_ratio >= self.config.major_ratio_threshold {
+                "major"
+            } else {
+                "moderate"
+            };
+            let finding_id = format!("flf-{tick}-{metric}");
+            self.store.insert_fair_lending_finding(&self.run_id, &FairLendingFindingRow {
+                finding_id:        finding_id.clone(),
+                tick,
+                metric:            metric.to_string(),
+                adverse_ratio,
+                severity:          severity.to_string(),
+                status:            "open".to_string(),
+                response_due_tick: tick + self.config.response_window_ticks,
+            })?;
+            tracing::warn!(
+                "{severity} {metric} disparity, ratio {adverse_ratio:.2}"
+            );
+            out.push(SimEvent::FairLendingFindingRaised {
+                tick,
+                finding_id,
+                metric: metric.to_string(),
+                adverse_ratio,
+                severity: severity.to_string(),
+            });
+        }
+
+        out.push(SimEvent::FairLendingReviewCompleted {
+            tick,
+            metrics_evaluated: evaluated,
+            findings_raised: out.len() as i64,
+        });
+        Ok(out)
+    }
+
+    fn respond(&self, finding_id: &str, response: &str, tick: Tick) -> SimResult<Option<SimEvent>> {
+        match self.store.get_fair_lending_finding(&self.run_id, finding_id)? {
+            Some(f) if f.status == "open" => {}
+            _ => {
+                tracing::warn!("{finding_id} is not an open finding");
+                return Ok(None);
+            }
+        }
+
+        let status = match response {
+            "remediate" => {
+                let relief = self
+                    .config
+                    .remediation_udaap_relief
+                    .min(self.store.get_udaap_score(&self.run_id)?.max(0.0));
+                self.store.adjust_udaap_score(&self.run_id, -relief, tick)?;
+                tracing::info!(
+                    "remediating {finding_id} at ${:.0}",
+                    self.config.remediation_cost,
+                );
+                "remediated"
+            }
+            "justify" => "justified",
+            other => {
+                tracing::warn!("unknown response '{other}' for {finding_id}");
+                return Ok(None);
+            }
+        };
+        self.store
+            .set_fair_lending_finding_status(&self.run_id, finding_id, status, tick, true)?;
+
+        Ok(Some(SimEvent::FairLendingFindingResolved {
+            tick,
+            finding_id: finding_id.to_string(),
+            response: response.to_string(),
+        }))
+    }
+
+    /// Escalate findings past their response deadline.
+    fn escalate_overdue(&self, tick: Tick) -> SimResult<Vec<SimEvent>> {
+        let mut out = Vec::new();
+        for finding in self.store.open_fair_lending_findings(&self.run_id)? {
+            if finding.response_due_tick >= tick {
+                continue;
+            }
+            self.store.set_fair_lending_finding_status(
+                &self.run_id, &finding.finding_id, "escalated", tick, false,
+            )?;
+            self.store
+                .adjust_udaap_score(&self.run_id, self.config.escalation_udaap_impact, tick)?;
+            tracing::warn!(
+                "{} unanswered, escalated",
+                finding.finding_id,
+            );
+            out.push(SimEvent::FairLendingFindingEscalated {
+                tick,
+                finding_id: finding.finding_id,
+                metric: finding.metric,
+            });
+        }
+        Ok(out)
+    }
+}
+
+const MANIFEST: SubsystemManifest = SubsystemManifest {
+    version: 1,
+    produces: &[
+        "fair_lending_finding_escalated", "fair_lending_finding_raised",
+        "fair_lending_finding_resolved", "fair_lending_review_completed",
+    ],
+    consumes: &["player_command_received"],
+    tables: &[
+        "fair_lending_finding", "fair_lending_review", "regulatory_score", "zip_demographics",
+    ],
+    requires: &[],
+};
+
+impl SimSubsystem for FairLendingSubsystem {
+    fn name(&self) -> &'static str {
+        "fair_lending"
+    }
+
+    fn manifest(&self) -> SubsystemManifest {
+        MANIFEST
+    }
+
+    fn update(
+        &mut self,
+        tick:      Tick,
+        events_in: &[SimEvent],
+        rng:       &mut SubsystemRng,
+    ) -> SimResult<Vec<SimEvent>> {
+        let mut out = Vec::new();
+
+        if !self.config.enabled || tick == 0 {
+            return Ok(out);
+        }
+
+        if !self.initialized {
+            self.initialized = true;
+            let zips = self.generate_demographics(rng)?;
+            tracing::info!("demographics for {zips} zips");
+        }
+
+        for event in events_in {
+            if let SimEvent::PlayerCommandReceived { command_id, .. } = event {
+                if let Some(PlayerCommand::RespondFairLendingFinding { finding_id, response }) =
+                    self.store.get_player_command(&self.run_id, command_id)?
+                {
+                    out.extend(self.respond(&finding_id, &response, tick)?);
+                }
+            }
+        }
+
+        out.extend(self.escalate_overdue(tick)?);
+
+        if tick.is_multiple_of(self.config.review_interval_ticks) {
+            out.extend(self.review(tick)?);
+        }
+
+        Ok(out)
+    }
+
+    fn save_state(&self) -> SimResult<Option<serde_json::Value>> {
+        Ok(Some(serde_json::json!({ "initialized": self.initialized })))
+    }
+
+    fn load_state(&mut self, state: serde_json::Value) -> SimResult<()> {
+        self.initialized = state["initialized"].as_bool().unwrap_or_default();
+        Ok(())
+    }
+
+    // Demographics are generated on the first tick played while enabled
+    fn replay(&mut self, _tick: Tick, _events: &[SimEvent]) -> SimResult<()> {
+        self.initialized |= self.config.enabled;
+        Ok(())
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+}
@@ -0,0 +1,64 @@
+//! Per-run feature flags (Phase 4.42).
+//!
+//! A scenario can leave subsystems out of a run, for ablation studies
+//! such as a bank that never makes offers. A disabled subsystem is not
+//! registered, so it neither ticks nor writes; the others keep their own
+//! RNG streams and draw exactly what they would have with it on.
+//!
+//! Flags either list what is left out or, from Phase 4.77, the only
+//! subsystems that run; a scenario file sets the latter.
+//!
+//! The core of the bank (macro, customers, transactions) cannot be
+//! turned off, nor can a subsystem another enabled one `requires` in its
+//! manifest — disputes, for instance, are raised on the card rail the
+//! payment hub runs. The flags are stored on the run for replay.
+
+use crate::{
+    config::FeatureFlagsConfig,
+    error::{SimError, SimResult},
+    subsystem::SubsystemInfo,
+};
+
+/// Subsystems every run needs.
+pub const CORE_SUBSYSTEMS: &[&str] = &["macro", "customer", "transaction"];
+
+impl FeatureFlagsConfig {
+    pub fn is_disabled(&self, subsystem: &str) -> bool {
+        let left_out = self.enabled_subsystems.as_ref().is_some_and(|enabled| {
+            !CORE_SUBSYSTEMS.contains(&subsystem) && !enabled.iter().any(|e| e == subsystem)
+        });
+        left_out || self.disabled_subsystems.iter().any(|d| d == subsystem)
+    }
+
+    /// Check the flags against the subsystems a build registers: every
+    /// name must be one of them, none may be core, and nothing left on
+    /// may require something switched off.
+    pub fn validate(&self, registered: &[SubsystemInfo]) -> SimResult<()> {
+        for name in &self.disabled_subsystems {
+            if !registered.iter().any(|s| &s.name == name) {
+                return Err(invalid(format!("unknown subsystem '{name}'")));
+            }
+            if CORE_SUBSYSTEMS.contains(&name.as_str()) {
+                return Err(invalid(format!("'{name}' is a core subsystem and cannot be disabled")));
+            }
+        }
+        for (i, name) in self.enabled_subsystems.iter().flatten().enumerate() {
+            if !registered.iter().any(|s| &s.name == name) {
+                return Err(invalid(format!("enabled_subsystems[{i}]: unknown subsystem '{name}'")));
+            }
+        }
+        for info in registered.iter().filter(|s| !self.is_disabled(&s.name)) {
+            if let Some(missing) = info.requires.iter().find(|r| self.is_disabled(r)) {
+                return Err(invalid(format!(
+                    "'{}' requires '{missing}'; disable both or neither",
+                    info.name
+                )));
+            }
+        }
+        Ok(())
+    }
+}
+
+fn invalid(reason: String) -> SimError {
+    SimError::generator("feature_flags", reason)
+}
@@ -0,0 +1,219 @@
+//! Customer financial health and credit cross-sell (Phase 4.34).
+//!
+//! Each month every active customer gets a 0..1 financial health score
+//! from three components over the lookback window:
+//!   - balance volatility: spread of their daily balance relative to its
+//!     level
+//!   - overdraft frequency: overdraft fees charged
+//!   - payroll stability: paydays received against the biweekly schedule
+//!
+//! With transaction enrichment on (Phase 4.35), the score also loses
+//! `high_risk_spend_penalty` per unit of the customer's gambling and
+//! crypto share of merchant spend, by enriched category.
+//!
+//! Credit offers only go to customers at or above the minimum score,
+//! which the player can lower. Every credit line opened for a low-health
+//! customer adds UDAAP exposure, and each open line can default monthly
+//! with a probability that rises as the holder's health falls; defaults
+//! are booked as credit losses.
+
+use crate::{
+    config::{FinancialHealthConfig, OfferConfig, TransactionEnrichmentConfig},
+    enrichment::TransactionEnrichment,
+    error::SimResult,
+    event::SimEvent,
+    rng::SubsystemRng,
+    store::{
+        financial_health::{CreditLineRow, CustomerHealthRow},
+        SimStore,
+    },
+    types::{RunId, Tick},
+};
+use std::collections::HashMap;
+
+/// Floor on the mean balance volatility is measured against, so a
+/// balance hovering near zero does not divide the spread by nothing.
+const BALANCE_FLOOR: f64 = 100.0;
+
+/// Payroll lands every 14 ticks.
+const PAYROLL_INTERVAL_TICKS: f64 = 14.0;
+
+pub struct FinancialHealth {
+    run_id: RunId,
+    config: FinancialHealthConfig,
+    enrichment: TransactionEnrichment,
+}
+
+impl FinancialHealth {
+    pub fn new(run_id: RunId, config: FinancialHealthConfig, enrichment: TransactionEnrichmentConfig) -> Self {
+        Self {
+            enrichment: TransactionEnrichment::new(run_id.clone(), enrichment),
+            run_id,
+            config,
+        }
+    }
+
+    pub fn enabled(&self) -> bool {
+        self.config.enabled
+    }
+
+    pub fn is_credit_offer(&self, offer: &OfferConfig) -> bool {
+        self.config.credit_offer_types.contains(&offer.offer_type)
+    }
+
+    /// Score the customer over the window ending at `tick`.
+    pub fn score(&self, store: &SimStore, customer_id: &str, tick: Tick) -> SimResult<CustomerHealthRow> {
+        let start = (tick + 1).saturating_sub(self.config.lookback_ticks);
+        let days = (tick + 1 - start) as f64;
+
+        // Walk the balance back from today through each day's net flow
+        let flows: HashMap<Tick, f64> = store
+            .customer_daily_net_flows(&self.run_id, customer_id, start, tick)?
+            .into_iter()
+            .collect();
+        let mut balance = store.get_customer_activity(&self.run_id, customer_id, tick)?.balance;
+        let mut balances = Vec::new();
+        for t in (start..=tick).rev() {
+            balances.push(balance);
+            balance -= flows.get(&t).copied().unwrap_or(0.0);
+        }
+        let mean = balances.iter().sum::<f64>() / days;
+        let variance = balances.iter().map(|b| (b - mean).powi(2)).sum::<f64>() / days;
+        let balance_volatility = variance.sqrt() / mean.abs().max(BALANCE_FLOOR);
+
+        let overdraft_count =
+            store.customer_transaction_count(&self.run_id, customer_id, "overdraft_fee", start, tick)?;
+        let paydays = store.customer_transaction_count(&self.run_id, customer_id, "payroll", start, tick)?;
+        let payroll_stability = (paydays as f64 / (days / PAYROLL_INTERVAL_TICKS).max(1.0)).min(1.0);
+
+        // Phase 4.35: gambling and crypto share of merchant spend
+        let high_risk_share = if self.enrichment.enabled() {
+            self.enrichment
+                .intensity(store, Some(customer_id), tick)?
+                .get(customer_id)
+                .map_or(0.0, |spend| spend.share())
+        } else {
+            0.0
+        };
+
+        let c = &self.config;
+        let score = c.volatility_weight * (1.0 - (balance_volatility / c.volatility_saturation).min(1.0))
+            + c.overdraft_weight * (1.0 - (overdraft_count as f64 / c.overdraft_saturation as f64).min(1.0))
+            + c.payroll_weight * payroll_stability
+            - c.high_risk_spend_penalty * high_risk_share;
+        Ok(CustomerHealthRow {
+            customer_id: customer_id.into(),
+            tick,
+            score: score.max(0.0),
+            balance_volatility,
+            overdraft_count,
+            payroll_stability,
+            high_risk_share,
+        })
+    }
+
+    /// Monthly pass: score every active customer.
+    pub fn rescore(&self, store: &SimStore, tick: Tick) -> SimResult<SimEvent> {
+        let (mut scored, mut low_health, mut total) = (0i64, 0i64, 0.0);
+        for customer in store.active_customers(&self.run_id)? {
+            let row = self.score(store, &customer.customer_id, tick)?;
+            store.insert_customer_health(&self.run_id, &row)?;
+            scored += 1;
+            low_health += (row.score < self.config.low_health_threshold) as i64;
+            total += row.score;
+        }
+        let average_score = if scored > 0 { total / scored as f64 } else { 0.0 };
+        tracing::info!("financial health: {scored} scored, avg {average_score:.2}, {low_health} low-health");
+        Ok(SimEvent::FinancialHealthScored { tick, customers_scored: scored, average_score, low_health })
+    }
+
+    /// The customer's latest score, scoring them now if they have none yet.
+    pub fn current(&self, store: &SimStore, customer_id: &str, tick: Tick) -> SimResult<f64> {
+        if let Some(score) = store.latest_customer_health(&self.run_id, customer_id)? {
+            return Ok(score);
+        }
+        let row = self.score(store, customer_id, tick)?;
+        store.insert_customer_health(&self.run_id, &row)?;
+        Ok(row.score)
+    }
+
+    /// Lowest score a credit offer goes to: the player's, or the default.
+    pub fn min_credit_score(&self, store: &SimStore) -> SimResult<f64> {
+        Ok(store.credit_health_policy(&self.run_id)?.unwrap_or(self.config.default_min_credit_score))
+    }
+
+    /// SetCreditHealthFloor: the floor must be a score.
+    pub fn set_min_credit_score(&self, store: &SimStore, min_score: f64, tick: Tick) -> SimResult<SimEvent> {
+        if !(0.0..=1.0).contains(&min_score) {
+            tracing::warn!("rejected credit health floor {min_score}: outside 0..1");
+            return Ok(SimEvent::CreditHealthFloorRejected {
+                tick,
+                reason: "minimum health score must be between 0 and 1".into(),
+            });
+        }
+        store.insert_credit_health_policy(&self.run_id, tick, min_score)?;
+        tracing::info!("credit health floor set to {min_score:.2}");
+        Ok(SimEvent::CreditHealthFloorSet { tick, min_score })
+    }
+
+    pub fn is_low_health(&self, score: f64) -> bool {
+        score < self.config.low_health_threshold
+    }
+
+    /// Monthly default probability for a line held at this score.
+    pub fn default_probability(&self, score: f64) -> f64 {
+        self.config.max_monthly_default_rate * (1.0 - score.clamp(0.0, 1.0)).powi(2)
+    }
+
+    /// Record the credit line a customer took up; one sold to a
+    /// low-health customer adds UDAAP exposure.
+    pub fn open_credit_line(
+        &self,
+        store:       &SimStore,
+        customer_id: &str,
+        offer:       &OfferConfig,
+        score:       f64,
+        tick:        Tick,
+    ) -> SimResult<()> {
+        store.insert_credit_line(&self.run_id, &CreditLineRow {
+            customer_id:    customer_id.into(),
+            offer_id:       offer.offer_id.clone(),
+            tick_opened:    tick,
+            credit_limit:   offer.credit_limit,
+            health_at_open: score,
+            status:         "open".into(),
+            tick_defaulted: None,
+            loss_amount:    0.0,
+        })?;
+        if self.is_low_health(score) {
+            store.adjust_udaap_score(&self.run_id, self.config.low_health_udaap_impact, tick)?;
+        }
+        Ok(())
+    }
+
+    /// Monthly pass over open lines: each defaults with the probability
+    /// for its holder's latest score, losing `loss_given_default` of the
+    /// limit.
+    pub fn process_defaults(&self, store: &SimStore, tick: Tick, rng: &mut SubsystemRng) -> SimResult<Vec<SimEvent>> {
+        let mut events = Vec::new();
+        for line in store.open_credit_lines(&self.run_id)? {
+            let score = store
+                .latest_customer_health(&self.run_id, &line.customer_id)?
+                .unwrap_or(line.health_at_open);
+            if !rng.chance(self.default_probability(score)) {
+                continue;
+            }
+            let loss = line.credit_limit * self.config.loss_given_default;
+            store.mark_credit_line_defaulted(&self.run_id, &line.customer_id, &line.offer_id, tick, loss)?;
+            tracing::info!("{} defaulted on {} (health {score:.2}), loss ${loss:.0}", line.customer_id, line.offer_id);
+            events.push(SimEvent::CreditLineDefaulted {
+                tick,
+                customer_id: line.customer_id,
+                offer_id: line.offer_id,
+                health_score: score,
+                loss_amount: loss,
+            });
+        }
+        Ok(events)
+    }
+}
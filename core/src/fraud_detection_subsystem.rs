@@ -7,16 +7,32 @@
 //!   4. Flags elder abuse patterns (unusual activity for seniors)
 //!   5. Calculates account-level fraud risk scores
 //!   6. Generates fraud alerts for investigation
+//!   7. Flags business email compromise wires and recalls them (Phase 4.53)
+//!   8. Runs the check serial rules and settles check returns (Phase 4.54)
+//!   9. Decides APP scam reimbursement claims, and refuses recalls of
+//!      instant payments (Phase 4.91)
+//!  10. Alerts cards used at many ATMs in one tick (Phase 4.92)
+//!  11. Alerts high-value payments from new devices and devices shared
+//!      by many customers, and scores both monthly (Phase 4.94)
 
 use crate::{
+    app_fraud::AppFraud,
+    atm::AtmNetwork,
+    bec::BusinessEmailCompromise,
+    check_fraud::CheckFraud,
+    command::PlayerCommand,
+    config::{
+        AppFraudConfig, AtmNetworkConfig, BecConfig, CheckFraudConfig, DeviceTelemetryConfig, PaymentRailConfig,
+    },
+    device::Devices,
     error::SimResult,
     event::SimEvent,
     rng::SubsystemRng,
     store::SimStore,
-    subsystem::SimSubsystem,
+    subsystem::{SimSubsystem, SubsystemManifest},
     types::{RunId, Tick},
 };
-use std::any::Any;
+use std::{any::Any, collections::HashMap};
 
 // ── Constants ────────────────────────────────────────────────────────────────
 
@@ -31,12 +47,69 @@ const METRICS_INTERVAL: i64 = 7; // weekly
 
 pub struct FraudDetectionSubsystem {
     run_id: RunId,
+    /// Phase 4.53: BEC_NEW_PAYEE alerts and wire recalls.
+    bec: BusinessEmailCompromise,
+    /// Phase 4.54: check serial rules, desk review and return deadlines.
+    check_fraud: CheckFraud,
+    /// Phase 4.91: APP scam claims and the reimbursement policy.
+    app_fraud: AppFraud,
+    /// Phase 4.91: rails whose payments no recall can bring back.
+    irrevocable_rails: Vec<String>,
+    /// Phase 4.92: ATM_MULTI_DEVICE alerts.
+    atm_network: AtmNetwork,
+    /// Phase 4.94: device alerts and the device score component.
+    devices: Devices,
     store: SimStore,
 }
 
 impl FraudDetectionSubsystem {
-    pub fn new(run_id: RunId, store: SimStore) -> Self {
-        Self { run_id, store }
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        run_id: RunId,
+        bec: BecConfig,
+        check_fraud: CheckFraudConfig,
+        app_fraud: AppFraudConfig,
+        atm_network: AtmNetworkConfig,
+        devices: DeviceTelemetryConfig,
+        rails: &[PaymentRailConfig],
+        store: SimStore,
+    ) -> Self {
+        Self {
+            bec: BusinessEmailCompromise::new(run_id.clone(), bec),
+            check_fraud: CheckFraud::new(run_id.clone(), check_fraud),
+            app_fraud: AppFraud::new(run_id.clone(), app_fraud),
+            atm_network: AtmNetwork::new(run_id.clone(), atm_network),
+            devices: Devices::new(run_id.clone(), devices),
+            irrevocable_rails: rails.iter().filter(|r| r.irrevocable).map(|r| r.rail_id.clone()).collect(),
+            run_id,
+            store,
+        }
+    }
+
+    /// Phase 4.91: refuse recalls of payments sent on an irrevocable
+    /// rail, keeping the rest for the BEC desk.
+    fn refuse_irrevocable_recalls(
+        &self,
+        tick: Tick,
+        commands: Vec<PlayerCommand>,
+    ) -> SimResult<(Vec<PlayerCommand>, Vec<SimEvent>)> {
+        let (mut kept, mut events) = (Vec::new(), Vec::new());
+        for command in commands {
+            if let PlayerCommand::RecallWire { txn_id } = &command {
+                let rail = self.store.debit_rail(&self.run_id, txn_id)?;
+                if rail.is_some_and(|r| self.irrevocable_rails.contains(&r)) {
+                    tracing::warn!("recall of {txn_id} rejected: instant payments are irrevocable");
+                    events.push(SimEvent::WireRecallRejected {
+                        tick,
+                        txn_id: txn_id.clone(),
+                        reason: "instant payments are irrevocable".into(),
+                    });
+                    continue;
+                }
+            }
+            kept.push(command);
+        }
+        Ok((kept, events))
     }
 
     /// Detect synthetic identity fraud using customer identity data.
@@ -106,6 +179,28 @@ impl FraudDetectionSubsystem {
                 }
             }
 
+            // Indicator 6: SSN validation findings at onboarding (Phase 4.14)
+            if let Some(validation) = self.store.ssn_validation(&self.run_id, &customer.customer_id)? {
+                if validation.synthetic_score > 0.0 {
+                    score += validation.synthetic_score;
+                    indicators.push(format!("SSN validation: {}", validation.issues.join(", ")));
+                }
+            }
+
+            // Indicator 7: IDV partial matches at onboarding (0.15, Phase 4.15)
+            if let Some(check) = self.store.idv_check(&self.run_id, &customer.customer_id)? {
+                let partial = ["name", "SSN", "DOB", "address"]
+                    .into_iter()
+                    .zip(check.match_codes())
+                    .filter(|(_, code)| *code == "partial")
+                    .map(|(element, _)| element)
+                    .collect::<Vec<_>>();
+                if !partial.is_empty() {
+                    score += 0.15;
+                    indicators.push(format!("IDV partial match: {}", partial.join(", ")));
+                }
+            }
+
             // Add deterministic noise
             score += rng.next_f64() * 0.05;
             score = score.min(1.0);
@@ -216,6 +311,13 @@ impl FraudDetectionSubsystem {
 
         let accounts = self.store.get_active_accounts(&self.run_id)?;
 
+        // Phase 4.94: device signals by customer
+        let device_components = if self.devices.enabled() {
+            self.devices.score_components(&self.store, tick)?
+        } else {
+            HashMap::new()
+        };
+
         for account in accounts {
             let mut score = 0.0;
 
@@ -254,7 +356,10 @@ impl FraudDetectionSubsystem {
             let pattern_component = if unique_counterparties > 15 { 0.20 } else { 0.0 };
             score += pattern_component;
 
-            // Component 4: Behavioral (cash intensity)
+            // Component 4: Behavioral (device telemetry, Phase 4.94)
+            let behavioral_component = device_components.get(&account.customer_id).copied().unwrap_or(0.0);
+            score += behavioral_component;
+
             // Component 5: Identity (from customer risk score)
 
             // Add noise
@@ -270,7 +375,7 @@ impl FraudDetectionSubsystem {
                 velocity_component,
                 amount_component,
                 pattern_component,
-                0.0, // behavioral_component
+                behavioral_component,
                 0.0, // identity_component
             )?;
 
@@ -304,15 +409,34 @@ impl FraudDetectionSubsystem {
     }
 }
 
+const MANIFEST: SubsystemManifest = SubsystemManifest {
+    version: 6,
+    produces: &[
+        "app_claim_decided", "app_reimbursement_policy_changed", "app_scam_reported",
+        "check_fraud_resolved", "fraud_alert_generated", "fraud_pattern_detected", "wire_recall_rejected",
+        "wire_recall_resolved",
+    ],
+    consumes: &["player_command_received"],
+    tables: &[
+        "account", "account_fraud_score", "app_scam_payment", "check_item", "customer_device", "fraud_alert", "fraud_pattern", "invoice_wire",
+        "transactions", "wire_recall",
+    ],
+    requires: &[],
+};
+
 impl SimSubsystem for FraudDetectionSubsystem {
     fn name(&self) -> &'static str {
         "fraud_detection"
     }
 
+    fn manifest(&self) -> SubsystemManifest {
+        MANIFEST
+    }
+
     fn update(
         &mut self,
         tick: Tick,
-        _events_in: &[SimEvent],
+        events_in: &[SimEvent],
         rng: &mut SubsystemRng,
     ) -> SimResult<Vec<SimEvent>> {
         let mut events = Vec::new();
@@ -332,9 +456,76 @@ impl SimSubsystem for FraudDetectionSubsystem {
         // 3. Calculate account fraud scores (monthly)
         events.extend(self.calculate_account_fraud_scores(tick, rng)?);
 
+        let mut commands = Vec::new();
+        if self.bec.enabled() || self.app_fraud.enabled() {
+            for event in events_in {
+                let SimEvent::PlayerCommandReceived { command_id, .. } = event else {
+                    continue;
+                };
+                if let Some(
+                    command @ (PlayerCommand::RecallWire { .. } | PlayerCommand::SetAppReimbursementPolicy { .. }),
+                ) = self.store.get_player_command(&self.run_id, command_id)?
+                {
+                    commands.push(command);
+                }
+            }
+            let (kept, refused) = self.refuse_irrevocable_recalls(tick, commands)?;
+            events.extend(refused);
+            commands = kept;
+        }
+
+        // 4. BEC new-payee wires and recalls (daily)
+        if self.bec.enabled() {
+            events.extend(self.bec.process(&self.store, tick, &commands, rng)?);
+        }
+
+        // 5. Check serial rules, desk review and returns (daily)
+        if self.check_fraud.enabled() {
+            events.extend(self.check_fraud.process(&self.store, tick, rng)?);
+        }
+
+        // 6. APP scam claims and the reimbursement policy (daily)
+        if self.app_fraud.enabled() {
+            events.extend(self.app_fraud.process(&self.store, tick, &commands)?);
+        }
+
+        // 7. Cards used at many ATMs today (daily)
+        if self.atm_network.enabled() {
+            events.extend(self.atm_network.detect(&self.store, tick)?);
+        }
+
+        // 8. Payments from new devices and shared devices (daily)
+        if self.devices.enabled() {
+            events.extend(self.devices.detect(&self.store, tick)?);
+        }
+
         Ok(events)
     }
 
+    // The player's APP reimbursement policy is the only state not in the store
+    fn save_state(&self) -> SimResult<Option<serde_json::Value>> {
+        if !self.app_fraud.enabled() {
+            return Ok(None);
+        }
+        Ok(Some(serde_json::json!({ "app_reimbursement_policy": self.app_fraud.policy() })))
+    }
+
+    fn load_state(&mut self, state: serde_json::Value) -> SimResult<()> {
+        if let Some(policy) = state["app_reimbursement_policy"].as_str() {
+            self.app_fraud.set_policy(policy);
+        }
+        Ok(())
+    }
+
+    fn replay(&mut self, _tick: Tick, events: &[SimEvent]) -> SimResult<()> {
+        for event in events {
+            if let SimEvent::AppReimbursementPolicyChanged { policy, .. } = event {
+                self.app_fraud.set_policy(policy);
+            }
+        }
+        Ok(())
+    }
+
     fn as_any(&self) -> &dyn Any {
         self
     }
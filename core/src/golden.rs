@@ -0,0 +1,125 @@
+//! Golden-run regression fixtures (Phase 4.49).
+//!
+//! A golden run is a short run from a fixed seed whose key tables are
+//! reduced to a row count and checksum each and committed as a fixture.
+//! Re-running it and comparing shows whether a refactor changed what the
+//! simulation does, and in which tables. A change that is meant to alter
+//! behaviour re-records the fixture in the same commit.
+//!
+//! Columns filled from `Uuid::new_v4` rather than the run's RNG differ
+//! on every run and are left out of the checksums.
+
+use crate::{engine::SimEngine, error::SimResult, provenance::hash_scenario, store::SimStore, types::Tick};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+pub const GOLDEN_SEED: u64 = 42;
+pub const GOLDEN_TICKS: Tick = 90;
+/// Keeps the run small enough to check on every CI build.
+pub const GOLDEN_POPULATION_SCALE: f64 = 0.1;
+/// Fixed so nothing derived from the run id varies between recordings.
+const GOLDEN_RUN_ID: &str = "golden";
+
+/// Tables checked, each with the columns left out.
+pub const GOLDEN_TABLES: &[(&str, &[&str])] = &[
+    ("customer", &[]),
+    ("account", &[]),
+    ("transactions", &["txn_id"]),
+    ("complaint", &[]),
+    ("pnl_snapshot", &[]),
+    ("aml_alert", &[]),
+    ("card_dispute", &[]),
+    ("recon_exception", &[]),
+    // Payloads carry transaction and wire ids
+    ("event_log", &["id", "created_at", "payload"]),
+];
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TableChecksum {
+    pub rows: u64,
+    pub sha256: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GoldenRun {
+    pub seed: u64,
+    pub ticks: Tick,
+    pub population_scale: f64,
+    /// Scenario data the run loaded; see `provenance::hash_scenario`.
+    pub scenario_hash: String,
+    pub tables: BTreeMap<String, TableChecksum>,
+}
+
+/// One way a re-run differs from the fixture.
+#[derive(Debug, Clone, PartialEq)]
+pub enum GoldenDiff {
+    /// The scenario data changed since recording, which explains any
+    /// table differences that follow.
+    Scenario { recorded: String, current: String },
+    Table { table: String, recorded: Option<TableChecksum>, current: Option<TableChecksum> },
+}
+
+impl std::fmt::Display for GoldenDiff {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let rows = |c: &Option<TableChecksum>| c.as_ref().map_or("missing".to_string(), |c| format!("{} rows", c.rows));
+        match self {
+            Self::Scenario { recorded, current } => {
+                write!(f, "scenario data changed: recorded {recorded}, now {current}")
+            }
+            Self::Table { table, recorded, current } => {
+                write!(f, "{table}: recorded {}, now {}", rows(recorded), rows(current))?;
+                if recorded.as_ref().zip(current.as_ref()).is_some_and(|(r, c)| r.rows == c.rows) {
+                    write!(f, " with different contents")?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+impl GoldenRun {
+    /// Run the scenario in `data_dir` from `seed` for `ticks` in a
+    /// scratch in-memory database and checksum the golden tables.
+    pub fn record(data_dir: &str, seed: u64, ticks: Tick, population_scale: f64) -> SimResult<Self> {
+        let store = SimStore::open(&format!("file:golden_{}?mode=memory&cache=shared", uuid::Uuid::new_v4()))?;
+        store.migrate()?;
+        store.insert_run(GOLDEN_RUN_ID, seed, env!("CARGO_PKG_VERSION"))?;
+        let mut engine =
+            SimEngine::build_with_population_scale(GOLDEN_RUN_ID.into(), seed, &store, data_dir, population_scale)?;
+        engine.run_ticks(ticks)?;
+
+        let tables = GOLDEN_TABLES
+            .iter()
+            .map(|(table, excluded)| Ok((table.to_string(), store.table_checksum(GOLDEN_RUN_ID, table, excluded)?)))
+            .collect::<SimResult<_>>()?;
+        Ok(Self { seed, ticks, population_scale, scenario_hash: hash_scenario(data_dir)?, tables })
+    }
+
+    /// Re-run this fixture's seed, ticks and scale against `data_dir`.
+    pub fn rerun(&self, data_dir: &str) -> SimResult<Self> {
+        Self::record(data_dir, self.seed, self.ticks, self.population_scale)
+    }
+
+    /// Where `current` differs from this recording; empty when it matches.
+    pub fn compare(&self, current: &GoldenRun) -> Vec<GoldenDiff> {
+        let mut diffs = Vec::new();
+        if self.scenario_hash != current.scenario_hash {
+            diffs.push(GoldenDiff::Scenario {
+                recorded: self.scenario_hash.clone(),
+                current: current.scenario_hash.clone(),
+            });
+        }
+        let names: std::collections::BTreeSet<_> = self.tables.keys().chain(current.tables.keys()).collect();
+        for table in names {
+            let (recorded, now) = (self.tables.get(table), current.tables.get(table));
+            if recorded != now {
+                diffs.push(GoldenDiff::Table {
+                    table: table.clone(),
+                    recorded: recorded.cloned(),
+                    current: now.cloned(),
+                });
+            }
+        }
+        diffs
+    }
+}
@@ -54,7 +54,7 @@ use crate::{
     event::SimEvent,
     rng::SubsystemRng,
     store::SimStore,
-    subsystem::SimSubsystem,
+    subsystem::{SimSubsystem, SubsystemManifest},
     types::{RunId, Tick},
 };
 
@@ -110,6 +110,8 @@ fn incident_description(component: &str, severity: &str) -> String {
         ("data_warehouse", _)      => "Data warehouse query failures".into(),
         ("aml_screening", _)       => "AML screening queue backlog".into(),
         ("customer_service", _)    => "Customer service platform degraded".into(),
+        ("atm_fleet", "P0" | "P1") => "ATM fleet offline — every ATM out of service".into(),
+        ("atm_fleet", _)           => "ATM fleet monitoring alarms".into(),
         _                          => format!("{component} incident (severity {severity})"),
     }
 }
@@ -218,8 +220,8 @@ impl IncidentSubsystem {
                 reason: format!("Incident {incident_id}"),
             });
 
-            log::info!(
-                "tick={tick} incident: {} on {} — {}",
+            tracing::info!(
+                "{} on {} — {}",
                 severity, comp.component_id, description
             );
         }
@@ -278,8 +280,8 @@ impl IncidentSubsystem {
                     reason: format!("Incident {} resolved", inc.incident_id),
                 });
 
-                log::info!(
-                    "tick={tick} incident: {} resolved after {elapsed} ticks",
+                tracing::info!(
+                    "{} resolved after {elapsed} ticks",
                     inc.incident_id
                 );
             } else {
@@ -298,8 +300,8 @@ impl IncidentSubsystem {
                         ticks_overdue,
                     });
 
-                    log::warn!(
-                        "tick={tick} incident SLA BREACH: {} ({}) overdue by {ticks_overdue} ticks",
+                    tracing::warn!(
+                        "incident SLA BREACH: {} ({}) overdue by {ticks_overdue} ticks",
                         inc.incident_id, inc.severity
                     );
                 }
@@ -389,11 +391,26 @@ impl IncidentSubsystem {
     }
 }
 
+const MANIFEST: SubsystemManifest = SubsystemManifest {
+    version: 1,
+    produces: &[
+        "cascading_impact_applied", "component_status_changed", "incident_created",
+        "incident_resolved", "incident_sla_breach", "system_metrics_computed",
+    ],
+    consumes: &[],
+    tables: &["incident", "incident_impact", "system_component", "system_metrics"],
+    requires: &[],
+};
+
 impl SimSubsystem for IncidentSubsystem {
     fn name(&self) -> &'static str {
         "incident"
     }
 
+    fn manifest(&self) -> SubsystemManifest {
+        MANIFEST
+    }
+
     fn update(
         &mut self,
         tick: Tick,
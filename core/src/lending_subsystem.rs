@@ -0,0 +1,359 @@
+//! Lending subsystem — Phase 4.82.
+//!
+//! Puts real loans behind the P&L's credit loss line. Each month an
+//! eligible customer — active, with a credit score, an income and an open
+//! account, and no loan open — applies with `monthly_application_rate` for
+//! one of the configured products. Underwriting declines a credit score
+//! under the product's minimum, or an annualised first payment over
+//! `max_dti` of income. An approved loan lends `income_multiple` of income,
+//! up to `max_amount`, into the borrower's account:
+//!   - installment: the full amount, repaid in `term_payments` fixed
+//!     payments; the amortization schedule is written at origination.
+//!   - line: `draw_share` of the limit, with a payment each period of the
+//!     interest plus `min_payment_share` of the balance.
+//!
+//! On each due date the borrower pays everything owed from their account
+//! if it covers it, unless they skip it, more likely the lower their
+//! score. A miss leaves the payment past due; the loan moves through the
+//! 1–29, 30–59, 60–89 and 90+ days-past-due buckets until the borrower
+//! catches up at a later due date or it reaches `charge_off_dpd`. A
+//! charge-off loses the balance less `recovery_rate`. Economics books the
+//! losses as credit loss and the interest received as NII.
+//!
+//! Execution: every tick, after CreditBureau and before Complaint, so the
+//! day's transactions have landed in the accounts it draws on.
+
+use crate::{
+    config::{LendingConfig, LoanKind, LoanProductConfig},
+    error::SimResult,
+    event::SimEvent,
+    rng::SubsystemRng,
+    store::{
+        lending::{DelinquencyStatusRow, LoanApplicantRow, LoanPaymentRow, LoanRow},
+        SimStore,
+    },
+    subsystem::{SimSubsystem, SubsystemManifest},
+    types::{RunId, Tick},
+};
+
+pub struct LendingSubsystem {
+    run_id: RunId,
+    config: LendingConfig,
+    store:  SimStore,
+}
+
+/// Delinquency bucket for a loan this many days past due.
+pub fn delinquency_bucket(days_past_due: i64) -> &'static str {
+    match days_past_due {
+        i64::MIN..=0 => "current",
+        1..=29 => "1_29",
+        30..=59 => "30_59",
+        60..=89 => "60_89",
+        _ => "90_plus",
+    }
+}
+
+/// Fixed payment amortizing `amount` over `n` payments at `rate` each.
+fn level_payment(amount: f64, rate: f64, n: u32) -> f64 {
+    if rate <= 0.0 {
+        return amount / n as f64;
+    }
+    amount * rate / (1.0 - (1.0 + rate).powi(-(n as i32)))
+}
+
+impl LendingSubsystem {
+    pub fn new(run_id: RunId, config: LendingConfig, store: SimStore) -> Self {
+        Self { run_id, config, store }
+    }
+
+    /// Interest rate per payment period.
+    fn period_rate(&self, product_annual_rate: f64) -> f64 {
+        product_annual_rate * self.config.payment_interval_ticks as f64 / 365.0
+    }
+
+    /// A line's next payment: the period's interest plus its share of
+    /// the balance, never less than the minimum while the balance covers it.
+    fn line_payment(&self, product: &LoanProductConfig, balance: f64) -> (f64, f64) {
+        let interest = balance * self.period_rate(product.annual_rate);
+        let principal = (balance * product.min_payment_share).max(product.min_payment).min(balance);
+        (principal, interest)
+    }
+
+    /// Underwrite an application: the loan to write, or why not.
+    fn underwrite(
+        &self,
+        applicant: &LoanApplicantRow,
+        product: &LoanProductConfig,
+        tick: Tick,
+    ) -> Result<(LoanRow, Vec<LoanPaymentRow>), String> {
+        if applicant.credit_score < product.min_credit_score {
+            return Err(format!(
+                "Credit score {} is under the {} minimum of {}",
+                applicant.credit_score, product.product_id, product.min_credit_score
+            ));
+        }
+        let amount = (applicant.annual_income * product.income_multiple).min(product.max_amount);
+        let amount = (amount / 100.0).floor() * 100.0;
+        if amount <= 0.0 {
+            return Err("Income supports no lending".into());
+        }
+
+        let loan_id = format!("loan-{}-{}", applicant.customer_id, tick);
+        let interval = self.config.payment_interval_ticks;
+        let (balance, schedule) = match product.kind {
+            LoanKind::Installment => {
+                let rate = self.period_rate(product.annual_rate);
+                let n = product.term_payments.max(1);
+                let payment = level_payment(amount, rate, n);
+                let mut remaining = amount;
+                let schedule = (1..=n)
+                    .map(|k| {
+                        let interest = remaining * rate;
+                        let principal = if k == n { remaining } else { payment - interest };
+                        remaining -= principal;
+                        LoanPaymentRow {
+                            loan_id: loan_id.clone(),
+                            payment_no: i64::from(k),
+                            due_tick: tick + interval * u64::from(k),
+                            amount_due: principal + interest,
+                            principal,
+                            interest,
+                            status: "scheduled".into(),
+                            tick_paid: None,
+                        }
+                    })
+                    .collect();
+                (amount, schedule)
+            }
+            LoanKind::Line => (amount * product.draw_share, Vec::new()),
+        };
+
+        let first_payment = match product.kind {
+            LoanKind::Installment => schedule.first().map_or(0.0, |p: &LoanPaymentRow| p.amount_due),
+            LoanKind::Line => {
+                let (principal, interest) = self.line_payment(product, balance);
+                principal + interest
+            }
+        };
+        let dti = first_payment * 365.0 / interval as f64 / applicant.annual_income;
+        if dti > self.config.max_dti {
+            return Err(format!("Debt-to-income {dti:.2} is over the {:.2} limit", self.config.max_dti));
+        }
+
+        let loan = LoanRow {
+            loan_id,
+            customer_id: applicant.customer_id.clone(),
+            account_id: applicant.account_id.clone(),
+            product_id: product.product_id.clone(),
+            kind: product.kind.as_str().into(),
+            tick_originated: tick,
+            amount,
+            annual_rate: product.annual_rate,
+            credit_score: applicant.credit_score,
+            dti,
+            balance,
+            interest_paid: 0.0,
+            status: "open".into(),
+            bucket: "current".into(),
+            tick_closed: None,
+            charge_off_loss: 0.0,
+        };
+        Ok((loan, schedule))
+    }
+
+    /// Take the month's applications and write the approved loans.
+    fn originate(&self, tick: Tick, rng: &mut SubsystemRng) -> SimResult<Vec<SimEvent>> {
+        let mut events = Vec::new();
+        if self.config.products.is_empty() {
+            return Ok(events);
+        }
+        let daily_rate = self.config.monthly_application_rate / 30.0;
+        for applicant in self.store.loan_applicants(&self.run_id)? {
+            if !rng.chance(daily_rate) {
+                continue;
+            }
+            let product = &self.config.products[rng.next_u64_below(self.config.products.len() as u64) as usize];
+            match self.underwrite(&applicant, product, tick) {
+                Err(reason) => {
+                    tracing::debug!("{} declined for {}: {reason}", applicant.customer_id, product.product_id);
+                    events.push(SimEvent::LoanApplicationDeclined {
+                        tick,
+                        customer_id: applicant.customer_id,
+                        product_id: product.product_id.clone(),
+                        reason,
+                    });
+                }
+                Ok((loan, schedule)) => {
+                    self.store.insert_loan(&self.run_id, &loan)?;
+                    for payment in &schedule {
+                        self.store.insert_loan_payment(&self.run_id, payment)?;
+                    }
+                    self.store.update_account_balance(&self.run_id, &loan.account_id, loan.balance)?;
+                    tracing::info!(
+                        "{} originated for {}: {} ${:.0} at {:.1}%",
+                        loan.loan_id,
+                        loan.customer_id,
+                        loan.product_id,
+                        loan.amount,
+                        loan.annual_rate * 100.0
+                    );
+                    events.push(SimEvent::LoanOriginated {
+                        tick,
+                        loan_id: loan.loan_id,
+                        customer_id: loan.customer_id,
+                        product_id: loan.product_id,
+                        kind: loan.kind,
+                        amount: loan.amount,
+                        annual_rate: loan.annual_rate,
+                    });
+                }
+            }
+        }
+        Ok(events)
+    }
+
+    /// Chance a borrower with this score skips a payment they could make.
+    fn miss_probability(&self, credit_score: i64) -> f64 {
+        let risk = ((850 - credit_score) as f64 / 550.0).clamp(0.0, 1.0);
+        self.config.max_miss_rate * risk * risk
+    }
+
+    /// Bill, collect, age and close one open loan.
+    fn service(&self, loan: &LoanRow, tick: Tick, rng: &mut SubsystemRng) -> SimResult<Vec<SimEvent>> {
+        let mut events = Vec::new();
+        let interval = self.config.payment_interval_ticks;
+        let since_open = tick.saturating_sub(loan.tick_originated);
+
+        // A line bills its next payment when it falls due.
+        if loan.kind == LoanKind::Line.as_str() && since_open > 0 && since_open.is_multiple_of(interval) && loan.balance > 0.0 {
+            if let Some(product) = self.config.products.iter().find(|p| p.product_id == loan.product_id) {
+                let (principal, interest) = self.line_payment(product, loan.balance);
+                self.store.insert_loan_payment(&self.run_id, &LoanPaymentRow {
+                    loan_id: loan.loan_id.clone(),
+                    payment_no: self.store.loan_payment_count(&self.run_id, &loan.loan_id)? + 1,
+                    due_tick: tick,
+                    amount_due: principal + interest,
+                    principal,
+                    interest,
+                    status: "scheduled".into(),
+                    tick_paid: None,
+                })?;
+            }
+        }
+
+        // On a due date the borrower pays everything owed, or misses.
+        let owed = self.store.loan_payments_owed(&self.run_id, &loan.loan_id, tick)?;
+        let mut balance = loan.balance;
+        let mut past_due = owed.iter().filter(|p| p.status == "missed").cloned().collect::<Vec<_>>();
+        if owed.iter().any(|p| p.due_tick == tick) {
+            let total: f64 = owed.iter().map(|p| p.amount_due).sum();
+            let affordable = self.store.account_balance(&self.run_id, &loan.account_id)? >= total;
+            if affordable && !rng.chance(self.miss_probability(loan.credit_score)) {
+                self.store.update_account_balance(&self.run_id, &loan.account_id, -total)?;
+                for p in &owed {
+                    self.store.set_loan_payment_status(&self.run_id, &loan.loan_id, p.payment_no, "paid", Some(tick))?;
+                }
+                let principal: f64 = owed.iter().map(|p| p.principal).sum();
+                let interest: f64 = owed.iter().map(|p| p.interest).sum();
+                self.store.apply_loan_payment(&self.run_id, &loan.loan_id, principal, interest)?;
+                balance = (balance - principal).max(0.0);
+                past_due.clear();
+            } else {
+                for p in owed.iter().filter(|p| p.status == "scheduled") {
+                    self.store.set_loan_payment_status(&self.run_id, &loan.loan_id, p.payment_no, "missed", None)?;
+                    past_due.push(LoanPaymentRow { status: "missed".into(), ..p.clone() });
+                }
+            }
+        }
+
+        // Age what is past due into its bucket.
+        let days_past_due = past_due.iter().map(|p| tick.saturating_sub(p.due_tick) as i64).max().unwrap_or(0);
+        let bucket = delinquency_bucket(days_past_due);
+        if bucket != loan.bucket {
+            self.store.set_loan_bucket(&self.run_id, &DelinquencyStatusRow {
+                loan_id: loan.loan_id.clone(),
+                tick,
+                days_past_due,
+                bucket: bucket.into(),
+                past_due_amount: past_due.iter().map(|p| p.amount_due).sum(),
+            })?;
+            events.push(SimEvent::LoanDelinquencyChanged {
+                tick,
+                loan_id: loan.loan_id.clone(),
+                customer_id: loan.customer_id.clone(),
+                from_bucket: loan.bucket.clone(),
+                to_bucket: bucket.into(),
+                days_past_due,
+            });
+        }
+
+        if days_past_due >= self.config.charge_off_dpd {
+            let loss = balance * (1.0 - self.config.recovery_rate);
+            self.store.close_loan(&self.run_id, &loan.loan_id, "charged_off", tick, loss)?;
+            tracing::info!("{} charged off at {days_past_due} days past due, loss ${loss:.0}", loan.loan_id);
+            events.push(SimEvent::LoanChargedOff {
+                tick,
+                loan_id: loan.loan_id.clone(),
+                customer_id: loan.customer_id.clone(),
+                balance,
+                loss,
+            });
+        } else if balance < 0.005 && past_due.is_empty() {
+            self.store.close_loan(&self.run_id, &loan.loan_id, "paid_off", tick, 0.0)?;
+            events.push(SimEvent::LoanPaidOff {
+                tick,
+                loan_id: loan.loan_id.clone(),
+                customer_id: loan.customer_id.clone(),
+            });
+        }
+        Ok(events)
+    }
+}
+
+const MANIFEST: SubsystemManifest = SubsystemManifest {
+    version: 1,
+    produces: &[
+        "loan_application_declined", "loan_charged_off", "loan_delinquency_changed", "loan_originated",
+        "loan_paid_off",
+    ],
+    consumes: &[],
+    tables: &["account", "delinquency_status", "loan", "loan_payment"],
+    requires: &[],
+};
+
+impl SimSubsystem for LendingSubsystem {
+    fn name(&self) -> &'static str {
+        "lending"
+    }
+
+    fn manifest(&self) -> SubsystemManifest {
+        MANIFEST
+    }
+
+    fn update(
+        &mut self,
+        tick:       Tick,
+        _events_in: &[SimEvent],
+        rng:        &mut SubsystemRng,
+    ) -> SimResult<Vec<SimEvent>> {
+        let mut out = Vec::new();
+
+        if !self.config.enabled || tick == 0 {
+            return Ok(out);
+        }
+
+        // 1. Service the book before today's originations join it.
+        for loan in self.store.open_loans(&self.run_id)? {
+            out.extend(self.service(&loan, tick, rng)?);
+        }
+
+        // 2. New applications.
+        out.extend(self.originate(tick, rng)?);
+
+        Ok(out)
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+}
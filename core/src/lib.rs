@@ -1,32 +1,106 @@
+pub mod alert_cases;                // Phase 4.60
+pub mod alert_qa;                   // Phase 4.73
+pub mod alert_triage;               // Phase 4.85
+pub mod app_fraud;                  // Phase 4.91
+pub mod atm;                        // Phase 4.92
+pub mod calendar;                   // Phase 4.41
 pub mod card_dispute_subsystem;
+pub mod case_documents;             // Phase 4.31
+pub mod case_subsystem;             // Phase 4.86
+pub mod channel;                    // Phase 4.90
+pub mod churn_evaluation;           // Phase 4.64
 pub mod churn_subsystem;
 pub mod clock;
 pub mod command;
+pub mod command_result;             // Phase 4.45
 pub mod complaint_analytics_subsystem;
+pub mod complaint_quality;          // Phase 4.65
+pub mod complaint_sla;              // Phase 4.29
 pub mod complaint_subsystem;
 pub mod config;
+pub mod correspondent;              // Phase 4.39
+pub mod coverage_assessment;        // Phase 4.18
+pub mod credit_bureau_subsystem;    // Phase 4.1
+pub mod cross_border;               // Phase 4.40
+pub mod customer_limits;            // Phase 4.12
 pub mod customer_subsystem;
+pub mod deposit_run;                // Phase 4.52
+pub mod device;                     // Phase 4.94
 pub mod economics_subsystem;
 pub mod engine;
+pub mod entity_timeline;              // Phase 4.58
+pub mod adverse_media;              // Phase 4.38
 pub mod aml_screening_subsystem;
+pub mod authorization;              // Phase 4.46
+pub mod batch;                      // Phase 4.78
+pub mod bec;                        // Phase 4.53
+pub mod check_fraud;                // Phase 4.54
+pub mod deposit_holds;              // Phase 4.55
+pub mod branch_subsystem;           // Phase 4.3
+pub mod breakpoint;                 // Phase 4.74
+pub mod bulk_resolution;            // Phase 4.47
+pub mod fair_lending_subsystem;     // Phase 4.4
+pub mod enforcement;                // Phase 4.81
+pub mod enrichment;                 // Phase 4.35
 pub mod error;
 pub mod event;
+pub mod event_stream;               // Phase 4.57
+pub mod evidence_bundle;            // Phase 4.59
+pub mod experiment;                 // Phase 4.20
+pub mod export;                     // Phase 4.79
+pub mod feature_flags;              // Phase 4.42
+pub mod financial_health;           // Phase 4.34
 pub mod fraud_detection_subsystem;
+pub mod golden;                     // Phase 4.49
+pub mod idv;                        // Phase 4.15
 pub mod incident_subsystem;
+pub mod lending_subsystem;          // Phase 4.82
 pub mod name_generator;
+pub mod name_matching;              // Phase 4.89
 pub mod macro_subsystem;
+pub mod merchant;                   // Phase 4.93
+pub mod mule_network;               // Phase 4.87
 pub mod offer_subsystem;
+pub mod operational_risk;           // Phase 4.61
+pub mod opex_allocation;            // Phase 4.63
+pub mod outreach;                   // Phase 4.32
+pub mod outreach_subsystem;         // Phase 4.32
 pub mod ops_specialist_role;
 pub mod payment_hub_subsystem;
+pub mod post_mortem;                // Phase 4.19
 pub mod pricing_subsystem;
+pub mod protocol;                   // Phase 4.22
+pub mod provenance;                 // Phase 4.24
+pub mod quarter_close;              // Phase 4.62
+pub mod queue;                      // Phase 4.43
+pub mod queue_aging;                // Phase 4.48
+pub mod readonly_query;             // Phase 4.67
 pub mod reconciliation_subsystem;
 pub mod regulatory_exam_subsystem;  // Phase 3.6
+pub mod remittance;                 // Phase 4.36
 pub mod reputation_subsystem;       // Phase 3.6
 pub mod risk_appetite_subsystem;
+pub mod risk_lifecycle;             // Phase 4.13
 pub mod rng;
+pub mod rule_backtest;              // Phase 4.72
+pub mod rule_tuning;                // Phase 4.17
+pub mod run_manager;                // Phase 4.70
+pub mod sar_export;                 // Phase 4.84
+pub mod satisfaction;               // Phase 4.33
+pub mod scenario;                   // Phase 4.77
+pub mod scenario_script;            // Phase 4.68
+pub mod seed_sensitivity;           // Phase 4.56
 pub mod snapshot;
+pub mod ssn_validation;             // Phase 4.14
+pub mod staffing_subsystem;         // Phase 4.80
+pub mod state_delta;                // Phase 4.44
+pub mod state_mirror;               // Phase 4.66
 pub mod store;
+pub mod stress_index;               // Phase 4.69
+pub mod structuring;                // Phase 4.88
 pub mod subsystem;
+pub mod tournament;                 // Phase 4.71
 pub mod transaction_subsystem;
 pub mod transaction_monitoring_subsystem;
 pub mod types;
+pub mod visa;                       // Phase 4.37
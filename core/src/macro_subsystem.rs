@@ -1,14 +1,29 @@
+//! Macro subsystem — the economic cycle and the base rate.
+//!
+//! Each quarter the phase either turns or the base rate moves. With the
+//! rate environment enabled (Phase 4.83) the move follows the phase's
+//! odds of a hike, a cut or a hold; otherwise it drifts a step up or
+//! down with a slight phase bias. Every rate set is written to
+//! `macro_rate_history` with the long end of the curve, which Economics
+//! averages for NII.
+
 use crate::{
+    config::{MacroRegimeConfig, RateEnvironmentConfig},
     error::SimResult,
     event::{EconomicPhase, SimEvent},
     rng::SubsystemRng,
-    subsystem::SimSubsystem,
-    types::Tick,
+    store::{macro_rates::MacroRateRow, SimStore},
+    subsystem::{SimSubsystem, SubsystemManifest},
+    types::{RunId, Tick},
 };
 use serde::{Deserialize, Serialize};
 
 pub const MACRO_UPDATE_INTERVAL: Tick = 90; // quarterly
 
+/// Bounds the base rate drifts between.
+pub const MIN_BASE_RATE: f64 = 0.005;
+pub const MAX_BASE_RATE: f64 = 0.12;
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MacroState {
     pub base_rate: f64,
@@ -20,16 +35,21 @@ pub struct MacroState {
 
 impl Default for MacroState {
     fn default() -> Self {
-        Self {
-            base_rate: 0.05,
-            economic_phase: EconomicPhase::Expansion,
-            fraud_multiplier: 1.0,
-            phase_ticks_left: 360, // 4 quarters to start
-        }
+        Self::starting(&MacroRegimeConfig::default())
     }
 }
 
 impl MacroState {
+    /// Phase 4.77: the economy a scenario starts in.
+    pub fn starting(regime: &MacroRegimeConfig) -> Self {
+        Self {
+            base_rate: regime.base_rate,
+            economic_phase: regime.economic_phase.clone(),
+            fraud_multiplier: regime.economic_phase.fraud_multiplier(),
+            phase_ticks_left: regime.phase_ticks,
+        }
+    }
+
     fn advance_phase(&mut self, rng: &mut SubsystemRng) {
         self.economic_phase = match self.economic_phase {
             EconomicPhase::Expansion => EconomicPhase::Peak,
@@ -43,7 +63,7 @@ impl MacroState {
         self.fraud_multiplier = self.economic_phase.fraud_multiplier();
     }
 
-    fn adjust_rate(&mut self, rng: &mut SubsystemRng) {
+    fn adjust_rate(&mut self, rng: &mut SubsystemRng) -> &'static str {
         // Rate moves ±0.25% per quarter with slight phase bias
         let direction: f64 = match self.economic_phase {
             EconomicPhase::Expansion => 0.5,    // more likely up
@@ -53,39 +73,108 @@ impl MacroState {
         };
         let roll = rng.next_f64() - 0.5 + direction * 0.2;
         let delta = if roll > 0.0 { 0.0025 } else { -0.0025 };
-        self.base_rate = (self.base_rate + delta).clamp(0.005, 0.12);
+        self.move_rate(delta)
+    }
+
+    /// Phase 4.83: one quarter's decision on the rate path.
+    fn follow_rate_path(&mut self, rates: &RateEnvironmentConfig, rng: &mut SubsystemRng) -> &'static str {
+        let (hike_chance, cut_chance) = rates
+            .regime_moves
+            .iter()
+            .find(|m| m.phase == self.economic_phase)
+            .map_or((0.0, 0.0), |m| (m.hike_chance, m.cut_chance));
+        let roll = rng.next_f64();
+        let delta = if roll < hike_chance {
+            rates.rate_step
+        } else if roll >= 1.0 - cut_chance {
+            -rates.rate_step
+        } else {
+            0.0
+        };
+        self.move_rate(delta)
+    }
+
+    fn move_rate(&mut self, delta: f64) -> &'static str {
+        let before = self.base_rate;
+        self.base_rate = (before + delta).clamp(MIN_BASE_RATE, MAX_BASE_RATE);
+        match self.base_rate.partial_cmp(&before) {
+            Some(std::cmp::Ordering::Greater) => "hike",
+            Some(std::cmp::Ordering::Less) => "cut",
+            _ => "hold",
+        }
     }
 }
 
+/// The long end of the curve for a base rate: the term spread, less the
+/// share of the base rate's distance from neutral the long end ignores.
+pub fn long_rate(rates: &RateEnvironmentConfig, base_rate: f64) -> f64 {
+    base_rate + rates.term_spread - rates.curve_flattening * (base_rate - rates.neutral_rate)
+}
+
 pub struct MacroSubsystem {
     pub state: MacroState,
+    run_id: RunId,
+    rates: RateEnvironmentConfig,
+    store: SimStore,
+    /// Whether the run's starting rates are known to be in the history.
+    history_started: bool,
 }
 
 impl MacroSubsystem {
-    pub fn new() -> Self {
+    pub fn new(run_id: RunId, regime: &MacroRegimeConfig, rates: RateEnvironmentConfig, store: SimStore) -> Self {
         Self {
-            state: MacroState::default(),
+            state: MacroState::starting(regime),
+            run_id,
+            rates,
+            store,
+            history_started: false,
         }
     }
-}
 
-impl Default for MacroSubsystem {
-    fn default() -> Self {
-        Self::new()
+    fn record_rate(&self, tick: Tick, rate_move: &str) -> SimResult<()> {
+        self.store.insert_macro_rate(
+            &self.run_id,
+            &MacroRateRow {
+                tick,
+                base_rate: self.state.base_rate,
+                long_rate: long_rate(&self.rates, self.state.base_rate),
+                economic_phase: self.state.economic_phase.as_str().into(),
+                rate_move: rate_move.into(),
+            },
+        )
     }
 }
 
+const MANIFEST: SubsystemManifest = SubsystemManifest {
+    version: 2,
+    produces: &["macro_state_updated"],
+    consumes: &[],
+    tables: &["macro_rate_history"],
+    requires: &[],
+};
+
 impl SimSubsystem for MacroSubsystem {
     fn name(&self) -> &'static str {
         "macro"
     }
 
+    fn manifest(&self) -> SubsystemManifest {
+        MANIFEST
+    }
+
     fn update(
         &mut self,
         tick: Tick,
         _events_in: &[SimEvent],
         rng: &mut SubsystemRng,
     ) -> SimResult<Vec<SimEvent>> {
+        if !self.history_started {
+            if self.store.macro_rate_history(&self.run_id)?.is_empty() {
+                self.record_rate(0, "start")?;
+            }
+            self.history_started = true;
+        }
+
         // Only compute on quarterly boundaries.
         if !tick.is_multiple_of(MACRO_UPDATE_INTERVAL) {
             return Ok(vec![]);
@@ -97,14 +186,18 @@ impl SimSubsystem for MacroSubsystem {
             .phase_ticks_left
             .saturating_sub(MACRO_UPDATE_INTERVAL);
 
-        if self.state.phase_ticks_left == 0 {
+        let rate_move = if self.state.phase_ticks_left == 0 {
             self.state.advance_phase(rng);
+            "hold"
+        } else if self.rates.enabled {
+            self.state.follow_rate_path(&self.rates, rng)
         } else {
-            self.state.adjust_rate(rng);
-        }
+            self.state.adjust_rate(rng)
+        };
+        self.record_rate(tick, rate_move)?;
 
-        log::debug!(
-            "tick={tick} macro: phase={:?} rate={:.4} fraud_mult={:.2}",
+        tracing::debug!(
+            "phase={:?} rate={:.4} fraud_mult={:.2}",
             self.state.economic_phase,
             self.state.base_rate,
             self.state.fraud_multiplier
@@ -118,6 +211,17 @@ impl SimSubsystem for MacroSubsystem {
         }])
     }
 
+    // Updates fall on quarter ends, which are snapshot ticks too, so
+    // there is never one to replay.
+    fn save_state(&self) -> SimResult<Option<serde_json::Value>> {
+        Ok(Some(serde_json::to_value(&self.state)?))
+    }
+
+    fn load_state(&mut self, state: serde_json::Value) -> SimResult<()> {
+        self.state = serde_json::from_value(state)?;
+        Ok(())
+    }
+
     fn as_any(&self) -> &dyn std::any::Any {
         self
     }
@@ -0,0 +1,159 @@
+//! Merchants (Phase 4.93).
+//!
+//! Card purchases are made at one of a fixed set of merchants rather
+//! than a free-text name. Each merchant has a category and a risk tier
+//! drawn by weight, and the tier sets its fraud rate: the chance a
+//! settled purchase there is later disputed. Authorizations and disputes
+//! both carry the merchant, so disputes and chargebacks can be read back
+//! merchant by merchant.
+//!
+//! Now and then a merchant goes bad — breached, or taking orders it never
+//! ships — for `bad_merchant_ticks`. Purchases made there meanwhile are
+//! disputed `bad_merchant_multiplier` times as often, so the spike
+//! surfaces a month or two later as the disputes are filed. A merchant
+//! goes bad at most once in a run.
+//!
+//! Each week the card dispute desk checks every merchant's disputes
+//! filed over the last `chargeback_window_ticks` against its purchases
+//! in the window; a merchant over `chargeback_alert_rate` with at least
+//! `chargeback_alert_min_disputes` disputes raises an early warning.
+
+use crate::{
+    complaint_analytics_subsystem::EarlyWarningAlert,
+    config::MerchantConfig,
+    error::SimResult,
+    event::SimEvent,
+    rng::SubsystemRng,
+    store::{merchant::MerchantRow, SimStore},
+    types::{RunId, Tick},
+};
+use std::collections::BTreeMap;
+
+/// Early warning alert type for a merchant's chargeback rate.
+pub const CHARGEBACK_ALERT: &str = "merchant_chargeback_rate";
+
+pub struct Merchants {
+    run_id: RunId,
+    config: MerchantConfig,
+}
+
+impl Merchants {
+    pub fn new(run_id: RunId, config: MerchantConfig) -> Self {
+        Self { run_id, config }
+    }
+
+    pub fn enabled(&self) -> bool {
+        self.config.enabled
+    }
+
+    /// Start the tick: sign up the merchants on first use and let any
+    /// that have not yet gone bad do so.
+    pub fn open_day(
+        &self,
+        store: &SimStore,
+        tick: Tick,
+        rng: &mut SubsystemRng,
+    ) -> SimResult<(Vec<MerchantRow>, Vec<SimEvent>)> {
+        let c = &self.config;
+        let mut merchants = store.merchants(&self.run_id)?;
+        if merchants.is_empty() {
+            for n in 0..c.count {
+                let risk_tier = draw(&c.risk_tiers, rng).unwrap_or("low").to_string();
+                let row = MerchantRow {
+                    merchant_id: format!("mer-{:03}", n + 1),
+                    name: format!("merchant-{n}"),
+                    category: draw(&c.categories, rng).unwrap_or("retail").to_string(),
+                    fraud_rate: c.fraud_rates.get(&risk_tier).copied().unwrap_or(0.0),
+                    risk_tier,
+                    bad_from: None,
+                    bad_until: None,
+                };
+                store.upsert_merchant(&self.run_id, &row)?;
+                merchants.push(row);
+            }
+        }
+
+        let mut events = Vec::new();
+        for merchant in &mut merchants {
+            if merchant.bad_from.is_some() || !rng.chance(c.bad_merchant_rate) {
+                continue;
+            }
+            let until = tick + c.bad_merchant_ticks.saturating_sub(1);
+            merchant.bad_from = Some(tick);
+            merchant.bad_until = Some(until);
+            store.upsert_merchant(&self.run_id, merchant)?;
+            tracing::warn!("{} ({}) has gone bad until tick {until}", merchant.merchant_id, merchant.name);
+            events.push(SimEvent::MerchantTurnedBad { tick, merchant_id: merchant.merchant_id.clone(), until });
+        }
+        Ok((merchants, events))
+    }
+
+    /// The merchant a purchase is made at.
+    pub fn pick<'a>(&self, merchants: &'a [MerchantRow], rng: &mut SubsystemRng) -> Option<&'a MerchantRow> {
+        if merchants.is_empty() {
+            return None;
+        }
+        Some(&merchants[rng.next_u64_below(merchants.len() as u64) as usize])
+    }
+
+    /// Chance a purchase at `merchant` authorized at `tick_authorized`
+    /// is disputed.
+    pub fn dispute_rate(&self, merchant: &MerchantRow, tick_authorized: Tick) -> f64 {
+        if merchant.bad_at(tick_authorized) {
+            (merchant.fraud_rate * self.config.bad_merchant_multiplier).min(1.0)
+        } else {
+            merchant.fraud_rate
+        }
+    }
+
+    /// Raise an early warning for every merchant whose disputes over the
+    /// window run over the alert rate.
+    pub fn monitor(&self, store: &SimStore, tick: Tick) -> SimResult<Vec<SimEvent>> {
+        let c = &self.config;
+        let start = tick.saturating_sub(c.chargeback_window_ticks.saturating_sub(1));
+        let mut events = Vec::new();
+        for stats in store.merchant_chargeback_stats(&self.run_id, start, tick)? {
+            if stats.disputes < c.chargeback_alert_min_disputes || stats.rate < c.chargeback_alert_rate {
+                continue;
+            }
+            store.insert_early_warning_alert(&self.run_id, tick, &EarlyWarningAlert {
+                alert_type: CHARGEBACK_ALERT.into(),
+                severity: if stats.rate >= 2.0 * c.chargeback_alert_rate { "high" } else { "medium" }.into(),
+                segment: Some(stats.merchant_id.clone()),
+                metric_name: "dispute_rate".into(),
+                current_value: stats.rate,
+                threshold_value: c.chargeback_alert_rate,
+                delta_pct: (stats.rate - c.chargeback_alert_rate) / c.chargeback_alert_rate,
+            })?;
+            tracing::warn!(
+                "{} disputes at {:.1}% of purchases ({} of {})",
+                stats.merchant_id,
+                stats.rate * 100.0,
+                stats.disputes,
+                stats.authorizations
+            );
+            events.push(SimEvent::MerchantChargebackAlert {
+                tick,
+                merchant_id: stats.merchant_id,
+                disputes: stats.disputes,
+                authorizations: stats.authorizations,
+                rate: stats.rate,
+            });
+        }
+        Ok(events)
+    }
+}
+
+/// A key drawn in proportion to its weight.
+fn draw<'a>(weights: &'a BTreeMap<String, f64>, rng: &mut SubsystemRng) -> Option<&'a str> {
+    let total: f64 = weights.values().sum();
+    let roll = rng.next_f64() * total;
+    let mut cumulative = 0.0;
+    for (key, weight) in weights {
+        cumulative += weight;
+        if roll < cumulative {
+            return Some(key);
+        }
+    }
+    weights.keys().next_back().map(String::as_str)
+}
@@ -0,0 +1,298 @@
+//! Money mule networks (Phase 4.87).
+//!
+//! Now and then a controller recruits a handful of existing customers as
+//! mules. Every cycle each mule takes a few credits from fresh senders,
+//! each well under any reporting threshold, and a tick later passes
+//! them on, less its cut, to the controller, who wires the day's take
+//! abroad. After its lifetime the network dissolves; whatever a mule
+//! still holds stays with it.
+//!
+//! The controller and each mule are linked by a suspicious
+//! `customer_relationship` row, so detection that walks relationships —
+//! alert grouping, case management — sees the network as one. Which
+//! customers belong to a network, and every leg they booked, is ground
+//! truth kept in `mule_network`, `mule_network_member` and
+//! `mule_transfer`.
+
+use crate::{
+    config::MuleNetworkConfig,
+    error::SimResult,
+    event::SimEvent,
+    rng::SubsystemRng,
+    store::{
+        mule_network::{MuleMemberRow, MuleNetworkRow, MuleTransferRow},
+        AccountRow, CustomerRelationshipRow, SimStore,
+    },
+    types::{RunId, Tick},
+};
+use std::collections::HashSet;
+use uuid::Uuid;
+
+/// Today's totals across every network.
+#[derive(Default)]
+struct Booked {
+    inbound: Vec<f64>,
+    forwarded: Vec<f64>,
+    cashed_out: f64,
+}
+
+pub struct MuleNetworks {
+    run_id: RunId,
+    config: MuleNetworkConfig,
+}
+
+impl MuleNetworks {
+    pub fn new(run_id: RunId, config: MuleNetworkConfig) -> Self {
+        Self { run_id, config }
+    }
+
+    pub fn enabled(&self) -> bool {
+        self.config.enabled
+    }
+
+    /// Seed a new network if one is recruited today, then book the
+    /// credits, forwards and cash-outs falling due.
+    pub fn process(
+        &self,
+        store: &SimStore,
+        accounts: &[AccountRow],
+        tick: Tick,
+        rng: &mut SubsystemRng,
+    ) -> SimResult<Vec<SimEvent>> {
+        let live: HashSet<&str> = accounts.iter().map(|a| a.account_id.as_str()).collect();
+
+        let mut events = Vec::new();
+        let active = store.active_mule_networks(&self.run_id)?;
+        if (active.len() as u32) < self.config.max_networks && rng.chance(self.config.seed_daily_rate) {
+            events.extend(self.seed(store, accounts, tick, rng)?);
+        }
+
+        let mut booked = Booked::default();
+        for network in store.active_mule_networks(&self.run_id)? {
+            let expired = tick >= network.seeded_tick + self.config.lifetime_ticks;
+            // Mules forward only to a controller whose account can take it
+            let collecting = live.contains(network.controller_account_id.as_str());
+            let mut collected = 0.0;
+            for mut member in store.mule_members(&self.run_id, &network.network_id)? {
+                // Closed and restricted accounts move nothing
+                if !live.contains(member.account_id.as_str()) {
+                    continue;
+                }
+                if !expired && member.next_tick <= tick {
+                    booked.inbound.extend(self.receive(store, &mut member, tick, rng)?);
+                }
+                if collecting && member.forward_tick.is_some_and(|t| t <= tick) {
+                    if let Some(amount) = self.forward(store, &network, &mut member, tick)? {
+                        booked.forwarded.push(amount);
+                        collected += amount;
+                    }
+                }
+                store.update_mule_member(&self.run_id, &member)?;
+            }
+            if collected > 0.0 {
+                booked.cashed_out += self.cash_out(store, &network, collected, tick, rng)?;
+            }
+            if expired {
+                store.dissolve_mule_network(&self.run_id, &network.network_id, tick)?;
+                tracing::info!("mule network {} dissolved", network.network_id);
+                events.push(SimEvent::MuleNetworkDissolved { tick, network_id: network.network_id });
+            }
+        }
+
+        if !booked.inbound.is_empty() || !booked.forwarded.is_empty() {
+            events.push(SimEvent::MuleTransfersBooked {
+                tick,
+                inbound_credits: booked.inbound.len() as i64,
+                inflow: booked.inbound.iter().sum(),
+                forwards: booked.forwarded.len() as i64,
+                forwarded: booked.forwarded.iter().sum(),
+                cashed_out: booked.cashed_out,
+            });
+        }
+        Ok(events)
+    }
+
+    /// Recruit a controller and its mules from customers in no network
+    /// yet, linking each mule to the controller.
+    fn seed(
+        &self,
+        store: &SimStore,
+        accounts: &[AccountRow],
+        tick: Tick,
+        rng: &mut SubsystemRng,
+    ) -> SimResult<Option<SimEvent>> {
+        let cfg = &self.config;
+        // One account per customer, from customers in no network yet
+        let mut taken = store.mule_network_customers(&self.run_id)?;
+        let mut candidates: Vec<&AccountRow> = Vec::new();
+        for acct in accounts {
+            if taken.insert(acct.customer_id.clone()) {
+                candidates.push(acct);
+            }
+        }
+        let min_mules = cfg.min_mules.max(1) as usize;
+        if candidates.len() <= min_mules {
+            return Ok(None);
+        }
+        let spread = cfg.max_mules.saturating_sub(cfg.min_mules) as u64 + 1;
+        let mules = (min_mules + rng.next_u64_below(spread) as usize).min(candidates.len() - 1);
+
+        let controller = candidates.swap_remove(rng.next_u64_below(candidates.len() as u64) as usize);
+        let network_id = format!("mn-{}", store.mule_networks(&self.run_id)?.len() + 1);
+        store.insert_mule_network(&self.run_id, &MuleNetworkRow {
+            network_id: network_id.clone(),
+            controller_id: controller.customer_id.clone(),
+            controller_account_id: controller.account_id.clone(),
+            seeded_tick: tick,
+            mule_count: mules as i64,
+            status: "active".into(),
+            dissolved_tick: None,
+        })?;
+        for _ in 0..mules {
+            let mule = candidates.swap_remove(rng.next_u64_below(candidates.len() as u64) as usize);
+            store.insert_mule_member(&self.run_id, &MuleMemberRow {
+                network_id: network_id.clone(),
+                customer_id: mule.customer_id.clone(),
+                account_id: mule.account_id.clone(),
+                recruited_tick: tick,
+                // Stagger the first round across the cycle
+                next_tick: tick + 1 + rng.next_u64_below(cfg.cycle_interval_ticks.max(1)),
+                forward_tick: None,
+                pending: 0.0,
+            })?;
+            store.insert_customer_relationship(&CustomerRelationshipRow {
+                relationship_id: format!("rel-mule-{network_id}-{}", mule.customer_id),
+                run_id: self.run_id.clone(),
+                customer_id_a: controller.customer_id.clone(),
+                customer_id_b: mule.customer_id.clone(),
+                relationship_type: "associate".into(),
+                strength: cfg.relationship_strength,
+                detected_tick: tick as i64,
+                detection_method: "inferred_txn".into(),
+                is_suspicious: 1,
+            })?;
+        }
+        tracing::info!("mule network {network_id} seeded: {} controls {mules} mules", controller.customer_id);
+        Ok(Some(SimEvent::MuleNetworkSeeded {
+            tick,
+            network_id,
+            controller_id: controller.customer_id.clone(),
+            mules: mules as u32,
+        }))
+    }
+
+    /// One round of credits into a mule from fresh senders, held until
+    /// the forwarding lag has passed. Returns the amounts received.
+    fn receive(
+        &self,
+        store: &SimStore,
+        member: &mut MuleMemberRow,
+        tick: Tick,
+        rng: &mut SubsystemRng,
+    ) -> SimResult<Vec<f64>> {
+        let cfg = &self.config;
+        let mut received = Vec::new();
+        for _ in 0..cfg.credits_per_cycle {
+            let sender = format!("p2p-{}", rng.next_u64_below(1_000_000));
+            let amount = cfg.credit_amount * (0.7 + rng.next_f64() * 0.6);
+            self.book(store, &member.network_id, &member.customer_id, &member.account_id, "inbound", &sender, amount, tick)?;
+            received.push(amount);
+        }
+        member.pending += received.iter().sum::<f64>() * (1.0 - cfg.mule_cut);
+        member.forward_tick = Some(tick + cfg.forward_lag_ticks);
+        member.next_tick = tick + cfg.cycle_interval_ticks.max(1);
+        Ok(received)
+    }
+
+    /// Pass what a mule holds on to the controller, as far as its balance
+    /// covers it. Returns the amount forwarded.
+    fn forward(
+        &self,
+        store: &SimStore,
+        network: &MuleNetworkRow,
+        member: &mut MuleMemberRow,
+        tick: Tick,
+    ) -> SimResult<Option<f64>> {
+        let amount = member.pending.min(store.account_balance(&self.run_id, &member.account_id)?.max(0.0));
+        member.pending = 0.0;
+        member.forward_tick = None;
+        if amount <= 0.0 {
+            return Ok(None);
+        }
+        self.book(
+            store, &network.network_id, &member.customer_id, &member.account_id, "forward",
+            &network.controller_account_id, amount, tick,
+        )?;
+        self.book(
+            store, &network.network_id, &network.controller_id, &network.controller_account_id, "collect",
+            &member.account_id, amount, tick,
+        )?;
+        Ok(Some(amount))
+    }
+
+    /// Wire what the controller collected today abroad. Returns the
+    /// amount wired.
+    fn cash_out(
+        &self,
+        store: &SimStore,
+        network: &MuleNetworkRow,
+        collected: f64,
+        tick: Tick,
+        rng: &mut SubsystemRng,
+    ) -> SimResult<f64> {
+        let amount = collected.min(store.account_balance(&self.run_id, &network.controller_account_id)?.max(0.0));
+        if amount <= 0.0 {
+            return Ok(0.0);
+        }
+        let beneficiary = format!("wire-{}", rng.next_u64_below(1_000_000));
+        self.book(
+            store, &network.network_id, &network.controller_id, &network.controller_account_id, "cash_out",
+            &beneficiary, amount, tick,
+        )?;
+        Ok(amount)
+    }
+
+    /// Book one leg on the account and record it.
+    #[allow(clippy::too_many_arguments)]
+    fn book(
+        &self,
+        store: &SimStore,
+        network_id: &str,
+        customer_id: &str,
+        account_id: &str,
+        leg: &str,
+        counterparty: &str,
+        amount: f64,
+        tick: Tick,
+    ) -> SimResult<()> {
+        let txn_id = Uuid::new_v4().to_string();
+        let (direction, category, rail, delta) = match leg {
+            "inbound" | "collect" => ("credit", "p2p_transfer", "RTP", amount),
+            "forward" => ("debit", "p2p_transfer", "RTP", -amount),
+            _ => ("debit", "international_wire", "wire", -amount),
+        };
+        store.insert_transaction_with_rail(
+            &self.run_id,
+            &txn_id,
+            account_id,
+            tick,
+            amount,
+            direction,
+            category,
+            Some(counterparty),
+            rail,
+            "settled",
+        )?;
+        store.update_account_balance(&self.run_id, account_id, delta)?;
+        store.insert_mule_transfer(&self.run_id, &MuleTransferRow {
+            txn_id,
+            network_id: network_id.to_string(),
+            customer_id: customer_id.to_string(),
+            account_id: account_id.to_string(),
+            tick,
+            leg: leg.to_string(),
+            counterparty: counterparty.to_string(),
+            amount,
+        })
+    }
+}
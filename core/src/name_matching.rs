@@ -0,0 +1,227 @@
+//! Sanctions name matching (Phase 4.89).
+//!
+//! Names are compared token by token with Jaro-Winkler, so word order,
+//! hyphens and small spelling differences ("Al-Mansoori" against "Ali Al
+//! Mansour") cost little, and an initial matches the word it stands for.
+//! A listed party is matched on its name or its best alias, whichever
+//! scores higher, an alias a little discounted.
+//!
+//! The name score is then corroborated: country and year of birth each
+//! carry a weight, earned in full when they agree with the listing,
+//! not at all when they contradict it, and half when either side leaves
+//! them out. A namesake born a decade apart clears; a listed party giving
+//! an alias, its country and its year of birth does not. Every match
+//! carries an explanation of how its score was reached.
+
+use crate::config::SanctionsScreeningConfig;
+
+/// Score of an initial against a word starting with it.
+const INITIAL_SCORE: f64 = 0.75;
+
+/// Jaro-Winkler similarity of two strings, in 0..1.
+pub fn jaro_winkler(a: &str, b: &str) -> f64 {
+    let (a, b): (Vec<char>, Vec<char>) = (a.chars().collect(), b.chars().collect());
+    if a.is_empty() && b.is_empty() {
+        return 1.0;
+    }
+    if a.is_empty() || b.is_empty() {
+        return 0.0;
+    }
+
+    let window = (a.len().max(b.len()) / 2).saturating_sub(1);
+    let mut a_matched = vec![false; a.len()];
+    let mut b_matched = vec![false; b.len()];
+    let mut matches = 0usize;
+    for (i, ca) in a.iter().enumerate() {
+        let lo = i.saturating_sub(window);
+        let hi = (i + window + 1).min(b.len());
+        for j in lo..hi {
+            if !b_matched[j] && b[j] == *ca {
+                a_matched[i] = true;
+                b_matched[j] = true;
+                matches += 1;
+                break;
+            }
+        }
+    }
+    if matches == 0 {
+        return 0.0;
+    }
+
+    let a_seq = a.iter().zip(&a_matched).filter(|(_, m)| **m).map(|(c, _)| c);
+    let b_seq = b.iter().zip(&b_matched).filter(|(_, m)| **m).map(|(c, _)| c);
+    let transpositions = a_seq.zip(b_seq).filter(|(x, y)| x != y).count() / 2;
+
+    let m = matches as f64;
+    let jaro = (m / a.len() as f64 + m / b.len() as f64 + (m - transpositions as f64) / m) / 3.0;
+    let prefix = a.iter().zip(&b).take(4).take_while(|(x, y)| x == y).count();
+    jaro + prefix as f64 * 0.1 * (1.0 - jaro)
+}
+
+/// A name's words, lowercased, split on spaces and hyphens, punctuation
+/// dropped.
+fn tokens(name: &str) -> Vec<String> {
+    name.split(|c: char| c.is_whitespace() || c == '-')
+        .map(|w| w.chars().filter(|c| c.is_alphanumeric()).collect::<String>().to_lowercase())
+        .filter(|w| !w.is_empty())
+        .collect()
+}
+
+fn token_score(x: &str, y: &str) -> f64 {
+    let initial = |i: &str, w: &str| i.chars().count() == 1 && w.starts_with(i);
+    if x.chars().count() > 1 && y.chars().count() > 1 {
+        jaro_winkler(x, y)
+    } else if x == y {
+        1.0
+    } else if initial(x, y) || initial(y, x) {
+        INITIAL_SCORE
+    } else {
+        0.0
+    }
+}
+
+/// Token-based name similarity in 0..1, independent of word order. Each
+/// word of the shorter name is paired with its best unused word of the
+/// longer; the total is averaged over both names' word counts, so a
+/// missing middle name costs something but not everything.
+pub fn token_similarity(a: &str, b: &str) -> f64 {
+    let (a, b) = (tokens(a), tokens(b));
+    if a.is_empty() || b.is_empty() {
+        return 0.0;
+    }
+    let (short, long) = if a.len() <= b.len() { (&a, &b) } else { (&b, &a) };
+    let mut used = vec![false; long.len()];
+    let mut total = 0.0;
+    for x in short {
+        let best = long
+            .iter()
+            .enumerate()
+            .filter(|(j, _)| !used[*j])
+            .map(|(j, y)| (j, token_score(x, y)))
+            .max_by(|p, q| p.1.total_cmp(&q.1));
+        if let Some((j, score)) = best {
+            used[j] = true;
+            total += score;
+        }
+    }
+    (total / short.len() as f64 + total / long.len() as f64) / 2.0
+}
+
+/// How an identifier on file compares with the listing's.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Corroboration {
+    Agrees,
+    Contradicts,
+    /// One side or the other does not have it.
+    Unknown,
+}
+
+impl Corroboration {
+    fn credit(self) -> f64 {
+        match self {
+            Self::Agrees => 1.0,
+            Self::Contradicts => 0.0,
+            Self::Unknown => 0.5,
+        }
+    }
+
+    fn describe(self) -> &'static str {
+        match self {
+            Self::Agrees => "agrees",
+            Self::Contradicts => "contradicts",
+            Self::Unknown => "unknown",
+        }
+    }
+}
+
+/// The identifiers a customer is screened on.
+#[derive(Debug, Clone)]
+pub struct ScreenedName<'a> {
+    pub name: &'a str,
+    pub country: Option<&'a str>,
+    pub birth_year: Option<u32>,
+}
+
+/// A listed party's identifiers.
+#[derive(Debug, Clone)]
+pub struct ListedName<'a> {
+    pub name: &'a str,
+    pub aliases: &'a [String],
+    pub countries: &'a [String],
+    pub birth_year: Option<u32>,
+}
+
+/// A candidate match and how its score was reached.
+#[derive(Debug, Clone)]
+pub struct NameMatch {
+    pub score: f64,
+    pub name_score: f64,
+    /// The listed name or alias matched.
+    pub matched_name: String,
+    pub via_alias: bool,
+    pub country: Corroboration,
+    pub birth_year: Corroboration,
+    pub explanation: String,
+}
+
+pub struct NameMatcher<'a> {
+    config: &'a SanctionsScreeningConfig,
+}
+
+impl<'a> NameMatcher<'a> {
+    pub fn new(config: &'a SanctionsScreeningConfig) -> Self {
+        Self { config }
+    }
+
+    /// Score a screened name against a listed party; None when neither
+    /// its name nor any alias is close enough to be a candidate.
+    pub fn score(&self, screened: &ScreenedName, listed: &ListedName) -> Option<NameMatch> {
+        let cfg = self.config;
+        let (matched_name, via_alias, name_score) = std::iter::once((listed.name, false, 1.0))
+            .chain(listed.aliases.iter().map(|a| (a.as_str(), true, cfg.alias_discount)))
+            .map(|(name, alias, discount)| (name, alias, token_similarity(screened.name, name) * discount))
+            .max_by(|p, q| p.2.total_cmp(&q.2))?;
+        if name_score < cfg.candidate_name_score {
+            return None;
+        }
+
+        let country = match screened.country {
+            Some(c) if !listed.countries.is_empty() => {
+                if listed.countries.iter().any(|l| l == c) {
+                    Corroboration::Agrees
+                } else {
+                    Corroboration::Contradicts
+                }
+            }
+            _ => Corroboration::Unknown,
+        };
+        let birth_year = match (screened.birth_year, listed.birth_year) {
+            (Some(s), Some(l)) if s.abs_diff(l) <= cfg.birth_year_tolerance => Corroboration::Agrees,
+            (Some(_), Some(_)) => Corroboration::Contradicts,
+            _ => Corroboration::Unknown,
+        };
+        let score = (cfg.name_weight * name_score
+            + cfg.country_weight * country.credit()
+            + cfg.birth_year_weight * birth_year.credit())
+            / (cfg.name_weight + cfg.country_weight + cfg.birth_year_weight);
+
+        let explanation = format!(
+            "name {:.2} against {} '{}'; country {}; year of birth {}; score {:.2}",
+            name_score,
+            if via_alias { "alias" } else { "listed name" },
+            matched_name,
+            country.describe(),
+            birth_year.describe(),
+            score,
+        );
+        Some(NameMatch {
+            score,
+            name_score,
+            matched_name: matched_name.to_string(),
+            via_alias,
+            country,
+            birth_year,
+            explanation,
+        })
+    }
+}
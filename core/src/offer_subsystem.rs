@@ -11,17 +11,39 @@
 //!   - Flag bonus-seekers probabilistically
 //!
 //! Phase 3 hook: bonus_seeker_flag feeds into AML risk scoring
+//!
+//! Phase 4.5: LaunchOffer activates a catalog offer with an optional zip
+//! exclusion list and targets every eligible existing customer. Before
+//! launch the targeting policy goes through a proxy-discrimination
+//! guardrail: the exclusion rate for customers in majority-minority zips
+//! is compared against all other zips. Over `warn_ratio` the launch
+//! proceeds with a UDAAP hit; over `block_ratio` (or when only
+//! majority-minority zips are excluded) it is blocked. A zip exclusion
+//! that cannot be assessed (no zip demographics yet) is also blocked.
+//! Every assessment is logged to `offer_guardrail_assessment`.
+//!
+//! Phase 4.32: with contact fatigue on, a launch is an outreach campaign —
+//! targeted customers are contacted under the player's contact policy
+//! and only those who respond take up the offer.
+//!
+//! Phase 4.34: with financial health on, credit offers only reach
+//! customers at or above the health floor (SetCreditHealthFloor lowers or
+//! raises it), every credit offer taken up opens a credit line, and each
+//! month customers are rescored and open lines can default.
 
 use crate::{
+    command::PlayerCommand,
     config::{OfferConfig, SimConfig},
     error::SimResult,
     event::SimEvent,
+    financial_health::FinancialHealth,
+    outreach::{Campaign, Outreach},
     rng::SubsystemRng,
     store::SimStore,
-    subsystem::SimSubsystem,
+    subsystem::{SimSubsystem, SubsystemManifest},
     types::{RunId, Tick},
 };
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 
 pub struct OfferSubsystem {
     run_id: RunId,
@@ -29,6 +51,8 @@ pub struct OfferSubsystem {
     store: SimStore,
     initialized: bool,
     active_offers: HashMap<String, OfferConfig>,
+    outreach: Outreach,
+    health: FinancialHealth,
 }
 
 #[derive(Debug, Clone)]
@@ -56,6 +80,7 @@ pub struct CustomerSnapshot {
     pub churn_risk: f64,
     pub open_tick: Tick,
     pub product_count: usize,
+    pub zip_code: Option<String>,
 }
 
 #[derive(Debug, Clone)]
@@ -84,12 +109,20 @@ impl OfferSubsystem {
             .map(|(k, v)| (k.clone(), v.clone()))
             .collect();
 
+        let outreach = Outreach::new(run_id.clone(), config.contact_fatigue.clone());
+        let health = FinancialHealth::new(
+            run_id.clone(),
+            config.financial_health.clone(),
+            config.transaction_enrichment.clone(),
+        );
         Self {
             run_id,
             config,
             store,
             initialized: false,
             active_offers,
+            outreach,
+            health,
         }
     }
 
@@ -163,6 +196,18 @@ impl OfferSubsystem {
             }
         }
 
+        // Geographic exclusions (Phase 4.5)
+        if let Some(zip) = &customer.zip_code {
+            if offer
+                .eligibility
+                .exclude_zip_prefixes
+                .iter()
+                .any(|p| zip.starts_with(p.as_str()))
+            {
+                return false;
+            }
+        }
+
         true
     }
 
@@ -180,11 +225,14 @@ impl OfferSubsystem {
         };
 
         // Collect eligible active offers
-        let mut eligible: Vec<&OfferConfig> = self
-            .active_offers
-            .values()
-            .filter(|o| self.is_customer_eligible(&customer, o, tick))
-            .collect();
+        let mut eligible = Vec::new();
+        for offer in self.active_offers.values() {
+            if self.is_customer_eligible(&customer, offer, tick)
+                && self.clears_credit_floor(customer_id, offer, tick)?
+            {
+                eligible.push(offer);
+            }
+        }
 
         if eligible.is_empty() {
             return Ok(out);
@@ -195,6 +243,29 @@ impl OfferSubsystem {
         eligible.sort_by(|a, b| a.offer_id.cmp(&b.offer_id)); // deterministic order before pick
         let offer = eligible[idx];
 
+        out.push(self.create_customer_offer(customer_id, offer, tick, rng)?);
+        Ok(out)
+    }
+
+    /// Phase 4.34: whether the customer's health clears the floor for a
+    /// credit offer. Non-credit offers, and everything when financial
+    /// health is off, always clear.
+    fn clears_credit_floor(&self, customer_id: &str, offer: &OfferConfig, tick: Tick) -> SimResult<bool> {
+        if !self.health.enabled() || !self.health.is_credit_offer(offer) {
+            return Ok(true);
+        }
+        let score = self.health.current(&self.store, customer_id, tick)?;
+        Ok(score >= self.health.min_credit_score(&self.store)?)
+    }
+
+    /// Insert an auto-accepted offer for the customer.
+    fn create_customer_offer(
+        &self,
+        customer_id: &str,
+        offer: &OfferConfig,
+        tick: Tick,
+        rng: &mut SubsystemRng,
+    ) -> SimResult<SimEvent> {
         let bonus_seeker_flag = rng.chance(offer.fraud_risk.bonus_seeker_probability);
 
         let record = CustomerOfferRecord {
@@ -217,20 +288,215 @@ impl OfferSubsystem {
 
         self.store.insert_customer_offer(&self.run_id, &record)?;
 
-        out.push(SimEvent::OfferMatched {
-            tick,
-            customer_id: customer_id.to_string(),
-            offer_id: offer.offer_id.clone(),
-            bonus_amount: offer.bonus_amount,
-        });
+        // Phase 4.34: a credit offer taken up opens a line of credit
+        if self.health.enabled() && self.health.is_credit_offer(offer) {
+            let score = self.health.current(&self.store, customer_id, tick)?;
+            self.health.open_credit_line(&self.store, customer_id, offer, score, tick)?;
+        }
 
-        log::info!(
-            "tick={tick} offer: matched {} to {} (bonus_seeker={})",
+        tracing::info!(
+            "matched {} to {} (bonus_seeker={})",
             customer_id,
             offer.offer_id,
             bonus_seeker_flag
         );
 
+        Ok(SimEvent::OfferMatched {
+            tick,
+            customer_id: customer_id.to_string(),
+            offer_id: offer.offer_id.clone(),
+            bonus_amount: offer.bonus_amount,
+        })
+    }
+
+    /// Proxy-discrimination check on a targeting policy. Returns the
+    /// outcome ("pass" | "warn" | "block" | "unassessed"), the protected
+    /// and control exclusion rates and the disparity ratio between them.
+    fn assess_targeting(
+        &self,
+        offer: &OfferConfig,
+        tick: Tick,
+    ) -> SimResult<(&'static str, f64, f64, Option<f64>)> {
+        // No geographic rule: nothing can act as a proxy
+        if offer.eligibility.exclude_zip_prefixes.is_empty() {
+            return Ok(("pass", 0.0, 0.0, Some(0.0)));
+        }
+
+        // Base population: customers the offer would reach without the
+        // geographic rule.
+        let mut base = offer.clone();
+        base.eligibility.exclude_zip_prefixes.clear();
+
+        let (mut p_total, mut p_excl, mut c_total, mut c_excl) = (0usize, 0usize, 0usize, 0usize);
+        for (customer_id, zip, majority_minority) in
+            self.store.offer_targeting_population(&self.run_id)?
+        {
+            let Some(protected) = majority_minority else { continue };
+            let snapshot = self.store.get_customer_snapshot(&self.run_id, &customer_id)?;
+            if !self.is_customer_eligible(&snapshot, &base, tick) {
+                continue;
+            }
+            let excluded = offer
+                .eligibility
+                .exclude_zip_prefixes
+                .iter()
+                .any(|p| zip.starts_with(p.as_str()));
+            if protected {
+                p_total += 1;
+                p_excl += excluded as usize;
+            } else {
+                c_total += 1;
+                c_excl += excluded as usize;
+            }
+        }
+
+        if p_total == 0 || c_total == 0 {
+            return Ok(("unassessed", 0.0, 0.0, None));
+        }
+        let p_rate = p_excl as f64 / p_total as f64;
+        let c_rate = c_excl as f64 / c_total as f64;
+        let guardrail = &self.config.offer_guardrail;
+
+        let (outcome, ratio) = if p_excl == 0 {
+            ("pass", Some(0.0))
+        } else if c_excl == 0 {
+            ("block", None) // only majority-minority geographies excluded
+        } else {
+            let ratio = p_rate / c_rate;
+            let outcome = if ratio > guardrail.block_ratio {
+                "block"
+            } else if ratio > guardrail.warn_ratio {
+                "warn"
+            } else {
+                "pass"
+            };
+            (outcome, Some(ratio))
+        };
+        Ok((outcome, p_rate, c_rate, ratio))
+    }
+
+    /// LaunchOffer: assess the targeting policy, then activate the offer
+    /// and match it to every eligible active customer.
+    fn launch_offer(
+        &mut self,
+        offer_id: &str,
+        exclude_zip_prefixes: Vec<String>,
+        tick: Tick,
+        rng: &mut SubsystemRng,
+    ) -> SimResult<Vec<SimEvent>> {
+        let mut out = Vec::new();
+        let Some(mut offer) = self.config.offers.get(offer_id).cloned() else {
+            tracing::warn!("launch of unknown offer {offer_id}");
+            return Ok(out);
+        };
+        offer.eligibility.exclude_zip_prefixes = exclude_zip_prefixes;
+
+        if self.config.offer_guardrail.enabled {
+            let (outcome, p_rate, c_rate, ratio) = self.assess_targeting(&offer, tick)?;
+            // A geographic rule that cannot be assessed fails closed
+            let launched = !matches!(outcome, "block" | "unassessed");
+            self.store.insert_offer_guardrail_assessment(
+                &self.run_id,
+                tick,
+                offer_id,
+                &offer.eligibility.exclude_zip_prefixes,
+                p_rate,
+                c_rate,
+                ratio,
+                outcome,
+                launched,
+            )?;
+            out.push(SimEvent::OfferGuardrailAssessed {
+                tick,
+                offer_id: offer_id.to_string(),
+                outcome: outcome.to_string(),
+                disparity_ratio: ratio,
+            });
+
+            match outcome {
+                "block" => {
+                    tracing::warn!(
+                        "guardrail blocked {offer_id} \
+                         (protected exclusion {p_rate:.2} vs control {c_rate:.2})"
+                    );
+                    return Ok(out);
+                }
+                "unassessed" => {
+                    tracing::warn!(
+                        "guardrail blocked {offer_id} \
+                         (no zip demographics to assess exclusions against)"
+                    );
+                    return Ok(out);
+                }
+                "warn" => {
+                    tracing::warn!("{offer_id} launched over guardrail warning");
+                    self.store.adjust_udaap_score(
+                        &self.run_id,
+                        self.config.offer_guardrail.warn_udaap_impact,
+                        tick,
+                    )?;
+                }
+                _ => {}
+            }
+        }
+
+        offer.active = true;
+        self.store.set_offer_active(&self.run_id, offer_id, true, tick)?;
+        self.active_offers.insert(offer_id.to_string(), offer.clone());
+
+        let mut targets = Vec::new();
+        let mut screened_out = 0i64;
+        for (customer_id, _, _) in self.store.offer_targeting_population(&self.run_id)? {
+            if self.store.customer_has_offer(&self.run_id, &customer_id, offer_id)? {
+                continue;
+            }
+            let snapshot = self.store.get_customer_snapshot(&self.run_id, &customer_id)?;
+            if !self.is_customer_eligible(&snapshot, &offer, tick) {
+                continue;
+            }
+            if !self.clears_credit_floor(&customer_id, &offer, tick)? {
+                screened_out += 1;
+                continue;
+            }
+            targets.push(customer_id);
+        }
+        let targeted = targets.len() as i64;
+
+        // Phase 4.34: record who the health floor held back
+        if self.health.enabled() && self.health.is_credit_offer(&offer) {
+            let min_score = self.health.min_credit_score(&self.store)?;
+            tracing::info!("{offer_id}: {screened_out} customers below health floor {min_score:.2}");
+            out.push(SimEvent::CreditOfferScreened {
+                tick,
+                offer_id: offer_id.to_string(),
+                min_score,
+                eligible: targeted,
+                screened_out,
+            });
+        }
+
+        // Phase 4.32: only customers who respond to the outreach take it up
+        if self.outreach.enabled() {
+            let campaign = Campaign {
+                campaign_type: "offer",
+                campaign_id:   offer_id,
+                channel:       "email",
+                response_rate: self.config.contact_fatigue.offer_response_rate,
+            };
+            let (responders, events) = self.outreach.run_campaign(&self.store, &campaign, &targets, tick, rng)?;
+            out.extend(events);
+            targets = responders;
+        }
+        for customer_id in &targets {
+            out.push(self.create_customer_offer(customer_id, &offer, tick, rng)?);
+        }
+
+        tracing::info!("launched {offer_id} to {targeted} customers");
+        out.push(SimEvent::OfferLaunched {
+            tick,
+            offer_id: offer_id.to_string(),
+            customers_targeted: targeted,
+        });
         Ok(out)
     }
 
@@ -300,8 +566,8 @@ impl OfferSubsystem {
                             bonus_seeker_flag: rec.bonus_seeker_flag,
                         });
 
-                        log::info!(
-                            "tick={tick} offer: bonus ${:.0} paid to {} (seeker={})",
+                        tracing::info!(
+                            "bonus ${:.0} paid to {} (seeker={})",
                             rec.bonus_amount,
                             rec.customer_id,
                             rec.bonus_seeker_flag
@@ -330,11 +596,32 @@ impl OfferSubsystem {
     }
 }
 
+const MANIFEST: SubsystemManifest = SubsystemManifest {
+    version: 1,
+    produces: &[
+        "contact_preference_violated", "credit_health_floor_rejected", "credit_health_floor_set",
+        "credit_line_defaulted", "credit_offer_screened", "financial_health_scored",
+        "offer_bonus_paid", "offer_completed", "offer_guardrail_assessed", "offer_launched",
+        "offer_matched", "outreach_campaign_run",
+    ],
+    consumes: &["customer_onboarded", "player_command_received"],
+    tables: &[
+        "account", "contact_preference", "credit_health_policy", "credit_line", "customer",
+        "customer_contact", "customer_health", "customer_offer", "offer_config_state",
+        "offer_guardrail_assessment", "offer_performance", "regulatory_score",
+    ],
+    requires: &[],
+};
+
 impl SimSubsystem for OfferSubsystem {
     fn name(&self) -> &'static str {
         "offer"
     }
 
+    fn manifest(&self) -> SubsystemManifest {
+        MANIFEST
+    }
+
     fn update(
         &mut self,
         tick: Tick,
@@ -349,18 +636,35 @@ impl SimSubsystem for OfferSubsystem {
         if !self.initialized {
             self.initialize_offer_config_state(tick)?;
             self.initialized = true;
-            log::info!(
-                "tick={tick} offer: initialized {} offers ({} active)",
+            tracing::info!(
+                "initialized {} offers ({} active)",
                 self.config.offers.len(),
                 self.active_offers.len()
             );
         }
 
-        // Match new customers to eligible offers
+        // Match new customers to eligible offers; launch player campaigns
         for event in events_in {
-            if let SimEvent::CustomerOnboarded { customer_id, .. } = event {
-                let matched = self.match_and_create_offer(customer_id, tick, rng)?;
-                out.extend(matched);
+            match event {
+                SimEvent::CustomerOnboarded { customer_id, .. } => {
+                    let matched = self.match_and_create_offer(customer_id, tick, rng)?;
+                    out.extend(matched);
+                }
+                SimEvent::PlayerCommandReceived { command_id, .. } => {
+                    match self.store.get_player_command(&self.run_id, command_id)? {
+                        Some(PlayerCommand::LaunchOffer {
+                            offer_id,
+                            exclude_zip_prefixes,
+                        }) => {
+                            out.extend(self.launch_offer(&offer_id, exclude_zip_prefixes, tick, rng)?);
+                        }
+                        Some(PlayerCommand::SetCreditHealthFloor { min_score }) if self.health.enabled() => {
+                            out.push(self.health.set_min_credit_score(&self.store, min_score, tick)?);
+                        }
+                        _ => {}
+                    }
+                }
+                _ => {}
             }
         }
 
@@ -382,11 +686,53 @@ impl SimSubsystem for OfferSubsystem {
                         .save_offer_performance(&self.run_id, &offer_id, tick, &perf);
                 }
             }
+
+            // Phase 4.34: rescore the book, then open credit lines may default
+            if self.health.enabled() {
+                out.push(self.health.rescore(&self.store, tick)?);
+                out.extend(self.health.process_defaults(&self.store, tick, rng)?);
+            }
         }
 
         Ok(out)
     }
 
+    fn save_state(&self) -> SimResult<Option<serde_json::Value>> {
+        let active: BTreeMap<_, _> = self.active_offers.iter().collect();
+        Ok(Some(serde_json::json!({ "initialized": self.initialized, "active_offers": active })))
+    }
+
+    fn load_state(&mut self, state: serde_json::Value) -> SimResult<()> {
+        self.initialized = state["initialized"].as_bool().unwrap_or_default();
+        self.active_offers = serde_json::from_value(state["active_offers"].clone())?;
+        Ok(())
+    }
+
+    fn replay(&mut self, tick: Tick, events: &[SimEvent]) -> SimResult<()> {
+        self.initialized = true;
+        for event in events {
+            let SimEvent::OfferLaunched { offer_id, .. } = event else {
+                continue;
+            };
+            let Some(mut offer) = self.config.offers.get(offer_id).cloned() else {
+                continue;
+            };
+            // The exclusions are on the command, sent the tick before
+            for (command_id, _) in self.store.player_commands_at(&self.run_id, tick - 1)? {
+                if let Some(PlayerCommand::LaunchOffer { offer_id: launched, exclude_zip_prefixes }) =
+                    self.store.get_player_command(&self.run_id, &command_id.to_string())?
+                {
+                    if launched == *offer_id {
+                        offer.eligibility.exclude_zip_prefixes = exclude_zip_prefixes;
+                    }
+                }
+            }
+            offer.active = true;
+            self.active_offers.insert(offer_id.clone(), offer);
+        }
+        Ok(())
+    }
+
     fn as_any(&self) -> &dyn std::any::Any {
         self
     }
@@ -0,0 +1,100 @@
+//! Loss event record and operational risk capital (Phase 4.61).
+//!
+//! Every operational loss the bank bears — check and wire fraud, exam
+//! and filing fines, consumer penalties and remediation, reconciliation
+//! write-offs — is copied from the table that booked it into one loss
+//! event record, filed under its Basel event type. Each tick records
+//! the losses booked in the two ticks before; a loss already on record
+//! is never counted twice.
+//!
+//! Each quarter the capital held against operational risk is estimated
+//! in the manner of the standardised approach: the business indicator
+//! component is a share of annualised gross income, the loss component a
+//! multiple of average yearly losses over the lookback, and the internal
+//! loss multiplier ln(e − 1 + (LC / BIC)^0.8) scales the first by how
+//! the bank's own losses compare with it. The estimate is reported with
+//! the quarter's P&L, and a deposit run cannot draw on the capital it
+//! holds.
+
+use crate::{
+    config::OperationalRiskConfig,
+    error::SimResult,
+    event::SimEvent,
+    store::{loss_event::OpRiskCapitalRow, SimStore},
+    types::{RunId, Tick},
+};
+
+pub struct OperationalRisk {
+    run_id: RunId,
+    config: OperationalRiskConfig,
+    /// Cost of remediating one fair lending finding.
+    remediation_cost: f64,
+}
+
+impl OperationalRisk {
+    pub fn new(run_id: RunId, config: OperationalRiskConfig, remediation_cost: f64) -> Self {
+        Self { run_id, config, remediation_cost }
+    }
+
+    pub fn enabled(&self) -> bool {
+        self.config.enabled
+    }
+
+    /// Record the losses booked in the two ticks before `tick`.
+    pub fn record_losses(&self, store: &SimStore, tick: Tick) -> SimResult<Vec<SimEvent>> {
+        if tick == 0 {
+            return Ok(Vec::new());
+        }
+        Ok(store
+            .record_loss_events(&self.run_id, tick.saturating_sub(2), tick - 1, self.remediation_cost)?
+            .into_iter()
+            .map(|loss| SimEvent::OperationalLossRecorded {
+                tick,
+                basel_category: loss.basel_category,
+                source: loss.source,
+                source_id: loss.source_id,
+                amount: loss.amount,
+            })
+            .collect())
+    }
+
+    /// Estimate capital at the end of the quarter closing at `tick`,
+    /// from the quarters' gross incomes, oldest first, up to a year of
+    /// them ending with this one.
+    pub fn estimate_capital(
+        &self,
+        store: &SimStore,
+        tick: Tick,
+        quarterly_gross_income: &[f64],
+    ) -> SimResult<OpRiskCapitalRow> {
+        let c = &self.config;
+        let quarters = &quarterly_gross_income[quarterly_gross_income.len().saturating_sub(4)..];
+        let business_indicator = if quarters.is_empty() {
+            0.0
+        } else {
+            quarters.iter().fold(0.0, |sum, g| sum + g) * 4.0 / quarters.len() as f64
+        };
+        let bic = c.bic_coefficient * business_indicator.max(0.0);
+
+        // Losses booked at `tick` itself are recorded next tick
+        let span = c.lookback_ticks.min(tick).max(1);
+        let losses = store.loss_total_between(&self.run_id, tick - span, tick.saturating_sub(1))?;
+        let annual_loss = losses * 365.0 / span as f64;
+        let loss_component = c.loss_multiplier * annual_loss;
+
+        let ilm = if bic > 0.0 {
+            (std::f64::consts::E - 1.0 + (loss_component / bic).powf(0.8)).ln()
+        } else {
+            1.0
+        };
+        Ok(OpRiskCapitalRow {
+            tick,
+            business_indicator,
+            bic,
+            annual_loss,
+            loss_component,
+            ilm,
+            capital: bic * ilm,
+        })
+    }
+}
@@ -0,0 +1,112 @@
+//! Opex allocation rules (Phase 4.63).
+//!
+//! Shared operating cost reaches segment P&L through pools, each spread
+//! over the segments by one driver:
+//!
+//! - `customers`: active customers at the quarter's end,
+//! - `transactions`: transactions posted in the quarter,
+//! - `headcount`: the heads of the desk the pool pays for working each
+//!   segment.
+//!
+//! A segment's share of a pool is its driver value over the total. The
+//! rules are recorded the first quarter they are applied and every later
+//! quarter applies the recorded ones, so the split behind segment
+//! profitability never shifts under it mid-run. Each quarter's split is
+//! kept pool by pool, so any segment's allocated opex can be traced back
+//! to the driver behind it.
+
+use crate::{
+    config::{OpexAllocationConfig, OpexPoolConfig},
+    error::SimResult,
+    store::{opex_allocation::OpexAllocationRow, SimStore},
+    types::{RunId, Tick},
+};
+use std::collections::HashMap;
+
+/// Drivers a pool can be spread by.
+pub const DRIVERS: &[&str] = &["customers", "transactions", "headcount"];
+
+pub struct OpexAllocation {
+    run_id: RunId,
+    config: OpexAllocationConfig,
+    /// A sampled run carries only its share of each pool.
+    population_scale: f64,
+}
+
+impl OpexAllocation {
+    pub fn new(run_id: RunId, config: OpexAllocationConfig, population_scale: f64) -> Self {
+        Self { run_id, config, population_scale }
+    }
+
+    pub fn enabled(&self) -> bool {
+        self.config.enabled
+    }
+
+    /// Spread every pool over `segments` for the quarter `start..=end`;
+    /// returns each segment's allocated opex.
+    pub fn allocate(
+        &self,
+        store: &SimStore,
+        tick: Tick,
+        start: Tick,
+        end: Tick,
+        segments: &[&str],
+    ) -> SimResult<HashMap<String, f64>> {
+        let pools = self.rules(store, tick)?;
+        let mut customers = HashMap::new();
+        let mut transactions = HashMap::new();
+        for segment in segments {
+            customers.insert(*segment, store.segment_customer_count(&self.run_id, segment, "active")? as f64);
+            let activity = store.segment_activity(&self.run_id, segment, start, end)?;
+            transactions.insert(*segment, activity.transaction_count as f64);
+        }
+
+        let mut allocated: HashMap<String, f64> = segments.iter().map(|s| (s.to_string(), 0.0)).collect();
+        for pool in &pools {
+            let cost = pool.quarterly_cost * self.population_scale;
+            let values: Vec<f64> = segments
+                .iter()
+                .map(|segment| match pool.driver.as_str() {
+                    "customers" => customers[segment],
+                    "transactions" => transactions[segment],
+                    _ => pool.headcount.get(*segment).copied().unwrap_or(0.0),
+                })
+                .collect();
+            let total = values.iter().fold(0.0, |sum, v| sum + v);
+            if total <= 0.0 {
+                tracing::warn!("{} pool left unallocated: no {} in any segment", pool.pool, pool.driver);
+            }
+            for (segment, value) in segments.iter().zip(values) {
+                let share = if total > 0.0 { value / total } else { 0.0 };
+                let amount = cost * share;
+                store.insert_opex_allocation(&self.run_id, &OpexAllocationRow {
+                    tick,
+                    pool: pool.pool.clone(),
+                    segment: segment.to_string(),
+                    driver: pool.driver.clone(),
+                    driver_value: value,
+                    share,
+                    amount,
+                })?;
+                *allocated.get_mut(*segment).expect("seeded above") += amount;
+            }
+        }
+        Ok(allocated)
+    }
+
+    /// The rules on record, recording the configured ones the first time.
+    fn rules(&self, store: &SimStore, tick: Tick) -> SimResult<Vec<OpexPoolConfig>> {
+        let recorded = store.opex_allocation_rules(&self.run_id)?;
+        if recorded.is_empty() {
+            for pool in &self.config.pools {
+                store.insert_opex_allocation_rule(&self.run_id, pool, tick)?;
+            }
+            return Ok(self.config.pools.clone());
+        }
+        let pools: Vec<OpexPoolConfig> = recorded.into_iter().map(|(pool, _)| pool).collect();
+        if pools != self.config.pools {
+            tracing::warn!("opex allocation config differs from the rules on record; keeping the recorded rules");
+        }
+        Ok(pools)
+    }
+}
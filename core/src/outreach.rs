@@ -0,0 +1,163 @@
+//! Outreach campaigns and communication fatigue (Phase 4.32).
+//!
+//! Every campaign contact (offer launches, satisfaction surveys,
+//! collections calls) goes through `Outreach::run_campaign`. Customers
+//! who asked not to be contacted are suppressed unless the player's
+//! policy overrides that, in which case the contact is a violation that
+//! can draw a complaint. A frequency cap holds back customers already
+//! contacted often. Each earlier contact in the window lowers the chance
+//! of a response, and past the fatigue threshold every contact costs
+//! satisfaction.
+
+use crate::{
+    config::ContactFatigueConfig,
+    error::SimResult,
+    event::SimEvent,
+    rng::SubsystemRng,
+    store::{
+        contact::{ContactPreferenceRow, CustomerContactRow},
+        SimStore,
+    },
+    types::{RunId, Tick},
+};
+
+pub const CHANNELS: &[&str] = &["email", "sms", "phone", "mail"];
+
+/// One campaign's contacts.
+pub struct Campaign<'a> {
+    /// "offer" | "survey" | "collections"
+    pub campaign_type: &'static str,
+    pub campaign_id:   &'a str,
+    pub channel:       &'static str,
+    /// Response rate before fatigue.
+    pub response_rate: f64,
+}
+
+/// Chance a customer responds, given the contacts they already had in
+/// the window and whether the campaign reached them on their preferred
+/// channel.
+pub fn response_probability(config: &ContactFatigueConfig, base_rate: f64, earlier: i64, on_channel: bool) -> f64 {
+    let channel_factor = if on_channel { 1.0 } else { config.off_channel_response_factor };
+    base_rate * config.response_decay.powi(earlier as i32) * channel_factor
+}
+
+pub struct Outreach {
+    run_id: RunId,
+    config: ContactFatigueConfig,
+}
+
+impl Outreach {
+    pub fn new(run_id: RunId, config: ContactFatigueConfig) -> Self {
+        Self { run_id, config }
+    }
+
+    pub fn enabled(&self) -> bool {
+        self.config.enabled
+    }
+
+    /// Record a new customer's do-not-contact choice and preferred channel.
+    pub fn assign_preferences(
+        &self,
+        store:       &SimStore,
+        customer_id: &str,
+        tick:        Tick,
+        rng:         &mut SubsystemRng,
+    ) -> SimResult<ContactPreferenceRow> {
+        let do_not_contact = rng.chance(self.config.do_not_contact_rate);
+        let channel = CHANNELS[rng.next_u64_below(CHANNELS.len() as u64) as usize];
+        let row = ContactPreferenceRow {
+            customer_id: customer_id.into(),
+            do_not_contact,
+            preferred_channel: channel.into(),
+            tick_set: tick,
+        };
+        store.insert_contact_preference(&self.run_id, &row)?;
+        Ok(row)
+    }
+
+    /// Contact each customer in turn under the policy in force. Returns
+    /// the customers who responded and the campaign's events. A customer
+    /// onboarded too late in the tick to have preferences gets them now.
+    pub fn run_campaign(
+        &self,
+        store:     &SimStore,
+        campaign:  &Campaign,
+        customers: &[String],
+        tick:      Tick,
+        rng:       &mut SubsystemRng,
+    ) -> SimResult<(Vec<String>, Vec<SimEvent>)> {
+        let policy = store.contact_policy(&self.run_id)?;
+        let window_start = (tick + 1).saturating_sub(self.config.window_ticks);
+        let mut responders = Vec::new();
+        let mut events = Vec::new();
+        let (mut contacted, mut suppressed, mut throttled) = (0i64, 0i64, 0i64);
+
+        for customer_id in customers {
+            let preference = match store.contact_preference(&self.run_id, customer_id)? {
+                Some(preference) => preference,
+                None => self.assign_preferences(store, customer_id, tick, rng)?,
+            };
+            let do_not_contact = preference.do_not_contact;
+            if do_not_contact && policy.honor_do_not_contact {
+                suppressed += 1;
+                continue;
+            }
+            let earlier = store.customer_contacts_in_window(&self.run_id, customer_id, window_start, tick)?;
+            if policy.max_contacts_per_window.is_some_and(|cap| earlier >= cap as i64) {
+                throttled += 1;
+                continue;
+            }
+
+            // A customer who asked not to be contacted never responds
+            let on_channel = preference.preferred_channel == campaign.channel;
+            let responded = !do_not_contact
+                && rng.chance(response_probability(&self.config, campaign.response_rate, earlier, on_channel));
+            store.insert_customer_contact(&self.run_id, &CustomerContactRow {
+                tick,
+                customer_id: customer_id.clone(),
+                campaign_type: campaign.campaign_type.into(),
+                campaign_id: campaign.campaign_id.into(),
+                channel: campaign.channel.into(),
+                responded,
+                do_not_contact_violated: do_not_contact,
+            })?;
+            contacted += 1;
+
+            if earlier + 1 > self.config.fatigue_threshold as i64 {
+                store.update_customer_satisfaction(
+                    &self.run_id,
+                    customer_id,
+                    self.config.fatigue_satisfaction_delta,
+                )?;
+            }
+            if do_not_contact {
+                events.push(SimEvent::ContactPreferenceViolated {
+                    tick,
+                    customer_id: customer_id.clone(),
+                    campaign_type: campaign.campaign_type.into(),
+                    campaign_id: campaign.campaign_id.into(),
+                });
+            }
+            if responded {
+                responders.push(customer_id.clone());
+            }
+        }
+
+        tracing::debug!(
+            "{} {}: {contacted} contacted, {suppressed} do-not-contact, {throttled} capped, {} responded",
+            campaign.campaign_type,
+            campaign.campaign_id,
+            responders.len()
+        );
+        events.push(SimEvent::OutreachCampaignRun {
+            tick,
+            campaign_type: campaign.campaign_type.into(),
+            campaign_id: campaign.campaign_id.into(),
+            contacted,
+            suppressed,
+            throttled,
+            responded: responders.len() as i64,
+        });
+        Ok((responders, events))
+    }
+}
@@ -0,0 +1,176 @@
+//! Outreach subsystem — Phase 4.32.
+//!
+//! Records each new customer's contact preferences, and runs the bank's
+//! own outreach campaigns through the contact fatigue model (see
+//! `outreach`):
+//!   - a satisfaction survey to a sample of active customers every
+//!     `survey_interval_ticks`
+//!   - collections calls to overdrawn customers every
+//!     `collections_interval_ticks`
+//!
+//! Offer launches (OfferSubsystem) go through the same model.
+//!
+//! Player commands:
+//!   - SetContactPolicy { honor_do_not_contact, max_contacts_per_window }
+//!     — the suppression policy every campaign runs under. Overriding
+//!     do-not-contact reaches more customers but each such contact is a
+//!     ContactPreferenceViolated event, which can draw a complaint.
+//!
+//! Execution: every tick, after Customer (sees CustomerOnboarded) and
+//!   before Offer (a policy set this tick applies to this tick's launches).
+
+use crate::{
+    command::PlayerCommand,
+    config::ContactFatigueConfig,
+    error::SimResult,
+    event::SimEvent,
+    outreach::{Campaign, Outreach},
+    rng::SubsystemRng,
+    store::{contact::ContactPolicy, SimStore},
+    subsystem::{SimSubsystem, SubsystemManifest},
+    types::{RunId, Tick},
+};
+use std::collections::BTreeSet;
+
+pub struct OutreachSubsystem {
+    run_id:   RunId,
+    config:   ContactFatigueConfig,
+    outreach: Outreach,
+    store:    SimStore,
+}
+
+impl OutreachSubsystem {
+    pub fn new(run_id: RunId, config: ContactFatigueConfig, store: SimStore) -> Self {
+        let outreach = Outreach::new(run_id.clone(), config.clone());
+        Self { run_id, config, outreach, store }
+    }
+
+    fn set_policy(&self, policy: ContactPolicy, tick: Tick) -> SimResult<SimEvent> {
+        if policy.max_contacts_per_window == Some(0) {
+            tracing::warn!("rejected contact policy {policy:?}: cap must allow a contact");
+            return Ok(SimEvent::ContactPolicyRejected {
+                tick,
+                reason: "max contacts per window must be at least 1".into(),
+            });
+        }
+        self.store.insert_contact_policy(&self.run_id, tick, &policy)?;
+        tracing::info!("contact policy set: {policy:?}");
+        Ok(SimEvent::ContactPolicySet {
+            tick,
+            honor_do_not_contact: policy.honor_do_not_contact,
+            max_contacts_per_window: policy.max_contacts_per_window,
+        })
+    }
+
+    /// Satisfaction survey to a random sample of active customers.
+    fn run_survey(&self, tick: Tick, rng: &mut SubsystemRng) -> SimResult<Vec<SimEvent>> {
+        let sample: Vec<String> = self
+            .store
+            .active_customers(&self.run_id)?
+            .into_iter()
+            .filter(|_| rng.chance(self.config.survey_sample_rate))
+            .map(|c| c.customer_id)
+            .collect();
+        let campaign_id = format!("survey-{tick}");
+        let campaign = Campaign {
+            campaign_type: "survey",
+            campaign_id:   &campaign_id,
+            channel:       "email",
+            response_rate: self.config.survey_response_rate,
+        };
+        let (_, events) = self.outreach.run_campaign(&self.store, &campaign, &sample, tick, rng)?;
+        Ok(events)
+    }
+
+    /// Collections call to every customer with an overdrawn account.
+    fn run_collections(&self, tick: Tick, rng: &mut SubsystemRng) -> SimResult<Vec<SimEvent>> {
+        let overdrawn: BTreeSet<String> = self
+            .store
+            .active_accounts(&self.run_id)?
+            .into_iter()
+            .filter(|a| a.balance < -0.01)
+            .map(|a| a.customer_id)
+            .collect();
+        if overdrawn.is_empty() {
+            return Ok(Vec::new());
+        }
+        let customers: Vec<String> = overdrawn.into_iter().collect();
+        let campaign_id = format!("collections-{tick}");
+        let campaign = Campaign {
+            campaign_type: "collections",
+            campaign_id:   &campaign_id,
+            channel:       "phone",
+            response_rate: self.config.collections_response_rate,
+        };
+        let (_, events) = self.outreach.run_campaign(&self.store, &campaign, &customers, tick, rng)?;
+        Ok(events)
+    }
+}
+
+const MANIFEST: SubsystemManifest = SubsystemManifest {
+    version: 1,
+    produces: &[
+        "contact_policy_rejected", "contact_policy_set", "contact_preference_violated",
+        "outreach_campaign_run",
+    ],
+    consumes: &["customer_onboarded", "player_command_received"],
+    tables: &["contact_policy", "contact_preference", "customer", "customer_contact"],
+    requires: &[],
+};
+
+impl SimSubsystem for OutreachSubsystem {
+    fn name(&self) -> &'static str {
+        "outreach"
+    }
+
+    fn manifest(&self) -> SubsystemManifest {
+        MANIFEST
+    }
+
+    fn update(
+        &mut self,
+        tick:      Tick,
+        events_in: &[SimEvent],
+        rng:       &mut SubsystemRng,
+    ) -> SimResult<Vec<SimEvent>> {
+        let mut out = Vec::new();
+
+        if !self.config.enabled || tick == 0 {
+            return Ok(out);
+        }
+
+        // 1. Preferences for new customers and policy changes.
+        for event in events_in {
+            match event {
+                SimEvent::CustomerOnboarded { customer_id, .. } => {
+                    self.outreach.assign_preferences(&self.store, customer_id, tick, rng)?;
+                }
+                SimEvent::PlayerCommandReceived { command_id, .. } => {
+                    if let Some(PlayerCommand::SetContactPolicy {
+                        honor_do_not_contact,
+                        max_contacts_per_window,
+                    }) = self.store.get_player_command(&self.run_id, command_id)?
+                    {
+                        let policy = ContactPolicy { honor_do_not_contact, max_contacts_per_window };
+                        out.push(self.set_policy(policy, tick)?);
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        // 2. The bank's own campaigns.
+        if tick.is_multiple_of(self.config.survey_interval_ticks) {
+            out.extend(self.run_survey(tick, rng)?);
+        }
+        if tick.is_multiple_of(self.config.collections_interval_ticks) {
+            out.extend(self.run_collections(tick, rng)?);
+        }
+
+        Ok(out)
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+}
@@ -4,32 +4,104 @@
 //! Execution order: After TransactionSubsystem, before ComplaintSubsystem.
 //! Reads transactions already inserted by TransactionSubsystem and manages
 //! the settlement lifecycle.
+//!
+//! Phase 4.8 — processing windows: the core books each day's non-card
+//! items to the ledger. On rails with a `cutoff_time_tick` each outbound
+//! customer payment gets an initiation hour; payments initiated at or after
+//! the cutoff miss the day's window and are settled (and reported by the rail) on the next
+//! processing day, so the statement lags the ledger by one day.
+//!
+//! Phase 4.9 — ACH returns: settled ACH items are returned with a NACHA
+//! reason code drawn from the configured taxonomy. The item is reversed and
+//! the code's handling applied: R01/R09 are re-presented once, account
+//! problems (R02/R03/R04/R08) log a customer contact, unauthorized claims
+//! (R05/R10/R29) open an ACH dispute the originator may contest, and
+//! R07/R16 close the originator relationship so later entries from it are
+//! returned automatically. Reversals and re-presentments are booked as
+//! ACH transactions with matching ledger entries.
+//!
+//! Phase 4.10 — the bank as ODFI: entries customers originate (bill pay,
+//! external transfers) come back R10 when unauthorized and otherwise at the
+//! configured administrative / NSF rates. Each review interval the
+//! unauthorized, administrative and overall return rates of originated
+//! debit entries are checked against the NACHA thresholds; a breach puts
+//! the bank under network scrutiny and draws a fine.
+//!
+//! Phase 4.41 — banking days: rails with a cutoff or batch latency (ACH,
+//! wire) settle, roll and return only on business days. Payments made on
+//! a weekend or holiday all roll to the next business day, a roll past
+//! Friday's cutoff lands on Monday, and cutoffs are read in the bank's
+//! own time zone. RTP runs every day.
+//!
+//! Phase 4.93 — merchants: each card purchase is authorized at one of the
+//! bank's merchants, whose name and category the authorization carries;
+//! merchants may go bad as the day opens. See `merchant`.
 
 use crate::{
-    config::PaymentHubConfig,
+    calendar::BusinessCalendar,
+    config::{
+        AchOriginationConfig, AchReturnCode, AchReturnConfig, BusinessCalendarConfig,
+        CorrespondentBankingConfig, MerchantConfig, PaymentCutoffConfig, PaymentHubConfig, PaymentRailConfig,
+    },
+    correspondent::Correspondent,
     error::SimResult,
     event::SimEvent,
+    merchant::Merchants,
     rng::SubsystemRng,
     store::{
+        ach_return::{AchDisputeRow, AchReturnCandidate, AchReturnRow},
+        originated_ach::OdfiReturnReviewRow,
+        payment_cutoff::CutoffRollRow,
         AuthorizationRow, ExternalStatementRow, PaymentBatchRow, SimStore,
     },
-    subsystem::SimSubsystem,
+    subsystem::{SimSubsystem, SubsystemManifest},
     types::{RunId, Tick},
 };
 
+/// Phase 4.8: customer-initiated outbound payment categories subject to
+/// rail cutoffs. Fees, cash, deposits and system postings never roll.
+const ROLLABLE_CATEGORIES: &[&str] = &["purchase", "bill_pay", "external_transfer_out"];
+
+/// Phase 4.10: NACHA administrative return codes.
+const ADMINISTRATIVE_RETURN_CODES: &[&str] = &["R02", "R03", "R04"];
+
 pub struct PaymentHubSubsystem {
     run_id: RunId,
     config: PaymentHubConfig,
+    cutoff: PaymentCutoffConfig,
+    returns: AchReturnConfig,
+    origination: AchOriginationConfig,
+    correspondent: Correspondent,
+    calendar: BusinessCalendar,
+    /// Phase 4.93: the merchants card purchases are made at.
+    merchants: Merchants,
     store: SimStore,
 }
 
 impl PaymentHubSubsystem {
-    pub fn new(run_id: RunId, config: PaymentHubConfig, store: SimStore) -> Self {
-        Self {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        run_id: RunId,
+        config: PaymentHubConfig,
+        cutoff: PaymentCutoffConfig,
+        returns: AchReturnConfig,
+        origination: AchOriginationConfig,
+        correspondent: CorrespondentBankingConfig,
+        calendar: BusinessCalendarConfig,
+        merchants: MerchantConfig,
+        store: SimStore,
+    ) -> SimResult<Self> {
+        Ok(Self {
+            merchants: Merchants::new(run_id.clone(), merchants),
+            correspondent: Correspondent::new(run_id.clone(), correspondent),
+            calendar: BusinessCalendar::new(&calendar)?,
             run_id,
             config,
+            cutoff,
+            returns,
+            origination,
             store,
-        }
+        })
     }
 
     /// Process card authorizations for transactions created this tick.
@@ -42,6 +114,15 @@ impl PaymentHubSubsystem {
     ) -> SimResult<Vec<SimEvent>> {
         let mut events = Vec::new();
 
+        // Phase 4.93: today's merchants
+        let merchants = if self.merchants.enabled() {
+            let (merchants, opened) = self.merchants.open_day(&self.store, tick, rng)?;
+            events.extend(opened);
+            merchants
+        } else {
+            Vec::new()
+        };
+
         // Get card transactions created this tick that need authorization
         let card_txns = self.store.get_card_transactions_at_tick(&self.run_id, tick)?;
 
@@ -63,14 +144,19 @@ impl PaymentHubSubsystem {
             let auth_id = format!("auth-{tick}-{auth_seq}");
 
             // Derive merchant name deterministically from counterparty-like data
-            let merchant_slot = rng.next_u64_below(100);
-            let merchant_name = format!("merchant-{merchant_slot}");
+            let (merchant_id, merchant_name, merchant_category) = match self.merchants.pick(&merchants, rng) {
+                Some(m) => (Some(m.merchant_id.clone()), m.name.clone(), m.category.clone()),
+                None => {
+                    let merchant_slot = rng.next_u64_below(100);
+                    (None, format!("merchant-{merchant_slot}"), txn.category.clone())
+                }
+            };
 
             let auth = AuthorizationRow {
                 authorization_id: auth_id.clone(),
                 account_id: txn.account_id.clone(),
                 merchant_name: Some(merchant_name.clone()),
-                merchant_category: Some(txn.category.clone()),
+                merchant_category: Some(merchant_category),
                 amount: txn.amount,
                 tick_authorized: tick,
                 status: "pending".into(),
@@ -78,6 +164,7 @@ impl PaymentHubSubsystem {
                 cleared_amount: None,
                 tick_settled: None,
                 interchange_fee: None,
+                merchant_id,
             };
 
             self.store.insert_authorization(&self.run_id, &auth)?;
@@ -253,6 +340,32 @@ impl PaymentHubSubsystem {
         Ok(events)
     }
 
+    /// Phase 4.41: initiation ticks whose items a rail settles (or returns)
+    /// `delay` ticks later, on this tick. Banking-day rails pick up nothing
+    /// on a closure and the closure's items on the next business day.
+    fn origin_ticks(&self, rail: &PaymentRailConfig, tick: Tick, delay: Tick) -> Vec<Tick> {
+        if rail.latency_type == "batch" || rail.cutoff_time_tick.is_some() {
+            self.calendar.due_from(tick, delay)
+        } else {
+            vec![tick.saturating_sub(delay)]
+        }
+    }
+
+    /// The ACH rail's config; returns and re-presentments run on its days.
+    fn ach_rail(&self) -> Option<&PaymentRailConfig> {
+        self.config.rails.iter().find(|r| r.rail_id == "ACH")
+    }
+
+    /// Initiation ticks of ACH entries coming back `delay` ticks later.
+    fn return_origin_ticks(&self, tick: Tick, delay: Tick) -> Vec<Tick> {
+        let mut origins = match self.ach_rail() {
+            Some(rail) => self.origin_ticks(rail, tick, delay),
+            None => vec![tick - delay],
+        };
+        origins.retain(|t| *t > 0);
+        origins
+    }
+
     /// Run settlement for non-card rails (ACH, wire, RTP).
     /// ACH: settles transactions from tick - 1 (T+1)
     /// Wire/RTP: settles transactions from this tick (T+0)
@@ -268,12 +381,14 @@ impl PaymentHubSubsystem {
                 continue; // Card is handled separately
             }
 
-            let settlement_tick = tick.saturating_sub(rail.settlement_delay_ticks);
-            let txns = self.store.get_transactions_for_settlement(
-                &self.run_id,
-                &rail.rail_id,
-                settlement_tick,
-            )?;
+            let mut txns = Vec::new();
+            for settlement_tick in self.origin_ticks(rail, tick, rail.settlement_delay_ticks) {
+                txns.extend(self.store.get_transactions_for_settlement(
+                    &self.run_id,
+                    &rail.rail_id,
+                    settlement_tick,
+                )?);
+            }
 
             if txns.is_empty() {
                 continue;
@@ -351,6 +466,505 @@ impl PaymentHubSubsystem {
         Ok(events)
     }
 
+    /// Run the day's processing window for non-card rails (Phase 4.8).
+    /// Settles items rolled in from yesterday, books today's initiations to
+    /// the ledger, and rolls items initiated after the rail's cutoff.
+    fn run_processing_window(
+        &self,
+        tick: Tick,
+        rng: &mut SubsystemRng,
+    ) -> SimResult<Vec<SimEvent>> {
+        let mut events = Vec::new();
+
+        for rail in &self.config.rails {
+            if rail.rail_type == "card" {
+                continue; // Card has its own clearing and settlement batches
+            }
+
+            for txn in self.store.transactions_rolled_into(&self.run_id, &rail.rail_id, tick)? {
+                self.store.mark_transaction_settled(&self.run_id, &txn.txn_id)?;
+            }
+
+            let txns = self
+                .store
+                .transactions_initiated_on_rail(&self.run_id, &rail.rail_id, tick)?;
+            if txns.is_empty() {
+                continue;
+            }
+
+            // The core books every item on its initiation day
+            let booked: f64 = txns.iter().map(|t| t.amount).sum();
+            self.store.insert_ledger_entry(
+                &self.run_id,
+                &format!("le-{}-{tick}-booked", rail.rail_id),
+                &rail.rail_id,
+                tick,
+                booked,
+                "debit",
+                None,
+                None,
+            )?;
+
+            let Some(cutoff) = rail.cutoff_time_tick else {
+                continue;
+            };
+            let cutoff = self.calendar.local_cutoff_hour(cutoff);
+            let processing_tick = self.calendar.add_business_days(tick, 1);
+            for txn in &txns {
+                // Only customer-initiated outbound payments wait for a window
+                if txn.direction != "debit"
+                    || !ROLLABLE_CATEGORIES.contains(&txn.category.as_str())
+                {
+                    continue;
+                }
+                // Nothing makes a window on a weekend or holiday
+                let hour = self.initiation_hour(rng);
+                if hour < cutoff && self.calendar.is_business_day(tick) {
+                    continue;
+                }
+                self.store.insert_cutoff_roll(&self.run_id, &CutoffRollRow {
+                    txn_id: txn.txn_id.clone(),
+                    rail_id: rail.rail_id.clone(),
+                    account_id: txn.account_id.clone(),
+                    amount: txn.amount,
+                    direction: txn.direction.clone(),
+                    initiated_hour: hour,
+                    booked_tick: tick,
+                    processing_tick,
+                })?;
+                self.store.update_transaction_settlement_status(
+                    &self.run_id,
+                    &txn.txn_id,
+                    "rolled",
+                )?;
+                events.push(SimEvent::PaymentRolledPastCutoff {
+                    tick,
+                    txn_id: txn.txn_id.clone(),
+                    account_id: txn.account_id.clone(),
+                    rail_id: rail.rail_id.clone(),
+                    amount: txn.amount,
+                    processing_tick,
+                });
+            }
+        }
+
+        Ok(events)
+    }
+
+    /// Hour of day a payment was initiated, uniform over the initiation window.
+    fn initiation_hour(&self, rng: &mut SubsystemRng) -> u64 {
+        let start = self.cutoff.initiation_start_hour;
+        let end = self.cutoff.initiation_end_hour.max(start + 1);
+        start + rng.next_u64_below(end - start)
+    }
+
+    /// Draw a return reason code by configured weight. Items with no
+    /// originator on record cannot carry a code that closes the relationship.
+    fn pick_return_code(&self, has_originator: bool, rng: &mut SubsystemRng) -> Option<&AchReturnCode> {
+        let eligible: Vec<&AchReturnCode> = self
+            .returns
+            .codes
+            .iter()
+            .filter(|c| has_originator || c.handling != "close_origination")
+            .collect();
+        let total: f64 = eligible.iter().map(|c| c.weight).sum();
+        if total <= 0.0 {
+            return None;
+        }
+        let mut roll = rng.next_f64() * total;
+        for code in &eligible {
+            if roll < code.weight {
+                return Some(code);
+            }
+            roll -= code.weight;
+        }
+        eligible.last().copied()
+    }
+
+    /// Book an ACH entry against the customer's account: a settled
+    /// transaction, its ledger entry and the balance change.
+    #[allow(clippy::too_many_arguments)]
+    fn book_ach_entry(
+        &self,
+        txn_id: &str,
+        account_id: &str,
+        amount: f64,
+        direction: &str,
+        category: &str,
+        counterparty: Option<&str>,
+        tick: Tick,
+    ) -> SimResult<()> {
+        self.store.insert_transaction_with_rail(
+            &self.run_id,
+            txn_id,
+            account_id,
+            tick,
+            amount,
+            direction,
+            category,
+            counterparty,
+            "ACH",
+            "settled",
+        )?;
+        self.store.insert_ledger_entry(
+            &self.run_id,
+            &format!("le-{txn_id}"),
+            "ACH",
+            tick,
+            amount,
+            direction,
+            Some(txn_id),
+            None,
+        )?;
+        let sign = if direction == "debit" { -1.0 } else { 1.0 };
+        self.store
+            .update_account_balance(&self.run_id, account_id, sign * amount)
+    }
+
+    /// Re-present R01/R09 returns whose retry date has arrived.
+    fn process_represents(
+        &self,
+        tick: Tick,
+        rng: &mut SubsystemRng,
+    ) -> SimResult<Vec<SimEvent>> {
+        let mut events = Vec::new();
+        for ret in self.store.ach_returns_due_for_represent(&self.run_id, tick)? {
+            let collected = rng.chance(self.returns.represent_success_rate);
+            let status = if collected {
+                self.book_ach_entry(
+                    &format!("{}-represent", ret.return_id),
+                    &ret.account_id,
+                    ret.amount,
+                    &ret.direction,
+                    "ach_represent",
+                    ret.counterparty.as_deref(),
+                    tick,
+                )?;
+                "represented"
+            } else {
+                "final_return"
+            };
+            self.store
+                .resolve_ach_return(&self.run_id, &ret.return_id, status, tick)?;
+            events.push(SimEvent::AchRepresentmentResolved {
+                tick,
+                return_id: ret.return_id,
+                collected,
+            });
+        }
+        Ok(events)
+    }
+
+    /// Close unauthorized-return disputes whose review window has ended. A
+    /// contested dispute means the originator produced proof of
+    /// authorization, and the debit is reinstated.
+    fn process_ach_disputes(
+        &self,
+        tick: Tick,
+        rng: &mut SubsystemRng,
+    ) -> SimResult<Vec<SimEvent>> {
+        let mut events = Vec::new();
+        let filed_by = tick.saturating_sub(self.returns.dispute_review_ticks);
+        for dispute in self.store.open_ach_disputes_filed_by(&self.run_id, filed_by)? {
+            let contested = rng.chance(self.returns.originator_contest_rate);
+            let outcome = if contested {
+                self.book_ach_entry(
+                    &format!("{}-reinstated", dispute.dispute_id),
+                    &dispute.account_id,
+                    dispute.amount,
+                    "debit",
+                    "ach_dispute_reinstated",
+                    None,
+                    tick,
+                )?;
+                "originator_upheld"
+            } else {
+                "customer_upheld"
+            };
+            self.store
+                .resolve_ach_dispute(&self.run_id, &dispute.dispute_id, outcome, tick)?;
+            events.push(SimEvent::AchDisputeResolved {
+                tick,
+                dispute_id: dispute.dispute_id,
+                outcome: outcome.into(),
+            });
+        }
+        Ok(events)
+    }
+
+    /// Return ACH items initiated `return_delay_ticks` ago and apply the
+    /// handling their reason code mandates.
+    fn process_ach_returns(
+        &self,
+        tick: Tick,
+        rng: &mut SubsystemRng,
+    ) -> SimResult<Vec<SimEvent>> {
+        let mut events = self.process_represents(tick, rng)?;
+        events.extend(self.process_ach_disputes(tick, rng)?);
+        if tick <= self.returns.return_delay_ticks {
+            return Ok(events);
+        }
+
+        let mut candidates = Vec::new();
+        for initiated in self.return_origin_ticks(tick, self.returns.return_delay_ticks) {
+            candidates.extend(self.store.ach_return_candidates(&self.run_id, initiated)?);
+        }
+        for (seq, item) in candidates.iter().enumerate() {
+            // Entries from a closed originator are returned without a draw
+            let blocked = match item.counterparty.as_deref() {
+                Some(cp) => self.store.ach_origination_block(&self.run_id, &item.account_id, cp)?,
+                None => None,
+            };
+            let code = match blocked {
+                Some(code) => self.returns.codes.iter().find(|c| c.code == code),
+                None if rng.chance(self.returns.return_rate) => {
+                    self.pick_return_code(item.counterparty.is_some(), rng)
+                }
+                None => None,
+            };
+            let Some(code) = code.cloned() else {
+                continue;
+            };
+
+            let return_id = format!("achr-{tick}-{seq}");
+            events.extend(self.apply_ach_return(tick, &return_id, item, &code, rng)?);
+        }
+        Ok(events)
+    }
+
+    fn apply_ach_return(
+        &self,
+        tick: Tick,
+        return_id: &str,
+        item: &AchReturnCandidate,
+        code: &AchReturnCode,
+        rng: &mut SubsystemRng,
+    ) -> SimResult<Vec<SimEvent>> {
+        // The return reverses the original entry
+        let reversal = if item.direction == "debit" { "credit" } else { "debit" };
+        self.book_ach_entry(
+            &format!("{return_id}-reversal"),
+            &item.account_id,
+            item.amount,
+            reversal,
+            "ach_return",
+            item.counterparty.as_deref(),
+            tick,
+        )?;
+        self.store
+            .update_transaction_settlement_status(&self.run_id, &item.txn_id, "returned")?;
+
+        let mut represent_tick = None;
+        let status = match code.handling.as_str() {
+            "represent" => {
+                represent_tick =
+                    Some(self.calendar.add_business_days(tick, self.returns.represent_delay_ticks));
+                "pending_represent"
+            }
+            "customer_contact" => {
+                let customer_id = self
+                    .store
+                    .get_account_customer_id(&self.run_id, &item.account_id)?;
+                let delta = self.returns.contact_satisfaction_delta;
+                self.store.insert_interaction(
+                    &self.run_id,
+                    &format!("int-{:016x}", rng.next_u64()),
+                    &customer_id,
+                    tick,
+                    "phone",
+                    "ach_return_contact",
+                    None,
+                    Some(&code.code),
+                    delta,
+                )?;
+                self.store
+                    .update_customer_satisfaction(&self.run_id, &customer_id, delta)?;
+                "contact_logged"
+            }
+            "dispute" => {
+                let customer_id = self
+                    .store
+                    .get_account_customer_id(&self.run_id, &item.account_id)?;
+                self.store.insert_ach_dispute(&self.run_id, &AchDisputeRow {
+                    dispute_id: format!("{return_id}-dispute"),
+                    return_id: return_id.to_string(),
+                    account_id: item.account_id.clone(),
+                    customer_id,
+                    amount: item.amount,
+                    return_code: code.code.clone(),
+                    tick_filed: tick,
+                    status: "open".into(),
+                })?;
+                "disputed"
+            }
+            "close_origination" => {
+                // Only drawn for items with an originator on record
+                let cp = item.counterparty.as_deref().unwrap_or_default();
+                self.store
+                    .block_ach_origination(&self.run_id, &item.account_id, cp, &code.code, tick)?;
+                "origination_closed"
+            }
+            _ => "returned",
+        };
+
+        self.store.insert_ach_return(&self.run_id, &AchReturnRow {
+            return_id: return_id.to_string(),
+            txn_id: item.txn_id.clone(),
+            account_id: item.account_id.clone(),
+            counterparty: item.counterparty.clone(),
+            amount: item.amount,
+            direction: item.direction.clone(),
+            return_code: code.code.clone(),
+            handling: code.handling.clone(),
+            tick_returned: tick,
+            status: status.into(),
+            represent_tick,
+        })?;
+
+        Ok(vec![SimEvent::AchReturnReceived {
+            tick,
+            return_id: return_id.to_string(),
+            txn_id: item.txn_id.clone(),
+            account_id: item.account_id.clone(),
+            return_code: code.code.clone(),
+            handling: code.handling.clone(),
+            amount: item.amount,
+        }])
+    }
+
+    /// Phase 4.10: returns on entries customers originated
+    /// `return_delay_ticks` ago. Takeover entries always come back R10; the
+    /// rest draw administrative, NSF and unauthorized returns.
+    fn process_originated_returns(
+        &self,
+        tick: Tick,
+        rng: &mut SubsystemRng,
+    ) -> SimResult<Vec<SimEvent>> {
+        let mut events = Vec::new();
+        if tick <= self.origination.return_delay_ticks {
+            return Ok(events);
+        }
+
+        let mut entries = Vec::new();
+        for initiated in self.return_origin_ticks(tick, self.origination.return_delay_ticks) {
+            entries.extend(self.store.submitted_originated_ach(&self.run_id, initiated)?);
+        }
+        for entry in entries {
+            let is_debit_entry = entry.entry_type == "external_transfer";
+            let code = if entry.unauthorized && is_debit_entry {
+                Some("R10")
+            } else if entry.unauthorized {
+                // Credits pushed to a mule account are not coming back
+                None
+            } else if rng.chance(self.origination.administrative_return_rate) {
+                let slot = rng.next_u64_below(ADMINISTRATIVE_RETURN_CODES.len() as u64);
+                Some(ADMINISTRATIVE_RETURN_CODES[slot as usize])
+            } else if is_debit_entry && rng.chance(self.origination.nsf_return_rate) {
+                Some("R01")
+            } else if is_debit_entry && rng.chance(self.origination.unauthorized_return_rate) {
+                Some("R10")
+            } else {
+                None
+            };
+            let Some(code) = code else {
+                continue;
+            };
+
+            // Bill pay funds come back to the customer; a returned pull
+            // takes back funds the customer may already have spent.
+            let reversal = if is_debit_entry { "debit" } else { "credit" };
+            self.book_ach_entry(
+                &format!("{}-return", entry.entry_id),
+                &entry.account_id,
+                entry.amount,
+                reversal,
+                "ach_return",
+                Some(&entry.counterparty),
+                tick,
+            )?;
+            self.store
+                .update_transaction_settlement_status(&self.run_id, &entry.entry_id, "returned")?;
+            self.store
+                .mark_originated_ach_returned(&self.run_id, &entry.entry_id, code, tick)?;
+            events.push(SimEvent::OriginatedAchReturned {
+                tick,
+                entry_id: entry.entry_id,
+                account_id: entry.account_id,
+                return_code: code.into(),
+                amount: entry.amount,
+            });
+        }
+        Ok(events)
+    }
+
+    /// Phase 4.10: ODFI return-rate review. Covers debit entries old enough
+    /// for their returns to have arrived.
+    fn review_odfi_return_rates(&self, tick: Tick) -> SimResult<Vec<SimEvent>> {
+        let mut events = Vec::new();
+        if self.origination.review_interval_ticks == 0
+            || !tick.is_multiple_of(self.origination.review_interval_ticks)
+            || tick <= self.origination.return_delay_ticks
+        {
+            return Ok(events);
+        }
+
+        let end = tick - self.origination.return_delay_ticks + 1;
+        let start = end.saturating_sub(self.origination.review_lookback_ticks);
+        let counts = self.store.odfi_return_counts(&self.run_id, start, end)?;
+        if counts.debit_entries == 0 {
+            return Ok(events);
+        }
+
+        let entries = counts.debit_entries as f64;
+        let unauthorized_rate = counts.unauthorized as f64 / entries;
+        let administrative_rate = counts.administrative as f64 / entries;
+        let overall_rate = counts.returned as f64 / entries;
+
+        let mut breached = Vec::new();
+        if unauthorized_rate > self.origination.unauthorized_threshold {
+            breached.push("unauthorized");
+        }
+        if administrative_rate > self.origination.administrative_threshold {
+            breached.push("administrative");
+        }
+        if overall_rate > self.origination.overall_threshold {
+            breached.push("overall");
+        }
+        let network_fine = if breached.is_empty() {
+            0.0
+        } else {
+            self.origination.network_fine
+        };
+
+        self.store.insert_odfi_return_review(&self.run_id, &OdfiReturnReviewRow {
+            tick,
+            debit_entries: counts.debit_entries,
+            unauthorized_rate,
+            administrative_rate,
+            overall_rate,
+            breached: (!breached.is_empty()).then(|| breached.join(",")),
+            network_fine,
+        })?;
+
+        if !breached.is_empty() {
+            tracing::warn!(
+                "ODFI return rates over threshold ({}), \
+                 unauthorized {:.2}% — network scrutiny, fine ${network_fine:.0}",
+                breached.join(", "),
+                unauthorized_rate * 100.0
+            );
+            events.push(SimEvent::OdfiReturnRateBreached {
+                tick,
+                breached: breached.join(","),
+                unauthorized_rate,
+                administrative_rate,
+                overall_rate,
+                network_fine,
+            });
+        }
+        Ok(events)
+    }
+
     /// Generate external settlement statements for each rail.
     /// These represent what an external clearinghouse/network would report.
     fn generate_external_statements(
@@ -361,10 +975,20 @@ impl PaymentHubSubsystem {
 
         for rail in &self.config.rails {
             // Get settlement totals for this rail at this tick
-            let (total_debits, total_credits, item_count) =
+            let (mut total_debits, mut total_credits, mut item_count) =
                 self.store
                     .settlement_totals_for_tick(&self.run_id, &rail.rail_id, tick)?;
 
+            // Phase 4.8: the rail reports rolled items on the day it settles them
+            if self.cutoff.enabled {
+                let (debits, credits, count) = self
+                    .store
+                    .rolled_settlement_totals(&self.run_id, &rail.rail_id, tick)?;
+                total_debits += debits;
+                total_credits += credits;
+                item_count += count;
+            }
+
             // Only create statement if there's activity
             if item_count > 0 {
                 let stmt_id = format!("stmt-{}-{tick}", rail.rail_id);
@@ -384,11 +1008,35 @@ impl PaymentHubSubsystem {
     }
 }
 
+const MANIFEST: SubsystemManifest = SubsystemManifest {
+    version: 2,
+    produces: &[
+        "ach_dispute_resolved", "ach_representment_resolved", "ach_return_received",
+        "card_authorization_created", "card_settled", "correspondent_due_diligence_completed",
+        "correspondent_due_diligence_requested", "correspondent_relationship_suspended",
+        "correspondent_wires_cleared", "odfi_return_rate_breached", "originated_ach_returned",
+        "merchant_turned_bad", "payment_batch_created", "payment_batch_settled",
+        "payment_rolled_past_cutoff",
+    ],
+    consumes: &[],
+    tables: &[
+        "account", "ach_dispute", "ach_origination_block", "ach_return", "authorization",
+        "correspondent_dd_review", "correspondent_relationship", "correspondent_wire", "customer",
+        "external_statement", "interaction", "ledger_entry", "merchant", "odfi_return_review", "originated_ach",
+        "payment_batch", "payment_cutoff_roll", "transactions",
+    ],
+    requires: &[],
+};
+
 impl SimSubsystem for PaymentHubSubsystem {
     fn name(&self) -> &'static str {
         "payment_hub"
     }
 
+    fn manifest(&self) -> SubsystemManifest {
+        MANIFEST
+    }
+
     fn update(
         &mut self,
         tick: Tick,
@@ -414,11 +1062,32 @@ impl SimSubsystem for PaymentHubSubsystem {
         // 4. Run non-card rail settlement (ACH T+1, wire/RTP T+0)
         out_events.extend(self.run_non_card_settlement(tick, rng)?);
 
+        // 4b. Processing window: book non-card items, roll those past cutoff
+        if self.cutoff.enabled {
+            out_events.extend(self.run_processing_window(tick, rng)?);
+        }
+
+        // 4c. ACH returns and re-presentments
+        if self.returns.enabled {
+            out_events.extend(self.process_ach_returns(tick, rng)?);
+        }
+
+        // 4d. Returns on customer-originated entries, ODFI return-rate review
+        if self.origination.enabled {
+            out_events.extend(self.process_originated_returns(tick, rng)?);
+            out_events.extend(self.review_odfi_return_rates(tick)?);
+        }
+
+        // 4e. Respondent bank wires and their due diligence (Phase 4.39)
+        if self.correspondent.enabled() {
+            out_events.extend(self.correspondent.process(&self.store, tick, rng)?);
+        }
+
         // 5. Generate external statements for reconciliation
         out_events.extend(self.generate_external_statements(tick)?);
 
-        log::debug!(
-            "tick={tick} payment_hub: {} events",
+        tracing::debug!(
+            "{} events",
             out_events.len()
         );
 
@@ -0,0 +1,303 @@
+//! Post-run analysis report (Phase 4.19).
+//!
+//! At run end the ground truth is revealed: every fraud case the
+//! simulation ran, with its timeline, what it cost, and when (if ever)
+//! monitoring first named the customer. The report adds the detection
+//! latency distribution and a counterfactual under perfect detection,
+//! where every case is caught on its first day and nothing after that
+//! is lost. The coverage assessment's ground truth (Phase 4.18) rides
+//! along.
+//!
+//! Phase 4.88: with structurers labeled, STRUCT_9K is scored against
+//! them: precision is the share of customers it alerted on who were
+//! structuring, recall the share of structurers it alerted on.
+//!
+//! Cases come from the simulation's own records, not from alerts:
+//!   - account_takeover: unauthorized pulls on a taken-over account
+//!   - mule_drain:       unauthorized transfers out to a verified mule link
+//!   - synthetic_identity: customers onboarded on a synthetic identity
+
+use crate::{
+    error::SimResult,
+    store::{post_mortem::UnauthorizedEntryRow, SimStore},
+    types::{RunId, Tick},
+};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+
+/// Latency bucket upper bounds, in ticks, and their labels.
+const LATENCY_BUCKETS: &[(Tick, &str)] =
+    &[(0, "same day"), (7, "1-7 days"), (30, "8-30 days"), (Tick::MAX, "31+ days")];
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PostMortem {
+    pub run_id: RunId,
+    pub final_tick: Tick,
+    pub cases: Vec<FraudCase>,
+    pub latency: LatencyDistribution,
+    pub counterfactual: Counterfactual,
+    pub coverage: Vec<TypologyCoverage>,
+    /// Phase 4.88: rules scored against typologies with labeled ground
+    /// truth.
+    #[serde(default)]
+    pub detection: Vec<DetectionScore>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FraudCase {
+    pub case_id: String,
+    /// "account_takeover" | "mule_drain" | "synthetic_identity"
+    pub typology: String,
+    pub customer_id: String,
+    pub first_tick: Tick,
+    pub last_tick: Tick,
+    pub timeline: Vec<TimelineEntry>,
+    /// Funds the fraudster moved: entries not declined.
+    pub loss: f64,
+    /// Loss after the first day, which detection on sight would have stopped.
+    pub avoidable_loss: f64,
+    pub detected_tick: Option<Tick>,
+    pub latency: Option<Tick>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TimelineEntry {
+    pub tick: Tick,
+    pub description: String,
+    pub amount: Option<f64>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct LatencyDistribution {
+    pub detected: usize,
+    pub undetected: usize,
+    pub mean: Option<f64>,
+    pub p50: Option<Tick>,
+    pub p90: Option<Tick>,
+    pub max: Option<Tick>,
+    pub buckets: Vec<LatencyBucket>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LatencyBucket {
+    pub label: String,
+    pub count: usize,
+}
+
+/// Actual outcomes against perfect detection.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Counterfactual {
+    pub actual_loss: f64,
+    pub perfect_detection_loss: f64,
+    pub avoidable_loss: f64,
+    /// Loss on cases monitoring never caught.
+    pub undetected_loss: f64,
+    pub detection_rate: Option<f64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TypologyCoverage {
+    pub typology: String,
+    pub rules: String,
+    pub covered: bool,
+    pub occurrences: i64,
+}
+
+/// A rule's alerts, by customer, against a typology's labeled customers.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DetectionScore {
+    pub typology: String,
+    pub rule_id: String,
+    /// Labeled customers who acted out the typology.
+    pub positives: usize,
+    /// Customers the rule alerted on.
+    pub flagged: usize,
+    pub true_positives: usize,
+    pub precision: Option<f64>,
+    pub recall: Option<f64>,
+}
+
+impl DetectionScore {
+    fn score(typology: &str, rule_id: &str, positives: &BTreeSet<String>, flagged: &BTreeSet<String>) -> Self {
+        let true_positives = flagged.intersection(positives).count();
+        Self {
+            typology: typology.into(),
+            rule_id: rule_id.into(),
+            positives: positives.len(),
+            flagged: flagged.len(),
+            true_positives,
+            precision: (!flagged.is_empty()).then(|| true_positives as f64 / flagged.len() as f64),
+            recall: (!positives.is_empty()).then(|| true_positives as f64 / positives.len() as f64),
+        }
+    }
+}
+
+impl PostMortem {
+    pub fn build(store: &SimStore, run_id: &str, final_tick: Tick) -> SimResult<Self> {
+        let mut cases = Vec::new();
+
+        // Takeover pulls and mule drains, one case per customer and typology
+        let mut entries: BTreeMap<(String, &'static str), Vec<UnauthorizedEntryRow>> = BTreeMap::new();
+        for entry in store.unauthorized_ach_entries(run_id)? {
+            let typology = if entry.entry_type == "external_transfer_out" {
+                "mule_drain"
+            } else {
+                "account_takeover"
+            };
+            entries.entry((entry.customer_id.clone(), typology)).or_default().push(entry);
+        }
+        for ((customer_id, typology), entries) in entries {
+            cases.push(entry_case(store, run_id, customer_id, typology, &entries)?);
+        }
+
+        // Synthetic identities; one turned away at onboarding was caught there
+        for customer in store.synthetic_identity_customers(run_id)? {
+            let mut timeline = vec![TimelineEntry {
+                tick: customer.open_tick,
+                description: "applied on a synthetic identity".into(),
+                amount: None,
+            }];
+            let detected_tick = if customer.status == "declined" {
+                timeline.push(TimelineEntry {
+                    tick: customer.close_tick.unwrap_or(customer.open_tick),
+                    description: "declined at identity verification".into(),
+                    amount: None,
+                });
+                customer.close_tick.or(Some(customer.open_tick))
+            } else {
+                store.first_detection_tick(run_id, &customer.customer_id, customer.open_tick)?
+            };
+            cases.push(FraudCase {
+                case_id: format!("case-{}-synthetic_identity", customer.customer_id),
+                typology: "synthetic_identity".into(),
+                customer_id: customer.customer_id,
+                first_tick: customer.open_tick,
+                last_tick: customer.close_tick.unwrap_or(final_tick),
+                timeline,
+                loss: 0.0,
+                avoidable_loss: 0.0,
+                detected_tick,
+                latency: detected_tick.map(|t| t - customer.open_tick),
+            });
+        }
+        cases.sort_by(|a, b| (a.first_tick, &a.case_id).cmp(&(b.first_tick, &b.case_id)));
+
+        let coverage = store
+            .latest_coverage_assessment(run_id)?
+            .into_iter()
+            .map(|row| TypologyCoverage {
+                typology: row.typology,
+                rules: row.rules,
+                covered: row.covered,
+                occurrences: row.occurrences,
+            })
+            .collect();
+
+        let mut detection = Vec::new();
+        if !store.structuring_labels(run_id)?.is_empty() {
+            detection.push(DetectionScore::score(
+                "structuring",
+                "STRUCT_9K",
+                &store.active_structurers(run_id)?,
+                &store.alerted_customers_for_rule(run_id, "STRUCT_9K")?,
+            ));
+        }
+
+        Ok(Self {
+            run_id: run_id.to_string(),
+            final_tick,
+            latency: latency_distribution(&cases),
+            counterfactual: counterfactual(&cases),
+            cases,
+            coverage,
+            detection,
+        })
+    }
+
+    pub fn undetected(&self) -> impl Iterator<Item = &FraudCase> {
+        self.cases.iter().filter(|c| c.detected_tick.is_none())
+    }
+}
+
+fn entry_case(
+    store: &SimStore,
+    run_id: &str,
+    customer_id: String,
+    typology: &str,
+    entries: &[UnauthorizedEntryRow],
+) -> SimResult<FraudCase> {
+    let first_tick = entries.iter().map(|e| e.tick).min().unwrap_or(0);
+    let last_tick = entries.iter().map(|e| e.tick).max().unwrap_or(0);
+    let mut loss = 0.0;
+    let mut avoidable_loss = 0.0;
+    let timeline = entries
+        .iter()
+        .map(|e| {
+            let declined = e.status.starts_with("declined");
+            if !declined {
+                loss += e.amount;
+                if e.tick > first_tick {
+                    avoidable_loss += e.amount;
+                }
+            }
+            TimelineEntry {
+                tick: e.tick,
+                description: format!("{} to {} ({})", e.entry_type, e.counterparty, e.status),
+                amount: Some(e.amount),
+            }
+        })
+        .collect();
+    let detected_tick = store.first_detection_tick(run_id, &customer_id, first_tick)?;
+    Ok(FraudCase {
+        case_id: format!("case-{customer_id}-{typology}"),
+        typology: typology.to_string(),
+        customer_id,
+        first_tick,
+        last_tick,
+        timeline,
+        loss,
+        avoidable_loss,
+        detected_tick,
+        latency: detected_tick.map(|t| t - first_tick),
+    })
+}
+
+fn latency_distribution(cases: &[FraudCase]) -> LatencyDistribution {
+    let mut latencies: Vec<Tick> = cases.iter().filter_map(|c| c.latency).collect();
+    latencies.sort_unstable();
+    let percentile = |p: f64| {
+        (!latencies.is_empty()).then(|| latencies[((latencies.len() - 1) as f64 * p).round() as usize])
+    };
+    let mut lower = 0;
+    let buckets = LATENCY_BUCKETS
+        .iter()
+        .map(|&(upper, label)| {
+            let count = latencies.iter().filter(|&&l| l >= lower && l <= upper).count();
+            lower = upper.saturating_add(1);
+            LatencyBucket { label: label.into(), count }
+        })
+        .collect();
+    LatencyDistribution {
+        detected: latencies.len(),
+        undetected: cases.len() - latencies.len(),
+        mean: (!latencies.is_empty())
+            .then(|| latencies.iter().sum::<Tick>() as f64 / latencies.len() as f64),
+        p50: percentile(0.5),
+        p90: percentile(0.9),
+        max: latencies.last().copied(),
+        buckets,
+    }
+}
+
+fn counterfactual(cases: &[FraudCase]) -> Counterfactual {
+    let actual_loss: f64 = cases.iter().map(|c| c.loss).sum();
+    let avoidable_loss: f64 = cases.iter().map(|c| c.avoidable_loss).sum();
+    let detected = cases.iter().filter(|c| c.detected_tick.is_some()).count();
+    Counterfactual {
+        actual_loss,
+        perfect_detection_loss: actual_loss - avoidable_loss,
+        avoidable_loss,
+        undetected_loss: cases.iter().filter(|c| c.detected_tick.is_none()).map(|c| c.loss).sum(),
+        detection_rate: (!cases.is_empty()).then(|| detected as f64 / cases.len() as f64),
+    }
+}
@@ -3,6 +3,10 @@
 //!
 //! This subsystem owns the product_state table and processes
 //! all fee change commands from the player.
+//!
+//! Phase 4.81: while a consent order's fee cap is in force, every fee is
+//! held at its soft limit: fees above it are lowered, and changes that
+//! would raise one past it are rejected.
 
 use crate::{
     command::PlayerCommand,
@@ -11,11 +15,14 @@ use crate::{
     event::SimEvent,
     rng::SubsystemRng,
     store::SimStore,
-    subsystem::SimSubsystem,
+    subsystem::{SimSubsystem, SubsystemManifest},
     types::{RunId, Tick},
 };
 use std::collections::HashMap;
 
+/// Fees a `SetProductFee` command can change.
+pub const FEE_TYPES: &[&str] = &["monthly_fee", "overdraft_fee", "nsf_fee", "atm_fee", "wire_fee"];
+
 pub struct PricingSubsystem {
     run_id: RunId,
     config: SimConfig,
@@ -68,6 +75,16 @@ impl PricingSubsystem {
         Ok(())
     }
 
+    /// Phase 4.76: take product state back from the store when resuming.
+    fn reload_product_state(&mut self) -> SimResult<()> {
+        for product in self.config.products.values() {
+            let state = self.store.get_product_state(&self.run_id, &product.product_id)?;
+            self.product_state.insert(product.product_id.clone(), state);
+        }
+        self.initialized = true;
+        Ok(())
+    }
+
     /// Validate a fee value against constraints.
     /// Returns Ok(Some(warning)) if above soft limit,
     /// Ok(None) if fine, Err if above hard limit.
@@ -84,6 +101,13 @@ impl PricingSubsystem {
             ));
         }
 
+        if new_value > constraint.soft_limit && self.fee_cap_in_force().map_err(|e| e.to_string())? {
+            return Err(format!(
+                "{fee_type} is capped at ${:.2} under a consent order until it is lifted",
+                constraint.soft_limit
+            ));
+        }
+
         if new_value > constraint.soft_limit {
             Ok(Some(constraint.soft_limit_warning.clone()))
         } else {
@@ -91,6 +115,55 @@ impl PricingSubsystem {
         }
     }
 
+    /// Phase 4.81: whether an open consent order caps fees.
+    fn fee_cap_in_force(&self) -> SimResult<bool> {
+        if !self.config.enforcement.enabled {
+            return Ok(false);
+        }
+        self.store.has_active_constraint(&self.run_id, "fee_cap")
+    }
+
+    /// Lower every fee above its soft limit to it, as the fee cap
+    /// requires.
+    fn apply_fee_cap(&mut self, tick: Tick) -> SimResult<Vec<SimEvent>> {
+        let mut events = Vec::new();
+        let mut product_ids: Vec<String> = self.product_state.keys().cloned().collect();
+        product_ids.sort();
+        for product_id in product_ids {
+            for fee_type in FEE_TYPES {
+                let Some(cap) = self.config.fee_constraints.get(*fee_type).map(|c| c.soft_limit) else {
+                    continue;
+                };
+                let state = self.product_state.get_mut(&product_id).expect("known product");
+                let fee = match *fee_type {
+                    "monthly_fee" => &mut state.monthly_fee,
+                    "overdraft_fee" => &mut state.overdraft_fee,
+                    "nsf_fee" => &mut state.nsf_fee,
+                    "atm_fee" => &mut state.atm_fee,
+                    "wire_fee" => &mut state.wire_fee,
+                    _ => unreachable!(),
+                };
+                if *fee <= cap {
+                    continue;
+                }
+                let old_value = std::mem::replace(fee, cap);
+                self.store.update_product_fee(&self.run_id, &product_id, fee_type, cap, tick)?;
+                self.store
+                    .log_fee_change(&self.run_id, tick, &product_id, fee_type, old_value, cap, false)?;
+                tracing::info!("{product_id}.{fee_type} lowered ${old_value:.2} -> ${cap:.2} under a consent order");
+                events.push(SimEvent::ProductFeeChanged {
+                    tick,
+                    product_id: product_id.clone(),
+                    fee_type: fee_type.to_string(),
+                    old_value,
+                    new_value: cap,
+                    warning: Some("Lowered to the consent order fee cap".into()),
+                });
+            }
+        }
+        Ok(events)
+    }
+
     fn process_fee_change(
         &mut self,
         product_id: &str,
@@ -103,7 +176,7 @@ impl PricingSubsystem {
         let warning = match self.validate_fee(fee_type, new_value) {
             Ok(w) => w,
             Err(reason) => {
-                log::warn!("tick={tick} pricing: fee validation failed for {product_id}.{fee_type}: {reason}");
+                tracing::warn!("fee validation failed for {product_id}.{fee_type}: {reason}");
                 events.push(SimEvent::FeeChangeRejected {
                     tick,
                     product_id: product_id.to_string(),
@@ -118,7 +191,7 @@ impl PricingSubsystem {
             Some(s) => s,
             None => {
                 let reason = format!("Unknown product: {product_id}");
-                log::warn!("tick={tick} pricing: {reason}");
+                tracing::warn!("{reason}");
                 events.push(SimEvent::FeeChangeRejected {
                     tick,
                     product_id: product_id.to_string(),
@@ -137,7 +210,7 @@ impl PricingSubsystem {
             "wire_fee" => state.wire_fee,
             _ => {
                 let reason = format!("Invalid fee type: {fee_type}");
-                log::warn!("tick={tick} pricing: {reason}");
+                tracing::warn!("{reason}");
                 events.push(SimEvent::FeeChangeRejected {
                     tick,
                     product_id: product_id.to_string(),
@@ -180,8 +253,8 @@ impl PricingSubsystem {
             warning,
         });
 
-        log::info!(
-            "tick={tick} pricing: {product_id}.{fee_type} changed ${:.2} -> ${:.2}",
+        tracing::info!(
+            "{product_id}.{fee_type} changed ${:.2} -> ${:.2}",
             old_value,
             new_value
         );
@@ -207,8 +280,8 @@ impl PricingSubsystem {
         if let (Some(thresh), Some(d)) = (threshold, delta) {
             if new_value > thresh {
                 self.store.adjust_udaap_score(&self.run_id, d, tick)?;
-                log::debug!(
-                    "tick={tick} pricing: UDAAP risk increased by {:.2} ({fee_type} above threshold)",
+                tracing::debug!(
+                    "UDAAP risk increased by {:.2} ({fee_type} above threshold)",
                     d
                 );
             }
@@ -218,11 +291,23 @@ impl PricingSubsystem {
     }
 }
 
+const MANIFEST: SubsystemManifest = SubsystemManifest {
+    version: 2,
+    produces: &["fee_change_rejected", "product_fee_changed"],
+    consumes: &["player_command_received"],
+    tables: &["fee_change_log", "product_state", "regulatory_score"],
+    requires: &[],
+};
+
 impl SimSubsystem for PricingSubsystem {
     fn name(&self) -> &'static str {
         "pricing"
     }
 
+    fn manifest(&self) -> SubsystemManifest {
+        MANIFEST
+    }
+
     fn update(
         &mut self,
         tick: Tick,
@@ -235,13 +320,18 @@ impl SimSubsystem for PricingSubsystem {
         if !self.initialized {
             self.initialize_product_state(tick)?;
             self.initialized = true;
-            log::info!(
-                "tick={tick} pricing: initialized {} products from catalog",
+            tracing::info!(
+                "initialized {} products from catalog",
                 self.product_state.len()
             );
             return Ok(out_events);
         }
 
+        // Phase 4.81: a consent order's fee cap holds before any change
+        if self.fee_cap_in_force()? {
+            out_events.extend(self.apply_fee_cap(tick)?);
+        }
+
         // Process player commands
         for event in events_in {
             if let SimEvent::PlayerCommandReceived { command_id, .. } = event {
@@ -257,10 +347,10 @@ impl SimSubsystem for PricingSubsystem {
                     }
                     Ok(Some(_)) => {} // Other commands handled elsewhere
                     Ok(None) => {
-                        log::warn!("tick={tick} pricing: command {command_id} not found in store");
+                        tracing::warn!("command {command_id} not found in store");
                     }
                     Err(e) => {
-                        log::warn!("tick={tick} pricing: error fetching command {command_id}: {e}");
+                        tracing::warn!("error fetching command {command_id}: {e}");
                     }
                 }
             }
@@ -269,6 +359,22 @@ impl SimSubsystem for PricingSubsystem {
         Ok(out_events)
     }
 
+    // Product state is the store's; only whether it was set up is kept
+    fn save_state(&self) -> SimResult<Option<serde_json::Value>> {
+        Ok(Some(serde_json::json!({ "initialized": self.initialized })))
+    }
+
+    fn load_state(&mut self, state: serde_json::Value) -> SimResult<()> {
+        if state["initialized"].as_bool().unwrap_or_default() {
+            self.reload_product_state()?;
+        }
+        Ok(())
+    }
+
+    fn replay(&mut self, _tick: Tick, _events: &[SimEvent]) -> SimResult<()> {
+        self.reload_product_state()
+    }
+
     fn as_any(&self) -> &dyn std::any::Any {
         self
     }
@@ -0,0 +1,385 @@
+//! IPC protocol between sim-runner and its clients (Phase 4.22).
+//!
+//! These are the message types sim-runner reads from stdin and writes to
+//! stdout, one JSON object per line. The JSON Schema published at
+//! `docs/ipc_protocol.schema.json` is generated from them by `schema()`
+//! (`sim-runner --print-schema`); a test keeps the two in step.
+//!
+//! Bump `PROTOCOL_VERSION` on any change an existing client could trip
+//! over: a renamed or removed field, a new required inbound field, or a
+//! changed meaning. Adding an outbound field is not a break.
+
+use crate::{
+    authorization::Role, clock::SimSpeed, command_result::CommandResult, complaint_subsystem::ComplaintRecord,
+    economics_subsystem::PnLSnapshot, engine::SimEngine, entity_timeline::EntityType,
+    error::{SimError, SimResult}, queue::{QueueFilter, QueueKind}, snapshot::SNAPSHOT_VERSION,
+    subsystem::SubsystemInfo, types::Tick,
+};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+pub const PROTOCOL_VERSION: u32 = 2;
+
+/// Client → sim-runner: a command and the run it is for.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct IpcRequest {
+    /// The hosted run to act on; unset means the run sim-runner started
+    /// with. `create_run` names the new run here.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub run_id: Option<String>,
+    #[serde(flatten)]
+    pub command: IpcCommand,
+}
+
+/// What a client asks of a run, tagged by `type`.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum IpcCommand {
+    /// Current state without advancing; answered with `UiState`.
+    /// `summary_only` on this, `tick` and `command` leaves out the
+    /// complaint list and P&L history; page queues with `query_queue`.
+    GetState {
+        #[serde(default)]
+        summary_only: bool,
+    },
+    /// Advance `count` steps at the run's speed, a tick each unless
+    /// `set_speed` changed it; answered with `UiState`. Refused while the
+    /// player has the run paused.
+    Tick {
+        count: u64,
+        #[serde(default)]
+        summary_only: bool,
+    },
+    /// A player action; answered with `CommandResult`, which carries the
+    /// state after applying it. `cmd` is `resolve_complaint` or any
+    /// `PlayerCommand` tag, with `payload` as its fields.
+    /// `role` is the sender's seat when the run gates commands by role;
+    /// unset means the run's default role.
+    Command {
+        cmd: String,
+        payload: serde_json::Value,
+        #[serde(default)]
+        summary_only: bool,
+        #[serde(default)]
+        role: Option<Role>,
+    },
+    /// What changed since `since_tick`; answered with `StateDelta`.
+    /// Events are counted by type, and sent in full only for the
+    /// `event_types` named.
+    GetStateDelta {
+        since_tick: Tick,
+        #[serde(default)]
+        event_types: Vec<String>,
+    },
+    /// One page of a work queue, oldest first; answered with `QueuePage`.
+    /// `limit` defaults to 50 and is capped at 500.
+    QueryQueue {
+        queue: QueueKind,
+        #[serde(default)]
+        filter: QueueFilter,
+        #[serde(default)]
+        offset: u64,
+        #[serde(default)]
+        limit: Option<u64>,
+    },
+    /// Open items on every desk by priority and age bucket, against a
+    /// week earlier; answered with `QueueAging`.
+    GetQueueAging,
+    /// Everything that happened to a customer, account or dispute case,
+    /// oldest first, optionally within a tick window; answered with
+    /// `EntityTimeline`.
+    GetEntityTimeline {
+        entity_type: EntityType,
+        entity_id: String,
+        #[serde(default)]
+        from_tick: Option<Tick>,
+        #[serde(default)]
+        to_tick: Option<Tick>,
+    },
+    /// One SELECT or WITH statement with positional `params`, run on a
+    /// read-only connection; answered with `QueryResult`. At most `limit`
+    /// rows come back, 500 if unset.
+    QueryReadonly {
+        sql: String,
+        #[serde(default)]
+        params: Vec<serde_json::Value>,
+        #[serde(default)]
+        limit: Option<u64>,
+    },
+    /// Replay a proposed monitoring rule over the run's transactions,
+    /// by default every tick played; answered with `BacktestReport`.
+    BacktestRule {
+        rule: crate::rule_backtest::RuleDefinition,
+        #[serde(default)]
+        from_tick: Option<Tick>,
+        #[serde(default)]
+        to_tick: Option<Tick>,
+    },
+    /// Pause when `breakpoint`'s condition fires, replacing any breakpoint
+    /// with its id; answered with `BreakpointList`. A `tick` that trips
+    /// one stops after that tick and lists it in `breakpoint_hits`.
+    SetBreakpoint {
+        breakpoint: crate::breakpoint::Breakpoint,
+    },
+    /// Remove a breakpoint; answered with `BreakpointList`.
+    ClearBreakpoint {
+        id: String,
+    },
+    /// The run's breakpoints; answered with `BreakpointList`.
+    ListBreakpoints,
+    /// Answered with `ProtocolVersion`. A client that sends its own
+    /// version and does not match is refused and the session ends.
+    GetProtocolVersion {
+        #[serde(default)]
+        client_version: Option<u32>,
+    },
+    /// The running build and its subsystems; answered with
+    /// `EngineDescription`.
+    DescribeEngine,
+    /// Start another run, under the request's `run_id`, from the scenario
+    /// sim-runner loaded; answered with `RunList`.
+    CreateRun {
+        seed: u64,
+    },
+    /// The runs this process hosts; answered with `RunList`.
+    ListRuns,
+    /// Stop hosting the request's run, or the default run; answered with
+    /// `RunList`. Its rows stay in the database.
+    CloseRun,
+    /// End the session.
+    Quit,
+}
+
+impl IpcCommand {
+    /// The command's `type` tag.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::GetState { .. } => "get_state",
+            Self::Tick { .. } => "tick",
+            Self::Command { .. } => "command",
+            Self::GetStateDelta { .. } => "get_state_delta",
+            Self::QueryQueue { .. } => "query_queue",
+            Self::GetQueueAging => "get_queue_aging",
+            Self::GetEntityTimeline { .. } => "get_entity_timeline",
+            Self::QueryReadonly { .. } => "query_readonly",
+            Self::BacktestRule { .. } => "backtest_rule",
+            Self::SetBreakpoint { .. } => "set_breakpoint",
+            Self::ClearBreakpoint { .. } => "clear_breakpoint",
+            Self::ListBreakpoints => "list_breakpoints",
+            Self::GetProtocolVersion { .. } => "get_protocol_version",
+            Self::DescribeEngine => "describe_engine",
+            Self::CreateRun { .. } => "create_run",
+            Self::ListRuns => "list_runs",
+            Self::CloseRun => "close_run",
+            Self::Quit => "quit",
+        }
+    }
+}
+
+/// sim-runner → client after `get_state` and `tick`, and inside the
+/// `CommandResult` answering `command`.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct UiState {
+    pub run_id: String,
+    pub tick: Tick,
+    pub paused: bool,
+    /// The player paused the run; `tick` is refused until they resume.
+    pub held: bool,
+    /// Ticks a `tick` step advances; see `set_speed`.
+    pub speed: SimSpeed,
+    pub active_customers: i64,
+    pub churned_customers: i64,
+    pub complaint_count: i64,
+    pub sla_breaches: i64,
+    pub backlog: i64,
+    pub nim: f64,
+    pub efficiency_ratio: f64,
+    pub pre_tax_profit: f64,
+    /// Composite resilience, 0–100: 100 is calm, 0 at the edge on every
+    /// stress component. Left out until the stress index has run.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub resilience: Option<f64>,
+    /// The breakpoints the last tick tripped, which stopped the clock
+    /// there. Left out when it tripped none.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub breakpoint_hits: Vec<crate::breakpoint::BreakpointHit>,
+    /// Empty when `summary_only`.
+    pub pnl_history: Vec<PnLSnapshot>,
+    /// Open complaints, oldest first. Empty when `summary_only`.
+    pub complaints: Vec<ComplaintRecord>,
+    /// The lists above were left out at the client's request.
+    pub summary_only: bool,
+    /// Share of the population simulated. Counts and dollar figures cover
+    /// the sample only; divide them by this for full-bank estimates.
+    pub population_scale: f64,
+}
+
+/// sim-runner → every WebSocket client but the sender after a `tick`:
+/// the run's state, summary only, as that tick request left it.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct StateUpdate {
+    pub state_update: UiState,
+}
+
+/// sim-runner → client after `get_protocol_version`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct ProtocolVersion {
+    pub protocol_version: u32,
+}
+
+/// sim-runner → client after `describe_engine`: what this build runs, so
+/// tooling can adapt to feature-flagged builds.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct EngineDescription {
+    pub run_id: String,
+    pub crate_version: String,
+    pub protocol_version: u32,
+    pub snapshot_version: u32,
+    /// In execution order.
+    pub subsystems: Vec<SubsystemInfo>,
+}
+
+impl EngineDescription {
+    pub fn from_engine(engine: &SimEngine) -> Self {
+        Self {
+            run_id: engine.run_id.clone(),
+            crate_version: env!("CARGO_PKG_VERSION").to_string(),
+            protocol_version: PROTOCOL_VERSION,
+            snapshot_version: SNAPSHOT_VERSION,
+            subsystems: engine.subsystems(),
+        }
+    }
+}
+
+/// sim-runner → client when a message cannot be handled.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct ErrorResponse {
+    pub error: String,
+    /// "invalid_message" | "protocol_mismatch", or a `SimError::code()`
+    /// such as "command_error" or "store_error".
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub code: Option<String>,
+    /// The run has stopped; sim-runner ends the session after sending this.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub fatal: bool,
+    /// The server's version, on a protocol mismatch.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub protocol_version: Option<u32>,
+}
+
+impl ErrorResponse {
+    pub fn invalid_message(error: impl ToString) -> Self {
+        Self {
+            error: error.to_string(),
+            code: Some("invalid_message".into()),
+            fatal: false,
+            protocol_version: None,
+        }
+    }
+}
+
+impl From<&SimError> for ErrorResponse {
+    fn from(e: &SimError) -> Self {
+        Self {
+            error: e.to_string(),
+            code: Some(e.code().into()),
+            fatal: e.is_fatal(),
+            protocol_version: None,
+        }
+    }
+}
+
+/// Answer a version query: the server's version, or a mismatch refusal
+/// when the client named a different one.
+pub fn check_client_version(client_version: Option<u32>) -> Result<ProtocolVersion, ErrorResponse> {
+    match client_version {
+        Some(v) if v != PROTOCOL_VERSION => Err(ErrorResponse {
+            error: format!("protocol version mismatch: client speaks {v}, sim-runner speaks {PROTOCOL_VERSION}"),
+            code: Some("protocol_mismatch".into()),
+            fatal: false,
+            protocol_version: Some(PROTOCOL_VERSION),
+        }),
+        _ => Ok(ProtocolVersion {
+            protocol_version: PROTOCOL_VERSION,
+        }),
+    }
+}
+
+/// Apply an IPC `command` message from the run's default role, as a
+/// recoverable `SimError::Command` if it is rejected. The stdin session
+/// sends the whole `CommandResult` instead.
+pub fn apply_command(engine: &mut SimEngine, cmd: &str, payload: &serde_json::Value) -> SimResult<()> {
+    CommandResult::apply(engine, cmd, payload, None)?.into_result().map(drop)
+}
+
+impl UiState {
+    pub fn from_engine(engine: &SimEngine) -> SimResult<Self> {
+        Self::build(engine, false)
+    }
+
+    /// The counts and latest P&L only, a bounded size however big the
+    /// run grows.
+    pub fn summary_from_engine(engine: &SimEngine) -> SimResult<Self> {
+        Self::build(engine, true)
+    }
+
+    /// The KPIs come from the engine's in-memory mirror, so a summary
+    /// makes no store query at all.
+    pub fn build(engine: &SimEngine, summary_only: bool) -> SimResult<Self> {
+        let run_id = engine.run_id.as_str();
+        let kpis = engine.state_mirror();
+        let (pnl_history, complaints) = if summary_only {
+            (Vec::new(), Vec::new())
+        } else {
+            (engine.store_all_pnl_snapshots(run_id)?, engine.store.open_complaints(run_id)?)
+        };
+        Ok(Self {
+            run_id: run_id.to_string(),
+            tick: engine.clock.current_tick,
+            paused: engine.clock.paused,
+            held: engine.clock.held,
+            speed: engine.clock.speed,
+            active_customers: kpis.active_customers,
+            churned_customers: kpis.churned_customers,
+            complaint_count: kpis.complaint_count,
+            sla_breaches: kpis.sla_breaches,
+            backlog: kpis.backlog,
+            nim: kpis.nim,
+            efficiency_ratio: kpis.efficiency_ratio,
+            pre_tax_profit: kpis.pre_tax_profit,
+            resilience: kpis.resilience,
+            breakpoint_hits: engine.breakpoint_hits().to_vec(),
+            pnl_history,
+            complaints,
+            summary_only,
+            population_scale: engine.population_scale(),
+        })
+    }
+}
+
+/// The protocol's JSON Schema: every inbound message and every outbound
+/// response, keyed by direction.
+pub fn schema() -> serde_json::Value {
+    serde_json::json!({
+        "$schema": "https://json-schema.org/draft/2020-12/schema",
+        "title": "FinCrime: The Desk IPC protocol",
+        "protocol_version": PROTOCOL_VERSION,
+        "inbound": schemars::schema_for!(IpcRequest),
+        "outbound": {
+            "state": schemars::schema_for!(UiState),
+            "state_update": schemars::schema_for!(StateUpdate),
+            "command_result": schemars::schema_for!(CommandResult),
+            "protocol_version": schemars::schema_for!(ProtocolVersion),
+            "engine_description": schemars::schema_for!(EngineDescription),
+            "queue_page": schemars::schema_for!(crate::queue::QueuePage),
+            "queue_aging": schemars::schema_for!(crate::queue_aging::QueueAging),
+            "entity_timeline": schemars::schema_for!(crate::entity_timeline::EntityTimeline),
+            "query_result": schemars::schema_for!(crate::readonly_query::QueryResult),
+            "backtest_report": schemars::schema_for!(crate::rule_backtest::BacktestReport),
+            "breakpoint_list": schemars::schema_for!(crate::breakpoint::BreakpointList),
+            "state_delta": schemars::schema_for!(crate::state_delta::StateDelta),
+            "run_list": schemars::schema_for!(crate::run_manager::RunList),
+            "error": schemars::schema_for!(ErrorResponse),
+        },
+    })
+}
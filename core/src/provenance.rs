@@ -0,0 +1,153 @@
+//! Run provenance (Phase 4.24).
+//!
+//! Everything needed to say how a stored run came to be, captured once
+//! when the run is created: the command line, a hash of the scenario
+//! data it loaded, the commit and features of the build, and the host.
+//! None of it feeds the simulation but the scenario file, which resuming
+//! applies again; two runs with the same seed and scenario hash replay
+//! identically whatever the rest says.
+
+use crate::{error::SimResult, scenario::ScenarioFile};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::path::{Path, PathBuf};
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct RunProvenance {
+    pub cli_args: Vec<String>,
+    /// SHA-256 over the scenario data directory and any scenario file;
+    /// see `hash_scenario` and `with_scenario`.
+    pub scenario_hash: Option<String>,
+    /// Phase 4.77: the scenario file the run was built with, if any.
+    pub scenario: Option<ScenarioFile>,
+    /// `git rev-parse HEAD` of the build, with "-dirty" for local changes.
+    pub git_commit: Option<String>,
+    /// Cargo features the binary was built with.
+    pub features: Vec<String>,
+    pub host: Option<HostInfo>,
+    /// Wall-clock seconds since the Unix epoch.
+    pub started_at: i64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HostInfo {
+    pub os: String,
+    pub arch: String,
+    pub hostname: Option<String>,
+    pub cpus: usize,
+}
+
+/// A stored run as `SimStore::describe_run` reports it. Runs created
+/// without provenance have the defaults: no args, hashes or host; runs
+/// stored before feature flags report every subsystem on.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RunDescription {
+    pub run_id: String,
+    pub seed: u64,
+    pub version: String,
+    pub provenance: RunProvenance,
+    /// Phase 4.42: the subsystems the run left out.
+    pub feature_flags: crate::config::FeatureFlagsConfig,
+}
+
+impl RunProvenance {
+    /// Capture provenance for a run loading its scenario from `data_dir`.
+    /// The commit and features belong to the calling binary, so it
+    /// passes its own.
+    pub fn capture(
+        cli_args: Vec<String>,
+        data_dir: &str,
+        git_commit: Option<&str>,
+        features: &[&str],
+    ) -> SimResult<Self> {
+        Ok(Self {
+            cli_args,
+            scenario_hash: Some(hash_scenario(data_dir)?),
+            scenario: None,
+            git_commit: git_commit.map(str::to_string),
+            features: features.iter().map(|f| f.to_string()).collect(),
+            host: Some(HostInfo::current()),
+            started_at: chrono::Utc::now().timestamp(),
+        })
+    }
+
+    /// Record the scenario file the run is built with. Its settings are
+    /// folded into the scenario hash, so runs of the same data directory
+    /// with different files do not compare as the same scenario.
+    pub fn with_scenario(mut self, scenario: &ScenarioFile) -> SimResult<Self> {
+        if let Some(hash) = &self.scenario_hash {
+            let mut hasher = Sha256::new();
+            hasher.update(hash.as_bytes());
+            hasher.update(serde_json::to_vec(scenario)?);
+            self.scenario_hash = Some(hex(&hasher.finalize()));
+        }
+        self.scenario = Some(scenario.clone());
+        Ok(self)
+    }
+}
+
+impl HostInfo {
+    pub fn current() -> Self {
+        Self {
+            os: std::env::consts::OS.into(),
+            arch: std::env::consts::ARCH.into(),
+            hostname: hostname(),
+            cpus: std::thread::available_parallelism().map_or(1, |n| n.get()),
+        }
+    }
+}
+
+fn hostname() -> Option<String> {
+    std::env::var("HOSTNAME")
+        .or_else(|_| std::env::var("COMPUTERNAME"))
+        .ok()
+        .or_else(|| std::fs::read_to_string("/etc/hostname").ok())
+        .map(|h| h.trim().to_string())
+        .filter(|h| !h.is_empty())
+}
+
+/// SHA-256 over every file under `data_dir`, in path order, covering
+/// each file's relative path and contents. Renaming, adding or editing
+/// any scenario file changes it; the directory's location does not.
+pub fn hash_scenario(data_dir: &str) -> SimResult<String> {
+    let root = Path::new(data_dir);
+    let mut files = Vec::new();
+    collect_files(root, &mut files)
+        .map_err(|e| anyhow::anyhow!("Cannot list {data_dir}: {e}"))?;
+    // Forward slashes so the hash and the order are the same on every OS
+    let mut files: Vec<(String, PathBuf)> = files
+        .into_iter()
+        .map(|path| {
+            let relative = path.strip_prefix(root).unwrap_or(&path);
+            (relative.to_string_lossy().replace('\\', "/"), path)
+        })
+        .collect();
+    files.sort();
+
+    let mut hasher = Sha256::new();
+    for (name, path) in files {
+        hasher.update((name.len() as u64).to_le_bytes());
+        hasher.update(name.as_bytes());
+        let contents = std::fs::read(&path)
+            .map_err(|e| anyhow::anyhow!("Cannot read {}: {e}", path.display()))?;
+        hasher.update((contents.len() as u64).to_le_bytes());
+        hasher.update(&contents);
+    }
+    Ok(hex(&hasher.finalize()))
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{b:02x}")).collect()
+}
+
+fn collect_files(dir: &Path, files: &mut Vec<PathBuf>) -> std::io::Result<()> {
+    for entry in std::fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.is_dir() {
+            collect_files(&path, files)?;
+        } else {
+            files.push(path);
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,224 @@
+//! Quarter-end close (Phase 4.62).
+//!
+//! A quarter's P&L is the product of a close, not a single query. At the
+//! quarter's end the close reverses the last quarter's accruals and books
+//! new ones for what the quarter earned or owes but has not yet paid or
+//! received:
+//!
+//! - bonuses on completed offers still unpaid,
+//! - a reserve against provisional credit on disputes still open,
+//! - interchange on card volume cleared but not yet settled.
+//!
+//! It also books any late adjustments to earlier quarters. The net goes
+//! into the snapshot's pre-tax profit, and a checklist of items opens for
+//! the player to sign off. The P&L approval comes last, after every other
+//! item. An adjustment posted to the quarter before sign-off and within
+//! the deadline restates its P&L. One posted after either is late: it is
+//! tracked against the quarter and booked at the next close. A close
+//! still open past its deadline is overdue.
+
+use crate::{
+    command::PlayerCommand,
+    config::QuarterCloseConfig,
+    economics_subsystem::PnLSnapshot,
+    error::SimResult,
+    event::SimEvent,
+    store::{
+        quarter_close::{CloseAdjustmentRow, CloseChecklistItemRow, QuarterCloseRow},
+        SimStore,
+    },
+    types::{RunId, Tick},
+};
+
+/// Checklist items, in the order they are worked; `pnl` is signed last.
+pub const CHECKLIST: &[&str] = &["accruals", "reconciliation", "disputes", "pnl"];
+
+/// Kinds of adjustment the close books itself, which the next close
+/// reverses.
+const ACCRUAL_KINDS: &[&str] = &["unpaid_bonus", "provisional_credit_reserve", "unposted_interchange"];
+
+pub struct QuarterClose {
+    run_id: RunId,
+    config: QuarterCloseConfig,
+    interchange_fee_rate: f64,
+}
+
+impl QuarterClose {
+    pub fn new(run_id: RunId, config: QuarterCloseConfig, interchange_fee_rate: f64) -> Self {
+        Self { run_id, config, interchange_fee_rate }
+    }
+
+    pub fn enabled(&self) -> bool {
+        self.config.enabled
+    }
+
+    /// Close the quarter ending at `tick`: book its adjustments into
+    /// `pnl` and open its checklist.
+    pub fn open(&self, store: &SimStore, tick: Tick, pnl: &mut PnLSnapshot) -> SimResult<Vec<SimEvent>> {
+        let period = pnl.period.clone();
+        let adjustment = |kind: &str, amount: f64, description: String| CloseAdjustmentRow {
+            adjustment_id: format!("adj-{period}-{kind}"),
+            period: period.clone(),
+            tick,
+            kind: kind.to_string(),
+            amount,
+            description,
+            late: false,
+            booked_period: Some(period.clone()),
+        };
+
+        let mut booked = Vec::new();
+        if let Some(previous) = store.quarter_closes(&self.run_id)?.last() {
+            let accrued = store
+                .close_adjustments(&self.run_id, &previous.period)?
+                .iter()
+                .filter(|a| ACCRUAL_KINDS.contains(&a.kind.as_str()))
+                .fold(0.0, |sum, a| sum + a.amount);
+            booked.push(adjustment("accrual_reversal", -accrued, format!("reverse {} accruals", previous.period)));
+        }
+        let basis = store.accrual_basis(&self.run_id, tick)?;
+        booked.push(adjustment(
+            "unpaid_bonus",
+            -basis.unpaid_bonuses,
+            "bonuses earned on completed offers, unpaid".into(),
+        ));
+        booked.push(adjustment(
+            "provisional_credit_reserve",
+            -basis.provisional_credit * self.config.provisional_credit_reserve_rate,
+            format!("reserve on ${:.0} provisional credit outstanding", basis.provisional_credit),
+        ));
+        booked.push(adjustment(
+            "unposted_interchange",
+            basis.unsettled_card_volume * self.interchange_fee_rate,
+            format!("interchange on ${:.0} cleared card volume unsettled", basis.unsettled_card_volume),
+        ));
+        booked.retain(|a| a.amount != 0.0);
+        for row in &booked {
+            store.insert_close_adjustment(&self.run_id, row)?;
+        }
+        let late = store.book_late_adjustments(&self.run_id, &period)?;
+
+        let total = booked.iter().chain(&late).fold(0.0, |sum, a| sum + a.amount);
+        pnl.accrual_adjustment = total;
+        pnl.pre_tax_profit += total;
+
+        let open_exceptions = store.get_open_recon_exceptions(&self.run_id)?.len();
+        for (item, detail) in [
+            ("accruals", format!("{} adjustments netting ${total:.0}", booked.len() + late.len())),
+            ("reconciliation", format!("{open_exceptions} reconciliation exceptions open")),
+            ("disputes", format!("${:.0} provisional credit outstanding", basis.provisional_credit)),
+            ("pnl", format!("pre-tax profit ${:.0}", pnl.pre_tax_profit)),
+        ] {
+            store.insert_close_checklist_item(&self.run_id, &CloseChecklistItemRow {
+                period: period.clone(),
+                item: item.to_string(),
+                detail,
+                status: "pending".into(),
+                signed_off_tick: None,
+            })?;
+        }
+        let due_tick = tick + self.config.signoff_ticks;
+        store.insert_quarter_close(&self.run_id, &QuarterCloseRow {
+            period: period.clone(),
+            close_tick: tick,
+            due_tick,
+            status: "open".into(),
+            closed_tick: None,
+            overdue: false,
+            adjustment_total: total,
+            late_adjustment_count: 0,
+            late_adjustment_total: 0.0,
+        })?;
+        Ok(vec![SimEvent::QuarterCloseOpened { tick, period, accrual_adjustment: total, due_tick }])
+    }
+
+    /// Apply the player's sign-offs and adjustments, then flag closes
+    /// still open past their deadline.
+    pub fn process(&self, store: &SimStore, tick: Tick, commands: &[PlayerCommand]) -> SimResult<Vec<SimEvent>> {
+        let mut events = Vec::new();
+        for command in commands {
+            events.push(self.apply(store, command, tick)?);
+        }
+        for mut close in store.quarter_closes(&self.run_id)? {
+            if close.status == "open" && !close.overdue && tick > close.due_tick {
+                close.overdue = true;
+                store.update_quarter_close(&self.run_id, &close)?;
+                let pending = store
+                    .close_checklist(&self.run_id, &close.period)?
+                    .iter()
+                    .filter(|i| i.status == "pending")
+                    .count() as u32;
+                tracing::warn!("{} close overdue with {pending} items unsigned", close.period);
+                events.push(SimEvent::QuarterCloseOverdue { tick, period: close.period, pending_items: pending });
+            }
+        }
+        Ok(events)
+    }
+
+    /// One sign-off or adjustment.
+    fn apply(&self, store: &SimStore, command: &PlayerCommand, tick: Tick) -> SimResult<SimEvent> {
+        let rejected = |command: &str, reason: String| {
+            tracing::warn!("{command} rejected: {reason}");
+            Ok(SimEvent::QuarterCloseCommandRejected { tick, command: command.to_string(), reason })
+        };
+        let (PlayerCommand::SignOffCloseItem { period, .. } | PlayerCommand::PostCloseAdjustment { period, .. }) =
+            command
+        else {
+            return rejected(command.name(), "not a quarter close command".into());
+        };
+        let Some(mut close) = store.quarter_close(&self.run_id, period)? else {
+            return rejected(command.name(), format!("no close for {period}"));
+        };
+
+        match command {
+            PlayerCommand::SignOffCloseItem { item, .. } => {
+                if close.status == "closed" {
+                    return rejected(command.name(), format!("{period} is already closed"));
+                }
+                let checklist = store.close_checklist(&self.run_id, period)?;
+                let Some(entry) = checklist.iter().find(|i| &i.item == item) else {
+                    return rejected(command.name(), format!("{item} is not on the checklist"));
+                };
+                if entry.status == "signed_off" {
+                    return rejected(command.name(), format!("{item} is already signed off"));
+                }
+                let pending = checklist.iter().filter(|i| i.status == "pending").count();
+                if item == "pnl" && pending > 1 {
+                    return rejected(command.name(), "sign off the other items before the P&L".into());
+                }
+                store.sign_off_close_item(&self.run_id, period, item, tick)?;
+                if pending > 1 {
+                    return Ok(SimEvent::CloseItemSignedOff { tick, period: period.clone(), item: item.clone() });
+                }
+                close.status = "closed".into();
+                close.closed_tick = Some(tick);
+                store.update_quarter_close(&self.run_id, &close)?;
+                Ok(SimEvent::QuarterClosed { tick, period: period.clone(), overdue: close.overdue })
+            }
+            PlayerCommand::PostCloseAdjustment { amount, reason, .. } => {
+                let late = close.status == "closed" || tick > close.due_tick;
+                let posted = store.close_adjustments(&self.run_id, period)?.len();
+                store.insert_close_adjustment(&self.run_id, &CloseAdjustmentRow {
+                    adjustment_id: format!("adj-{period}-manual-{posted}"),
+                    period: period.clone(),
+                    tick,
+                    kind: "manual".into(),
+                    amount: *amount,
+                    description: reason.clone(),
+                    late,
+                    booked_period: (!late).then(|| period.clone()),
+                })?;
+                if late {
+                    close.late_adjustment_count += 1;
+                    close.late_adjustment_total += amount;
+                } else {
+                    store.restate_pnl_snapshot(&self.run_id, period, *amount)?;
+                    close.adjustment_total += amount;
+                }
+                store.update_quarter_close(&self.run_id, &close)?;
+                Ok(SimEvent::CloseAdjustmentPosted { tick, period: period.clone(), amount: *amount, late })
+            }
+            _ => unreachable!("matched above"),
+        }
+    }
+}
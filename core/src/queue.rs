@@ -0,0 +1,112 @@
+//! Paginated work queues (Phase 4.43).
+//!
+//! A big run holds thousands of open complaints, alerts, reconciliation
+//! exceptions and dispute cases, too many to send in one message. Clients
+//! page through a queue instead, oldest item first, with optional
+//! filters. Every queue's rows share one shape so a desk UI can render
+//! any of them the same way.
+
+use crate::{error::SimResult, store::SimStore, types::Tick};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// Items per page when the client does not say.
+pub const DEFAULT_PAGE_SIZE: u64 = 50;
+/// Largest page served, whatever the client asks for.
+pub const MAX_PAGE_SIZE: u64 = 500;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum QueueKind {
+    Complaints,
+    /// AML screening and transaction monitoring alerts.
+    Alerts,
+    /// Reconciliation breaks.
+    Exceptions,
+    /// Card dispute cases.
+    Cases,
+}
+
+impl QueueKind {
+    pub const ALL: [QueueKind; 4] = [Self::Complaints, Self::Alerts, Self::Exceptions, Self::Cases];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Complaints => "complaints",
+            Self::Alerts => "alerts",
+            Self::Exceptions => "exceptions",
+            Self::Cases => "cases",
+        }
+    }
+}
+
+/// Every field narrows the queue; none set means every open item.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct QueueFilter {
+    /// Exact status, closed ones included. Unset: open items only.
+    #[serde(default)]
+    pub status: Option<String>,
+    #[serde(default)]
+    pub customer_id: Option<String>,
+    /// Complaint issue, alert type, suspected break cause or dispute reason.
+    #[serde(default)]
+    pub category: Option<String>,
+    /// Opened at or after this tick.
+    #[serde(default)]
+    pub from_tick: Option<Tick>,
+    /// Opened at or before this tick.
+    #[serde(default)]
+    pub to_tick: Option<Tick>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct QueueItem {
+    pub id: String,
+    /// None for reconciliation exceptions, which belong to a rail.
+    pub customer_id: Option<String>,
+    pub opened_tick: Tick,
+    pub status: String,
+    pub category: String,
+    /// Complaint priority or alert severity.
+    pub priority: Option<String>,
+    /// Alert trigger, break delta or disputed amount.
+    pub amount: Option<f64>,
+    /// Complaint SLA due tick.
+    pub due_tick: Option<Tick>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct QueuePage {
+    pub queue: QueueKind,
+    /// Items matching the filter across all pages.
+    pub total: u64,
+    pub offset: u64,
+    pub limit: u64,
+    /// Offset of the next page; None on the last.
+    pub next_offset: Option<u64>,
+    pub items: Vec<QueueItem>,
+}
+
+impl QueuePage {
+    /// One page of a queue. `limit` is clamped to 1..=`MAX_PAGE_SIZE`.
+    pub fn query(
+        store: &SimStore,
+        run_id: &str,
+        queue: QueueKind,
+        filter: &QueueFilter,
+        offset: u64,
+        limit: Option<u64>,
+    ) -> SimResult<Self> {
+        let limit = limit.unwrap_or(DEFAULT_PAGE_SIZE).clamp(1, MAX_PAGE_SIZE);
+        let (total, items) = store.queue_page(run_id, queue, filter, offset, limit)?;
+        let next = offset + items.len() as u64;
+        Ok(Self {
+            queue,
+            total,
+            offset,
+            limit,
+            next_offset: (next < total).then_some(next),
+            items,
+        })
+    }
+}
@@ -0,0 +1,131 @@
+//! Queue aging heatmap (Phase 4.48).
+//!
+//! Every open work item counted by desk, priority and age bucket, with
+//! how many are past their SLA and how the same cell stood a week
+//! earlier. The desk UI draws its heatmap from this and complaint
+//! analytics reads the same counts for its aged-backlog warning, so
+//! neither rebuilds them from raw queue pages.
+
+use crate::{error::SimResult, queue::QueueKind, store::SimStore, types::Tick};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Age in ticks at which each bucket begins: 0–2, 3–6, 7–13, 14–29, 30+.
+pub const AGE_BUCKET_STARTS: [Tick; 5] = [0, 3, 7, 14, 30];
+/// First bucket counted as aged backlog.
+pub const AGED_BUCKET: usize = 3;
+/// How far back the trend compares.
+pub const TREND_TICKS: Tick = 7;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct AgingCell {
+    pub desk: QueueKind,
+    /// Complaint priority or alert severity; None on desks without one.
+    pub priority: Option<String>,
+    /// Index into `QueueAging::age_buckets`.
+    pub age_bucket: usize,
+    pub count: u64,
+    /// Past their SLA due tick. Only complaints have one.
+    pub overdue: u64,
+    /// The same cell `TREND_TICKS` earlier.
+    pub week_ago: u64,
+    pub delta: i64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct DeskAging {
+    pub desk: QueueKind,
+    pub open: u64,
+    pub overdue: u64,
+    /// Open items in the aged buckets.
+    pub aged: u64,
+    pub open_week_ago: u64,
+    pub aged_week_ago: u64,
+}
+
+/// sim-runner → client after `get_queue_aging`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct QueueAging {
+    pub tick: Tick,
+    pub week_ago_tick: Tick,
+    /// Bucket labels, youngest first, such as "0-2" and "30+".
+    pub age_buckets: Vec<String>,
+    /// Index of the first bucket counted as aged.
+    pub aged_from_bucket: usize,
+    /// One per desk, in `QueueKind::ALL` order.
+    pub desks: Vec<DeskAging>,
+    /// Cells with an item now or a week ago, by desk, priority and bucket.
+    pub cells: Vec<AgingCell>,
+}
+
+impl QueueAging {
+    pub fn query(store: &SimStore, run_id: &str, tick: Tick) -> SimResult<Self> {
+        let week_ago_tick = tick.saturating_sub(TREND_TICKS);
+        let mut desks = Vec::new();
+        let mut cells = Vec::new();
+        for desk in QueueKind::ALL {
+            let mut by_cell: BTreeMap<(Option<String>, usize), AgingCell> = BTreeMap::new();
+            for row in store.queue_aging_counts(run_id, desk, tick, &AGE_BUCKET_STARTS)? {
+                let cell = cell_at(&mut by_cell, desk, row.priority, row.bucket);
+                cell.count = row.count;
+                cell.overdue = row.overdue;
+            }
+            if week_ago_tick < tick {
+                for row in store.queue_aging_counts(run_id, desk, week_ago_tick, &AGE_BUCKET_STARTS)? {
+                    cell_at(&mut by_cell, desk, row.priority, row.bucket).week_ago = row.count;
+                }
+            }
+
+            let mut totals = DeskAging { desk, open: 0, overdue: 0, aged: 0, open_week_ago: 0, aged_week_ago: 0 };
+            for mut cell in by_cell.into_values() {
+                cell.delta = cell.count as i64 - cell.week_ago as i64;
+                totals.open += cell.count;
+                totals.overdue += cell.overdue;
+                totals.open_week_ago += cell.week_ago;
+                if cell.age_bucket >= AGED_BUCKET {
+                    totals.aged += cell.count;
+                    totals.aged_week_ago += cell.week_ago;
+                }
+                cells.push(cell);
+            }
+            desks.push(totals);
+        }
+        Ok(Self {
+            tick,
+            week_ago_tick,
+            age_buckets: bucket_labels(),
+            aged_from_bucket: AGED_BUCKET,
+            desks,
+            cells,
+        })
+    }
+}
+
+fn cell_at(
+    cells: &mut BTreeMap<(Option<String>, usize), AgingCell>,
+    desk: QueueKind,
+    priority: Option<String>,
+    age_bucket: usize,
+) -> &mut AgingCell {
+    cells.entry((priority.clone(), age_bucket)).or_insert(AgingCell {
+        desk,
+        priority,
+        age_bucket,
+        count: 0,
+        overdue: 0,
+        week_ago: 0,
+        delta: 0,
+    })
+}
+
+fn bucket_labels() -> Vec<String> {
+    AGE_BUCKET_STARTS
+        .iter()
+        .enumerate()
+        .map(|(i, start)| match AGE_BUCKET_STARTS.get(i + 1) {
+            Some(next) => format!("{start}-{}", next - 1),
+            None => format!("{start}+"),
+        })
+        .collect()
+}
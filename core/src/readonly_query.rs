@@ -0,0 +1,175 @@
+//! Ad hoc read-only SQL (Phase 4.67).
+//!
+//! Power users query the run's database from the UI with
+//! `query_readonly` instead of opening the file while the sim writes to
+//! it. Three guards keep a query from changing anything:
+//!
+//! - the text must be one statement, starting SELECT or WITH,
+//! - SQLite must judge the prepared statement read-only, which catches a
+//!   WITH that ends in an INSERT,
+//! - it runs on a connection of its own opened read-only.
+//!
+//! A query is cut off after `MAX_QUERY_STEPS` thousand SQLite VM steps.
+//! The budget counts work, not wall time, so the same query on the same
+//! data always gets as far. Rows stop at the row limit or once the reply
+//! would pass `MAX_RESULT_BYTES`, and the result says it was truncated.
+
+use crate::{
+    error::{SimError, SimResult},
+    store::SimStore,
+};
+use rusqlite::types::Value as SqlValue;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// Rows returned when the client does not say.
+pub const DEFAULT_ROW_LIMIT: u64 = 500;
+/// Most rows returned, whatever the client asks for.
+pub const MAX_ROW_LIMIT: u64 = 5_000;
+/// Largest reply, measured as the rows' JSON.
+pub const MAX_RESULT_BYTES: usize = 4 << 20;
+/// Thousands of SQLite VM steps a query may take.
+pub const MAX_QUERY_STEPS: u32 = 100_000;
+
+/// sim-runner → client after `query_readonly`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct QueryResult {
+    pub columns: Vec<String>,
+    /// Per column, the storage class of its first non-NULL value:
+    /// "integer" | "real" | "text" | "blob", or "null" if it had none.
+    /// Blobs are sent as lowercase hex.
+    pub column_types: Vec<String>,
+    /// One array per row, in column order.
+    pub rows: Vec<Vec<Value>>,
+    /// More rows matched than were sent.
+    pub truncated: bool,
+}
+
+impl QueryResult {
+    /// Run `sql` with positional `params` (`?1`, `?2`, …). A statement
+    /// the guards refuse, or one SQLite rejects or cuts off, comes back
+    /// as a recoverable `SimError::Command`.
+    pub fn query(store: &SimStore, sql: &str, params: &[Value], limit: Option<u64>) -> SimResult<Self> {
+        check_statement(sql).map_err(reject)?;
+        let params = params.iter().map(sql_param).collect::<Result<Vec<_>, _>>().map_err(reject)?;
+        let limit = limit.unwrap_or(DEFAULT_ROW_LIMIT).clamp(1, MAX_ROW_LIMIT) as usize;
+        let found = store.readonly_query(sql, &params, limit + 1, MAX_QUERY_STEPS)?;
+
+        let mut truncated = found.rows.len() > limit;
+        let mut column_types = vec!["null"; found.columns.len()];
+        let mut rows = Vec::new();
+        let mut bytes = 0;
+        for row in found.rows.into_iter().take(limit) {
+            for (ty, value) in column_types.iter_mut().zip(&row) {
+                if *ty == "null" {
+                    *ty = storage_class(value);
+                }
+            }
+            let row: Vec<Value> = row.into_iter().map(json_value).collect();
+            bytes += serde_json::to_string(&row)?.len();
+            if bytes > MAX_RESULT_BYTES {
+                truncated = true;
+                break;
+            }
+            rows.push(row);
+        }
+        Ok(Self {
+            columns: found.columns,
+            column_types: column_types.into_iter().map(String::from).collect(),
+            rows,
+            truncated,
+        })
+    }
+}
+
+pub(crate) fn reject(reason: impl Into<String>) -> SimError {
+    SimError::command("query_readonly", reason)
+}
+
+/// One statement, starting SELECT or WITH. Comments, quoted strings and
+/// identifiers are skipped, so a `;` inside one does not count.
+pub fn check_statement(sql: &str) -> Result<(), String> {
+    let mut bare = String::with_capacity(sql.len());
+    let mut chars = sql.chars().peekable();
+    let mut ended = false;
+    while let Some(c) = chars.next() {
+        match c {
+            '-' if chars.peek() == Some(&'-') => {
+                chars.by_ref().find(|&c| c == '\n');
+                bare.push(' ');
+            }
+            '/' if chars.peek() == Some(&'*') => {
+                chars.next();
+                let mut star = false;
+                loop {
+                    match chars.next() {
+                        None => return Err("unterminated comment".into()),
+                        Some('/') if star => break,
+                        Some(c) => star = c == '*',
+                    }
+                }
+                bare.push(' ');
+            }
+            ';' => ended = true,
+            c if c.is_whitespace() => bare.push(' '),
+            _ if ended => return Err("only one statement may be run".into()),
+            '\'' | '"' | '`' | '[' => {
+                let close = if c == '[' { ']' } else { c };
+                loop {
+                    match chars.next() {
+                        None => return Err("unterminated quote".into()),
+                        // A doubled quote is an escaped one
+                        Some(q) if q == close && c != '[' && chars.peek() == Some(&close) => {
+                            chars.next();
+                        }
+                        Some(q) if q == close => break,
+                        Some(_) => {}
+                    }
+                }
+                bare.push_str(" _ ");
+            }
+            c => bare.push(c),
+        }
+    }
+    let first: String = bare.trim_start().chars().take_while(char::is_ascii_alphabetic).collect();
+    match first.to_ascii_uppercase().as_str() {
+        "SELECT" | "WITH" => Ok(()),
+        "" => Err("no statement".into()),
+        other => Err(format!("{other} is not allowed; only SELECT and WITH queries are")),
+    }
+}
+
+fn sql_param(value: &Value) -> Result<SqlValue, String> {
+    Ok(match value {
+        Value::Null => SqlValue::Null,
+        Value::Bool(b) => SqlValue::Integer(*b as i64),
+        Value::Number(n) => match n.as_i64() {
+            Some(i) => SqlValue::Integer(i),
+            None => SqlValue::Real(n.as_f64().ok_or("parameter out of range")?),
+        },
+        Value::String(s) => SqlValue::Text(s.clone()),
+        Value::Array(_) | Value::Object(_) => return Err("parameters must be null, a number, a string or a bool".into()),
+    })
+}
+
+fn json_value(value: SqlValue) -> Value {
+    match value {
+        SqlValue::Null => Value::Null,
+        SqlValue::Integer(i) => i.into(),
+        // JSON has no NaN or infinity
+        SqlValue::Real(f) => serde_json::Number::from_f64(f).map_or(Value::Null, Value::Number),
+        SqlValue::Text(s) => s.into(),
+        SqlValue::Blob(bytes) => bytes.iter().map(|b| format!("{b:02x}")).collect::<String>().into(),
+    }
+}
+
+fn storage_class(value: &SqlValue) -> &'static str {
+    match value {
+        SqlValue::Null => "null",
+        SqlValue::Integer(_) => "integer",
+        SqlValue::Real(_) => "real",
+        SqlValue::Text(_) => "text",
+        SqlValue::Blob(_) => "blob",
+    }
+}
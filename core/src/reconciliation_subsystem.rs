@@ -9,31 +9,78 @@
 //!   - External total = external_statement.total_debits + total_credits for rail/tick
 //!   - Delta > tolerance → create recon_exception
 //!   - Auto-clear on day 2+ if delta < auto_clear_threshold AND cause = 'timing'
+//!   - Deltas explained by items rolled past a rail cutoff are 'cutoff_timing'
+//!     and auto-clear on day 2+ regardless of size (Phase 4.8)
 //!   - SLA breach at sla_days, escalation at escalation_age_days
 //!   - Write-offs drive UDAAP penalty via regulatory_score_component
+//!
+//! Phase 4.6 — card network invoices:
+//!   - Every invoice_interval_ticks the network sends interchange,
+//!     assessment and dispute-fee lines for the period
+//!   - Each line is compared with the amount computed from authorization
+//!     and card_dispute records; network errors open a recon_exception
+//!     on the card rail (cause 'amount_error')
+//!   - ClaimNetworkRecovery inside the recovery window recovers the delta
+//!     (or is denied and written off); unclaimed errors are written off
+//!
+//! Phase 4.7 — settlement (nostro) accounts:
+//!   - One prefunded settlement account per rail
+//!   - Each tick the account is funded at the trailing-average outflow plus
+//!     a buffer; actual settled outflow above that is borrowed at a penalty
+//!   - Outflow is measured from settled transactions (card: settled
+//!     authorizations) for every rail in the payment hub config
+//!   - Shortfalls beyond alert_shortfall_pct of funding raise an early
+//!     warning alert
+//!
+//! Phase 4.80 — staffed exception queue:
+//!   - Staff investigate and correct breaks auto-clear leaves, oldest
+//!     first, as many a day as the queue's capacity
 
 use crate::{
-    config::ReconciliationConfig,
+    command::PlayerCommand,
+    complaint_analytics_subsystem::EarlyWarningAlert,
+    config::{
+        NetworkInvoiceConfig, PaymentRailConfig, ReconciliationConfig, SettlementAccountConfig,
+    },
     error::SimResult,
     event::SimEvent,
+    queue::QueueKind,
     rng::SubsystemRng,
-    store::{ReconExceptionRow, ReconMetricsRow, SimStore},
-    subsystem::SimSubsystem,
+    store::{
+        network_invoice::NetworkInvoiceRow, settlement::SettlementPositionRow, ReconExceptionRow,
+        ReconMetricsRow, SimStore,
+    },
+    subsystem::{SimSubsystem, SubsystemManifest},
     types::{RunId, Tick},
 };
 
 pub struct ReconciliationSubsystem {
     run_id: RunId,
     config: ReconciliationConfig,
+    network: NetworkInvoiceConfig,
+    settlement: SettlementAccountConfig,
+    rails: Vec<PaymentRailConfig>,
     store: SimStore,
+    accounts_opened: bool,
 }
 
 impl ReconciliationSubsystem {
-    pub fn new(run_id: RunId, config: ReconciliationConfig, store: SimStore) -> Self {
+    pub fn new(
+        run_id: RunId,
+        config: ReconciliationConfig,
+        network: NetworkInvoiceConfig,
+        settlement: SettlementAccountConfig,
+        rails: Vec<PaymentRailConfig>,
+        store: SimStore,
+    ) -> Self {
         Self {
             run_id,
             config,
+            network,
+            settlement,
+            rails,
             store,
+            accounts_opened: false,
         }
     }
 
@@ -84,7 +131,17 @@ impl ReconciliationSubsystem {
         let seq = rng.next_u64_below(1_000_000_000);
         let exception_id = format!("RECON-{current_tick}-{rail_id}-{seq}");
 
-        let cause = self.infer_cause(delta, &cfg);
+        // Phase 4.8: the gap matches items rolled out today net of items
+        // rolled in from yesterday
+        let net_roll = self
+            .store
+            .net_cutoff_roll(&self.run_id, rail_id, settle_tick)?;
+        let explained = (internal_total - external_total - net_roll).abs();
+        let cause = if net_roll != 0.0 && explained <= cfg.tolerance_amount {
+            "cutoff_timing".to_string()
+        } else {
+            self.infer_cause(delta, &cfg)
+        };
 
         let ex = ReconExceptionRow {
             exception_id: exception_id.clone(),
@@ -129,6 +186,306 @@ impl ReconciliationSubsystem {
         }
     }
 
+    // ── Phase 4.6: Card network invoices ──────────────────────────
+
+    /// Receive the network invoice for the period ending at `tick` and
+    /// reconcile each line against internally computed amounts.
+    fn reconcile_network_invoice(
+        &self,
+        tick: Tick,
+        rng: &mut SubsystemRng,
+    ) -> SimResult<Vec<SimEvent>> {
+        let mut events = Vec::new();
+        let cfg = &self.network;
+        let start = tick.saturating_sub(cfg.invoice_interval_ticks);
+
+        let (interchange, volume) = self.store.card_settlement_totals(&self.run_id, start, tick)?;
+        let disputes = self.store.card_disputes_filed_between(&self.run_id, start, tick)?;
+        let tolerance = self.store.get_recon_queue_config("card")?.tolerance_amount;
+
+        let lines = [
+            ("interchange", interchange),
+            ("assessment", volume * cfg.assessment_rate),
+            ("dispute_fee", disputes as f64 * cfg.dispute_fee),
+        ];
+
+        let mut invoiced_total = 0.0;
+        let mut discrepancies = 0i64;
+        for (line_type, internal) in lines {
+            let internal = (internal * 100.0).round() / 100.0;
+
+            // Network errors always land in the network's favor: short-paid
+            // interchange, over-billed assessments, duplicate dispute fees.
+            let (invoiced, error_type) = if rng.chance(cfg.network_error_rate) {
+                let pct = cfg.min_error_pct
+                    + (cfg.max_error_pct - cfg.min_error_pct) * rng.next_f64();
+                match line_type {
+                    "interchange" => (internal * (1.0 - pct), Some("short_remittance")),
+                    "assessment" => (internal * (1.0 + pct), Some("rate_error")),
+                    _ => {
+                        let dupes = 1 + rng.next_u64_below(3);
+                        (internal + dupes as f64 * cfg.dispute_fee, Some("duplicate_dispute_fee"))
+                    }
+                }
+            } else {
+                (internal, None)
+            };
+            let invoiced = (invoiced * 100.0).round() / 100.0;
+            invoiced_total += invoiced;
+
+            let delta = (invoiced - internal).abs();
+            let invoice_id = format!("NETINV-{tick}-{line_type}");
+            let mut row = NetworkInvoiceRow {
+                invoice_id: invoice_id.clone(),
+                tick_received: tick,
+                period_start: start,
+                period_end: tick,
+                line_type: line_type.to_string(),
+                internal_amount: internal,
+                invoiced_amount: invoiced,
+                delta_amount: delta,
+                error_type: error_type.map(str::to_string),
+                status: "matched".into(),
+                exception_id: None,
+                recovery_deadline_tick: None,
+            };
+
+            if delta > tolerance {
+                let seq = rng.next_u64_below(1_000_000_000);
+                let exception_id = format!("RECON-{tick}-card-{seq}");
+                self.store.insert_recon_exception(&ReconExceptionRow {
+                    exception_id: exception_id.clone(),
+                    run_id: self.run_id.clone(),
+                    rail_id: "card".into(),
+                    tick_detected: tick,
+                    tick_resolved: None,
+                    status: "open".into(),
+                    delta_amount: delta,
+                    internal_total: internal,
+                    external_total: invoiced,
+                    item_count_delta: None,
+                    suspected_cause: Some("amount_error".into()),
+                    assigned_to: None,
+                    resolution_notes: Some(format!("Network invoice {invoice_id}")),
+                    resolution_type: None,
+                    write_off_amount: 0.0,
+                })?;
+                row.status = "exception".into();
+                row.exception_id = Some(exception_id.clone());
+                row.recovery_deadline_tick = Some(tick + cfg.recovery_window_ticks);
+                discrepancies += 1;
+
+                events.push(SimEvent::ReconExceptionCreated {
+                    tick,
+                    exception_id: exception_id.clone(),
+                    rail_id: "card".into(),
+                    delta_amount: delta,
+                });
+                events.push(SimEvent::NetworkInvoiceDiscrepancy {
+                    tick,
+                    invoice_id,
+                    line_type: line_type.to_string(),
+                    delta_amount: delta,
+                    exception_id,
+                });
+            }
+            self.store.insert_network_invoice(&self.run_id, &row)?;
+        }
+
+        tracing::info!(
+            "network invoice ${invoiced_total:.2}, \
+             {discrepancies} discrepancies"
+        );
+        events.push(SimEvent::NetworkInvoiceReceived {
+            tick,
+            lines: 3,
+            invoiced_total,
+            discrepancies,
+        });
+        Ok(events)
+    }
+
+    /// ClaimNetworkRecovery: file a claim with the network for an open
+    /// invoice discrepancy. Accepted claims recover the delta; denied
+    /// claims are written off.
+    fn claim_network_recovery(
+        &self,
+        invoice_id: &str,
+        tick: Tick,
+        rng: &mut SubsystemRng,
+    ) -> SimResult<Vec<SimEvent>> {
+        let invoice = match self.store.get_network_invoice(&self.run_id, invoice_id)? {
+            Some(inv) if inv.status == "exception" => inv,
+            _ => {
+                tracing::warn!("no open discrepancy on {invoice_id}");
+                return Ok(Vec::new());
+            }
+        };
+
+        if rng.chance(self.network.recovery_success_rate) {
+            self.close_network_discrepancy(&invoice, "recovered", tick)
+        } else {
+            self.close_network_discrepancy(&invoice, "claim_denied", tick)
+        }
+    }
+
+    /// Write off discrepancies whose recovery window has passed.
+    fn expire_network_recoveries(&self, tick: Tick) -> SimResult<Vec<SimEvent>> {
+        let mut events = Vec::new();
+        for invoice in self.store.open_network_invoice_exceptions(&self.run_id)? {
+            if invoice.recovery_deadline_tick.is_some_and(|d| d < tick) {
+                events.extend(self.close_network_discrepancy(&invoice, "written_off", tick)?);
+            }
+        }
+        Ok(events)
+    }
+
+    fn close_network_discrepancy(
+        &self,
+        invoice: &NetworkInvoiceRow,
+        outcome: &str,
+        tick: Tick,
+    ) -> SimResult<Vec<SimEvent>> {
+        let mut events = Vec::new();
+        let recovered = if outcome == "recovered" { invoice.delta_amount } else { 0.0 };
+        let write_off = invoice.delta_amount - recovered;
+        self.store
+            .resolve_network_invoice(&self.run_id, &invoice.invoice_id, outcome, recovered, tick)?;
+
+        if let Some(exception_id) = &invoice.exception_id {
+            let (resolution_type, notes) = if recovered > 0.0 {
+                ("manual_adjustment", "Recovered from card network via claim")
+            } else if outcome == "claim_denied" {
+                ("write_off", "Network denied recovery claim")
+            } else {
+                ("write_off", "Recovery window expired without a claim")
+            };
+            self.store.resolve_recon_exception(
+                &self.run_id,
+                exception_id,
+                tick,
+                resolution_type,
+                notes,
+                write_off,
+            )?;
+            events.push(SimEvent::ReconExceptionResolved {
+                tick,
+                exception_id: exception_id.clone(),
+                resolution_type: resolution_type.into(),
+                write_off_amount: write_off,
+            });
+        }
+
+        events.push(SimEvent::NetworkRecoveryResolved {
+            tick,
+            invoice_id: invoice.invoice_id.clone(),
+            outcome: outcome.to_string(),
+            recovered_amount: recovered,
+        });
+        Ok(events)
+    }
+
+    // ── Phase 4.7: Settlement account positions ───────────────────
+
+    /// Compare each rail's funded settlement position with the day's actual
+    /// outflow; borrow any shortfall at the penalty rate.
+    fn update_settlement_positions(&mut self, tick: Tick) -> SimResult<Vec<SimEvent>> {
+        let mut events = Vec::new();
+
+        if !self.accounts_opened {
+            for rail in &self.rails {
+                let counterparty = match rail.rail_type.as_str() {
+                    "ACH" => "Federal Reserve (FedACH)",
+                    "wire" => "Federal Reserve (Fedwire)",
+                    "RTP" => "The Clearing House",
+                    "FedNow" => "Federal Reserve (FedNow)",
+                    "card" => "Card network settlement bank",
+                    "check" => "Check image exchange",
+                    _ => "Correspondent bank",
+                };
+                self.store
+                    .open_settlement_account(&self.run_id, &rail.rail_id, counterparty, tick)?;
+            }
+            self.accounts_opened = true;
+        }
+
+        let cfg = &self.settlement;
+        let window_start = tick.saturating_sub(cfg.projection_window_ticks).max(1);
+        if window_start >= tick {
+            return Ok(events); // no settlement history to project from yet
+        }
+        for rail in &self.rails {
+            let rail_id = &rail.rail_id;
+            let history = self.store.settled_outflow_between(
+                &self.run_id,
+                rail_id,
+                &rail.rail_type,
+                window_start,
+                tick,
+            )?;
+            let projected = history / (tick - window_start) as f64;
+            let actual = self.store.settled_outflow_between(
+                &self.run_id,
+                rail_id,
+                &rail.rail_type,
+                tick,
+                tick + 1,
+            )?;
+            if projected == 0.0 && actual == 0.0 {
+                continue;
+            }
+
+            let funded = projected * (1.0 + cfg.funding_buffer_pct);
+            let shortfall = (actual - funded).max(0.0);
+            let idle_balance = (funded - actual).max(0.0);
+            let borrowing_cost = if shortfall > 0.0 {
+                shortfall * cfg.penalty_rate + cfg.borrowing_fee
+            } else {
+                0.0
+            };
+            let alert = shortfall > 0.0 && shortfall > funded * cfg.alert_shortfall_pct;
+
+            self.store.insert_settlement_position(&self.run_id, &SettlementPositionRow {
+                rail_id: rail_id.clone(),
+                tick,
+                projected_outflow: projected,
+                funded_amount: funded,
+                actual_outflow: actual,
+                shortfall,
+                idle_balance,
+                borrowing_cost,
+                alert,
+            })?;
+
+            if shortfall > 0.0 {
+                if alert {
+                    let threshold = funded * cfg.alert_shortfall_pct;
+                    self.store.insert_early_warning_alert(&self.run_id, tick, &EarlyWarningAlert {
+                        alert_type: "settlement_shortfall".into(),
+                        severity: "high".into(),
+                        segment: Some(rail_id.clone()),
+                        metric_name: "settlement_shortfall".into(),
+                        current_value: shortfall,
+                        threshold_value: threshold,
+                        delta_pct: if funded > 0.0 { shortfall / funded } else { 1.0 },
+                    })?;
+                    tracing::warn!(
+                        "{rail_id} settlement shortfall \
+                         ${shortfall:.2} (funded ${funded:.2})"
+                    );
+                }
+                events.push(SimEvent::SettlementShortfall {
+                    tick,
+                    rail_id: rail_id.clone(),
+                    shortfall,
+                    borrowing_cost,
+                    alert,
+                });
+            }
+        }
+        Ok(events)
+    }
+
     // ── Aging and lifecycle management ────────────────────────────
 
     /// Process all open exceptions: emit SLA breach / escalation events as needed.
@@ -200,10 +557,14 @@ impl ReconciliationSubsystem {
                 .suspected_cause
                 .as_deref()
                 .map_or(false, |c| c == "timing");
+            let is_cutoff = ex
+                .suspected_cause
+                .as_deref()
+                .is_some_and(|c| c == "cutoff_timing");
             let matured = age_days >= 1; // Must wait at least 1 day per spec
             let within_threshold = ex.delta_amount < cfg.auto_clear_threshold;
 
-            if is_timing && matured && within_threshold {
+            if (is_timing || is_cutoff) && within_threshold && matured {
                 self.store.resolve_recon_exception(
                     &self.run_id,
                     &ex.exception_id,
@@ -231,6 +592,37 @@ impl ReconciliationSubsystem {
         Ok(events)
     }
 
+    /// Phase 4.80: staff work up to `capacity` breaks at least a day
+    /// old, oldest first, tracing each to its cause and correcting it.
+    fn investigate_exceptions(&self, current_tick: Tick, capacity: usize) -> SimResult<Vec<SimEvent>> {
+        let mut events = Vec::new();
+
+        let open = self.store.get_open_recon_exceptions(&self.run_id)?;
+        let workable: Vec<&ReconExceptionRow> =
+            open.iter().filter(|ex| ex.tick_detected < current_tick).take(capacity).collect();
+
+        for ex in &workable {
+            self.store.resolve_recon_exception(
+                &self.run_id,
+                &ex.exception_id,
+                current_tick,
+                "investigated",
+                "Investigated: break traced to its cause and corrected",
+                0.0,
+            )?;
+            events.push(SimEvent::ReconExceptionResolved {
+                tick: current_tick,
+                exception_id: ex.exception_id.clone(),
+                resolution_type: "investigated".into(),
+                write_off_amount: 0.0,
+            });
+        }
+        self.store
+            .record_staffing_work(&self.run_id, current_tick, QueueKind::Exceptions, workable.len())?;
+
+        Ok(events)
+    }
+
     // ── Weekly metrics snapshot ────────────────────────────────────
 
     fn compute_recon_metrics(&self, current_tick: Tick) -> SimResult<()> {
@@ -353,15 +745,34 @@ impl ReconciliationSubsystem {
     }
 }
 
+const MANIFEST: SubsystemManifest = SubsystemManifest {
+    version: 2,
+    produces: &[
+        "network_invoice_discrepancy", "network_invoice_received", "network_recovery_resolved",
+        "recon_exception_auto_cleared", "recon_exception_created", "recon_exception_escalated",
+        "recon_exception_resolved", "recon_exception_sla_breach", "settlement_shortfall",
+    ],
+    consumes: &["player_command_received"],
+    tables: &[
+        "early_warning_alert", "network_invoice", "recon_exception", "recon_metrics",
+        "regulatory_score_component", "settlement_account", "settlement_position", "staffing_snapshot",
+    ],
+    requires: &["payment_hub"],
+};
+
 impl SimSubsystem for ReconciliationSubsystem {
     fn name(&self) -> &'static str {
         "reconciliation"
     }
 
+    fn manifest(&self) -> SubsystemManifest {
+        MANIFEST
+    }
+
     fn update(
         &mut self,
         tick: Tick,
-        _events_in: &[SimEvent],
+        events_in: &[SimEvent],
         rng: &mut SubsystemRng,
     ) -> SimResult<Vec<SimEvent>> {
         let mut out_events = Vec::new();
@@ -379,9 +790,36 @@ impl SimSubsystem for ReconciliationSubsystem {
             out_events.extend(self.reconcile_rail(rail_id, settle_tick, tick, rng)?);
         }
 
+        // Phase 4.6: network invoice claims, expiries and monthly invoice
+        if self.network.enabled {
+            for event in events_in {
+                if let SimEvent::PlayerCommandReceived { command_id, .. } = event {
+                    if let Some(PlayerCommand::ClaimNetworkRecovery { invoice_id }) =
+                        self.store.get_player_command(&self.run_id, command_id)?
+                    {
+                        out_events.extend(self.claim_network_recovery(&invoice_id, tick, rng)?);
+                    }
+                }
+            }
+            out_events.extend(self.expire_network_recoveries(tick)?);
+            if tick.is_multiple_of(self.network.invoice_interval_ticks) {
+                out_events.extend(self.reconcile_network_invoice(tick, rng)?);
+            }
+        }
+
+        // Phase 4.7: settlement account funding positions for yesterday
+        if self.settlement.enabled {
+            out_events.extend(self.update_settlement_positions(settle_tick)?);
+        }
+
         // Auto-clear timing exceptions from previous days
         out_events.extend(self.auto_clear_exceptions(tick)?);
 
+        // Phase 4.80: staff work what auto-clear left
+        if let Some(capacity) = self.store.staffing_capacity(&self.run_id, tick, QueueKind::Exceptions)? {
+            out_events.extend(self.investigate_exceptions(tick, capacity)?);
+        }
+
         // Process aging: SLA breaches and escalations
         out_events.extend(self.process_aging_exceptions(tick)?);
 
@@ -393,14 +831,29 @@ impl SimSubsystem for ReconciliationSubsystem {
         // Regulatory exam pressure (runs every day, no-op unless thresholds hit)
         self.apply_recon_exam_pressure(tick)?;
 
-        log::debug!(
-            "tick={tick} reconciliation: {} events",
+        tracing::debug!(
+            "{} events",
             out_events.len()
         );
 
         Ok(out_events)
     }
 
+    fn save_state(&self) -> SimResult<Option<serde_json::Value>> {
+        Ok(Some(serde_json::json!({ "accounts_opened": self.accounts_opened })))
+    }
+
+    fn load_state(&mut self, state: serde_json::Value) -> SimResult<()> {
+        self.accounts_opened = state["accounts_opened"].as_bool().unwrap_or_default();
+        Ok(())
+    }
+
+    // Settlement accounts open with the first position, on tick 2
+    fn replay(&mut self, tick: Tick, _events: &[SimEvent]) -> SimResult<()> {
+        self.accounts_opened |= self.settlement.enabled && tick >= 2;
+        Ok(())
+    }
+
     fn as_any(&self) -> &dyn std::any::Any {
         self
     }
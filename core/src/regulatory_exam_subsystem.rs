@@ -5,23 +5,35 @@
 //! This subsystem:
 //!   1. Opens an exam cycle every `exam_interval_ticks` ticks.
 //!   2. During the exam window scans the event log for compliance
-//!      evidence (SLA breaches, SAR late filings, AML critical alerts).
+//!      evidence (SLA breaches, SAR late filings, AML critical alerts), and
+//!      looks back over the whole exam cycle for missed SAR filings and
+//!      for disputes and complaints filed without their required
+//!      documents or on deficient evidence bundles, and reviews
+//!      monitoring rule tunings in force for documentation.
 //!   3. At the end of the exam window issues findings, levies fines,
 //!      and optionally issues an MOU (Memorandum of Understanding).
+//!   4. Phase 4.81: with enforcement on, also samples the cycle's
+//!      complaints and SARs and weighs its fee changes and UDAAP rise,
+//!      opens a targeted CFPB exam early when UDAAP risk climbs, turns
+//!      findings into MRAs and consent orders, and works the lookbacks
+//!      those mandate (see `enforcement`).
 //!
 //! Downstream: the `ReputationSubsystem` reads `MOUReceived` and
-//!   `RegulatoryExamClosed` events from this tick's output.
+//!   `RegulatoryExamClosed` events from this tick's output; `PricingSubsystem`
+//!   holds fees at their soft limits while a consent order's fee cap is
+//!   in force.
 //!
 //! Execution: every tick, after TransactionMonitoring.
 //! Depends on: none (pulls from DB).
 
 use crate::{
-    config::RegulatoryExamConfig,
+    config::{EnforcementConfig, RegulatoryExamConfig},
+    enforcement::{Enforcement, RecordedFinding},
     error::SimResult,
     event::SimEvent,
     rng::SubsystemRng,
     store::{regulatory_exam::RegulatoryExamRow, SimStore},
-    subsystem::SimSubsystem,
+    subsystem::{SimSubsystem, SubsystemManifest},
     types::{RunId, Tick},
 };
 
@@ -57,6 +69,28 @@ fn derive_findings(
         run_id, tick_start, tick_end, "sar_late_filing",
     ).unwrap_or(0);
 
+    // Phase 4.16: missed filings are found by looking back over the cycle
+    let sar_missed: i64 = store.count_events_in_range(
+        run_id, tick_end.saturating_sub(config.exam_interval_ticks), tick_end, "sar_filing_missed",
+    ).unwrap_or(0);
+
+    // Phase 4.17: rule tunings in force without an approved tuning review
+    let undocumented_tunings = store
+        .active_rule_tunings(run_id)?
+        .iter()
+        .filter(|t| !t.documented)
+        .count();
+
+    // Phase 4.31: case files missing required documents
+    let undocumented_cases = store.cases_missing_documents(
+        run_id, tick_end.saturating_sub(config.exam_interval_ticks), tick_end,
+    )?;
+
+    // Phase 4.59: SARs and disputes filed on deficient evidence
+    let (_, deficient_filings) = store.evidence_bundle_counts(
+        run_id, tick_end.saturating_sub(config.exam_interval_ticks), tick_end,
+    )?;
+
     let total_breaches = sla_breaches + incident_sla_breaches;
 
     let mut findings: Vec<ExamFinding> = Vec::new();
@@ -86,6 +120,84 @@ fn derive_findings(
         if spec.severity == "critical" { critical_count += 1; }
     }
 
+    // Missed SAR findings (Phase 4.16)
+    if sar_missed > 0 {
+        let spec = match sar_missed {
+            1..=2 => FindingSpec { category: "sar_missed_filing", severity: "major",
+                description: "Reportable activity left unfiled past the SAR deadline" },
+            _ => FindingSpec { category: "sar_missed_filing", severity: "critical",
+                description: "Systemic failure to file SARs on reportable activity" },
+        };
+        let fine = fine_for_severity(spec.severity, config);
+        let finding_id = format!("fnd-{}-sarm-{}", exam_id, rng.next_u64() % 100000);
+        findings.push(ExamFinding {
+            finding_id,
+            category: spec.category.into(),
+            severity: spec.severity.into(),
+            description: spec.description.into(),
+            fine_amount: fine,
+        });
+        fine_total += fine;
+        if spec.severity == "critical" { critical_count += 1; }
+    }
+
+    // Undocumented suppression findings (Phase 4.17)
+    if undocumented_tunings > 0 {
+        let spec = FindingSpec { category: "undocumented_suppression", severity: "critical",
+            description: "Monitoring rules suppressed or tuned without a documented tuning review" };
+        let fine = fine_for_severity(spec.severity, config);
+        let finding_id = format!("fnd-{}-tune-{}", exam_id, rng.next_u64() % 100000);
+        findings.push(ExamFinding {
+            finding_id,
+            category: spec.category.into(),
+            severity: spec.severity.into(),
+            description: spec.description.into(),
+            fine_amount: fine,
+        });
+        fine_total += fine;
+        critical_count += 1;
+    }
+
+    // Missing case documentation findings (Phase 4.31)
+    if undocumented_cases > 0 {
+        let spec = match undocumented_cases {
+            1..=5 => FindingSpec { category: "case_documentation", severity: "moderate",
+                description: "Dispute and complaint files missing required documents" },
+            _ => FindingSpec { category: "case_documentation", severity: "major",
+                description: "Widespread gaps in dispute and complaint documentation" },
+        };
+        let fine = fine_for_severity(spec.severity, config);
+        let finding_id = format!("fnd-{}-doc-{}", exam_id, rng.next_u64() % 100000);
+        findings.push(ExamFinding {
+            finding_id,
+            category: spec.category.into(),
+            severity: spec.severity.into(),
+            description: spec.description.into(),
+            fine_amount: fine,
+        });
+        fine_total += fine;
+    }
+
+    // Filing quality findings
+    if deficient_filings > 0 {
+        let spec = match deficient_filings {
+            1..=5 => FindingSpec { category: "filing_quality", severity: "moderate",
+                description: "SAR and dispute filings lacking supporting evidence" },
+            _ => FindingSpec { category: "filing_quality", severity: "major",
+                description: "Filings routinely made without the evidence to support them" },
+        };
+        let fine = fine_for_severity(spec.severity, config);
+        let finding_id = format!("fnd-{}-evb-{}", exam_id, rng.next_u64() % 100000);
+        findings.push(ExamFinding {
+            finding_id,
+            category: spec.category.into(),
+            severity: spec.severity.into(),
+            description: spec.description.into(),
+            fine_amount: fine,
+        });
+        fine_total += fine;
+    }
+
     // Complaint SLA findings
     if total_breaches > 10 {
         let spec = if total_breaches > 50 {
@@ -162,11 +274,18 @@ pub struct RegulatoryExamSubsystem {
     config:       RegulatoryExamConfig,
     store:        SimStore,
     examiner_idx: usize,
+    enforcement:  Enforcement,
 }
 
 impl RegulatoryExamSubsystem {
-    pub fn new(run_id: RunId, config: RegulatoryExamConfig, store: SimStore) -> Self {
-        Self { run_id, config, store, examiner_idx: 0 }
+    pub fn new(
+        run_id: RunId,
+        config: RegulatoryExamConfig,
+        enforcement: EnforcementConfig,
+        store: SimStore,
+    ) -> Self {
+        let enforcement = Enforcement::new(run_id.clone(), enforcement);
+        Self { run_id, config, store, examiner_idx: 0, enforcement }
     }
 
     fn next_examiner(&mut self) -> String {
@@ -178,7 +297,7 @@ impl RegulatoryExamSubsystem {
         name
     }
 
-    /// Open a new exam at this tick.
+    /// Open the next scheduled exam at this tick.
     fn open_exam(&mut self, tick: Tick, rng: &mut SubsystemRng) -> SimResult<Vec<SimEvent>> {
         let examiner = self.next_examiner();
         // Alternate scope round-robin from the examiner index
@@ -189,6 +308,16 @@ impl RegulatoryExamSubsystem {
         } else {
             "full"
         };
+        self.start_exam(tick, examiner, scope, rng)
+    }
+
+    fn start_exam(
+        &self,
+        tick: Tick,
+        examiner: String,
+        scope: &str,
+        rng: &mut SubsystemRng,
+    ) -> SimResult<Vec<SimEvent>> {
         let exam_id = format!(
             "exam-{}-{}-{}",
             examiner.to_lowercase(),
@@ -200,7 +329,7 @@ impl RegulatoryExamSubsystem {
             &self.run_id, &exam_id, tick, &examiner, scope,
         )?;
 
-        log::info!("tick={tick} regulatory exam opened: {exam_id} ({examiner} / {scope})");
+        tracing::info!("regulatory exam opened: {exam_id} ({examiner} / {scope})");
 
         Ok(vec![SimEvent::RegulatoryExamStarted {
             tick,
@@ -219,7 +348,7 @@ impl RegulatoryExamSubsystem {
     ) -> SimResult<Vec<SimEvent>> {
         let mut events = Vec::new();
 
-        let (findings, fine_total, critical_count) = derive_findings(
+        let (mut findings, mut fine_total, mut critical_count) = derive_findings(
             &self.run_id,
             &exam.exam_id,
             &self.store,
@@ -229,6 +358,25 @@ impl RegulatoryExamSubsystem {
             rng,
         )?;
 
+        // Phase 4.81: the exam's own testing of the cycle
+        let cycle_start = tick.saturating_sub(self.config.exam_interval_ticks);
+        if self.enforcement.enabled() {
+            for t in self.enforcement.test_cycle(&self.store, &exam.exam_id, cycle_start, tick, rng)? {
+                let fine = fine_for_severity(t.severity, &self.config);
+                findings.push(ExamFinding {
+                    finding_id: format!("fnd-{}-{}-{}", exam.exam_id, t.category, rng.next_u64() % 100000),
+                    category: t.category.into(),
+                    severity: t.severity.into(),
+                    description: t.description.into(),
+                    fine_amount: fine,
+                });
+                fine_total += fine;
+                if t.severity == "critical" {
+                    critical_count += 1;
+                }
+            }
+        }
+
         // Persist findings
         for f in &findings {
             self.store.insert_exam_finding(
@@ -251,6 +399,19 @@ impl RegulatoryExamSubsystem {
             });
         }
 
+        if self.enforcement.enabled() {
+            let recorded: Vec<RecordedFinding> = findings
+                .iter()
+                .map(|f| RecordedFinding {
+                    finding_id:  &f.finding_id,
+                    category:    &f.category,
+                    severity:    &f.severity,
+                    fine_amount: f.fine_amount,
+                })
+                .collect();
+            events.extend(self.enforcement.act_on_exam(&self.store, &exam.exam_id, &recorded, cycle_start, tick)?);
+        }
+
         let mou_issued = critical_count >= self.config.mou_critical_threshold;
         let finding_count = findings.len() as i64;
 
@@ -279,13 +440,13 @@ impl RegulatoryExamSubsystem {
                 examiner: exam.examiner.clone(),
                 fine_total,
             });
-            log::warn!(
-                "tick={tick} MOU issued by {} — {} findings, ${:.0} total fines",
+            tracing::warn!(
+                "MOU issued by {} — {} findings, ${:.0} total fines",
                 exam.examiner, finding_count, fine_total
             );
         } else {
-            log::info!(
-                "tick={tick} exam {} closed: {} findings, ${:.0}",
+            tracing::info!(
+                "exam {} closed: {} findings, ${:.0}",
                 exam.exam_id, finding_count, fine_total
             );
         }
@@ -294,11 +455,26 @@ impl RegulatoryExamSubsystem {
     }
 }
 
+const MANIFEST: SubsystemManifest = SubsystemManifest {
+    version: 2,
+    produces: &[
+        "enforcement_action_issued", "enforcement_action_lifted", "exam_finding_recorded", "lookback_completed",
+        "mou_received", "regulatory_exam_closed", "regulatory_exam_started",
+    ],
+    consumes: &[],
+    tables: &["enforcement_action", "exam_finding", "exam_sample", "regulatory_exam"],
+    requires: &[],
+};
+
 impl SimSubsystem for RegulatoryExamSubsystem {
     fn name(&self) -> &'static str {
         "regulatory_exam"
     }
 
+    fn manifest(&self) -> SubsystemManifest {
+        MANIFEST
+    }
+
     fn update(
         &mut self,
         tick:       Tick,
@@ -327,9 +503,37 @@ impl SimSubsystem for RegulatoryExamSubsystem {
             }
         }
 
+        // 3. Phase 4.81: climbing UDAAP risk brings the CFPB in early.
+        if self.store.get_open_exam(&self.run_id)?.is_none() && self.enforcement.udaap_exam_due(&self.store)? {
+            out.extend(self.start_exam(tick, "CFPB".into(), "targeted_udaap", rng)?);
+        }
+
+        // 4. Mandated lookbacks work through their items.
+        if self.enforcement.enabled() {
+            out.extend(self.enforcement.work_lookbacks(&self.store, tick)?);
+        }
+
         Ok(out)
     }
 
+    fn save_state(&self) -> SimResult<Option<serde_json::Value>> {
+        Ok(Some(serde_json::json!({ "examiner_idx": self.examiner_idx })))
+    }
+
+    fn load_state(&mut self, state: serde_json::Value) -> SimResult<()> {
+        self.examiner_idx = serde_json::from_value(state["examiner_idx"].clone())?;
+        Ok(())
+    }
+
+    fn replay(&mut self, _tick: Tick, events: &[SimEvent]) -> SimResult<()> {
+        // Only scheduled exams advance the examiner rotation
+        let opened = events.iter().filter(|e| {
+            matches!(e, SimEvent::RegulatoryExamStarted { scope, .. } if scope != "targeted_udaap")
+        });
+        self.examiner_idx += opened.count();
+        Ok(())
+    }
+
     fn as_any(&self) -> &dyn std::any::Any {
         self
     }
@@ -0,0 +1,282 @@
+//! Remittance corridors for international customers (Phase 4.36).
+//!
+//! Each international customer is profiled once, on their first live
+//! tick with an account. Most whose citizenship has a corridor remit home:
+//! a family remittance of around the corridor's typical amount, every
+//! corridor interval, to one of a few fixed beneficiaries. A small share
+//! instead layer funds through the account: an inbound wire every week or
+//! so, fanned straight out to fresh beneficiaries across medium- and
+//! high-risk corridors.
+//!
+//! REMIT_LAYERING tells the two apart on what monitoring can see: fan-out
+//! across beneficiaries or corridors while most of what comes in goes
+//! straight back out. The profile's pattern is ground truth, kept for
+//! analysis of the export.
+
+use crate::{
+    config::{RemittanceConfig, RemittanceCorridorConfig},
+    error::SimResult,
+    event::SimEvent,
+    rng::SubsystemRng,
+    store::{
+        remittance::{RemittanceActivityRow, RemittanceProfileRow, RemittanceTransferRow},
+        AccountRow, SimStore,
+    },
+    types::{RunId, Tick},
+};
+use std::collections::HashMap;
+use uuid::Uuid;
+
+pub struct Remittance {
+    run_id: RunId,
+    config: RemittanceConfig,
+}
+
+impl Remittance {
+    pub fn new(run_id: RunId, config: RemittanceConfig) -> Self {
+        Self { run_id, config }
+    }
+
+    pub fn enabled(&self) -> bool {
+        self.config.enabled
+    }
+
+    pub fn config(&self) -> &RemittanceConfig {
+        &self.config
+    }
+
+    pub fn corridor(&self, country: &str) -> Option<&RemittanceCorridorConfig> {
+        self.config.corridors.iter().find(|c| c.country == country)
+    }
+
+    /// Profile new international customers, then send the remittances
+    /// falling due today.
+    pub fn process(
+        &self,
+        store: &SimStore,
+        accounts: &[AccountRow],
+        tick: Tick,
+        rng: &mut SubsystemRng,
+    ) -> SimResult<Vec<SimEvent>> {
+        let mut first_account: HashMap<&str, &str> = HashMap::new();
+        for acct in accounts {
+            first_account
+                .entry(acct.customer_id.as_str())
+                .or_insert(acct.account_id.as_str());
+        }
+
+        let mut events = self.enroll(store, &first_account, tick, rng)?;
+
+        let mut transfers = 0;
+        let mut amount = 0.0;
+        for profile in store.remittance_profiles_due(&self.run_id, tick)? {
+            // Closed and restricted accounts send nothing; a restricted
+            // sender tries again once lifted
+            if !accounts.iter().any(|a| a.account_id == profile.account_id) {
+                continue;
+            }
+            let sent = if profile.pattern == "layering" {
+                self.layer(store, &profile, tick, rng)?
+            } else {
+                self.remit_home(store, &profile, tick, rng)?
+            };
+            transfers += sent.len();
+            amount += sent.iter().sum::<f64>();
+            store.set_remittance_next_tick(&self.run_id, &profile.customer_id, tick + profile.interval_ticks)?;
+        }
+
+        if transfers > 0 {
+            events.push(SimEvent::RemittancesSent {
+                tick,
+                transfers: transfers as i64,
+                amount,
+            });
+        }
+        Ok(events)
+    }
+
+    /// Decide, once, how each new international customer remits.
+    fn enroll(
+        &self,
+        store: &SimStore,
+        first_account: &HashMap<&str, &str>,
+        tick: Tick,
+        rng: &mut SubsystemRng,
+    ) -> SimResult<Vec<SimEvent>> {
+        let mut events = Vec::new();
+        for (customer_id, country) in store.unprofiled_international_customers(&self.run_id)? {
+            let Some(account_id) = first_account.get(customer_id.as_str()) else {
+                continue;
+            };
+            let corridor = self.corridor(&country);
+            let remits = corridor.is_some() && rng.chance(self.config.participation_rate);
+            let mut profile = RemittanceProfileRow {
+                customer_id: customer_id.clone(),
+                account_id: account_id.to_string(),
+                corridor: country,
+                pattern: "none".into(),
+                amount: 0.0,
+                interval_ticks: 0,
+                beneficiaries: 0,
+                next_tick: tick,
+                tick_enrolled: tick,
+            };
+            if let Some(corridor) = corridor.filter(|_| remits) {
+                if rng.chance(self.config.layering_rate) {
+                    profile.pattern = "layering".into();
+                    profile.amount = corridor.typical_amount * self.config.layering_inflow_multiple;
+                    profile.interval_ticks = self.config.layering_interval_ticks;
+                    profile.beneficiaries = self.config.layering_fanout;
+                } else {
+                    profile.pattern = "legitimate".into();
+                    profile.amount = corridor.typical_amount * (0.7 + rng.next_f64() * 0.6);
+                    profile.interval_ticks = corridor.interval_ticks;
+                    profile.beneficiaries =
+                        1 + rng.next_u64_below(self.config.max_family_beneficiaries.max(1) as u64) as u32;
+                }
+                // Stagger the first send across the interval
+                profile.next_tick = tick + 1 + rng.next_u64_below(profile.interval_ticks.max(1));
+                events.push(SimEvent::RemittanceCorridorOpened {
+                    tick,
+                    customer_id: customer_id.clone(),
+                    corridor: corridor.country.clone(),
+                    risk_rating: corridor.risk_rating.clone(),
+                });
+            }
+            store.insert_remittance_profile(&self.run_id, &profile)?;
+        }
+        Ok(events)
+    }
+
+    /// A family remittance to one of the customer's beneficiaries, if the
+    /// balance covers it. Returns the amounts sent.
+    fn remit_home(
+        &self,
+        store: &SimStore,
+        profile: &RemittanceProfileRow,
+        tick: Tick,
+        rng: &mut SubsystemRng,
+    ) -> SimResult<Vec<f64>> {
+        let amount = profile.amount * (1.0 + (rng.next_f64() - 0.5) * 0.2);
+        if store.account_balance(&self.run_id, &profile.account_id)? < amount {
+            return Ok(Vec::new());
+        }
+        let beneficiary = rng.next_u64_below(profile.beneficiaries.max(1) as u64);
+        let counterparty = format!("remit-{}-{}-{beneficiary}", profile.corridor, profile.customer_id);
+        self.transfer(store, profile, "outbound", &profile.corridor, &counterparty, amount, tick)?;
+        Ok(vec![amount])
+    }
+
+    /// One layering cycle: an inbound wire fanned out, less a skim, to
+    /// fresh beneficiaries across medium- and high-risk corridors.
+    fn layer(
+        &self,
+        store: &SimStore,
+        profile: &RemittanceProfileRow,
+        tick: Tick,
+        rng: &mut SubsystemRng,
+    ) -> SimResult<Vec<f64>> {
+        let inflow = profile.amount * (1.0 + (rng.next_f64() - 0.5) * 0.3);
+        let sender = format!("remit-in-{}", rng.next_u64_below(100_000));
+        self.transfer(store, profile, "inbound", &profile.corridor, &sender, inflow, tick)?;
+
+        let risky: Vec<&str> = self
+            .config
+            .corridors
+            .iter()
+            .filter(|c| c.risk_rating != "low")
+            .map(|c| c.country.as_str())
+            .collect();
+        let fanout = profile.beneficiaries.max(1);
+        let each = inflow * 0.95 / fanout as f64;
+        let mut sent = Vec::new();
+        for _ in 0..fanout {
+            let corridor = if risky.is_empty() {
+                profile.corridor.as_str()
+            } else {
+                risky[rng.next_u64_below(risky.len() as u64) as usize]
+            };
+            let counterparty = format!("remit-{corridor}-{}", rng.next_u64_below(100_000));
+            let amount = each * (0.9 + rng.next_f64() * 0.2);
+            self.transfer(store, profile, "outbound", corridor, &counterparty, amount, tick)?;
+            sent.push(amount);
+        }
+        Ok(sent)
+    }
+
+    /// Book a remittance wire on the account and record it.
+    #[allow(clippy::too_many_arguments)]
+    fn transfer(
+        &self,
+        store: &SimStore,
+        profile: &RemittanceProfileRow,
+        direction: &str,
+        corridor: &str,
+        counterparty: &str,
+        amount: f64,
+        tick: Tick,
+    ) -> SimResult<()> {
+        let txn_id = Uuid::new_v4().to_string();
+        let (txn_direction, category, delta) = if direction == "inbound" {
+            ("credit", "remittance_in", amount)
+        } else {
+            ("debit", "remittance", -amount)
+        };
+        store.insert_transaction_with_rail(
+            &self.run_id,
+            &txn_id,
+            &profile.account_id,
+            tick,
+            amount,
+            txn_direction,
+            category,
+            Some(counterparty),
+            "wire",
+            "settled",
+        )?;
+        store.update_account_balance(&self.run_id, &profile.account_id, delta)?;
+        store.insert_remittance_transfer(&self.run_id, &RemittanceTransferRow {
+            txn_id,
+            customer_id: profile.customer_id.clone(),
+            account_id: profile.account_id.clone(),
+            tick,
+            direction: direction.to_string(),
+            corridor: corridor.to_string(),
+            counterparty: counterparty.to_string(),
+            amount,
+        })
+    }
+
+    /// Remittances out as a share of everything credited; 0 with no
+    /// credits.
+    pub fn pass_through(activity: &RemittanceActivityRow) -> f64 {
+        if activity.credits > 0.0 {
+            activity.sent / activity.credits
+        } else {
+            0.0
+        }
+    }
+
+    /// REMIT_LAYERING score for a customer's window of activity, or None
+    /// when it reads as ordinary remittance: fan-out alone is a large
+    /// family, pass-through alone is a customer who sends home what they
+    /// earn. The riskiest corridor used lifts the score.
+    pub fn layering_score(&self, activity: &RemittanceActivityRow) -> Option<f64> {
+        let fans_out = activity.beneficiaries >= self.config.min_beneficiaries
+            || activity.corridors.len() as i64 >= self.config.min_corridors;
+        if !fans_out || Self::pass_through(activity) < self.config.pass_through_threshold {
+            return None;
+        }
+        let uplift = activity
+            .corridors
+            .iter()
+            .filter_map(|c| self.corridor(c))
+            .map(|c| match c.risk_rating.as_str() {
+                "high" => self.config.high_risk_uplift,
+                "medium" => self.config.medium_risk_uplift,
+                _ => 0.0,
+            })
+            .fold(0.0, f64::max);
+        Some(self.config.alert_score + uplift)
+    }
+}
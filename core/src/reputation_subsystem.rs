@@ -8,6 +8,10 @@
 //! The score gates onboarding in CustomerSubsystem (future hook) and is
 //! surfaced to the UI via the event stream for the PlayerKPI panel.
 //!
+//! Phase 4.69: once the score is set, the subsystem also scores the
+//! composite stress index, which reads it (see `stress_index`). Each is
+//! enabled on its own.
+//!
 //! Execution: every tick, last in registration order (reads all
 //!   signals from the current tick's event list).
 //! Depends on: reads events_in for MOUReceived, RegulatoryExamClosed,
@@ -19,19 +23,21 @@ use crate::{
     event::SimEvent,
     rng::SubsystemRng,
     store::SimStore,
-    subsystem::SimSubsystem,
+    stress_index::StressIndex,
+    subsystem::{SimSubsystem, SubsystemManifest},
     types::{RunId, Tick},
 };
 
 pub struct ReputationSubsystem {
     run_id: RunId,
     config: ReputationConfig,
+    stress: StressIndex,
     store:  SimStore,
 }
 
 impl ReputationSubsystem {
-    pub fn new(run_id: RunId, config: ReputationConfig, store: SimStore) -> Self {
-        Self { run_id, config, store }
+    pub fn new(run_id: RunId, config: ReputationConfig, stress: StressIndex, store: SimStore) -> Self {
+        Self { run_id, config, stress, store }
     }
 
     /// Scan this tick's events and compute the total reputation delta.
@@ -115,11 +121,26 @@ impl ReputationSubsystem {
     }
 }
 
+const MANIFEST: SubsystemManifest = SubsystemManifest {
+    version: 1,
+    produces: &["reputation_updated", "resilience_level_changed", "stress_index_computed"],
+    consumes: &[
+        "incident_sla_breach", "mou_received", "regulatory_exam_closed", "sar_late_filing",
+        "sla_breached",
+    ],
+    tables: &["reputation_event", "reputation_snapshot", "stress_index"],
+    requires: &[],
+};
+
 impl SimSubsystem for ReputationSubsystem {
     fn name(&self) -> &'static str {
         "reputation"
     }
 
+    fn manifest(&self) -> SubsystemManifest {
+        MANIFEST
+    }
+
     fn update(
         &mut self,
         tick:       Tick,
@@ -128,18 +149,35 @@ impl SimSubsystem for ReputationSubsystem {
     ) -> SimResult<Vec<SimEvent>> {
         let mut out = Vec::new();
 
-        if !self.config.enabled {
-            return Ok(out);
-        }
-
         // Tick 0: seed the initial score snapshot.
         if tick == 0 {
-            self.store.insert_reputation_snapshot(
-                &self.run_id, 0, self.config.initial_score, 0.0,
-            )?;
+            if self.config.enabled {
+                self.store.insert_reputation_snapshot(
+                    &self.run_id, 0, self.config.initial_score, 0.0,
+                )?;
+            }
             return Ok(out);
         }
 
+        let score = if self.config.enabled {
+            Some(self.update_score(tick, events_in, &mut out)?)
+        } else {
+            None
+        };
+        if self.stress.enabled() {
+            out.extend(self.stress.compute(&self.store, tick, score)?);
+        }
+        Ok(out)
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+}
+
+impl ReputationSubsystem {
+    /// Move the score by this tick's signals and record it.
+    fn update_score(&self, tick: Tick, events_in: &[SimEvent], out: &mut Vec<SimEvent>) -> SimResult<f64> {
         let (total_delta, drivers) = self.compute_delta(tick, events_in)?;
 
         // Apply delta and clamp.
@@ -170,19 +208,15 @@ impl SimSubsystem for ReputationSubsystem {
         });
 
         if actual_delta < -2.0 {
-            log::warn!(
-                "tick={tick} reputation drop: {prev:.1} -> {new_score:.1} ({actual_delta:+.2})"
+            tracing::warn!(
+                "reputation drop: {prev:.1} -> {new_score:.1} ({actual_delta:+.2})"
             );
         } else {
-            log::debug!(
-                "tick={tick} reputation: {new_score:.1} ({actual_delta:+.2})"
+            tracing::debug!(
+                "{new_score:.1} ({actual_delta:+.2})"
             );
         }
 
-        Ok(out)
-    }
-
-    fn as_any(&self) -> &dyn std::any::Any {
-        self
+        Ok(new_score)
     }
 }
@@ -10,7 +10,7 @@
 
 use crate::{
     command::PlayerCommand, config::SimConfig, error::SimResult, event::SimEvent,
-    rng::SubsystemRng, store::SimStore, subsystem::SimSubsystem, types::Tick,
+    rng::SubsystemRng, store::SimStore, subsystem::{SimSubsystem, SubsystemManifest}, types::Tick,
 };
 use serde::{Deserialize, Serialize};
 
@@ -265,8 +265,8 @@ impl RiskAppetiteSubsystem {
                         severity: severity.to_string(),
                     });
 
-                    log::warn!(
-                        "tick={tick} BOARD PRESSURE: {} - {}",
+                    tracing::warn!(
+                        "BOARD PRESSURE: {} - {}",
                         violation_key,
                         message
                     );
@@ -278,11 +278,23 @@ impl RiskAppetiteSubsystem {
     }
 }
 
+const MANIFEST: SubsystemManifest = SubsystemManifest {
+    version: 1,
+    produces: &["board_pressure_fired", "risk_dial_changed", "risk_dial_rejected"],
+    consumes: &["player_command_received"],
+    tables: &["board_pressure_event", "dial_change_log", "risk_appetite_state"],
+    requires: &[],
+};
+
 impl SimSubsystem for RiskAppetiteSubsystem {
     fn name(&self) -> &'static str {
         "risk_appetite"
     }
 
+    fn manifest(&self) -> SubsystemManifest {
+        MANIFEST
+    }
+
     fn update(
         &mut self,
         tick: Tick,
@@ -327,8 +339,8 @@ impl SimSubsystem for RiskAppetiteSubsystem {
                                 },
                             });
 
-                            log::info!(
-                                "tick={tick} risk: {} changed {:.2} -> {:.2}",
+                            tracing::info!(
+                                "{} changed {:.2} -> {:.2}",
                                 dial_id,
                                 old_value,
                                 new_value
@@ -363,8 +375,8 @@ impl SimSubsystem for RiskAppetiteSubsystem {
             let pressure_events = self.fire_board_pressure(&violations, tick)?;
             out_events.extend(pressure_events);
 
-            log::debug!(
-                "tick={tick} risk: profile={} score={:.2} violations={}",
+            tracing::debug!(
+                "profile={} score={:.2} violations={}",
                 self.state.risk_level,
                 self.state.overall_risk_score,
                 self.state.comfort_zone_violations
@@ -374,6 +386,26 @@ impl SimSubsystem for RiskAppetiteSubsystem {
         Ok(out_events)
     }
 
+    // The risk profile is recomputed on snapshot ticks; only dial
+    // changes fall between them
+    fn save_state(&self) -> SimResult<Option<serde_json::Value>> {
+        Ok(Some(serde_json::to_value(&self.state)?))
+    }
+
+    fn load_state(&mut self, state: serde_json::Value) -> SimResult<()> {
+        self.state = serde_json::from_value(state)?;
+        Ok(())
+    }
+
+    fn replay(&mut self, _tick: Tick, events: &[SimEvent]) -> SimResult<()> {
+        for event in events {
+            if let SimEvent::RiskDialChanged { dial_id, new_value, .. } = event {
+                self.state.set_dial_value(dial_id, *new_value);
+            }
+        }
+        Ok(())
+    }
+
     fn as_any(&self) -> &dyn std::any::Any {
         self
     }
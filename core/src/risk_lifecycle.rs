@@ -0,0 +1,59 @@
+//! Risk-tiered customer lifecycle (Phase 4.13).
+//!
+//! A customer's composite tier sets how they are serviced: how often they
+//! are reviewed, what a review costs, and how sensitive transaction
+//! monitoring is to them (limits follow the tier through the Phase 4.12
+//! tier-scoped rules). Behavior re-scores move a customer up as soon as
+//! the signals warrant it; they only come back down, never below their
+//! onboarding tier, when an EDD review finds the behavior has subsided.
+
+use crate::config::{RiskLifecycleConfig, RiskTierServicing};
+use crate::store::risk_lifecycle::BehaviorSignals;
+
+/// Composite tiers, lowest risk first.
+pub const TIERS: &[&str] = &["low", "medium", "high", "critical"];
+
+/// Position of `tier` in `TIERS`; unknown tiers rank as low.
+pub fn tier_rank(tier: &str) -> usize {
+    TIERS.iter().position(|t| *t == tier).unwrap_or(0)
+}
+
+/// The riskier of two tiers.
+pub fn higher_tier<'a>(a: &'a str, b: &'a str) -> &'a str {
+    if tier_rank(b) > tier_rank(a) { b } else { a }
+}
+
+/// Behavior risk from the signals in the lookback window, capped at 1.0.
+pub fn behavior_risk(signals: &BehaviorSignals, config: &RiskLifecycleConfig) -> f64 {
+    (signals.alerts as f64 * config.alert_weight
+        + signals.declines as f64 * config.decline_weight
+        + signals.unauthorized as f64 * config.unauthorized_weight)
+        .min(1.0)
+}
+
+/// The tier the behavior score alone puts a customer in.
+pub fn behavior_tier(behavior_risk: f64, config: &RiskLifecycleConfig) -> &'static str {
+    if behavior_risk >= config.critical_threshold {
+        "critical"
+    } else if behavior_risk >= config.high_threshold {
+        "high"
+    } else if behavior_risk >= config.medium_threshold {
+        "medium"
+    } else {
+        "low"
+    }
+}
+
+/// Servicing terms for `tier`, falling back to the lowest tier's.
+pub fn servicing<'a>(config: &'a RiskLifecycleConfig, tier: &str) -> Option<&'a RiskTierServicing> {
+    config
+        .tiers
+        .iter()
+        .find(|s| s.tier == tier)
+        .or_else(|| config.tiers.iter().min_by_key(|s| tier_rank(&s.tier)))
+}
+
+/// Scale applied to monitoring thresholds for `tier`.
+pub fn monitoring_multiplier(config: &RiskLifecycleConfig, tier: &str) -> f64 {
+    servicing(config, tier).map_or(1.0, |s| s.monitoring_multiplier)
+}
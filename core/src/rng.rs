@@ -115,6 +115,13 @@ pub enum SubsystemSlot {
     TransactionMonitoring = 19, // Phase 3.5 Week 5
     RegulatoryExam = 20,     // Phase 3.6
     Reputation = 21,         // Phase 3.6
+    CreditBureau = 22,       // Phase 4.1
+    Branch = 23,             // Phase 4.3
+    FairLending = 24,        // Phase 4.4
+    Outreach = 25,           // Phase 4.32
+    Staffing = 26,           // Phase 4.80
+    Lending = 27,            // Phase 4.82
+    Case = 28,               // Phase 4.86
                              // Add new subsystems here — append only.
 }
 
@@ -143,6 +150,13 @@ impl SubsystemSlot {
             Self::TransactionMonitoring => "transaction_monitoring",
             Self::RegulatoryExam => "regulatory_exam",
             Self::Reputation => "reputation",
+            Self::CreditBureau => "credit_bureau",
+            Self::Branch => "branch",
+            Self::FairLending => "fair_lending",
+            Self::Outreach => "outreach",
+            Self::Staffing => "staffing",
+            Self::Lending => "lending",
+            Self::Case => "case",
         }
     }
 }
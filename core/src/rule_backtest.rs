@@ -0,0 +1,315 @@
+//! Monitoring rule backtests (Phase 4.72).
+//!
+//! Before deploying a rule, a player can replay it over the run's stored
+//! transactions and see what it would have done. The rule is evaluated
+//! at every tick of the window, over the same trailing lookback the live
+//! detectors use, so a customer whose activity stays over the line is
+//! alerted on each tick it does, as they would be live.
+//!
+//! The report sets what the rule flags against two references:
+//! - the run's own AML alerts, by customer: flagged by both, by the rule
+//!   only (new coverage) or live only (what the rule would not replace),
+//! - ground truth: the fraud cases the post-mortem reconstructs, active
+//!   in the window. Hit rate is the share of flagged customers who were
+//!   in one; recall the share of those customers the rule flagged.
+//!
+//! Nothing is written; the backtest reads the store only.
+
+use crate::{
+    error::{SimError, SimResult},
+    post_mortem::PostMortem,
+    store::SimStore,
+    transaction_monitoring_subsystem::TransactionRow,
+    types::Tick,
+};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+
+/// Alerts listed in a report; the counts cover them all.
+pub const MAX_LISTED_ALERTS: usize = 500;
+
+/// Longest lookback a backtested rule may use.
+pub const MAX_LOOKBACK_TICKS: Tick = 90;
+
+/// A proposed rule, tagged by `kind`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum RuleDefinition {
+    /// At least `min_count` transactions of `min_amount` up to, not
+    /// including, `max_amount` in the lookback. The live STRUCT_9K rule
+    /// is 9,000–10,000, 3 transactions, 7 ticks.
+    Structuring { min_amount: f64, max_amount: f64, min_count: usize, lookback_ticks: Tick },
+    /// At least `min_amount` moved, in or out, over at least `min_count`
+    /// transactions in the lookback. The live VEL_50K_7D rule alerts on
+    /// more than 50,000 over more than 10 transactions in 7 ticks.
+    Velocity { min_amount: f64, min_count: usize, lookback_ticks: Tick },
+    /// A credit of at least `min_amount` followed on the same account by
+    /// a debit of at least as much within `window_ticks`. The live
+    /// RAPID_MOVE rule is 5,000 within 1 tick.
+    RapidMovement { min_amount: f64, window_ticks: Tick },
+    /// A single transaction of at least `min_amount`, optionally in one
+    /// `direction` ("credit" or "debit").
+    LargeTransaction {
+        min_amount: f64,
+        #[serde(default)]
+        direction: Option<String>,
+    },
+}
+
+impl RuleDefinition {
+    fn lookback(&self) -> Tick {
+        match self {
+            Self::Structuring { lookback_ticks, .. } | Self::Velocity { lookback_ticks, .. } => *lookback_ticks,
+            Self::RapidMovement { window_ticks, .. } => *window_ticks,
+            Self::LargeTransaction { .. } => 0,
+        }
+    }
+
+    fn validate(&self) -> SimResult<()> {
+        let refuse = |reason: String| Err(SimError::command("backtest_rule", reason));
+        let amounts = match self {
+            Self::Structuring { min_amount, max_amount, .. } => vec![*min_amount, *max_amount],
+            Self::Velocity { min_amount, .. }
+            | Self::RapidMovement { min_amount, .. }
+            | Self::LargeTransaction { min_amount, .. } => vec![*min_amount],
+        };
+        if amounts.iter().any(|a| !a.is_finite() || *a < 0.0) {
+            return refuse("amounts must be finite and not negative".into());
+        }
+        if self.lookback() > MAX_LOOKBACK_TICKS {
+            return refuse(format!("lookback is at most {MAX_LOOKBACK_TICKS} ticks"));
+        }
+        match self {
+            Self::Structuring { min_amount, max_amount, .. } if max_amount <= min_amount => {
+                refuse("max_amount must be above min_amount".into())
+            }
+            Self::Structuring { min_count: 0, .. } | Self::Velocity { min_count: 0, .. } => {
+                refuse("min_count must be at least 1".into())
+            }
+            Self::LargeTransaction { direction: Some(d), .. } if d != "credit" && d != "debit" => {
+                refuse(format!("direction must be credit or debit, not '{d}'"))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Customers the rule flags at `tick`, given the transactions of the
+    /// trailing lookback, oldest first.
+    fn evaluate(&self, tick: Tick, txns: &[&TransactionRow]) -> Vec<BacktestAlert> {
+        let mut alerts = Vec::new();
+        match self {
+            Self::Structuring { min_amount, max_amount, min_count, .. } => {
+                let hits = txns.iter().filter(|t| t.amount.abs() >= *min_amount && t.amount.abs() < *max_amount);
+                for (customer_id, hits) in by_customer(hits) {
+                    if hits.len() >= *min_count {
+                        alerts.push(BacktestAlert::new(tick, customer_id, None, &hits));
+                    }
+                }
+            }
+            Self::Velocity { min_amount, min_count, .. } => {
+                for (customer_id, hits) in by_customer(txns.iter()) {
+                    let moved: f64 = hits.iter().map(|t| t.amount.abs()).sum();
+                    if hits.len() >= *min_count && moved >= *min_amount {
+                        alerts.push(BacktestAlert::new(tick, customer_id, None, &hits));
+                    }
+                }
+            }
+            Self::RapidMovement { min_amount, window_ticks } => {
+                let mut by_account: BTreeMap<&str, Vec<&TransactionRow>> = BTreeMap::new();
+                for txn in txns {
+                    by_account.entry(&txn.account_id).or_default().push(txn);
+                }
+                for (account_id, txns) in by_account {
+                    // Once per account per tick, on the first pair found
+                    let pair = txns.windows(2).find(|pair| {
+                        let (credit, debit) = (pair[0], pair[1]);
+                        credit.txn_type == "credit"
+                            && debit.txn_type == "debit"
+                            && credit.amount.abs() >= *min_amount
+                            && debit.amount.abs() >= *min_amount
+                            && debit.tick <= credit.tick + window_ticks
+                    });
+                    if let Some(pair) = pair {
+                        alerts.push(BacktestAlert::new(tick, &pair[0].customer_id, Some(account_id), pair));
+                    }
+                }
+            }
+            Self::LargeTransaction { min_amount, direction } => {
+                let hits = txns.iter().filter(|t| {
+                    t.tick == tick
+                        && t.amount.abs() >= *min_amount
+                        && direction.as_ref().is_none_or(|d| *d == t.txn_type)
+                });
+                for (customer_id, hits) in by_customer(hits) {
+                    alerts.push(BacktestAlert::new(tick, customer_id, None, &hits));
+                }
+            }
+        }
+        alerts
+    }
+}
+
+fn by_customer<'a>(txns: impl Iterator<Item = &'a &'a TransactionRow>) -> BTreeMap<&'a str, Vec<&'a TransactionRow>> {
+    let mut grouped: BTreeMap<&str, Vec<&TransactionRow>> = BTreeMap::new();
+    for txn in txns {
+        grouped.entry(&txn.customer_id).or_default().push(txn);
+    }
+    grouped
+}
+
+/// One alert the rule would have raised.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct BacktestAlert {
+    pub tick: Tick,
+    pub customer_id: String,
+    /// For rapid movement, the account the pair moved through.
+    pub account_id: Option<String>,
+    /// Total of the transactions behind the alert, in and out.
+    pub amount: f64,
+    pub transaction_count: usize,
+    /// The run alerted on this customer somewhere in the window.
+    pub live_alerted: bool,
+    /// The fraud case the customer was in, if any.
+    pub ground_truth: Option<String>,
+}
+
+impl BacktestAlert {
+    fn new(tick: Tick, customer_id: &str, account_id: Option<&str>, txns: &[&TransactionRow]) -> Self {
+        Self {
+            tick,
+            customer_id: customer_id.to_string(),
+            account_id: account_id.map(str::to_string),
+            amount: txns.iter().map(|t| t.amount.abs()).sum(),
+            transaction_count: txns.len(),
+            live_alerted: false,
+            ground_truth: None,
+        }
+    }
+}
+
+/// Customers flagged by the rule against those the run alerted on.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct AlertOverlap {
+    pub both: usize,
+    pub rule_only: usize,
+    pub live_only: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct TypologyHits {
+    pub typology: String,
+    /// Customers in a case of this typology active in the window.
+    pub customers: usize,
+    pub flagged: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct GroundTruthHits {
+    /// Customers in any fraud case active in the window.
+    pub customers: usize,
+    pub flagged: usize,
+    /// Flagged customers who were in a case; None when nobody was flagged.
+    pub hit_rate: Option<f64>,
+    /// Case customers the rule flagged; None when there were none.
+    pub recall: Option<f64>,
+    pub by_typology: Vec<TypologyHits>,
+}
+
+/// sim-runner → client after `backtest_rule`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct BacktestReport {
+    pub rule: RuleDefinition,
+    pub from_tick: Tick,
+    pub to_tick: Tick,
+    /// One per customer (per account for rapid movement) per tick flagged.
+    pub alert_count: usize,
+    pub customers_flagged: usize,
+    pub overlap: AlertOverlap,
+    pub ground_truth: GroundTruthHits,
+    /// The first `MAX_LISTED_ALERTS` alerts, oldest first.
+    pub alerts: Vec<BacktestAlert>,
+    pub truncated: bool,
+}
+
+impl BacktestReport {
+    /// Replay `rule` over ticks `from_tick..=to_tick`, by default the
+    /// whole run up to `current_tick`.
+    pub fn run(
+        store: &SimStore,
+        run_id: &str,
+        current_tick: Tick,
+        rule: RuleDefinition,
+        from_tick: Option<Tick>,
+        to_tick: Option<Tick>,
+    ) -> SimResult<Self> {
+        rule.validate()?;
+        let from_tick = from_tick.unwrap_or(1);
+        let to_tick = to_tick.unwrap_or(current_tick);
+        if from_tick > to_tick || to_tick > current_tick {
+            return Err(SimError::command(
+                "backtest_rule",
+                format!("window {from_tick}..={to_tick} is not within ticks played (0..={current_tick})"),
+            ));
+        }
+
+        let mut txns = store.get_all_transactions_in_window(run_id, from_tick.saturating_sub(rule.lookback()), to_tick)?;
+        txns.sort_by(|a, b| (a.tick, &a.transaction_id).cmp(&(b.tick, &b.transaction_id)));
+        let mut alerts = Vec::new();
+        let mut start = 0;
+        for tick in from_tick..=to_tick {
+            let lookback_start = tick.saturating_sub(rule.lookback());
+            while start < txns.len() && txns[start].tick < lookback_start {
+                start += 1;
+            }
+            let window: Vec<&TransactionRow> = txns[start..].iter().take_while(|t| t.tick <= tick).collect();
+            alerts.extend(rule.evaluate(tick, &window));
+        }
+
+        let live: BTreeSet<String> = store.alerted_customers_between(run_id, from_tick, to_tick)?.into_iter().collect();
+        let report = PostMortem::build(store, run_id, current_tick)?;
+        let mut truth: HashMap<String, String> = HashMap::new();
+        for case in report.cases.iter().filter(|c| c.first_tick <= to_tick && c.last_tick >= from_tick) {
+            truth.entry(case.customer_id.clone()).or_insert_with(|| case.typology.clone());
+        }
+        for alert in &mut alerts {
+            alert.live_alerted = live.contains(&alert.customer_id);
+            alert.ground_truth = truth.get(&alert.customer_id).cloned();
+        }
+
+        let flagged: BTreeSet<String> = alerts.iter().map(|a| a.customer_id.clone()).collect();
+        let both = flagged.iter().filter(|c| live.contains(*c)).count();
+        let caught = flagged.iter().filter(|c| truth.contains_key(*c)).count();
+        let mut by_typology: BTreeMap<&str, TypologyHits> = BTreeMap::new();
+        for (customer_id, typology) in &truth {
+            let hits = by_typology.entry(typology).or_insert_with(|| TypologyHits {
+                typology: typology.clone(),
+                customers: 0,
+                flagged: 0,
+            });
+            hits.customers += 1;
+            hits.flagged += usize::from(flagged.contains(customer_id));
+        }
+
+        let alert_count = alerts.len();
+        let truncated = alert_count > MAX_LISTED_ALERTS;
+        alerts.truncate(MAX_LISTED_ALERTS);
+        Ok(Self {
+            rule,
+            from_tick,
+            to_tick,
+            alert_count,
+            customers_flagged: flagged.len(),
+            overlap: AlertOverlap { both, rule_only: flagged.len() - both, live_only: live.len() - both },
+            ground_truth: GroundTruthHits {
+                customers: truth.len(),
+                flagged: caught,
+                hit_rate: (!flagged.is_empty()).then(|| caught as f64 / flagged.len() as f64),
+                recall: (!truth.is_empty()).then(|| caught as f64 / truth.len() as f64),
+                by_typology: by_typology.into_values().collect(),
+            },
+            alerts,
+            truncated,
+        })
+    }
+}
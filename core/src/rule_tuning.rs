@@ -0,0 +1,63 @@
+//! Monitoring rule tuning (Phase 4.17).
+//!
+//! The player can suppress a transaction monitoring rule, raise its
+//! thresholds, or exclude a customer from it. Governance expects every
+//! change to cite an approved tuning review of the same rule; a change
+//! that does not is still applied, but stays on the books as undocumented
+//! and draws a critical finding at the next exam. CTR filing is a
+//! regulatory requirement, not a tunable rule.
+
+use crate::store::rule_tuning::RuleTuningRow;
+use std::collections::{HashMap, HashSet};
+
+/// Rules the player can tune, by their `transaction_monitoring_rule` id.
+pub const TUNABLE_RULES: &[&str] = &["STRUCT_9K", "VEL_50K_7D", "RAPID_MOVE"];
+
+/// Changes a tuning can make; "restore" reverts the rule's earlier ones.
+pub const CHANGES: &[&str] = &["suppress", "raise_threshold", "exclude_customer", "restore"];
+
+/// The active tunings, as the detectors apply them.
+#[derive(Debug, Clone, Default)]
+pub struct RuleTunings {
+    suppressed: HashSet<String>,
+    multipliers: HashMap<String, f64>,
+    /// (rule_id, customer_id)
+    exclusions: HashSet<(String, String)>,
+}
+
+impl RuleTunings {
+    pub fn new(active: &[RuleTuningRow]) -> Self {
+        let mut tunings = Self::default();
+        for row in active {
+            match row.change.as_str() {
+                "suppress" => {
+                    tunings.suppressed.insert(row.rule_id.clone());
+                }
+                "raise_threshold" => {
+                    tunings.multipliers.insert(row.rule_id.clone(), row.threshold_multiplier);
+                }
+                "exclude_customer" => {
+                    if let Some(customer_id) = &row.customer_id {
+                        tunings.exclusions.insert((row.rule_id.clone(), customer_id.clone()));
+                    }
+                }
+                _ => {}
+            }
+        }
+        tunings
+    }
+
+    pub fn suppressed(&self, rule_id: &str) -> bool {
+        self.suppressed.contains(rule_id)
+    }
+
+    /// Scale on the rule's thresholds; 1.0 when untuned.
+    pub fn multiplier(&self, rule_id: &str) -> f64 {
+        self.multipliers.get(rule_id).copied().unwrap_or(1.0)
+    }
+
+    pub fn excluded(&self, rule_id: &str, customer_id: &str) -> bool {
+        self.exclusions
+            .contains(&(rule_id.to_string(), customer_id.to_string()))
+    }
+}
@@ -0,0 +1,160 @@
+//! Several runs in one process (Phase 4.70).
+//!
+//! A `SimEngine` is one run. The run manager hosts several side by side,
+//! each with its own run id, seed, clock and delta tracker, so one
+//! sim-runner can serve a multi-session front end or a tournament. IPC
+//! requests name their run with `run_id`; a request without one goes to
+//! the run sim-runner started with.
+//!
+//! Each run created here gets a database of its own, since customer,
+//! account and complaint ids are only unique within a database. Runs are
+//! stepped one request at a time, never concurrently. A run that halts on
+//! a fatal error is closed; the others carry on.
+
+use crate::{
+    config::SimConfig,
+    engine::SimEngine,
+    error::{SimError, SimResult},
+    state_delta::DeltaTracker,
+    store::SimStore,
+    types::{RunId, Tick},
+};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::{collections::BTreeMap, path::PathBuf};
+
+/// Runs one process hosts at most, the first included.
+pub const MAX_RUNS: usize = 32;
+
+/// One hosted run and what its session has been sent.
+pub struct ManagedRun {
+    pub engine: SimEngine,
+    pub tracker: DeltaTracker,
+}
+
+/// sim-runner → client after `create_run`, `close_run` and `list_runs`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct RunList {
+    /// By run id.
+    pub runs: Vec<RunSummary>,
+    /// Where requests without a `run_id` go; None once it is closed.
+    pub default_run: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct RunSummary {
+    pub run_id: String,
+    pub seed: u64,
+    pub tick: Tick,
+}
+
+/// How new runs are built: where their databases go and the scenario
+/// each starts from.
+pub struct RunTemplate {
+    /// Each new run is stored in `{run_id}.db` here. None keeps it in a
+    /// private in-memory database, dropped when the run is closed.
+    pub db_dir: Option<PathBuf>,
+    pub config: SimConfig,
+    /// Recorded as each new run's version.
+    pub version: String,
+}
+
+pub struct RunManager {
+    template: RunTemplate,
+    runs: BTreeMap<RunId, ManagedRun>,
+    default_run: Option<RunId>,
+}
+
+impl RunManager {
+    /// Host `first`, already built and stored, as the default run.
+    pub fn new(first: SimEngine, template: RunTemplate) -> Self {
+        let run_id = first.run_id.clone();
+        let mut runs = BTreeMap::new();
+        runs.insert(run_id.clone(), ManagedRun { engine: first, tracker: DeltaTracker::new() });
+        Self { template, runs, default_run: Some(run_id) }
+    }
+
+    /// Start a run under a new id from the template's scenario.
+    pub fn create(&mut self, run_id: Option<&str>, seed: u64) -> SimResult<RunList> {
+        let refuse = |reason: String| SimError::command("create_run", reason);
+        let run_id = run_id.ok_or_else(|| refuse("name the new run with run_id".into()))?;
+        if run_id.trim().is_empty() || run_id.contains(['/', '\\', '?', '&', '#']) {
+            return Err(refuse(format!("run_id '{run_id}' cannot name a database")));
+        }
+        if self.runs.len() >= MAX_RUNS {
+            return Err(refuse(format!("this process already hosts {MAX_RUNS} runs; close one first")));
+        }
+        if self.runs.contains_key(run_id) {
+            return Err(refuse(format!("run '{run_id}' already exists")));
+        }
+        let path = match &self.template.db_dir {
+            Some(dir) => {
+                let path = dir.join(format!("{run_id}.db"));
+                // An existing database holds an earlier run's rows
+                if path.exists() {
+                    return Err(refuse(format!("run '{run_id}' already exists at {}", path.display())));
+                }
+                path.to_string_lossy().into_owned()
+            }
+            None => format!("file:simrun_{run_id}?mode=memory&cache=shared"),
+        };
+
+        let store = SimStore::open(&path)?;
+        store.migrate()?;
+        store.insert_run(run_id, seed, &self.template.version)?;
+        let engine = SimEngine::build_with_config(run_id.to_string(), seed, &store, self.template.config.clone())
+            .map_err(|e| refuse(format!("could not build run '{run_id}': {e}")))?;
+        self.runs.insert(run_id.to_string(), ManagedRun { engine, tracker: DeltaTracker::new() });
+        Ok(self.list())
+    }
+
+    /// Stop hosting a run. A run stored in a file keeps its rows there.
+    pub fn close(&mut self, run_id: Option<&str>) -> SimResult<RunList> {
+        let run_id = self.resolve("close_run", run_id)?;
+        self.runs.remove(&run_id);
+        if self.default_run.as_deref() == Some(run_id.as_str()) {
+            self.default_run = None;
+        }
+        Ok(self.list())
+    }
+
+    pub fn list(&self) -> RunList {
+        RunList {
+            runs: self
+                .runs
+                .values()
+                .map(|run| RunSummary {
+                    run_id: run.engine.run_id.clone(),
+                    seed: run.engine.seed(),
+                    tick: run.engine.clock.current_tick,
+                })
+                .collect(),
+            default_run: self.default_run.clone(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.runs.is_empty()
+    }
+
+    /// The run a request names, or the default run. `command` names the
+    /// request in the error when there is no such run.
+    pub fn run_mut(&mut self, command: &str, run_id: Option<&str>) -> SimResult<&mut ManagedRun> {
+        let run_id = self.resolve(command, run_id)?;
+        Ok(self.runs.get_mut(&run_id).expect("resolved runs are hosted"))
+    }
+
+    fn resolve(&self, command: &str, run_id: Option<&str>) -> SimResult<RunId> {
+        let run_id = match run_id {
+            Some(run_id) => run_id,
+            None => self
+                .default_run
+                .as_deref()
+                .ok_or_else(|| SimError::command(command, "the default run is closed; name a run with run_id"))?,
+        };
+        if !self.runs.contains_key(run_id) {
+            return Err(SimError::command(command, format!("no run '{run_id}' in this process")));
+        }
+        Ok(run_id.to_string())
+    }
+}
@@ -0,0 +1,383 @@
+//! SAR e-filing export (Phase 4.84).
+//!
+//! Renders the run's SARs as FinCEN-style structured filings and writes
+//! them out in batches, as a bank's e-filing would. Each filing carries:
+//!   - the subject: the customer's KYC record and every account they hold;
+//!   - the suspicious activity: a FinCEN category and subcategory mapped
+//!     from the alert type, the amount, and the period it covers;
+//!   - the alerts the SAR was filed on, and the largest of the subject's
+//!     transactions over the period;
+//!   - a narrative assembled from all of the above, ending with the
+//!     investigator's own notes.
+//!
+//! `export_sars` writes every SAR not yet exported to one batch file,
+//! `<dir>/<batch_id>.json` or `.xml`, and marks them exported with the
+//! batch id, so the next batch starts where this one ended.
+
+use crate::{
+    error::SimResult,
+    evidence_bundle::KycRecord,
+    store::{sar_export::SarTransactionRow, SimStore},
+    transaction_monitoring_subsystem::SuspiciousActivityReport,
+    types::Tick,
+};
+use serde::Serialize;
+use std::{
+    fmt::{self, Write as _},
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+
+/// Ticks of activity before the first alert a filing covers.
+pub const SAR_LOOKBACK_TICKS: Tick = 30;
+
+/// Most transactions listed on a filing.
+pub const SAR_MAX_TRANSACTIONS: usize = 25;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SarExportFormat {
+    Json,
+    Xml,
+}
+
+impl SarExportFormat {
+    pub fn extension(self) -> &'static str {
+        match self {
+            Self::Json => "json",
+            Self::Xml => "xml",
+        }
+    }
+}
+
+impl fmt::Display for SarExportFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.extension())
+    }
+}
+
+impl FromStr for SarExportFormat {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "json" => Ok(Self::Json),
+            "xml" => Ok(Self::Xml),
+            other => Err(anyhow::anyhow!("unknown SAR export format '{other}'; expected json or xml")),
+        }
+    }
+}
+
+/// FinCEN SAR category and subcategory for an alert type.
+pub fn activity_classification(alert_type: &str) -> (&'static str, &'static str) {
+    match alert_type {
+        "structuring" => ("Structuring", "Transaction(s) below CTR threshold"),
+        "velocity" | "category_intensity" => ("Money Laundering", "Transaction out of pattern for customer(s)"),
+        "rapid_movement" => ("Money Laundering", "Suspicious use of multiple accounts"),
+        "remittance_layering" | "correspondent_nested" => ("Money Laundering", "Suspicious EFT/wire transfers"),
+        "cross_border_funnel" => ("Money Laundering", "Funnel account"),
+        "sanctions_hit" => ("Terrorist Financing", "Known or suspected terrorist/terrorist organization"),
+        "fraud" | "identity_theft" => ("Fraud", "Other"),
+        _ => ("Other Suspicious Activities", "Other"),
+    }
+}
+
+/// Who the filing is about.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SarSubject {
+    pub subject_type: String,
+    pub subject_id: String,
+    /// None when the subject has no KYC record on file, such as a
+    /// respondent bank.
+    pub kyc: Option<KycRecord>,
+    pub accounts: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SarActivity {
+    pub category: String,
+    pub subcategory: String,
+    pub alert_type: String,
+    pub amount: f64,
+    pub from_tick: Tick,
+    pub to_tick: Tick,
+    /// Every transaction on the subject's accounts over the period.
+    pub transaction_count: i64,
+    pub transaction_total: f64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SarAlert {
+    pub alert_id: String,
+    pub tick: Tick,
+    pub rule_id: String,
+    pub alert_type: String,
+    pub score: f64,
+    pub amount: Option<f64>,
+    pub description: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SarTransaction {
+    pub txn_id: String,
+    pub account_id: String,
+    pub tick: Tick,
+    pub amount: f64,
+    pub direction: String,
+    pub category: String,
+    pub counterparty: Option<String>,
+}
+
+impl From<SarTransactionRow> for SarTransaction {
+    fn from(row: SarTransactionRow) -> Self {
+        Self {
+            txn_id: row.txn_id,
+            account_id: row.account_id,
+            tick: row.tick,
+            amount: row.amount,
+            direction: row.direction,
+            category: row.category,
+            counterparty: row.counterparty,
+        }
+    }
+}
+
+/// One SAR as filed.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SarDocument {
+    pub sar_id: String,
+    pub filing_tick: Tick,
+    pub filing_deadline: Tick,
+    /// 'filed' | 'late'
+    pub filing_status: String,
+    pub subject: SarSubject,
+    pub activity: SarActivity,
+    pub alerts: Vec<SarAlert>,
+    /// The largest transactions over the period, largest first.
+    pub transactions: Vec<SarTransaction>,
+    pub narrative: String,
+}
+
+/// One batch of filings.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SarBatch {
+    pub batch_id: String,
+    pub run_id: String,
+    pub filings: Vec<SarDocument>,
+}
+
+/// A batch written by `export_sars`.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SarExport {
+    pub batch_id: String,
+    pub path: PathBuf,
+    pub sars: usize,
+}
+
+/// Alert ids from a SAR's `related_alerts`. Older runs wrote the array
+/// without the closing quote, so anything not valid JSON is split by hand.
+pub fn related_alert_ids(related_alerts: Option<&str>) -> Vec<String> {
+    let Some(raw) = related_alerts else {
+        return Vec::new();
+    };
+    serde_json::from_str(raw).unwrap_or_else(|_| {
+        raw.trim_matches(|c| c == '[' || c == ']')
+            .split(',')
+            .map(|id| id.trim().trim_matches('"').to_string())
+            .filter(|id| !id.is_empty())
+            .collect()
+    })
+}
+
+/// Render a SAR as filed, from what the store holds on it.
+pub fn render_sar(store: &SimStore, run_id: &str, sar: &SuspiciousActivityReport) -> SimResult<SarDocument> {
+    let mut alerts = Vec::new();
+    for alert_id in related_alert_ids(sar.related_alerts.as_deref()) {
+        if let Some(alert) = store.aml_alert(run_id, &alert_id)? {
+            alerts.push(SarAlert {
+                alert_id: alert.alert_id,
+                tick: alert.tick,
+                rule_id: alert.rule_id,
+                alert_type: alert.alert_type,
+                score: alert.alert_score,
+                amount: alert.triggered_amount,
+                description: alert.description,
+            });
+        }
+    }
+
+    let first_alert = alerts.iter().map(|a| a.tick).min().unwrap_or(sar.filing_tick);
+    let from_tick = first_alert.saturating_sub(SAR_LOOKBACK_TICKS);
+    let (kyc, accounts, (transaction_count, transaction_total, largest)) = if sar.subject_type == "customer" {
+        (
+            store.kyc_record(run_id, &sar.subject_id)?,
+            store.customer_account_ids(run_id, &sar.subject_id)?,
+            store.sar_transactions(run_id, &sar.subject_id, from_tick, sar.filing_tick, SAR_MAX_TRANSACTIONS)?,
+        )
+    } else {
+        (None, Vec::new(), (0, 0.0, Vec::new()))
+    };
+
+    let (category, subcategory) = activity_classification(&sar.activity_type);
+    let mut document = SarDocument {
+        sar_id: sar.sar_id.clone(),
+        filing_tick: sar.filing_tick,
+        filing_deadline: sar.filing_deadline,
+        filing_status: sar.filing_status.clone(),
+        subject: SarSubject {
+            subject_type: sar.subject_type.clone(),
+            subject_id: sar.subject_id.clone(),
+            kyc,
+            accounts,
+        },
+        activity: SarActivity {
+            category: category.into(),
+            subcategory: subcategory.into(),
+            alert_type: sar.activity_type.clone(),
+            amount: sar.suspicious_amount,
+            from_tick,
+            to_tick: sar.filing_tick,
+            transaction_count,
+            transaction_total,
+        },
+        alerts,
+        transactions: largest.into_iter().map(SarTransaction::from).collect(),
+        narrative: String::new(),
+    };
+    document.narrative = narrative(&document, &sar.narrative);
+    Ok(document)
+}
+
+/// The filing's narrative: who, what and when, the alerts behind it, how
+/// the money moved, and the investigator's notes.
+pub fn narrative(document: &SarDocument, investigator_notes: &str) -> String {
+    let subject = &document.subject;
+    let activity = &document.activity;
+    let mut text = match &subject.kyc {
+        Some(kyc) => format!(
+            "This report concerns {} {}, a {} customer since day {} living in {}, {}.",
+            subject.subject_type, subject.subject_id, kyc.segment, kyc.open_tick, kyc.city, kyc.state
+        ),
+        None => format!("This report concerns {} {}.", subject.subject_type, subject.subject_id),
+    };
+    let _ = write!(
+        text,
+        " Between day {} and day {} the bank identified ${:.2} of activity it reports as {} ({}).",
+        activity.from_tick, activity.to_tick, activity.amount, activity.category, activity.subcategory
+    );
+
+    if !document.alerts.is_empty() {
+        let _ = write!(text, "\n\nThe activity raised {} alert(s):", document.alerts.len());
+        for alert in &document.alerts {
+            let _ = write!(
+                text,
+                "\n- Day {}: {} ({}, score {:.0}): {}",
+                alert.tick, alert.rule_id, alert.alert_type, alert.score, alert.description
+            );
+        }
+    }
+
+    if activity.transaction_count > 0 {
+        let _ = write!(
+            text,
+            "\n\nOver the period the subject's accounts ({}) carried {} transaction(s) totalling ${:.2}; the {} largest are listed with this report.",
+            subject.accounts.join(", "),
+            activity.transaction_count,
+            activity.transaction_total,
+            document.transactions.len()
+        );
+    }
+
+    let _ = write!(text, "\n\nInvestigator notes: {investigator_notes}");
+    if document.filing_status == "late" {
+        let _ = write!(text, "\n\nThis report is filed late; it was due on day {}.", document.filing_deadline);
+    }
+    text
+}
+
+/// Write every SAR not yet exported to one batch in `dir`, creating it if
+/// need be, and mark them exported. None when there is nothing to export.
+pub fn export_sars(store: &SimStore, run_id: &str, format: SarExportFormat, dir: &Path) -> SimResult<Option<SarExport>> {
+    let sars = store.unexported_sars(run_id)?;
+    if sars.is_empty() {
+        return Ok(None);
+    }
+    std::fs::create_dir_all(dir).map_err(|e| anyhow::anyhow!("Cannot create {}: {e}", dir.display()))?;
+
+    let batch_id = format!("sarx-{run_id}-{:04}", store.sar_export_batch_count(run_id)? + 1);
+    let filings = sars.iter().map(|sar| render_sar(store, run_id, sar)).collect::<SimResult<Vec<_>>>()?;
+    let batch = SarBatch {
+        batch_id: batch_id.clone(),
+        run_id: run_id.into(),
+        filings,
+    };
+    let content = match format {
+        SarExportFormat::Json => serde_json::to_string_pretty(&batch)?,
+        SarExportFormat::Xml => to_xml(&batch)?,
+    };
+    let path = dir.join(format!("{batch_id}.{}", format.extension()));
+    std::fs::write(&path, content).map_err(|e| anyhow::anyhow!("Cannot write {}: {e}", path.display()))?;
+
+    let sar_ids: Vec<String> = sars.into_iter().map(|s| s.sar_id).collect();
+    store.mark_sars_exported(run_id, &batch_id, format.extension(), &sar_ids)?;
+    Ok(Some(SarExport {
+        batch_id,
+        path,
+        sars: sar_ids.len(),
+    }))
+}
+
+/// The batch as XML: a `SARBatch` root, each field an element named in
+/// PascalCase, each list item an element named for its list in the
+/// singular, and absent values left out.
+pub fn to_xml(batch: &SarBatch) -> SimResult<String> {
+    let mut out = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    xml_element(&mut out, "SARBatch", &serde_json::to_value(batch)?, 0);
+    Ok(out)
+}
+
+fn xml_element(out: &mut String, name: &str, value: &serde_json::Value, depth: usize) {
+    use serde_json::Value;
+    let indent = "  ".repeat(depth);
+    match value {
+        Value::Null => {}
+        Value::Object(fields) => {
+            let _ = writeln!(out, "{indent}<{name}>");
+            for (key, field) in fields {
+                xml_element(out, &pascal_case(key), field, depth + 1);
+            }
+            let _ = writeln!(out, "{indent}</{name}>");
+        }
+        Value::Array(items) => {
+            let _ = writeln!(out, "{indent}<{name}>");
+            let item_name = name.strip_suffix('s').unwrap_or(name);
+            for item in items {
+                xml_element(out, item_name, item, depth + 1);
+            }
+            let _ = writeln!(out, "{indent}</{name}>");
+        }
+        Value::String(s) => {
+            let _ = writeln!(out, "{indent}<{name}>{}</{name}>", xml_escape(s));
+        }
+        other => {
+            let _ = writeln!(out, "{indent}<{name}>{other}</{name}>");
+        }
+    }
+}
+
+fn pascal_case(key: &str) -> String {
+    key.split('_')
+        .map(|word| {
+            let mut chars = word.chars();
+            chars.next().map_or(String::new(), |c| c.to_ascii_uppercase().to_string() + chars.as_str())
+        })
+        .collect()
+}
+
+fn xml_escape(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
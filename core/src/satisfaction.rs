@@ -0,0 +1,117 @@
+//! Satisfaction dynamics with memory (Phase 4.33).
+//!
+//! Without this, satisfaction only moves by instantaneous deltas. With it:
+//!   - each month satisfaction reverts part of the way to the customer's
+//!     baseline, which starts at their segment's and only goes down
+//!   - repeated goodwill gestures in a window buy less each time
+//!   - a severe incident (an SLA breach on an urgent complaint) leaves a
+//!     scar that lowers the baseline for good, so the customer never
+//!     fully recovers
+
+use crate::{
+    config::SatisfactionDynamicsConfig,
+    customer_subsystem::CustomerRecord,
+    error::SimResult,
+    event::SimEvent,
+    store::{
+        satisfaction::{SatisfactionGestureRow, SatisfactionScarRow},
+        SimStore,
+    },
+    types::{RunId, Tick},
+};
+
+pub struct SatisfactionDynamics {
+    run_id: RunId,
+    config: SatisfactionDynamicsConfig,
+}
+
+impl SatisfactionDynamics {
+    pub fn new(run_id: RunId, config: SatisfactionDynamicsConfig) -> Self {
+        Self { run_id, config }
+    }
+
+    pub fn enabled(&self) -> bool {
+        self.config.enabled
+    }
+
+    /// The level satisfaction reverts to: the segment's baseline less the
+    /// customer's scars, never below the floor.
+    pub fn baseline(&self, segment: &str, scars: f64) -> f64 {
+        let segment_baseline = self
+            .config
+            .segment_baselines
+            .get(segment)
+            .copied()
+            .unwrap_or(self.config.default_baseline);
+        (segment_baseline - scars).max(self.config.baseline_floor)
+    }
+
+    /// Apply a satisfaction change. A gain is a goodwill gesture and is
+    /// worth `goodwill_decay` less for each earlier one in the window;
+    /// losses, and everything when dynamics are off, apply as given.
+    /// Returns the change applied.
+    pub fn goodwill(
+        &self,
+        store:       &SimStore,
+        customer_id: &str,
+        source:      &str,
+        delta:       f64,
+        tick:        Tick,
+    ) -> SimResult<f64> {
+        if !self.config.enabled || delta <= 0.0 {
+            store.update_customer_satisfaction(&self.run_id, customer_id, delta)?;
+            return Ok(delta);
+        }
+        let window_start = (tick + 1).saturating_sub(self.config.goodwill_window_ticks);
+        let earlier = store.satisfaction_gestures_in_window(&self.run_id, customer_id, window_start, tick)?;
+        let applied = delta * self.config.goodwill_decay.powi(earlier as i32);
+        store.insert_satisfaction_gesture(&self.run_id, &SatisfactionGestureRow {
+            tick,
+            customer_id: customer_id.into(),
+            source: source.into(),
+            requested_delta: delta,
+            applied_delta: applied,
+        })?;
+        store.update_customer_satisfaction(&self.run_id, customer_id, applied)?;
+        if earlier > 0 {
+            tracing::debug!("{source} goodwill to {customer_id} worth {applied:.3} of {delta:.3} after {earlier} earlier");
+        }
+        Ok(applied)
+    }
+
+    /// Whether a breach on a complaint of this priority leaves a scar.
+    pub fn scars_on_breach(&self, priority: &str) -> bool {
+        self.config.enabled && self.config.scar_priorities.iter().any(|p| p == priority)
+    }
+
+    /// Scar the customer for a breached complaint, lowering their baseline.
+    pub fn scar(&self, store: &SimStore, customer_id: &str, complaint_id: &str, tick: Tick) -> SimResult<SimEvent> {
+        store.insert_satisfaction_scar(&self.run_id, &SatisfactionScarRow {
+            customer_id: customer_id.into(),
+            complaint_id: complaint_id.into(),
+            tick,
+            severity: self.config.scar_severity,
+        })?;
+        let segment = store.customer_segment(&self.run_id, customer_id)?;
+        let baseline = self.baseline(&segment, store.customer_scar_total(&self.run_id, customer_id)?);
+        tracing::debug!("{customer_id} scarred by breach of {complaint_id}, baseline now {baseline:.2}");
+        Ok(SimEvent::SatisfactionScarred {
+            tick,
+            customer_id: customer_id.into(),
+            complaint_id: complaint_id.into(),
+            baseline,
+        })
+    }
+
+    /// Monthly pass: move each customer `monthly_reversion` of the way
+    /// back to their baseline.
+    pub fn revert(&self, store: &SimStore, customers: Vec<CustomerRecord>) -> SimResult<()> {
+        let scars = store.satisfaction_scar_totals(&self.run_id)?;
+        for c in customers {
+            let baseline = self.baseline(&c.segment, scars.get(&c.customer_id).copied().unwrap_or(0.0));
+            let satisfaction = c.satisfaction + (baseline - c.satisfaction) * self.config.monthly_reversion;
+            store.update_customer_churn_satisfaction(&self.run_id, &c.customer_id, c.churn_risk, satisfaction)?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,199 @@
+//! Scenario files (Phase 4.77).
+//!
+//! A scenario file sets up a bank without editing the data directory:
+//! how many customers it starts with, the segment mix they are drawn
+//! from, where the economy stands, what each product charges and which
+//! subsystems run. Everything it leaves out keeps the data directory's
+//! value. Files are TOML or, with a `.json` extension, JSON:
+//!
+//! ```toml
+//! name = "recession"
+//! initial_customers = 2000
+//! enabled_subsystems = ["complaint", "economics", "pricing", "churn"]
+//!
+//! [segment_mix]
+//! mass_market = 0.7
+//! mid_tier = 0.2
+//! student = 0.1
+//!
+//! [macro_regime]
+//! economic_phase = "contraction"
+//! base_rate = 0.02
+//! phase_ticks = 180
+//!
+//! [fees.basic_checking]
+//! overdraft_fee = 25.0
+//! ```
+//!
+//! Every value is checked against the loaded config before anything is
+//! built, and an error names the field it is about, such as
+//! `fees.basic_checking.overdraft_fee`. Syntax errors and unknown fields
+//! name the line instead. Subsystem names are checked by the feature
+//! flags once the engine has registered its subsystems.
+//!
+//! A scenario the run was built with is stored on it, so `--resume`
+//! builds the same bank, and folded into its scenario hash.
+
+use crate::{
+    config::{MacroRegimeConfig, SimConfig},
+    error::{SimError, SimResult},
+    macro_subsystem::{MAX_BASE_RATE, MIN_BASE_RATE},
+    pricing_subsystem::FEE_TYPES,
+};
+use serde::{Deserialize, Serialize};
+use std::{collections::BTreeMap, path::Path};
+
+/// How far the segment mix may stray from summing to 1.
+const MIX_TOLERANCE: f64 = 1e-6;
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ScenarioFile {
+    #[serde(default)]
+    pub name: Option<String>,
+    /// Customers onboarded on the first tick, before population scaling.
+    #[serde(default)]
+    pub initial_customers: Option<usize>,
+    /// Share of customers drawn from each segment, by segment id. Must
+    /// sum to 1; segments left out get no new customers.
+    #[serde(default)]
+    pub segment_mix: Option<BTreeMap<String, f64>>,
+    #[serde(default)]
+    pub macro_regime: Option<MacroRegimeConfig>,
+    /// Starting fees by product id, then fee type.
+    #[serde(default)]
+    pub fees: BTreeMap<String, BTreeMap<String, f64>>,
+    /// Subsystems that run, besides the core ones, which always do; see
+    /// `FeatureFlagsConfig::enabled_subsystems`. Names are checked once
+    /// the engine has registered its subsystems.
+    #[serde(default)]
+    pub enabled_subsystems: Option<Vec<String>>,
+}
+
+impl ScenarioFile {
+    /// Read a scenario file: JSON if it ends in `.json`, TOML otherwise.
+    pub fn load(path: &Path) -> SimResult<Self> {
+        let content = std::fs::read_to_string(path)
+            .map_err(|e| anyhow::anyhow!("Cannot read {}: {e}", path.display()))?;
+        if path.extension().is_some_and(|ext| ext == "json") {
+            Self::from_json(&content)
+        } else {
+            Self::from_toml(&content)
+        }
+    }
+
+    pub fn from_toml(content: &str) -> SimResult<Self> {
+        toml::from_str(content).map_err(|e| {
+            let at = e.span().map_or_else(|| "the file".to_string(), |span| line_and_column(content, span.start));
+            SimError::scenario(at, e.message())
+        })
+    }
+
+    pub fn from_json(content: &str) -> SimResult<Self> {
+        serde_json::from_str(content)
+            .map_err(|e| SimError::scenario(format!("line {}, column {}", e.line(), e.column()), e.to_string()))
+    }
+
+    /// Check every value against `config`, then write them into it.
+    /// `config` is untouched if any value is refused.
+    pub fn apply(&self, config: &mut SimConfig) -> SimResult<()> {
+        self.validate(config)?;
+        if let Some(customers) = self.initial_customers {
+            config.initial_population = customers;
+        }
+        if let Some(mix) = &self.segment_mix {
+            for (id, segment) in config.segments.iter_mut() {
+                segment.population_share = mix.get(id).copied().unwrap_or(0.0);
+            }
+        }
+        if let Some(regime) = &self.macro_regime {
+            config.macro_regime = regime.clone();
+        }
+        if let Some(enabled) = &self.enabled_subsystems {
+            config.feature_flags.enabled_subsystems = Some(enabled.clone());
+        }
+        for (product_id, fees) in &self.fees {
+            let product = config.products.get_mut(product_id).expect("validated");
+            for (fee_type, &value) in fees {
+                match fee_type.as_str() {
+                    "monthly_fee" => product.monthly_fee = value,
+                    "overdraft_fee" => product.overdraft_fee = value,
+                    "nsf_fee" => product.nsf_fee = value,
+                    "atm_fee" => product.atm_fee = value,
+                    "wire_fee" => product.wire_fee = value,
+                    _ => unreachable!("validated"),
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn validate(&self, config: &SimConfig) -> SimResult<()> {
+        if self.initial_customers == Some(0) {
+            return Err(SimError::scenario("initial_customers", "must be at least 1"));
+        }
+        if let Some(mix) = &self.segment_mix {
+            for (id, &share) in mix {
+                let at = format!("segment_mix.{id}");
+                if !config.segments.contains_key(id) {
+                    return Err(SimError::scenario(at, format!("unknown segment; expected one of {}", sorted(config.segments.keys()))));
+                }
+                if !(0.0..=1.0).contains(&share) {
+                    return Err(SimError::scenario(at, format!("share must be between 0 and 1, got {share}")));
+                }
+            }
+            let total: f64 = mix.values().sum();
+            if (total - 1.0).abs() > MIX_TOLERANCE {
+                return Err(SimError::scenario("segment_mix", format!("shares must sum to 1, got {total}")));
+            }
+        }
+        if let Some(regime) = &self.macro_regime {
+            if !(MIN_BASE_RATE..=MAX_BASE_RATE).contains(&regime.base_rate) {
+                return Err(SimError::scenario(
+                    "macro_regime.base_rate",
+                    format!("must be between {MIN_BASE_RATE} and {MAX_BASE_RATE}, got {}", regime.base_rate),
+                ));
+            }
+            if regime.phase_ticks == 0 {
+                return Err(SimError::scenario("macro_regime.phase_ticks", "must be at least 1"));
+            }
+        }
+        for (product_id, fees) in &self.fees {
+            if !config.products.contains_key(product_id) {
+                return Err(SimError::scenario(
+                    format!("fees.{product_id}"),
+                    format!("unknown product; expected one of {}", sorted(config.products.keys())),
+                ));
+            }
+            for (fee_type, &value) in fees {
+                let at = format!("fees.{product_id}.{fee_type}");
+                if !FEE_TYPES.contains(&fee_type.as_str()) {
+                    return Err(SimError::scenario(at, format!("unknown fee type; expected one of {}", FEE_TYPES.join(", "))));
+                }
+                if let Some(limit) = config.fee_constraints.get(fee_type) {
+                    if !(limit.min_value..=limit.max_value).contains(&value) {
+                        return Err(SimError::scenario(
+                            at,
+                            format!("must be between {} and {}, got {value}", limit.min_value, limit.max_value),
+                        ));
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// 1-based line and column of byte `offset` in `content`.
+fn line_and_column(content: &str, offset: usize) -> String {
+    let before = &content[..offset.min(content.len())];
+    let line = before.matches('\n').count() + 1;
+    let column = before.chars().rev().take_while(|&c| c != '\n').count() + 1;
+    format!("line {line}, column {column}")
+}
+
+fn sorted<'a>(names: impl Iterator<Item = &'a String>) -> String {
+    let mut names: Vec<&str> = names.map(String::as_str).collect();
+    names.sort_unstable();
+    names.join(", ")
+}
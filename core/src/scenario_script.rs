@@ -0,0 +1,233 @@
+//! Scenario scripting hooks (Phase 4.68).
+//!
+//! Scenario authors write conditional events as Rhai scripts instead of
+//! new Rust: "if the backlog passes 200 after tick 300, the regulator
+//! opens an inquiry". Each script runs once at the end of every tick,
+//! after the subsystems and before the snapshot, with two constants in
+//! scope:
+//!
+//! - `tick`, the tick just run,
+//! - `state`, a map of the headline KPIs from the engine's mirror:
+//!   `active_customers`, `churned_customers`, `complaint_count`,
+//!   `sla_breaches`, `backlog`, `nim`, `efficiency_ratio`,
+//!   `pre_tax_profit`, and `resilience` (`()` until the stress index
+//!   has run).
+//!
+//! and these functions:
+//!
+//! - `event_count(type)`: events of that type logged this tick,
+//! - `once(key)`: true the first time it is called with `key` in the
+//!   run, false ever after, even once the run is resumed,
+//! - `command(cmd, payload)` or `command(cmd)`: send a player command
+//!   through the same checks as an IPC `command`; it lands next tick,
+//! - `regulator_inquiry(reason)`: open a regulatory exam now, unless one
+//!   is already open,
+//! - `emit(name, detail)`: log a `scenario_script_fired` event for the UI.
+//!
+//! That is the whole surface. Scripts cannot import modules, read the
+//! clock or touch the store, and `print` goes to the log rather than
+//! stdout. Each run is stopped after `max_operations` Rhai operations. A
+//! script that fails or runs over is logged with `scenario_script_failed`
+//! and not run again; the run carries on.
+
+use crate::{
+    config::{ScenarioScript, ScenarioScriptConfig},
+    error::{SimError, SimResult},
+    state_mirror::StateMirror,
+    store::SimStore,
+    types::Tick,
+};
+use rhai::{Dynamic, Engine, EvalAltResult, Map, Scope, AST};
+use std::{
+    collections::{HashMap, HashSet},
+    sync::{Arc, Mutex},
+};
+
+/// Something a script asked for, applied by the engine once the script
+/// has finished.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ScriptAction {
+    Command { cmd: String, payload: serde_json::Value },
+    RegulatorInquiry { reason: String },
+    Emit { name: String, detail: String },
+}
+
+/// What the registered functions read and write while one script runs.
+#[derive(Default)]
+struct Call {
+    script: String,
+    event_counts: HashMap<String, i64>,
+    /// `(script, key)` of every `once` already spent.
+    spent: HashSet<(String, String)>,
+    /// Keys spent by this run, to be recorded.
+    newly_spent: Vec<String>,
+    actions: Vec<ScriptAction>,
+}
+
+struct Compiled {
+    name: String,
+    ast: AST,
+    failed: bool,
+}
+
+/// One script's tick: the `once` keys it spent and what it asked for, or
+/// why it failed.
+pub struct ScriptRun {
+    pub script: String,
+    pub spent: Vec<String>,
+    pub outcome: Result<Vec<ScriptAction>, String>,
+}
+
+pub struct ScenarioScripts {
+    engine: Engine,
+    scripts: Vec<Compiled>,
+    call: Arc<Mutex<Call>>,
+}
+
+impl ScenarioScripts {
+    /// Compile every script, loading the `once` keys the run has already
+    /// spent. A script that does not compile fails the build.
+    pub fn new(run_id: &str, config: &ScenarioScriptConfig, store: &SimStore) -> SimResult<Self> {
+        let call = Arc::new(Mutex::new(Call {
+            spent: store.spent_script_once_keys(run_id)?.into_iter().collect(),
+            ..Call::default()
+        }));
+        let engine = sandbox(config.max_operations, &call);
+        // Strict variables are checked against the names each run has.
+        // Plain variables here, not constants: the optimizer would fold a
+        // constant `tick` of 0 into every condition.
+        let mut scope = Scope::new();
+        scope.push("tick", 0_i64);
+        scope.push("state", state_map(&StateMirror::default()));
+        let scripts = config
+            .scripts
+            .iter()
+            .map(|ScenarioScript { name, source }| {
+                let ast = engine.compile_with_scope(&scope, source).map_err(|e| {
+                    SimError::Generator {
+                        generator: format!("scenario script '{name}'"),
+                        reason: e.to_string(),
+                    }
+                })?;
+                Ok(Compiled { name: name.clone(), ast, failed: false })
+            })
+            .collect::<SimResult<_>>()?;
+        Ok(Self { engine, scripts, call })
+    }
+
+    /// Phase 4.76: stop running `script`, which failed before the run
+    /// was resumed.
+    pub fn mark_failed(&mut self, script: &str) {
+        for compiled in self.scripts.iter_mut().filter(|s| s.name == script) {
+            compiled.failed = true;
+        }
+    }
+
+    /// Run every script still live against the tick's KPIs and event
+    /// counts. A script that fails is not run again.
+    pub fn run(&mut self, tick: Tick, kpis: &StateMirror, event_counts: HashMap<String, i64>) -> Vec<ScriptRun> {
+        let state = state_map(kpis);
+        lock(&self.call).event_counts = event_counts;
+        let mut runs = Vec::new();
+        for script in self.scripts.iter_mut().filter(|s| !s.failed) {
+            {
+                let mut call = lock(&self.call);
+                call.script = script.name.clone();
+                call.actions.clear();
+                call.newly_spent.clear();
+            }
+            let mut scope = tick_scope(tick, state.clone());
+            let result = self.engine.run_ast_with_scope(&mut scope, &script.ast);
+
+            let mut call = lock(&self.call);
+            let spent = std::mem::take(&mut call.newly_spent);
+            let actions = std::mem::take(&mut call.actions);
+            let outcome = match result {
+                Ok(()) => Ok(actions),
+                Err(e) => {
+                    script.failed = true;
+                    Err(e.to_string())
+                }
+            };
+            runs.push(ScriptRun { script: script.name.clone(), spent, outcome });
+        }
+        runs
+    }
+}
+
+fn lock(call: &Mutex<Call>) -> std::sync::MutexGuard<'_, Call> {
+    // A panic inside a script function leaves nothing half-written
+    call.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+/// A Rhai engine with the scenario API registered and nothing else that
+/// reaches outside the script.
+fn sandbox(max_operations: u64, call: &Arc<Mutex<Call>>) -> Engine {
+    let mut engine = Engine::new();
+    engine
+        .set_strict_variables(true)
+        .set_max_operations(max_operations.max(1))
+        .set_max_call_levels(32)
+        .set_max_expr_depths(64, 32)
+        .set_max_string_size(64 * 1024)
+        .set_max_array_size(10_000)
+        .set_max_map_size(10_000)
+        .on_print(|text| tracing::info!(target: "scenario_script", "{text}"))
+        .on_debug(|text, _, pos| tracing::debug!(target: "scenario_script", "{pos}: {text}"));
+
+    let c = call.clone();
+    engine.register_fn("event_count", move |event_type: &str| -> i64 {
+        lock(&c).event_counts.get(event_type).copied().unwrap_or(0)
+    });
+    let c = call.clone();
+    engine.register_fn("once", move |key: &str| -> bool {
+        let mut call = lock(&c);
+        let spent = (call.script.clone(), key.to_string());
+        let first = call.spent.insert(spent);
+        if first {
+            call.newly_spent.push(key.to_string());
+        }
+        first
+    });
+    let c = call.clone();
+    engine.register_fn("command", move |cmd: &str, payload: Map| -> Result<(), Box<EvalAltResult>> {
+        let payload = rhai::serde::from_dynamic(&Dynamic::from_map(payload))?;
+        lock(&c).actions.push(ScriptAction::Command { cmd: cmd.to_string(), payload });
+        Ok(())
+    });
+    let c = call.clone();
+    engine.register_fn("command", move |cmd: &str| {
+        let payload = serde_json::Value::Object(Default::default());
+        lock(&c).actions.push(ScriptAction::Command { cmd: cmd.to_string(), payload });
+    });
+    let c = call.clone();
+    engine.register_fn("regulator_inquiry", move |reason: &str| {
+        lock(&c).actions.push(ScriptAction::RegulatorInquiry { reason: reason.to_string() });
+    });
+    let c = call.clone();
+    engine.register_fn("emit", move |name: &str, detail: &str| {
+        lock(&c).actions.push(ScriptAction::Emit { name: name.to_string(), detail: detail.to_string() });
+    });
+    engine
+}
+
+fn tick_scope(tick: Tick, state: Dynamic) -> Scope<'static> {
+    let mut scope = Scope::new();
+    scope.push_constant("tick", tick as i64);
+    scope.push_constant("state", state);
+    scope
+}
+
+fn state_map(kpis: &StateMirror) -> Dynamic {
+    let mut state = Map::new();
+    state.insert("active_customers".into(), kpis.active_customers.into());
+    state.insert("churned_customers".into(), kpis.churned_customers.into());
+    state.insert("complaint_count".into(), kpis.complaint_count.into());
+    state.insert("sla_breaches".into(), kpis.sla_breaches.into());
+    state.insert("backlog".into(), kpis.backlog.into());
+    state.insert("nim".into(), kpis.nim.into());
+    state.insert("efficiency_ratio".into(), kpis.efficiency_ratio.into());
+    state.insert("pre_tax_profit".into(), kpis.pre_tax_profit.into());
+    state.insert("resilience".into(), kpis.resilience.map_or(Dynamic::UNIT, Dynamic::from));
+    state.into()
+}
@@ -0,0 +1,128 @@
+//! Seed sensitivity reports (Phase 4.56).
+//!
+//! Runs one scenario from a handful of neighbouring seeds and summarises
+//! how far its key outcomes (profit, losses, detection, churn and
+//! complaints) move on chance alone. A metric whose spread is large next
+//! to its mean is flagged seed-fragile. Each metric's noise band is the
+//! smallest gap between two scenarios' means, over as many seeds, that
+//! stands out from seed noise at 95%; a strategy that improves a metric
+//! by less than that has not shown anything yet.
+
+use crate::{
+    engine::SimEngine,
+    error::SimResult,
+    experiment::{Experiment, MetricSummary, Metrics, Scenario},
+    post_mortem::PostMortem,
+    store::SimStore,
+    types::Tick,
+};
+use serde::{Deserialize, Serialize};
+
+/// Coefficient of variation above which a metric is seed-fragile.
+pub const DEFAULT_FRAGILE_CV: f64 = 0.25;
+
+/// The outcomes compared across seeds. A run with no fraud cases has no
+/// detection rate and leaves it out.
+pub fn outcome_metrics(engine: &SimEngine) -> SimResult<Metrics> {
+    stored_outcome_metrics(&engine.store, &engine.run_id, engine.clock.current_tick)
+}
+
+/// `outcome_metrics` for a stored run that stopped at `final_tick`.
+pub fn stored_outcome_metrics(store: &SimStore, run_id: &str, final_tick: Tick) -> SimResult<Metrics> {
+    let pnl = store.all_pnl_snapshots(run_id)?;
+    let report = PostMortem::build(store, run_id, final_tick)?;
+    let mut metrics = vec![
+        ("pre_tax_profit".to_string(), pnl.iter().fold(0.0, |sum, p| sum + p.pre_tax_profit)),
+        ("fraud_loss".to_string(), pnl.iter().fold(0.0, |sum, p| sum + p.fraud_loss)),
+        ("case_loss".to_string(), report.counterfactual.actual_loss),
+    ];
+    if let Some(rate) = report.counterfactual.detection_rate {
+        metrics.push(("detection_rate".to_string(), rate));
+    }
+    metrics.push(("churned".to_string(), store.churned_customer_count(run_id)? as f64));
+    metrics.push(("complaints".to_string(), store.complaint_count(run_id)? as f64));
+    Ok(metrics)
+}
+
+/// How one outcome varied across the seeds.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MetricSensitivity {
+    pub metric: String,
+    pub runs: usize,
+    pub mean: f64,
+    pub std_dev: f64,
+    pub min: f64,
+    pub max: f64,
+    /// Standard deviation over the absolute mean; None when the mean is 0.
+    pub cv: Option<f64>,
+    /// 1.96 standard errors of the difference between two such means.
+    pub noise_band: f64,
+    pub fragile: bool,
+}
+
+impl MetricSensitivity {
+    pub fn from_summary(summary: &MetricSummary, fragile_cv: f64) -> Self {
+        let cv = (summary.mean != 0.0).then(|| summary.std_dev / summary.mean.abs());
+        Self {
+            metric: summary.metric.clone(),
+            runs: summary.runs,
+            mean: summary.mean,
+            std_dev: summary.std_dev,
+            min: summary.min,
+            max: summary.max,
+            cv,
+            noise_band: 1.96 * summary.std_dev * (2.0 / summary.runs as f64).sqrt(),
+            // Any spread around a zero mean swamps it
+            fragile: cv.map_or(summary.std_dev > 0.0, |cv| cv > fragile_cv),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SeedFailure {
+    pub seed: u64,
+    pub error: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SeedSensitivity {
+    pub seeds: Vec<u64>,
+    pub ticks: Tick,
+    pub fragile_cv: f64,
+    /// In `outcome_metrics` order, a metric only some runs report
+    /// coming after those all do when the first seed lacks it.
+    pub metrics: Vec<MetricSensitivity>,
+    pub failures: Vec<SeedFailure>,
+}
+
+impl SeedSensitivity {
+    /// The `count` seeds from `base` up.
+    pub fn nearby_seeds(base: u64, count: usize) -> Vec<u64> {
+        (0..count as u64).map(|n| base.wrapping_add(n)).collect()
+    }
+
+    /// Run `scenario` once per seed on `threads` workers and summarise
+    /// its outcomes.
+    pub fn run(scenario: Scenario, seeds: Vec<u64>, threads: usize, fragile_cv: f64) -> Self {
+        let ticks = scenario.ticks;
+        let frame = Experiment::new(outcome_metrics).scenario(scenario).seeds(seeds.clone()).threads(threads).run();
+        Self {
+            seeds,
+            ticks,
+            fragile_cv,
+            metrics: frame.summarize().iter().map(|s| MetricSensitivity::from_summary(s, fragile_cv)).collect(),
+            failures: frame
+                .failures
+                .into_iter()
+                .map(|f| SeedFailure {
+                    seed: f.seed,
+                    error: f.error,
+                })
+                .collect(),
+        }
+    }
+
+    pub fn fragile(&self) -> impl Iterator<Item = &MetricSensitivity> {
+        self.metrics.iter().filter(|m| m.fragile)
+    }
+}
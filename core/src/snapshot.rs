@@ -3,20 +3,110 @@
 //! A snapshot is taken every SNAPSHOT_INTERVAL ticks.
 //! It captures the complete state needed to resume simulation
 //! from that tick without replaying from tick 0.
+//!
+//! Stored JSON is a versioned envelope around the state. Reading one
+//! walks it through `UPGRADES` to the current version, so snapshots
+//! written by older crates still restore; one written by a newer crate is
+//! rejected rather than half-read. The unversioned JSON saved before the
+//! envelope existed reads as version 1.
+//!
+//! Since version 3 a snapshot also carries each subsystem's own state,
+//! keyed by subsystem name; `SimEngine::resume` loads it and replays the
+//! event log from there.
 
 use crate::{
     clock::SimClock,
+    error::{SimError, SimResult},
     types::{RunId, Tick},
 };
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::BTreeMap;
 
 pub const SNAPSHOT_INTERVAL: Tick = 30; // monthly
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+/// Version of the state layout this crate writes. Bump it, and add an
+/// upgrade to `UPGRADES`, whenever `SimSnapshot` changes shape.
+pub const SNAPSHOT_VERSION: u32 = 3;
+
+/// `UPGRADES[n]` takes version `n + 1` state to version `n + 2`.
+const UPGRADES: &[fn(Value) -> SimResult<Value>] = &[upgrade_v1_to_v2, upgrade_v2_to_v3];
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct SimSnapshot {
     pub run_id: RunId,
     pub tick: Tick,
     pub clock: SimClock,
-    // Phase 1+ subsystem states added here as they are built.
-    // Each subsystem exposes a SnapshotState struct.
+    /// Subsystem name → what its `save_state` returned. Subsystems that
+    /// keep everything in the store have no entry.
+    pub subsystems: BTreeMap<String, Value>,
+}
+
+/// What is stored in `snapshot.state_json`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SnapshotEnvelope {
+    pub version: u32,
+    /// Crate that wrote the snapshot, for error messages and support.
+    pub crate_version: String,
+    pub state: Value,
+}
+
+impl SimSnapshot {
+    /// Serialize inside an envelope at the current version.
+    pub fn to_json(&self) -> SimResult<String> {
+        let envelope = SnapshotEnvelope {
+            version: SNAPSHOT_VERSION,
+            crate_version: env!("CARGO_PKG_VERSION").to_string(),
+            state: serde_json::to_value(self)?,
+        };
+        Ok(serde_json::to_string(&envelope)?)
+    }
+
+    /// Read stored snapshot JSON of any supported version.
+    pub fn from_json(json: &str) -> SimResult<Self> {
+        let envelope = read_envelope(json)?;
+        if envelope.version == 0 || envelope.version > SNAPSHOT_VERSION {
+            return Err(SimError::SnapshotVersion {
+                found: envelope.version,
+                supported: SNAPSHOT_VERSION,
+                crate_version: envelope.crate_version,
+            });
+        }
+        let mut state = envelope.state;
+        for upgrade in &UPGRADES[envelope.version as usize - 1..] {
+            state = upgrade(state)?;
+        }
+        Ok(serde_json::from_value(state)?)
+    }
+}
+
+/// The stored version of snapshot JSON, without upgrading it.
+pub fn snapshot_version(json: &str) -> SimResult<u32> {
+    Ok(read_envelope(json)?.version)
+}
+
+fn read_envelope(json: &str) -> SimResult<SnapshotEnvelope> {
+    let value: Value = serde_json::from_str(json)?;
+    if value.get("version").is_some() && value.get("state").is_some() {
+        return Ok(serde_json::from_value(value)?);
+    }
+    Ok(SnapshotEnvelope {
+        version: 1,
+        crate_version: "unknown".into(),
+        state: value,
+    })
+}
+
+/// Version 1 stored the state bare; the envelope moved it, unchanged,
+/// under `state`.
+fn upgrade_v1_to_v2(state: Value) -> SimResult<Value> {
+    Ok(state)
+}
+
+/// Version 3 added subsystem state; older snapshots have none.
+fn upgrade_v2_to_v3(mut state: Value) -> SimResult<Value> {
+    if let Some(fields) = state.as_object_mut() {
+        fields.entry("subsystems").or_insert_with(|| Value::Object(Default::default()));
+    }
+    Ok(state)
 }
@@ -0,0 +1,92 @@
+//! SSN plausibility checks at onboarding (Phase 4.14).
+//!
+//! Before randomization in June 2011 the SSA issued area numbers by
+//! geography and era, so an SSN says something about when it could have
+//! been issued. Onboarding checks the number against the ranges that were
+//! never issued, against the issuance eras that cannot fit the customer's
+//! date of birth, and against the death master file. Every mismatch adds
+//! to the customer's synthetic-identity score.
+
+use crate::config::SsnValidationConfig;
+
+/// Year SSN randomization began.
+pub const RANDOMIZATION_YEAR: i32 = 2011;
+
+/// Railroad Retirement Board areas, issued only through 1963.
+const RAILROAD_AREAS: (u16, u16) = (700, 728);
+const RAILROAD_LAST_ISSUED_YEAR: i32 = 1963;
+
+/// Areas first issued under randomization.
+pub const POST_RANDOMIZATION_AREAS: &[(u16, u16)] = &[(734, 749), (773, 899)];
+
+/// Age by 2011 past which a first SSN is implausible for a customer
+/// onboarding with a full credit history.
+const ADULT_AT_RANDOMIZATION: i32 = 18;
+
+/// Findings for one SSN, in the order they are checked.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SsnFindings {
+    /// "invalid_area", "invalid_group", "invalid_serial"
+    pub invalid: Vec<&'static str>,
+    /// "issued_before_birth", "issued_after_adulthood"
+    pub issuance: Vec<&'static str>,
+    pub deceased: bool,
+}
+
+impl SsnFindings {
+    pub fn is_clean(&self) -> bool {
+        self.invalid.is_empty() && self.issuance.is_empty() && !self.deceased
+    }
+
+    /// Every finding as a single list.
+    pub fn issues(&self) -> Vec<&'static str> {
+        let mut issues: Vec<_> = self.invalid.iter().chain(&self.issuance).copied().collect();
+        if self.deceased {
+            issues.push("deceased");
+        }
+        issues
+    }
+
+    /// Contribution to the synthetic-identity score, capped at 1.0.
+    pub fn synthetic_score(&self, config: &SsnValidationConfig) -> f64 {
+        let mut score = 0.0;
+        if !self.invalid.is_empty() {
+            score += config.invalid_range_weight;
+        }
+        if !self.issuance.is_empty() {
+            score += config.issuance_mismatch_weight;
+        }
+        if self.deceased {
+            score += config.deceased_weight;
+        }
+        score.min(1.0)
+    }
+}
+
+/// Check the SSN's parts against the never-issued ranges and the issuance
+/// eras for `birth_year`. The death master file is checked by the caller.
+pub fn validate(area: u16, group: u8, serial: u16, birth_year: i32) -> SsnFindings {
+    let mut findings = SsnFindings::default();
+
+    if area == 0 || area == 666 || area >= 900 {
+        findings.invalid.push("invalid_area");
+    }
+    if group == 0 {
+        findings.invalid.push("invalid_group");
+    }
+    if serial == 0 {
+        findings.invalid.push("invalid_serial");
+    }
+
+    if (RAILROAD_AREAS.0..=RAILROAD_AREAS.1).contains(&area) && birth_year > RAILROAD_LAST_ISSUED_YEAR {
+        findings.issuance.push("issued_before_birth");
+    }
+    let post_randomization = POST_RANDOMIZATION_AREAS
+        .iter()
+        .any(|(lo, hi)| (*lo..=*hi).contains(&area));
+    if post_randomization && birth_year <= RANDOMIZATION_YEAR - ADULT_AT_RANDOMIZATION {
+        findings.issuance.push("issued_after_adulthood");
+    }
+
+    findings
+}
@@ -0,0 +1,180 @@
+//! Staffing subsystem — Phase 4.80.
+//!
+//! Puts case workers on the work queues: complaints, AML alerts,
+//! reconciliation exceptions and card dispute cases. Each tick, every
+//! staffed queue gets a capacity of `ftes × items_per_fte` items. A queue
+//! whose backlog is over that draws overtime, up to `max_overtime_share`
+//! more; what is worked beyond the regular day is paid at
+//! `overtime_premium` times the regular rate. Fractional capacity is
+//! rounded up or down at random so a small desk still works its expected
+//! share.
+//!
+//! The subsystem that owns a queue works no more than the capacity, oldest
+//! or most urgent first, and records what it got through:
+//!   - Complaint: the desk closes complaints, earliest SLA first, before
+//!     SLA aging; the rest age into breaches.
+//!   - CardDispute: each investigation or decision step takes an item;
+//!     steps past capacity wait for the next tick.
+//!   - Reconciliation: breaks auto-clear could not take are investigated
+//!     and corrected, oldest first.
+//!   - TransactionMonitoring: SARs are filed each tick instead of weekly,
+//!     oldest alert first, late if the backlog runs past the deadline;
+//!     spare capacity clears alerts too weak to report.
+//!
+//! A queue with no staff is worked as before, without limit. Pay lands in
+//! `staffing_snapshot` and Economics books it as opex.
+//!
+//! Player commands:
+//!   - SetStaffing { queue, ftes } — the queue's headcount from this tick.
+//!
+//! Execution: every tick, after PaymentHub and before Reconciliation,
+//!   CardDispute, TransactionMonitoring and Complaint, which read its
+//!   capacity.
+
+use crate::{
+    command::PlayerCommand,
+    config::StaffingConfig,
+    error::SimResult,
+    event::SimEvent,
+    queue::QueueKind,
+    rng::SubsystemRng,
+    store::{staffing::StaffingSnapshotRow, SimStore},
+    subsystem::{SimSubsystem, SubsystemManifest},
+    types::{RunId, Tick},
+};
+
+pub struct StaffingSubsystem {
+    run_id: RunId,
+    config: StaffingConfig,
+    store:  SimStore,
+}
+
+impl StaffingSubsystem {
+    pub fn new(run_id: RunId, config: StaffingConfig, store: SimStore) -> Self {
+        Self { run_id, config, store }
+    }
+
+    /// Items one FTE works a day on `queue`, if it can be staffed.
+    fn items_per_fte(&self, queue: QueueKind) -> Option<f64> {
+        self.config.queues.iter().find(|q| q.queue == queue).map(|q| q.items_per_fte)
+    }
+
+    /// The queue's headcount: the player's last plan, else the configured
+    /// one. None for a queue nobody has staffed.
+    fn ftes(&self, queue: QueueKind) -> SimResult<Option<f64>> {
+        if let Some(ftes) = self.store.staffing_plan(&self.run_id, queue)? {
+            return Ok(Some(ftes));
+        }
+        Ok(self.config.queues.iter().find(|q| q.queue == queue).map(|q| q.ftes))
+    }
+
+    fn set_staffing(&self, queue: QueueKind, ftes: f64, tick: Tick) -> SimResult<SimEvent> {
+        let reason = if !ftes.is_finite() || ftes < 0.0 {
+            Some("FTEs must be zero or more".to_string())
+        } else if self.items_per_fte(queue).is_none() {
+            Some(format!("{} has no configured throughput to staff", queue.as_str()))
+        } else {
+            None
+        };
+        if let Some(reason) = reason {
+            tracing::warn!("rejected staffing {ftes} on {}: {reason}", queue.as_str());
+            return Ok(SimEvent::StaffingRejected { tick, queue: queue.as_str().into(), reason });
+        }
+        let old_ftes = self.ftes(queue)?.unwrap_or(0.0);
+        self.store.insert_staffing_plan(&self.run_id, tick, queue, ftes)?;
+        tracing::info!("{} staffed with {ftes} FTEs (was {old_ftes})", queue.as_str());
+        Ok(SimEvent::StaffingChanged { tick, queue: queue.as_str().into(), old_ftes, new_ftes: ftes })
+    }
+
+    /// The queue's capacity and regular pay for the tick; overtime is
+    /// paid when its owner records what it worked.
+    fn staff_queue(
+        &self,
+        queue: QueueKind,
+        ftes: f64,
+        items_per_fte: f64,
+        tick: Tick,
+        rng: &mut SubsystemRng,
+    ) -> SimResult<StaffingSnapshotRow> {
+        let backlog = self.store.queue_open_count(&self.run_id, queue)? as f64;
+        let regular_items = ftes * items_per_fte;
+        let overtime = (backlog - regular_items).clamp(0.0, regular_items * self.config.max_overtime_share);
+        let items = regular_items + overtime;
+        let capacity = items.floor() as i64 + i64::from(rng.chance(items.fract()));
+
+        let overtime_item_cost = if items_per_fte > 0.0 {
+            self.config.fte_daily_cost * self.config.overtime_premium / items_per_fte
+        } else {
+            0.0
+        };
+        Ok(StaffingSnapshotRow {
+            tick,
+            queue: queue.as_str().into(),
+            ftes,
+            backlog: backlog as i64,
+            regular_items,
+            capacity,
+            worked: 0,
+            overtime_items: 0.0,
+            overtime_item_cost,
+            cost: ftes * self.config.fte_daily_cost,
+        })
+    }
+}
+
+const MANIFEST: SubsystemManifest = SubsystemManifest {
+    version: 1,
+    produces: &["staffing_changed", "staffing_rejected"],
+    consumes: &["player_command_received"],
+    tables: &["staffing_plan", "staffing_snapshot"],
+    requires: &[],
+};
+
+impl SimSubsystem for StaffingSubsystem {
+    fn name(&self) -> &'static str {
+        "staffing"
+    }
+
+    fn manifest(&self) -> SubsystemManifest {
+        MANIFEST
+    }
+
+    fn update(
+        &mut self,
+        tick:      Tick,
+        events_in: &[SimEvent],
+        rng:       &mut SubsystemRng,
+    ) -> SimResult<Vec<SimEvent>> {
+        let mut out = Vec::new();
+
+        if !self.config.enabled || tick == 0 {
+            return Ok(out);
+        }
+
+        // 1. Headcount changes, so one made this tick staffs this tick.
+        for event in events_in {
+            if let SimEvent::PlayerCommandReceived { command_id, .. } = event {
+                if let Some(PlayerCommand::SetStaffing { queue, ftes }) =
+                    self.store.get_player_command(&self.run_id, command_id)?
+                {
+                    out.push(self.set_staffing(queue, ftes, tick)?);
+                }
+            }
+        }
+
+        // 2. Each staffed queue's capacity and pay.
+        for queue in QueueKind::ALL {
+            let (Some(ftes), Some(items_per_fte)) = (self.ftes(queue)?, self.items_per_fte(queue)) else {
+                continue;
+            };
+            let row = self.staff_queue(queue, ftes, items_per_fte, tick, rng)?;
+            self.store.insert_staffing_snapshot(&self.run_id, &row)?;
+        }
+
+        Ok(out)
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+}
@@ -0,0 +1,244 @@
+//! Delta state for polling clients (Phase 4.44).
+//!
+//! A UI that polls every tick does not need the whole state each time,
+//! only what moved since the tick it last saw: how many events of each
+//! type were logged, the events themselves for the types it asks for,
+//! queue items opened or closed, new P&L snapshots, and the KPIs that
+//! changed. A busy tick logs a few hundred events, so sending them all
+//! would cost more than the full state.
+//!
+//! KPIs are not stored per tick, so a `DeltaTracker` keeps the ones a
+//! session last sent for each recent tick and diffs against those. A
+//! client asking from a tick the session never answered for gets every
+//! KPI.
+
+use crate::{
+    economics_subsystem::PnLSnapshot,
+    engine::SimEngine,
+    error::{SimError, SimResult},
+    protocol::UiState,
+    queue::{QueueItem, QueueKind, MAX_PAGE_SIZE},
+    types::Tick,
+};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Events sent in one delta; a longer window is flagged `truncated`.
+pub const MAX_DELTA_EVENTS: usize = 2_000;
+/// Ticks of sent KPIs a tracker remembers.
+const TRACKED_TICKS: usize = 64;
+
+/// The headline figures of a `UiState`.
+#[derive(Debug, Clone, PartialEq)]
+struct Kpis {
+    active_customers: i64,
+    churned_customers: i64,
+    complaint_count: i64,
+    sla_breaches: i64,
+    backlog: i64,
+    nim: f64,
+    efficiency_ratio: f64,
+    pre_tax_profit: f64,
+    resilience: Option<f64>,
+}
+
+impl From<&UiState> for Kpis {
+    fn from(s: &UiState) -> Self {
+        Self {
+            active_customers: s.active_customers,
+            churned_customers: s.churned_customers,
+            complaint_count: s.complaint_count,
+            sla_breaches: s.sla_breaches,
+            backlog: s.backlog,
+            nim: s.nim,
+            efficiency_ratio: s.efficiency_ratio,
+            pre_tax_profit: s.pre_tax_profit,
+            resilience: s.resilience,
+        }
+    }
+}
+
+/// KPIs that differ from the client's last state; unchanged ones are
+/// left out.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct KpiChanges {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub active_customers: Option<i64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub churned_customers: Option<i64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub complaint_count: Option<i64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sla_breaches: Option<i64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub backlog: Option<i64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub nim: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub efficiency_ratio: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pre_tax_profit: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub resilience: Option<f64>,
+}
+
+impl KpiChanges {
+    fn between(seen: Option<&Kpis>, now: &Kpis) -> Self {
+        fn changed<T: PartialEq + Copy>(seen: Option<T>, now: T) -> Option<T> {
+            (seen != Some(now)).then_some(now)
+        }
+        Self {
+            active_customers: changed(seen.map(|k| k.active_customers), now.active_customers),
+            churned_customers: changed(seen.map(|k| k.churned_customers), now.churned_customers),
+            complaint_count: changed(seen.map(|k| k.complaint_count), now.complaint_count),
+            sla_breaches: changed(seen.map(|k| k.sla_breaches), now.sla_breaches),
+            backlog: changed(seen.map(|k| k.backlog), now.backlog),
+            nim: changed(seen.map(|k| k.nim), now.nim),
+            efficiency_ratio: changed(seen.map(|k| k.efficiency_ratio), now.efficiency_ratio),
+            pre_tax_profit: changed(seen.map(|k| k.pre_tax_profit), now.pre_tax_profit),
+            resilience: changed(seen.map(|k| k.resilience), now.resilience).flatten(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct DeltaEvent {
+    pub tick: Tick,
+    pub subsystem: String,
+    pub event_type: String,
+    /// The event as logged.
+    pub payload: serde_json::Value,
+}
+
+/// Items of one queue that opened or closed in the window.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct QueueChanges {
+    pub queue: QueueKind,
+    pub opened: Vec<QueueItem>,
+    pub closed: Vec<QueueItem>,
+}
+
+/// sim-runner → client after `get_state_delta`: what changed in
+/// `(since_tick, tick]`.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct StateDelta {
+    pub run_id: String,
+    pub since_tick: Tick,
+    pub tick: Tick,
+    pub paused: bool,
+    pub kpis: KpiChanges,
+    /// Events logged in the window, by type.
+    pub event_counts: BTreeMap<String, u64>,
+    /// The window's events of the types the client asked for.
+    pub events: Vec<DeltaEvent>,
+    /// Only queues with a change are listed.
+    pub queues: Vec<QueueChanges>,
+    /// P&L snapshots taken in the window.
+    pub pnl: Vec<PnLSnapshot>,
+    /// The window held more events, or queue changes, than one delta
+    /// carries; fetch the full state or page the queues to catch up.
+    pub truncated: bool,
+}
+
+/// Remembers the KPIs a session sent so deltas report only changes.
+#[derive(Debug, Default)]
+pub struct DeltaTracker {
+    sent: BTreeMap<Tick, Kpis>,
+}
+
+impl DeltaTracker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Note a full or summary state sent to the client.
+    pub fn record(&mut self, state: &UiState) {
+        self.remember(state.tick, Kpis::from(state));
+    }
+
+    /// What changed since `since_tick`, which must not be ahead of the
+    /// run, with the events of `event_types` in full. The reply counts
+    /// as seen for later deltas.
+    pub fn delta(
+        &mut self,
+        engine: &SimEngine,
+        since_tick: Tick,
+        event_types: &[String],
+    ) -> SimResult<StateDelta> {
+        let tick = engine.clock.current_tick;
+        if since_tick > tick {
+            return Err(SimError::command(
+                "get_state_delta",
+                format!("since_tick {since_tick} is ahead of the run at tick {tick}"),
+            ));
+        }
+        let run_id = engine.run_id.as_str();
+        let now = UiState::summary_from_engine(engine)?;
+        let kpis = Kpis::from(&now);
+        let mut truncated = false;
+
+        let mut event_counts = BTreeMap::new();
+        let mut events = Vec::new();
+        for t in since_tick + 1..=tick {
+            for entry in engine.store.events_for_tick(run_id, t)? {
+                *event_counts.entry(entry.event_type.clone()).or_insert(0) += 1;
+                if !event_types.contains(&entry.event_type) {
+                    continue;
+                }
+                if events.len() == MAX_DELTA_EVENTS {
+                    truncated = true;
+                    continue;
+                }
+                events.push(DeltaEvent {
+                    tick: entry.tick,
+                    subsystem: entry.subsystem,
+                    event_type: entry.event_type,
+                    payload: serde_json::from_str(&entry.payload)?,
+                });
+            }
+        }
+
+        let mut queues = Vec::new();
+        for queue in QueueKind::ALL {
+            let (opened, closed) = engine.store.queue_changes(run_id, queue, since_tick, tick, MAX_PAGE_SIZE)?;
+            truncated |= [&opened, &closed].iter().any(|items| items.len() as u64 == MAX_PAGE_SIZE);
+            if !opened.is_empty() || !closed.is_empty() {
+                queues.push(QueueChanges { queue, opened, closed });
+            }
+        }
+
+        let pnl = engine
+            .store
+            .all_pnl_snapshots(run_id)?
+            .into_iter()
+            .filter(|p| p.tick > since_tick)
+            .collect();
+
+        let delta = StateDelta {
+            run_id: run_id.to_string(),
+            since_tick,
+            tick,
+            paused: now.paused,
+            kpis: KpiChanges::between(self.sent.get(&since_tick), &kpis),
+            event_counts,
+            events,
+            queues,
+            pnl,
+            truncated,
+        };
+        self.remember(tick, kpis);
+        Ok(delta)
+    }
+
+    fn remember(&mut self, tick: Tick, kpis: Kpis) {
+        self.sent.insert(tick, kpis);
+        while self.sent.len() > TRACKED_TICKS {
+            self.sent.pop_first();
+        }
+    }
+}
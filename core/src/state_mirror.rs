@@ -0,0 +1,84 @@
+//! In-memory KPI mirror (Phase 4.66).
+//!
+//! The headline figures of a `UiState` each cost a store query, several
+//! of them counts over whole tables. The engine keeps them here instead,
+//! loaded once when it is built and then moved by the events it logs:
+//!
+//! - `CustomerOnboarded` and `CustomerChurned` move the customer counts,
+//! - `ComplaintFiled` and `SLABreached` the complaint counts,
+//! - `QuarterlyPnLComputed` and `CloseAdjustmentPosted` re-read the
+//!   latest P&L snapshot, one row,
+//! - `StressIndexComputed` carries the resilience score.
+//!
+//! Complaints close and reopen down several paths, some of them player
+//! commands between ticks, so the backlog is taken from the running
+//! `complaint_backlog` row at the end of each tick and after each direct
+//! closure. Answering a state request then costs no query at all; only
+//! the P&L history and the complaint list, when asked for, touch the
+//! store.
+
+use crate::{error::SimResult, event::SimEvent, store::SimStore};
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct StateMirror {
+    pub active_customers: i64,
+    pub churned_customers: i64,
+    pub complaint_count: i64,
+    pub sla_breaches: i64,
+    pub backlog: i64,
+    pub nim: f64,
+    pub efficiency_ratio: f64,
+    pub pre_tax_profit: f64,
+    /// None until the stress index first runs.
+    pub resilience: Option<f64>,
+}
+
+impl StateMirror {
+    /// Every figure from the store, for a run being built or resumed.
+    pub fn load(store: &SimStore, run_id: &str) -> SimResult<Self> {
+        let mut mirror = Self {
+            active_customers: store.customer_count(run_id, "active")?,
+            churned_customers: store.churned_customer_count(run_id)?,
+            complaint_count: store.complaint_count(run_id)?,
+            sla_breaches: store.sla_breach_count(run_id)?,
+            backlog: store.complaint_backlog(run_id)?,
+            resilience: store.latest_stress_index(run_id)?.map(|s| s.resilience),
+            ..Self::default()
+        };
+        mirror.reload_pnl(store, run_id)?;
+        Ok(mirror)
+    }
+
+    /// Fold in one logged event.
+    pub fn observe(&mut self, store: &SimStore, run_id: &str, event: &SimEvent) -> SimResult<()> {
+        match event {
+            SimEvent::CustomerOnboarded { .. } => self.active_customers += 1,
+            SimEvent::CustomerChurned { .. } => {
+                self.active_customers -= 1;
+                self.churned_customers += 1;
+            }
+            SimEvent::ComplaintFiled { .. } => self.complaint_count += 1,
+            SimEvent::SLABreached { .. } => self.sla_breaches += 1,
+            SimEvent::StressIndexComputed { resilience, .. } => self.resilience = Some(*resilience),
+            SimEvent::QuarterlyPnLComputed { .. } | SimEvent::CloseAdjustmentPosted { .. } => {
+                self.reload_pnl(store, run_id)?
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    /// Take the backlog from its running total.
+    pub fn sync_backlog(&mut self, store: &SimStore, run_id: &str) -> SimResult<()> {
+        self.backlog = store.complaint_backlog(run_id)?;
+        Ok(())
+    }
+
+    fn reload_pnl(&mut self, store: &SimStore, run_id: &str) -> SimResult<()> {
+        let latest = store.latest_pnl_snapshot(run_id)?;
+        (self.nim, self.efficiency_ratio, self.pre_tax_profit) = latest
+            .as_ref()
+            .map_or((0.0, 0.0, 0.0), |p| (p.nim, p.efficiency_ratio, p.pre_tax_profit));
+        Ok(())
+    }
+}
@@ -0,0 +1,247 @@
+//! Store methods for ACH returns and return reason codes (Phase 4.9).
+
+use crate::{error::SimResult, types::Tick};
+use rusqlite::{params, OptionalExtension};
+
+/// A settled ACH item eligible for return.
+#[derive(Debug, Clone)]
+pub struct AchReturnCandidate {
+    pub txn_id:       String,
+    pub account_id:   String,
+    pub counterparty: Option<String>,
+    pub amount:       f64,
+    pub direction:    String,
+}
+
+/// Row from the `ach_return` table.
+#[derive(Debug, Clone)]
+pub struct AchReturnRow {
+    pub return_id:      String,
+    pub txn_id:         String,
+    pub account_id:     String,
+    pub counterparty:   Option<String>,
+    pub amount:         f64,
+    pub direction:      String,
+    pub return_code:    String,
+    pub handling:       String,
+    pub tick_returned:  Tick,
+    pub status:         String,
+    pub represent_tick: Option<Tick>,
+}
+
+/// Row from the `ach_dispute` table.
+#[derive(Debug, Clone)]
+pub struct AchDisputeRow {
+    pub dispute_id:  String,
+    pub return_id:   String,
+    pub account_id:  String,
+    pub customer_id: String,
+    pub amount:      f64,
+    pub return_code: String,
+    pub tick_filed:  Tick,
+    pub status:      String,
+}
+
+use super::SimStore;
+
+fn dispute_row_mapper(row: &rusqlite::Row<'_>) -> rusqlite::Result<AchDisputeRow> {
+    Ok(AchDisputeRow {
+        dispute_id:  row.get(0)?,
+        return_id:   row.get(1)?,
+        account_id:  row.get(2)?,
+        customer_id: row.get(3)?,
+        amount:      row.get(4)?,
+        return_code: row.get(5)?,
+        tick_filed:  row.get::<_, i64>(6)? as u64,
+        status:      row.get(7)?,
+    })
+}
+
+fn return_row_mapper(row: &rusqlite::Row<'_>) -> rusqlite::Result<AchReturnRow> {
+    Ok(AchReturnRow {
+        return_id:      row.get(0)?,
+        txn_id:         row.get(1)?,
+        account_id:     row.get(2)?,
+        counterparty:   row.get(3)?,
+        amount:         row.get(4)?,
+        direction:      row.get(5)?,
+        return_code:    row.get(6)?,
+        handling:       row.get(7)?,
+        tick_returned:  row.get::<_, i64>(8)? as u64,
+        status:         row.get(9)?,
+        represent_tick: row.get::<_, Option<i64>>(10)?.map(|t| t as u64),
+    })
+}
+
+const RETURN_COLUMNS: &str = "return_id, txn_id, account_id, counterparty, amount, direction,
+     return_code, handling, tick_returned, status, represent_tick";
+
+impl SimStore {
+    /// Settled ACH payment entries (purchases and payroll deposits)
+    /// initiated at `tick`. Fees, cash withdrawals and the entries returns
+    /// themselves book are never returned.
+    pub fn ach_return_candidates(&self, run_id: &str, tick: Tick) -> SimResult<Vec<AchReturnCandidate>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT txn_id, account_id, counterparty, amount, direction FROM transactions
+             WHERE run_id = ?1 AND payment_rail_id = 'ACH' AND tick = ?2
+               AND settlement_status = 'settled' AND category IN ('purchase', 'payroll')
+             ORDER BY account_id ASC, amount ASC, txn_id ASC",
+        )?;
+        let rows = stmt.query_map(params![run_id, tick as i64], |row| {
+            Ok(AchReturnCandidate {
+                txn_id:       row.get(0)?,
+                account_id:   row.get(1)?,
+                counterparty: row.get(2)?,
+                amount:       row.get(3)?,
+                direction:    row.get(4)?,
+            })
+        })?;
+        rows.collect::<Result<Vec<_>, _>>().map_err(Into::into)
+    }
+
+    pub fn insert_ach_return(&self, run_id: &str, row: &AchReturnRow) -> SimResult<()> {
+        self.conn.execute(
+            "INSERT INTO ach_return
+             (run_id, return_id, txn_id, account_id, counterparty, amount, direction,
+              return_code, handling, tick_returned, status, represent_tick)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12)",
+            params![
+                run_id, row.return_id, row.txn_id, row.account_id, row.counterparty, row.amount,
+                row.direction, row.return_code, row.handling, row.tick_returned as i64,
+                row.status, row.represent_tick.map(|t| t as i64),
+            ],
+        )?;
+        Ok(())
+    }
+
+    /// Returns awaiting re-presentment on `tick`.
+    pub fn ach_returns_due_for_represent(&self, run_id: &str, tick: Tick) -> SimResult<Vec<AchReturnRow>> {
+        let mut stmt = self.conn.prepare(&format!(
+            "SELECT {RETURN_COLUMNS} FROM ach_return
+             WHERE run_id = ?1 AND status = 'pending_represent' AND represent_tick <= ?2
+             ORDER BY return_id",
+        ))?;
+        let rows = stmt.query_map(params![run_id, tick as i64], return_row_mapper)?;
+        rows.collect::<Result<Vec<_>, _>>().map_err(Into::into)
+    }
+
+    pub fn resolve_ach_return(&self, run_id: &str, return_id: &str, status: &str, tick: Tick) -> SimResult<()> {
+        self.conn.execute(
+            "UPDATE ach_return SET status = ?1, resolved_tick = ?2
+             WHERE run_id = ?3 AND return_id = ?4",
+            params![status, tick as i64, run_id, return_id],
+        )?;
+        Ok(())
+    }
+
+    pub fn insert_ach_dispute(&self, run_id: &str, row: &AchDisputeRow) -> SimResult<()> {
+        self.conn.execute(
+            "INSERT INTO ach_dispute
+             (run_id, dispute_id, return_id, account_id, customer_id, amount,
+              return_code, tick_filed, status)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
+            params![
+                run_id, row.dispute_id, row.return_id, row.account_id, row.customer_id,
+                row.amount, row.return_code, row.tick_filed as i64, row.status,
+            ],
+        )?;
+        Ok(())
+    }
+
+    /// Open disputes filed on or before `filed_by`, oldest first.
+    pub fn open_ach_disputes_filed_by(&self, run_id: &str, filed_by: Tick) -> SimResult<Vec<AchDisputeRow>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT dispute_id, return_id, account_id, customer_id, amount,
+                    return_code, tick_filed, status
+             FROM ach_dispute WHERE run_id = ?1 AND status = 'open' AND tick_filed <= ?2
+             ORDER BY tick_filed, dispute_id",
+        )?;
+        let rows = stmt.query_map(params![run_id, filed_by as i64], dispute_row_mapper)?;
+        rows.collect::<Result<Vec<_>, _>>().map_err(Into::into)
+    }
+
+    pub fn resolve_ach_dispute(&self, run_id: &str, dispute_id: &str, status: &str, tick: Tick) -> SimResult<()> {
+        self.conn.execute(
+            "UPDATE ach_dispute SET status = ?1, resolved_tick = ?2
+             WHERE run_id = ?3 AND dispute_id = ?4",
+            params![status, tick as i64, run_id, dispute_id],
+        )?;
+        Ok(())
+    }
+
+    pub fn block_ach_origination(
+        &self,
+        run_id:       &str,
+        account_id:   &str,
+        counterparty: &str,
+        return_code:  &str,
+        tick:         Tick,
+    ) -> SimResult<()> {
+        self.conn.execute(
+            "INSERT OR IGNORE INTO ach_origination_block
+             (run_id, account_id, counterparty, tick_blocked, return_code)
+             VALUES (?1, ?2, ?3, ?4, ?5)",
+            params![run_id, account_id, counterparty, tick as i64, return_code],
+        )?;
+        Ok(())
+    }
+
+    /// Return code that closed the originator relationship, if any.
+    pub fn ach_origination_block(
+        &self,
+        run_id:       &str,
+        account_id:   &str,
+        counterparty: &str,
+    ) -> SimResult<Option<String>> {
+        let code = self.conn.query_row(
+            "SELECT return_code FROM ach_origination_block
+             WHERE run_id = ?1 AND account_id = ?2 AND counterparty = ?3",
+            params![run_id, account_id, counterparty],
+            |row| row.get(0),
+        ).optional()?;
+        Ok(code)
+    }
+
+    // ── Test / summary helpers ────────────────────────────────────────
+
+    /// All ACH returns for a run, oldest first (for tests).
+    pub fn ach_returns(&self, run_id: &str) -> SimResult<Vec<AchReturnRow>> {
+        let mut stmt = self.conn.prepare(&format!(
+            "SELECT {RETURN_COLUMNS} FROM ach_return WHERE run_id = ?1
+             ORDER BY tick_returned, return_id",
+        ))?;
+        let rows = stmt.query_map(params![run_id], return_row_mapper)?;
+        rows.collect::<Result<Vec<_>, _>>().map_err(Into::into)
+    }
+
+    /// All ACH disputes for a run, oldest first (for tests).
+    pub fn ach_disputes(&self, run_id: &str) -> SimResult<Vec<AchDisputeRow>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT dispute_id, return_id, account_id, customer_id, amount,
+                    return_code, tick_filed, status
+             FROM ach_dispute WHERE run_id = ?1 ORDER BY tick_filed, dispute_id",
+        )?;
+        let rows = stmt.query_map(params![run_id], dispute_row_mapper)?;
+        rows.collect::<Result<Vec<_>, _>>().map_err(Into::into)
+    }
+
+    /// Number of customer interactions of the given type (for tests).
+    pub fn interaction_count_by_type(&self, run_id: &str, interaction_type: &str) -> SimResult<i64> {
+        let count: i64 = self.conn.query_row(
+            "SELECT COUNT(*) FROM interaction WHERE run_id = ?1 AND interaction_type = ?2",
+            params![run_id, interaction_type],
+            |row| row.get(0),
+        )?;
+        Ok(count)
+    }
+
+    /// Number of closed originator relationships (for tests).
+    pub fn ach_origination_block_count(&self, run_id: &str) -> SimResult<i64> {
+        let count: i64 = self.conn.query_row(
+            "SELECT COUNT(*) FROM ach_origination_block WHERE run_id = ?1",
+            params![run_id],
+            |row| row.get(0),
+        )?;
+        Ok(count)
+    }
+}
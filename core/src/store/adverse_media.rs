@@ -0,0 +1,213 @@
+//! Store methods for adverse media screening (Phase 4.38).
+
+use crate::{error::SimResult, types::Tick};
+use rusqlite::{params, OptionalExtension};
+
+use super::SimStore;
+
+/// Row from the `adverse_media_article` table.
+#[derive(Debug, Clone)]
+pub struct AdverseMediaArticleRow {
+    pub article_id:   String,
+    pub tick:         Tick,
+    /// Ground truth: 'customer' | 'counterparty' | 'stranger', and the
+    /// customer or counterparty the story is really about.
+    pub subject_kind: String,
+    pub subject_id:   Option<String>,
+    /// 'individual' | 'business'
+    pub subject_type: String,
+    pub name:         String,
+    pub country:      Option<String>,
+    pub birth_year:   Option<u32>,
+    pub category:     String,
+    pub severity:     f64,
+}
+
+/// Row from the `adverse_media_match` table.
+#[derive(Debug, Clone)]
+pub struct AdverseMediaMatchRow {
+    pub article_id:    String,
+    /// 'customer' | 'counterparty'
+    pub entity_kind:   String,
+    pub entity_id:     String,
+    pub customer_id:   String,
+    pub tick:          Tick,
+    pub match_score:   f64,
+    pub escalated:     bool,
+    pub edd_triggered: bool,
+    /// Ground truth: the screened entity is the story's subject.
+    pub true_match:    bool,
+}
+
+/// An active customer as name screening sees them.
+#[derive(Debug, Clone)]
+pub struct ScreeningSubjectRow {
+    pub customer_id: String,
+    pub name:        String,
+    pub business:    bool,
+    /// Citizenship for international customers, otherwise "US".
+    pub country:     String,
+    pub birth_year:  Option<u32>,
+}
+
+impl SimStore {
+    /// Every active customer with the identifiers adverse media is
+    /// screened on.
+    pub fn adverse_media_subjects(&self, run_id: &str) -> SimResult<Vec<ScreeningSubjectRow>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT c.customer_id, COALESCE(c.name, ''), c.segment,
+                    COALESCE(ci.citizenship_country, 'US'),
+                    CAST(substr(id.date_of_birth, 1, 4) AS INTEGER)
+             FROM customer c
+             LEFT JOIN customer_international ci
+               ON ci.run_id = c.run_id AND ci.customer_id = c.customer_id
+             LEFT JOIN customer_identity id
+               ON id.run_id = c.run_id AND id.customer_id = c.customer_id
+             WHERE c.run_id = ?1 AND c.status = 'active'
+             ORDER BY c.customer_id",
+        )?;
+        let rows = stmt.query_map(params![run_id], |row| {
+            let segment: String = row.get(2)?;
+            Ok(ScreeningSubjectRow {
+                customer_id: row.get(0)?,
+                name:        row.get(1)?,
+                business:    segment == "small_business" || segment == "commercial",
+                country:     row.get(3)?,
+                birth_year:  row.get::<_, Option<i64>>(4)?.map(|y| y as u32),
+            })
+        })?;
+        rows.collect::<Result<Vec<_>, _>>().map_err(Into::into)
+    }
+
+    /// (customer_id, counterparty) for every counterparty a customer has
+    /// paid from `since` onwards.
+    pub fn recent_counterparties(&self, run_id: &str, since: Tick) -> SimResult<Vec<(String, String)>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT DISTINCT a.customer_id, t.counterparty
+             FROM transactions t
+             JOIN account a ON a.account_id = t.account_id
+             WHERE t.run_id = ?1 AND t.tick >= ?2 AND t.direction = 'debit'
+               AND t.counterparty IS NOT NULL
+             ORDER BY t.counterparty, a.customer_id",
+        )?;
+        let rows = stmt.query_map(params![run_id, since as i64], |row| Ok((row.get(0)?, row.get(1)?)))?;
+        rows.collect::<Result<Vec<_>, _>>().map_err(Into::into)
+    }
+
+    pub fn insert_adverse_media_article(&self, run_id: &str, row: &AdverseMediaArticleRow) -> SimResult<()> {
+        self.conn.execute(
+            "INSERT INTO adverse_media_article
+             (article_id, run_id, tick, subject_kind, subject_id, subject_type, name,
+              country, birth_year, category, severity)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11)",
+            params![
+                row.article_id, run_id, row.tick as i64, row.subject_kind, row.subject_id,
+                row.subject_type, row.name, row.country, row.birth_year, row.category, row.severity,
+            ],
+        )?;
+        Ok(())
+    }
+
+    pub fn insert_adverse_media_match(&self, run_id: &str, row: &AdverseMediaMatchRow) -> SimResult<()> {
+        self.conn.execute(
+            "INSERT OR IGNORE INTO adverse_media_match
+             (run_id, article_id, entity_kind, entity_id, customer_id, tick, match_score,
+              escalated, edd_triggered, true_match)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)",
+            params![
+                run_id, row.article_id, row.entity_kind, row.entity_id, row.customer_id,
+                row.tick as i64, row.match_score, row.escalated as i32, row.edd_triggered as i32,
+                row.true_match as i32,
+            ],
+        )?;
+        Ok(())
+    }
+
+    /// Bring the customer's outstanding EDD review forward to `due_tick`
+    /// on an adverse media trigger, or open one at their current tier if
+    /// none is scheduled. A review already due sooner is left alone.
+    pub fn expedite_edd_review(
+        &self,
+        run_id:      &str,
+        customer_id: &str,
+        due_tick:    Tick,
+        tick:        Tick,
+    ) -> SimResult<()> {
+        if let Some(review) = self.scheduled_edd_review(run_id, customer_id)? {
+            if review.due_tick > due_tick {
+                self.conn.execute(
+                    "UPDATE edd_review SET review_trigger = 'adverse_media', due_tick = ?1
+                     WHERE run_id = ?2 AND review_id = ?3",
+                    params![due_tick as i64, run_id, review.review_id],
+                )?;
+            }
+            return Ok(());
+        }
+        let tier: String = self
+            .conn
+            .query_row(
+                "SELECT composite_risk FROM customer_risk_score WHERE run_id = ?1 AND customer_id = ?2",
+                params![run_id, customer_id],
+                |row| row.get(0),
+            )
+            .optional()?
+            .unwrap_or_else(|| "low".into());
+        self.conn.execute(
+            "INSERT INTO edd_review
+             (run_id, review_id, customer_id, tier, review_trigger, due_tick, completed_tick, outcome, cost)
+             VALUES (?1, ?2, ?3, ?4, 'adverse_media', ?5, NULL, NULL, 0.0)",
+            params![run_id, format!("edd-am-{customer_id}-{tick}"), customer_id, tier, due_tick as i64],
+        )?;
+        Ok(())
+    }
+
+    // ── Test / summary helpers ────────────────────────────────────────
+
+    /// Every story the feed published for a run, oldest first (for tests).
+    pub fn adverse_media_articles(&self, run_id: &str) -> SimResult<Vec<AdverseMediaArticleRow>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT article_id, tick, subject_kind, subject_id, subject_type, name,
+                    country, birth_year, category, severity
+             FROM adverse_media_article WHERE run_id = ?1 ORDER BY tick, article_id",
+        )?;
+        let rows = stmt.query_map(params![run_id], |row| {
+            Ok(AdverseMediaArticleRow {
+                article_id:   row.get(0)?,
+                tick:         row.get::<_, i64>(1)? as Tick,
+                subject_kind: row.get(2)?,
+                subject_id:   row.get(3)?,
+                subject_type: row.get(4)?,
+                name:         row.get(5)?,
+                country:      row.get(6)?,
+                birth_year:   row.get(7)?,
+                category:     row.get(8)?,
+                severity:     row.get(9)?,
+            })
+        })?;
+        rows.collect::<Result<Vec<_>, _>>().map_err(Into::into)
+    }
+
+    /// Every scored screening match for a run, oldest first (for tests).
+    pub fn adverse_media_matches(&self, run_id: &str) -> SimResult<Vec<AdverseMediaMatchRow>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT article_id, entity_kind, entity_id, customer_id, tick, match_score,
+                    escalated, edd_triggered, true_match
+             FROM adverse_media_match WHERE run_id = ?1
+             ORDER BY tick, article_id, entity_id, customer_id",
+        )?;
+        let rows = stmt.query_map(params![run_id], |row| {
+            Ok(AdverseMediaMatchRow {
+                article_id:    row.get(0)?,
+                entity_kind:   row.get(1)?,
+                entity_id:     row.get(2)?,
+                customer_id:   row.get(3)?,
+                tick:          row.get::<_, i64>(4)? as Tick,
+                match_score:   row.get(5)?,
+                escalated:     row.get::<_, i32>(6)? != 0,
+                edd_triggered: row.get::<_, i32>(7)? != 0,
+                true_match:    row.get::<_, i32>(8)? != 0,
+            })
+        })?;
+        rows.collect::<Result<Vec<_>, _>>().map_err(Into::into)
+    }
+}
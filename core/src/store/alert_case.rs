@@ -0,0 +1,201 @@
+//! Store methods for alert cases (Phase 4.60).
+
+use crate::{error::SimResult, transaction_monitoring_subsystem::AMLAlert, types::Tick};
+use rusqlite::{params, OptionalExtension, Row};
+
+use super::SimStore;
+
+/// Row from the `alert_case` table.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AlertCaseRow {
+    pub case_id:         String,
+    pub customer_id:     String,
+    pub opened_tick:     Tick,
+    pub last_alert_tick: Tick,
+    pub alert_count:     i64,
+    pub customer_count:  i64,
+    pub total_amount:    f64,
+    pub max_score:       f64,
+    /// 'open' | 'closed'
+    pub status:          String,
+    pub closed_tick:     Option<Tick>,
+    /// When an analyst escalated the case (Phase 4.85).
+    pub escalated_tick:  Option<Tick>,
+}
+
+const COLUMNS: &str = "case_id, customer_id, opened_tick, last_alert_tick, alert_count, customer_count,
+     total_amount, max_score, status, closed_tick, escalated_tick";
+
+fn case_from_row(row: &Row) -> rusqlite::Result<AlertCaseRow> {
+    Ok(AlertCaseRow {
+        case_id:         row.get(0)?,
+        customer_id:     row.get(1)?,
+        opened_tick:     row.get::<_, i64>(2)? as Tick,
+        last_alert_tick: row.get::<_, i64>(3)? as Tick,
+        alert_count:     row.get(4)?,
+        customer_count:  row.get(5)?,
+        total_amount:    row.get(6)?,
+        max_score:       row.get(7)?,
+        status:          row.get(8)?,
+        closed_tick:     row.get::<_, Option<i64>>(9)?.map(|t| t as Tick),
+        escalated_tick:  row.get::<_, Option<i64>>(10)?.map(|t| t as Tick),
+    })
+}
+
+/// The open case, newest activity first, that an alert on ?2 at or after
+/// ?4 joins. ?3 is the relationship strength linking customers into a
+/// cluster; NULL keeps the cluster to ?2. UNION stops the walk at
+/// customers already reached.
+const OPEN_CASE_SQL: &str = "
+WITH RECURSIVE cluster(customer_id) AS (
+    SELECT ?2
+    UNION
+    SELECT CASE r.customer_id_a WHEN c.customer_id THEN r.customer_id_b ELSE r.customer_id_a END
+      FROM customer_relationship r
+      JOIN cluster c ON c.customer_id IN (r.customer_id_a, r.customer_id_b)
+     WHERE r.run_id = ?1 AND r.strength >= ?3
+)
+SELECT k.case_id FROM alert_case k
+ WHERE k.run_id = ?1 AND k.status = 'open' AND k.last_alert_tick >= ?4
+   AND EXISTS (SELECT 1 FROM aml_alert a
+                WHERE a.run_id = ?1 AND a.case_id = k.case_id AND a.customer_id IN cluster)
+ ORDER BY k.last_alert_tick DESC, k.rowid DESC
+ LIMIT 1";
+
+impl SimStore {
+    /// Alerts not yet in a case, oldest first.
+    pub fn ungrouped_alerts(&self, run_id: &str) -> SimResult<Vec<AMLAlert>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT alert_id, run_id, customer_id, tick, COALESCE(rule_id, 'UNKNOWN'), alert_type,
+                    COALESCE(alert_score, 0.0), description, triggered_amount, transaction_count, status
+               FROM aml_alert WHERE run_id = ?1 AND case_id IS NULL
+              ORDER BY tick, rowid",
+        )?;
+        let rows = stmt.query_map(params![run_id], |row| {
+            Ok(AMLAlert {
+                alert_id: row.get(0)?,
+                run_id: row.get(1)?,
+                customer_id: row.get(2)?,
+                tick: row.get::<_, i64>(3)? as Tick,
+                rule_id: row.get(4)?,
+                alert_type: row.get(5)?,
+                alert_score: row.get(6)?,
+                description: row.get(7)?,
+                triggered_amount: row.get(8)?,
+                transaction_count: row.get(9)?,
+                status: row.get(10)?,
+            })
+        })?;
+        rows.collect::<Result<Vec<_>, _>>().map_err(Into::into)
+    }
+
+    /// The open case in the customer's cluster with an alert at or after
+    /// `since`, if any.
+    pub fn open_alert_case_for(
+        &self,
+        run_id: &str,
+        customer_id: &str,
+        min_relationship_strength: Option<f64>,
+        since: Tick,
+    ) -> SimResult<Option<String>> {
+        let case_id = self
+            .conn
+            .query_row(
+                OPEN_CASE_SQL,
+                params![run_id, customer_id, min_relationship_strength, since as i64],
+                |row| row.get(0),
+            )
+            .optional()?;
+        Ok(case_id)
+    }
+
+    /// Open a case with `alert` as its first alert.
+    pub fn open_alert_case(&self, run_id: &str, case_id: &str, alert: &AMLAlert) -> SimResult<()> {
+        self.conn.execute(
+            "INSERT INTO alert_case (run_id, case_id, customer_id, opened_tick, last_alert_tick, alert_count,
+                                     customer_count, total_amount, max_score)
+             VALUES (?1, ?2, ?3, ?4, ?4, 1, 1, ?5, ?6)",
+            params![
+                run_id,
+                case_id,
+                alert.customer_id,
+                alert.tick as i64,
+                alert.triggered_amount.unwrap_or(0.0),
+                alert.alert_score,
+            ],
+        )?;
+        self.conn.execute(
+            "UPDATE aml_alert SET case_id = ?3 WHERE run_id = ?1 AND alert_id = ?2",
+            params![run_id, alert.alert_id, case_id],
+        )?;
+        Ok(())
+    }
+
+    /// Add `alert` to an open case and update the case's totals.
+    pub fn attach_alert_to_case(&self, run_id: &str, case_id: &str, alert: &AMLAlert) -> SimResult<()> {
+        self.conn.execute(
+            "UPDATE aml_alert SET case_id = ?3 WHERE run_id = ?1 AND alert_id = ?2",
+            params![run_id, alert.alert_id, case_id],
+        )?;
+        self.conn.execute(
+            "UPDATE alert_case
+                SET alert_count = alert_count + 1,
+                    last_alert_tick = MAX(last_alert_tick, ?3),
+                    total_amount = total_amount + ?4,
+                    max_score = MAX(max_score, ?5),
+                    customer_count = (SELECT COUNT(DISTINCT customer_id) FROM aml_alert
+                                       WHERE run_id = ?1 AND case_id = ?2)
+              WHERE run_id = ?1 AND case_id = ?2",
+            params![
+                run_id,
+                case_id,
+                alert.tick as i64,
+                alert.triggered_amount.unwrap_or(0.0),
+                alert.alert_score,
+            ],
+        )?;
+        Ok(())
+    }
+
+    /// Close every open case none of whose alerts is still open or under
+    /// investigation; returns the cases closed.
+    pub fn close_worked_alert_cases(&self, run_id: &str, tick: Tick) -> SimResult<Vec<AlertCaseRow>> {
+        let mut stmt = self.conn.prepare(&format!(
+            "SELECT {COLUMNS} FROM alert_case k
+              WHERE run_id = ?1 AND status = 'open'
+                AND NOT EXISTS (SELECT 1 FROM aml_alert a
+                                 WHERE a.run_id = ?1 AND a.case_id = k.case_id
+                                   AND a.status IN ('open', 'investigating'))
+              ORDER BY rowid"
+        ))?;
+        let worked = stmt
+            .query_map(params![run_id], case_from_row)?
+            .collect::<Result<Vec<_>, _>>()?;
+        for case in &worked {
+            self.conn.execute(
+                "UPDATE alert_case SET status = 'closed', closed_tick = ?3 WHERE run_id = ?1 AND case_id = ?2",
+                params![run_id, case.case_id, tick as i64],
+            )?;
+        }
+        Ok(worked)
+    }
+
+    /// Every case in the run, oldest first.
+    pub fn alert_cases(&self, run_id: &str) -> SimResult<Vec<AlertCaseRow>> {
+        let mut stmt = self.conn.prepare(&format!(
+            "SELECT {COLUMNS} FROM alert_case WHERE run_id = ?1 ORDER BY opened_tick, rowid"
+        ))?;
+        let rows = stmt.query_map(params![run_id], case_from_row)?;
+        rows.collect::<Result<Vec<_>, _>>().map_err(Into::into)
+    }
+
+    /// The case an alert was grouped into.
+    pub fn alert_case_of(&self, run_id: &str, alert_id: &str) -> SimResult<Option<String>> {
+        let case_id = self.conn.query_row(
+            "SELECT case_id FROM aml_alert WHERE run_id = ?1 AND alert_id = ?2",
+            params![run_id, alert_id],
+            |row| row.get(0),
+        )?;
+        Ok(case_id)
+    }
+}
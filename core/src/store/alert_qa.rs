@@ -0,0 +1,259 @@
+//! Store methods for alert disposition QA (Phase 4.73).
+
+use crate::{error::SimResult, types::Tick};
+use rusqlite::{params, OptionalExtension, Row};
+
+use super::SimStore;
+
+/// A SAR decision QA has not re-reviewed, with the alert behind it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct QaCandidate {
+    pub alert_id:    String,
+    pub customer_id: String,
+    pub alert_type:  String,
+    pub alert_score: f64,
+    /// "filed" | "defensive" | "missed"
+    pub decision:    String,
+}
+
+/// Row from the `alert_qa_review` table.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AlertQaReviewRow {
+    pub alert_id:    String,
+    pub cycle_tick:  Tick,
+    pub customer_id: String,
+    pub alert_type:  String,
+    pub alert_score: f64,
+    pub decision:    String,
+    pub desk_filed:  bool,
+    pub qa_filed:    bool,
+    /// None when QA agrees; 'over_filing' | 'under_filing'
+    pub error_kind:  Option<String>,
+    pub cost:        f64,
+}
+
+/// Row from the `alert_qa_cycle` table.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AlertQaCycleRow {
+    pub tick:               Tick,
+    /// Decisions made in the cycle.
+    pub population:         i64,
+    pub sampled:            i64,
+    pub disagreements:      i64,
+    /// None when nothing was sampled.
+    pub disagreement_rate:  Option<f64>,
+    /// disagreement_rate less the previous cycle's; None on the first
+    /// cycle or when either sampled nothing.
+    pub disagreement_trend: Option<f64>,
+}
+
+/// Row from the `qa_retraining` table.
+#[derive(Debug, Clone, PartialEq)]
+pub struct QaRetrainingRow {
+    pub retraining_id:     String,
+    pub alert_type:        String,
+    /// 'over_filing' | 'under_filing' | 'mixed'
+    pub error_kind:        String,
+    pub disagreement_rate: f64,
+    pub opened_tick:       Tick,
+    pub due_tick:          Tick,
+    /// 'open' | 'completed'
+    pub status:            String,
+    pub completed_tick:    Option<Tick>,
+    pub cost:              f64,
+}
+
+const REVIEW_COLUMNS: &str = "alert_id, cycle_tick, customer_id, alert_type, alert_score, decision, desk_filed,
+     qa_filed, error_kind, cost";
+
+fn review_from_row(row: &Row) -> rusqlite::Result<AlertQaReviewRow> {
+    Ok(AlertQaReviewRow {
+        alert_id:    row.get(0)?,
+        cycle_tick:  row.get::<_, i64>(1)? as Tick,
+        customer_id: row.get(2)?,
+        alert_type:  row.get(3)?,
+        alert_score: row.get(4)?,
+        decision:    row.get(5)?,
+        desk_filed:  row.get(6)?,
+        qa_filed:    row.get(7)?,
+        error_kind:  row.get(8)?,
+        cost:        row.get(9)?,
+    })
+}
+
+const CYCLE_COLUMNS: &str = "tick, population, sampled, disagreements, disagreement_rate, disagreement_trend";
+
+fn cycle_from_row(row: &Row) -> rusqlite::Result<AlertQaCycleRow> {
+    Ok(AlertQaCycleRow {
+        tick:               row.get::<_, i64>(0)? as Tick,
+        population:         row.get(1)?,
+        sampled:            row.get(2)?,
+        disagreements:      row.get(3)?,
+        disagreement_rate:  row.get(4)?,
+        disagreement_trend: row.get(5)?,
+    })
+}
+
+const RETRAINING_COLUMNS: &str = "retraining_id, alert_type, error_kind, disagreement_rate, opened_tick, due_tick,
+     status, completed_tick, cost";
+
+fn retraining_from_row(row: &Row) -> rusqlite::Result<QaRetrainingRow> {
+    Ok(QaRetrainingRow {
+        retraining_id:     row.get(0)?,
+        alert_type:        row.get(1)?,
+        error_kind:        row.get(2)?,
+        disagreement_rate: row.get(3)?,
+        opened_tick:       row.get::<_, i64>(4)? as Tick,
+        due_tick:          row.get::<_, i64>(5)? as Tick,
+        status:            row.get(6)?,
+        completed_tick:    row.get::<_, Option<i64>>(7)?.map(|t| t as Tick),
+        cost:              row.get(8)?,
+    })
+}
+
+impl SimStore {
+    /// SAR decisions made in `start..=end` that QA has not re-reviewed,
+    /// in the order made.
+    pub fn unreviewed_sar_decisions(&self, run_id: &str, start: Tick, end: Tick) -> SimResult<Vec<QaCandidate>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT d.alert_id, d.customer_id, COALESCE(a.alert_type, 'unknown'), d.alert_score, d.decision
+               FROM sar_decision d
+               LEFT JOIN aml_alert a ON a.run_id = d.run_id AND a.alert_id = d.alert_id
+              WHERE d.run_id = ?1 AND d.tick >= ?2 AND d.tick <= ?3
+                AND NOT EXISTS (SELECT 1 FROM alert_qa_review r
+                                 WHERE r.run_id = d.run_id AND r.alert_id = d.alert_id)
+              ORDER BY d.tick, d.alert_id",
+        )?;
+        let rows = stmt.query_map(params![run_id, start as i64, end as i64], |row| {
+            Ok(QaCandidate {
+                alert_id:    row.get(0)?,
+                customer_id: row.get(1)?,
+                alert_type:  row.get(2)?,
+                alert_score: row.get(3)?,
+                decision:    row.get(4)?,
+            })
+        })?;
+        rows.collect::<Result<Vec<_>, _>>().map_err(Into::into)
+    }
+
+    pub fn insert_alert_qa_review(&self, run_id: &str, row: &AlertQaReviewRow) -> SimResult<()> {
+        self.conn.execute(
+            &format!(
+                "INSERT INTO alert_qa_review (run_id, {REVIEW_COLUMNS})
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11)"
+            ),
+            params![
+                run_id, row.alert_id, row.cycle_tick as i64, row.customer_id, row.alert_type, row.alert_score,
+                row.decision, row.desk_filed, row.qa_filed, row.error_kind, row.cost,
+            ],
+        )?;
+        Ok(())
+    }
+
+    /// Every QA review in the run, by cycle and then alert.
+    pub fn alert_qa_reviews(&self, run_id: &str) -> SimResult<Vec<AlertQaReviewRow>> {
+        let mut stmt = self.conn.prepare(&format!(
+            "SELECT {REVIEW_COLUMNS} FROM alert_qa_review WHERE run_id = ?1 ORDER BY cycle_tick, alert_id"
+        ))?;
+        let rows = stmt.query_map(params![run_id], review_from_row)?;
+        rows.collect::<Result<Vec<_>, _>>().map_err(Into::into)
+    }
+
+    pub fn insert_alert_qa_cycle(&self, run_id: &str, row: &AlertQaCycleRow) -> SimResult<()> {
+        self.conn.execute(
+            &format!("INSERT INTO alert_qa_cycle (run_id, {CYCLE_COLUMNS}) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)"),
+            params![
+                run_id, row.tick as i64, row.population, row.sampled, row.disagreements, row.disagreement_rate,
+                row.disagreement_trend,
+            ],
+        )?;
+        Ok(())
+    }
+
+    /// Every QA cycle in the run, oldest first.
+    pub fn alert_qa_cycles(&self, run_id: &str) -> SimResult<Vec<AlertQaCycleRow>> {
+        let mut stmt =
+            self.conn.prepare(&format!("SELECT {CYCLE_COLUMNS} FROM alert_qa_cycle WHERE run_id = ?1 ORDER BY tick"))?;
+        let rows = stmt.query_map(params![run_id], cycle_from_row)?;
+        rows.collect::<Result<Vec<_>, _>>().map_err(Into::into)
+    }
+
+    pub fn latest_alert_qa_cycle(&self, run_id: &str) -> SimResult<Option<AlertQaCycleRow>> {
+        self.conn
+            .query_row(
+                &format!("SELECT {CYCLE_COLUMNS} FROM alert_qa_cycle WHERE run_id = ?1 ORDER BY tick DESC LIMIT 1"),
+                params![run_id],
+                cycle_from_row,
+            )
+            .optional()
+            .map_err(Into::into)
+    }
+
+    pub fn insert_qa_retraining(&self, run_id: &str, row: &QaRetrainingRow) -> SimResult<()> {
+        self.conn.execute(
+            &format!(
+                "INSERT INTO qa_retraining (run_id, {RETRAINING_COLUMNS})
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)"
+            ),
+            params![
+                run_id, row.retraining_id, row.alert_type, row.error_kind, row.disagreement_rate,
+                row.opened_tick as i64, row.due_tick as i64, row.status, row.completed_tick.map(|t| t as i64),
+                row.cost,
+            ],
+        )?;
+        Ok(())
+    }
+
+    /// Whether retraining on the alert type is already under way.
+    pub fn qa_retraining_open_for(&self, run_id: &str, alert_type: &str) -> SimResult<bool> {
+        let open: i64 = self.conn.query_row(
+            "SELECT COUNT(*) FROM qa_retraining WHERE run_id = ?1 AND alert_type = ?2 AND status = 'open'",
+            params![run_id, alert_type],
+            |row| row.get(0),
+        )?;
+        Ok(open > 0)
+    }
+
+    /// Complete every open retraining due by `tick`; returns those
+    /// completed.
+    pub fn complete_due_qa_retrainings(&self, run_id: &str, tick: Tick) -> SimResult<Vec<QaRetrainingRow>> {
+        let mut stmt = self.conn.prepare(&format!(
+            "SELECT {RETRAINING_COLUMNS} FROM qa_retraining
+              WHERE run_id = ?1 AND status = 'open' AND due_tick <= ?2
+              ORDER BY due_tick, retraining_id"
+        ))?;
+        let due = stmt
+            .query_map(params![run_id, tick as i64], retraining_from_row)?
+            .collect::<Result<Vec<_>, _>>()?;
+        for item in &due {
+            self.conn.execute(
+                "UPDATE qa_retraining SET status = 'completed', completed_tick = ?3
+                  WHERE run_id = ?1 AND retraining_id = ?2",
+                params![run_id, item.retraining_id, tick as i64],
+            )?;
+        }
+        Ok(due)
+    }
+
+    /// Every retraining work item in the run, oldest first.
+    pub fn qa_retrainings(&self, run_id: &str) -> SimResult<Vec<QaRetrainingRow>> {
+        let mut stmt = self.conn.prepare(&format!(
+            "SELECT {RETRAINING_COLUMNS} FROM qa_retraining WHERE run_id = ?1 ORDER BY opened_tick, retraining_id"
+        ))?;
+        let rows = stmt.query_map(params![run_id], retraining_from_row)?;
+        rows.collect::<Result<Vec<_>, _>>().map_err(Into::into)
+    }
+
+    /// QA reviews done and retraining completed in the window, inclusive.
+    pub fn alert_qa_cost_between(&self, run_id: &str, start_tick: Tick, end_tick: Tick) -> SimResult<f64> {
+        let total: f64 = self.conn.query_row(
+            "SELECT (SELECT COALESCE(SUM(cost), 0.0) FROM alert_qa_review
+                      WHERE run_id = ?1 AND cycle_tick >= ?2 AND cycle_tick <= ?3)
+                  + (SELECT COALESCE(SUM(cost), 0.0) FROM qa_retraining
+                      WHERE run_id = ?1 AND status = 'completed' AND completed_tick >= ?2 AND completed_tick <= ?3)",
+            params![run_id, start_tick as i64, end_tick as i64],
+            |row| row.get(0),
+        )?;
+        Ok(total)
+    }
+}
@@ -0,0 +1,223 @@
+//! Store methods for alert triage (Phase 4.85).
+
+use crate::{error::SimResult, types::Tick};
+use rusqlite::{params, OptionalExtension};
+
+use super::SimStore;
+
+/// Row from the `alert_note` table.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AlertNoteRow {
+    pub tick:    Tick,
+    pub analyst: Option<String>,
+    /// 'assign' | 'close' | 'escalate'
+    pub action:  String,
+    pub note:    String,
+}
+
+/// Row from the `alert_aging` table.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AlertAgingRow {
+    pub tick:                  Tick,
+    pub open_alerts:           i64,
+    pub investigating_alerts:  i64,
+    pub avg_age_ticks:         f64,
+    pub max_age_ticks:         i64,
+    pub aged_alerts:           i64,
+    pub closed_false_positive: i64,
+    pub closed_suspicious:     i64,
+    pub closed_sar_filed:      i64,
+}
+
+impl SimStore {
+    /// The analyst an alert is assigned to, if any.
+    pub fn alert_assignee(&self, run_id: &str, alert_id: &str) -> SimResult<Option<String>> {
+        let analyst = self
+            .conn
+            .query_row(
+                "SELECT assigned_to FROM aml_alert WHERE run_id = ?1 AND alert_id = ?2",
+                params![run_id, alert_id],
+                |row| row.get(0),
+            )
+            .optional()?;
+        Ok(analyst.flatten())
+    }
+
+    /// How an alert was closed; None while it is open.
+    pub fn alert_disposition(&self, run_id: &str, alert_id: &str) -> SimResult<Option<String>> {
+        let disposition = self
+            .conn
+            .query_row(
+                "SELECT disposition FROM aml_alert WHERE run_id = ?1 AND alert_id = ?2",
+                params![run_id, alert_id],
+                |row| row.get(0),
+            )
+            .optional()?;
+        Ok(disposition.flatten())
+    }
+
+    /// Put an alert under investigation by `analyst`.
+    pub fn assign_alert(&self, run_id: &str, alert_id: &str, analyst: &str) -> SimResult<()> {
+        self.conn.execute(
+            "UPDATE aml_alert SET status = 'investigating', assigned_to = ?3
+              WHERE run_id = ?1 AND alert_id = ?2",
+            params![run_id, alert_id, analyst],
+        )?;
+        Ok(())
+    }
+
+    /// Put an open alert under investigation, keeping any assignee.
+    pub fn start_alert_investigation(&self, run_id: &str, alert_id: &str) -> SimResult<()> {
+        self.conn.execute(
+            "UPDATE aml_alert SET status = 'investigating'
+              WHERE run_id = ?1 AND alert_id = ?2 AND status = 'open'",
+            params![run_id, alert_id],
+        )?;
+        Ok(())
+    }
+
+    /// Resolve an alert without a filing, as 'false_positive' or
+    /// 'suspicious'.
+    pub fn close_alert(
+        &self,
+        run_id: &str,
+        alert_id: &str,
+        disposition: &str,
+        tick: Tick,
+        note: &str,
+    ) -> SimResult<()> {
+        self.conn.execute(
+            "UPDATE aml_alert SET status = 'resolved', disposition = ?3, resolved_tick = ?4,
+                    resolution_notes = ?5
+              WHERE run_id = ?1 AND alert_id = ?2",
+            params![run_id, alert_id, disposition, tick as i64, note],
+        )?;
+        Ok(())
+    }
+
+    /// Mark a case escalated, keeping the first escalation's tick.
+    pub fn escalate_alert_case(&self, run_id: &str, case_id: &str, tick: Tick) -> SimResult<()> {
+        self.conn.execute(
+            "UPDATE alert_case SET escalated_tick = COALESCE(escalated_tick, ?3)
+              WHERE run_id = ?1 AND case_id = ?2",
+            params![run_id, case_id, tick as i64],
+        )?;
+        Ok(())
+    }
+
+    pub fn insert_alert_note(
+        &self,
+        run_id: &str,
+        alert_id: &str,
+        tick: Tick,
+        analyst: Option<&str>,
+        action: &str,
+        note: &str,
+    ) -> SimResult<()> {
+        self.conn.execute(
+            "INSERT INTO alert_note (run_id, alert_id, tick, analyst, action, note)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+            params![run_id, alert_id, tick as i64, analyst, action, note],
+        )?;
+        Ok(())
+    }
+
+    /// An alert's notes, oldest first.
+    pub fn alert_notes(&self, run_id: &str, alert_id: &str) -> SimResult<Vec<AlertNoteRow>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT tick, analyst, action, note FROM alert_note
+              WHERE run_id = ?1 AND alert_id = ?2 ORDER BY tick, rowid",
+        )?;
+        let rows = stmt.query_map(params![run_id, alert_id], |row| {
+            Ok(AlertNoteRow {
+                tick:    row.get::<_, i64>(0)? as Tick,
+                analyst: row.get(1)?,
+                action:  row.get(2)?,
+                note:    row.get(3)?,
+            })
+        })?;
+        rows.collect::<Result<Vec<_>, _>>().map_err(Into::into)
+    }
+
+    /// The alert queue at `tick`: alerts open or under investigation and
+    /// their ages, those older than `aged_after` ticks, and the alerts
+    /// closed during the tick by disposition.
+    pub fn alert_aging_at(&self, run_id: &str, tick: Tick, aged_after: Tick) -> SimResult<AlertAgingRow> {
+        let (open_alerts, investigating_alerts, avg_age_ticks, max_age_ticks, aged_alerts) = self.conn.query_row(
+            "SELECT COALESCE(SUM(status = 'open'), 0),
+                    COALESCE(SUM(status = 'investigating'), 0),
+                    COALESCE(AVG(?2 - tick), 0.0),
+                    COALESCE(MAX(?2 - tick), 0),
+                    COALESCE(SUM(?2 - tick > ?3), 0)
+               FROM aml_alert
+              WHERE run_id = ?1 AND status IN ('open', 'investigating')",
+            params![run_id, tick as i64, aged_after as i64],
+            |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?, row.get(4)?)),
+        )?;
+        let (closed_false_positive, closed_suspicious, closed_sar_filed) = self.conn.query_row(
+            "SELECT COALESCE(SUM(disposition = 'false_positive'), 0),
+                    COALESCE(SUM(disposition = 'suspicious'), 0),
+                    COALESCE(SUM(disposition = 'sar_filed'), 0)
+               FROM aml_alert
+              WHERE run_id = ?1 AND resolved_tick = ?2",
+            params![run_id, tick as i64],
+            |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)),
+        )?;
+        Ok(AlertAgingRow {
+            tick,
+            open_alerts,
+            investigating_alerts,
+            avg_age_ticks,
+            max_age_ticks,
+            aged_alerts,
+            closed_false_positive,
+            closed_suspicious,
+            closed_sar_filed,
+        })
+    }
+
+    pub fn insert_alert_aging(&self, run_id: &str, row: &AlertAgingRow) -> SimResult<()> {
+        self.conn.execute(
+            "INSERT INTO alert_aging (run_id, tick, open_alerts, investigating_alerts, avg_age_ticks,
+                                      max_age_ticks, aged_alerts, closed_false_positive, closed_suspicious,
+                                      closed_sar_filed)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)",
+            params![
+                run_id,
+                row.tick as i64,
+                row.open_alerts,
+                row.investigating_alerts,
+                row.avg_age_ticks,
+                row.max_age_ticks,
+                row.aged_alerts,
+                row.closed_false_positive,
+                row.closed_suspicious,
+                row.closed_sar_filed,
+            ],
+        )?;
+        Ok(())
+    }
+
+    /// Every tick's alert queue metrics, in tick order.
+    pub fn alert_aging(&self, run_id: &str) -> SimResult<Vec<AlertAgingRow>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT tick, open_alerts, investigating_alerts, avg_age_ticks, max_age_ticks, aged_alerts,
+                    closed_false_positive, closed_suspicious, closed_sar_filed
+               FROM alert_aging WHERE run_id = ?1 ORDER BY tick",
+        )?;
+        let rows = stmt.query_map(params![run_id], |row| {
+            Ok(AlertAgingRow {
+                tick:                  row.get::<_, i64>(0)? as Tick,
+                open_alerts:           row.get(1)?,
+                investigating_alerts:  row.get(2)?,
+                avg_age_ticks:         row.get(3)?,
+                max_age_ticks:         row.get(4)?,
+                aged_alerts:           row.get(5)?,
+                closed_false_positive: row.get(6)?,
+                closed_suspicious:     row.get(7)?,
+                closed_sar_filed:      row.get(8)?,
+            })
+        })?;
+        rows.collect::<Result<Vec<_>, _>>().map_err(Into::into)
+    }
+}
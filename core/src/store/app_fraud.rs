@@ -0,0 +1,154 @@
+//! Store methods for authorized push payment fraud (Phase 4.91).
+
+use crate::{error::SimResult, types::Tick};
+use rusqlite::{params, OptionalExtension, Row};
+
+use super::SimStore;
+
+/// Row from the `app_scam_payment` table.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AppScamPaymentRow {
+    pub txn_id:       String,
+    pub customer_id:  String,
+    pub account_id:   String,
+    pub tick:         Tick,
+    pub amount:       f64,
+    pub rail_id:      String,
+    pub scam_type:    String,
+    pub payee:        String,
+    pub vulnerable:   bool,
+    /// When the victim reports it; None if they never do.
+    pub report_tick:  Option<Tick>,
+    /// 'pending' | 'reimbursed' | 'declined'; None until reported.
+    pub claim_status: Option<String>,
+    pub decided_tick: Option<Tick>,
+    /// Reimbursement policy the claim was decided under.
+    pub policy:       Option<String>,
+    pub reimbursed:   f64,
+}
+
+const COLUMNS: &str = "txn_id, customer_id, account_id, tick, amount, rail_id, scam_type, payee, vulnerable,
+     report_tick, claim_status, decided_tick, policy, reimbursed";
+
+fn from_row(row: &Row) -> rusqlite::Result<AppScamPaymentRow> {
+    Ok(AppScamPaymentRow {
+        txn_id:       row.get(0)?,
+        customer_id:  row.get(1)?,
+        account_id:   row.get(2)?,
+        tick:         row.get::<_, i64>(3)? as Tick,
+        amount:       row.get(4)?,
+        rail_id:      row.get(5)?,
+        scam_type:    row.get(6)?,
+        payee:        row.get(7)?,
+        vulnerable:   row.get::<_, i64>(8)? != 0,
+        report_tick:  row.get::<_, Option<i64>>(9)?.map(|t| t as Tick),
+        claim_status: row.get(10)?,
+        decided_tick: row.get::<_, Option<i64>>(11)?.map(|t| t as Tick),
+        policy:       row.get(12)?,
+        reimbursed:   row.get(13)?,
+    })
+}
+
+impl SimStore {
+    pub fn insert_app_scam_payment(&self, run_id: &str, row: &AppScamPaymentRow) -> SimResult<()> {
+        self.conn.execute(
+            &format!(
+                "INSERT INTO app_scam_payment (run_id, {COLUMNS})
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15)"
+            ),
+            params![
+                run_id, row.txn_id, row.customer_id, row.account_id, row.tick as i64, row.amount, row.rail_id,
+                row.scam_type, row.payee, row.vulnerable as i64, row.report_tick.map(|t| t as i64),
+                row.claim_status, row.decided_tick.map(|t| t as i64), row.policy, row.reimbursed,
+            ],
+        )?;
+        Ok(())
+    }
+
+    /// Record a claim's status and, once decided, its outcome.
+    pub fn update_app_claim(&self, run_id: &str, row: &AppScamPaymentRow) -> SimResult<()> {
+        self.conn.execute(
+            "UPDATE app_scam_payment SET claim_status = ?3, decided_tick = ?4, policy = ?5, reimbursed = ?6
+             WHERE run_id = ?1 AND txn_id = ?2",
+            params![
+                run_id, row.txn_id, row.claim_status, row.decided_tick.map(|t| t as i64), row.policy,
+                row.reimbursed,
+            ],
+        )?;
+        Ok(())
+    }
+
+    /// Every scam payment of the run, oldest first.
+    pub fn app_scam_payments(&self, run_id: &str) -> SimResult<Vec<AppScamPaymentRow>> {
+        let mut stmt = self.conn.prepare(&format!(
+            "SELECT {COLUMNS} FROM app_scam_payment WHERE run_id = ?1 ORDER BY tick, txn_id"
+        ))?;
+        let rows = stmt.query_map(params![run_id], from_row)?;
+        rows.collect::<Result<Vec<_>, _>>().map_err(Into::into)
+    }
+
+    /// Scam payments their victims report on `tick`.
+    pub fn app_scams_reported_on(&self, run_id: &str, tick: Tick) -> SimResult<Vec<AppScamPaymentRow>> {
+        let mut stmt = self.conn.prepare(&format!(
+            "SELECT {COLUMNS} FROM app_scam_payment
+             WHERE run_id = ?1 AND report_tick = ?2 AND claim_status IS NULL ORDER BY txn_id"
+        ))?;
+        let rows = stmt.query_map(params![run_id, tick as i64], from_row)?;
+        rows.collect::<Result<Vec<_>, _>>().map_err(Into::into)
+    }
+
+    /// Claims still pending that were reported on or before `reported_by`.
+    pub fn app_claims_due(&self, run_id: &str, reported_by: Tick) -> SimResult<Vec<AppScamPaymentRow>> {
+        let mut stmt = self.conn.prepare(&format!(
+            "SELECT {COLUMNS} FROM app_scam_payment
+             WHERE run_id = ?1 AND claim_status = 'pending' AND report_tick <= ?2
+             ORDER BY report_tick, txn_id"
+        ))?;
+        let rows = stmt.query_map(params![run_id, reported_by as i64], from_row)?;
+        rows.collect::<Result<Vec<_>, _>>().map_err(Into::into)
+    }
+
+    /// Claims decided over `[start, end]`: what the bank reimbursed, and
+    /// what the victims were left to bear.
+    pub fn app_fraud_losses_between(&self, run_id: &str, start: Tick, end: Tick) -> SimResult<(f64, f64)> {
+        self.conn
+            .query_row(
+                "SELECT COALESCE(SUM(reimbursed), 0.0), COALESCE(SUM(amount - reimbursed), 0.0)
+                 FROM app_scam_payment
+                 WHERE run_id = ?1 AND decided_tick >= ?2 AND decided_tick <= ?3",
+                params![run_id, start as i64, end as i64],
+                |row| Ok((row.get(0)?, row.get(1)?)),
+            )
+            .map_err(Into::into)
+    }
+
+    /// Whether a customer is flagged vulnerable or was at least
+    /// `min_age` when they opened.
+    pub fn customer_is_vulnerable(&self, run_id: &str, customer_id: &str, min_age: i64) -> SimResult<bool> {
+        let vulnerable: Option<i64> = self
+            .conn
+            .query_row(
+                "SELECT c.is_vulnerable OR COALESCE(i.age_at_open >= ?3, 0)
+                 FROM customer c
+                 LEFT JOIN customer_identity i ON i.run_id = c.run_id AND i.customer_id = c.customer_id
+                 WHERE c.run_id = ?1 AND c.customer_id = ?2",
+                params![run_id, customer_id, min_age],
+                |row| row.get(0),
+            )
+            .optional()?;
+        Ok(vulnerable.unwrap_or(0) != 0)
+    }
+
+    /// The rail an outgoing payment went out on.
+    pub fn debit_rail(&self, run_id: &str, txn_id: &str) -> SimResult<Option<String>> {
+        self.conn
+            .query_row(
+                "SELECT payment_rail_id FROM transactions
+                 WHERE run_id = ?1 AND txn_id = ?2 AND direction = 'debit'",
+                params![run_id, txn_id],
+                |row| row.get(0),
+            )
+            .optional()
+            .map_err(Into::into)
+    }
+}
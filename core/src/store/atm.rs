@@ -0,0 +1,171 @@
+//! Store methods for the ATM network (Phase 4.92).
+
+use crate::{error::SimResult, types::Tick};
+use rusqlite::params;
+
+use super::SimStore;
+
+/// Row from the `atm` table.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AtmRow {
+    pub atm_id:      String,
+    pub location:    String,
+    /// 'in_service' | 'out_of_service'
+    pub status:      String,
+    /// The fault that took it out of service, while it is out.
+    pub incident_id: Option<String>,
+    pub out_since:   Option<Tick>,
+}
+
+/// One ATM's withdrawals in a tick.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AtmActivity {
+    pub atm_id:      String,
+    pub withdrawals: i64,
+    /// Distinct cards used.
+    pub cards:       i64,
+    pub amount:      f64,
+}
+
+/// Row from the `atm_cash_out` table.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AtmCashOutRow {
+    pub txn_id:      String,
+    pub cash_out_id: String,
+    pub tick:        Tick,
+    pub target_atm:  String,
+    pub atm_id:      String,
+    pub account_id:  String,
+    pub amount:      f64,
+}
+
+impl SimStore {
+    pub fn upsert_atm(&self, run_id: &str, row: &AtmRow) -> SimResult<()> {
+        self.conn.execute(
+            "INSERT OR REPLACE INTO atm (run_id, atm_id, location, status, incident_id, out_since)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+            params![
+                run_id, row.atm_id, row.location, row.status, row.incident_id,
+                row.out_since.map(|t| t as i64),
+            ],
+        )?;
+        Ok(())
+    }
+
+    /// The run's ATM fleet, in id order.
+    pub fn atms(&self, run_id: &str) -> SimResult<Vec<AtmRow>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT atm_id, location, status, incident_id, out_since
+             FROM atm WHERE run_id = ?1 ORDER BY atm_id",
+        )?;
+        let rows = stmt.query_map(params![run_id], |row| {
+            Ok(AtmRow {
+                atm_id:      row.get(0)?,
+                location:    row.get(1)?,
+                status:      row.get(2)?,
+                incident_id: row.get(3)?,
+                out_since:   row.get::<_, Option<i64>>(4)?.map(|t| t as Tick),
+            })
+        })?;
+        rows.collect::<Result<Vec<_>, _>>().map_err(Into::into)
+    }
+
+    /// Whether an incident has been resolved, by whoever closed it.
+    pub fn incident_resolved(&self, run_id: &str, incident_id: &str) -> SimResult<bool> {
+        let resolved: i64 = self.conn.query_row(
+            "SELECT COUNT(*) FROM incident WHERE run_id = ?1 AND incident_id = ?2 AND status = 'resolved'",
+            params![run_id, incident_id],
+            |row| row.get(0),
+        )?;
+        Ok(resolved > 0)
+    }
+
+    /// Whether a P0 or P1 against the whole ATM fleet is unresolved.
+    pub fn atm_fleet_down(&self, run_id: &str) -> SimResult<bool> {
+        let open: i64 = self.conn.query_row(
+            "SELECT COUNT(*) FROM incident
+             WHERE run_id = ?1 AND component_id = 'atm_fleet' AND status != 'resolved'
+               AND severity IN ('P0', 'P1')",
+            params![run_id],
+            |row| row.get(0),
+        )?;
+        Ok(open > 0)
+    }
+
+    /// Record the ATM a booked withdrawal was made at.
+    pub fn set_transaction_atm(&self, run_id: &str, txn_id: &str, atm_id: &str) -> SimResult<()> {
+        self.conn.execute(
+            "UPDATE transactions SET atm_id = ?1 WHERE run_id = ?2 AND txn_id = ?3",
+            params![atm_id, run_id, txn_id],
+        )?;
+        Ok(())
+    }
+
+    /// Each ATM's withdrawals on `tick`, in id order; ATMs without any
+    /// are left out.
+    pub fn atm_activity(&self, run_id: &str, tick: Tick) -> SimResult<Vec<AtmActivity>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT atm_id, COUNT(*), COUNT(DISTINCT account_id), COALESCE(SUM(amount), 0.0)
+             FROM transactions
+             WHERE run_id = ?1 AND tick = ?2 AND atm_id IS NOT NULL
+             GROUP BY atm_id ORDER BY atm_id",
+        )?;
+        let rows = stmt.query_map(params![run_id, tick as i64], |row| {
+            Ok(AtmActivity {
+                atm_id:      row.get(0)?,
+                withdrawals: row.get(1)?,
+                cards:       row.get(2)?,
+                amount:      row.get(3)?,
+            })
+        })?;
+        rows.collect::<Result<Vec<_>, _>>().map_err(Into::into)
+    }
+
+    /// Cards used at `min_atms` or more distinct ATMs on `tick`, with how
+    /// many, in account order.
+    pub fn cards_at_many_atms(&self, run_id: &str, tick: Tick, min_atms: usize) -> SimResult<Vec<(String, i64)>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT account_id, COUNT(DISTINCT atm_id) AS atms
+             FROM transactions
+             WHERE run_id = ?1 AND tick = ?2 AND atm_id IS NOT NULL
+             GROUP BY account_id HAVING atms >= ?3 ORDER BY account_id",
+        )?;
+        let rows = stmt.query_map(params![run_id, tick as i64, min_atms as i64], |row| {
+            Ok((row.get(0)?, row.get(1)?))
+        })?;
+        rows.collect::<Result<Vec<_>, _>>().map_err(Into::into)
+    }
+
+    pub fn insert_atm_cash_out(&self, run_id: &str, row: &AtmCashOutRow) -> SimResult<()> {
+        self.conn.execute(
+            "INSERT INTO atm_cash_out
+             (run_id, txn_id, cash_out_id, tick, target_atm, atm_id, account_id, amount)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+            params![
+                run_id, row.txn_id, row.cash_out_id, row.tick as i64, row.target_atm, row.atm_id,
+                row.account_id, row.amount,
+            ],
+        )?;
+        Ok(())
+    }
+
+    /// Every cloned-card withdrawal of the run, oldest first.
+    pub fn atm_cash_outs(&self, run_id: &str) -> SimResult<Vec<AtmCashOutRow>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT txn_id, cash_out_id, tick, target_atm, atm_id, account_id, amount
+             FROM atm_cash_out WHERE run_id = ?1 ORDER BY tick, cash_out_id, account_id, txn_id",
+        )?;
+        let rows = stmt.query_map(params![run_id], |row| {
+            Ok(AtmCashOutRow {
+                txn_id:      row.get(0)?,
+                cash_out_id: row.get(1)?,
+                tick:        row.get::<_, i64>(2)? as Tick,
+                target_atm:  row.get(3)?,
+                atm_id:      row.get(4)?,
+                account_id:  row.get(5)?,
+                amount:      row.get(6)?,
+            })
+        })?;
+        rows.collect::<Result<Vec<_>, _>>().map_err(Into::into)
+    }
+}
@@ -0,0 +1,62 @@
+//! Store methods for the command authorization audit log (Phase 4.46).
+
+use crate::{authorization::Role, error::SimResult, types::Tick};
+use rusqlite::params;
+
+/// Row from the `command_authorization` table.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AuthorizationAuditRow {
+    pub tick:          Tick,
+    pub command:       String,
+    pub role:          Role,
+    pub required_role: Role,
+    pub allowed:       bool,
+    pub reason:        String,
+}
+
+use super::SimStore;
+
+impl SimStore {
+    pub fn insert_authorization_audit(&self, run_id: &str, row: &AuthorizationAuditRow) -> SimResult<()> {
+        self.conn.execute(
+            "INSERT INTO command_authorization
+             (run_id, tick, command, role, required_role, allowed, reason)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+            params![
+                run_id, row.tick as i64, row.command, row.role.as_str(),
+                row.required_role.as_str(), row.allowed, row.reason,
+            ],
+        )?;
+        Ok(())
+    }
+
+    /// Every gated command of a run, oldest first.
+    pub fn authorization_audit(&self, run_id: &str) -> SimResult<Vec<AuthorizationAuditRow>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT tick, command, role, required_role, allowed, reason
+             FROM command_authorization WHERE run_id = ?1 ORDER BY audit_id",
+        )?;
+        let rows = stmt.query_map(params![run_id], |row| {
+            Ok((
+                row.get::<_, i64>(0)? as Tick,
+                row.get::<_, String>(1)?,
+                row.get::<_, String>(2)?,
+                row.get::<_, String>(3)?,
+                row.get::<_, bool>(4)?,
+                row.get::<_, String>(5)?,
+            ))
+        })?;
+        rows.map(|row| {
+            let (tick, command, role, required_role, allowed, reason) = row?;
+            Ok(AuthorizationAuditRow {
+                tick,
+                command,
+                role: role.parse()?,
+                required_role: required_role.parse()?,
+                allowed,
+                reason,
+            })
+        })
+        .collect()
+    }
+}
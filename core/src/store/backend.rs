@@ -0,0 +1,108 @@
+//! Storage backend trait for the engine's own persistence (Phase 4.21).
+//!
+//! `StoreBackend` covers what `SimEngine` writes and reads back itself:
+//! the event log and snapshots. `SimStore` implements it over SQLite and
+//! stays the default; `MemoryBackend` keeps the same data in plain
+//! vectors for hosts that cannot link SQLite and for runs that never need
+//! to be queried afterwards. Swap one in with `SimEngine::set_backend`.
+//!
+//! Subsystem stores are not covered yet: their queries join across
+//! tables and still go through `SimStore` directly, as do player commands,
+//! which subsystems look up by id. Readers of the SQLite event log
+//! (exports, state deltas, regulatory exam counts) see nothing of a run
+//! logged to another backend.
+
+use super::SimStore;
+use crate::{error::SimResult, event::EventLogEntry, types::Tick};
+use std::sync::Mutex;
+
+pub trait StoreBackend: Send {
+    fn append_event(&self, entry: &EventLogEntry) -> SimResult<()>;
+
+    /// A tick's events in the order they were appended.
+    fn events_for_tick(&self, run_id: &str, tick: Tick) -> SimResult<Vec<EventLogEntry>>;
+
+    fn save_snapshot(&self, run_id: &str, tick: Tick, state_json: &str) -> SimResult<()>;
+
+    /// The latest snapshot at or before `tick`.
+    fn latest_snapshot_before(&self, run_id: &str, tick: Tick) -> SimResult<Option<(Tick, String)>>;
+}
+
+impl StoreBackend for SimStore {
+    fn append_event(&self, entry: &EventLogEntry) -> SimResult<()> {
+        SimStore::append_event(self, entry)
+    }
+
+    fn events_for_tick(&self, run_id: &str, tick: Tick) -> SimResult<Vec<EventLogEntry>> {
+        SimStore::events_for_tick(self, run_id, tick)
+    }
+
+    fn save_snapshot(&self, run_id: &str, tick: Tick, state_json: &str) -> SimResult<()> {
+        SimStore::save_snapshot(self, run_id, tick, state_json)
+    }
+
+    fn latest_snapshot_before(&self, run_id: &str, tick: Tick) -> SimResult<Option<(Tick, String)>> {
+        SimStore::latest_snapshot_before(self, run_id, tick)
+    }
+}
+
+/// In-memory backend. Event ids count up from 1, as SQLite rowids do.
+#[derive(Default)]
+pub struct MemoryBackend {
+    tables: Mutex<MemoryTables>,
+}
+
+#[derive(Default)]
+struct MemoryTables {
+    events: Vec<EventLogEntry>,
+    snapshots: Vec<(String, Tick, String)>,
+}
+
+impl MemoryBackend {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn tables(&self) -> std::sync::MutexGuard<'_, MemoryTables> {
+        self.tables.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+impl StoreBackend for MemoryBackend {
+    fn append_event(&self, entry: &EventLogEntry) -> SimResult<()> {
+        let mut tables = self.tables();
+        let id = tables.events.len() as i64 + 1;
+        tables.events.push(EventLogEntry {
+            id: Some(id),
+            ..entry.clone()
+        });
+        Ok(())
+    }
+
+    fn events_for_tick(&self, run_id: &str, tick: Tick) -> SimResult<Vec<EventLogEntry>> {
+        Ok(self
+            .tables()
+            .events
+            .iter()
+            .filter(|e| e.run_id == run_id && e.tick == tick)
+            .cloned()
+            .collect())
+    }
+
+    fn save_snapshot(&self, run_id: &str, tick: Tick, state_json: &str) -> SimResult<()> {
+        self.tables()
+            .snapshots
+            .push((run_id.to_string(), tick, state_json.to_string()));
+        Ok(())
+    }
+
+    fn latest_snapshot_before(&self, run_id: &str, tick: Tick) -> SimResult<Option<(Tick, String)>> {
+        Ok(self
+            .tables()
+            .snapshots
+            .iter()
+            .filter(|(r, t, _)| r == run_id && *t <= tick)
+            .max_by_key(|(_, t, _)| *t)
+            .map(|(_, t, json)| (*t, json.clone())))
+    }
+}
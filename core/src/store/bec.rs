@@ -0,0 +1,233 @@
+//! Store methods for business email compromise (Phase 4.53).
+
+use crate::{error::SimResult, types::Tick};
+use rusqlite::{params, OptionalExtension, Row};
+
+use super::SimStore;
+
+/// Row from the `invoice_wire` table.
+#[derive(Debug, Clone, PartialEq)]
+pub struct InvoiceWireRow {
+    pub txn_id:       String,
+    pub customer_id:  String,
+    pub account_id:   String,
+    pub tick:         Tick,
+    pub amount:       f64,
+    pub counterparty: String,
+    /// Paid to a fraudster posing as a vendor.
+    pub spoofed:      bool,
+}
+
+/// An outgoing wire to a payee the account has not paid in the lookback,
+/// with the largest debit it made there.
+#[derive(Debug, Clone, PartialEq)]
+pub struct NewPayeeWireRow {
+    pub txn_id:        String,
+    pub account_id:    String,
+    pub customer_id:   String,
+    pub segment:       String,
+    pub amount:        f64,
+    pub counterparty:  String,
+    pub largest_debit: f64,
+}
+
+/// Row from the `wire_recall` table.
+#[derive(Debug, Clone, PartialEq)]
+pub struct WireRecallRow {
+    pub txn_id:       String,
+    pub account_id:   String,
+    pub wire_tick:    Tick,
+    pub amount:       f64,
+    pub alert_id:     Option<String>,
+    pub alerted_tick: Option<Tick>,
+    pub recall_tick:  Option<Tick>,
+    /// 'recovered' | 'funds_gone' | 'not_fraud'
+    pub outcome:      Option<String>,
+    pub recovered:    f64,
+}
+
+const INVOICE_COLUMNS: &str = "txn_id, customer_id, account_id, tick, amount, counterparty, spoofed";
+
+fn invoice_from_row(row: &Row) -> rusqlite::Result<InvoiceWireRow> {
+    Ok(InvoiceWireRow {
+        txn_id:       row.get(0)?,
+        customer_id:  row.get(1)?,
+        account_id:   row.get(2)?,
+        tick:         row.get::<_, i64>(3)? as Tick,
+        amount:       row.get(4)?,
+        counterparty: row.get(5)?,
+        spoofed:      row.get::<_, i64>(6)? != 0,
+    })
+}
+
+const RECALL_COLUMNS: &str = "txn_id, account_id, wire_tick, amount, alert_id, alerted_tick,
+     recall_tick, outcome, recovered";
+
+fn recall_from_row(row: &Row) -> rusqlite::Result<WireRecallRow> {
+    Ok(WireRecallRow {
+        txn_id:       row.get(0)?,
+        account_id:   row.get(1)?,
+        wire_tick:    row.get::<_, i64>(2)? as Tick,
+        amount:       row.get(3)?,
+        alert_id:     row.get(4)?,
+        alerted_tick: row.get::<_, Option<i64>>(5)?.map(|t| t as Tick),
+        recall_tick:  row.get::<_, Option<i64>>(6)?.map(|t| t as Tick),
+        outcome:      row.get(7)?,
+        recovered:    row.get(8)?,
+    })
+}
+
+impl SimStore {
+    /// Customers in any of `segments`.
+    pub fn customers_in_segments(&self, run_id: &str, segments: &[String]) -> SimResult<Vec<String>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT customer_id, segment FROM customer WHERE run_id = ?1 ORDER BY customer_id",
+        )?;
+        let rows = stmt.query_map(params![run_id], |row| Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?)))?;
+        let mut customers = Vec::new();
+        for row in rows {
+            let (customer_id, segment) = row?;
+            if segments.contains(&segment) {
+                customers.push(customer_id);
+            }
+        }
+        Ok(customers)
+    }
+
+    pub fn insert_invoice_wire(&self, run_id: &str, row: &InvoiceWireRow) -> SimResult<()> {
+        self.conn.execute(
+            &format!("INSERT INTO invoice_wire (run_id, {INVOICE_COLUMNS}) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)"),
+            params![
+                run_id, row.txn_id, row.customer_id, row.account_id, row.tick as i64,
+                row.amount, row.counterparty, row.spoofed as i64,
+            ],
+        )?;
+        Ok(())
+    }
+
+    pub fn invoice_wire(&self, run_id: &str, txn_id: &str) -> SimResult<Option<InvoiceWireRow>> {
+        self.conn
+            .query_row(
+                &format!("SELECT {INVOICE_COLUMNS} FROM invoice_wire WHERE run_id = ?1 AND txn_id = ?2"),
+                params![run_id, txn_id],
+                invoice_from_row,
+            )
+            .optional()
+            .map_err(Into::into)
+    }
+
+    /// Every invoice paid by wire, oldest first.
+    pub fn invoice_wires(&self, run_id: &str) -> SimResult<Vec<InvoiceWireRow>> {
+        let mut stmt = self.conn.prepare(&format!(
+            "SELECT {INVOICE_COLUMNS} FROM invoice_wire WHERE run_id = ?1 ORDER BY tick, account_id"
+        ))?;
+        let rows = stmt.query_map(params![run_id], invoice_from_row)?;
+        rows.collect::<Result<Vec<_>, _>>().map_err(Into::into)
+    }
+
+    /// Outgoing wires booked at `tick` to a payee their account has not
+    /// paid since `lookback_start`.
+    pub fn new_payee_wires(&self, run_id: &str, tick: Tick, lookback_start: Tick) -> SimResult<Vec<NewPayeeWireRow>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT t.txn_id, t.account_id, a.customer_id, c.segment, t.amount, t.counterparty,
+                    (SELECT COALESCE(MAX(p.amount), 0.0) FROM transactions p
+                     WHERE p.run_id = t.run_id AND p.account_id = t.account_id
+                       AND p.direction = 'debit' AND p.tick >= ?3 AND p.tick < t.tick)
+             FROM transactions t
+             JOIN account a ON a.account_id = t.account_id AND a.run_id = t.run_id
+             JOIN customer c ON c.customer_id = a.customer_id AND c.run_id = t.run_id
+             WHERE t.run_id = ?1 AND t.tick = ?2 AND t.direction = 'debit'
+               AND t.payment_rail_id = 'wire' AND t.counterparty IS NOT NULL
+               AND NOT EXISTS (
+                   SELECT 1 FROM transactions p
+                   WHERE p.run_id = t.run_id AND p.account_id = t.account_id
+                     AND p.counterparty = t.counterparty AND p.tick >= ?3 AND p.tick < t.tick)
+             ORDER BY t.account_id, t.rowid",
+        )?;
+        let rows = stmt.query_map(params![run_id, tick as i64, lookback_start as i64], |row| {
+            Ok(NewPayeeWireRow {
+                txn_id:        row.get(0)?,
+                account_id:    row.get(1)?,
+                customer_id:   row.get(2)?,
+                segment:       row.get(3)?,
+                amount:        row.get(4)?,
+                counterparty:  row.get(5)?,
+                largest_debit: row.get(6)?,
+            })
+        })?;
+        rows.collect::<Result<Vec<_>, _>>().map_err(Into::into)
+    }
+
+    /// An outgoing wire with a payee, as a recall row not yet alerted or
+    /// recalled.
+    pub fn outgoing_wire(&self, run_id: &str, txn_id: &str) -> SimResult<Option<WireRecallRow>> {
+        self.conn
+            .query_row(
+                "SELECT txn_id, account_id, tick, amount FROM transactions
+                 WHERE run_id = ?1 AND txn_id = ?2 AND direction = 'debit'
+                   AND payment_rail_id = 'wire' AND counterparty IS NOT NULL",
+                params![run_id, txn_id],
+                |row| {
+                    Ok(WireRecallRow {
+                        txn_id:       row.get(0)?,
+                        account_id:   row.get(1)?,
+                        wire_tick:    row.get::<_, i64>(2)? as Tick,
+                        amount:       row.get(3)?,
+                        alert_id:     None,
+                        alerted_tick: None,
+                        recall_tick:  None,
+                        outcome:      None,
+                        recovered:    0.0,
+                    })
+                },
+            )
+            .optional()
+            .map_err(Into::into)
+    }
+
+    pub fn upsert_wire_recall(&self, run_id: &str, row: &WireRecallRow) -> SimResult<()> {
+        self.conn.execute(
+            &format!(
+                "INSERT OR REPLACE INTO wire_recall (run_id, {RECALL_COLUMNS})
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)"
+            ),
+            params![
+                run_id, row.txn_id, row.account_id, row.wire_tick as i64, row.amount, row.alert_id,
+                row.alerted_tick.map(|t| t as i64), row.recall_tick.map(|t| t as i64), row.outcome,
+                row.recovered,
+            ],
+        )?;
+        Ok(())
+    }
+
+    pub fn wire_recall(&self, run_id: &str, txn_id: &str) -> SimResult<Option<WireRecallRow>> {
+        self.conn
+            .query_row(
+                &format!("SELECT {RECALL_COLUMNS} FROM wire_recall WHERE run_id = ?1 AND txn_id = ?2"),
+                params![run_id, txn_id],
+                recall_from_row,
+            )
+            .optional()
+            .map_err(Into::into)
+    }
+
+    /// Every alerted or recalled wire, oldest first.
+    pub fn wire_recalls(&self, run_id: &str) -> SimResult<Vec<WireRecallRow>> {
+        let mut stmt = self.conn.prepare(&format!(
+            "SELECT {RECALL_COLUMNS} FROM wire_recall WHERE run_id = ?1 ORDER BY wire_tick, account_id, amount"
+        ))?;
+        let rows = stmt.query_map(params![run_id], recall_from_row)?;
+        rows.collect::<Result<Vec<_>, _>>().map_err(Into::into)
+    }
+
+    /// Alerted wires nobody has recalled, alerted at or before `tick`.
+    pub fn unrecalled_wires_alerted_by(&self, run_id: &str, tick: Tick) -> SimResult<Vec<WireRecallRow>> {
+        let mut stmt = self.conn.prepare(&format!(
+            "SELECT {RECALL_COLUMNS} FROM wire_recall
+             WHERE run_id = ?1 AND recall_tick IS NULL AND alerted_tick <= ?2
+             ORDER BY alerted_tick, account_id, amount"
+        ))?;
+        let rows = stmt.query_map(params![run_id, tick as i64], recall_from_row)?;
+        rows.collect::<Result<Vec<_>, _>>().map_err(Into::into)
+    }
+}
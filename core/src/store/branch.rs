@@ -0,0 +1,344 @@
+//! Store methods for the branch network (Phase 4.3).
+
+use crate::{error::SimResult, types::Tick};
+use rusqlite::{params, OptionalExtension};
+
+/// Row from the `branch` table.
+#[derive(Debug, Clone)]
+pub struct BranchRow {
+    pub branch_id:   String,
+    pub state_code:  String,
+    pub status:      String,
+    pub staff_count: i64,
+    pub opened_tick: Tick,
+    pub closed_tick: Option<Tick>,
+}
+
+/// One branch's line in the monthly performance report.
+#[derive(Debug, Clone)]
+pub struct BranchPerformanceRow {
+    pub branch_id:              String,
+    pub customer_count:         i64,
+    pub staff_count:            i64,
+    pub cash_txn_count:         i64,
+    pub cash_volume:            f64,
+    pub complaints_opened:      i64,
+    pub complaint_rate:         f64,
+    pub churned_count:          i64,
+    pub projected_acquisitions: f64,
+    pub operating_cost:         f64,
+}
+
+use super::SimStore;
+
+fn branch_row_mapper(row: &rusqlite::Row<'_>) -> rusqlite::Result<BranchRow> {
+    Ok(BranchRow {
+        branch_id:   row.get(0)?,
+        state_code:  row.get(1)?,
+        status:      row.get(2)?,
+        staff_count: row.get(3)?,
+        opened_tick: row.get::<_, i64>(4)? as u64,
+        closed_tick: row.get::<_, Option<i64>>(5)?.map(|t| t as u64),
+    })
+}
+
+impl SimStore {
+    /// Open a new branch.
+    pub fn insert_branch(
+        &self,
+        run_id:      &str,
+        branch_id:   &str,
+        state_code:  &str,
+        staff_count: i64,
+        tick:        Tick,
+    ) -> SimResult<()> {
+        self.conn.execute(
+            "INSERT INTO branch (run_id, branch_id, state_code, status, staff_count, opened_tick)
+             VALUES (?1, ?2, ?3, 'open', ?4, ?5)",
+            params![run_id, branch_id, state_code, staff_count, tick as i64],
+        )?;
+        Ok(())
+    }
+
+    /// Close a branch.
+    pub fn close_branch(&self, run_id: &str, branch_id: &str, tick: Tick) -> SimResult<()> {
+        self.conn.execute(
+            "UPDATE branch SET status = 'closed', closed_tick = ?1
+             WHERE run_id = ?2 AND branch_id = ?3",
+            params![tick as i64, run_id, branch_id],
+        )?;
+        Ok(())
+    }
+
+    /// Update a branch's headcount.
+    pub fn set_branch_staff(&self, run_id: &str, branch_id: &str, staff_count: i64) -> SimResult<()> {
+        self.conn.execute(
+            "UPDATE branch SET staff_count = ?1 WHERE run_id = ?2 AND branch_id = ?3",
+            params![staff_count, run_id, branch_id],
+        )?;
+        Ok(())
+    }
+
+    pub fn get_branch(&self, run_id: &str, branch_id: &str) -> SimResult<Option<BranchRow>> {
+        let row = self.conn.query_row(
+            "SELECT branch_id, state_code, status, staff_count, opened_tick, closed_tick
+             FROM branch WHERE run_id = ?1 AND branch_id = ?2",
+            params![run_id, branch_id],
+            branch_row_mapper,
+        ).optional()?;
+        Ok(row)
+    }
+
+    /// All open branches, in branch_id order.
+    pub fn open_branches(&self, run_id: &str) -> SimResult<Vec<BranchRow>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT branch_id, state_code, status, staff_count, opened_tick, closed_tick
+             FROM branch WHERE run_id = ?1 AND status = 'open'
+             ORDER BY branch_id",
+        )?;
+        let rows = stmt.query_map(params![run_id], branch_row_mapper)?;
+        rows.collect::<Result<Vec<_>, _>>().map_err(Into::into)
+    }
+
+    /// Number of branches ever opened in a state (open or closed).
+    pub fn branches_in_state(&self, run_id: &str, state_code: &str) -> SimResult<i64> {
+        let count: i64 = self.conn.query_row(
+            "SELECT COUNT(*) FROM branch WHERE run_id = ?1 AND state_code = ?2",
+            params![run_id, state_code],
+            |row| row.get(0),
+        )?;
+        Ok(count)
+    }
+
+    /// Active customer counts per state, in state order.
+    pub fn active_customer_counts_by_state(&self, run_id: &str) -> SimResult<Vec<(String, i64)>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT state_code, COUNT(*) FROM customer
+             WHERE run_id = ?1 AND status = 'active' AND state_code IS NOT NULL
+             GROUP BY state_code ORDER BY state_code",
+        )?;
+        let rows = stmt.query_map(params![run_id], |row| Ok((row.get(0)?, row.get(1)?)))?;
+        rows.collect::<Result<Vec<_>, _>>().map_err(Into::into)
+    }
+
+    /// Active customer ids in a state, in id order.
+    pub fn active_customers_in_state(&self, run_id: &str, state_code: &str) -> SimResult<Vec<String>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT customer_id FROM customer
+             WHERE run_id = ?1 AND status = 'active' AND state_code = ?2
+             ORDER BY customer_id",
+        )?;
+        let rows = stmt.query_map(params![run_id, state_code], |row| row.get(0))?;
+        rows.collect::<Result<Vec<_>, _>>().map_err(Into::into)
+    }
+
+    /// Whether a state code exists in the `state_config` reference table.
+    pub fn is_known_state(&self, state_code: &str) -> SimResult<bool> {
+        let count: i64 = self.conn.query_row(
+            "SELECT COUNT(*) FROM state_config WHERE state_code = ?1",
+            params![state_code],
+            |row| row.get(0),
+        )?;
+        Ok(count > 0)
+    }
+
+    /// Onboard a walk-in customer at a branch, modelled on an existing
+    /// customer of that branch (same segment, income, state and product).
+    pub fn insert_branch_acquisition(
+        &self,
+        run_id:      &str,
+        template_id: &str,
+        customer_id: &str,
+        account_id:  &str,
+        branch_id:   &str,
+        tick:        Tick,
+    ) -> SimResult<()> {
+        self.conn.execute(
+            "INSERT INTO customer
+             (customer_id, run_id, name, segment, income_band, risk_band, open_tick, status,
+              monthly_txn_mean, cash_intensity, payroll_amount, has_payroll, state_code)
+             SELECT ?3, run_id, '', segment, income_band, risk_band, ?4, 'active',
+                    monthly_txn_mean, cash_intensity, payroll_amount, has_payroll, state_code
+             FROM customer WHERE run_id = ?1 AND customer_id = ?2",
+            params![run_id, template_id, customer_id, tick as i64],
+        )?;
+        self.conn.execute(
+            "INSERT INTO account
+             (account_id, run_id, customer_id, product_id, balance, available_balance,
+              open_tick, status)
+             SELECT ?3, a.run_id, ?4, a.product_id, c.payroll_amount * 2.0,
+                    c.payroll_amount * 2.0, ?5, 'open'
+             FROM account a
+             JOIN customer c ON c.run_id = a.run_id AND c.customer_id = a.customer_id
+             WHERE a.run_id = ?1 AND a.customer_id = ?2
+             ORDER BY a.account_id LIMIT 1",
+            params![run_id, template_id, account_id, customer_id, tick as i64],
+        )?;
+        self.assign_customer_branch(run_id, customer_id, branch_id, tick)
+    }
+
+    /// Total branch operating cost reported over [start_tick, end_tick].
+    pub fn branch_operating_cost_between(
+        &self,
+        run_id:     &str,
+        start_tick: Tick,
+        end_tick:   Tick,
+    ) -> SimResult<f64> {
+        let cost: f64 = self.conn.query_row(
+            "SELECT COALESCE(SUM(operating_cost), 0.0) FROM branch_performance
+             WHERE run_id = ?1 AND tick >= ?2 AND tick <= ?3",
+            params![run_id, start_tick as i64, end_tick as i64],
+            |row| row.get(0),
+        )?;
+        Ok(cost)
+    }
+
+    /// Active customer ids assigned to a branch, in id order.
+    pub fn branch_customers(&self, run_id: &str, branch_id: &str) -> SimResult<Vec<String>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT cb.customer_id FROM customer_branch cb
+             JOIN customer c ON c.run_id = cb.run_id AND c.customer_id = cb.customer_id
+             WHERE cb.run_id = ?1 AND cb.branch_id = ?2 AND c.status = 'active'
+             ORDER BY cb.customer_id",
+        )?;
+        let rows = stmt.query_map(params![run_id, branch_id], |row| row.get(0))?;
+        rows.collect::<Result<Vec<_>, _>>().map_err(Into::into)
+    }
+
+    /// Assign (or reassign) a customer's home branch.
+    pub fn assign_customer_branch(
+        &self,
+        run_id:      &str,
+        customer_id: &str,
+        branch_id:   &str,
+        tick:        Tick,
+    ) -> SimResult<()> {
+        self.conn.execute(
+            "INSERT OR REPLACE INTO customer_branch (run_id, customer_id, branch_id, assigned_tick)
+             VALUES (?1, ?2, ?3, ?4)",
+            params![run_id, customer_id, branch_id, tick as i64],
+        )?;
+        Ok(())
+    }
+
+    /// Activity counts for a branch's customers over (start_tick, end_tick]:
+    /// (cash_txn_count, cash_volume, complaints_opened, churned_count).
+    pub fn branch_activity(
+        &self,
+        run_id:     &str,
+        branch_id:  &str,
+        start_tick: Tick,
+        end_tick:   Tick,
+    ) -> SimResult<(i64, f64, i64, i64)> {
+        let (cash_count, cash_volume): (i64, f64) = self.conn.query_row(
+            "SELECT COUNT(*), COALESCE(SUM(t.amount), 0.0)
+             FROM transactions t
+             JOIN account a ON a.run_id = t.run_id AND a.account_id = t.account_id
+             JOIN customer_branch cb ON cb.run_id = a.run_id AND cb.customer_id = a.customer_id
+             WHERE t.run_id = ?1 AND cb.branch_id = ?2
+               AND t.category = 'cash_withdrawal' AND t.tick > ?3 AND t.tick <= ?4",
+            params![run_id, branch_id, start_tick as i64, end_tick as i64],
+            |row| Ok((row.get(0)?, row.get(1)?)),
+        )?;
+        let complaints: i64 = self.conn.query_row(
+            "SELECT COUNT(*) FROM complaint c
+             JOIN customer_branch cb ON cb.run_id = c.run_id AND cb.customer_id = c.customer_id
+             WHERE c.run_id = ?1 AND cb.branch_id = ?2
+               AND c.tick_opened > ?3 AND c.tick_opened <= ?4",
+            params![run_id, branch_id, start_tick as i64, end_tick as i64],
+            |row| row.get(0),
+        )?;
+        let churned: i64 = self.conn.query_row(
+            "SELECT COUNT(*) FROM customer c
+             JOIN customer_branch cb ON cb.run_id = c.run_id AND cb.customer_id = c.customer_id
+             WHERE c.run_id = ?1 AND cb.branch_id = ?2 AND c.status = 'churned'
+               AND c.close_tick > ?3 AND c.close_tick <= ?4",
+            params![run_id, branch_id, start_tick as i64, end_tick as i64],
+            |row| row.get(0),
+        )?;
+        Ok((cash_count, cash_volume, complaints, churned))
+    }
+
+    pub fn insert_branch_performance(
+        &self,
+        run_id: &str,
+        tick:   Tick,
+        row:    &BranchPerformanceRow,
+    ) -> SimResult<()> {
+        self.conn.execute(
+            "INSERT OR REPLACE INTO branch_performance
+             (run_id, tick, branch_id, customer_count, staff_count, cash_txn_count,
+              cash_volume, complaints_opened, complaint_rate, churned_count,
+              projected_acquisitions, operating_cost)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12)",
+            params![
+                run_id, tick as i64, row.branch_id, row.customer_count, row.staff_count,
+                row.cash_txn_count, row.cash_volume, row.complaints_opened,
+                row.complaint_rate, row.churned_count, row.projected_acquisitions,
+                row.operating_cost,
+            ],
+        )?;
+        Ok(())
+    }
+
+    /// The most recent monthly branch performance report, in branch order.
+    pub fn latest_branch_performance(&self, run_id: &str) -> SimResult<Vec<BranchPerformanceRow>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT branch_id, customer_count, staff_count, cash_txn_count, cash_volume,
+                    complaints_opened, complaint_rate, churned_count,
+                    projected_acquisitions, operating_cost
+             FROM branch_performance
+             WHERE run_id = ?1
+               AND tick = (SELECT MAX(tick) FROM branch_performance WHERE run_id = ?1)
+             ORDER BY branch_id",
+        )?;
+        let rows = stmt.query_map(params![run_id], |row| {
+            Ok(BranchPerformanceRow {
+                branch_id:              row.get(0)?,
+                customer_count:         row.get(1)?,
+                staff_count:            row.get(2)?,
+                cash_txn_count:         row.get(3)?,
+                cash_volume:            row.get(4)?,
+                complaints_opened:      row.get(5)?,
+                complaint_rate:         row.get(6)?,
+                churned_count:          row.get(7)?,
+                projected_acquisitions: row.get(8)?,
+                operating_cost:         row.get(9)?,
+            })
+        })?;
+        rows.collect::<Result<Vec<_>, _>>().map_err(Into::into)
+    }
+
+    // ── Test / summary helpers ────────────────────────────────────────
+
+    /// Number of branches with the given status (for tests).
+    pub fn branch_count(&self, run_id: &str, status: &str) -> SimResult<i64> {
+        let count: i64 = self.conn.query_row(
+            "SELECT COUNT(*) FROM branch WHERE run_id = ?1 AND status = ?2",
+            params![run_id, status],
+            |row| row.get(0),
+        )?;
+        Ok(count)
+    }
+
+    /// Customers a branch has onboarded, wherever they are served now (for tests).
+    pub fn branch_acquisition_count(&self, run_id: &str, branch_id: &str) -> SimResult<i64> {
+        let count: i64 = self.conn.query_row(
+            "SELECT COUNT(*) FROM customer
+             WHERE run_id = ?1 AND customer_id LIKE 'c-' || ?2 || '-%'",
+            params![run_id, branch_id],
+            |row| row.get(0),
+        )?;
+        Ok(count)
+    }
+
+    /// Number of customers assigned to a branch (for tests).
+    pub fn customer_branch_count(&self, run_id: &str, branch_id: &str) -> SimResult<i64> {
+        let count: i64 = self.conn.query_row(
+            "SELECT COUNT(*) FROM customer_branch WHERE run_id = ?1 AND branch_id = ?2",
+            params![run_id, branch_id],
+            |row| row.get(0),
+        )?;
+        Ok(count)
+    }
+}
@@ -0,0 +1,22 @@
+//! Store methods for pause-on-event breakpoints (Phase 4.74).
+
+use crate::error::SimResult;
+use rusqlite::{params, OptionalExtension};
+
+use super::SimStore;
+
+impl SimStore {
+    /// The account with the lowest balance in the run and that balance,
+    /// or None before any account is opened.
+    pub fn lowest_account_balance(&self, run_id: &str) -> SimResult<Option<(String, f64)>> {
+        self.conn
+            .query_row(
+                "SELECT account_id, balance FROM account WHERE run_id = ?1
+                  ORDER BY balance, account_id LIMIT 1",
+                params![run_id],
+                |row| Ok((row.get(0)?, row.get(1)?)),
+            )
+            .optional()
+            .map_err(Into::into)
+    }
+}
@@ -0,0 +1,68 @@
+//! Store queries over business-day timing (Phase 4.41).
+//!
+//! The calendar itself is config; these read back when settlement,
+//! returns, SLAs and filing deadlines landed.
+
+use crate::{error::SimResult, types::Tick};
+use rusqlite::params;
+
+use super::SimStore;
+
+impl SimStore {
+    // ── Test / summary helpers ────────────────────────────────────────
+
+    /// Ticks a rail ran a settlement batch (for tests).
+    pub fn payment_batch_ticks(&self, run_id: &str, rail_id: &str) -> SimResult<Vec<Tick>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT DISTINCT tick_processed FROM payment_batch
+             WHERE run_id = ?1 AND rail_id = ?2 AND tick_processed IS NOT NULL
+             ORDER BY tick_processed",
+        )?;
+        let rows = stmt.query_map(params![run_id, rail_id], |row| {
+            Ok(row.get::<_, i64>(0)? as Tick)
+        })?;
+        rows.collect::<Result<Vec<_>, _>>().map_err(Into::into)
+    }
+
+    /// Ticks ACH returns came back on (for tests).
+    pub fn ach_return_ticks(&self, run_id: &str) -> SimResult<Vec<Tick>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT DISTINCT tick_returned FROM ach_return
+             WHERE run_id = ?1 ORDER BY tick_returned",
+        )?;
+        let rows = stmt.query_map(params![run_id], |row| Ok(row.get::<_, i64>(0)? as Tick))?;
+        rows.collect::<Result<Vec<_>, _>>().map_err(Into::into)
+    }
+
+    /// (tick_opened, sla_due_tick) of every complaint (for tests).
+    pub fn complaint_sla_windows(&self, run_id: &str) -> SimResult<Vec<(Tick, Tick)>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT tick_opened, sla_due_tick FROM complaint
+             WHERE run_id = ?1 ORDER BY tick_opened, complaint_id",
+        )?;
+        let rows = stmt.query_map(params![run_id], |row| {
+            Ok((row.get::<_, i64>(0)? as Tick, row.get::<_, i64>(1)? as Tick))
+        })?;
+        rows.collect::<Result<Vec<_>, _>>().map_err(Into::into)
+    }
+
+    /// (filing_tick, filing_deadline, filed_on_time) of every SAR and CTR
+    /// (for tests).
+    pub fn regulatory_filing_deadlines(&self, run_id: &str) -> SimResult<Vec<(Tick, Tick, bool)>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT filing_tick, filing_deadline, filed_on_time
+             FROM suspicious_activity_report WHERE run_id = ?1
+             UNION ALL
+             SELECT filing_tick, filing_deadline, filed_on_time
+             FROM currency_transaction_report WHERE run_id = ?1",
+        )?;
+        let rows = stmt.query_map(params![run_id], |row| {
+            Ok((
+                row.get::<_, i64>(0)? as Tick,
+                row.get::<_, i64>(1)? as Tick,
+                row.get::<_, i64>(2)? != 0,
+            ))
+        })?;
+        rows.collect::<Result<Vec<_>, _>>().map_err(Into::into)
+    }
+}
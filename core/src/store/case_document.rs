@@ -0,0 +1,81 @@
+//! Store methods for dispute and complaint documents (Phase 4.31).
+
+use crate::{error::SimResult, types::Tick};
+use rusqlite::params;
+
+use super::SimStore;
+
+/// Row from the `case_document` table. `content` is the document's
+/// structured fields as JSON, `None` while it is missing.
+#[derive(Debug, Clone)]
+pub struct CaseDocumentRow {
+    pub document_id:  String,
+    pub case_type:    String,
+    pub case_id:      String,
+    pub customer_id:  String,
+    pub doc_type:     String,
+    pub tick_created: Tick,
+    pub status:       String,
+    pub content:      Option<String>,
+}
+
+impl SimStore {
+    pub fn insert_case_document(&self, run_id: &str, row: &CaseDocumentRow) -> SimResult<()> {
+        self.conn.execute(
+            "INSERT INTO case_document
+             (run_id, document_id, case_type, case_id, customer_id, doc_type, tick_created, status, content)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
+            params![
+                run_id, row.document_id, row.case_type, row.case_id, row.customer_id,
+                row.doc_type, row.tick_created as i64, row.status, row.content,
+            ],
+        )?;
+        Ok(())
+    }
+
+    /// A case's documents, in the order they were generated.
+    pub fn case_documents(&self, run_id: &str, case_type: &str, case_id: &str) -> SimResult<Vec<CaseDocumentRow>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT document_id, case_type, case_id, customer_id, doc_type, tick_created, status, content
+             FROM case_document WHERE run_id = ?1 AND case_type = ?2 AND case_id = ?3
+             ORDER BY rowid",
+        )?;
+        let rows = stmt.query_map(params![run_id, case_type, case_id], |row| {
+            Ok(CaseDocumentRow {
+                document_id:  row.get(0)?,
+                case_type:    row.get(1)?,
+                case_id:      row.get(2)?,
+                customer_id:  row.get(3)?,
+                doc_type:     row.get(4)?,
+                tick_created: row.get::<_, i64>(5)? as Tick,
+                status:       row.get(6)?,
+                content:      row.get(7)?,
+            })
+        })?;
+        rows.collect::<Result<Vec<_>, _>>().map_err(Into::into)
+    }
+
+    /// Cases filed in the inclusive tick window with at least one
+    /// required document missing.
+    pub fn cases_missing_documents(&self, run_id: &str, tick_start: Tick, tick_end: Tick) -> SimResult<i64> {
+        let count: i64 = self.conn.query_row(
+            "SELECT COUNT(DISTINCT case_type || ':' || case_id) FROM case_document
+             WHERE run_id = ?1 AND status = 'missing' AND tick_created >= ?2 AND tick_created <= ?3",
+            params![run_id, tick_start as i64, tick_end as i64],
+            |row| row.get(0),
+        )?;
+        Ok(count)
+    }
+
+    // ── Test / summary helpers ────────────────────────────────────────
+
+    /// Distinct case ids of a type with any documents (for tests).
+    pub fn documented_cases(&self, run_id: &str, case_type: &str) -> SimResult<Vec<String>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT DISTINCT case_id FROM case_document WHERE run_id = ?1 AND case_type = ?2
+             ORDER BY case_id",
+        )?;
+        let rows = stmt.query_map(params![run_id, case_type], |row| row.get(0))?;
+        rows.collect::<Result<Vec<_>, _>>().map_err(Into::into)
+    }
+}
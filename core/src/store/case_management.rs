@@ -0,0 +1,352 @@
+//! Store methods for case management (Phase 4.86).
+
+use crate::{error::SimResult, types::Tick};
+use rusqlite::{params, OptionalExtension, Row};
+
+use super::SimStore;
+
+/// Row from the `investigation_case` table.
+#[derive(Debug, Clone, PartialEq)]
+pub struct InvestigationCaseRow {
+    pub case_id:            String,
+    pub customer_id:        String,
+    pub opened_tick:        Tick,
+    pub last_activity_tick: Tick,
+    pub item_count:         i64,
+    pub assigned_to:        Option<String>,
+    /// 'open' | 'closed'
+    pub status:             String,
+    /// 'unfounded' | 'confirmed' | 'worked'; None while open.
+    pub disposition:        Option<String>,
+    pub closed_tick:        Option<Tick>,
+}
+
+impl InvestigationCaseRow {
+    /// Ticks the case has been open at `tick`, or was open for once
+    /// closed.
+    pub fn age_ticks(&self, tick: Tick) -> Tick {
+        self.closed_tick.unwrap_or(tick).saturating_sub(self.opened_tick)
+    }
+}
+
+/// An item a case can link: one row of an alert, hit, dispute or
+/// complaint table.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CaseItemRow {
+    /// 'aml_alert' | 'fraud_alert' | 'screening_hit' | 'dispute' | 'complaint'
+    pub item_type:   String,
+    pub item_id:     String,
+    pub customer_id: String,
+    /// When the item was raised, or linked once in a case.
+    pub tick:        Tick,
+    /// Still open on its own desk.
+    pub open:        bool,
+    /// What closing the case set on the item.
+    pub disposition: Option<String>,
+}
+
+/// Row from the `case_note` table.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CaseNoteRow {
+    pub tick:    Tick,
+    pub analyst: Option<String>,
+    /// 'assign' | 'close'
+    pub action:  String,
+    pub note:    String,
+}
+
+const COLUMNS: &str = "case_id, customer_id, opened_tick, last_activity_tick, item_count, assigned_to, status,
+     disposition, closed_tick";
+
+fn case_from_row(row: &Row) -> rusqlite::Result<InvestigationCaseRow> {
+    Ok(InvestigationCaseRow {
+        case_id:            row.get(0)?,
+        customer_id:        row.get(1)?,
+        opened_tick:        row.get::<_, i64>(2)? as Tick,
+        last_activity_tick: row.get::<_, i64>(3)? as Tick,
+        item_count:         row.get(4)?,
+        assigned_to:        row.get(5)?,
+        status:             row.get(6)?,
+        disposition:        row.get(7)?,
+        closed_tick:        row.get::<_, Option<i64>>(8)?.map(|t| t as Tick),
+    })
+}
+
+fn item_from_row(row: &Row) -> rusqlite::Result<CaseItemRow> {
+    Ok(CaseItemRow {
+        item_type:   row.get(0)?,
+        item_id:     row.get(1)?,
+        customer_id: row.get(2)?,
+        tick:        row.get::<_, i64>(3)? as Tick,
+        open:        row.get(4)?,
+        disposition: row.get(5)?,
+    })
+}
+
+/// Whether case item `i` is still open on its own desk.
+const ITEM_OPEN_SQL: &str = "
+CASE i.item_type
+    WHEN 'aml_alert' THEN (SELECT status IN ('open', 'investigating') FROM aml_alert
+                            WHERE run_id = i.run_id AND alert_id = i.item_id)
+    WHEN 'fraud_alert' THEN (SELECT investigation_status IN ('open', 'investigating') FROM fraud_alert
+                              WHERE run_id = i.run_id AND alert_id = i.item_id)
+    WHEN 'screening_hit' THEN (SELECT status IN ('pending_review', 'escalated') FROM aml_screening_result
+                                WHERE run_id = i.run_id AND screening_id = i.item_id)
+    WHEN 'dispute' THEN (SELECT status NOT LIKE 'resolved%' AND status <> 'closed' FROM card_dispute
+                          WHERE run_id = i.run_id AND dispute_id = i.item_id)
+    WHEN 'complaint' THEN (SELECT status = 'open' FROM complaint
+                            WHERE run_id = i.run_id AND complaint_id = i.item_id)
+END";
+
+/// Items raised at or after ?2 that are in no case yet, oldest first and,
+/// within a tick, the items that can open a case before those that can
+/// only join one. A fraud alert belongs to the customer owning the
+/// account or transaction it names.
+const NEW_ITEMS_SQL: &str = "
+WITH items AS (
+    SELECT 'aml_alert' AS item_type, alert_id AS item_id, customer_id, tick, 1 AS opens
+      FROM aml_alert WHERE run_id = ?1 AND tick >= ?2
+    UNION ALL
+    SELECT 'fraud_alert', f.alert_id,
+           CASE f.entity_type WHEN 'customer' THEN f.entity_id ELSE a.customer_id END, f.tick, 1
+      FROM fraud_alert f
+      LEFT JOIN transactions t ON f.entity_type = 'transaction' AND t.txn_id = f.entity_id
+      LEFT JOIN account a
+        ON a.run_id = f.run_id AND a.account_id = CASE f.entity_type WHEN 'account' THEN f.entity_id ELSE t.account_id END
+     WHERE f.run_id = ?1 AND f.tick >= ?2
+    UNION ALL
+    SELECT 'screening_hit', screening_id, customer_id, screening_tick, 1
+      FROM aml_screening_result WHERE run_id = ?1 AND screening_tick >= ?2 AND match_type <> 'none'
+    UNION ALL
+    SELECT 'dispute', dispute_id, customer_id, tick_filed, 0
+      FROM card_dispute WHERE run_id = ?1 AND tick_filed >= ?2
+    UNION ALL
+    SELECT 'complaint', complaint_id, customer_id, tick_opened, 0
+      FROM complaint WHERE run_id = ?1 AND tick_opened >= ?2
+)
+SELECT item_type, item_id, customer_id, tick, opens FROM items i
+ WHERE customer_id IS NOT NULL
+   AND NOT EXISTS (SELECT 1 FROM case_item c
+                    WHERE c.run_id = ?1 AND c.item_type = i.item_type AND c.item_id = i.item_id)
+ ORDER BY tick, opens DESC, item_type, item_id";
+
+/// The open case, newest activity first, with an item on ?2's cluster.
+/// ?3 is the relationship strength linking customers into a cluster;
+/// NULL keeps the cluster to ?2.
+const OPEN_CASE_SQL: &str = "
+WITH RECURSIVE cluster(customer_id) AS (
+    SELECT ?2
+    UNION
+    SELECT CASE r.customer_id_a WHEN c.customer_id THEN r.customer_id_b ELSE r.customer_id_a END
+      FROM customer_relationship r
+      JOIN cluster c ON c.customer_id IN (r.customer_id_a, r.customer_id_b)
+     WHERE r.run_id = ?1 AND r.strength >= ?3
+)
+SELECT k.case_id FROM investigation_case k
+ WHERE k.run_id = ?1 AND k.status = 'open'
+   AND EXISTS (SELECT 1 FROM case_item i
+                WHERE i.run_id = ?1 AND i.case_id = k.case_id AND i.customer_id IN cluster)
+ ORDER BY k.last_activity_tick DESC, k.rowid DESC
+ LIMIT 1";
+
+impl SimStore {
+    /// Items raised at or after `since` not yet in a case, each with
+    /// whether it can open one.
+    pub fn unlinked_case_items(&self, run_id: &str, since: Tick) -> SimResult<Vec<(CaseItemRow, bool)>> {
+        let mut stmt = self.conn.prepare(NEW_ITEMS_SQL)?;
+        let rows = stmt.query_map(params![run_id, since as i64], |row| {
+            let item = CaseItemRow {
+                item_type:   row.get(0)?,
+                item_id:     row.get(1)?,
+                customer_id: row.get(2)?,
+                tick:        row.get::<_, i64>(3)? as Tick,
+                open:        true,
+                disposition: None,
+            };
+            Ok((item, row.get(4)?))
+        })?;
+        rows.collect::<Result<Vec<_>, _>>().map_err(Into::into)
+    }
+
+    /// The open case on the customer's cluster, if any.
+    pub fn open_investigation_case_for(
+        &self,
+        run_id: &str,
+        customer_id: &str,
+        min_relationship_strength: Option<f64>,
+    ) -> SimResult<Option<String>> {
+        let case_id = self
+            .conn
+            .query_row(OPEN_CASE_SQL, params![run_id, customer_id, min_relationship_strength], |row| row.get(0))
+            .optional()?;
+        Ok(case_id)
+    }
+
+    /// Open an empty case on the customer at `tick`; items join it
+    /// through `link_case_item`.
+    pub fn open_investigation_case(&self, run_id: &str, case_id: &str, customer_id: &str, tick: Tick) -> SimResult<()> {
+        self.conn.execute(
+            "INSERT INTO investigation_case (run_id, case_id, customer_id, opened_tick, last_activity_tick,
+                                             item_count)
+             VALUES (?1, ?2, ?3, ?4, ?4, 0)",
+            params![run_id, case_id, customer_id, tick as i64],
+        )?;
+        Ok(())
+    }
+
+    /// Link `item` into an open case at `tick`.
+    pub fn link_case_item(&self, run_id: &str, case_id: &str, item: &CaseItemRow, tick: Tick) -> SimResult<()> {
+        self.conn.execute(
+            "INSERT INTO case_item (run_id, case_id, item_type, item_id, customer_id, linked_tick)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+            params![run_id, case_id, item.item_type, item.item_id, item.customer_id, tick as i64],
+        )?;
+        self.conn.execute(
+            "UPDATE investigation_case
+                SET item_count = item_count + 1, last_activity_tick = MAX(last_activity_tick, ?3)
+              WHERE run_id = ?1 AND case_id = ?2",
+            params![run_id, case_id, tick as i64],
+        )?;
+        Ok(())
+    }
+
+    pub fn investigation_case(&self, run_id: &str, case_id: &str) -> SimResult<Option<InvestigationCaseRow>> {
+        let case = self
+            .conn
+            .query_row(
+                &format!("SELECT {COLUMNS} FROM investigation_case WHERE run_id = ?1 AND case_id = ?2"),
+                params![run_id, case_id],
+                case_from_row,
+            )
+            .optional()?;
+        Ok(case)
+    }
+
+    /// Every case in the run, oldest first.
+    pub fn investigation_cases(&self, run_id: &str) -> SimResult<Vec<InvestigationCaseRow>> {
+        let mut stmt = self.conn.prepare(&format!(
+            "SELECT {COLUMNS} FROM investigation_case WHERE run_id = ?1 ORDER BY opened_tick, rowid"
+        ))?;
+        let rows = stmt.query_map(params![run_id], case_from_row)?;
+        rows.collect::<Result<Vec<_>, _>>().map_err(Into::into)
+    }
+
+    /// A case's items in the order they were linked, each with whether it
+    /// is still open on its own desk.
+    pub fn case_items(&self, run_id: &str, case_id: &str) -> SimResult<Vec<CaseItemRow>> {
+        let mut stmt = self.conn.prepare(&format!(
+            "SELECT i.item_type, i.item_id, i.customer_id, i.linked_tick, COALESCE({ITEM_OPEN_SQL}, 0),
+                    i.disposition
+               FROM case_item i WHERE i.run_id = ?1 AND i.case_id = ?2
+              ORDER BY i.linked_tick, i.rowid"
+        ))?;
+        let rows = stmt.query_map(params![run_id, case_id], item_from_row)?;
+        rows.collect::<Result<Vec<_>, _>>().map_err(Into::into)
+    }
+
+    /// Open cases none of whose items is still open, oldest first.
+    pub fn worked_investigation_cases(&self, run_id: &str) -> SimResult<Vec<InvestigationCaseRow>> {
+        let mut stmt = self.conn.prepare(&format!(
+            "SELECT {COLUMNS} FROM investigation_case k
+              WHERE run_id = ?1 AND status = 'open'
+                AND NOT EXISTS (SELECT 1 FROM case_item i
+                                 WHERE i.run_id = ?1 AND i.case_id = k.case_id AND COALESCE({ITEM_OPEN_SQL}, 0))
+              ORDER BY opened_tick, rowid"
+        ))?;
+        let rows = stmt.query_map(params![run_id], case_from_row)?;
+        rows.collect::<Result<Vec<_>, _>>().map_err(Into::into)
+    }
+
+    pub fn assign_investigation_case(&self, run_id: &str, case_id: &str, analyst: &str) -> SimResult<()> {
+        self.conn.execute(
+            "UPDATE investigation_case SET assigned_to = ?3 WHERE run_id = ?1 AND case_id = ?2",
+            params![run_id, case_id, analyst],
+        )?;
+        Ok(())
+    }
+
+    pub fn close_investigation_case(&self, run_id: &str, case_id: &str, disposition: &str, tick: Tick) -> SimResult<()> {
+        self.conn.execute(
+            "UPDATE investigation_case SET status = 'closed', disposition = ?3, closed_tick = ?4
+              WHERE run_id = ?1 AND case_id = ?2",
+            params![run_id, case_id, disposition, tick as i64],
+        )?;
+        Ok(())
+    }
+
+    /// Record what closing its case set on an item.
+    pub fn set_case_item_disposition(
+        &self,
+        run_id: &str,
+        item_type: &str,
+        item_id: &str,
+        disposition: &str,
+    ) -> SimResult<()> {
+        self.conn.execute(
+            "UPDATE case_item SET disposition = ?4 WHERE run_id = ?1 AND item_type = ?2 AND item_id = ?3",
+            params![run_id, item_type, item_id, disposition],
+        )?;
+        Ok(())
+    }
+
+    /// Close a fraud alert as 'resolved' or 'false_positive'.
+    pub fn resolve_fraud_alert(&self, run_id: &str, alert_id: &str, status: &str, tick: Tick) -> SimResult<()> {
+        self.conn.execute(
+            "UPDATE fraud_alert SET investigation_status = ?3, resolved_tick = ?4
+              WHERE run_id = ?1 AND alert_id = ?2",
+            params![run_id, alert_id, status, tick as i64],
+        )?;
+        Ok(())
+    }
+
+    /// Review a screening hit as 'confirmed' or 'false_positive'.
+    pub fn review_screening_hit(
+        &self,
+        run_id: &str,
+        screening_id: &str,
+        status: &str,
+        tick: Tick,
+        note: &str,
+    ) -> SimResult<()> {
+        self.conn.execute(
+            "UPDATE aml_screening_result SET status = ?3, reviewed_tick = ?4, review_notes = ?5
+              WHERE run_id = ?1 AND screening_id = ?2",
+            params![run_id, screening_id, status, tick as i64, note],
+        )?;
+        Ok(())
+    }
+
+    pub fn insert_case_note(
+        &self,
+        run_id: &str,
+        case_id: &str,
+        tick: Tick,
+        analyst: Option<&str>,
+        action: &str,
+        note: &str,
+    ) -> SimResult<()> {
+        self.conn.execute(
+            "INSERT INTO case_note (run_id, case_id, tick, analyst, action, note)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+            params![run_id, case_id, tick as i64, analyst, action, note],
+        )?;
+        Ok(())
+    }
+
+    /// A case's notes, oldest first.
+    pub fn case_notes(&self, run_id: &str, case_id: &str) -> SimResult<Vec<CaseNoteRow>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT tick, analyst, action, note FROM case_note
+              WHERE run_id = ?1 AND case_id = ?2 ORDER BY tick, rowid",
+        )?;
+        let rows = stmt.query_map(params![run_id, case_id], |row| {
+            Ok(CaseNoteRow {
+                tick:    row.get::<_, i64>(0)? as Tick,
+                analyst: row.get(1)?,
+                action:  row.get(2)?,
+                note:    row.get(3)?,
+            })
+        })?;
+        rows.collect::<Result<Vec<_>, _>>().map_err(Into::into)
+    }
+}
@@ -0,0 +1,105 @@
+//! Store methods for service channels (Phase 4.90).
+
+use crate::{error::SimResult, types::Tick};
+use rusqlite::params;
+
+use super::SimStore;
+
+/// Row from the `channel_activity` table.
+#[derive(Debug, Clone)]
+pub struct ChannelActivityRow {
+    pub tick:      Tick,
+    pub channel:   String,
+    /// 'up' | 'degraded' | 'down'
+    pub status:    String,
+    /// Transactions the channel served, its own and those diverted to it.
+    pub completed: i64,
+    /// Turned away here and completed on another channel.
+    pub diverted:  i64,
+    /// Turned away here and never booked.
+    pub abandoned: i64,
+    pub cost:      f64,
+}
+
+/// Debits of one category on one rail through one channel.
+#[derive(Debug, Clone)]
+pub struct DebitChannelCount {
+    pub category: String,
+    pub rail_id:  String,
+    pub channel:  Option<String>,
+    pub count:    i64,
+}
+
+impl SimStore {
+    /// Record the channel a booked transaction went through.
+    pub fn set_transaction_channel(&self, run_id: &str, txn_id: &str, channel: &str) -> SimResult<()> {
+        self.conn.execute(
+            "UPDATE transactions SET channel = ?1 WHERE run_id = ?2 AND txn_id = ?3",
+            params![channel, run_id, txn_id],
+        )?;
+        Ok(())
+    }
+
+    pub fn insert_channel_activity(&self, run_id: &str, row: &ChannelActivityRow) -> SimResult<()> {
+        self.conn.execute(
+            "INSERT OR REPLACE INTO channel_activity
+             (run_id, tick, channel, status, completed, diverted, abandoned, cost)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+            params![
+                run_id, row.tick as i64, row.channel, row.status, row.completed, row.diverted,
+                row.abandoned, row.cost,
+            ],
+        )?;
+        Ok(())
+    }
+
+    /// Every channel's day, oldest first, channels in name order.
+    pub fn channel_activity(&self, run_id: &str) -> SimResult<Vec<ChannelActivityRow>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT tick, channel, status, completed, diverted, abandoned, cost
+             FROM channel_activity WHERE run_id = ?1 ORDER BY tick, channel",
+        )?;
+        let rows = stmt.query_map(params![run_id], |row| {
+            Ok(ChannelActivityRow {
+                tick:      row.get::<_, i64>(0)? as Tick,
+                channel:   row.get(1)?,
+                status:    row.get(2)?,
+                completed: row.get(3)?,
+                diverted:  row.get(4)?,
+                abandoned: row.get(5)?,
+                cost:      row.get(6)?,
+            })
+        })?;
+        rows.collect::<Result<Vec<_>, _>>().map_err(Into::into)
+    }
+
+    /// Cost to serve across every channel over `[start, end]`.
+    pub fn channel_cost_between(&self, run_id: &str, start: Tick, end: Tick) -> SimResult<f64> {
+        let cost: f64 = self.conn.query_row(
+            "SELECT COALESCE(SUM(cost), 0.0) FROM channel_activity
+             WHERE run_id = ?1 AND tick BETWEEN ?2 AND ?3",
+            params![run_id, start as i64, end as i64],
+            |row| row.get(0),
+        )?;
+        Ok(cost)
+    }
+
+    /// Debits booked by category, rail and channel. Card purchases and
+    /// the bank's own postings, such as fees, have no channel.
+    pub fn debit_channel_counts(&self, run_id: &str) -> SimResult<Vec<DebitChannelCount>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT category, COALESCE(payment_rail_id, 'ACH'), channel, COUNT(*) FROM transactions
+             WHERE run_id = ?1 AND direction = 'debit'
+             GROUP BY category, payment_rail_id, channel ORDER BY category, payment_rail_id, channel",
+        )?;
+        let rows = stmt.query_map(params![run_id], |row| {
+            Ok(DebitChannelCount {
+                category: row.get(0)?,
+                rail_id:  row.get(1)?,
+                channel:  row.get(2)?,
+                count:    row.get(3)?,
+            })
+        })?;
+        rows.collect::<Result<Vec<_>, _>>().map_err(Into::into)
+    }
+}
@@ -0,0 +1,179 @@
+//! Store methods for check fraud (Phase 4.54).
+
+use crate::{error::SimResult, types::Tick};
+use rusqlite::{params, OptionalExtension, Row};
+
+use super::SimStore;
+
+/// Row from the `check_item` table.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CheckItemRow {
+    pub item_id:       String,
+    pub account_id:    String,
+    pub tick:          Tick,
+    /// 'presented' | 'deposited'
+    pub side:          String,
+    /// 'clearing' | 'atm' | 'branch'
+    pub channel:       String,
+    pub drawer:        String,
+    pub serial:        i64,
+    pub payee:         String,
+    pub amount:        f64,
+    /// 'genuine' | 'washed' | 'counterfeit'
+    pub typology:      String,
+    pub txn_id:        String,
+    pub rule:          Option<String>,
+    pub alert_id:      Option<String>,
+    pub deadline_tick: Tick,
+    pub status:        String,
+    pub resolved_tick: Option<Tick>,
+    pub loss_bearer:   Option<String>,
+    pub loss:          f64,
+}
+
+const COLUMNS: &str = "item_id, account_id, tick, side, channel, drawer, serial, payee, amount, typology,
+     txn_id, rule, alert_id, deadline_tick, status, resolved_tick, loss_bearer, loss";
+
+fn item_from_row(row: &Row) -> rusqlite::Result<CheckItemRow> {
+    Ok(CheckItemRow {
+        item_id:       row.get(0)?,
+        account_id:    row.get(1)?,
+        tick:          row.get::<_, i64>(2)? as Tick,
+        side:          row.get(3)?,
+        channel:       row.get(4)?,
+        drawer:        row.get(5)?,
+        serial:        row.get(6)?,
+        payee:         row.get(7)?,
+        amount:        row.get(8)?,
+        typology:      row.get(9)?,
+        txn_id:        row.get(10)?,
+        rule:          row.get(11)?,
+        alert_id:      row.get(12)?,
+        deadline_tick: row.get::<_, i64>(13)? as Tick,
+        status:        row.get(14)?,
+        resolved_tick: row.get::<_, Option<i64>>(15)?.map(|t| t as Tick),
+        loss_bearer:   row.get(16)?,
+        loss:          row.get(17)?,
+    })
+}
+
+impl SimStore {
+    pub fn insert_check_item(&self, run_id: &str, row: &CheckItemRow) -> SimResult<()> {
+        self.conn.execute(
+            &format!(
+                "INSERT INTO check_item (run_id, {COLUMNS})
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, ?17, ?18, ?19)"
+            ),
+            params![
+                run_id, row.item_id, row.account_id, row.tick as i64, row.side, row.channel, row.drawer,
+                row.serial, row.payee, row.amount, row.typology, row.txn_id, row.rule, row.alert_id,
+                row.deadline_tick as i64, row.status, row.resolved_tick.map(|t| t as i64), row.loss_bearer,
+                row.loss,
+            ],
+        )?;
+        Ok(())
+    }
+
+    /// Record a flag, hold or resolution on an item already cleared.
+    pub fn update_check_item(&self, run_id: &str, row: &CheckItemRow) -> SimResult<()> {
+        self.conn.execute(
+            "UPDATE check_item SET rule = ?3, alert_id = ?4, status = ?5, resolved_tick = ?6,
+                    loss_bearer = ?7, loss = ?8
+             WHERE run_id = ?1 AND item_id = ?2",
+            params![
+                run_id, row.item_id, row.rule, row.alert_id, row.status, row.resolved_tick.map(|t| t as i64),
+                row.loss_bearer, row.loss,
+            ],
+        )?;
+        Ok(())
+    }
+
+    pub fn check_item(&self, run_id: &str, item_id: &str) -> SimResult<Option<CheckItemRow>> {
+        self.conn
+            .query_row(
+                &format!("SELECT {COLUMNS} FROM check_item WHERE run_id = ?1 AND item_id = ?2"),
+                params![run_id, item_id],
+                item_from_row,
+            )
+            .optional()
+            .map_err(Into::into)
+    }
+
+    /// Every check item, oldest first.
+    pub fn check_items(&self, run_id: &str) -> SimResult<Vec<CheckItemRow>> {
+        let mut stmt = self.conn.prepare(&format!(
+            "SELECT {COLUMNS} FROM check_item WHERE run_id = ?1 ORDER BY tick, side, account_id, serial, amount"
+        ))?;
+        let rows = stmt.query_map(params![run_id], item_from_row)?;
+        rows.collect::<Result<Vec<_>, _>>().map_err(Into::into)
+    }
+
+    /// Items cleared at `tick`, in clearing order.
+    pub fn check_items_at(&self, run_id: &str, tick: Tick) -> SimResult<Vec<CheckItemRow>> {
+        let mut stmt = self.conn.prepare(&format!(
+            "SELECT {COLUMNS} FROM check_item WHERE run_id = ?1 AND tick = ?2 ORDER BY rowid"
+        ))?;
+        let rows = stmt.query_map(params![run_id, tick as i64], item_from_row)?;
+        rows.collect::<Result<Vec<_>, _>>().map_err(Into::into)
+    }
+
+    /// Items in `status`, oldest first.
+    pub fn check_items_in_status(&self, run_id: &str, status: &str) -> SimResult<Vec<CheckItemRow>> {
+        let mut stmt = self.conn.prepare(&format!(
+            "SELECT {COLUMNS} FROM check_item WHERE run_id = ?1 AND status = ?2 ORDER BY tick, rowid"
+        ))?;
+        let rows = stmt.query_map(params![run_id, status], item_from_row)?;
+        rows.collect::<Result<Vec<_>, _>>().map_err(Into::into)
+    }
+
+    /// Washed and counterfeit items nobody has flagged or settled yet,
+    /// oldest first.
+    pub fn outstanding_fraudulent_checks(&self, run_id: &str) -> SimResult<Vec<CheckItemRow>> {
+        let mut stmt = self.conn.prepare(&format!(
+            "SELECT {COLUMNS} FROM check_item
+             WHERE run_id = ?1 AND typology != 'genuine' AND status IN ('paid', 'cashed_out')
+             ORDER BY tick, rowid"
+        ))?;
+        let rows = stmt.query_map(params![run_id], item_from_row)?;
+        rows.collect::<Result<Vec<_>, _>>().map_err(Into::into)
+    }
+
+    /// Whether `drawer` has a check numbered `serial` cleared before
+    /// `item_id`.
+    pub fn check_serial_seen(&self, run_id: &str, drawer: &str, serial: i64, item_id: &str) -> SimResult<bool> {
+        let seen: i64 = self.conn.query_row(
+            "SELECT COUNT(*) FROM check_item
+             WHERE run_id = ?1 AND drawer = ?2 AND serial = ?3
+               AND rowid < (SELECT rowid FROM check_item WHERE run_id = ?1 AND item_id = ?4)",
+            params![run_id, drawer, serial, item_id],
+            |row| row.get(0),
+        )?;
+        Ok(seen > 0)
+    }
+
+    /// Highest serial `drawer` has had paid before `tick`; with
+    /// `genuine_only` ignores the checks they never wrote.
+    pub fn last_check_serial(&self, run_id: &str, drawer: &str, tick: Tick, genuine_only: bool) -> SimResult<Option<i64>> {
+        self.conn
+            .query_row(
+                "SELECT MAX(serial) FROM check_item
+                 WHERE run_id = ?1 AND drawer = ?2 AND side = 'presented' AND tick < ?3
+                   AND (?4 = 0 AND status IN ('paid', 'cleared') OR ?4 = 1 AND typology = 'genuine')",
+                params![run_id, drawer, tick as i64, genuine_only as i64],
+                |row| row.get(0),
+            )
+            .map_err(Into::into)
+    }
+
+    /// Total check fraud losses the bank ate from `start` to `end`, inclusive.
+    pub fn check_fraud_losses_between(&self, run_id: &str, start: Tick, end: Tick) -> SimResult<f64> {
+        self.conn
+            .query_row(
+                "SELECT COALESCE(SUM(loss), 0.0) FROM check_item
+                 WHERE run_id = ?1 AND loss_bearer = 'bank' AND resolved_tick >= ?2 AND resolved_tick <= ?3",
+                params![run_id, start as i64, end as i64],
+                |row| row.get(0),
+            )
+            .map_err(Into::into)
+    }
+}
@@ -161,6 +161,7 @@ impl SimStore {
             product_count: product_count as usize,
             has_active_retention_offer: has_offer,
             active_life_event_delta: life_event_delta,
+            in_hardship_program: self.in_hardship_program(run_id, customer_id, tick)?,
         })
     }
 
@@ -349,20 +350,10 @@ impl SimStore {
             )
             .unwrap_or(0);
 
-        let lookback = tick.saturating_sub(30) as i64;
+        let lookback = tick.saturating_sub(30);
         let tick_i = tick as i64;
-
-        let churned: i64 = self
-            .conn
-            .query_row(
-                "SELECT COUNT(*) FROM customer
-             WHERE run_id = ?1 AND segment = ?2 AND status = 'churned'
-               AND close_tick IS NOT NULL
-               AND close_tick >= ?3 AND close_tick <= ?4",
-                params![run_id, segment, lookback, tick_i],
-                |row| row.get(0),
-            )
-            .unwrap_or(0);
+        let window = self.segment_activity(run_id, segment, lookback, tick)?;
+        let churned = window.churned_customers;
 
         let high_risk: i64 = self
             .conn
@@ -397,51 +388,15 @@ impl SimStore {
             0.0
         };
 
-        let fee_driven: i64 = self
-            .conn
-            .query_row(
-                "SELECT COUNT(*) FROM churn_cohort
-             WHERE run_id = ?1 AND segment = ?2
-               AND tick_churned >= ?3 AND tick_churned <= ?4
-               AND primary_churn_driver = 'fee_burden'",
-                params![run_id, segment, lookback, tick_i],
-                |row| row.get(0),
-            )
-            .unwrap_or(0);
-
-        let service_driven: i64 = self
-            .conn
-            .query_row(
-                "SELECT COUNT(*) FROM churn_cohort
-             WHERE run_id = ?1 AND segment = ?2
-               AND tick_churned >= ?3 AND tick_churned <= ?4
-               AND primary_churn_driver IN ('complaints', 'sla_breach')",
-                params![run_id, segment, lookback, tick_i],
-                |row| row.get(0),
-            )
-            .unwrap_or(0);
-
-        let life_event_driven: i64 = self
-            .conn
-            .query_row(
-                "SELECT COUNT(*) FROM churn_cohort
-             WHERE run_id = ?1 AND segment = ?2
-               AND tick_churned >= ?3 AND tick_churned <= ?4
-               AND primary_churn_driver = 'life_event'",
-                params![run_id, segment, lookback, tick_i],
-                |row| row.get(0),
-            )
-            .unwrap_or(0);
-
         Ok(crate::churn_subsystem::ChurnAggregate {
             active_customers: active,
             churned_this_period: churned,
             high_risk_count: high_risk,
             churn_rate,
             avg_churn_risk: avg_risk,
-            fee_driven_churn: fee_driven,
-            service_driven_churn: service_driven,
-            life_event_churn: life_event_driven,
+            fee_driven_churn: window.fee_driven_churn,
+            service_driven_churn: window.service_driven_churn,
+            life_event_churn: window.life_event_churn,
         })
     }
 
@@ -0,0 +1,146 @@
+//! Store methods for churn prediction evaluation (Phase 4.64).
+
+use crate::{error::SimResult, types::Tick};
+use rusqlite::{params, OptionalExtension, Row};
+
+use super::SimStore;
+
+/// Row from the `churn_prediction_eval` table: one segment's scores from
+/// one batch, judged at one horizon.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ChurnPredictionEvalRow {
+    /// Tick the scores were taken.
+    pub score_tick:      Tick,
+    /// 30 or 90, the prediction judged.
+    pub horizon:         Tick,
+    pub segment:         String,
+    /// Tick the evaluation ran, once the horizon had elapsed.
+    pub eval_tick:       Tick,
+    pub customers:       i64,
+    pub churned:         i64,
+    pub mean_predicted:  f64,
+    pub realized_rate:   f64,
+    /// mean_predicted less realized_rate; positive when the model
+    /// over-predicts churn.
+    pub calibration_gap: f64,
+    pub brier_score:     f64,
+    /// None when the segment had no churners or no stayers.
+    pub auc:             Option<f64>,
+    pub drift:           bool,
+}
+
+/// One scored customer's prediction and what came of it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ChurnOutcome {
+    pub segment:   String,
+    pub predicted: f64,
+    pub churned:   bool,
+}
+
+const EVAL_COLUMNS: &str = "score_tick, horizon, segment, eval_tick, customers, churned, mean_predicted,
+     realized_rate, calibration_gap, brier_score, auc, drift";
+
+fn eval_from_row(row: &Row) -> rusqlite::Result<ChurnPredictionEvalRow> {
+    Ok(ChurnPredictionEvalRow {
+        score_tick:      row.get::<_, i64>(0)? as Tick,
+        horizon:         row.get::<_, i64>(1)? as Tick,
+        segment:         row.get(2)?,
+        eval_tick:       row.get::<_, i64>(3)? as Tick,
+        customers:       row.get(4)?,
+        churned:         row.get(5)?,
+        mean_predicted:  row.get(6)?,
+        realized_rate:   row.get(7)?,
+        calibration_gap: row.get(8)?,
+        brier_score:     row.get(9)?,
+        auc:             row.get(10)?,
+        drift:           row.get(11)?,
+    })
+}
+
+impl SimStore {
+    /// Score batches whose `horizon` had elapsed by `tick` and that have
+    /// not been judged at it, oldest first.
+    pub fn unevaluated_churn_score_ticks(&self, run_id: &str, horizon: Tick, tick: Tick) -> SimResult<Vec<Tick>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT DISTINCT s.tick FROM customer_churn_score s
+              WHERE s.run_id = ?1 AND s.tick + ?2 <= ?3
+                AND NOT EXISTS (SELECT 1 FROM churn_prediction_eval e
+                                 WHERE e.run_id = s.run_id AND e.score_tick = s.tick AND e.horizon = ?2)
+              ORDER BY s.tick",
+        )?;
+        let rows = stmt.query_map(params![run_id, horizon as i64, tick as i64], |row| {
+            row.get::<_, i64>(0).map(|t| t as Tick)
+        })?;
+        rows.collect::<Result<Vec<_>, _>>().map_err(Into::into)
+    }
+
+    /// Each customer scored at `score_tick`, with their `horizon`-day
+    /// prediction (30 or 90) and whether they churned within it, by
+    /// segment.
+    pub fn churn_outcomes(&self, run_id: &str, score_tick: Tick, horizon: Tick) -> SimResult<Vec<ChurnOutcome>> {
+        let predicted = match horizon {
+            30 => "s.predicted_churn_30d",
+            _ => "s.predicted_churn_90d",
+        };
+        let mut stmt = self.conn.prepare(&format!(
+            "SELECT c.segment, {predicted},
+                    c.status = 'churned' AND c.close_tick >= s.tick AND c.close_tick < s.tick + ?3
+               FROM customer_churn_score s
+               JOIN customer c ON c.run_id = s.run_id AND c.customer_id = s.customer_id
+              WHERE s.run_id = ?1 AND s.tick = ?2
+              ORDER BY c.segment, s.customer_id"
+        ))?;
+        let rows = stmt.query_map(params![run_id, score_tick as i64, horizon as i64], |row| {
+            Ok(ChurnOutcome { segment: row.get(0)?, predicted: row.get(1)?, churned: row.get(2)? })
+        })?;
+        rows.collect::<Result<Vec<_>, _>>().map_err(Into::into)
+    }
+
+    pub fn insert_churn_prediction_eval(&self, run_id: &str, row: &ChurnPredictionEvalRow) -> SimResult<()> {
+        self.conn.execute(
+            &format!(
+                "INSERT INTO churn_prediction_eval (run_id, {EVAL_COLUMNS})
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13)"
+            ),
+            params![
+                run_id, row.score_tick as i64, row.horizon as i64, row.segment, row.eval_tick as i64,
+                row.customers, row.churned, row.mean_predicted, row.realized_rate, row.calibration_gap,
+                row.brier_score, row.auc, row.drift,
+            ],
+        )?;
+        Ok(())
+    }
+
+    /// Every evaluation in the run, by score tick, horizon and segment.
+    pub fn churn_prediction_evals(&self, run_id: &str) -> SimResult<Vec<ChurnPredictionEvalRow>> {
+        let mut stmt = self.conn.prepare(&format!(
+            "SELECT {EVAL_COLUMNS} FROM churn_prediction_eval WHERE run_id = ?1
+              ORDER BY score_tick, horizon, segment"
+        ))?;
+        let rows = stmt.query_map(params![run_id], eval_from_row)?;
+        rows.collect::<Result<Vec<_>, _>>().map_err(Into::into)
+    }
+
+    /// A segment's first evaluation at `horizon` over at least
+    /// `min_customers`, the baseline later ones are held to.
+    pub fn churn_eval_baseline(
+        &self,
+        run_id: &str,
+        segment: &str,
+        horizon: Tick,
+        min_customers: i64,
+    ) -> SimResult<Option<ChurnPredictionEvalRow>> {
+        self.conn
+            .query_row(
+                &format!(
+                    "SELECT {EVAL_COLUMNS} FROM churn_prediction_eval
+                      WHERE run_id = ?1 AND segment = ?2 AND horizon = ?3 AND customers >= ?4
+                      ORDER BY score_tick LIMIT 1"
+                ),
+                params![run_id, segment, horizon as i64, min_customers],
+                eval_from_row,
+            )
+            .optional()
+            .map_err(Into::into)
+    }
+}
@@ -1,6 +1,6 @@
 use super::SimStore;
 use crate::{error::SimResult, types::Tick};
-use rusqlite::params;
+use rusqlite::{params, OptionalExtension};
 
 // Helper function for mapping complaint rows
 fn complaint_row_mapper(
@@ -21,6 +21,8 @@ fn complaint_row_mapper(
         resolution_code: row.get(11)?,
         amount_refunded: row.get(12)?,
         udaap_flag: row.get::<_, i32>(13)? != 0,
+        channel: row.get(14)?,
+        service_channel: row.get(15)?,
     })
 }
 
@@ -36,8 +38,8 @@ impl SimStore {
             "INSERT INTO complaint (
                 complaint_id, run_id, customer_id, account_id, tick_opened, tick_closed,
                 product, issue, priority, status, sla_due_tick, sla_breached,
-                resolution_code, amount_refunded, udaap_flag
-             ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15)",
+                resolution_code, amount_refunded, udaap_flag, channel, service_channel
+             ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, ?17)",
             params![
                 &c.complaint_id,
                 run_id,
@@ -54,8 +56,13 @@ impl SimStore {
                 c.resolution_code.as_deref(),
                 c.amount_refunded,
                 if c.udaap_flag { 1i32 } else { 0i32 },
+                &c.channel,
+                c.service_channel.as_deref(),
             ],
         )?;
+        if c.status == "open" {
+            self.adjust_complaint_backlog(run_id, 1, c.tick_opened as i64)?;
+        }
         Ok(())
     }
 
@@ -68,7 +75,7 @@ impl SimStore {
             .query_row(
                 "SELECT complaint_id, customer_id, account_id, tick_opened, tick_closed,
                     product, issue, priority, status, sla_due_tick, sla_breached,
-                    resolution_code, amount_refunded, udaap_flag
+                    resolution_code, amount_refunded, udaap_flag, channel, service_channel
              FROM complaint WHERE run_id = ?1 AND complaint_id = ?2",
                 params![run_id, complaint_id],
                 complaint_row_mapper,
@@ -83,7 +90,7 @@ impl SimStore {
         let mut stmt = self.conn.prepare(
             "SELECT complaint_id, customer_id, account_id, tick_opened, tick_closed,
                     product, issue, priority, status, sla_due_tick, sla_breached,
-                    resolution_code, amount_refunded, udaap_flag
+                    resolution_code, amount_refunded, udaap_flag, channel, service_channel
              FROM complaint WHERE run_id = ?1 AND status = 'open'
              ORDER BY tick_opened ASC",
         )?;
@@ -99,6 +106,15 @@ impl SimStore {
         resolution_code: &str,
         amount_refunded: f64,
     ) -> SimResult<()> {
+        let was_open: Option<i64> = self
+            .conn
+            .query_row(
+                "SELECT tick_opened FROM complaint
+                 WHERE run_id = ?1 AND complaint_id = ?2 AND status = 'open'",
+                params![run_id, complaint_id],
+                |row| row.get(0),
+            )
+            .optional()?;
         self.conn.execute(
             "UPDATE complaint SET status = 'closed', tick_closed = ?1,
              resolution_code = ?2, amount_refunded = ?3
@@ -111,9 +127,65 @@ impl SimStore {
                 complaint_id
             ],
         )?;
+        if let Some(tick_opened) = was_open {
+            self.adjust_complaint_backlog(run_id, -1, -tick_opened)?;
+        }
+        Ok(())
+    }
+
+    /// Open complaints and the sum of their open ticks, kept as running
+    /// totals so backlog and average age never rescan the complaint table.
+    pub fn complaint_backlog_totals(&self, run_id: &str) -> SimResult<(i64, i64)> {
+        let totals = self
+            .conn
+            .query_row(
+                "SELECT open_count, opened_tick_sum FROM complaint_backlog WHERE run_id = ?1",
+                params![run_id],
+                |row| Ok((row.get(0)?, row.get(1)?)),
+            )
+            .optional()?;
+        Ok(totals.unwrap_or((0, 0)))
+    }
+
+    fn adjust_complaint_backlog(&self, run_id: &str, count: i64, tick_sum: i64) -> SimResult<()> {
+        self.conn.execute(
+            "INSERT INTO complaint_backlog (run_id, open_count, opened_tick_sum)
+             VALUES (?1, ?2, ?3)
+             ON CONFLICT(run_id) DO UPDATE SET
+                open_count = open_count + excluded.open_count,
+                opened_tick_sum = opened_tick_sum + excluded.opened_tick_sum",
+            params![run_id, count, tick_sum],
+        )?;
         Ok(())
     }
 
+    /// The fee a fee dispute is about: the latest fee debited to the
+    /// complaint's account by the tick it was opened. None for other
+    /// complaints.
+    pub fn complaint_fee(
+        &self,
+        run_id: &str,
+        complaint: &crate::complaint_subsystem::ComplaintRecord,
+    ) -> SimResult<Option<f64>> {
+        let Some(account_id) = complaint.account_id.as_deref() else {
+            return Ok(None);
+        };
+        if complaint.issue != "fee_dispute" {
+            return Ok(None);
+        }
+        self.conn
+            .query_row(
+                "SELECT amount FROM transactions
+                 WHERE run_id = ?1 AND account_id = ?2 AND tick <= ?3
+                   AND direction = 'debit' AND category GLOB '*_fee'
+                 ORDER BY tick DESC, rowid DESC LIMIT 1",
+                params![run_id, account_id, complaint.tick_opened as i64],
+                |row| row.get(0),
+            )
+            .optional()
+            .map_err(Into::into)
+    }
+
     pub fn mark_complaint_sla_breach(&self, run_id: &str, complaint_id: &str) -> SimResult<()> {
         self.conn.execute(
             "UPDATE complaint SET sla_breached = 1 WHERE run_id = ?1 AND complaint_id = ?2",
@@ -121,4 +193,47 @@ impl SimStore {
         )?;
         Ok(())
     }
+
+    /// Closed complaints whose latest closure has no quality score yet
+    /// (Phase 4.65), oldest closure first.
+    pub fn unscored_complaint_closures(
+        &self,
+        run_id: &str,
+    ) -> SimResult<Vec<crate::complaint_subsystem::ComplaintRecord>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT complaint_id, customer_id, account_id, tick_opened, tick_closed,
+                    product, issue, priority, status, sla_due_tick, sla_breached,
+                    resolution_code, amount_refunded, udaap_flag, channel, service_channel
+             FROM complaint c WHERE run_id = ?1 AND status = 'closed'
+               AND NOT EXISTS (SELECT 1 FROM complaint_quality q
+                                WHERE q.run_id = c.run_id AND q.complaint_id = c.complaint_id
+                                  AND q.tick_closed = c.tick_closed)
+             ORDER BY tick_closed ASC, complaint_id ASC",
+        )?;
+        let rows = stmt.query_map(params![run_id], complaint_row_mapper)?;
+        rows.collect::<Result<Vec<_>, _>>().map_err(Into::into)
+    }
+
+    /// Put a closed complaint back in the queue as if opened at `tick`,
+    /// through `channel` (Phase 4.65). An SLA it already breached stays
+    /// breached.
+    pub fn reopen_complaint(
+        &self,
+        run_id: &str,
+        complaint_id: &str,
+        tick: Tick,
+        sla_due_tick: Tick,
+        channel: &str,
+    ) -> SimResult<()> {
+        let reopened = self.conn.execute(
+            "UPDATE complaint SET status = 'open', tick_opened = ?1, tick_closed = NULL,
+                    resolution_code = NULL, sla_due_tick = ?2, channel = ?3
+             WHERE run_id = ?4 AND complaint_id = ?5 AND status = 'closed'",
+            params![tick as i64, sla_due_tick as i64, channel, run_id, complaint_id],
+        )?;
+        if reopened > 0 {
+            self.adjust_complaint_backlog(run_id, 1, tick as i64)?;
+        }
+        Ok(())
+    }
 }
@@ -0,0 +1,151 @@
+//! Store methods for complaint response quality (Phase 4.65).
+
+use crate::{error::SimResult, types::Tick};
+use rusqlite::{params, OptionalExtension, Row};
+
+use super::SimStore;
+
+/// Row from the `complaint_quality` table: one closure's score and what
+/// came of it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ComplaintQualityRow {
+    pub complaint_id:    String,
+    pub tick_closed:     Tick,
+    pub scored_tick:     Tick,
+    pub resolution_code: String,
+    pub analyst_skill:   f64,
+    /// Ticks from opening to the closure.
+    pub handling_ticks:  Tick,
+    pub time_score:      f64,
+    /// The resolution code suits the issue.
+    pub appropriate:     bool,
+    pub quality:         f64,
+    /// 'stands' | 'reopened' | 'escalated'
+    pub outcome:         String,
+}
+
+/// Row from the `desk_performance_report` table.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DeskPerformanceRow {
+    pub tick:               Tick,
+    /// Closures scored in the period.
+    pub closures:           i64,
+    /// None without closures.
+    pub avg_quality:        Option<f64>,
+    /// avg_quality less the previous report's; None on the first report
+    /// or when either period had no closures.
+    pub quality_trend:      Option<f64>,
+    pub poor_quality_count: i64,
+    pub reopened_count:     i64,
+    pub escalated_count:    i64,
+    pub avg_handling_ticks: Option<f64>,
+}
+
+const QUALITY_COLUMNS: &str = "complaint_id, tick_closed, scored_tick, resolution_code, analyst_skill,
+     handling_ticks, time_score, appropriate, quality, outcome";
+
+fn quality_from_row(row: &Row) -> rusqlite::Result<ComplaintQualityRow> {
+    Ok(ComplaintQualityRow {
+        complaint_id:    row.get(0)?,
+        tick_closed:     row.get::<_, i64>(1)? as Tick,
+        scored_tick:     row.get::<_, i64>(2)? as Tick,
+        resolution_code: row.get(3)?,
+        analyst_skill:   row.get(4)?,
+        handling_ticks:  row.get::<_, i64>(5)? as Tick,
+        time_score:      row.get(6)?,
+        appropriate:     row.get(7)?,
+        quality:         row.get(8)?,
+        outcome:         row.get(9)?,
+    })
+}
+
+const REPORT_COLUMNS: &str = "tick, closures, avg_quality, quality_trend, poor_quality_count, reopened_count,
+     escalated_count, avg_handling_ticks";
+
+fn report_from_row(row: &Row) -> rusqlite::Result<DeskPerformanceRow> {
+    Ok(DeskPerformanceRow {
+        tick:               row.get::<_, i64>(0)? as Tick,
+        closures:           row.get(1)?,
+        avg_quality:        row.get(2)?,
+        quality_trend:      row.get(3)?,
+        poor_quality_count: row.get(4)?,
+        reopened_count:     row.get(5)?,
+        escalated_count:    row.get(6)?,
+        avg_handling_ticks: row.get(7)?,
+    })
+}
+
+impl SimStore {
+    pub fn insert_complaint_quality(&self, run_id: &str, row: &ComplaintQualityRow) -> SimResult<()> {
+        self.conn.execute(
+            &format!(
+                "INSERT INTO complaint_quality (run_id, {QUALITY_COLUMNS})
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11)"
+            ),
+            params![
+                run_id, row.complaint_id, row.tick_closed as i64, row.scored_tick as i64, row.resolution_code,
+                row.analyst_skill, row.handling_ticks as i64, row.time_score, row.appropriate, row.quality,
+                row.outcome,
+            ],
+        )?;
+        Ok(())
+    }
+
+    /// Every closure of a complaint, oldest first.
+    pub fn complaint_quality(&self, run_id: &str, complaint_id: &str) -> SimResult<Vec<ComplaintQualityRow>> {
+        let mut stmt = self.conn.prepare(&format!(
+            "SELECT {QUALITY_COLUMNS} FROM complaint_quality WHERE run_id = ?1 AND complaint_id = ?2
+              ORDER BY tick_closed"
+        ))?;
+        let rows = stmt.query_map(params![run_id, complaint_id], quality_from_row)?;
+        rows.collect::<Result<Vec<_>, _>>().map_err(Into::into)
+    }
+
+    /// Closures scored in `start..=end`, in the order scored.
+    pub fn complaint_quality_scored(&self, run_id: &str, start: Tick, end: Tick) -> SimResult<Vec<ComplaintQualityRow>> {
+        let mut stmt = self.conn.prepare(&format!(
+            "SELECT {QUALITY_COLUMNS} FROM complaint_quality
+              WHERE run_id = ?1 AND scored_tick >= ?2 AND scored_tick <= ?3
+              ORDER BY scored_tick, rowid"
+        ))?;
+        let rows = stmt.query_map(params![run_id, start as i64, end as i64], quality_from_row)?;
+        rows.collect::<Result<Vec<_>, _>>().map_err(Into::into)
+    }
+
+    pub fn insert_desk_performance_report(&self, run_id: &str, row: &DeskPerformanceRow) -> SimResult<()> {
+        self.conn.execute(
+            &format!(
+                "INSERT INTO desk_performance_report (run_id, {REPORT_COLUMNS})
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)"
+            ),
+            params![
+                run_id, row.tick as i64, row.closures, row.avg_quality, row.quality_trend,
+                row.poor_quality_count, row.reopened_count, row.escalated_count, row.avg_handling_ticks,
+            ],
+        )?;
+        Ok(())
+    }
+
+    /// Every desk performance report in the run, oldest first.
+    pub fn desk_performance_reports(&self, run_id: &str) -> SimResult<Vec<DeskPerformanceRow>> {
+        let mut stmt = self.conn.prepare(&format!(
+            "SELECT {REPORT_COLUMNS} FROM desk_performance_report WHERE run_id = ?1 ORDER BY tick"
+        ))?;
+        let rows = stmt.query_map(params![run_id], report_from_row)?;
+        rows.collect::<Result<Vec<_>, _>>().map_err(Into::into)
+    }
+
+    pub fn latest_desk_performance_report(&self, run_id: &str) -> SimResult<Option<DeskPerformanceRow>> {
+        self.conn
+            .query_row(
+                &format!(
+                    "SELECT {REPORT_COLUMNS} FROM desk_performance_report WHERE run_id = ?1
+                      ORDER BY tick DESC LIMIT 1"
+                ),
+                params![run_id],
+                report_from_row,
+            )
+            .optional()
+            .map_err(Into::into)
+    }
+}
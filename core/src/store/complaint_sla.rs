@@ -0,0 +1,50 @@
+//! Store methods for complaint SLA policy changes (Phase 4.29).
+
+use crate::{config::SlaPolicyRule, error::SimResult, types::Tick};
+use rusqlite::params;
+
+use super::SimStore;
+
+impl SimStore {
+    pub fn insert_sla_policy(
+        &self,
+        run_id:         &str,
+        tick:           Tick,
+        rule:           &SlaPolicyRule,
+        effective_tick: Tick,
+    ) -> SimResult<()> {
+        self.conn.execute(
+            "INSERT INTO complaint_sla_policy
+             (run_id, tick, priority, product, channel, resolve_days, effective_tick)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+            params![
+                run_id, tick as i64, rule.priority, rule.product, rule.channel,
+                rule.resolve_days as i64, effective_tick as i64,
+            ],
+        )?;
+        Ok(())
+    }
+
+    // ── Test / summary helpers ────────────────────────────────────────
+
+    /// Scheduled policy changes as (effective tick, rule), in the order
+    /// the player made them (for tests).
+    pub fn sla_policies(&self, run_id: &str) -> SimResult<Vec<(Tick, SlaPolicyRule)>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT effective_tick, priority, product, channel, resolve_days
+             FROM complaint_sla_policy WHERE run_id = ?1 ORDER BY policy_id",
+        )?;
+        let rows = stmt.query_map(params![run_id], |row| {
+            Ok((
+                row.get::<_, i64>(0)? as Tick,
+                SlaPolicyRule {
+                    priority:     row.get(1)?,
+                    product:      row.get(2)?,
+                    channel:      row.get(3)?,
+                    resolve_days: row.get::<_, i64>(4)? as u64,
+                },
+            ))
+        })?;
+        rows.collect::<Result<Vec<_>, _>>().map_err(Into::into)
+    }
+}
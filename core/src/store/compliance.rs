@@ -522,7 +522,19 @@ impl SimStore {
             |row| row.get(0),
         )?;
 
-        let false_positive_rate = 0.12; // Placeholder - would need review data
+        // Phase 4.89: sanctions hits on namesakes, over all sanctions hits
+        let false_positives: i64 = self.conn.query_row(
+            "SELECT COUNT(*) FROM aml_screening_result
+             WHERE run_id = ? AND screening_type = 'ofac_sanctions' AND COALESCE(true_match, 0) = 0
+               AND screening_tick >= ? AND screening_tick <= ?",
+            params![run_id, start_tick, end_tick],
+            |row| row.get(0),
+        )?;
+        let false_positive_rate = if sanctions_hits > 0 {
+            false_positives as f64 / sanctions_hits as f64
+        } else {
+            0.0
+        };
 
         Ok(AMLMetrics {
             screenings_performed,
@@ -677,11 +689,18 @@ impl SimStore {
         &self,
         alert: &crate::transaction_monitoring_subsystem::AMLAlert,
     ) -> SimResult<()> {
+        // aml_alert.severity is NOT NULL; derive it from the rule score
+        let severity = match alert.alert_score {
+            s if s >= 90.0 => "critical",
+            s if s >= 75.0 => "high",
+            s if s >= 50.0 => "medium",
+            _ => "low",
+        };
         self.conn.execute(
             "INSERT INTO aml_alert (
                 alert_id, run_id, customer_id, tick, rule_id, alert_type,
-                alert_score, description, triggered_amount, transaction_count, status
-            ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11)",
+                alert_score, severity, description, triggered_amount, transaction_count, status
+            ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12)",
             params![
                 alert.alert_id,
                 alert.run_id,
@@ -690,6 +709,7 @@ impl SimStore {
                 alert.rule_id,
                 alert.alert_type,
                 alert.alert_score,
+                severity,
                 alert.description,
                 alert.triggered_amount,
                 alert.transaction_count,
@@ -751,6 +771,20 @@ impl SimStore {
         )?)
     }
 
+    /// Phase 4.25: customers and accounts with an AML or fraud alert
+    /// still open or under investigation.
+    pub fn entities_with_open_alerts(&self, run_id: &str) -> SimResult<std::collections::HashSet<String>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT customer_id FROM aml_alert
+             WHERE run_id = ?1 AND status IN ('open', 'investigating')
+             UNION
+             SELECT entity_id FROM fraud_alert
+             WHERE run_id = ?1 AND investigation_status IN ('open', 'investigating')",
+        )?;
+        let rows = stmt.query_map(params![run_id], |r| r.get(0))?;
+        rows.collect::<Result<_, _>>().map_err(Into::into)
+    }
+
     /// Count CTRs filed in a time window
     pub fn count_ctrs_in_window(
         &self,
@@ -929,11 +963,11 @@ impl SimStore {
     }
 
     /// Mark alert as having SAR filed
-    pub fn mark_alert_sar_filed(&self, run_id: &str, alert_id: &str) -> SimResult<()> {
+    pub fn mark_alert_sar_filed(&self, run_id: &str, alert_id: &str, tick: Tick) -> SimResult<()> {
         self.conn.execute(
-            "UPDATE aml_alert SET status = 'sar_filed'
+            "UPDATE aml_alert SET status = 'sar_filed', disposition = 'sar_filed', resolved_tick = ?
              WHERE run_id = ? AND alert_id = ?",
-            params![run_id, alert_id],
+            params![tick as i64, run_id, alert_id],
         )?;
         Ok(())
     }
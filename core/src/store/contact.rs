@@ -0,0 +1,171 @@
+//! Store methods for contact preferences and outreach (Phase 4.32).
+
+use crate::{error::SimResult, types::Tick};
+use rusqlite::{params, OptionalExtension};
+
+use super::SimStore;
+
+/// Row from the `contact_preference` table.
+#[derive(Debug, Clone)]
+pub struct ContactPreferenceRow {
+    pub customer_id:       String,
+    pub do_not_contact:    bool,
+    pub preferred_channel: String,
+    pub tick_set:          Tick,
+}
+
+/// Row from the `customer_contact` table.
+#[derive(Debug, Clone)]
+pub struct CustomerContactRow {
+    pub tick:                    Tick,
+    pub customer_id:             String,
+    pub campaign_type:           String,
+    pub campaign_id:             String,
+    pub channel:                 String,
+    pub responded:               bool,
+    pub do_not_contact_violated: bool,
+}
+
+/// The player's suppression policy for every campaign.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ContactPolicy {
+    pub honor_do_not_contact:    bool,
+    /// Contacts per customer per fatigue window; `None` is uncapped.
+    pub max_contacts_per_window: Option<u32>,
+}
+
+impl Default for ContactPolicy {
+    fn default() -> Self {
+        Self { honor_do_not_contact: true, max_contacts_per_window: None }
+    }
+}
+
+impl SimStore {
+    pub fn insert_contact_preference(&self, run_id: &str, row: &ContactPreferenceRow) -> SimResult<()> {
+        self.conn.execute(
+            "INSERT OR IGNORE INTO contact_preference
+             (run_id, customer_id, do_not_contact, preferred_channel, tick_set)
+             VALUES (?1, ?2, ?3, ?4, ?5)",
+            params![
+                run_id, row.customer_id, row.do_not_contact as i32,
+                row.preferred_channel, row.tick_set as i64,
+            ],
+        )?;
+        Ok(())
+    }
+
+    pub fn contact_preference(&self, run_id: &str, customer_id: &str) -> SimResult<Option<ContactPreferenceRow>> {
+        let row = self
+            .conn
+            .query_row(
+                "SELECT customer_id, do_not_contact, preferred_channel, tick_set
+                 FROM contact_preference WHERE run_id = ?1 AND customer_id = ?2",
+                params![run_id, customer_id],
+                |row| {
+                    Ok(ContactPreferenceRow {
+                        customer_id:       row.get(0)?,
+                        do_not_contact:    row.get::<_, i32>(1)? != 0,
+                        preferred_channel: row.get(2)?,
+                        tick_set:          row.get::<_, i64>(3)? as Tick,
+                    })
+                },
+            )
+            .optional()?;
+        Ok(row)
+    }
+
+    pub fn insert_customer_contact(&self, run_id: &str, row: &CustomerContactRow) -> SimResult<()> {
+        self.conn.execute(
+            "INSERT INTO customer_contact
+             (run_id, tick, customer_id, campaign_type, campaign_id, channel, responded, do_not_contact_violated)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+            params![
+                run_id, row.tick as i64, row.customer_id, row.campaign_type, row.campaign_id,
+                row.channel, row.responded as i32, row.do_not_contact_violated as i32,
+            ],
+        )?;
+        Ok(())
+    }
+
+    /// Contacts made to the customer in the inclusive tick window.
+    pub fn customer_contacts_in_window(
+        &self,
+        run_id:      &str,
+        customer_id: &str,
+        tick_start:  Tick,
+        tick_end:    Tick,
+    ) -> SimResult<i64> {
+        let count: i64 = self.conn.query_row(
+            "SELECT COUNT(*) FROM customer_contact
+             WHERE run_id = ?1 AND customer_id = ?2 AND tick >= ?3 AND tick <= ?4",
+            params![run_id, customer_id, tick_start as i64, tick_end as i64],
+            |row| row.get(0),
+        )?;
+        Ok(count)
+    }
+
+    pub fn insert_contact_policy(&self, run_id: &str, tick: Tick, policy: &ContactPolicy) -> SimResult<()> {
+        self.conn.execute(
+            "INSERT OR REPLACE INTO contact_policy
+             (run_id, tick, honor_do_not_contact, max_contacts_per_window)
+             VALUES (?1, ?2, ?3, ?4)",
+            params![
+                run_id, tick as i64, policy.honor_do_not_contact as i32,
+                policy.max_contacts_per_window.map(i64::from),
+            ],
+        )?;
+        Ok(())
+    }
+
+    /// The policy in force: the latest one set, or the default of
+    /// honoring do-not-contact without a cap.
+    pub fn contact_policy(&self, run_id: &str) -> SimResult<ContactPolicy> {
+        let policy = self
+            .conn
+            .query_row(
+                "SELECT honor_do_not_contact, max_contacts_per_window FROM contact_policy
+                 WHERE run_id = ?1 ORDER BY tick DESC LIMIT 1",
+                params![run_id],
+                |row| {
+                    Ok(ContactPolicy {
+                        honor_do_not_contact:    row.get::<_, i32>(0)? != 0,
+                        max_contacts_per_window: row.get::<_, Option<i64>>(1)?.map(|n| n as u32),
+                    })
+                },
+            )
+            .optional()?;
+        Ok(policy.unwrap_or_default())
+    }
+
+    // ── Test / summary helpers ────────────────────────────────────────
+
+    /// Every contact for a run, oldest first (for tests).
+    pub fn customer_contacts(&self, run_id: &str) -> SimResult<Vec<CustomerContactRow>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT tick, customer_id, campaign_type, campaign_id, channel, responded, do_not_contact_violated
+             FROM customer_contact WHERE run_id = ?1 ORDER BY contact_id",
+        )?;
+        let rows = stmt.query_map(params![run_id], |row| {
+            Ok(CustomerContactRow {
+                tick:                    row.get::<_, i64>(0)? as Tick,
+                customer_id:             row.get(1)?,
+                campaign_type:           row.get(2)?,
+                campaign_id:             row.get(3)?,
+                channel:                 row.get(4)?,
+                responded:               row.get::<_, i32>(5)? != 0,
+                do_not_contact_violated: row.get::<_, i32>(6)? != 0,
+            })
+        })?;
+        rows.collect::<Result<Vec<_>, _>>().map_err(Into::into)
+    }
+
+    /// Customers who asked not to be contacted (for tests).
+    pub fn do_not_contact_customers(&self, run_id: &str) -> SimResult<Vec<String>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT customer_id FROM contact_preference
+             WHERE run_id = ?1 AND do_not_contact = 1 ORDER BY customer_id",
+        )?;
+        let rows = stmt.query_map(params![run_id], |row| row.get(0))?;
+        rows.collect::<Result<Vec<_>, _>>().map_err(Into::into)
+    }
+}
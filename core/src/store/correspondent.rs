@@ -0,0 +1,313 @@
+//! Store methods for correspondent banking (Phase 4.39).
+
+use crate::{error::SimResult, types::Tick};
+use rusqlite::{params, OptionalExtension};
+
+use super::SimStore;
+
+/// Row from the `correspondent_relationship` table.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CorrespondentRelationshipRow {
+    pub bank_id:              String,
+    pub country:              String,
+    pub risk_rating:          String,
+    pub declared_originators: u32,
+    /// Ground truth: downstream banks clearing through the respondent.
+    pub nested_banks:         u32,
+    pub nested_disclosed:     bool,
+    /// 'active' | 'suspended'
+    pub status:               String,
+    pub next_dd_tick:         Tick,
+    pub opened_tick:          Tick,
+}
+
+/// Row from the `correspondent_wire` table.
+#[derive(Debug, Clone)]
+pub struct CorrespondentWireRow {
+    pub wire_id:              String,
+    pub bank_id:              String,
+    pub tick:                 Tick,
+    pub amount:               f64,
+    pub originator:           String,
+    /// What the payment message shows.
+    pub ordering_institution: String,
+    pub originator_country:   String,
+    /// Ground truth: the nested bank behind the wire, if any.
+    pub nested_bank:          Option<String>,
+    /// 'cleared' | 'rejected'
+    pub status:               String,
+    pub fee:                  f64,
+}
+
+/// Row from the `correspondent_dd_review` table.
+#[derive(Debug, Clone)]
+pub struct CorrespondentDdReviewRow {
+    pub review_id:      String,
+    pub bank_id:        String,
+    pub requested_tick: Tick,
+    /// Ground truth: when the questionnaire comes back, if ever.
+    pub responds_tick:  Option<Tick>,
+    pub completed_tick: Option<Tick>,
+    /// 'satisfactory' | 'nested_disclosed' | 'overdue'
+    pub outcome:        Option<String>,
+    pub cost:           f64,
+}
+
+/// A respondent's cleared wires over a window, as monitoring sees them.
+#[derive(Debug, Clone)]
+pub struct CorrespondentFlowRow {
+    pub bank_id:              String,
+    pub wires:                i64,
+    pub amount:               f64,
+    /// Wires whose ordering institution is not the respondent.
+    pub third_party_wires:    i64,
+    pub distinct_originators: i64,
+}
+
+fn relationship_row_mapper(row: &rusqlite::Row<'_>) -> rusqlite::Result<CorrespondentRelationshipRow> {
+    Ok(CorrespondentRelationshipRow {
+        bank_id:              row.get(0)?,
+        country:              row.get(1)?,
+        risk_rating:          row.get(2)?,
+        declared_originators: row.get::<_, i64>(3)? as u32,
+        nested_banks:         row.get::<_, i64>(4)? as u32,
+        nested_disclosed:     row.get::<_, i32>(5)? != 0,
+        status:               row.get(6)?,
+        next_dd_tick:         row.get::<_, i64>(7)? as Tick,
+        opened_tick:          row.get::<_, i64>(8)? as Tick,
+    })
+}
+
+fn review_row_mapper(row: &rusqlite::Row<'_>) -> rusqlite::Result<CorrespondentDdReviewRow> {
+    Ok(CorrespondentDdReviewRow {
+        review_id:      row.get(0)?,
+        bank_id:        row.get(1)?,
+        requested_tick: row.get::<_, i64>(2)? as Tick,
+        responds_tick:  row.get::<_, Option<i64>>(3)?.map(|t| t as Tick),
+        completed_tick: row.get::<_, Option<i64>>(4)?.map(|t| t as Tick),
+        outcome:        row.get(5)?,
+        cost:           row.get(6)?,
+    })
+}
+
+const RELATIONSHIP_COLUMNS: &str = "bank_id, country, risk_rating, declared_originators, nested_banks, \
+     nested_disclosed, status, next_dd_tick, opened_tick";
+
+const REVIEW_COLUMNS: &str =
+    "review_id, bank_id, requested_tick, responds_tick, completed_tick, outcome, cost";
+
+impl SimStore {
+    /// Open a respondent relationship. The respondent is also booked as a
+    /// customer with segment and status 'correspondent', so monitoring
+    /// alerts can be raised against it without it joining the active book.
+    pub fn insert_correspondent_relationship(
+        &self,
+        run_id: &str,
+        row:    &CorrespondentRelationshipRow,
+    ) -> SimResult<()> {
+        self.conn.execute(
+            "INSERT OR IGNORE INTO customer
+             (customer_id, run_id, segment, income_band, risk_band, open_tick, status,
+              monthly_txn_mean, cash_intensity, name)
+             VALUES (?1, ?2, 'correspondent', 'n/a', ?3, ?4, 'correspondent', 0.0, 0.0, ?1)",
+            params![row.bank_id, run_id, row.risk_rating, row.opened_tick as i64],
+        )?;
+        self.conn.execute(
+            "INSERT OR IGNORE INTO correspondent_relationship
+             (run_id, bank_id, country, risk_rating, declared_originators, nested_banks,
+              nested_disclosed, status, next_dd_tick, opened_tick)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)",
+            params![
+                run_id, row.bank_id, row.country, row.risk_rating, row.declared_originators as i64,
+                row.nested_banks as i64, row.nested_disclosed as i32, row.status,
+                row.next_dd_tick as i64, row.opened_tick as i64,
+            ],
+        )?;
+        Ok(())
+    }
+
+    /// Every respondent relationship for a run, by bank.
+    pub fn correspondent_relationships(&self, run_id: &str) -> SimResult<Vec<CorrespondentRelationshipRow>> {
+        let mut stmt = self.conn.prepare(&format!(
+            "SELECT {RELATIONSHIP_COLUMNS} FROM correspondent_relationship
+             WHERE run_id = ?1 ORDER BY bank_id",
+        ))?;
+        let rows = stmt.query_map(params![run_id], relationship_row_mapper)?;
+        rows.collect::<Result<Vec<_>, _>>().map_err(Into::into)
+    }
+
+    pub fn update_correspondent_relationship(
+        &self,
+        run_id: &str,
+        row:    &CorrespondentRelationshipRow,
+    ) -> SimResult<()> {
+        self.conn.execute(
+            "UPDATE correspondent_relationship
+             SET nested_disclosed = ?3, status = ?4, next_dd_tick = ?5
+             WHERE run_id = ?1 AND bank_id = ?2",
+            params![
+                run_id, row.bank_id, row.nested_disclosed as i32, row.status, row.next_dd_tick as i64,
+            ],
+        )?;
+        Ok(())
+    }
+
+    pub fn insert_correspondent_wire(&self, run_id: &str, row: &CorrespondentWireRow) -> SimResult<()> {
+        self.conn.execute(
+            "INSERT INTO correspondent_wire
+             (wire_id, run_id, bank_id, tick, amount, originator, ordering_institution,
+              originator_country, nested_bank, status, fee)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11)",
+            params![
+                row.wire_id, run_id, row.bank_id, row.tick as i64, row.amount, row.originator,
+                row.ordering_institution, row.originator_country, row.nested_bank, row.status, row.fee,
+            ],
+        )?;
+        Ok(())
+    }
+
+    /// Each respondent's cleared wires in the window, inclusive.
+    pub fn correspondent_flows(
+        &self,
+        run_id:     &str,
+        start_tick: Tick,
+        end_tick:   Tick,
+    ) -> SimResult<Vec<CorrespondentFlowRow>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT bank_id, COUNT(*), COALESCE(SUM(amount), 0.0),
+                    SUM(CASE WHEN ordering_institution != bank_id THEN 1 ELSE 0 END),
+                    COUNT(DISTINCT originator)
+             FROM correspondent_wire
+             WHERE run_id = ?1 AND status = 'cleared' AND tick >= ?2 AND tick <= ?3
+             GROUP BY bank_id ORDER BY bank_id",
+        )?;
+        let rows = stmt.query_map(params![run_id, start_tick as i64, end_tick as i64], |row| {
+            Ok(CorrespondentFlowRow {
+                bank_id:              row.get(0)?,
+                wires:                row.get(1)?,
+                amount:               row.get(2)?,
+                third_party_wires:    row.get(3)?,
+                distinct_originators: row.get(4)?,
+            })
+        })?;
+        rows.collect::<Result<Vec<_>, _>>().map_err(Into::into)
+    }
+
+    /// Clearing fees earned in the window, inclusive.
+    pub fn correspondent_fees_between(
+        &self,
+        run_id:     &str,
+        start_tick: Tick,
+        end_tick:   Tick,
+    ) -> SimResult<f64> {
+        let total: f64 = self.conn.query_row(
+            "SELECT COALESCE(SUM(fee), 0.0) FROM correspondent_wire
+             WHERE run_id = ?1 AND tick >= ?2 AND tick <= ?3",
+            params![run_id, start_tick as i64, end_tick as i64],
+            |row| row.get(0),
+        )?;
+        Ok(total)
+    }
+
+    pub fn insert_correspondent_dd_review(&self, run_id: &str, row: &CorrespondentDdReviewRow) -> SimResult<()> {
+        self.conn.execute(
+            "INSERT INTO correspondent_dd_review
+             (review_id, run_id, bank_id, requested_tick, responds_tick, completed_tick, outcome, cost)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+            params![
+                row.review_id, run_id, row.bank_id, row.requested_tick as i64,
+                row.responds_tick.map(|t| t as i64), row.completed_tick.map(|t| t as i64),
+                row.outcome, row.cost,
+            ],
+        )?;
+        Ok(())
+    }
+
+    /// The respondent's open due diligence refresh, if any.
+    pub fn open_correspondent_dd_review(
+        &self,
+        run_id:  &str,
+        bank_id: &str,
+    ) -> SimResult<Option<CorrespondentDdReviewRow>> {
+        let row = self
+            .conn
+            .query_row(
+                &format!(
+                    "SELECT {REVIEW_COLUMNS} FROM correspondent_dd_review
+                     WHERE run_id = ?1 AND bank_id = ?2 AND completed_tick IS NULL",
+                ),
+                params![run_id, bank_id],
+                review_row_mapper,
+            )
+            .optional()?;
+        Ok(row)
+    }
+
+    pub fn close_correspondent_dd_review(
+        &self,
+        run_id:    &str,
+        review_id: &str,
+        outcome:   &str,
+        cost:      f64,
+        tick:      Tick,
+    ) -> SimResult<()> {
+        self.conn.execute(
+            "UPDATE correspondent_dd_review SET completed_tick = ?3, outcome = ?4, cost = ?5
+             WHERE run_id = ?1 AND review_id = ?2",
+            params![run_id, review_id, tick as i64, outcome, cost],
+        )?;
+        Ok(())
+    }
+
+    /// Cost of due diligence refreshes completed in the window, inclusive.
+    pub fn correspondent_dd_cost_between(
+        &self,
+        run_id:     &str,
+        start_tick: Tick,
+        end_tick:   Tick,
+    ) -> SimResult<f64> {
+        let total: f64 = self.conn.query_row(
+            "SELECT COALESCE(SUM(cost), 0.0) FROM correspondent_dd_review
+             WHERE run_id = ?1 AND completed_tick >= ?2 AND completed_tick <= ?3",
+            params![run_id, start_tick as i64, end_tick as i64],
+            |row| row.get(0),
+        )?;
+        Ok(total)
+    }
+
+    // ── Test / summary helpers ────────────────────────────────────────
+
+    /// Every wire respondents sent through us, oldest first (for tests).
+    pub fn correspondent_wires(&self, run_id: &str) -> SimResult<Vec<CorrespondentWireRow>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT wire_id, bank_id, tick, amount, originator, ordering_institution,
+                    originator_country, nested_bank, status, fee
+             FROM correspondent_wire WHERE run_id = ?1 ORDER BY tick, wire_id",
+        )?;
+        let rows = stmt.query_map(params![run_id], |row| {
+            Ok(CorrespondentWireRow {
+                wire_id:              row.get(0)?,
+                bank_id:              row.get(1)?,
+                tick:                 row.get::<_, i64>(2)? as Tick,
+                amount:               row.get(3)?,
+                originator:           row.get(4)?,
+                ordering_institution: row.get(5)?,
+                originator_country:   row.get(6)?,
+                nested_bank:          row.get(7)?,
+                status:               row.get(8)?,
+                fee:                  row.get(9)?,
+            })
+        })?;
+        rows.collect::<Result<Vec<_>, _>>().map_err(Into::into)
+    }
+
+    /// Every due diligence refresh for a run, oldest first (for tests).
+    pub fn correspondent_dd_reviews(&self, run_id: &str) -> SimResult<Vec<CorrespondentDdReviewRow>> {
+        let mut stmt = self.conn.prepare(&format!(
+            "SELECT {REVIEW_COLUMNS} FROM correspondent_dd_review
+             WHERE run_id = ?1 ORDER BY requested_tick, review_id",
+        ))?;
+        let rows = stmt.query_map(params![run_id], review_row_mapper)?;
+        rows.collect::<Result<Vec<_>, _>>().map_err(Into::into)
+    }
+}
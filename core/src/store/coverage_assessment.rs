@@ -0,0 +1,136 @@
+//! Store methods for scenario coverage assessments (Phase 4.18).
+
+use crate::{error::SimResult, types::Tick};
+use rusqlite::params;
+
+/// Row from the `coverage_assessment` table.
+#[derive(Debug, Clone)]
+pub struct CoverageAssessmentRow {
+    pub tick:        Tick,
+    /// "structuring" | "funnel" | "tbml" | "mule" | "ato"
+    pub typology:    String,
+    /// Active rules meant to catch the typology, comma-separated.
+    pub rules:       String,
+    pub covered:     bool,
+    /// Ground truth: instances of the typology running in the window.
+    pub occurrences: i64,
+}
+
+impl CoverageAssessmentRow {
+    pub fn present(&self) -> bool {
+        self.occurrences > 0
+    }
+}
+
+use super::SimStore;
+
+fn row_mapper(row: &rusqlite::Row<'_>) -> rusqlite::Result<CoverageAssessmentRow> {
+    Ok(CoverageAssessmentRow {
+        tick:        row.get::<_, i64>(0)? as u64,
+        typology:    row.get(1)?,
+        rules:       row.get(2)?,
+        covered:     row.get::<_, i64>(3)? != 0,
+        occurrences: row.get(4)?,
+    })
+}
+
+impl SimStore {
+    pub fn insert_coverage_assessment(&self, run_id: &str, row: &CoverageAssessmentRow) -> SimResult<()> {
+        self.conn.execute(
+            "INSERT OR REPLACE INTO coverage_assessment
+             (run_id, tick, typology, rules, covered, occurrences)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+            params![
+                run_id, row.tick as i64, row.typology, row.rules, row.covered as i64,
+                row.occurrences,
+            ],
+        )?;
+        Ok(())
+    }
+
+    /// Ids of the monitoring rules enabled in the rule inventory.
+    pub fn enabled_monitoring_rules(&self) -> SimResult<Vec<String>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT rule_id FROM transaction_monitoring_rule WHERE enabled = 1 ORDER BY rule_id",
+        )?;
+        let rows = stmt.query_map([], |row| row.get(0))?;
+        rows.collect::<Result<Vec<_>, _>>().map_err(Into::into)
+    }
+
+    /// Customers with two or more cash deposits just under the CTR
+    /// threshold in the window, inclusive.
+    pub fn structuring_customers_between(
+        &self,
+        run_id:     &str,
+        start_tick: Tick,
+        end_tick:   Tick,
+    ) -> SimResult<i64> {
+        let count: i64 = self.conn.query_row(
+            "SELECT COUNT(*) FROM (
+                 SELECT a.customer_id FROM transactions t
+                 JOIN account a ON t.account_id = a.account_id
+                 WHERE t.run_id = ?1 AND t.tick >= ?2 AND t.tick <= ?3
+                   AND t.direction = 'credit' AND t.category LIKE 'cash%'
+                   AND t.amount >= 9000.0 AND t.amount < 10000.0
+                 GROUP BY a.customer_id HAVING COUNT(*) >= 2
+             )",
+            params![run_id, start_tick as i64, end_tick as i64],
+            |row| row.get(0),
+        )?;
+        Ok(count)
+    }
+
+    /// Fraudulent mule links requested in the window, inclusive.
+    pub fn mule_links_between(&self, run_id: &str, start_tick: Tick, end_tick: Tick) -> SimResult<i64> {
+        let count: i64 = self.conn.query_row(
+            "SELECT COUNT(*) FROM external_account_link
+             WHERE run_id = ?1 AND fraudulent = 1
+               AND requested_tick >= ?2 AND requested_tick <= ?3",
+            params![run_id, start_tick as i64, end_tick as i64],
+            |row| row.get(0),
+        )?;
+        Ok(count)
+    }
+
+    /// Customers with unauthorized (takeover) ACH entries in the window,
+    /// inclusive.
+    pub fn takeover_customers_between(
+        &self,
+        run_id:     &str,
+        start_tick: Tick,
+        end_tick:   Tick,
+    ) -> SimResult<i64> {
+        let count: i64 = self.conn.query_row(
+            "SELECT COUNT(DISTINCT customer_id) FROM originated_ach
+             WHERE run_id = ?1 AND unauthorized = 1 AND tick >= ?2 AND tick <= ?3",
+            params![run_id, start_tick as i64, end_tick as i64],
+            |row| row.get(0),
+        )?;
+        Ok(count)
+    }
+
+    // ── Test / summary helpers ────────────────────────────────────────
+
+    /// The most recent assessment, one row per typology (for the post-run
+    /// export).
+    pub fn latest_coverage_assessment(&self, run_id: &str) -> SimResult<Vec<CoverageAssessmentRow>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT tick, typology, rules, covered, occurrences FROM coverage_assessment
+             WHERE run_id = ?1
+               AND tick = (SELECT MAX(tick) FROM coverage_assessment WHERE run_id = ?1)
+             ORDER BY rowid",
+        )?;
+        let rows = stmt.query_map(params![run_id], row_mapper)?;
+        rows.collect::<Result<Vec<_>, _>>().map_err(Into::into)
+    }
+
+    /// All coverage assessments for a run (for tests).
+    pub fn coverage_assessments(&self, run_id: &str) -> SimResult<Vec<CoverageAssessmentRow>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT tick, typology, rules, covered, occurrences FROM coverage_assessment
+             WHERE run_id = ?1 ORDER BY tick, rowid",
+        )?;
+        let rows = stmt.query_map(params![run_id], row_mapper)?;
+        rows.collect::<Result<Vec<_>, _>>().map_err(Into::into)
+    }
+}
@@ -0,0 +1,218 @@
+//! Store methods for credit bureau furnishing and disputes (Phase 4.1).
+
+use crate::{error::SimResult, types::Tick};
+use rusqlite::params;
+
+/// Row from the `bureau_dispute` table.
+#[derive(Debug, Clone)]
+pub struct BureauDisputeRow {
+    pub dispute_id:     String,
+    pub customer_id:    String,
+    pub account_id:     String,
+    pub complaint_id:   String,
+    pub filed_tick:     Tick,
+    pub due_tick:       Tick,
+    pub status:         String,
+    pub overdue:        bool,
+    pub penalty_amount: f64,
+}
+
+/// Row from the `bureau_furnishing` table.
+#[derive(Debug, Clone)]
+pub struct BureauFurnishingRow {
+    pub tick:            Tick,
+    /// 'deposit' | 'loan'
+    pub tradeline:       String,
+    /// The deposit account, or the loan id.
+    pub account_id:      String,
+    pub customer_id:     String,
+    pub actual_status:   String,
+    pub reported_status: String,
+    pub balance:         f64,
+}
+
+use super::SimStore;
+
+impl SimStore {
+    /// Record one tradeline's status as furnished to the bureau.
+    /// Returns the furnishing row id (referenced by disputes).
+    #[allow(clippy::too_many_arguments)]
+    pub fn insert_bureau_furnishing(
+        &self,
+        run_id:          &str,
+        tick:            Tick,
+        tradeline:       &str,
+        account_id:      &str,
+        customer_id:     &str,
+        actual_status:   &str,
+        reported_status: &str,
+        balance:         f64,
+    ) -> SimResult<i64> {
+        self.conn.execute(
+            "INSERT INTO bureau_furnishing
+             (run_id, tick, tradeline, account_id, customer_id, actual_status,
+              reported_status, balance, is_error)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
+            params![
+                run_id, tick as i64, tradeline, account_id, customer_id, actual_status,
+                reported_status, balance, (actual_status != reported_status) as i64,
+            ],
+        )?;
+        Ok(self.conn.last_insert_rowid())
+    }
+
+    /// Open a consumer dispute against a furnishing row.
+    #[allow(clippy::too_many_arguments)]
+    pub fn insert_bureau_dispute(
+        &self,
+        run_id:        &str,
+        dispute_id:    &str,
+        furnishing_id: i64,
+        customer_id:   &str,
+        account_id:    &str,
+        complaint_id:  &str,
+        filed_tick:    Tick,
+        due_tick:      Tick,
+    ) -> SimResult<()> {
+        self.conn.execute(
+            "INSERT INTO bureau_dispute
+             (dispute_id, run_id, furnishing_id, customer_id, account_id,
+              complaint_id, filed_tick, due_tick, status)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, 'open')",
+            params![
+                dispute_id, run_id, furnishing_id, customer_id, account_id,
+                complaint_id, filed_tick as i64, due_tick as i64,
+            ],
+        )?;
+        Ok(())
+    }
+
+    /// All disputes still awaiting correction, oldest first.
+    pub fn open_bureau_disputes(&self, run_id: &str) -> SimResult<Vec<BureauDisputeRow>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT dispute_id, customer_id, account_id, complaint_id,
+                    filed_tick, due_tick, status, overdue, penalty_amount
+             FROM bureau_dispute
+             WHERE run_id = ?1 AND status = 'open'
+             ORDER BY filed_tick, dispute_id",
+        )?;
+        let rows = stmt.query_map(params![run_id], |row| {
+            Ok(BureauDisputeRow {
+                dispute_id:     row.get(0)?,
+                customer_id:    row.get(1)?,
+                account_id:     row.get(2)?,
+                complaint_id:   row.get(3)?,
+                filed_tick:     row.get::<_, i64>(4)? as u64,
+                due_tick:       row.get::<_, i64>(5)? as u64,
+                status:         row.get(6)?,
+                overdue:        row.get::<_, i64>(7)? != 0,
+                penalty_amount: row.get(8)?,
+            })
+        })?;
+        rows.collect::<Result<Vec<_>, _>>().map_err(Into::into)
+    }
+
+    /// Flag a dispute as past its investigation window and book the penalty.
+    pub fn mark_bureau_dispute_overdue(
+        &self,
+        run_id:     &str,
+        dispute_id: &str,
+        penalty:    f64,
+    ) -> SimResult<()> {
+        self.conn.execute(
+            "UPDATE bureau_dispute SET overdue = 1, penalty_amount = penalty_amount + ?1
+             WHERE run_id = ?2 AND dispute_id = ?3",
+            params![penalty, run_id, dispute_id],
+        )?;
+        Ok(())
+    }
+
+    /// Mark a dispute corrected (the furnished record was fixed).
+    pub fn resolve_bureau_dispute(
+        &self,
+        run_id:     &str,
+        dispute_id: &str,
+        tick:       Tick,
+    ) -> SimResult<()> {
+        self.conn.execute(
+            "UPDATE bureau_dispute SET status = 'corrected', resolved_tick = ?1
+             WHERE run_id = ?2 AND dispute_id = ?3",
+            params![tick as i64, run_id, dispute_id],
+        )?;
+        Ok(())
+    }
+
+    /// Late-correction penalties incurred over [start_tick, end_tick]. A
+    /// penalty lands the tick after the dispute's investigation window closes.
+    pub fn bureau_penalty_between(
+        &self,
+        run_id:     &str,
+        start_tick: Tick,
+        end_tick:   Tick,
+    ) -> SimResult<f64> {
+        let total: f64 = self.conn.query_row(
+            "SELECT COALESCE(SUM(penalty_amount), 0.0) FROM bureau_dispute
+             WHERE run_id = ?1 AND overdue = 1 AND due_tick + 1 >= ?2 AND due_tick + 1 <= ?3",
+            params![run_id, start_tick as i64, end_tick as i64],
+            |row| row.get(0),
+        )?;
+        Ok(total)
+    }
+
+    // ── Test / summary helpers ────────────────────────────────────────
+
+    /// Total furnishing rows, optionally only the erroneous ones (for tests).
+    pub fn bureau_furnishing_count(&self, run_id: &str, errors_only: bool) -> SimResult<i64> {
+        let count: i64 = self.conn.query_row(
+            "SELECT COUNT(*) FROM bureau_furnishing
+             WHERE run_id = ?1 AND (?2 = 0 OR is_error = 1)",
+            params![run_id, errors_only as i64],
+            |row| row.get(0),
+        )?;
+        Ok(count)
+    }
+
+    /// A customer's bureau file: every tradeline furnished for them,
+    /// oldest first.
+    pub fn bureau_file(&self, run_id: &str, customer_id: &str) -> SimResult<Vec<BureauFurnishingRow>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT tick, tradeline, account_id, customer_id, actual_status,
+                    reported_status, balance
+             FROM bureau_furnishing
+             WHERE run_id = ?1 AND customer_id = ?2
+             ORDER BY id",
+        )?;
+        let rows = stmt.query_map(params![run_id, customer_id], |row| {
+            Ok(BureauFurnishingRow {
+                tick:            row.get::<_, i64>(0)? as Tick,
+                tradeline:       row.get(1)?,
+                account_id:      row.get(2)?,
+                customer_id:     row.get(3)?,
+                actual_status:   row.get(4)?,
+                reported_status: row.get(5)?,
+                balance:         row.get(6)?,
+            })
+        })?;
+        rows.collect::<Result<Vec<_>, _>>().map_err(Into::into)
+    }
+
+    /// Total disputes filed (for tests).
+    pub fn bureau_dispute_count(&self, run_id: &str) -> SimResult<i64> {
+        let count: i64 = self.conn.query_row(
+            "SELECT COUNT(*) FROM bureau_dispute WHERE run_id = ?1",
+            params![run_id],
+            |row| row.get(0),
+        )?;
+        Ok(count)
+    }
+
+    /// Sum of late-correction penalties booked (for tests).
+    pub fn bureau_penalty_total(&self, run_id: &str) -> SimResult<f64> {
+        let total: f64 = self.conn.query_row(
+            "SELECT COALESCE(SUM(penalty_amount), 0.0) FROM bureau_dispute WHERE run_id = ?1",
+            params![run_id],
+            |row| row.get(0),
+        )?;
+        Ok(total)
+    }
+}
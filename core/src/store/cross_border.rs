@@ -0,0 +1,388 @@
+//! Store methods for cross-border fan-in/fan-out (Phase 4.40).
+
+use crate::{error::SimResult, types::Tick};
+use rusqlite::{params, OptionalExtension};
+
+use super::SimStore;
+
+/// Row from the `cross_border_profile` table.
+#[derive(Debug, Clone)]
+pub struct CrossBorderProfileRow {
+    pub customer_id:   String,
+    pub account_id:    String,
+    pub home_country:  String,
+    /// Ground truth: 'funnel' | 'inbound' | 'none'.
+    pub pattern:       String,
+    pub next_tick:     Tick,
+    /// When a funnel's collected funds go back out, if it is holding any.
+    pub disperse_tick: Option<Tick>,
+    pub pending:       f64,
+    pub tick_enrolled: Tick,
+}
+
+/// Row from the `cross_border_transfer` table.
+#[derive(Debug, Clone)]
+pub struct CrossBorderTransferRow {
+    pub txn_id:       String,
+    pub customer_id:  String,
+    pub account_id:   String,
+    pub tick:         Tick,
+    /// 'inbound' | 'domestic'
+    pub direction:    String,
+    pub counterparty: String,
+    pub country:      String,
+    pub amount:       f64,
+}
+
+/// A customer's foreign wires in over a window — the typology's and
+/// remittance layering's — against their domestic transfers out.
+#[derive(Debug, Clone)]
+pub struct CrossBorderActivityRow {
+    pub customer_id: String,
+    pub inflow:      f64,
+    pub senders:     i64,
+    pub countries:   Vec<String>,
+    pub outflow:     f64,
+    pub payees:      i64,
+}
+
+/// Row from the `cross_border_finding` table.
+#[derive(Debug, Clone)]
+pub struct CrossBorderFindingRow {
+    pub finding_id:          String,
+    pub customer_id:         String,
+    pub alert_id:            String,
+    pub tick:                Tick,
+    pub window_start:        Tick,
+    pub senders:             i64,
+    pub countries:           Vec<String>,
+    /// Source countries on the high-risk jurisdiction list.
+    pub high_risk_countries: Vec<String>,
+    pub inflow:              f64,
+    pub payees:              i64,
+    pub outflow:             f64,
+    pub alert_score:         f64,
+}
+
+/// One transaction behind a finding.
+#[derive(Debug, Clone)]
+pub struct CrossBorderEvidenceRow {
+    pub txn_id:       String,
+    pub tick:         Tick,
+    /// 'inbound' | 'domestic'
+    pub direction:    String,
+    pub counterparty: String,
+    pub country:      String,
+    pub amount:       f64,
+}
+
+fn split_list(list: String) -> Vec<String> {
+    list.split(',').filter(|s| !s.is_empty()).map(str::to_string).collect()
+}
+
+fn finding_row_mapper(row: &rusqlite::Row<'_>) -> rusqlite::Result<CrossBorderFindingRow> {
+    Ok(CrossBorderFindingRow {
+        finding_id:          row.get(0)?,
+        customer_id:         row.get(1)?,
+        alert_id:            row.get(2)?,
+        tick:                row.get::<_, i64>(3)? as Tick,
+        window_start:        row.get::<_, i64>(4)? as Tick,
+        senders:             row.get(5)?,
+        countries:           split_list(row.get(6)?),
+        high_risk_countries: split_list(row.get(7)?),
+        inflow:              row.get(8)?,
+        payees:              row.get(9)?,
+        outflow:             row.get(10)?,
+        alert_score:         row.get(11)?,
+    })
+}
+
+fn evidence_row_mapper(row: &rusqlite::Row<'_>) -> rusqlite::Result<CrossBorderEvidenceRow> {
+    Ok(CrossBorderEvidenceRow {
+        txn_id:       row.get(0)?,
+        tick:         row.get::<_, i64>(1)? as Tick,
+        direction:    row.get(2)?,
+        counterparty: row.get(3)?,
+        country:      row.get(4)?,
+        amount:       row.get(5)?,
+    })
+}
+
+const FINDING_COLUMNS: &str = "finding_id, customer_id, alert_id, tick, window_start, senders, countries, \
+     high_risk_countries, inflow, payees, outflow, alert_score";
+
+impl SimStore {
+    /// International customers with no cross-border profile yet, with
+    /// their citizenship country.
+    pub fn unprofiled_cross_border_customers(&self, run_id: &str) -> SimResult<Vec<(String, String)>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT ci.customer_id, ci.citizenship_country FROM customer_international ci
+             WHERE ci.run_id = ?1
+               AND NOT EXISTS (
+                   SELECT 1 FROM cross_border_profile p
+                   WHERE p.run_id = ci.run_id AND p.customer_id = ci.customer_id
+               )
+             ORDER BY ci.customer_id",
+        )?;
+        let rows = stmt.query_map(params![run_id], |row| Ok((row.get(0)?, row.get(1)?)))?;
+        rows.collect::<Result<Vec<_>, _>>().map_err(Into::into)
+    }
+
+    pub fn insert_cross_border_profile(&self, run_id: &str, row: &CrossBorderProfileRow) -> SimResult<()> {
+        self.conn.execute(
+            "INSERT OR IGNORE INTO cross_border_profile
+             (run_id, customer_id, account_id, home_country, pattern, next_tick,
+              disperse_tick, pending, tick_enrolled)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
+            params![
+                run_id, row.customer_id, row.account_id, row.home_country, row.pattern,
+                row.next_tick as i64, row.disperse_tick.map(|t| t as i64), row.pending,
+                row.tick_enrolled as i64,
+            ],
+        )?;
+        Ok(())
+    }
+
+    /// Active profiles with a collection, support wire or dispersal due by
+    /// `tick`.
+    pub fn cross_border_profiles_due(&self, run_id: &str, tick: Tick) -> SimResult<Vec<CrossBorderProfileRow>> {
+        self.cross_border_profiles_where(
+            "run_id = ?1 AND pattern != 'none' AND (next_tick <= ?2 OR disperse_tick <= ?2)",
+            params![run_id, tick as i64],
+        )
+    }
+
+    pub fn update_cross_border_profile(&self, run_id: &str, row: &CrossBorderProfileRow) -> SimResult<()> {
+        self.conn.execute(
+            "UPDATE cross_border_profile SET next_tick = ?3, disperse_tick = ?4, pending = ?5
+             WHERE run_id = ?1 AND customer_id = ?2",
+            params![
+                run_id, row.customer_id, row.next_tick as i64, row.disperse_tick.map(|t| t as i64),
+                row.pending,
+            ],
+        )?;
+        Ok(())
+    }
+
+    pub fn insert_cross_border_transfer(&self, run_id: &str, row: &CrossBorderTransferRow) -> SimResult<()> {
+        self.conn.execute(
+            "INSERT INTO cross_border_transfer
+             (txn_id, run_id, customer_id, account_id, tick, direction,
+              counterparty, country, amount)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
+            params![
+                row.txn_id, run_id, row.customer_id, row.account_id, row.tick as i64,
+                row.direction, row.counterparty, row.country, row.amount,
+            ],
+        )?;
+        Ok(())
+    }
+
+    /// Foreign wires in per customer over the inclusive window, with the
+    /// customer's domestic transfers out over it. Only customers with
+    /// something in appear.
+    pub fn cross_border_activity(
+        &self,
+        run_id:     &str,
+        tick_start: Tick,
+        tick_end:   Tick,
+    ) -> SimResult<Vec<CrossBorderActivityRow>> {
+        let mut stmt = self.conn.prepare(
+            "WITH inflow AS (
+                 SELECT customer_id, counterparty, country, amount FROM cross_border_transfer
+                 WHERE run_id = ?1 AND direction = 'inbound' AND tick >= ?2 AND tick <= ?3
+                 UNION ALL
+                 SELECT customer_id, counterparty, corridor, amount FROM remittance_transfer
+                 WHERE run_id = ?1 AND direction = 'inbound' AND tick >= ?2 AND tick <= ?3
+             ),
+             outflow AS (
+                 SELECT customer_id, SUM(amount) AS amount, COUNT(DISTINCT counterparty) AS payees
+                 FROM cross_border_transfer
+                 WHERE run_id = ?1 AND direction = 'domestic' AND tick >= ?2 AND tick <= ?3
+                 GROUP BY customer_id
+             )
+             SELECT i.customer_id, SUM(i.amount), COUNT(DISTINCT i.counterparty),
+                    GROUP_CONCAT(DISTINCT i.country),
+                    COALESCE(MAX(o.amount), 0.0), COALESCE(MAX(o.payees), 0)
+             FROM inflow i
+             LEFT JOIN outflow o ON o.customer_id = i.customer_id
+             GROUP BY i.customer_id
+             ORDER BY i.customer_id",
+        )?;
+        let rows = stmt.query_map(params![run_id, tick_start as i64, tick_end as i64], |row| {
+            Ok(CrossBorderActivityRow {
+                customer_id: row.get(0)?,
+                inflow:      row.get(1)?,
+                senders:     row.get(2)?,
+                countries:   split_list(row.get(3)?),
+                outflow:     row.get(4)?,
+                payees:      row.get(5)?,
+            })
+        })?;
+        rows.collect::<Result<Vec<_>, _>>().map_err(Into::into)
+    }
+
+    /// The customer's foreign wires in and domestic transfers out over the
+    /// inclusive window, oldest first.
+    pub fn cross_border_window_evidence(
+        &self,
+        run_id:      &str,
+        customer_id: &str,
+        tick_start:  Tick,
+        tick_end:    Tick,
+    ) -> SimResult<Vec<CrossBorderEvidenceRow>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT txn_id, tick, direction, counterparty, country, amount FROM cross_border_transfer
+             WHERE run_id = ?1 AND customer_id = ?2 AND tick >= ?3 AND tick <= ?4
+             UNION ALL
+             SELECT txn_id, tick, direction, counterparty, corridor, amount FROM remittance_transfer
+             WHERE run_id = ?1 AND customer_id = ?2 AND direction = 'inbound'
+               AND tick >= ?3 AND tick <= ?4
+             ORDER BY 2, 1",
+        )?;
+        let rows = stmt.query_map(
+            params![run_id, customer_id, tick_start as i64, tick_end as i64],
+            evidence_row_mapper,
+        )?;
+        rows.collect::<Result<Vec<_>, _>>().map_err(Into::into)
+    }
+
+    /// Record a finding and the transactions behind it.
+    pub fn insert_cross_border_finding(
+        &self,
+        run_id:   &str,
+        row:      &CrossBorderFindingRow,
+        evidence: &[CrossBorderEvidenceRow],
+    ) -> SimResult<()> {
+        self.conn.execute(
+            "INSERT INTO cross_border_finding
+             (finding_id, run_id, customer_id, alert_id, tick, window_start, senders, countries,
+              high_risk_countries, inflow, payees, outflow, alert_score)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13)",
+            params![
+                row.finding_id, run_id, row.customer_id, row.alert_id, row.tick as i64,
+                row.window_start as i64, row.senders, row.countries.join(","),
+                row.high_risk_countries.join(","), row.inflow, row.payees, row.outflow,
+                row.alert_score,
+            ],
+        )?;
+        for item in evidence {
+            self.conn.execute(
+                "INSERT OR IGNORE INTO cross_border_evidence
+                 (finding_id, txn_id, tick, direction, counterparty, country, amount)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+                params![
+                    row.finding_id, item.txn_id, item.tick as i64, item.direction,
+                    item.counterparty, item.country, item.amount,
+                ],
+            )?;
+        }
+        Ok(())
+    }
+
+    /// The finding behind an XB_FUNNEL alert, if any.
+    pub fn cross_border_finding_for_alert(
+        &self,
+        run_id:   &str,
+        alert_id: &str,
+    ) -> SimResult<Option<CrossBorderFindingRow>> {
+        let row = self
+            .conn
+            .query_row(
+                &format!(
+                    "SELECT {FINDING_COLUMNS} FROM cross_border_finding
+                     WHERE run_id = ?1 AND alert_id = ?2",
+                ),
+                params![run_id, alert_id],
+                finding_row_mapper,
+            )
+            .optional()?;
+        Ok(row)
+    }
+
+    /// The transactions recorded behind a finding, oldest first.
+    pub fn cross_border_evidence(&self, finding_id: &str) -> SimResult<Vec<CrossBorderEvidenceRow>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT txn_id, tick, direction, counterparty, country, amount
+             FROM cross_border_evidence WHERE finding_id = ?1 ORDER BY tick, txn_id",
+        )?;
+        let rows = stmt.query_map(params![finding_id], evidence_row_mapper)?;
+        rows.collect::<Result<Vec<_>, _>>().map_err(Into::into)
+    }
+
+    fn cross_border_profiles_where(
+        &self,
+        filter: &str,
+        params: impl rusqlite::Params,
+    ) -> SimResult<Vec<CrossBorderProfileRow>> {
+        let mut stmt = self.conn.prepare(&format!(
+            "SELECT customer_id, account_id, home_country, pattern, next_tick, disperse_tick,
+                    pending, tick_enrolled
+             FROM cross_border_profile WHERE {filter} ORDER BY customer_id",
+        ))?;
+        let rows = stmt.query_map(params, |row| {
+            Ok(CrossBorderProfileRow {
+                customer_id:   row.get(0)?,
+                account_id:    row.get(1)?,
+                home_country:  row.get(2)?,
+                pattern:       row.get(3)?,
+                next_tick:     row.get::<_, i64>(4)? as Tick,
+                disperse_tick: row.get::<_, Option<i64>>(5)?.map(|t| t as Tick),
+                pending:       row.get(6)?,
+                tick_enrolled: row.get::<_, i64>(7)? as Tick,
+            })
+        })?;
+        rows.collect::<Result<Vec<_>, _>>().map_err(Into::into)
+    }
+
+    // ── Test / summary helpers ────────────────────────────────────────
+
+    /// Every cross-border profile for a run (for tests).
+    pub fn cross_border_profiles(&self, run_id: &str) -> SimResult<Vec<CrossBorderProfileRow>> {
+        self.cross_border_profiles_where("run_id = ?1", params![run_id])
+    }
+
+    /// Every transfer the typology booked for a run, oldest first (for
+    /// tests).
+    pub fn cross_border_transfers(&self, run_id: &str) -> SimResult<Vec<CrossBorderTransferRow>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT txn_id, customer_id, account_id, tick, direction, counterparty, country, amount
+             FROM cross_border_transfer WHERE run_id = ?1 ORDER BY tick, txn_id",
+        )?;
+        let rows = stmt.query_map(params![run_id], |row| {
+            Ok(CrossBorderTransferRow {
+                txn_id:       row.get(0)?,
+                customer_id:  row.get(1)?,
+                account_id:   row.get(2)?,
+                tick:         row.get::<_, i64>(3)? as Tick,
+                direction:    row.get(4)?,
+                counterparty: row.get(5)?,
+                country:      row.get(6)?,
+                amount:       row.get(7)?,
+            })
+        })?;
+        rows.collect::<Result<Vec<_>, _>>().map_err(Into::into)
+    }
+
+    /// (subject, narrative) of every SAR filed on an XB_FUNNEL alert
+    /// (for tests).
+    pub fn cross_border_sar_narratives(&self, run_id: &str) -> SimResult<Vec<(String, String)>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT subject_id, narrative FROM suspicious_activity_report
+             WHERE run_id = ?1 AND activity_type = 'cross_border_funnel'
+             ORDER BY filing_tick, sar_id",
+        )?;
+        let rows = stmt.query_map(params![run_id], |row| Ok((row.get(0)?, row.get(1)?)))?;
+        rows.collect::<Result<Vec<_>, _>>().map_err(Into::into)
+    }
+
+    /// Every XB_FUNNEL finding for a run, oldest first (for tests).
+    pub fn cross_border_findings(&self, run_id: &str) -> SimResult<Vec<CrossBorderFindingRow>> {
+        let mut stmt = self.conn.prepare(&format!(
+            "SELECT {FINDING_COLUMNS} FROM cross_border_finding
+             WHERE run_id = ?1 ORDER BY tick, finding_id",
+        ))?;
+        let rows = stmt.query_map(params![run_id], finding_row_mapper)?;
+        rows.collect::<Result<Vec<_>, _>>().map_err(Into::into)
+    }
+}
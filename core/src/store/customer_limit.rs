@@ -0,0 +1,88 @@
+//! Store methods for customer-level limits (Phase 4.12).
+
+use crate::{config::CustomerLimitRule, error::SimResult, types::Tick};
+use rusqlite::params;
+use std::collections::HashMap;
+
+/// Row from the `customer_limit_decline` table.
+#[derive(Debug, Clone)]
+pub struct LimitDeclineRow {
+    pub tick:         Tick,
+    pub customer_id:  String,
+    pub account_id:   String,
+    pub limit_type:   String,
+    pub amount:       f64,
+    pub limit_amount: f64,
+}
+
+use super::SimStore;
+
+impl SimStore {
+    pub fn upsert_customer_limit(&self, run_id: &str, rule: &CustomerLimitRule, tick: Tick) -> SimResult<()> {
+        self.conn.execute(
+            "INSERT OR REPLACE INTO customer_limit (run_id, limit_type, scope, amount, set_tick)
+             VALUES (?1, ?2, ?3, ?4, ?5)",
+            params![run_id, rule.limit_type, rule.scope, rule.amount, tick as i64],
+        )?;
+        Ok(())
+    }
+
+    /// customer_id → composite risk tier, for customers with a risk score.
+    pub fn customer_risk_tiers(&self, run_id: &str) -> SimResult<HashMap<String, String>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT customer_id, composite_risk FROM customer_risk_score WHERE run_id = ?1",
+        )?;
+        let rows = stmt.query_map(params![run_id], |row| Ok((row.get(0)?, row.get(1)?)))?;
+        rows.collect::<Result<HashMap<_, _>, _>>().map_err(Into::into)
+    }
+
+    pub fn insert_limit_decline(&self, run_id: &str, row: &LimitDeclineRow) -> SimResult<()> {
+        self.conn.execute(
+            "INSERT INTO customer_limit_decline
+             (run_id, tick, customer_id, account_id, limit_type, amount, limit_amount)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+            params![
+                run_id, row.tick as i64, row.customer_id, row.account_id,
+                row.limit_type, row.amount, row.limit_amount,
+            ],
+        )?;
+        Ok(())
+    }
+
+    // ── Test / summary helpers ────────────────────────────────────────
+
+    /// Limits the player has set (for tests).
+    pub fn customer_limits(&self, run_id: &str) -> SimResult<Vec<CustomerLimitRule>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT limit_type, scope, amount FROM customer_limit WHERE run_id = ?1
+             ORDER BY limit_type, scope",
+        )?;
+        let rows = stmt.query_map(params![run_id], |row| {
+            Ok(CustomerLimitRule {
+                limit_type: row.get(0)?,
+                scope:      row.get(1)?,
+                amount:     row.get(2)?,
+            })
+        })?;
+        rows.collect::<Result<Vec<_>, _>>().map_err(Into::into)
+    }
+
+    /// All limit declines for a run, oldest first (for tests).
+    pub fn limit_declines(&self, run_id: &str) -> SimResult<Vec<LimitDeclineRow>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT tick, customer_id, account_id, limit_type, amount, limit_amount
+             FROM customer_limit_decline WHERE run_id = ?1 ORDER BY decline_id",
+        )?;
+        let rows = stmt.query_map(params![run_id], |row| {
+            Ok(LimitDeclineRow {
+                tick:         row.get::<_, i64>(0)? as u64,
+                customer_id:  row.get(1)?,
+                account_id:   row.get(2)?,
+                limit_type:   row.get(3)?,
+                amount:       row.get(4)?,
+                limit_amount: row.get(5)?,
+            })
+        })?;
+        rows.collect::<Result<Vec<_>, _>>().map_err(Into::into)
+    }
+}
@@ -0,0 +1,157 @@
+//! Store methods for deposit holds (Phase 4.55).
+
+use crate::{config::HoldRule, error::SimResult, types::Tick};
+use rusqlite::{params, OptionalExtension, Row};
+
+use super::SimStore;
+
+/// Row from the `deposit_hold` table.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DepositHoldRow {
+    pub txn_id:        String,
+    pub account_id:    String,
+    pub customer_id:   String,
+    pub tick:          Tick,
+    /// 'atm' | 'branch' | 'new_account' | 'large_item'
+    pub class:         String,
+    /// 'standard' | 'elevated'
+    pub risk:          String,
+    pub amount:        f64,
+    pub held_amount:   f64,
+    pub hold_ticks:    Tick,
+    pub release_tick:  Tick,
+    pub released_tick: Option<Tick>,
+    pub pressure:      f64,
+}
+
+const COLUMNS: &str = "txn_id, account_id, customer_id, tick, class, risk, amount, held_amount, hold_ticks,
+     release_tick, released_tick, pressure";
+
+fn hold_from_row(row: &Row) -> rusqlite::Result<DepositHoldRow> {
+    Ok(DepositHoldRow {
+        txn_id:        row.get(0)?,
+        account_id:    row.get(1)?,
+        customer_id:   row.get(2)?,
+        tick:          row.get::<_, i64>(3)? as Tick,
+        class:         row.get(4)?,
+        risk:          row.get(5)?,
+        amount:        row.get(6)?,
+        held_amount:   row.get(7)?,
+        hold_ticks:    row.get::<_, i64>(8)? as Tick,
+        release_tick:  row.get::<_, i64>(9)? as Tick,
+        released_tick: row.get::<_, Option<i64>>(10)?.map(|t| t as Tick),
+        pressure:      row.get(11)?,
+    })
+}
+
+impl SimStore {
+    pub fn insert_deposit_hold(&self, run_id: &str, row: &DepositHoldRow) -> SimResult<()> {
+        self.conn.execute(
+            &format!(
+                "INSERT INTO deposit_hold (run_id, {COLUMNS})
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13)"
+            ),
+            params![
+                run_id, row.txn_id, row.account_id, row.customer_id, row.tick as i64, row.class, row.risk,
+                row.amount, row.held_amount, row.hold_ticks as i64, row.release_tick as i64,
+                row.released_tick.map(|t| t as i64), row.pressure,
+            ],
+        )?;
+        Ok(())
+    }
+
+    /// Every hold placed, oldest first.
+    pub fn deposit_holds(&self, run_id: &str) -> SimResult<Vec<DepositHoldRow>> {
+        let mut stmt = self.conn.prepare(&format!(
+            "SELECT {COLUMNS} FROM deposit_hold WHERE run_id = ?1 ORDER BY tick, rowid"
+        ))?;
+        let rows = stmt.query_map(params![run_id], hold_from_row)?;
+        rows.collect::<Result<Vec<_>, _>>().map_err(Into::into)
+    }
+
+    /// Holds still on whose release tick has come by `tick`.
+    pub fn deposit_holds_due(&self, run_id: &str, tick: Tick) -> SimResult<Vec<DepositHoldRow>> {
+        let mut stmt = self.conn.prepare(&format!(
+            "SELECT {COLUMNS} FROM deposit_hold
+             WHERE run_id = ?1 AND released_tick IS NULL AND release_tick <= ?2
+             ORDER BY release_tick, tick, rowid"
+        ))?;
+        let rows = stmt.query_map(params![run_id, tick as i64], hold_from_row)?;
+        rows.collect::<Result<Vec<_>, _>>().map_err(Into::into)
+    }
+
+    /// The hold still on the deposit `txn_id`, if any.
+    pub fn active_deposit_hold(&self, run_id: &str, txn_id: &str) -> SimResult<Option<DepositHoldRow>> {
+        self.conn
+            .query_row(
+                &format!(
+                    "SELECT {COLUMNS} FROM deposit_hold
+                     WHERE run_id = ?1 AND txn_id = ?2 AND released_tick IS NULL"
+                ),
+                params![run_id, txn_id],
+                hold_from_row,
+            )
+            .optional()
+            .map_err(Into::into)
+    }
+
+    pub fn release_deposit_hold(&self, run_id: &str, txn_id: &str, tick: Tick) -> SimResult<()> {
+        self.conn.execute(
+            "UPDATE deposit_hold SET released_tick = ?3 WHERE run_id = ?1 AND txn_id = ?2",
+            params![run_id, txn_id, tick as i64],
+        )?;
+        Ok(())
+    }
+
+    pub fn insert_deposit_hold_policy(&self, run_id: &str, tick: Tick, rule: &HoldRule) -> SimResult<()> {
+        self.conn.execute(
+            "INSERT INTO deposit_hold_policy (run_id, tick, class, risk, hold_ticks)
+             VALUES (?1, ?2, ?3, ?4, ?5)",
+            params![run_id, tick as i64, rule.class, rule.risk, rule.hold_ticks as i64],
+        )?;
+        Ok(())
+    }
+
+    /// Hold rules the player has set, with the tick each was set, in
+    /// order.
+    pub fn deposit_hold_policies(&self, run_id: &str) -> SimResult<Vec<(Tick, HoldRule)>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT tick, class, risk, hold_ticks FROM deposit_hold_policy
+             WHERE run_id = ?1 ORDER BY tick, rowid",
+        )?;
+        let rows = stmt.query_map(params![run_id], |row| {
+            Ok((
+                row.get::<_, i64>(0)? as Tick,
+                HoldRule {
+                    class:      row.get(1)?,
+                    risk:       row.get(2)?,
+                    hold_ticks: row.get::<_, i64>(3)? as Tick,
+                },
+            ))
+        })?;
+        rows.collect::<Result<Vec<_>, _>>().map_err(Into::into)
+    }
+
+    pub fn account_open_tick(&self, run_id: &str, account_id: &str) -> SimResult<Tick> {
+        let tick: i64 = self.conn.query_row(
+            "SELECT open_tick FROM account WHERE run_id = ?1 AND account_id = ?2",
+            params![run_id, account_id],
+            |row| row.get(0),
+        )?;
+        Ok(tick as Tick)
+    }
+
+    /// Check deposits to the account that came back unpaid at or after
+    /// `since`.
+    pub fn returned_check_deposits_since(&self, run_id: &str, account_id: &str, since: Tick) -> SimResult<i64> {
+        self.conn
+            .query_row(
+                "SELECT COUNT(*) FROM check_item
+                 WHERE run_id = ?1 AND account_id = ?2 AND side = 'deposited'
+                   AND status IN ('returned', 'late_return') AND resolved_tick >= ?3",
+                params![run_id, account_id, since as i64],
+                |row| row.get(0),
+            )
+            .map_err(Into::into)
+    }
+}
@@ -0,0 +1,203 @@
+//! Store methods for the deposit run scenario (Phase 4.52).
+
+use crate::{error::SimResult, types::Tick};
+use rusqlite::{params, OptionalExtension, Row};
+
+use super::SimStore;
+
+/// Row from the `liquidity_position` table.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LiquidityPositionRow {
+    pub tick:                Tick,
+    pub deposits:            f64,
+    pub uninsured_deposits:  f64,
+    pub liquid_assets:       f64,
+    /// Discount window credit the player drew.
+    pub emergency_borrowing: f64,
+    /// Credit taken automatically to pay withdrawals liquid assets could
+    /// not.
+    pub penalty_borrowing:   f64,
+    pub capital:             f64,
+    /// Capital held against operational risk (Phase 4.61), which a run
+    /// cannot draw on.
+    pub op_risk_capital:     f64,
+    /// Annual percentage points paid on deposits above the usual rate.
+    pub rate_premium:        f64,
+    /// Share of uninsured deposits leaving per tick; 0 outside a run.
+    pub run_rate:            f64,
+    pub severity:            f64,
+    pub outflow:             f64,
+    pub premium_cost:        f64,
+    pub liquidity_cost:      f64,
+}
+
+impl LiquidityPositionRow {
+    /// Liquid assets as a share of deposits; 1 with no deposits.
+    pub fn coverage(&self) -> f64 {
+        if self.deposits > 0.0 {
+            self.liquid_assets / self.deposits
+        } else {
+            1.0
+        }
+    }
+}
+
+/// Row from the `deposit_run` table.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DepositRunRow {
+    pub shock_tick:        Tick,
+    /// 'scheduled' | 'reputation'
+    pub trigger:           String,
+    /// Reputation score when the shock hit.
+    pub reputation_score:  f64,
+    pub ended_tick:        Option<Tick>,
+    pub peak_run_rate:     f64,
+    pub total_outflow:     f64,
+    pub uninsured_outflow: f64,
+    pub emergency_drawn:   f64,
+    pub shortfall:         f64,
+}
+
+const POSITION_COLUMNS: &str = "tick, deposits, uninsured_deposits, liquid_assets, emergency_borrowing,
+     penalty_borrowing, capital, rate_premium, run_rate, severity, outflow, premium_cost, liquidity_cost,
+     op_risk_capital";
+
+fn position_from_row(row: &Row) -> rusqlite::Result<LiquidityPositionRow> {
+    Ok(LiquidityPositionRow {
+        tick:                row.get::<_, i64>(0)? as Tick,
+        deposits:            row.get(1)?,
+        uninsured_deposits:  row.get(2)?,
+        liquid_assets:       row.get(3)?,
+        emergency_borrowing: row.get(4)?,
+        penalty_borrowing:   row.get(5)?,
+        capital:             row.get(6)?,
+        rate_premium:        row.get(7)?,
+        run_rate:            row.get(8)?,
+        severity:            row.get(9)?,
+        outflow:             row.get(10)?,
+        premium_cost:        row.get(11)?,
+        liquidity_cost:      row.get(12)?,
+        op_risk_capital:     row.get(13)?,
+    })
+}
+
+const RUN_COLUMNS: &str = "shock_tick, trigger, reputation_score, ended_tick, peak_run_rate,
+     total_outflow, uninsured_outflow, emergency_drawn, shortfall";
+
+fn run_from_row(row: &Row) -> rusqlite::Result<DepositRunRow> {
+    Ok(DepositRunRow {
+        shock_tick:        row.get::<_, i64>(0)? as Tick,
+        trigger:           row.get(1)?,
+        reputation_score:  row.get(2)?,
+        ended_tick:        row.get::<_, Option<i64>>(3)?.map(|t| t as Tick),
+        peak_run_rate:     row.get(4)?,
+        total_outflow:     row.get(5)?,
+        uninsured_outflow: row.get(6)?,
+        emergency_drawn:   row.get(7)?,
+        shortfall:         row.get(8)?,
+    })
+}
+
+impl SimStore {
+    /// Open accounts holding money, with their balances.
+    pub fn deposit_balances(&self, run_id: &str) -> SimResult<Vec<(String, f64)>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT account_id, balance FROM account
+             WHERE run_id = ?1 AND status = 'open' AND balance > 0
+             ORDER BY account_id",
+        )?;
+        let rows = stmt.query_map(params![run_id], |row| Ok((row.get(0)?, row.get(1)?)))?;
+        rows.collect::<Result<Vec<_>, _>>().map_err(Into::into)
+    }
+
+    pub fn insert_liquidity_position(&self, run_id: &str, row: &LiquidityPositionRow) -> SimResult<()> {
+        self.conn.execute(
+            &format!(
+                "INSERT OR REPLACE INTO liquidity_position (run_id, {POSITION_COLUMNS})
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15)"
+            ),
+            params![
+                run_id, row.tick as i64, row.deposits, row.uninsured_deposits, row.liquid_assets,
+                row.emergency_borrowing, row.penalty_borrowing, row.capital, row.rate_premium,
+                row.run_rate, row.severity, row.outflow, row.premium_cost, row.liquidity_cost,
+                row.op_risk_capital,
+            ],
+        )?;
+        Ok(())
+    }
+
+    pub fn latest_liquidity_position(&self, run_id: &str) -> SimResult<Option<LiquidityPositionRow>> {
+        self.conn
+            .query_row(
+                &format!(
+                    "SELECT {POSITION_COLUMNS} FROM liquidity_position
+                     WHERE run_id = ?1 ORDER BY tick DESC LIMIT 1"
+                ),
+                params![run_id],
+                position_from_row,
+            )
+            .optional()
+            .map_err(Into::into)
+    }
+
+    /// Every tick's position, oldest first.
+    pub fn liquidity_positions(&self, run_id: &str) -> SimResult<Vec<LiquidityPositionRow>> {
+        let mut stmt = self.conn.prepare(&format!(
+            "SELECT {POSITION_COLUMNS} FROM liquidity_position WHERE run_id = ?1 ORDER BY tick"
+        ))?;
+        let rows = stmt.query_map(params![run_id], position_from_row)?;
+        rows.collect::<Result<Vec<_>, _>>().map_err(Into::into)
+    }
+
+    /// Deposit rate premium interest and borrowing interest paid between
+    /// two ticks, inclusive.
+    pub fn deposit_run_costs_between(
+        &self,
+        run_id:     &str,
+        start_tick: Tick,
+        end_tick:   Tick,
+    ) -> SimResult<(f64, f64)> {
+        let costs = self.conn.query_row(
+            "SELECT COALESCE(SUM(premium_cost), 0.0), COALESCE(SUM(liquidity_cost), 0.0)
+             FROM liquidity_position WHERE run_id = ?1 AND tick >= ?2 AND tick <= ?3",
+            params![run_id, start_tick as i64, end_tick as i64],
+            |row| Ok((row.get(0)?, row.get(1)?)),
+        )?;
+        Ok(costs)
+    }
+
+    pub fn insert_deposit_run(&self, run_id: &str, row: &DepositRunRow) -> SimResult<()> {
+        self.conn.execute(
+            &format!("INSERT INTO deposit_run (run_id, {RUN_COLUMNS}) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)"),
+            params![
+                run_id, row.shock_tick as i64, row.trigger, row.reputation_score,
+                row.ended_tick.map(|t| t as i64), row.peak_run_rate, row.total_outflow,
+                row.uninsured_outflow, row.emergency_drawn, row.shortfall,
+            ],
+        )?;
+        Ok(())
+    }
+
+    pub fn update_deposit_run(&self, run_id: &str, row: &DepositRunRow) -> SimResult<()> {
+        self.conn.execute(
+            "UPDATE deposit_run SET ended_tick = ?3, peak_run_rate = ?4, total_outflow = ?5,
+                 uninsured_outflow = ?6, emergency_drawn = ?7, shortfall = ?8
+             WHERE run_id = ?1 AND shock_tick = ?2",
+            params![
+                run_id, row.shock_tick as i64, row.ended_tick.map(|t| t as i64), row.peak_run_rate,
+                row.total_outflow, row.uninsured_outflow, row.emergency_drawn, row.shortfall,
+            ],
+        )?;
+        Ok(())
+    }
+
+    /// Every deposit run, oldest first; the last is open while it has no
+    /// `ended_tick`.
+    pub fn deposit_runs(&self, run_id: &str) -> SimResult<Vec<DepositRunRow>> {
+        let mut stmt = self.conn.prepare(&format!(
+            "SELECT {RUN_COLUMNS} FROM deposit_run WHERE run_id = ?1 ORDER BY shock_tick"
+        ))?;
+        let rows = stmt.query_map(params![run_id], run_from_row)?;
+        rows.collect::<Result<Vec<_>, _>>().map_err(Into::into)
+    }
+}
@@ -0,0 +1,210 @@
+//! Store methods for device and IP telemetry (Phase 4.94).
+
+use crate::{error::SimResult, types::Tick};
+use rusqlite::params;
+
+use super::SimStore;
+
+/// Row from the `customer_device` table.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CustomerDeviceRow {
+    pub customer_id:     String,
+    pub device_id:       String,
+    pub fingerprint:     String,
+    pub first_seen_tick: Tick,
+    pub last_seen_tick:  Tick,
+    /// Customers who have logged in from the device.
+    pub shared_count:    i64,
+}
+
+/// Row from the `device_session` table.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DeviceSessionRow {
+    pub session_id:  String,
+    pub tick:        Tick,
+    pub customer_id: String,
+    pub device_id:   String,
+    pub ip_address:  String,
+    /// The customer's first login from the device.
+    pub new_device:  bool,
+    /// A fraudster's login to the customer's account.
+    pub takeover:    bool,
+}
+
+/// A payment sent from a device, with what is known of the device.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DevicePayment {
+    pub txn_id:          String,
+    pub account_id:      String,
+    pub customer_id:     String,
+    pub device_id:       String,
+    pub tick:            Tick,
+    pub amount:          f64,
+    /// When the customer first logged in from the device.
+    pub first_seen_tick: Tick,
+    pub shared_count:    i64,
+}
+
+impl SimStore {
+    /// Record a customer's first login from a device and recount the
+    /// customers on it; returns the new count.
+    pub fn register_customer_device(
+        &self,
+        run_id: &str,
+        customer_id: &str,
+        device_id: &str,
+        fingerprint: &str,
+        tick: Tick,
+    ) -> SimResult<i64> {
+        self.conn.execute(
+            "INSERT OR IGNORE INTO customer_device
+             (run_id, customer_id, device_id, fingerprint, first_seen_tick, last_seen_tick)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?5)",
+            params![run_id, customer_id, device_id, fingerprint, tick as i64],
+        )?;
+        let shared: i64 = self.conn.query_row(
+            "SELECT COUNT(*) FROM customer_device WHERE run_id = ?1 AND device_id = ?2",
+            params![run_id, device_id],
+            |row| row.get(0),
+        )?;
+        self.conn.execute(
+            "UPDATE customer_device SET shared_count = ?1 WHERE run_id = ?2 AND device_id = ?3",
+            params![shared, run_id, device_id],
+        )?;
+        Ok(shared)
+    }
+
+    pub fn touch_customer_device(&self, run_id: &str, customer_id: &str, device_id: &str, tick: Tick) -> SimResult<()> {
+        self.conn.execute(
+            "UPDATE customer_device SET last_seen_tick = ?1
+             WHERE run_id = ?2 AND customer_id = ?3 AND device_id = ?4",
+            params![tick as i64, run_id, customer_id, device_id],
+        )?;
+        Ok(())
+    }
+
+    /// Every customer's devices, by customer and then first seen.
+    pub fn customer_devices(&self, run_id: &str) -> SimResult<Vec<CustomerDeviceRow>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT customer_id, device_id, fingerprint, first_seen_tick, last_seen_tick, shared_count
+             FROM customer_device WHERE run_id = ?1
+             ORDER BY customer_id, first_seen_tick, device_id",
+        )?;
+        let rows = stmt.query_map(params![run_id], |row| {
+            Ok(CustomerDeviceRow {
+                customer_id:     row.get(0)?,
+                device_id:       row.get(1)?,
+                fingerprint:     row.get(2)?,
+                first_seen_tick: row.get::<_, i64>(3)? as Tick,
+                last_seen_tick:  row.get::<_, i64>(4)? as Tick,
+                shared_count:    row.get(5)?,
+            })
+        })?;
+        rows.collect::<Result<Vec<_>, _>>().map_err(Into::into)
+    }
+
+    pub fn insert_device_session(&self, run_id: &str, row: &DeviceSessionRow) -> SimResult<()> {
+        self.conn.execute(
+            "INSERT INTO device_session
+             (session_id, run_id, tick, customer_id, device_id, ip_address, new_device, takeover)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+            params![
+                row.session_id, run_id, row.tick as i64, row.customer_id, row.device_id, row.ip_address,
+                row.new_device, row.takeover,
+            ],
+        )?;
+        Ok(())
+    }
+
+    /// The logins on `tick`, in session order.
+    pub fn device_sessions(&self, run_id: &str, tick: Tick) -> SimResult<Vec<DeviceSessionRow>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT session_id, tick, customer_id, device_id, ip_address, new_device, takeover
+             FROM device_session WHERE run_id = ?1 AND tick = ?2 ORDER BY session_id",
+        )?;
+        let rows = stmt.query_map(params![run_id, tick as i64], |row| {
+            Ok(DeviceSessionRow {
+                session_id:  row.get(0)?,
+                tick:        row.get::<_, i64>(1)? as Tick,
+                customer_id: row.get(2)?,
+                device_id:   row.get(3)?,
+                ip_address:  row.get(4)?,
+                new_device:  row.get(5)?,
+                takeover:    row.get(6)?,
+            })
+        })?;
+        rows.collect::<Result<Vec<_>, _>>().map_err(Into::into)
+    }
+
+    pub fn set_transaction_device(&self, run_id: &str, txn_id: &str, device_id: &str) -> SimResult<()> {
+        self.conn.execute(
+            "UPDATE transactions SET device_id = ?1 WHERE run_id = ?2 AND txn_id = ?3",
+            params![device_id, run_id, txn_id],
+        )?;
+        Ok(())
+    }
+
+    /// Payments sent online or in the mobile app on `tick`, as
+    /// (txn_id, customer_id), in transaction order.
+    pub fn digital_payments(&self, run_id: &str, tick: Tick) -> SimResult<Vec<(String, String)>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT t.txn_id, a.customer_id
+             FROM transactions t
+             JOIN account a ON a.run_id = t.run_id AND a.account_id = t.account_id
+             WHERE t.run_id = ?1 AND t.tick = ?2 AND t.direction = 'debit'
+               AND t.channel IN ('online', 'mobile')
+             ORDER BY t.txn_id",
+        )?;
+        let rows = stmt.query_map(params![run_id, tick as i64], |row| Ok((row.get(0)?, row.get(1)?)))?;
+        rows.collect::<Result<Vec<_>, _>>().map_err(Into::into)
+    }
+
+    /// Payments of at least `min_amount` sent from a device over
+    /// `[start, end]`, in transaction order.
+    pub fn device_payments(
+        &self,
+        run_id: &str,
+        start: Tick,
+        end: Tick,
+        min_amount: f64,
+    ) -> SimResult<Vec<DevicePayment>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT t.txn_id, t.account_id, d.customer_id, t.device_id, t.tick, t.amount,
+                    d.first_seen_tick, d.shared_count
+             FROM transactions t
+             JOIN account a ON a.run_id = t.run_id AND a.account_id = t.account_id
+             JOIN customer_device d
+               ON d.run_id = t.run_id AND d.customer_id = a.customer_id AND d.device_id = t.device_id
+             WHERE t.run_id = ?1 AND t.tick >= ?2 AND t.tick <= ?3
+               AND t.device_id IS NOT NULL AND t.amount >= ?4
+             ORDER BY t.txn_id",
+        )?;
+        let rows = stmt.query_map(params![run_id, start as i64, end as i64, min_amount], |row| {
+            Ok(DevicePayment {
+                txn_id:          row.get(0)?,
+                account_id:      row.get(1)?,
+                customer_id:     row.get(2)?,
+                device_id:       row.get(3)?,
+                tick:            row.get::<_, i64>(4)? as Tick,
+                amount:          row.get(5)?,
+                first_seen_tick: row.get::<_, i64>(6)? as Tick,
+                shared_count:    row.get(7)?,
+            })
+        })?;
+        rows.collect::<Result<Vec<_>, _>>().map_err(Into::into)
+    }
+
+    /// Devices that reached `min_customers` customers on `tick`, as
+    /// (device_id, customers), in id order.
+    pub fn devices_shared_on(&self, run_id: &str, tick: Tick, min_customers: i64) -> SimResult<Vec<(String, i64)>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT device_id, COUNT(*) AS customers
+             FROM customer_device WHERE run_id = ?1
+             GROUP BY device_id
+             HAVING customers >= ?3 AND SUM(first_seen_tick < ?2) < ?3
+             ORDER BY device_id",
+        )?;
+        let rows = stmt.query_map(params![run_id, tick as i64, min_customers], |row| Ok((row.get(0)?, row.get(1)?)))?;
+        rows.collect::<Result<Vec<_>, _>>().map_err(Into::into)
+    }
+}
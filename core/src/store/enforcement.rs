@@ -0,0 +1,255 @@
+//! Store methods for regulatory enforcement (Phase 4.81).
+
+use crate::{error::SimResult, types::Tick};
+use rusqlite::{params, OptionalExtension, Row};
+
+use super::SimStore;
+
+/// Row from the `enforcement_action` table.
+#[derive(Debug, Clone, PartialEq)]
+pub struct EnforcementActionRow {
+    pub action_id:            String,
+    pub exam_id:              String,
+    pub finding_id:           String,
+    /// 'mra' | 'consent_order'
+    pub kind:                 String,
+    pub category:             String,
+    pub tick_issued:          Tick,
+    /// Earliest tick an exam can find the action remediated.
+    pub remediation_due_tick: Tick,
+    /// 'fee_cap' | 'complaint_lookback' | 'sar_lookback'
+    pub constraint_kind:      Option<String>,
+    pub penalty:              f64,
+    pub lookback_items:       i64,
+    pub lookback_reviewed:    i64,
+    pub lookback_cost:        f64,
+    /// 'open' | 'lifted' | 'escalated'
+    pub status:               String,
+    pub tick_closed:          Option<Tick>,
+    pub superseded_by:        Option<String>,
+}
+
+const ACTION_COLUMNS: &str = "action_id, exam_id, finding_id, kind, category, tick_issued, remediation_due_tick,
+     constraint_kind, penalty, lookback_items, lookback_reviewed, lookback_cost, status, tick_closed, superseded_by";
+
+fn action_from_row(row: &Row) -> rusqlite::Result<EnforcementActionRow> {
+    Ok(EnforcementActionRow {
+        action_id:            row.get(0)?,
+        exam_id:              row.get(1)?,
+        finding_id:           row.get(2)?,
+        kind:                 row.get(3)?,
+        category:             row.get(4)?,
+        tick_issued:          row.get::<_, i64>(5)? as Tick,
+        remediation_due_tick: row.get::<_, i64>(6)? as Tick,
+        constraint_kind:      row.get(7)?,
+        penalty:              row.get(8)?,
+        lookback_items:       row.get(9)?,
+        lookback_reviewed:    row.get(10)?,
+        lookback_cost:        row.get(11)?,
+        status:               row.get(12)?,
+        tick_closed:          row.get::<_, Option<i64>>(13)?.map(|t| t as Tick),
+        superseded_by:        row.get(14)?,
+    })
+}
+
+impl SimStore {
+    // ── Exam testing ─────────────────────────────────────────────────
+
+    /// Complaints closed in `start..=end`, by id, each true when it was
+    /// mishandled: closed past its SLA, or a UDAAP complaint closed
+    /// without a refund.
+    pub fn exam_complaint_population(&self, run_id: &str, start: Tick, end: Tick) -> SimResult<Vec<bool>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT sla_breached = 1 OR (udaap_flag = 1 AND COALESCE(amount_refunded, 0.0) = 0.0)
+               FROM complaint
+              WHERE run_id = ?1 AND tick_closed >= ?2 AND tick_closed <= ?3
+              ORDER BY complaint_id",
+        )?;
+        let rows = stmt.query_map(params![run_id, start as i64, end as i64], |row| row.get(0))?;
+        rows.collect::<Result<Vec<_>, _>>().map_err(Into::into)
+    }
+
+    /// SARs filed in `start..=end`, by id, each true when filed late.
+    pub fn exam_sar_population(&self, run_id: &str, start: Tick, end: Tick) -> SimResult<Vec<bool>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT filed_on_time = 0 FROM suspicious_activity_report
+              WHERE run_id = ?1 AND filing_tick >= ?2 AND filing_tick <= ?3
+              ORDER BY sar_id",
+        )?;
+        let rows = stmt.query_map(params![run_id, start as i64, end as i64], |row| row.get(0))?;
+        rows.collect::<Result<Vec<_>, _>>().map_err(Into::into)
+    }
+
+    /// Fee changes made in `start..=end`, and how many of them raised a fee.
+    pub fn fee_changes_between(&self, run_id: &str, start: Tick, end: Tick) -> SimResult<(i64, i64)> {
+        self.conn
+            .query_row(
+                "SELECT COUNT(*), COALESCE(SUM(new_value > old_value), 0) FROM fee_change_log
+                  WHERE run_id = ?1 AND tick >= ?2 AND tick <= ?3",
+                params![run_id, start as i64, end as i64],
+                |row| Ok((row.get(0)?, row.get(1)?)),
+            )
+            .map_err(Into::into)
+    }
+
+    pub fn insert_exam_sample(
+        &self,
+        run_id: &str,
+        exam_id: &str,
+        population: &str,
+        available: usize,
+        sampled: usize,
+        failed: usize,
+    ) -> SimResult<()> {
+        self.conn.execute(
+            "INSERT OR REPLACE INTO exam_sample (run_id, exam_id, population, available, sampled, failed)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+            params![run_id, exam_id, population, available as i64, sampled as i64, failed as i64],
+        )?;
+        Ok(())
+    }
+
+    /// (population, available, sampled, failed) an exam tested (for tests).
+    pub fn exam_samples(&self, run_id: &str, exam_id: &str) -> SimResult<Vec<(String, i64, i64, i64)>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT population, available, sampled, failed FROM exam_sample
+              WHERE run_id = ?1 AND exam_id = ?2 ORDER BY population",
+        )?;
+        let rows = stmt.query_map(params![run_id, exam_id], |row| {
+            Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?))
+        })?;
+        rows.collect::<Result<Vec<_>, _>>().map_err(Into::into)
+    }
+
+    pub fn set_exam_udaap_score(&self, run_id: &str, exam_id: &str, score: f64) -> SimResult<()> {
+        self.conn.execute(
+            "UPDATE regulatory_exam SET udaap_score = ?3 WHERE run_id = ?1 AND exam_id = ?2",
+            params![run_id, exam_id, score],
+        )?;
+        Ok(())
+    }
+
+    /// The UDAAP score the latest closed exam left behind, if any closed
+    /// with one.
+    pub fn last_exam_udaap_score(&self, run_id: &str) -> SimResult<Option<f64>> {
+        self.conn
+            .query_row(
+                "SELECT udaap_score FROM regulatory_exam
+                  WHERE run_id = ?1 AND status = 'closed' AND udaap_score IS NOT NULL
+                  ORDER BY tick_completed DESC LIMIT 1",
+                params![run_id],
+                |row| row.get(0),
+            )
+            .optional()
+            .map_err(Into::into)
+    }
+
+    // ── Enforcement actions ──────────────────────────────────────────
+
+    /// Items a lookback of `constraint_kind` re-reviews: complaints opened
+    /// or AML alerts raised in `start..=end`.
+    pub fn lookback_population(&self, run_id: &str, constraint_kind: &str, start: Tick, end: Tick) -> SimResult<i64> {
+        let sql = match constraint_kind {
+            "complaint_lookback" => "SELECT COUNT(*) FROM complaint WHERE run_id = ?1 AND tick_opened >= ?2 AND tick_opened <= ?3",
+            "sar_lookback" => "SELECT COUNT(*) FROM aml_alert WHERE run_id = ?1 AND tick >= ?2 AND tick <= ?3",
+            _ => return Ok(0),
+        };
+        self.conn
+            .query_row(sql, params![run_id, start as i64, end as i64], |row| row.get(0))
+            .map_err(Into::into)
+    }
+
+    pub fn insert_enforcement_action(&self, run_id: &str, row: &EnforcementActionRow) -> SimResult<()> {
+        self.conn.execute(
+            &format!(
+                "INSERT INTO enforcement_action (run_id, {ACTION_COLUMNS})
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16)"
+            ),
+            params![
+                run_id,
+                row.action_id,
+                row.exam_id,
+                row.finding_id,
+                row.kind,
+                row.category,
+                row.tick_issued as i64,
+                row.remediation_due_tick as i64,
+                row.constraint_kind,
+                row.penalty,
+                row.lookback_items,
+                row.lookback_reviewed,
+                row.lookback_cost,
+                row.status,
+                row.tick_closed.map(|t| t as i64),
+                row.superseded_by,
+            ],
+        )?;
+        Ok(())
+    }
+
+    /// Actions still in force, oldest first.
+    pub fn open_enforcement_actions(&self, run_id: &str) -> SimResult<Vec<EnforcementActionRow>> {
+        let mut stmt = self.conn.prepare(&format!(
+            "SELECT {ACTION_COLUMNS} FROM enforcement_action
+              WHERE run_id = ?1 AND status = 'open' ORDER BY tick_issued, action_id"
+        ))?;
+        let rows = stmt.query_map(params![run_id], action_from_row)?;
+        rows.collect::<Result<Vec<_>, _>>().map_err(Into::into)
+    }
+
+    /// Every action issued in the run, oldest first.
+    pub fn enforcement_actions(&self, run_id: &str) -> SimResult<Vec<EnforcementActionRow>> {
+        let mut stmt = self.conn.prepare(&format!(
+            "SELECT {ACTION_COLUMNS} FROM enforcement_action WHERE run_id = ?1 ORDER BY tick_issued, action_id"
+        ))?;
+        let rows = stmt.query_map(params![run_id], action_from_row)?;
+        rows.collect::<Result<Vec<_>, _>>().map_err(Into::into)
+    }
+
+    /// Close an action as 'lifted' or 'escalated'.
+    pub fn close_enforcement_action(
+        &self,
+        run_id: &str,
+        action_id: &str,
+        tick: Tick,
+        status: &str,
+        superseded_by: Option<&str>,
+    ) -> SimResult<()> {
+        self.conn.execute(
+            "UPDATE enforcement_action SET status = ?3, tick_closed = ?4, superseded_by = ?5
+              WHERE run_id = ?1 AND action_id = ?2",
+            params![run_id, action_id, status, tick as i64, superseded_by],
+        )?;
+        Ok(())
+    }
+
+    pub fn record_lookback_progress(&self, run_id: &str, action_id: &str, reviewed: i64) -> SimResult<()> {
+        self.conn.execute(
+            "UPDATE enforcement_action SET lookback_reviewed = ?3 WHERE run_id = ?1 AND action_id = ?2",
+            params![run_id, action_id, reviewed],
+        )?;
+        Ok(())
+    }
+
+    /// Whether an open action imposes `constraint_kind`.
+    pub fn has_active_constraint(&self, run_id: &str, constraint_kind: &str) -> SimResult<bool> {
+        let count: i64 = self.conn.query_row(
+            "SELECT COUNT(*) FROM enforcement_action
+              WHERE run_id = ?1 AND status = 'open' AND constraint_kind = ?2",
+            params![run_id, constraint_kind],
+            |row| row.get(0),
+        )?;
+        Ok(count > 0)
+    }
+
+    /// Penalties and lookback costs of actions issued in `start..=end`.
+    pub fn enforcement_cost_between(&self, run_id: &str, start_tick: Tick, end_tick: Tick) -> SimResult<f64> {
+        let total: f64 = self.conn.query_row(
+            "SELECT COALESCE(SUM(penalty + lookback_cost), 0.0) FROM enforcement_action
+              WHERE run_id = ?1 AND tick_issued >= ?2 AND tick_issued <= ?3",
+            params![run_id, start_tick as i64, end_tick as i64],
+            |row| row.get(0),
+        )?;
+        Ok(total)
+    }
+}
@@ -0,0 +1,109 @@
+//! Store methods for transaction categorization enrichment (Phase 4.35).
+
+use crate::{error::SimResult, types::Tick};
+use rusqlite::params;
+
+use super::SimStore;
+
+/// Row from the `merchant_category` table.
+#[derive(Debug, Clone)]
+pub struct MerchantCategoryRow {
+    pub counterparty:      String,
+    pub true_category:     String,
+    pub enriched_category: String,
+    pub tick_first_seen:   Tick,
+}
+
+/// A customer's merchant spend in one enriched category.
+#[derive(Debug, Clone)]
+pub struct CategorySpendRow {
+    pub customer_id: String,
+    pub category:    String,
+    pub amount:      f64,
+}
+
+impl SimStore {
+    /// Merchants paid by purchases at the tick that have not been
+    /// categorized yet.
+    pub fn uncategorized_merchants(&self, run_id: &str, tick: Tick) -> SimResult<Vec<String>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT DISTINCT t.counterparty FROM transactions t
+             WHERE t.run_id = ?1 AND t.tick = ?2 AND t.category = 'purchase'
+               AND t.counterparty IS NOT NULL
+               AND NOT EXISTS (
+                   SELECT 1 FROM merchant_category m
+                   WHERE m.run_id = t.run_id AND m.counterparty = t.counterparty
+               )
+             ORDER BY t.counterparty",
+        )?;
+        let rows = stmt.query_map(params![run_id, tick as i64], |row| row.get(0))?;
+        rows.collect::<Result<Vec<_>, _>>().map_err(Into::into)
+    }
+
+    pub fn insert_merchant_category(&self, run_id: &str, row: &MerchantCategoryRow) -> SimResult<()> {
+        self.conn.execute(
+            "INSERT OR IGNORE INTO merchant_category
+             (run_id, counterparty, true_category, enriched_category, tick_first_seen)
+             VALUES (?1, ?2, ?3, ?4, ?5)",
+            params![
+                run_id, row.counterparty, row.true_category, row.enriched_category,
+                row.tick_first_seen as i64,
+            ],
+        )?;
+        Ok(())
+    }
+
+    /// Purchase spend by customer and enriched category in the inclusive
+    /// window, for one customer or all of them. Merchants not categorized
+    /// yet are left out.
+    pub fn merchant_spend_by_category(
+        &self,
+        run_id:      &str,
+        customer_id: Option<&str>,
+        tick_start:  Tick,
+        tick_end:    Tick,
+    ) -> SimResult<Vec<CategorySpendRow>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT a.customer_id, m.enriched_category, SUM(t.amount)
+             FROM transactions t
+             JOIN account a ON a.account_id = t.account_id AND a.run_id = t.run_id
+             JOIN merchant_category m ON m.run_id = t.run_id AND m.counterparty = t.counterparty
+             WHERE t.run_id = ?1 AND t.category = 'purchase'
+               AND t.tick >= ?2 AND t.tick <= ?3
+               AND (?4 IS NULL OR a.customer_id = ?4)
+             GROUP BY a.customer_id, m.enriched_category
+             ORDER BY a.customer_id, m.enriched_category",
+        )?;
+        let rows = stmt.query_map(
+            params![run_id, tick_start as i64, tick_end as i64, customer_id],
+            |row| {
+                Ok(CategorySpendRow {
+                    customer_id: row.get(0)?,
+                    category:    row.get(1)?,
+                    amount:      row.get(2)?,
+                })
+            },
+        )?;
+        rows.collect::<Result<Vec<_>, _>>().map_err(Into::into)
+    }
+
+    // ── Test / summary helpers ────────────────────────────────────────
+
+    /// Every categorized merchant for a run, with its ground truth (for
+    /// tests).
+    pub fn merchant_categories(&self, run_id: &str) -> SimResult<Vec<MerchantCategoryRow>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT counterparty, true_category, enriched_category, tick_first_seen
+             FROM merchant_category WHERE run_id = ?1 ORDER BY counterparty",
+        )?;
+        let rows = stmt.query_map(params![run_id], |row| {
+            Ok(MerchantCategoryRow {
+                counterparty:      row.get(0)?,
+                true_category:     row.get(1)?,
+                enriched_category: row.get(2)?,
+                tick_first_seen:   row.get::<_, i64>(3)? as Tick,
+            })
+        })?;
+        rows.collect::<Result<Vec<_>, _>>().map_err(Into::into)
+    }
+}
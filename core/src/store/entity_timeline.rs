@@ -0,0 +1,215 @@
+//! Entity timeline query (Phase 4.58).
+
+use crate::{
+    entity_timeline::{EntityType, TimelineEntry, TimelineKind},
+    error::SimResult,
+    types::Tick,
+};
+use rusqlite::params;
+
+use super::SimStore;
+
+/// Every timeline source as (tick, rank, seq, kind, source, ref_id,
+/// account_id, summary, amount). ?1 is the run; ?2, ?3, ?4 and ?7 the
+/// customer, account, dispute and investigation case asked about, NULL
+/// for the others. `items` are the investigation case's linked items,
+/// split by type into the `case_*` lists, and `accts` and `disputes`
+/// the accounts and disputes in view. `rank`
+/// orders entries within a tick and `seq`, the row's insertion order,
+/// those from one source.
+const TIMELINE_SQL: &str = "
+WITH items AS (
+    SELECT item_type, item_id FROM case_item WHERE run_id = ?1 AND case_id = ?7
+),
+case_alerts AS (SELECT item_id FROM items WHERE item_type = 'aml_alert'),
+case_fraud_alerts AS (SELECT item_id FROM items WHERE item_type = 'fraud_alert'),
+case_hits AS (SELECT item_id FROM items WHERE item_type = 'screening_hit'),
+case_complaints AS (SELECT item_id FROM items WHERE item_type = 'complaint'),
+accts AS (
+    SELECT account_id FROM account WHERE run_id = ?1 AND (customer_id = ?2 OR account_id = ?3)
+),
+disputes AS (
+    SELECT dispute_id FROM card_dispute
+    WHERE run_id = ?1
+      AND (dispute_id = ?4 OR customer_id = ?2 OR account_id IN accts
+           OR dispute_id IN (SELECT item_id FROM items WHERE item_type = 'dispute'))
+),
+entries AS (
+    SELECT open_tick AS tick, 0 AS rank, rowid AS seq, 'status_change' AS kind, 'customer' AS source,
+           customer_id AS ref_id, NULL AS account_id,
+           'customer opened (' || segment || ')' AS summary, NULL AS amount
+      FROM customer WHERE run_id = ?1 AND customer_id = ?2
+    UNION ALL
+    SELECT close_tick, 9, rowid, 'status_change', 'customer', customer_id, NULL, 'customer ' || status, NULL
+      FROM customer WHERE run_id = ?1 AND customer_id = ?2 AND status <> 'active' AND close_tick IS NOT NULL
+    UNION ALL
+    SELECT tick, 6, rowid, 'status_change', 'risk_tier_transition', CAST(transition_id AS TEXT), NULL,
+           'risk tier ' || from_tier || ' -> ' || to_tier || ': ' || reason, NULL
+      FROM risk_tier_transition WHERE run_id = ?1 AND customer_id = ?2
+    UNION ALL
+    SELECT open_tick, 1, rowid, 'status_change', 'account', account_id, account_id,
+           'account opened (' || product_id || ')', NULL
+      FROM account WHERE run_id = ?1 AND account_id IN accts
+    UNION ALL
+    SELECT close_tick, 8, rowid, 'status_change', 'account', account_id, account_id, 'account ' || status, NULL
+      FROM account WHERE run_id = ?1 AND account_id IN accts AND status <> 'open' AND close_tick IS NOT NULL
+    UNION ALL
+    SELECT tick, 2, rowid, 'transaction', 'transactions', txn_id, account_id,
+           direction || ' ' || category || COALESCE(' ' || counterparty, ''), amount
+      FROM transactions WHERE run_id = ?1 AND account_id IN accts
+    UNION ALL
+    SELECT f.tick, 3, f.rowid, 'alert', 'fraud_alert', f.alert_id,
+           CASE f.entity_type WHEN 'account' THEN f.entity_id ELSE t.account_id END,
+           f.alert_type || ' (' || f.severity || ')', NULL
+      FROM fraud_alert f
+      LEFT JOIN transactions t ON f.entity_type = 'transaction' AND t.txn_id = f.entity_id
+     WHERE f.run_id = ?1
+       AND ((f.entity_type = 'account' AND f.entity_id IN accts)
+            OR (f.entity_type = 'transaction' AND t.run_id = ?1 AND t.account_id IN accts)
+            OR f.alert_id IN case_fraud_alerts)
+    UNION ALL
+    SELECT tick, 3, rowid, 'alert', 'aml_alert', alert_id, NULL,
+           alert_type || ' (' || severity || '): ' || description, triggered_amount
+      FROM aml_alert WHERE run_id = ?1 AND (customer_id = ?2 OR alert_id IN case_alerts)
+    UNION ALL
+    SELECT resolved_tick, 6, rowid, 'status_change', 'aml_alert', alert_id, NULL, 'alert ' || status, NULL
+      FROM aml_alert
+     WHERE run_id = ?1 AND (customer_id = ?2 OR alert_id IN case_alerts) AND resolved_tick IS NOT NULL
+    UNION ALL
+    SELECT resolved_tick, 7, rowid, 'note', 'aml_alert', alert_id, NULL, resolution_notes, NULL
+      FROM aml_alert
+     WHERE run_id = ?1 AND (customer_id = ?2 OR alert_id IN case_alerts)
+       AND resolved_tick IS NOT NULL AND resolution_notes <> ''
+    UNION ALL
+    SELECT n.tick, 7, n.rowid, 'note', 'alert_note', n.alert_id, NULL,
+           n.action || COALESCE(' by ' || n.analyst, '') || ': ' || n.note, NULL
+      FROM alert_note n
+      JOIN aml_alert a ON a.run_id = n.run_id AND a.alert_id = n.alert_id
+     WHERE n.run_id = ?1 AND (a.customer_id = ?2 OR a.alert_id IN case_alerts)
+    UNION ALL
+    SELECT filing_tick, 6, rowid, 'status_change', 'suspicious_activity_report', sar_id,
+           CASE subject_type WHEN 'account' THEN subject_id END,
+           'SAR ' || filing_status || ': ' || activity_type, suspicious_amount
+      FROM suspicious_activity_report
+     WHERE run_id = ?1
+       AND ((subject_type = 'customer' AND subject_id = ?2)
+            OR (subject_type = 'account' AND subject_id IN accts))
+    UNION ALL
+    SELECT tick_opened, 4, rowid, 'complaint', 'complaint', complaint_id, account_id,
+           product || ' ' || issue || ' (' || priority || ')', NULL
+      FROM complaint WHERE run_id = ?1 AND (customer_id = ?2 OR account_id IN accts OR complaint_id IN case_complaints)
+    UNION ALL
+    SELECT tick_closed, 6, rowid, 'status_change', 'complaint', complaint_id, account_id,
+           'complaint ' || status || COALESCE(': ' || resolution_code, ''), NULLIF(amount_refunded, 0.0)
+      FROM complaint
+     WHERE run_id = ?1 AND (customer_id = ?2 OR account_id IN accts OR complaint_id IN case_complaints)
+       AND tick_closed IS NOT NULL
+    UNION ALL
+    SELECT tick, 7, rowid, 'note', 'interaction', interaction_id, NULL,
+           channel || ' ' || interaction_type || COALESCE(': ' || outcome, ''), NULL
+      FROM interaction WHERE run_id = ?1 AND customer_id = ?2
+    UNION ALL
+    SELECT tick_filed, 5, rowid, 'case', 'card_dispute', dispute_id, account_id,
+           'dispute ' || reason || ' at ' || merchant_name, amount
+      FROM card_dispute WHERE run_id = ?1 AND dispute_id IN disputes
+    UNION ALL
+    SELECT tick, 6, id, 'status_change', 'dispute_timeline', dispute_id, NULL,
+           'dispute ' || from_status || ' -> ' || to_status, NULL
+      FROM dispute_timeline WHERE run_id = ?1 AND dispute_id IN disputes
+    UNION ALL
+    SELECT tick, 7, id, 'note', 'dispute_timeline', dispute_id, NULL, notes, NULL
+      FROM dispute_timeline WHERE run_id = ?1 AND dispute_id IN disputes AND notes <> ''
+    UNION ALL
+    SELECT tick_created, 7, rowid, 'note', 'case_document', document_id, NULL,
+           case_type || ' ' || case_id || ': ' || doc_type || ' ' || status, NULL
+      FROM case_document
+     WHERE run_id = ?1 AND ((case_type = 'dispute' AND case_id IN disputes) OR customer_id = ?2)
+    UNION ALL
+    SELECT screening_tick, 3, rowid, 'alert', 'aml_screening_result', screening_id, NULL,
+           screening_type || ' ' || match_type, NULL
+      FROM aml_screening_result
+     WHERE run_id = ?1 AND screening_id IN case_hits
+    UNION ALL
+    SELECT reviewed_tick, 6, rowid, 'status_change', 'aml_screening_result', screening_id, NULL,
+           'screening hit ' || status || COALESCE(': ' || review_notes, ''), NULL
+      FROM aml_screening_result
+     WHERE run_id = ?1 AND screening_id IN case_hits
+       AND reviewed_tick IS NOT NULL
+    UNION ALL
+    SELECT opened_tick, 0, rowid, 'status_change', 'investigation_case', case_id, NULL,
+           'case opened on ' || customer_id, NULL
+      FROM investigation_case WHERE run_id = ?1 AND case_id = ?7
+    UNION ALL
+    SELECT closed_tick, 9, rowid, 'status_change', 'investigation_case', case_id, NULL,
+           'case closed: ' || disposition, NULL
+      FROM investigation_case WHERE run_id = ?1 AND case_id = ?7 AND closed_tick IS NOT NULL
+    UNION ALL
+    SELECT tick, 7, rowid, 'note', 'case_note', case_id, NULL,
+           action || COALESCE(' by ' || analyst, '') || ': ' || note, NULL
+      FROM case_note WHERE run_id = ?1 AND case_id = ?7
+)
+SELECT tick, kind, source, ref_id, account_id, summary, amount
+  FROM entries
+ WHERE (?5 IS NULL OR tick >= ?5) AND (?6 IS NULL OR tick <= ?6)
+ ORDER BY tick, rank, source, seq";
+
+impl SimStore {
+    pub fn timeline_entity_exists(&self, run_id: &str, entity_type: EntityType, entity_id: &str) -> SimResult<bool> {
+        let (table, id) = match entity_type {
+            EntityType::Customer => ("customer", "customer_id"),
+            EntityType::Account => ("account", "account_id"),
+            EntityType::Case => ("card_dispute", "dispute_id"),
+            EntityType::InvestigationCase => ("investigation_case", "case_id"),
+        };
+        let exists = self.conn.query_row(
+            &format!("SELECT EXISTS (SELECT 1 FROM {table} WHERE run_id = ?1 AND {id} = ?2)"),
+            params![run_id, entity_id],
+            |row| row.get(0),
+        )?;
+        Ok(exists)
+    }
+
+    /// Everything in the entity's timeline within the tick window, oldest
+    /// first.
+    pub fn entity_timeline(
+        &self,
+        run_id: &str,
+        entity_type: EntityType,
+        entity_id: &str,
+        from_tick: Option<Tick>,
+        to_tick: Option<Tick>,
+    ) -> SimResult<Vec<TimelineEntry>> {
+        let id_for = |t: EntityType| (t == entity_type).then_some(entity_id);
+        let mut stmt = self.conn.prepare(TIMELINE_SQL)?;
+        let rows = stmt.query_map(
+            params![
+                run_id,
+                id_for(EntityType::Customer),
+                id_for(EntityType::Account),
+                id_for(EntityType::Case),
+                from_tick.map(|t| t as i64),
+                to_tick.map(|t| t as i64),
+                id_for(EntityType::InvestigationCase),
+            ],
+            |row| {
+                let kind: String = row.get(1)?;
+                Ok(TimelineEntry {
+                    tick: row.get::<_, i64>(0)? as Tick,
+                    kind: TimelineKind::parse(&kind).ok_or_else(|| {
+                        rusqlite::Error::FromSqlConversionFailure(
+                            1,
+                            rusqlite::types::Type::Text,
+                            format!("unknown timeline kind {kind}").into(),
+                        )
+                    })?,
+                    source: row.get(2)?,
+                    ref_id: row.get(3)?,
+                    account_id: row.get(4)?,
+                    summary: row.get(5)?,
+                    amount: row.get(6)?,
+                })
+            },
+        )?;
+        rows.collect::<Result<Vec<_>, _>>().map_err(Into::into)
+    }
+}
@@ -0,0 +1,192 @@
+//! Store methods for the archived event log tier (Phase 4.28).
+//!
+//! Recent ticks live in `event_log` one row per event. Compaction moves
+//! older ticks into `event_log_archive` as one compressed blob per tick,
+//! optionally writing them out as JSON lines too. `events_for_tick` and
+//! the event counts read whichever tier holds the tick.
+
+use crate::{error::SimResult, event::EventLogEntry, types::Tick};
+use flate2::{read::GzDecoder, write::GzEncoder, Compression};
+use rusqlite::{params, OptionalExtension};
+use std::collections::BTreeMap;
+use std::io::{Read, Write};
+
+use super::SimStore;
+
+/// What one compaction pass moved out of `event_log`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CompactionSummary {
+    pub ticks:  usize,
+    pub events: usize,
+}
+
+impl SimStore {
+    /// Move every detailed tick up to and including `through_tick` into
+    /// the archive, one transaction for the whole pass. With `export_dir`
+    /// each tick is also appended to `<export_dir>/<run_id>.events.jsonl`.
+    pub fn compact_event_log(
+        &self,
+        run_id:       &str,
+        through_tick: Tick,
+        export_dir:   Option<&str>,
+    ) -> SimResult<CompactionSummary> {
+        let ticks: Vec<Tick> = {
+            let mut stmt = self.conn.prepare(
+                "SELECT DISTINCT tick FROM event_log WHERE run_id = ?1 AND tick <= ?2 ORDER BY tick",
+            )?;
+            let rows = stmt.query_map(params![run_id, through_tick as i64], |row| {
+                Ok(row.get::<_, i64>(0)? as Tick)
+            })?;
+            rows.collect::<Result<_, _>>()?
+        };
+        if ticks.is_empty() {
+            return Ok(CompactionSummary::default());
+        }
+
+        let mut export = match export_dir {
+            Some(dir) => {
+                std::fs::create_dir_all(dir)
+                    .map_err(|e| anyhow::anyhow!("Cannot create {dir}: {e}"))?;
+                let path = format!("{dir}/{run_id}.events.jsonl");
+                let file = std::fs::OpenOptions::new()
+                    .create(true)
+                    .append(true)
+                    .open(&path)
+                    .map_err(|e| anyhow::anyhow!("Cannot open {path}: {e}"))?;
+                Some((path, std::io::BufWriter::new(file)))
+            }
+            None => None,
+        };
+
+        let tx = self.conn.unchecked_transaction()?;
+        let mut summary = CompactionSummary::default();
+        for tick in ticks {
+            // A tick compacted before keeps its earlier entries first
+            let mut entries = self.archived_events_for_tick(run_id, tick)?;
+            let detailed = self.detailed_events_for_tick(run_id, tick)?;
+            summary.events += detailed.len();
+            entries.extend(detailed);
+
+            let mut counts: BTreeMap<&str, i64> = BTreeMap::new();
+            for entry in &entries {
+                *counts.entry(entry.event_type.as_str()).or_default() += 1;
+            }
+            self.conn.execute(
+                "INSERT OR REPLACE INTO event_log_archive (run_id, tick, event_count, events)
+                 VALUES (?1, ?2, ?3, ?4)",
+                params![run_id, tick as i64, entries.len() as i64, compress(&entries)?],
+            )?;
+            for (event_type, count) in counts {
+                self.conn.execute(
+                    "INSERT OR REPLACE INTO event_log_archive_count (run_id, tick, event_type, event_count)
+                     VALUES (?1, ?2, ?3, ?4)",
+                    params![run_id, tick as i64, event_type, count],
+                )?;
+            }
+            self.conn.execute(
+                "DELETE FROM event_log WHERE run_id = ?1 AND tick = ?2",
+                params![run_id, tick as i64],
+            )?;
+
+            if let Some((path, out)) = &mut export {
+                let line = serde_json::json!({ "tick": tick, "events": entries });
+                writeln!(out, "{line}").map_err(|e| anyhow::anyhow!("Cannot write {path}: {e}"))?;
+            }
+            summary.ticks += 1;
+        }
+        if let Some((path, out)) = &mut export {
+            out.flush().map_err(|e| anyhow::anyhow!("Cannot write {path}: {e}"))?;
+        }
+        tx.commit()?;
+
+        tracing::debug!("archived {} events from {} ticks", summary.events, summary.ticks);
+        Ok(summary)
+    }
+
+    /// A tick's entries from the archive, in their original append order;
+    /// empty if the tick was never compacted.
+    pub fn archived_events_for_tick(&self, run_id: &str, tick: Tick) -> SimResult<Vec<EventLogEntry>> {
+        let blob: Option<Vec<u8>> = self
+            .conn
+            .query_row(
+                "SELECT events FROM event_log_archive WHERE run_id = ?1 AND tick = ?2",
+                params![run_id, tick as i64],
+                |row| row.get(0),
+            )
+            .optional()?;
+        match blob {
+            Some(blob) => decompress(&blob),
+            None => Ok(Vec::new()),
+        }
+    }
+
+    /// Archived events of `event_type` in the inclusive tick window.
+    pub(super) fn archived_event_count(
+        &self,
+        run_id:     &str,
+        tick_start: Tick,
+        tick_end:   Tick,
+        event_type: &str,
+    ) -> SimResult<i64> {
+        let count: i64 = self.conn.query_row(
+            "SELECT COALESCE(SUM(event_count), 0) FROM event_log_archive_count
+             WHERE run_id = ?1 AND event_type = ?2 AND tick >= ?3 AND tick <= ?4",
+            params![run_id, event_type, tick_start as i64, tick_end as i64],
+            |row| row.get(0),
+        )?;
+        Ok(count)
+    }
+
+    /// Ticks compacted into the archive, oldest first.
+    pub(super) fn archived_ticks(&self, run_id: &str) -> SimResult<Vec<Tick>> {
+        let mut stmt = self
+            .conn
+            .prepare("SELECT tick FROM event_log_archive WHERE run_id = ?1 ORDER BY tick")?;
+        let rows = stmt.query_map(params![run_id], |row| Ok(row.get::<_, i64>(0)? as Tick))?;
+        Ok(rows.collect::<Result<_, _>>()?)
+    }
+
+    /// Payloads of every archived `event_type` event, oldest first.
+    pub(super) fn archived_event_payloads(&self, run_id: &str, event_type: &str) -> SimResult<Vec<String>> {
+        let ticks: Vec<Tick> = {
+            let mut stmt = self.conn.prepare(
+                "SELECT tick FROM event_log_archive_count
+                  WHERE run_id = ?1 AND event_type = ?2 ORDER BY tick",
+            )?;
+            let rows = stmt.query_map(params![run_id, event_type], |row| Ok(row.get::<_, i64>(0)? as Tick))?;
+            rows.collect::<Result<_, _>>()?
+        };
+        let mut payloads = Vec::new();
+        for tick in ticks {
+            let entries = self.archived_events_for_tick(run_id, tick)?;
+            payloads.extend(entries.into_iter().filter(|e| e.event_type == event_type).map(|e| e.payload));
+        }
+        Ok(payloads)
+    }
+
+    /// Events still held as individual rows, across all ticks.
+    pub fn detailed_event_count(&self, run_id: &str) -> SimResult<i64> {
+        let count: i64 = self.conn.query_row(
+            "SELECT COUNT(*) FROM event_log WHERE run_id = ?1",
+            params![run_id],
+            |row| row.get(0),
+        )?;
+        Ok(count)
+    }
+}
+
+fn compress(entries: &[EventLogEntry]) -> SimResult<Vec<u8>> {
+    let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+    serde_json::to_writer(&mut encoder, entries)?;
+    Ok(encoder
+        .finish()
+        .map_err(|e| anyhow::anyhow!("Cannot compress archived events: {e}"))?)
+}
+
+fn decompress(blob: &[u8]) -> SimResult<Vec<EventLogEntry>> {
+    let mut json = Vec::new();
+    GzDecoder::new(blob)
+        .read_to_end(&mut json)
+        .map_err(|e| anyhow::anyhow!("Corrupt event archive: {e}"))?;
+    Ok(serde_json::from_slice(&json)?)
+}
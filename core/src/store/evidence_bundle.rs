@@ -0,0 +1,123 @@
+//! Store methods for evidence bundles (Phase 4.59).
+
+use crate::{
+    error::SimResult,
+    evidence_bundle::{EvidenceBundle, KycRecord, PriorSar},
+    types::Tick,
+};
+use rusqlite::{params, OptionalExtension};
+
+use super::SimStore;
+
+impl SimStore {
+    pub fn insert_evidence_bundle(&self, run_id: &str, bundle: &EvidenceBundle, deficient: bool) -> SimResult<()> {
+        self.conn.execute(
+            "INSERT INTO evidence_bundle (run_id, bundle_id, filing_type, filing_id, source_id, customer_id,
+                                          tick, quality, deficient, gaps, content)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11)",
+            params![
+                run_id,
+                bundle.bundle_id,
+                bundle.filing_type,
+                bundle.filing_id,
+                bundle.source_id,
+                bundle.customer_id,
+                bundle.tick as i64,
+                bundle.quality,
+                deficient,
+                serde_json::to_string(&bundle.gaps)?,
+                serde_json::to_string(bundle)?,
+            ],
+        )?;
+        Ok(())
+    }
+
+    /// The bundle kept with a filing; `filing_type` is "sar" | "dispute".
+    pub fn evidence_bundle_for_filing(
+        &self,
+        run_id: &str,
+        filing_type: &str,
+        filing_id: &str,
+    ) -> SimResult<Option<EvidenceBundle>> {
+        let content: Option<String> = self
+            .conn
+            .query_row(
+                "SELECT content FROM evidence_bundle WHERE run_id = ?1 AND filing_type = ?2 AND filing_id = ?3",
+                params![run_id, filing_type, filing_id],
+                |row| row.get(0),
+            )
+            .optional()?;
+        content.map(|c| serde_json::from_str(&c).map_err(Into::into)).transpose()
+    }
+
+    /// Bundles assembled in ticks `from_tick..=to_tick`, and how many of
+    /// them were deficient.
+    pub fn evidence_bundle_counts(&self, run_id: &str, from_tick: Tick, to_tick: Tick) -> SimResult<(i64, i64)> {
+        let counts = self.conn.query_row(
+            "SELECT COUNT(*), COALESCE(SUM(deficient), 0) FROM evidence_bundle
+             WHERE run_id = ?1 AND tick >= ?2 AND tick <= ?3",
+            params![run_id, from_tick as i64, to_tick as i64],
+            |row| Ok((row.get(0)?, row.get(1)?)),
+        )?;
+        Ok(counts)
+    }
+
+    /// The customer's profile, identity and first address as a bundle
+    /// records them; None if the customer has no identity or address on
+    /// file.
+    pub fn kyc_record(&self, run_id: &str, customer_id: &str) -> SimResult<Option<KycRecord>> {
+        let record = self
+            .conn
+            .query_row(
+                "SELECT c.segment, c.risk_band, c.open_tick, i.identity_type, i.ssn_status, i.date_of_birth,
+                        a.city, a.state, a.zip_code, a.address_type, a.verification_status
+                   FROM customer c
+                   JOIN customer_identity i ON i.run_id = c.run_id AND i.customer_id = c.customer_id
+                   JOIN customer_address a ON a.address_id = (
+                        SELECT address_id FROM customer_address
+                         WHERE run_id = c.run_id AND customer_id = c.customer_id
+                         ORDER BY first_seen_tick ASC LIMIT 1)
+                  WHERE c.run_id = ?1 AND c.customer_id = ?2",
+                params![run_id, customer_id],
+                |row| {
+                    Ok(KycRecord {
+                        segment: row.get(0)?,
+                        risk_band: row.get(1)?,
+                        open_tick: row.get::<_, i64>(2)? as Tick,
+                        identity_type: row.get(3)?,
+                        ssn_status: row.get(4)?,
+                        date_of_birth: row.get(5)?,
+                        city: row.get(6)?,
+                        state: row.get(7)?,
+                        zip_code: row.get(8)?,
+                        address_type: row.get(9)?,
+                        verification_status: row.get(10)?,
+                    })
+                },
+            )
+            .optional()?;
+        Ok(record)
+    }
+
+    /// SARs filed on the customer up to `tick`, other than `exclude_sar`,
+    /// oldest first.
+    pub fn prior_sars(&self, run_id: &str, customer_id: &str, tick: Tick, exclude_sar: &str) -> SimResult<Vec<PriorSar>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT sar_id, filing_tick, activity_type, suspicious_amount, filing_status
+               FROM suspicious_activity_report
+              WHERE run_id = ?1 AND subject_type = 'customer' AND subject_id = ?2
+                AND filing_tick <= ?3 AND sar_id <> ?4
+              ORDER BY filing_tick, rowid",
+        )?;
+        let rows = stmt.query_map(params![run_id, customer_id, tick as i64, exclude_sar], |row| {
+            Ok(PriorSar {
+                sar_id: row.get(0)?,
+                filing_tick: row.get::<_, i64>(1)? as Tick,
+                activity_type: row.get(2)?,
+                suspicious_amount: row.get(3)?,
+                filing_status: row.get(4)?,
+            })
+        })?;
+        rows.collect::<Result<Vec<_>, _>>().map_err(Into::into)
+    }
+}
@@ -0,0 +1,84 @@
+//! Store methods for exporting run data (Phase 4.79).
+
+use crate::{
+    error::SimResult,
+    export::{Column, ColumnKind, ExportFormat, TableWriter},
+};
+use rusqlite::{params, types::Value};
+use std::path::Path;
+
+use super::SimStore;
+
+impl SimStore {
+    /// Write the run's rows of `table`, every column in insertion order,
+    /// to `path` in `format`, returning how many rows were written. The
+    /// table must exist and have a `run_id` column. `event_log` also
+    /// takes in the run's archived ticks, ahead of the detailed rows and
+    /// with no `id` or `created_at`, which the archive does not keep.
+    pub fn export_table(&self, run_id: &str, table: &str, format: ExportFormat, path: &Path) -> SimResult<usize> {
+        let columns = self.export_columns(table)?;
+        let names: Vec<String> = columns.iter().map(|c| quote_identifier(&c.name)).collect();
+        let mut stmt = self.conn.prepare(&format!(
+            "SELECT {} FROM {} WHERE run_id = ?1 ORDER BY rowid",
+            names.join(", "),
+            quote_identifier(table)
+        ))?;
+        let mut writer = TableWriter::create(format, path, &columns)?;
+        let mut values: Vec<Value> = Vec::with_capacity(columns.len());
+        let mut written = 0;
+        if table == "event_log" {
+            for tick in self.archived_ticks(run_id)? {
+                for entry in self.archived_events_for_tick(run_id, tick)? {
+                    values.clear();
+                    values.extend(columns.iter().map(|c| match c.name.as_str() {
+                        "run_id" => Value::Text(entry.run_id.clone()),
+                        "tick" => Value::Integer(entry.tick as i64),
+                        "subsystem" => Value::Text(entry.subsystem.clone()),
+                        "event_type" => Value::Text(entry.event_type.clone()),
+                        "payload" => Value::Text(entry.payload.clone()),
+                        _ => Value::Null,
+                    }));
+                    writer.push(&values)?;
+                    written += 1;
+                }
+            }
+        }
+        let mut rows = stmt.query(params![run_id])?;
+        while let Some(row) = rows.next()? {
+            values.clear();
+            for i in 0..columns.len() {
+                values.push(row.get(i)?);
+            }
+            writer.push(&values)?;
+            written += 1;
+        }
+        writer.finish()?;
+        Ok(written)
+    }
+
+    fn export_columns(&self, table: &str) -> SimResult<Vec<Column>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT p.name, p.type FROM sqlite_master m, pragma_table_info(m.name) p
+             WHERE m.type = 'table' AND m.name = ?1 ORDER BY p.cid",
+        )?;
+        let columns: Vec<Column> = stmt
+            .query_map(params![table], |row| {
+                Ok(Column {
+                    name: row.get(0)?,
+                    kind: ColumnKind::from_declared(&row.get::<_, String>(1)?),
+                })
+            })?
+            .collect::<Result<_, _>>()?;
+        if columns.is_empty() {
+            return Err(anyhow::anyhow!("no table '{table}' to export").into());
+        }
+        if !columns.iter().any(|c| c.name == "run_id") {
+            return Err(anyhow::anyhow!("table '{table}' has no run_id column to export by").into());
+        }
+        Ok(columns)
+    }
+}
+
+fn quote_identifier(name: &str) -> String {
+    format!("\"{}\"", name.replace('"', "\"\""))
+}
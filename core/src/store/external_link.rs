@@ -0,0 +1,131 @@
+//! Store methods for external account linking and micro-deposit
+//! verification (Phase 4.11).
+
+use crate::{error::SimResult, types::Tick};
+use rusqlite::{params, OptionalExtension};
+
+/// Row from the `external_account_link` table.
+#[derive(Debug, Clone)]
+pub struct ExternalLinkRow {
+    pub link_id:        String,
+    pub account_id:     String,
+    pub customer_id:    String,
+    pub external_ref:   String,
+    pub purpose:        String,
+    pub method:         String,
+    pub fraudulent:     bool,
+    pub status:         String,
+    pub requested_tick: Tick,
+    pub resolved_tick:  Option<Tick>,
+}
+
+use super::SimStore;
+
+fn link_row_mapper(row: &rusqlite::Row<'_>) -> rusqlite::Result<ExternalLinkRow> {
+    Ok(ExternalLinkRow {
+        link_id:        row.get(0)?,
+        account_id:     row.get(1)?,
+        customer_id:    row.get(2)?,
+        external_ref:   row.get(3)?,
+        purpose:        row.get(4)?,
+        method:         row.get(5)?,
+        fraudulent:     row.get::<_, i64>(6)? != 0,
+        status:         row.get(7)?,
+        requested_tick: row.get::<_, i64>(8)? as u64,
+        resolved_tick:  row.get::<_, Option<i64>>(9)?.map(|t| t as u64),
+    })
+}
+
+const LINK_COLUMNS: &str = "link_id, account_id, customer_id, external_ref, purpose, method,
+     fraudulent, status, requested_tick, resolved_tick";
+
+impl SimStore {
+    pub fn insert_external_link(&self, run_id: &str, row: &ExternalLinkRow) -> SimResult<()> {
+        self.conn.execute(
+            "INSERT INTO external_account_link
+             (run_id, link_id, account_id, customer_id, external_ref, purpose, method,
+              fraudulent, status, requested_tick, resolved_tick)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11)",
+            params![
+                run_id, row.link_id, row.account_id, row.customer_id, row.external_ref,
+                row.purpose, row.method, row.fraudulent as i64, row.status,
+                row.requested_tick as i64, row.resolved_tick.map(|t| t as i64),
+            ],
+        )?;
+        Ok(())
+    }
+
+    /// Micro-deposit links requested on or before `requested_by` that are
+    /// still awaiting confirmation.
+    pub fn pending_external_links(&self, run_id: &str, requested_by: Tick) -> SimResult<Vec<ExternalLinkRow>> {
+        let mut stmt = self.conn.prepare(&format!(
+            "SELECT {LINK_COLUMNS} FROM external_account_link
+             WHERE run_id = ?1 AND status = 'pending' AND requested_tick <= ?2
+             ORDER BY requested_tick, link_id",
+        ))?;
+        let rows = stmt.query_map(params![run_id, requested_by as i64], link_row_mapper)?;
+        rows.collect::<Result<Vec<_>, _>>().map_err(Into::into)
+    }
+
+    pub fn resolve_external_link(&self, run_id: &str, link_id: &str, status: &str, tick: Tick) -> SimResult<()> {
+        self.conn.execute(
+            "UPDATE external_account_link SET status = ?1, resolved_tick = ?2
+             WHERE run_id = ?3 AND link_id = ?4",
+            params![status, tick as i64, run_id, link_id],
+        )?;
+        Ok(())
+    }
+
+    /// The account's most recent link for `purpose`, if any.
+    pub fn latest_external_link(
+        &self,
+        run_id:     &str,
+        account_id: &str,
+        purpose:    &str,
+    ) -> SimResult<Option<ExternalLinkRow>> {
+        let row = self.conn.query_row(
+            &format!(
+                "SELECT {LINK_COLUMNS} FROM external_account_link
+                 WHERE run_id = ?1 AND account_id = ?2 AND purpose = ?3
+                 ORDER BY requested_tick DESC, link_id DESC LIMIT 1",
+            ),
+            params![run_id, account_id, purpose],
+            link_row_mapper,
+        ).optional()?;
+        Ok(row)
+    }
+
+    /// Mule links verified on or after `since`: still inside their drain window.
+    pub fn draining_mule_links(&self, run_id: &str, since: Tick) -> SimResult<Vec<ExternalLinkRow>> {
+        let mut stmt = self.conn.prepare(&format!(
+            "SELECT {LINK_COLUMNS} FROM external_account_link
+             WHERE run_id = ?1 AND purpose = 'mule' AND status = 'verified'
+               AND resolved_tick >= ?2
+             ORDER BY resolved_tick, link_id",
+        ))?;
+        let rows = stmt.query_map(params![run_id, since as i64], link_row_mapper)?;
+        rows.collect::<Result<Vec<_>, _>>().map_err(Into::into)
+    }
+
+    /// Move a customer's payroll to this bank.
+    pub fn switch_direct_deposit(&self, run_id: &str, customer_id: &str, payroll_amount: f64) -> SimResult<()> {
+        self.conn.execute(
+            "UPDATE customer SET has_payroll = 1, payroll_amount = ?1
+             WHERE run_id = ?2 AND customer_id = ?3",
+            params![payroll_amount, run_id, customer_id],
+        )?;
+        Ok(())
+    }
+
+    // ── Test / summary helpers ────────────────────────────────────────
+
+    /// All external account links for a run, oldest first (for tests).
+    pub fn external_links(&self, run_id: &str) -> SimResult<Vec<ExternalLinkRow>> {
+        let mut stmt = self.conn.prepare(&format!(
+            "SELECT {LINK_COLUMNS} FROM external_account_link WHERE run_id = ?1
+             ORDER BY requested_tick, link_id",
+        ))?;
+        let rows = stmt.query_map(params![run_id], link_row_mapper)?;
+        rows.collect::<Result<Vec<_>, _>>().map_err(Into::into)
+    }
+}
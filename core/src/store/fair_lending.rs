@@ -0,0 +1,295 @@
+//! Store methods for zip demographics and fair lending analytics (Phase 4.4).
+
+use crate::{error::SimResult, types::Tick};
+use rusqlite::{params, OptionalExtension};
+
+/// Row from the `zip_demographics` table.
+#[derive(Debug, Clone)]
+pub struct ZipDemographicsRow {
+    pub zip_code:          String,
+    pub state_code:        String,
+    pub population:        i64,
+    pub median_income:     f64,
+    pub minority_share:    f64,
+    pub lmi_flag:          bool,
+    pub majority_minority: bool,
+}
+
+/// Outcome counts for one geography group over a review window.
+#[derive(Debug, Clone, Default)]
+pub struct FairLendingGroupMetrics {
+    pub customers:        i64,
+    pub overdraft_fees:   f64,
+    pub closures:         i64,
+    pub offers:           i64,
+}
+
+/// Row from the `fair_lending_finding` table.
+#[derive(Debug, Clone)]
+pub struct FairLendingFindingRow {
+    pub finding_id:        String,
+    pub tick:              Tick,
+    pub metric:            String,
+    pub adverse_ratio:     f64,
+    pub severity:          String,
+    pub status:            String,
+    pub response_due_tick: Tick,
+}
+
+use super::SimStore;
+
+fn finding_row_mapper(row: &rusqlite::Row<'_>) -> rusqlite::Result<FairLendingFindingRow> {
+    Ok(FairLendingFindingRow {
+        finding_id:        row.get(0)?,
+        tick:              row.get::<_, i64>(1)? as u64,
+        metric:            row.get(2)?,
+        adverse_ratio:     row.get(3)?,
+        severity:          row.get(4)?,
+        status:            row.get(5)?,
+        response_due_tick: row.get::<_, i64>(6)? as u64,
+    })
+}
+
+impl SimStore {
+    /// Distinct (zip, state) pairs across the customer address book, in zip order.
+    pub fn customer_zip_codes(&self, run_id: &str) -> SimResult<Vec<(String, String)>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT zip_code, MIN(state) FROM customer_address
+             WHERE run_id = ?1 GROUP BY zip_code ORDER BY zip_code",
+        )?;
+        let rows = stmt.query_map(params![run_id], |row| Ok((row.get(0)?, row.get(1)?)))?;
+        rows.collect::<Result<Vec<_>, _>>().map_err(Into::into)
+    }
+
+    /// State median household income from the `state_config` reference table.
+    pub fn state_median_income(&self, state_code: &str) -> SimResult<Option<f64>> {
+        let income = self.conn.query_row(
+            "SELECT median_income FROM state_config WHERE state_code = ?1",
+            params![state_code],
+            |row| row.get(0),
+        ).optional()?;
+        Ok(income)
+    }
+
+    pub fn insert_zip_demographics(&self, run_id: &str, row: &ZipDemographicsRow) -> SimResult<()> {
+        self.conn.execute(
+            "INSERT OR REPLACE INTO zip_demographics
+             (run_id, zip_code, state_code, population, median_income,
+              minority_share, lmi_flag, majority_minority)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+            params![
+                run_id, row.zip_code, row.state_code, row.population, row.median_income,
+                row.minority_share, row.lmi_flag as i64, row.majority_minority as i64,
+            ],
+        )?;
+        Ok(())
+    }
+
+    pub fn get_zip_demographics(
+        &self,
+        run_id:   &str,
+        zip_code: &str,
+    ) -> SimResult<Option<ZipDemographicsRow>> {
+        let row = self.conn.query_row(
+            "SELECT zip_code, state_code, population, median_income,
+                    minority_share, lmi_flag, majority_minority
+             FROM zip_demographics WHERE run_id = ?1 AND zip_code = ?2",
+            params![run_id, zip_code],
+            |row| {
+                Ok(ZipDemographicsRow {
+                    zip_code:          row.get(0)?,
+                    state_code:        row.get(1)?,
+                    population:        row.get(2)?,
+                    median_income:     row.get(3)?,
+                    minority_share:    row.get(4)?,
+                    lmi_flag:          row.get::<_, i64>(5)? != 0,
+                    majority_minority: row.get::<_, i64>(6)? != 0,
+                })
+            },
+        ).optional()?;
+        Ok(row)
+    }
+
+    /// Fee, closure and offer outcomes over (start_tick, end_tick] for
+    /// customers living in majority-minority zips (`protected = true`) or
+    /// in all other zips (`protected = false`).
+    pub fn fair_lending_group_metrics(
+        &self,
+        run_id:     &str,
+        start_tick: Tick,
+        end_tick:   Tick,
+        protected:  bool,
+    ) -> SimResult<FairLendingGroupMetrics> {
+        let metrics = self.conn.query_row(
+            "WITH grp AS (
+                 SELECT DISTINCT c.customer_id FROM customer c
+                 JOIN customer_address ca ON ca.run_id = c.run_id AND ca.customer_id = c.customer_id
+                 JOIN zip_demographics z ON z.run_id = ca.run_id AND z.zip_code = ca.zip_code
+                 WHERE c.run_id = ?1 AND z.majority_minority = ?4
+                   AND (c.status = 'active' OR c.close_tick > ?2)
+             )
+             SELECT
+                 (SELECT COUNT(*) FROM grp),
+                 (SELECT COALESCE(SUM(t.amount), 0.0) FROM transactions t
+                  JOIN account a ON a.run_id = t.run_id AND a.account_id = t.account_id
+                  WHERE t.run_id = ?1 AND t.category = 'overdraft_fee'
+                    AND t.tick > ?2 AND t.tick <= ?3
+                    AND a.customer_id IN (SELECT customer_id FROM grp)),
+                 (SELECT COUNT(*) FROM customer c
+                  WHERE c.run_id = ?1 AND c.close_tick > ?2 AND c.close_tick <= ?3
+                    AND c.customer_id IN (SELECT customer_id FROM grp)),
+                 (SELECT COUNT(*) FROM customer_offer o
+                  WHERE o.run_id = ?1 AND o.tick_offered > ?2 AND o.tick_offered <= ?3
+                    AND o.customer_id IN (SELECT customer_id FROM grp))",
+            params![run_id, start_tick as i64, end_tick as i64, protected as i64],
+            |row| {
+                Ok(FairLendingGroupMetrics {
+                    customers:      row.get(0)?,
+                    overdraft_fees: row.get(1)?,
+                    closures:       row.get(2)?,
+                    offers:         row.get(3)?,
+                })
+            },
+        )?;
+        Ok(metrics)
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn insert_fair_lending_review(
+        &self,
+        run_id:          &str,
+        tick:            Tick,
+        metric:          &str,
+        protected_value: f64,
+        control_value:   f64,
+        adverse_ratio:   f64,
+        flagged:         bool,
+    ) -> SimResult<()> {
+        self.conn.execute(
+            "INSERT OR REPLACE INTO fair_lending_review
+             (run_id, tick, metric, protected_value, control_value, adverse_ratio, flagged)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+            params![
+                run_id, tick as i64, metric, protected_value, control_value,
+                adverse_ratio, flagged as i64,
+            ],
+        )?;
+        Ok(())
+    }
+
+    pub fn insert_fair_lending_finding(&self, run_id: &str, row: &FairLendingFindingRow) -> SimResult<()> {
+        self.conn.execute(
+            "INSERT INTO fair_lending_finding
+             (finding_id, run_id, tick, metric, adverse_ratio, severity, status, response_due_tick)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+            params![
+                row.finding_id, run_id, row.tick as i64, row.metric, row.adverse_ratio,
+                row.severity, row.status, row.response_due_tick as i64,
+            ],
+        )?;
+        Ok(())
+    }
+
+    pub fn get_fair_lending_finding(
+        &self,
+        run_id:     &str,
+        finding_id: &str,
+    ) -> SimResult<Option<FairLendingFindingRow>> {
+        let row = self.conn.query_row(
+            "SELECT finding_id, tick, metric, adverse_ratio, severity, status, response_due_tick
+             FROM fair_lending_finding WHERE run_id = ?1 AND finding_id = ?2",
+            params![run_id, finding_id],
+            finding_row_mapper,
+        ).optional()?;
+        Ok(row)
+    }
+
+    /// Findings still awaiting a player response, oldest first.
+    pub fn open_fair_lending_findings(&self, run_id: &str) -> SimResult<Vec<FairLendingFindingRow>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT finding_id, tick, metric, adverse_ratio, severity, status, response_due_tick
+             FROM fair_lending_finding WHERE run_id = ?1 AND status = 'open'
+             ORDER BY tick, finding_id",
+        )?;
+        let rows = stmt.query_map(params![run_id], finding_row_mapper)?;
+        rows.collect::<Result<Vec<_>, _>>().map_err(Into::into)
+    }
+
+    /// Set a finding's status; `responded` stamps the response tick.
+    pub fn set_fair_lending_finding_status(
+        &self,
+        run_id:     &str,
+        finding_id: &str,
+        status:     &str,
+        tick:       Tick,
+        responded:  bool,
+    ) -> SimResult<()> {
+        self.conn.execute(
+            "UPDATE fair_lending_finding
+             SET status = ?1,
+                 responded_tick = CASE WHEN ?2 = 1 THEN ?3 ELSE responded_tick END
+             WHERE run_id = ?4 AND finding_id = ?5",
+            params![status, responded as i64, tick as i64, run_id, finding_id],
+        )?;
+        Ok(())
+    }
+
+    /// Findings remediated over [start_tick, end_tick].
+    pub fn fair_lending_remediations_between(
+        &self,
+        run_id:     &str,
+        start_tick: Tick,
+        end_tick:   Tick,
+    ) -> SimResult<i64> {
+        let count: i64 = self.conn.query_row(
+            "SELECT COUNT(*) FROM fair_lending_finding
+             WHERE run_id = ?1 AND status = 'remediated'
+               AND responded_tick >= ?2 AND responded_tick <= ?3",
+            params![run_id, start_tick as i64, end_tick as i64],
+            |row| row.get(0),
+        )?;
+        Ok(count)
+    }
+
+    // ── Test / summary helpers ────────────────────────────────────────
+
+    /// Number of zips with demographics (for tests).
+    pub fn zip_demographics_count(&self, run_id: &str) -> SimResult<i64> {
+        let count: i64 = self.conn.query_row(
+            "SELECT COUNT(*) FROM zip_demographics WHERE run_id = ?1",
+            params![run_id],
+            |row| row.get(0),
+        )?;
+        Ok(count)
+    }
+
+    /// Zips in the customer book on either side of the majority-minority split (for tests).
+    pub fn zip_codes_by_group(&self, run_id: &str, majority_minority: bool) -> SimResult<Vec<String>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT zip_code FROM zip_demographics
+             WHERE run_id = ?1 AND majority_minority = ?2 ORDER BY zip_code",
+        )?;
+        let rows = stmt.query_map(params![run_id, majority_minority as i64], |row| row.get(0))?;
+        rows.collect::<Result<Vec<_>, _>>().map_err(Into::into)
+    }
+
+    /// Number of fair lending review rows (for tests).
+    pub fn fair_lending_review_count(&self, run_id: &str) -> SimResult<i64> {
+        let count: i64 = self.conn.query_row(
+            "SELECT COUNT(*) FROM fair_lending_review WHERE run_id = ?1",
+            params![run_id],
+            |row| row.get(0),
+        )?;
+        Ok(count)
+    }
+
+    /// Number of findings with the given status (for tests).
+    pub fn fair_lending_finding_count(&self, run_id: &str, status: &str) -> SimResult<i64> {
+        let count: i64 = self.conn.query_row(
+            "SELECT COUNT(*) FROM fair_lending_finding WHERE run_id = ?1 AND status = ?2",
+            params![run_id, status],
+            |row| row.get(0),
+        )?;
+        Ok(count)
+    }
+}
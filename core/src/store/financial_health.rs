@@ -0,0 +1,231 @@
+//! Store methods for financial health and credit lines (Phase 4.34).
+
+use crate::{error::SimResult, types::Tick};
+use rusqlite::{params, OptionalExtension};
+
+use super::SimStore;
+
+/// Row from the `customer_health` table.
+#[derive(Debug, Clone)]
+pub struct CustomerHealthRow {
+    pub customer_id:        String,
+    pub tick:               Tick,
+    pub score:              f64,
+    pub balance_volatility: f64,
+    pub overdraft_count:    i64,
+    pub payroll_stability:  f64,
+    /// Gambling and crypto share of merchant spend (Phase 4.35).
+    pub high_risk_share:    f64,
+}
+
+/// Row from the `credit_line` table.
+#[derive(Debug, Clone)]
+pub struct CreditLineRow {
+    pub customer_id:    String,
+    pub offer_id:       String,
+    pub tick_opened:    Tick,
+    pub credit_limit:   f64,
+    pub health_at_open: f64,
+    pub status:         String,
+    pub tick_defaulted: Option<Tick>,
+    pub loss_amount:    f64,
+}
+
+fn credit_line_row_mapper(row: &rusqlite::Row) -> rusqlite::Result<CreditLineRow> {
+    Ok(CreditLineRow {
+        customer_id:    row.get(0)?,
+        offer_id:       row.get(1)?,
+        tick_opened:    row.get::<_, i64>(2)? as Tick,
+        credit_limit:   row.get(3)?,
+        health_at_open: row.get(4)?,
+        status:         row.get(5)?,
+        tick_defaulted: row.get::<_, Option<i64>>(6)?.map(|t| t as Tick),
+        loss_amount:    row.get(7)?,
+    })
+}
+
+impl SimStore {
+    /// Net flow (credits less debits) across the customer's accounts for
+    /// each tick in the inclusive window that had any transactions.
+    pub fn customer_daily_net_flows(
+        &self,
+        run_id:      &str,
+        customer_id: &str,
+        tick_start:  Tick,
+        tick_end:    Tick,
+    ) -> SimResult<Vec<(Tick, f64)>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT t.tick, SUM(CASE WHEN t.direction = 'credit' THEN t.amount ELSE -t.amount END)
+             FROM transactions t
+             JOIN account a ON a.account_id = t.account_id AND a.run_id = t.run_id
+             WHERE t.run_id = ?1 AND a.customer_id = ?2 AND t.tick >= ?3 AND t.tick <= ?4
+             GROUP BY t.tick ORDER BY t.tick",
+        )?;
+        let rows = stmt.query_map(
+            params![run_id, customer_id, tick_start as i64, tick_end as i64],
+            |row| Ok((row.get::<_, i64>(0)? as Tick, row.get(1)?)),
+        )?;
+        rows.collect::<Result<Vec<_>, _>>().map_err(Into::into)
+    }
+
+    /// Transactions of a category across the customer's accounts in the
+    /// inclusive window.
+    pub fn customer_transaction_count(
+        &self,
+        run_id:      &str,
+        customer_id: &str,
+        category:    &str,
+        tick_start:  Tick,
+        tick_end:    Tick,
+    ) -> SimResult<i64> {
+        let count: i64 = self.conn.query_row(
+            "SELECT COUNT(*) FROM transactions t
+             JOIN account a ON a.account_id = t.account_id AND a.run_id = t.run_id
+             WHERE t.run_id = ?1 AND a.customer_id = ?2 AND t.category = ?3
+               AND t.tick >= ?4 AND t.tick <= ?5",
+            params![run_id, customer_id, category, tick_start as i64, tick_end as i64],
+            |row| row.get(0),
+        )?;
+        Ok(count)
+    }
+
+    pub fn insert_customer_health(&self, run_id: &str, row: &CustomerHealthRow) -> SimResult<()> {
+        self.conn.execute(
+            "INSERT OR REPLACE INTO customer_health
+             (run_id, customer_id, tick, score, balance_volatility, overdraft_count,
+              payroll_stability, high_risk_share)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+            params![
+                run_id, row.customer_id, row.tick as i64, row.score,
+                row.balance_volatility, row.overdraft_count, row.payroll_stability,
+                row.high_risk_share,
+            ],
+        )?;
+        Ok(())
+    }
+
+    /// The customer's most recent health score, if they have been scored.
+    pub fn latest_customer_health(&self, run_id: &str, customer_id: &str) -> SimResult<Option<f64>> {
+        let score = self
+            .conn
+            .query_row(
+                "SELECT score FROM customer_health
+                 WHERE run_id = ?1 AND customer_id = ?2 ORDER BY tick DESC LIMIT 1",
+                params![run_id, customer_id],
+                |row| row.get(0),
+            )
+            .optional()?;
+        Ok(score)
+    }
+
+    pub fn insert_credit_line(&self, run_id: &str, row: &CreditLineRow) -> SimResult<()> {
+        self.conn.execute(
+            "INSERT OR IGNORE INTO credit_line
+             (run_id, customer_id, offer_id, tick_opened, credit_limit, health_at_open, status)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+            params![
+                run_id, row.customer_id, row.offer_id, row.tick_opened as i64,
+                row.credit_limit, row.health_at_open, row.status,
+            ],
+        )?;
+        Ok(())
+    }
+
+    pub fn open_credit_lines(&self, run_id: &str) -> SimResult<Vec<CreditLineRow>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT customer_id, offer_id, tick_opened, credit_limit, health_at_open,
+                    status, tick_defaulted, loss_amount
+             FROM credit_line WHERE run_id = ?1 AND status = 'open'
+             ORDER BY tick_opened, customer_id, offer_id",
+        )?;
+        let rows = stmt.query_map(params![run_id], credit_line_row_mapper)?;
+        rows.collect::<Result<Vec<_>, _>>().map_err(Into::into)
+    }
+
+    pub fn mark_credit_line_defaulted(
+        &self,
+        run_id:      &str,
+        customer_id: &str,
+        offer_id:    &str,
+        tick:        Tick,
+        loss_amount: f64,
+    ) -> SimResult<()> {
+        self.conn.execute(
+            "UPDATE credit_line SET status = 'defaulted', tick_defaulted = ?4, loss_amount = ?5
+             WHERE run_id = ?1 AND customer_id = ?2 AND offer_id = ?3",
+            params![run_id, customer_id, offer_id, tick as i64, loss_amount],
+        )?;
+        Ok(())
+    }
+
+    /// Credit line losses booked in the inclusive tick window.
+    pub fn sum_credit_losses(&self, run_id: &str, start_tick: Tick, end_tick: Tick) -> SimResult<f64> {
+        let sum: f64 = self.conn.query_row(
+            "SELECT COALESCE(SUM(loss_amount), 0.0) FROM credit_line
+             WHERE run_id = ?1 AND status = 'defaulted'
+               AND tick_defaulted >= ?2 AND tick_defaulted <= ?3",
+            params![run_id, start_tick as i64, end_tick as i64],
+            |row| row.get(0),
+        )?;
+        Ok(sum)
+    }
+
+    pub fn insert_credit_health_policy(&self, run_id: &str, tick: Tick, min_score: f64) -> SimResult<()> {
+        self.conn.execute(
+            "INSERT OR REPLACE INTO credit_health_policy (run_id, tick, min_score)
+             VALUES (?1, ?2, ?3)",
+            params![run_id, tick as i64, min_score],
+        )?;
+        Ok(())
+    }
+
+    /// The player's latest minimum health score for credit offers, if
+    /// they have set one.
+    pub fn credit_health_policy(&self, run_id: &str) -> SimResult<Option<f64>> {
+        let min_score = self
+            .conn
+            .query_row(
+                "SELECT min_score FROM credit_health_policy
+                 WHERE run_id = ?1 ORDER BY tick DESC LIMIT 1",
+                params![run_id],
+                |row| row.get(0),
+            )
+            .optional()?;
+        Ok(min_score)
+    }
+
+    // ── Test / summary helpers ────────────────────────────────────────
+
+    /// Every credit line for a run (for tests).
+    pub fn credit_lines(&self, run_id: &str) -> SimResult<Vec<CreditLineRow>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT customer_id, offer_id, tick_opened, credit_limit, health_at_open,
+                    status, tick_defaulted, loss_amount
+             FROM credit_line WHERE run_id = ?1
+             ORDER BY tick_opened, customer_id, offer_id",
+        )?;
+        let rows = stmt.query_map(params![run_id], credit_line_row_mapper)?;
+        rows.collect::<Result<Vec<_>, _>>().map_err(Into::into)
+    }
+
+    /// Every health score computed at a tick (for tests).
+    pub fn customer_health_at(&self, run_id: &str, tick: Tick) -> SimResult<Vec<CustomerHealthRow>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT customer_id, tick, score, balance_volatility, overdraft_count, payroll_stability,
+                    high_risk_share
+             FROM customer_health WHERE run_id = ?1 AND tick = ?2 ORDER BY customer_id",
+        )?;
+        let rows = stmt.query_map(params![run_id, tick as i64], |row| {
+            Ok(CustomerHealthRow {
+                customer_id:        row.get(0)?,
+                tick:               row.get::<_, i64>(1)? as Tick,
+                score:              row.get(2)?,
+                balance_volatility: row.get(3)?,
+                overdraft_count:    row.get(4)?,
+                payroll_stability:  row.get(5)?,
+                high_risk_share:    row.get(6)?,
+            })
+        })?;
+        rows.collect::<Result<Vec<_>, _>>().map_err(Into::into)
+    }
+}
@@ -0,0 +1,60 @@
+//! Table checksums for golden-run fixtures (Phase 4.49).
+
+use super::SimStore;
+use crate::{error::SimResult, golden::TableChecksum};
+use rusqlite::{params, types::ValueRef};
+use sha2::{Digest, Sha256};
+
+impl SimStore {
+    /// Row count and SHA-256 over one run's rows of `table`, leaving out
+    /// `run_id` and the `excluded` columns. Rows are hashed in the order
+    /// of their values, so insertion order and rowids do not matter.
+    pub fn table_checksum(&self, run_id: &str, table: &str, excluded: &[&str]) -> SimResult<TableChecksum> {
+        let columns = self
+            .conn
+            .prepare(&format!("SELECT name FROM pragma_table_info('{table}') ORDER BY cid"))?
+            .query_map([], |row| row.get::<_, String>(0))?
+            .collect::<Result<Vec<_>, _>>()?
+            .into_iter()
+            .filter(|c| c != "run_id" && !excluded.contains(&c.as_str()))
+            .collect::<Vec<_>>();
+        if columns.is_empty() {
+            return Err(anyhow::anyhow!("no table {table}").into());
+        }
+        let list = columns.join(", ");
+        let order = (1..=columns.len()).map(|i| i.to_string()).collect::<Vec<_>>().join(", ");
+        let mut stmt = self
+            .conn
+            .prepare(&format!("SELECT {list} FROM {table} WHERE run_id = ?1 ORDER BY {order}"))?;
+        let mut rows = stmt.query(params![run_id])?;
+
+        let mut hasher = Sha256::new();
+        hasher.update(list.as_bytes());
+        let mut count = 0;
+        while let Some(row) = rows.next()? {
+            count += 1;
+            for i in 0..columns.len() {
+                match row.get_ref(i)? {
+                    ValueRef::Null => hasher.update([0]),
+                    ValueRef::Integer(v) => {
+                        hasher.update([1]);
+                        hasher.update(v.to_le_bytes());
+                    }
+                    ValueRef::Real(v) => {
+                        hasher.update([2]);
+                        hasher.update(v.to_bits().to_le_bytes());
+                    }
+                    ValueRef::Text(v) | ValueRef::Blob(v) => {
+                        hasher.update([3]);
+                        hasher.update((v.len() as u64).to_le_bytes());
+                        hasher.update(v);
+                    }
+                }
+            }
+        }
+        Ok(TableChecksum {
+            rows: count,
+            sha256: hasher.finalize().iter().map(|b| format!("{b:02x}")).collect(),
+        })
+    }
+}
@@ -0,0 +1,120 @@
+//! Store methods for the customer hardship program (Phase 4.30).
+
+use crate::{error::SimResult, types::Tick};
+use rusqlite::params;
+use std::collections::HashSet;
+
+use super::SimStore;
+
+/// Row from the `hardship_enrollment` table.
+#[derive(Debug, Clone)]
+pub struct HardshipEnrollmentRow {
+    pub customer_id:   String,
+    pub tick_enrolled: Tick,
+    pub tick_expires:  Tick,
+    pub life_event:    String,
+}
+
+/// Row from the `hardship_fee_waiver` table.
+#[derive(Debug, Clone)]
+pub struct HardshipWaiverRow {
+    pub tick:        Tick,
+    pub customer_id: String,
+    pub account_id:  String,
+    pub fee_type:    String,
+    pub amount:      f64,
+}
+
+impl SimStore {
+    pub fn insert_hardship_enrollment(&self, run_id: &str, row: &HardshipEnrollmentRow) -> SimResult<()> {
+        self.conn.execute(
+            "INSERT INTO hardship_enrollment (run_id, customer_id, tick_enrolled, tick_expires, life_event)
+             VALUES (?1, ?2, ?3, ?4, ?5)",
+            params![
+                run_id, row.customer_id, row.tick_enrolled as i64,
+                row.tick_expires as i64, row.life_event,
+            ],
+        )?;
+        Ok(())
+    }
+
+    /// Customers enrolled at `tick`.
+    pub fn hardship_customers(&self, run_id: &str, tick: Tick) -> SimResult<HashSet<String>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT DISTINCT customer_id FROM hardship_enrollment
+             WHERE run_id = ?1 AND tick_enrolled <= ?2 AND tick_expires > ?2",
+        )?;
+        let rows = stmt.query_map(params![run_id, tick as i64], |row| row.get(0))?;
+        rows.collect::<Result<HashSet<_>, _>>().map_err(Into::into)
+    }
+
+    pub fn in_hardship_program(&self, run_id: &str, customer_id: &str, tick: Tick) -> SimResult<bool> {
+        let enrolled: bool = self.conn.query_row(
+            "SELECT COUNT(*) > 0 FROM hardship_enrollment
+             WHERE run_id = ?1 AND customer_id = ?2 AND tick_enrolled <= ?3 AND tick_expires > ?3",
+            params![run_id, customer_id, tick as i64],
+            |row| row.get(0),
+        )?;
+        Ok(enrolled)
+    }
+
+    /// Customers in `segment` enrolled at `tick`.
+    pub fn segment_hardship_active(&self, run_id: &str, segment: &str, tick: Tick) -> SimResult<i64> {
+        let count: i64 = self.conn.query_row(
+            "SELECT COUNT(DISTINCT h.customer_id)
+             FROM hardship_enrollment h
+             JOIN customer c ON h.customer_id = c.customer_id AND h.run_id = c.run_id
+             WHERE h.run_id = ?1 AND c.segment = ?2 AND c.status = 'active'
+               AND h.tick_enrolled <= ?3 AND h.tick_expires > ?3",
+            params![run_id, segment, tick as i64],
+            |row| row.get(0),
+        )?;
+        Ok(count)
+    }
+
+    pub fn insert_hardship_waiver(&self, run_id: &str, row: &HardshipWaiverRow) -> SimResult<()> {
+        self.conn.execute(
+            "INSERT INTO hardship_fee_waiver (run_id, tick, customer_id, account_id, fee_type, amount)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+            params![run_id, row.tick as i64, row.customer_id, row.account_id, row.fee_type, row.amount],
+        )?;
+        Ok(())
+    }
+
+    // ── Test / summary helpers ────────────────────────────────────────
+
+    /// All enrollments for a run, oldest first (for tests).
+    pub fn hardship_enrollments(&self, run_id: &str) -> SimResult<Vec<HardshipEnrollmentRow>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT customer_id, tick_enrolled, tick_expires, life_event
+             FROM hardship_enrollment WHERE run_id = ?1 ORDER BY tick_enrolled, customer_id",
+        )?;
+        let rows = stmt.query_map(params![run_id], |row| {
+            Ok(HardshipEnrollmentRow {
+                customer_id:   row.get(0)?,
+                tick_enrolled: row.get::<_, i64>(1)? as Tick,
+                tick_expires:  row.get::<_, i64>(2)? as Tick,
+                life_event:    row.get(3)?,
+            })
+        })?;
+        rows.collect::<Result<Vec<_>, _>>().map_err(Into::into)
+    }
+
+    /// All fee waivers for a run, oldest first (for tests).
+    pub fn hardship_waivers(&self, run_id: &str) -> SimResult<Vec<HardshipWaiverRow>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT tick, customer_id, account_id, fee_type, amount
+             FROM hardship_fee_waiver WHERE run_id = ?1 ORDER BY waiver_id",
+        )?;
+        let rows = stmt.query_map(params![run_id], |row| {
+            Ok(HardshipWaiverRow {
+                tick:        row.get::<_, i64>(0)? as Tick,
+                customer_id: row.get(1)?,
+                account_id:  row.get(2)?,
+                fee_type:    row.get(3)?,
+                amount:      row.get(4)?,
+            })
+        })?;
+        rows.collect::<Result<Vec<_>, _>>().map_err(Into::into)
+    }
+}
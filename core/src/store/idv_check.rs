@@ -0,0 +1,113 @@
+//! Store methods for identity verification vendor checks (Phase 4.15).
+
+use crate::{error::SimResult, types::Tick};
+use rusqlite::{params, OptionalExtension};
+
+/// Row from the `idv_check` table.
+#[derive(Debug, Clone)]
+pub struct IdvCheckRow {
+    pub customer_id:   String,
+    pub tick:          Tick,
+    pub vendor:        String,
+    pub name_match:    String,
+    pub ssn_match:     String,
+    pub dob_match:     String,
+    pub address_match: String,
+    pub decision:      String,
+    pub cost:          f64,
+}
+
+impl IdvCheckRow {
+    /// The four element match codes, in name/SSN/DOB/address order.
+    pub fn match_codes(&self) -> [&str; 4] {
+        [&self.name_match, &self.ssn_match, &self.dob_match, &self.address_match]
+    }
+}
+
+use super::SimStore;
+
+fn idv_row_mapper(row: &rusqlite::Row<'_>) -> rusqlite::Result<IdvCheckRow> {
+    Ok(IdvCheckRow {
+        customer_id:   row.get(0)?,
+        tick:          row.get::<_, i64>(1)? as u64,
+        vendor:        row.get(2)?,
+        name_match:    row.get(3)?,
+        ssn_match:     row.get(4)?,
+        dob_match:     row.get(5)?,
+        address_match: row.get(6)?,
+        decision:      row.get(7)?,
+        cost:          row.get(8)?,
+    })
+}
+
+const IDV_COLUMNS: &str =
+    "customer_id, tick, vendor, name_match, ssn_match, dob_match, address_match, decision, cost";
+
+impl SimStore {
+    pub fn insert_idv_check(&self, run_id: &str, row: &IdvCheckRow) -> SimResult<()> {
+        self.conn.execute(
+            "INSERT INTO idv_check
+             (run_id, customer_id, tick, vendor, name_match, ssn_match, dob_match,
+              address_match, decision, cost)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)",
+            params![
+                run_id, row.customer_id, row.tick as i64, row.vendor, row.name_match,
+                row.ssn_match, row.dob_match, row.address_match, row.decision, row.cost,
+            ],
+        )?;
+        Ok(())
+    }
+
+    /// Mark an applicant who failed verification as declined and close the
+    /// account opened for them.
+    pub fn decline_applicant(&self, run_id: &str, customer_id: &str, tick: Tick) -> SimResult<()> {
+        self.conn.execute(
+            "UPDATE customer SET status = 'declined', close_tick = ?1
+             WHERE run_id = ?2 AND customer_id = ?3",
+            params![tick as i64, run_id, customer_id],
+        )?;
+        self.conn.execute(
+            "UPDATE account SET status = 'closed', close_tick = ?1
+             WHERE run_id = ?2 AND customer_id = ?3 AND status = 'open'",
+            params![tick as i64, run_id, customer_id],
+        )?;
+        Ok(())
+    }
+
+    /// The customer's onboarding IDV check, if one was run.
+    pub fn idv_check(&self, run_id: &str, customer_id: &str) -> SimResult<Option<IdvCheckRow>> {
+        let row = self.conn.query_row(
+            &format!("SELECT {IDV_COLUMNS} FROM idv_check WHERE run_id = ?1 AND customer_id = ?2"),
+            params![run_id, customer_id],
+            idv_row_mapper,
+        ).optional()?;
+        Ok(row)
+    }
+
+    /// Vendor fees for checks run in the window, inclusive.
+    pub fn idv_cost_between(
+        &self,
+        run_id:     &str,
+        start_tick: Tick,
+        end_tick:   Tick,
+    ) -> SimResult<f64> {
+        let total: f64 = self.conn.query_row(
+            "SELECT COALESCE(SUM(cost), 0.0) FROM idv_check
+             WHERE run_id = ?1 AND tick >= ?2 AND tick <= ?3",
+            params![run_id, start_tick as i64, end_tick as i64],
+            |row| row.get(0),
+        )?;
+        Ok(total)
+    }
+
+    // ── Test / summary helpers ────────────────────────────────────────
+
+    /// All IDV checks for a run (for tests).
+    pub fn idv_checks(&self, run_id: &str) -> SimResult<Vec<IdvCheckRow>> {
+        let mut stmt = self.conn.prepare(&format!(
+            "SELECT {IDV_COLUMNS} FROM idv_check WHERE run_id = ?1 ORDER BY customer_id",
+        ))?;
+        let rows = stmt.query_map(params![run_id], idv_row_mapper)?;
+        rows.collect::<Result<Vec<_>, _>>().map_err(Into::into)
+    }
+}
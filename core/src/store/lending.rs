@@ -0,0 +1,353 @@
+//! Store methods for lending (Phase 4.82).
+
+use crate::{error::SimResult, types::Tick};
+use rusqlite::{params, Row};
+
+use super::SimStore;
+
+/// An active customer lending can underwrite.
+#[derive(Debug, Clone)]
+pub struct LoanApplicantRow {
+    pub customer_id:   String,
+    /// Their first open account, which the loan pays into and is paid from.
+    pub account_id:    String,
+    pub credit_score:  i64,
+    pub annual_income: f64,
+}
+
+/// Row from the `loan` table.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LoanRow {
+    pub loan_id:         String,
+    pub customer_id:     String,
+    pub account_id:      String,
+    pub product_id:      String,
+    /// 'installment' | 'line'
+    pub kind:            String,
+    pub tick_originated: Tick,
+    /// Amount lent, or the line's limit.
+    pub amount:          f64,
+    pub annual_rate:     f64,
+    pub credit_score:    i64,
+    /// Annualised first payment over annual income, as underwritten.
+    pub dti:             f64,
+    /// Principal outstanding.
+    pub balance:         f64,
+    pub interest_paid:   f64,
+    /// 'open' | 'paid_off' | 'charged_off'
+    pub status:          String,
+    /// 'current' | '1_29' | '30_59' | '60_89' | '90_plus'
+    pub bucket:          String,
+    pub tick_closed:     Option<Tick>,
+    pub charge_off_loss: f64,
+}
+
+/// Row from the `loan_payment` table.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LoanPaymentRow {
+    pub loan_id:    String,
+    pub payment_no: i64,
+    pub due_tick:   Tick,
+    pub amount_due: f64,
+    pub principal:  f64,
+    pub interest:   f64,
+    /// 'scheduled' | 'paid' | 'missed'
+    pub status:     String,
+    pub tick_paid:  Option<Tick>,
+}
+
+/// Row from the `delinquency_status` table.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DelinquencyStatusRow {
+    pub loan_id:         String,
+    pub tick:            Tick,
+    pub days_past_due:   i64,
+    pub bucket:          String,
+    pub past_due_amount: f64,
+}
+
+const LOAN_COLUMNS: &str = "loan_id, customer_id, account_id, product_id, kind, tick_originated, amount, annual_rate,
+     credit_score, dti, balance, interest_paid, status, bucket, tick_closed, charge_off_loss";
+
+fn loan_from_row(row: &Row) -> rusqlite::Result<LoanRow> {
+    Ok(LoanRow {
+        loan_id:         row.get(0)?,
+        customer_id:     row.get(1)?,
+        account_id:      row.get(2)?,
+        product_id:      row.get(3)?,
+        kind:            row.get(4)?,
+        tick_originated: row.get::<_, i64>(5)? as Tick,
+        amount:          row.get(6)?,
+        annual_rate:     row.get(7)?,
+        credit_score:    row.get(8)?,
+        dti:             row.get(9)?,
+        balance:         row.get(10)?,
+        interest_paid:   row.get(11)?,
+        status:          row.get(12)?,
+        bucket:          row.get(13)?,
+        tick_closed:     row.get::<_, Option<i64>>(14)?.map(|t| t as Tick),
+        charge_off_loss: row.get(15)?,
+    })
+}
+
+const PAYMENT_COLUMNS: &str = "loan_id, payment_no, due_tick, amount_due, principal, interest, status, tick_paid";
+
+fn payment_from_row(row: &Row) -> rusqlite::Result<LoanPaymentRow> {
+    Ok(LoanPaymentRow {
+        loan_id:    row.get(0)?,
+        payment_no: row.get(1)?,
+        due_tick:   row.get::<_, i64>(2)? as Tick,
+        amount_due: row.get(3)?,
+        principal:  row.get(4)?,
+        interest:   row.get(5)?,
+        status:     row.get(6)?,
+        tick_paid:  row.get::<_, Option<i64>>(7)?.map(|t| t as Tick),
+    })
+}
+
+impl SimStore {
+    // ── Origination ──────────────────────────────────────────────────
+
+    /// Active customers with a credit score, an income and an open
+    /// account, and no loan open, by customer id.
+    pub fn loan_applicants(&self, run_id: &str) -> SimResult<Vec<LoanApplicantRow>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT c.customer_id, MIN(a.account_id), c.credit_score, c.annual_income
+               FROM customer c
+               JOIN account a ON a.run_id = c.run_id AND a.customer_id = c.customer_id AND a.status = 'open'
+              WHERE c.run_id = ?1 AND c.status = 'active'
+                AND c.credit_score IS NOT NULL AND c.annual_income > 0
+                AND NOT EXISTS (
+                    SELECT 1 FROM loan l
+                     WHERE l.run_id = c.run_id AND l.customer_id = c.customer_id AND l.status = 'open'
+                )
+              GROUP BY c.customer_id
+              ORDER BY c.customer_id",
+        )?;
+        let rows = stmt.query_map(params![run_id], |row| {
+            Ok(LoanApplicantRow {
+                customer_id:   row.get(0)?,
+                account_id:    row.get(1)?,
+                credit_score:  row.get(2)?,
+                annual_income: row.get(3)?,
+            })
+        })?;
+        rows.collect::<Result<Vec<_>, _>>().map_err(Into::into)
+    }
+
+    pub fn insert_loan(&self, run_id: &str, row: &LoanRow) -> SimResult<()> {
+        self.conn.execute(
+            &format!(
+                "INSERT INTO loan (run_id, {LOAN_COLUMNS})
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, ?17)"
+            ),
+            params![
+                run_id,
+                row.loan_id,
+                row.customer_id,
+                row.account_id,
+                row.product_id,
+                row.kind,
+                row.tick_originated as i64,
+                row.amount,
+                row.annual_rate,
+                row.credit_score,
+                row.dti,
+                row.balance,
+                row.interest_paid,
+                row.status,
+                row.bucket,
+                row.tick_closed.map(|t| t as i64),
+                row.charge_off_loss,
+            ],
+        )?;
+        Ok(())
+    }
+
+    pub fn insert_loan_payment(&self, run_id: &str, row: &LoanPaymentRow) -> SimResult<()> {
+        self.conn.execute(
+            &format!(
+                "INSERT INTO loan_payment (run_id, {PAYMENT_COLUMNS})
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)"
+            ),
+            params![
+                run_id,
+                row.loan_id,
+                row.payment_no,
+                row.due_tick as i64,
+                row.amount_due,
+                row.principal,
+                row.interest,
+                row.status,
+                row.tick_paid.map(|t| t as i64),
+            ],
+        )?;
+        Ok(())
+    }
+
+    // ── Servicing ────────────────────────────────────────────────────
+
+    /// Loans still open, oldest first.
+    pub fn open_loans(&self, run_id: &str) -> SimResult<Vec<LoanRow>> {
+        let mut stmt = self.conn.prepare(&format!(
+            "SELECT {LOAN_COLUMNS} FROM loan WHERE run_id = ?1 AND status = 'open'
+              ORDER BY tick_originated, loan_id"
+        ))?;
+        let rows = stmt.query_map(params![run_id], loan_from_row)?;
+        rows.collect::<Result<Vec<_>, _>>().map_err(Into::into)
+    }
+
+    /// Every loan written in the run, oldest first.
+    pub fn loans(&self, run_id: &str) -> SimResult<Vec<LoanRow>> {
+        let mut stmt = self.conn.prepare(&format!(
+            "SELECT {LOAN_COLUMNS} FROM loan WHERE run_id = ?1 ORDER BY tick_originated, loan_id"
+        ))?;
+        let rows = stmt.query_map(params![run_id], loan_from_row)?;
+        rows.collect::<Result<Vec<_>, _>>().map_err(Into::into)
+    }
+
+    /// Loans to furnish to the bureau: those open, and those closed
+    /// after `closed_after`, oldest first.
+    pub fn furnishable_loans(&self, run_id: &str, closed_after: Tick) -> SimResult<Vec<LoanRow>> {
+        let mut stmt = self.conn.prepare(&format!(
+            "SELECT {LOAN_COLUMNS} FROM loan
+              WHERE run_id = ?1 AND (status = 'open' OR tick_closed > ?2)
+              ORDER BY tick_originated, loan_id"
+        ))?;
+        let rows = stmt.query_map(params![run_id, closed_after as i64], loan_from_row)?;
+        rows.collect::<Result<Vec<_>, _>>().map_err(Into::into)
+    }
+
+    /// A loan's payments in schedule order.
+    pub fn loan_payments(&self, run_id: &str, loan_id: &str) -> SimResult<Vec<LoanPaymentRow>> {
+        let mut stmt = self.conn.prepare(&format!(
+            "SELECT {PAYMENT_COLUMNS} FROM loan_payment WHERE run_id = ?1 AND loan_id = ?2 ORDER BY payment_no"
+        ))?;
+        let rows = stmt.query_map(params![run_id, loan_id], payment_from_row)?;
+        rows.collect::<Result<Vec<_>, _>>().map_err(Into::into)
+    }
+
+    /// Payments on a loan due by `tick` and not yet paid, oldest first.
+    pub fn loan_payments_owed(&self, run_id: &str, loan_id: &str, tick: Tick) -> SimResult<Vec<LoanPaymentRow>> {
+        let mut stmt = self.conn.prepare(&format!(
+            "SELECT {PAYMENT_COLUMNS} FROM loan_payment
+              WHERE run_id = ?1 AND loan_id = ?2 AND due_tick <= ?3 AND status IN ('scheduled', 'missed')
+              ORDER BY payment_no"
+        ))?;
+        let rows = stmt.query_map(params![run_id, loan_id, tick as i64], payment_from_row)?;
+        rows.collect::<Result<Vec<_>, _>>().map_err(Into::into)
+    }
+
+    pub fn loan_payment_count(&self, run_id: &str, loan_id: &str) -> SimResult<i64> {
+        let count: i64 = self.conn.query_row(
+            "SELECT COUNT(*) FROM loan_payment WHERE run_id = ?1 AND loan_id = ?2",
+            params![run_id, loan_id],
+            |row| row.get(0),
+        )?;
+        Ok(count)
+    }
+
+    /// Mark a payment 'paid' at `tick`, or 'missed'.
+    pub fn set_loan_payment_status(
+        &self,
+        run_id: &str,
+        loan_id: &str,
+        payment_no: i64,
+        status: &str,
+        tick_paid: Option<Tick>,
+    ) -> SimResult<()> {
+        self.conn.execute(
+            "UPDATE loan_payment SET status = ?4, tick_paid = ?5
+              WHERE run_id = ?1 AND loan_id = ?2 AND payment_no = ?3",
+            params![run_id, loan_id, payment_no, status, tick_paid.map(|t| t as i64)],
+        )?;
+        Ok(())
+    }
+
+    /// Book principal and interest received against a loan.
+    pub fn apply_loan_payment(&self, run_id: &str, loan_id: &str, principal: f64, interest: f64) -> SimResult<()> {
+        self.conn.execute(
+            "UPDATE loan SET balance = MAX(0.0, balance - ?3), interest_paid = interest_paid + ?4
+              WHERE run_id = ?1 AND loan_id = ?2",
+            params![run_id, loan_id, principal, interest],
+        )?;
+        Ok(())
+    }
+
+    /// Move a loan to a new delinquency bucket, keeping the move in
+    /// `delinquency_status`.
+    pub fn set_loan_bucket(&self, run_id: &str, row: &DelinquencyStatusRow) -> SimResult<()> {
+        self.conn.execute(
+            "UPDATE loan SET bucket = ?3 WHERE run_id = ?1 AND loan_id = ?2",
+            params![run_id, row.loan_id, row.bucket],
+        )?;
+        self.conn.execute(
+            "INSERT OR REPLACE INTO delinquency_status (run_id, loan_id, tick, days_past_due, bucket, past_due_amount)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+            params![run_id, row.loan_id, row.tick as i64, row.days_past_due, row.bucket, row.past_due_amount],
+        )?;
+        Ok(())
+    }
+
+    /// A loan's bucket moves, oldest first.
+    pub fn delinquency_history(&self, run_id: &str, loan_id: &str) -> SimResult<Vec<DelinquencyStatusRow>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT loan_id, tick, days_past_due, bucket, past_due_amount FROM delinquency_status
+              WHERE run_id = ?1 AND loan_id = ?2 ORDER BY tick",
+        )?;
+        let rows = stmt.query_map(params![run_id, loan_id], |row| {
+            Ok(DelinquencyStatusRow {
+                loan_id:         row.get(0)?,
+                tick:            row.get::<_, i64>(1)? as Tick,
+                days_past_due:   row.get(2)?,
+                bucket:          row.get(3)?,
+                past_due_amount: row.get(4)?,
+            })
+        })?;
+        rows.collect::<Result<Vec<_>, _>>().map_err(Into::into)
+    }
+
+    /// Close a loan as 'paid_off', or 'charged_off' with its loss.
+    pub fn close_loan(&self, run_id: &str, loan_id: &str, status: &str, tick: Tick, loss: f64) -> SimResult<()> {
+        self.conn.execute(
+            "UPDATE loan SET status = ?3, tick_closed = ?4, charge_off_loss = ?5
+              WHERE run_id = ?1 AND loan_id = ?2",
+            params![run_id, loan_id, status, tick as i64, loss],
+        )?;
+        Ok(())
+    }
+
+    // ── Economics ────────────────────────────────────────────────────
+
+    /// Charge-off losses booked in the inclusive tick window.
+    pub fn loan_charge_offs_between(&self, run_id: &str, start_tick: Tick, end_tick: Tick) -> SimResult<f64> {
+        let sum: f64 = self.conn.query_row(
+            "SELECT COALESCE(SUM(charge_off_loss), 0.0) FROM loan
+              WHERE run_id = ?1 AND status = 'charged_off' AND tick_closed >= ?2 AND tick_closed <= ?3",
+            params![run_id, start_tick as i64, end_tick as i64],
+            |row| row.get(0),
+        )?;
+        Ok(sum)
+    }
+
+    /// Interest received on loan payments made in the inclusive window.
+    pub fn loan_interest_between(&self, run_id: &str, start_tick: Tick, end_tick: Tick) -> SimResult<f64> {
+        let sum: f64 = self.conn.query_row(
+            "SELECT COALESCE(SUM(interest), 0.0) FROM loan_payment
+              WHERE run_id = ?1 AND status = 'paid' AND tick_paid >= ?2 AND tick_paid <= ?3",
+            params![run_id, start_tick as i64, end_tick as i64],
+            |row| row.get(0),
+        )?;
+        Ok(sum)
+    }
+
+    /// Principal outstanding on open loans.
+    pub fn loan_balance_outstanding(&self, run_id: &str) -> SimResult<f64> {
+        let sum: f64 = self.conn.query_row(
+            "SELECT COALESCE(SUM(balance), 0.0) FROM loan WHERE run_id = ?1 AND status = 'open'",
+            params![run_id],
+            |row| row.get(0),
+        )?;
+        Ok(sum)
+    }
+}
@@ -0,0 +1,216 @@
+//! Store methods for the loss event record and operational risk capital
+//! (Phase 4.61).
+
+use crate::{error::SimResult, types::Tick};
+use rusqlite::{params, OptionalExtension, Row};
+
+use super::SimStore;
+
+/// Row from the `loss_event` table.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LossEventRow {
+    /// Table the loss came from.
+    pub source:         String,
+    pub source_id:      String,
+    pub tick:           Tick,
+    /// 'external_fraud' | 'clients_products_business_practices'
+    /// | 'execution_delivery_process_management'
+    pub basel_category: String,
+    pub amount:         f64,
+    pub description:    String,
+}
+
+/// Row from the `op_risk_capital` table.
+#[derive(Debug, Clone, PartialEq)]
+pub struct OpRiskCapitalRow {
+    pub tick:               Tick,
+    /// Annualised gross income.
+    pub business_indicator: f64,
+    pub bic:                f64,
+    /// Average yearly loss over the lookback.
+    pub annual_loss:        f64,
+    pub loss_component:     f64,
+    /// Internal loss multiplier.
+    pub ilm:                f64,
+    pub capital:            f64,
+}
+
+const LOSS_COLUMNS: &str = "source, source_id, tick, basel_category, amount, description";
+
+fn loss_from_row(row: &Row) -> rusqlite::Result<LossEventRow> {
+    Ok(LossEventRow {
+        source:         row.get(0)?,
+        source_id:      row.get(1)?,
+        tick:           row.get::<_, i64>(2)? as Tick,
+        basel_category: row.get(3)?,
+        amount:         row.get(4)?,
+        description:    row.get(5)?,
+    })
+}
+
+const CAPITAL_COLUMNS: &str = "tick, business_indicator, bic, annual_loss, loss_component, ilm, capital";
+
+fn capital_from_row(row: &Row) -> rusqlite::Result<OpRiskCapitalRow> {
+    Ok(OpRiskCapitalRow {
+        tick:               row.get::<_, i64>(0)? as Tick,
+        business_indicator: row.get(1)?,
+        bic:                row.get(2)?,
+        annual_loss:        row.get(3)?,
+        loss_component:     row.get(4)?,
+        ilm:                row.get(5)?,
+        capital:            row.get(6)?,
+    })
+}
+
+/// Losses booked in ticks ?2..=?3, in `LOSS_COLUMNS` order. ?4 is the
+/// cost of remediating one fair lending finding.
+const LOSS_SOURCES_SQL: &str = "
+SELECT 'check_item', item_id, resolved_tick, 'external_fraud', loss, 'check fraud (' || typology || ')'
+  FROM check_item
+ WHERE run_id = ?1 AND loss_bearer = 'bank' AND loss > 0 AND resolved_tick BETWEEN ?2 AND ?3
+UNION ALL
+SELECT 'wire_recall', txn_id, recall_tick, 'external_fraud', amount, 'unrecovered business email compromise wire'
+  FROM wire_recall
+ WHERE run_id = ?1 AND outcome = 'funds_gone' AND recall_tick BETWEEN ?2 AND ?3
+UNION ALL
+SELECT 'exam_finding', finding_id, tick, 'clients_products_business_practices', fine_amount,
+       'exam finding fine (' || category || ')'
+  FROM exam_finding
+ WHERE run_id = ?1 AND fine_amount > 0 AND tick BETWEEN ?2 AND ?3
+UNION ALL
+SELECT 'suspicious_activity_report', sar_id, filing_tick, 'clients_products_business_practices',
+       regulatory_fine, 'late SAR filing fine'
+  FROM suspicious_activity_report
+ WHERE run_id = ?1 AND regulatory_fine > 0 AND filing_tick BETWEEN ?2 AND ?3
+UNION ALL
+SELECT 'bureau_dispute', dispute_id, due_tick + 1, 'clients_products_business_practices', penalty_amount,
+       'FCRA late correction penalty'
+  FROM bureau_dispute
+ WHERE run_id = ?1 AND overdue = 1 AND penalty_amount > 0 AND due_tick + 1 BETWEEN ?2 AND ?3
+UNION ALL
+SELECT 'fair_lending_finding', finding_id, responded_tick, 'clients_products_business_practices', ?4,
+       'fair lending remediation'
+  FROM fair_lending_finding
+ WHERE run_id = ?1 AND status = 'remediated' AND ?4 > 0 AND responded_tick BETWEEN ?2 AND ?3
+UNION ALL
+SELECT 'odfi_return_review', CAST(tick AS TEXT), tick, 'clients_products_business_practices', network_fine,
+       'ACH return rate network fine (' || COALESCE(breached, 'unknown') || ')'
+  FROM odfi_return_review
+ WHERE run_id = ?1 AND network_fine > 0 AND tick BETWEEN ?2 AND ?3
+UNION ALL
+SELECT 'recon_exception', exception_id, tick_resolved, 'execution_delivery_process_management',
+       write_off_amount, 'reconciliation write-off (' || rail_id || ')'
+  FROM recon_exception
+ WHERE run_id = ?1 AND write_off_amount > 0 AND tick_resolved BETWEEN ?2 AND ?3
+ORDER BY 3, 1, 2";
+
+impl SimStore {
+    /// Record the losses booked in ticks `from_tick..=to_tick` that are
+    /// not on record yet; returns the ones recorded.
+    pub fn record_loss_events(
+        &self,
+        run_id: &str,
+        from_tick: Tick,
+        to_tick: Tick,
+        remediation_cost: f64,
+    ) -> SimResult<Vec<LossEventRow>> {
+        let mut stmt = self.conn.prepare(LOSS_SOURCES_SQL)?;
+        let losses = stmt
+            .query_map(
+                params![run_id, from_tick as i64, to_tick as i64, remediation_cost],
+                loss_from_row,
+            )?
+            .collect::<Result<Vec<_>, _>>()?;
+        let mut recorded = Vec::new();
+        for loss in losses {
+            let inserted = self.conn.execute(
+                &format!("INSERT OR IGNORE INTO loss_event (run_id, {LOSS_COLUMNS}) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)"),
+                params![
+                    run_id,
+                    loss.source,
+                    loss.source_id,
+                    loss.tick as i64,
+                    loss.basel_category,
+                    loss.amount,
+                    loss.description,
+                ],
+            )?;
+            if inserted > 0 {
+                recorded.push(loss);
+            }
+        }
+        Ok(recorded)
+    }
+
+    /// Every loss on record, oldest first.
+    pub fn loss_events(&self, run_id: &str) -> SimResult<Vec<LossEventRow>> {
+        let mut stmt = self.conn.prepare(&format!(
+            "SELECT {LOSS_COLUMNS} FROM loss_event WHERE run_id = ?1 ORDER BY tick, source, source_id"
+        ))?;
+        let rows = stmt.query_map(params![run_id], loss_from_row)?;
+        rows.collect::<Result<Vec<_>, _>>().map_err(Into::into)
+    }
+
+    /// Total losses booked in ticks `from_tick..=to_tick`.
+    pub fn loss_total_between(&self, run_id: &str, from_tick: Tick, to_tick: Tick) -> SimResult<f64> {
+        let amounts = {
+            let mut stmt = self.conn.prepare(
+                "SELECT amount FROM loss_event WHERE run_id = ?1 AND tick >= ?2 AND tick <= ?3
+                 ORDER BY tick, source, source_id",
+            )?;
+            let rows = stmt.query_map(params![run_id, from_tick as i64, to_tick as i64], |row| {
+                row.get::<_, f64>(0)
+            })?;
+            rows.collect::<Result<Vec<_>, _>>()?
+        };
+        Ok(amounts.into_iter().fold(0.0, |sum, a| sum + a))
+    }
+
+    /// Losses booked in ticks `from_tick..=to_tick` by Basel category,
+    /// largest first.
+    pub fn loss_totals_by_category(
+        &self,
+        run_id: &str,
+        from_tick: Tick,
+        to_tick: Tick,
+    ) -> SimResult<Vec<(String, f64)>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT basel_category, SUM(amount) AS total FROM loss_event
+             WHERE run_id = ?1 AND tick >= ?2 AND tick <= ?3
+             GROUP BY basel_category ORDER BY total DESC, basel_category",
+        )?;
+        let rows = stmt.query_map(params![run_id, from_tick as i64, to_tick as i64], |row| {
+            Ok((row.get(0)?, row.get(1)?))
+        })?;
+        rows.collect::<Result<Vec<_>, _>>().map_err(Into::into)
+    }
+
+    pub fn insert_op_risk_capital(&self, run_id: &str, row: &OpRiskCapitalRow) -> SimResult<()> {
+        self.conn.execute(
+            &format!(
+                "INSERT OR REPLACE INTO op_risk_capital (run_id, {CAPITAL_COLUMNS})
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)"
+            ),
+            params![
+                run_id, row.tick as i64, row.business_indicator, row.bic, row.annual_loss,
+                row.loss_component, row.ilm, row.capital,
+            ],
+        )?;
+        Ok(())
+    }
+
+    /// The most recent capital estimate, if one has been taken.
+    pub fn latest_op_risk_capital(&self, run_id: &str) -> SimResult<Option<OpRiskCapitalRow>> {
+        self.conn
+            .query_row(
+                &format!(
+                    "SELECT {CAPITAL_COLUMNS} FROM op_risk_capital
+                     WHERE run_id = ?1 ORDER BY tick DESC LIMIT 1"
+                ),
+                params![run_id],
+                capital_from_row,
+            )
+            .optional()
+            .map_err(Into::into)
+    }
+}
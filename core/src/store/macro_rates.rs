@@ -0,0 +1,88 @@
+//! Store methods for the rate environment (Phase 4.83).
+
+use crate::{error::SimResult, types::Tick};
+use rusqlite::params;
+
+use super::SimStore;
+
+/// Row from the `macro_rate_history` table.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MacroRateRow {
+    pub tick:           Tick,
+    pub base_rate:      f64,
+    pub long_rate:      f64,
+    pub economic_phase: String,
+    /// 'start' | 'hike' | 'cut' | 'hold'
+    pub rate_move:      String,
+}
+
+impl SimStore {
+    pub fn insert_macro_rate(&self, run_id: &str, row: &MacroRateRow) -> SimResult<()> {
+        self.conn.execute(
+            "INSERT INTO macro_rate_history (run_id, tick, base_rate, long_rate, economic_phase, move)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+            params![run_id, row.tick as i64, row.base_rate, row.long_rate, row.economic_phase, row.rate_move],
+        )?;
+        Ok(())
+    }
+
+    /// The run's rate history, oldest first.
+    pub fn macro_rate_history(&self, run_id: &str) -> SimResult<Vec<MacroRateRow>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT tick, base_rate, long_rate, economic_phase, move
+               FROM macro_rate_history
+              WHERE run_id = ?1
+              ORDER BY tick",
+        )?;
+        let rows = stmt.query_map(params![run_id], |row| {
+            Ok(MacroRateRow {
+                tick:           row.get::<_, i64>(0)? as Tick,
+                base_rate:      row.get(1)?,
+                long_rate:      row.get(2)?,
+                economic_phase: row.get(3)?,
+                rate_move:      row.get(4)?,
+            })
+        })?;
+        rows.collect::<Result<Vec<_>, _>>().map_err(Into::into)
+    }
+
+    /// Average of a rate over the ticks `start..=end`, each tick at the
+    /// rate in force on it; before the first row, the first row's rate.
+    /// `None` with no history.
+    pub(super) fn avg_macro_rate(
+        &self,
+        run_id: &str,
+        start: Tick,
+        end: Tick,
+        rate: fn(&MacroRateRow) -> f64,
+    ) -> SimResult<Option<f64>> {
+        let history = self.macro_rate_history(run_id)?;
+        let Some(first) = history.first() else {
+            return Ok(None);
+        };
+        let end = end.max(start);
+        let total: f64 = (start..=end)
+            .map(|t| rate(history.iter().rev().find(|r| r.tick <= t).unwrap_or(first)))
+            .sum();
+        Ok(Some(total / (end - start + 1) as f64))
+    }
+
+    /// Average long rate over `start..=end`: the yield on fixed-rate
+    /// assets that reprice evenly over that window.
+    pub fn avg_macro_long_rate(&self, run_id: &str, start: Tick, end: Tick) -> SimResult<f64> {
+        Ok(self.avg_macro_rate(run_id, start, end, |r| r.long_rate)?.unwrap_or(0.05))
+    }
+
+    /// Balances in open accounts, by product.
+    pub fn account_balances_by_product(&self, run_id: &str) -> SimResult<Vec<(String, f64)>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT product_id, COALESCE(SUM(balance), 0.0)
+               FROM account
+              WHERE run_id = ?1 AND status = 'open'
+              GROUP BY product_id
+              ORDER BY product_id",
+        )?;
+        let rows = stmt.query_map(params![run_id], |row| Ok((row.get(0)?, row.get(1)?)))?;
+        rows.collect::<Result<Vec<_>, _>>().map_err(Into::into)
+    }
+}
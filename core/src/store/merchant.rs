@@ -0,0 +1,110 @@
+//! Store methods for merchants (Phase 4.93).
+
+use crate::{error::SimResult, types::Tick};
+use rusqlite::params;
+
+use super::SimStore;
+
+/// Row from the `merchant` table.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MerchantRow {
+    pub merchant_id: String,
+    pub name:        String,
+    pub category:    String,
+    /// 'low' | 'medium' | 'high'
+    pub risk_tier:   String,
+    /// Chance a settled purchase here is disputed.
+    pub fraud_rate:  f64,
+    /// The latest spell the merchant went bad, if it has.
+    pub bad_from:    Option<Tick>,
+    pub bad_until:   Option<Tick>,
+}
+
+impl MerchantRow {
+    pub fn bad_at(&self, tick: Tick) -> bool {
+        matches!((self.bad_from, self.bad_until), (Some(from), Some(until)) if from <= tick && tick <= until)
+    }
+}
+
+/// One merchant's purchases and disputes over a window.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MerchantChargebackStats {
+    pub merchant_id:    String,
+    /// Purchases authorized in the window.
+    pub authorizations: i64,
+    /// Disputes filed in the window, and those of them charged back.
+    pub disputes:       i64,
+    pub chargebacks:    i64,
+    /// Disputes over authorizations; 0 without authorizations.
+    pub rate:           f64,
+}
+
+impl SimStore {
+    pub fn upsert_merchant(&self, run_id: &str, row: &MerchantRow) -> SimResult<()> {
+        self.conn.execute(
+            "INSERT OR REPLACE INTO merchant
+             (run_id, merchant_id, name, category, risk_tier, fraud_rate, bad_from, bad_until)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+            params![
+                run_id, row.merchant_id, row.name, row.category, row.risk_tier, row.fraud_rate,
+                row.bad_from.map(|t| t as i64), row.bad_until.map(|t| t as i64),
+            ],
+        )?;
+        Ok(())
+    }
+
+    /// The run's merchants, in id order.
+    pub fn merchants(&self, run_id: &str) -> SimResult<Vec<MerchantRow>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT merchant_id, name, category, risk_tier, fraud_rate, bad_from, bad_until
+             FROM merchant WHERE run_id = ?1 ORDER BY merchant_id",
+        )?;
+        let rows = stmt.query_map(params![run_id], |row| {
+            Ok(MerchantRow {
+                merchant_id: row.get(0)?,
+                name:        row.get(1)?,
+                category:    row.get(2)?,
+                risk_tier:   row.get(3)?,
+                fraud_rate:  row.get(4)?,
+                bad_from:    row.get::<_, Option<i64>>(5)?.map(|t| t as Tick),
+                bad_until:   row.get::<_, Option<i64>>(6)?.map(|t| t as Tick),
+            })
+        })?;
+        rows.collect::<Result<Vec<_>, _>>().map_err(Into::into)
+    }
+
+    /// Every merchant's authorizations and disputes filed over
+    /// `[start, end]`, in id order.
+    pub fn merchant_chargeback_stats(
+        &self,
+        run_id: &str,
+        start: Tick,
+        end: Tick,
+    ) -> SimResult<Vec<MerchantChargebackStats>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT m.merchant_id,
+                    (SELECT COUNT(*) FROM authorization a
+                     WHERE a.run_id = m.run_id AND a.merchant_id = m.merchant_id
+                       AND a.tick_authorized >= ?2 AND a.tick_authorized <= ?3),
+                    (SELECT COUNT(*) FROM card_dispute d
+                     WHERE d.run_id = m.run_id AND d.merchant_id = m.merchant_id
+                       AND d.tick_filed >= ?2 AND d.tick_filed <= ?3),
+                    (SELECT COUNT(*) FROM card_dispute d
+                     WHERE d.run_id = m.run_id AND d.merchant_id = m.merchant_id
+                       AND d.tick_filed >= ?2 AND d.tick_filed <= ?3 AND d.chargeback_issued = 1)
+             FROM merchant m WHERE m.run_id = ?1 ORDER BY m.merchant_id",
+        )?;
+        let rows = stmt.query_map(params![run_id, start as i64, end as i64], |row| {
+            let authorizations: i64 = row.get(1)?;
+            let disputes: i64 = row.get(2)?;
+            Ok(MerchantChargebackStats {
+                merchant_id: row.get(0)?,
+                authorizations,
+                disputes,
+                chargebacks: row.get(3)?,
+                rate: if authorizations > 0 { disputes as f64 / authorizations as f64 } else { 0.0 },
+            })
+        })?;
+        rows.collect::<Result<Vec<_>, _>>().map_err(Into::into)
+    }
+}
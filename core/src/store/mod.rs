@@ -3,7 +3,7 @@
 //! RULE: Only store.rs talks to the database.
 //! Subsystems call store methods — they never execute SQL directly.
 
-use crate::{error::SimResult, event::EventLogEntry, types::Tick};
+use crate::{error::{SimError, SimResult}, event::EventLogEntry, types::Tick};
 mod incident;
 mod compliance;
 mod reconciliation;
@@ -17,6 +17,80 @@ mod business;
 mod complaint;
 pub mod regulatory_exam;  // Phase 3.6
 pub mod reputation;       // Phase 3.6
+pub mod credit_bureau;    // Phase 4.1
+pub mod protected_funds;  // Phase 4.2
+pub mod branch;           // Phase 4.3
+pub mod fair_lending;     // Phase 4.4
+pub mod network_invoice;  // Phase 4.6
+pub mod settlement;       // Phase 4.7
+pub mod payment_cutoff;   // Phase 4.8
+pub mod ach_return;       // Phase 4.9
+pub mod originated_ach;   // Phase 4.10
+pub mod external_link;    // Phase 4.11
+pub mod customer_limit;   // Phase 4.12
+pub mod risk_lifecycle;   // Phase 4.13
+pub mod ssn_validation;   // Phase 4.14
+pub mod idv_check;        // Phase 4.15
+pub mod sar_decision;     // Phase 4.16
+pub mod rule_tuning;      // Phase 4.17
+pub mod coverage_assessment; // Phase 4.18
+pub mod post_mortem;      // Phase 4.19
+pub mod backend;          // Phase 4.21
+pub mod segment_activity; // Phase 4.27
+pub mod event_archive;    // Phase 4.28
+pub mod complaint_sla;    // Phase 4.29
+pub mod hardship;         // Phase 4.30
+pub mod case_document;    // Phase 4.31
+pub mod contact;          // Phase 4.32
+pub mod satisfaction;     // Phase 4.33
+pub mod financial_health; // Phase 4.34
+pub mod enrichment;       // Phase 4.35
+pub mod remittance;       // Phase 4.36
+pub mod visa;             // Phase 4.37
+pub mod adverse_media;    // Phase 4.38
+pub mod correspondent;    // Phase 4.39
+pub mod cross_border;     // Phase 4.40
+pub mod calendar;         // Phase 4.41
+pub mod queue;            // Phase 4.43
+pub mod authorization;    // Phase 4.46
+pub mod golden;           // Phase 4.49
+pub mod retention;        // Phase 4.50
+pub mod deposit_run;      // Phase 4.52
+pub mod bec;              // Phase 4.53
+pub mod check_fraud;      // Phase 4.54
+pub mod deposit_holds;    // Phase 4.55
+pub mod entity_timeline;  // Phase 4.58
+pub mod evidence_bundle;  // Phase 4.59
+pub mod alert_case;       // Phase 4.60
+pub mod loss_event;       // Phase 4.61
+pub mod quarter_close;    // Phase 4.62
+pub mod opex_allocation;  // Phase 4.63
+pub mod churn_evaluation; // Phase 4.64
+pub mod complaint_quality; // Phase 4.65
+pub mod readonly_query;   // Phase 4.67
+pub mod scenario_script;  // Phase 4.68
+pub mod stress_index;     // Phase 4.69
+pub mod tournament;       // Phase 4.71
+pub mod rule_backtest;    // Phase 4.72
+pub mod alert_qa;         // Phase 4.73
+pub mod breakpoint;       // Phase 4.74
+pub mod resume;           // Phase 4.76
+pub mod export;           // Phase 4.79
+pub mod staffing;         // Phase 4.80
+pub mod enforcement;      // Phase 4.81
+pub mod lending;          // Phase 4.82
+pub mod macro_rates;      // Phase 4.83
+pub mod sar_export;       // Phase 4.84
+pub mod alert_triage;     // Phase 4.85
+pub mod case_management;  // Phase 4.86
+pub mod mule_network;     // Phase 4.87
+pub mod structuring;      // Phase 4.88
+pub mod sanctions_screening; // Phase 4.89
+pub mod channel;          // Phase 4.90
+pub mod app_fraud;        // Phase 4.91
+pub mod atm;              // Phase 4.92
+pub mod merchant;         // Phase 4.93
+pub mod device;           // Phase 4.94
 use rusqlite::{params, Connection, OptionalExtension};
 
 pub struct SimStore {
@@ -58,6 +132,15 @@ impl SimStore {
         }
     }
 
+    /// Run `f` in one transaction on this connection: every write it
+    /// makes is kept if it returns Ok and rolled back if it fails.
+    pub fn atomically<T>(&self, f: impl FnOnce() -> SimResult<T>) -> SimResult<T> {
+        let tx = self.conn.unchecked_transaction()?;
+        let value = f()?;
+        tx.commit()?;
+        Ok(value)
+    }
+
     /// Apply all schema migrations in order.
     pub fn migrate(&self) -> SimResult<()> {
         self.conn
@@ -114,6 +197,140 @@ impl SimStore {
             .execute_batch(include_str!("../../../migrations/026_regulatory_exam.sql"))?;
         self.conn
             .execute_batch(include_str!("../../../migrations/027_reputation.sql"))?;
+        self.conn
+            .execute_batch(include_str!("../../../migrations/028_credit_bureau.sql"))?;
+        self.conn
+            .execute_batch(include_str!("../../../migrations/029_protected_funds.sql"))?;
+        self.conn
+            .execute_batch(include_str!("../../../migrations/030_branch_network.sql"))?;
+        self.conn
+            .execute_batch(include_str!("../../../migrations/031_fair_lending.sql"))?;
+        self.conn
+            .execute_batch(include_str!("../../../migrations/032_offer_guardrail.sql"))?;
+        self.conn
+            .execute_batch(include_str!("../../../migrations/033_network_invoice.sql"))?;
+        self.conn
+            .execute_batch(include_str!("../../../migrations/034_settlement_accounts.sql"))?;
+        self.conn
+            .execute_batch(include_str!("../../../migrations/035_payment_cutoff.sql"))?;
+        self.conn
+            .execute_batch(include_str!("../../../migrations/036_ach_returns.sql"))?;
+        self.conn
+            .execute_batch(include_str!("../../../migrations/037_ach_origination.sql"))?;
+        self.conn
+            .execute_batch(include_str!("../../../migrations/038_external_links.sql"))?;
+        self.conn
+            .execute_batch(include_str!("../../../migrations/039_customer_limits.sql"))?;
+        self.conn
+            .execute_batch(include_str!("../../../migrations/040_risk_lifecycle.sql"))?;
+        self.conn
+            .execute_batch(include_str!("../../../migrations/041_ssn_validation.sql"))?;
+        self.conn
+            .execute_batch(include_str!("../../../migrations/042_idv_checks.sql"))?;
+        self.conn
+            .execute_batch(include_str!("../../../migrations/043_sar_decisions.sql"))?;
+        self.conn
+            .execute_batch(include_str!("../../../migrations/044_rule_tuning.sql"))?;
+        self.conn
+            .execute_batch(include_str!("../../../migrations/045_coverage_assessment.sql"))?;
+        self.conn
+            .execute_batch(include_str!("../../../migrations/046_run_provenance.sql"))?;
+        self.conn
+            .execute_batch(include_str!("../../../migrations/047_aggregate_maintenance.sql"))?;
+        self.conn
+            .execute_batch(include_str!("../../../migrations/048_event_log_archive.sql"))?;
+        self.conn
+            .execute_batch(include_str!("../../../migrations/049_complaint_sla_policy.sql"))?;
+        self.conn
+            .execute_batch(include_str!("../../../migrations/050_hardship_program.sql"))?;
+        self.conn
+            .execute_batch(include_str!("../../../migrations/051_case_documents.sql"))?;
+        self.conn
+            .execute_batch(include_str!("../../../migrations/052_contact_preferences.sql"))?;
+        self.conn
+            .execute_batch(include_str!("../../../migrations/053_satisfaction_dynamics.sql"))?;
+        self.conn
+            .execute_batch(include_str!("../../../migrations/054_financial_health.sql"))?;
+        self.conn
+            .execute_batch(include_str!("../../../migrations/055_transaction_enrichment.sql"))?;
+        self.conn
+            .execute_batch(include_str!("../../../migrations/056_remittance_corridors.sql"))?;
+        self.conn
+            .execute_batch(include_str!("../../../migrations/057_visa_expiry.sql"))?;
+        self.conn
+            .execute_batch(include_str!("../../../migrations/058_adverse_media.sql"))?;
+        self.conn
+            .execute_batch(include_str!("../../../migrations/059_correspondent_banking.sql"))?;
+        self.conn
+            .execute_batch(include_str!("../../../migrations/060_cross_border_funnels.sql"))?;
+        self.conn
+            .execute_batch(include_str!("../../../migrations/061_feature_flags.sql"))?;
+        self.conn
+            .execute_batch(include_str!("../../../migrations/062_command_authorization.sql"))?;
+        self.conn
+            .execute_batch(include_str!("../../../migrations/063_ctr_transaction_key.sql"))?;
+        self.conn
+            .execute_batch(include_str!("../../../migrations/064_deposit_run.sql"))?;
+        self.conn
+            .execute_batch(include_str!("../../../migrations/065_business_email_compromise.sql"))?;
+        self.conn
+            .execute_batch(include_str!("../../../migrations/066_check_fraud.sql"))?;
+        self.conn
+            .execute_batch(include_str!("../../../migrations/067_deposit_holds.sql"))?;
+        self.conn
+            .execute_batch(include_str!("../../../migrations/068_evidence_bundles.sql"))?;
+        self.conn
+            .execute_batch(include_str!("../../../migrations/069_alert_cases.sql"))?;
+        self.conn
+            .execute_batch(include_str!("../../../migrations/070_operational_risk.sql"))?;
+        self.conn
+            .execute_batch(include_str!("../../../migrations/071_quarter_close.sql"))?;
+        self.conn
+            .execute_batch(include_str!("../../../migrations/072_opex_allocation.sql"))?;
+        self.conn
+            .execute_batch(include_str!("../../../migrations/073_churn_evaluation.sql"))?;
+        self.conn
+            .execute_batch(include_str!("../../../migrations/074_complaint_quality.sql"))?;
+        self.conn
+            .execute_batch(include_str!("../../../migrations/075_scenario_script.sql"))?;
+        self.conn
+            .execute_batch(include_str!("../../../migrations/076_stress_index.sql"))?;
+        self.conn
+            .execute_batch(include_str!("../../../migrations/077_alert_qa.sql"))?;
+        self.conn
+            .execute_batch(include_str!("../../../migrations/078_run_scenario.sql"))?;
+        self.conn
+            .execute_batch(include_str!("../../../migrations/079_staffing.sql"))?;
+        self.conn
+            .execute_batch(include_str!("../../../migrations/080_enforcement.sql"))?;
+        self.conn
+            .execute_batch(include_str!("../../../migrations/081_lending.sql"))?;
+        self.conn
+            .execute_batch(include_str!("../../../migrations/082_macro_rate_history.sql"))?;
+        self.conn
+            .execute_batch(include_str!("../../../migrations/083_sar_export.sql"))?;
+        self.conn
+            .execute_batch(include_str!("../../../migrations/084_alert_triage.sql"))?;
+        self.conn
+            .execute_batch(include_str!("../../../migrations/085_case_management.sql"))?;
+        self.conn
+            .execute_batch(include_str!("../../../migrations/086_mule_network.sql"))?;
+        self.conn
+            .execute_batch(include_str!("../../../migrations/087_structuring.sql"))?;
+        self.conn
+            .execute_batch(include_str!("../../../migrations/088_sanctions_matching.sql"))?;
+        self.conn
+            .execute_batch(include_str!("../../../migrations/089_channels.sql"))?;
+        self.conn
+            .execute_batch(include_str!("../../../migrations/090_app_fraud.sql"))?;
+        self.conn
+            .execute_batch(include_str!("../../../migrations/091_atm_network.sql"))?;
+        self.conn
+            .execute_batch(include_str!("../../../migrations/092_merchants.sql"))?;
+        self.conn
+            .execute_batch(include_str!("../../../migrations/093_device_telemetry.sql"))?;
+        self.conn
+            .execute_batch(include_str!("../../../migrations/094_bureau_loan_tradelines.sql"))?;
         Ok(())
     }
 
@@ -127,6 +344,95 @@ impl SimStore {
         Ok(())
     }
 
+    /// Phase 4.24: insert a run with how it was produced.
+    pub fn insert_run_with_provenance(
+        &self,
+        run_id: &str,
+        seed: u64,
+        version: &str,
+        provenance: &crate::provenance::RunProvenance,
+    ) -> SimResult<()> {
+        self.conn.execute(
+            "INSERT INTO run (run_id, seed, version, started_at, cli_args, scenario_hash,
+                              git_commit, features, host, scenario)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)",
+            params![
+                run_id,
+                seed as i64,
+                version,
+                provenance.started_at,
+                serde_json::to_string(&provenance.cli_args)?,
+                provenance.scenario_hash,
+                provenance.git_commit,
+                serde_json::to_string(&provenance.features)?,
+                provenance.host.as_ref().map(serde_json::to_string).transpose()?,
+                provenance.scenario.as_ref().map(serde_json::to_string).transpose()?,
+            ],
+        )?;
+        Ok(())
+    }
+
+    /// Phase 4.42: store the feature flags a run was built with.
+    pub fn record_feature_flags(
+        &self,
+        run_id: &str,
+        flags: &crate::config::FeatureFlagsConfig,
+    ) -> SimResult<()> {
+        self.conn.execute(
+            "UPDATE run SET feature_flags = ?2 WHERE run_id = ?1",
+            params![run_id, serde_json::to_string(flags)?],
+        )?;
+        Ok(())
+    }
+
+    /// Phase 4.24: a stored run and its provenance, or None for an
+    /// unknown run_id.
+    pub fn describe_run(&self, run_id: &str) -> SimResult<Option<crate::provenance::RunDescription>> {
+        let row = self
+            .conn
+            .query_row(
+                "SELECT seed, version, started_at, cli_args, scenario_hash, git_commit, features, host,
+                        feature_flags, scenario
+                 FROM run WHERE run_id = ?1",
+                params![run_id],
+                |row| {
+                    Ok((
+                        row.get::<_, i64>(0)?,
+                        row.get::<_, String>(1)?,
+                        row.get::<_, i64>(2)?,
+                        row.get::<_, Option<String>>(3)?,
+                        row.get::<_, Option<String>>(4)?,
+                        row.get::<_, Option<String>>(5)?,
+                        row.get::<_, Option<String>>(6)?,
+                        row.get::<_, Option<String>>(7)?,
+                        row.get::<_, Option<String>>(8)?,
+                        row.get::<_, Option<String>>(9)?,
+                    ))
+                },
+            )
+            .optional()?;
+        let Some((seed, version, started_at, cli_args, scenario_hash, git_commit, features, host, flags, scenario)) =
+            row
+        else {
+            return Ok(None);
+        };
+        Ok(Some(crate::provenance::RunDescription {
+            run_id: run_id.to_string(),
+            seed: seed as u64,
+            version,
+            provenance: crate::provenance::RunProvenance {
+                cli_args: cli_args.map(|s| serde_json::from_str(&s)).transpose()?.unwrap_or_default(),
+                scenario_hash,
+                scenario: scenario.map(|s| serde_json::from_str(&s)).transpose()?,
+                git_commit,
+                features: features.map(|s| serde_json::from_str(&s)).transpose()?.unwrap_or_default(),
+                host: host.map(|s| serde_json::from_str(&s)).transpose()?,
+                started_at,
+            },
+            feature_flags: flags.map(|s| serde_json::from_str(&s)).transpose()?.unwrap_or_default(),
+        }))
+    }
+
     // ── Event log ──────────────────────────────────────────────
 
     pub fn append_event(&self, entry: &EventLogEntry) -> SimResult<()> {
@@ -145,7 +451,35 @@ impl SimStore {
         Ok(())
     }
 
+    /// Every table in the schema.
+    pub fn table_names(&self) -> SimResult<Vec<String>> {
+        let mut stmt = self
+            .conn
+            .prepare("SELECT name FROM sqlite_master WHERE type = 'table' ORDER BY name")?;
+        let rows = stmt.query_map([], |row| row.get(0))?;
+        rows.collect::<Result<Vec<_>, _>>().map_err(Into::into)
+    }
+
+    /// Distinct (subsystem, event_type) pairs in the detailed log.
+    pub fn logged_event_sources(&self, run_id: &str) -> SimResult<Vec<(String, String)>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT DISTINCT subsystem, event_type FROM event_log
+             WHERE run_id = ?1 ORDER BY subsystem, event_type",
+        )?;
+        let rows = stmt.query_map(params![run_id], |row| Ok((row.get(0)?, row.get(1)?)))?;
+        rows.collect::<Result<Vec<_>, _>>().map_err(Into::into)
+    }
+
+    /// A tick's events from whichever retention tier holds it.
     pub fn events_for_tick(&self, run_id: &str, tick: Tick) -> SimResult<Vec<EventLogEntry>> {
+        let detailed = self.detailed_events_for_tick(run_id, tick)?;
+        if !detailed.is_empty() {
+            return Ok(detailed);
+        }
+        self.archived_events_for_tick(run_id, tick)
+    }
+
+    fn detailed_events_for_tick(&self, run_id: &str, tick: Tick) -> SimResult<Vec<EventLogEntry>> {
         let mut stmt = self.conn.prepare(
             "SELECT id, run_id, tick, subsystem, event_type, payload
              FROM event_log WHERE run_id = ?1 AND tick = ?2
@@ -428,19 +762,12 @@ impl SimStore {
             params![run_id, tick as i64], |row| row.get(0),
         ).unwrap_or(0);
 
-        let backlog_count: i64 = self
-            .conn
-            .query_row(
-                "SELECT COUNT(*) FROM complaint WHERE run_id = ?1 AND status = 'open'",
-                params![run_id],
-                |row| row.get(0),
-            )
-            .unwrap_or(0);
-
-        let avg_age_days: f64 = self.conn.query_row(
-            "SELECT COALESCE(AVG(?2 - tick_opened), 0.0) FROM complaint WHERE run_id = ?1 AND status = 'open'",
-            params![run_id, tick as i64], |row| row.get(0),
-        ).unwrap_or(0.0);
+        let (backlog_count, opened_tick_sum) = self.complaint_backlog_totals(run_id)?;
+        let avg_age_days = if backlog_count > 0 {
+            tick as f64 - opened_tick_sum as f64 / backlog_count as f64
+        } else {
+            0.0
+        };
 
         Ok(ComplaintAggregate {
             complaints_opened,
@@ -497,7 +824,7 @@ impl SimStore {
         let mut stmt = self.conn.prepare(
             "SELECT complaint_id, customer_id, account_id, tick_opened, tick_closed,
                     product, issue, priority, status, sla_due_tick, sla_breached,
-                    resolution_code, amount_refunded, udaap_flag
+                    resolution_code, amount_refunded, udaap_flag, channel, service_channel
              FROM complaint WHERE run_id = ?1 AND status = 'open'
              ORDER BY tick_opened ASC LIMIT 1",
         )?;
@@ -515,12 +842,7 @@ impl SimStore {
     }
 
     pub fn complaint_backlog(&self, run_id: &str) -> SimResult<i64> {
-        let count: i64 = self.conn.query_row(
-            "SELECT COUNT(*) FROM complaint WHERE run_id = ?1 AND status = 'open'",
-            params![run_id],
-            |row| row.get(0),
-        )?;
-        Ok(count)
+        Ok(self.complaint_backlog_totals(run_id)?.0)
     }
 
     pub fn fee_event_count(&self, run_id: &str) -> SimResult<i64> {
@@ -529,15 +851,25 @@ impl SimStore {
             params![run_id],
             |row| row.get(0),
         )?;
-        Ok(count)
+        Ok(count + self.archived_event_count(run_id, 0, i64::MAX as Tick, "fee_charged")?)
     }
 
     pub fn churned_customer_count(&self, run_id: &str) -> SimResult<i64> {
         self.customer_count(run_id, "churned")
     }
 
-    /// Count event_log entries of a given type within a tick window.
-    /// Used by RegulatoryExamSubsystem to scan compliance evidence.
+    /// Everyone ever onboarded, whatever their status now.
+    pub fn onboarded_customer_count(&self, run_id: &str) -> SimResult<i64> {
+        let count: i64 = self.conn.query_row(
+            "SELECT COUNT(*) FROM customer WHERE run_id = ?1",
+            params![run_id],
+            |row| row.get(0),
+        )?;
+        Ok(count)
+    }
+
+    /// Count logged events of a given type within a tick window, in
+    /// either retention tier. Used by RegulatoryExamSubsystem to scan compliance evidence.
     pub fn count_events_in_range(
         &self,
         run_id:     &str,
@@ -551,7 +883,7 @@ impl SimStore {
             params![run_id, tick_start as i64, tick_end as i64, event_type],
             |row| row.get(0),
         )?;
-        Ok(count)
+        Ok(count + self.archived_event_count(run_id, tick_start, tick_end, event_type)?)
     }
 
     // ── P&L ─────────────────────────────────────────────────────
@@ -567,8 +899,11 @@ impl SimStore {
                 nii, fee_income, gross_income,
                 credit_loss, fraud_loss, opex, complaint_cost,
                 pre_tax_profit, nim, efficiency_ratio,
-                avg_deposits, avg_loans, customer_count, active_accounts
-            ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, ?17)",
+                avg_deposits, avg_loans, customer_count, active_accounts,
+                operational_loss, op_risk_capital, accrual_adjustment,
+                app_fraud_loss, app_customer_loss
+            ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, ?17, ?18, ?19, ?20,
+                      ?21, ?22)",
             params![
                 run_id,
                 pnl.tick as i64,
@@ -587,6 +922,11 @@ impl SimStore {
                 pnl.avg_loans,
                 pnl.customer_count,
                 pnl.active_accounts,
+                pnl.operational_loss,
+                pnl.op_risk_capital,
+                pnl.accrual_adjustment,
+                pnl.app_fraud_loss,
+                pnl.app_customer_loss,
             ],
         )?;
         Ok(())
@@ -601,37 +941,44 @@ impl SimStore {
             "SELECT tick, period, nii, fee_income, gross_income,
                     credit_loss, fraud_loss, opex, complaint_cost,
                     pre_tax_profit, nim, efficiency_ratio,
-                    avg_deposits, avg_loans, customer_count, active_accounts
+                    avg_deposits, avg_loans, customer_count, active_accounts,
+                    operational_loss, op_risk_capital, accrual_adjustment,
+                    app_fraud_loss, app_customer_loss
              FROM pnl_snapshot
              WHERE run_id = ?1
              ORDER BY tick ASC
              LIMIT ?2",
         )?;
         let snapshots = stmt
-            .query_map(params![run_id, count as i64], |row| {
-                Ok(crate::economics_subsystem::PnLSnapshot {
-                    tick: row.get::<_, i64>(0)? as u64,
-                    period: row.get(1)?,
-                    nii: row.get(2)?,
-                    fee_income: row.get(3)?,
-                    gross_income: row.get(4)?,
-                    credit_loss: row.get(5)?,
-                    fraud_loss: row.get(6)?,
-                    opex: row.get(7)?,
-                    complaint_cost: row.get(8)?,
-                    pre_tax_profit: row.get(9)?,
-                    nim: row.get(10)?,
-                    efficiency_ratio: row.get(11)?,
-                    avg_deposits: row.get(12)?,
-                    avg_loans: row.get(13)?,
-                    customer_count: row.get(14)?,
-                    active_accounts: row.get(15)?,
-                })
-            })?
+            .query_map(params![run_id, count as i64], pnl_row)?
             .collect::<Result<Vec<_>, _>>()?;
         Ok(snapshots)
     }
 
+    /// The most recent P&L snapshot, if one has been taken.
+    pub fn latest_pnl_snapshot(
+        &self,
+        run_id: &str,
+    ) -> SimResult<Option<crate::economics_subsystem::PnLSnapshot>> {
+        self.conn
+            .query_row(
+                "SELECT tick, period, nii, fee_income, gross_income,
+                        credit_loss, fraud_loss, opex, complaint_cost,
+                        pre_tax_profit, nim, efficiency_ratio,
+                        avg_deposits, avg_loans, customer_count, active_accounts,
+                        operational_loss, op_risk_capital, accrual_adjustment,
+                        app_fraud_loss, app_customer_loss
+                 FROM pnl_snapshot
+                 WHERE run_id = ?1
+                 ORDER BY tick DESC
+                 LIMIT 1",
+                params![run_id],
+                pnl_row,
+            )
+            .optional()
+            .map_err(Into::into)
+    }
+
     pub fn pnl_count(&self, run_id: &str) -> SimResult<i64> {
         self.conn
             .query_row(
@@ -681,21 +1028,17 @@ impl SimStore {
 
     // ── Macro state average ────────────────────────────────────
 
+    /// Average base rate over the ticks `start_tick..=end_tick`, from the
+    /// rate history (Phase 4.83).
     pub fn avg_macro_base_rate(
         &self,
         run_id: &str,
-        _start_tick: Tick,
-        _end_tick: Tick,
+        start_tick: Tick,
+        end_tick: Tick,
     ) -> SimResult<f64> {
-        let rate: f64 = self
-            .conn
-            .query_row(
-                "SELECT base_rate FROM macro_state WHERE run_id = ?1",
-                params![run_id],
-                |row| row.get(0),
-            )
-            .unwrap_or(0.05);
-        Ok(rate)
+        Ok(self
+            .avg_macro_rate(run_id, start_tick, end_tick, |r| r.base_rate)?
+            .unwrap_or(0.05))
     }
 
     // ── Fee and complaint aggregates ───────────────────────────
@@ -770,7 +1113,7 @@ impl SimStore {
             "nsf_fee" => "nsf_fee",
             "atm_fee" => "atm_fee",
             "wire_fee" => "wire_fee",
-            _ => return Err(anyhow::anyhow!("Invalid fee type: {fee_type}").into()),
+            _ => return Err(SimError::command("set_product_fee", format!("invalid fee type: {fee_type}"))),
         };
 
         let sql = format!(
@@ -912,14 +1255,7 @@ impl SimStore {
         tick: Tick,
         command: &crate::command::PlayerCommand,
     ) -> SimResult<i64> {
-        let cmd_type = match command {
-            crate::command::PlayerCommand::Pause => "pause",
-            crate::command::PlayerCommand::Resume => "resume",
-            crate::command::PlayerCommand::SetSpeed { .. } => "set_speed",
-            crate::command::PlayerCommand::CloseComplaint { .. } => "close_complaint",
-            crate::command::PlayerCommand::SetProductFee { .. } => "set_product_fee",
-            crate::command::PlayerCommand::SetRiskDial { .. } => "set_risk_dial",
-        };
+        let cmd_type = command.name();
 
         let payload = serde_json::to_string(command)?;
 
@@ -974,10 +1310,12 @@ impl SimStore {
                 segment_profit, customer_margin, profit_per_customer,
                 active_customers, avg_balance,
                 avg_revenue_per_customer, avg_cost_per_customer,
-                below_target_margin, cross_subsidy_recipient
+                below_target_margin, cross_subsidy_recipient,
+                hardship_enrollments, hardship_active, hardship_fees_waived
             ) VALUES (
                 ?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10,
-                ?11, ?12, ?13, ?14, ?15, ?16, ?17, ?18, ?19, ?20, ?21, ?22, ?23
+                ?11, ?12, ?13, ?14, ?15, ?16, ?17, ?18, ?19, ?20, ?21, ?22, ?23,
+                ?24, ?25, ?26
             )",
             params![
                 run_id,
@@ -1003,6 +1341,9 @@ impl SimStore {
                 pnl.avg_cost_per_customer,
                 if pnl.below_target_margin { 1i64 } else { 0 },
                 if pnl.cross_subsidy_recipient { 1i64 } else { 0 },
+                pnl.hardship_enrollments,
+                pnl.hardship_active,
+                pnl.hardship_fees_waived,
             ],
         )?;
         Ok(())
@@ -1030,7 +1371,8 @@ impl SimStore {
                     segment_profit, customer_margin, profit_per_customer,
                     active_customers, avg_balance,
                     avg_revenue_per_customer, avg_cost_per_customer,
-                    below_target_margin, cross_subsidy_recipient
+                    below_target_margin, cross_subsidy_recipient,
+                    hardship_enrollments, hardship_active, hardship_fees_waived
              FROM segment_pnl
              WHERE run_id = ?1 AND tick = ?2",
         )?;
@@ -1060,6 +1402,9 @@ impl SimStore {
                     avg_cost_per_customer: row.get(20)?,
                     below_target_margin: row.get::<_, i64>(21)? != 0,
                     cross_subsidy_recipient: row.get::<_, i64>(22)? != 0,
+                    hardship_enrollments: row.get(23)?,
+                    hardship_active: row.get(24)?,
+                    hardship_fees_waived: row.get(25)?,
                 })
             })?
             .collect::<Result<Vec<_>, _>>()?;
@@ -1100,147 +1445,6 @@ impl SimStore {
         Ok(balance)
     }
 
-    pub fn segment_fee_income(
-        &self,
-        run_id: &str,
-        segment: &str,
-        start_tick: Tick,
-        end_tick: Tick,
-    ) -> SimResult<f64> {
-        // Fee income from transactions in the fee categories, attributed to the customer's segment
-        let result: f64 = self
-            .conn
-            .query_row(
-                "SELECT COALESCE(SUM(t.amount), 0.0)
-             FROM transaction t
-             JOIN account a  ON t.account_id = a.account_id AND t.run_id = a.run_id
-             JOIN customer c ON a.customer_id = c.customer_id AND a.run_id = c.run_id
-             WHERE t.run_id = ?1
-               AND c.segment = ?2
-               AND t.category IN ('overdraft_fee','nsf_fee','monthly_fee','atm_fee','wire_fee')
-               AND t.tick >= ?3 AND t.tick <= ?4",
-                params![run_id, segment, start_tick as i64, end_tick as i64],
-                |row| row.get(0),
-            )
-            .unwrap_or(0.0);
-        Ok(result)
-    }
-
-    pub fn segment_new_customers(
-        &self,
-        run_id: &str,
-        segment: &str,
-        start_tick: Tick,
-        end_tick: Tick,
-    ) -> SimResult<i64> {
-        let count: i64 = self.conn.query_row(
-            "SELECT COUNT(*)
-             FROM customer
-             WHERE run_id = ?1 AND segment = ?2
-               AND open_tick >= ?3 AND open_tick <= ?4",
-            params![run_id, segment, start_tick as i64, end_tick as i64],
-            |row| row.get(0),
-        )?;
-        Ok(count)
-    }
-
-    pub fn segment_complaints(
-        &self,
-        run_id: &str,
-        segment: &str,
-        start_tick: Tick,
-        end_tick: Tick,
-    ) -> SimResult<crate::economics_subsystem::SegmentComplaints> {
-        let standard: i64 = self
-            .conn
-            .query_row(
-                "SELECT COUNT(*)
-             FROM complaint c
-             JOIN customer cu ON c.customer_id = cu.customer_id AND c.run_id = cu.run_id
-             WHERE c.run_id = ?1 AND cu.segment = ?2
-               AND c.priority = 'standard'
-               AND c.tick_opened >= ?3 AND c.tick_opened <= ?4",
-                params![run_id, segment, start_tick as i64, end_tick as i64],
-                |row| row.get(0),
-            )
-            .unwrap_or(0);
-
-        let high: i64 = self
-            .conn
-            .query_row(
-                "SELECT COUNT(*)
-             FROM complaint c
-             JOIN customer cu ON c.customer_id = cu.customer_id AND c.run_id = cu.run_id
-             WHERE c.run_id = ?1 AND cu.segment = ?2
-               AND c.priority = 'high'
-               AND c.tick_opened >= ?3 AND c.tick_opened <= ?4",
-                params![run_id, segment, start_tick as i64, end_tick as i64],
-                |row| row.get(0),
-            )
-            .unwrap_or(0);
-
-        let urgent: i64 = self
-            .conn
-            .query_row(
-                "SELECT COUNT(*)
-             FROM complaint c
-             JOIN customer cu ON c.customer_id = cu.customer_id AND c.run_id = cu.run_id
-             WHERE c.run_id = ?1 AND cu.segment = ?2
-               AND c.priority = 'urgent'
-               AND c.tick_opened >= ?3 AND c.tick_opened <= ?4",
-                params![run_id, segment, start_tick as i64, end_tick as i64],
-                |row| row.get(0),
-            )
-            .unwrap_or(0);
-
-        Ok(crate::economics_subsystem::SegmentComplaints {
-            standard,
-            high,
-            urgent,
-        })
-    }
-
-    pub fn segment_retention_offer_cost(
-        &self,
-        run_id: &str,
-        segment: &str,
-        start_tick: Tick,
-        end_tick: Tick,
-    ) -> SimResult<f64> {
-        let result: f64 = self
-            .conn
-            .query_row(
-                "SELECT COALESCE(SUM(co.bonus_paid), 0.0)
-             FROM customer_offer co
-             JOIN customer c ON co.customer_id = c.customer_id AND co.run_id = c.run_id
-             WHERE co.run_id = ?1 AND c.segment = ?2
-               AND co.offer_id LIKE '%retention%'
-               AND co.tick_paid >= ?3 AND co.tick_paid <= ?4",
-                params![run_id, segment, start_tick as i64, end_tick as i64],
-                |row| row.get(0),
-            )
-            .unwrap_or(0.0);
-        Ok(result)
-    }
-
-    pub fn segment_churned_customers(
-        &self,
-        run_id: &str,
-        segment: &str,
-        start_tick: Tick,
-        end_tick: Tick,
-    ) -> SimResult<i64> {
-        let count: i64 = self.conn.query_row(
-            "SELECT COUNT(*)
-             FROM customer
-             WHERE run_id = ?1 AND segment = ?2 AND status = 'churned'
-               AND close_tick >= ?3 AND close_tick <= ?4",
-            params![run_id, segment, start_tick as i64, end_tick as i64],
-            |row| row.get(0),
-        )?;
-        Ok(count)
-    }
-
     pub fn total_active_customers(&self, run_id: &str) -> SimResult<i64> {
         let count: i64 = self.conn.query_row(
             "SELECT COUNT(*) FROM customer WHERE run_id = ?1 AND status = 'active'",
@@ -1923,7 +2127,7 @@ impl SimStore {
         let mut stmt = self.conn.prepare(
             "SELECT authorization_id, account_id, merchant_name, merchant_category,
                     amount, tick_authorized, status, tick_cleared, cleared_amount,
-                    tick_settled, interchange_fee
+                    tick_settled, interchange_fee, merchant_id
              FROM authorization
              WHERE run_id = ? AND status = 'settled'
                AND tick_settled >= ? AND tick_settled <= ?
@@ -1943,36 +2147,25 @@ impl SimStore {
                 cleared_amount: row.get(8)?,
                 tick_settled: row.get(9)?,
                 interchange_fee: row.get(10)?,
+                merchant_id: row.get(11)?,
             })
         })?;
 
         rows.collect::<Result<Vec<_>, _>>().map_err(Into::into)
     }
 
-    pub fn insert_dispute(
-        &self,
-        run_id: &str,
-        dispute_id: &str,
-        authorization_id: &str,
-        account_id: &str,
-        customer_id: &str,
-        tick_filed: i64,
-        amount: f64,
-        merchant_name: &str,
-        merchant_category: &str,
-        reason: &str,
-        friendly_fraud_score: f64,
-    ) -> SimResult<()> {
+    /// Open a dispute as filed, under investigation.
+    pub fn insert_dispute(&self, run_id: &str, dispute: &NewDispute) -> SimResult<()> {
         self.conn.execute(
             "INSERT INTO card_dispute (
                 dispute_id, run_id, authorization_id, account_id, customer_id,
                 tick_filed, amount, merchant_name, merchant_category, reason,
-                status, friendly_fraud_score
-            ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, 'investigating', ?11)",
+                status, friendly_fraud_score, merchant_id
+            ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, 'investigating', ?11, ?12)",
             params![
-                dispute_id, run_id, authorization_id, account_id, customer_id,
-                tick_filed, amount, merchant_name, merchant_category, reason,
-                friendly_fraud_score
+                dispute.dispute_id, run_id, dispute.authorization_id, dispute.account_id, dispute.customer_id,
+                dispute.tick_filed, dispute.amount, dispute.merchant_name, dispute.merchant_category, dispute.reason,
+                dispute.friendly_fraud_score, dispute.merchant_id
             ],
         )?;
         Ok(())
@@ -2467,6 +2660,8 @@ fn complaint_row_mapper(
         resolution_code: row.get(11)?,
         amount_refunded: row.get(12)?,
         udaap_flag: row.get::<_, i32>(13)? != 0,
+        channel: row.get(14)?,
+        service_channel: row.get(15)?,
     })
 }
 
@@ -2485,6 +2680,8 @@ pub struct AuthorizationRow {
     pub cleared_amount: Option<f64>,
     pub tick_settled: Option<Tick>,
     pub interchange_fee: Option<f64>,
+    /// Phase 4.93: the merchant entity; None when merchants are off.
+    pub merchant_id: Option<String>,
 }
 
 #[derive(Debug, Clone)]
@@ -3287,6 +3484,23 @@ pub struct DisputeRow {
     pub chargeback_issued: bool,
 }
 
+/// A dispute as filed, for `insert_dispute`.
+#[derive(Debug, Clone)]
+pub struct NewDispute {
+    pub dispute_id: String,
+    pub authorization_id: String,
+    pub account_id: String,
+    pub customer_id: String,
+    pub tick_filed: i64,
+    pub amount: f64,
+    pub merchant_name: String,
+    pub merchant_category: String,
+    pub reason: String,
+    pub friendly_fraud_score: f64,
+    /// None for authorizations with no merchant on file.
+    pub merchant_id: Option<String>,
+}
+
 #[derive(Debug, Clone)]
 pub struct DisputeConfigRow {
     pub reason: String,
@@ -3534,3 +3748,28 @@ fn map_dispute_row(row: &rusqlite::Row<'_>) -> rusqlite::Result<DisputeRow> {
     })
 }
 
+fn pnl_row(row: &rusqlite::Row<'_>) -> rusqlite::Result<crate::economics_subsystem::PnLSnapshot> {
+    Ok(crate::economics_subsystem::PnLSnapshot {
+        tick: row.get::<_, i64>(0)? as u64,
+        period: row.get(1)?,
+        nii: row.get(2)?,
+        fee_income: row.get(3)?,
+        gross_income: row.get(4)?,
+        credit_loss: row.get(5)?,
+        fraud_loss: row.get(6)?,
+        opex: row.get(7)?,
+        complaint_cost: row.get(8)?,
+        pre_tax_profit: row.get(9)?,
+        nim: row.get(10)?,
+        efficiency_ratio: row.get(11)?,
+        avg_deposits: row.get(12)?,
+        avg_loans: row.get(13)?,
+        customer_count: row.get(14)?,
+        active_accounts: row.get(15)?,
+        operational_loss: row.get(16)?,
+        op_risk_capital: row.get(17)?,
+        accrual_adjustment: row.get(18)?,
+        app_fraud_loss: row.get(19)?,
+        app_customer_loss: row.get(20)?,
+    })
+}
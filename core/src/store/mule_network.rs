@@ -0,0 +1,206 @@
+//! Store methods for money mule networks (Phase 4.87).
+
+use crate::{error::SimResult, types::Tick};
+use rusqlite::params;
+use std::collections::HashSet;
+
+use super::SimStore;
+
+/// Row from the `mule_network` table.
+#[derive(Debug, Clone)]
+pub struct MuleNetworkRow {
+    pub network_id:            String,
+    pub controller_id:         String,
+    pub controller_account_id: String,
+    pub seeded_tick:           Tick,
+    pub mule_count:            i64,
+    /// 'active' | 'dissolved'
+    pub status:                String,
+    pub dissolved_tick:        Option<Tick>,
+}
+
+/// Row from the `mule_network_member` table.
+#[derive(Debug, Clone)]
+pub struct MuleMemberRow {
+    pub network_id:     String,
+    pub customer_id:    String,
+    pub account_id:     String,
+    pub recruited_tick: Tick,
+    pub next_tick:      Tick,
+    /// When the mule forwards what it holds, if it is holding any.
+    pub forward_tick:   Option<Tick>,
+    pub pending:        f64,
+}
+
+/// Row from the `mule_transfer` table.
+#[derive(Debug, Clone)]
+pub struct MuleTransferRow {
+    pub txn_id:       String,
+    pub network_id:   String,
+    pub customer_id:  String,
+    pub account_id:   String,
+    pub tick:         Tick,
+    /// 'inbound' | 'forward' | 'collect' | 'cash_out'
+    pub leg:          String,
+    pub counterparty: String,
+    pub amount:       f64,
+}
+
+fn network_row_mapper(row: &rusqlite::Row<'_>) -> rusqlite::Result<MuleNetworkRow> {
+    Ok(MuleNetworkRow {
+        network_id:            row.get(0)?,
+        controller_id:         row.get(1)?,
+        controller_account_id: row.get(2)?,
+        seeded_tick:           row.get::<_, i64>(3)? as Tick,
+        mule_count:            row.get(4)?,
+        status:                row.get(5)?,
+        dissolved_tick:        row.get::<_, Option<i64>>(6)?.map(|t| t as Tick),
+    })
+}
+
+const NETWORK_COLUMNS: &str =
+    "network_id, controller_id, controller_account_id, seeded_tick, mule_count, status, dissolved_tick";
+
+impl SimStore {
+    pub fn insert_mule_network(&self, run_id: &str, row: &MuleNetworkRow) -> SimResult<()> {
+        self.conn.execute(
+            "INSERT INTO mule_network
+             (run_id, network_id, controller_id, controller_account_id, seeded_tick, mule_count, status)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+            params![
+                run_id, row.network_id, row.controller_id, row.controller_account_id,
+                row.seeded_tick as i64, row.mule_count, row.status,
+            ],
+        )?;
+        Ok(())
+    }
+
+    /// Every network seeded in the run, oldest first.
+    pub fn mule_networks(&self, run_id: &str) -> SimResult<Vec<MuleNetworkRow>> {
+        let mut stmt = self.conn.prepare(&format!(
+            "SELECT {NETWORK_COLUMNS} FROM mule_network WHERE run_id = ?1 ORDER BY seeded_tick, network_id"
+        ))?;
+        let rows = stmt.query_map(params![run_id], network_row_mapper)?;
+        rows.collect::<Result<Vec<_>, _>>().map_err(Into::into)
+    }
+
+    pub fn active_mule_networks(&self, run_id: &str) -> SimResult<Vec<MuleNetworkRow>> {
+        let mut stmt = self.conn.prepare(&format!(
+            "SELECT {NETWORK_COLUMNS} FROM mule_network
+             WHERE run_id = ?1 AND status = 'active' ORDER BY seeded_tick, network_id"
+        ))?;
+        let rows = stmt.query_map(params![run_id], network_row_mapper)?;
+        rows.collect::<Result<Vec<_>, _>>().map_err(Into::into)
+    }
+
+    pub fn dissolve_mule_network(&self, run_id: &str, network_id: &str, tick: Tick) -> SimResult<()> {
+        self.conn.execute(
+            "UPDATE mule_network SET status = 'dissolved', dissolved_tick = ?3
+             WHERE run_id = ?1 AND network_id = ?2",
+            params![run_id, network_id, tick as i64],
+        )?;
+        Ok(())
+    }
+
+    /// Controllers and mules of every network, active or dissolved: no
+    /// customer is recruited twice.
+    pub fn mule_network_customers(&self, run_id: &str) -> SimResult<HashSet<String>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT controller_id FROM mule_network WHERE run_id = ?1
+             UNION
+             SELECT customer_id FROM mule_network_member WHERE run_id = ?1",
+        )?;
+        let rows = stmt.query_map(params![run_id], |row| row.get(0))?;
+        rows.collect::<Result<HashSet<_>, _>>().map_err(Into::into)
+    }
+
+    pub fn insert_mule_member(&self, run_id: &str, row: &MuleMemberRow) -> SimResult<()> {
+        self.conn.execute(
+            "INSERT INTO mule_network_member
+             (run_id, network_id, customer_id, account_id, recruited_tick, next_tick, forward_tick, pending)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+            params![
+                run_id, row.network_id, row.customer_id, row.account_id, row.recruited_tick as i64,
+                row.next_tick as i64, row.forward_tick.map(|t| t as i64), row.pending,
+            ],
+        )?;
+        Ok(())
+    }
+
+    /// The network's mules, in customer order.
+    pub fn mule_members(&self, run_id: &str, network_id: &str) -> SimResult<Vec<MuleMemberRow>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT network_id, customer_id, account_id, recruited_tick, next_tick, forward_tick, pending
+             FROM mule_network_member WHERE run_id = ?1 AND network_id = ?2 ORDER BY customer_id",
+        )?;
+        let rows = stmt.query_map(params![run_id, network_id], |row| {
+            Ok(MuleMemberRow {
+                network_id:     row.get(0)?,
+                customer_id:    row.get(1)?,
+                account_id:     row.get(2)?,
+                recruited_tick: row.get::<_, i64>(3)? as Tick,
+                next_tick:      row.get::<_, i64>(4)? as Tick,
+                forward_tick:   row.get::<_, Option<i64>>(5)?.map(|t| t as Tick),
+                pending:        row.get(6)?,
+            })
+        })?;
+        rows.collect::<Result<Vec<_>, _>>().map_err(Into::into)
+    }
+
+    pub fn update_mule_member(&self, run_id: &str, row: &MuleMemberRow) -> SimResult<()> {
+        self.conn.execute(
+            "UPDATE mule_network_member SET next_tick = ?3, forward_tick = ?4, pending = ?5
+             WHERE run_id = ?1 AND customer_id = ?2",
+            params![
+                run_id, row.customer_id, row.next_tick as i64, row.forward_tick.map(|t| t as i64),
+                row.pending,
+            ],
+        )?;
+        Ok(())
+    }
+
+    pub fn insert_mule_transfer(&self, run_id: &str, row: &MuleTransferRow) -> SimResult<()> {
+        self.conn.execute(
+            "INSERT INTO mule_transfer
+             (txn_id, run_id, network_id, customer_id, account_id, tick, leg, counterparty, amount)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
+            params![
+                row.txn_id, run_id, row.network_id, row.customer_id, row.account_id, row.tick as i64,
+                row.leg, row.counterparty, row.amount,
+            ],
+        )?;
+        Ok(())
+    }
+
+    /// Every leg booked in the run, in tick order.
+    pub fn mule_transfers(&self, run_id: &str) -> SimResult<Vec<MuleTransferRow>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT txn_id, network_id, customer_id, account_id, tick, leg, counterparty, amount
+             FROM mule_transfer WHERE run_id = ?1 ORDER BY tick, rowid",
+        )?;
+        let rows = stmt.query_map(params![run_id], |row| {
+            Ok(MuleTransferRow {
+                txn_id:       row.get(0)?,
+                network_id:   row.get(1)?,
+                customer_id:  row.get(2)?,
+                account_id:   row.get(3)?,
+                tick:         row.get::<_, i64>(4)? as Tick,
+                leg:          row.get(5)?,
+                counterparty: row.get(6)?,
+                amount:       row.get(7)?,
+            })
+        })?;
+        rows.collect::<Result<Vec<_>, _>>().map_err(Into::into)
+    }
+
+    /// Mules with credits coming in over the window, inclusive.
+    pub fn active_mules_between(&self, run_id: &str, start_tick: Tick, end_tick: Tick) -> SimResult<i64> {
+        let count: i64 = self.conn.query_row(
+            "SELECT COUNT(DISTINCT customer_id) FROM mule_transfer
+             WHERE run_id = ?1 AND leg = 'inbound' AND tick >= ?2 AND tick <= ?3",
+            params![run_id, start_tick as i64, end_tick as i64],
+            |row| row.get(0),
+        )?;
+        Ok(count)
+    }
+}
@@ -0,0 +1,164 @@
+//! Store methods for card network invoice reconciliation (Phase 4.6).
+
+use crate::{error::SimResult, types::Tick};
+use rusqlite::{params, OptionalExtension};
+
+/// Row from the `network_invoice` table.
+#[derive(Debug, Clone)]
+pub struct NetworkInvoiceRow {
+    pub invoice_id:             String,
+    pub tick_received:          Tick,
+    pub period_start:           Tick,
+    pub period_end:             Tick,
+    pub line_type:              String,
+    pub internal_amount:        f64,
+    pub invoiced_amount:        f64,
+    pub delta_amount:           f64,
+    pub error_type:             Option<String>,
+    pub status:                 String,
+    pub exception_id:           Option<String>,
+    pub recovery_deadline_tick: Option<Tick>,
+}
+
+use super::SimStore;
+
+fn invoice_row_mapper(row: &rusqlite::Row<'_>) -> rusqlite::Result<NetworkInvoiceRow> {
+    Ok(NetworkInvoiceRow {
+        invoice_id:             row.get(0)?,
+        tick_received:          row.get::<_, i64>(1)? as Tick,
+        period_start:           row.get::<_, i64>(2)? as Tick,
+        period_end:             row.get::<_, i64>(3)? as Tick,
+        line_type:              row.get(4)?,
+        internal_amount:        row.get(5)?,
+        invoiced_amount:        row.get(6)?,
+        delta_amount:           row.get(7)?,
+        error_type:             row.get(8)?,
+        status:                 row.get(9)?,
+        exception_id:           row.get(10)?,
+        recovery_deadline_tick: row.get::<_, Option<i64>>(11)?.map(|t| t as Tick),
+    })
+}
+
+const INVOICE_COLUMNS: &str =
+    "invoice_id, tick_received, period_start, period_end, line_type, internal_amount,
+     invoiced_amount, delta_amount, error_type, status, exception_id, recovery_deadline_tick";
+
+impl SimStore {
+    /// Interchange earned and card volume settled over (start_tick, end_tick].
+    pub fn card_settlement_totals(
+        &self,
+        run_id:     &str,
+        start_tick: Tick,
+        end_tick:   Tick,
+    ) -> SimResult<(f64, f64)> {
+        let totals = self.conn.query_row(
+            "SELECT COALESCE(SUM(interchange_fee), 0.0),
+                    COALESCE(SUM(COALESCE(cleared_amount, amount)), 0.0)
+             FROM authorization
+             WHERE run_id = ?1 AND status = 'settled'
+               AND tick_settled > ?2 AND tick_settled <= ?3",
+            params![run_id, start_tick as i64, end_tick as i64],
+            |row| Ok((row.get(0)?, row.get(1)?)),
+        )?;
+        Ok(totals)
+    }
+
+    /// Card disputes filed over (start_tick, end_tick] (each carries a network fee).
+    pub fn card_disputes_filed_between(
+        &self,
+        run_id:     &str,
+        start_tick: Tick,
+        end_tick:   Tick,
+    ) -> SimResult<i64> {
+        let count: i64 = self.conn.query_row(
+            "SELECT COUNT(*) FROM card_dispute
+             WHERE run_id = ?1 AND tick_filed > ?2 AND tick_filed <= ?3",
+            params![run_id, start_tick as i64, end_tick as i64],
+            |row| row.get(0),
+        )?;
+        Ok(count)
+    }
+
+    pub fn insert_network_invoice(&self, run_id: &str, row: &NetworkInvoiceRow) -> SimResult<()> {
+        self.conn.execute(
+            "INSERT INTO network_invoice
+             (run_id, invoice_id, tick_received, period_start, period_end, line_type,
+              internal_amount, invoiced_amount, delta_amount, error_type, status,
+              exception_id, recovery_deadline_tick)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13)",
+            params![
+                run_id, row.invoice_id, row.tick_received as i64, row.period_start as i64,
+                row.period_end as i64, row.line_type, row.internal_amount, row.invoiced_amount,
+                row.delta_amount, row.error_type, row.status, row.exception_id,
+                row.recovery_deadline_tick.map(|t| t as i64),
+            ],
+        )?;
+        Ok(())
+    }
+
+    pub fn get_network_invoice(
+        &self,
+        run_id:     &str,
+        invoice_id: &str,
+    ) -> SimResult<Option<NetworkInvoiceRow>> {
+        let row = self.conn.query_row(
+            &format!("SELECT {INVOICE_COLUMNS} FROM network_invoice
+                      WHERE run_id = ?1 AND invoice_id = ?2"),
+            params![run_id, invoice_id],
+            invoice_row_mapper,
+        ).optional()?;
+        Ok(row)
+    }
+
+    /// Invoice lines with an open discrepancy, oldest first.
+    pub fn open_network_invoice_exceptions(&self, run_id: &str) -> SimResult<Vec<NetworkInvoiceRow>> {
+        let mut stmt = self.conn.prepare(&format!(
+            "SELECT {INVOICE_COLUMNS} FROM network_invoice
+             WHERE run_id = ?1 AND status = 'exception'
+             ORDER BY tick_received, invoice_id"
+        ))?;
+        let rows = stmt.query_map(params![run_id], invoice_row_mapper)?;
+        rows.collect::<Result<Vec<_>, _>>().map_err(Into::into)
+    }
+
+    /// Close a discrepancy as recovered, denied or written off.
+    pub fn resolve_network_invoice(
+        &self,
+        run_id:           &str,
+        invoice_id:       &str,
+        status:           &str,
+        recovered_amount: f64,
+        tick:             Tick,
+    ) -> SimResult<()> {
+        self.conn.execute(
+            "UPDATE network_invoice
+             SET status = ?1, recovered_amount = ?2, tick_resolved = ?3
+             WHERE run_id = ?4 AND invoice_id = ?5",
+            params![status, recovered_amount, tick as i64, run_id, invoice_id],
+        )?;
+        Ok(())
+    }
+
+    // ── Test / summary helpers ────────────────────────────────────────
+
+    /// Number of invoice lines with the given status (for tests).
+    pub fn network_invoice_count(&self, run_id: &str, status: Option<&str>) -> SimResult<i64> {
+        let count: i64 = self.conn.query_row(
+            "SELECT COUNT(*) FROM network_invoice
+             WHERE run_id = ?1 AND (?2 IS NULL OR status = ?2)",
+            params![run_id, status],
+            |row| row.get(0),
+        )?;
+        Ok(count)
+    }
+
+    /// Total recovered from the network through claims (for tests).
+    pub fn network_recovered_total(&self, run_id: &str) -> SimResult<f64> {
+        let total: f64 = self.conn.query_row(
+            "SELECT COALESCE(SUM(recovered_amount), 0.0) FROM network_invoice WHERE run_id = ?1",
+            params![run_id],
+            |row| row.get(0),
+        )?;
+        Ok(total)
+    }
+}
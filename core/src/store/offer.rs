@@ -1,6 +1,6 @@
 use super::SimStore;
 use crate::{error::SimResult, types::Tick};
-use rusqlite::params;
+use rusqlite::{params, OptionalExtension};
 
 impl SimStore {
 pub fn insert_offer_config_state(
@@ -157,11 +157,23 @@ pub fn get_customer_snapshot(
         )
         .unwrap_or(0);
 
+    let zip_code: Option<String> = self
+        .conn
+        .query_row(
+            "SELECT zip_code FROM customer_address
+         WHERE run_id = ?1 AND customer_id = ?2
+         ORDER BY first_seen_tick LIMIT 1",
+            params![run_id, customer_id],
+            |row| row.get(0),
+        )
+        .optional()?;
+
     Ok(crate::offer_subsystem::CustomerSnapshot {
         segment,
         churn_risk,
         open_tick: open_tick as u64,
         product_count: product_count as usize,
+        zip_code,
     })
 }
 
@@ -349,6 +361,88 @@ pub fn sum_offer_bonuses_paid(
     Ok(sum)
 }
 
+// ── Phase 4.5: Targeting guardrail ─────────────────────────
+
+pub fn set_offer_active(&self, run_id: &str, offer_id: &str, active: bool, tick: Tick) -> SimResult<()> {
+    self.conn.execute(
+        "UPDATE offer_config_state SET active = ?1, modified_tick = ?2
+         WHERE run_id = ?3 AND offer_id = ?4",
+        params![if active { 1i64 } else { 0i64 }, tick as i64, run_id, offer_id],
+    )?;
+    Ok(())
+}
+
+/// Active customers with their zip and whether it is majority-minority
+/// (`None` when the zip has no demographics), in customer_id order.
+pub fn offer_targeting_population(
+    &self,
+    run_id: &str,
+) -> SimResult<Vec<(String, String, Option<bool>)>> {
+    let mut stmt = self.conn.prepare(
+        "SELECT c.customer_id, ca.zip_code, z.majority_minority
+         FROM customer c
+         JOIN customer_address ca ON ca.run_id = c.run_id AND ca.customer_id = c.customer_id
+         LEFT JOIN zip_demographics z ON z.run_id = ca.run_id AND z.zip_code = ca.zip_code
+         WHERE c.run_id = ?1 AND c.status = 'active'
+         GROUP BY c.customer_id
+         ORDER BY c.customer_id",
+    )?;
+    let rows = stmt
+        .query_map(params![run_id], |row| {
+            Ok((
+                row.get(0)?,
+                row.get(1)?,
+                row.get::<_, Option<i64>>(2)?.map(|v| v != 0),
+            ))
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(rows)
+}
+
+pub fn customer_has_offer(&self, run_id: &str, customer_id: &str, offer_id: &str) -> SimResult<bool> {
+    let count: i64 = self.conn.query_row(
+        "SELECT COUNT(*) FROM customer_offer
+         WHERE run_id = ?1 AND customer_id = ?2 AND offer_id = ?3",
+        params![run_id, customer_id, offer_id],
+        |row| row.get(0),
+    )?;
+    Ok(count > 0)
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn insert_offer_guardrail_assessment(
+    &self,
+    run_id: &str,
+    tick: Tick,
+    offer_id: &str,
+    exclude_zip_prefixes: &[String],
+    protected_exclusion_rate: f64,
+    control_exclusion_rate: f64,
+    disparity_ratio: Option<f64>,
+    outcome: &str,
+    launched: bool,
+) -> SimResult<()> {
+    self.conn.execute(
+        "INSERT INTO offer_guardrail_assessment (
+            run_id, tick, offer_id, exclude_zip_prefixes,
+            protected_exclusion_rate, control_exclusion_rate,
+            disparity_ratio, outcome, launched
+        ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
+        params![
+            run_id,
+            tick as i64,
+            offer_id,
+            serde_json::to_string(exclude_zip_prefixes)?,
+            protected_exclusion_rate,
+            control_exclusion_rate,
+            disparity_ratio,
+            outcome,
+            if launched { 1i64 } else { 0i64 },
+        ],
+    )?;
+    Ok(())
+}
+
 // ── Test helpers: offers ────────────────────────────────────
 
 pub fn matched_offer_count(&self, run_id: &str) -> SimResult<i64> {
@@ -394,6 +488,27 @@ pub fn bonus_seeker_count(&self, run_id: &str) -> SimResult<i64> {
         .map_err(Into::into)
 }
 
+pub fn offer_match_count(&self, run_id: &str, offer_id: &str) -> SimResult<i64> {
+    self.conn
+        .query_row(
+            "SELECT COUNT(*) FROM customer_offer WHERE run_id = ?1 AND offer_id = ?2",
+            params![run_id, offer_id],
+            |row| row.get(0),
+        )
+        .map_err(Into::into)
+}
+
+pub fn offer_guardrail_assessment_count(&self, run_id: &str, outcome: &str) -> SimResult<i64> {
+    self.conn
+        .query_row(
+            "SELECT COUNT(*) FROM offer_guardrail_assessment
+         WHERE run_id = ?1 AND outcome = ?2",
+            params![run_id, outcome],
+            |row| row.get(0),
+        )
+        .map_err(Into::into)
+}
+
 pub fn all_account_balances(&self, run_id: &str) -> SimResult<Vec<f64>> {
     let mut stmt = self.conn.prepare(
         "SELECT balance FROM account
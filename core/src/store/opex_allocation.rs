@@ -0,0 +1,98 @@
+//! Store methods for opex allocation rules (Phase 4.63).
+
+use crate::{config::OpexPoolConfig, error::SimResult, types::Tick};
+use rusqlite::{params, Row};
+
+use super::SimStore;
+
+/// Row from the `opex_allocation` table: one pool's share for one
+/// segment in one quarter.
+#[derive(Debug, Clone, PartialEq)]
+pub struct OpexAllocationRow {
+    pub tick:         Tick,
+    pub pool:         String,
+    pub segment:      String,
+    pub driver:       String,
+    /// The segment's customers, transactions or heads.
+    pub driver_value: f64,
+    pub share:        f64,
+    pub amount:       f64,
+}
+
+const ALLOCATION_COLUMNS: &str = "tick, pool, segment, driver, driver_value, share, amount";
+
+fn allocation_from_row(row: &Row) -> rusqlite::Result<OpexAllocationRow> {
+    Ok(OpexAllocationRow {
+        tick:         row.get::<_, i64>(0)? as Tick,
+        pool:         row.get(1)?,
+        segment:      row.get(2)?,
+        driver:       row.get(3)?,
+        driver_value: row.get(4)?,
+        share:        row.get(5)?,
+        amount:       row.get(6)?,
+    })
+}
+
+impl SimStore {
+    pub fn insert_opex_allocation_rule(&self, run_id: &str, pool: &OpexPoolConfig, tick: Tick) -> SimResult<()> {
+        self.conn.execute(
+            "INSERT INTO opex_allocation_rule (run_id, pool, driver, quarterly_cost, headcount, recorded_tick)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+            params![
+                run_id,
+                pool.pool,
+                pool.driver,
+                pool.quarterly_cost,
+                serde_json::to_string(&pool.headcount)?,
+                tick as i64,
+            ],
+        )?;
+        Ok(())
+    }
+
+    /// The rules the run allocates by, in the order recorded, with the
+    /// tick each was recorded.
+    pub fn opex_allocation_rules(&self, run_id: &str) -> SimResult<Vec<(OpexPoolConfig, Tick)>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT pool, driver, quarterly_cost, headcount, recorded_tick FROM opex_allocation_rule
+              WHERE run_id = ?1 ORDER BY rowid",
+        )?;
+        let rows = stmt
+            .query_map(params![run_id], |row| {
+                Ok((
+                    row.get::<_, String>(0)?,
+                    row.get::<_, String>(1)?,
+                    row.get::<_, f64>(2)?,
+                    row.get::<_, String>(3)?,
+                    row.get::<_, i64>(4)? as Tick,
+                ))
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+        rows.into_iter()
+            .map(|(pool, driver, quarterly_cost, headcount, tick)| {
+                let headcount = serde_json::from_str(&headcount)?;
+                Ok((OpexPoolConfig { pool, quarterly_cost, driver, headcount }, tick))
+            })
+            .collect()
+    }
+
+    pub fn insert_opex_allocation(&self, run_id: &str, row: &OpexAllocationRow) -> SimResult<()> {
+        self.conn.execute(
+            &format!("INSERT OR REPLACE INTO opex_allocation (run_id, {ALLOCATION_COLUMNS}) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)"),
+            params![
+                run_id, row.tick as i64, row.pool, row.segment, row.driver, row.driver_value, row.share,
+                row.amount,
+            ],
+        )?;
+        Ok(())
+    }
+
+    /// The quarter's split closing at `tick`, pool by pool.
+    pub fn opex_allocations(&self, run_id: &str, tick: Tick) -> SimResult<Vec<OpexAllocationRow>> {
+        let mut stmt = self.conn.prepare(&format!(
+            "SELECT {ALLOCATION_COLUMNS} FROM opex_allocation WHERE run_id = ?1 AND tick = ?2 ORDER BY rowid"
+        ))?;
+        let rows = stmt.query_map(params![run_id, tick as i64], allocation_from_row)?;
+        rows.collect::<Result<Vec<_>, _>>().map_err(Into::into)
+    }
+}
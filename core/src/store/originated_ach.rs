@@ -0,0 +1,197 @@
+//! Store methods for customer-originated ACH and ODFI return-rate
+//! monitoring (Phase 4.10).
+
+use crate::{error::SimResult, types::Tick};
+use rusqlite::params;
+
+/// Row from the `originated_ach` table.
+#[derive(Debug, Clone)]
+pub struct OriginatedAchRow {
+    pub entry_id:     String,
+    pub account_id:   String,
+    pub customer_id:  String,
+    pub entry_type:   String,
+    pub counterparty: String,
+    pub amount:       f64,
+    pub tick:         Tick,
+    pub unauthorized: bool,
+    pub status:       String,
+    pub return_code:  Option<String>,
+}
+
+/// Row from the `odfi_return_review` table.
+#[derive(Debug, Clone)]
+pub struct OdfiReturnReviewRow {
+    pub tick:                Tick,
+    pub debit_entries:       i64,
+    pub unauthorized_rate:   f64,
+    pub administrative_rate: f64,
+    pub overall_rate:        f64,
+    pub breached:            Option<String>,
+    pub network_fine:        f64,
+}
+
+/// Originated debit entries and their returns over a review window.
+#[derive(Debug, Clone, Default)]
+pub struct OdfiReturnCounts {
+    pub debit_entries:  i64,
+    pub unauthorized:   i64,
+    pub administrative: i64,
+    pub returned:       i64,
+}
+
+use super::SimStore;
+
+fn entry_row_mapper(row: &rusqlite::Row<'_>) -> rusqlite::Result<OriginatedAchRow> {
+    Ok(OriginatedAchRow {
+        entry_id:     row.get(0)?,
+        account_id:   row.get(1)?,
+        customer_id:  row.get(2)?,
+        entry_type:   row.get(3)?,
+        counterparty: row.get(4)?,
+        amount:       row.get(5)?,
+        tick:         row.get::<_, i64>(6)? as u64,
+        unauthorized: row.get::<_, i64>(7)? != 0,
+        status:       row.get(8)?,
+        return_code:  row.get(9)?,
+    })
+}
+
+const ENTRY_COLUMNS: &str = "entry_id, account_id, customer_id, entry_type, counterparty,
+     amount, tick, unauthorized, status, return_code";
+
+impl SimStore {
+    pub fn insert_originated_ach(&self, run_id: &str, row: &OriginatedAchRow) -> SimResult<()> {
+        self.conn.execute(
+            "INSERT INTO originated_ach
+             (run_id, entry_id, account_id, customer_id, entry_type, counterparty,
+              amount, tick, unauthorized, status, return_code)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11)",
+            params![
+                run_id, row.entry_id, row.account_id, row.customer_id, row.entry_type,
+                row.counterparty, row.amount, row.tick as i64, row.unauthorized as i64,
+                row.status, row.return_code,
+            ],
+        )?;
+        Ok(())
+    }
+
+    /// Entries originated at `tick` that went out to the network.
+    pub fn submitted_originated_ach(&self, run_id: &str, tick: Tick) -> SimResult<Vec<OriginatedAchRow>> {
+        let mut stmt = self.conn.prepare(&format!(
+            "SELECT {ENTRY_COLUMNS} FROM originated_ach
+             WHERE run_id = ?1 AND tick = ?2 AND status = 'submitted'
+             ORDER BY entry_id",
+        ))?;
+        let rows = stmt.query_map(params![run_id, tick as i64], entry_row_mapper)?;
+        rows.collect::<Result<Vec<_>, _>>().map_err(Into::into)
+    }
+
+    pub fn mark_originated_ach_returned(
+        &self,
+        run_id:      &str,
+        entry_id:    &str,
+        return_code: &str,
+        tick:        Tick,
+    ) -> SimResult<()> {
+        self.conn.execute(
+            "UPDATE originated_ach SET status = 'returned', return_code = ?1, return_tick = ?2
+             WHERE run_id = ?3 AND entry_id = ?4",
+            params![return_code, tick as i64, run_id, entry_id],
+        )?;
+        Ok(())
+    }
+
+    /// Debit entries (external transfers) originated over [start_tick,
+    /// end_tick) and how many came back, by NACHA return category.
+    /// Unauthorized: R05/R07/R10/R29; administrative: R02/R03/R04.
+    pub fn odfi_return_counts(
+        &self,
+        run_id:     &str,
+        start_tick: Tick,
+        end_tick:   Tick,
+    ) -> SimResult<OdfiReturnCounts> {
+        let counts = self.conn.query_row(
+            "SELECT COUNT(*),
+                    COALESCE(SUM(return_code IN ('R05', 'R07', 'R10', 'R29')), 0),
+                    COALESCE(SUM(return_code IN ('R02', 'R03', 'R04')), 0),
+                    COALESCE(SUM(status = 'returned'), 0)
+             FROM originated_ach
+             WHERE run_id = ?1 AND entry_type = 'external_transfer'
+               AND status IN ('submitted', 'returned') AND tick >= ?2 AND tick < ?3",
+            params![run_id, start_tick as i64, end_tick as i64],
+            |row| {
+                Ok(OdfiReturnCounts {
+                    debit_entries:  row.get(0)?,
+                    unauthorized:   row.get(1)?,
+                    administrative: row.get(2)?,
+                    returned:       row.get(3)?,
+                })
+            },
+        )?;
+        Ok(counts)
+    }
+
+    pub fn insert_odfi_return_review(&self, run_id: &str, row: &OdfiReturnReviewRow) -> SimResult<()> {
+        self.conn.execute(
+            "INSERT OR REPLACE INTO odfi_return_review
+             (run_id, tick, debit_entries, unauthorized_rate, administrative_rate,
+              overall_rate, breached, network_fine)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+            params![
+                run_id, row.tick as i64, row.debit_entries, row.unauthorized_rate,
+                row.administrative_rate, row.overall_rate, row.breached, row.network_fine,
+            ],
+        )?;
+        Ok(())
+    }
+
+    /// Network fines from ODFI reviews held over [start_tick, end_tick].
+    pub fn odfi_fines_between(
+        &self,
+        run_id:     &str,
+        start_tick: Tick,
+        end_tick:   Tick,
+    ) -> SimResult<f64> {
+        let total: f64 = self.conn.query_row(
+            "SELECT COALESCE(SUM(network_fine), 0.0) FROM odfi_return_review
+             WHERE run_id = ?1 AND tick >= ?2 AND tick <= ?3",
+            params![run_id, start_tick as i64, end_tick as i64],
+            |row| row.get(0),
+        )?;
+        Ok(total)
+    }
+
+    // ── Test / summary helpers ────────────────────────────────────────
+
+    /// All originated entries for a run, oldest first (for tests).
+    pub fn originated_ach_entries(&self, run_id: &str) -> SimResult<Vec<OriginatedAchRow>> {
+        let mut stmt = self.conn.prepare(&format!(
+            "SELECT {ENTRY_COLUMNS} FROM originated_ach WHERE run_id = ?1
+             ORDER BY tick, entry_id",
+        ))?;
+        let rows = stmt.query_map(params![run_id], entry_row_mapper)?;
+        rows.collect::<Result<Vec<_>, _>>().map_err(Into::into)
+    }
+
+    /// All ODFI return-rate reviews for a run, oldest first (for tests).
+    pub fn odfi_return_reviews(&self, run_id: &str) -> SimResult<Vec<OdfiReturnReviewRow>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT tick, debit_entries, unauthorized_rate, administrative_rate,
+                    overall_rate, breached, network_fine
+             FROM odfi_return_review WHERE run_id = ?1 ORDER BY tick",
+        )?;
+        let rows = stmt.query_map(params![run_id], |row| {
+            Ok(OdfiReturnReviewRow {
+                tick:                row.get::<_, i64>(0)? as u64,
+                debit_entries:       row.get(1)?,
+                unauthorized_rate:   row.get(2)?,
+                administrative_rate: row.get(3)?,
+                overall_rate:        row.get(4)?,
+                breached:            row.get(5)?,
+                network_fine:        row.get(6)?,
+            })
+        })?;
+        rows.collect::<Result<Vec<_>, _>>().map_err(Into::into)
+    }
+}
@@ -0,0 +1,173 @@
+//! Store methods for rail cutoff times and next-day rolls (Phase 4.8).
+
+use crate::{error::SimResult, types::Tick};
+use rusqlite::params;
+
+use super::{SimStore, TransactionForSettlement};
+
+/// Row from the `payment_cutoff_roll` table.
+#[derive(Debug, Clone)]
+pub struct CutoffRollRow {
+    pub txn_id:          String,
+    pub rail_id:         String,
+    pub account_id:      String,
+    pub amount:          f64,
+    pub direction:       String,
+    pub initiated_hour:  u64,
+    pub booked_tick:     Tick,
+    pub processing_tick: Tick,
+}
+
+impl SimStore {
+    pub fn insert_cutoff_roll(&self, run_id: &str, row: &CutoffRollRow) -> SimResult<()> {
+        self.conn.execute(
+            "INSERT OR REPLACE INTO payment_cutoff_roll
+             (run_id, txn_id, rail_id, account_id, amount, direction,
+              initiated_hour, booked_tick, processing_tick)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
+            params![
+                run_id, row.txn_id, row.rail_id, row.account_id, row.amount, row.direction,
+                row.initiated_hour as i64, row.booked_tick as i64, row.processing_tick as i64,
+            ],
+        )?;
+        Ok(())
+    }
+
+    /// Non-card items initiated on a rail at `tick` (already settled by the
+    /// transaction subsystem, not yet assigned to a processing window).
+    /// Every item is booked; only those in `ROLLABLE_CATEGORIES` can roll.
+    pub fn transactions_initiated_on_rail(
+        &self,
+        run_id:  &str,
+        rail_id: &str,
+        tick:    Tick,
+    ) -> SimResult<Vec<TransactionForSettlement>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT txn_id, account_id, amount, direction, category
+             FROM transactions
+             WHERE run_id = ?1 AND payment_rail_id = ?2 AND tick = ?3
+               AND settlement_status = 'settled'
+             ORDER BY account_id ASC, amount ASC, txn_id ASC",
+        )?;
+        let rows = stmt.query_map(params![run_id, rail_id, tick as i64], |row| {
+            Ok(TransactionForSettlement {
+                txn_id:     row.get(0)?,
+                account_id: row.get(1)?,
+                amount:     row.get(2)?,
+                direction:  row.get(3)?,
+                category:   row.get(4)?,
+            })
+        })?;
+        rows.collect::<Result<Vec<_>, _>>().map_err(Into::into)
+    }
+
+    /// Items that missed an earlier cutoff and settle on `processing_tick`.
+    pub fn transactions_rolled_into(
+        &self,
+        run_id:          &str,
+        rail_id:         &str,
+        processing_tick: Tick,
+    ) -> SimResult<Vec<TransactionForSettlement>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT r.txn_id, r.account_id, r.amount, r.direction, t.category
+             FROM payment_cutoff_roll r
+             JOIN transactions t ON t.run_id = r.run_id AND t.txn_id = r.txn_id
+             WHERE r.run_id = ?1 AND r.rail_id = ?2 AND r.processing_tick = ?3
+               AND t.settlement_status = 'rolled'
+             ORDER BY r.account_id ASC, r.amount ASC",
+        )?;
+        let rows = stmt.query_map(params![run_id, rail_id, processing_tick as i64], |row| {
+            Ok(TransactionForSettlement {
+                txn_id:     row.get(0)?,
+                account_id: row.get(1)?,
+                amount:     row.get(2)?,
+                direction:  row.get(3)?,
+                category:   row.get(4)?,
+            })
+        })?;
+        rows.collect::<Result<Vec<_>, _>>().map_err(Into::into)
+    }
+
+    /// (debits, credits, count) of items rolled into `processing_tick` that
+    /// have settled.
+    pub fn rolled_settlement_totals(
+        &self,
+        run_id:          &str,
+        rail_id:         &str,
+        processing_tick: Tick,
+    ) -> SimResult<(f64, f64, i64)> {
+        let totals = self.conn.query_row(
+            "SELECT
+                 COALESCE(SUM(CASE WHEN r.direction = 'debit' THEN r.amount ELSE 0 END), 0.0),
+                 COALESCE(SUM(CASE WHEN r.direction = 'credit' THEN r.amount ELSE 0 END), 0.0),
+                 COUNT(*)
+             FROM payment_cutoff_roll r
+             JOIN transactions t ON t.run_id = r.run_id AND t.txn_id = r.txn_id
+             WHERE r.run_id = ?1 AND r.rail_id = ?2 AND r.processing_tick = ?3
+               AND t.settlement_status = 'settled'",
+            params![run_id, rail_id, processing_tick as i64],
+            |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)),
+        )?;
+        Ok(totals)
+    }
+
+    /// Net roll on `tick`: items booked that day but rolled out, minus items
+    /// rolled in from the day before. This is the booked-minus-settled gap
+    /// the roll alone explains.
+    pub fn net_cutoff_roll(&self, run_id: &str, rail_id: &str, tick: Tick) -> SimResult<f64> {
+        let net: f64 = self.conn.query_row(
+            "SELECT COALESCE(SUM(CASE WHEN booked_tick = ?3 THEN amount ELSE 0 END), 0.0)
+                  - COALESCE(SUM(CASE WHEN processing_tick = ?3 THEN amount ELSE 0 END), 0.0)
+             FROM payment_cutoff_roll
+             WHERE run_id = ?1 AND rail_id = ?2 AND (booked_tick = ?3 OR processing_tick = ?3)",
+            params![run_id, rail_id, tick as i64],
+            |row| row.get(0),
+        )?;
+        Ok(net)
+    }
+
+    // ── Test / summary helpers ────────────────────────────────────────
+
+    /// Items on a rail that rolled past the cutoff (for tests).
+    pub fn cutoff_rolls(&self, run_id: &str, rail_id: &str) -> SimResult<Vec<CutoffRollRow>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT txn_id, rail_id, account_id, amount, direction,
+                    initiated_hour, booked_tick, processing_tick
+             FROM payment_cutoff_roll WHERE run_id = ?1 AND rail_id = ?2
+             ORDER BY booked_tick, txn_id",
+        )?;
+        let rows = stmt.query_map(params![run_id, rail_id], |row| {
+            Ok(CutoffRollRow {
+                txn_id:          row.get(0)?,
+                rail_id:         row.get(1)?,
+                account_id:      row.get(2)?,
+                amount:          row.get(3)?,
+                direction:       row.get(4)?,
+                initiated_hour:  row.get::<_, i64>(5)? as u64,
+                booked_tick:     row.get::<_, i64>(6)? as u64,
+                processing_tick: row.get::<_, i64>(7)? as u64,
+            })
+        })?;
+        rows.collect::<Result<Vec<_>, _>>().map_err(Into::into)
+    }
+
+    /// Settlement status of a single transaction (for tests).
+    pub fn transaction_settlement_status(&self, run_id: &str, txn_id: &str) -> SimResult<String> {
+        let status: String = self.conn.query_row(
+            "SELECT settlement_status FROM transactions WHERE run_id = ?1 AND txn_id = ?2",
+            params![run_id, txn_id],
+            |row| row.get(0),
+        )?;
+        Ok(status)
+    }
+
+    /// Category of a single transaction (for tests).
+    pub fn transaction_category(&self, run_id: &str, txn_id: &str) -> SimResult<String> {
+        let category: String = self.conn.query_row(
+            "SELECT category FROM transactions WHERE run_id = ?1 AND txn_id = ?2",
+            params![run_id, txn_id],
+            |row| row.get(0),
+        )?;
+        Ok(category)
+    }
+}
@@ -13,8 +13,8 @@ impl SimStore {
         self.conn.execute(
             "INSERT INTO authorization (
                 authorization_id, run_id, account_id, merchant_name, merchant_category,
-                amount, tick_authorized, status
-            ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+                amount, tick_authorized, status, merchant_id
+            ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
             params![
                 auth.authorization_id,
                 run_id,
@@ -24,6 +24,7 @@ impl SimStore {
                 auth.amount,
                 auth.tick_authorized as i64,
                 auth.status,
+                auth.merchant_id,
             ],
         )?;
         Ok(())
@@ -37,7 +38,7 @@ impl SimStore {
         let row = self.conn.query_row(
             "SELECT authorization_id, account_id, merchant_name, merchant_category,
                     amount, tick_authorized, status, tick_cleared, cleared_amount,
-                    tick_settled, interchange_fee
+                    tick_settled, interchange_fee, merchant_id
              FROM authorization
              WHERE run_id = ?1 AND authorization_id = ?2",
             params![run_id, auth_id],
@@ -54,6 +55,7 @@ impl SimStore {
                     cleared_amount: row.get(8)?,
                     tick_settled: row.get::<_, Option<i64>>(9)?.map(|t| t as u64),
                     interchange_fee: row.get(10)?,
+                    merchant_id: row.get(11)?,
                 })
             },
         )?;
@@ -100,7 +102,7 @@ impl SimStore {
         let mut stmt = self.conn.prepare(
             "SELECT authorization_id, account_id, merchant_name, merchant_category,
                     amount, tick_authorized, status, tick_cleared, cleared_amount,
-                    tick_settled, interchange_fee
+                    tick_settled, interchange_fee, merchant_id
              FROM authorization
              WHERE run_id = ?1 AND status = 'pending' AND tick_authorized <= ?2",
         )?;
@@ -118,6 +120,7 @@ impl SimStore {
                     cleared_amount: row.get(8)?,
                     tick_settled: row.get::<_, Option<i64>>(9)?.map(|t| t as u64),
                     interchange_fee: row.get(10)?,
+                    merchant_id: row.get(11)?,
                 })
             })?
             .collect::<Result<Vec<_>, _>>()?;
@@ -140,7 +143,7 @@ impl SimStore {
         let mut stmt = self.conn.prepare(
             "SELECT authorization_id, account_id, merchant_name, merchant_category,
                     amount, tick_authorized, status, tick_cleared, cleared_amount,
-                    tick_settled, interchange_fee
+                    tick_settled, interchange_fee, merchant_id
              FROM authorization
              WHERE run_id = ?1 AND account_id = ?2 AND status = 'pending'",
         )?;
@@ -158,6 +161,7 @@ impl SimStore {
                     cleared_amount: row.get(8)?,
                     tick_settled: row.get::<_, Option<i64>>(9)?.map(|t| t as u64),
                     interchange_fee: row.get(10)?,
+                    merchant_id: row.get(11)?,
                 })
             })?
             .collect::<Result<Vec<_>, _>>()?;
@@ -174,7 +178,7 @@ impl SimStore {
         let mut stmt = self.conn.prepare(
             "SELECT authorization_id, account_id, merchant_name, merchant_category,
                     amount, tick_authorized, status, tick_cleared, cleared_amount,
-                    tick_settled, interchange_fee
+                    tick_settled, interchange_fee, merchant_id
              FROM authorization
              WHERE run_id = ?1 AND status = 'pending' AND tick_authorized = ?2
              ORDER BY authorization_id ASC",
@@ -193,6 +197,7 @@ impl SimStore {
                     cleared_amount: row.get(8)?,
                     tick_settled: row.get::<_, Option<i64>>(9)?.map(|t| t as u64),
                     interchange_fee: row.get(10)?,
+                    merchant_id: row.get(11)?,
                 })
             })?
             .collect::<Result<Vec<_>, _>>()?;
@@ -209,7 +214,7 @@ impl SimStore {
         let mut stmt = self.conn.prepare(
             "SELECT authorization_id, account_id, merchant_name, merchant_category,
                     amount, tick_authorized, status, tick_cleared, cleared_amount,
-                    tick_settled, interchange_fee
+                    tick_settled, interchange_fee, merchant_id
              FROM authorization
              WHERE run_id = ?1 AND status = 'captured' AND tick_cleared = ?2
              ORDER BY authorization_id ASC",
@@ -228,6 +233,7 @@ impl SimStore {
                     cleared_amount: row.get(8)?,
                     tick_settled: row.get::<_, Option<i64>>(9)?.map(|t| t as u64),
                     interchange_fee: row.get(10)?,
+                    merchant_id: row.get(11)?,
                 })
             })?
             .collect::<Result<Vec<_>, _>>()?;
@@ -0,0 +1,90 @@
+//! Store methods for the post-run analysis report (Phase 4.19).
+
+use crate::{error::SimResult, types::Tick};
+use rusqlite::params;
+
+/// An unauthorized originated ACH entry: a takeover pull or a mule drain.
+#[derive(Debug, Clone)]
+pub struct UnauthorizedEntryRow {
+    pub entry_id:     String,
+    pub customer_id:  String,
+    /// "external_transfer" (takeover pull) | "external_transfer_out" (mule drain)
+    pub entry_type:   String,
+    pub counterparty: String,
+    pub amount:       f64,
+    pub tick:         Tick,
+    pub status:       String,
+}
+
+/// A customer onboarded, or turned away, on a synthetic identity.
+#[derive(Debug, Clone)]
+pub struct SyntheticCustomerRow {
+    pub customer_id: String,
+    pub open_tick:   Tick,
+    pub status:      String,
+    pub close_tick:  Option<Tick>,
+}
+
+use super::SimStore;
+
+impl SimStore {
+    /// Every unauthorized originated ACH entry for a run, declined or not.
+    pub fn unauthorized_ach_entries(&self, run_id: &str) -> SimResult<Vec<UnauthorizedEntryRow>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT entry_id, customer_id, entry_type, counterparty, amount, tick, status
+             FROM originated_ach WHERE run_id = ?1 AND unauthorized = 1
+             ORDER BY customer_id, tick, entry_id",
+        )?;
+        let rows = stmt.query_map(params![run_id], |row| {
+            Ok(UnauthorizedEntryRow {
+                entry_id:     row.get(0)?,
+                customer_id:  row.get(1)?,
+                entry_type:   row.get(2)?,
+                counterparty: row.get(3)?,
+                amount:       row.get(4)?,
+                tick:         row.get::<_, i64>(5)? as u64,
+                status:       row.get(6)?,
+            })
+        })?;
+        rows.collect::<Result<Vec<_>, _>>().map_err(Into::into)
+    }
+
+    pub fn synthetic_identity_customers(&self, run_id: &str) -> SimResult<Vec<SyntheticCustomerRow>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT c.customer_id, c.open_tick, c.status, c.close_tick
+             FROM customer c
+             JOIN customer_identity i ON i.customer_id = c.customer_id
+             WHERE c.run_id = ?1 AND i.identity_type = 'synthetic'
+             ORDER BY c.open_tick, c.customer_id",
+        )?;
+        let rows = stmt.query_map(params![run_id], |row| {
+            Ok(SyntheticCustomerRow {
+                customer_id: row.get(0)?,
+                open_tick:   row.get::<_, i64>(1)? as u64,
+                status:      row.get(2)?,
+                close_tick:  row.get::<_, Option<i64>>(3)?.map(|t| t as u64),
+            })
+        })?;
+        rows.collect::<Result<Vec<_>, _>>().map_err(Into::into)
+    }
+
+    /// First tick at or after `since` that an AML alert, fraud pattern or
+    /// account fraud alert named the customer.
+    pub fn first_detection_tick(&self, run_id: &str, customer_id: &str, since: Tick) -> SimResult<Option<Tick>> {
+        let tick: Option<i64> = self.conn.query_row(
+            "SELECT MIN(tick) FROM (
+                 SELECT tick FROM aml_alert WHERE run_id = ?1 AND customer_id = ?2
+                 UNION ALL
+                 SELECT detected_tick AS tick FROM fraud_pattern
+                 WHERE run_id = ?1 AND primary_customer_id = ?2
+                 UNION ALL
+                 SELECT f.tick FROM fraud_alert f
+                 JOIN account a ON a.account_id = f.entity_id
+                 WHERE f.run_id = ?1 AND f.entity_type = 'account' AND a.customer_id = ?2
+             ) WHERE tick >= ?3",
+            params![run_id, customer_id, since as i64],
+            |row| row.get(0),
+        )?;
+        Ok(tick.map(|t| t as u64))
+    }
+}
@@ -0,0 +1,153 @@
+//! Store methods for benefit deposits and protected funds (Phase 4.2).
+
+use crate::{error::SimResult, types::Tick};
+use rusqlite::params;
+
+/// Row from the `benefit_recipient` table.
+#[derive(Debug, Clone)]
+pub struct BenefitRecipientRow {
+    pub account_id:     String,
+    pub customer_id:    String,
+    pub benefit_type:   String,
+    pub monthly_amount: f64,
+}
+
+/// One bank-initiated debit checked against the protected amount.
+#[derive(Debug, Clone)]
+pub struct ProtectedFundsEventRow {
+    pub account_id:       String,
+    pub customer_id:      String,
+    pub debit_type:       String,
+    pub requested_amount: f64,
+    pub applied_amount:   f64,
+    pub protected_amount: f64,
+    pub outcome:          String,
+    pub violation:        bool,
+}
+
+use super::SimStore;
+
+impl SimStore {
+    /// Enrol an account as a federal benefit recipient.
+    pub fn insert_benefit_recipient(
+        &self,
+        run_id: &str,
+        row:    &BenefitRecipientRow,
+        tick:   Tick,
+    ) -> SimResult<()> {
+        self.conn.execute(
+            "INSERT INTO benefit_recipient
+             (run_id, account_id, customer_id, benefit_type, monthly_amount, enrolled_tick)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+            params![
+                run_id, row.account_id, row.customer_id, row.benefit_type,
+                row.monthly_amount, tick as i64,
+            ],
+        )?;
+        Ok(())
+    }
+
+    /// All enrolled benefit recipients, in account order.
+    pub fn benefit_recipients(&self, run_id: &str) -> SimResult<Vec<BenefitRecipientRow>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT account_id, customer_id, benefit_type, monthly_amount
+             FROM benefit_recipient WHERE run_id = ?1 ORDER BY account_id",
+        )?;
+        let rows = stmt.query_map(params![run_id], |row| {
+            Ok(BenefitRecipientRow {
+                account_id:     row.get(0)?,
+                customer_id:    row.get(1)?,
+                benefit_type:   row.get(2)?,
+                monthly_amount: row.get(3)?,
+            })
+        })?;
+        rows.collect::<Result<Vec<_>, _>>().map_err(Into::into)
+    }
+
+    /// Sum of federal benefit credits posted to an account in (since_tick, tick].
+    pub fn benefit_deposits_since(
+        &self,
+        run_id:     &str,
+        account_id: &str,
+        since_tick: Tick,
+        tick:       Tick,
+    ) -> SimResult<f64> {
+        let total: f64 = self.conn.query_row(
+            "SELECT COALESCE(SUM(amount), 0.0) FROM transactions
+             WHERE run_id = ?1 AND account_id = ?2 AND category = 'federal_benefit'
+               AND tick > ?3 AND tick <= ?4",
+            params![run_id, account_id, since_tick as i64, tick as i64],
+            |row| row.get(0),
+        )?;
+        Ok(total)
+    }
+
+    /// Record the outcome of a protected-funds check.
+    pub fn insert_protected_funds_event(
+        &self,
+        run_id: &str,
+        tick:   Tick,
+        row:    &ProtectedFundsEventRow,
+    ) -> SimResult<()> {
+        self.conn.execute(
+            "INSERT INTO protected_funds_event
+             (run_id, tick, account_id, customer_id, debit_type, requested_amount,
+              applied_amount, protected_amount, outcome, violation)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)",
+            params![
+                run_id, tick as i64, row.account_id, row.customer_id, row.debit_type,
+                row.requested_amount, row.applied_amount, row.protected_amount,
+                row.outcome, row.violation as i64,
+            ],
+        )?;
+        Ok(())
+    }
+
+    // ── Test / summary helpers ────────────────────────────────────────
+
+    /// Number of enrolled benefit recipients (for tests).
+    pub fn benefit_recipient_count(&self, run_id: &str) -> SimResult<i64> {
+        let count: i64 = self.conn.query_row(
+            "SELECT COUNT(*) FROM benefit_recipient WHERE run_id = ?1",
+            params![run_id],
+            |row| row.get(0),
+        )?;
+        Ok(count)
+    }
+
+    /// Number of federal benefit credits posted (for tests).
+    pub fn benefit_deposit_count(&self, run_id: &str) -> SimResult<i64> {
+        let count: i64 = self.conn.query_row(
+            "SELECT COUNT(*) FROM transactions
+             WHERE run_id = ?1 AND category = 'federal_benefit'",
+            params![run_id],
+            |row| row.get(0),
+        )?;
+        Ok(count)
+    }
+
+    /// Protected-funds checks with the given outcome, or violations when
+    /// `outcome` is "violation" (for tests).
+    pub fn protected_funds_event_count(&self, run_id: &str, outcome: &str) -> SimResult<i64> {
+        let count: i64 = self.conn.query_row(
+            "SELECT COUNT(*) FROM protected_funds_event
+             WHERE run_id = ?1
+               AND (outcome = ?2 OR (?2 = 'violation' AND violation = 1))",
+            params![run_id, outcome],
+            |row| row.get(0),
+        )?;
+        Ok(count)
+    }
+
+    /// Protected-funds checks of a debit type with nothing protected at the
+    /// time, and how many of them were booked as violations (for tests).
+    pub fn unprotected_debit_counts(&self, run_id: &str, debit_type: &str) -> SimResult<(i64, i64)> {
+        let counts = self.conn.query_row(
+            "SELECT COUNT(*), COALESCE(SUM(violation), 0) FROM protected_funds_event
+             WHERE run_id = ?1 AND debit_type = ?2 AND protected_amount <= 0.0",
+            params![run_id, debit_type],
+            |row| Ok((row.get(0)?, row.get(1)?)),
+        )?;
+        Ok(counts)
+    }
+}
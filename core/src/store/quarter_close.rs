@@ -0,0 +1,265 @@
+//! Store methods for the quarter-end close (Phase 4.62).
+
+use crate::{error::SimResult, types::Tick};
+use rusqlite::{params, OptionalExtension, Row};
+
+use super::SimStore;
+
+/// Row from the `quarter_close` table.
+#[derive(Debug, Clone, PartialEq)]
+pub struct QuarterCloseRow {
+    pub period:                String,
+    /// The quarter's last tick, when its P&L was taken.
+    pub close_tick:            Tick,
+    /// Last tick the checklist can be signed off on time.
+    pub due_tick:              Tick,
+    /// 'open' | 'closed'
+    pub status:                String,
+    pub closed_tick:           Option<Tick>,
+    /// Set once the due tick passed with items unsigned.
+    pub overdue:               bool,
+    /// Net adjustments booked into the quarter's P&L, accruals and
+    /// on-time manual adjustments alike.
+    pub adjustment_total:      f64,
+    pub late_adjustment_count: i64,
+    pub late_adjustment_total: f64,
+}
+
+/// Row from the `close_adjustment` table. Amounts are signed: positive
+/// raises profit.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CloseAdjustmentRow {
+    pub adjustment_id: String,
+    /// Quarter the adjustment belongs to.
+    pub period:        String,
+    pub tick:          Tick,
+    /// 'unpaid_bonus' | 'provisional_credit_reserve' | 'unposted_interchange'
+    /// | 'accrual_reversal' | 'manual'
+    pub kind:          String,
+    pub amount:        f64,
+    pub description:   String,
+    pub late:          bool,
+    /// Quarter whose P&L carries it; a late adjustment waits for the next
+    /// close.
+    pub booked_period: Option<String>,
+}
+
+/// Row from the `close_checklist_item` table.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CloseChecklistItemRow {
+    pub period:          String,
+    pub item:            String,
+    /// What the item asks the player to review.
+    pub detail:          String,
+    /// 'pending' | 'signed_off'
+    pub status:          String,
+    pub signed_off_tick: Option<Tick>,
+}
+
+/// What the close accrues for at a quarter's end.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct AccrualBasis {
+    /// Bonuses earned on completed offers but not yet paid.
+    pub unpaid_bonuses:         f64,
+    /// Provisional credit outstanding on disputes still open.
+    pub provisional_credit:     f64,
+    /// Card volume cleared but not yet settled.
+    pub unsettled_card_volume:  f64,
+}
+
+const CLOSE_COLUMNS: &str = "period, close_tick, due_tick, status, closed_tick, overdue, adjustment_total,
+     late_adjustment_count, late_adjustment_total";
+
+fn close_from_row(row: &Row) -> rusqlite::Result<QuarterCloseRow> {
+    Ok(QuarterCloseRow {
+        period:                row.get(0)?,
+        close_tick:            row.get::<_, i64>(1)? as Tick,
+        due_tick:              row.get::<_, i64>(2)? as Tick,
+        status:                row.get(3)?,
+        closed_tick:           row.get::<_, Option<i64>>(4)?.map(|t| t as Tick),
+        overdue:               row.get(5)?,
+        adjustment_total:      row.get(6)?,
+        late_adjustment_count: row.get(7)?,
+        late_adjustment_total: row.get(8)?,
+    })
+}
+
+const ADJUSTMENT_COLUMNS: &str = "adjustment_id, period, tick, kind, amount, description, late, booked_period";
+
+fn adjustment_from_row(row: &Row) -> rusqlite::Result<CloseAdjustmentRow> {
+    Ok(CloseAdjustmentRow {
+        adjustment_id: row.get(0)?,
+        period:        row.get(1)?,
+        tick:          row.get::<_, i64>(2)? as Tick,
+        kind:          row.get(3)?,
+        amount:        row.get(4)?,
+        description:   row.get(5)?,
+        late:          row.get(6)?,
+        booked_period: row.get(7)?,
+    })
+}
+
+const ITEM_COLUMNS: &str = "period, item, detail, status, signed_off_tick";
+
+fn item_from_row(row: &Row) -> rusqlite::Result<CloseChecklistItemRow> {
+    Ok(CloseChecklistItemRow {
+        period:          row.get(0)?,
+        item:            row.get(1)?,
+        detail:          row.get(2)?,
+        status:          row.get(3)?,
+        signed_off_tick: row.get::<_, Option<i64>>(4)?.map(|t| t as Tick),
+    })
+}
+
+impl SimStore {
+    /// Balances the close accrues for, as they stand when it runs at
+    /// `tick`.
+    pub fn accrual_basis(&self, run_id: &str, tick: Tick) -> SimResult<AccrualBasis> {
+        let t = tick as i64;
+        let unpaid_bonuses = self.conn.query_row(
+            "SELECT COALESCE(SUM(bonus_amount), 0.0) FROM customer_offer
+             WHERE run_id = ?1 AND tick_completed <= ?2 AND (tick_paid IS NULL OR tick_paid > ?2)",
+            params![run_id, t],
+            |row| row.get(0),
+        )?;
+        let provisional_credit = self.conn.query_row(
+            "SELECT COALESCE(SUM(provisional_credit_amount), 0.0) FROM card_dispute
+             WHERE run_id = ?1 AND provisional_credit_issued = 1 AND tick_filed <= ?2
+               AND (tick_resolved IS NULL OR tick_resolved > ?2)",
+            params![run_id, t],
+            |row| row.get(0),
+        )?;
+        let unsettled_card_volume = self.conn.query_row(
+            "SELECT COALESCE(SUM(COALESCE(cleared_amount, amount)), 0.0) FROM authorization
+             WHERE run_id = ?1 AND tick_cleared <= ?2 AND (tick_settled IS NULL OR tick_settled > ?2)",
+            params![run_id, t],
+            |row| row.get(0),
+        )?;
+        Ok(AccrualBasis { unpaid_bonuses, provisional_credit, unsettled_card_volume })
+    }
+
+    pub fn insert_quarter_close(&self, run_id: &str, row: &QuarterCloseRow) -> SimResult<()> {
+        self.conn.execute(
+            &format!("INSERT INTO quarter_close (run_id, {CLOSE_COLUMNS}) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)"),
+            params![
+                run_id, row.period, row.close_tick as i64, row.due_tick as i64, row.status,
+                row.closed_tick.map(|t| t as i64), row.overdue, row.adjustment_total,
+                row.late_adjustment_count, row.late_adjustment_total,
+            ],
+        )?;
+        Ok(())
+    }
+
+    pub fn update_quarter_close(&self, run_id: &str, row: &QuarterCloseRow) -> SimResult<()> {
+        self.conn.execute(
+            "UPDATE quarter_close SET status = ?3, closed_tick = ?4, overdue = ?5, adjustment_total = ?6,
+                    late_adjustment_count = ?7, late_adjustment_total = ?8
+              WHERE run_id = ?1 AND period = ?2",
+            params![
+                run_id, row.period, row.status, row.closed_tick.map(|t| t as i64), row.overdue,
+                row.adjustment_total, row.late_adjustment_count, row.late_adjustment_total,
+            ],
+        )?;
+        Ok(())
+    }
+
+    pub fn quarter_close(&self, run_id: &str, period: &str) -> SimResult<Option<QuarterCloseRow>> {
+        self.conn
+            .query_row(
+                &format!("SELECT {CLOSE_COLUMNS} FROM quarter_close WHERE run_id = ?1 AND period = ?2"),
+                params![run_id, period],
+                close_from_row,
+            )
+            .optional()
+            .map_err(Into::into)
+    }
+
+    /// Every close in the run, oldest first.
+    pub fn quarter_closes(&self, run_id: &str) -> SimResult<Vec<QuarterCloseRow>> {
+        let mut stmt = self.conn.prepare(&format!(
+            "SELECT {CLOSE_COLUMNS} FROM quarter_close WHERE run_id = ?1 ORDER BY close_tick"
+        ))?;
+        let rows = stmt.query_map(params![run_id], close_from_row)?;
+        rows.collect::<Result<Vec<_>, _>>().map_err(Into::into)
+    }
+
+    pub fn insert_close_adjustment(&self, run_id: &str, row: &CloseAdjustmentRow) -> SimResult<()> {
+        self.conn.execute(
+            &format!(
+                "INSERT INTO close_adjustment (run_id, {ADJUSTMENT_COLUMNS}) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)"
+            ),
+            params![
+                run_id, row.adjustment_id, row.period, row.tick as i64, row.kind, row.amount,
+                row.description, row.late, row.booked_period,
+            ],
+        )?;
+        Ok(())
+    }
+
+    /// A quarter's adjustments, in the order posted.
+    pub fn close_adjustments(&self, run_id: &str, period: &str) -> SimResult<Vec<CloseAdjustmentRow>> {
+        let mut stmt = self.conn.prepare(&format!(
+            "SELECT {ADJUSTMENT_COLUMNS} FROM close_adjustment WHERE run_id = ?1 AND period = ?2
+              ORDER BY tick, rowid"
+        ))?;
+        let rows = stmt.query_map(params![run_id, period], adjustment_from_row)?;
+        rows.collect::<Result<Vec<_>, _>>().map_err(Into::into)
+    }
+
+    /// Late adjustments to earlier quarters no close has booked yet;
+    /// books them into `period` and returns them.
+    pub fn book_late_adjustments(&self, run_id: &str, period: &str) -> SimResult<Vec<CloseAdjustmentRow>> {
+        let mut stmt = self.conn.prepare(&format!(
+            "SELECT {ADJUSTMENT_COLUMNS} FROM close_adjustment
+              WHERE run_id = ?1 AND late = 1 AND booked_period IS NULL ORDER BY tick, rowid"
+        ))?;
+        let late = stmt
+            .query_map(params![run_id], adjustment_from_row)?
+            .collect::<Result<Vec<_>, _>>()?;
+        self.conn.execute(
+            "UPDATE close_adjustment SET booked_period = ?2 WHERE run_id = ?1 AND late = 1 AND booked_period IS NULL",
+            params![run_id, period],
+        )?;
+        Ok(late)
+    }
+
+    /// Fold an adjustment into the quarter's stored P&L snapshot.
+    pub fn restate_pnl_snapshot(&self, run_id: &str, period: &str, amount: f64) -> SimResult<()> {
+        self.conn.execute(
+            "UPDATE pnl_snapshot SET accrual_adjustment = accrual_adjustment + ?3,
+                    pre_tax_profit = pre_tax_profit + ?3
+              WHERE run_id = ?1 AND period = ?2",
+            params![run_id, period, amount],
+        )?;
+        Ok(())
+    }
+
+    pub fn insert_close_checklist_item(&self, run_id: &str, row: &CloseChecklistItemRow) -> SimResult<()> {
+        self.conn.execute(
+            &format!("INSERT INTO close_checklist_item (run_id, {ITEM_COLUMNS}) VALUES (?1, ?2, ?3, ?4, ?5, ?6)"),
+            params![
+                run_id, row.period, row.item, row.detail, row.status,
+                row.signed_off_tick.map(|t| t as i64),
+            ],
+        )?;
+        Ok(())
+    }
+
+    pub fn sign_off_close_item(&self, run_id: &str, period: &str, item: &str, tick: Tick) -> SimResult<()> {
+        self.conn.execute(
+            "UPDATE close_checklist_item SET status = 'signed_off', signed_off_tick = ?4
+              WHERE run_id = ?1 AND period = ?2 AND item = ?3",
+            params![run_id, period, item, tick as i64],
+        )?;
+        Ok(())
+    }
+
+    /// A quarter's checklist, in the order it is worked.
+    pub fn close_checklist(&self, run_id: &str, period: &str) -> SimResult<Vec<CloseChecklistItemRow>> {
+        let mut stmt = self.conn.prepare(&format!(
+            "SELECT {ITEM_COLUMNS} FROM close_checklist_item WHERE run_id = ?1 AND period = ?2 ORDER BY rowid"
+        ))?;
+        let rows = stmt.query_map(params![run_id, period], item_from_row)?;
+        rows.collect::<Result<Vec<_>, _>>().map_err(Into::into)
+    }
+}
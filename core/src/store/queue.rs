@@ -0,0 +1,248 @@
+//! Paginated queue queries (Phase 4.43), what changed in a queue over a
+//! tick window (Phase 4.44) and queue aging counts (Phase 4.48).
+
+use crate::{
+    error::SimResult,
+    queue::{QueueFilter, QueueItem, QueueKind},
+    types::Tick,
+};
+use rusqlite::params;
+
+use super::SimStore;
+
+/// Open items of one priority in one age bucket.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AgingCount {
+    pub priority: Option<String>,
+    pub bucket: usize,
+    pub count: u64,
+    pub overdue: u64,
+}
+
+/// Where a queue lives and how its columns map onto `QueueItem`.
+struct QueueSource {
+    table: &'static str,
+    id: &'static str,
+    customer_id: &'static str,
+    opened: &'static str,
+    category: &'static str,
+    priority: &'static str,
+    amount: &'static str,
+    due: &'static str,
+    /// Which rows are still being worked when no status is asked for.
+    open: &'static str,
+    /// Tick the item was closed or resolved.
+    closed: &'static str,
+}
+
+fn source(queue: QueueKind) -> QueueSource {
+    match queue {
+        QueueKind::Complaints => QueueSource {
+            table: "complaint",
+            id: "complaint_id",
+            customer_id: "customer_id",
+            opened: "tick_opened",
+            category: "issue",
+            priority: "priority",
+            amount: "NULL",
+            due: "sla_due_tick",
+            open: "status = 'open'",
+            closed: "tick_closed",
+        },
+        QueueKind::Alerts => QueueSource {
+            table: "aml_alert",
+            id: "alert_id",
+            customer_id: "customer_id",
+            opened: "tick",
+            category: "alert_type",
+            priority: "severity",
+            amount: "triggered_amount",
+            due: "NULL",
+            open: "status IN ('open', 'investigating')",
+            closed: "resolved_tick",
+        },
+        QueueKind::Exceptions => QueueSource {
+            table: "recon_exception",
+            id: "exception_id",
+            customer_id: "NULL",
+            opened: "tick_detected",
+            category: "COALESCE(suspected_cause, 'unknown')",
+            priority: "NULL",
+            amount: "delta_amount",
+            due: "NULL",
+            open: "status IN ('open', 'investigating')",
+            closed: "tick_resolved",
+        },
+        QueueKind::Cases => QueueSource {
+            table: "card_dispute",
+            id: "dispute_id",
+            customer_id: "customer_id",
+            opened: "tick_filed",
+            category: "reason",
+            priority: "NULL",
+            amount: "amount",
+            due: "NULL",
+            open: "status NOT LIKE 'resolved%' AND status <> 'closed'",
+            closed: "tick_resolved",
+        },
+    }
+}
+
+impl SimStore {
+    /// The matching total and one page of a queue, oldest first.
+    pub fn queue_page(
+        &self,
+        run_id: &str,
+        queue: QueueKind,
+        filter: &QueueFilter,
+        offset: u64,
+        limit: u64,
+    ) -> SimResult<(u64, Vec<QueueItem>)> {
+        let s = source(queue);
+        let filters = format!(
+            "FROM {table}
+             WHERE run_id = ?1
+               AND (CASE WHEN ?2 IS NULL THEN {open} ELSE status = ?2 END)
+               AND (?3 IS NULL OR {customer_id} = ?3)
+               AND (?4 IS NULL OR {category} = ?4)
+               AND (?5 IS NULL OR {opened} >= ?5)
+               AND (?6 IS NULL OR {opened} <= ?6)",
+            table = s.table,
+            open = s.open,
+            customer_id = s.customer_id,
+            category = s.category,
+            opened = s.opened,
+        );
+        let args = params![
+            run_id,
+            filter.status,
+            filter.customer_id,
+            filter.category,
+            filter.from_tick.map(|t| t as i64),
+            filter.to_tick.map(|t| t as i64),
+        ];
+
+        let total: i64 = self
+            .conn
+            .query_row(&format!("SELECT COUNT(*) {filters}"), args, |row| row.get(0))?;
+
+        let page = format!(
+            "{filters} ORDER BY {opened}, {id} LIMIT {limit} OFFSET {offset}",
+            opened = s.opened,
+            id = s.id,
+        );
+        let items = self.queue_items(&s, &page, args)?;
+        Ok((total as u64, items))
+    }
+
+    /// Items still being worked in a queue (Phase 4.80).
+    pub fn queue_open_count(&self, run_id: &str, queue: QueueKind) -> SimResult<u64> {
+        let s = source(queue);
+        let count: i64 = self.conn.query_row(
+            &format!("SELECT COUNT(*) FROM {table} WHERE run_id = ?1 AND {open}", table = s.table, open = s.open),
+            params![run_id],
+            |row| row.get(0),
+        )?;
+        Ok(count as u64)
+    }
+
+    /// Items opened, and items closed, in `(since, until]`, each oldest
+    /// first and at most `limit` long.
+    pub fn queue_changes(
+        &self,
+        run_id: &str,
+        queue: QueueKind,
+        since: Tick,
+        until: Tick,
+        limit: u64,
+    ) -> SimResult<(Vec<QueueItem>, Vec<QueueItem>)> {
+        let s = source(queue);
+        let args = params![run_id, since as i64, until as i64];
+        let window = |column: &str| {
+            format!(
+                "FROM {table} WHERE run_id = ?1 AND {column} > ?2 AND {column} <= ?3
+                 ORDER BY {column}, {id} LIMIT {limit}",
+                table = s.table,
+                id = s.id,
+            )
+        };
+        let opened = self.queue_items(&s, &window(s.opened), args)?;
+        let closed = self.queue_items(&s, &window(s.closed), args)?;
+        Ok((opened, closed))
+    }
+
+    /// Items open at tick `at`, counted by priority and age bucket, with
+    /// how many of them were past their due tick. `bucket_starts` are the
+    /// ascending ages at which each bucket begins. Works for past ticks
+    /// too: an item closed since still counts as open then.
+    pub fn queue_aging_counts(
+        &self,
+        run_id: &str,
+        queue: QueueKind,
+        at: Tick,
+        bucket_starts: &[Tick],
+    ) -> SimResult<Vec<AgingCount>> {
+        let s = source(queue);
+        let bucket = bucket_starts
+            .iter()
+            .enumerate()
+            .rev()
+            .map(|(i, start)| format!("WHEN ?2 - {opened} >= {start} THEN {i}", opened = s.opened))
+            .collect::<Vec<_>>()
+            .join(" ");
+        let mut stmt = self.conn.prepare(&format!(
+            "SELECT {priority}, CASE {bucket} ELSE 0 END AS bucket, COUNT(*),
+                    COALESCE(SUM({due} IS NOT NULL AND {due} < ?2), 0)
+             FROM {table}
+             WHERE run_id = ?1 AND {opened} <= ?2 AND (({open}) OR {closed} > ?2)
+             GROUP BY 1, 2 ORDER BY 1, 2",
+            table = s.table,
+            priority = s.priority,
+            due = s.due,
+            opened = s.opened,
+            open = s.open,
+            closed = s.closed,
+        ))?;
+        let rows = stmt.query_map(params![run_id, at as i64], |row| {
+            Ok(AgingCount {
+                priority: row.get(0)?,
+                bucket: row.get::<_, i64>(1)? as usize,
+                count: row.get::<_, i64>(2)? as u64,
+                overdue: row.get::<_, i64>(3)? as u64,
+            })
+        })?;
+        rows.collect::<Result<Vec<_>, _>>().map_err(Into::into)
+    }
+
+    fn queue_items(
+        &self,
+        s: &QueueSource,
+        from: &str,
+        args: &[&dyn rusqlite::ToSql],
+    ) -> SimResult<Vec<QueueItem>> {
+        let mut stmt = self.conn.prepare(&format!(
+            "SELECT {id}, {customer_id}, {opened}, status, {category}, {priority}, {amount}, {due}
+             {from}",
+            id = s.id,
+            customer_id = s.customer_id,
+            opened = s.opened,
+            category = s.category,
+            priority = s.priority,
+            amount = s.amount,
+            due = s.due,
+        ))?;
+        let rows = stmt.query_map(args, |row| {
+            Ok(QueueItem {
+                id: row.get(0)?,
+                customer_id: row.get(1)?,
+                opened_tick: row.get::<_, i64>(2)? as Tick,
+                status: row.get(3)?,
+                category: row.get(4)?,
+                priority: row.get(5)?,
+                amount: row.get(6)?,
+                due_tick: row.get::<_, Option<i64>>(7)?.map(|t| t as Tick),
+            })
+        })?;
+        rows.collect::<Result<Vec<_>, _>>().map_err(Into::into)
+    }
+}
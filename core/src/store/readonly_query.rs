@@ -0,0 +1,77 @@
+//! Store methods for ad hoc read-only SQL (Phase 4.67).
+
+use crate::{error::SimResult, readonly_query::reject};
+use rusqlite::{types::Value, Connection, OpenFlags};
+
+use super::SimStore;
+
+/// What a read-only query found, before it is shaped for the client.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReadonlyRows {
+    pub columns: Vec<String>,
+    pub rows:    Vec<Vec<Value>>,
+}
+
+impl SimStore {
+    /// Run one statement on a connection opened read-only, returning at
+    /// most `max_rows` rows. The statement is interrupted once it has
+    /// taken `max_steps` thousand VM steps. Anything wrong with the
+    /// statement itself is the caller's error, not the store's.
+    pub fn readonly_query(
+        &self,
+        sql: &str,
+        params: &[Value],
+        max_rows: usize,
+        max_steps: u32,
+    ) -> SimResult<ReadonlyRows> {
+        let Some(path) = &self.path else {
+            // An in-memory database cannot be opened twice
+            self.conn.execute_batch("PRAGMA query_only = ON;")?;
+            let found = run_readonly(&self.conn, sql, params, max_rows, max_steps);
+            self.conn.progress_handler(0, None::<fn() -> bool>);
+            self.conn.execute_batch("PRAGMA query_only = OFF;")?;
+            return found;
+        };
+        let conn = Connection::open_with_flags(path, OpenFlags::SQLITE_OPEN_READ_ONLY | OpenFlags::SQLITE_OPEN_URI)?;
+        run_readonly(&conn, sql, params, max_rows, max_steps)
+    }
+}
+
+fn run_readonly(
+    conn: &Connection,
+    sql: &str,
+    params: &[Value],
+    max_rows: usize,
+    max_steps: u32,
+) -> SimResult<ReadonlyRows> {
+    let mut stmt = conn.prepare(sql).map_err(|e| reject(e.to_string()))?;
+    if !stmt.readonly() {
+        return Err(reject("the statement would write to the database"));
+    }
+    let columns: Vec<String> = stmt.column_names().into_iter().map(String::from).collect();
+    let mut steps = 0;
+    conn.progress_handler(
+        1_000,
+        Some(move || {
+            steps += 1;
+            steps > max_steps
+        }),
+    );
+    let mut found = stmt
+        .query(rusqlite::params_from_iter(params))
+        .map_err(|e| reject(e.to_string()))?;
+    let mut rows = Vec::new();
+    while rows.len() < max_rows {
+        let Some(row) = found.next().map_err(|e| match e.sqlite_error_code() {
+            Some(rusqlite::ErrorCode::OperationInterrupted) => {
+                reject(format!("stopped after {max_steps} thousand steps; narrow the query"))
+            }
+            _ => reject(e.to_string()),
+        })?
+        else {
+            break;
+        };
+        rows.push((0..columns.len()).map(|i| row.get(i)).collect::<Result<Vec<Value>, _>>()?);
+    }
+    Ok(ReadonlyRows { columns, rows })
+}
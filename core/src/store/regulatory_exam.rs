@@ -146,4 +146,14 @@ impl SimStore {
         )?;
         Ok(total)
     }
+
+    /// (severity, fine) of every finding in a category (for tests).
+    pub fn exam_findings_in_category(&self, run_id: &str, category: &str) -> SimResult<Vec<(String, f64)>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT severity, fine_amount FROM exam_finding
+             WHERE run_id = ?1 AND category = ?2 ORDER BY tick",
+        )?;
+        let rows = stmt.query_map(params![run_id, category], |row| Ok((row.get(0)?, row.get(1)?)))?;
+        rows.collect::<Result<Vec<_>, _>>().map_err(Into::into)
+    }
 }
@@ -0,0 +1,200 @@
+//! Store methods for remittance corridors (Phase 4.36).
+
+use crate::{error::SimResult, types::Tick};
+use rusqlite::params;
+
+use super::SimStore;
+
+/// Row from the `remittance_profile` table.
+#[derive(Debug, Clone)]
+pub struct RemittanceProfileRow {
+    pub customer_id:    String,
+    pub account_id:     String,
+    pub corridor:       String,
+    /// Ground truth: 'legitimate' | 'layering' | 'none'.
+    pub pattern:        String,
+    pub amount:         f64,
+    pub interval_ticks: Tick,
+    pub beneficiaries:  u32,
+    pub next_tick:      Tick,
+    pub tick_enrolled:  Tick,
+}
+
+/// Row from the `remittance_transfer` table.
+#[derive(Debug, Clone)]
+pub struct RemittanceTransferRow {
+    pub txn_id:       String,
+    pub customer_id:  String,
+    pub account_id:   String,
+    pub tick:         Tick,
+    /// 'outbound' | 'inbound'
+    pub direction:    String,
+    pub corridor:     String,
+    pub counterparty: String,
+    pub amount:       f64,
+}
+
+/// A customer's outbound remittances over a window, against everything
+/// credited to their accounts over the same window.
+#[derive(Debug, Clone)]
+pub struct RemittanceActivityRow {
+    pub customer_id:   String,
+    pub sent:          f64,
+    pub beneficiaries: i64,
+    pub corridors:     Vec<String>,
+    pub credits:       f64,
+}
+
+impl SimStore {
+    /// International customers with no remittance profile yet, with their
+    /// citizenship country.
+    pub fn unprofiled_international_customers(&self, run_id: &str) -> SimResult<Vec<(String, String)>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT ci.customer_id, ci.citizenship_country FROM customer_international ci
+             WHERE ci.run_id = ?1
+               AND NOT EXISTS (
+                   SELECT 1 FROM remittance_profile p
+                   WHERE p.run_id = ci.run_id AND p.customer_id = ci.customer_id
+               )
+             ORDER BY ci.customer_id",
+        )?;
+        let rows = stmt.query_map(params![run_id], |row| Ok((row.get(0)?, row.get(1)?)))?;
+        rows.collect::<Result<Vec<_>, _>>().map_err(Into::into)
+    }
+
+    pub fn insert_remittance_profile(&self, run_id: &str, row: &RemittanceProfileRow) -> SimResult<()> {
+        self.conn.execute(
+            "INSERT OR IGNORE INTO remittance_profile
+             (run_id, customer_id, account_id, corridor, pattern, amount,
+              interval_ticks, beneficiaries, next_tick, tick_enrolled)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)",
+            params![
+                run_id, row.customer_id, row.account_id, row.corridor, row.pattern,
+                row.amount, row.interval_ticks as i64, row.beneficiaries,
+                row.next_tick as i64, row.tick_enrolled as i64,
+            ],
+        )?;
+        Ok(())
+    }
+
+    /// Remitting profiles whose next remittance falls due by `tick`.
+    pub fn remittance_profiles_due(&self, run_id: &str, tick: Tick) -> SimResult<Vec<RemittanceProfileRow>> {
+        self.remittance_profiles_where(
+            "run_id = ?1 AND pattern != 'none' AND next_tick <= ?2",
+            params![run_id, tick as i64],
+        )
+    }
+
+    pub fn set_remittance_next_tick(&self, run_id: &str, customer_id: &str, next_tick: Tick) -> SimResult<()> {
+        self.conn.execute(
+            "UPDATE remittance_profile SET next_tick = ?3
+             WHERE run_id = ?1 AND customer_id = ?2",
+            params![run_id, customer_id, next_tick as i64],
+        )?;
+        Ok(())
+    }
+
+    pub fn insert_remittance_transfer(&self, run_id: &str, row: &RemittanceTransferRow) -> SimResult<()> {
+        self.conn.execute(
+            "INSERT INTO remittance_transfer
+             (txn_id, run_id, customer_id, account_id, tick, direction,
+              corridor, counterparty, amount)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
+            params![
+                row.txn_id, run_id, row.customer_id, row.account_id, row.tick as i64,
+                row.direction, row.corridor, row.counterparty, row.amount,
+            ],
+        )?;
+        Ok(())
+    }
+
+    /// Outbound remittance activity per customer in the inclusive window,
+    /// with the customer's total credits over it.
+    pub fn remittance_activity(
+        &self,
+        run_id:     &str,
+        tick_start: Tick,
+        tick_end:   Tick,
+    ) -> SimResult<Vec<RemittanceActivityRow>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT r.customer_id, SUM(r.amount), COUNT(DISTINCT r.counterparty),
+                    GROUP_CONCAT(DISTINCT r.corridor),
+                    (SELECT COALESCE(SUM(t.amount), 0.0) FROM transactions t
+                     JOIN account a ON a.account_id = t.account_id AND a.run_id = t.run_id
+                     WHERE t.run_id = r.run_id AND a.customer_id = r.customer_id
+                       AND t.direction = 'credit'
+                       AND t.tick >= ?2 AND t.tick <= ?3)
+             FROM remittance_transfer r
+             WHERE r.run_id = ?1 AND r.direction = 'outbound'
+               AND r.tick >= ?2 AND r.tick <= ?3
+             GROUP BY r.customer_id
+             ORDER BY r.customer_id",
+        )?;
+        let rows = stmt.query_map(params![run_id, tick_start as i64, tick_end as i64], |row| {
+            let corridors: String = row.get(3)?;
+            Ok(RemittanceActivityRow {
+                customer_id:   row.get(0)?,
+                sent:          row.get(1)?,
+                beneficiaries: row.get(2)?,
+                corridors:     corridors.split(',').map(str::to_string).collect(),
+                credits:       row.get(4)?,
+            })
+        })?;
+        rows.collect::<Result<Vec<_>, _>>().map_err(Into::into)
+    }
+
+    fn remittance_profiles_where(
+        &self,
+        filter: &str,
+        params: impl rusqlite::Params,
+    ) -> SimResult<Vec<RemittanceProfileRow>> {
+        let mut stmt = self.conn.prepare(&format!(
+            "SELECT customer_id, account_id, corridor, pattern, amount, interval_ticks,
+                    beneficiaries, next_tick, tick_enrolled
+             FROM remittance_profile WHERE {filter} ORDER BY customer_id"
+        ))?;
+        let rows = stmt.query_map(params, |row| {
+            Ok(RemittanceProfileRow {
+                customer_id:    row.get(0)?,
+                account_id:     row.get(1)?,
+                corridor:       row.get(2)?,
+                pattern:        row.get(3)?,
+                amount:         row.get(4)?,
+                interval_ticks: row.get::<_, i64>(5)? as Tick,
+                beneficiaries:  row.get(6)?,
+                next_tick:      row.get::<_, i64>(7)? as Tick,
+                tick_enrolled:  row.get::<_, i64>(8)? as Tick,
+            })
+        })?;
+        rows.collect::<Result<Vec<_>, _>>().map_err(Into::into)
+    }
+
+    // ── Test / summary helpers ────────────────────────────────────────
+
+    /// Every remittance profile for a run, with its ground truth (for
+    /// tests).
+    pub fn remittance_profiles(&self, run_id: &str) -> SimResult<Vec<RemittanceProfileRow>> {
+        self.remittance_profiles_where("run_id = ?1", params![run_id])
+    }
+
+    /// Every remittance transfer for a run, oldest first (for tests).
+    pub fn remittance_transfers(&self, run_id: &str) -> SimResult<Vec<RemittanceTransferRow>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT txn_id, customer_id, account_id, tick, direction, corridor, counterparty, amount
+             FROM remittance_transfer WHERE run_id = ?1 ORDER BY tick, txn_id",
+        )?;
+        let rows = stmt.query_map(params![run_id], |row| {
+            Ok(RemittanceTransferRow {
+                txn_id:       row.get(0)?,
+                customer_id:  row.get(1)?,
+                account_id:   row.get(2)?,
+                tick:         row.get::<_, i64>(3)? as Tick,
+                direction:    row.get(4)?,
+                corridor:     row.get(5)?,
+                counterparty: row.get(6)?,
+                amount:       row.get(7)?,
+            })
+        })?;
+        rows.collect::<Result<Vec<_>, _>>().map_err(Into::into)
+    }
+}
@@ -0,0 +1,45 @@
+//! Store methods for resuming a stopped run (Phase 4.76).
+
+use crate::{error::SimResult, types::Tick};
+use rusqlite::params;
+
+use super::SimStore;
+
+impl SimStore {
+    /// (command_id, command type) of the player commands logged at
+    /// `tick`, in the order they were sent.
+    pub fn player_commands_at(&self, run_id: &str, tick: Tick) -> SimResult<Vec<(i64, String)>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT id, cmd_type FROM player_command
+              WHERE run_id = ?1 AND tick = ?2 ORDER BY id",
+        )?;
+        let rows = stmt.query_map(params![run_id, tick as i64], |row| Ok((row.get(0)?, row.get(1)?)))?;
+        rows.collect::<Result<Vec<_>, _>>().map_err(Into::into)
+    }
+
+    /// Payloads of every `event_type` event the run logged, oldest
+    /// first: the archived ticks', then the detailed log's.
+    pub fn logged_event_payloads(&self, run_id: &str, event_type: &str) -> SimResult<Vec<String>> {
+        let mut payloads = self.archived_event_payloads(run_id, event_type)?;
+        let mut stmt = self.conn.prepare(
+            "SELECT payload FROM event_log
+              WHERE run_id = ?1 AND event_type = ?2 ORDER BY id",
+        )?;
+        let rows = stmt.query_map(params![run_id, event_type], |row| row.get::<_, String>(0))?;
+        for payload in rows {
+            payloads.push(payload?);
+        }
+        Ok(payloads)
+    }
+
+    /// Every pause, resume and speed change sent on the run, oldest
+    /// first.
+    pub fn clock_commands(&self, run_id: &str) -> SimResult<Vec<crate::command::PlayerCommand>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT payload FROM player_command
+              WHERE run_id = ?1 AND cmd_type IN ('pause', 'resume', 'set_speed') ORDER BY id",
+        )?;
+        let rows = stmt.query_map(params![run_id], |row| row.get::<_, String>(0))?;
+        rows.map(|payload| Ok(serde_json::from_str(&payload?)?)).collect()
+    }
+}
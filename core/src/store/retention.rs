@@ -0,0 +1,245 @@
+//! Run-level data retention: PII scrubbing and purging (Phase 4.50).
+//!
+//! `scrub_run` replaces the personal data of one run in place. Each value
+//! is swapped for a pseudonym hashed from it with a salt drawn once per
+//! scrub, so two rows that shared a name, tax id, street, phone number or
+//! IP address before still share one afterwards, across tables, while the
+//! original cannot be recovered by hashing candidates. Identifiers keep
+//! their format: an SSN stays `NNN-NN-NNNN`, and an IP address stays a
+//! dotted quad, moved into 100.64.0.0/10, which simulated logins never
+//! use. City, state and ZIP are kept, as are ids, dates of birth and
+//! every non-personal column.
+//!
+//! `purge_run` deletes every row of a run, in every table, in one
+//! transaction. Both turn on `secure_delete` so replaced and deleted
+//! values are zeroed in the database file rather than left in free pages.
+
+use super::SimStore;
+use crate::error::SimResult;
+use rusqlite::{params, OptionalExtension};
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Pii {
+    /// People, trusts and businesses.
+    Name,
+    /// SSNs, EINs, ITINs and foreign TINs; one kind so an SSN used as an
+    /// account's tax id or listed in the death master file still matches.
+    TaxId,
+    Street,
+    /// The exchange and subscriber digits of a phone number.
+    Phone,
+    /// A login's IP address.
+    IpAddress,
+}
+
+/// Columns holding personal data, by table. Phone numbers are scrubbed
+/// separately since their parts are stored beside the full number.
+const PII_COLUMNS: &[(&str, &str, Pii)] = &[
+    ("customer", "name", Pii::Name),
+    ("customer_beneficiary", "beneficiary_name", Pii::Name),
+    ("trust_account", "trust_name", Pii::Name),
+    ("trust_beneficiary", "beneficiary_name", Pii::Name),
+    ("business_entity", "legal_name", Pii::Name),
+    ("business_entity", "dba_name", Pii::Name),
+    ("dba_registration", "dba_name", Pii::Name),
+    ("adverse_media_article", "name", Pii::Name),
+    ("customer_identity", "ssn_full", Pii::TaxId),
+    ("account", "primary_tax_id", Pii::TaxId),
+    ("death_master_file", "ssn", Pii::TaxId),
+    ("custodial_account", "tax_reporting_ssn", Pii::TaxId),
+    ("trust_account", "trust_ein", Pii::TaxId),
+    ("trust_account", "tax_reporting_id", Pii::TaxId),
+    ("business_entity", "ein", Pii::TaxId),
+    ("customer_international", "foreign_tin", Pii::TaxId),
+    ("customer_address", "street_address", Pii::Street),
+    ("device_session", "ip_address", Pii::IpAddress),
+];
+
+/// Rows rewritten or deleted for one run, by table.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct RetentionReport {
+    pub run_id: String,
+    /// Tables with no rows touched are left out.
+    pub tables: BTreeMap<String, u64>,
+}
+
+impl RetentionReport {
+    pub fn total(&self) -> u64 {
+        self.tables.values().sum()
+    }
+}
+
+struct Pseudonymizer {
+    salt: String,
+}
+
+impl Pseudonymizer {
+    fn hash(&self, kind: Pii, value: &str) -> [u8; 32] {
+        Sha256::new()
+            .chain_update(self.salt.as_bytes())
+            .chain_update(format!("{kind:?}").as_bytes())
+            .chain_update(value.as_bytes())
+            .finalize()
+            .into()
+    }
+
+    fn replace(&self, kind: Pii, value: &str) -> String {
+        let hash = self.hash(kind, value);
+        match kind {
+            Pii::Name => format!("Anon {}", hex(&hash[..4])),
+            // Every digit replaced, punctuation and letters kept in place
+            Pii::TaxId => {
+                let mut digits = digits(&hash);
+                value
+                    .chars()
+                    .map(|c| if c.is_ascii_digit() { digits.next().unwrap() } else { c })
+                    .collect()
+            }
+            Pii::Street => format!("{} Anon {} St", digits(&hash).take(4).collect::<String>(), hex(&hash[4..7])),
+            Pii::Phone => digits(&hash).take(7).collect(),
+            Pii::IpAddress => format!("100.{}.{}.{}", 64 + hash[0] % 64, hash[1], hash[2]),
+        }
+    }
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{b:02x}")).collect()
+}
+
+/// Decimal digits drawn from a hash, cycling if more are needed.
+fn digits(hash: &[u8; 32]) -> impl Iterator<Item = char> + '_ {
+    hash.iter().cycle().map(|b| char::from(b'0' + b % 10))
+}
+
+impl SimStore {
+    fn require_run(&self, run_id: &str) -> SimResult<()> {
+        let found = self
+            .conn
+            .query_row("SELECT 1 FROM run WHERE run_id = ?1", params![run_id], |_| Ok(()))
+            .optional()?;
+        found.ok_or_else(|| anyhow::anyhow!("no run {run_id}").into())
+    }
+
+    /// Replace the names, tax ids, street addresses, phone numbers and IP
+    /// addresses of `run_id` with pseudonyms, keeping equal values equal.
+    pub fn scrub_run(&self, run_id: &str) -> SimResult<RetentionReport> {
+        self.require_run(run_id)?;
+        self.conn.execute_batch("PRAGMA secure_delete = ON;")?;
+        let pseudonyms = Pseudonymizer { salt: uuid::Uuid::new_v4().to_string() };
+        let mut report = RetentionReport { run_id: run_id.to_string(), ..Default::default() };
+
+        self.atomically(|| {
+            for &(table, column, kind) in PII_COLUMNS {
+                let rows = self
+                    .conn
+                    .prepare(&format!("SELECT rowid, {column} FROM {table} WHERE run_id = ?1 AND {column} IS NOT NULL"))?
+                    .query_map(params![run_id], |row| Ok((row.get::<_, i64>(0)?, row.get::<_, String>(1)?)))?
+                    .collect::<Result<Vec<_>, _>>()?;
+                let mut update = self.conn.prepare(&format!("UPDATE {table} SET {column} = ?1 WHERE rowid = ?2"))?;
+                for (rowid, value) in &rows {
+                    update.execute(params![pseudonyms.replace(kind, value), rowid])?;
+                }
+                if !rows.is_empty() {
+                    *report.tables.entry(table.to_string()).or_default() += rows.len() as u64;
+                }
+            }
+            // The SSN's parts follow the scrubbed whole
+            self.conn.execute(
+                "UPDATE customer_identity
+                 SET ssn_area = substr(ssn_full, 1, 3), ssn_group = substr(ssn_full, 5, 2),
+                     ssn_serial = substr(ssn_full, 8, 4)
+                 WHERE run_id = ?1",
+                params![run_id],
+            )?;
+
+            // Area codes are kept; exchange and subscriber are replaced
+            let phones = self
+                .conn
+                .prepare("SELECT rowid, country_code, area_code, full_number FROM customer_phone WHERE run_id = ?1")?
+                .query_map(params![run_id], |row| {
+                    Ok((row.get::<_, i64>(0)?, row.get::<_, String>(1)?, row.get::<_, String>(2)?, row.get::<_, String>(3)?))
+                })?
+                .collect::<Result<Vec<_>, _>>()?;
+            for (rowid, country, area, number) in &phones {
+                let local = pseudonyms.replace(Pii::Phone, number);
+                let (exchange, subscriber) = local.split_at(3);
+                self.conn.execute(
+                    "UPDATE customer_phone SET exchange_code = ?1, subscriber_number = ?2, full_number = ?3
+                     WHERE rowid = ?4",
+                    params![exchange, subscriber, format!("{country}-{area}-{exchange}-{subscriber}"), rowid],
+                )?;
+            }
+            if !phones.is_empty() {
+                report.tables.insert("customer_phone".into(), phones.len() as u64);
+            }
+            Ok(())
+        })?;
+        Ok(report)
+    }
+
+    /// Delete every row of `run_id` from every table, the run itself
+    /// last, all or nothing. Rows in tables without a `run_id` column
+    /// are found through their foreign key into a table that has one.
+    pub fn purge_run(&self, run_id: &str) -> SimResult<RetentionReport> {
+        self.require_run(run_id)?;
+        self.conn.execute_batch("PRAGMA secure_delete = ON;")?;
+        let mut report = RetentionReport { run_id: run_id.to_string(), ..Default::default() };
+
+        let tables = self
+            .conn
+            .prepare("SELECT name FROM sqlite_master WHERE type = 'table' AND name NOT LIKE 'sqlite_%' ORDER BY name")?
+            .query_map([], |row| row.get::<_, String>(0))?
+            .collect::<Result<Vec<_>, _>>()?;
+        let has_run_id = |table: &str| -> SimResult<bool> {
+            Ok(self.conn.query_row(
+                "SELECT COUNT(*) > 0 FROM pragma_table_info(?1) WHERE name = 'run_id'",
+                params![table],
+                |row| row.get(0),
+            )?)
+        };
+        let mut scoped = Vec::new();
+        let mut children = Vec::new();
+        for table in tables.iter().filter(|t| t.as_str() != "run") {
+            if has_run_id(table)? {
+                scoped.push(table.clone());
+                continue;
+            }
+            let keys = self
+                .conn
+                .prepare("SELECT \"table\", \"from\", \"to\" FROM pragma_foreign_key_list(?1)")?
+                .query_map(params![table], |row| Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?, row.get::<_, String>(2)?)))?
+                .collect::<Result<Vec<_>, _>>()?;
+            for (parent, from, to) in keys {
+                if has_run_id(&parent)? {
+                    children.push((
+                        table.clone(),
+                        format!("DELETE FROM {table} WHERE {from} IN (SELECT {to} FROM {parent} WHERE run_id = ?1)"),
+                    ));
+                }
+            }
+        }
+
+        self.atomically(|| {
+            // Rows reference each other in every direction; check the
+            // foreign keys once everything is gone
+            self.conn.execute_batch("PRAGMA defer_foreign_keys = ON;")?;
+            let mut count = |table: &str, deleted: usize| {
+                if deleted > 0 {
+                    *report.tables.entry(table.to_string()).or_default() += deleted as u64;
+                }
+            };
+            for (table, delete) in &children {
+                count(table, self.conn.execute(delete, params![run_id])?);
+            }
+            for table in &scoped {
+                count(table, self.conn.execute(&format!("DELETE FROM {table} WHERE run_id = ?1"), params![run_id])?);
+            }
+            count("run", self.conn.execute("DELETE FROM run WHERE run_id = ?1", params![run_id])?);
+            Ok(())
+        })?;
+        Ok(report)
+    }
+}
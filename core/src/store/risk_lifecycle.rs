@@ -0,0 +1,291 @@
+//! Store methods for the risk-tiered customer lifecycle: behavior signals,
+//! tier transitions and EDD reviews (Phase 4.13).
+
+use crate::{error::SimResult, types::Tick};
+use rusqlite::{params, OptionalExtension};
+use std::collections::HashMap;
+
+/// A customer's current tier alongside the tier they were onboarded at.
+#[derive(Debug, Clone)]
+pub struct RiskTierRow {
+    pub customer_id:   String,
+    pub tier:          String,
+    pub base_tier:     String,
+    pub behavior_risk: f64,
+}
+
+/// Behavior signals counted over the lookback window.
+#[derive(Debug, Clone, Default)]
+pub struct BehaviorSignals {
+    pub alerts:       u32,
+    pub declines:     u32,
+    pub unauthorized: u32,
+}
+
+/// Row from the `risk_tier_transition` table.
+#[derive(Debug, Clone)]
+pub struct RiskTierTransitionRow {
+    pub tick:          Tick,
+    pub customer_id:   String,
+    pub from_tier:     String,
+    pub to_tier:       String,
+    pub reason:        String,
+    pub behavior_risk: f64,
+}
+
+/// Row from the `edd_review` table.
+#[derive(Debug, Clone)]
+pub struct EddReviewRow {
+    pub review_id:      String,
+    pub customer_id:    String,
+    pub tier:           String,
+    pub trigger:        String,
+    pub due_tick:       Tick,
+    pub completed_tick: Option<Tick>,
+    pub outcome:        Option<String>,
+    pub cost:           f64,
+}
+
+use super::SimStore;
+
+/// Writes one query's count into the matching signal.
+type SignalSetter = fn(&mut BehaviorSignals, u32);
+
+fn review_row_mapper(row: &rusqlite::Row<'_>) -> rusqlite::Result<EddReviewRow> {
+    Ok(EddReviewRow {
+        review_id:      row.get(0)?,
+        customer_id:    row.get(1)?,
+        tier:           row.get(2)?,
+        trigger:        row.get(3)?,
+        due_tick:       row.get::<_, i64>(4)? as u64,
+        completed_tick: row.get::<_, Option<i64>>(5)?.map(|t| t as u64),
+        outcome:        row.get(6)?,
+        cost:           row.get(7)?,
+    })
+}
+
+const REVIEW_COLUMNS: &str =
+    "review_id, customer_id, tier, review_trigger, due_tick, completed_tick, outcome, cost";
+
+impl SimStore {
+    /// Every scored customer's tiers. The onboarding tier is recorded as
+    /// the base tier the first time it is read.
+    pub fn customer_risk_tier_rows(&self, run_id: &str) -> SimResult<Vec<RiskTierRow>> {
+        self.conn.execute(
+            "UPDATE customer_risk_score SET base_risk = composite_risk
+             WHERE run_id = ?1 AND base_risk IS NULL",
+            params![run_id],
+        )?;
+        let mut stmt = self.conn.prepare(
+            "SELECT customer_id, composite_risk, base_risk, behavior_risk_score
+             FROM customer_risk_score WHERE run_id = ?1 ORDER BY customer_id",
+        )?;
+        let rows = stmt.query_map(params![run_id], |row| {
+            Ok(RiskTierRow {
+                customer_id:   row.get(0)?,
+                tier:          row.get(1)?,
+                base_tier:     row.get(2)?,
+                behavior_risk: row.get(3)?,
+            })
+        })?;
+        rows.collect::<Result<Vec<_>, _>>().map_err(Into::into)
+    }
+
+    /// AML alerts, limit declines and unauthorized originated entries per
+    /// customer from `since` onwards.
+    pub fn behavior_signals_since(&self, run_id: &str, since: Tick) -> SimResult<HashMap<String, BehaviorSignals>> {
+        let mut signals: HashMap<String, BehaviorSignals> = HashMap::new();
+        let queries: [(&str, SignalSetter); 3] = [
+            (
+                "SELECT customer_id, COUNT(*) FROM aml_alert
+                 WHERE run_id = ?1 AND tick >= ?2 GROUP BY customer_id",
+                |s, n| s.alerts = n,
+            ),
+            (
+                "SELECT customer_id, COUNT(*) FROM customer_limit_decline
+                 WHERE run_id = ?1 AND tick >= ?2 GROUP BY customer_id",
+                |s, n| s.declines = n,
+            ),
+            (
+                "SELECT customer_id, COUNT(*) FROM originated_ach
+                 WHERE run_id = ?1 AND tick >= ?2 AND unauthorized = 1
+                   AND status IN ('submitted', 'returned')
+                 GROUP BY customer_id",
+                |s, n| s.unauthorized = n,
+            ),
+        ];
+        for (sql, set) in queries {
+            let mut stmt = self.conn.prepare(sql)?;
+            let rows = stmt.query_map(params![run_id, since as i64], |row| {
+                Ok((row.get::<_, String>(0)?, row.get::<_, i64>(1)?))
+            })?;
+            for row in rows {
+                let (customer_id, count) = row?;
+                set(signals.entry(customer_id).or_default(), count as u32);
+            }
+        }
+        Ok(signals)
+    }
+
+    pub fn update_behavior_risk(&self, run_id: &str, customer_id: &str, behavior_risk: f64) -> SimResult<()> {
+        self.conn.execute(
+            "UPDATE customer_risk_score SET behavior_risk_score = ?1
+             WHERE run_id = ?2 AND customer_id = ?3",
+            params![behavior_risk, run_id, customer_id],
+        )?;
+        Ok(())
+    }
+
+    /// Move the customer to `to_tier`, record the transition and flag the
+    /// override. EDD is required for high and critical tiers.
+    pub fn transition_risk_tier(
+        &self,
+        run_id:        &str,
+        row:           &RiskTierTransitionRow,
+        override_kind: &str,
+    ) -> SimResult<()> {
+        let edd_required = matches!(row.to_tier.as_str(), "high" | "critical");
+        self.conn.execute(
+            "UPDATE customer_risk_score
+             SET composite_risk = ?1, edd_required = ?2, risk_override = ?3, risk_override_reason = ?4
+             WHERE run_id = ?5 AND customer_id = ?6",
+            params![
+                row.to_tier, edd_required as i64, override_kind, row.reason,
+                run_id, row.customer_id,
+            ],
+        )?;
+        self.conn.execute(
+            "INSERT INTO risk_tier_transition
+             (run_id, tick, customer_id, from_tier, to_tier, reason, behavior_risk)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+            params![
+                run_id, row.tick as i64, row.customer_id, row.from_tier,
+                row.to_tier, row.reason, row.behavior_risk,
+            ],
+        )?;
+        Ok(())
+    }
+
+    pub fn insert_edd_review(&self, run_id: &str, row: &EddReviewRow) -> SimResult<()> {
+        self.conn.execute(
+            "INSERT INTO edd_review
+             (run_id, review_id, customer_id, tier, review_trigger, due_tick, completed_tick, outcome, cost)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
+            params![
+                run_id, row.review_id, row.customer_id, row.tier, row.trigger,
+                row.due_tick as i64, row.completed_tick.map(|t| t as i64),
+                row.outcome, row.cost,
+            ],
+        )?;
+        Ok(())
+    }
+
+    /// The customer's outstanding review, if one is scheduled.
+    pub fn scheduled_edd_review(&self, run_id: &str, customer_id: &str) -> SimResult<Option<EddReviewRow>> {
+        let row = self.conn.query_row(
+            &format!(
+                "SELECT {REVIEW_COLUMNS} FROM edd_review
+                 WHERE run_id = ?1 AND customer_id = ?2 AND completed_tick IS NULL",
+            ),
+            params![run_id, customer_id],
+            review_row_mapper,
+        ).optional()?;
+        Ok(row)
+    }
+
+    /// Bring a scheduled review forward after a tier change.
+    pub fn reschedule_edd_review(
+        &self,
+        run_id:    &str,
+        review_id: &str,
+        tier:      &str,
+        due_tick:  Tick,
+    ) -> SimResult<()> {
+        self.conn.execute(
+            "UPDATE edd_review SET tier = ?1, review_trigger = 'tier_change', due_tick = ?2
+             WHERE run_id = ?3 AND review_id = ?4",
+            params![tier, due_tick as i64, run_id, review_id],
+        )?;
+        Ok(())
+    }
+
+    /// Outstanding reviews due on or before `tick`.
+    pub fn due_edd_reviews(&self, run_id: &str, tick: Tick) -> SimResult<Vec<EddReviewRow>> {
+        let mut stmt = self.conn.prepare(&format!(
+            "SELECT {REVIEW_COLUMNS} FROM edd_review
+             WHERE run_id = ?1 AND completed_tick IS NULL AND due_tick <= ?2
+             ORDER BY due_tick, review_id",
+        ))?;
+        let rows = stmt.query_map(params![run_id, tick as i64], review_row_mapper)?;
+        rows.collect::<Result<Vec<_>, _>>().map_err(Into::into)
+    }
+
+    /// Close the review and stamp the customer's last review tick.
+    pub fn complete_edd_review(
+        &self,
+        run_id:  &str,
+        review:  &EddReviewRow,
+        outcome: &str,
+        cost:    f64,
+        tick:    Tick,
+    ) -> SimResult<()> {
+        self.conn.execute(
+            "UPDATE edd_review SET completed_tick = ?1, outcome = ?2, cost = ?3
+             WHERE run_id = ?4 AND review_id = ?5",
+            params![tick as i64, outcome, cost, run_id, review.review_id],
+        )?;
+        self.conn.execute(
+            "UPDATE customer_risk_score SET edd_last_review_tick = ?1
+             WHERE run_id = ?2 AND customer_id = ?3",
+            params![tick as i64, run_id, review.customer_id],
+        )?;
+        Ok(())
+    }
+
+    /// Cost of reviews completed in the window, inclusive.
+    pub fn edd_review_cost_between(
+        &self,
+        run_id:     &str,
+        start_tick: Tick,
+        end_tick:   Tick,
+    ) -> SimResult<f64> {
+        let total: f64 = self.conn.query_row(
+            "SELECT COALESCE(SUM(cost), 0.0) FROM edd_review
+             WHERE run_id = ?1 AND completed_tick >= ?2 AND completed_tick <= ?3",
+            params![run_id, start_tick as i64, end_tick as i64],
+            |row| row.get(0),
+        )?;
+        Ok(total)
+    }
+
+    // ── Test / summary helpers ────────────────────────────────────────
+
+    /// All tier transitions for a run, oldest first (for tests).
+    pub fn risk_tier_transitions(&self, run_id: &str) -> SimResult<Vec<RiskTierTransitionRow>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT tick, customer_id, from_tier, to_tier, reason, behavior_risk
+             FROM risk_tier_transition WHERE run_id = ?1 ORDER BY transition_id",
+        )?;
+        let rows = stmt.query_map(params![run_id], |row| {
+            Ok(RiskTierTransitionRow {
+                tick:          row.get::<_, i64>(0)? as u64,
+                customer_id:   row.get(1)?,
+                from_tier:     row.get(2)?,
+                to_tier:       row.get(3)?,
+                reason:        row.get(4)?,
+                behavior_risk: row.get(5)?,
+            })
+        })?;
+        rows.collect::<Result<Vec<_>, _>>().map_err(Into::into)
+    }
+
+    /// All EDD reviews for a run, by due tick (for tests).
+    pub fn edd_reviews(&self, run_id: &str) -> SimResult<Vec<EddReviewRow>> {
+        let mut stmt = self.conn.prepare(&format!(
+            "SELECT {REVIEW_COLUMNS} FROM edd_review WHERE run_id = ?1
+             ORDER BY due_tick, review_id",
+        ))?;
+        let rows = stmt.query_map(params![run_id], review_row_mapper)?;
+        rows.collect::<Result<Vec<_>, _>>().map_err(Into::into)
+    }
+}
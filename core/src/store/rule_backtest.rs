@@ -0,0 +1,20 @@
+//! Store methods for rule backtests (Phase 4.72).
+
+use crate::{error::SimResult, types::Tick};
+use rusqlite::params;
+
+use super::SimStore;
+
+impl SimStore {
+    /// Customers the run raised an AML alert on between the two ticks,
+    /// inclusive, by id.
+    pub fn alerted_customers_between(&self, run_id: &str, start_tick: Tick, end_tick: Tick) -> SimResult<Vec<String>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT DISTINCT customer_id FROM aml_alert
+             WHERE run_id = ?1 AND tick BETWEEN ?2 AND ?3
+             ORDER BY customer_id",
+        )?;
+        let rows = stmt.query_map(params![run_id, start_tick as i64, end_tick as i64], |r| r.get(0))?;
+        rows.collect::<Result<_, _>>().map_err(Into::into)
+    }
+}
@@ -0,0 +1,188 @@
+//! Store methods for monitoring rule tuning and tuning reviews (Phase 4.17).
+
+use crate::{error::SimResult, types::Tick};
+use rusqlite::{params, OptionalExtension};
+
+/// Row from the `tuning_review` table.
+#[derive(Debug, Clone)]
+pub struct TuningReviewRow {
+    pub review_id:      String,
+    pub rule_id:        String,
+    pub rationale:      String,
+    pub opened_tick:    Tick,
+    pub due_tick:       Tick,
+    /// "in_review" | "approved"
+    pub status:         String,
+    pub completed_tick: Option<Tick>,
+    pub cost:           f64,
+}
+
+/// Row from the `rule_tuning` table.
+#[derive(Debug, Clone)]
+pub struct RuleTuningRow {
+    pub tuning_id:            String,
+    pub tick:                 Tick,
+    pub rule_id:              String,
+    /// "suppress" | "raise_threshold" | "exclude_customer"
+    pub change:               String,
+    pub threshold_multiplier: f64,
+    pub customer_id:          Option<String>,
+    pub review_id:            Option<String>,
+    pub documented:           bool,
+    pub active:               bool,
+}
+
+use super::SimStore;
+
+fn review_row_mapper(row: &rusqlite::Row<'_>) -> rusqlite::Result<TuningReviewRow> {
+    Ok(TuningReviewRow {
+        review_id:      row.get(0)?,
+        rule_id:        row.get(1)?,
+        rationale:      row.get(2)?,
+        opened_tick:    row.get::<_, i64>(3)? as u64,
+        due_tick:       row.get::<_, i64>(4)? as u64,
+        status:         row.get(5)?,
+        completed_tick: row.get::<_, Option<i64>>(6)?.map(|t| t as u64),
+        cost:           row.get(7)?,
+    })
+}
+
+fn tuning_row_mapper(row: &rusqlite::Row<'_>) -> rusqlite::Result<RuleTuningRow> {
+    Ok(RuleTuningRow {
+        tuning_id:            row.get(0)?,
+        tick:                 row.get::<_, i64>(1)? as u64,
+        rule_id:              row.get(2)?,
+        change:               row.get(3)?,
+        threshold_multiplier: row.get(4)?,
+        customer_id:          row.get(5)?,
+        review_id:            row.get(6)?,
+        documented:           row.get::<_, i64>(7)? != 0,
+        active:               row.get::<_, i64>(8)? != 0,
+    })
+}
+
+const REVIEW_COLUMNS: &str =
+    "review_id, rule_id, rationale, opened_tick, due_tick, status, completed_tick, cost";
+const TUNING_COLUMNS: &str =
+    "tuning_id, tick, rule_id, change, threshold_multiplier, customer_id, review_id, documented, active";
+
+impl SimStore {
+    pub fn insert_tuning_review(&self, run_id: &str, row: &TuningReviewRow) -> SimResult<()> {
+        self.conn.execute(
+            "INSERT INTO tuning_review
+             (run_id, review_id, rule_id, rationale, opened_tick, due_tick, status, completed_tick, cost)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
+            params![
+                run_id, row.review_id, row.rule_id, row.rationale, row.opened_tick as i64,
+                row.due_tick as i64, row.status, row.completed_tick.map(|t| t as i64), row.cost,
+            ],
+        )?;
+        Ok(())
+    }
+
+    pub fn tuning_review(&self, run_id: &str, review_id: &str) -> SimResult<Option<TuningReviewRow>> {
+        let row = self.conn.query_row(
+            &format!("SELECT {REVIEW_COLUMNS} FROM tuning_review WHERE run_id = ?1 AND review_id = ?2"),
+            params![run_id, review_id],
+            review_row_mapper,
+        ).optional()?;
+        Ok(row)
+    }
+
+    /// Reviews still in review whose due tick has arrived.
+    pub fn due_tuning_reviews(&self, run_id: &str, tick: Tick) -> SimResult<Vec<TuningReviewRow>> {
+        let mut stmt = self.conn.prepare(&format!(
+            "SELECT {REVIEW_COLUMNS} FROM tuning_review
+             WHERE run_id = ?1 AND status = 'in_review' AND due_tick <= ?2
+             ORDER BY due_tick, review_id",
+        ))?;
+        let rows = stmt.query_map(params![run_id, tick as i64], review_row_mapper)?;
+        rows.collect::<Result<Vec<_>, _>>().map_err(Into::into)
+    }
+
+    pub fn approve_tuning_review(&self, run_id: &str, review_id: &str, tick: Tick) -> SimResult<()> {
+        self.conn.execute(
+            "UPDATE tuning_review SET status = 'approved', completed_tick = ?1
+             WHERE run_id = ?2 AND review_id = ?3",
+            params![tick as i64, run_id, review_id],
+        )?;
+        Ok(())
+    }
+
+    /// Review costs for reviews approved in the window, inclusive.
+    pub fn tuning_review_cost_between(
+        &self,
+        run_id:     &str,
+        start_tick: Tick,
+        end_tick:   Tick,
+    ) -> SimResult<f64> {
+        let total: f64 = self.conn.query_row(
+            "SELECT COALESCE(SUM(cost), 0.0) FROM tuning_review
+             WHERE run_id = ?1 AND completed_tick >= ?2 AND completed_tick <= ?3",
+            params![run_id, start_tick as i64, end_tick as i64],
+            |row| row.get(0),
+        )?;
+        Ok(total)
+    }
+
+    pub fn insert_rule_tuning(&self, run_id: &str, row: &RuleTuningRow) -> SimResult<()> {
+        self.conn.execute(
+            "INSERT INTO rule_tuning
+             (run_id, tuning_id, tick, rule_id, change, threshold_multiplier, customer_id,
+              review_id, documented, active)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)",
+            params![
+                run_id, row.tuning_id, row.tick as i64, row.rule_id, row.change,
+                row.threshold_multiplier, row.customer_id, row.review_id,
+                row.documented as i64, row.active as i64,
+            ],
+        )?;
+        Ok(())
+    }
+
+    /// Deactivate the rule's active changes of `change`, or all of them
+    /// when `change` is None.
+    pub fn deactivate_rule_tunings(
+        &self,
+        run_id:  &str,
+        rule_id: &str,
+        change:  Option<&str>,
+    ) -> SimResult<()> {
+        self.conn.execute(
+            "UPDATE rule_tuning SET active = 0
+             WHERE run_id = ?1 AND rule_id = ?2 AND active = 1
+               AND (?3 IS NULL OR change = ?3)",
+            params![run_id, rule_id, change],
+        )?;
+        Ok(())
+    }
+
+    pub fn active_rule_tunings(&self, run_id: &str) -> SimResult<Vec<RuleTuningRow>> {
+        let mut stmt = self.conn.prepare(&format!(
+            "SELECT {TUNING_COLUMNS} FROM rule_tuning
+             WHERE run_id = ?1 AND active = 1 ORDER BY tick, tuning_id",
+        ))?;
+        let rows = stmt.query_map(params![run_id], tuning_row_mapper)?;
+        rows.collect::<Result<Vec<_>, _>>().map_err(Into::into)
+    }
+
+    // ── Test / summary helpers ────────────────────────────────────────
+
+    /// All tuning reviews for a run (for tests).
+    pub fn tuning_reviews(&self, run_id: &str) -> SimResult<Vec<TuningReviewRow>> {
+        let mut stmt = self.conn.prepare(&format!(
+            "SELECT {REVIEW_COLUMNS} FROM tuning_review WHERE run_id = ?1 ORDER BY opened_tick, review_id",
+        ))?;
+        let rows = stmt.query_map(params![run_id], review_row_mapper)?;
+        rows.collect::<Result<Vec<_>, _>>().map_err(Into::into)
+    }
+
+    /// All rule tunings for a run, active or not (for tests).
+    pub fn rule_tunings(&self, run_id: &str) -> SimResult<Vec<RuleTuningRow>> {
+        let mut stmt = self.conn.prepare(&format!(
+            "SELECT {TUNING_COLUMNS} FROM rule_tuning WHERE run_id = ?1 ORDER BY tick, tuning_id",
+        ))?;
+        let rows = stmt.query_map(params![run_id], tuning_row_mapper)?;
+        rows.collect::<Result<Vec<_>, _>>().map_err(Into::into)
+    }
+}
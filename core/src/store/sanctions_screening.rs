@@ -0,0 +1,97 @@
+//! Store methods for sanctions name matching (Phase 4.89).
+
+use crate::{error::SimResult, types::Tick};
+use rusqlite::params;
+
+use super::{adverse_media::ScreeningSubjectRow, SimStore};
+
+/// A sanctions screening hit with how it was scored.
+#[derive(Debug, Clone)]
+pub struct SanctionsHitRow {
+    pub screening_id:      String,
+    pub customer_id:       String,
+    pub tick:              Tick,
+    pub entity_id:         String,
+    /// 'exact_match' | 'alias_match' | 'fuzzy_match'
+    pub match_type:        String,
+    pub match_score:       f64,
+    pub match_explanation: String,
+    /// Ground truth: the customer is the listed party.
+    pub true_match:        bool,
+}
+
+impl SimStore {
+    /// Customers onboarded from `start` to `end`, with the identifiers
+    /// they are screened on.
+    pub fn screening_subjects_onboarded(
+        &self,
+        run_id: &str,
+        start: Tick,
+        end: Tick,
+    ) -> SimResult<Vec<ScreeningSubjectRow>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT c.customer_id, COALESCE(c.name, ''), c.segment,
+                    COALESCE(ci.citizenship_country, 'US'),
+                    CAST(substr(id.date_of_birth, 1, 4) AS INTEGER)
+             FROM customer c
+             LEFT JOIN customer_international ci
+               ON ci.run_id = c.run_id AND ci.customer_id = c.customer_id
+             LEFT JOIN customer_identity id
+               ON id.run_id = c.run_id AND id.customer_id = c.customer_id
+             WHERE c.run_id = ?1 AND c.open_tick >= ?2 AND c.open_tick <= ?3
+             ORDER BY c.customer_id",
+        )?;
+        let rows = stmt.query_map(params![run_id, start as i64, end as i64], |row| {
+            let segment: String = row.get(2)?;
+            Ok(ScreeningSubjectRow {
+                customer_id: row.get(0)?,
+                name:        row.get(1)?,
+                business:    segment == "small_business" || segment == "commercial",
+                country:     row.get(3)?,
+                birth_year:  row.get::<_, Option<i64>>(4)?.map(|y| y as u32),
+            })
+        })?;
+        rows.collect::<Result<Vec<_>, _>>().map_err(Into::into)
+    }
+
+    /// Record how a screening result was scored and, for sanctions hits,
+    /// whether the customer really is the listed party.
+    pub fn annotate_screening_result(
+        &self,
+        run_id: &str,
+        screening_id: &str,
+        explanation: &str,
+        true_match: Option<bool>,
+    ) -> SimResult<()> {
+        self.conn.execute(
+            "UPDATE aml_screening_result SET match_explanation = ?3, true_match = ?4
+             WHERE run_id = ?1 AND screening_id = ?2",
+            params![run_id, screening_id, explanation, true_match],
+        )?;
+        Ok(())
+    }
+
+    /// Every sanctions hit in the run, in tick order.
+    pub fn sanctions_hits(&self, run_id: &str) -> SimResult<Vec<SanctionsHitRow>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT screening_id, customer_id, screening_tick, matched_entity_id, match_type,
+                    match_score, COALESCE(match_explanation, ''), COALESCE(true_match, 0)
+             FROM aml_screening_result
+             WHERE run_id = ?1 AND screening_type = 'ofac_sanctions'
+             ORDER BY screening_tick, customer_id",
+        )?;
+        let rows = stmt.query_map(params![run_id], |row| {
+            Ok(SanctionsHitRow {
+                screening_id:      row.get(0)?,
+                customer_id:       row.get(1)?,
+                tick:              row.get::<_, i64>(2)? as Tick,
+                entity_id:         row.get(3)?,
+                match_type:        row.get(4)?,
+                match_score:       row.get(5)?,
+                match_explanation: row.get(6)?,
+                true_match:        row.get::<_, i64>(7)? != 0,
+            })
+        })?;
+        rows.collect::<Result<Vec<_>, _>>().map_err(Into::into)
+    }
+}
@@ -0,0 +1,208 @@
+//! Store methods for SAR filing decisions and quality metrics (Phase 4.16).
+
+use crate::{error::SimResult, types::Tick};
+use rusqlite::params;
+
+/// Row from the `sar_decision` table.
+#[derive(Debug, Clone)]
+pub struct SarDecisionRow {
+    pub alert_id:    String,
+    pub tick:        Tick,
+    pub customer_id: String,
+    pub alert_score: f64,
+    pub threshold:   f64,
+    /// "filed" | "defensive" | "missed"
+    pub decision:    String,
+    pub sar_id:      Option<String>,
+    pub cost:        f64,
+}
+
+/// Row from the `sar_quality_metrics` table.
+#[derive(Debug, Clone)]
+pub struct SarQualityMetricsRow {
+    pub tick:              Tick,
+    pub threshold:         f64,
+    pub sars_filed:        i64,
+    pub defensive_filings: i64,
+    pub missed_filings:    i64,
+    pub sar_quality:       Option<f64>,
+    pub filing_cost:       f64,
+}
+
+/// A reportable alert still open, as the missed-filing sweep sees it.
+#[derive(Debug, Clone)]
+pub struct UnfiledAlertRow {
+    pub alert_id:    String,
+    pub customer_id: String,
+    pub tick:        Tick,
+    pub alert_score: f64,
+}
+
+/// Filed, defensive and missed decision counts for a window.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SarDecisionCounts {
+    pub filed:     i64,
+    pub defensive: i64,
+    pub missed:    i64,
+}
+
+use super::SimStore;
+
+impl SimStore {
+    pub fn insert_sar_decision(&self, run_id: &str, row: &SarDecisionRow) -> SimResult<()> {
+        self.conn.execute(
+            "INSERT OR REPLACE INTO sar_decision
+             (run_id, alert_id, tick, customer_id, alert_score, threshold, decision, sar_id, cost)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
+            params![
+                run_id, row.alert_id, row.tick as i64, row.customer_id, row.alert_score,
+                row.threshold, row.decision, row.sar_id, row.cost,
+            ],
+        )?;
+        Ok(())
+    }
+
+    /// Alerts raised before `before_tick` that are reportable but will
+    /// not be filed: open ones scored in `[reportable, threshold)`, below
+    /// the filing threshold, and any reportable one an analyst has held
+    /// under investigation (Phase 4.85).
+    pub fn unfiled_reportable_alerts(
+        &self,
+        run_id:      &str,
+        reportable:  f64,
+        threshold:   f64,
+        before_tick: Tick,
+    ) -> SimResult<Vec<UnfiledAlertRow>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT alert_id, customer_id, tick, COALESCE(alert_score, 0.0)
+             FROM aml_alert
+             WHERE run_id = ?1
+               AND COALESCE(alert_score, 0.0) >= ?2
+               AND (status = 'open' AND COALESCE(alert_score, 0.0) < ?3 OR status = 'investigating')
+               AND tick < ?4
+             ORDER BY tick, alert_id",
+        )?;
+        let rows = stmt.query_map(
+            params![run_id, reportable, threshold, before_tick as i64],
+            |row| {
+                Ok(UnfiledAlertRow {
+                    alert_id:    row.get(0)?,
+                    customer_id: row.get(1)?,
+                    tick:        row.get::<_, i64>(2)? as u64,
+                    alert_score: row.get(3)?,
+                })
+            },
+        )?;
+        rows.collect::<Result<Vec<_>, _>>().map_err(Into::into)
+    }
+
+    pub fn mark_alert_sar_missed(&self, run_id: &str, alert_id: &str) -> SimResult<()> {
+        self.conn.execute(
+            "UPDATE aml_alert SET status = 'sar_missed' WHERE run_id = ?1 AND alert_id = ?2",
+            params![run_id, alert_id],
+        )?;
+        Ok(())
+    }
+
+    /// Decision counts for the window, inclusive.
+    pub fn sar_decision_counts_between(
+        &self,
+        run_id:     &str,
+        start_tick: Tick,
+        end_tick:   Tick,
+    ) -> SimResult<SarDecisionCounts> {
+        let mut stmt = self.conn.prepare(
+            "SELECT decision, COUNT(*) FROM sar_decision
+             WHERE run_id = ?1 AND tick >= ?2 AND tick <= ?3
+             GROUP BY decision",
+        )?;
+        let rows = stmt.query_map(params![run_id, start_tick as i64, end_tick as i64], |row| {
+            Ok((row.get::<_, String>(0)?, row.get::<_, i64>(1)?))
+        })?;
+        let mut counts = SarDecisionCounts::default();
+        for row in rows {
+            let (decision, count) = row?;
+            match decision.as_str() {
+                "filed" => counts.filed = count,
+                "defensive" => counts.defensive = count,
+                "missed" => counts.missed = count,
+                _ => {}
+            }
+        }
+        Ok(counts)
+    }
+
+    /// Filing costs for decisions in the window, inclusive.
+    pub fn sar_filing_cost_between(
+        &self,
+        run_id:     &str,
+        start_tick: Tick,
+        end_tick:   Tick,
+    ) -> SimResult<f64> {
+        let total: f64 = self.conn.query_row(
+            "SELECT COALESCE(SUM(cost), 0.0) FROM sar_decision
+             WHERE run_id = ?1 AND tick >= ?2 AND tick <= ?3",
+            params![run_id, start_tick as i64, end_tick as i64],
+            |row| row.get(0),
+        )?;
+        Ok(total)
+    }
+
+    pub fn insert_sar_quality_metrics(&self, run_id: &str, row: &SarQualityMetricsRow) -> SimResult<()> {
+        self.conn.execute(
+            "INSERT OR REPLACE INTO sar_quality_metrics
+             (run_id, tick, threshold, sars_filed, defensive_filings, missed_filings,
+              sar_quality, filing_cost)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+            params![
+                run_id, row.tick as i64, row.threshold, row.sars_filed, row.defensive_filings,
+                row.missed_filings, row.sar_quality, row.filing_cost,
+            ],
+        )?;
+        Ok(())
+    }
+
+    // ── Test / summary helpers ────────────────────────────────────────
+
+    /// All SAR decisions for a run (for tests).
+    pub fn sar_decisions(&self, run_id: &str) -> SimResult<Vec<SarDecisionRow>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT alert_id, tick, customer_id, alert_score, threshold, decision, sar_id, cost
+             FROM sar_decision WHERE run_id = ?1 ORDER BY tick, alert_id",
+        )?;
+        let rows = stmt.query_map(params![run_id], |row| {
+            Ok(SarDecisionRow {
+                alert_id:    row.get(0)?,
+                tick:        row.get::<_, i64>(1)? as u64,
+                customer_id: row.get(2)?,
+                alert_score: row.get(3)?,
+                threshold:   row.get(4)?,
+                decision:    row.get(5)?,
+                sar_id:      row.get(6)?,
+                cost:        row.get(7)?,
+            })
+        })?;
+        rows.collect::<Result<Vec<_>, _>>().map_err(Into::into)
+    }
+
+    /// All monthly SAR quality metrics for a run (for tests).
+    pub fn sar_quality_metrics(&self, run_id: &str) -> SimResult<Vec<SarQualityMetricsRow>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT tick, threshold, sars_filed, defensive_filings, missed_filings,
+                    sar_quality, filing_cost
+             FROM sar_quality_metrics WHERE run_id = ?1 ORDER BY tick",
+        )?;
+        let rows = stmt.query_map(params![run_id], |row| {
+            Ok(SarQualityMetricsRow {
+                tick:              row.get::<_, i64>(0)? as u64,
+                threshold:         row.get(1)?,
+                sars_filed:        row.get(2)?,
+                defensive_filings: row.get(3)?,
+                missed_filings:    row.get(4)?,
+                sar_quality:       row.get(5)?,
+                filing_cost:       row.get(6)?,
+            })
+        })?;
+        rows.collect::<Result<Vec<_>, _>>().map_err(Into::into)
+    }
+}
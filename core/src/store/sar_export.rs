@@ -0,0 +1,173 @@
+//! Store methods for SAR e-filing export (Phase 4.84).
+
+use crate::{
+    error::SimResult,
+    transaction_monitoring_subsystem::{AMLAlert, SuspiciousActivityReport},
+    types::Tick,
+};
+use rusqlite::{params, OptionalExtension};
+
+use super::SimStore;
+
+/// A transaction listed on an exported SAR.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SarTransactionRow {
+    pub txn_id:       String,
+    pub account_id:   String,
+    pub tick:         Tick,
+    pub amount:       f64,
+    pub direction:    String,
+    pub category:     String,
+    pub counterparty: Option<String>,
+}
+
+impl SimStore {
+    /// SARs not yet in an export batch, in filing order.
+    pub fn unexported_sars(&self, run_id: &str) -> SimResult<Vec<SuspiciousActivityReport>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT s.sar_id, s.run_id, s.filing_tick, s.subject_type, s.subject_id, s.activity_type,
+                    s.suspicious_amount, s.narrative, s.filing_deadline, s.filed_on_time, s.filing_status,
+                    COALESCE(s.regulatory_fine, 0.0), s.related_alerts
+               FROM suspicious_activity_report s
+              WHERE s.run_id = ?1
+                AND NOT EXISTS (SELECT 1 FROM sar_export e WHERE e.run_id = s.run_id AND e.sar_id = s.sar_id)
+              ORDER BY s.filing_tick, s.rowid",
+        )?;
+        let rows = stmt.query_map(params![run_id], |row| {
+            Ok(SuspiciousActivityReport {
+                sar_id: row.get(0)?,
+                run_id: row.get(1)?,
+                filing_tick: row.get::<_, i64>(2)? as Tick,
+                subject_type: row.get(3)?,
+                subject_id: row.get(4)?,
+                activity_type: row.get(5)?,
+                suspicious_amount: row.get(6)?,
+                narrative: row.get(7)?,
+                filing_deadline: row.get::<_, i64>(8)? as Tick,
+                filed_on_time: row.get(9)?,
+                filing_status: row.get(10)?,
+                regulatory_fine: row.get(11)?,
+                related_alerts: row.get(12)?,
+            })
+        })?;
+        rows.collect::<Result<Vec<_>, _>>().map_err(Into::into)
+    }
+
+    /// An AML alert by id; None if the run has no such alert.
+    pub fn aml_alert(&self, run_id: &str, alert_id: &str) -> SimResult<Option<AMLAlert>> {
+        let alert = self
+            .conn
+            .query_row(
+                "SELECT alert_id, run_id, customer_id, tick, COALESCE(rule_id, 'UNKNOWN'), alert_type,
+                        COALESCE(alert_score, 0.0), description, triggered_amount, transaction_count, status
+                   FROM aml_alert
+                  WHERE run_id = ?1 AND alert_id = ?2",
+                params![run_id, alert_id],
+                |row| {
+                    Ok(AMLAlert {
+                        alert_id: row.get(0)?,
+                        run_id: row.get(1)?,
+                        customer_id: row.get(2)?,
+                        tick: row.get::<_, i64>(3)? as Tick,
+                        rule_id: row.get(4)?,
+                        alert_type: row.get(5)?,
+                        alert_score: row.get(6)?,
+                        description: row.get(7)?,
+                        triggered_amount: row.get(8)?,
+                        transaction_count: row.get(9)?,
+                        status: row.get(10)?,
+                    })
+                },
+            )
+            .optional()?;
+        Ok(alert)
+    }
+
+    /// Every account the customer has held, open or closed.
+    pub fn customer_account_ids(&self, run_id: &str, customer_id: &str) -> SimResult<Vec<String>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT account_id FROM account WHERE run_id = ?1 AND customer_id = ?2 ORDER BY account_id",
+        )?;
+        let rows = stmt.query_map(params![run_id, customer_id], |row| row.get(0))?;
+        rows.collect::<Result<Vec<_>, _>>().map_err(Into::into)
+    }
+
+    /// The customer's transactions in ticks `from_tick..=to_tick`: the
+    /// count and total of all of them, and the `limit` largest, largest
+    /// first.
+    pub fn sar_transactions(
+        &self,
+        run_id: &str,
+        customer_id: &str,
+        from_tick: Tick,
+        to_tick: Tick,
+        limit: usize,
+    ) -> SimResult<(i64, f64, Vec<SarTransactionRow>)> {
+        let (count, total) = self.conn.query_row(
+            "SELECT COUNT(*), COALESCE(SUM(t.amount), 0.0)
+               FROM transactions t
+               JOIN account a ON a.account_id = t.account_id
+              WHERE t.run_id = ?1 AND a.customer_id = ?2 AND t.tick BETWEEN ?3 AND ?4",
+            params![run_id, customer_id, from_tick as i64, to_tick as i64],
+            |row| Ok((row.get(0)?, row.get(1)?)),
+        )?;
+        let mut stmt = self.conn.prepare(
+            "SELECT t.txn_id, t.account_id, t.tick, t.amount, t.direction, t.category, t.counterparty
+               FROM transactions t
+               JOIN account a ON a.account_id = t.account_id
+              WHERE t.run_id = ?1 AND a.customer_id = ?2 AND t.tick BETWEEN ?3 AND ?4
+              ORDER BY t.amount DESC, t.tick, t.txn_id
+              LIMIT ?5",
+        )?;
+        let rows = stmt.query_map(
+            params![run_id, customer_id, from_tick as i64, to_tick as i64, limit as i64],
+            |row| {
+                Ok(SarTransactionRow {
+                    txn_id:       row.get(0)?,
+                    account_id:   row.get(1)?,
+                    tick:         row.get::<_, i64>(2)? as Tick,
+                    amount:       row.get(3)?,
+                    direction:    row.get(4)?,
+                    category:     row.get(5)?,
+                    counterparty: row.get(6)?,
+                })
+            },
+        )?;
+        let largest = rows.collect::<Result<Vec<_>, _>>()?;
+        Ok((count, total, largest))
+    }
+
+    /// Record SARs as sent in export batch `batch_id`.
+    pub fn mark_sars_exported(&self, run_id: &str, batch_id: &str, format: &str, sar_ids: &[String]) -> SimResult<()> {
+        let mut stmt = self
+            .conn
+            .prepare("INSERT INTO sar_export (run_id, sar_id, batch_id, format) VALUES (?1, ?2, ?3, ?4)")?;
+        for sar_id in sar_ids {
+            stmt.execute(params![run_id, sar_id, batch_id, format])?;
+        }
+        Ok(())
+    }
+
+    /// The batch a SAR was exported in, if it has been.
+    pub fn sar_export_batch(&self, run_id: &str, sar_id: &str) -> SimResult<Option<String>> {
+        let batch = self
+            .conn
+            .query_row(
+                "SELECT batch_id FROM sar_export WHERE run_id = ?1 AND sar_id = ?2",
+                params![run_id, sar_id],
+                |row| row.get(0),
+            )
+            .optional()?;
+        Ok(batch)
+    }
+
+    /// Export batches written for the run so far.
+    pub fn sar_export_batch_count(&self, run_id: &str) -> SimResult<i64> {
+        let count = self.conn.query_row(
+            "SELECT COUNT(DISTINCT batch_id) FROM sar_export WHERE run_id = ?1",
+            params![run_id],
+            |row| row.get(0),
+        )?;
+        Ok(count)
+    }
+}
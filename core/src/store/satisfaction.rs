@@ -0,0 +1,135 @@
+//! Store methods for satisfaction dynamics (Phase 4.33).
+
+use crate::{error::SimResult, types::Tick};
+use rusqlite::params;
+use std::collections::HashMap;
+
+use super::SimStore;
+
+/// Row from the `satisfaction_gesture` table.
+#[derive(Debug, Clone)]
+pub struct SatisfactionGestureRow {
+    pub tick:            Tick,
+    pub customer_id:     String,
+    pub source:          String,
+    pub requested_delta: f64,
+    pub applied_delta:   f64,
+}
+
+/// Row from the `satisfaction_scar` table.
+#[derive(Debug, Clone)]
+pub struct SatisfactionScarRow {
+    pub customer_id:  String,
+    pub complaint_id: String,
+    pub tick:         Tick,
+    pub severity:     f64,
+}
+
+impl SimStore {
+    pub fn insert_satisfaction_gesture(&self, run_id: &str, row: &SatisfactionGestureRow) -> SimResult<()> {
+        self.conn.execute(
+            "INSERT INTO satisfaction_gesture
+             (run_id, tick, customer_id, source, requested_delta, applied_delta)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+            params![
+                run_id, row.tick as i64, row.customer_id, row.source,
+                row.requested_delta, row.applied_delta,
+            ],
+        )?;
+        Ok(())
+    }
+
+    /// Goodwill gestures to the customer in the inclusive tick window.
+    pub fn satisfaction_gestures_in_window(
+        &self,
+        run_id:      &str,
+        customer_id: &str,
+        tick_start:  Tick,
+        tick_end:    Tick,
+    ) -> SimResult<i64> {
+        let count: i64 = self.conn.query_row(
+            "SELECT COUNT(*) FROM satisfaction_gesture
+             WHERE run_id = ?1 AND customer_id = ?2 AND tick >= ?3 AND tick <= ?4",
+            params![run_id, customer_id, tick_start as i64, tick_end as i64],
+            |row| row.get(0),
+        )?;
+        Ok(count)
+    }
+
+    /// Record a scar; a complaint scars its customer at most once.
+    pub fn insert_satisfaction_scar(&self, run_id: &str, row: &SatisfactionScarRow) -> SimResult<()> {
+        self.conn.execute(
+            "INSERT OR IGNORE INTO satisfaction_scar
+             (run_id, customer_id, complaint_id, tick, severity)
+             VALUES (?1, ?2, ?3, ?4, ?5)",
+            params![run_id, row.customer_id, row.complaint_id, row.tick as i64, row.severity],
+        )?;
+        Ok(())
+    }
+
+    /// Total scar severity per scarred customer.
+    pub fn satisfaction_scar_totals(&self, run_id: &str) -> SimResult<HashMap<String, f64>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT customer_id, SUM(severity) FROM satisfaction_scar
+             WHERE run_id = ?1 GROUP BY customer_id",
+        )?;
+        let rows = stmt.query_map(params![run_id], |row| Ok((row.get(0)?, row.get(1)?)))?;
+        rows.collect::<Result<HashMap<_, _>, _>>().map_err(Into::into)
+    }
+
+    pub fn customer_segment(&self, run_id: &str, customer_id: &str) -> SimResult<String> {
+        let segment: String = self.conn.query_row(
+            "SELECT segment FROM customer WHERE run_id = ?1 AND customer_id = ?2",
+            params![run_id, customer_id],
+            |row| row.get(0),
+        )?;
+        Ok(segment)
+    }
+
+    pub fn customer_scar_total(&self, run_id: &str, customer_id: &str) -> SimResult<f64> {
+        let total: f64 = self.conn.query_row(
+            "SELECT COALESCE(SUM(severity), 0.0) FROM satisfaction_scar
+             WHERE run_id = ?1 AND customer_id = ?2",
+            params![run_id, customer_id],
+            |row| row.get(0),
+        )?;
+        Ok(total)
+    }
+
+    // ── Test / summary helpers ────────────────────────────────────────
+
+    /// Every goodwill gesture for a run, oldest first (for tests).
+    pub fn satisfaction_gestures(&self, run_id: &str) -> SimResult<Vec<SatisfactionGestureRow>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT tick, customer_id, source, requested_delta, applied_delta
+             FROM satisfaction_gesture WHERE run_id = ?1 ORDER BY gesture_id",
+        )?;
+        let rows = stmt.query_map(params![run_id], |row| {
+            Ok(SatisfactionGestureRow {
+                tick:            row.get::<_, i64>(0)? as Tick,
+                customer_id:     row.get(1)?,
+                source:          row.get(2)?,
+                requested_delta: row.get(3)?,
+                applied_delta:   row.get(4)?,
+            })
+        })?;
+        rows.collect::<Result<Vec<_>, _>>().map_err(Into::into)
+    }
+
+    /// Every scar for a run, oldest first (for tests).
+    pub fn satisfaction_scars(&self, run_id: &str) -> SimResult<Vec<SatisfactionScarRow>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT customer_id, complaint_id, tick, severity
+             FROM satisfaction_scar WHERE run_id = ?1 ORDER BY tick, customer_id",
+        )?;
+        let rows = stmt.query_map(params![run_id], |row| {
+            Ok(SatisfactionScarRow {
+                customer_id:  row.get(0)?,
+                complaint_id: row.get(1)?,
+                tick:         row.get::<_, i64>(2)? as Tick,
+                severity:     row.get(3)?,
+            })
+        })?;
+        rows.collect::<Result<Vec<_>, _>>().map_err(Into::into)
+    }
+}
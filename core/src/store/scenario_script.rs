@@ -0,0 +1,30 @@
+//! Store methods for scenario scripting hooks (Phase 4.68).
+
+use crate::{error::SimResult, types::Tick};
+use rusqlite::params;
+
+use super::SimStore;
+
+impl SimStore {
+    /// Record that `script` has spent its `once(key)`.
+    pub fn spend_script_once_key(&self, run_id: &str, script: &str, key: &str, tick: Tick) -> SimResult<()> {
+        self.conn.execute(
+            "INSERT OR IGNORE INTO scenario_script_once (run_id, script, once_key, tick)
+             VALUES (?1, ?2, ?3, ?4)",
+            params![run_id, script, key, tick as i64],
+        )?;
+        Ok(())
+    }
+
+    /// `(script, key)` of every `once` the run's scripts have spent.
+    pub fn spent_script_once_keys(&self, run_id: &str) -> SimResult<Vec<(String, String)>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT script, once_key FROM scenario_script_once
+             WHERE run_id = ?1 ORDER BY tick, script, once_key",
+        )?;
+        let rows = stmt
+            .query_map(params![run_id], |row| Ok((row.get(0)?, row.get(1)?)))?
+            .collect::<rusqlite::Result<_>>()?;
+        Ok(rows)
+    }
+}
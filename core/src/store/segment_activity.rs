@@ -0,0 +1,278 @@
+//! Store methods for per-tick segment activity (Phase 4.27).
+//!
+//! Each completed tick is folded once into `segment_activity`; range
+//! reads sum the folded rows and scan live only the ticks not folded yet,
+//! so callers get the same totals either way.
+
+use crate::{error::SimResult, types::Tick};
+use rusqlite::params;
+use std::collections::HashMap;
+
+use super::SimStore;
+
+const FEE_CATEGORIES: &str = "('overdraft_fee','nsf_fee','monthly_fee','atm_fee','wire_fee')";
+
+/// Summable activity for one segment over a tick range.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SegmentActivity {
+    pub fee_income:           f64,
+    pub new_customers:        i64,
+    pub complaints_standard:  i64,
+    pub complaints_high:      i64,
+    pub complaints_urgent:    i64,
+    /// Retention offer bonuses paid.
+    pub retention_cost:       f64,
+    pub churned_customers:    i64,
+    pub fee_driven_churn:     i64,
+    /// Churn driven by complaints or SLA breaches.
+    pub service_driven_churn: i64,
+    pub life_event_churn:     i64,
+    /// Hardship program enrollments and the fees waived for them.
+    pub hardship_enrollments: i64,
+    pub hardship_fees_waived: f64,
+    /// Transactions posted, fees aside.
+    pub transaction_count:    i64,
+}
+
+impl SegmentActivity {
+    fn add(&mut self, other: &Self) {
+        self.fee_income += other.fee_income;
+        self.new_customers += other.new_customers;
+        self.complaints_standard += other.complaints_standard;
+        self.complaints_high += other.complaints_high;
+        self.complaints_urgent += other.complaints_urgent;
+        self.retention_cost += other.retention_cost;
+        self.churned_customers += other.churned_customers;
+        self.fee_driven_churn += other.fee_driven_churn;
+        self.service_driven_churn += other.service_driven_churn;
+        self.life_event_churn += other.life_event_churn;
+        self.hardship_enrollments += other.hardship_enrollments;
+        self.hardship_fees_waived += other.hardship_fees_waived;
+        self.transaction_count += other.transaction_count;
+    }
+}
+
+impl SimStore {
+    /// Fold `tick` into `segment_activity`, one row per segment. Call it
+    /// once the tick is complete; folding a tick again replaces its rows.
+    pub fn fold_segment_activity(&self, run_id: &str, tick: Tick, segments: &[&str]) -> SimResult<()> {
+        let mut by_segment = self.scan_segment_activity(run_id, tick, tick)?;
+        for segment in segments {
+            let a = by_segment.remove(*segment).unwrap_or_default();
+            self.conn.execute(
+                "INSERT OR REPLACE INTO segment_activity
+                 (run_id, tick, segment, fee_income, new_customers, complaints_standard,
+                  complaints_high, complaints_urgent, retention_cost, churned_customers,
+                  fee_driven_churn, service_driven_churn, life_event_churn,
+                  hardship_enrollments, hardship_fees_waived, transaction_count)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16)",
+                params![
+                    run_id, tick as i64, segment, a.fee_income, a.new_customers,
+                    a.complaints_standard, a.complaints_high, a.complaints_urgent,
+                    a.retention_cost, a.churned_customers, a.fee_driven_churn,
+                    a.service_driven_churn, a.life_event_churn,
+                    a.hardship_enrollments, a.hardship_fees_waived, a.transaction_count,
+                ],
+            )?;
+        }
+        Ok(())
+    }
+
+    /// A segment's activity over `start..=end`: folded ticks from
+    /// `segment_activity`, anything later scanned live.
+    pub fn segment_activity(
+        &self,
+        run_id:  &str,
+        segment: &str,
+        start:   Tick,
+        end:     Tick,
+    ) -> SimResult<SegmentActivity> {
+        let folded_through: Option<i64> = self.conn.query_row(
+            "SELECT MAX(tick) FROM segment_activity WHERE run_id = ?1",
+            params![run_id],
+            |row| row.get(0),
+        )?;
+
+        let mut total = SegmentActivity::default();
+        let live_from = match folded_through {
+            Some(through) if through >= start as i64 => {
+                total = self.conn.query_row(
+                    "SELECT COALESCE(SUM(fee_income), 0.0), COALESCE(SUM(new_customers), 0),
+                            COALESCE(SUM(complaints_standard), 0), COALESCE(SUM(complaints_high), 0),
+                            COALESCE(SUM(complaints_urgent), 0), COALESCE(SUM(retention_cost), 0.0),
+                            COALESCE(SUM(churned_customers), 0), COALESCE(SUM(fee_driven_churn), 0),
+                            COALESCE(SUM(service_driven_churn), 0), COALESCE(SUM(life_event_churn), 0),
+                            COALESCE(SUM(hardship_enrollments), 0), COALESCE(SUM(hardship_fees_waived), 0.0),
+                            COALESCE(SUM(transaction_count), 0)
+                     FROM segment_activity
+                     WHERE run_id = ?1 AND segment = ?2 AND tick >= ?3 AND tick <= ?4",
+                    params![run_id, segment, start as i64, end as i64],
+                    |row| {
+                        Ok(SegmentActivity {
+                            fee_income:           row.get(0)?,
+                            new_customers:        row.get(1)?,
+                            complaints_standard:  row.get(2)?,
+                            complaints_high:      row.get(3)?,
+                            complaints_urgent:    row.get(4)?,
+                            retention_cost:       row.get(5)?,
+                            churned_customers:    row.get(6)?,
+                            fee_driven_churn:     row.get(7)?,
+                            service_driven_churn: row.get(8)?,
+                            life_event_churn:     row.get(9)?,
+                            hardship_enrollments: row.get(10)?,
+                            hardship_fees_waived: row.get(11)?,
+                            transaction_count:    row.get(12)?,
+                        })
+                    },
+                )?;
+                through as Tick + 1
+            }
+            _ => start,
+        };
+        if live_from <= end {
+            if let Some(live) = self.scan_segment_activity(run_id, live_from, end)?.get(segment) {
+                total.add(live);
+            }
+        }
+        Ok(total)
+    }
+
+    /// Activity per segment over `start..=end`, straight from the source
+    /// tables. Cheap for a tick or two; the index on each tick column
+    /// keeps it from touching older rows.
+    fn scan_segment_activity(
+        &self,
+        run_id: &str,
+        start:  Tick,
+        end:    Tick,
+    ) -> SimResult<HashMap<String, SegmentActivity>> {
+        let mut out: HashMap<String, SegmentActivity> = HashMap::new();
+        let range = params![run_id, start as i64, end as i64];
+
+        let mut stmt = self.conn.prepare(&format!(
+            "SELECT c.segment, SUM(t.amount)
+             FROM transactions t
+             JOIN account a  ON t.account_id = a.account_id AND t.run_id = a.run_id
+             JOIN customer c ON a.customer_id = c.customer_id AND a.run_id = c.run_id
+             WHERE t.run_id = ?1 AND t.tick >= ?2 AND t.tick <= ?3
+               AND t.category IN {FEE_CATEGORIES}
+             GROUP BY c.segment"
+        ))?;
+        for row in stmt.query_map(range, |row| Ok((row.get::<_, String>(0)?, row.get::<_, f64>(1)?)))? {
+            let (segment, amount) = row?;
+            out.entry(segment).or_default().fee_income = amount;
+        }
+
+        let mut stmt = self.conn.prepare(&format!(
+            "SELECT c.segment, COUNT(*)
+             FROM transactions t
+             JOIN account a  ON t.account_id = a.account_id AND t.run_id = a.run_id
+             JOIN customer c ON a.customer_id = c.customer_id AND a.run_id = c.run_id
+             WHERE t.run_id = ?1 AND t.tick >= ?2 AND t.tick <= ?3
+               AND t.category NOT IN {FEE_CATEGORIES}
+             GROUP BY c.segment"
+        ))?;
+        for row in stmt.query_map(range, |row| Ok((row.get::<_, String>(0)?, row.get::<_, i64>(1)?)))? {
+            let (segment, count) = row?;
+            out.entry(segment).or_default().transaction_count = count;
+        }
+
+        let mut stmt = self.conn.prepare(
+            "SELECT segment, COUNT(*) FROM customer
+             WHERE run_id = ?1 AND open_tick >= ?2 AND open_tick <= ?3
+             GROUP BY segment",
+        )?;
+        for row in stmt.query_map(range, |row| Ok((row.get::<_, String>(0)?, row.get::<_, i64>(1)?)))? {
+            let (segment, count) = row?;
+            out.entry(segment).or_default().new_customers = count;
+        }
+
+        let mut stmt = self.conn.prepare(
+            "SELECT cu.segment,
+                    SUM(c.priority = 'standard'), SUM(c.priority = 'high'), SUM(c.priority = 'urgent')
+             FROM complaint c
+             JOIN customer cu ON c.customer_id = cu.customer_id AND c.run_id = cu.run_id
+             WHERE c.run_id = ?1 AND c.tick_opened >= ?2 AND c.tick_opened <= ?3
+             GROUP BY cu.segment",
+        )?;
+        let rows = stmt.query_map(range, |row| {
+            Ok((row.get::<_, String>(0)?, row.get::<_, i64>(1)?, row.get::<_, i64>(2)?, row.get::<_, i64>(3)?))
+        })?;
+        for row in rows {
+            let (segment, standard, high, urgent) = row?;
+            let a = out.entry(segment).or_default();
+            a.complaints_standard = standard;
+            a.complaints_high = high;
+            a.complaints_urgent = urgent;
+        }
+
+        let mut stmt = self.conn.prepare(
+            "SELECT c.segment, SUM(co.bonus_paid)
+             FROM customer_offer co
+             JOIN customer c ON co.customer_id = c.customer_id AND co.run_id = c.run_id
+             WHERE co.run_id = ?1 AND co.tick_paid >= ?2 AND co.tick_paid <= ?3
+               AND co.offer_id LIKE '%retention%'
+             GROUP BY c.segment",
+        )?;
+        for row in stmt.query_map(range, |row| Ok((row.get::<_, String>(0)?, row.get::<_, f64>(1)?)))? {
+            let (segment, cost) = row?;
+            out.entry(segment).or_default().retention_cost = cost;
+        }
+
+        let mut stmt = self.conn.prepare(
+            "SELECT segment, COUNT(*) FROM customer
+             WHERE run_id = ?1 AND status = 'churned'
+               AND close_tick >= ?2 AND close_tick <= ?3
+             GROUP BY segment",
+        )?;
+        for row in stmt.query_map(range, |row| Ok((row.get::<_, String>(0)?, row.get::<_, i64>(1)?)))? {
+            let (segment, count) = row?;
+            out.entry(segment).or_default().churned_customers = count;
+        }
+
+        let mut stmt = self.conn.prepare(
+            "SELECT segment,
+                    SUM(primary_churn_driver = 'fee_burden'),
+                    SUM(primary_churn_driver IN ('complaints', 'sla_breach')),
+                    SUM(primary_churn_driver = 'life_event')
+             FROM churn_cohort
+             WHERE run_id = ?1 AND tick_churned >= ?2 AND tick_churned <= ?3
+             GROUP BY segment",
+        )?;
+        let rows = stmt.query_map(range, |row| {
+            Ok((row.get::<_, String>(0)?, row.get::<_, i64>(1)?, row.get::<_, i64>(2)?, row.get::<_, i64>(3)?))
+        })?;
+        for row in rows {
+            let (segment, fee, service, life_event) = row?;
+            let a = out.entry(segment).or_default();
+            a.fee_driven_churn = fee;
+            a.service_driven_churn = service;
+            a.life_event_churn = life_event;
+        }
+
+        let mut stmt = self.conn.prepare(
+            "SELECT c.segment, COUNT(*)
+             FROM hardship_enrollment h
+             JOIN customer c ON h.customer_id = c.customer_id AND h.run_id = c.run_id
+             WHERE h.run_id = ?1 AND h.tick_enrolled >= ?2 AND h.tick_enrolled <= ?3
+             GROUP BY c.segment",
+        )?;
+        for row in stmt.query_map(range, |row| Ok((row.get::<_, String>(0)?, row.get::<_, i64>(1)?)))? {
+            let (segment, count) = row?;
+            out.entry(segment).or_default().hardship_enrollments = count;
+        }
+
+        let mut stmt = self.conn.prepare(
+            "SELECT c.segment, SUM(w.amount)
+             FROM hardship_fee_waiver w
+             JOIN customer c ON w.customer_id = c.customer_id AND w.run_id = c.run_id
+             WHERE w.run_id = ?1 AND w.tick >= ?2 AND w.tick <= ?3
+             GROUP BY c.segment",
+        )?;
+        for row in stmt.query_map(range, |row| Ok((row.get::<_, String>(0)?, row.get::<_, f64>(1)?)))? {
+            let (segment, amount) = row?;
+            out.entry(segment).or_default().hardship_fees_waived = amount;
+        }
+        Ok(out)
+    }
+}
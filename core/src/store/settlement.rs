@@ -0,0 +1,173 @@
+//! Store methods for per-rail settlement accounts (Phase 4.7).
+
+use crate::{error::SimResult, types::Tick};
+use rusqlite::params;
+
+/// Row from the `settlement_position` table.
+#[derive(Debug, Clone)]
+pub struct SettlementPositionRow {
+    pub rail_id:           String,
+    pub tick:              Tick,
+    pub projected_outflow: f64,
+    pub funded_amount:     f64,
+    pub actual_outflow:    f64,
+    pub shortfall:         f64,
+    pub idle_balance:      f64,
+    pub borrowing_cost:    f64,
+    pub alert:             bool,
+}
+
+use super::SimStore;
+
+impl SimStore {
+    pub fn open_settlement_account(
+        &self,
+        run_id:       &str,
+        rail_id:      &str,
+        counterparty: &str,
+        tick:         Tick,
+    ) -> SimResult<()> {
+        self.conn.execute(
+            "INSERT OR IGNORE INTO settlement_account
+             (run_id, rail_id, account_id, counterparty, opened_tick)
+             VALUES (?1, ?2, ?3, ?4, ?5)",
+            params![run_id, rail_id, format!("NOSTRO-{rail_id}"), counterparty, tick as i64],
+        )?;
+        Ok(())
+    }
+
+    /// Outflow a rail settled over [start_tick, end_tick). Card outflow is
+    /// the cleared amount of authorizations settled in the window; other
+    /// rails sum outbound transactions on the day they settle (rolled items
+    /// on their processing day, returned items included).
+    pub fn settled_outflow_between(
+        &self,
+        run_id:     &str,
+        rail_id:    &str,
+        rail_type:  &str,
+        start_tick: Tick,
+        end_tick:   Tick,
+    ) -> SimResult<f64> {
+        if rail_type == "card" {
+            let total: f64 = self.conn.query_row(
+                "SELECT COALESCE(SUM(COALESCE(cleared_amount, amount)), 0.0)
+                 FROM authorization
+                 WHERE run_id = ?1 AND tick_settled >= ?2 AND tick_settled < ?3",
+                params![run_id, start_tick as i64, end_tick as i64],
+                |row| row.get(0),
+            )?;
+            return Ok(total);
+        }
+        let total: f64 = self.conn.query_row(
+            "SELECT COALESCE(SUM(t.amount), 0.0)
+             FROM transactions t
+             LEFT JOIN payment_cutoff_roll r ON r.run_id = t.run_id AND r.txn_id = t.txn_id
+             WHERE t.run_id = ?1 AND t.payment_rail_id = ?2 AND t.direction = 'debit'
+               AND t.settlement_status IN ('settled', 'returned')
+               AND COALESCE(r.processing_tick, t.tick) >= ?3
+               AND COALESCE(r.processing_tick, t.tick) < ?4",
+            params![run_id, rail_id, start_tick as i64, end_tick as i64],
+            |row| row.get(0),
+        )?;
+        Ok(total)
+    }
+
+    pub fn insert_settlement_position(&self, run_id: &str, row: &SettlementPositionRow) -> SimResult<()> {
+        self.conn.execute(
+            "INSERT OR REPLACE INTO settlement_position
+             (run_id, rail_id, tick, projected_outflow, funded_amount, actual_outflow,
+              shortfall, idle_balance, borrowing_cost, alert)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)",
+            params![
+                run_id, row.rail_id, row.tick as i64, row.projected_outflow, row.funded_amount,
+                row.actual_outflow, row.shortfall, row.idle_balance, row.borrowing_cost,
+                row.alert as i64,
+            ],
+        )?;
+        if row.shortfall > 0.0 {
+            self.conn.execute(
+                "UPDATE settlement_account
+                 SET total_borrowed = total_borrowed + ?1,
+                     total_borrowing_cost = total_borrowing_cost + ?2,
+                     shortfall_count = shortfall_count + 1
+                 WHERE run_id = ?3 AND rail_id = ?4",
+                params![row.shortfall, row.borrowing_cost, run_id, row.rail_id],
+            )?;
+        }
+        Ok(())
+    }
+
+    /// Borrowing cost across all settlement accounts over [start_tick, end_tick].
+    pub fn settlement_borrowing_cost_between(
+        &self,
+        run_id:     &str,
+        start_tick: Tick,
+        end_tick:   Tick,
+    ) -> SimResult<f64> {
+        let cost: f64 = self.conn.query_row(
+            "SELECT COALESCE(SUM(borrowing_cost), 0.0) FROM settlement_position
+             WHERE run_id = ?1 AND tick >= ?2 AND tick <= ?3",
+            params![run_id, start_tick as i64, end_tick as i64],
+            |row| row.get(0),
+        )?;
+        Ok(cost)
+    }
+
+    // ── Test / summary helpers ────────────────────────────────────────
+
+    /// Number of settlement accounts opened (for tests).
+    pub fn settlement_account_count(&self, run_id: &str) -> SimResult<i64> {
+        let count: i64 = self.conn.query_row(
+            "SELECT COUNT(*) FROM settlement_account WHERE run_id = ?1",
+            params![run_id],
+            |row| row.get(0),
+        )?;
+        Ok(count)
+    }
+
+    /// Settlement shortfall alerts raised for a rail (for tests).
+    pub fn settlement_alert_count(&self, run_id: &str, rail_id: &str) -> SimResult<i64> {
+        let count: i64 = self.conn.query_row(
+            "SELECT COUNT(*) FROM early_warning_alert
+             WHERE run_id = ?1 AND alert_type = 'settlement_shortfall' AND segment = ?2",
+            params![run_id, rail_id],
+            |row| row.get(0),
+        )?;
+        Ok(count)
+    }
+
+    /// All positions for a rail, oldest first (for tests).
+    pub fn settlement_positions(&self, run_id: &str, rail_id: &str) -> SimResult<Vec<SettlementPositionRow>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT rail_id, tick, projected_outflow, funded_amount, actual_outflow,
+                    shortfall, idle_balance, borrowing_cost, alert
+             FROM settlement_position WHERE run_id = ?1 AND rail_id = ?2
+             ORDER BY tick",
+        )?;
+        let rows = stmt.query_map(params![run_id, rail_id], |row| {
+            Ok(SettlementPositionRow {
+                rail_id:           row.get(0)?,
+                tick:              row.get::<_, i64>(1)? as Tick,
+                projected_outflow: row.get(2)?,
+                funded_amount:     row.get(3)?,
+                actual_outflow:    row.get(4)?,
+                shortfall:         row.get(5)?,
+                idle_balance:      row.get(6)?,
+                borrowing_cost:    row.get(7)?,
+                alert:             row.get::<_, i64>(8)? != 0,
+            })
+        })?;
+        rows.collect::<Result<Vec<_>, _>>().map_err(Into::into)
+    }
+
+    /// Cumulative (borrowed, borrowing cost) on a rail's settlement account (for tests).
+    pub fn settlement_account_totals(&self, run_id: &str, rail_id: &str) -> SimResult<(f64, f64)> {
+        let totals = self.conn.query_row(
+            "SELECT total_borrowed, total_borrowing_cost FROM settlement_account
+             WHERE run_id = ?1 AND rail_id = ?2",
+            params![run_id, rail_id],
+            |row| Ok((row.get(0)?, row.get(1)?)),
+        )?;
+        Ok(totals)
+    }
+}
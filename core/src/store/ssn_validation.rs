@@ -0,0 +1,107 @@
+//! Store methods for SSN validation and the death master file (Phase 4.14).
+
+use crate::{error::SimResult, types::Tick};
+use rusqlite::{params, OptionalExtension};
+
+/// Row from the `ssn_validation` table.
+#[derive(Debug, Clone)]
+pub struct SsnValidationRow {
+    pub customer_id:     String,
+    pub tick:            Tick,
+    pub issues:          Vec<String>,
+    pub synthetic_score: f64,
+}
+
+/// A synthetic-identity pattern from the `fraud_pattern` table.
+#[derive(Debug, Clone)]
+pub struct SyntheticIdentityPatternRow {
+    pub customer_id:      String,
+    pub detected_tick:    Tick,
+    pub confidence_score: f64,
+    pub indicators:       Vec<String>,
+}
+
+use super::SimStore;
+
+fn validation_row_mapper(row: &rusqlite::Row<'_>) -> rusqlite::Result<SsnValidationRow> {
+    let issues: String = row.get(2)?;
+    Ok(SsnValidationRow {
+        customer_id:     row.get(0)?,
+        tick:            row.get::<_, i64>(1)? as u64,
+        issues:          issues.split(',').filter(|i| !i.is_empty()).map(String::from).collect(),
+        synthetic_score: row.get(3)?,
+    })
+}
+
+impl SimStore {
+    pub fn insert_death_master_file_entry(&self, run_id: &str, ssn: &str, death_year: i32) -> SimResult<()> {
+        self.conn.execute(
+            "INSERT OR IGNORE INTO death_master_file (run_id, ssn, death_year) VALUES (?1, ?2, ?3)",
+            params![run_id, ssn, death_year],
+        )?;
+        Ok(())
+    }
+
+    pub fn on_death_master_file(&self, run_id: &str, ssn: &str) -> SimResult<bool> {
+        let found = self.conn.query_row(
+            "SELECT 1 FROM death_master_file WHERE run_id = ?1 AND ssn = ?2",
+            params![run_id, ssn],
+            |_| Ok(()),
+        ).optional()?;
+        Ok(found.is_some())
+    }
+
+    pub fn insert_ssn_validation(&self, run_id: &str, row: &SsnValidationRow) -> SimResult<()> {
+        self.conn.execute(
+            "INSERT INTO ssn_validation (run_id, customer_id, tick, issues, synthetic_score)
+             VALUES (?1, ?2, ?3, ?4, ?5)",
+            params![
+                run_id, row.customer_id, row.tick as i64,
+                row.issues.join(","), row.synthetic_score,
+            ],
+        )?;
+        Ok(())
+    }
+
+    /// The customer's onboarding SSN validation, if they were validated.
+    pub fn ssn_validation(&self, run_id: &str, customer_id: &str) -> SimResult<Option<SsnValidationRow>> {
+        let row = self.conn.query_row(
+            "SELECT customer_id, tick, issues, synthetic_score FROM ssn_validation
+             WHERE run_id = ?1 AND customer_id = ?2",
+            params![run_id, customer_id],
+            validation_row_mapper,
+        ).optional()?;
+        Ok(row)
+    }
+
+    // ── Test / summary helpers ────────────────────────────────────────
+
+    /// All SSN validations for a run (for tests).
+    pub fn ssn_validations(&self, run_id: &str) -> SimResult<Vec<SsnValidationRow>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT customer_id, tick, issues, synthetic_score FROM ssn_validation
+             WHERE run_id = ?1 ORDER BY customer_id",
+        )?;
+        let rows = stmt.query_map(params![run_id], validation_row_mapper)?;
+        rows.collect::<Result<Vec<_>, _>>().map_err(Into::into)
+    }
+
+    /// Synthetic-identity patterns the fraud detector raised (for tests).
+    pub fn synthetic_identity_patterns(&self, run_id: &str) -> SimResult<Vec<SyntheticIdentityPatternRow>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT primary_customer_id, detected_tick, confidence_score, fraud_indicators
+             FROM fraud_pattern WHERE run_id = ?1 AND pattern_type = 'synthetic_identity'
+             ORDER BY detected_tick, pattern_id",
+        )?;
+        let rows = stmt.query_map(params![run_id], |row| {
+            let indicators: String = row.get(3)?;
+            Ok(SyntheticIdentityPatternRow {
+                customer_id:      row.get(0)?,
+                detected_tick:    row.get::<_, i64>(1)? as u64,
+                confidence_score: row.get(2)?,
+                indicators:       serde_json::from_str(&indicators).unwrap_or_default(),
+            })
+        })?;
+        rows.collect::<Result<Vec<_>, _>>().map_err(Into::into)
+    }
+}
@@ -0,0 +1,163 @@
+//! Store methods for staffing and case-worker capacity (Phase 4.80).
+
+use crate::{error::SimResult, queue::QueueKind, types::Tick};
+use rusqlite::{params, OptionalExtension, Row};
+
+use super::SimStore;
+
+/// Row from the `staffing_snapshot` table.
+#[derive(Debug, Clone, PartialEq)]
+pub struct StaffingSnapshotRow {
+    pub tick:               Tick,
+    /// 'complaints' | 'alerts' | 'exceptions' | 'cases'
+    pub queue:              String,
+    pub ftes:               f64,
+    /// Open items at the start of the tick.
+    pub backlog:            i64,
+    /// Items the queue's staff work in a regular day.
+    pub regular_items:      f64,
+    /// Items they can work this tick, overtime included.
+    pub capacity:           i64,
+    /// Items the owning subsystem worked.
+    pub worked:             i64,
+    /// Worked items beyond `regular_items`, paid as overtime.
+    pub overtime_items:     f64,
+    pub overtime_item_cost: f64,
+    /// Regular pay plus the overtime worked.
+    pub cost:               f64,
+}
+
+const SNAPSHOT_COLUMNS: &str =
+    "tick, queue, ftes, backlog, regular_items, capacity, worked, overtime_items, overtime_item_cost, cost";
+
+fn snapshot_from_row(row: &Row) -> rusqlite::Result<StaffingSnapshotRow> {
+    Ok(StaffingSnapshotRow {
+        tick:               row.get::<_, i64>(0)? as Tick,
+        queue:              row.get(1)?,
+        ftes:               row.get(2)?,
+        backlog:            row.get(3)?,
+        regular_items:      row.get(4)?,
+        capacity:           row.get(5)?,
+        worked:             row.get(6)?,
+        overtime_items:     row.get(7)?,
+        overtime_item_cost: row.get(8)?,
+        cost:               row.get(9)?,
+    })
+}
+
+impl SimStore {
+    pub fn insert_staffing_plan(&self, run_id: &str, tick: Tick, queue: QueueKind, ftes: f64) -> SimResult<()> {
+        self.conn.execute(
+            "INSERT OR REPLACE INTO staffing_plan (run_id, tick, queue, ftes) VALUES (?1, ?2, ?3, ?4)",
+            params![run_id, tick as i64, queue.as_str(), ftes],
+        )?;
+        Ok(())
+    }
+
+    /// FTEs the player last put on `queue`, if ever.
+    pub fn staffing_plan(&self, run_id: &str, queue: QueueKind) -> SimResult<Option<f64>> {
+        self.conn
+            .query_row(
+                "SELECT ftes FROM staffing_plan WHERE run_id = ?1 AND queue = ?2 ORDER BY tick DESC LIMIT 1",
+                params![run_id, queue.as_str()],
+                |row| row.get(0),
+            )
+            .optional()
+            .map_err(Into::into)
+    }
+
+    pub fn insert_staffing_snapshot(&self, run_id: &str, row: &StaffingSnapshotRow) -> SimResult<()> {
+        self.conn.execute(
+            &format!(
+                "INSERT OR REPLACE INTO staffing_snapshot (run_id, {SNAPSHOT_COLUMNS})
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11)"
+            ),
+            params![
+                run_id, row.tick as i64, row.queue, row.ftes, row.backlog, row.regular_items, row.capacity,
+                row.worked, row.overtime_items, row.overtime_item_cost, row.cost,
+            ],
+        )?;
+        Ok(())
+    }
+
+    /// Items `queue`'s staff can work at `tick`, or None when the queue
+    /// is not staffed and its owner works everything due.
+    pub fn staffing_capacity(&self, run_id: &str, tick: Tick, queue: QueueKind) -> SimResult<Option<usize>> {
+        self.conn
+            .query_row(
+                "SELECT capacity FROM staffing_snapshot WHERE run_id = ?1 AND tick = ?2 AND queue = ?3",
+                params![run_id, tick as i64, queue.as_str()],
+                |row| row.get::<_, i64>(0),
+            )
+            .optional()
+            .map(|capacity| capacity.map(|c| c as usize))
+            .map_err(Into::into)
+    }
+
+    /// Record what the owning subsystem worked of `queue` at `tick`, and
+    /// pay the overtime it took.
+    pub fn record_staffing_work(&self, run_id: &str, tick: Tick, queue: QueueKind, worked: usize) -> SimResult<()> {
+        self.conn.execute(
+            "UPDATE staffing_snapshot
+                SET worked = ?4,
+                    overtime_items = MAX(0.0, ?4 - regular_items),
+                    cost = cost + (MAX(0.0, ?4 - regular_items) - overtime_items) * overtime_item_cost
+              WHERE run_id = ?1 AND tick = ?2 AND queue = ?3",
+            params![run_id, tick as i64, queue.as_str(), worked as i64],
+        )?;
+        Ok(())
+    }
+
+    /// Every staffed queue's snapshots, by tick then queue.
+    pub fn staffing_snapshots(&self, run_id: &str) -> SimResult<Vec<StaffingSnapshotRow>> {
+        let mut stmt = self.conn.prepare(&format!(
+            "SELECT {SNAPSHOT_COLUMNS} FROM staffing_snapshot WHERE run_id = ?1 ORDER BY tick, queue"
+        ))?;
+        let rows = stmt.query_map(params![run_id], snapshot_from_row)?;
+        rows.collect::<Result<Vec<_>, _>>().map_err(Into::into)
+    }
+
+    /// Staff pay, overtime included, for ticks in `start..=end`.
+    pub fn staffing_cost_between(&self, run_id: &str, start_tick: Tick, end_tick: Tick) -> SimResult<f64> {
+        let total: f64 = self.conn.query_row(
+            "SELECT COALESCE(SUM(cost), 0.0) FROM staffing_snapshot
+              WHERE run_id = ?1 AND tick >= ?2 AND tick <= ?3",
+            params![run_id, start_tick as i64, end_tick as i64],
+            |row| row.get(0),
+        )?;
+        Ok(total)
+    }
+
+    /// Open monitoring alerts scoring below `below_score` raised before
+    /// `before_tick`, oldest first and at most `limit` of them: the ones
+    /// an analyst can clear without a filing decision. Screening alerts,
+    /// which carry no score, are left to screening.
+    pub fn clearable_alerts(
+        &self,
+        run_id: &str,
+        below_score: f64,
+        before_tick: Tick,
+        limit: usize,
+    ) -> SimResult<Vec<(String, f64)>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT alert_id, alert_score FROM aml_alert
+              WHERE run_id = ?1 AND status = 'open'
+                AND alert_score < ?2 AND tick < ?3
+              ORDER BY tick, alert_id LIMIT ?4",
+        )?;
+        let rows = stmt.query_map(params![run_id, below_score, before_tick as i64, limit as i64], |row| {
+            Ok((row.get(0)?, row.get(1)?))
+        })?;
+        rows.collect::<Result<Vec<_>, _>>().map_err(Into::into)
+    }
+
+    pub fn clear_alert(&self, run_id: &str, alert_id: &str, tick: Tick) -> SimResult<()> {
+        self.conn.execute(
+            "UPDATE aml_alert SET status = 'resolved', disposition = 'false_positive', resolved_tick = ?3,
+                    resolution_notes = 'Cleared: no suspicious activity found'
+              WHERE run_id = ?1 AND alert_id = ?2",
+            params![run_id, alert_id, tick as i64],
+        )?;
+        Ok(())
+    }
+}
@@ -0,0 +1,89 @@
+//! Store methods for the composite stress index (Phase 4.69).
+
+use crate::{error::SimResult, types::Tick};
+use rusqlite::{params, OptionalExtension, Row};
+
+use super::SimStore;
+
+/// Row from the `stress_index` table. A component is None when its
+/// source had nothing to go on that tick.
+#[derive(Debug, Clone, PartialEq)]
+pub struct StressIndexRow {
+    pub tick:                Tick,
+    pub backlog_pressure:    Option<f64>,
+    pub capital_pressure:    Option<f64>,
+    pub reputation_pressure: Option<f64>,
+    pub staffing_pressure:   Option<f64>,
+    pub findings_pressure:   Option<f64>,
+    /// Weighted mean of the components present, 0–1.
+    pub stress:              f64,
+    /// 100 × (1 − stress).
+    pub resilience:          f64,
+    /// 'steady' | 'strained' | 'critical'
+    pub level:               String,
+}
+
+const STRESS_COLUMNS: &str = "tick, backlog_pressure, capital_pressure, reputation_pressure, staffing_pressure,
+     findings_pressure, stress, resilience, level";
+
+fn stress_from_row(row: &Row) -> rusqlite::Result<StressIndexRow> {
+    Ok(StressIndexRow {
+        tick:                row.get::<_, i64>(0)? as Tick,
+        backlog_pressure:    row.get(1)?,
+        capital_pressure:    row.get(2)?,
+        reputation_pressure: row.get(3)?,
+        staffing_pressure:   row.get(4)?,
+        findings_pressure:   row.get(5)?,
+        stress:              row.get(6)?,
+        resilience:          row.get(7)?,
+        level:               row.get(8)?,
+    })
+}
+
+impl SimStore {
+    pub fn insert_stress_index(&self, run_id: &str, row: &StressIndexRow) -> SimResult<()> {
+        self.conn.execute(
+            &format!(
+                "INSERT OR REPLACE INTO stress_index (run_id, {STRESS_COLUMNS})
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)"
+            ),
+            params![
+                run_id, row.tick as i64, row.backlog_pressure, row.capital_pressure, row.reputation_pressure,
+                row.staffing_pressure, row.findings_pressure, row.stress, row.resilience, row.level,
+            ],
+        )?;
+        Ok(())
+    }
+
+    pub fn latest_stress_index(&self, run_id: &str) -> SimResult<Option<StressIndexRow>> {
+        self.conn
+            .query_row(
+                &format!("SELECT {STRESS_COLUMNS} FROM stress_index WHERE run_id = ?1 ORDER BY tick DESC LIMIT 1"),
+                params![run_id],
+                stress_from_row,
+            )
+            .optional()
+            .map_err(Into::into)
+    }
+
+    /// Every tick's index in `start..=end`, oldest first.
+    pub fn stress_index_between(&self, run_id: &str, start: Tick, end: Tick) -> SimResult<Vec<StressIndexRow>> {
+        let mut stmt = self.conn.prepare(&format!(
+            "SELECT {STRESS_COLUMNS} FROM stress_index
+              WHERE run_id = ?1 AND tick >= ?2 AND tick <= ?3 ORDER BY tick"
+        ))?;
+        let rows = stmt.query_map(params![run_id, start as i64, end as i64], stress_from_row)?;
+        rows.collect::<Result<Vec<_>, _>>().map_err(Into::into)
+    }
+
+    /// Exam findings raised at or after `since`.
+    pub fn exam_findings_since(&self, run_id: &str, since: Tick) -> SimResult<i64> {
+        self.conn
+            .query_row(
+                "SELECT COUNT(*) FROM exam_finding WHERE run_id = ?1 AND tick >= ?2",
+                params![run_id, since as i64],
+                |row| row.get(0),
+            )
+            .map_err(Into::into)
+    }
+}
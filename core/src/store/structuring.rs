@@ -0,0 +1,137 @@
+//! Store methods for structuring (Phase 4.88).
+
+use crate::{error::SimResult, types::Tick};
+use rusqlite::params;
+use std::collections::BTreeSet;
+
+use super::SimStore;
+
+/// Row from the `structuring_label` table.
+#[derive(Debug, Clone)]
+pub struct StructuringLabelRow {
+    pub customer_id:   String,
+    /// Ground truth: 'structurer' | 'benign'.
+    pub label:         String,
+    /// The customer's composite risk level when labeled.
+    pub risk_level:    String,
+    pub labeled_tick:  Tick,
+    /// When a structurer's next episode starts; None for the benign.
+    pub next_tick:     Option<Tick>,
+    /// When the episode under way started, if one is.
+    pub episode_start: Option<Tick>,
+    pub deposits_left: u32,
+    pub episodes:      u32,
+}
+
+/// Row from the `structuring_deposit` table.
+#[derive(Debug, Clone)]
+pub struct StructuringDepositRow {
+    pub txn_id:      String,
+    pub customer_id: String,
+    pub account_id:  String,
+    pub tick:        Tick,
+    pub amount:      f64,
+    pub episode:     u32,
+}
+
+impl SimStore {
+    pub fn insert_structuring_label(&self, run_id: &str, row: &StructuringLabelRow) -> SimResult<()> {
+        self.conn.execute(
+            "INSERT OR IGNORE INTO structuring_label
+             (run_id, customer_id, label, risk_level, labeled_tick, next_tick, episode_start,
+              deposits_left, episodes)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
+            params![
+                run_id, row.customer_id, row.label, row.risk_level, row.labeled_tick as i64,
+                row.next_tick.map(|t| t as i64), row.episode_start.map(|t| t as i64),
+                row.deposits_left, row.episodes,
+            ],
+        )?;
+        Ok(())
+    }
+
+    /// Every customer labeled, in customer order.
+    pub fn structuring_labels(&self, run_id: &str) -> SimResult<Vec<StructuringLabelRow>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT customer_id, label, risk_level, labeled_tick, next_tick, episode_start,
+                    deposits_left, episodes
+             FROM structuring_label WHERE run_id = ?1 ORDER BY customer_id",
+        )?;
+        let rows = stmt.query_map(params![run_id], |row| {
+            Ok(StructuringLabelRow {
+                customer_id:   row.get(0)?,
+                label:         row.get(1)?,
+                risk_level:    row.get(2)?,
+                labeled_tick:  row.get::<_, i64>(3)? as Tick,
+                next_tick:     row.get::<_, Option<i64>>(4)?.map(|t| t as Tick),
+                episode_start: row.get::<_, Option<i64>>(5)?.map(|t| t as Tick),
+                deposits_left: row.get(6)?,
+                episodes:      row.get(7)?,
+            })
+        })?;
+        rows.collect::<Result<Vec<_>, _>>().map_err(Into::into)
+    }
+
+    pub fn update_structuring_label(&self, run_id: &str, row: &StructuringLabelRow) -> SimResult<()> {
+        self.conn.execute(
+            "UPDATE structuring_label
+             SET next_tick = ?3, episode_start = ?4, deposits_left = ?5, episodes = ?6
+             WHERE run_id = ?1 AND customer_id = ?2",
+            params![
+                run_id, row.customer_id, row.next_tick.map(|t| t as i64),
+                row.episode_start.map(|t| t as i64), row.deposits_left, row.episodes,
+            ],
+        )?;
+        Ok(())
+    }
+
+    pub fn insert_structuring_deposit(&self, run_id: &str, row: &StructuringDepositRow) -> SimResult<()> {
+        self.conn.execute(
+            "INSERT INTO structuring_deposit (txn_id, run_id, customer_id, account_id, tick, amount, episode)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+            params![
+                row.txn_id, run_id, row.customer_id, row.account_id, row.tick as i64, row.amount,
+                row.episode,
+            ],
+        )?;
+        Ok(())
+    }
+
+    /// Every structured deposit in the run, in tick order.
+    pub fn structuring_deposits(&self, run_id: &str) -> SimResult<Vec<StructuringDepositRow>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT txn_id, customer_id, account_id, tick, amount, episode
+             FROM structuring_deposit WHERE run_id = ?1 ORDER BY tick, customer_id, rowid",
+        )?;
+        let rows = stmt.query_map(params![run_id], |row| {
+            Ok(StructuringDepositRow {
+                txn_id:      row.get(0)?,
+                customer_id: row.get(1)?,
+                account_id:  row.get(2)?,
+                tick:        row.get::<_, i64>(3)? as Tick,
+                amount:      row.get(4)?,
+                episode:     row.get(5)?,
+            })
+        })?;
+        rows.collect::<Result<Vec<_>, _>>().map_err(Into::into)
+    }
+
+    /// Structurers who made at least one deposit: the customers a
+    /// structuring rule should catch.
+    pub fn active_structurers(&self, run_id: &str) -> SimResult<BTreeSet<String>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT DISTINCT customer_id FROM structuring_deposit WHERE run_id = ?1",
+        )?;
+        let rows = stmt.query_map(params![run_id], |row| row.get(0))?;
+        rows.collect::<Result<BTreeSet<_>, _>>().map_err(Into::into)
+    }
+
+    /// Customers the rule alerted on at any point in the run.
+    pub fn alerted_customers_for_rule(&self, run_id: &str, rule_id: &str) -> SimResult<BTreeSet<String>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT DISTINCT customer_id FROM aml_alert WHERE run_id = ?1 AND rule_id = ?2",
+        )?;
+        let rows = stmt.query_map(params![run_id, rule_id], |row| row.get(0))?;
+        rows.collect::<Result<BTreeSet<_>, _>>().map_err(Into::into)
+    }
+}
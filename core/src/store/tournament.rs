@@ -0,0 +1,23 @@
+//! Store methods for tournaments (Phase 4.71).
+
+use crate::{error::SimResult, types::Tick};
+use rusqlite::params;
+
+use super::SimStore;
+
+impl SimStore {
+    /// The last tick the run logged an event on, in either tier of the
+    /// event log; None for a run that never ticked.
+    pub fn last_logged_tick(&self, run_id: &str) -> SimResult<Option<Tick>> {
+        let tick: Option<i64> = self.conn.query_row(
+            "SELECT MAX(tick) FROM (
+                 SELECT MAX(tick) AS tick FROM event_log WHERE run_id = ?1
+                 UNION ALL
+                 SELECT MAX(tick) FROM event_log_archive WHERE run_id = ?1
+             )",
+            params![run_id],
+            |row| row.get(0),
+        )?;
+        Ok(tick.map(|t| t as Tick))
+    }
+}
@@ -0,0 +1,243 @@
+//! Store methods for visa expiry and documentation refresh (Phase 4.37).
+
+use crate::{error::SimResult, types::Tick};
+use rusqlite::{params, OptionalExtension};
+use std::collections::HashMap;
+
+use super::SimStore;
+
+/// Row from the `visa_document` table.
+#[derive(Debug, Clone, PartialEq)]
+pub struct VisaDocumentRow {
+    pub customer_id:         String,
+    pub visa_status:         String,
+    pub expiry_tick:         Tick,
+    /// Ground truth: whether the customer's visa gets renewed, and when
+    /// they will send the new documents.
+    pub renews:              bool,
+    pub planned_submit_tick: Option<Tick>,
+    /// 'valid' | 'refresh_requested' | 'submitted'
+    pub status:              String,
+    pub submitted_tick:      Option<Tick>,
+    pub lapsed_tick:         Option<Tick>,
+    /// 'outbound' | 'full'; None when unrestricted.
+    pub restriction:         Option<String>,
+    pub restricted_tick:     Option<Tick>,
+}
+
+/// Row from the `visa_refresh_item` table.
+#[derive(Debug, Clone)]
+pub struct VisaRefreshItemRow {
+    pub item_id:        String,
+    pub customer_id:    String,
+    pub opened_tick:    Tick,
+    pub expiry_tick:    Tick,
+    pub submitted_tick: Option<Tick>,
+    pub completed_tick: Option<Tick>,
+    pub cost:           f64,
+}
+
+/// The restriction policy for lapsed visa documentation.
+#[derive(Debug, Clone, PartialEq)]
+pub struct VisaRestrictionPolicy {
+    /// 'none' | 'outbound' | 'full'
+    pub level:       String,
+    pub grace_ticks: Tick,
+}
+
+fn opt_tick(value: Option<i64>) -> Option<Tick> {
+    value.map(|t| t as Tick)
+}
+
+impl SimStore {
+    /// International customers holding a visa with no documentation
+    /// timeline yet, with their visa status.
+    pub fn untracked_visa_holders(&self, run_id: &str) -> SimResult<Vec<(String, String)>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT ci.customer_id, ci.visa_status FROM customer_international ci
+             WHERE ci.run_id = ?1 AND ci.visa_status IS NOT NULL
+               AND NOT EXISTS (
+                   SELECT 1 FROM visa_document d
+                   WHERE d.run_id = ci.run_id AND d.customer_id = ci.customer_id
+               )
+             ORDER BY ci.customer_id",
+        )?;
+        let rows = stmt.query_map(params![run_id], |row| Ok((row.get(0)?, row.get(1)?)))?;
+        rows.collect::<Result<Vec<_>, _>>().map_err(Into::into)
+    }
+
+    pub fn insert_visa_document(&self, run_id: &str, row: &VisaDocumentRow) -> SimResult<()> {
+        self.conn.execute(
+            "INSERT OR IGNORE INTO visa_document
+             (run_id, customer_id, visa_status, expiry_tick, renews, planned_submit_tick,
+              status, submitted_tick, lapsed_tick, restriction, restricted_tick)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11)",
+            params![
+                run_id, row.customer_id, row.visa_status, row.expiry_tick as i64,
+                row.renews as i32, row.planned_submit_tick.map(|t| t as i64), row.status,
+                row.submitted_tick.map(|t| t as i64), row.lapsed_tick.map(|t| t as i64),
+                row.restriction, row.restricted_tick.map(|t| t as i64),
+            ],
+        )?;
+        Ok(())
+    }
+
+    pub fn update_visa_document(&self, run_id: &str, row: &VisaDocumentRow) -> SimResult<()> {
+        self.conn.execute(
+            "UPDATE visa_document SET expiry_tick = ?3, planned_submit_tick = ?4, status = ?5,
+                    submitted_tick = ?6, lapsed_tick = ?7, restriction = ?8, restricted_tick = ?9
+             WHERE run_id = ?1 AND customer_id = ?2",
+            params![
+                run_id, row.customer_id, row.expiry_tick as i64,
+                row.planned_submit_tick.map(|t| t as i64), row.status,
+                row.submitted_tick.map(|t| t as i64), row.lapsed_tick.map(|t| t as i64),
+                row.restriction, row.restricted_tick.map(|t| t as i64),
+            ],
+        )?;
+        Ok(())
+    }
+
+    /// Every visa documentation timeline for a run.
+    pub fn visa_documents(&self, run_id: &str) -> SimResult<Vec<VisaDocumentRow>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT customer_id, visa_status, expiry_tick, renews, planned_submit_tick,
+                    status, submitted_tick, lapsed_tick, restriction, restricted_tick
+             FROM visa_document WHERE run_id = ?1 ORDER BY customer_id",
+        )?;
+        let rows = stmt.query_map(params![run_id], |row| {
+            Ok(VisaDocumentRow {
+                customer_id:         row.get(0)?,
+                visa_status:         row.get(1)?,
+                expiry_tick:         row.get::<_, i64>(2)? as Tick,
+                renews:              row.get::<_, i32>(3)? != 0,
+                planned_submit_tick: opt_tick(row.get(4)?),
+                status:              row.get(5)?,
+                submitted_tick:      opt_tick(row.get(6)?),
+                lapsed_tick:         opt_tick(row.get(7)?),
+                restriction:         row.get(8)?,
+                restricted_tick:     opt_tick(row.get(9)?),
+            })
+        })?;
+        rows.collect::<Result<Vec<_>, _>>().map_err(Into::into)
+    }
+
+    /// Customers under a visa restriction, with its level.
+    pub fn visa_restrictions(&self, run_id: &str) -> SimResult<HashMap<String, String>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT customer_id, restriction FROM visa_document
+             WHERE run_id = ?1 AND restriction IS NOT NULL",
+        )?;
+        let rows = stmt.query_map(params![run_id], |row| Ok((row.get(0)?, row.get(1)?)))?;
+        rows.collect::<Result<HashMap<_, _>, _>>().map_err(Into::into)
+    }
+
+    pub fn insert_visa_refresh_item(&self, run_id: &str, row: &VisaRefreshItemRow) -> SimResult<()> {
+        self.conn.execute(
+            "INSERT INTO visa_refresh_item
+             (item_id, run_id, customer_id, opened_tick, expiry_tick, submitted_tick, completed_tick, cost)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+            params![
+                row.item_id, run_id, row.customer_id, row.opened_tick as i64, row.expiry_tick as i64,
+                row.submitted_tick.map(|t| t as i64), row.completed_tick.map(|t| t as i64), row.cost,
+            ],
+        )?;
+        Ok(())
+    }
+
+    /// Record documents received against the customer's open refresh.
+    pub fn submit_visa_refresh_item(&self, run_id: &str, customer_id: &str, tick: Tick) -> SimResult<()> {
+        self.conn.execute(
+            "UPDATE visa_refresh_item SET submitted_tick = ?3
+             WHERE run_id = ?1 AND customer_id = ?2 AND completed_tick IS NULL",
+            params![run_id, customer_id, tick as i64],
+        )?;
+        Ok(())
+    }
+
+    /// Close the customer's open refresh at its cost.
+    pub fn complete_visa_refresh_item(
+        &self,
+        run_id:      &str,
+        customer_id: &str,
+        cost:        f64,
+        tick:        Tick,
+    ) -> SimResult<()> {
+        self.conn.execute(
+            "UPDATE visa_refresh_item SET completed_tick = ?3, cost = ?4
+             WHERE run_id = ?1 AND customer_id = ?2 AND completed_tick IS NULL",
+            params![run_id, customer_id, tick as i64, cost],
+        )?;
+        Ok(())
+    }
+
+    /// Cost of refreshes completed in the window, inclusive.
+    pub fn visa_refresh_cost_between(
+        &self,
+        run_id:     &str,
+        start_tick: Tick,
+        end_tick:   Tick,
+    ) -> SimResult<f64> {
+        let total: f64 = self.conn.query_row(
+            "SELECT COALESCE(SUM(cost), 0.0) FROM visa_refresh_item
+             WHERE run_id = ?1 AND completed_tick >= ?2 AND completed_tick <= ?3",
+            params![run_id, start_tick as i64, end_tick as i64],
+            |row| row.get(0),
+        )?;
+        Ok(total)
+    }
+
+    pub fn insert_visa_restriction_policy(
+        &self,
+        run_id: &str,
+        tick:   Tick,
+        policy: &VisaRestrictionPolicy,
+    ) -> SimResult<()> {
+        self.conn.execute(
+            "INSERT OR REPLACE INTO visa_restriction_policy (run_id, tick, level, grace_ticks)
+             VALUES (?1, ?2, ?3, ?4)",
+            params![run_id, tick as i64, policy.level, policy.grace_ticks as i64],
+        )?;
+        Ok(())
+    }
+
+    /// The latest policy the player set, if any.
+    pub fn visa_restriction_policy(&self, run_id: &str) -> SimResult<Option<VisaRestrictionPolicy>> {
+        let policy = self
+            .conn
+            .query_row(
+                "SELECT level, grace_ticks FROM visa_restriction_policy
+                 WHERE run_id = ?1 ORDER BY tick DESC LIMIT 1",
+                params![run_id],
+                |row| {
+                    Ok(VisaRestrictionPolicy {
+                        level:       row.get(0)?,
+                        grace_ticks: row.get::<_, i64>(1)? as Tick,
+                    })
+                },
+            )
+            .optional()?;
+        Ok(policy)
+    }
+
+    // ── Test / summary helpers ────────────────────────────────────────
+
+    /// Every refresh work item for a run, oldest first (for tests).
+    pub fn visa_refresh_items(&self, run_id: &str) -> SimResult<Vec<VisaRefreshItemRow>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT item_id, customer_id, opened_tick, expiry_tick, submitted_tick, completed_tick, cost
+             FROM visa_refresh_item WHERE run_id = ?1 ORDER BY opened_tick, item_id",
+        )?;
+        let rows = stmt.query_map(params![run_id], |row| {
+            Ok(VisaRefreshItemRow {
+                item_id:        row.get(0)?,
+                customer_id:    row.get(1)?,
+                opened_tick:    row.get::<_, i64>(2)? as Tick,
+                expiry_tick:    row.get::<_, i64>(3)? as Tick,
+                submitted_tick: opt_tick(row.get(4)?),
+                completed_tick: opt_tick(row.get(5)?),
+                cost:           row.get(6)?,
+            })
+        })?;
+        rows.collect::<Result<Vec<_>, _>>().map_err(Into::into)
+    }
+}
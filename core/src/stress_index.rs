@@ -0,0 +1,153 @@
+//! Composite stress index (Phase 4.69).
+//!
+//! One number for "how close to the edge am I". Each tick, after the
+//! reputation score is set, five readings are turned into a pressure
+//! from 0 (calm) to 1 (at the edge):
+//!
+//! - backlog: open complaints per 1,000 active customers,
+//! - capital: free capital over deposits, from the latest liquidity
+//!   position; pressure rises as it falls,
+//! - reputation: the score just set; pressure rises as it falls,
+//! - staffing: branch customers over what their staff can serve, from
+//!   the latest branch performance,
+//! - findings: open fair lending findings plus exam findings raised in
+//!   the lookback window.
+//!
+//! Each reading maps linearly between its band's `calm` and `edge`
+//! values, either way round, and is clamped to 0–1. A component with
+//! nothing to read that tick (no liquidity position, no branches,
+//! reputation off) is left out, and the stress is the weighted mean of
+//! the rest. Resilience is 100 × (1 − stress), banded into a level:
+//! `steady`, `strained` below `strained_below`, `critical` below
+//! `critical_below`. A change of level is its own event.
+
+use crate::{
+    config::{PressureBand, StressIndexConfig},
+    error::SimResult,
+    event::SimEvent,
+    store::{stress_index::StressIndexRow, SimStore},
+    types::{RunId, Tick},
+};
+
+pub struct StressIndex {
+    run_id: RunId,
+    config: StressIndexConfig,
+    /// Customers one branch staff member serves.
+    customers_per_staff: usize,
+}
+
+impl StressIndex {
+    pub fn new(run_id: RunId, config: StressIndexConfig, customers_per_staff: usize) -> Self {
+        Self { run_id, config, customers_per_staff }
+    }
+
+    pub fn enabled(&self) -> bool {
+        self.config.enabled
+    }
+
+    /// Score the tick. `reputation` is the score just set, None when the
+    /// reputation model is off.
+    pub fn compute(&self, store: &SimStore, tick: Tick, reputation: Option<f64>) -> SimResult<Vec<SimEvent>> {
+        let c = &self.config;
+        let active = store.customer_count(&self.run_id, "active")?;
+        let backlog = (active > 0)
+            .then(|| store.complaint_backlog(&self.run_id))
+            .transpose()?
+            .map(|open| pressure(open as f64 * 1_000.0 / active as f64, &c.backlog_per_1k));
+        let capital = store
+            .latest_liquidity_position(&self.run_id)?
+            .filter(|p| p.deposits > 0.0)
+            .map(|p| pressure((p.capital - p.op_risk_capital) / p.deposits, &c.capital_ratio));
+        let reputation = reputation.map(|score| pressure(score, &c.reputation));
+        let branches = store.latest_branch_performance(&self.run_id)?;
+        let capacity: i64 = branches.iter().map(|b| b.staff_count).sum::<i64>() * self.customers_per_staff as i64;
+        let staffing = (capacity > 0).then(|| {
+            let served: i64 = branches.iter().map(|b| b.customer_count).sum();
+            pressure(served as f64 / capacity as f64, &c.staffing_utilization)
+        });
+        let since = tick.saturating_sub(c.finding_lookback_ticks);
+        let findings = store.fair_lending_finding_count(&self.run_id, "open")?
+            + store.exam_findings_since(&self.run_id, since)?;
+        let findings = Some(pressure(findings as f64, &c.open_findings));
+
+        let components = [
+            (backlog, c.weights.backlog),
+            (capital, c.weights.capital),
+            (reputation, c.weights.reputation),
+            (staffing, c.weights.staffing),
+            (findings, c.weights.findings),
+        ];
+        let (weighted, total) = components
+            .iter()
+            .filter_map(|(p, w)| p.map(|p| (p * w, *w)))
+            .fold((0.0, 0.0), |(sum, total), (pw, w)| (sum + pw, total + w));
+        let stress = if total > 0.0 { weighted / total } else { 0.0 };
+        let resilience = 100.0 * (1.0 - stress);
+        let level = self.level(resilience);
+        let row = StressIndexRow {
+            tick,
+            backlog_pressure: backlog,
+            capital_pressure: capital,
+            reputation_pressure: reputation,
+            staffing_pressure: staffing,
+            findings_pressure: findings,
+            stress,
+            resilience,
+            level: level.to_string(),
+        };
+
+        let mut events = vec![SimEvent::StressIndexComputed {
+            tick,
+            stress,
+            resilience,
+            level: level.to_string(),
+            driver: driver(&row).to_string(),
+        }];
+        let previous = store.latest_stress_index(&self.run_id)?;
+        store.insert_stress_index(&self.run_id, &row)?;
+        if let Some(previous) = previous.filter(|p| p.level != level) {
+            tracing::info!("resilience {:.1}: {} -> {level}", resilience, previous.level);
+            events.push(SimEvent::ResilienceLevelChanged {
+                tick,
+                from: previous.level,
+                to: level.to_string(),
+                resilience,
+            });
+        }
+        Ok(events)
+    }
+
+    fn level(&self, resilience: f64) -> &'static str {
+        if resilience < self.config.critical_below {
+            "critical"
+        } else if resilience < self.config.strained_below {
+            "strained"
+        } else {
+            "steady"
+        }
+    }
+}
+
+/// Where `value` sits from the band's calm end (0) to its edge (1). A
+/// band whose ends meet puts no pressure on.
+pub fn pressure(value: f64, band: &PressureBand) -> f64 {
+    if band.edge == band.calm {
+        return 0.0;
+    }
+    ((value - band.calm) / (band.edge - band.calm)).clamp(0.0, 1.0)
+}
+
+/// The component under the most pressure; the first listed on a tie.
+fn driver(row: &StressIndexRow) -> &'static str {
+    [
+        ("backlog", row.backlog_pressure),
+        ("capital", row.capital_pressure),
+        ("reputation", row.reputation_pressure),
+        ("staffing", row.staffing_pressure),
+        ("findings", row.findings_pressure),
+    ]
+    .into_iter()
+    .filter_map(|(name, p)| p.map(|p| (name, p)))
+    .fold(("none", -1.0), |best, (name, p)| if p > best.1 { (name, p) } else { best })
+    .0
+}
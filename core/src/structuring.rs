@@ -0,0 +1,161 @@
+//! Structuring (Phase 4.88).
+//!
+//! Each customer the risk model puts at a risky composite level is
+//! labeled once, the first tick they are seen there: a share become
+//! structurers, the rest stay benign. Every episode a structurer makes a
+//! few cash deposits just under the CTR threshold, spread over a few
+//! days and over their accounts, so no one day or account shows the
+//! total.
+//!
+//! The labels and deposits are ground truth, kept in `structuring_label`
+//! and `structuring_deposit` and never announced; the desk sees only the
+//! cash deposits. The post-mortem scores STRUCT_9K against them.
+
+use crate::{
+    config::StructuringConfig,
+    error::SimResult,
+    rng::SubsystemRng,
+    store::{
+        structuring::{StructuringDepositRow, StructuringLabelRow},
+        AccountRow, SimStore,
+    },
+    types::{RunId, Tick},
+};
+use std::collections::{HashMap, HashSet};
+use uuid::Uuid;
+
+pub struct Structuring {
+    run_id: RunId,
+    config: StructuringConfig,
+}
+
+impl Structuring {
+    pub fn new(run_id: RunId, config: StructuringConfig) -> Self {
+        Self { run_id, config }
+    }
+
+    pub fn enabled(&self) -> bool {
+        self.config.enabled
+    }
+
+    /// Label newly risky customers, then make the deposits falling due.
+    pub fn process(
+        &self,
+        store: &SimStore,
+        accounts: &[AccountRow],
+        tick: Tick,
+        rng: &mut SubsystemRng,
+    ) -> SimResult<()> {
+        let mut by_customer: HashMap<&str, Vec<&AccountRow>> = HashMap::new();
+        for acct in accounts {
+            by_customer.entry(acct.customer_id.as_str()).or_default().push(acct);
+        }
+        let labels = store.structuring_labels(&self.run_id)?;
+        self.label(store, &labels, &by_customer, tick, rng)?;
+
+        for mut label in labels {
+            if label.label != "structurer" {
+                continue;
+            }
+            // Closed and restricted accounts take nothing; an episode
+            // under way lapses with them
+            let Some(own) = by_customer.get(label.customer_id.as_str()) else {
+                if label.episode_start.take().is_some() {
+                    label.deposits_left = 0;
+                    store.update_structuring_label(&self.run_id, &label)?;
+                }
+                continue;
+            };
+            if label.episode_start.is_none() && label.next_tick.is_some_and(|t| t <= tick) {
+                label.episode_start = Some(tick);
+                label.deposits_left = self.config.deposits_per_episode;
+                label.episodes += 1;
+                label.next_tick = Some(tick + self.config.episode_interval_ticks.max(1));
+            }
+            let Some(start) = label.episode_start else {
+                continue;
+            };
+            let days_left = (start + self.config.episode_spread_ticks.max(1)).saturating_sub(tick).max(1) as u32;
+            let today = if label.deposits_left >= days_left {
+                label.deposits_left.div_ceil(days_left)
+            } else {
+                u32::from(rng.chance(label.deposits_left as f64 / days_left as f64))
+            };
+            for _ in 0..today {
+                let acct = own[rng.next_u64_below(own.len() as u64) as usize];
+                self.deposit(store, acct, label.episodes, tick, rng)?;
+            }
+            label.deposits_left -= today;
+            if label.deposits_left == 0 {
+                label.episode_start = None;
+            }
+            store.update_structuring_label(&self.run_id, &label)?;
+        }
+        Ok(())
+    }
+
+    /// Label, once, each customer with an account who is now at a risky
+    /// level. A structurer's first episode is staggered across the
+    /// interval.
+    fn label(
+        &self,
+        store: &SimStore,
+        labels: &[StructuringLabelRow],
+        by_customer: &HashMap<&str, Vec<&AccountRow>>,
+        tick: Tick,
+        rng: &mut SubsystemRng,
+    ) -> SimResult<()> {
+        let cfg = &self.config;
+        let labeled: HashSet<&str> = labels.iter().map(|l| l.customer_id.as_str()).collect();
+        let mut risky: Vec<(String, String)> = store
+            .customer_risk_tiers(&self.run_id)?
+            .into_iter()
+            .filter(|(customer_id, level)| {
+                cfg.risk_levels.contains(level)
+                    && by_customer.contains_key(customer_id.as_str())
+                    && !labeled.contains(customer_id.as_str())
+            })
+            .collect();
+        risky.sort();
+        for (customer_id, risk_level) in risky {
+            let structurer = rng.chance(cfg.structurer_rate);
+            store.insert_structuring_label(&self.run_id, &StructuringLabelRow {
+                customer_id,
+                label: if structurer { "structurer" } else { "benign" }.into(),
+                risk_level,
+                labeled_tick: tick,
+                next_tick: structurer
+                    .then(|| tick + 1 + rng.next_u64_below(cfg.episode_interval_ticks.max(1))),
+                episode_start: None,
+                deposits_left: 0,
+                episodes: 0,
+            })?;
+        }
+        Ok(())
+    }
+
+    /// One cash deposit just under the threshold, rounded to $10.
+    fn deposit(
+        &self,
+        store: &SimStore,
+        acct: &AccountRow,
+        episode: u32,
+        tick: Tick,
+        rng: &mut SubsystemRng,
+    ) -> SimResult<()> {
+        let cfg = &self.config;
+        let raw = cfg.min_deposit + rng.next_f64() * (cfg.max_deposit - cfg.min_deposit);
+        let amount = ((raw / 10.0).floor() * 10.0).max(cfg.min_deposit);
+        let txn_id = Uuid::new_v4().to_string();
+        store.insert_transaction(&self.run_id, &txn_id, &acct.account_id, tick, amount, "credit", "cash_deposit", None)?;
+        store.update_account_balance(&self.run_id, &acct.account_id, amount)?;
+        store.insert_structuring_deposit(&self.run_id, &StructuringDepositRow {
+            txn_id,
+            customer_id: acct.customer_id.clone(),
+            account_id: acct.account_id.clone(),
+            tick,
+            amount,
+            episode,
+        })
+    }
+}
@@ -4,10 +4,53 @@
 //! The engine calls update() on each registered subsystem
 //! in registration order, every tick.
 //! Execution order is fixed and documented in engine.rs.
+//!
+//! Each subsystem also publishes a `SubsystemManifest` — what it emits,
+//! what it reads and which tables it writes — so tooling can see what a
+//! build is running; `SimEngine::subsystems()` lists them.
+//!
+//! A subsystem that keeps state outside the store saves it with each
+//! snapshot and replays the events it logged since, so
+//! `SimEngine::resume` can pick a stopped run back up.
 
 use crate::{error::SimResult, event::SimEvent, rng::SubsystemRng, types::Tick};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
 use std::any::Any;
 
+/// What a subsystem declares about itself. Event types are the names the
+/// event log records them under.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SubsystemManifest {
+    /// Bumped when the subsystem's behaviour or what it stores changes
+    /// in a way a saved run or an external tool would notice.
+    pub version: u32,
+    /// Event types it emits, including those from helpers it drives.
+    pub produces: &'static [&'static str],
+    /// Event types it reads from `events_in`.
+    pub consumes: &'static [&'static str],
+    /// Store tables it writes.
+    pub tables: &'static [&'static str],
+    /// Subsystems whose output it cannot run without; a run may not
+    /// disable those while keeping this one.
+    pub requires: &'static [&'static str],
+}
+
+/// A registered subsystem as tooling sees it.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct SubsystemInfo {
+    pub name: String,
+    /// RNG slot; its seed stream.
+    pub slot: String,
+    /// Position in the tick's execution order, from 0.
+    pub order: usize,
+    pub version: u32,
+    pub produces: Vec<String>,
+    pub consumes: Vec<String>,
+    pub tables: Vec<String>,
+    pub requires: Vec<String>,
+}
+
 /// The contract every subsystem must fulfill.
 pub trait SimSubsystem: Send {
     /// Unique stable name for this subsystem.
@@ -27,6 +70,32 @@ pub trait SimSubsystem: Send {
         rng: &mut SubsystemRng,
     ) -> SimResult<Vec<SimEvent>>;
 
+    /// Declared events and tables; subsystems with nothing to declare
+    /// keep the empty default.
+    fn manifest(&self) -> SubsystemManifest {
+        SubsystemManifest { version: 1, ..SubsystemManifest::default() }
+    }
+
+    /// Phase 4.76: in-memory state the store does not hold, saved with
+    /// each snapshot. None for subsystems that keep everything in the
+    /// store.
+    fn save_state(&self) -> SimResult<Option<serde_json::Value>> {
+        Ok(None)
+    }
+
+    /// Restore what `save_state` returned, when resuming a run from a
+    /// snapshot that holds it.
+    fn load_state(&mut self, _state: serde_json::Value) -> SimResult<()> {
+        Ok(())
+    }
+
+    /// Bring in-memory state forward over a tick played after the
+    /// snapshot, from the events this subsystem logged that tick. The
+    /// store already holds what the tick wrote.
+    fn replay(&mut self, _tick: Tick, _events: &[SimEvent]) -> SimResult<()> {
+        Ok(())
+    }
+
     /// For downcasting in tests and tooling only.
     /// Production sim code never uses this.
     fn as_any(&self) -> &dyn Any;
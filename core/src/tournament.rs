@@ -0,0 +1,287 @@
+//! Tournaments and leaderboards across stored runs (Phase 4.71).
+//!
+//! A tournament fixes a scenario (by its hash), a set of seeds and a run
+//! length. Players play the seeds with whatever strategy they like and
+//! enter the runs they stored; the leaderboard scores every entry and
+//! ranks the players.
+//!
+//! Seeds differ in how hard they are, so a run is only compared with the
+//! other entries on its seed. Each outcome from `stored_outcome_metrics`,
+//! plus the final resilience, is placed between the worst (0) and best
+//! (1) entry on that seed, and the run's score is 100 × the weighted mean
+//! of those places. A player's score is the mean over the seeds they
+//! played. Players who played every seed rank ahead of those who did not.
+//!
+//! Runs cannot share a database, so each entry names the database file
+//! its run is stored in. An entry that does not fit the tournament is
+//! listed with the reason and left out of the standings.
+
+use crate::{
+    error::{SimError, SimResult},
+    seed_sensitivity::stored_outcome_metrics,
+    store::SimStore,
+    types::Tick,
+};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::BTreeMap,
+    path::{Path, PathBuf},
+};
+
+/// A tournament definition, as read from its JSON file.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Tournament {
+    pub name: String,
+    pub seeds: Vec<u64>,
+    /// Every entry must have stopped at this tick.
+    pub ticks: Tick,
+    /// `hash_scenario` of the scenario every entry must have loaded.
+    /// Unset means the scenario sim-runner is pointed at.
+    #[serde(default)]
+    pub scenario_hash: Option<String>,
+    #[serde(default)]
+    pub weights: ScoreWeights,
+    #[serde(default)]
+    pub entries: Vec<TournamentEntry>,
+}
+
+/// Weight of each outcome in a run's score.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ScoreWeights {
+    pub pre_tax_profit: f64,
+    pub fraud_loss: f64,
+    pub detection_rate: f64,
+    pub churned: f64,
+    pub complaints: f64,
+    pub resilience: f64,
+}
+
+impl Default for ScoreWeights {
+    fn default() -> Self {
+        Self {
+            pre_tax_profit: 0.3,
+            fraud_loss: 0.2,
+            detection_rate: 0.2,
+            churned: 0.1,
+            complaints: 0.1,
+            resilience: 0.1,
+        }
+    }
+}
+
+impl ScoreWeights {
+    /// Each scored outcome, its weight, and whether more is better.
+    fn scored(&self) -> [(&'static str, f64, bool); 6] {
+        [
+            ("pre_tax_profit", self.pre_tax_profit, true),
+            ("fraud_loss", self.fraud_loss, false),
+            ("detection_rate", self.detection_rate, true),
+            ("churned", self.churned, false),
+            ("complaints", self.complaints, false),
+            ("resilience", self.resilience, true),
+        ]
+    }
+}
+
+/// One player's stored run. A relative `db` is read from beside the
+/// tournament file.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TournamentEntry {
+    pub player: String,
+    pub db: PathBuf,
+    pub run_id: String,
+}
+
+/// A stored run's outcomes, ready to score.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RunOutcome {
+    pub player: String,
+    pub run_id: String,
+    pub seed: u64,
+    /// By outcome name; an outcome the run could not report is missing.
+    pub metrics: BTreeMap<String, f64>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RunScore {
+    pub player: String,
+    pub run_id: String,
+    pub seed: u64,
+    /// 0–100 against the other entries on the seed.
+    pub score: f64,
+    pub metrics: BTreeMap<String, f64>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Standing {
+    /// Players with equal completeness and score share a rank.
+    pub rank: usize,
+    pub player: String,
+    /// Mean of the player's run scores.
+    pub score: f64,
+    pub seeds_played: usize,
+    /// Played every seed in the tournament.
+    pub complete: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RejectedEntry {
+    pub player: String,
+    pub run_id: String,
+    pub reason: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Leaderboard {
+    pub tournament: String,
+    pub scenario_hash: Option<String>,
+    pub seeds: Vec<u64>,
+    pub ticks: Tick,
+    pub standings: Vec<Standing>,
+    /// By seed, best first.
+    pub runs: Vec<RunScore>,
+    pub rejected: Vec<RejectedEntry>,
+}
+
+impl Tournament {
+    /// Refuse a definition no leaderboard could be drawn from.
+    pub fn validate(&self) -> SimResult<()> {
+        let refuse = |reason: &str| SimError::command("tournament", format!("{}: {reason}", self.name));
+        if self.seeds.is_empty() {
+            return Err(refuse("name at least one seed"));
+        }
+        if self.ticks == 0 {
+            return Err(refuse("ticks must be at least 1"));
+        }
+        let weights = self.weights.scored();
+        if weights.iter().any(|(_, w, _)| *w < 0.0) || weights.iter().all(|(_, w, _)| *w == 0.0) {
+            return Err(refuse("weights must be non-negative and not all zero"));
+        }
+        Ok(())
+    }
+
+    /// Read each entry's run, from databases relative to `base_dir`, and
+    /// rank the players.
+    pub fn leaderboard(&self, base_dir: &Path) -> SimResult<Leaderboard> {
+        self.validate()?;
+        let mut outcomes: Vec<RunOutcome> = Vec::new();
+        let mut rejected = Vec::new();
+        for entry in &self.entries {
+            match self.outcome(entry, base_dir, &outcomes) {
+                Ok(outcome) => outcomes.push(outcome),
+                Err(reason) => rejected.push(RejectedEntry {
+                    player: entry.player.clone(),
+                    run_id: entry.run_id.clone(),
+                    reason,
+                }),
+            }
+        }
+        let mut board = self.rank(outcomes);
+        board.rejected = rejected;
+        Ok(board)
+    }
+
+    /// The entry's outcomes, or why it does not count.
+    fn outcome(&self, entry: &TournamentEntry, base_dir: &Path, so_far: &[RunOutcome]) -> Result<RunOutcome, String> {
+        let path = base_dir.join(&entry.db);
+        // Opening a missing file would create an empty database
+        if !path.exists() {
+            return Err(format!("no database at {}", path.display()));
+        }
+        let read = |e: SimError| format!("cannot read {}: {e}", path.display());
+        let store = SimStore::open(&path.to_string_lossy()).map_err(read)?;
+        let run = store
+            .describe_run(&entry.run_id)
+            .map_err(read)?
+            .ok_or_else(|| format!("no run '{}' in {}", entry.run_id, path.display()))?;
+        if !self.seeds.contains(&run.seed) {
+            return Err(format!("seed {} is not one of the tournament's", run.seed));
+        }
+        if self.scenario_hash.is_some() && run.provenance.scenario_hash != self.scenario_hash {
+            return Err("played on a different scenario".into());
+        }
+        let last_tick = store.last_logged_tick(&entry.run_id).map_err(read)?.unwrap_or(0);
+        if last_tick != self.ticks {
+            return Err(format!("stopped at tick {last_tick}, not {}", self.ticks));
+        }
+        if so_far.iter().any(|o| o.player == entry.player && o.seed == run.seed) {
+            return Err(format!("{} already entered a run on seed {}", entry.player, run.seed));
+        }
+
+        let mut metrics: BTreeMap<String, f64> =
+            stored_outcome_metrics(&store, &entry.run_id, last_tick).map_err(read)?.into_iter().collect();
+        if let Some(index) = store.latest_stress_index(&entry.run_id).map_err(read)? {
+            metrics.insert("resilience".into(), index.resilience);
+        }
+        Ok(RunOutcome { player: entry.player.clone(), run_id: entry.run_id.clone(), seed: run.seed, metrics })
+    }
+
+    /// Score each run against its seed and rank the players.
+    pub fn rank(&self, outcomes: Vec<RunOutcome>) -> Leaderboard {
+        let weights = self.weights.scored();
+        let mut runs: Vec<RunScore> = outcomes
+            .iter()
+            .map(|run| {
+                let field: Vec<&RunOutcome> = outcomes.iter().filter(|o| o.seed == run.seed).collect();
+                let (weighted, total) = weights
+                    .iter()
+                    .filter(|(_, w, _)| *w > 0.0)
+                    .filter_map(|&(name, w, more_is_better)| {
+                        let value = *run.metrics.get(name)?;
+                        let values = field.iter().filter_map(|o| o.metrics.get(name).copied());
+                        let (lo, hi) = values.fold((value, value), |(lo, hi), v| (lo.min(v), hi.max(v)));
+                        let place = if hi == lo {
+                            1.0
+                        } else if more_is_better {
+                            (value - lo) / (hi - lo)
+                        } else {
+                            (hi - value) / (hi - lo)
+                        };
+                        Some((place * w, w))
+                    })
+                    .fold((0.0, 0.0), |(sum, total), (pw, w)| (sum + pw, total + w));
+                RunScore {
+                    player: run.player.clone(),
+                    run_id: run.run_id.clone(),
+                    seed: run.seed,
+                    score: if total > 0.0 { 100.0 * weighted / total } else { 0.0 },
+                    metrics: run.metrics.clone(),
+                }
+            })
+            .collect();
+        runs.sort_by(|a, b| a.seed.cmp(&b.seed).then(b.score.total_cmp(&a.score)).then(a.player.cmp(&b.player)));
+
+        let mut by_player: BTreeMap<&str, Vec<f64>> = BTreeMap::new();
+        for run in &runs {
+            by_player.entry(&run.player).or_default().push(run.score);
+        }
+        let mut standings: Vec<Standing> = by_player
+            .into_iter()
+            .map(|(player, scores)| Standing {
+                rank: 0,
+                player: player.to_string(),
+                score: scores.iter().sum::<f64>() / scores.len() as f64,
+                seeds_played: scores.len(),
+                complete: scores.len() == self.seeds.len(),
+            })
+            .collect();
+        standings.sort_by(|a, b| b.complete.cmp(&a.complete).then(b.score.total_cmp(&a.score)));
+        for i in 0..standings.len() {
+            let tied = i > 0
+                && standings[i].complete == standings[i - 1].complete
+                && standings[i].score == standings[i - 1].score;
+            standings[i].rank = if tied { standings[i - 1].rank } else { i + 1 };
+        }
+
+        Leaderboard {
+            tournament: self.name.clone(),
+            scenario_hash: self.scenario_hash.clone(),
+            seeds: self.seeds.clone(),
+            ticks: self.ticks,
+            standings,
+            runs,
+            rejected: Vec::new(),
+        }
+    }
+}
@@ -8,16 +8,86 @@
 //! - Rapid Money Movement (immediate withdrawal after deposit)
 //!
 //! Execution: Every tick, monitors recent transactions and generates alerts.
+//!
+//! Phase 4.16: the SAR filing threshold is a player dial. Filing below the
+//! reportable score is defensive over-filing that costs analyst time and
+//! dilutes SAR quality; a threshold above it leaves reportable alerts
+//! unfiled until they are missed and surface at the next exam.
+//!
+//! Phase 4.17: the player can tune the structuring, velocity and rapid
+//! movement rules through a tuning review workflow; see `rule_tuning`.
+//!
+//! Phase 4.18: a periodic coverage assessment compares the rules in force
+//! against the typologies running; see `coverage_assessment`.
+//!
+//! Phase 4.35: an enrichment pass categorizes new merchants every tick,
+//! and HIGH_RISK_MCC alerts weekly on gambling and crypto spend intensity
+//! by enriched category; see `enrichment`.
+//!
+//! Phase 4.36: REMIT_LAYERING alerts weekly on remittance fan-out with
+//! high pass-through, scored up by corridor risk; see `remittance`.
+//!
+//! Phase 4.39: CORR_NESTED alerts weekly on respondent banks whose
+//! aggregated wires show nested activity; see `correspondent`.
+//!
+//! Phase 4.40: XB_FUNNEL alerts weekly on foreign fan-in dispersed
+//! domestically, recording a finding with its evidence for the SAR
+//! narrative; see `cross_border`.
+//!
+//! Phase 4.41: CTR and SAR deadlines falling on a weekend or holiday
+//! move to the next business day; see `calendar`.
+//!
+//! Phase 4.59: each SAR is filed with an evidence bundle of the activity,
+//! KYC record and prior SARs behind it; see `evidence_bundle`.
+//!
+//! Phase 4.60: new alerts are grouped into investigation cases by
+//! customer, relationship cluster and time window; see `alert_cases`.
+//!
+//! Phase 4.73: each month QA re-reviews a sample of the SAR decisions
+//! and opens retraining where an alert type is often judged wrongly; see
+//! `alert_qa`.
+//!
+//! Phase 4.80: a staffed alert desk files SARs every tick instead of in a
+//! weekly batch, oldest alert first and no more than its capacity, so a
+//! backlog runs past deadlines into late filings. Capacity left over
+//! clears alerts too weak to report; see `staffing_subsystem`.
+//!
+//! Phase 4.85: the player triages alerts by hand, assigning, closing and
+//! escalating them, and the queue's aging is recorded every tick; see
+//! `alert_triage`.
 
 use crate::{
+    alert_cases::AlertCases,
+    alert_qa::AlertQa,
+    alert_triage::AlertTriage,
+    calendar::BusinessCalendar,
+    command::PlayerCommand,
+    config::{
+        AlertGroupingConfig, AlertQaConfig, AlertTriageConfig, BusinessCalendarConfig, CorrespondentBankingConfig, CoverageAssessmentConfig, CrossBorderConfig, EvidenceBundleConfig, RemittanceConfig,
+        RiskLifecycleConfig, RuleTuningConfig, SarDecisionConfig, TransactionEnrichmentConfig,
+    },
+    correspondent::Correspondent,
+    coverage_assessment,
+    queue::QueueKind,
+    cross_border::CrossBorder,
+    enrichment::TransactionEnrichment,
     error::SimResult,
     event::SimEvent,
+    evidence_bundle::EvidenceBundles,
+    remittance::Remittance,
     rng::SubsystemRng,
-    store::SimStore,
-    subsystem::SimSubsystem,
+    rule_tuning::{self, RuleTunings},
+    store::{
+        coverage_assessment::CoverageAssessmentRow,
+        cross_border::CrossBorderFindingRow,
+        rule_tuning::{RuleTuningRow, TuningReviewRow},
+        sar_decision::{SarDecisionRow, SarQualityMetricsRow},
+        SimStore,
+    },
+    subsystem::{SimSubsystem, SubsystemManifest},
     types::{RunId, Tick},
 };
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 
 // ── Constants ────────────────────────────────────────────────────────────────
 
@@ -34,6 +104,8 @@ const RAPID_MOVEMENT_WINDOW_DAYS: u64 = 1; // Within 1 day
 
 const METRICS_INTERVAL: u64 = 7; // Compute metrics every 7 ticks (weekly)
 
+const SAR_FILING_DEADLINE_DAYS: u64 = 30; // SAR due within 30 days of detection
+
 // ── Data Structures ──────────────────────────────────────────────────────────
 
 #[derive(Debug, Clone)]
@@ -112,12 +184,301 @@ pub struct SuspiciousActivityReport {
 
 pub struct TransactionMonitoringSubsystem {
     run_id: RunId,
+    /// Phase 4.13: per-tier monitoring sensitivity.
+    lifecycle: RiskLifecycleConfig,
+    /// Phase 4.16: SAR decision dynamics.
+    sar: SarDecisionConfig,
+    /// Alert score at or above which SARs are filed; set by the player.
+    sar_threshold: f64,
+    /// Phase 4.17: tuning review workflow and the active rule tunings.
+    tuning: RuleTuningConfig,
+    tunings: RuleTunings,
+    /// Phase 4.18: periodic coverage assessment.
+    coverage: CoverageAssessmentConfig,
+    /// Phase 4.35: merchant categorization and spend intensity.
+    enrichment: TransactionEnrichment,
+    /// Phase 4.36: remittance layering detection.
+    remittance: Remittance,
+    /// Phase 4.39: nested correspondent activity detection.
+    correspondent: Correspondent,
+    /// Phase 4.40: cross-border fan-in/fan-out analytics.
+    cross_border: CrossBorder,
+    /// Phase 4.41: business days for filing deadlines.
+    calendar: BusinessCalendar,
+    /// Phase 4.59: the evidence bundle kept with each SAR.
+    evidence: EvidenceBundles,
+    /// Phase 4.60: alert-to-case grouping.
+    cases: AlertCases,
+    /// Phase 4.73: QA re-review of SAR decisions.
+    qa: AlertQa,
+    /// Phase 4.85: the player's alert triage.
+    triage: AlertTriage,
     store: SimStore,
 }
 
 impl TransactionMonitoringSubsystem {
-    pub fn new(run_id: RunId, store: SimStore) -> Self {
-        Self { run_id, store }
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        run_id: RunId,
+        lifecycle: RiskLifecycleConfig,
+        sar: SarDecisionConfig,
+        tuning: RuleTuningConfig,
+        coverage: CoverageAssessmentConfig,
+        enrichment: TransactionEnrichmentConfig,
+        remittance: RemittanceConfig,
+        correspondent: CorrespondentBankingConfig,
+        cross_border: CrossBorderConfig,
+        calendar: BusinessCalendarConfig,
+        evidence: EvidenceBundleConfig,
+        grouping: AlertGroupingConfig,
+        qa: AlertQaConfig,
+        triage: AlertTriageConfig,
+        store: SimStore,
+    ) -> SimResult<Self> {
+        Ok(Self {
+            triage: AlertTriage::new(run_id.clone(), triage),
+            qa: AlertQa::new(run_id.clone(), qa, sar.reportable_score),
+            cases: AlertCases::new(run_id.clone(), grouping),
+            calendar: BusinessCalendar::new(&calendar)?,
+            evidence: EvidenceBundles::new(run_id.clone(), evidence),
+            cross_border: CrossBorder::new(run_id.clone(), cross_border),
+            correspondent: Correspondent::new(run_id.clone(), correspondent),
+            enrichment: TransactionEnrichment::new(run_id.clone(), enrichment),
+            remittance: Remittance::new(run_id.clone(), remittance),
+            run_id,
+            lifecycle,
+            sar_threshold: sar.filing_threshold,
+            sar,
+            tuning,
+            tunings: RuleTunings::default(),
+            coverage,
+            store,
+        })
+    }
+
+    /// Apply player changes to the SAR filing threshold (Phase 4.16), to
+    /// monitoring rules (Phase 4.17) and to alerts (Phase 4.85).
+    fn apply_player_commands(
+        &mut self,
+        tick: Tick,
+        events_in: &[SimEvent],
+        rng: &mut SubsystemRng,
+    ) -> SimResult<Vec<SimEvent>> {
+        let mut events = Vec::new();
+        for event in events_in {
+            let SimEvent::PlayerCommandReceived { command_id, .. } = event else {
+                continue;
+            };
+            match self.store.get_player_command(&self.run_id, command_id)? {
+                Some(PlayerCommand::SetSarThreshold { threshold }) => {
+                    self.sar_threshold = threshold.clamp(0.0, 100.0);
+                    tracing::info!("SARs filed on alerts scoring {:.0}+", self.sar_threshold);
+                    events.push(SimEvent::SarThresholdChanged {
+                        tick,
+                        threshold: self.sar_threshold,
+                    });
+                }
+                Some(PlayerCommand::OpenTuningReview { rule_id, rationale }) if self.tuning.enabled => {
+                    events.push(self.open_tuning_review(rule_id, rationale, tick)?);
+                }
+                Some(PlayerCommand::TuneMonitoringRule {
+                    rule_id,
+                    change,
+                    threshold_multiplier,
+                    customer_id,
+                    review_id,
+                }) if self.tuning.enabled => {
+                    events.push(self.tune_rule(
+                        RuleTuningRow {
+                            tuning_id: format!("tune-{command_id}"),
+                            tick,
+                            rule_id,
+                            change,
+                            threshold_multiplier,
+                            customer_id,
+                            review_id,
+                            documented: false,
+                            active: true,
+                        },
+                        tick,
+                    )?);
+                }
+                Some(PlayerCommand::AssignAlert { alert_id, analyst, note }) if self.triage.enabled() => {
+                    events.push(self.triage.assign(&self.store, &alert_id, &analyst, note.as_deref(), tick)?);
+                }
+                Some(PlayerCommand::CloseAlert { alert_id, disposition, note }) if self.triage.enabled() => {
+                    events.extend(self.close_alert(&alert_id, &disposition, &note, tick, rng)?);
+                }
+                Some(PlayerCommand::EscalateAlertToCase { alert_id, note }) if self.triage.enabled() => {
+                    events.extend(self.triage.escalate(&self.store, &alert_id, &note, tick)?);
+                }
+                _ => {}
+            }
+        }
+        Ok(events)
+    }
+
+    /// Phase 4.85: close an alert as the analyst decided. A `sar_filed`
+    /// close files the SAR now; closing a reportable alert any other way
+    /// is a missed filing.
+    fn close_alert(
+        &self,
+        alert_id: &str,
+        disposition: &str,
+        note: &str,
+        tick: Tick,
+        rng: &mut SubsystemRng,
+    ) -> SimResult<Vec<SimEvent>> {
+        let alert = match self.triage.load(&self.store, alert_id, tick)? {
+            Ok(alert) => alert,
+            Err(rejected) => return Ok(vec![rejected]),
+        };
+        if disposition == "sar_filed" {
+            self.triage.note(&self.store, alert_id, "close", note, tick)?;
+            let mut events = self.file_sar(alert, tick, rng)?;
+            events.push(SimEvent::AlertClosed { tick, alert_id: alert_id.into(), disposition: disposition.into() });
+            return Ok(events);
+        }
+
+        let mut events = vec![self.triage.close(&self.store, &alert, disposition, note, tick)?];
+        if self.sar.enabled && alert.alert_score >= self.sar.reportable_score {
+            self.store.insert_sar_decision(&self.run_id, &SarDecisionRow {
+                alert_id: alert.alert_id.clone(),
+                tick,
+                customer_id: alert.customer_id.clone(),
+                alert_score: alert.alert_score,
+                threshold: self.sar_threshold,
+                decision: "missed".into(),
+                sar_id: None,
+                cost: 0.0,
+            })?;
+            tracing::warn!(
+                "SAR missed: alert {} (score {:.0}) closed as {disposition}",
+                alert.alert_id,
+                alert.alert_score
+            );
+            events.push(SimEvent::SarFilingMissed {
+                tick,
+                alert_id: alert.alert_id,
+                customer_id: alert.customer_id,
+                alert_score: alert.alert_score,
+            });
+        }
+        Ok(events)
+    }
+
+    /// Phase 4.17: open a documented review of a proposed tuning.
+    fn open_tuning_review(&self, rule_id: String, rationale: String, tick: Tick) -> SimResult<SimEvent> {
+        if !rule_tuning::TUNABLE_RULES.contains(&rule_id.as_str()) {
+            return Ok(SimEvent::RuleTuningRejected {
+                tick,
+                rule_id,
+                change: "review".into(),
+                reason: "unknown rule".into(),
+            });
+        }
+        let review = TuningReviewRow {
+            review_id: format!("tr-{rule_id}-{tick}"),
+            rule_id,
+            rationale,
+            opened_tick: tick,
+            due_tick: tick + self.tuning.review_ticks,
+            status: "in_review".into(),
+            completed_tick: None,
+            cost: self.tuning.review_cost,
+        };
+        self.store.insert_tuning_review(&self.run_id, &review)?;
+        tracing::info!("tuning review {} opened for {}", review.review_id, review.rule_id);
+        Ok(SimEvent::TuningReviewOpened {
+            tick,
+            review_id: review.review_id,
+            rule_id: review.rule_id,
+            due_tick: review.due_tick,
+        })
+    }
+
+    /// Phase 4.17: apply a tuning, documented when it cites an approved
+    /// review of the same rule.
+    fn tune_rule(&mut self, mut row: RuleTuningRow, tick: Tick) -> SimResult<SimEvent> {
+        let reason = if !rule_tuning::TUNABLE_RULES.contains(&row.rule_id.as_str()) {
+            Some("unknown rule")
+        } else if !rule_tuning::CHANGES.contains(&row.change.as_str()) {
+            Some("unknown change")
+        } else if row.change == "raise_threshold" && row.threshold_multiplier < 1.0 {
+            Some("threshold multiplier below 1.0")
+        } else if row.change == "exclude_customer" && row.customer_id.is_none() {
+            Some("no customer to exclude")
+        } else {
+            None
+        };
+        if let Some(reason) = reason {
+            return Ok(SimEvent::RuleTuningRejected {
+                tick,
+                rule_id: row.rule_id,
+                change: row.change,
+                reason: reason.into(),
+            });
+        }
+
+        let review = match &row.review_id {
+            Some(review_id) => self.store.tuning_review(&self.run_id, review_id)?,
+            None => None,
+        };
+        row.documented = review.is_some_and(|r| r.rule_id == row.rule_id && r.status == "approved");
+        match row.change.as_str() {
+            "restore" => self.store.deactivate_rule_tunings(&self.run_id, &row.rule_id, None)?,
+            "exclude_customer" => {}
+            change => self.store.deactivate_rule_tunings(&self.run_id, &row.rule_id, Some(change))?,
+        }
+        // A restore is recorded for the audit trail but has nothing to apply
+        row.active = row.change != "restore";
+        self.store.insert_rule_tuning(&self.run_id, &row)?;
+        self.tunings = RuleTunings::new(&self.store.active_rule_tunings(&self.run_id)?);
+
+        if row.documented {
+            tracing::info!("{} {} under review {:?}", row.rule_id, row.change, row.review_id);
+        } else {
+            tracing::warn!("{} {} with no approved tuning review", row.rule_id, row.change);
+        }
+        Ok(SimEvent::MonitoringRuleTuned {
+            tick,
+            tuning_id: row.tuning_id,
+            rule_id: row.rule_id,
+            change: row.change,
+            documented: row.documented,
+        })
+    }
+
+    /// Phase 4.17: approve tuning reviews that have run their course.
+    fn approve_tuning_reviews(&self, tick: Tick) -> SimResult<Vec<SimEvent>> {
+        let mut events = Vec::new();
+        for review in self.store.due_tuning_reviews(&self.run_id, tick)? {
+            self.store.approve_tuning_review(&self.run_id, &review.review_id, tick)?;
+            events.push(SimEvent::TuningReviewApproved {
+                tick,
+                review_id: review.review_id,
+                rule_id: review.rule_id,
+            });
+        }
+        Ok(events)
+    }
+
+    /// Phase 4.13: customer_id → scale on the velocity and rapid movement
+    /// thresholds for the customer's risk tier. Empty when the lifecycle
+    /// is off, leaving every threshold as configured.
+    fn threshold_multipliers(&self) -> SimResult<HashMap<String, f64>> {
+        if !self.lifecycle.enabled {
+            return Ok(HashMap::new());
+        }
+        Ok(self
+            .store
+            .customer_risk_tiers(&self.run_id)?
+            .into_iter()
+            .map(|(customer_id, tier)| {
+                let multiplier = crate::risk_lifecycle::monitoring_multiplier(&self.lifecycle, &tier);
+                (customer_id, multiplier)
+            })
+            .collect())
     }
 
     /// Detect structuring: Multiple transactions just under $10k threshold
@@ -127,6 +488,11 @@ impl TransactionMonitoringSubsystem {
         rng: &mut SubsystemRng,
     ) -> SimResult<Vec<SimEvent>> {
         let mut events = Vec::new();
+        if self.tunings.suppressed("STRUCT_9K") {
+            return Ok(events);
+        }
+        let count_threshold =
+            (STRUCTURING_COUNT_THRESHOLD as f64 * self.tunings.multiplier("STRUCT_9K")).ceil() as usize;
 
         // Get transactions in last 7 days that are just under $10k
         let lookback_start = tick.saturating_sub(STRUCTURING_LOOKBACK_DAYS);
@@ -138,8 +504,8 @@ impl TransactionMonitoringSubsystem {
             CTR_THRESHOLD,
         )?;
 
-        // Group by customer
-        let mut customer_txns: HashMap<String, Vec<_>> = HashMap::new();
+        // Group by customer, in id order so alert ids draw the same rng
+        let mut customer_txns: BTreeMap<String, Vec<_>> = BTreeMap::new();
         for txn in suspicious_txns {
             customer_txns
                 .entry(txn.customer_id.clone())
@@ -148,7 +514,7 @@ impl TransactionMonitoringSubsystem {
         }
 
         for (customer_id, txns) in customer_txns {
-            if txns.len() >= STRUCTURING_COUNT_THRESHOLD {
+            if txns.len() >= count_threshold && !self.tunings.excluded("STRUCT_9K", &customer_id) {
                 let total_amount: f64 = txns.iter().map(|t| t.amount).sum();
                 let alert_id = format!("STRUCT-{}-{}", customer_id, rng.next_u64_below(100000));
 
@@ -184,9 +550,8 @@ impl TransactionMonitoringSubsystem {
                     description,
                 });
 
-                log::warn!(
-                    "tick={} Structuring detected: {} ({} transactions, ${:.2})",
-                    tick,
+                tracing::warn!(
+                    "Structuring detected: {} ({} transactions, ${:.2})",
                     customer_id,
                     txns.len(),
                     total_amount
@@ -204,6 +569,9 @@ impl TransactionMonitoringSubsystem {
         rng: &mut SubsystemRng,
     ) -> SimResult<Vec<SimEvent>> {
         let mut events = Vec::new();
+        if self.tunings.suppressed("VEL_50K_7D") {
+            return Ok(events);
+        }
 
         let lookback_start = tick.saturating_sub(7); // 7-day window
         let all_txns = self.store.get_all_transactions_in_window(
@@ -212,8 +580,10 @@ impl TransactionMonitoringSubsystem {
             tick,
         )?;
 
-        // Group by customer
-        let mut customer_txns: HashMap<String, Vec<_>> = HashMap::new();
+        let multipliers = self.threshold_multipliers()?;
+
+        // Group by customer, in id order so alert ids draw the same rng
+        let mut customer_txns: BTreeMap<String, Vec<_>> = BTreeMap::new();
         for txn in all_txns {
             customer_txns
                 .entry(txn.customer_id.clone())
@@ -224,14 +594,20 @@ impl TransactionMonitoringSubsystem {
         for (customer_id, txns) in customer_txns {
             let total_amount: f64 = txns.iter().map(|t| t.amount.abs()).sum();
             let txn_count = txns.len();
+            let multiplier = multipliers.get(&customer_id).copied().unwrap_or(1.0)
+                * self.tunings.multiplier("VEL_50K_7D");
+            let amount_threshold = HIGH_VELOCITY_AMOUNT_7D * multiplier;
 
             // Check if exceeds thresholds
-            if total_amount > HIGH_VELOCITY_AMOUNT_7D && txn_count > HIGH_VELOCITY_COUNT_7D {
+            if !self.tunings.excluded("VEL_50K_7D", &customer_id)
+                && total_amount > amount_threshold
+                && txn_count as f64 > HIGH_VELOCITY_COUNT_7D as f64 * multiplier
+            {
                 let alert_id = format!("VEL-{}-{}", customer_id, rng.next_u64_below(100000));
 
                 let description = format!(
-                    "{} transactions totaling ${:.2} in 7 days (threshold: ${})",
-                    txn_count, total_amount, HIGH_VELOCITY_AMOUNT_7D
+                    "{} transactions totaling ${:.2} in 7 days (threshold: ${:.0})",
+                    txn_count, total_amount, amount_threshold
                 );
 
                 let alert = AMLAlert {
@@ -259,9 +635,8 @@ impl TransactionMonitoringSubsystem {
                     description,
                 });
 
-                log::info!(
-                    "tick={} High velocity detected: {} ({} txns, ${:.2})",
-                    tick,
+                tracing::info!(
+                    "High velocity detected: {} ({} txns, ${:.2})",
                     customer_id,
                     txn_count,
                     total_amount
@@ -272,6 +647,262 @@ impl TransactionMonitoringSubsystem {
         Ok(events)
     }
 
+    /// Phase 4.35: alert on customers whose gambling and crypto share of
+    /// merchant spend, by enriched category, crosses the threshold.
+    fn detect_high_risk_spend(&self, tick: Tick, rng: &mut SubsystemRng) -> SimResult<Vec<SimEvent>> {
+        let mut events = Vec::new();
+        let config = self.enrichment.config();
+        for (customer_id, spend) in self.enrichment.intensity(&self.store, None, tick)? {
+            if !self.enrichment.is_intense(&spend) {
+                continue;
+            }
+            let alert_id = format!("MCC-{}-{}", customer_id, rng.next_u64_below(100000));
+            let description = format!(
+                "{:.0}% of ${:.2} merchant spend in high-risk categories over {} days",
+                spend.share() * 100.0,
+                spend.total_spend,
+                config.intensity_lookback_ticks
+            );
+
+            let alert = AMLAlert {
+                alert_id: alert_id.clone(),
+                run_id: self.run_id.clone(),
+                customer_id: customer_id.clone(),
+                tick,
+                rule_id: "HIGH_RISK_MCC".into(),
+                alert_type: "category_intensity".into(),
+                alert_score: config.intensity_alert_score,
+                description: description.clone(),
+                triggered_amount: Some(spend.high_risk_spend),
+                transaction_count: None,
+                status: "open".into(),
+            };
+            self.store.insert_transaction_monitoring_alert(&alert)?;
+
+            events.push(SimEvent::TransactionMonitoringAlert {
+                tick,
+                alert_id,
+                alert_type: "category_intensity".into(),
+                customer_id: customer_id.clone(),
+                alert_score: config.intensity_alert_score,
+                description,
+            });
+            tracing::info!(
+                "High-risk spend intensity: {} ({:.0}% of ${:.2})",
+                customer_id,
+                spend.share() * 100.0,
+                spend.total_spend
+            );
+        }
+        Ok(events)
+    }
+
+    /// Phase 4.36: alert on customers fanning remittances out across
+    /// beneficiaries or corridors while passing most credits straight
+    /// back out.
+    fn detect_remittance_layering(&self, tick: Tick, rng: &mut SubsystemRng) -> SimResult<Vec<SimEvent>> {
+        let mut events = Vec::new();
+        let lookback = self.remittance.config().lookback_ticks;
+        let start = (tick + 1).saturating_sub(lookback);
+        for activity in self.store.remittance_activity(&self.run_id, start, tick)? {
+            let Some(alert_score) = self.remittance.layering_score(&activity) else {
+                continue;
+            };
+            let alert_id = format!("REMIT-{}-{}", activity.customer_id, rng.next_u64_below(100000));
+            let description = format!(
+                "${:.2} remitted to {} beneficiaries via {} over {} days, {:.0}% of credits in",
+                activity.sent,
+                activity.beneficiaries,
+                activity.corridors.join("/"),
+                lookback,
+                Remittance::pass_through(&activity) * 100.0
+            );
+
+            let alert = AMLAlert {
+                alert_id: alert_id.clone(),
+                run_id: self.run_id.clone(),
+                customer_id: activity.customer_id.clone(),
+                tick,
+                rule_id: "REMIT_LAYERING".into(),
+                alert_type: "remittance_layering".into(),
+                alert_score,
+                description: description.clone(),
+                triggered_amount: Some(activity.sent),
+                transaction_count: Some(activity.beneficiaries),
+                status: "open".into(),
+            };
+            self.store.insert_transaction_monitoring_alert(&alert)?;
+
+            events.push(SimEvent::TransactionMonitoringAlert {
+                tick,
+                alert_id,
+                alert_type: "remittance_layering".into(),
+                customer_id: activity.customer_id.clone(),
+                alert_score,
+                description,
+            });
+            tracing::info!(
+                "Remittance layering: {} (${:.2} to {} beneficiaries)",
+                activity.customer_id,
+                activity.sent,
+                activity.beneficiaries
+            );
+        }
+        Ok(events)
+    }
+
+    /// Phase 4.39: alert on respondent banks whose wires over the lookback
+    /// show third-party ordering institutions or more originators than
+    /// they declared. The alert is raised against the respondent.
+    fn detect_correspondent_nested(&self, tick: Tick, rng: &mut SubsystemRng) -> SimResult<Vec<SimEvent>> {
+        let mut events = Vec::new();
+        let lookback = self.correspondent.config().lookback_ticks;
+        let start = (tick + 1).saturating_sub(lookback);
+        let relationships = self.store.correspondent_relationships(&self.run_id)?;
+        for flow in self.store.correspondent_flows(&self.run_id, start, tick)? {
+            let Some(relationship) = relationships.iter().find(|r| r.bank_id == flow.bank_id) else {
+                continue;
+            };
+            let Some(alert_score) = self.correspondent.nested_score(&flow, relationship) else {
+                continue;
+            };
+            let alert_id = format!("CORR-{}-{}-{}", flow.bank_id, tick, rng.next_u64_below(100000));
+            let description = format!(
+                "{} wires (${:.2}) from {} over {} days: {} from third-party institutions, {} originators against {} declared",
+                flow.wires,
+                flow.amount,
+                flow.bank_id,
+                lookback,
+                flow.third_party_wires,
+                flow.distinct_originators,
+                relationship.declared_originators
+            );
+
+            let alert = AMLAlert {
+                alert_id: alert_id.clone(),
+                run_id: self.run_id.clone(),
+                customer_id: flow.bank_id.clone(),
+                tick,
+                rule_id: "CORR_NESTED".into(),
+                alert_type: "correspondent_nested".into(),
+                alert_score,
+                description: description.clone(),
+                triggered_amount: Some(flow.amount),
+                transaction_count: Some(flow.wires),
+                status: "open".into(),
+            };
+            self.store.insert_transaction_monitoring_alert(&alert)?;
+
+            events.push(SimEvent::TransactionMonitoringAlert {
+                tick,
+                alert_id,
+                alert_type: "correspondent_nested".into(),
+                customer_id: flow.bank_id.clone(),
+                alert_score,
+                description,
+            });
+            tracing::info!(
+                "Correspondent nested activity: {} ({} third-party wires, {} originators)",
+                flow.bank_id,
+                flow.third_party_wires,
+                flow.distinct_originators
+            );
+        }
+        Ok(events)
+    }
+
+    /// Phase 4.40: alert on customers whose foreign wires in over the
+    /// lookback came from many senders and went back out to many domestic
+    /// payees, recording the window's transactions as the finding's
+    /// evidence.
+    fn detect_cross_border_funnels(&self, tick: Tick, rng: &mut SubsystemRng) -> SimResult<Vec<SimEvent>> {
+        let mut events = Vec::new();
+        let lookback = self.cross_border.config().lookback_ticks;
+        let start = (tick + 1).saturating_sub(lookback);
+        let jurisdictions = self.store.get_high_risk_jurisdictions()?;
+        for activity in self.store.cross_border_activity(&self.run_id, start, tick)? {
+            let Some(alert_score) = self.cross_border.funnel_score(&activity, &jurisdictions) else {
+                continue;
+            };
+            let listed: Vec<String> = CrossBorder::listed_countries(&activity, &jurisdictions)
+                .iter()
+                .map(|j| j.country_code.clone())
+                .collect();
+            let alert_id = format!("XB-{}-{}", activity.customer_id, rng.next_u64_below(100000));
+            let description = format!(
+                "${:.2} wired in by {} senders from {} over {} days, {:.0}% sent on to {} domestic payees",
+                activity.inflow,
+                activity.senders,
+                activity.countries.join("/"),
+                lookback,
+                CrossBorder::dispersal(&activity) * 100.0,
+                activity.payees
+            );
+
+            let alert = AMLAlert {
+                alert_id: alert_id.clone(),
+                run_id: self.run_id.clone(),
+                customer_id: activity.customer_id.clone(),
+                tick,
+                rule_id: "XB_FUNNEL".into(),
+                alert_type: "cross_border_funnel".into(),
+                alert_score,
+                description: description.clone(),
+                triggered_amount: Some(activity.inflow),
+                transaction_count: Some(activity.senders),
+                status: "open".into(),
+            };
+            self.store.insert_transaction_monitoring_alert(&alert)?;
+
+            let finding_id = format!("xbf-{alert_id}");
+            let evidence =
+                self.store
+                    .cross_border_window_evidence(&self.run_id, &activity.customer_id, start, tick)?;
+            self.store.insert_cross_border_finding(
+                &self.run_id,
+                &CrossBorderFindingRow {
+                    finding_id: finding_id.clone(),
+                    customer_id: activity.customer_id.clone(),
+                    alert_id: alert_id.clone(),
+                    tick,
+                    window_start: start,
+                    senders: activity.senders,
+                    countries: activity.countries.clone(),
+                    high_risk_countries: listed,
+                    inflow: activity.inflow,
+                    payees: activity.payees,
+                    outflow: activity.outflow,
+                    alert_score,
+                },
+                &evidence,
+            )?;
+
+            events.push(SimEvent::TransactionMonitoringAlert {
+                tick,
+                alert_id: alert_id.clone(),
+                alert_type: "cross_border_funnel".into(),
+                customer_id: activity.customer_id.clone(),
+                alert_score,
+                description,
+            });
+            events.push(SimEvent::CrossBorderFunnelFinding {
+                tick,
+                finding_id,
+                alert_id,
+                customer_id: activity.customer_id.clone(),
+                senders: activity.senders,
+                payees: activity.payees,
+            });
+            tracing::info!(
+                "Cross-border funnel: {} ({} senders in, {} payees out)",
+                activity.customer_id,
+                activity.senders,
+                activity.payees
+            );
+        }
+        Ok(events)
+    }
+
     /// Auto-file CTRs for cash transactions >= $10k
     fn file_ctrs(&self, tick: Tick, rng: &mut SubsystemRng) -> SimResult<Vec<SimEvent>> {
         let mut events = Vec::new();
@@ -287,7 +918,7 @@ impl TransactionMonitoringSubsystem {
             let ctr_id = format!("CTR-{}-{}", txn.transaction_id, rng.next_u64_below(1000));
 
             // CTR must be filed within 15 days
-            let filing_deadline = tick + 15;
+            let filing_deadline = self.calendar.roll_forward(tick + 15);
 
             let ctr = CurrencyTransactionReport {
                 ctr_id: ctr_id.clone(),
@@ -317,9 +948,8 @@ impl TransactionMonitoringSubsystem {
                 transaction_type: ctr.transaction_type,
             });
 
-            log::info!(
-                "tick={} CTR filed: ${:.2} {} transaction",
-                tick,
+            tracing::info!(
+                "CTR filed: ${:.2} {} transaction",
                 txn.amount,
                 txn.txn_type
             );
@@ -335,6 +965,9 @@ impl TransactionMonitoringSubsystem {
         rng: &mut SubsystemRng,
     ) -> SimResult<Vec<SimEvent>> {
         let mut events = Vec::new();
+        if self.tunings.suppressed("RAPID_MOVE") {
+            return Ok(events);
+        }
 
         // Get transactions from last day
         let lookback_start = tick.saturating_sub(RAPID_MOVEMENT_WINDOW_DAYS);
@@ -344,8 +977,10 @@ impl TransactionMonitoringSubsystem {
             tick,
         )?;
 
-        // Group by account
-        let mut account_txns: HashMap<String, Vec<_>> = HashMap::new();
+        let multipliers = self.threshold_multipliers()?;
+
+        // Group by account, in id order so alert ids draw the same rng
+        let mut account_txns: BTreeMap<String, Vec<_>> = BTreeMap::new();
         for txn in recent_txns {
             account_txns
                 .entry(txn.account_id.clone())
@@ -362,11 +997,15 @@ impl TransactionMonitoringSubsystem {
             for i in 0..sorted_txns.len().saturating_sub(1) {
                 let deposit = &sorted_txns[i];
                 let withdrawal = &sorted_txns[i + 1];
+                let threshold = RAPID_MOVEMENT_THRESHOLD
+                    * multipliers.get(&deposit.customer_id).copied().unwrap_or(1.0)
+                    * self.tunings.multiplier("RAPID_MOVE");
 
-                if deposit.txn_type == "credit"
+                if !self.tunings.excluded("RAPID_MOVE", &deposit.customer_id)
+                    && deposit.txn_type == "credit"
                     && withdrawal.txn_type == "debit"
-                    && deposit.amount >= RAPID_MOVEMENT_THRESHOLD
-                    && withdrawal.amount >= RAPID_MOVEMENT_THRESHOLD
+                    && deposit.amount >= threshold
+                    && withdrawal.amount >= threshold
                     && withdrawal.tick <= deposit.tick + 1
                 // Within 1 day
                 {
@@ -406,9 +1045,8 @@ impl TransactionMonitoringSubsystem {
                         description,
                     });
 
-                    log::info!(
-                        "tick={} Rapid movement detected: account {} (deposit ${:.2}, withdrawal ${:.2})",
-                        tick,
+                    tracing::info!(
+                        "Rapid movement detected: account {} (deposit ${:.2}, withdrawal ${:.2})",
                         account_id,
                         deposit.amount,
                         withdrawal.amount
@@ -477,115 +1115,309 @@ impl TransactionMonitoringSubsystem {
     }
 
     /// File SARs for high-scoring alerts (Week 6)
-    fn file_sars(&self, tick: Tick, rng: &mut SubsystemRng) -> SimResult<Vec<SimEvent>> {
+    fn file_sars(&self, tick: Tick, capacity: Option<usize>, rng: &mut SubsystemRng) -> SimResult<Vec<SimEvent>> {
         let mut events = Vec::new();
 
-        // Get high-scoring alerts from last 30 days that haven't had SAR filed
-        let lookback_start = tick.saturating_sub(30);
-        let high_score_alerts = self.store.get_alerts_above_threshold(
+        // Get high-scoring alerts from last 30 days that haven't had SAR
+        // filed; a staffed desk works its whole backlog instead, oldest
+        // first, as far as its capacity goes (Phase 4.80)
+        let lookback_start = match capacity {
+            Some(_) => 0,
+            None => tick.saturating_sub(SAR_FILING_DEADLINE_DAYS),
+        };
+        let mut high_score_alerts = self.store.get_alerts_above_threshold(
             &self.run_id,
-            85.0, // SAR threshold: 85+ alert score
+            self.sar_threshold, // SAR threshold: 85+ alert score by default
             lookback_start,
             tick,
         )?;
+        if let Some(capacity) = capacity {
+            high_score_alerts.sort_by(|a, b| a.tick.cmp(&b.tick).then_with(|| a.alert_id.cmp(&b.alert_id)));
+            high_score_alerts.truncate(capacity);
+        }
+        let filed = high_score_alerts.len();
 
         for alert in high_score_alerts {
-            // Generate SAR ID
-            let sar_id = format!("SAR-{}-{}", alert.customer_id, rng.next_u64_below(1000000));
-
-            // Generate narrative based on alert type
-            let narrative = match alert.alert_type.as_str() {
-                "structuring" => format!(
-                    "Customer {} engaged in potential structuring activity. {}",
-                    alert.customer_id, alert.description
-                ),
-                "velocity" => format!(
-                    "Customer {} exhibited high-velocity transaction patterns. {}",
-                    alert.customer_id, alert.description
-                ),
-                "rapid_movement" => format!(
-                    "Customer {} demonstrated rapid money movement patterns. {}",
-                    alert.customer_id, alert.description
-                ),
-                _ => format!(
-                    "Suspicious activity detected for customer {}. Type: {}. {}",
-                    alert.customer_id, alert.alert_type, alert.description
-                ),
-            };
+            events.extend(self.file_sar(alert, tick, rng)?);
+        }
+
+        if let Some(capacity) = capacity {
+            let cleared = self.clear_alerts(tick, capacity - filed)?;
+            self.store
+                .record_staffing_work(&self.run_id, tick, QueueKind::Alerts, filed + cleared.len())?;
+            events.extend(cleared);
+        }
+
+        Ok(events)
+    }
+
+    /// File a SAR on one alert: the report, its evidence bundle and the
+    /// filing decision.
+    fn file_sar(&self, alert: AMLAlert, tick: Tick, rng: &mut SubsystemRng) -> SimResult<Vec<SimEvent>> {
+        let mut events = Vec::new();
+
+        // Generate SAR ID
+        let sar_id = format!("SAR-{}-{}", alert.customer_id, rng.next_u64_below(1000000));
+
+        // Generate narrative based on alert type
+        let narrative = match alert.alert_type.as_str() {
+            "structuring" => format!(
+                "Customer {} engaged in potential structuring activity. {}",
+                alert.customer_id, alert.description
+            ),
+            "velocity" => format!(
+                "Customer {} exhibited high-velocity transaction patterns. {}",
+                alert.customer_id, alert.description
+            ),
+            "rapid_movement" => format!(
+                "Customer {} demonstrated rapid money movement patterns. {}",
+                alert.customer_id, alert.description
+            ),
+            // Phase 4.40: quote the finding's evidence where it was kept
+            "cross_border_funnel" => {
+                match self.store.cross_border_finding_for_alert(&self.run_id, &alert.alert_id)? {
+                    Some(finding) => {
+                        let evidence = self.store.cross_border_evidence(&finding.finding_id)?;
+                        CrossBorder::narrative(&finding, &evidence)
+                    }
+                    None => format!(
+                        "Customer {} moved foreign funds through to domestic payees. {}",
+                        alert.customer_id, alert.description
+                    ),
+                }
+            }
+            _ => format!(
+                "Suspicious activity detected for customer {}. Type: {}. {}",
+                alert.customer_id, alert.alert_type, alert.description
+            ),
+        };
+
+        // SAR must be filed within 30 days of detection
+        let filing_deadline = self.sar_deadline(alert.tick);
+        let filed_on_time = tick <= filing_deadline;
 
-            // SAR must be filed within 30 days of detection
-            let filing_deadline = alert.tick + 30;
-            let days_elapsed = tick.saturating_sub(alert.tick);
-            let filed_on_time = days_elapsed <= 30;
+        // Calculate regulatory fine for late filing: $25,000 base + $1,000 per day late
+        let regulatory_fine = if !filed_on_time {
+            let days_late = tick - filing_deadline;
+            25000.0 + (days_late as f64 * 1000.0)
+        } else {
+            0.0
+        };
 
-            // Calculate regulatory fine for late filing: $25,000 base + $1,000 per day late
-            let regulatory_fine = if !filed_on_time {
-                let days_late = days_elapsed - 30;
-                25000.0 + (days_late as f64 * 1000.0)
+        let sar = SuspiciousActivityReport {
+            sar_id: sar_id.clone(),
+            run_id: self.run_id.clone(),
+            filing_tick: tick,
+            subject_type: "customer".into(),
+            subject_id: alert.customer_id.clone(),
+            activity_type: alert.alert_type.clone(),
+            suspicious_amount: alert.triggered_amount.unwrap_or(0.0),
+            narrative,
+            filing_deadline,
+            filed_on_time,
+            filing_status: if filed_on_time {
+                "filed".into()
             } else {
-                0.0
-            };
+                "late".into()
+            },
+            regulatory_fine,
+            related_alerts: Some(serde_json::to_string(&[&alert.alert_id])?),
+        };
 
-            let sar = SuspiciousActivityReport {
-                sar_id: sar_id.clone(),
-                run_id: self.run_id.clone(),
-                filing_tick: tick,
-                subject_type: "customer".into(),
-                subject_id: alert.customer_id.clone(),
-                activity_type: alert.alert_type.clone(),
-                suspicious_amount: alert.triggered_amount.unwrap_or(0.0),
-                narrative,
-                filing_deadline,
-                filed_on_time,
-                filing_status: if filed_on_time {
-                    "filed".into()
-                } else {
-                    "late".into()
-                },
+        self.store.insert_sar(&sar)?;
+        self.store.mark_alert_sar_filed(&self.run_id, &alert.alert_id, tick)?;
+
+        // Phase 4.59: keep the evidence behind the filing with it
+        let evidence = if self.evidence.enabled() {
+            Some(self.evidence.for_sar(&self.store, &sar, &alert, tick)?)
+        } else {
+            None
+        };
+
+        // Phase 4.16: record the filing decision and its cost
+        if self.sar.enabled {
+            let defensive = alert.alert_score < self.sar.reportable_score;
+            self.store.insert_sar_decision(&self.run_id, &SarDecisionRow {
+                alert_id: alert.alert_id.clone(),
+                tick,
+                customer_id: alert.customer_id.clone(),
+                alert_score: alert.alert_score,
+                threshold: self.sar_threshold,
+                decision: if defensive { "defensive" } else { "filed" }.into(),
+                sar_id: Some(sar_id.clone()),
+                cost: self.sar.cost_per_filing,
+            })?;
+        }
+
+        events.push(SimEvent::SARFiled {
+            tick,
+            sar_id: sar_id.clone(),
+            customer_id: alert.customer_id.clone(),
+            activity_type: sar.activity_type.clone(),
+            suspicious_amount: sar.suspicious_amount,
+        });
+        events.extend(evidence);
+
+        if !filed_on_time {
+            let days_late = (tick - filing_deadline) as i64;
+            events.push(SimEvent::SARLateFiling {
+                tick,
+                sar_id,
+                customer_id: alert.customer_id,
+                days_late,
                 regulatory_fine,
-                related_alerts: Some(format!("[\"{}]", alert.alert_id)),
-            };
+            });
+
+            tracing::warn!(
+                "SAR filed LATE: {} ({} days late, fine: ${:.2})",
+                sar.sar_id,
+                days_late,
+                regulatory_fine
+            );
+        } else {
+            tracing::info!(
+                "SAR filed: {} for {} ({})",
+                sar.sar_id,
+                alert.customer_id,
+                sar.activity_type
+            );
+        }
+
+        Ok(events)
+    }
+
+    /// Phase 4.80: up to `limit` alerts the desk can close without a
+    /// filing decision, oldest first: those below the reportable score,
+    /// or below the filing threshold when decisions are not recorded.
+    /// Reportable ones are left for the missed-SAR sweep.
+    fn clear_alerts(&self, tick: Tick, limit: usize) -> SimResult<Vec<SimEvent>> {
+        let ceiling = if self.sar.enabled {
+            self.sar.reportable_score.min(self.sar_threshold)
+        } else {
+            self.sar_threshold
+        };
+        let mut events = Vec::new();
+        for (alert_id, alert_score) in self.store.clearable_alerts(&self.run_id, ceiling, tick, limit)? {
+            self.store.clear_alert(&self.run_id, &alert_id, tick)?;
+            events.push(SimEvent::AlertCleared { tick, alert_id, alert_score });
+        }
+        Ok(events)
+    }
 
-            self.store.insert_sar(&sar)?;
-            self.store.mark_alert_sar_filed(&self.run_id, &alert.alert_id)?;
+    /// SAR deadline for an alert: 30 days on, or the next business day
+    /// after if that is a closure (Phase 4.41).
+    fn sar_deadline(&self, alert_tick: Tick) -> Tick {
+        self.calendar.roll_forward(alert_tick + SAR_FILING_DEADLINE_DAYS)
+    }
 
-            events.push(SimEvent::SARFiled {
+    /// Phase 4.16: reportable alerts below the filing threshold that have
+    /// run past the SAR deadline are missed filings.
+    fn sweep_missed_sars(&self, tick: Tick) -> SimResult<Vec<SimEvent>> {
+        let mut events = Vec::new();
+        let missed = self.store.unfiled_reportable_alerts(
+            &self.run_id,
+            self.sar.reportable_score,
+            self.sar_threshold,
+            tick.saturating_sub(SAR_FILING_DEADLINE_DAYS),
+        )?;
+        // A deadline rolled past a closure has not run out yet
+        for alert in missed.into_iter().filter(|a| tick > self.sar_deadline(a.tick)) {
+            self.store.mark_alert_sar_missed(&self.run_id, &alert.alert_id)?;
+            self.store.insert_sar_decision(&self.run_id, &SarDecisionRow {
+                alert_id: alert.alert_id.clone(),
                 tick,
-                sar_id: sar_id.clone(),
                 customer_id: alert.customer_id.clone(),
-                activity_type: sar.activity_type.clone(),
-                suspicious_amount: sar.suspicious_amount,
+                alert_score: alert.alert_score,
+                threshold: self.sar_threshold,
+                decision: "missed".into(),
+                sar_id: None,
+                cost: 0.0,
+            })?;
+            tracing::warn!(
+                "SAR missed: alert {} (score {:.0}) unfiled past deadline",
+                alert.alert_id,
+                alert.alert_score
+            );
+            events.push(SimEvent::SarFilingMissed {
+                tick,
+                alert_id: alert.alert_id,
+                customer_id: alert.customer_id,
+                alert_score: alert.alert_score,
             });
+        }
+        Ok(events)
+    }
 
-            if !filed_on_time {
-                let days_late = (days_elapsed - 30) as i64;
-                events.push(SimEvent::SARLateFiling {
-                    tick,
-                    sar_id,
-                    customer_id: alert.customer_id,
-                    days_late,
-                    regulatory_fine,
-                });
+    /// Phase 4.16: monthly SAR volume and quality.
+    fn compute_sar_quality(&self, tick: Tick) -> SimResult<Vec<SimEvent>> {
+        let window_start = tick.saturating_sub(29);
+        let counts = self.store.sar_decision_counts_between(&self.run_id, window_start, tick)?;
+        let sars_filed = counts.filed + counts.defensive;
+        let sar_quality = (sars_filed > 0).then(|| counts.filed as f64 / sars_filed as f64);
+        self.store.insert_sar_quality_metrics(&self.run_id, &SarQualityMetricsRow {
+            tick,
+            threshold: self.sar_threshold,
+            sars_filed,
+            defensive_filings: counts.defensive,
+            missed_filings: counts.missed,
+            sar_quality,
+            filing_cost: self.store.sar_filing_cost_between(&self.run_id, window_start, tick)?,
+        })?;
+        Ok(vec![SimEvent::SarQualityComputed {
+            tick,
+            sars_filed,
+            defensive_filings: counts.defensive,
+            missed_filings: counts.missed,
+            sar_quality,
+        }])
+    }
 
-                log::warn!(
-                    "tick={} SAR filed LATE: {} ({} days late, fine: ${:.2})",
-                    tick,
-                    sar.sar_id,
-                    days_late,
-                    regulatory_fine
-                );
-            } else {
-                log::info!(
-                    "tick={} SAR filed: {} for {} ({})",
-                    tick,
-                    sar.sar_id,
-                    alert.customer_id,
-                    sar.activity_type
-                );
+    /// Phase 4.18: compare the rule inventory against the typologies
+    /// running over the last interval. The event carries the inventory
+    /// only; ground truth goes to the store.
+    fn assess_coverage(&self, tick: Tick) -> SimResult<Vec<SimEvent>> {
+        let window_start = tick.saturating_sub(self.coverage.interval_ticks.saturating_sub(1));
+        let (active_rules, suppressed_rules): (Vec<String>, Vec<String>) = self
+            .store
+            .enabled_monitoring_rules()?
+            .into_iter()
+            .partition(|rule_id| !self.tunings.suppressed(rule_id));
+
+        let mut typologies_addressed = Vec::new();
+        let mut uncovered_present = 0;
+        for (typology, rules) in coverage_assessment::TYPOLOGIES {
+            let covering: Vec<&str> = rules
+                .iter()
+                .copied()
+                .filter(|rule_id| active_rules.iter().any(|r| r == rule_id))
+                .collect();
+            let row = CoverageAssessmentRow {
+                tick,
+                typology: typology.to_string(),
+                rules: covering.join(","),
+                covered: !covering.is_empty(),
+                occurrences: coverage_assessment::occurrences(
+                    &self.store, &self.run_id, typology, window_start, tick,
+                )?,
+            };
+            if row.covered {
+                typologies_addressed.push(row.typology.clone());
+            } else if row.present() {
+                uncovered_present += 1;
             }
+            self.store.insert_coverage_assessment(&self.run_id, &row)?;
         }
 
-        Ok(events)
+        tracing::debug!(
+            "coverage assessed, {} typologies addressed, {uncovered_present} uncovered and present",
+            typologies_addressed.len(),
+        );
+        Ok(vec![SimEvent::CoverageAssessed {
+            tick,
+            active_rules,
+            suppressed_rules,
+            typologies_addressed,
+        }])
     }
 
     /// Compute monthly SAR filing metrics
@@ -621,9 +1453,8 @@ impl TransactionMonitoringSubsystem {
             total_fines,
         });
 
-        log::info!(
-            "tick={} SAR metrics: {} filed, {} late, ${:.2} fines",
-            tick,
+        tracing::info!(
+            "SAR metrics: {} filed, {} late, ${:.2} fines",
             sars_filed,
             sars_late,
             total_fines
@@ -633,15 +1464,40 @@ impl TransactionMonitoringSubsystem {
     }
 }
 
+const MANIFEST: SubsystemManifest = SubsystemManifest {
+    version: 3,
+    produces: &[
+        "alert_assigned", "alert_case_closed", "alert_cleared", "alert_case_opened", "alert_closed", "alert_escalated",
+        "alert_grouped_into_case", "alert_qa_cycle_completed", "alert_triage_rejected", "coverage_assessed", "cross_border_funnel_finding", "ctr_filed", "evidence_bundle_assembled",
+        "merchants_categorized",
+        "monitoring_rule_tuned", "qa_retraining_completed", "qa_retraining_opened", "rule_tuning_rejected", "sar_filed", "sar_filing_missed",
+        "sar_late_filing", "sar_metrics_computed", "sar_quality_computed", "sar_threshold_changed",
+        "transaction_monitoring_alert", "transaction_monitoring_metrics_computed",
+        "tuning_review_approved", "tuning_review_opened",
+    ],
+    consumes: &["player_command_received"],
+    tables: &[
+        "alert_aging", "alert_case", "alert_note", "alert_qa_cycle", "alert_qa_review", "aml_alert", "coverage_assessment", "cross_border_evidence", "cross_border_finding",
+        "currency_transaction_report", "evidence_bundle", "merchant_category", "qa_retraining", "rule_tuning", "sar_decision",
+        "sar_filing_metrics", "sar_quality_metrics", "staffing_snapshot", "suspicious_activity_report",
+        "transaction_monitoring_metrics", "tuning_review",
+    ],
+    requires: &[],
+};
+
 impl SimSubsystem for TransactionMonitoringSubsystem {
     fn name(&self) -> &'static str {
         "transaction_monitoring"
     }
 
+    fn manifest(&self) -> SubsystemManifest {
+        MANIFEST
+    }
+
     fn update(
         &mut self,
         tick: Tick,
-        _events_in: &[SimEvent],
+        events_in: &[SimEvent],
         rng: &mut SubsystemRng,
     ) -> SimResult<Vec<SimEvent>> {
         let mut out = Vec::new();
@@ -650,6 +1506,18 @@ impl SimSubsystem for TransactionMonitoringSubsystem {
             return Ok(out);
         }
 
+        // 0. Tuning reviews that have run their course, then player changes
+        //    to the SAR filing threshold and rule tunings
+        if self.tuning.enabled {
+            out.extend(self.approve_tuning_reviews(tick)?);
+        }
+        out.extend(self.apply_player_commands(tick, events_in, rng)?);
+
+        // 0b. Categorize new merchants (Phase 4.35)
+        if self.enrichment.enabled() {
+            out.extend(self.enrichment.enrich(&self.store, tick, rng)?);
+        }
+
         // 1. Detect structuring (every tick)
         out.extend(self.detect_structuring(tick, rng)?);
 
@@ -662,24 +1530,103 @@ impl SimSubsystem for TransactionMonitoringSubsystem {
         // 4. Detect rapid money movement (every tick)
         out.extend(self.detect_rapid_movement(tick, rng)?);
 
+        // 4b. Gambling and crypto spend intensity (weekly, Phase 4.35)
+        if self.enrichment.enabled() && tick.is_multiple_of(METRICS_INTERVAL) {
+            out.extend(self.detect_high_risk_spend(tick, rng)?);
+        }
+
+        // 4c. Remittance layering (weekly, Phase 4.36)
+        if self.remittance.enabled() && tick.is_multiple_of(METRICS_INTERVAL) {
+            out.extend(self.detect_remittance_layering(tick, rng)?);
+        }
+
+        // 4d. Nested correspondent activity (weekly, Phase 4.39)
+        if self.correspondent.enabled() && tick.is_multiple_of(METRICS_INTERVAL) {
+            out.extend(self.detect_correspondent_nested(tick, rng)?);
+        }
+
+        // 4e. Cross-border fan-in/fan-out (weekly, Phase 4.40)
+        if self.cross_border.enabled() && tick.is_multiple_of(METRICS_INTERVAL) {
+            out.extend(self.detect_cross_border_funnels(tick, rng)?);
+        }
+
+        // 4f. Group the tick's new alerts into cases (Phase 4.60)
+        if self.cases.enabled() {
+            out.extend(self.cases.group(&self.store, tick)?);
+        }
+
         // 5. Compute metrics (weekly)
         if tick.is_multiple_of(METRICS_INTERVAL) {
             out.extend(self.compute_metrics(tick)?);
         }
 
-        // 6. File SARs for high-scoring alerts (weekly)
-        if tick.is_multiple_of(METRICS_INTERVAL) {
-            out.extend(self.file_sars(tick, rng)?);
+        // 6. File SARs for high-scoring alerts (weekly, or every tick a
+        //    staffed desk works the queue — Phase 4.80)
+        let capacity = self.store.staffing_capacity(&self.run_id, tick, QueueKind::Alerts)?;
+        if capacity.is_some() || tick.is_multiple_of(METRICS_INTERVAL) {
+            out.extend(self.file_sars(tick, capacity, rng)?);
+        }
+        if self.sar.enabled && tick.is_multiple_of(METRICS_INTERVAL) {
+            out.extend(self.sweep_missed_sars(tick)?);
+        }
+
+        // 6b. Close cases whose alerts have all been worked (Phase 4.60),
+        //     including those opened on escalation (Phase 4.85)
+        if self.cases.enabled() || self.triage.enabled() {
+            out.extend(self.cases.close_worked(&self.store, tick)?);
         }
 
         // 7. Compute SAR metrics (monthly - every 30 ticks)
         if tick.is_multiple_of(30) {
             out.extend(self.compute_sar_metrics(tick)?);
+            if self.sar.enabled {
+                out.extend(self.compute_sar_quality(tick)?);
+            }
+        }
+
+        // 7b. QA re-review of SAR decisions and retraining (Phase 4.73)
+        if self.sar.enabled && self.qa.enabled() {
+            out.extend(self.qa.complete_retraining(&self.store, tick)?);
+            out.extend(self.qa.review(&self.store, tick, rng)?);
+        }
+
+        // 8. Coverage assessment (Phase 4.18)
+        if self.coverage.enabled && tick.is_multiple_of(self.coverage.interval_ticks) {
+            out.extend(self.assess_coverage(tick)?);
+        }
+
+        // 9. The alert queue's aging (Phase 4.85)
+        if self.triage.enabled() {
+            self.triage.record_aging(&self.store, tick)?;
         }
 
         Ok(out)
     }
 
+    // Rule tunings are the store's and are read back from it
+    fn save_state(&self) -> SimResult<Option<serde_json::Value>> {
+        Ok(Some(serde_json::json!({ "sar_threshold": self.sar_threshold })))
+    }
+
+    fn load_state(&mut self, state: serde_json::Value) -> SimResult<()> {
+        self.sar_threshold = serde_json::from_value(state["sar_threshold"].clone())?;
+        self.tunings = RuleTunings::new(&self.store.active_rule_tunings(&self.run_id)?);
+        Ok(())
+    }
+
+    fn replay(&mut self, _tick: Tick, events: &[SimEvent]) -> SimResult<()> {
+        for event in events {
+            match event {
+                SimEvent::SarThresholdChanged { threshold, .. } => self.sar_threshold = *threshold,
+                SimEvent::MonitoringRuleTuned { .. } => {
+                    self.tunings = RuleTunings::new(&self.store.active_rule_tunings(&self.run_id)?);
+                }
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+
     fn as_any(&self) -> &dyn std::any::Any {
         self
     }
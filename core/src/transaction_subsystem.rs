@@ -1,24 +1,765 @@
 use crate::{
+    app_fraud::AppFraud,
+    atm::{AtmDay, AtmNetwork},
+    bec::BusinessEmailCompromise,
+    channel::{Channel, ChannelDay, Channels},
+    check_fraud::CheckFraud,
+    command::PlayerCommand,
+    config::{
+        AchOriginationConfig, AppFraudConfig, AtmNetworkConfig, BecConfig, ChannelConfig, CheckFraudConfig, CrossBorderConfig, CustomerLimitConfig,
+        CustomerLimitRule, DepositHoldConfig, DeviceTelemetryConfig, ExternalLinkConfig, HardshipConfig, HoldRule, MuleNetworkConfig, ProtectedFundsConfig,
+        RemittanceConfig, StructuringConfig, TimeAccelerationConfig, VisaExpiryConfig,
+    },
+    cross_border::CrossBorder,
+    device::Devices,
+    customer_limits::{self, DailyLimits},
+    deposit_holds::DepositHolds,
     error::SimResult,
     event::SimEvent,
+    mule_network::MuleNetworks,
+    remittance::Remittance,
     rng::SubsystemRng,
-    store::SimStore,
-    subsystem::SimSubsystem,
+    store::{
+        customer_limit::LimitDeclineRow,
+        external_link::ExternalLinkRow,
+        hardship::HardshipWaiverRow,
+        originated_ach::OriginatedAchRow,
+        protected_funds::{BenefitRecipientRow, ProtectedFundsEventRow},
+        AccountRow, SimStore,
+    },
+    structuring::Structuring,
+    subsystem::{SimSubsystem, SubsystemManifest},
     types::{RunId, Tick},
+    visa,
 };
+use std::collections::{BTreeMap, HashMap, HashSet};
 use uuid::Uuid;
 
 pub struct TransactionSubsystem {
     run_id: RunId,
+    protected_funds: ProtectedFundsConfig,
+    origination: AchOriginationConfig,
+    /// Phase 4.10: per-customer velocity limits, adjustable by the player.
+    daily_count_limit: u32,
+    daily_amount_limit: f64,
+    links: ExternalLinkConfig,
+    /// Phase 4.11: share of new links sent through micro-deposits,
+    /// adjustable by the player.
+    verification_strictness: f64,
+    /// Phase 4.12: limit rules, with the player's changes applied.
+    limits: CustomerLimitConfig,
+    /// Phase 4.25: coarse steps for low-risk accounts late in a run.
+    acceleration: TimeAccelerationConfig,
+    /// Phase 4.25: last tick each account was processed, so a step
+    /// covers exactly the days since. Only kept when acceleration is on.
+    last_processed: HashMap<String, Tick>,
+    /// Phase 4.30: fee waivers and setoff suspension for customers in
+    /// the hardship program, refreshed each tick.
+    hardship: HardshipConfig,
+    hardship_customers: HashSet<String>,
+    /// Phase 4.36: remittance corridors for international customers.
+    remittance: Remittance,
+    /// Phase 4.37: visa restrictions in force, refreshed each tick.
+    visa: VisaExpiryConfig,
+    restrictions: HashMap<String, String>,
+    /// Phase 4.40: cross-border funnels and support wires.
+    cross_border: CrossBorder,
+    /// Phase 4.53: vendor and spoofed invoices paid by business customers.
+    bec: BusinessEmailCompromise,
+    /// Phase 4.54: checks written, washed, deposited and counterfeited.
+    check_fraud: CheckFraud,
+    /// Phase 4.55: funds availability on check deposits.
+    deposit_holds: DepositHolds,
+    /// Phase 4.87: mule networks moving money through recruited customers.
+    mule_networks: MuleNetworks,
+    /// Phase 4.88: cash structuring by a share of risky customers.
+    structuring: Structuring,
+    /// Phase 4.90: the channel each customer debit goes through.
+    channels: Channels,
+    /// Phase 4.91: instant payments scammers talk customers into.
+    app_fraud: AppFraud,
+    /// Phase 4.92: the ATMs cash is withdrawn at.
+    atm_network: AtmNetwork,
+    /// Phase 4.94: logins from customers' devices, and account takeovers.
+    devices: Devices,
     store: SimStore,
 }
 
 impl TransactionSubsystem {
-    pub fn new(run_id: RunId, store: SimStore) -> Self {
-        Self { run_id, store }
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        run_id: RunId,
+        protected_funds: ProtectedFundsConfig,
+        origination: AchOriginationConfig,
+        links: ExternalLinkConfig,
+        limits: CustomerLimitConfig,
+        acceleration: TimeAccelerationConfig,
+        hardship: HardshipConfig,
+        remittance: RemittanceConfig,
+        visa: VisaExpiryConfig,
+        cross_border: CrossBorderConfig,
+        bec: BecConfig,
+        check_fraud: CheckFraudConfig,
+        deposit_holds: DepositHoldConfig,
+        mule_networks: MuleNetworkConfig,
+        structuring: StructuringConfig,
+        channels: ChannelConfig,
+        app_fraud: AppFraudConfig,
+        atm_network: AtmNetworkConfig,
+        devices: DeviceTelemetryConfig,
+        store: SimStore,
+    ) -> Self {
+        Self {
+            remittance: Remittance::new(run_id.clone(), remittance),
+            cross_border: CrossBorder::new(run_id.clone(), cross_border),
+            bec: BusinessEmailCompromise::new(run_id.clone(), bec),
+            check_fraud: CheckFraud::new(run_id.clone(), check_fraud),
+            deposit_holds: DepositHolds::new(run_id.clone(), deposit_holds),
+            mule_networks: MuleNetworks::new(run_id.clone(), mule_networks),
+            structuring: Structuring::new(run_id.clone(), structuring),
+            channels: Channels::new(run_id.clone(), channels),
+            app_fraud: AppFraud::new(run_id.clone(), app_fraud),
+            atm_network: AtmNetwork::new(run_id.clone(), atm_network),
+            devices: Devices::new(run_id.clone(), devices),
+            run_id,
+            protected_funds,
+            daily_count_limit: origination.daily_count_limit,
+            daily_amount_limit: origination.daily_amount_limit,
+            origination,
+            verification_strictness: links.verification_strictness,
+            links,
+            limits,
+            acceleration,
+            last_processed: HashMap::new(),
+            hardship,
+            hardship_customers: HashSet::new(),
+            visa,
+            restrictions: HashMap::new(),
+            store,
+        }
+    }
+
+    /// Phase 4.30: whether the customer's hardship enrollment waives this fee.
+    fn hardship_waives(&self, fee_type: &str, customer_id: &str) -> bool {
+        self.hardship.waived_fee_types.iter().any(|f| f == fee_type)
+            && self.hardship_customers.contains(customer_id)
+    }
+
+    /// Phase 4.37: accounts whose customer's visa restriction does not
+    /// block `channel`.
+    fn unrestricted(&self, accounts: &[AccountRow], channel: &str) -> Vec<AccountRow> {
+        accounts
+            .iter()
+            .filter(|a| {
+                self.restrictions
+                    .get(&a.customer_id)
+                    .is_none_or(|level| !visa::blocks(level, channel))
+            })
+            .cloned()
+            .collect()
+    }
+
+    /// Phase 4.37: refuse a restricted customer's debit.
+    fn block_restricted(
+        &self,
+        customer_id: &str,
+        account_id: &str,
+        channel: &str,
+        amount: f64,
+        tick: Tick,
+    ) -> SimResult<SimEvent> {
+        self.store.update_customer_satisfaction(
+            &self.run_id,
+            customer_id,
+            self.visa.blocked_satisfaction_delta,
+        )?;
+        Ok(SimEvent::RestrictedTransactionBlocked {
+            tick,
+            customer_id: customer_id.to_string(),
+            account_id: account_id.to_string(),
+            channel: channel.to_string(),
+            amount,
+        })
+    }
+
+    /// Phase 4.25: customers stepped coarsely this tick. Empty until
+    /// acceleration kicks in; never includes a customer with an open
+    /// alert on them or their accounts, or a benefit recipient.
+    fn coarse_customers(
+        &self,
+        tick: Tick,
+        accounts: &[AccountRow],
+        recipients: &HashMap<String, BenefitRecipientRow>,
+    ) -> SimResult<HashSet<String>> {
+        if !self.acceleration.enabled || tick <= self.acceleration.coarse_after_tick {
+            return Ok(HashSet::new());
+        }
+        let flagged = self.store.entities_with_open_alerts(&self.run_id)?;
+        let daily: HashSet<&str> = accounts
+            .iter()
+            .filter(|a| flagged.contains(&a.account_id))
+            .map(|a| a.customer_id.as_str())
+            .chain(recipients.values().map(|r| r.customer_id.as_str()))
+            .collect();
+        Ok(self
+            .store
+            .customer_risk_tiers(&self.run_id)?
+            .into_iter()
+            .filter(|(customer_id, tier)| {
+                self.acceleration.coarse_risk_tiers.contains(tier)
+                    && !flagged.contains(customer_id)
+                    && !daily.contains(customer_id.as_str())
+            })
+            .map(|(customer_id, _)| customer_id)
+            .collect())
+    }
+
+    /// Apply player changes to velocity limits (Phase 4.10), link
+    /// verification strictness (Phase 4.11) and customer limits (Phase 4.12).
+    fn apply_player_commands(&mut self, tick: Tick, events_in: &[SimEvent]) -> SimResult<Vec<SimEvent>> {
+        let mut events = Vec::new();
+        for event in events_in {
+            let SimEvent::PlayerCommandReceived { command_id, .. } = event else {
+                continue;
+            };
+            match self.store.get_player_command(&self.run_id, command_id)? {
+                Some(PlayerCommand::SetAchOriginationLimits {
+                    daily_count,
+                    daily_amount,
+                }) => {
+                    self.daily_count_limit = daily_count;
+                    self.daily_amount_limit = daily_amount.max(0.0);
+                    tracing::info!(
+                        "ACH origination limits set to {} entries / ${:.0} per day",
+                        self.daily_count_limit,
+                        self.daily_amount_limit
+                    );
+                    events.push(SimEvent::AchOriginationLimitsChanged {
+                        tick,
+                        daily_count: self.daily_count_limit,
+                        daily_amount: self.daily_amount_limit,
+                    });
+                }
+                Some(PlayerCommand::SetLinkVerificationStrictness { strictness }) => {
+                    self.verification_strictness = strictness.clamp(0.0, 1.0);
+                    tracing::info!(
+                        "{:.0}% of new external links go through micro-deposits",
+                        self.verification_strictness * 100.0
+                    );
+                    events.push(SimEvent::LinkVerificationStrictnessChanged {
+                        tick,
+                        strictness: self.verification_strictness,
+                    });
+                }
+                Some(PlayerCommand::SetCustomerLimit { limit_type, scope, amount }) => {
+                    events.push(self.set_customer_limit(limit_type, scope, amount, tick)?);
+                }
+                Some(PlayerCommand::SetDepositHoldPolicy { class, risk, hold_ticks }) => {
+                    let rule = HoldRule { class, risk, hold_ticks };
+                    events.push(self.deposit_holds.set_policy(&self.store, rule, tick)?);
+                }
+                _ => {}
+            }
+        }
+        Ok(events)
+    }
+
+    /// Phase 4.12: replace the rule with the same type and scope.
+    fn set_customer_limit(
+        &mut self,
+        limit_type: String,
+        scope: String,
+        amount: f64,
+        tick: Tick,
+    ) -> SimResult<SimEvent> {
+        let reason = if !customer_limits::LIMIT_TYPES.contains(&limit_type.as_str()) {
+            Some("unknown limit type")
+        } else if !customer_limits::valid_scope(&scope) {
+            Some("scope must be global, tier:<tier> or product:<product_id>")
+        } else if amount.is_nan() || amount < 0.0 {
+            Some("amount must be zero or more")
+        } else {
+            None
+        };
+        if let Some(reason) = reason {
+            tracing::warn!("rejected {limit_type} limit for {scope}: {reason}");
+            return Ok(SimEvent::CustomerLimitRejected {
+                tick,
+                limit_type,
+                scope,
+                reason: reason.into(),
+            });
+        }
+
+        let rule = CustomerLimitRule {
+            limit_type: limit_type.clone(),
+            scope: scope.clone(),
+            amount,
+        };
+        self.store.upsert_customer_limit(&self.run_id, &rule, tick)?;
+        self.limits
+            .rules
+            .retain(|r| r.limit_type != limit_type || r.scope != scope);
+        self.limits.rules.push(rule);
+        tracing::info!("{limit_type} limit for {scope} set to ${amount:.0}");
+        Ok(SimEvent::CustomerLimitSet {
+            tick,
+            limit_type,
+            scope,
+            amount,
+        })
+    }
+
+    /// Phase 4.12: record an attempt declined by a customer limit.
+    #[allow(clippy::too_many_arguments)]
+    fn decline_for_limit(
+        &self,
+        customer_id: &str,
+        account_id: &str,
+        limit_type: &str,
+        amount: f64,
+        limit_amount: f64,
+        genuine: bool,
+        tick: Tick,
+    ) -> SimResult<SimEvent> {
+        self.store.insert_limit_decline(
+            &self.run_id,
+            &LimitDeclineRow {
+                tick,
+                customer_id: customer_id.to_string(),
+                account_id: account_id.to_string(),
+                limit_type: limit_type.to_string(),
+                amount,
+                limit_amount,
+            },
+        )?;
+        if genuine {
+            self.store.update_customer_satisfaction(
+                &self.run_id,
+                customer_id,
+                self.limits.declined_satisfaction_delta,
+            )?;
+        }
+        Ok(SimEvent::CustomerLimitDeclined {
+            tick,
+            customer_id: customer_id.to_string(),
+            account_id: account_id.to_string(),
+            limit_type: limit_type.to_string(),
+            amount,
+            limit_amount,
+        })
+    }
+
+    /// Phase 4.11: request a link to an external account. Instant
+    /// verification settles the link on the spot and stops few
+    /// fraudsters; micro-deposits take days to confirm, lose some genuine
+    /// customers and stop most fraudsters.
+    fn request_external_link(
+        &self,
+        acct: &AccountRow,
+        purpose: &str,
+        external_ref: String,
+        tick: Tick,
+        rng: &mut SubsystemRng,
+    ) -> SimResult<Vec<SimEvent>> {
+        let fraudulent = purpose == "mule";
+        let micro_deposit = rng.chance(self.verification_strictness);
+        let mut link = ExternalLinkRow {
+            link_id: format!("xlink-{}-{tick}-{purpose}", acct.account_id),
+            account_id: acct.account_id.clone(),
+            customer_id: acct.customer_id.clone(),
+            external_ref,
+            purpose: purpose.to_string(),
+            method: if micro_deposit { "micro_deposit" } else { "instant" }.to_string(),
+            fraudulent,
+            status: "pending".into(),
+            requested_tick: tick,
+            resolved_tick: None,
+        };
+        if micro_deposit {
+            self.store.insert_external_link(&self.run_id, &link)?;
+            return Ok(Vec::new());
+        }
+
+        let passed = !fraudulent || rng.chance(self.links.instant_bypass_rate);
+        link.status = if passed { "verified" } else { "failed" }.into();
+        link.resolved_tick = Some(tick);
+        self.store.insert_external_link(&self.run_id, &link)?;
+        self.link_resolved(&link, tick, rng)
+    }
+
+    /// Phase 4.11: side effects of a link leaving verification.
+    fn link_resolved(
+        &self,
+        link: &ExternalLinkRow,
+        tick: Tick,
+        rng: &mut SubsystemRng,
+    ) -> SimResult<Vec<SimEvent>> {
+        if link.status == "failed" {
+            if !link.fraudulent {
+                self.store.update_customer_satisfaction(
+                    &self.run_id,
+                    &link.customer_id,
+                    self.links.failed_link_satisfaction_delta,
+                )?;
+            }
+            return Ok(vec![SimEvent::ExternalLinkFailed {
+                tick,
+                link_id: link.link_id.clone(),
+                customer_id: link.customer_id.clone(),
+                purpose: link.purpose.clone(),
+                method: link.method.clone(),
+            }]);
+        }
+
+        if link.purpose == "direct_deposit" {
+            let payroll_amount = rng.pareto(900.0, 2.0).min(6_000.0);
+            self.store
+                .switch_direct_deposit(&self.run_id, &link.customer_id, payroll_amount)?;
+        }
+        Ok(vec![SimEvent::ExternalAccountLinked {
+            tick,
+            link_id: link.link_id.clone(),
+            customer_id: link.customer_id.clone(),
+            purpose: link.purpose.clone(),
+            method: link.method.clone(),
+        }])
+    }
+
+    /// Phase 4.11: confirm micro-deposit links whose deposits have landed,
+    /// then open new direct deposit switch and mule links.
+    fn process_external_links(
+        &self,
+        accounts: &[AccountRow],
+        tick: Tick,
+        rng: &mut SubsystemRng,
+    ) -> SimResult<Vec<SimEvent>> {
+        let mut events = Vec::new();
+
+        let requested_by = tick.saturating_sub(self.links.micro_deposit_delay_ticks);
+        for mut link in self.store.pending_external_links(&self.run_id, requested_by)? {
+            let confirmed = if link.fraudulent {
+                rng.chance(self.links.micro_deposit_bypass_rate)
+            } else {
+                rng.chance(self.links.micro_deposit_confirm_rate)
+            };
+            link.status = if confirmed { "verified" } else { "failed" }.into();
+            self.store
+                .resolve_external_link(&self.run_id, &link.link_id, &link.status, tick)?;
+            events.extend(self.link_resolved(&link, tick, rng)?);
+        }
+
+        for acct in accounts {
+            if !acct.has_payroll
+                && rng.chance(self.links.dd_switch_daily_rate)
+                && self
+                    .store
+                    .latest_external_link(&self.run_id, &acct.account_id, "direct_deposit")?
+                    .is_none_or(|l| l.status == "failed")
+            {
+                let employer = format!("employer-{}", acct.customer_id);
+                events.extend(self.request_external_link(acct, "direct_deposit", employer, tick, rng)?);
+            }
+            if rng.chance(self.links.mule_link_daily_rate) {
+                let mule = format!("mule-{}", rng.next_u64_below(100_000));
+                events.extend(self.request_external_link(acct, "mule", mule, tick, rng)?);
+            }
+        }
+        Ok(events)
+    }
+
+    /// Phase 4.10: customer-originated ACH. Customers pay bills to external
+    /// billers and pull funds in from their accounts at other banks; a
+    /// taken-over account fires a burst of unauthorized pulls. Every entry
+    /// counts against the customer's daily velocity limits, and attempts
+    /// over a limit are declined rather than sent to the network.
+    ///
+    /// Phase 4.11: with linking enabled, transfers need a verified link
+    /// (a first transfer requests one instead), and a verified mule link
+    /// drains the account up to the daily amount limit.
+    fn originate_ach(
+        &self,
+        accounts: &[AccountRow],
+        mut limits: Option<&mut DailyLimits>,
+        tick: Tick,
+        rng: &mut SubsystemRng,
+    ) -> SimResult<Vec<SimEvent>> {
+        let mut events = Vec::new();
+        let mut used: HashMap<&str, (u32, f64)> = HashMap::new();
+        let mut seq = 0;
+
+        let mut mule_links = HashMap::new();
+        if self.links.enabled {
+            let since = (tick + 1).saturating_sub(self.links.drain_window_ticks);
+            for link in self.store.draining_mule_links(&self.run_id, since)? {
+                mule_links.insert(link.account_id, link.external_ref);
+            }
+        }
+
+        for acct in accounts {
+            // (entry_type, counterparty, amount, unauthorized)
+            let mut attempts = Vec::new();
+            if let Some(mule) = mule_links.get(&acct.account_id) {
+                let balance = self.store.account_balance(&self.run_id, &acct.account_id)?;
+                if balance > 0.0 {
+                    // Fraudsters probe for the most the limits let through
+                    let transfer_room = limits
+                        .as_deref()
+                        .and_then(|l| l.remaining(&acct.customer_id, &acct.product_id, "daily_transfer"))
+                        .unwrap_or(f64::MAX);
+                    let amount = balance
+                        .min(self.daily_amount_limit)
+                        .min(transfer_room)
+                        .max(0.01);
+                    attempts.push(("external_transfer_out", mule.clone(), amount, true));
+                }
+            }
+            if rng.chance(self.origination.bill_pay_daily_rate) {
+                let biller = format!("biller-{}", rng.next_u64_below(500));
+                attempts.push(("bill_pay", biller, rng.pareto(60.0, 1.5).min(5_000.0), false));
+            }
+            if rng.chance(self.origination.external_transfer_daily_rate) {
+                let amount = rng.pareto(200.0, 1.3).min(10_000.0);
+                if !self.links.enabled {
+                    attempts.push(("external_transfer", format!("ext-{}", acct.customer_id), amount, false));
+                } else {
+                    match self
+                        .store
+                        .latest_external_link(&self.run_id, &acct.account_id, "transfer")?
+                    {
+                        Some(link) if link.status == "verified" => {
+                            attempts.push(("external_transfer", link.external_ref, amount, false));
+                        }
+                        Some(link) if link.status == "pending" => {}
+                        _ => {
+                            let external = format!("ext-{}", acct.customer_id);
+                            events.extend(self.request_external_link(acct, "transfer", external, tick, rng)?);
+                        }
+                    }
+                }
+            }
+            if rng.chance(self.origination.takeover_daily_rate) {
+                for _ in 0..self.origination.takeover_burst_size {
+                    let external = format!("ext-{}", rng.next_u64_below(100_000));
+                    attempts.push(("external_transfer", external, rng.pareto(900.0, 1.5).min(10_000.0), true));
+                }
+            }
+
+            for (entry_type, counterparty, amount, unauthorized) in attempts {
+                let entry_id = format!("oach-{tick}-{seq}");
+                seq += 1;
+
+                let (count, total) = used.entry(acct.customer_id.as_str()).or_default();
+                let mut over_limit = None;
+                let status = if *count >= self.daily_count_limit {
+                    "declined_count"
+                } else if *total + amount > self.daily_amount_limit {
+                    "declined_amount"
+                } else if let Some(Err(limit)) = limits.as_deref_mut().map(|l| {
+                    l.try_use(&acct.customer_id, &acct.product_id, "daily_transfer", amount)
+                }) {
+                    over_limit = Some(limit);
+                    "declined_limit"
+                } else {
+                    *count += 1;
+                    *total += amount;
+                    "submitted"
+                };
+
+                if let Some(limit_amount) = over_limit {
+                    events.push(self.decline_for_limit(
+                        &acct.customer_id,
+                        &acct.account_id,
+                        "daily_transfer",
+                        amount,
+                        limit_amount,
+                        !unauthorized,
+                        tick,
+                    )?);
+                } else if status == "submitted" {
+                    let (direction, delta) = if entry_type == "external_transfer" {
+                        ("credit", amount)
+                    } else {
+                        ("debit", -amount)
+                    };
+                    self.store.insert_transaction(
+                        &self.run_id,
+                        &entry_id,
+                        &acct.account_id,
+                        tick,
+                        amount,
+                        direction,
+                        entry_type,
+                        Some(&counterparty),
+                    )?;
+                    self.store
+                        .update_account_balance(&self.run_id, &acct.account_id, delta)?;
+                } else {
+                    // Only the real customer notices a declined payment
+                    if !unauthorized {
+                        self.store.update_customer_satisfaction(
+                            &self.run_id,
+                            &acct.customer_id,
+                            self.origination.declined_satisfaction_delta,
+                        )?;
+                    }
+                    events.push(SimEvent::AchOriginationDeclined {
+                        tick,
+                        entry_id: entry_id.clone(),
+                        customer_id: acct.customer_id.clone(),
+                        entry_type: entry_type.to_string(),
+                        reason: status.trim_start_matches("declined_").to_string(),
+                        amount,
+                    });
+                }
+
+                self.store.insert_originated_ach(
+                    &self.run_id,
+                    &OriginatedAchRow {
+                        entry_id,
+                        account_id: acct.account_id.clone(),
+                        customer_id: acct.customer_id.clone(),
+                        entry_type: entry_type.to_string(),
+                        counterparty,
+                        amount,
+                        tick,
+                        unauthorized,
+                        status: status.to_string(),
+                        return_code: None,
+                    },
+                )?;
+            }
+        }
+        Ok(events)
+    }
+
+    /// Phase 4.2: enrol a share of accounts as federal benefit recipients.
+    /// Recipients with payroll have it replaced by a monthly benefit of the
+    /// same size.
+    fn enroll_benefit_recipients(&self, tick: Tick, rng: &mut SubsystemRng) -> SimResult<()> {
+        let mut enrolled = 0;
+        for acct in self.store.active_accounts(&self.run_id)? {
+            if !rng.chance(self.protected_funds.beneficiary_share) {
+                continue;
+            }
+            let monthly_amount = if acct.has_payroll && acct.payroll_amount > 0.0 {
+                acct.payroll_amount * 2.0
+            } else {
+                self.protected_funds.default_monthly_benefit
+            };
+            self.store.insert_benefit_recipient(
+                &self.run_id,
+                &BenefitRecipientRow {
+                    account_id: acct.account_id,
+                    customer_id: acct.customer_id,
+                    benefit_type: "social_security".into(),
+                    monthly_amount,
+                },
+                tick,
+            )?;
+            enrolled += 1;
+        }
+        tracing::info!("enrolled {enrolled} federal benefit recipients");
+        Ok(())
     }
 
-    /// Generate transactions for one account for this tick.
+    /// Phase 4.2: check a bank-initiated debit against the protected amount.
+    ///
+    /// Benefits deposited within the lookback window are protected. The bank
+    /// may only collect from the balance above that amount; an overdraft fee
+    /// on a recipient account with protected deposits is never collectible
+    /// because it would be recouped from the next protected deposit. With
+    /// nothing protected the debit is an ordinary one. Returns the amount
+    /// that may actually be debited, plus any violation / blocked events.
+    fn apply_protected_debit(
+        &self,
+        recipient: &BenefitRecipientRow,
+        debit_type: &str,
+        requested: f64,
+        balance: f64,
+        tick: Tick,
+    ) -> SimResult<(f64, Vec<SimEvent>)> {
+        let mut events = Vec::new();
+        let since = tick.saturating_sub(self.protected_funds.lookback_ticks);
+        let protected_amount =
+            self.store
+                .benefit_deposits_since(&self.run_id, &recipient.account_id, since, tick)?;
+
+        // Fees push the balance negative; garnishments only take what exists.
+        let collectible = if debit_type == "overdraft_fee" {
+            requested
+        } else {
+            requested.min(balance.max(0.0))
+        };
+        let allowed = if protected_amount > 0.0 {
+            (balance - protected_amount).max(0.0).min(collectible)
+        } else {
+            collectible
+        };
+        let applied = if self.protected_funds.enforce_protection {
+            allowed
+        } else {
+            collectible
+        };
+        // Only the part of the debit that reached protected funds is a violation
+        let taken_from_protected = (applied - allowed).min(protected_amount).max(0.0);
+        let violation = taken_from_protected > 0.005;
+
+        let outcome = if applied >= requested - 0.005 {
+            "applied"
+        } else if applied <= 0.005 {
+            "blocked"
+        } else {
+            "partial"
+        };
+        self.store.insert_protected_funds_event(
+            &self.run_id,
+            tick,
+            &ProtectedFundsEventRow {
+                account_id: recipient.account_id.clone(),
+                customer_id: recipient.customer_id.clone(),
+                debit_type: debit_type.to_string(),
+                requested_amount: requested,
+                applied_amount: applied,
+                protected_amount,
+                outcome: outcome.to_string(),
+                violation,
+            },
+        )?;
+
+        if violation {
+            self.store.adjust_udaap_score(
+                &self.run_id,
+                self.protected_funds.violation_udaap_impact,
+                tick,
+            )?;
+            tracing::warn!(
+                "{debit_type} of ${taken_from_protected:.2} taken from \
+                 protected funds on {}",
+                recipient.account_id,
+            );
+            events.push(SimEvent::ProtectedFundsViolation {
+                tick,
+                customer_id: recipient.customer_id.clone(),
+                account_id: recipient.account_id.clone(),
+                debit_type: debit_type.to_string(),
+                amount: taken_from_protected,
+            });
+        } else if applied < requested - 0.005 {
+            events.push(SimEvent::ProtectedDebitBlocked {
+                tick,
+                customer_id: recipient.customer_id.clone(),
+                account_id: recipient.account_id.clone(),
+                debit_type: debit_type.to_string(),
+                amount_blocked: requested - applied,
+            });
+        }
+
+        Ok((applied, events))
+    }
+
+    /// Generate transactions for one account for this tick, covering the
+    /// last `days` days: 1 normally, a whole step for a coarse account.
     #[allow(clippy::too_many_arguments)]
     fn process_account(
         &self,
@@ -28,38 +769,67 @@ impl TransactionSubsystem {
         cash_intensity: f64,
         payroll_amount: f64,
         has_payroll: bool,
-        _product_id: &str,
+        product_id: &str,
+        benefit: Option<&BenefitRecipientRow>,
+        mut limits: Option<&mut DailyLimits>,
+        mut channels: Option<&mut ChannelDay>,
+        atms: Option<&AtmDay>,
+        days: Tick,
         tick: Tick,
         rng: &mut SubsystemRng,
     ) -> SimResult<Vec<SimEvent>> {
         let mut events = Vec::new();
 
-        // Payroll credit: biweekly on tick % 14 == 0
-        if has_payroll && tick.is_multiple_of(14) && payroll_amount > 0.0 {
-            let jitter = 1.0 + (rng.next_f64() - 0.5) * 0.05;
-            let amount = payroll_amount * jitter;
-            let txn_id = Uuid::new_v4().to_string();
-            self.store.insert_transaction(
-                &self.run_id,
-                &txn_id,
-                account_id,
-                tick,
-                amount,
-                "credit",
-                "payroll",
-                Some("payroll-employer"),
-            )?;
-            self.store
-                .update_account_balance(&self.run_id, account_id, amount)?;
+        if let Some(recipient) = benefit {
+            // Phase 4.2: federal benefit replaces payroll, paid monthly
+            if tick % 30 == self.protected_funds.benefit_payment_day {
+                let txn_id = Uuid::new_v4().to_string();
+                self.store.insert_transaction(
+                    &self.run_id,
+                    &txn_id,
+                    account_id,
+                    tick,
+                    recipient.monthly_amount,
+                    "credit",
+                    "federal_benefit",
+                    Some("SSA-TREAS-310"),
+                )?;
+                self.store.update_account_balance(
+                    &self.run_id,
+                    account_id,
+                    recipient.monthly_amount,
+                )?;
+            }
+        } else if has_payroll && payroll_amount > 0.0 {
+            // Payroll credit: biweekly on tick % 14 == 0, once for each
+            // payday a coarse step covers
+            for _ in (tick + 1 - days..=tick).filter(|t| t.is_multiple_of(14)) {
+                let jitter = 1.0 + (rng.next_f64() - 0.5) * 0.05;
+                let amount = payroll_amount * jitter;
+                let txn_id = Uuid::new_v4().to_string();
+                self.store.insert_transaction(
+                    &self.run_id,
+                    &txn_id,
+                    account_id,
+                    tick,
+                    amount,
+                    "credit",
+                    "payroll",
+                    Some("payroll-employer"),
+                )?;
+                self.store
+                    .update_account_balance(&self.run_id, account_id, amount)?;
+            }
         }
 
         // Daily transaction probability from monthly mean.
         // monthly_mean / 30 = daily expected count.
         let daily_prob = (monthly_txn_mean / 30.0).min(5.0);
-        // Poisson approximation: floor(daily_prob) certain,
+        let expected = daily_prob * days as f64;
+        // Poisson approximation: floor(expected) certain,
         // remainder is probabilistic.
-        let certain = daily_prob.floor() as u32;
-        let extra = if rng.chance(daily_prob.fract()) { 1 } else { 0 };
+        let certain = expected.floor() as u32;
+        let extra = if rng.chance(expected.fract()) { 1 } else { 0 };
         let txn_count = certain + extra;
 
         for _ in 0..txn_count {
@@ -109,6 +879,58 @@ impl TransactionSubsystem {
                 }
             };
 
+            // Phase 4.37: a visa restriction refuses the debit outright
+            let channel = if is_cash { "cash" } else { rail_id };
+            if self
+                .restrictions
+                .get(customer_id)
+                .is_some_and(|level| visa::blocks(level, channel))
+            {
+                events.push(self.block_restricted(customer_id, account_id, channel, amount, tick)?);
+                continue;
+            }
+
+            // Phase 4.90: cash and payments go through a service channel,
+            // card purchases through the merchant
+            let mut service_channel = None;
+            if let Some(day) = channels.as_deref_mut().filter(|_| rail_id != "card") {
+                match self.channels.route(day, is_cash, rng) {
+                    Ok(routed) => service_channel = routed,
+                    Err(refused) => {
+                        events.push(SimEvent::ChannelTransactionAbandoned {
+                            tick,
+                            customer_id: customer_id.to_string(),
+                            account_id: account_id.to_string(),
+                            channel: refused.as_str().into(),
+                            amount,
+                        });
+                        continue;
+                    }
+                }
+            }
+
+            // Phase 4.12: cash, wires and P2P (RTP) sends count against limits
+            let limit_type = match (is_cash, rail_id) {
+                (true, _) => Some("cash_withdrawal"),
+                (false, "wire") => Some("wire"),
+                (false, "RTP") => Some("p2p_send"),
+                _ => None,
+            };
+            if let (Some(limit_type), Some(limits)) = (limit_type, limits.as_deref_mut()) {
+                if let Err(limit_amount) = limits.try_use(customer_id, product_id, limit_type, amount) {
+                    events.push(self.decline_for_limit(
+                        customer_id,
+                        account_id,
+                        limit_type,
+                        amount,
+                        limit_amount,
+                        true,
+                        tick,
+                    )?);
+                    continue;
+                }
+            }
+
             self.store.insert_transaction_with_rail(
                 &self.run_id,
                 &txn_id,
@@ -121,6 +943,15 @@ impl TransactionSubsystem {
                 rail_id,
                 settlement_status,
             )?;
+            if let Some(channel) = service_channel {
+                self.store.set_transaction_channel(&self.run_id, &txn_id, channel.as_str())?;
+            }
+            // Phase 4.92: at which ATM
+            if let Some(day) = atms.filter(|_| service_channel == Some(Channel::Atm)) {
+                if let Some(atm_id) = self.atm_network.pick(day, customer_id, rng) {
+                    self.store.set_transaction_atm(&self.run_id, &txn_id, atm_id)?;
+                }
+            }
             // For non-card rails, update balance immediately (already settled)
             // For card rails, only available_balance is affected (handled by PaymentHub)
             if rail_id != "card" {
@@ -130,48 +961,134 @@ impl TransactionSubsystem {
             // Card transactions: PaymentHubSubsystem will handle auth hold on available_balance
         }
 
+        // Phase 4.2: garnishment orders and setoffs against recipient accounts
+        if let Some(recipient) = benefit {
+            if rng.chance(self.protected_funds.garnishment_daily_rate) {
+                let requested = rng.pareto(250.0, 1.5).min(5_000.0);
+                let (debit_type, counterparty) = if rng.chance(0.30) {
+                    ("setoff", "bank-setoff")
+                } else {
+                    ("garnishment", "creditor-levy")
+                };
+                // Phase 4.30: the bank holds off its own setoffs during hardship
+                let suspended = debit_type == "setoff"
+                    && self.hardship.suspend_setoffs
+                    && self.hardship_customers.contains(customer_id);
+                if !suspended {
+                    let balance = self.store.account_balance(&self.run_id, account_id)?;
+                    let (applied, check_events) =
+                        self.apply_protected_debit(recipient, debit_type, requested, balance, tick)?;
+                    events.extend(check_events);
+                    if applied > 0.0 {
+                        let txn_id = Uuid::new_v4().to_string();
+                        self.store.insert_transaction(
+                            &self.run_id,
+                            &txn_id,
+                            account_id,
+                            tick,
+                            applied,
+                            "debit",
+                            debit_type,
+                            Some(counterparty),
+                        )?;
+                        self.store
+                            .update_account_balance(&self.run_id, account_id, -applied)?;
+                    }
+                }
+            }
+        }
+
         // Overdraft check: if balance < 0 after debits
         let balance = self.store.account_balance(&self.run_id, account_id)?;
         if balance < -0.01 {
-            let od_fee = 27.08;
-            let fee_id = Uuid::new_v4().to_string();
-            self.store.insert_transaction(
-                &self.run_id,
-                &fee_id,
-                account_id,
-                tick,
-                od_fee,
-                "debit",
-                "overdraft_fee",
-                None,
-            )?;
-            self.store
-                .update_account_balance(&self.run_id, account_id, -od_fee)?;
-            events.push(SimEvent::FeeCharged {
-                tick,
-                customer_id: customer_id.to_string(),
-                account_id: account_id.to_string(),
-                fee_type: "overdraft".to_string(),
-                amount: od_fee,
-            });
+            let mut od_fee = 27.08;
+            // Phase 4.30: waived for hardship program customers
+            if self.hardship_waives("overdraft", customer_id) {
+                self.store.insert_hardship_waiver(&self.run_id, &HardshipWaiverRow {
+                    tick,
+                    customer_id: customer_id.to_string(),
+                    account_id: account_id.to_string(),
+                    fee_type: "overdraft".into(),
+                    amount: od_fee,
+                })?;
+                od_fee = 0.0;
+            }
+            // Phase 4.2: recipient accounts go through the protected-funds check
+            if let Some(recipient) = benefit.filter(|_| od_fee > 0.0) {
+                let (applied, check_events) =
+                    self.apply_protected_debit(recipient, "overdraft_fee", od_fee, balance, tick)?;
+                events.extend(check_events);
+                od_fee = applied;
+            }
+            if od_fee > 0.0 {
+                let fee_id = Uuid::new_v4().to_string();
+                self.store.insert_transaction(
+                    &self.run_id,
+                    &fee_id,
+                    account_id,
+                    tick,
+                    od_fee,
+                    "debit",
+                    "overdraft_fee",
+                    None,
+                )?;
+                self.store
+                    .update_account_balance(&self.run_id, account_id, -od_fee)?;
+                events.push(SimEvent::FeeCharged {
+                    tick,
+                    customer_id: customer_id.to_string(),
+                    account_id: account_id.to_string(),
+                    fee_type: "overdraft".to_string(),
+                    amount: od_fee,
+                });
+            }
         }
 
         Ok(events)
     }
 }
 
+const MANIFEST: SubsystemManifest = SubsystemManifest {
+    version: 2,
+    produces: &[
+        "ach_origination_declined", "ach_origination_limits_changed", "app_scam_payments_booked",
+        "account_takeover", "atm_back_in_service", "atm_cash_out", "atm_out_of_service", "channel_transaction_abandoned", "check_items_booked",
+        "cross_border_transfers_booked", "customer_limit_declined", "customer_limit_rejected",
+        "customer_limit_set", "deposit_hold_policy_rejected", "deposit_hold_policy_set", "device_sessions_logged",
+        "external_account_linked", "external_link_failed", "fee_charged", "funds_held", "invoice_wires_booked",
+        "link_verification_strictness_changed", "mule_network_dissolved", "mule_network_seeded",
+        "mule_transfers_booked", "protected_debit_blocked",
+        "protected_funds_violation", "remittance_corridor_opened", "remittances_sent",
+        "restricted_transaction_blocked",
+    ],
+    consumes: &["player_command_received"],
+    tables: &[
+        "account", "app_scam_payment", "atm", "atm_cash_out", "benefit_recipient", "channel_activity", "check_item", "cross_border_profile", "cross_border_transfer", "customer",
+        "customer_device", "customer_limit", "customer_limit_decline", "daily_aggregate", "deposit_hold",
+        "deposit_hold_policy", "device_session",
+        "customer_relationship", "external_account_link",
+        "hardship_fee_waiver", "incident", "invoice_wire", "mule_network", "mule_network_member", "mule_transfer", "originated_ach", "protected_funds_event", "regulatory_score",
+        "remittance_profile", "remittance_transfer", "structuring_deposit", "structuring_label", "transactions",
+    ],
+    requires: &[],
+};
+
 impl SimSubsystem for TransactionSubsystem {
     fn name(&self) -> &'static str {
         "transaction"
     }
 
+    fn manifest(&self) -> SubsystemManifest {
+        MANIFEST
+    }
+
     fn update(
         &mut self,
         tick: Tick,
-        _events_in: &[SimEvent],
+        events_in: &[SimEvent],
         rng: &mut SubsystemRng,
     ) -> SimResult<Vec<SimEvent>> {
-        let mut out_events = Vec::new();
+        let mut out_events = self.apply_player_commands(tick, events_in)?;
 
         // Skip tick 0 — customers are being onboarded this tick,
         // accounts aren't written yet when transaction runs.
@@ -179,9 +1096,80 @@ impl SimSubsystem for TransactionSubsystem {
             return Ok(out_events);
         }
 
+        // Phase 4.2: benefit recipients (enrolled on the first live tick)
+        let mut recipients: HashMap<String, BenefitRecipientRow> = HashMap::new();
+        if self.protected_funds.enabled {
+            if tick == 1 {
+                self.enroll_benefit_recipients(tick, rng)?;
+            }
+            for r in self.store.benefit_recipients(&self.run_id)? {
+                recipients.insert(r.account_id.clone(), r);
+            }
+        }
+
         let accounts = self.store.active_accounts(&self.run_id)?;
 
-        for acct in accounts {
+        // Phase 4.30: hardship program enrollments in force today
+        if self.hardship.enabled {
+            self.hardship_customers = self.store.hardship_customers(&self.run_id, tick)?;
+        }
+
+        // Phase 4.37: visa restrictions in force today
+        if self.visa.enabled {
+            self.restrictions = self.store.visa_restrictions(&self.run_id)?;
+        }
+
+        // Phase 4.12: customer limits, tracked per customer for the day
+        let mut limits = if self.limits.enabled {
+            let tiers = self.store.customer_risk_tiers(&self.run_id)?;
+            Some(DailyLimits::new(self.limits.rules.clone(), tiers))
+        } else {
+            None
+        };
+
+        // Phase 4.90: channels as today's incidents leave them
+        let mut channel_day = if self.channels.enabled() {
+            Some(self.channels.open_day(&self.store, tick)?)
+        } else {
+            None
+        };
+
+        // Phase 4.92: the ATMs in service today; with none the ATM
+        // channel is down
+        let atm_day = match channel_day.as_mut().filter(|_| self.atm_network.enabled()) {
+            Some(day) => {
+                let (atms, events) = self.atm_network.open_day(&self.store, tick, rng)?;
+                out_events.extend(events);
+                if atms.in_service().is_empty() {
+                    day.take_down(Channel::Atm);
+                }
+                Some(atms)
+            }
+            None => None,
+        };
+
+        // Phase 4.25: coarse accounts wait for a step tick, then catch up
+        let coarse = self.coarse_customers(tick, &accounts, &recipients)?;
+        if !coarse.is_empty() && tick == self.acceleration.coarse_after_tick + 1 {
+            tracing::info!(
+                "time acceleration: {} customers now step every {} ticks",
+                coarse.len(),
+                self.acceleration.coarse_step_ticks
+            );
+        }
+
+        for acct in &accounts {
+            let days = if self.acceleration.enabled {
+                if coarse.contains(&acct.customer_id) && !self.acceleration.is_step_tick(tick) {
+                    continue;
+                }
+                let last = self.last_processed.insert(acct.account_id.clone(), tick);
+                tick - last.unwrap_or(tick - 1)
+            } else {
+                1
+            };
+            // Limits are per day; a multi-day step is not checked against them
+            let limits = if days == 1 { limits.as_mut() } else { None };
             let events = self.process_account(
                 &acct.account_id,
                 &acct.customer_id,
@@ -190,18 +1178,99 @@ impl SimSubsystem for TransactionSubsystem {
                 acct.payroll_amount,
                 acct.has_payroll,
                 &acct.product_id,
+                recipients.get(&acct.account_id),
+                limits,
+                channel_day.as_mut(),
+                atm_day.as_ref(),
+                days,
                 tick,
                 rng,
             )?;
             out_events.extend(events);
         }
 
+        // Phase 4.92: cash-out crews at the ATMs
+        if let Some(atms) = &atm_day {
+            let cards = self.unrestricted(&accounts, "cash");
+            out_events.extend(self.atm_network.cash_out(&self.store, atms, channel_day.as_mut(), &cards, tick, rng)?);
+        }
+
+        // Phase 4.94: logins, the devices digital payments came from, and
+        // account takeovers
+        if self.devices.enabled() {
+            out_events.extend(self.devices.log_sessions(&self.store, &accounts, channel_day.as_mut(), tick, rng)?);
+        }
+
+        if let Some(day) = channel_day {
+            self.channels.close_day(&self.store, day)?;
+        }
+
+        // Phase 4.11: external account links
+        if self.links.enabled {
+            out_events.extend(self.process_external_links(&accounts, tick, rng)?);
+        }
+
+        // Phase 4.36: remittance corridors
+        if self.remittance.enabled() {
+            let senders = self.unrestricted(&accounts, "remittance");
+            out_events.extend(self.remittance.process(&self.store, &senders, tick, rng)?);
+        }
+
+        // Phase 4.40: cross-border funnels and support wires
+        if self.cross_border.enabled() {
+            let movers = self.unrestricted(&accounts, "RTP");
+            out_events.extend(self.cross_border.process(&self.store, &movers, tick, rng)?);
+        }
+
+        // Phase 4.53: invoices paid by wire, spoofed or genuine
+        if self.bec.enabled() {
+            let payers = self.unrestricted(&accounts, "wire");
+            out_events.extend(self.bec.book_invoices(&self.store, &payers, tick, rng)?);
+        }
+
+        // Phase 4.91: scam payments pushed out on instant rails
+        if self.app_fraud.enabled() {
+            let victims = self.unrestricted(&accounts, "RTP");
+            out_events.extend(self.app_fraud.book_scams(&self.store, &victims, tick, rng)?);
+        }
+
+        // Phase 4.54: checks cleared and deposited, washed or counterfeit
+        if self.check_fraud.enabled() {
+            let writers = self.unrestricted(&accounts, "check");
+            out_events.extend(self.check_fraud.book_checks(&self.store, &writers, tick, rng)?);
+        }
+
+        // Phase 4.55: release held deposit funds that are due, then hold
+        // today's deposits
+        if self.deposit_holds.enabled() {
+            self.deposit_holds.release(&self.store, tick)?;
+            out_events.extend(self.deposit_holds.place(&self.store, tick)?);
+        }
+
+        // Phase 4.87: mule networks
+        if self.mule_networks.enabled() {
+            let movers = self.unrestricted(&accounts, "RTP");
+            out_events.extend(self.mule_networks.process(&self.store, &movers, tick, rng)?);
+        }
+
+        // Phase 4.88: structured cash deposits
+        if self.structuring.enabled() {
+            let depositors = self.unrestricted(&accounts, "cash");
+            self.structuring.process(&self.store, &depositors, tick, rng)?;
+        }
+
+        // Phase 4.10: customer-originated ACH
+        if self.origination.enabled {
+            let originators = self.unrestricted(&accounts, "ach_origination");
+            out_events.extend(self.originate_ach(&originators, limits.as_mut(), tick, rng)?);
+        }
+
         // Write daily aggregate
         let agg = self.store.compute_daily_aggregate(&self.run_id, tick)?;
         self.store.save_daily_aggregate(&self.run_id, tick, &agg)?;
 
-        log::debug!(
-            "tick={tick} txn: {} txns, vol=${:.0}, fees=${:.2}",
+        tracing::debug!(
+            "{} txns, vol=${:.0}, fees=${:.2}",
             agg.txn_count,
             agg.txn_volume,
             agg.fee_income
@@ -210,6 +1279,57 @@ impl SimSubsystem for TransactionSubsystem {
         Ok(out_events)
     }
 
+    fn save_state(&self) -> SimResult<Option<serde_json::Value>> {
+        let last_processed: BTreeMap<_, _> = self.last_processed.iter().collect();
+        Ok(Some(serde_json::json!({
+            "daily_count_limit": self.daily_count_limit,
+            "daily_amount_limit": self.daily_amount_limit,
+            "verification_strictness": self.verification_strictness,
+            "customer_limits": self.limits.rules,
+            "last_processed": last_processed,
+        })))
+    }
+
+    fn load_state(&mut self, state: serde_json::Value) -> SimResult<()> {
+        self.daily_count_limit = serde_json::from_value(state["daily_count_limit"].clone())?;
+        self.daily_amount_limit = serde_json::from_value(state["daily_amount_limit"].clone())?;
+        self.verification_strictness = serde_json::from_value(state["verification_strictness"].clone())?;
+        self.limits.rules = serde_json::from_value(state["customer_limits"].clone())?;
+        self.last_processed = serde_json::from_value(state["last_processed"].clone())?;
+        Ok(())
+    }
+
+    fn replay(&mut self, tick: Tick, events: &[SimEvent]) -> SimResult<()> {
+        for event in events {
+            match event {
+                SimEvent::AchOriginationLimitsChanged { daily_count, daily_amount, .. } => {
+                    self.daily_count_limit = *daily_count;
+                    self.daily_amount_limit = *daily_amount;
+                }
+                SimEvent::LinkVerificationStrictnessChanged { strictness, .. } => {
+                    self.verification_strictness = *strictness;
+                }
+                SimEvent::CustomerLimitSet { limit_type, scope, amount, .. } => {
+                    self.limits.rules.retain(|r| r.limit_type != *limit_type || r.scope != *scope);
+                    self.limits.rules.push(CustomerLimitRule {
+                        limit_type: limit_type.clone(),
+                        scope: scope.clone(),
+                        amount: *amount,
+                    });
+                }
+                _ => {}
+            }
+        }
+        // Which accounts were coarse that tick is not logged; counting
+        // them all as stepped means a coarse account resumed between step
+        // ticks skips the days since its last step rather than playing
+        // any day twice.
+        for last in self.last_processed.values_mut() {
+            *last = tick;
+        }
+        Ok(())
+    }
+
     fn as_any(&self) -> &dyn std::any::Any {
         self
     }
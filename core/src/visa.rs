@@ -0,0 +1,234 @@
+//! Visa expiry and documentation refresh (Phase 4.37).
+//!
+//! Every visa holder gets an expiry inside their visa type's validity
+//! period. `refresh_lead_ticks` before it, the bank opens a documentation
+//! refresh work item. Customers whose visa is renewed send the new
+//! documents at some point between the request and a little after expiry;
+//! the bank verifies them `processing_ticks` later and the expiry rolls
+//! forward. The rest never send anything.
+//!
+//! Documentation past its expiry without verified renewal is lapsed. Once
+//! it has been lapsed for the policy's grace period the customer is
+//! restricted: "outbound" blocks wires, instant payments, originated ACH
+//! and remittances; "full" blocks every debit. Restrictions cost
+//! satisfaction when they land and on each blocked transaction, and draw
+//! complaints — most of all on customers whose documents are sitting in
+//! the bank's own verification queue. A lax policy leaves lapsed
+//! customers transacting freely.
+
+use crate::{
+    config::VisaExpiryConfig,
+    error::{SimError, SimResult},
+    event::SimEvent,
+    rng::SubsystemRng,
+    store::{
+        visa::{VisaDocumentRow, VisaRefreshItemRow, VisaRestrictionPolicy},
+        SimStore,
+    },
+    types::{RunId, Tick},
+};
+
+/// Restriction levels, least restrictive first.
+pub const LEVELS: &[&str] = &["none", "outbound", "full"];
+
+/// Whether a restriction at `level` blocks a debit on `channel`: a rail
+/// ("card", "ACH", "wire", "RTP"), "cash", "ach_origination" or
+/// "remittance".
+pub fn blocks(level: &str, channel: &str) -> bool {
+    match level {
+        "full" => true,
+        "outbound" => matches!(channel, "wire" | "RTP" | "ach_origination" | "remittance"),
+        _ => false,
+    }
+}
+
+pub struct VisaLifecycle {
+    run_id: RunId,
+    config: VisaExpiryConfig,
+}
+
+impl VisaLifecycle {
+    pub fn new(run_id: RunId, config: VisaExpiryConfig) -> Self {
+        Self { run_id, config }
+    }
+
+    pub fn enabled(&self) -> bool {
+        self.config.enabled
+    }
+
+    /// The policy in force: the player's latest, or the configured one.
+    pub fn policy(&self, store: &SimStore) -> SimResult<VisaRestrictionPolicy> {
+        Ok(store.visa_restriction_policy(&self.run_id)?.unwrap_or_else(|| VisaRestrictionPolicy {
+            level:       self.config.restriction_level.clone(),
+            grace_ticks: self.config.grace_ticks,
+        }))
+    }
+
+    pub fn set_policy(&self, store: &SimStore, policy: VisaRestrictionPolicy, tick: Tick) -> SimResult<SimEvent> {
+        if !LEVELS.contains(&policy.level.as_str()) {
+            tracing::warn!("rejected visa restriction policy {policy:?}");
+            return Ok(SimEvent::VisaRestrictionPolicyRejected {
+                tick,
+                reason: format!("unknown restriction level '{}'", policy.level),
+            });
+        }
+        store.insert_visa_restriction_policy(&self.run_id, tick, &policy)?;
+        tracing::info!("visa restriction policy set: {policy:?}");
+        Ok(SimEvent::VisaRestrictionPolicySet {
+            tick,
+            level: policy.level,
+            grace_ticks: policy.grace_ticks,
+        })
+    }
+
+    fn validity(&self, visa_status: &str) -> Tick {
+        self.config
+            .validity
+            .iter()
+            .find(|v| v.visa_status == visa_status)
+            .map_or(365, |v| v.validity_ticks)
+    }
+
+    /// Track new visa holders, then move every timeline on a tick:
+    /// refresh requests, submissions, verification, lapses and the
+    /// restrictions the policy puts on them.
+    pub fn process(&self, store: &SimStore, tick: Tick, rng: &mut SubsystemRng) -> SimResult<Vec<SimEvent>> {
+        let mut events = Vec::new();
+
+        for (customer_id, visa_status) in store.untracked_visa_holders(&self.run_id)? {
+            let remaining = rng.next_u64_below(self.validity(&visa_status));
+            store.insert_visa_document(&self.run_id, &VisaDocumentRow {
+                customer_id,
+                visa_status,
+                expiry_tick: tick + 1 + remaining,
+                renews: rng.chance(self.config.renewal_rate),
+                planned_submit_tick: None,
+                status: "valid".into(),
+                submitted_tick: None,
+                lapsed_tick: None,
+                restriction: None,
+                restricted_tick: None,
+            })?;
+        }
+
+        let policy = self.policy(store)?;
+        for before in store.visa_documents(&self.run_id)? {
+            let mut doc = before.clone();
+
+            if doc.status == "valid" && tick + self.config.refresh_lead_ticks >= doc.expiry_tick {
+                let item_id = format!("visa-{}-{tick}", doc.customer_id);
+                store.insert_visa_refresh_item(&self.run_id, &VisaRefreshItemRow {
+                    item_id: item_id.clone(),
+                    customer_id: doc.customer_id.clone(),
+                    opened_tick: tick,
+                    expiry_tick: doc.expiry_tick,
+                    submitted_tick: None,
+                    completed_tick: None,
+                    cost: 0.0,
+                })?;
+                doc.status = "refresh_requested".into();
+                if doc.renews {
+                    let window = (doc.expiry_tick + self.config.late_submission_ticks).saturating_sub(tick);
+                    doc.planned_submit_tick = Some(tick + rng.next_u64_below(window + 1));
+                }
+                events.push(SimEvent::VisaRefreshRequested {
+                    tick,
+                    customer_id: doc.customer_id.clone(),
+                    item_id,
+                    expiry_tick: doc.expiry_tick,
+                });
+            }
+
+            if doc.status == "refresh_requested" && doc.planned_submit_tick.is_some_and(|t| t <= tick) {
+                doc.status = "submitted".into();
+                doc.submitted_tick = Some(tick);
+                store.submit_visa_refresh_item(&self.run_id, &doc.customer_id, tick)?;
+            }
+
+            if doc.status == "submitted"
+                && doc.submitted_tick.is_some_and(|t| t + self.config.processing_ticks <= tick)
+            {
+                store.complete_visa_refresh_item(&self.run_id, &doc.customer_id, self.config.refresh_cost, tick)?;
+                doc.expiry_tick = tick + self.validity(&doc.visa_status);
+                doc.status = "valid".into();
+                doc.planned_submit_tick = None;
+                doc.submitted_tick = None;
+                doc.lapsed_tick = None;
+                events.push(SimEvent::VisaDocumentsRenewed {
+                    tick,
+                    customer_id: doc.customer_id.clone(),
+                    expiry_tick: doc.expiry_tick,
+                });
+            }
+
+            if doc.status != "valid" && tick > doc.expiry_tick && doc.lapsed_tick.is_none() {
+                doc.lapsed_tick = Some(tick);
+                events.push(SimEvent::VisaDocumentationLapsed {
+                    tick,
+                    customer_id: doc.customer_id.clone(),
+                    documents_pending: doc.status == "submitted",
+                });
+            }
+
+            let target = doc
+                .lapsed_tick
+                .filter(|lapsed| policy.level != "none" && tick >= lapsed + policy.grace_ticks)
+                .map(|_| policy.level.clone());
+            if target != doc.restriction {
+                events.extend(self.restrict(store, &mut doc, target, tick)?);
+            }
+
+            if doc != before {
+                store.update_visa_document(&self.run_id, &doc)?;
+            }
+        }
+        Ok(events)
+    }
+
+    /// Move a customer to the `target` restriction, or lift theirs. A
+    /// customer with no open account left is not restricted.
+    fn restrict(
+        &self,
+        store: &SimStore,
+        doc: &mut VisaDocumentRow,
+        target: Option<String>,
+        tick: Tick,
+    ) -> SimResult<Option<SimEvent>> {
+        let Some(level) = target else {
+            doc.restriction = None;
+            doc.restricted_tick = None;
+            return Ok(Some(SimEvent::AccountRestrictionLifted {
+                tick,
+                customer_id: doc.customer_id.clone(),
+                reason: if doc.lapsed_tick.is_none() { "renewed" } else { "policy" }.into(),
+            }));
+        };
+        let account_id = match store.customer_primary_account(&self.run_id, &doc.customer_id) {
+            Ok(account_id) => account_id,
+            Err(SimError::Store(rusqlite::Error::QueryReturnedNoRows)) => return Ok(None),
+            Err(e) => return Err(e),
+        };
+
+        let documents_pending = doc.status == "submitted";
+        let mut delta = if level == "full" {
+            self.config.full_satisfaction_delta
+        } else {
+            self.config.outbound_satisfaction_delta
+        };
+        if documents_pending {
+            delta += self.config.pending_satisfaction_delta;
+        }
+        store.update_customer_satisfaction(&self.run_id, &doc.customer_id, delta)?;
+
+        doc.restriction = Some(level.clone());
+        doc.restricted_tick = Some(tick);
+        tracing::debug!("visa restriction: {} now {level}", doc.customer_id);
+        Ok(Some(SimEvent::AccountRestricted {
+            tick,
+            customer_id: doc.customer_id.clone(),
+            account_id,
+            level,
+            documents_pending,
+        }))
+    }
+}
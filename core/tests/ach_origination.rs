@@ -0,0 +1,124 @@
+//! Customer-originated ACH tests — Phase 4.10.
+//!
+//! Tests cover: bill pay and external transfers booked as ACH entries,
+//! player-set velocity limits declining over-limit attempts, takeover
+//! entries returned R10, and ODFI return-rate reviews that put the bank
+//! under network scrutiny when unauthorized returns exceed the threshold.
+
+use fincrime_core::command::PlayerCommand;
+use fincrime_core::engine::SimEngine;
+use std::collections::HashMap;
+
+/// Originated entries are booked as settled ACH transactions in the
+/// direction their entry type implies.
+#[test]
+fn originated_entries_are_booked() {
+    let run_id = "ach-origination-booked-test";
+    let mut engine = SimEngine::build_test_with_ach_origination(run_id.to_string(), 0x0DF1_0001)
+        .expect("build test engine with ACH origination");
+
+    engine.run_ticks(10).unwrap();
+
+    let entries = engine.store_originated_ach_entries(run_id).unwrap();
+    let submitted: Vec<_> = entries.iter().filter(|e| e.status == "submitted").collect();
+    assert!(submitted.iter().any(|e| e.entry_type == "bill_pay"));
+    assert!(submitted.iter().any(|e| e.entry_type == "external_transfer"));
+    for entry in &submitted {
+        assert!(!entry.unauthorized, "No takeovers at the default test rate");
+        assert_eq!(
+            engine.store.transaction_category(run_id, &entry.entry_id).unwrap(),
+            entry.entry_type
+        );
+    }
+    assert!(
+        engine.store_odfi_return_reviews(run_id).unwrap().is_empty(),
+        "No review before the first review interval"
+    );
+}
+
+/// Tightened limits decline attempts over the per-customer daily count
+/// and amount, and no customer exceeds them afterwards.
+#[test]
+fn velocity_limits_decline_over_limit_attempts() {
+    let run_id = "ach-origination-limits-test";
+    let mut engine = SimEngine::build_test_with_ach_origination(run_id.to_string(), 0x0DF1_0002)
+        .expect("build test engine with ACH origination");
+
+    engine
+        .submit_command(PlayerCommand::SetAchOriginationLimits { daily_count: 1, daily_amount: 150.0 })
+        .unwrap();
+    engine.run_ticks(20).unwrap();
+
+    let entries = engine.store_originated_ach_entries(run_id).unwrap();
+    assert!(entries.iter().any(|e| e.status == "declined_count"));
+    assert!(entries.iter().any(|e| e.status == "declined_amount"));
+
+    let mut per_day: HashMap<(&str, u64), (u32, f64)> = HashMap::new();
+    for entry in entries.iter().filter(|e| e.status == "submitted" && e.tick > 1) {
+        let used = per_day.entry((entry.customer_id.as_str(), entry.tick)).or_default();
+        used.0 += 1;
+        used.1 += entry.amount;
+    }
+    for ((customer_id, tick), (count, amount)) in per_day {
+        assert!(count <= 1, "{customer_id} originated {count} entries on tick {tick}");
+        assert!(amount <= 150.0, "{customer_id} originated ${amount:.2} on tick {tick}");
+    }
+}
+
+/// Takeover entries that get through the limits always come back R10 and
+/// are reversed.
+#[test]
+fn takeover_entries_return_unauthorized() {
+    let run_id = "ach-origination-takeover-test";
+    let mut engine = SimEngine::build_test_with_ach_takeovers(run_id.to_string(), 0x0DF1_0003)
+        .expect("build test engine with ACH takeovers");
+
+    engine.run_ticks(20).unwrap();
+
+    let takeovers: Vec<_> = engine
+        .store_originated_ach_entries(run_id)
+        .unwrap()
+        .into_iter()
+        .filter(|e| e.unauthorized && e.status != "declined_count" && e.status != "declined_amount")
+        .collect();
+    assert!(!takeovers.is_empty());
+    for entry in takeovers.iter().filter(|e| e.tick + 2 <= 20) {
+        assert_eq!(entry.status, "returned");
+        assert_eq!(entry.return_code.as_deref(), Some("R10"));
+        assert_eq!(
+            engine
+                .store_transaction_settlement_status(run_id, &format!("{}-return", entry.entry_id))
+                .unwrap(),
+            "settled",
+            "Each return books a settled reversal"
+        );
+    }
+}
+
+/// Unauthorized returns past 0.5% of originated debits breach the ODFI
+/// threshold and draw a network fine; a clean book passes review.
+#[test]
+fn unauthorized_return_breach_triggers_scrutiny() {
+    let run_id = "ach-origination-breach-test";
+    let mut engine = SimEngine::build_test_with_ach_takeovers(run_id.to_string(), 0x0DF1_0004)
+        .expect("build test engine with ACH takeovers");
+    engine.run_ticks(30).unwrap();
+
+    let reviews = engine.store_odfi_return_reviews(run_id).unwrap();
+    assert_eq!(reviews.len(), 1);
+    let review = &reviews[0];
+    assert!(review.unauthorized_rate > 0.005);
+    assert!(review.breached.as_deref().unwrap_or_default().contains("unauthorized"));
+    assert_eq!(review.network_fine, 25_000.0);
+
+    let clean_id = "ach-origination-clean-test";
+    let mut clean = SimEngine::build_test_with_ach_origination(clean_id.to_string(), 0x0DF1_0004)
+        .expect("build test engine with ACH origination");
+    clean.run_ticks(30).unwrap();
+
+    let reviews = clean.store_odfi_return_reviews(clean_id).unwrap();
+    assert_eq!(reviews.len(), 1);
+    assert!(reviews[0].unauthorized_rate <= 0.005, "{}", reviews[0].unauthorized_rate);
+    assert_eq!(reviews[0].breached, None);
+    assert_eq!(reviews[0].network_fine, 0.0);
+}
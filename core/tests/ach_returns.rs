@@ -0,0 +1,157 @@
+//! ACH return code tests — Phase 4.9.
+//!
+//! Tests cover: returns carrying taxonomy codes with matching handling and
+//! booked reversals, re-presentment of R01/R09, customer contact for
+//! account problems, disputes from unauthorized returns, closure of
+//! originator relationships.
+
+use fincrime_core::engine::SimEngine;
+
+fn build_with_returns(run_id: &str, seed: u64) -> SimEngine {
+    SimEngine::build_test_with_ach_returns(run_id.to_string(), seed)
+        .expect("build test engine with ACH returns")
+}
+
+fn expected_handling(code: &str) -> &'static str {
+    match code {
+        "R01" | "R09" => "represent",
+        "R02" | "R03" | "R04" | "R08" => "customer_contact",
+        "R05" | "R10" | "R29" => "dispute",
+        "R07" | "R16" => "close_origination",
+        other => panic!("Unexpected return code {other}"),
+    }
+}
+
+/// Every return carries a taxonomy code, arrives after the return delay,
+/// and books a reversal of the original item.
+#[test]
+fn returns_carry_taxonomy_codes() {
+    let run_id = "ach-return-codes-test";
+    let mut engine = build_with_returns(run_id, 0xAC4E_0001);
+
+    engine.run_ticks(30).unwrap();
+
+    let returns = engine.store_ach_returns(run_id).unwrap();
+    assert!(!returns.is_empty(), "Expected ACH returns at the test return rate");
+    for ret in &returns {
+        assert_eq!(ret.handling, expected_handling(&ret.return_code));
+        assert!(ret.tick_returned >= 3, "Returns arrive two ticks after initiation");
+        assert_eq!(
+            engine.store_transaction_settlement_status(run_id, &ret.txn_id).unwrap(),
+            "returned"
+        );
+        assert_eq!(
+            engine
+                .store_transaction_settlement_status(run_id, &format!("{}-reversal", ret.return_id))
+                .unwrap(),
+            "settled",
+            "Each return books a settled reversal transaction"
+        );
+    }
+    let codes: std::collections::HashSet<_> = returns.iter().map(|r| &r.return_code).collect();
+    assert!(codes.len() > 3, "Expected a spread of return codes, got {codes:?}");
+}
+
+/// R01/R09 returns are re-presented once and end collected or final.
+#[test]
+fn insufficient_funds_returns_are_represented() {
+    let run_id = "ach-return-represent-test";
+    let mut engine = build_with_returns(run_id, 0xAC4E_0002);
+
+    engine.run_ticks(30).unwrap();
+
+    let represent: Vec<_> = engine
+        .store_ach_returns(run_id)
+        .unwrap()
+        .into_iter()
+        .filter(|r| r.handling == "represent")
+        .collect();
+    assert!(!represent.is_empty());
+    for ret in &represent {
+        assert_eq!(ret.represent_tick, Some(ret.tick_returned + 2));
+        let expected: &[&str] = if ret.tick_returned + 2 <= 30 {
+            &["represented", "final_return"]
+        } else {
+            &["pending_represent"]
+        };
+        assert!(expected.contains(&ret.status.as_str()), "Unexpected status {}", ret.status);
+    }
+}
+
+/// Account-problem returns log a contact; unauthorized returns open an ACH
+/// dispute that resolves after the originator's review window.
+#[test]
+fn contact_and_dispute_handling() {
+    let run_id = "ach-return-handling-test";
+    let mut engine = build_with_returns(run_id, 0xAC4E_0003);
+
+    engine.run_ticks(30).unwrap();
+
+    let returns = engine.store_ach_returns(run_id).unwrap();
+    let contacts = returns.iter().filter(|r| r.handling == "customer_contact").count();
+    let disputes = returns.iter().filter(|r| r.handling == "dispute").count();
+    assert!(contacts > 0 && disputes > 0);
+
+    assert!(returns
+        .iter()
+        .filter(|r| r.handling == "customer_contact")
+        .all(|r| r.status == "contact_logged"));
+    assert_eq!(
+        engine.store_interaction_count_by_type(run_id, "ach_return_contact").unwrap(),
+        contacts as i64
+    );
+    assert_eq!(
+        engine
+            .store
+            .complaint_count_by_category(run_id, "unauthorized_transaction", 0, 30)
+            .unwrap(),
+        disputes as i64,
+        "Every unauthorized return opens a dispute complaint in the test config"
+    );
+
+    let records = engine.store_ach_disputes(run_id).unwrap();
+    assert_eq!(records.len(), disputes, "Each unauthorized return opens a dispute record");
+    for d in &records {
+        let expected: &[&str] = if d.tick_filed + 10 <= 30 {
+            &["customer_upheld", "originator_upheld"]
+        } else {
+            &["open"]
+        };
+        assert!(expected.contains(&d.status.as_str()), "Unexpected status {}", d.status);
+    }
+}
+
+/// R07/R16 close the originator relationship and later entries from the
+/// same originator are returned automatically with the same code.
+#[test]
+fn closed_originators_are_blocked() {
+    let run_id = "ach-return-block-test";
+    let mut engine = build_with_returns(run_id, 0xAC4E_0004);
+
+    engine.run_ticks(60).unwrap();
+
+    let returns = engine.store_ach_returns(run_id).unwrap();
+    let closures: Vec<_> = returns.iter().filter(|r| r.handling == "close_origination").collect();
+    assert!(!closures.is_empty(), "Expected at least one originator closure");
+    assert!(closures.iter().all(|r| r.counterparty.is_some()));
+    assert_eq!(
+        engine.store_ach_origination_block_count(run_id).unwrap(),
+        closures
+            .iter()
+            .map(|r| (&r.account_id, &r.counterparty))
+            .collect::<std::collections::HashSet<_>>()
+            .len() as i64,
+        "One block per closed originator relationship"
+    );
+
+    for first in &closures {
+        let later = returns.iter().filter(|r| {
+            r.tick_returned > first.tick_returned
+                && r.account_id == first.account_id
+                && r.counterparty == first.counterparty
+        });
+        for ret in later {
+            assert_eq!(ret.return_code, first.return_code);
+        }
+    }
+}
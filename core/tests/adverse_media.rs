@@ -0,0 +1,138 @@
+//! Adverse media screening tests — Phase 4.38.
+//!
+//! Tests cover: a feed mixing stories about customers, counterparties and
+//! strangers, screening escalating true subjects more readily than
+//! namesakes, counterparty hits reaching the customers who paid them,
+//! escalated hits feeding the AML screening record with strong ones
+//! bringing EDD reviews forward, and screening staying off unless enabled.
+
+use fincrime_core::{adverse_media, engine::SimEngine, event::SimEvent};
+use std::collections::HashMap;
+
+fn build_with_adverse_media(run_id: &str, seed: u64) -> SimEngine {
+    SimEngine::build_test_with_adverse_media(run_id.to_string(), seed)
+        .expect("build test engine with adverse media")
+}
+
+/// The feed writes about customers, counterparties and strangers, and a
+/// counterparty story prints that counterparty's trading name.
+#[test]
+fn feed_mixes_customers_counterparties_and_strangers() {
+    let run_id = "media-feed-test";
+    let mut engine = build_with_adverse_media(run_id, 42);
+    engine.run_ticks(30).unwrap();
+
+    let articles = engine.store.adverse_media_articles(run_id).unwrap();
+    for kind in ["customer", "counterparty", "stranger"] {
+        assert!(articles.iter().any(|a| a.subject_kind == kind), "no {kind} stories");
+    }
+    for article in &articles {
+        assert_eq!(article.subject_id.is_some(), article.subject_kind != "stranger", "{article:?}");
+        if article.subject_kind == "counterparty" {
+            let counterparty = article.subject_id.as_deref().unwrap();
+            assert_eq!(article.name, adverse_media::counterparty_name(counterparty));
+        }
+    }
+}
+
+/// Escalated hits clear the match threshold. Screening escalates most
+/// stories about customers onto the right customer, and namesakes far
+/// less often — but some still get through.
+#[test]
+fn true_subjects_escalate_more_than_namesakes() {
+    let run_id = "media-match-test";
+    let mut engine = build_with_adverse_media(run_id, 42);
+    engine.run_ticks(60).unwrap();
+
+    let matches = engine.store.adverse_media_matches(run_id).unwrap();
+    let rate = |true_match: bool| {
+        let group: Vec<_> = matches
+            .iter()
+            .filter(|m| m.entity_kind == "customer" && m.true_match == true_match)
+            .collect();
+        assert!(!group.is_empty());
+        group.iter().filter(|m| m.escalated).count() as f64 / group.len() as f64
+    };
+    for m in &matches {
+        assert_eq!(m.escalated, m.match_score >= 0.75, "{m:?}");
+    }
+    let (true_rate, false_rate) = (rate(true), rate(false));
+    assert!(true_rate > false_rate, "true {true_rate} vs namesake {false_rate}");
+    assert!(matches.iter().any(|m| m.escalated && !m.true_match), "no false positives");
+}
+
+/// A counterparty hit lands on a customer who paid that counterparty,
+/// and never triggers EDD on its own.
+#[test]
+fn counterparty_hits_reach_paying_customers() {
+    let run_id = "media-counterparty-test";
+    let mut engine = build_with_adverse_media(run_id, 42);
+    engine.run_ticks(30).unwrap();
+
+    let hits: Vec<_> = engine
+        .store
+        .adverse_media_matches(run_id)
+        .unwrap()
+        .into_iter()
+        .filter(|m| m.entity_kind == "counterparty" && m.escalated)
+        .collect();
+    assert!(!hits.is_empty());
+    assert!(hits.iter().any(|m| m.true_match));
+    let paid = engine.store.recent_counterparties(run_id, 0).unwrap();
+    for hit in &hits {
+        assert!(!hit.edd_triggered);
+        assert!(paid.contains(&(hit.customer_id.clone(), hit.entity_id.clone())), "{hit:?}");
+    }
+}
+
+/// Every escalated customer hit lands on the AML screening record, and
+/// one that triggers EDD leaves a review due within 14 ticks of the hit.
+#[test]
+fn escalated_hits_feed_risk_and_edd() {
+    let run_id = "media-edd-test";
+    let mut engine = build_with_adverse_media(run_id, 42);
+    engine.run_ticks(60).unwrap();
+
+    let matches = engine.store.adverse_media_matches(run_id).unwrap();
+    let triggered: Vec<_> = matches.iter().filter(|m| m.edd_triggered).collect();
+    assert!(!triggered.is_empty());
+
+    let mut reviews: HashMap<String, Vec<_>> = HashMap::new();
+    for review in engine.store.edd_reviews(run_id).unwrap() {
+        reviews.entry(review.customer_id.clone()).or_default().push(review);
+    }
+    for hit in &triggered {
+        assert!(hit.escalated && hit.entity_kind == "customer");
+        assert!(
+            reviews[&hit.customer_id]
+                .iter()
+                .any(|r| r.due_tick >= hit.tick && r.due_tick <= hit.tick + 14),
+            "{hit:?}"
+        );
+    }
+    assert!(reviews.values().flatten().any(|r| r.trigger == "adverse_media"));
+
+    for hit in matches.iter().filter(|m| m.escalated) {
+        let screenings = engine
+            .store
+            .get_customer_aml_screenings(run_id, &hit.customer_id, hit.tick as i64, hit.tick as i64)
+            .unwrap();
+        assert!(screenings.iter().any(|s| s.screening_type == "adverse_media"), "{hit:?}");
+    }
+}
+
+/// Adverse media is opt-in: the default test build publishes nothing and
+/// screens no one.
+#[test]
+fn adverse_media_opt_in() {
+    let run_id = "media-off-test";
+    let mut engine = SimEngine::build_test(run_id.to_string(), 42).unwrap();
+    engine.run_ticks(30).unwrap();
+    assert!(engine.store.adverse_media_articles(run_id).unwrap().is_empty());
+    assert!(engine.store.adverse_media_matches(run_id).unwrap().is_empty());
+    let screened = (1..=30)
+        .flat_map(|tick| engine.store_events_for_tick(run_id, tick).unwrap())
+        .filter_map(|e| serde_json::from_str::<SimEvent>(&e.payload).ok())
+        .any(|e| matches!(e, SimEvent::AdverseMediaScreened { .. }));
+    assert!(!screened);
+}
@@ -0,0 +1,182 @@
+//! Incremental aggregate maintenance tests — Phase 4.27.
+//!
+//! Tests cover: folded segment activity summing to exactly what a live
+//! scan reports, the churn aggregate window reading folded ticks, the
+//! running complaint backlog following opens and closes, and a full run
+//! whose folded quarter totals match the source tables.
+
+use fincrime_core::complaint_subsystem::ComplaintRecord;
+use fincrime_core::customer_subsystem::CustomerRecord;
+use fincrime_core::engine::SimEngine;
+use fincrime_core::store::SimStore;
+use fincrime_core::types::Tick;
+
+const RUN: &str = "agg-run";
+
+fn store() -> SimStore {
+    let store = SimStore::open(":memory:").unwrap();
+    store.migrate().unwrap();
+    store.insert_run(RUN, 1, "0.1.0-test").unwrap();
+    store
+}
+
+fn customer(store: &SimStore, id: &str, segment: &str, open_tick: Tick) {
+    store
+        .insert_customer(RUN, &CustomerRecord {
+            customer_id: id.into(),
+            name: id.into(),
+            segment: segment.into(),
+            income_band: "low".into(),
+            risk_band: "low".into(),
+            open_tick,
+            status: "active".into(),
+            churn_risk: 0.0,
+            satisfaction: 0.8,
+            monthly_txn_mean: 10.0,
+            cash_intensity: 0.1,
+            payroll_amount: 0.0,
+            has_payroll: false,
+            product_id: "basic_checking".into(),
+        })
+        .unwrap();
+    store.insert_account(RUN, &format!("a-{id}"), id, "basic_checking", 100.0, open_tick).unwrap();
+}
+
+fn complaint(store: &SimStore, id: &str, customer_id: &str, tick: Tick, priority: &str) {
+    store
+        .insert_complaint(RUN, &ComplaintRecord {
+            complaint_id: id.into(),
+            customer_id: customer_id.into(),
+            account_id: None,
+            tick_opened: tick,
+            tick_closed: None,
+            product: "checking".into(),
+            issue: "fees".into(),
+            priority: priority.into(),
+            status: "open".into(),
+            sla_due_tick: tick + 15,
+            sla_breached: false,
+            resolution_code: None,
+            amount_refunded: 0.0,
+            udaap_flag: false,
+            channel: "phone".into(),
+            service_channel: None,
+        })
+        .unwrap();
+}
+
+/// Two customers with fees, complaints and a churn: the totals are the
+/// same scanned live, fully folded, or folded part way.
+#[test]
+fn folded_totals_match_live_scan() {
+    let store = store();
+    customer(&store, "c-1", "mass_market", 3);
+    customer(&store, "c-2", "premium", 4);
+    store.insert_transaction(RUN, "t-1", "a-c-1", 3, 35.0, "debit", "overdraft_fee", None).unwrap();
+    store.insert_transaction(RUN, "t-2", "a-c-1", 5, 12.0, "debit", "monthly_fee", None).unwrap();
+    store.insert_transaction(RUN, "t-3", "a-c-1", 5, 80.0, "debit", "groceries", None).unwrap();
+    store.insert_transaction(RUN, "t-4", "a-c-2", 6, 25.0, "debit", "wire_fee", None).unwrap();
+    complaint(&store, "cmp-1", "c-1", 4, "standard");
+    complaint(&store, "cmp-2", "c-1", 6, "urgent");
+    store.churn_customer(RUN, "c-1", 7).unwrap();
+
+    let live = store.segment_activity(RUN, "mass_market", 1, 10).unwrap();
+    assert_eq!(live.fee_income, 47.0);
+    assert_eq!(live.new_customers, 1);
+    assert_eq!((live.complaints_standard, live.complaints_urgent), (1, 1));
+    assert_eq!(live.churned_customers, 1);
+
+    let segments = ["mass_market", "premium"];
+    for tick in 0..=5 {
+        store.fold_segment_activity(RUN, tick, &segments).unwrap();
+    }
+    assert_eq!(store.segment_activity(RUN, "mass_market", 1, 10).unwrap(), live, "Folded through tick 5");
+    for tick in 6..=10 {
+        store.fold_segment_activity(RUN, tick, &segments).unwrap();
+    }
+    assert_eq!(store.segment_activity(RUN, "mass_market", 1, 10).unwrap(), live, "Fully folded");
+    assert_eq!(store.segment_activity(RUN, "premium", 5, 6).unwrap().fee_income, 25.0);
+}
+
+/// The churn aggregate's 30-tick window counts folded churn cohorts by
+/// driver and ignores churn before the window.
+#[test]
+fn churn_aggregate_reads_folded_window() {
+    let store = store();
+    for (n, (tick, driver)) in [(5, "fee_burden"), (20, "fee_burden"), (35, "sla_breach"), (41, "life_event")]
+        .into_iter()
+        .enumerate()
+    {
+        let id = format!("c-{n}");
+        customer(&store, &id, "mass_market", 1);
+        store.churn_customer(RUN, &id, tick).unwrap();
+        store
+            .insert_churn_cohort(RUN, &format!("coh-{n}"), tick, "mass_market", tick, 0.9, 0.2, 0, 0.0, false, driver)
+            .unwrap();
+    }
+    for tick in 0..=39 {
+        store.fold_segment_activity(RUN, tick, &["mass_market"]).unwrap();
+    }
+
+    let agg = store.compute_churn_aggregate(RUN, "mass_market", 41).unwrap();
+    assert_eq!(agg.churned_this_period, 3, "Ticks 11..=41");
+    assert_eq!(agg.fee_driven_churn, 1);
+    assert_eq!(agg.service_driven_churn, 1);
+    assert_eq!(agg.life_event_churn, 1);
+}
+
+/// Backlog and average age follow the running totals, and closing a
+/// complaint twice only takes it off the backlog once.
+#[test]
+fn complaint_backlog_follows_opens_and_closes() {
+    let store = store();
+    customer(&store, "c-1", "mass_market", 1);
+    complaint(&store, "cmp-1", "c-1", 2, "standard");
+    complaint(&store, "cmp-2", "c-1", 4, "high");
+    complaint(&store, "cmp-3", "c-1", 6, "standard");
+    assert_eq!(store.complaint_backlog(RUN).unwrap(), 3);
+
+    store.close_complaint(RUN, "cmp-2", 8, "goodwill", 0.0).unwrap();
+    store.close_complaint(RUN, "cmp-2", 9, "goodwill", 0.0).unwrap();
+    assert_eq!(store.complaint_backlog(RUN).unwrap(), 2);
+    assert_eq!(store.open_complaints(RUN).unwrap().len(), 2);
+
+    let agg = store.compute_complaint_aggregate(RUN, 10).unwrap();
+    assert_eq!(agg.backlog_count, 2);
+    assert_eq!(agg.avg_age_days, 6.0, "Ages 8 and 4");
+
+    for id in ["cmp-1", "cmp-3"] {
+        store.close_complaint(RUN, id, 10, "goodwill", 0.0).unwrap();
+    }
+    let agg = store.compute_complaint_aggregate(RUN, 11).unwrap();
+    assert_eq!((agg.backlog_count, agg.avg_age_days), (0, 0.0));
+}
+
+/// Over a real run, the quarter's folded activity adds up to what the
+/// complaint and customer tables hold, and the backlog to the open list.
+#[test]
+fn run_totals_match_source_tables() {
+    let run_id = "agg-engine-test";
+    let mut engine = SimEngine::build_test(run_id.into(), 42).unwrap();
+    engine.run_ticks(95).unwrap();
+
+    let quarter: Vec<_> = ["mass_market", "premium", "small_business"]
+        .iter()
+        .map(|segment| engine.store.segment_activity(run_id, segment, 1, 90).unwrap())
+        .collect();
+    let complaints: i64 = quarter
+        .iter()
+        .map(|a| a.complaints_standard + a.complaints_high + a.complaints_urgent)
+        .sum();
+    assert_eq!(complaints, engine.store.total_complaints_in_window(run_id, 1, 90).unwrap());
+
+    let churned: i64 = ["mass_market", "premium", "small_business"]
+        .iter()
+        .map(|segment| engine.store.segment_activity(run_id, segment, 0, 95).unwrap().churned_customers)
+        .sum();
+    assert_eq!(churned, engine.store.churned_customer_count(run_id).unwrap());
+    assert_eq!(
+        engine.store.complaint_backlog(run_id).unwrap(),
+        engine.store.open_complaints(run_id).unwrap().len() as i64,
+    );
+}
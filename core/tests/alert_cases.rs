@@ -0,0 +1,186 @@
+//! Alert case grouping tests — Phase 4.60.
+//!
+//! Tests cover: repeat alerts on a customer joining one case with its
+//! totals kept; the window running from the case's newest alert; strong
+//! relationships pulling linked customers into one case and weak ones
+//! not; per-type windows and customer-only grouping; and cases closing
+//! once worked, with grouping off by default.
+
+use fincrime_core::{
+    config::SimConfig,
+    engine::SimEngine,
+    store::{alert_case::AlertCaseRow, CustomerRelationshipRow, SimStore},
+    transaction_monitoring_subsystem::AMLAlert,
+};
+
+/// Run the engine to `tick`, then raise an alert of `alert_type` on the
+/// customer at that tick.
+fn raise(engine: &mut SimEngine, alert_id: &str, customer_id: &str, alert_type: &str, tick: u64, amount: f64) {
+    engine.run_ticks(tick - engine.clock.current_tick).unwrap();
+    engine
+        .store
+        .insert_transaction_monitoring_alert(&AMLAlert {
+            alert_id: alert_id.into(),
+            run_id: engine.run_id.clone(),
+            customer_id: customer_id.into(),
+            tick,
+            rule_id: "TEST".into(),
+            alert_type: alert_type.into(),
+            alert_score: 50.0 + amount / 1_000.0,
+            description: "test alert".into(),
+            triggered_amount: Some(amount),
+            transaction_count: Some(1),
+            status: "open".into(),
+        })
+        .unwrap();
+}
+
+fn customers(engine: &mut SimEngine) -> Vec<String> {
+    if engine.clock.current_tick == 0 {
+        engine.run_ticks(1).unwrap();
+    }
+    engine.store.active_customers(&engine.run_id).unwrap().into_iter().map(|c| c.customer_id).collect()
+}
+
+fn case_of(engine: &SimEngine, alert_id: &str) -> String {
+    engine.store.alert_case_of(&engine.run_id, alert_id).unwrap().unwrap_or_else(|| panic!("{alert_id} ungrouped"))
+}
+
+fn case(engine: &SimEngine, case_id: &str) -> AlertCaseRow {
+    engine.store.alert_cases(&engine.run_id).unwrap().into_iter().find(|c| c.case_id == case_id).unwrap()
+}
+
+fn relate(engine: &SimEngine, a: &str, b: &str, strength: f64) {
+    engine
+        .store
+        .insert_customer_relationship(&CustomerRelationshipRow {
+            relationship_id: format!("rel-test-{a}-{b}"),
+            run_id: engine.run_id.clone(),
+            customer_id_a: a.into(),
+            customer_id_b: b.into(),
+            relationship_type: "employer".into(),
+            strength,
+            detected_tick: 1,
+            detection_method: "inferred_txn".into(),
+            is_suspicious: 0,
+        })
+        .unwrap();
+}
+
+/// A second alert on a customer inside the window joins the first's
+/// case, which keeps count, total and top score; another customer's
+/// alert opens its own.
+#[test]
+fn repeat_alerts_on_a_customer_share_a_case() {
+    let mut engine = SimEngine::build_test_with_alert_grouping("cases-repeat-test".into(), 42, 10).unwrap();
+    let c = customers(&mut engine);
+    raise(&mut engine, "R0", &c[0], "structuring", 1, 9_000.0);
+    raise(&mut engine, "R1", &c[0], "velocity", 5, 20_000.0);
+    raise(&mut engine, "R2", &c[1], "structuring", 5, 9_500.0);
+    engine.run_ticks(1).unwrap();
+
+    let case_id = case_of(&engine, "R0");
+    assert_eq!(case_of(&engine, "R1"), case_id);
+    assert_ne!(case_of(&engine, "R2"), case_id);
+    let grouped = case(&engine, &case_id);
+    assert_eq!((grouped.customer_id.as_str(), grouped.opened_tick, grouped.last_alert_tick), (c[0].as_str(), 1, 5));
+    assert_eq!((grouped.alert_count, grouped.customer_count, grouped.status.as_str()), (2, 1, "open"));
+    assert!((grouped.total_amount - 29_000.0).abs() < 1e-9);
+    assert_eq!(grouped.max_score, 70.0);
+
+    let run_id = engine.run_id.clone();
+    assert_eq!(engine.store.count_events_in_range(&run_id, 0, 6, "alert_grouped_into_case").unwrap(), 1);
+    assert_eq!(engine.store.count_events_in_range(&run_id, 0, 6, "alert_case_opened").unwrap(), 2);
+}
+
+/// The window runs back from each new alert to the case's newest, so a
+/// steady run of alerts stays in one case and a gap starts another.
+#[test]
+fn window_runs_from_the_newest_alert() {
+    let mut engine = SimEngine::build_test_with_alert_grouping("cases-window-test".into(), 42, 5).unwrap();
+    let c = customers(&mut engine);
+    for (id, tick) in [("W0", 1), ("W1", 5), ("W2", 9), ("W3", 16)] {
+        raise(&mut engine, id, &c[0], "structuring", tick, 9_000.0);
+    }
+    engine.run_ticks(1).unwrap();
+
+    let first = case_of(&engine, "W0");
+    assert_eq!((case_of(&engine, "W1"), case_of(&engine, "W2")), (first.clone(), first.clone()));
+    assert_ne!(case_of(&engine, "W3"), first);
+    assert_eq!(case(&engine, &first).last_alert_tick, 9);
+}
+
+/// Customers linked through strong relationships, even by way of a third
+/// customer, share a case; a weak link does not.
+#[test]
+fn strong_relationships_share_a_case() {
+    let mut engine = SimEngine::build_test_with_alert_grouping("cases-cluster-test".into(), 42, 10).unwrap();
+    let c = customers(&mut engine);
+    relate(&engine, &c[0], &c[1], 0.9);
+    relate(&engine, &c[2], &c[1], 0.8);
+    relate(&engine, &c[0], &c[3], 0.3);
+    raise(&mut engine, "K0", &c[0], "structuring", 1, 9_000.0);
+    raise(&mut engine, "K2", &c[2], "rapid_movement", 2, 6_000.0);
+    raise(&mut engine, "K3", &c[3], "structuring", 2, 9_000.0);
+    engine.run_ticks(1).unwrap();
+
+    let case_id = case_of(&engine, "K0");
+    assert_eq!(case_of(&engine, "K2"), case_id);
+    assert_ne!(case_of(&engine, "K3"), case_id);
+    assert_eq!((case(&engine, &case_id).alert_count, case(&engine, &case_id).customer_count), (2, 2));
+}
+
+/// A type's own window overrides the default, and with no relationship
+/// strength set only the same customer's alerts group.
+#[test]
+fn type_windows_and_customer_only_grouping() {
+    let run_id = "cases-config-test";
+    let path = format!("./test_{run_id}.db");
+    let _ = std::fs::remove_file(&path);
+    let store = SimStore::open(&path).unwrap();
+    store.migrate().unwrap();
+    store.insert_run(run_id, 42, "0.1.0-test").unwrap();
+    let mut config = SimConfig::default_test();
+    config.alert_grouping.enabled = true;
+    config.alert_grouping.window_ticks = 3;
+    config.alert_grouping.type_windows = [("sanctions_hit".to_string(), 20)].into();
+    config.alert_grouping.min_relationship_strength = None;
+    let mut engine = SimEngine::build_with_config(run_id.into(), 42, &store, config).unwrap();
+
+    let c = customers(&mut engine);
+    relate(&engine, &c[0], &c[1], 1.0);
+    raise(&mut engine, "T0", &c[0], "sanctions_hit", 1, 0.0);
+    raise(&mut engine, "T1", &c[1], "sanctions_hit", 2, 0.0);
+    raise(&mut engine, "T2", &c[0], "sanctions_hit", 15, 0.0);
+    raise(&mut engine, "T3", &c[0], "structuring", 25, 9_000.0);
+    engine.run_ticks(1).unwrap();
+
+    assert_eq!(case_of(&engine, "T2"), case_of(&engine, "T0"));
+    assert_ne!(case_of(&engine, "T1"), case_of(&engine, "T0"));
+    assert_ne!(case_of(&engine, "T3"), case_of(&engine, "T0"));
+    drop(engine);
+    let _ = std::fs::remove_file(&path);
+}
+
+/// A case closes once its alert is reported in a SAR, and a later alert
+/// opens a new case; the test default groups nothing.
+#[test]
+fn worked_cases_close_and_grouping_is_opt_in() {
+    let mut engine = SimEngine::build_test_with_alert_grouping("cases-close-test".into(), 42, 30).unwrap();
+    let c = customers(&mut engine);
+    raise(&mut engine, "S0", &c[0], "structuring", 1, 40_000.0);
+    raise(&mut engine, "S1", &c[0], "structuring", 8, 9_000.0);
+    engine.run_ticks(1).unwrap();
+
+    let closed = case(&engine, &case_of(&engine, "S0"));
+    assert_eq!((closed.status.as_str(), closed.closed_tick), ("closed", Some(7)));
+    assert_ne!(case_of(&engine, "S1"), closed.case_id);
+    assert_eq!(engine.store.count_events_in_range(&engine.run_id, 7, 7, "alert_case_closed").unwrap(), 1);
+
+    let mut off = SimEngine::build_test("cases-off-test".into(), 42).unwrap();
+    let c = customers(&mut off);
+    raise(&mut off, "O0", &c[0], "structuring", 1, 9_000.0);
+    off.run_ticks(10).unwrap();
+    assert_eq!(off.store.alert_case_of("cases-off-test", "O0").unwrap(), None);
+    assert!(off.store.alert_cases("cases-off-test").unwrap().is_empty());
+}
@@ -0,0 +1,190 @@
+//! Alert disposition QA tests — Phase 4.73.
+//!
+//! Tests cover: perfect reviewers finding exactly the defensive filings
+//! as over-filing and opening retraining on the alert type; missed
+//! filings found as under-filing, the retraining completing with its
+//! cost booked; the sample capped at QA's capacity; reviewers who always
+//! err disagreeing with every correct decision; and no second retraining
+//! on a type while one is under way, with the cycle's trend against the
+//! last.
+
+use fincrime_core::{
+    command::PlayerCommand, config::SimConfig, engine::SimEngine, store::SimStore,
+    transaction_monitoring_subsystem::AMLAlert,
+};
+
+fn set_threshold(engine: &mut SimEngine, threshold: f64) {
+    engine.submit_command(PlayerCommand::SetSarThreshold { threshold }).unwrap();
+}
+
+/// Raise an open structuring alert at `tick` on a different customer for
+/// each score, ids prefixed by `prefix`.
+fn raise_alerts(engine: &SimEngine, prefix: &str, tick: u64, scores: &[f64]) {
+    let customers = engine.store.active_customers(&engine.run_id).unwrap();
+    for (i, (score, customer)) in scores.iter().zip(&customers).enumerate() {
+        engine
+            .store
+            .insert_transaction_monitoring_alert(&AMLAlert {
+                alert_id: format!("{prefix}{i}"),
+                run_id: engine.run_id.clone(),
+                customer_id: customer.customer_id.clone(),
+                tick,
+                rule_id: "TEST".into(),
+                alert_type: "structuring".into(),
+                alert_score: *score,
+                description: "test alert".into(),
+                triggered_amount: Some(9_500.0),
+                transaction_count: Some(3),
+                status: "open".into(),
+            })
+            .unwrap();
+    }
+}
+
+/// A test engine on `config` with SAR decisions and QA on.
+fn build_with(run_id: &str, seed: u64, tweak: impl FnOnce(&mut SimConfig)) -> SimEngine {
+    let db = format!("test_{run_id}.db");
+    let _ = std::fs::remove_file(&db);
+    let store = SimStore::open(&db).unwrap();
+    store.migrate().unwrap();
+    store.insert_run(run_id, seed, "0.1.0-test").unwrap();
+    let mut config = SimConfig::default_test();
+    config.sar_decision.enabled = true;
+    config.alert_qa.enabled = true;
+    config.alert_qa.sample_rate = 1.0;
+    config.alert_qa.reviewer_accuracy = 1.0;
+    tweak(&mut config);
+    SimEngine::build_with_config(run_id.into(), seed, &store, config).unwrap()
+}
+
+/// Filing below the reportable score is over-filing and nothing else is;
+/// three of four structuring decisions wrong opens retraining on it.
+#[test]
+fn perfect_reviewers_find_defensive_filings() {
+    let mut engine = SimEngine::build_test_with_alert_qa("alert-qa-defensive-test".into(), 0xA1A0_0001).unwrap();
+    set_threshold(&mut engine, 60.0);
+    engine.run_ticks(1).unwrap();
+    raise_alerts(&engine, "D", 1, &[65.0, 75.0, 80.0, 90.0]);
+    engine.run_ticks(29).unwrap();
+
+    let run_id = engine.run_id.clone();
+    let reviews = engine.store.alert_qa_reviews(&run_id).unwrap();
+    let decisions = engine.store_sar_decisions(&run_id).unwrap();
+    assert_eq!(reviews.len(), decisions.len(), "Everything sampled");
+    for review in &reviews {
+        assert_eq!(review.cycle_tick, 30);
+        assert_eq!(review.cost, 120.0);
+        assert!(review.desk_filed);
+        let expected = (review.decision == "defensive").then(|| "over_filing".to_string());
+        assert_eq!(review.error_kind, expected, "{}", review.alert_id);
+    }
+
+    let cycles = engine.store.alert_qa_cycles(&run_id).unwrap();
+    assert_eq!(cycles.len(), 1);
+    let cycle = &cycles[0];
+    assert_eq!(cycle.population, decisions.len() as i64);
+    assert_eq!(cycle.disagreements, decisions.iter().filter(|d| d.decision == "defensive").count() as i64);
+    assert_eq!(cycle.disagreement_trend, None, "No cycle before");
+
+    let retraining = engine.store.qa_retrainings(&run_id).unwrap();
+    assert_eq!(retraining.len(), 1);
+    assert_eq!(retraining[0].alert_type, "structuring");
+    assert_eq!(retraining[0].error_kind, "over_filing");
+    assert_eq!((retraining[0].opened_tick, retraining[0].due_tick), (30, 44));
+    let events = engine.store_events_for_tick(&run_id, 30).unwrap();
+    assert!(events.iter().any(|e| e.event_type == "alert_qa_cycle_completed"));
+    assert!(events.iter().any(|e| e.event_type == "qa_retraining_opened"));
+}
+
+/// Reportable alerts left unfiled are under-filing; the retraining they
+/// prompt completes when due, and its cost is booked then.
+#[test]
+fn missed_filings_are_under_filing() {
+    let mut engine = SimEngine::build_test_with_alert_qa("alert-qa-missed-test".into(), 0xA1A0_0002).unwrap();
+    set_threshold(&mut engine, 95.0);
+    engine.run_ticks(1).unwrap();
+    raise_alerts(&engine, "M", 1, &[88.0, 90.0, 91.0, 93.0]);
+    engine.run_ticks(59).unwrap();
+
+    let run_id = engine.run_id.clone();
+    let ours: Vec<_> =
+        engine.store.alert_qa_reviews(&run_id).unwrap().into_iter().filter(|r| r.alert_id.starts_with('M')).collect();
+    assert_eq!(ours.len(), 4);
+    for review in &ours {
+        assert_eq!(review.decision, "missed");
+        assert_eq!(review.cycle_tick, 60, "Missed at tick 35, reviewed in the second cycle");
+        assert!(!review.desk_filed && review.qa_filed);
+        assert_eq!(review.error_kind.as_deref(), Some("under_filing"));
+    }
+    let item = engine.store.qa_retrainings(&run_id).unwrap().into_iter().find(|r| r.opened_tick == 60).unwrap();
+    assert_eq!(item.error_kind, "under_filing");
+    assert_eq!(item.status, "open");
+    assert!(engine.store.alert_qa_cost_between(&run_id, 61, 74).unwrap() == 0.0);
+
+    engine.run_ticks(14).unwrap();
+    let item = engine.store.qa_retrainings(&run_id).unwrap().into_iter().find(|r| r.opened_tick == 60).unwrap();
+    assert_eq!((item.status.as_str(), item.completed_tick), ("completed", Some(74)));
+    assert_eq!(engine.store.alert_qa_cost_between(&run_id, 61, 74).unwrap(), 6_000.0);
+    let events = engine.store_events_for_tick(&run_id, 74).unwrap();
+    assert!(events.iter().any(|e| e.event_type == "qa_retraining_completed"));
+}
+
+/// QA reviews no more than it has capacity for, and pays for each.
+#[test]
+fn sample_is_capped_at_capacity() {
+    let mut engine = build_with("alert-qa-capacity-test", 0xA1A0_0003, |c| c.alert_qa.review_capacity = 2);
+    engine.run_ticks(1).unwrap();
+    raise_alerts(&engine, "C", 1, &[86.0, 88.0, 90.0, 92.0, 94.0]);
+    engine.run_ticks(29).unwrap();
+
+    let run_id = engine.run_id.clone();
+    let cycle = engine.store.latest_alert_qa_cycle(&run_id).unwrap().unwrap();
+    assert!(cycle.population >= 5);
+    assert_eq!(cycle.sampled, 2);
+    assert_eq!(engine.store.alert_qa_reviews(&run_id).unwrap().len(), 2);
+    assert_eq!(engine.store.alert_qa_cost_between(&run_id, 1, 30).unwrap(), 240.0);
+    assert!(engine.store.qa_retrainings(&run_id).unwrap().is_empty(), "Too few reviews to judge");
+}
+
+/// Reviewers who always get it wrong disagree with every correct filing,
+/// so a disagreement is QA's finding, not the desk's error.
+#[test]
+fn fallible_reviewers_disagree_with_correct_decisions() {
+    let mut engine = build_with("alert-qa-fallible-test", 0xA1A0_0004, |c| c.alert_qa.reviewer_accuracy = 0.0);
+    engine.run_ticks(1).unwrap();
+    raise_alerts(&engine, "F", 1, &[86.0, 90.0, 95.0, 99.0]);
+    engine.run_ticks(29).unwrap();
+
+    let reviews = engine.store.alert_qa_reviews(&engine.run_id).unwrap();
+    let ours: Vec<_> = reviews.iter().filter(|r| r.alert_id.starts_with('F')).collect();
+    assert_eq!(ours.len(), 4);
+    for review in ours {
+        assert_eq!(review.decision, "filed", "The desk got it right");
+        assert_eq!(review.error_kind.as_deref(), Some("over_filing"));
+    }
+}
+
+/// While retraining on a type is under way a second cycle's errors on it
+/// open no more; the cycle's rate is set against the last.
+#[test]
+fn one_retraining_per_type_at_a_time() {
+    let mut engine = build_with("alert-qa-repeat-test", 0xA1A0_0005, |c| c.alert_qa.retraining_ticks = 90);
+    set_threshold(&mut engine, 60.0);
+    engine.run_ticks(1).unwrap();
+    raise_alerts(&engine, "R", 1, &[65.0, 70.0, 75.0, 90.0]);
+    engine.run_ticks(30).unwrap();
+    raise_alerts(&engine, "S", 31, &[65.0, 70.0, 75.0, 80.0]);
+    engine.run_ticks(29).unwrap();
+
+    let run_id = engine.run_id.clone();
+    let retraining = engine.store.qa_retrainings(&run_id).unwrap();
+    assert_eq!(retraining.len(), 1, "{retraining:?}");
+    assert_eq!(retraining[0].status, "open");
+
+    let cycles = engine.store.alert_qa_cycles(&run_id).unwrap();
+    assert_eq!(cycles.len(), 2);
+    let (first, second) = (&cycles[0], &cycles[1]);
+    assert!(second.disagreements >= 4);
+    let trend = second.disagreement_rate.unwrap() - first.disagreement_rate.unwrap();
+    assert!((second.disagreement_trend.unwrap() - trend).abs() < 1e-12);
+}
@@ -0,0 +1,222 @@
+//! Alert triage tests — Phase 4.85.
+//!
+//! Tests cover: an assigned alert held by its analyst instead of the desk
+//! and still counted against its SAR deadline; closing as a false
+//! positive or suspicious, with a reportable alert closed that way
+//! recorded as a missed filing; closing as `sar_filed` filing the SAR;
+//! escalating into a case, with the note on the customer's timeline; IPC
+//! validation of the commands; and the alert queue's aging metrics each
+//! tick.
+
+use fincrime_core::{
+    command::PlayerCommand,
+    command_result::{CommandResult, RejectionCode},
+    engine::SimEngine,
+    entity_timeline::{EntityTimeline, EntityType},
+    transaction_monitoring_subsystem::AMLAlert,
+};
+use serde_json::{json, Value};
+
+fn build(run_id: &str) -> SimEngine {
+    SimEngine::build_test_with_alert_triage(run_id.into(), 42).unwrap()
+}
+
+fn events(engine: &SimEngine, event_type: &str) -> Vec<Value> {
+    (0..=engine.clock.current_tick)
+        .flat_map(|tick| engine.store.events_for_tick(&engine.run_id, tick).unwrap())
+        .filter(|e| e.event_type == event_type)
+        .map(|e| serde_json::from_str(&e.payload).unwrap())
+        .collect()
+}
+
+/// Raise an alert scoring `score` on the first customer at the current
+/// tick.
+fn raise_alert(engine: &SimEngine, alert_id: &str, score: f64) {
+    let run_id = engine.run_id.clone();
+    let customer_id = engine.store.active_customers(&run_id).unwrap()[0].customer_id.clone();
+    engine
+        .store
+        .insert_transaction_monitoring_alert(&AMLAlert {
+            alert_id: alert_id.into(),
+            run_id,
+            customer_id,
+            tick: engine.clock.current_tick,
+            rule_id: "TEST".into(),
+            alert_type: "structuring".into(),
+            alert_score: score,
+            description: "test alert".into(),
+            triggered_amount: Some(9_500.0),
+            transaction_count: Some(3),
+            status: "open".into(),
+        })
+        .unwrap();
+}
+
+fn status(engine: &SimEngine, alert_id: &str) -> String {
+    engine.store.aml_alert(&engine.run_id, alert_id).unwrap().unwrap().status
+}
+
+fn decisions(engine: &SimEngine, alert_id: &str) -> Vec<String> {
+    let decisions = engine.store.sar_decisions(&engine.run_id).unwrap();
+    decisions.into_iter().filter(|d| d.alert_id == alert_id).map(|d| d.decision).collect()
+}
+
+fn close(engine: &mut SimEngine, alert_id: &str, disposition: &str) {
+    engine
+        .submit_command(PlayerCommand::CloseAlert {
+            alert_id: alert_id.into(),
+            disposition: disposition.into(),
+            note: format!("closed as {disposition}"),
+        })
+        .unwrap();
+}
+
+/// An assigned alert is the analyst's: the weekly desk does not file it,
+/// and left sitting it runs past its deadline into a missed filing.
+#[test]
+fn assigned_alert_held_by_analyst_until_deadline() {
+    let run_id = "triage-assign-test";
+    let mut engine = build(run_id);
+    engine.run_ticks(40).unwrap();
+    raise_alert(&engine, "A0", 95.0);
+    engine
+        .submit_command(PlayerCommand::AssignAlert {
+            alert_id: "A0".into(),
+            analyst: "dana".into(),
+            note: Some("pulling statements".into()),
+        })
+        .unwrap();
+    engine.run_ticks(10).unwrap();
+
+    assert_eq!(status(&engine, "A0"), "investigating");
+    assert_eq!(engine.store.alert_assignee(run_id, "A0").unwrap().as_deref(), Some("dana"));
+    let notes = engine.store.alert_notes(run_id, "A0").unwrap();
+    assert_eq!(notes.len(), 1);
+    assert_eq!((notes[0].tick, notes[0].analyst.as_deref(), notes[0].action.as_str()), (41, Some("dana"), "assign"));
+    assert_eq!(events(&engine, "alert_assigned")[0]["analyst"], "dana");
+    assert!(decisions(&engine, "A0").is_empty(), "the desk filed an assigned alert");
+
+    engine.run_ticks(30).unwrap();
+    assert_eq!(status(&engine, "A0"), "sar_missed");
+    assert_eq!(decisions(&engine, "A0"), ["missed"]);
+}
+
+/// A false positive or suspicious close resolves the alert with its
+/// disposition and note; closing a reportable alert without a filing is
+/// a missed SAR, a weak one is not.
+#[test]
+fn closing_without_filing_misses_only_reportable_alerts() {
+    let run_id = "triage-close-test";
+    let mut engine = build(run_id);
+    engine.run_ticks(40).unwrap();
+    raise_alert(&engine, "WEAK", 40.0);
+    raise_alert(&engine, "STRONG", 95.0);
+    close(&mut engine, "WEAK", "false_positive");
+    close(&mut engine, "STRONG", "suspicious");
+    engine.run_ticks(1).unwrap();
+
+    for (alert_id, disposition) in [("WEAK", "false_positive"), ("STRONG", "suspicious")] {
+        assert_eq!(status(&engine, alert_id), "resolved");
+        assert_eq!(engine.store.alert_disposition(run_id, alert_id).unwrap().as_deref(), Some(disposition));
+        let notes = engine.store.alert_notes(run_id, alert_id).unwrap();
+        assert_eq!((notes[0].action.as_str(), notes[0].note.clone()), ("close", format!("closed as {disposition}")));
+    }
+    assert!(decisions(&engine, "WEAK").is_empty());
+    assert_eq!(decisions(&engine, "STRONG"), ["missed"]);
+    let missed = events(&engine, "sar_filing_missed");
+    assert_eq!((missed.len(), missed[0]["alert_id"].as_str()), (1, Some("STRONG")));
+    assert_eq!(events(&engine, "alert_closed").len(), 2);
+}
+
+/// Closing as `sar_filed` files the SAR on the alert there and then; a
+/// second close of the same alert is rejected when it lands.
+#[test]
+fn sar_filed_close_files_the_sar() {
+    let run_id = "triage-file-test";
+    let mut engine = build(run_id);
+    engine.run_ticks(40).unwrap();
+    raise_alert(&engine, "A0", 60.0);
+    close(&mut engine, "A0", "sar_filed");
+    engine.run_ticks(1).unwrap();
+
+    assert_eq!(status(&engine, "A0"), "sar_filed");
+    assert_eq!(engine.store.alert_disposition(run_id, "A0").unwrap().as_deref(), Some("sar_filed"));
+    assert_eq!(decisions(&engine, "A0"), ["defensive"]);
+    let filed = events(&engine, "sar_filed");
+    assert_eq!(filed.last().unwrap()["tick"].as_u64(), Some(41));
+    assert_eq!(events(&engine, "alert_closed")[0]["disposition"], "sar_filed");
+
+    close(&mut engine, "A0", "false_positive");
+    engine.run_ticks(1).unwrap();
+    let rejected = events(&engine, "alert_triage_rejected");
+    assert_eq!(rejected[0]["reason"], "alert A0 is sar_filed");
+    assert_eq!(status(&engine, "A0"), "sar_filed");
+}
+
+/// Escalating an alert in no case opens one for it and marks it
+/// escalated; the alert goes under investigation, the note shows on the
+/// customer's timeline, and the case closes once the alert is closed.
+#[test]
+fn escalation_opens_and_escalates_a_case() {
+    let run_id = "triage-escalate-test";
+    let mut engine = build(run_id);
+    engine.run_ticks(40).unwrap();
+    raise_alert(&engine, "A0", 70.0);
+    engine
+        .submit_command(PlayerCommand::EscalateAlertToCase { alert_id: "A0".into(), note: "linked wires".into() })
+        .unwrap();
+    engine.run_ticks(1).unwrap();
+
+    let case = engine.store.alert_cases(run_id).unwrap().into_iter().find(|c| c.case_id == "case-A0").unwrap();
+    assert_eq!((case.escalated_tick, case.status.as_str()), (Some(41), "open"));
+    assert_eq!(status(&engine, "A0"), "investigating");
+    assert_eq!(events(&engine, "alert_escalated")[0]["case_id"], "case-A0");
+    assert_eq!(engine.store.alert_notes(run_id, "A0").unwrap()[0].action, "escalate");
+    let customer_id = engine.store.aml_alert(run_id, "A0").unwrap().unwrap().customer_id;
+    let timeline =
+        EntityTimeline::query(&engine.store, run_id, EntityType::Customer, &customer_id, Some(41), Some(41)).unwrap();
+    assert!(timeline.entries.iter().any(|e| e.source == "alert_note" && e.summary == "escalate: linked wires"));
+
+    close(&mut engine, "A0", "false_positive");
+    engine.run_ticks(1).unwrap();
+    let case = engine.store.alert_cases(run_id).unwrap().into_iter().find(|c| c.case_id == "case-A0").unwrap();
+    assert_eq!((case.status.as_str(), case.closed_tick), ("closed", Some(42)));
+}
+
+/// Over IPC the commands are checked for their fields and for an alert
+/// still open before they are queued; the queue's aging is written every
+/// tick with the day's closures by disposition.
+#[test]
+fn ipc_validation_and_aging_metrics() {
+    let run_id = "triage-ipc-test";
+    let mut engine = build(run_id);
+    engine.run_ticks(40).unwrap();
+    raise_alert(&engine, "A0", 50.0);
+
+    let bad = json!({ "alert_id": "A0", "disposition": "closed", "note": "" });
+    let rejected = CommandResult::apply(&mut engine, "close_alert", &bad, None).unwrap();
+    assert_eq!(rejected.code, Some(RejectionCode::ValidationFailed));
+    let fields: Vec<&str> = rejected.errors.iter().map(|e| e.field.as_str()).collect();
+    assert_eq!(fields, ["disposition", "note"]);
+
+    let missing = json!({ "alert_id": "NOPE", "analyst": "dana" });
+    let rejected = CommandResult::apply(&mut engine, "assign_alert", &missing, None).unwrap();
+    assert_eq!(rejected.code, Some(RejectionCode::NotFound));
+
+    let payload = json!({ "alert_id": "A0", "disposition": "false_positive", "note": "payroll" });
+    let accepted = CommandResult::apply(&mut engine, "close_alert", &payload, None).unwrap();
+    assert!(accepted.accepted);
+    assert_eq!(accepted.entity_ids, ["A0"]);
+    engine.run_ticks(2).unwrap();
+
+    let aging = engine.store.alert_aging(run_id).unwrap();
+    assert_eq!(aging.len(), 42);
+    assert_eq!(aging.iter().map(|a| a.tick).collect::<Vec<_>>(), (1..=42).collect::<Vec<_>>());
+    let day = &aging[40];
+    assert_eq!(day.tick, 41);
+    assert!(day.closed_false_positive >= 1);
+    for a in &aging {
+        assert!(a.max_age_ticks as f64 >= a.avg_age_ticks);
+        assert!(a.aged_alerts <= a.open_alerts + a.investigating_alerts);
+    }
+}
@@ -0,0 +1,224 @@
+//! Authorized push payment fraud tests — Phase 4.91.
+//!
+//! Tests cover: nothing booked unless enabled; scam payments pushed out
+//! on the instant rails and debited from the victim; claims opened when
+//! reported and decided a fixed number of ticks later; a full policy
+//! reimbursing less the excess and a none policy declining, with
+//! complaints following; the player's policy change applying to claims
+//! decided after it and surviving a resume; recalls of instant payments
+//! refused as irrevocable; and the quarter's reimbursements charged to
+//! the P&L.
+
+use fincrime_core::{
+    command::PlayerCommand,
+    config::SimConfig,
+    engine::SimEngine,
+    store::{app_fraud::AppScamPaymentRow, SimStore},
+};
+use serde_json::Value;
+
+const TICKS: u64 = 40;
+
+fn engine(run_id: &str, policy: &str) -> SimEngine {
+    SimEngine::build_test_with_app_fraud(run_id.into(), 42, policy).unwrap()
+}
+
+/// Payloads of every `event_type` event so far, in order.
+fn events(engine: &SimEngine, event_type: &str) -> Vec<Value> {
+    (0..=engine.clock.current_tick)
+        .flat_map(|tick| engine.store.events_for_tick(&engine.run_id, tick).unwrap())
+        .filter(|e| e.event_type == event_type)
+        .map(|e| serde_json::from_str(&e.payload).unwrap())
+        .collect()
+}
+
+fn decided(scams: &[AppScamPaymentRow]) -> Vec<&AppScamPaymentRow> {
+    scams.iter().filter(|s| s.decided_tick.is_some()).collect()
+}
+
+/// Off by default; on, victims push a share of their balance to a
+/// scammer over RTP or FedNow, settled at once.
+#[test]
+fn scam_payments_sent_on_instant_rails() {
+    let mut plain = SimEngine::build_test("app-off-test".into(), 42).unwrap();
+    plain.run_ticks(TICKS).unwrap();
+    assert!(plain.store.app_scam_payments(&plain.run_id).unwrap().is_empty());
+    assert!(events(&plain, "app_scam_payments_booked").is_empty());
+
+    let mut engine = engine("app-payments-test", "full");
+    engine.run_ticks(TICKS).unwrap();
+    let scams = engine.store.app_scam_payments(&engine.run_id).unwrap();
+    assert!(scams.len() >= 5, "only {} scam payments", scams.len());
+    let config = SimConfig::default_test().app_fraud;
+    for s in &scams {
+        assert!(config.rails.contains(&s.rail_id), "scam sent on {}", s.rail_id);
+        assert!(config.scam_types.contains(&s.scam_type));
+        assert!(s.amount >= config.min_payment);
+        let segment = engine.store.customer_segment(&engine.run_id, &s.customer_id).unwrap();
+        assert!(config.segments.contains(&segment));
+        assert_eq!(engine.store.debit_rail(&engine.run_id, &s.txn_id).unwrap().as_deref(), Some(s.rail_id.as_str()));
+        assert_eq!(engine.store.transaction_settlement_status(&engine.run_id, &s.txn_id).unwrap(), "settled");
+    }
+    assert!(scams.iter().any(|s| s.rail_id == "FedNow"));
+
+    let booked = events(&engine, "app_scam_payments_booked");
+    let count: i64 = booked.iter().map(|e| e["payments"].as_i64().unwrap()).sum();
+    let total: f64 = booked.iter().map(|e| e["amount"].as_f64().unwrap()).sum();
+    assert_eq!(count as usize, scams.len());
+    assert!((total - scams.iter().map(|s| s.amount).sum::<f64>()).abs() < 1e-6);
+}
+
+/// Every victim reports within three ticks, and the desk decides each
+/// claim two ticks after it is reported.
+#[test]
+fn claims_decided_after_report() {
+    let mut engine = engine("app-claims-test", "full");
+    engine.run_ticks(TICKS).unwrap();
+    let scams = engine.store.app_scam_payments(&engine.run_id).unwrap();
+    for s in &scams {
+        let report = s.report_tick.expect("every victim reports in this test");
+        assert!(report > s.tick && report <= s.tick + 3);
+        match s.decided_tick {
+            Some(decided) => assert_eq!(decided, report + 2),
+            None => assert!(report + 2 > TICKS),
+        }
+    }
+    assert!(decided(&scams).len() >= 3);
+
+    let reported = events(&engine, "app_scam_reported");
+    assert_eq!(reported.len(), scams.iter().filter(|s| s.claim_status.is_some()).count());
+    let decisions = events(&engine, "app_claim_decided");
+    assert_eq!(decisions.len(), decided(&scams).len());
+}
+
+/// A full policy pays every claim less the excess; a none policy pays
+/// nothing and the declined victims complain.
+#[test]
+fn policy_decides_reimbursement() {
+    let config = SimConfig::default_test().app_fraud;
+    let mut full = engine("app-full-test", "full");
+    full.run_ticks(TICKS).unwrap();
+    let scams = full.store.app_scam_payments(&full.run_id).unwrap();
+    for s in decided(&scams) {
+        assert_eq!(s.claim_status.as_deref(), Some("reimbursed"));
+        assert_eq!(s.policy.as_deref(), Some("full"));
+        assert!((s.reimbursed - (s.amount - config.excess).min(config.reimbursement_cap)).abs() < 1e-6);
+    }
+
+    let mut none = engine("app-none-test", "none");
+    none.run_ticks(TICKS).unwrap();
+    let scams = none.store.app_scam_payments(&none.run_id).unwrap();
+    let declined = decided(&scams);
+    assert!(!declined.is_empty());
+    assert!(declined.iter().all(|s| s.claim_status.as_deref() == Some("declined") && s.reimbursed == 0.0));
+
+    let complaints = none.store.open_complaints(&none.run_id).unwrap();
+    let victims: Vec<_> = declined.iter().map(|s| s.customer_id.as_str()).collect();
+    assert!(complaints.iter().any(|c| c.issue == "unauthorized_transaction" && victims.contains(&c.customer_id.as_str())));
+}
+
+/// Switching the policy mid-run applies to every claim decided after.
+#[test]
+fn policy_command_changes_outcomes() {
+    let mut engine = engine("app-policy-test", "none");
+    engine.run_ticks(20).unwrap();
+    engine
+        .submit_command(PlayerCommand::SetAppReimbursementPolicy { policy: "full".into() })
+        .unwrap();
+    engine.run_ticks(TICKS - 20).unwrap();
+
+    let changed = events(&engine, "app_reimbursement_policy_changed");
+    assert_eq!(changed.len(), 1);
+    let switched = changed[0]["tick"].as_u64().unwrap();
+    let scams = engine.store.app_scam_payments(&engine.run_id).unwrap();
+    let (before, after): (Vec<_>, Vec<_>) =
+        decided(&scams).into_iter().partition(|s| s.decided_tick.unwrap() < switched);
+    assert!(!before.is_empty() && !after.is_empty());
+    assert!(before.iter().all(|s| s.claim_status.as_deref() == Some("declined")));
+    assert!(after.iter().all(|s| s.claim_status.as_deref() == Some("reimbursed")));
+}
+
+/// The policy set by the player survives a resume, whether restored
+/// from a snapshot taken after the change or replayed from the ticks
+/// logged since the last one.
+#[test]
+fn policy_survives_resume() {
+    let run_id = "app-resume-test";
+    let db = "test_app_resume.db";
+    let _ = std::fs::remove_file(db);
+    let store = SimStore::open(db).unwrap();
+    store.migrate().unwrap();
+    store.insert_run(run_id, 42, "0.1.0-test").unwrap();
+    let mut config = SimConfig::default_test();
+    config.app_fraud.enabled = true;
+    config.app_fraud.rails = vec!["RTP".into()];
+    config.app_fraud.scam_rate = 0.05;
+    config.app_fraud.fall_rate = 1.0;
+    config.app_fraud.report_rate = 1.0;
+    config.app_fraud.report_max_ticks = 3;
+    config.app_fraud.decision_ticks = 2;
+    config.app_fraud.reimbursement_policy = "none".into();
+
+    // Changed on tick 21, before the tick-30 snapshot
+    let mut engine = SimEngine::build_with_config(run_id.into(), 42, &store, config.clone()).unwrap();
+    engine.run_ticks(20).unwrap();
+    engine
+        .submit_command(PlayerCommand::SetAppReimbursementPolicy { policy: "full".into() })
+        .unwrap();
+    engine.run_ticks(20).unwrap();
+    drop(engine);
+
+    // Changed on tick 51, after it
+    let mut engine = SimEngine::resume_with_config(run_id.into(), &store, config.clone()).unwrap();
+    engine.run_ticks(10).unwrap();
+    engine
+        .submit_command(PlayerCommand::SetAppReimbursementPolicy { policy: "vulnerable".into() })
+        .unwrap();
+    engine.run_ticks(9).unwrap();
+    drop(engine);
+
+    let mut engine = SimEngine::resume_with_config(run_id.into(), &store, config).unwrap();
+    engine.run_ticks(21).unwrap();
+    let scams = engine.store.app_scam_payments(run_id).unwrap();
+    let policies: Vec<(u64, &str)> =
+        decided(&scams).into_iter().map(|s| (s.decided_tick.unwrap(), s.policy.as_deref().unwrap())).collect();
+    for (from, to, policy) in [(41, 50, "full"), (51, 80, "vulnerable")] {
+        let window: Vec<_> = policies.iter().filter(|(t, _)| (from..=to).contains(t)).collect();
+        assert!(!window.is_empty(), "no claims decided over {from}..={to}");
+        assert!(window.iter().all(|(_, p)| *p == policy), "{window:?} not all {policy}");
+    }
+}
+
+/// Instant payments are final: the desk cannot recall one.
+#[test]
+fn instant_payments_cannot_be_recalled() {
+    let mut engine = engine("app-recall-test", "none");
+    engine.run_ticks(20).unwrap();
+    let scam = engine.store.app_scam_payments(&engine.run_id).unwrap().remove(0);
+    engine.submit_command(PlayerCommand::RecallWire { txn_id: scam.txn_id.clone() }).unwrap();
+    engine.run_ticks(1).unwrap();
+
+    let rejected = events(&engine, "wire_recall_rejected");
+    assert_eq!(rejected.len(), 1);
+    assert_eq!(rejected[0]["txn_id"], scam.txn_id);
+    assert_eq!(rejected[0]["reason"], "instant payments are irrevocable");
+}
+
+/// The quarter's reimbursements are charged to the P&L and come off
+/// pre-tax profit alongside the other losses.
+#[test]
+fn reimbursements_charged_to_pnl() {
+    let mut engine = engine("app-pnl-test", "full");
+    engine.run_ticks(90).unwrap();
+    let pnl = engine.store_latest_pnl(&engine.run_id).unwrap().expect("Should have P&L");
+    let scams = engine.store.app_scam_payments(&engine.run_id).unwrap();
+    let quarter: Vec<_> =
+        scams.iter().filter(|s| s.decided_tick.is_some_and(|t| t + 89 >= pnl.tick && t <= pnl.tick)).collect();
+    let reimbursed: f64 = quarter.iter().map(|s| s.reimbursed).sum();
+    let borne: f64 = quarter.iter().map(|s| s.amount - s.reimbursed).sum();
+    assert!(reimbursed > 0.0);
+    assert!((pnl.app_fraud_loss - reimbursed).abs() < 1e-6, "{} vs {reimbursed}", pnl.app_fraud_loss);
+    assert!((pnl.app_customer_loss - borne).abs() < 1e-6);
+    let expected = pnl.gross_income - pnl.credit_loss - pnl.fraud_loss - pnl.app_fraud_loss - pnl.opex;
+    assert!((pnl.pre_tax_profit - expected).abs() < 1e-6);
+}
@@ -0,0 +1,141 @@
+//! ATM network tests — Phase 4.92.
+//!
+//! Tests cover: no fleet unless enabled, and every withdrawal on the ATM
+//! channel made at one of the fleet's ATMs; device faults logged as
+//! incidents, with no withdrawals at an ATM while it is out and every
+//! ATM back within the repair time; cash-outs hitting one ATM with every
+//! cloned card; and ATM_MULTI_DEVICE alerting the cards taken on to
+//! other ATMs.
+
+use fincrime_core::{atm, engine::SimEngine};
+use serde_json::Value;
+use std::collections::{BTreeMap, BTreeSet};
+
+const TICKS: u64 = 30;
+
+fn engine(run_id: &str) -> SimEngine {
+    SimEngine::build_test_with_atm_network(run_id.into(), 42).unwrap()
+}
+
+/// Payloads of every `event_type` event so far, in order.
+fn events(engine: &SimEngine, event_type: &str) -> Vec<Value> {
+    (0..=engine.clock.current_tick)
+        .flat_map(|tick| engine.store.events_for_tick(&engine.run_id, tick).unwrap())
+        .filter(|e| e.event_type == event_type)
+        .map(|e| serde_json::from_str(&e.payload).unwrap())
+        .collect()
+}
+
+/// Off by default; on, the ATM channel's withdrawals are each made at an
+/// ATM of the fleet.
+#[test]
+fn withdrawals_made_at_atms() {
+    let mut plain = SimEngine::build_test("atm-off-test".into(), 42).unwrap();
+    plain.run_ticks(5).unwrap();
+    assert!(plain.store.atms(&plain.run_id).unwrap().is_empty());
+
+    let mut engine = engine("atm-withdrawals-test");
+    engine.run_ticks(TICKS).unwrap();
+    let fleet: BTreeSet<String> = engine.store.atms(&engine.run_id).unwrap().into_iter().map(|a| a.atm_id).collect();
+    assert_eq!(fleet.len(), 8);
+
+    let completed: BTreeMap<u64, i64> = engine
+        .store
+        .channel_activity(&engine.run_id)
+        .unwrap()
+        .into_iter()
+        .filter(|r| r.channel == "atm")
+        .map(|r| (r.tick, r.completed))
+        .collect();
+    let mut total = 0;
+    for tick in 1..=TICKS {
+        let activity = engine.store.atm_activity(&engine.run_id, tick).unwrap();
+        assert!(activity.iter().all(|a| fleet.contains(&a.atm_id)));
+        let withdrawals: i64 = activity.iter().map(|a| a.withdrawals).sum();
+        assert_eq!(withdrawals, completed[&tick], "tick {tick}");
+        total += withdrawals;
+    }
+    assert!(total > 20, "only {total} ATM withdrawals");
+}
+
+/// A fault is an incident against the fleet; the ATM takes no
+/// withdrawals until it is resolved, at most two ticks later.
+#[test]
+fn faults_take_atms_out_of_service() {
+    let mut engine = engine("atm-faults-test");
+    engine.run_ticks(TICKS).unwrap();
+    let out = events(&engine, "atm_out_of_service");
+    let back = events(&engine, "atm_back_in_service");
+    assert!(out.len() >= 3, "only {} faults", out.len());
+
+    for fault in &out {
+        let incident_id = fault["incident_id"].as_str().unwrap();
+        let atm_id = fault["atm_id"].as_str().unwrap();
+        let from = fault["tick"].as_u64().unwrap();
+        let Some(until) = back.iter().find(|b| b["incident_id"] == incident_id).map(|b| b["tick"].as_u64().unwrap())
+        else {
+            assert!(from + 2 > TICKS, "{atm_id} never back in service");
+            continue;
+        };
+        assert!(until > from && until <= from + 2);
+        assert!(engine.store.incident_resolved(&engine.run_id, incident_id).unwrap());
+        for tick in from..until {
+            let activity = engine.store.atm_activity(&engine.run_id, tick).unwrap();
+            assert!(activity.iter().all(|a| a.atm_id != atm_id), "{atm_id} used while out on {tick}");
+        }
+    }
+}
+
+/// A crew hits one ATM with every cloned card it carries.
+#[test]
+fn cash_out_hits_one_atm() {
+    let mut engine = engine("atm-cash-out-test");
+    engine.run_ticks(TICKS).unwrap();
+    let bursts = events(&engine, "atm_cash_out");
+    assert!(bursts.len() >= 3, "only {} cash-outs", bursts.len());
+    let rows = engine.store.atm_cash_outs(&engine.run_id).unwrap();
+
+    for burst in &bursts {
+        let id = burst["cash_out_id"].as_str().unwrap();
+        let target = burst["atm_id"].as_str().unwrap();
+        let tick = burst["tick"].as_u64().unwrap();
+        let mine: Vec<_> = rows.iter().filter(|r| r.cash_out_id == id).collect();
+        assert_eq!(mine.len() as i64, burst["withdrawals"].as_i64().unwrap());
+        let cards: BTreeSet<&str> = mine.iter().map(|r| r.account_id.as_str()).collect();
+        assert_eq!(cards.len() as i64, burst["cards"].as_i64().unwrap());
+        for card in &cards {
+            assert!(mine.iter().any(|r| r.account_id == *card && r.atm_id == target));
+        }
+
+        let hit = engine.store.atm_activity(&engine.run_id, tick).unwrap().into_iter().find(|a| a.atm_id == target);
+        assert!(hit.is_some_and(|a| a.cards >= cards.len() as i64));
+    }
+}
+
+/// Cards taken on to other ATMs after the hit are alerted that tick,
+/// and nothing is alerted without enough ATMs behind it.
+#[test]
+fn cards_at_many_atms_alerted() {
+    let mut engine = engine("atm-alerts-test");
+    engine.run_ticks(TICKS).unwrap();
+    let alerted: BTreeSet<(u64, String)> = events(&engine, "fraud_alert_generated")
+        .into_iter()
+        .filter(|e| e["alert_type"] == atm::MULTI_DEVICE)
+        .map(|e| (e["tick"].as_u64().unwrap(), e["entity_id"].as_str().unwrap().to_string()))
+        .collect();
+    assert!(!alerted.is_empty());
+
+    let mut atms_used: BTreeMap<(u64, String), BTreeSet<String>> = BTreeMap::new();
+    for row in engine.store.atm_cash_outs(&engine.run_id).unwrap() {
+        atms_used.entry((row.tick, row.account_id)).or_default().insert(row.atm_id);
+    }
+    for (card, atms) in &atms_used {
+        if atms.len() >= 3 {
+            assert!(alerted.contains(card), "{card:?} not alerted");
+        }
+    }
+    for (tick, account_id) in &alerted {
+        let many = engine.store.cards_at_many_atms(&engine.run_id, *tick, 3).unwrap();
+        assert!(many.iter().any(|(a, n)| a == account_id && *n >= 3));
+    }
+}
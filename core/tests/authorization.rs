@@ -0,0 +1,145 @@
+//! Command authorization tests — Phase 4.46.
+//!
+//! Tests cover: gates off by default, commands reserved for a role,
+//! refund write-off limits per role, risk dials outside their comfort
+//! zone, and the role riding on the IPC `command` message, with every
+//! gated decision in the audit log.
+
+use fincrime_core::{
+    authorization::Role,
+    command_result::{CommandResult, RejectionCode},
+    engine::SimEngine,
+    error::SimError,
+    protocol::{self, IpcCommand, UiState},
+};
+use serde_json::json;
+
+fn engine(run_id: &str) -> SimEngine {
+    let mut engine = SimEngine::build_test_with_authorization(run_id.into(), 42).unwrap();
+    engine.run_ticks(10).unwrap();
+    engine
+}
+
+fn refund(engine: &SimEngine, amount: f64) -> serde_json::Value {
+    let complaint = &UiState::from_engine(engine).unwrap().complaints[0];
+    json!({ "complaint_id": complaint.complaint_id, "resolution": "monetary_relief", "refund": amount })
+}
+
+/// Without gates any seat sends anything and nothing is audited.
+#[test]
+fn gates_off_by_default() {
+    let mut engine = SimEngine::build_test("auth-off-test".into(), 42).unwrap();
+    engine.run_ticks(10).unwrap();
+    assert!(engine.authorizer().is_none());
+
+    let result = CommandResult::apply(&mut engine, "set_sar_threshold", &json!({ "threshold": 90.0 }), None).unwrap();
+    assert!(result.accepted, "{:?}", result.errors);
+    let payload = refund(&engine, 10_000.0);
+    assert!(CommandResult::apply(&mut engine, "resolve_complaint", &payload, Some(Role::Analyst)).unwrap().accepted);
+    assert!(engine.store.authorization_audit(&engine.run_id).unwrap().is_empty());
+}
+
+/// A command reserved for the CCO is refused to an analyst, or a sender
+/// naming no role, and accepted from the CCO; both decisions are logged.
+#[test]
+fn reserved_commands_need_their_role() {
+    let mut engine = engine("auth-reserved-test");
+    let payload = json!({ "threshold": 90.0 });
+
+    let refused = CommandResult::apply(&mut engine, "set_sar_threshold", &payload, None).unwrap();
+    assert!(!refused.accepted);
+    assert_eq!(refused.code, Some(RejectionCode::Unauthorized));
+    assert_eq!(refused.errors[0].field, "role");
+    assert!(refused.errors[0].message.contains("cco"), "{:?}", refused.errors);
+    assert!(refused.command_id.is_none());
+
+    let manager = CommandResult::apply(&mut engine, "set_sar_threshold", &payload, Some(Role::Manager)).unwrap();
+    assert_eq!(manager.code, Some(RejectionCode::Unauthorized));
+    assert!(CommandResult::apply(&mut engine, "set_sar_threshold", &payload, Some(Role::Cco)).unwrap().accepted);
+    assert!(CommandResult::apply(&mut engine, "pause", &json!({}), None).unwrap().accepted, "ungated");
+
+    let audit = engine.store.authorization_audit(&engine.run_id).unwrap();
+    let decisions: Vec<_> = audit.iter().map(|a| (a.role, a.required_role, a.allowed)).collect();
+    assert_eq!(
+        decisions,
+        [
+            (Role::Analyst, Role::Cco, false),
+            (Role::Manager, Role::Cco, false),
+            (Role::Cco, Role::Cco, true),
+        ]
+    );
+    assert!(audit.iter().all(|a| a.command == "set_sar_threshold" && a.tick == 10));
+}
+
+/// Refunds up to the analyst limit are anyone's; above it a manager's,
+/// and above the manager limit only the CCO's.
+#[test]
+fn refund_write_off_limits() {
+    let mut engine = engine("auth-refund-test");
+
+    let small = refund(&engine, 100.0);
+    assert!(CommandResult::apply(&mut engine, "resolve_complaint", &small, None).unwrap().accepted);
+    assert!(engine.store.authorization_audit(&engine.run_id).unwrap().is_empty(), "no gate applied");
+
+    let medium = refund(&engine, 500.0);
+    let refused = CommandResult::apply(&mut engine, "resolve_complaint", &medium, Some(Role::Analyst)).unwrap();
+    assert_eq!(refused.code, Some(RejectionCode::Unauthorized));
+    assert!(refused.errors[0].message.contains("write-off limit"), "{:?}", refused.errors);
+    assert!(CommandResult::apply(&mut engine, "resolve_complaint", &medium, Some(Role::Manager)).unwrap().accepted);
+
+    let large = refund(&engine, 3_000.0);
+    let refused = CommandResult::apply(&mut engine, "resolve_complaint", &large, Some(Role::Manager)).unwrap();
+    assert_eq!(refused.code, Some(RejectionCode::Unauthorized));
+    assert!(CommandResult::apply(&mut engine, "resolve_complaint", &large, Some(Role::Cco)).unwrap().accepted);
+
+    let required: Vec<_> = engine
+        .store
+        .authorization_audit(&engine.run_id)
+        .unwrap()
+        .iter()
+        .map(|a| (a.required_role, a.allowed))
+        .collect();
+    assert_eq!(
+        required,
+        [(Role::Manager, false), (Role::Manager, true), (Role::Cco, false), (Role::Cco, true)]
+    );
+}
+
+/// A dial set inside its comfort zone is an analyst's call; outside it
+/// needs a manager.
+#[test]
+fn dials_outside_comfort_zone_need_a_manager() {
+    let mut engine = engine("auth-dial-test");
+    let authorizer = engine.authorizer().unwrap();
+    let inside = json!({ "dial_id": "fee_aggressiveness", "new_value": 1.2 });
+    let outside = json!({ "dial_id": "fee_aggressiveness", "new_value": 1.8 });
+    assert_eq!(authorizer.requirement("set_risk_dial", &inside), None);
+    let requirement = authorizer.requirement("set_risk_dial", &outside).unwrap();
+    assert_eq!(requirement.role, Role::Manager);
+    assert!(requirement.reason.contains("comfort zone"), "{}", requirement.reason);
+
+    assert!(CommandResult::apply(&mut engine, "set_risk_dial", &inside, None).unwrap().accepted);
+    let refused = CommandResult::apply(&mut engine, "set_risk_dial", &outside, None).unwrap();
+    assert_eq!(refused.code, Some(RejectionCode::Unauthorized));
+    let accepted = CommandResult::apply(&mut engine, "set_risk_dial", &outside, Some(Role::Manager)).unwrap();
+    assert!(accepted.accepted);
+    assert_eq!(accepted.entity_ids, ["fee_aggressiveness"]);
+}
+
+/// The role is an optional field of the IPC `command` message; callers
+/// without one, such as the gRPC server, act as the default role.
+#[test]
+fn role_on_ipc_command() {
+    let parse = |line: &str| serde_json::from_str::<IpcCommand>(line);
+    let with_role = parse(r#"{"type":"command","cmd":"pause","payload":{},"role":"manager"}"#).unwrap();
+    assert!(matches!(with_role, IpcCommand::Command { role: Some(Role::Manager), .. }));
+    let without = parse(r#"{"type":"command","cmd":"pause","payload":{}}"#).unwrap();
+    assert!(matches!(without, IpcCommand::Command { role: None, .. }));
+    assert!(parse(r#"{"type":"command","cmd":"pause","payload":{},"role":"intern"}"#).is_err());
+
+    let mut engine = engine("auth-ipc-test");
+    assert_eq!(engine.authorizer().unwrap().default_role(), Role::Analyst);
+    let err = protocol::apply_command(&mut engine, "set_sar_threshold", &json!({ "threshold": 90.0 })).unwrap_err();
+    assert!(matches!(err, SimError::Command { .. }), "{err}");
+    assert!(err.to_string().contains("role: needs cco"), "{err}");
+}
@@ -0,0 +1,105 @@
+//! Batch run tests — Phase 4.78.
+//!
+//! Tests cover: a database kept per seed with the KPI file beside them,
+//! one line per seed in seed order; each kept database holding a run
+//! that can be read back; the KPIs agreeing with the store they came
+//! from; reruns replacing the databases they find; and the wide CSV
+//! leaving a metric a run did not report empty.
+
+use fincrime_core::{
+    batch::{self, KPI_FILE},
+    config::SimConfig,
+    experiment::{run_db_path, ResultRow, ResultsFrame, Scenario},
+    store::SimStore,
+};
+use std::path::PathBuf;
+
+const TICKS: u64 = 6;
+
+fn out_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("batch_{name}_{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    dir
+}
+
+fn scenario() -> Scenario {
+    Scenario::new("sweep", SimConfig::default_test(), TICKS)
+}
+
+/// Every seed gets its own database and a line in the KPI file, in seed
+/// order whichever worker finished first.
+#[test]
+fn each_seed_gets_a_database_and_a_kpi_line() {
+    let dir = out_dir("layout");
+    let frame = batch::run_batch(scenario(), vec![3, 1, 2], 2, &dir).unwrap();
+    assert!(frame.failures.is_empty(), "{:?}", frame.failures);
+    for seed in 1..=3 {
+        assert!(run_db_path(&dir, "sweep", seed).exists(), "seed {seed} database");
+    }
+
+    let csv = std::fs::read_to_string(dir.join(KPI_FILE)).unwrap();
+    let lines: Vec<&str> = csv.lines().collect();
+    assert_eq!(lines[0], "scenario,seed,churn_rate,pre_tax_profit,sla_breaches,sar_count");
+    assert_eq!(lines.len(), 4);
+    let seeds: Vec<&str> = lines[1..].iter().map(|l| l.split(',').nth(1).unwrap()).collect();
+    assert_eq!(seeds, ["1", "2", "3"]);
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+/// A kept database holds the finished run under its experiment run id.
+#[test]
+fn kept_database_holds_the_run() {
+    let dir = out_dir("kept");
+    batch::run_batch(scenario(), vec![5], 1, &dir).unwrap();
+    let store = SimStore::open(&run_db_path(&dir, "sweep", 5).to_string_lossy()).unwrap();
+    let run = store.describe_run("exp-sweep-5").unwrap().expect("run stored");
+    assert_eq!(run.seed, 5);
+    assert!(store.onboarded_customer_count("exp-sweep-5").unwrap() > 0);
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+/// The KPIs in the file are the ones the run's own store reports.
+#[test]
+fn kpis_match_the_stored_run() {
+    let dir = out_dir("kpis");
+    let frame = batch::run_batch(scenario(), vec![9], 1, &dir).unwrap();
+    let store = SimStore::open(&run_db_path(&dir, "sweep", 9).to_string_lossy()).unwrap();
+    let run_id = "exp-sweep-9";
+
+    let profit: f64 = store.all_pnl_snapshots(run_id).unwrap().iter().map(|p| p.pre_tax_profit).sum();
+    assert!((frame.values("sweep", "pre_tax_profit")[0] - profit).abs() < 1e-6);
+    assert_eq!(frame.values("sweep", "sla_breaches"), [store.sla_breach_count(run_id).unwrap() as f64]);
+    let churn = store.churned_customer_count(run_id).unwrap() as f64
+        / store.onboarded_customer_count(run_id).unwrap() as f64;
+    assert_eq!(frame.values("sweep", "churn_rate"), [churn]);
+    assert_eq!(frame.values("sweep", "sar_count"), [store.count_sars_in_window(run_id, 0, TICKS).unwrap() as f64]);
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+/// Running the same batch into the same directory replaces its
+/// databases rather than failing on them, and gives the same KPIs.
+#[test]
+fn rerun_replaces_existing_databases() {
+    let dir = out_dir("rerun");
+    let first = batch::run_batch(scenario(), vec![4], 1, &dir).unwrap();
+    let second = batch::run_batch(scenario(), vec![4], 1, &dir).unwrap();
+    assert!(second.failures.is_empty(), "{:?}", second.failures);
+    assert_eq!(first.rows, second.rows);
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+/// A metric only some runs report leaves the others' cells empty.
+#[test]
+fn wide_csv_leaves_missing_metrics_empty() {
+    let row = |seed, metric: &str, value| ResultRow {
+        scenario: "s".into(),
+        seed,
+        metric: metric.into(),
+        value,
+    };
+    let frame = ResultsFrame {
+        rows: vec![row(1, "a", 1.0), row(1, "b", 2.5), row(2, "a", 3.0)],
+        failures: vec![],
+    };
+    assert_eq!(frame.to_wide_csv(), "scenario,seed,a,b\ns,1,1,2.5\ns,2,3,\n");
+}
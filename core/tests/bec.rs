@@ -0,0 +1,183 @@
+//! Business email compromise tests — Phase 4.53.
+//!
+//! Tests cover: no invoice wires unless enabled, and spoofed ones paid
+//! to fresh payees while genuine ones mostly go to regular vendors; BEC_NEW_PAYEE alerting on the
+//! anomalous spoofed wires; recovery odds falling with every day of
+//! delay; the fraud desk recalling alerted wires on its own; and recalls
+//! of unknown or already recalled wires rejected.
+
+use fincrime_core::{
+    bec::BusinessEmailCompromise, command::PlayerCommand, config::SimConfig, engine::SimEngine,
+    store::bec::InvoiceWireRow,
+};
+use serde_json::Value;
+
+const TICKS: u64 = 30;
+
+fn engine(run_id: &str, desk_recall_ticks: Option<u64>) -> SimEngine {
+    SimEngine::build_test_with_bec(run_id.into(), 42, desk_recall_ticks).unwrap()
+}
+
+/// Payloads of every `event_type` event so far, in order.
+fn events(engine: &SimEngine, event_type: &str) -> Vec<Value> {
+    (0..=engine.clock.current_tick)
+        .flat_map(|tick| engine.store.events_for_tick(&engine.run_id, tick).unwrap())
+        .filter(|e| e.event_type == event_type)
+        .map(|e| serde_json::from_str(&e.payload).unwrap())
+        .collect()
+}
+
+/// Off by default; on, business customers wire spoofed invoices to
+/// payees they have never paid and genuine ones mostly to vendors they
+/// pay again and again.
+#[test]
+fn invoices_paid_to_fresh_payees() {
+    let mut plain = SimEngine::build_test("bec-off-test".into(), 42).unwrap();
+    plain.run_ticks(TICKS).unwrap();
+    assert!(plain.store.invoice_wires(&plain.run_id).unwrap().is_empty());
+    assert!(events(&plain, "invoice_wires_booked").is_empty());
+
+    let mut engine = engine("bec-invoices-test", None);
+    engine.run_ticks(TICKS).unwrap();
+    let wires = engine.store.invoice_wires(&engine.run_id).unwrap();
+    let fake = wires.iter().filter(|w| w.spoofed).count();
+    assert!(fake >= 3 && wires.len() - fake >= 3, "{fake} spoofed of {}", wires.len());
+    for w in &wires {
+        assert_eq!(engine.store.customer_segment(&engine.run_id, &w.customer_id).unwrap(), "small_business");
+        assert!(w.amount >= 250.0);
+    }
+    let paid_before = |w: &InvoiceWireRow| {
+        wires.iter().any(|o| o.tick < w.tick && o.account_id == w.account_id && o.counterparty == w.counterparty)
+    };
+    assert!(wires.iter().filter(|w| w.spoofed).all(|w| !paid_before(w)));
+    assert!(wires.iter().any(|w| !w.spoofed && paid_before(w)));
+
+    let booked = events(&engine, "invoice_wires_booked");
+    let total: f64 = booked.iter().map(|e| e["amount"].as_f64().unwrap()).sum();
+    let amount: f64 = wires.iter().map(|w| w.amount).sum();
+    assert!((total - amount).abs() < 1e-6);
+    let received: i64 = booked.iter().map(|e| e["spoofed_invoices"].as_i64().unwrap()).sum();
+    assert!(received as usize >= fake);
+}
+
+/// Spoofed invoices, far larger than anything the business usually pays,
+/// raise BEC_NEW_PAYEE alerts on the wire; the rule scores bigger jumps
+/// higher.
+#[test]
+fn new_payee_rule_flags_spoofed_wires() {
+    let mut engine = engine("bec-alert-test", None);
+    engine.run_ticks(TICKS).unwrap();
+    let alerts: Vec<Value> = events(&engine, "fraud_alert_generated")
+        .into_iter()
+        .filter(|e| e["alert_type"] == "bec_new_payee")
+        .collect();
+    let recalls = engine.store.wire_recalls(&engine.run_id).unwrap();
+    assert_eq!(alerts.len(), recalls.len());
+
+    let alerted_share = |wires: Vec<InvoiceWireRow>| {
+        let alerted = wires.iter().filter(|w| recalls.iter().any(|r| r.txn_id == w.txn_id)).count();
+        alerted as f64 / wires.len() as f64
+    };
+    let (wires, genuine): (Vec<_>, Vec<_>) =
+        engine.store.invoice_wires(&engine.run_id).unwrap().into_iter().partition(|w| w.spoofed);
+    let (fake, real) = (alerted_share(wires), alerted_share(genuine));
+    assert!(fake > 0.0 && fake > real, "{fake} of spoofed wires alerted vs {real} of genuine");
+    for alert in &alerts {
+        let recall = recalls.iter().find(|r| r.txn_id == alert["entity_id"].as_str().unwrap()).unwrap();
+        assert_eq!(recall.alerted_tick, Some(recall.wire_tick));
+        assert_eq!(recall.alerted_tick, alert["tick"].as_u64());
+        assert!(alert["fraud_score"].as_f64().unwrap() >= 0.6);
+        assert_eq!(recall.recall_tick, None);
+    }
+}
+
+/// Recovery odds fall with every tick of delay and are gone past the
+/// window: recalling each alerted wire the tick after its alert gets
+/// money back, recalling them all at the end gets none from old wires.
+#[test]
+fn recovery_depends_on_recall_speed() {
+    let bec = BusinessEmailCompromise::new("bec-odds".into(), SimConfig::default_test().bec);
+    let odds: Vec<f64> = (0..=11).map(|t| bec.recovery_chance(t)).collect();
+    assert!(odds[..=10].windows(2).all(|w| w[1] < w[0]), "{odds:?}");
+    assert_eq!((odds[0], odds[11]), (0.85, 0.0));
+
+    let mut prompt = engine("bec-prompt-test", None);
+    for _ in 0..TICKS {
+        prompt.run_ticks(1).unwrap();
+        let tick = prompt.clock.current_tick;
+        for r in prompt.store.wire_recalls(&prompt.run_id).unwrap() {
+            if r.alerted_tick == Some(tick) {
+                prompt.submit_command(PlayerCommand::RecallWire { txn_id: r.txn_id }).unwrap();
+            }
+        }
+    }
+    prompt.run_ticks(1).unwrap();
+
+    let mut late = engine("bec-late-test", None);
+    late.run_ticks(TICKS).unwrap();
+    for r in late.store.wire_recalls(&late.run_id).unwrap() {
+        late.submit_command(PlayerCommand::RecallWire { txn_id: r.txn_id }).unwrap();
+    }
+    late.run_ticks(1).unwrap();
+
+    let recovered = |engine: &SimEngine| -> f64 {
+        events(engine, "wire_recall_resolved").iter().map(|e| e["recovered"].as_f64().unwrap()).sum()
+    };
+    let resolved = events(&prompt, "wire_recall_resolved");
+    assert!(resolved.iter().all(|e| e["ticks_elapsed"] == 1));
+    assert!(recovered(&prompt) > recovered(&late), "{} vs {}", recovered(&prompt), recovered(&late));
+    for e in events(&late, "wire_recall_resolved") {
+        if e["ticks_elapsed"].as_u64().unwrap() > 10 {
+            assert_ne!(e["outcome"], "recovered", "{e}");
+        }
+    }
+}
+
+/// With the desk on, every alerted wire is recalled the configured number
+/// of ticks later; genuine payments come back as not fraud.
+#[test]
+fn desk_recalls_alerted_wires() {
+    let mut engine = engine("bec-desk-test", Some(3));
+    engine.run_ticks(TICKS).unwrap();
+    let recalls = engine.store.wire_recalls(&engine.run_id).unwrap();
+    assert!(!recalls.is_empty());
+    for r in &recalls {
+        let due = r.alerted_tick.unwrap() + 3;
+        if due > TICKS {
+            assert_eq!(r.recall_tick, None);
+            continue;
+        }
+        assert_eq!(r.recall_tick, Some(due), "{r:?}");
+        let spoofed = engine.store.invoice_wire(&engine.run_id, &r.txn_id).unwrap().is_some_and(|w| w.spoofed);
+        match r.outcome.as_deref().unwrap() {
+            "not_fraud" => assert!(!spoofed && r.recovered == 0.0),
+            "recovered" => assert!(spoofed && r.recovered == r.amount),
+            "funds_gone" => assert!(spoofed && r.recovered == 0.0),
+            other => panic!("{other}"),
+        }
+    }
+    let resolved = events(&engine, "wire_recall_resolved");
+    assert_eq!(resolved.len(), recalls.iter().filter(|r| r.recall_tick.is_some()).count());
+    assert!(resolved.iter().all(|e| e["ticks_elapsed"] == 3));
+}
+
+/// Recalling a wire that does not exist, or one already recalled, is
+/// rejected without touching the account.
+#[test]
+fn bad_recalls_rejected() {
+    let mut engine = engine("bec-reject-test", None);
+    engine.run_ticks(TICKS).unwrap();
+    let wire = engine.store.invoice_wires(&engine.run_id).unwrap().remove(0);
+    engine.submit_command(PlayerCommand::RecallWire { txn_id: "no-such-wire".into() }).unwrap();
+    engine.submit_command(PlayerCommand::RecallWire { txn_id: wire.txn_id.clone() }).unwrap();
+    engine.run_ticks(1).unwrap();
+    engine.submit_command(PlayerCommand::RecallWire { txn_id: wire.txn_id.clone() }).unwrap();
+    engine.run_ticks(1).unwrap();
+
+    let rejected = events(&engine, "wire_recall_rejected");
+    let reasons: Vec<_> = rejected.iter().map(|e| e["reason"].as_str().unwrap()).collect();
+    assert_eq!(reasons, ["not an outgoing wire", "already recalled"]);
+    let resolved = events(&engine, "wire_recall_resolved");
+    assert_eq!(resolved.len(), 1);
+    assert_eq!(resolved[0]["txn_id"], wire.txn_id.as_str());
+}
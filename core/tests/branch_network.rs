@@ -0,0 +1,160 @@
+//! Branch network subsystem tests — Phase 4.3.
+//!
+//! Tests cover: seeding and customer assignment, closing a branch via
+//! player command, reopening a market, rejecting unknown markets, the
+//! monthly performance report and branch acquisitions.
+
+use fincrime_core::{
+    branch_subsystem::DIGITAL_BRANCH_ID, command::PlayerCommand, engine::SimEngine,
+};
+
+fn build_with_branches(run_id: &str, seed: u64) -> SimEngine {
+    SimEngine::build_test_with_branches(run_id.to_string(), seed)
+        .expect("build test engine with branches")
+}
+
+/// Every onboarded customer has a home branch (physical or digital).
+#[test]
+fn branches_seeded_and_customers_assigned() {
+    let run_id = "branch-seed-test";
+    let mut engine = build_with_branches(run_id, 0xB4A1_0001);
+
+    engine.run_ticks(1).unwrap();
+
+    let branches = engine.store_open_branches(run_id).unwrap();
+    assert!(!branches.is_empty(), "Expected at least one seeded branch");
+    assert!(
+        branches.iter().all(|b| b.staff_count >= 3),
+        "Every branch should have at least the minimum staff"
+    );
+
+    let mut assigned = engine.store_customer_branch_count(run_id, DIGITAL_BRANCH_ID).unwrap();
+    for b in &branches {
+        assigned += engine.store_customer_branch_count(run_id, &b.branch_id).unwrap();
+    }
+    assert_eq!(assigned, 50, "All 50 test customers should have a home branch");
+}
+
+/// Closing a branch moves its customers to digital and records the closure.
+#[test]
+fn close_branch_moves_customers_to_digital() {
+    let run_id = "branch-close-test";
+    let mut engine = build_with_branches(run_id, 0xB4A1_0002);
+    engine.run_ticks(1).unwrap();
+
+    let branch = engine.store_open_branches(run_id).unwrap().remove(0);
+    let book = engine.store_customer_branch_count(run_id, &branch.branch_id).unwrap();
+    let digital_before = engine.store_customer_branch_count(run_id, DIGITAL_BRANCH_ID).unwrap();
+
+    engine
+        .submit_command(PlayerCommand::CloseBranch { branch_id: branch.branch_id.clone() })
+        .unwrap();
+    engine.run_ticks(1).unwrap();
+
+    assert_eq!(engine.store_branch_count(run_id, "closed").unwrap(), 1);
+    let digital_after = engine.store_customer_branch_count(run_id, DIGITAL_BRANCH_ID).unwrap();
+    assert!(
+        digital_after >= digital_before + book - 1,
+        "Expected ~{book} customers moved to digital ({digital_before} -> {digital_after})"
+    );
+
+    // Closing the same branch again is rejected, not double-counted.
+    engine
+        .submit_command(PlayerCommand::CloseBranch { branch_id: branch.branch_id })
+        .unwrap();
+    engine.run_ticks(1).unwrap();
+    assert_eq!(engine.store_branch_count(run_id, "closed").unwrap(), 1);
+}
+
+/// Reopening a market after a closure creates a new branch id.
+#[test]
+fn open_branch_in_vacated_market() {
+    let run_id = "branch-open-test";
+    let mut engine = build_with_branches(run_id, 0xB4A1_0003);
+    engine.run_ticks(1).unwrap();
+
+    let branch = engine.store_open_branches(run_id).unwrap().remove(0);
+    let open_before = engine.store_branch_count(run_id, "open").unwrap();
+
+    engine
+        .submit_command(PlayerCommand::CloseBranch { branch_id: branch.branch_id.clone() })
+        .unwrap();
+    engine.run_ticks(1).unwrap();
+    engine
+        .submit_command(PlayerCommand::OpenBranch { state_code: branch.state_code.clone() })
+        .unwrap();
+    engine.run_ticks(1).unwrap();
+
+    assert_eq!(engine.store_branch_count(run_id, "open").unwrap(), open_before);
+    let reopened = engine
+        .store_open_branches(run_id)
+        .unwrap()
+        .into_iter()
+        .find(|b| b.state_code == branch.state_code)
+        .expect("market should have an open branch again");
+    assert_ne!(reopened.branch_id, branch.branch_id, "Reopened branch needs a fresh id");
+}
+
+/// Opening a branch in a state code missing from state_config is rejected.
+#[test]
+fn open_branch_rejects_unknown_state() {
+    let run_id = "branch-unknown-state-test";
+    let mut engine = build_with_branches(run_id, 0xB4A1_0005);
+    engine.run_ticks(1).unwrap();
+
+    let open_before = engine.store_branch_count(run_id, "open").unwrap();
+    engine
+        .submit_command(PlayerCommand::OpenBranch { state_code: "ZZ".into() })
+        .unwrap();
+    engine.run_ticks(1).unwrap();
+
+    assert_eq!(engine.store_branch_count(run_id, "open").unwrap(), open_before);
+}
+
+/// Open branches bring in new customers each month; a closed branch stops
+/// acquiring in its market.
+#[test]
+fn open_branches_acquire_customers() {
+    let run_id = "branch-acquire-test";
+    let mut engine = build_with_branches(run_id, 0xB4A1_0006);
+
+    engine.run_ticks(30).unwrap();
+
+    let branch = engine.store_open_branches(run_id).unwrap().remove(0);
+    let acquired = engine.store_branch_acquisition_count(run_id, &branch.branch_id).unwrap();
+    assert!(acquired > 0, "Expected {} to onboard customers", branch.branch_id);
+
+    engine
+        .submit_command(PlayerCommand::CloseBranch { branch_id: branch.branch_id.clone() })
+        .unwrap();
+    engine.run_ticks(30).unwrap();
+
+    assert_eq!(
+        engine.store_branch_acquisition_count(run_id, &branch.branch_id).unwrap(),
+        acquired,
+        "A closed branch acquires no one"
+    );
+}
+
+/// The monthly report has a line per open branch plus the digital channel.
+#[test]
+fn branch_performance_report_written_monthly() {
+    let run_id = "branch-report-test";
+    let mut engine = build_with_branches(run_id, 0xB4A1_0004);
+
+    engine.run_ticks(30).unwrap();
+
+    let open = engine.store_branch_count(run_id, "open").unwrap();
+    let report = engine.store_branch_performance_report(run_id).unwrap();
+    assert_eq!(report.len() as i64, open + 1, "Expected one line per branch plus digital");
+
+    let total_cash: i64 = report.iter().map(|r| r.cash_txn_count).sum();
+    assert!(total_cash > 0, "Expected cash operations over a 30-tick period");
+    assert!(
+        report
+            .iter()
+            .filter(|r| r.branch_id != DIGITAL_BRANCH_ID)
+            .all(|r| r.operating_cost > 0.0),
+        "Physical branches should carry operating cost"
+    );
+}
@@ -0,0 +1,141 @@
+//! Pause-on-event breakpoints tests — Phase 4.74.
+//!
+//! Tests cover: a tick breakpoint stopping `run_ticks` early with the
+//! clock paused and the hit logged; an event breakpoint stopping on the
+//! first complaint, the tick an uninterrupted run files it; a balance
+//! breakpoint firing when it becomes true and not again until it has
+//! been false; `once` breakpoints dropped when they fire and ids
+//! replaced; and unknown KPIs refused, with hits in the state.
+
+use fincrime_core::{
+    breakpoint::{Breakpoint, BreakpointCondition},
+    engine::SimEngine,
+    protocol::UiState,
+};
+
+fn breakpoint(id: &str, condition: BreakpointCondition) -> Breakpoint {
+    Breakpoint { id: id.into(), condition, once: false }
+}
+
+/// The clock stops after the tick that trips a breakpoint, not at the
+/// end of the ticks asked for, and the next call carries on.
+#[test]
+fn tick_breakpoint_stops_run_early() {
+    let mut engine = SimEngine::build_test("breakpoint-tick-test".into(), 0xB0_0001).unwrap();
+    engine.set_breakpoint(breakpoint("day-5", BreakpointCondition::AtTick { tick: 5 })).unwrap();
+    engine.run_ticks(20).unwrap();
+
+    assert_eq!(engine.clock.current_tick, 5);
+    assert!(engine.clock.paused);
+    let hits = engine.breakpoint_hits();
+    assert_eq!(hits.len(), 1);
+    assert_eq!((hits[0].breakpoint_id.as_str(), hits[0].tick), ("day-5", 5));
+    let events = engine.store_events_for_tick(&engine.run_id, 5).unwrap();
+    let logged = events.iter().find(|e| e.event_type == "breakpoint_hit").unwrap();
+    assert_eq!(logged.subsystem, "engine");
+    assert!(logged.payload.contains("day-5"));
+
+    engine.run_ticks(10).unwrap();
+    assert_eq!(engine.clock.current_tick, 15);
+    assert!(engine.breakpoint_hits().is_empty());
+}
+
+/// "Pause when the first complaint is filed" stops on the tick an
+/// uninterrupted run with the same seed files it.
+#[test]
+fn event_breakpoint_stops_on_first_occurrence() {
+    let mut reference = SimEngine::build_test("breakpoint-event-ref-test".into(), 0xB0_0002).unwrap();
+    reference.run_ticks(60).unwrap();
+    let first = (1..=60)
+        .find(|&tick| {
+            let events = reference.store_events_for_tick(&reference.run_id, tick).unwrap();
+            events.iter().any(|e| e.event_type == "complaint_filed")
+        })
+        .expect("a complaint within 60 ticks");
+
+    let mut engine = SimEngine::build_test("breakpoint-event-test".into(), 0xB0_0002).unwrap();
+    let condition = BreakpointCondition::Event { event_type: "complaint_filed".into() };
+    engine.set_breakpoint(breakpoint("first-complaint", condition)).unwrap();
+    engine.run_ticks(60).unwrap();
+
+    assert_eq!(engine.clock.current_tick, first);
+    let hit = &engine.breakpoint_hits()[0];
+    assert_eq!(hit.tick, first);
+    assert!(hit.detail.contains("complaint_filed"), "{}", hit.detail);
+}
+
+/// A balance condition fires as it becomes true, stays quiet while it
+/// holds, and fires again once it has cleared and recurred.
+#[test]
+fn balance_breakpoint_fires_on_crossing() {
+    let mut engine = SimEngine::build_test("breakpoint-balance-test".into(), 0xB0_0003).unwrap();
+    engine.set_breakpoint(breakpoint("overdrawn", BreakpointCondition::BalanceBelow { amount: -10_000.0 })).unwrap();
+    engine.run_ticks(3).unwrap();
+    assert_eq!(engine.clock.current_tick, 3, "Nothing overdrawn that far");
+
+    let run_id = engine.run_id.clone();
+    let (account_id, _) = engine.store.lowest_account_balance(&run_id).unwrap().unwrap();
+    engine.store.update_account_balance(&run_id, &account_id, -50_000.0).unwrap();
+    engine.run_ticks(10).unwrap();
+    assert_eq!(engine.clock.current_tick, 4);
+    let hit = &engine.breakpoint_hits()[0];
+    assert!(hit.detail.contains(&account_id), "{}", hit.detail);
+
+    engine.run_ticks(5).unwrap();
+    assert_eq!(engine.clock.current_tick, 9, "Still overdrawn, so no second stop");
+
+    engine.store.update_account_balance(&run_id, &account_id, 50_000.0).unwrap();
+    engine.run_ticks(1).unwrap();
+    engine.store.update_account_balance(&run_id, &account_id, -50_000.0).unwrap();
+    engine.run_ticks(10).unwrap();
+    assert_eq!(engine.clock.current_tick, 11);
+    assert_eq!(engine.breakpoint_hits()[0].breakpoint_id, "overdrawn");
+}
+
+/// A `once` breakpoint is gone after it fires; setting an id again
+/// replaces it rather than adding a second.
+#[test]
+fn once_breakpoints_are_removed_and_ids_replaced() {
+    let mut engine = SimEngine::build_test("breakpoint-once-test".into(), 0xB0_0004).unwrap();
+    engine.set_breakpoint(breakpoint("at", BreakpointCondition::AtTick { tick: 2 })).unwrap();
+    engine.set_breakpoint(breakpoint("at", BreakpointCondition::AtTick { tick: 3 })).unwrap();
+    let once = Breakpoint { once: true, ..breakpoint("once", BreakpointCondition::AtTick { tick: 3 }) };
+    engine.set_breakpoint(once).unwrap();
+    assert_eq!(engine.breakpoints().len(), 2);
+
+    engine.run_ticks(10).unwrap();
+    assert_eq!(engine.clock.current_tick, 3);
+    let ids: Vec<_> = engine.breakpoint_hits().iter().map(|h| h.breakpoint_id.as_str()).collect();
+    assert_eq!(ids, ["at", "once"]);
+    let left: Vec<_> = engine.breakpoints().into_iter().map(|b| b.id).collect();
+    assert_eq!(left, ["at"]);
+
+    assert!(engine.clear_breakpoint("at"));
+    assert!(!engine.clear_breakpoint("at"));
+    engine.run_ticks(5).unwrap();
+    assert_eq!(engine.clock.current_tick, 8);
+}
+
+/// An unknown KPI is refused without halting the run; a KPI breakpoint
+/// that fires shows in the state sent to clients.
+#[test]
+fn kpi_breakpoints_validate_and_show_in_state() {
+    let mut engine = SimEngine::build_test("breakpoint-kpi-test".into(), 0xB0_0005).unwrap();
+    let unknown = BreakpointCondition::KpiAbove { metric: "morale".into(), value: 1.0 };
+    let err = engine.set_breakpoint(breakpoint("bad", unknown)).unwrap_err();
+    assert!(!err.is_fatal());
+    assert!(err.to_string().contains("morale"), "{err}");
+    assert!(engine.breakpoints().is_empty());
+
+    let state = UiState::build(&engine, true).unwrap();
+    assert!(!serde_json::to_value(&state).unwrap().as_object().unwrap().contains_key("breakpoint_hits"));
+
+    let condition = BreakpointCondition::KpiAbove { metric: "active_customers".into(), value: 0.0 };
+    engine.set_breakpoint(breakpoint("customers", condition)).unwrap();
+    engine.run_ticks(10).unwrap();
+    assert_eq!(engine.clock.current_tick, 1);
+    let state = UiState::build(&engine, true).unwrap();
+    assert_eq!(state.breakpoint_hits.len(), 1);
+    assert!(state.breakpoint_hits[0].detail.starts_with("active_customers"));
+    assert!(state.paused);
+}
@@ -0,0 +1,162 @@
+//! Bulk complaint resolution tests — Phase 4.47.
+//!
+//! Tests cover: a configured template refunding fees under its cap,
+//! complaints over the cap skipped with a reason and left open, filters
+//! by issue, priority and age, malformed requests rejected per field,
+//! and all-or-nothing application under role gates.
+
+use fincrime_core::{
+    authorization::Role,
+    command_result::{CommandResult, RejectionCode},
+    engine::SimEngine,
+    error::SimError,
+};
+use serde_json::json;
+
+fn engine(run_id: &str) -> SimEngine {
+    let mut engine = SimEngine::build_test(run_id.into(), 42).unwrap();
+    engine.run_ticks(20).unwrap();
+    engine
+}
+
+fn open_count(engine: &SimEngine) -> usize {
+    engine.store.open_complaints(&engine.run_id).unwrap().len()
+}
+
+/// `refund_small_fees` closes every fee dispute with the overdraft fee
+/// that prompted it refunded, and reports each one.
+#[test]
+fn template_refunds_small_fees() {
+    let mut engine = engine("bulk-refund-test");
+    let open = open_count(&engine);
+    assert!(open > 1, "need a backlog, got {open}");
+
+    let payload = json!({ "template": "refund_small_fees", "filter": { "issue": "fee_dispute" } });
+    let result = CommandResult::apply(&mut engine, "bulk_resolve", &payload, None).unwrap();
+    assert!(result.accepted, "{:?}", result.errors);
+    assert!(result.command_id.is_none(), "applied directly, not queued");
+    let bulk = result.bulk.unwrap();
+    assert_eq!(bulk.template_id, "refund_small_fees");
+    assert_eq!(bulk.matched, open);
+    assert_eq!(bulk.applied.len(), open, "{:?}", bulk.skipped);
+    assert!(bulk.applied.iter().all(|a| a.refund == 27.08));
+    assert!((bulk.total_refunded - 27.08 * open as f64).abs() < 1e-6);
+    assert_eq!(result.entity_ids.len(), open);
+
+    assert_eq!(open_count(&engine), 0);
+    assert_eq!(engine.store.complaint_backlog(&engine.run_id).unwrap(), 0);
+    let closed = engine.store.get_complaint(&engine.run_id, &result.entity_ids[0]).unwrap();
+    assert_eq!(closed.resolution_code.as_deref(), Some("monetary_relief"));
+    assert_eq!(closed.amount_refunded, 27.08);
+}
+
+/// An inline template whose cap is under the fee skips every complaint,
+/// saying why, and closes none of them.
+#[test]
+fn fees_over_the_cap_skipped() {
+    let mut engine = engine("bulk-skip-test");
+    let open = open_count(&engine);
+    let template = json!({ "resolution_code": "monetary_relief", "refund": { "rule": "fee_up_to", "max": 20.0 } });
+
+    let result = CommandResult::apply(&mut engine, "bulk_resolve", &json!({ "template": template }), None).unwrap();
+    assert!(result.accepted, "{:?}", result.errors);
+    let bulk = result.bulk.unwrap();
+    assert_eq!(bulk.template_id, "", "inline");
+    assert!(bulk.applied.is_empty());
+    assert_eq!(bulk.skipped.len(), open);
+    assert!(bulk.skipped[0].reason.contains("$27.08 is over $20.00"), "{:?}", bulk.skipped[0]);
+    assert_eq!(open_count(&engine), open);
+}
+
+/// Only complaints matching every filter field are touched.
+#[test]
+fn filter_by_issue_priority_and_age() {
+    let mut engine = engine("bulk-filter-test");
+    let tick = engine.clock.current_tick;
+    let complaints = engine.store.open_complaints(&engine.run_id).unwrap();
+    let old: Vec<_> = complaints.iter().filter(|c| tick - c.tick_opened >= 10).collect();
+    assert!(!old.is_empty() && old.len() < complaints.len(), "need old and new complaints");
+
+    let none = json!({ "template": "explain_policy", "filter": { "issue": "unwanted_contact" } });
+    let result = CommandResult::apply(&mut engine, "bulk_resolve", &none, None).unwrap();
+    assert_eq!(result.bulk.unwrap().matched, 0);
+
+    let payload = json!({
+        "template": "explain_policy",
+        "filter": { "issue": "fee_dispute", "priority": "standard", "min_age_ticks": 10 },
+    });
+    let result = CommandResult::apply(&mut engine, "bulk_resolve", &payload, None).unwrap();
+    let bulk = result.bulk.unwrap();
+    assert_eq!(bulk.matched, old.len());
+    let mut expected: Vec<_> = old.iter().map(|c| c.complaint_id.clone()).collect();
+    expected.sort();
+    let mut closed = result.entity_ids.clone();
+    closed.sort();
+    assert_eq!(closed, expected);
+    assert!(bulk.applied.iter().all(|a| a.refund == 0.0));
+    assert_eq!(open_count(&engine), complaints.len() - old.len());
+}
+
+/// Unknown templates and codes, bad filters and malformed inline
+/// templates are rejected with the field at fault.
+#[test]
+fn malformed_requests_rejected() {
+    let mut engine = engine("bulk-invalid-test");
+    let open = open_count(&engine);
+    let cases = [
+        (json!({ "template": "refund_everything" }), RejectionCode::ValidationFailed, vec!["template"]),
+        (json!({ "filter": {} }), RejectionCode::InvalidPayload, vec!["template"]),
+        (json!({ "template": { "refund": { "rule": "fixed", "amount": 5.0 } } }), RejectionCode::InvalidPayload, vec!["template"]),
+        (
+            json!({ "template": { "resolution_code": "refund", "refund": { "rule": "fixed", "amount": -5.0 } } }),
+            RejectionCode::ValidationFailed,
+            vec!["template.resolution_code", "template.refund"],
+        ),
+        (
+            json!({ "template": "explain_policy", "filter": { "priority": "whenever", "min_age_ticks": 5, "max_age_ticks": 2 } }),
+            RejectionCode::ValidationFailed,
+            vec!["filter.priority", "filter.min_age_ticks"],
+        ),
+    ];
+    for (payload, code, fields) in cases {
+        let result = CommandResult::apply(&mut engine, "bulk_resolve", &payload, None).unwrap();
+        assert_eq!(result.code, Some(code), "{payload}");
+        assert_eq!(result.errors.iter().map(|e| e.field.as_str()).collect::<Vec<_>>(), fields, "{payload}");
+        assert!(result.bulk.is_none());
+    }
+    assert_eq!(open_count(&engine), open);
+}
+
+/// The largest refund in the batch is held to the sender's write-off
+/// limit, and the batch is applied in full or not at all.
+#[test]
+fn all_or_nothing_under_role_gates() {
+    let mut gated = SimEngine::build_test_with_authorization("bulk-auth-test".into(), 42).unwrap();
+    gated.run_ticks(20).unwrap();
+    let open = open_count(&gated);
+    let payload = json!({ "template": { "resolution_code": "monetary_relief", "refund": { "rule": "fixed", "amount": 300.0 } } });
+
+    let refused = CommandResult::apply(&mut gated, "bulk_resolve", &payload, Some(Role::Analyst)).unwrap();
+    assert_eq!(refused.code, Some(RejectionCode::Unauthorized));
+    assert!(refused.errors[0].message.contains("write-off limit"), "{:?}", refused.errors);
+    assert_eq!(open_count(&gated), open, "nothing closed");
+    let accepted = CommandResult::apply(&mut gated, "bulk_resolve", &payload, Some(Role::Manager)).unwrap();
+    assert_eq!(accepted.bulk.unwrap().applied.len(), open);
+    let audit = gated.store.authorization_audit(&gated.run_id).unwrap();
+    assert_eq!(audit.iter().map(|a| a.allowed).collect::<Vec<_>>(), [false, true]);
+    assert!(audit.iter().all(|a| a.command == "bulk_resolve" && a.required_role == Role::Manager));
+
+    // A failure part way through a transaction leaves earlier closes undone.
+    let engine = engine("bulk-atomic-test");
+    let first = engine.store.open_complaints(&engine.run_id).unwrap().remove(0);
+    let tick = engine.clock.current_tick;
+    let err = engine
+        .store
+        .atomically(|| {
+            engine.store.close_complaint(&engine.run_id, &first.complaint_id, tick, "explanation_only", 0.0)?;
+            Err::<(), _>(SimError::command("bulk_resolve", "stopped"))
+        })
+        .unwrap_err();
+    assert!(matches!(err, SimError::Command { .. }));
+    assert_eq!(engine.store.get_complaint(&engine.run_id, &first.complaint_id).unwrap().status, "open");
+}
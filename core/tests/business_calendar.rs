@@ -0,0 +1,163 @@
+//! Business-day calendar tests — Phase 4.41.
+//!
+//! Tests cover: weekends and observed federal holidays, payments rolling
+//! off closures and ACH returns only on business days, complaint SLAs
+//! and filing deadlines landing on business days, malformed dates and
+//! day names refused, and the calendar staying off unless enabled.
+
+use fincrime_core::{
+    calendar::BusinessCalendar, config::SimConfig, engine::SimEngine,
+    transaction_monitoring_subsystem::AMLAlert,
+};
+
+fn calendar() -> BusinessCalendar {
+    let mut config = SimConfig::default_test().calendar;
+    config.enabled = true;
+    BusinessCalendar::new(&config).unwrap()
+}
+
+fn build_with_calendar(run_id: &str, seed: u64) -> SimEngine {
+    SimEngine::build_test_with_business_calendar(run_id.to_string(), seed)
+        .expect("build test engine with business calendar")
+}
+
+/// Tick 1 is New Year's Day 2025. Weekends close, MLK Day is the third
+/// Monday of January, and Independence Day 2026 falls on a Saturday so is
+/// observed the Friday before.
+#[test]
+fn weekends_and_observed_holidays_close() {
+    let cal = calendar();
+    assert_eq!(cal.date(1).to_string(), "2025-01-01");
+    assert_eq!(cal.holiday(1), Some("New Year's Day"));
+    assert!(cal.is_business_day(2));
+    assert!(!cal.is_business_day(4) && !cal.is_business_day(5)); // Sat, Sun
+    assert_eq!(cal.holiday(20), Some("Martin Luther King Jr. Day"));
+
+    let july_3_2026 = (1..800).find(|t| cal.date(*t).to_string() == "2026-07-03").unwrap();
+    assert_eq!(cal.holiday(july_3_2026), Some("Independence Day"));
+    assert!(cal.holiday(july_3_2026 + 1).is_none());
+
+    // Friday plus one business day is Monday; Friday 17 Jan plus one
+    // skips MLK Day too.
+    assert_eq!(cal.add_business_days(3, 1), 6);
+    assert_eq!(cal.add_business_days(17, 1), 21);
+    assert_eq!(cal.roll_forward(4), 6);
+    // Monday's batch picks up Friday's and the weekend's T+1 items
+    assert_eq!(cal.due_from(6, 1), vec![3, 4, 5]);
+    assert!(cal.due_from(5, 1).is_empty());
+}
+
+/// Payments made on a weekend or holiday, or past a cutoff, roll to a
+/// business day, and ACH returns only come back on one.
+#[test]
+fn batch_rails_run_on_business_days() {
+    let run_id = "cal-settlement-test";
+    let mut engine = build_with_calendar(run_id, 42);
+    engine.run_ticks(45).unwrap();
+    let cal = calendar();
+
+    let mut closed_rolls = 0;
+    for (rail, cutoff) in [("ACH", 16), ("wire", 18)] {
+        for roll in engine.store.cutoff_rolls(run_id, rail).unwrap() {
+            assert!(cal.is_business_day(roll.processing_tick), "{}", cal.date(roll.processing_tick));
+            assert_eq!(roll.processing_tick, cal.add_business_days(roll.booked_tick, 1));
+            if !cal.is_business_day(roll.booked_tick) {
+                closed_rolls += 1;
+            } else {
+                assert!(roll.initiated_hour >= cutoff);
+            }
+        }
+    }
+    assert!(closed_rolls > 0, "weekend payments wait for Monday");
+
+    let returns = engine.store.ach_return_ticks(run_id).unwrap();
+    assert!(!returns.is_empty());
+    for tick in returns {
+        assert!(cal.is_business_day(tick), "return on {}", cal.date(tick));
+    }
+}
+
+/// Complaint SLAs count business days, so every due date is one.
+#[test]
+fn complaint_sla_due_on_business_days() {
+    let run_id = "cal-sla-test";
+    let mut engine = build_with_calendar(run_id, 42);
+    engine.run_ticks(60).unwrap();
+    let cal = calendar();
+
+    let windows = engine.store.complaint_sla_windows(run_id).unwrap();
+    assert!(!windows.is_empty());
+    for (opened, due) in windows {
+        assert!(due >= opened);
+        assert!(cal.is_business_day(due), "due {}", cal.date(due));
+    }
+}
+
+/// A SAR deadline 30 days after an alert on Friday 3 January lands on
+/// Sunday 2 February and moves to the Monday.
+#[test]
+fn filing_deadlines_roll_to_business_days() {
+    let run_id = "cal-deadline-test";
+    let mut engine = build_with_calendar(run_id, 42);
+    engine.run_ticks(3).unwrap();
+    let customer = engine.store.active_customers(run_id).unwrap().remove(0);
+    engine
+        .store
+        .insert_transaction_monitoring_alert(&AMLAlert {
+            alert_id: "CAL-1".into(),
+            run_id: run_id.into(),
+            customer_id: customer.customer_id,
+            tick: 3,
+            rule_id: "TEST".into(),
+            alert_type: "structuring".into(),
+            alert_score: 90.0,
+            description: "test alert".into(),
+            triggered_amount: Some(9_500.0),
+            transaction_count: Some(3),
+            status: "open".into(),
+        })
+        .unwrap();
+    engine.run_ticks(11).unwrap();
+    let cal = calendar();
+
+    let filings = engine.store.regulatory_filing_deadlines(run_id).unwrap();
+    assert!(!filings.is_empty());
+    for (filed, deadline, on_time) in &filings {
+        assert!(cal.is_business_day(*deadline), "deadline {}", cal.date(*deadline));
+        assert_eq!(*on_time, filed <= deadline);
+    }
+    assert!(filings.iter().any(|(_, deadline, _)| cal.date(*deadline).to_string() == "2025-02-03"));
+}
+
+/// A start date or extra closure that is not `YYYY-MM-DD`, or a weekend
+/// day that names no weekday, is refused with the field it is in.
+#[test]
+fn malformed_calendar_refused() {
+    let good = SimConfig::default_test().calendar;
+    let mut start = good.clone();
+    start.start_date = "01/01/2025".into();
+    let mut weekend = good.clone();
+    weekend.weekend_days = vec!["Sat".into(), "Sunday-ish".into()];
+    let mut extra = good.clone();
+    extra.extra_holidays = vec!["2025-02-30".into()];
+
+    for (config, field) in [(start, "start_date"), (weekend, "weekend_days"), (extra, "extra_holidays")] {
+        let error = BusinessCalendar::new(&config).err().expect(field).to_string();
+        assert!(error.contains(field), "{error}");
+    }
+}
+
+/// The calendar is opt-in: with only cutoffs on, a roll still lands on
+/// the next calendar day, weekend or not.
+#[test]
+fn business_calendar_opt_in() {
+    let run_id = "cal-off-test";
+    let mut engine = SimEngine::build_test_with_payment_cutoffs(run_id.to_string(), 42).unwrap();
+    engine.run_ticks(30).unwrap();
+    let cal = calendar();
+
+    let rolls = engine.store.cutoff_rolls(run_id, "ACH").unwrap();
+    assert!(!rolls.is_empty());
+    assert!(rolls.iter().all(|r| r.processing_tick == r.booked_tick + 1));
+    assert!(rolls.iter().any(|r| !cal.is_business_day(r.processing_tick)));
+}
@@ -0,0 +1,114 @@
+//! Case document tests — Phase 4.31.
+//!
+//! Tests cover: the documents each dispute reason requires, complaint
+//! documents following the account, missing documents surfacing as an
+//! exam finding, and documents staying off unless enabled.
+
+use fincrime_core::engine::SimEngine;
+
+fn doc_types(engine: &SimEngine, run_id: &str, case_type: &str, case_id: &str) -> Vec<String> {
+    engine
+        .store
+        .case_documents(run_id, case_type, case_id)
+        .unwrap()
+        .into_iter()
+        .map(|d| d.doc_type)
+        .collect()
+}
+
+/// Every dispute gets its form and statement on file, plus an ID copy
+/// for fraud claims and merchant correspondence for merchant disputes.
+#[test]
+fn disputes_carry_required_documents() {
+    let run_id = "case-docs-dispute-test";
+    let mut engine = SimEngine::build_test_with_case_documents(run_id.into(), 42, 0.0).unwrap();
+    engine.run_ticks(100).unwrap();
+
+    let disputes = engine.store.documented_cases(run_id, "dispute").unwrap();
+    assert!(!disputes.is_empty());
+    assert_eq!(
+        engine.store.count_events_in_range(run_id, 0, 100, "dispute_filed").unwrap(),
+        disputes.len() as i64
+    );
+    for dispute_id in &disputes {
+        let dispute = engine.store.get_dispute(run_id, dispute_id).unwrap();
+        let mut expected = vec!["dispute_form", "statement"];
+        match dispute.reason.as_str() {
+            "unauthorized_charge" | "card_stolen" | "merchant_fraud" => expected.push("id_copy"),
+            "service_not_rendered" | "defective_product" | "not_as_described"
+            | "cancelled_subscription" | "credit_not_received" => expected.push("merchant_correspondence"),
+            _ => {}
+        }
+        assert_eq!(doc_types(&engine, run_id, "dispute", dispute_id), expected, "{}", dispute.reason);
+
+        let form = &engine.store.case_documents(run_id, "dispute", dispute_id).unwrap()[0];
+        let content: serde_json::Value = serde_json::from_str(form.content.as_deref().unwrap()).unwrap();
+        assert!((content["amount"].as_f64().unwrap() - dispute.amount).abs() < 1e-9);
+        assert_eq!(form.status, "on_file");
+    }
+}
+
+/// Complaints get their narrative, and a statement for the account they
+/// are about when there is one.
+#[test]
+fn complaint_documents_follow_the_account() {
+    let run_id = "case-docs-complaint-test";
+    let mut engine = SimEngine::build_test_with_case_documents(run_id.into(), 42, 0.0).unwrap();
+    engine.run_ticks(20).unwrap();
+
+    let complaints = engine.store.documented_cases(run_id, "complaint").unwrap();
+    assert!(!complaints.is_empty());
+    assert_eq!(
+        engine.store.count_events_in_range(run_id, 0, 20, "complaint_filed").unwrap(),
+        complaints.len() as i64
+    );
+    for complaint_id in &complaints {
+        let complaint = engine.store.get_complaint(run_id, complaint_id).unwrap();
+        let docs = engine.store.case_documents(run_id, "complaint", complaint_id).unwrap();
+        let statement = docs.iter().find(|d| d.doc_type == "statement").map(|d| {
+            let content: serde_json::Value = serde_json::from_str(d.content.as_deref().unwrap()).unwrap();
+            content["account_id"].as_str().unwrap().to_string()
+        });
+        assert_eq!(docs[0].doc_type, "complaint_narrative");
+        assert_eq!(statement, complaint.account_id, "{complaint_id}");
+    }
+}
+
+/// With nothing collected the exam closing at tick 26 finds the gaps;
+/// with everything on file it finds none.
+#[test]
+fn missing_documents_raise_exam_finding() {
+    let mut gaps = SimEngine::build_test_with_case_documents("case-docs-gaps-test".into(), 7, 1.0).unwrap();
+    let mut complete = SimEngine::build_test_with_case_documents("case-docs-complete-test".into(), 7, 0.0).unwrap();
+    gaps.run_ticks(27).unwrap();
+    complete.run_ticks(27).unwrap();
+
+    let complaint_id = &gaps.store.documented_cases("case-docs-gaps-test", "complaint").unwrap()[0];
+    for doc in gaps.store.case_documents("case-docs-gaps-test", "complaint", complaint_id).unwrap() {
+        assert_eq!((doc.status.as_str(), doc.content), ("missing", None));
+    }
+    assert!(gaps.store.cases_missing_documents("case-docs-gaps-test", 6, 26).unwrap() > 5);
+    assert_eq!(
+        gaps.store.exam_findings_in_category("case-docs-gaps-test", "case_documentation").unwrap(),
+        vec![("major".to_string(), 250_000.0)]
+    );
+    assert!(complete
+        .store
+        .exam_findings_in_category("case-docs-complete-test", "case_documentation")
+        .unwrap()
+        .is_empty());
+}
+
+/// The test default generates no documents.
+#[test]
+fn case_documents_are_opt_in() {
+    let run_id = "case-docs-off-test";
+    let mut engine = SimEngine::build_test(run_id.into(), 42).unwrap();
+    engine.run_ticks(20).unwrap();
+
+    assert!(engine.store.documented_cases(run_id, "complaint").unwrap().is_empty());
+    assert_eq!(
+        engine.store.count_events_in_range(run_id, 0, 20, "case_documents_generated").unwrap(),
+        0
+    );
+}
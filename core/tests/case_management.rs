@@ -0,0 +1,315 @@
+//! Case management tests — Phase 4.86.
+//!
+//! Tests cover: alerts and screening hits opening cases that complaints
+//! and disputes join, with a complaint on a customer in no case left
+//! out; a confirmed close cascading to the case's alerts and complaints
+//! while a reportable alert stays with the SAR desk; an unfounded close
+//! clearing a screening hit and leaving a dispute to its own desk; a
+//! case closing as worked once its items close elsewhere; and the case
+//! timeline with IPC validation of the commands.
+
+use fincrime_core::{
+    command::PlayerCommand,
+    command_result::{CommandResult, RejectionCode},
+    complaint_subsystem::ComplaintRecord,
+    engine::SimEngine,
+    entity_timeline::{EntityTimeline, EntityType, TimelineKind},
+    store::NewDispute,
+    transaction_monitoring_subsystem::AMLAlert,
+};
+use serde_json::{json, Value};
+
+fn build(run_id: &str) -> SimEngine {
+    SimEngine::build_test_with_case_management(run_id.into(), 42).unwrap()
+}
+
+fn events(engine: &SimEngine, event_type: &str) -> Vec<Value> {
+    (0..=engine.clock.current_tick)
+        .flat_map(|tick| engine.store.events_for_tick(&engine.run_id, tick).unwrap())
+        .filter(|e| e.event_type == event_type)
+        .map(|e| serde_json::from_str(&e.payload).unwrap())
+        .collect()
+}
+
+/// An active customer with an account and no open case on their
+/// cluster, with that account.
+fn uncased_customer(engine: &SimEngine, skip: usize) -> (String, String) {
+    let run_id = &engine.run_id;
+    engine
+        .store
+        .active_accounts(run_id)
+        .unwrap()
+        .into_iter()
+        .filter(|a| engine.store.open_investigation_case_for(run_id, &a.customer_id, Some(0.7)).unwrap().is_none())
+        .map(|a| (a.customer_id, a.account_id))
+        .nth(skip)
+        .unwrap()
+}
+
+fn raise_alert(engine: &SimEngine, alert_id: &str, customer_id: &str, score: f64) {
+    engine
+        .store
+        .insert_transaction_monitoring_alert(&AMLAlert {
+            alert_id: alert_id.into(),
+            run_id: engine.run_id.clone(),
+            customer_id: customer_id.into(),
+            tick: engine.clock.current_tick,
+            rule_id: "TEST".into(),
+            alert_type: "structuring".into(),
+            alert_score: score,
+            description: "test alert".into(),
+            triggered_amount: Some(9_500.0),
+            transaction_count: Some(3),
+            status: "open".into(),
+        })
+        .unwrap();
+}
+
+fn file_complaint(engine: &SimEngine, complaint_id: &str, customer_id: &str) {
+    let tick = engine.clock.current_tick;
+    engine
+        .store
+        .insert_complaint(&engine.run_id, &ComplaintRecord {
+            complaint_id: complaint_id.into(),
+            customer_id: customer_id.into(),
+            account_id: None,
+            tick_opened: tick,
+            tick_closed: None,
+            product: "checking".into(),
+            issue: "fees".into(),
+            priority: "standard".into(),
+            status: "open".into(),
+            sla_due_tick: tick + 15,
+            sla_breached: false,
+            resolution_code: None,
+            amount_refunded: 0.0,
+            udaap_flag: false,
+            channel: "phone".into(),
+            service_channel: None,
+        })
+        .unwrap();
+}
+
+fn screening_hit(engine: &SimEngine, screening_id: &str, customer_id: &str) {
+    let tick = engine.clock.current_tick as i64;
+    engine
+        .store
+        .insert_aml_screening_result(
+            &engine.run_id, screening_id, customer_id, tick, "adverse_media", "fuzzy_match", 0.8, None, "{}", 0.1,
+        )
+        .unwrap();
+}
+
+/// The case's items as (type, id, disposition).
+fn items(engine: &SimEngine, case_id: &str) -> Vec<(String, String, Option<String>)> {
+    let items = engine.store.case_items(&engine.run_id, case_id).unwrap();
+    items.into_iter().map(|i| (i.item_type, i.item_id, i.disposition)).collect()
+}
+
+fn item<'a>(
+    items: &'a [(String, String, Option<String>)],
+    item_id: &str,
+) -> Option<&'a (String, String, Option<String>)> {
+    items.iter().find(|(_, id, _)| id == item_id)
+}
+
+fn close(engine: &mut SimEngine, case_id: &str, disposition: &str) {
+    engine
+        .submit_command(PlayerCommand::CloseCase {
+            case_id: case_id.into(),
+            disposition: disposition.into(),
+            note: format!("closed as {disposition}"),
+        })
+        .unwrap();
+}
+
+/// An AML alert opens a case on its customer, and a screening hit and a
+/// complaint on the same customer join it; a complaint on a customer in
+/// no case opens none.
+#[test]
+fn alerts_open_cases_that_complaints_join() {
+    let run_id = "case-link-test";
+    let mut engine = build(run_id);
+    engine.run_ticks(40).unwrap();
+    let (customer, _) = uncased_customer(&engine, 0);
+    raise_alert(&engine, "A0", &customer, 50.0);
+    screening_hit(&engine, "S0", &customer);
+    file_complaint(&engine, "C0", &customer);
+    engine.run_ticks(1).unwrap();
+
+    let case = engine.store.investigation_case(run_id, "ic-A0").unwrap().unwrap();
+    assert_eq!((case.customer_id.as_str(), case.opened_tick, case.status.as_str()), (customer.as_str(), 41, "open"));
+    let linked = items(&engine, "ic-A0");
+    for (item_type, item_id) in [("aml_alert", "A0"), ("screening_hit", "S0"), ("complaint", "C0")] {
+        assert_eq!(item(&linked, item_id).map(|i| i.0.as_str()), Some(item_type), "{item_id} not linked");
+    }
+    assert_eq!(case.item_count, linked.len() as i64);
+    let opened = events(&engine, "investigation_case_opened");
+    let opened = opened.iter().find(|e| e["case_id"] == "ic-A0").unwrap();
+    assert_eq!((opened["item_type"].as_str(), opened["item_id"].as_str()), (Some("aml_alert"), Some("A0")));
+
+    let (loner, _) = uncased_customer(&engine, 0);
+    file_complaint(&engine, "C1", &loner);
+    engine.run_ticks(1).unwrap();
+    let cases = engine.store.investigation_cases(run_id).unwrap();
+    assert!(cases.iter().all(|c| item(&items(&engine, &c.case_id), "C1").is_none()));
+}
+
+/// A confirmed close closes the case's open alerts as suspicious and its
+/// complaints, under the assigned analyst; an alert at the reportable
+/// score is left for the SAR desk.
+#[test]
+fn confirmed_close_cascades_to_open_items() {
+    let run_id = "case-confirm-test";
+    let mut engine = build(run_id);
+    engine.run_ticks(40).unwrap();
+    let (customer, _) = uncased_customer(&engine, 0);
+    raise_alert(&engine, "A0", &customer, 50.0);
+    raise_alert(&engine, "A1", &customer, 95.0);
+    engine
+        .store
+        .insert_fraud_alert(run_id, "F0", 40, "velocity_spike", "customer", &customer, 0.9, "high")
+        .unwrap();
+    file_complaint(&engine, "C0", &customer);
+    engine.run_ticks(1).unwrap();
+
+    engine
+        .submit_command(PlayerCommand::AssignCase {
+            case_id: "ic-A0".into(),
+            analyst: "dana".into(),
+            note: Some("one customer, three desks".into()),
+        })
+        .unwrap();
+    close(&mut engine, "ic-A0", "confirmed");
+    engine.run_ticks(1).unwrap();
+
+    let case = engine.store.investigation_case(run_id, "ic-A0").unwrap().unwrap();
+    assert_eq!(
+        (case.status.as_str(), case.disposition.as_deref(), case.closed_tick, case.assigned_to.as_deref()),
+        ("closed", Some("confirmed"), Some(42), Some("dana"))
+    );
+    let linked = items(&engine, "ic-A0");
+    assert_eq!(item(&linked, "A0").unwrap().2.as_deref(), Some("suspicious"));
+    assert_eq!(item(&linked, "F0").unwrap().2.as_deref(), Some("resolved"));
+    assert_eq!(item(&linked, "C0").unwrap().2.as_deref(), Some("explanation_only"));
+    assert_eq!(item(&linked, "A1").unwrap().2, None);
+    assert_eq!(engine.store.alert_disposition(run_id, "A0").unwrap().as_deref(), Some("suspicious"));
+    assert_eq!(engine.store.alert_notes(run_id, "A0").unwrap()[0].analyst.as_deref(), Some("dana"));
+    assert_eq!(engine.store.get_complaint(run_id, "C0").unwrap().status, "closed");
+
+    let closed = events(&engine, "investigation_case_closed");
+    let closed = closed.iter().find(|e| e["case_id"] == "ic-A0").unwrap();
+    let cascaded = linked.iter().filter(|i| i.2.is_some()).count();
+    assert_eq!(closed["items_closed"].as_u64(), Some(cascaded as u64));
+    let notes = engine.store.case_notes(run_id, "ic-A0").unwrap();
+    let notes: Vec<_> = notes.iter().map(|n| (n.action.as_str(), n.analyst.as_deref())).collect();
+    assert_eq!(notes, [("assign", Some("dana")), ("close", Some("dana"))]);
+}
+
+/// An unfounded close clears a screening hit as a false positive and
+/// leaves a dispute on the card network's timeline; a second close is
+/// rejected when it lands.
+#[test]
+fn unfounded_close_clears_hits_and_leaves_disputes() {
+    let run_id = "case-unfounded-test";
+    let mut engine = build(run_id);
+    engine.run_ticks(40).unwrap();
+    let (customer, account) = uncased_customer(&engine, 0);
+    screening_hit(&engine, "S0", &customer);
+    engine
+        .store
+        .insert_dispute(run_id, &NewDispute {
+            dispute_id: "D0".into(),
+            authorization_id: "auth-D0".into(),
+            account_id: account.clone(),
+            customer_id: customer.clone(),
+            tick_filed: 40,
+            amount: 120.0,
+            merchant_name: "Test Merchant".into(),
+            merchant_category: "retail".into(),
+            reason: "not_as_described".into(),
+            friendly_fraud_score: 0.1,
+            merchant_id: None,
+        })
+        .unwrap();
+    engine.run_ticks(1).unwrap();
+    assert!(item(&items(&engine, "ic-S0"), "D0").is_some());
+
+    close(&mut engine, "ic-S0", "unfounded");
+    engine.run_ticks(1).unwrap();
+    let linked = items(&engine, "ic-S0");
+    assert_eq!(item(&linked, "S0").unwrap().2.as_deref(), Some("false_positive"));
+    assert_eq!(item(&linked, "D0").unwrap().2, None);
+    assert_eq!(engine.store.investigation_case(run_id, "ic-S0").unwrap().unwrap().disposition.as_deref(), Some("unfounded"));
+
+    close(&mut engine, "ic-S0", "confirmed");
+    engine.run_ticks(1).unwrap();
+    let rejected = events(&engine, "investigation_case_rejected");
+    assert_eq!(rejected[0]["reason"], "case ic-S0 is closed");
+    assert_eq!(engine.store.investigation_case(run_id, "ic-S0").unwrap().unwrap().disposition.as_deref(), Some("unfounded"));
+}
+
+/// A case nobody closes closes as worked once its desks have closed
+/// every item in it.
+#[test]
+fn case_closes_as_worked_when_its_items_close_elsewhere() {
+    let run_id = "case-worked-test";
+    let mut engine = build(run_id);
+    engine.run_ticks(40).unwrap();
+    let (customer, _) = uncased_customer(&engine, 0);
+    raise_alert(&engine, "A0", &customer, 40.0);
+    engine.run_ticks(1).unwrap();
+    let case = engine.store.investigation_case(run_id, "ic-A0").unwrap().unwrap();
+    assert_eq!(case.age_ticks(41), 0);
+
+    engine.store.close_alert(run_id, "A0", "false_positive", 41, "cleared").unwrap();
+    engine.run_ticks(1).unwrap();
+    let case = engine.store.investigation_case(run_id, "ic-A0").unwrap().unwrap();
+    assert_eq!((case.status.as_str(), case.disposition.as_deref(), case.closed_tick), ("closed", Some("worked"), Some(42)));
+    assert_eq!(case.age_ticks(100), 1);
+    let closed = events(&engine, "investigation_case_closed");
+    assert_eq!(closed.iter().find(|e| e["case_id"] == "ic-A0").unwrap()["items_closed"], 0);
+}
+
+/// The case's timeline runs from its first item through its opening and
+/// notes to its closing; over IPC the commands are checked for their
+/// fields and for a case still open.
+#[test]
+fn case_timeline_and_ipc_validation() {
+    let run_id = "case-ipc-test";
+    let mut engine = build(run_id);
+    engine.run_ticks(40).unwrap();
+    let (customer, _) = uncased_customer(&engine, 0);
+    raise_alert(&engine, "A0", &customer, 50.0);
+    file_complaint(&engine, "C0", &customer);
+    engine.run_ticks(1).unwrap();
+
+    let bad = json!({ "case_id": "ic-A0", "disposition": "maybe", "note": "" });
+    let rejected = CommandResult::apply(&mut engine, "close_case", &bad, None).unwrap();
+    assert_eq!(rejected.code, Some(RejectionCode::ValidationFailed));
+    let fields: Vec<&str> = rejected.errors.iter().map(|e| e.field.as_str()).collect();
+    assert_eq!(fields, ["disposition", "note"]);
+    let missing = json!({ "case_id": "ic-NOPE", "analyst": "dana" });
+    let rejected = CommandResult::apply(&mut engine, "assign_case", &missing, None).unwrap();
+    assert_eq!(rejected.code, Some(RejectionCode::NotFound));
+
+    let payload = json!({ "case_id": "ic-A0", "disposition": "unfounded", "note": "known payroll pattern" });
+    let accepted = CommandResult::apply(&mut engine, "close_case", &payload, None).unwrap();
+    assert!(accepted.accepted);
+    assert_eq!(accepted.entity_ids, ["ic-A0"]);
+    engine.run_ticks(1).unwrap();
+
+    let timeline = EntityTimeline::query(&engine.store, run_id, EntityType::InvestigationCase, "ic-A0", None, None).unwrap();
+    let first = &timeline.entries[0];
+    assert_eq!((first.tick, first.ref_id.as_str(), first.kind), (40, "A0", TimelineKind::Alert));
+    let opened = timeline.entries.iter().find(|e| e.source == "investigation_case").unwrap();
+    assert_eq!((opened.tick, opened.kind), (41, TimelineKind::StatusChange));
+    let last = timeline.entries.last().unwrap();
+    assert_eq!((last.tick, last.summary.as_str()), (42, "case closed: unfounded"));
+    for source in ["aml_alert", "complaint", "case_note"] {
+        assert!(timeline.entries.iter().any(|e| e.source == source), "no {source} on the timeline");
+    }
+    assert!(timeline.entries.iter().any(|e| e.summary == "close: known payroll pattern"));
+    assert!(timeline.entries.iter().all(|e| e.source != "transactions"));
+    assert!(EntityTimeline::query(&engine.store, run_id, EntityType::InvestigationCase, "ic-NOPE", None, None).is_err());
+}
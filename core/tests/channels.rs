@@ -0,0 +1,165 @@
+//! Service channel tests — Phase 4.90.
+//!
+//! Tests cover: cash and payments booked through a channel and card
+//! purchases through none; each tick's channel activity costed per
+//! completed transaction; an outage on a channel's component diverting
+//! and abandoning its traffic, with complaints naming the channel; the
+//! economics subsystem booking the cost to serve as opex; channels
+//! staying off unless enabled; and incidents raised in a scenario run
+//! degrading the channels on their components.
+
+use fincrime_core::{
+    config::SimConfig, engine::SimEngine, event::SimEvent, scenario::ScenarioFile, store::SimStore,
+};
+use std::collections::HashMap;
+use std::path::Path;
+
+fn build(run_id: &str) -> SimEngine {
+    SimEngine::build_test_with_channels(run_id.into(), 42).unwrap()
+}
+
+fn events(engine: &SimEngine, run_id: &str, ticks: std::ops::RangeInclusive<u64>) -> Vec<SimEvent> {
+    ticks
+        .flat_map(|tick| engine.store_events_for_tick(run_id, tick).unwrap())
+        .map(|e| serde_json::from_str(&e.payload).unwrap())
+        .collect()
+}
+
+/// Every channel books debits; withdrawals go through an ATM or a
+/// branch, and card purchases carry no channel.
+#[test]
+fn debits_booked_through_channels() {
+    let run_id = "channels-booked-test";
+    let mut engine = build(run_id);
+    engine.run_ticks(10).unwrap();
+
+    let counts = engine.store.debit_channel_counts(run_id).unwrap();
+    let mut by_channel: HashMap<&str, i64> = HashMap::new();
+    for row in &counts {
+        if let Some(channel) = &row.channel {
+            *by_channel.entry(channel.as_str()).or_default() += row.count;
+        }
+        if row.rail_id == "card" {
+            assert_eq!(row.channel, None);
+        }
+        if row.category == "cash_withdrawal" {
+            assert!(matches!(row.channel.as_deref(), Some("atm" | "branch")), "{row:?}");
+        }
+    }
+    for channel in ["atm", "branch", "online", "mobile", "call_center"] {
+        assert!(by_channel.get(channel).copied().unwrap_or(0) > 0, "no {channel} debits: {by_channel:?}");
+    }
+    assert!(counts.iter().any(|r| r.rail_id == "card" && r.count > 0));
+}
+
+/// Every channel reports every tick, up with nothing turned away while
+/// no incident is open, and costs its completed transactions.
+#[test]
+fn activity_costed_per_completed_transaction() {
+    let run_id = "channels-activity-test";
+    let mut engine = build(run_id);
+    engine.run_ticks(5).unwrap();
+
+    let rows = engine.store.channel_activity(run_id).unwrap();
+    assert_eq!(rows.len(), 5 * 5);
+    let rates = SimConfig::default_test().channels.cost_per_transaction;
+    for row in &rows {
+        let rate = rates.iter().find(|(c, _)| c.as_str() == row.channel).map(|(_, r)| *r).unwrap();
+        assert!((row.cost - row.completed as f64 * rate).abs() < 1e-9);
+        if row.status == "up" {
+            assert_eq!(row.diverted + row.abandoned, 0);
+        }
+    }
+    let total: f64 = rows.iter().map(|r| r.cost).sum();
+    assert!((engine.store.channel_cost_between(run_id, 1, 5).unwrap() - total).abs() < 1e-6);
+}
+
+/// With mobile banking on a P0 the mobile channel is down: its traffic
+/// is diverted or abandoned, and abandonments raise complaints about
+/// the mobile channel.
+#[test]
+fn outage_diverts_and_abandons_traffic() {
+    let run_id = "channels-outage-test";
+    let mut engine = build(run_id);
+    engine.run_ticks(5).unwrap();
+    engine.store.insert_incident(run_id, "INC-MOBILE", "mobile_banking", 5, "P0", "App down", 10).unwrap();
+    engine.run_ticks(1).unwrap();
+
+    let rows = engine.store.channel_activity(run_id).unwrap();
+    let mobile = rows.iter().find(|r| r.tick == 6 && r.channel == "mobile").unwrap();
+    assert_eq!(mobile.status, "down");
+    assert_eq!(mobile.completed, 0);
+    assert!(mobile.diverted > 0);
+    assert!(mobile.abandoned > 0);
+
+    let abandoned: Vec<_> = events(&engine, run_id, 6..=6)
+        .into_iter()
+        .filter_map(|e| match e {
+            SimEvent::ChannelTransactionAbandoned { channel, .. } => Some(channel),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(abandoned.len() as i64, rows.iter().filter(|r| r.tick == 6).map(|r| r.abandoned).sum::<i64>());
+    assert!(abandoned.iter().all(|c| c == "mobile"));
+
+    let complaints: Vec<_> = engine
+        .store
+        .open_complaints(run_id)
+        .unwrap()
+        .into_iter()
+        .filter(|c| c.service_channel.as_deref() == Some("mobile"))
+        .collect();
+    assert!(!complaints.is_empty());
+    assert!(complaints.iter().all(|c| c.issue == "service_failure"));
+}
+
+/// A quarter's opex includes what its channels cost to serve.
+#[test]
+fn channel_cost_booked_as_opex() {
+    let run_id = "channels-opex-test";
+    let mut engine = build(run_id);
+    engine.run_ticks(90).unwrap();
+
+    let pnl = engine.store_latest_pnl(run_id).unwrap().expect("Should have P&L");
+    let cost = engine.store.channel_cost_between(run_id, 1, 90).unwrap();
+    assert!(cost > 0.0);
+    assert!(pnl.opex > cost);
+}
+
+/// Channels are opt-in in tests.
+#[test]
+fn disabled_by_default() {
+    let run_id = "channels-disabled-test";
+    let mut engine = SimEngine::build_test(run_id.into(), 42).unwrap();
+    engine.run_ticks(5).unwrap();
+
+    assert!(engine.store.debit_channel_counts(run_id).unwrap().iter().all(|r| r.channel.is_none()));
+    assert!(engine.store.channel_activity(run_id).unwrap().is_empty());
+}
+
+/// A run built from a scenario raises incidents of its own, and the
+/// channels on an affected component report it down or degraded.
+#[test]
+fn scenario_run_incidents_degrade_channels() {
+    let (db, run_id) = ("test_channels_scenario.db", "channels-scenario-test");
+    let _ = std::fs::remove_file(db);
+    let store = SimStore::open(db).unwrap();
+    store.migrate().unwrap();
+    store.insert_run(run_id, 42, "0.1.0-test").unwrap();
+    let data_dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("../data");
+    let scenario = ScenarioFile::from_toml("initial_customers = 40").unwrap();
+    let mut engine =
+        SimEngine::build_with_scenario(run_id.into(), 42, &store, &data_dir.to_string_lossy(), 0.5, &scenario)
+            .unwrap();
+    engine.run_ticks(60).unwrap();
+
+    let incidents = events(&engine, run_id, 1..=60)
+        .into_iter()
+        .filter(|e| matches!(e, SimEvent::IncidentCreated { .. }))
+        .count();
+    assert!(incidents > 0, "no incidents in 60 ticks");
+    let rows = engine.store.channel_activity(run_id).unwrap();
+    assert!(rows.iter().any(|r| r.status != "up"), "no channel was affected");
+    drop(engine);
+    let _ = std::fs::remove_file(db);
+}
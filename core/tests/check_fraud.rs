@@ -0,0 +1,186 @@
+//! Check fraud tests — Phase 4.54.
+//!
+//! Tests cover: no checks unless enabled, and genuine checks clearing on
+//! the check rail in serial order with more fraud during the wave; the
+//! serial rules flagging washed and counterfeit items far more often than
+//! genuine ones; the midnight deadline putting late returns on the bank;
+//! counterfeit deposits cashed out before the paying bank returns them;
+//! and the bank's share of the losses reaching the quarterly P&L.
+
+use fincrime_core::{
+    check_fraud::{DUPLICATE_SERIAL, SERIAL_OUT_OF_RANGE},
+    engine::SimEngine,
+    store::check_fraud::CheckItemRow,
+};
+use serde_json::Value;
+
+const TICKS: u64 = 30;
+
+fn engine(run_id: &str, review_capacity: usize) -> SimEngine {
+    SimEngine::build_test_with_check_fraud(run_id.into(), 42, review_capacity).unwrap()
+}
+
+/// Payloads of every `event_type` event so far, in order.
+fn events(engine: &SimEngine, event_type: &str) -> Vec<Value> {
+    (0..=engine.clock.current_tick)
+        .flat_map(|tick| engine.store.events_for_tick(&engine.run_id, tick).unwrap())
+        .filter(|e| e.event_type == event_type)
+        .map(|e| serde_json::from_str(&e.payload).unwrap())
+        .collect()
+}
+
+fn bank_loss(items: &[CheckItemRow]) -> f64 {
+    items.iter().filter(|i| i.loss_bearer.as_deref() == Some("bank")).map(|i| i.loss).sum()
+}
+
+/// Off by default; on, each business writes its checks in serial order
+/// and the wave brings more washed and counterfeit items a day.
+#[test]
+fn checks_clear_in_serial_order() {
+    let mut plain = SimEngine::build_test("check-fraud-off-test".into(), 42).unwrap();
+    plain.run_ticks(TICKS).unwrap();
+    assert!(plain.store.check_items(&plain.run_id).unwrap().is_empty());
+    assert!(events(&plain, "check_items_booked").is_empty());
+
+    let mut engine = engine("check-fraud-clear-test", 20);
+    engine.run_ticks(TICKS).unwrap();
+    let items = engine.store.check_items(&engine.run_id).unwrap();
+    for side in ["presented", "deposited"] {
+        assert!(items.iter().any(|i| i.side == side && i.typology == "genuine"), "no genuine {side} checks");
+    }
+    let mut drawers: Vec<&str> = items.iter().filter(|i| i.side == "presented").map(|i| i.drawer.as_str()).collect();
+    drawers.dedup();
+    for drawer in drawers {
+        let serials: Vec<i64> = items
+            .iter()
+            .filter(|i| i.drawer == drawer && i.side == "presented" && i.typology == "genuine")
+            .map(|i| i.serial)
+            .collect();
+        assert!(serials.iter().enumerate().all(|(n, s)| *s == 1001 + n as i64), "{drawer}: {serials:?}");
+    }
+
+    let booked = events(&engine, "check_items_booked");
+    let total: f64 = booked.iter().map(|e| e["amount"].as_f64().unwrap()).sum();
+    assert!((total - items.iter().map(|i| i.amount).sum::<f64>()).abs() < 1e-6);
+    let per_tick = |wave: bool| {
+        let days: Vec<&Value> = booked.iter().filter(|e| e["wave"] == wave).collect();
+        days.iter().map(|e| e["fraudulent"].as_f64().unwrap()).sum::<f64>() / days.len() as f64
+    };
+    assert!(per_tick(true) > 3.0 * per_tick(false), "{} vs {}", per_tick(true), per_tick(false));
+}
+
+/// DUPLICATE_SERIAL and SERIAL_OUT_OF_RANGE flag most washed and
+/// counterfeit items and few genuine ones; every flag is a fraud alert.
+#[test]
+fn serial_rules_flag_fraud() {
+    let mut engine = engine("check-fraud-rules-test", 20);
+    engine.run_ticks(TICKS).unwrap();
+    let items = engine.store.check_items(&engine.run_id).unwrap();
+    let flagged_share = |fraud: bool| {
+        let pool: Vec<&CheckItemRow> = items.iter().filter(|i| (i.typology != "genuine") == fraud).collect();
+        pool.iter().filter(|i| i.rule.is_some()).count() as f64 / pool.len() as f64
+    };
+    let (fraud, genuine) = (flagged_share(true), flagged_share(false));
+    assert!(fraud > 0.5 && genuine < 0.05, "{fraud} of fraudulent items flagged vs {genuine} of genuine");
+
+    let alerts: Vec<Value> = events(&engine, "fraud_alert_generated")
+        .into_iter()
+        .filter(|e| e["alert_type"] == DUPLICATE_SERIAL || e["alert_type"] == SERIAL_OUT_OF_RANGE)
+        .collect();
+    assert_eq!(alerts.len(), items.iter().filter(|i| i.rule.is_some()).count());
+    for alert in &alerts {
+        let item = items.iter().find(|i| i.txn_id == alert["entity_id"].as_str().unwrap()).unwrap();
+        assert_eq!(item.rule.as_deref(), alert["alert_type"].as_str());
+        assert_eq!(alert["tick"].as_u64(), Some(item.tick));
+        if item.side == "deposited" {
+            assert_eq!(item.rule.as_deref(), Some(SERIAL_OUT_OF_RANGE));
+            assert!(!(1001..=9999).contains(&item.serial));
+        }
+    }
+    assert!(items.iter().any(|i| i.rule.as_deref() == Some(DUPLICATE_SERIAL) && i.typology == "washed"));
+}
+
+/// A washed check the desk returns by the midnight deadline goes back to
+/// the depositary bank; with the desk swamped by the wave, late returns
+/// and unflagged items land on the bank instead.
+#[test]
+fn midnight_deadline_decides_who_pays() {
+    let mut staffed = engine("check-fraud-staffed-test", 100);
+    staffed.run_ticks(TICKS).unwrap();
+    let mut swamped = engine("check-fraud-swamped-test", 1);
+    swamped.run_ticks(TICKS).unwrap();
+
+    let washed = |engine: &SimEngine| -> Vec<CheckItemRow> {
+        engine.store.check_items(&engine.run_id).unwrap().into_iter().filter(|i| i.typology == "washed").collect()
+    };
+    for item in washed(&staffed).iter().chain(&washed(&swamped)) {
+        match (item.status.as_str(), item.rule.is_some()) {
+            ("returned", true) => {
+                assert!(item.resolved_tick.unwrap() <= item.deadline_tick);
+                assert_eq!(item.loss_bearer.as_deref(), Some("depositary_bank"));
+            }
+            ("reimbursed", flagged) => {
+                assert!(item.resolved_tick.unwrap() > item.deadline_tick, "{flagged}: {item:?}");
+                assert_eq!((item.loss_bearer.as_deref(), item.loss), (Some("bank"), item.amount));
+            }
+            ("pending_review", true) => assert_eq!(item.resolved_tick, None),
+            ("paid", false) => assert!(item.deadline_tick >= TICKS),
+            other => panic!("{other:?}: {item:?}"),
+        }
+    }
+    let flagged_returned = |items: Vec<CheckItemRow>| {
+        let flagged: Vec<CheckItemRow> = items.into_iter().filter(|i| i.rule.is_some()).collect();
+        flagged.iter().filter(|i| i.status == "returned").count() as f64 / flagged.len() as f64
+    };
+    assert_eq!(flagged_returned(washed(&staffed)), 1.0);
+    assert!(flagged_returned(washed(&swamped)) < 1.0);
+    assert!(bank_loss(&washed(&swamped)) > bank_loss(&washed(&staffed)));
+}
+
+/// Unflagged counterfeit deposits are cashed out before the paying bank
+/// returns them, so the charge-back falls short; held ones are charged
+/// back in full.
+#[test]
+fn counterfeit_deposits_cashed_out_before_return() {
+    let mut engine = engine("check-fraud-counterfeit-test", 100);
+    engine.run_ticks(TICKS).unwrap();
+    let counterfeits: Vec<CheckItemRow> = engine
+        .store
+        .check_items(&engine.run_id)
+        .unwrap()
+        .into_iter()
+        .filter(|i| i.typology == "counterfeit" && i.resolved_tick.is_some())
+        .collect();
+    let (held, missed): (Vec<_>, Vec<_>) = counterfeits.into_iter().partition(|i| i.rule.is_some());
+    assert!(!held.is_empty() && !missed.is_empty());
+    for item in &held {
+        assert_eq!((item.status.as_str(), item.resolved_tick), ("returned", Some(item.tick)));
+    }
+    for item in &missed {
+        assert_eq!(item.resolved_tick, Some(item.deadline_tick));
+        assert!(item.loss <= item.amount);
+        match item.status.as_str() {
+            "returned" => assert!(item.loss_bearer.is_none() || item.loss_bearer.as_deref() == Some("bank")),
+            "late_return" => assert_eq!((item.loss_bearer.as_deref(), item.loss), (Some("paying_bank"), item.amount)),
+            other => panic!("{other}"),
+        }
+    }
+    assert!(bank_loss(&missed) > bank_loss(&held), "{} vs {}", bank_loss(&missed), bank_loss(&held));
+
+    let resolved = events(&engine, "check_fraud_resolved");
+    let total: f64 = resolved.iter().filter(|e| e["loss_bearer"] == "bank").map(|e| e["loss"].as_f64().unwrap()).sum();
+    assert!((total - bank_loss(&engine.store.check_items(&engine.run_id).unwrap())).abs() < 1e-6);
+}
+
+/// The quarter's P&L books what the bank lost to check fraud.
+#[test]
+fn bank_losses_reach_the_pnl() {
+    let mut engine = engine("check-fraud-pnl-test", 1);
+    engine.run_ticks(90).unwrap();
+    let pnl = engine.store.latest_pnl_snapshot(&engine.run_id).unwrap().unwrap();
+    let items = engine.store.check_items(&engine.run_id).unwrap();
+    let quarter: Vec<CheckItemRow> =
+        items.into_iter().filter(|i| i.resolved_tick.is_some_and(|t| t + 89 >= pnl.tick && t <= pnl.tick)).collect();
+    assert!(bank_loss(&quarter) > 0.0);
+    assert!((pnl.fraud_loss - bank_loss(&quarter)).abs() < 1e-6, "{} vs {}", pnl.fraud_loss, bank_loss(&quarter));
+}
@@ -0,0 +1,197 @@
+//! Churn prediction evaluation tests — Phase 4.64.
+//!
+//! Tests cover: each score batch judged once per horizon, as soon as it
+//! elapses; realized churn, calibration and Brier score matching the
+//! scores and the churn that followed; AUC matching a count of
+//! churner-stayer pairs; later batches that score worse than the
+//! segment's baseline flagged as drift, small segments never; and no
+//! evaluation unless enabled.
+
+use fincrime_core::{
+    config::SimConfig,
+    engine::SimEngine,
+    store::{churn_evaluation::ChurnPredictionEvalRow, SimStore},
+};
+use serde_json::Value;
+use std::collections::HashMap;
+
+fn events(engine: &SimEngine, event_type: &str) -> Vec<Value> {
+    (0..=engine.clock.current_tick)
+        .flat_map(|tick| engine.store.events_for_tick(&engine.run_id, tick).unwrap())
+        .filter(|e| e.event_type == event_type)
+        .map(|e| serde_json::from_str(&e.payload).unwrap())
+        .collect()
+}
+
+/// Each customer in `row`'s segment scored in its batch, with their
+/// prediction for its horizon and whether a churn event followed within
+/// it.
+fn outcomes(engine: &SimEngine, row: &ChurnPredictionEvalRow) -> Vec<(f64, bool)> {
+    let churn_ticks: HashMap<String, u64> = events(engine, "customer_churned")
+        .iter()
+        .map(|e| (e["customer_id"].as_str().unwrap().to_string(), e["tick"].as_u64().unwrap()))
+        .collect();
+    engine
+        .store_all_churn_scores(&engine.run_id, row.score_tick)
+        .unwrap()
+        .into_iter()
+        .filter(|score| engine.store.customer_segment(&engine.run_id, &score.customer_id).unwrap() == row.segment)
+        .map(|score| {
+            let predicted = if row.horizon == 30 { score.predicted_churn_30d } else { score.predicted_churn_90d };
+            let churned = churn_ticks
+                .get(&score.customer_id)
+                .is_some_and(|tick| (row.score_tick..row.score_tick + row.horizon).contains(tick));
+            (predicted, churned)
+        })
+        .collect()
+}
+
+/// Every batch is judged at 30 days once those have passed, and at 90
+/// days once those have; a batch is never judged twice.
+#[test]
+fn horizons_are_judged_once_elapsed() {
+    let mut engine = SimEngine::build_test_with_churn_evaluation("ce-horizon-test".into(), 42).unwrap();
+    let run_id = engine.run_id.clone();
+    engine.run_ticks(121).unwrap();
+
+    let rows = engine.store.churn_prediction_evals(&run_id).unwrap();
+    let mut judged: Vec<(u64, u64)> = rows.iter().map(|r| (r.score_tick, r.horizon)).collect();
+    judged.dedup();
+    assert_eq!(judged, [(30, 30), (30, 90), (60, 30), (90, 30)]);
+    assert!(rows.iter().all(|r| r.eval_tick == r.score_tick + r.horizon));
+    assert!(rows.iter().all(|r| !r.drift));
+
+    let evaluated = events(&engine, "churn_predictions_evaluated");
+    assert_eq!(evaluated.len(), judged.len());
+    for event in &evaluated {
+        let (score_tick, horizon) = (event["score_tick"].as_u64().unwrap(), event["horizon"].as_u64().unwrap());
+        let batch: Vec<_> = rows.iter().filter(|r| (r.score_tick, r.horizon) == (score_tick, horizon)).collect();
+        let customers = batch.iter().map(|r| r.customers).sum::<i64>();
+        assert_eq!(event["customers"].as_i64(), Some(customers));
+        assert_eq!(event["drifting_segments"].as_u64(), Some(0));
+    }
+}
+
+/// A segment's realized rate counts the customers scored in the batch
+/// who churned within the horizon; calibration and Brier score follow
+/// from their predictions.
+#[test]
+fn metrics_follow_the_scores_and_realized_churn() {
+    let mut engine = SimEngine::build_test_with_churn_evaluation("ce-metrics-test".into(), 42).unwrap();
+    let run_id = engine.run_id.clone();
+    engine.run_ticks(121).unwrap();
+
+    let rows = engine.store.churn_prediction_evals(&run_id).unwrap();
+    assert!(rows.iter().any(|r| r.churned > 0));
+    for row in &rows {
+        let outcomes = outcomes(&engine, row);
+        let n = outcomes.len() as f64;
+        let churned = outcomes.iter().filter(|(_, churned)| *churned).count();
+        let mean = outcomes.iter().map(|(p, _)| p).sum::<f64>() / n;
+        let brier = outcomes.iter().map(|(p, c)| (p - if *c { 1.0 } else { 0.0 }).powi(2)).sum::<f64>() / n;
+        let label = format!("{} {} {}", row.score_tick, row.horizon, row.segment);
+        assert_eq!((row.customers, row.churned), (outcomes.len() as i64, churned as i64), "{label}");
+        assert!((row.mean_predicted - mean).abs() < 1e-12, "{label}");
+        assert!((row.realized_rate - churned as f64 / n).abs() < 1e-12, "{label}");
+        assert!((row.calibration_gap - (row.mean_predicted - row.realized_rate)).abs() < 1e-12, "{label}");
+        assert!((row.brier_score - brier).abs() < 1e-12, "{label}");
+    }
+}
+
+/// AUC is the share of churner-stayer pairs where the churner was scored
+/// higher, ties counting half; a segment without both has none.
+#[test]
+fn auc_counts_correctly_ranked_pairs() {
+    let mut engine = SimEngine::build_test_with_churn_evaluation("ce-auc-test".into(), 7).unwrap();
+    let run_id = engine.run_id.clone();
+    engine.run_ticks(121).unwrap();
+
+    let rows = engine.store.churn_prediction_evals(&run_id).unwrap();
+    assert!(rows.iter().any(|r| r.auc.is_some()));
+    for row in &rows {
+        let outcomes = outcomes(&engine, row);
+        let churners: Vec<f64> = outcomes.iter().filter(|(_, c)| *c).map(|(p, _)| *p).collect();
+        let stayers: Vec<f64> = outcomes.iter().filter(|(_, c)| !*c).map(|(p, _)| *p).collect();
+        if churners.is_empty() || stayers.is_empty() {
+            assert_eq!(row.auc, None);
+            continue;
+        }
+        let mut wins = 0.0;
+        for churner in &churners {
+            for stayer in &stayers {
+                wins += if churner > stayer { 1.0 } else if churner == stayer { 0.5 } else { 0.0 };
+            }
+        }
+        let expected = wins / (churners.len() * stayers.len()) as f64;
+        assert!((row.auc.unwrap() - expected).abs() < 1e-12, "{} {}", row.score_tick, row.segment);
+    }
+}
+
+/// With no tolerance, every batch after a segment's baseline that scored
+/// worse is flagged, and the warning carries the baseline it was held
+/// to. Segments under the minimum size are never judged.
+#[test]
+fn decay_from_the_baseline_is_flagged_as_drift() {
+    let run_id = "ce-drift-test";
+    let path = format!("./test_{run_id}.db");
+    let _ = std::fs::remove_file(&path);
+    let store = SimStore::open(&path).unwrap();
+    store.migrate().unwrap();
+    store.insert_run(run_id, 42, "0.1.0-test").unwrap();
+    let mut config = SimConfig::default_test();
+    config.churn_evaluation.enabled = true;
+    config.churn_evaluation.brier_rise_tolerance = 0.0;
+    let min_customers = config.churn_evaluation.min_customers as i64;
+    let mut engine = SimEngine::build_with_config(run_id.into(), 42, &store, config).unwrap();
+    engine.run_ticks(181).unwrap();
+
+    let rows = engine.store.churn_prediction_evals(run_id).unwrap();
+    let mut baselines: HashMap<(String, u64), &ChurnPredictionEvalRow> = HashMap::new();
+    let mut flagged = Vec::new();
+    for row in &rows {
+        if row.customers < min_customers {
+            assert!(!row.drift, "{} {}", row.score_tick, row.segment);
+            continue;
+        }
+        let Some(baseline) = baselines.get(&(row.segment.clone(), row.horizon)) else {
+            assert!(!row.drift);
+            baselines.insert((row.segment.clone(), row.horizon), row);
+            continue;
+        };
+        assert_eq!(row.drift, row.brier_score > baseline.brier_score, "{} {}", row.score_tick, row.segment);
+        if row.drift {
+            flagged.push((row.segment.clone(), row.horizon, row.score_tick, baseline.brier_score));
+        }
+    }
+    assert!(!flagged.is_empty());
+
+    let drift = events(&engine, "churn_model_drift_detected");
+    let mut warned: Vec<_> = drift
+        .iter()
+        .map(|e| {
+            (
+                e["segment"].as_str().unwrap().to_string(),
+                e["horizon"].as_u64().unwrap(),
+                e["score_tick"].as_u64().unwrap(),
+                e["baseline_brier_score"].as_f64().unwrap(),
+            )
+        })
+        .collect();
+    let key = |w: &(String, u64, u64, f64)| (w.0.clone(), w.1, w.2);
+    warned.sort_by_key(key);
+    flagged.sort_by_key(key);
+    assert_eq!(warned.iter().map(key).collect::<Vec<_>>(), flagged.iter().map(key).collect::<Vec<_>>());
+    assert!(warned.iter().zip(&flagged).all(|(w, f)| (w.3 - f.3).abs() < 1e-12));
+}
+
+/// The test default judges nothing.
+#[test]
+fn churn_evaluation_is_opt_in() {
+    let mut engine = SimEngine::build_test("ce-off-test".into(), 42).unwrap();
+    let run_id = engine.run_id.clone();
+    engine.run_ticks(121).unwrap();
+
+    assert!(engine.store_churn_score_count(&run_id).unwrap() > 0);
+    assert!(engine.store.churn_prediction_evals(&run_id).unwrap().is_empty());
+    assert!(events(&engine, "churn_predictions_evaluated").is_empty());
+}
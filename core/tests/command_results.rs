@@ -0,0 +1,212 @@
+//! IPC command result tests — Phase 4.45.
+//!
+//! Tests cover: unknown commands and malformed payloads rejected with a
+//! code and the offending field, out-of-range values reported one per
+//! field, `resolve_complaint` closing a complaint once and then refusing
+//! it, any `PlayerCommand` tag queued under the id it reports and
+//! applied next tick, and pause, resume and speed changes applied at
+//! once.
+
+use fincrime_core::{
+    clock::SimSpeed,
+    command::PlayerCommand,
+    command_result::{CommandResult, RejectionCode},
+    engine::SimEngine,
+    protocol::UiState,
+};
+use serde_json::json;
+
+fn engine(run_id: &str) -> SimEngine {
+    let mut engine = SimEngine::build_test(run_id.into(), 42).unwrap();
+    engine.run_ticks(10).unwrap();
+    engine
+}
+
+fn fields(result: &CommandResult) -> Vec<&str> {
+    result.errors.iter().map(|e| e.field.as_str()).collect()
+}
+
+/// An unknown `cmd` and a payload that is not an object, is missing a
+/// field or mistypes one are rejected without touching the run.
+#[test]
+fn unknown_and_malformed_commands_rejected() {
+    let mut engine = engine("cmd-result-malformed-test");
+
+    let unknown = CommandResult::apply(&mut engine, "launch_rocket", &json!({}), None).unwrap();
+    assert!(!unknown.accepted);
+    assert_eq!(unknown.code, Some(RejectionCode::UnknownCommand));
+    assert_eq!(fields(&unknown), ["cmd"]);
+
+    let cases = [
+        ("set_sar_threshold", json!([1, 2]), "payload"),
+        ("set_sar_threshold", json!({}), "threshold"),
+        ("set_sar_threshold", json!({ "threshold": "high" }), "payload"),
+        ("resolve_complaint", json!({ "complaint_id": "x", "resolution": "explanation_only" }), "refund"),
+    ];
+    for (cmd, payload, field) in cases {
+        let result = CommandResult::apply(&mut engine, cmd, &payload, None).unwrap();
+        assert_eq!(result.code, Some(RejectionCode::InvalidPayload), "{cmd} {payload}");
+        assert_eq!(fields(&result), [field], "{cmd} {payload}");
+        assert!(result.command_id.is_none() && result.state.is_none());
+    }
+
+    let before = UiState::from_engine(&engine).unwrap();
+    let with_state = unknown.with_state(&engine, false).unwrap();
+    assert!(with_state.state.is_none(), "a rejection carries no state");
+    assert_eq!(UiState::from_engine(&engine).unwrap().complaint_count, before.complaint_count);
+}
+
+/// Out-of-range and unknown values fail validation, with every bad
+/// field listed, and the rejection reads as a command error for gRPC.
+#[test]
+fn invalid_values_listed_per_field() {
+    let mut engine = engine("cmd-result-values-test");
+
+    let result = CommandResult::apply(
+        &mut engine,
+        "set_customer_limit",
+        &json!({ "limit_type": "lunch", "scope": "galaxy", "amount": -5.0 }),
+        None,
+    )
+    .unwrap();
+    assert_eq!(result.code, Some(RejectionCode::ValidationFailed));
+    assert_eq!(fields(&result), ["limit_type", "scope", "amount"]);
+
+    let cases = [
+        ("set_link_verification_strictness", json!({ "strictness": 1.5 }), "strictness"),
+        ("set_sar_threshold", json!({ "threshold": 120.0 }), "threshold"),
+        ("set_visa_restriction_policy", json!({ "level": "frozen", "grace_ticks": 0 }), "level"),
+        ("draw_emergency_liquidity", json!({ "amount": 0.0 }), "amount"),
+        ("recall_wire", json!({ "txn_id": "" }), "txn_id"),
+        ("set_deposit_hold_policy", json!({ "class": "mail", "risk": null, "hold_ticks": 2 }), "class"),
+        ("sign_off_close_item", json!({ "period": "Q1-Y1", "item": "vibes" }), "item"),
+        ("post_close_adjustment", json!({ "period": "Q1-Y1", "amount": 250.0, "reason": " " }), "reason"),
+        ("set_staffing", json!({ "queue": "alerts", "ftes": -1.0 }), "ftes"),
+        ("set_app_reimbursement_policy", json!({ "policy": "some" }), "policy"),
+        (
+            "tune_monitoring_rule",
+            json!({ "rule_id": "STRUCT_9K", "change": "exclude_customer", "threshold_multiplier": 1.0,
+                    "customer_id": null, "review_id": null }),
+            "customer_id",
+        ),
+    ];
+    for (cmd, payload, field) in cases {
+        let result = CommandResult::apply(&mut engine, cmd, &payload, None).unwrap();
+        assert_eq!(result.code, Some(RejectionCode::ValidationFailed), "{cmd}");
+        assert_eq!(fields(&result), [field], "{cmd}");
+    }
+
+    let err = result.into_result().unwrap_err();
+    assert_eq!(err.code(), "command_error");
+    assert!(err.to_string().contains("scope:"), "{err}");
+}
+
+/// `resolve_complaint` closes an open complaint at once and reports it;
+/// the same complaint again, or one that never existed, is not found.
+#[test]
+fn resolve_complaint_accepted_once() {
+    let mut engine = engine("cmd-result-resolve-test");
+    let before = UiState::from_engine(&engine).unwrap();
+    let complaint_id = before.complaints[0].complaint_id.clone();
+    let payload = json!({ "complaint_id": complaint_id, "resolution": "monetary_relief", "refund": 25.0 });
+
+    let result = CommandResult::apply(&mut engine, "resolve_complaint", &payload, None)
+        .and_then(|r| r.with_state(&engine, false))
+        .unwrap();
+    assert!(result.accepted, "{:?}", result.errors);
+    assert_eq!(result.entity_ids, std::slice::from_ref(&complaint_id));
+    assert!(result.command_id.is_none(), "applied directly, not queued");
+    let state = result.state.unwrap();
+    assert_eq!(state.backlog, before.backlog - 1);
+    assert!(state.complaints.iter().all(|c| c.complaint_id != complaint_id));
+
+    let again = CommandResult::apply(&mut engine, "resolve_complaint", &payload, None).unwrap();
+    assert_eq!(again.code, Some(RejectionCode::NotFound));
+    assert!(again.errors[0].message.contains("closed"), "{:?}", again.errors);
+
+    let missing = json!({ "complaint_id": "cmp-missing", "resolution": "explanation_only", "refund": 0.0 });
+    let missing = CommandResult::apply(&mut engine, "resolve_complaint", &missing, None).unwrap();
+    assert_eq!(missing.code, Some(RejectionCode::NotFound));
+
+    let bad_code = json!({ "complaint_id": state.complaints[0].complaint_id, "resolution": "refund", "refund": 0.0 });
+    let bad_code = CommandResult::apply(&mut engine, "resolve_complaint", &bad_code, None).unwrap();
+    assert_eq!(bad_code.code, Some(RejectionCode::ValidationFailed));
+    assert_eq!(fields(&bad_code), ["resolution"]);
+}
+
+/// Any player command is accepted by its tag, stored under the id it
+/// reports and applied on the next tick.
+#[test]
+fn player_commands_queued_with_id() {
+    let mut engine = engine("cmd-result-queued-test");
+    let payload = json!({ "product_id": "basic_checking", "fee_type": "overdraft_fee", "new_value": 30.0 });
+
+    let result = CommandResult::apply(&mut engine, "set_product_fee", &payload, None).unwrap();
+    assert!(result.accepted, "{:?}", result.errors);
+    assert_eq!(result.code, None);
+    assert_eq!(result.entity_ids, ["basic_checking"]);
+    let command_id = result.command_id.unwrap();
+
+    let stored = engine.store.get_player_command(&engine.run_id, &command_id).unwrap();
+    assert!(matches!(stored, Some(PlayerCommand::SetProductFee { new_value, .. }) if new_value == 30.0));
+
+    engine.run_ticks(1).unwrap();
+    let changed = engine
+        .store
+        .events_for_tick(&engine.run_id, 11)
+        .unwrap()
+        .into_iter()
+        .filter(|e| e.event_type == "product_fee_changed")
+        .map(|e| serde_json::from_str::<serde_json::Value>(&e.payload).unwrap())
+        .collect::<Vec<_>>();
+    assert_eq!(changed.len(), 1);
+    assert_eq!(changed[0]["new_value"], 30.0);
+}
+
+/// Pause holds the run until resume, refusing ticks without halting it,
+/// and a speed change sets how many ticks a step advances; each is still
+/// logged.
+#[test]
+fn clock_commands_apply_at_once() {
+    let mut engine = engine("cmd-result-clock-test");
+
+    let paused = CommandResult::apply(&mut engine, "pause", &json!({}), None).unwrap();
+    assert!(paused.accepted, "{:?}", paused.errors);
+    let command_id = paused.command_id.clone().unwrap();
+    let state = paused.with_state(&engine, true).unwrap().state.unwrap();
+    assert!(state.held);
+    let refused = engine.run_ticks(1).unwrap_err();
+    assert!(!refused.is_fatal(), "{refused}");
+    assert_eq!(engine.clock.current_tick, 10);
+
+    let speed = CommandResult::apply(&mut engine, "set_speed", &json!({ "speed": "accelerated" }), None).unwrap();
+    assert!(speed.accepted, "{:?}", speed.errors);
+    let bad_speed = CommandResult::apply(&mut engine, "set_speed", &json!({ "speed": "warp" }), None).unwrap();
+    assert_eq!(bad_speed.code, Some(RejectionCode::InvalidPayload));
+    assert!(engine.run_steps(1).is_err());
+
+    let resumed = CommandResult::apply(&mut engine, "resume", &json!(null), None).unwrap();
+    assert!(resumed.accepted, "{:?}", resumed.errors);
+    engine.run_steps(2).unwrap();
+    assert_eq!(engine.clock.current_tick, 24);
+    let state = UiState::from_engine(&engine).unwrap();
+    assert!(!state.held);
+    assert_eq!(state.speed, SimSpeed::Accelerated);
+
+    let stored = engine.store.get_player_command(&engine.run_id, &command_id).unwrap();
+    assert!(matches!(stored, Some(PlayerCommand::Pause)));
+}
+
+/// Every name in `PlayerCommand::NAMES` is a real tag and matches what
+/// `name()` reports, so the IPC never calls a known command unknown.
+#[test]
+fn command_names_match_tags() {
+    for name in PlayerCommand::NAMES {
+        if let Err(e) = serde_json::from_value::<PlayerCommand>(json!({ "cmd": name })) {
+            assert!(!e.to_string().contains("unknown variant"), "{name}: {e}");
+        }
+    }
+    let command = PlayerCommand::SetSarThreshold { threshold: 80.0 };
+    assert_eq!(serde_json::to_value(&command).unwrap()["cmd"], command.name());
+    assert!(PlayerCommand::NAMES.contains(&command.name()));
+}
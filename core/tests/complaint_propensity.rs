@@ -0,0 +1,141 @@
+//! Complaint propensity tests — Phase 4.51.
+//!
+//! Tests cover: no factors leaving complaint generation exactly as it
+//! was, issue factors scaling how often an issue is filed, segment and
+//! product factors acting on who complains, and scenario files with
+//! unknown names or negative factors rejected at load.
+
+use fincrime_core::{
+    complaint_subsystem::ComplaintRecord,
+    config::{ComplaintPropensity, SimConfig},
+    engine::SimEngine,
+};
+use std::path::Path;
+
+const TICKS: u64 = 30;
+
+fn run(run_id: &str, propensity: ComplaintPropensity) -> SimEngine {
+    let mut engine = SimEngine::build_test_with_complaint_propensity(run_id.into(), 42, propensity).unwrap();
+    engine.run_ticks(TICKS).unwrap();
+    engine
+}
+
+/// Every complaint filed in the run, open or closed.
+fn filed(engine: &SimEngine) -> Vec<ComplaintRecord> {
+    (1..=engine.clock.current_tick)
+        .flat_map(|tick| engine.store.events_for_tick(&engine.run_id, tick).unwrap())
+        .filter(|e| e.event_type == "complaint_filed")
+        .map(|e| {
+            let payload: serde_json::Value = serde_json::from_str(&e.payload).unwrap();
+            engine.store.get_complaint(&engine.run_id, payload["complaint_id"].as_str().unwrap()).unwrap()
+        })
+        .collect()
+}
+
+fn factors(pairs: &[(&str, f64)]) -> std::collections::HashMap<String, f64> {
+    pairs.iter().map(|(k, v)| (k.to_string(), *v)).collect()
+}
+
+/// An empty propensity files the same complaints as the plain test
+/// engine, on the same ticks.
+#[test]
+fn no_factors_change_nothing() {
+    let mut plain = SimEngine::build_test("propensity-plain-test".into(), 42).unwrap();
+    plain.run_ticks(TICKS).unwrap();
+    let neutral = run("propensity-neutral-test", ComplaintPropensity::default());
+
+    let summary = |engine: &SimEngine| -> Vec<_> {
+        filed(engine).into_iter().map(|c| (c.complaint_id, c.tick_opened, c.issue, c.customer_id)).collect()
+    };
+    let expected = summary(&plain);
+    assert!(expected.len() > 5, "need complaints, got {}", expected.len());
+    assert_eq!(summary(&neutral), expected);
+}
+
+/// An issue scaled to zero is never filed, and doubled is filed more
+/// often.
+#[test]
+fn issue_factor_scales_an_issue() {
+    let fee_disputes = |run_id: &str, factor: Option<f64>| {
+        let issues = factor.map(|f| factors(&[("fee_dispute", f)])).unwrap_or_default();
+        let engine = run(run_id, ComplaintPropensity { issues, ..Default::default() });
+        filed(&engine).iter().filter(|c| c.issue == "fee_dispute").count()
+    };
+    let baseline = fee_disputes("propensity-issue-base-test", None);
+    assert!(baseline > 0);
+    assert_eq!(fee_disputes("propensity-issue-off-test", Some(0.0)), 0);
+    assert!(fee_disputes("propensity-issue-double-test", Some(2.0)) > baseline);
+}
+
+/// A segment scaled to zero stops complaining while a tripled one
+/// complains more than it did.
+#[test]
+fn segment_factors_pick_who_complains() {
+    let segment_counts = |engine: &SimEngine| {
+        let mut counts = std::collections::BTreeMap::<String, usize>::new();
+        for c in filed(engine) {
+            *counts.entry(engine.store.customer_segment(&engine.run_id, &c.customer_id).unwrap()).or_default() += 1;
+        }
+        counts
+    };
+    let baseline = segment_counts(&run("propensity-segment-base-test", ComplaintPropensity::default()));
+    assert!(baseline.get("mass_market").copied().unwrap_or(0) > 0, "{baseline:?}");
+
+    let propensity = ComplaintPropensity {
+        segments: factors(&[("mass_market", 3.0), ("small_business", 0.0), ("premium", 0.0)]),
+        ..Default::default()
+    };
+    let scaled = segment_counts(&run("propensity-segment-test", propensity));
+    assert_eq!(scaled.keys().collect::<Vec<_>>(), ["mass_market"], "{scaled:?}");
+    assert!(scaled["mass_market"] > baseline["mass_market"], "{scaled:?} vs {baseline:?}");
+}
+
+/// A product scaled to zero files no complaints about it. The test
+/// scenario has only basic checking, so none are filed at all.
+#[test]
+fn product_factor_scales_a_product() {
+    let baseline = filed(&run("propensity-product-base-test", ComplaintPropensity::default()));
+    assert!(baseline.iter().any(|c| c.product == "basic_checking"));
+
+    let propensity = ComplaintPropensity { products: factors(&[("basic_checking", 0.0)]), ..Default::default() };
+    assert!(filed(&run("propensity-product-test", propensity)).is_empty());
+}
+
+/// Propensity naming a product, segment or issue the scenario lacks, or
+/// a negative factor, fails to load.
+#[test]
+fn bad_scenario_files_rejected() {
+    let data = Path::new(env!("CARGO_MANIFEST_DIR")).join("../data");
+    assert!(SimConfig::load(data.to_str().unwrap()).is_ok());
+
+    let cases = [
+        (r#"{ "products": { "mortgage": 2.0 } }"#, "complaint propensity products: unknown mortgage"),
+        (r#"{ "segments": { "retirees": 2.0 } }"#, "complaint propensity segments: unknown retirees"),
+        (r#"{ "issues": { "fee_dispute": -1.0 } }"#, "issues.fee_dispute must be a non-negative number"),
+    ];
+    for (i, (propensity, message)) in cases.into_iter().enumerate() {
+        let dir = std::env::temp_dir().join(format!("propensity_data_{}_{i}", std::process::id()));
+        copy_dir(&data, &dir);
+        let triggers = dir.join("complaints/complaint_triggers.json");
+        let mut file: serde_json::Value = serde_json::from_str(&std::fs::read_to_string(&triggers).unwrap()).unwrap();
+        file["propensity"] = serde_json::from_str(propensity).unwrap();
+        std::fs::write(&triggers, file.to_string()).unwrap();
+
+        let err = SimConfig::load(dir.to_str().unwrap()).unwrap_err().to_string();
+        let _ = std::fs::remove_dir_all(&dir);
+        assert!(err.contains(message), "{err}");
+    }
+}
+
+fn copy_dir(from: &Path, to: &Path) {
+    std::fs::create_dir_all(to).unwrap();
+    for entry in std::fs::read_dir(from).unwrap() {
+        let entry = entry.unwrap();
+        let target = to.join(entry.file_name());
+        if entry.file_type().unwrap().is_dir() {
+            copy_dir(&entry.path(), &target);
+        } else {
+            std::fs::copy(entry.path(), target).unwrap();
+        }
+    }
+}
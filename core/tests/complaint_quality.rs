@@ -0,0 +1,203 @@
+//! Complaint response quality tests — Phase 4.65.
+//!
+//! Tests cover: each closure scored from analyst skill, handling time and
+//! whether the resolution suits the issue; poor closures reopened with an
+//! SLA as long as the first and scored again when next closed; escalations
+//! coming back on the regulator channel and raising the UDAAP score; the
+//! desk performance report summing up each period with its quality trend;
+//! and no scoring unless enabled.
+
+use fincrime_core::{
+    config::{ComplaintQualityConfig, SimConfig},
+    engine::SimEngine,
+    store::SimStore,
+};
+
+fn build(run_id: &str, tune: impl FnOnce(&mut ComplaintQualityConfig)) -> SimEngine {
+    let path = format!("./test_{run_id}.db");
+    let _ = std::fs::remove_file(&path);
+    let store = SimStore::open(&path).unwrap();
+    store.migrate().unwrap();
+    store.insert_run(run_id, 42, "0.1.0-test").unwrap();
+    let mut config = SimConfig::default_test();
+    config.complaint_quality.enabled = true;
+    tune(&mut config.complaint_quality);
+    SimEngine::build_with_config(run_id.into(), 42, &store, config).unwrap()
+}
+
+/// Quality rests on appropriateness alone: 1 for a suited code, 0 for
+/// any other.
+fn appropriateness_only(config: &mut ComplaintQualityConfig) {
+    config.skill_weight = 0.0;
+    config.time_weight = 0.0;
+    config.appropriateness_weight = 1.0;
+}
+
+/// Close every open complaint at the current tick, the first `suited` of
+/// them with a code that suits a fee dispute and the rest with one that
+/// does not.
+fn close_open(engine: &SimEngine, suited: usize) -> Vec<String> {
+    let tick = engine.clock.current_tick;
+    let open = engine.store.open_complaints(&engine.run_id).unwrap();
+    for (i, complaint) in open.iter().enumerate() {
+        let code = if i < suited { "monetary_relief" } else { "explanation_only" };
+        engine.store_close_complaint_direct(&engine.run_id, &complaint.complaint_id, tick, code, 0.0).unwrap();
+    }
+    open.into_iter().map(|c| c.complaint_id).collect()
+}
+
+fn count_events(engine: &SimEngine, event_type: &str) -> i64 {
+    engine.store.count_events_in_range(&engine.run_id, 0, engine.clock.current_tick, event_type).unwrap()
+}
+
+/// The tick after a closure it is scored from the desk's skill, the time
+/// spent against a thorough response, and whether the code suits the
+/// issue.
+#[test]
+fn closures_are_scored_from_skill_time_and_appropriateness() {
+    let mut engine = SimEngine::build_test_with_complaint_quality("cq-score-test".into(), 42).unwrap();
+    let run_id = engine.run_id.clone();
+    engine.run_ticks(20).unwrap();
+    let closed = close_open(&engine, 2);
+    assert!(closed.len() > 2);
+    let opened: Vec<u64> =
+        closed.iter().map(|id| engine.store.get_complaint(&run_id, id).unwrap().tick_opened).collect();
+    engine.run_ticks(1).unwrap();
+
+    let config = SimConfig::default_test().complaint_quality;
+    for (i, (complaint_id, tick_opened)) in closed.iter().zip(opened).enumerate() {
+        let rows = engine.store.complaint_quality(&run_id, complaint_id).unwrap();
+        assert_eq!(rows.len(), 1, "{complaint_id}");
+        let row = &rows[0];
+        assert_eq!((row.tick_closed, row.scored_tick, row.handling_ticks), (20, 21, 20 - tick_opened));
+        assert_eq!(row.appropriate, i < 2);
+        let time_score = ((20 - tick_opened) as f64 / config.thorough_handling_ticks as f64).min(1.0);
+        let expected = config.skill_weight * config.analyst_skill
+            + config.time_weight * time_score
+            + config.appropriateness_weight * if row.appropriate { 1.0 } else { 0.0 };
+        assert!((row.time_score - time_score).abs() < 1e-12);
+        assert!((row.quality - expected).abs() < 1e-12, "{complaint_id}");
+
+        let status = engine.store.get_complaint(&run_id, complaint_id).unwrap().status;
+        assert_eq!(status == "open", row.outcome != "stands", "{complaint_id}");
+    }
+}
+
+/// A closure of quality 0 is always reopened and one of quality 1 never.
+/// The reopened complaint counts its age from the reopen, keeps its SLA
+/// window, and is scored again when next closed.
+#[test]
+fn poor_closures_are_reopened() {
+    let mut engine = build("cq-reopen-test", |c| {
+        appropriateness_only(c);
+        c.max_reopen_probability = 1.0;
+        c.max_escalation_probability = 0.0;
+    });
+    let run_id = engine.run_id.clone();
+    engine.run_ticks(20).unwrap();
+    let before: Vec<_> = engine.store.open_complaints(&run_id).unwrap();
+    let closed = close_open(&engine, 1);
+    engine.run_ticks(1).unwrap();
+
+    let (good, poor) = (&closed[0], &closed[1..]);
+    assert_eq!(engine.store.complaint_quality(&run_id, good).unwrap()[0].outcome, "stands");
+    assert_eq!(engine.store.get_complaint(&run_id, good).unwrap().status, "closed");
+    for complaint_id in poor {
+        let row = &engine.store.complaint_quality(&run_id, complaint_id).unwrap()[0];
+        assert_eq!((row.quality, row.outcome.as_str()), (0.0, "reopened"));
+        let original = before.iter().find(|c| &c.complaint_id == complaint_id).unwrap();
+        let reopened = engine.store.get_complaint(&run_id, complaint_id).unwrap();
+        assert_eq!((reopened.status.as_str(), reopened.tick_opened, reopened.tick_closed), ("open", 21, None));
+        assert_eq!(reopened.resolution_code, None);
+        assert_eq!(reopened.channel, original.channel);
+        assert_eq!(reopened.sla_due_tick - 21, original.sla_due_tick - original.tick_opened);
+    }
+    assert_eq!(count_events(&engine, "complaint_reopened"), poor.len() as i64);
+
+    engine.run_ticks(2).unwrap();
+    engine.store_close_complaint_direct(&run_id, &poor[0], 23, "monetary_relief", 0.0).unwrap();
+    engine.run_ticks(1).unwrap();
+    let rows = engine.store.complaint_quality(&run_id, &poor[0]).unwrap();
+    let closures: Vec<_> = rows.iter().map(|r| (r.tick_closed, r.handling_ticks)).collect();
+    assert_eq!(closures, [(20, 20 - before[1].tick_opened), (23, 2)]);
+    assert_eq!(rows[1].outcome, "stands");
+}
+
+/// An escalated complaint comes back on the regulator's channel, and each
+/// escalation adds to the UDAAP score.
+#[test]
+fn escalations_reach_the_regulator() {
+    let mut engine = build("cq-escalate-test", |c| {
+        appropriateness_only(c);
+        c.max_escalation_probability = 1.0;
+        c.escalation_udaap_impact = 2.5;
+    });
+    let run_id = engine.run_id.clone();
+    engine.run_ticks(20).unwrap();
+    let udaap_before = engine.store.get_udaap_score(&run_id).unwrap();
+    let closed = close_open(&engine, 0);
+    engine.run_ticks(1).unwrap();
+
+    for complaint_id in &closed {
+        assert_eq!(engine.store.complaint_quality(&run_id, complaint_id).unwrap()[0].outcome, "escalated");
+        let complaint = engine.store.get_complaint(&run_id, complaint_id).unwrap();
+        assert_eq!((complaint.status.as_str(), complaint.channel.as_str()), ("open", "regulator"));
+    }
+    assert_eq!(count_events(&engine, "complaint_escalated_to_regulator"), closed.len() as i64);
+    assert_eq!(count_events(&engine, "complaint_reopened"), 0);
+    let rise = engine.store.get_udaap_score(&run_id).unwrap() - udaap_before;
+    assert!((rise - 2.5 * closed.len() as f64).abs() < 1e-9, "{rise}");
+}
+
+/// Each report covers the closures scored in its period; the trend is the
+/// change in average quality from the report before.
+#[test]
+fn desk_report_tracks_the_quality_trend() {
+    let mut engine = build("cq-report-test", |c| {
+        c.max_reopen_probability = 0.0;
+        c.max_escalation_probability = 0.0;
+    });
+    let run_id = engine.run_id.clone();
+    for tick in 1..=60u64 {
+        engine.run_ticks(1).unwrap();
+        if tick % 5 == 0 {
+            // Suited codes in the first period, unsuited in the second
+            close_open(&engine, if tick < 30 { usize::MAX } else { 0 });
+        }
+    }
+
+    let reports = engine.store.desk_performance_reports(&run_id).unwrap();
+    assert_eq!(reports.iter().map(|r| r.tick).collect::<Vec<_>>(), [30, 60]);
+    for report in &reports {
+        let scored = engine.store.complaint_quality_scored(&run_id, report.tick - 29, report.tick).unwrap();
+        assert!(!scored.is_empty());
+        let mean = scored.iter().map(|r| r.quality).sum::<f64>() / scored.len() as f64;
+        let poor = scored.iter().filter(|r| r.quality < 0.5).count() as i64;
+        assert_eq!((report.closures, report.poor_quality_count), (scored.len() as i64, poor));
+        assert!((report.avg_quality.unwrap() - mean).abs() < 1e-12);
+        assert_eq!((report.reopened_count, report.escalated_count), (0, 0));
+    }
+    assert_eq!(reports[0].quality_trend, None);
+    let trend = reports[1].quality_trend.unwrap();
+    assert!((trend - (reports[1].avg_quality.unwrap() - reports[0].avg_quality.unwrap())).abs() < 1e-12);
+    assert!(trend < 0.0, "{trend}");
+    assert_eq!(count_events(&engine, "desk_performance_reported"), 2);
+}
+
+/// The test default scores nothing and leaves every closure closed.
+#[test]
+fn complaint_quality_is_opt_in() {
+    let mut engine = SimEngine::build_test("cq-off-test".into(), 42).unwrap();
+    let run_id = engine.run_id.clone();
+    engine.run_ticks(20).unwrap();
+    let closed = close_open(&engine, 0);
+    assert!(!closed.is_empty());
+    engine.run_ticks(40).unwrap();
+
+    for complaint_id in &closed {
+        assert!(engine.store.complaint_quality(&run_id, complaint_id).unwrap().is_empty());
+        assert_eq!(engine.store.get_complaint(&run_id, complaint_id).unwrap().status, "closed");
+    }
+    assert!(engine.store.desk_performance_reports(&run_id).unwrap().is_empty());
+    assert_eq!(count_events(&engine, "desk_performance_reported"), 0);
+}
@@ -0,0 +1,132 @@
+//! Complaint SLA policy tests — Phase 4.29.
+//!
+//! Tests cover: the most specific policy winning with regulatory limits
+//! capping it, a player policy change applying only to complaints opened
+//! from its effective tick, and rejection of changes that would relax a
+//! regulatory deadline or are malformed.
+
+use fincrime_core::command::PlayerCommand;
+use fincrime_core::complaint_sla::SlaPolicies;
+use fincrime_core::config::{ComplaintSlaConfig, SimConfig, SlaPolicyRule};
+use fincrime_core::engine::SimEngine;
+
+fn rule(priority: Option<&str>, product: Option<&str>, channel: Option<&str>, resolve_days: u64) -> SlaPolicyRule {
+    SlaPolicyRule {
+        priority: priority.map(Into::into),
+        product: product.map(Into::into),
+        channel: channel.map(Into::into),
+        resolve_days,
+    }
+}
+
+fn set_policy(engine: &mut SimEngine, policy: SlaPolicyRule, effective_tick: u64) {
+    engine
+        .submit_command(PlayerCommand::SetComplaintSlaPolicy {
+            priority: policy.priority,
+            product: policy.product,
+            channel: policy.channel,
+            resolve_days: policy.resolve_days,
+            effective_tick,
+        })
+        .unwrap();
+}
+
+/// More fields in scope beats fewer, the trigger's deadline applies when
+/// nothing matches, and regulatory limits cap whatever policy picked.
+#[test]
+fn most_specific_policy_wins_under_regulatory_caps() {
+    let mut policies = SlaPolicies::new(&ComplaintSlaConfig {
+        policies: vec![
+            rule(Some("standard"), None, None, 12),
+            rule(Some("standard"), Some("basic_checking"), None, 8),
+            rule(None, None, Some("branch"), 4),
+            rule(None, None, Some("regulator"), 30),
+        ],
+        regulatory_limits: SimConfig::default_test().complaint_sla.regulatory_limits,
+    });
+
+    assert_eq!(policies.resolve_days("standard", "basic_checking", "branch", 0, 15), 8);
+    assert_eq!(policies.resolve_days("standard", "savings", "phone", 0, 15), 12);
+    assert_eq!(policies.resolve_days("low", "savings", "branch", 0, 20), 4);
+    assert_eq!(policies.resolve_days("low", "savings", "phone", 0, 20), 20, "Trigger deadline");
+    assert_eq!(policies.resolve_days("low", "savings", "regulator", 0, 20), 15, "CFPB window");
+    assert_eq!(policies.resolve_days("urgent", "savings", "phone", 0, 30), 10, "Reg E");
+
+    policies.schedule(rule(Some("standard"), None, None, 6), 50);
+    assert_eq!(policies.resolve_days("standard", "savings", "phone", 49, 15), 12);
+    assert_eq!(policies.resolve_days("standard", "savings", "phone", 50, 15), 6);
+    assert_eq!(policies.regulatory_cap(&rule(Some("urgent"), Some("savings"), None, 1)), Some(10));
+    assert_eq!(policies.regulatory_cap(&rule(Some("standard"), None, None, 1)), None);
+}
+
+/// Complaints opened before the effective tick keep the old 15-day
+/// deadline; those opened from it get the new one.
+#[test]
+fn policy_change_applies_from_effective_tick() {
+    let run_id = "sla-policy-effective-test";
+    let mut engine = SimEngine::build_test(run_id.into(), 42).unwrap();
+    set_policy(&mut engine, rule(Some("standard"), None, Some("phone"), 5), 20);
+    engine.run_ticks(40).unwrap();
+
+    assert_eq!(engine.store.sla_policies(run_id).unwrap().len(), 1);
+    let complaints = engine.store.open_complaints(run_id).unwrap();
+    let (before, after): (Vec<_>, Vec<_>) = complaints.iter().partition(|c| c.tick_opened < 20);
+    assert!(!before.is_empty() && !after.is_empty());
+    for c in before {
+        assert_eq!(c.sla_due_tick - c.tick_opened, 15, "{}", c.complaint_id);
+    }
+    for c in after {
+        assert_eq!(c.sla_due_tick - c.tick_opened, 5, "{}", c.complaint_id);
+    }
+    assert!(engine.store_sla_breach_count(run_id).unwrap() > 0);
+}
+
+/// An urgent policy longer than Reg E allows is rejected, and urgent
+/// disputes keep their 3-day deadline.
+#[test]
+fn relaxing_regulatory_deadline_is_rejected() {
+    let run_id = "sla-policy-regulatory-test";
+    let mut engine = SimEngine::build_test_with_ach_returns(run_id.into(), 42).unwrap();
+    set_policy(&mut engine, rule(Some("urgent"), None, None, 30), 0);
+    engine.run_ticks(30).unwrap();
+
+    assert!(engine.store.sla_policies(run_id).unwrap().is_empty());
+    assert_eq!(
+        engine.store.count_events_in_range(run_id, 0, 2, "complaint_sla_policy_rejected").unwrap(),
+        1
+    );
+    let urgent: Vec<_> = engine
+        .store
+        .open_complaints(run_id)
+        .unwrap()
+        .into_iter()
+        .filter(|c| c.priority == "urgent")
+        .collect();
+    assert!(!urgent.is_empty());
+    assert!(urgent.iter().all(|c| c.sla_due_tick - c.tick_opened == 3));
+}
+
+/// Unknown priorities or channels, zero-day deadlines and backdated
+/// changes are rejected without touching the policy.
+#[test]
+fn malformed_policies_are_rejected() {
+    let run_id = "sla-policy-reject-test";
+    let mut engine = SimEngine::build_test(run_id.into(), 42).unwrap();
+    engine.run_ticks(5).unwrap();
+
+    set_policy(&mut engine, rule(Some("critical"), None, None, 5), 10);
+    set_policy(&mut engine, rule(None, None, Some("carrier_pigeon"), 5), 10);
+    set_policy(&mut engine, rule(Some("standard"), None, None, 0), 10);
+    set_policy(&mut engine, rule(Some("standard"), None, None, 5), 2);
+    set_policy(&mut engine, rule(Some("low"), Some("basic_checking"), Some("online"), 25), 10);
+    engine.run_ticks(2).unwrap();
+
+    assert_eq!(
+        engine.store.count_events_in_range(run_id, 5, 7, "complaint_sla_policy_rejected").unwrap(),
+        4
+    );
+    assert_eq!(
+        engine.store.sla_policies(run_id).unwrap(),
+        vec![(10, rule(Some("low"), Some("basic_checking"), Some("online"), 25))]
+    );
+}
@@ -0,0 +1,133 @@
+//! Contact preference and fatigue tests — Phase 4.32.
+//!
+//! Tests cover: do-not-contact honored by default, overriding it drawing
+//! complaints, the frequency cap, fatigue lowering response rates and an
+//! offer launch reaching only responders, and outreach staying off unless
+//! enabled.
+
+use fincrime_core::{
+    command::PlayerCommand,
+    config::SimConfig,
+    engine::SimEngine,
+    outreach::response_probability,
+};
+use std::collections::{BTreeMap, BTreeSet};
+
+fn build_with_fatigue(run_id: &str, seed: u64) -> SimEngine {
+    SimEngine::build_test_with_contact_fatigue(run_id.to_string(), seed)
+        .expect("build test engine with contact fatigue")
+}
+
+fn set_policy(engine: &mut SimEngine, honor_do_not_contact: bool, max_contacts_per_window: Option<u32>) {
+    engine
+        .submit_command(PlayerCommand::SetContactPolicy { honor_do_not_contact, max_contacts_per_window })
+        .unwrap();
+}
+
+/// Under the default policy no campaign reaches a do-not-contact customer.
+#[test]
+fn do_not_contact_honored_by_default() {
+    let run_id = "contact-dnc-test";
+    let mut engine = build_with_fatigue(run_id, 42);
+    engine.run_ticks(31).unwrap();
+
+    let do_not_contact: BTreeSet<String> =
+        engine.store.do_not_contact_customers(run_id).unwrap().into_iter().collect();
+    let contacts = engine.store.customer_contacts(run_id).unwrap();
+    assert!(!do_not_contact.is_empty());
+    assert!(contacts.iter().any(|c| c.campaign_type == "collections"));
+    assert!(contacts.iter().any(|c| c.campaign_type == "survey"));
+    for contact in &contacts {
+        assert!(!do_not_contact.contains(&contact.customer_id), "{contact:?}");
+        assert!(!contact.do_not_contact_violated);
+    }
+    assert_eq!(engine.store.count_events_in_range(run_id, 0, 31, "contact_preference_violated").unwrap(), 0);
+}
+
+/// Overriding do-not-contact reaches those customers, and each such
+/// contact draws an "unwanted contact" complaint unless the customer has
+/// left in the meantime.
+#[test]
+fn overriding_do_not_contact_draws_complaints() {
+    let run_id = "contact-override-test";
+    let mut engine = build_with_fatigue(run_id, 42);
+    set_policy(&mut engine, false, None);
+    engine.run_ticks(32).unwrap();
+
+    let violations = engine.store.count_events_in_range(run_id, 0, 31, "contact_preference_violated").unwrap();
+    let contacts = engine.store.customer_contacts(run_id).unwrap();
+    assert!(violations > 0);
+    assert_eq!(contacts.iter().filter(|c| c.do_not_contact_violated).count() as i64, violations);
+    assert!(contacts.iter().filter(|c| c.do_not_contact_violated).all(|c| !c.responded));
+    let complaints = engine.store.complaint_count_by_category(run_id, "unwanted_contact", 0, 31).unwrap();
+    assert!(complaints > 0 && complaints <= violations, "{complaints} of {violations}");
+}
+
+/// A cap of one contact per window holds back every repeat contact, and
+/// a cap of zero is rejected.
+#[test]
+fn frequency_cap_throttles_repeat_contacts() {
+    let run_id = "contact-cap-test";
+    let mut engine = build_with_fatigue(run_id, 42);
+    set_policy(&mut engine, true, Some(0));
+    set_policy(&mut engine, true, Some(1));
+    engine.run_ticks(31).unwrap();
+
+    assert_eq!(engine.store.count_events_in_range(run_id, 0, 31, "contact_policy_rejected").unwrap(), 1);
+    assert_eq!(engine.store.count_events_in_range(run_id, 0, 31, "contact_policy_set").unwrap(), 1);
+
+    // Every campaign falls inside one 30-tick window
+    let mut per_customer: BTreeMap<String, usize> = BTreeMap::new();
+    for contact in engine.store.customer_contacts(run_id).unwrap() {
+        assert!(contact.tick <= 30);
+        *per_customer.entry(contact.customer_id).or_default() += 1;
+    }
+    assert!(!per_customer.is_empty());
+    assert!(per_customer.values().all(|&n| n == 1), "{per_customer:?}");
+}
+
+/// Each earlier contact and an off-channel campaign lower the response
+/// rate, and an offer launch only goes to the customers who respond.
+#[test]
+fn fatigue_lowers_response_and_offers_reach_responders() {
+    let config = SimConfig::default_test().contact_fatigue;
+    let base = config.offer_response_rate;
+    assert_eq!(response_probability(&config, base, 0, true), base);
+    assert!((response_probability(&config, base, 3, true) - base * 0.75f64.powi(3)).abs() < 1e-12);
+    assert!((response_probability(&config, base, 0, false) - base * 0.5).abs() < 1e-12);
+
+    let run_id = "contact-offer-test";
+    let mut engine = build_with_fatigue(run_id, 42);
+    engine.run_ticks(2).unwrap();
+    engine
+        .submit_command(PlayerCommand::LaunchOffer {
+            offer_id: "campaign_cash_50".into(),
+            exclude_zip_prefixes: vec![],
+        })
+        .unwrap();
+    engine.run_ticks(1).unwrap();
+
+    let offer_contacts: Vec<_> = engine
+        .store
+        .customer_contacts(run_id)
+        .unwrap()
+        .into_iter()
+        .filter(|c| c.campaign_type == "offer")
+        .collect();
+    let responders = offer_contacts.iter().filter(|c| c.responded).count() as i64;
+    assert!(responders > 0);
+    assert!(responders < offer_contacts.len() as i64);
+    assert_eq!(engine.store_offer_match_count(run_id, "campaign_cash_50").unwrap(), responders);
+}
+
+/// The test default records no preferences or contacts.
+#[test]
+fn contact_fatigue_is_opt_in() {
+    let run_id = "contact-off-test";
+    let mut engine = SimEngine::build_test(run_id.into(), 42).unwrap();
+    engine.run_ticks(31).unwrap();
+
+    assert!(engine.store.customer_contacts(run_id).unwrap().is_empty());
+    assert!(engine.store.do_not_contact_customers(run_id).unwrap().is_empty());
+    assert_eq!(engine.store.count_events_in_range(run_id, 0, 31, "outreach_campaign_run").unwrap(), 0);
+}
@@ -0,0 +1,128 @@
+//! Correspondent banking tests — Phase 4.39.
+//!
+//! Tests cover: respondents clearing their own and nested banks' wires
+//! with most nested wires stripped of the ordering institution, CORR_NESTED
+//! alerting the nested respondents but not the clean one, due diligence
+//! refreshing on cadence with overdue refreshes suspending the relationship
+//! and rejecting its wires, and correspondent banking staying off unless
+//! enabled.
+
+use fincrime_core::{engine::SimEngine, event::SimEvent};
+
+fn build_with_correspondent_banking(run_id: &str, seed: u64) -> SimEngine {
+    SimEngine::build_test_with_correspondent_banking(run_id.to_string(), seed)
+        .expect("build test engine with correspondent banking")
+}
+
+/// Every respondent clears wires. Only respondents with nested banks send
+/// nested wires, and most of those arrive stripped: the payment message
+/// names the respondent, not the nested bank.
+#[test]
+fn nested_wires_mostly_stripped() {
+    let run_id = "corr-wires-test";
+    let mut engine = build_with_correspondent_banking(run_id, 42);
+    engine.run_ticks(30).unwrap();
+
+    let wires = engine.store.correspondent_wires(run_id).unwrap();
+    for bank in ["RESP-GB-01", "RESP-MX-01", "RESP-AE-01", "RESP-PA-01"] {
+        assert!(wires.iter().any(|w| w.bank_id == bank), "no wires from {bank}");
+    }
+    assert!(wires.iter().filter(|w| w.bank_id == "RESP-GB-01").all(|w| w.nested_bank.is_none()));
+
+    let nested: Vec<_> = wires.iter().filter(|w| w.nested_bank.is_some()).collect();
+    assert!(!nested.is_empty());
+    let stripped = nested.iter().filter(|w| w.ordering_institution == w.bank_id).count();
+    assert!(stripped * 2 > nested.len(), "{stripped} of {} stripped", nested.len());
+    for wire in &nested {
+        let nested_bank = wire.nested_bank.as_deref().unwrap();
+        assert!(nested_bank.starts_with(&wire.bank_id));
+        assert!(wire.ordering_institution == wire.bank_id || wire.ordering_institution == nested_bank);
+    }
+}
+
+/// CORR_NESTED alerts respondents with nested banks behind them and
+/// scores the high-risk respondent above the medium-risk ones. The clean
+/// respondent never alerts.
+#[test]
+fn nested_respondents_alert() {
+    let run_id = "corr-alert-test";
+    let mut engine = build_with_correspondent_banking(run_id, 42);
+    engine.run_ticks(60).unwrap();
+
+    let alerts: Vec<_> = (1..=60)
+        .flat_map(|tick| engine.store_events_for_tick(run_id, tick).unwrap())
+        .filter_map(|e| serde_json::from_str::<SimEvent>(&e.payload).ok())
+        .filter_map(|e| match e {
+            SimEvent::TransactionMonitoringAlert { alert_type, customer_id, alert_score, .. }
+                if alert_type == "correspondent_nested" =>
+            {
+                Some((customer_id, alert_score))
+            }
+            _ => None,
+        })
+        .collect();
+    assert!(!alerts.is_empty());
+    assert!(alerts.iter().all(|(bank, _)| bank != "RESP-GB-01"));
+    assert!(alerts.iter().any(|(bank, _)| bank == "RESP-PA-01"));
+    let max_score = |bank: &str| {
+        alerts.iter().filter(|(b, _)| b == bank).map(|(_, s)| *s).fold(0.0, f64::max)
+    };
+    assert!(max_score("RESP-PA-01") > max_score("RESP-MX-01"));
+    assert!(max_score("RESP-PA-01") > max_score("RESP-AE-01"));
+}
+
+/// Due diligence is requested on cadence. Answered refreshes complete at
+/// their risk rating's cost; unanswered ones go overdue, suspend the
+/// relationship and its wires are rejected with no fee until a refresh
+/// comes back.
+#[test]
+fn overdue_due_diligence_suspends_relationship() {
+    let run_id = "corr-dd-test";
+    let mut engine = build_with_correspondent_banking(run_id, 42);
+    engine.run_ticks(90).unwrap();
+
+    let reviews = engine.store.correspondent_dd_reviews(run_id).unwrap();
+    assert!(reviews.iter().any(|r| r.outcome.as_deref() == Some("overdue")));
+    assert!(reviews.iter().any(|r| matches!(r.outcome.as_deref(), Some("satisfactory" | "nested_disclosed"))));
+    for review in &reviews {
+        match review.outcome.as_deref() {
+            Some("overdue") => {
+                assert_eq!(review.cost, 0.0);
+                assert_eq!(review.completed_tick, Some(review.requested_tick + 10));
+            }
+            Some(_) => {
+                assert!(review.cost > 0.0);
+                assert_eq!(review.completed_tick, review.responds_tick);
+            }
+            None => assert!(review.completed_tick.is_none()),
+        }
+    }
+
+    let wires = engine.store.correspondent_wires(run_id).unwrap();
+    let rejected: Vec<_> = wires.iter().filter(|w| w.status == "rejected").collect();
+    assert!(!rejected.is_empty());
+    assert!(rejected.iter().all(|w| w.fee == 0.0));
+    // A wire is only rejected after one of its respondent's refreshes lapsed
+    for wire in &rejected {
+        assert!(
+            reviews
+                .iter()
+                .any(|r| r.bank_id == wire.bank_id
+                    && r.outcome.as_deref() == Some("overdue")
+                    && r.completed_tick.is_some_and(|t| t <= wire.tick)),
+            "{wire:?}"
+        );
+    }
+}
+
+/// Correspondent banking is opt-in: the default test build opens no
+/// relationships and clears no wires.
+#[test]
+fn correspondent_banking_opt_in() {
+    let run_id = "corr-off-test";
+    let mut engine = SimEngine::build_test(run_id.to_string(), 42).unwrap();
+    engine.run_ticks(30).unwrap();
+    assert!(engine.store.correspondent_relationships(run_id).unwrap().is_empty());
+    assert!(engine.store.correspondent_wires(run_id).unwrap().is_empty());
+    assert!(engine.store.correspondent_dd_reviews(run_id).unwrap().is_empty());
+}
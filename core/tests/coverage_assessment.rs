@@ -0,0 +1,147 @@
+//! Scenario coverage assessment tests — Phase 4.18.
+//!
+//! Tests cover: the monthly assessment covering every typology against
+//! the default rule inventory, a suppressed rule leaving its typology
+//! uncovered, planted structuring showing up as ground truth, and the
+//! player-visible event carrying the rule inventory but not ground truth.
+
+use fincrime_core::command::PlayerCommand;
+use fincrime_core::engine::SimEngine;
+use fincrime_core::event::SimEvent;
+use fincrime_core::store::coverage_assessment::CoverageAssessmentRow;
+
+fn build_with_coverage(run_id: &str, seed: u64) -> SimEngine {
+    SimEngine::build_test_with_coverage_assessment(run_id.to_string(), seed)
+        .expect("build test engine with coverage assessment")
+}
+
+fn suppress(engine: &mut SimEngine, rule_id: &str) {
+    engine
+        .submit_command(PlayerCommand::TuneMonitoringRule {
+            rule_id: rule_id.into(),
+            change: "suppress".into(),
+            threshold_multiplier: 1.0,
+            customer_id: None,
+            review_id: None,
+        })
+        .unwrap();
+}
+
+fn assessment_at<'a>(rows: &'a [CoverageAssessmentRow], tick: u64, typology: &str) -> &'a CoverageAssessmentRow {
+    rows.iter()
+        .find(|r| r.tick == tick && r.typology == typology)
+        .unwrap_or_else(|| panic!("no {typology} assessment at tick {tick}"))
+}
+
+fn coverage_event(engine: &SimEngine, run_id: &str, tick: u64) -> (String, SimEvent) {
+    let entry = engine
+        .store_events_for_tick(run_id, tick)
+        .unwrap()
+        .into_iter()
+        .find(|e| e.event_type == "coverage_assessed")
+        .expect("coverage_assessed event");
+    let event = serde_json::from_str(&entry.payload).unwrap();
+    (entry.payload, event)
+}
+
+/// Each month every typology is assessed; the default inventory covers
+/// structuring, funnels and mules, while nothing addresses TBML or ATO.
+#[test]
+fn monthly_assessment_covers_default_inventory() {
+    let run_id = "coverage-default-test";
+    let mut engine = build_with_coverage(run_id, 0xC0DE_0001);
+    engine.run_ticks(61).unwrap();
+
+    let rows = engine.store_coverage_assessments(run_id).unwrap();
+    assert_eq!(rows.len(), 10, "Five typologies at ticks 30 and 60");
+    assert!(rows.iter().all(|r| r.tick == 30 || r.tick == 60));
+    for typology in ["structuring", "funnel", "mule"] {
+        assert!(assessment_at(&rows, 30, typology).covered, "{typology} covered");
+    }
+    for typology in ["tbml", "ato"] {
+        let row = assessment_at(&rows, 30, typology);
+        assert!(!row.covered, "{typology} uncovered");
+        assert_eq!(row.rules, "");
+    }
+    assert_eq!(assessment_at(&rows, 30, "structuring").rules, "STRUCT_9K");
+    assert_eq!(assessment_at(&rows, 30, "funnel").rules, "VEL_50K_7D,VEL_100K_30D");
+    assert_eq!(assessment_at(&rows, 30, "tbml").occurrences, 0, "The sim runs no TBML");
+}
+
+/// Suppressing the only structuring rule leaves structuring uncovered,
+/// and the player sees the rule listed as suppressed.
+#[test]
+fn suppressed_rule_leaves_typology_uncovered() {
+    let run_id = "coverage-suppressed-test";
+    let mut engine = build_with_coverage(run_id, 0xC0DE_0002);
+    engine.run_ticks(1).unwrap();
+    suppress(&mut engine, "STRUCT_9K");
+    engine.run_ticks(30).unwrap();
+
+    let rows = engine.store_coverage_assessments(run_id).unwrap();
+    let structuring = assessment_at(&rows, 30, "structuring");
+    assert!(!structuring.covered);
+    assert_eq!(structuring.rules, "");
+
+    let (_, event) = coverage_event(&engine, run_id, 30);
+    let SimEvent::CoverageAssessed { active_rules, suppressed_rules, typologies_addressed, .. } = event else {
+        panic!("unexpected event {event:?}");
+    };
+    assert_eq!(suppressed_rules, vec!["STRUCT_9K".to_string()]);
+    assert!(!active_rules.contains(&"STRUCT_9K".to_string()));
+    assert_eq!(typologies_addressed, vec!["funnel".to_string(), "mule".to_string()]);
+}
+
+/// Cash deposits just under the CTR threshold are counted as structuring
+/// running in the window, whether or not a rule is there to catch it.
+#[test]
+fn planted_structuring_is_present_in_ground_truth() {
+    let run_id = "coverage-structuring-test";
+    let mut engine = build_with_coverage(run_id, 0xC0DE_0003);
+    engine.run_ticks(1).unwrap();
+    suppress(&mut engine, "STRUCT_9K");
+    for (account_id, tick) in [("a-000001", 5), ("a-000002", 12)] {
+        for n in 0..3 {
+            let txn_id = format!("struct-{account_id}-{n}");
+            engine
+                .store
+                .insert_transaction(run_id, &txn_id, account_id, tick, 9_500.0, "credit", "cash", None)
+                .unwrap();
+        }
+    }
+    engine.run_ticks(60).unwrap();
+
+    let rows = engine.store_coverage_assessments(run_id).unwrap();
+    let month = assessment_at(&rows, 30, "structuring");
+    assert!(month.occurrences >= 2);
+    assert!(month.present() && !month.covered, "Present and uncovered");
+    assert_eq!(
+        assessment_at(&rows, 60, "structuring").occurrences,
+        month.occurrences - 2,
+        "Planted deposits fall out of the next window",
+    );
+}
+
+/// The player-visible event lists the rule inventory and nothing about
+/// which typologies are present; the assessment is off unless enabled.
+#[test]
+fn event_carries_inventory_not_ground_truth() {
+    let run_id = "coverage-event-test";
+    let mut engine = build_with_coverage(run_id, 0xC0DE_0004);
+    engine.run_ticks(31).unwrap();
+
+    let (payload, event) = coverage_event(&engine, run_id, 30);
+    assert!(!payload.contains("occurrences"));
+    assert!(!payload.contains("tbml"));
+    let SimEvent::CoverageAssessed { active_rules, suppressed_rules, .. } = event else {
+        panic!("unexpected event {event:?}");
+    };
+    assert!(suppressed_rules.is_empty());
+    assert!(active_rules.contains(&"STRUCT_9K".to_string()));
+    assert!(active_rules.contains(&"CTR_10K".to_string()));
+
+    let run_id = "coverage-disabled-test";
+    let mut engine = SimEngine::build_test(run_id.to_string(), 0xC0DE_0004).unwrap();
+    engine.run_ticks(31).unwrap();
+    assert!(engine.store_coverage_assessments(run_id).unwrap().is_empty());
+}
@@ -0,0 +1,168 @@
+//! Credit bureau reporting subsystem tests — Phase 4.1.
+//!
+//! Tests cover: monthly furnishing, dispute → complaint linkage,
+//! late-correction penalties, player resolutions, loan tradelines in the
+//! bureau file, opt-in behaviour, determinism.
+
+use fincrime_core::{engine::SimEngine, store::lending::LoanRow};
+
+fn build(run_id: &str, seed: u64) -> SimEngine {
+    SimEngine::build_test(run_id.to_string(), seed).expect("build test engine")
+}
+
+fn build_with_bureau(run_id: &str, seed: u64) -> SimEngine {
+    SimEngine::build_test_with_credit_bureau(run_id.to_string(), seed)
+        .expect("build test engine with credit bureau")
+}
+
+/// Every active account is furnished once per cycle, and the noisy test
+/// furnisher produces inaccurate records that turn into disputes.
+#[test]
+fn bureau_furnishes_and_disputes_open_complaints() {
+    let run_id = "bureau-furnish-test";
+    let mut engine = build_with_bureau(run_id, 0x00C0_FFEE);
+
+    engine.run_ticks(20).unwrap();
+
+    let furnished = engine.store_bureau_furnishing_count(run_id, false).unwrap();
+    assert!(furnished > 0, "Expected furnishing rows after two cycles, got 0");
+
+    let errors = engine.store_bureau_furnishing_count(run_id, true).unwrap();
+    assert!(errors > 0, "Expected some inaccurate furnishings at 30% error rate");
+
+    let disputes = engine.store_bureau_dispute_count(run_id).unwrap();
+    assert!(disputes > 0, "Expected disputes from {errors} inaccurate records");
+    assert!(disputes <= errors, "More disputes ({disputes}) than errors ({errors})");
+
+    // Each dispute is also a complaint in the complaint queue.
+    let complaints = engine.store_complaint_count(run_id).unwrap();
+    assert!(
+        complaints >= disputes,
+        "Expected at least {disputes} complaints (one per dispute), got {complaints}"
+    );
+}
+
+/// Disputes left uncorrected past the investigation window book penalties.
+#[test]
+fn bureau_late_corrections_are_penalised() {
+    let run_id = "bureau-penalty-test";
+    let mut engine = build_with_bureau(run_id, 0x0BAD_F00D);
+
+    engine.run_ticks(40).unwrap();
+
+    let penalty = engine.store_bureau_penalty_total(run_id).unwrap();
+    assert!(
+        penalty > 0.0,
+        "Expected late-correction penalties after 40 ticks with a 10-tick window"
+    );
+    assert_eq!(
+        penalty % 2_500.0,
+        0.0,
+        "Penalties should be whole multiples of the configured amount: {penalty}"
+    );
+}
+
+/// A player who resolves the linked complaint closes out the dispute, and
+/// the bureau never overwrites the player's resolution.
+#[test]
+fn player_resolution_closes_dispute() {
+    let run_id = "bureau-player-test";
+    let mut engine = build_with_bureau(run_id, 0x00C0_FFEE);
+
+    engine.run_ticks(11).unwrap();
+
+    let dispute = engine
+        .store
+        .open_bureau_disputes(run_id)
+        .unwrap()
+        .into_iter()
+        .next()
+        .expect("Expected an open dispute after the first furnishing cycle");
+    engine
+        .store_close_complaint_direct(run_id, &dispute.complaint_id, 11, "monetary_relief", 25.0)
+        .unwrap();
+
+    engine.run_ticks(1).unwrap();
+
+    let open = engine.store.open_bureau_disputes(run_id).unwrap();
+    assert!(open.iter().all(|d| d.dispute_id != dispute.dispute_id));
+    let complaint = engine.store.get_complaint(run_id, &dispute.complaint_id).unwrap();
+    assert_eq!(complaint.resolution_code.as_deref(), Some("monetary_relief"));
+}
+
+/// A booked loan is furnished as a loan tradeline in its borrower's
+/// bureau file, by its bucket and balance.
+#[test]
+fn booked_loan_appears_in_bureau_file() {
+    let run_id = "bureau-loan-test";
+    let mut engine = build_with_bureau(run_id, 0x00C0_FFEE);
+    engine.run_ticks(5).unwrap();
+
+    let account = engine.store.active_accounts(run_id).unwrap().remove(0);
+    let loan = LoanRow {
+        loan_id:         "loan-bureau-1".into(),
+        customer_id:     account.customer_id.clone(),
+        account_id:      account.account_id,
+        product_id:      "personal_loan".into(),
+        kind:            "installment".into(),
+        tick_originated: 5,
+        amount:          5_000.0,
+        annual_rate:     0.12,
+        credit_score:    700,
+        dti:             0.1,
+        balance:         4_200.0,
+        interest_paid:   0.0,
+        status:          "open".into(),
+        bucket:          "30_59".into(),
+        tick_closed:     None,
+        charge_off_loss: 0.0,
+    };
+    engine.store.insert_loan(run_id, &loan).unwrap();
+    engine.run_ticks(5).unwrap();
+
+    let file = engine.store.bureau_file(run_id, &account.customer_id).unwrap();
+    let loans: Vec<_> = file.iter().filter(|r| r.tradeline == "loan").collect();
+    assert_eq!(loans.len(), 1, "{file:?}");
+    assert_eq!(loans[0].account_id, "loan-bureau-1");
+    assert_eq!(loans[0].tick, 10);
+    assert_eq!(loans[0].actual_status, "30_59");
+    assert_eq!(loans[0].balance, 4_200.0);
+    assert!(file.iter().any(|r| r.tradeline == "deposit"));
+}
+
+/// The subsystem is opt-in for tests: the default test build furnishes nothing.
+#[test]
+fn bureau_disabled_by_default_in_tests() {
+    let run_id = "bureau-disabled-test";
+    let mut engine = build(run_id, 0x1234_5678);
+
+    engine.run_ticks(20).unwrap();
+
+    assert_eq!(engine.store_bureau_furnishing_count(run_id, false).unwrap(), 0);
+    assert_eq!(engine.store_bureau_dispute_count(run_id).unwrap(), 0);
+}
+
+/// Determinism: two engines with the same seed furnish and dispute identically.
+#[test]
+fn bureau_determinism() {
+    const SEED: u64 = 0xFC5A_0611;
+    let run_id = format!("bureau-det-{SEED}");
+
+    let mut engine_a = build_with_bureau(&run_id, SEED);
+    let mut engine_b = build_with_bureau(&run_id, SEED);
+
+    engine_a.run_ticks(30).unwrap();
+    engine_b.run_ticks(30).unwrap();
+
+    let errors_a = engine_a.store_bureau_furnishing_count(&run_id, true).unwrap();
+    let errors_b = engine_b.store_bureau_furnishing_count(&run_id, true).unwrap();
+    assert_eq!(errors_a, errors_b, "Inaccurate furnishing count diverged");
+
+    let disputes_a = engine_a.store_bureau_dispute_count(&run_id).unwrap();
+    let disputes_b = engine_b.store_bureau_dispute_count(&run_id).unwrap();
+    assert_eq!(disputes_a, disputes_b, "Dispute count diverged");
+
+    let penalty_a = engine_a.store_bureau_penalty_total(&run_id).unwrap();
+    let penalty_b = engine_b.store_bureau_penalty_total(&run_id).unwrap();
+    assert_eq!(penalty_a.to_bits(), penalty_b.to_bits(), "Penalty total diverged");
+}
@@ -0,0 +1,168 @@
+//! Cross-border fan-in/fan-out tests — Phase 4.40.
+//!
+//! Tests cover: funnels collecting from many senders abroad and dispersing
+//! to domestic payees while support customers hear from a relative or two
+//! at home, XB_FUNNEL alerting funnels but not support customers, each
+//! finding keeping the evidence its totals are built from, SAR narratives
+//! quoting the finding, and the typology staying off unless enabled.
+
+use fincrime_core::{command::PlayerCommand, engine::SimEngine, event::SimEvent};
+use std::collections::{HashMap, HashSet};
+
+fn build_with_cross_border(run_id: &str, seed: u64) -> SimEngine {
+    SimEngine::build_test_with_cross_border(run_id.to_string(), seed)
+        .expect("build test engine with cross-border funnels")
+}
+
+/// Funnels take wires from many senders across several countries and send
+/// them on domestically a couple of days later. Support customers only
+/// ever hear from home, from at most two relatives.
+#[test]
+fn funnels_fan_in_and_out() {
+    let run_id = "xb-funnel-test";
+    let mut engine = build_with_cross_border(run_id, 42);
+    engine.run_ticks(40).unwrap();
+
+    let patterns: HashMap<_, _> = engine
+        .store
+        .cross_border_profiles(run_id)
+        .unwrap()
+        .into_iter()
+        .map(|p| (p.customer_id.clone(), p))
+        .collect();
+    let transfers = engine.store.cross_border_transfers(run_id).unwrap();
+    assert!(!transfers.is_empty());
+
+    let mut funnels = 0;
+    for (customer_id, profile) in &patterns {
+        let mine: Vec<_> = transfers.iter().filter(|t| &t.customer_id == customer_id).collect();
+        let senders: HashSet<_> =
+            mine.iter().filter(|t| t.direction == "inbound").map(|t| &t.counterparty).collect();
+        let countries: HashSet<_> =
+            mine.iter().filter(|t| t.direction == "inbound").map(|t| &t.country).collect();
+        match profile.pattern.as_str() {
+            "funnel" if !senders.is_empty() => {
+                funnels += 1;
+                assert!(senders.len() >= 6, "{customer_id}: {} senders", senders.len());
+                assert!(countries.len() > 1);
+                let first_in = mine.iter().find(|t| t.direction == "inbound").unwrap().tick;
+                for out in mine.iter().filter(|t| t.direction == "domestic") {
+                    assert_eq!(out.country, "US");
+                    assert!(out.tick >= first_in + 2);
+                }
+            }
+            "inbound" => {
+                assert!(senders.len() <= 2);
+                assert!(countries.iter().all(|c| **c == profile.home_country));
+            }
+            "none" => assert!(mine.is_empty()),
+            _ => {}
+        }
+    }
+    assert!(funnels > 0);
+}
+
+/// XB_FUNNEL raises alerts, each with a finding, on funnels. Support
+/// customers never alert.
+#[test]
+fn funnels_alert_support_does_not() {
+    let run_id = "xb-alert-test";
+    let mut engine = build_with_cross_border(run_id, 42);
+    engine.run_ticks(60).unwrap();
+
+    let patterns: HashMap<_, _> = engine
+        .store
+        .cross_border_profiles(run_id)
+        .unwrap()
+        .into_iter()
+        .map(|p| (p.customer_id, p.pattern))
+        .collect();
+    let alerted: Vec<_> = (1..=60)
+        .flat_map(|tick| engine.store_events_for_tick(run_id, tick).unwrap())
+        .filter_map(|e| serde_json::from_str::<SimEvent>(&e.payload).ok())
+        .filter_map(|e| match e {
+            SimEvent::TransactionMonitoringAlert { alert_type, customer_id, .. }
+                if alert_type == "cross_border_funnel" =>
+            {
+                Some(customer_id)
+            }
+            _ => None,
+        })
+        .collect();
+    assert!(!alerted.is_empty());
+    for customer_id in &alerted {
+        assert_eq!(patterns[customer_id], "funnel", "{customer_id}");
+    }
+    assert!(patterns.values().any(|p| p == "inbound"));
+
+    let findings = engine.store.cross_border_findings(run_id).unwrap();
+    assert_eq!(findings.len(), alerted.len());
+}
+
+/// A finding's totals are built from the evidence kept with it, and its
+/// high-risk countries are source countries on the jurisdiction list.
+#[test]
+fn findings_keep_their_evidence() {
+    let run_id = "xb-evidence-test";
+    let mut engine = build_with_cross_border(run_id, 42);
+    engine.run_ticks(60).unwrap();
+
+    let listed: HashSet<_> = engine
+        .store
+        .get_high_risk_jurisdictions()
+        .unwrap()
+        .into_iter()
+        .map(|j| j.country_code)
+        .collect();
+    let findings = engine.store.cross_border_findings(run_id).unwrap();
+    assert!(!findings.is_empty());
+    for finding in &findings {
+        let evidence = engine.store.cross_border_evidence(&finding.finding_id).unwrap();
+        let sum = |direction: &str| {
+            evidence.iter().filter(|e| e.direction == direction).map(|e| e.amount).sum::<f64>()
+        };
+        assert!((sum("inbound") - finding.inflow).abs() < 0.01, "{finding:?}");
+        assert!((sum("domestic") - finding.outflow).abs() < 0.01, "{finding:?}");
+        assert!(evidence.iter().all(|e| e.tick >= finding.window_start && e.tick <= finding.tick));
+        assert!(finding.senders >= 5 && finding.payees >= 4);
+        assert!(finding.outflow >= finding.inflow * 0.70);
+        for country in &finding.high_risk_countries {
+            assert!(finding.countries.contains(country) && listed.contains(country));
+        }
+        let found = engine.store.cross_border_finding_for_alert(run_id, &finding.alert_id).unwrap();
+        assert_eq!(found.unwrap().finding_id, finding.finding_id);
+    }
+    assert!(findings.iter().any(|f| !f.high_risk_countries.is_empty()));
+}
+
+/// With the SAR threshold low enough to file on XB_FUNNEL alerts, the
+/// narrative quotes the finding's totals.
+#[test]
+fn sar_narrative_quotes_finding() {
+    let run_id = "xb-sar-test";
+    let mut engine = build_with_cross_border(run_id, 42);
+    engine
+        .submit_command(PlayerCommand::SetSarThreshold { threshold: 50.0 })
+        .unwrap();
+    engine.run_ticks(60).unwrap();
+
+    let narratives = engine.store.cross_border_sar_narratives(run_id).unwrap();
+    assert!(!narratives.is_empty());
+    for (customer_id, narrative) in &narratives {
+        assert!(narrative.starts_with(&format!("Customer {customer_id} received $")), "{narrative}");
+        assert!(narrative.contains("domestic payees"), "{narrative}");
+        assert!(narrative.contains("Largest wire in"), "{narrative}");
+    }
+}
+
+/// Cross-border funnels are opt-in: the default test build profiles no
+/// one and books nothing.
+#[test]
+fn cross_border_opt_in() {
+    let run_id = "xb-off-test";
+    let mut engine = SimEngine::build_test(run_id.to_string(), 42).unwrap();
+    engine.run_ticks(30).unwrap();
+    assert!(engine.store.cross_border_profiles(run_id).unwrap().is_empty());
+    assert!(engine.store.cross_border_transfers(run_id).unwrap().is_empty());
+    assert!(engine.store.cross_border_findings(run_id).unwrap().is_empty());
+}
@@ -0,0 +1,123 @@
+//! Customer limit tests — Phase 4.12.
+//!
+//! Tests cover: cash, wire and P2P attempts declined over their limits,
+//! tighter limits for high-risk tiers, limits set by player command
+//! globally or per tier, and rejection of malformed limit commands.
+
+use fincrime_core::command::PlayerCommand;
+use fincrime_core::engine::SimEngine;
+
+fn build_with_limits(run_id: &str, seed: u64) -> SimEngine {
+    SimEngine::build_test_with_customer_limits(run_id.to_string(), seed)
+        .expect("build test engine with customer limits")
+}
+
+fn set_limit(engine: &mut SimEngine, limit_type: &str, scope: &str, amount: f64) {
+    engine
+        .submit_command(PlayerCommand::SetCustomerLimit {
+            limit_type: limit_type.into(),
+            scope: scope.into(),
+            amount,
+        })
+        .unwrap();
+}
+
+/// Every decline carries the tightest limit for the customer's tier,
+/// including a tier limit set by the player.
+#[test]
+fn declines_use_the_tightest_tier_limit() {
+    let run_id = "customer-limits-tier-test";
+    let mut engine = build_with_limits(run_id, 0x4C1A_0001);
+
+    set_limit(&mut engine, "cash_withdrawal", "tier:low", 40.0);
+    engine.run_ticks(30).unwrap();
+
+    let declines = engine.store_limit_declines(run_id).unwrap();
+    let tiers = engine.store.customer_risk_tiers(run_id).unwrap();
+    assert!(declines
+        .iter()
+        .any(|d| d.limit_type == "cash_withdrawal" && tiers[&d.customer_id] == "low"));
+    for decline in declines.iter().filter(|d| d.tick > 1) {
+        let tier = tiers.get(&decline.customer_id).map(String::as_str);
+        let expected = match (decline.limit_type.as_str(), tier) {
+            ("cash_withdrawal", Some("critical")) => 300.0,
+            ("cash_withdrawal", Some("high")) => 500.0,
+            ("cash_withdrawal", Some("low")) => 40.0,
+            ("cash_withdrawal", _) => 1_000.0,
+            ("p2p_send", Some("critical")) => 500.0,
+            ("p2p_send", Some("high")) => 1_000.0,
+            ("p2p_send", _) => 2_500.0,
+            ("wire", Some("critical")) => 5_000.0,
+            ("wire", Some("high")) => 10_000.0,
+            ("wire", _) => 25_000.0,
+            (other, _) => panic!("Unexpected limit type {other}"),
+        };
+        assert_eq!(decline.limit_amount, expected);
+    }
+}
+
+/// A global limit set by the player declines anything that would take
+/// the customer's daily total over it.
+#[test]
+fn player_global_limit_declines_over_limit_attempts() {
+    let run_id = "customer-limits-global-test";
+    let mut engine = build_with_limits(run_id, 0x4C1A_0002);
+
+    set_limit(&mut engine, "cash_withdrawal", "global", 100.0);
+    engine.run_ticks(20).unwrap();
+
+    let limits = engine.store.customer_limits(run_id).unwrap();
+    assert_eq!(limits.len(), 1);
+    assert_eq!(limits[0].amount, 100.0);
+
+    let cash: Vec<_> = engine
+        .store_limit_declines(run_id)
+        .unwrap()
+        .into_iter()
+        .filter(|d| d.limit_type == "cash_withdrawal" && d.tick > 1)
+        .collect();
+    assert!(cash.len() > 10, "Expected many cash declines, got {}", cash.len());
+    for decline in &cash {
+        assert!(decline.limit_amount <= 100.0);
+        assert!(decline.amount > 0.0);
+    }
+}
+
+/// Wires are capped per wire: any single wire over the limit is declined.
+#[test]
+fn wire_limit_applies_per_wire() {
+    let run_id = "customer-limits-wire-test";
+    let mut engine = build_with_limits(run_id, 0x4C1A_0003);
+
+    set_limit(&mut engine, "wire", "global", 50.0);
+    engine.run_ticks(20).unwrap();
+
+    let wires: Vec<_> = engine
+        .store_limit_declines(run_id)
+        .unwrap()
+        .into_iter()
+        .filter(|d| d.limit_type == "wire" && d.tick > 1)
+        .collect();
+    assert!(!wires.is_empty());
+    for decline in &wires {
+        assert!(decline.amount > decline.limit_amount);
+    }
+}
+
+/// Malformed limit commands are rejected and leave the limits unchanged.
+#[test]
+fn malformed_limits_are_rejected() {
+    let run_id = "customer-limits-reject-test";
+    let mut engine = build_with_limits(run_id, 0x4C1A_0004);
+
+    set_limit(&mut engine, "crypto_purchase", "global", 100.0);
+    set_limit(&mut engine, "cash_withdrawal", "segment:premium", 100.0);
+    set_limit(&mut engine, "cash_withdrawal", "tier:high", -5.0);
+    set_limit(&mut engine, "p2p_send", "tier:high", 200.0);
+    engine.run_ticks(2).unwrap();
+
+    let limits = engine.store.customer_limits(run_id).unwrap();
+    assert_eq!(limits.len(), 1);
+    assert_eq!(limits[0].limit_type, "p2p_send");
+    assert_eq!(limits[0].scope, "tier:high");
+}
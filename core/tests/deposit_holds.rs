@@ -0,0 +1,201 @@
+//! Deposit hold tests — Phase 4.55.
+//!
+//! Tests cover: no holds unless enabled, and holds set by deposit channel
+//! and risk with the new-account and large-item exceptions; statutory
+//! limits capping the bank's policy and rejecting player changes past
+//! them; longer holds keeping fraud rings from cashing out counterfeits;
+//! and longer holds costing complaints and satisfaction, most among
+//! liquidity-sensitive segments.
+
+use fincrime_core::{
+    command::PlayerCommand,
+    config::{HoldRule, SimConfig},
+    deposit_holds::DepositHolds,
+    engine::SimEngine,
+    store::{check_fraud::CheckItemRow, deposit_holds::DepositHoldRow},
+};
+use serde_json::Value;
+
+const TICKS: u64 = 30;
+
+fn engine(run_id: &str, hold_ticks: u64) -> SimEngine {
+    SimEngine::build_test_with_deposit_holds(run_id.into(), 42, hold_ticks).unwrap()
+}
+
+/// Payloads of every `event_type` event so far, in order.
+fn events(engine: &SimEngine, event_type: &str) -> Vec<Value> {
+    (0..=engine.clock.current_tick)
+        .flat_map(|tick| engine.store.events_for_tick(&engine.run_id, tick).unwrap())
+        .filter(|e| e.event_type == event_type)
+        .map(|e| serde_json::from_str(&e.payload).unwrap())
+        .collect()
+}
+
+fn rule(class: Option<&str>, risk: Option<&str>, hold_ticks: u64) -> HoldRule {
+    HoldRule {
+        class: class.map(Into::into),
+        risk: risk.map(Into::into),
+        hold_ticks,
+    }
+}
+
+fn holds(engine: &SimEngine) -> Vec<DepositHoldRow> {
+    engine.store.deposit_holds(&engine.run_id).unwrap()
+}
+
+/// Off by default; on, every check deposit over the next-day amount is
+/// held for the longest of its classes' holds and released on time.
+#[test]
+fn holds_follow_class_and_risk() {
+    let mut plain = SimEngine::build_test_with_check_fraud("deposit-holds-off-test".into(), 42, 20).unwrap();
+    plain.run_ticks(10).unwrap();
+    assert!(holds(&plain).is_empty());
+
+    let defaults = DepositHolds::new("unit".into(), SimConfig::default_test().deposit_holds);
+    assert_eq!(defaults.hold_ticks("branch", "standard", &[]), 1);
+    assert_eq!(defaults.hold_ticks("atm", "standard", &[]), 2);
+    assert_eq!(defaults.hold_ticks("atm", "elevated", &[]), 5);
+    assert_eq!(defaults.hold_ticks("large_item", "standard", &[]), 5);
+    assert_eq!(defaults.hold_ticks("atm", "standard", &[(3, rule(Some("atm"), None, 0))]), 0);
+
+    let mut engine = engine("deposit-holds-class-test", 5);
+    engine.run_ticks(TICKS).unwrap();
+    let holds = holds(&engine);
+    let deposits: Vec<CheckItemRow> = engine
+        .store
+        .check_items(&engine.run_id)
+        .unwrap()
+        .into_iter()
+        .filter(|i| i.side == "deposited" && i.amount > 225.0)
+        .collect();
+    assert_eq!(holds.len(), deposits.len());
+    for class in ["atm", "branch", "new_account", "large_item"] {
+        assert!(holds.iter().any(|h| h.class == class), "no {class} holds");
+    }
+    for hold in &holds {
+        assert!((hold.held_amount - (hold.amount - 225.0)).abs() < 1e-9);
+        let expected = match (hold.class.as_str(), hold.risk.as_str()) {
+            ("atm" | "branch", "standard") => 2,
+            _ => 5,
+        };
+        assert_eq!(hold.hold_ticks, expected, "{hold:?}");
+        if hold.tick < 10 && hold.risk == "standard" {
+            assert_eq!(hold.class, "new_account");
+        }
+        if hold.class == "large_item" {
+            assert!(hold.amount > 2_000.0);
+        }
+        match hold.released_tick {
+            // Charged back early, or released on time
+            Some(tick) => assert!(tick <= hold.release_tick, "{hold:?}"),
+            None => assert!(hold.release_tick > engine.clock.current_tick, "{hold:?}"),
+        }
+    }
+    assert_eq!(events(&engine, "funds_held").len(), holds.len());
+}
+
+/// Statutory limits cap the bank's policy; the player can lengthen a hold
+/// up to a limit, but a change past one is rejected.
+#[test]
+fn statutory_limits_cap_policy() {
+    let mut engine = engine("deposit-holds-limit-test", 5);
+    for (class, risk, hold_ticks) in [
+        (Some("atm"), Some("standard"), 4),
+        (Some("new_account"), None, 9),
+        (Some("mail"), None, 1),
+        (Some("new_account"), None, 6),
+    ] {
+        engine
+            .submit_command(PlayerCommand::SetDepositHoldPolicy {
+                class: class.map(Into::into),
+                risk: risk.map(Into::into),
+                hold_ticks,
+            })
+            .unwrap();
+    }
+    engine.run_ticks(10).unwrap();
+
+    let rejected = events(&engine, "deposit_hold_policy_rejected");
+    let reasons: Vec<&str> = rejected.iter().map(|e| e["reason"].as_str().unwrap()).collect();
+    assert_eq!(
+        reasons,
+        ["regulation allows holds of at most 2 days", "regulation allows holds of at most 7 days", "unknown class"]
+    );
+    let set = events(&engine, "deposit_hold_policy_set");
+    assert_eq!(set.len(), 1);
+    assert_eq!((set[0]["class"].as_str(), set[0]["hold_ticks"].as_u64()), (Some("new_account"), Some(6)));
+    assert_eq!(engine.store.deposit_hold_policies(&engine.run_id).unwrap().len(), 1);
+
+    let new_account: Vec<DepositHoldRow> = holds(&engine).into_iter().filter(|h| h.class == "new_account").collect();
+    assert!(!new_account.is_empty());
+    assert!(new_account.iter().all(|h| h.hold_ticks == 6), "{new_account:?}");
+}
+
+/// Held past the paying bank's return, a counterfeit is charged back
+/// before the ring can withdraw it; released sooner, it is cashed out and
+/// the bank eats the shortfall.
+#[test]
+fn longer_holds_cut_check_fraud_losses() {
+    let loss = |hold_ticks| {
+        let mut engine = engine(&format!("deposit-holds-loss-{hold_ticks}-test"), hold_ticks);
+        engine.run_ticks(TICKS).unwrap();
+        let items = engine.store.check_items(&engine.run_id).unwrap();
+        for item in items.iter().filter(|i| i.typology == "counterfeit" && i.status == "cashed_out") {
+            let hold = engine.store.deposit_holds(&engine.run_id).unwrap().into_iter().find(|h| h.txn_id == item.txn_id);
+            assert!(hold.is_none_or(|h| h.released_tick.is_some()), "{item:?}");
+        }
+        items
+            .iter()
+            .filter(|i| i.side == "deposited" && i.loss_bearer.as_deref() == Some("bank"))
+            .map(|i| i.loss)
+            .sum::<f64>()
+    };
+    let (short, long) = (loss(1), loss(5));
+    assert!(short > 0.0 && long < short / 2.0, "{short} vs {long}");
+}
+
+/// Longer holds bring more complaints about access to funds and cost
+/// more satisfaction.
+#[test]
+fn longer_holds_raise_complaints() {
+    let outcome = |hold_ticks| {
+        let mut engine = engine(&format!("deposit-holds-complaints-{hold_ticks}-test"), hold_ticks);
+        engine.run_ticks(TICKS).unwrap();
+        let complaints = events(&engine, "complaint_filed")
+            .iter()
+            .filter(|e| e["issue"] == "account_access")
+            .count();
+        let pressure: f64 = holds(&engine).iter().map(|h| h.pressure).sum();
+        (complaints, pressure)
+    };
+    let (short, long) = (outcome(1), outcome(5));
+    assert!(long.0 > short.0, "{short:?} vs {long:?}");
+    assert!(long.1 > 2.0 * short.1, "{short:?} vs {long:?}");
+}
+
+/// A hold weighs on mass-market customers far more than premium ones: its
+/// pressure scales with the segment's liquidity sensitivity, and so do
+/// the satisfaction lost and the complaints.
+#[test]
+fn liquidity_sensitive_segments_feel_it_most() {
+    let mut engine = engine("deposit-holds-segment-test", 5);
+    engine.run_ticks(TICKS).unwrap();
+    let sensitivity = SimConfig::default_test().deposit_holds.liquidity_sensitivity;
+    let complained: Vec<String> = events(&engine, "complaint_filed")
+        .iter()
+        .filter(|e| e["issue"] == "account_access")
+        .map(|e| e["customer_id"].as_str().unwrap().to_string())
+        .collect();
+    let (mut mass, mut other) = ((0, 0), (0, 0));
+    for hold in holds(&engine) {
+        let segment = engine.store.customer_segment(&engine.run_id, &hold.customer_id).unwrap();
+        let expected = hold.hold_ticks as f64 * sensitivity.get(&segment).copied().unwrap_or(1.0);
+        assert!((hold.pressure - expected).abs() < 1e-9, "{hold:?}");
+        let tally = if segment == "mass_market" { &mut mass } else { &mut other };
+        tally.0 += 1;
+        tally.1 += complained.contains(&hold.customer_id) as i32;
+    }
+    assert!(mass.0 > 0 && other.0 > 0);
+    let rate = |(holds, complaints): (i32, i32)| complaints as f64 / holds as f64;
+    assert!(rate(mass) > rate(other), "{mass:?} vs {other:?}");
+}
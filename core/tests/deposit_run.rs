@@ -0,0 +1,165 @@
+//! Deposit run tests — Phase 4.52.
+//!
+//! Tests cover: no run and no withdrawals without a shock, a scripted
+//! shock accelerating outflows concentrated in uninsured balances, low
+//! reputation both deepening a run and setting one off, a deposit rate
+//! premium slowing the run, and discount window credit covering the
+//! shortfall and being repaid once the run is over.
+
+use fincrime_core::{command::PlayerCommand, engine::SimEngine};
+use serde_json::Value;
+
+fn engine(run_id: &str) -> SimEngine {
+    SimEngine::build_test_with_deposit_run(run_id.into(), 42).unwrap()
+}
+
+/// Payloads of every `event_type` event so far, in order.
+fn events(engine: &SimEngine, event_type: &str) -> Vec<Value> {
+    (0..=engine.clock.current_tick)
+        .flat_map(|tick| engine.store.events_for_tick(&engine.run_id, tick).unwrap())
+        .filter(|e| e.event_type == event_type)
+        .map(|e| serde_json::from_str(&e.payload).unwrap())
+        .collect()
+}
+
+fn rates(engine: &SimEngine) -> Vec<f64> {
+    events(engine, "deposit_outflow").iter().map(|e| e["run_rate"].as_f64().unwrap()).collect()
+}
+
+/// Off by default; on, the bank holds its usual liquidity and nobody
+/// runs until the shock.
+#[test]
+fn no_run_without_shock() {
+    let mut plain = SimEngine::build_test("deposit-run-off-test".into(), 42).unwrap();
+    plain.run_ticks(15).unwrap();
+    assert!(plain.store.liquidity_positions(&plain.run_id).unwrap().is_empty());
+    assert!(plain.store.deposit_runs(&plain.run_id).unwrap().is_empty());
+
+    let mut engine = engine("deposit-run-calm-test");
+    engine.run_ticks(9).unwrap();
+    let positions = engine.store.liquidity_positions(&engine.run_id).unwrap();
+    assert_eq!(positions.len(), 9);
+    for p in &positions {
+        assert!((p.liquid_assets - 0.15 * p.deposits).abs() < 1e-6, "{p:?}");
+        assert_eq!((p.run_rate, p.outflow, p.penalty_borrowing), (0.0, 0.0, 0.0));
+    }
+    assert!(engine.store.deposit_runs(&engine.run_id).unwrap().is_empty());
+}
+
+/// The scripted shock starts a run whose pace grows every tick and whose
+/// withdrawals come mostly from uninsured balances.
+#[test]
+fn shock_drains_uninsured_balances_faster() {
+    let mut engine = engine("deposit-run-shock-test");
+    engine.run_ticks(9).unwrap();
+    let before = engine.store.latest_liquidity_position(&engine.run_id).unwrap().unwrap();
+    engine.run_ticks(10).unwrap();
+
+    let runs = engine.store.deposit_runs(&engine.run_id).unwrap();
+    assert_eq!(runs.len(), 1);
+    assert_eq!((runs[0].shock_tick, runs[0].trigger.as_str(), runs[0].ended_tick), (10, "scheduled", None));
+    let rates = rates(&engine);
+    assert_eq!(rates.len(), 10);
+    assert!(rates.windows(2).all(|w| w[1] > w[0] || w[1] == 0.40), "{rates:?}");
+
+    let outflows = events(&engine, "deposit_outflow");
+    let total: f64 = outflows.iter().map(|e| e["outflow"].as_f64().unwrap()).sum();
+    assert!((total - runs[0].total_outflow).abs() < 1e-6);
+    let uninsured_share = runs[0].uninsured_outflow / runs[0].total_outflow;
+    assert!(
+        uninsured_share > 2.0 * before.uninsured_deposits / before.deposits,
+        "{uninsured_share} of outflow vs {} of deposits",
+        before.uninsured_deposits / before.deposits
+    );
+}
+
+/// A reputation below neutral makes the same shock hit harder, and one
+/// below the trigger sets a run off with no shock scripted.
+#[test]
+fn reputation_feeds_the_run() {
+    let first_rate = |run_id: &str, score: Option<f64>| {
+        let mut engine = engine(run_id);
+        if let Some(score) = score {
+            engine.store.insert_reputation_snapshot(&engine.run_id, 0, score, 0.0).unwrap();
+        }
+        engine.run_ticks(10).unwrap();
+        (events(&engine, "deposit_run_started"), engine)
+    };
+    let (neutral, _) = first_rate("deposit-run-rep-neutral-test", None);
+    let (damaged, _) = first_rate("deposit-run-rep-damaged-test", Some(50.0));
+    assert_eq!(neutral[0]["tick"], 10);
+    assert_eq!(damaged[0]["tick"], 10);
+    assert!(damaged[0]["run_rate"].as_f64() > neutral[0]["run_rate"].as_f64(), "{damaged:?} vs {neutral:?}");
+
+    let (started, engine) = first_rate("deposit-run-rep-trigger-test", Some(20.0));
+    assert_eq!(started.len(), 1);
+    assert_eq!(started[0]["tick"], 1);
+    assert_eq!(started[0]["trigger"], "reputation");
+    assert_eq!(engine.store.deposit_runs(&engine.run_id).unwrap()[0].reputation_score, 20.0);
+}
+
+/// Paying a premium turns the run around at a cost to NII; one over the
+/// ceiling is rejected.
+#[test]
+fn rate_premium_slows_the_run() {
+    let outflow_by_30 = |run_id: &str, premium: Option<f64>| {
+        let mut engine = engine(run_id);
+        engine.run_ticks(12).unwrap();
+        if let Some(premium) = premium {
+            engine.submit_command(PlayerCommand::SetDepositRatePremium { premium }).unwrap();
+        }
+        engine.run_ticks(18).unwrap();
+        let outflow = engine.store.deposit_runs(&engine.run_id).unwrap()[0].total_outflow;
+        (outflow, engine)
+    };
+    let (baseline, _) = outflow_by_30("deposit-run-premium-base-test", None);
+    let (slowed, engine) = outflow_by_30("deposit-run-premium-test", Some(0.05));
+    assert!(slowed < baseline, "{slowed} vs {baseline}");
+    let rates = rates(&engine);
+    assert!(rates[2..].windows(2).all(|w| w[1] < w[0]), "{rates:?}");
+    let positions = engine.store.liquidity_positions(&engine.run_id).unwrap();
+    assert!(positions.iter().filter(|p| p.tick >= 13).all(|p| p.rate_premium == 0.05 && p.premium_cost > 0.0));
+
+    let (_, engine) = outflow_by_30("deposit-run-premium-ceiling-test", Some(0.10));
+    assert_eq!(events(&engine, "deposit_run_command_rejected").len(), 1);
+    assert_eq!(engine.store.latest_liquidity_position(&engine.run_id).unwrap().unwrap().rate_premium, 0.0);
+}
+
+/// Discount window credit is refused before a run and past capacity;
+/// drawn during one it covers withdrawals the bank would otherwise have
+/// borrowed at the penalty rate, and is repaid when the run is over.
+#[test]
+fn emergency_liquidity_covers_the_shortfall() {
+    let shortfall_by_25 = |run_id: &str, draw: bool| {
+        let mut engine = engine(run_id);
+        engine.run_ticks(5).unwrap();
+        engine.submit_command(PlayerCommand::DrawEmergencyLiquidity { amount: 1_000.0 }).unwrap();
+        engine.run_ticks(10).unwrap();
+        if draw {
+            engine.submit_command(PlayerCommand::DrawEmergencyLiquidity { amount: 10_000_000.0 }).unwrap();
+            engine.submit_command(PlayerCommand::DrawEmergencyLiquidity { amount: 25_000.0 }).unwrap();
+            engine.submit_command(PlayerCommand::SetDepositRatePremium { premium: 0.05 }).unwrap();
+        }
+        engine.run_ticks(10).unwrap();
+        (engine.store.deposit_runs(&engine.run_id).unwrap()[0].shortfall, engine)
+    };
+    let (baseline, engine) = shortfall_by_25("deposit-run-draw-base-test", false);
+    let rejected = events(&engine, "deposit_run_command_rejected");
+    assert_eq!(rejected[0]["reason"], "no deposit run under way");
+    assert!(baseline > 0.0);
+
+    let (covered, mut engine) = shortfall_by_25("deposit-run-draw-test", true);
+    assert!(covered < baseline, "{covered} vs {baseline}");
+    assert_eq!(events(&engine, "deposit_run_command_rejected").len(), 2);
+    let drawn = events(&engine, "emergency_liquidity_drawn");
+    assert_eq!((drawn.len(), drawn[0]["outstanding"].as_f64()), (1, Some(25_000.0)));
+
+    engine.run_ticks(60).unwrap();
+    let run = engine.store.deposit_runs(&engine.run_id).unwrap().remove(0);
+    let ended = events(&engine, "deposit_run_ended");
+    assert_eq!(ended.len(), 1);
+    assert_eq!(ended[0]["tick"].as_u64(), run.ended_tick);
+    assert!((ended[0]["repaid"].as_f64().unwrap() - (25_000.0 + run.shortfall)).abs() < 1e-6);
+    let last = engine.store.latest_liquidity_position(&engine.run_id).unwrap().unwrap();
+    assert_eq!((last.emergency_borrowing, last.penalty_borrowing, last.run_rate), (0.0, 0.0, 0.0));
+}
@@ -0,0 +1,202 @@
+//! Device telemetry tests — Phase 4.94.
+//!
+//! Tests cover: no devices unless enabled, and logins from customers'
+//! own devices with every digital payment stamped with one; account
+//! takeovers from fraudster devices that end up shared and alerted;
+//! high-value payments from new devices alerted; and the device signals
+//! in the monthly account fraud score.
+
+use fincrime_core::{device, engine::SimEngine};
+use rusqlite::types::Value;
+use serde_json::Value as Json;
+use std::collections::{BTreeMap, BTreeSet};
+
+const TICKS: u64 = 30;
+
+fn engine(run_id: &str) -> SimEngine {
+    let mut engine = SimEngine::build_test_with_devices(run_id.into(), 42).unwrap();
+    engine.run_ticks(TICKS).unwrap();
+    engine
+}
+
+/// Payloads of every `event_type` event so far, in order.
+fn events(engine: &SimEngine, event_type: &str) -> Vec<Json> {
+    (0..=engine.clock.current_tick)
+        .flat_map(|tick| engine.store.events_for_tick(&engine.run_id, tick).unwrap())
+        .filter(|e| e.event_type == event_type)
+        .map(|e| serde_json::from_str(&e.payload).unwrap())
+        .collect()
+}
+
+/// Off by default; on, digital customers log in from their own devices
+/// and each digital payment carries the device of the login.
+#[test]
+fn logins_from_own_devices() {
+    let mut plain = SimEngine::build_test("devices-off-test".into(), 42).unwrap();
+    plain.run_ticks(5).unwrap();
+    assert!(plain.store.customer_devices(&plain.run_id).unwrap().is_empty());
+
+    let engine = engine("devices-logins-test");
+    let devices = engine.store.customer_devices(&engine.run_id).unwrap();
+    let mut customers_on: BTreeMap<&str, i64> = BTreeMap::new();
+    for d in &devices {
+        *customers_on.entry(d.device_id.as_str()).or_default() += 1;
+    }
+    for d in &devices {
+        assert_eq!(d.shared_count, customers_on[d.device_id.as_str()], "{}", d.device_id);
+        assert!(d.first_seen_tick <= d.last_seen_tick);
+    }
+
+    let logged = events(&engine, "device_sessions_logged");
+    assert!(!logged.is_empty());
+    let mut paid = 0;
+    for event in &logged {
+        let tick = event["tick"].as_u64().unwrap();
+        let sessions = engine.store.device_sessions(&engine.run_id, tick).unwrap();
+        assert_eq!(sessions.len() as i64, event["sessions"].as_i64().unwrap());
+        assert_eq!(sessions.iter().filter(|s| s.new_device).count() as i64, event["new_devices"].as_i64().unwrap());
+        for s in sessions.iter().filter(|s| !s.takeover) {
+            assert!(!s.device_id.starts_with("dev-farm-"));
+            assert!(s.ip_address.starts_with("10."));
+        }
+
+        // Every digital payment came from a device of the customer's
+        let digital = engine.store.digital_payments(&engine.run_id, tick).unwrap();
+        let stamped = engine.store.device_payments(&engine.run_id, tick, tick, 0.0).unwrap();
+        assert_eq!(stamped.len(), digital.len(), "tick {tick}");
+        for p in &stamped {
+            assert!(sessions.iter().any(|s| s.customer_id == p.customer_id && s.device_id == p.device_id));
+        }
+        paid += digital.len();
+    }
+    assert!(paid > 20, "only {paid} digital payments");
+}
+
+/// A takeover is a login from a fraudster's device through a proxy and
+/// a transfer out; each device reaching three customers is alerted once.
+#[test]
+fn takeovers_share_fraud_devices() {
+    let engine = engine("devices-takeover-test");
+    let takeovers = events(&engine, "account_takeover");
+    assert!(takeovers.len() >= 4, "only {} takeovers", takeovers.len());
+    for t in &takeovers {
+        let tick = t["tick"].as_u64().unwrap();
+        let device_id = t["device_id"].as_str().unwrap();
+        assert!(device_id.starts_with("dev-farm-"));
+        let sessions = engine.store.device_sessions(&engine.run_id, tick).unwrap();
+        let session = sessions.iter().find(|s| s.takeover).unwrap();
+        assert_eq!(session.device_id, device_id);
+        assert!(session.ip_address.starts_with("203.0.113."));
+        let payments = engine.store.device_payments(&engine.run_id, tick, tick, 1000.0).unwrap();
+        assert!(payments.iter().any(|p| p.txn_id == t["txn_id"] && p.device_id == device_id));
+    }
+
+    let shared: Vec<(String, u64)> = events(&engine, "fraud_alert_generated")
+        .into_iter()
+        .filter(|e| e["alert_type"] == device::SHARED_DEVICE)
+        .map(|e| (e["entity_id"].as_str().unwrap().to_string(), e["tick"].as_u64().unwrap()))
+        .collect();
+    // When each device's third customer first used it
+    let mut firsts: BTreeMap<String, Vec<u64>> = BTreeMap::new();
+    for d in engine.store.customer_devices(&engine.run_id).unwrap() {
+        firsts.entry(d.device_id).or_default().push(d.first_seen_tick);
+    }
+    let expected: Vec<(String, u64)> = firsts
+        .into_iter()
+        .filter(|(_, ticks)| ticks.len() >= 3)
+        .map(|(device_id, mut ticks)| {
+            ticks.sort();
+            (device_id, ticks[2])
+        })
+        .collect();
+    assert!(!expected.is_empty());
+    assert_eq!(shared, expected);
+}
+
+/// Each payment of $1,000 or more from a device first used within three
+/// ticks is alerted that tick, high when the device is shared.
+#[test]
+fn new_device_high_value_alerted() {
+    let engine = engine("devices-alert-test");
+    let alerts: BTreeMap<String, Json> = events(&engine, "fraud_alert_generated")
+        .into_iter()
+        .filter(|e| e["alert_type"] == device::NEW_DEVICE_HIGH_VALUE)
+        .map(|e| (e["alert_id"].as_str().unwrap().to_string(), e))
+        .collect();
+    assert!(!alerts.is_empty());
+    let devices = engine.store.customer_devices(&engine.run_id).unwrap();
+
+    for p in engine.store.device_payments(&engine.run_id, 1, TICKS, 1000.0).unwrap() {
+        let alert = alerts.get(&format!("fraud-device-{}", p.txn_id));
+        if p.tick > p.first_seen_tick + 3 {
+            assert!(alert.is_none(), "{} alerted on an old device", p.txn_id);
+            continue;
+        }
+        let alert = alert.unwrap_or_else(|| panic!("{} not alerted", p.txn_id));
+        assert_eq!(alert["tick"].as_u64(), Some(p.tick));
+        assert_eq!(alert["entity_id"].as_str(), Some(p.account_id.as_str()));
+        // Shared as the device stood that tick
+        let customers = devices.iter().filter(|d| d.device_id == p.device_id && d.first_seen_tick <= p.tick).count();
+        let severity = if customers >= 3 { "high" } else { "medium" };
+        assert_eq!(alert["severity"], severity);
+    }
+    // Every takeover from a device new to the victim is among them
+    let mut first_logins = 0;
+    for t in events(&engine, "account_takeover") {
+        let sessions = engine.store.device_sessions(&engine.run_id, t["tick"].as_u64().unwrap()).unwrap();
+        if sessions.iter().any(|s| s.takeover && s.new_device) {
+            assert!(alerts.contains_key(&format!("fraud-device-{}", t["txn_id"].as_str().unwrap())));
+            first_logins += 1;
+        }
+    }
+    assert!(first_logins > 0);
+}
+
+/// The monthly account score carries the device signals in its
+/// behavioural component, for takeover victims and no one untouched.
+#[test]
+fn device_signals_scored_monthly() {
+    let engine = engine("devices-score-test");
+    let found = engine
+        .store
+        .readonly_query(
+            "SELECT a.customer_id, s.behavioral_component
+             FROM account_fraud_score s
+             JOIN account a ON a.run_id = s.run_id AND a.account_id = s.account_id
+             WHERE s.run_id = ?1 AND s.tick = 30",
+            &[Value::Text(engine.run_id.clone())],
+            10_000,
+            100_000,
+        )
+        .unwrap();
+    let scored: Vec<(String, f64)> = found
+        .rows
+        .into_iter()
+        .map(|r| match (&r[0], &r[1]) {
+            (Value::Text(c), Value::Real(b)) => (c.clone(), *b),
+            other => panic!("unexpected row {other:?}"),
+        })
+        .collect();
+    assert!(!scored.is_empty());
+
+    let victims: BTreeSet<String> =
+        events(&engine, "account_takeover").into_iter().map(|t| t["customer_id"].as_str().unwrap().to_string()).collect();
+    let new_device_payers: BTreeSet<String> = engine
+        .store
+        .device_payments(&engine.run_id, 1, TICKS, 1000.0)
+        .unwrap()
+        .into_iter()
+        .filter(|p| p.tick <= p.first_seen_tick + 3)
+        .map(|p| p.customer_id)
+        .collect();
+    let mut victims_scored = 0;
+    for (customer_id, component) in &scored {
+        if victims.contains(customer_id) {
+            assert!(*component >= 0.15, "{customer_id} scored {component}");
+            victims_scored += 1;
+        } else if *component > 0.0 {
+            assert!(new_device_payers.contains(customer_id), "{customer_id} scored {component}");
+        }
+    }
+    assert!(victims_scored > 0);
+}
@@ -0,0 +1,180 @@
+//! Regulatory enforcement tests — Phase 4.81.
+//!
+//! Tests cover: exams sampling the cycle's complaints, SARs and fee
+//! changes, a UDAAP rise bringing the CFPB in early, a repeat finding
+//! escalating an MRA to a consent order, a consent order's fee cap
+//! holding fees at their soft limits, a mandated lookback being worked
+//! before the order is lifted, and enforcement staying off unless enabled.
+
+use fincrime_core::{command::PlayerCommand, engine::SimEngine};
+
+fn build(run_id: &str) -> SimEngine {
+    SimEngine::build_test_with_enforcement(run_id.to_string(), 42).unwrap()
+}
+
+/// Raise the UDAAP score by `delta` at the current tick.
+fn raise_udaap(engine: &SimEngine, run_id: &str, tick: u64, delta: f64) {
+    engine.store.adjust_udaap_score(run_id, delta, tick).unwrap();
+}
+
+/// The scheduled exam (open 21, close 26) samples no more than the
+/// sample size of each population and records every fee change.
+#[test]
+fn exam_samples_the_cycle() {
+    let run_id = "enforcement-sample-test";
+    let mut engine = build(run_id);
+    engine.run_ticks(22).unwrap();
+    let exam_id = engine.store.get_open_exam(run_id).unwrap().expect("scheduled exam open").exam_id;
+    engine.run_ticks(5).unwrap();
+
+    let samples = engine.store.exam_samples(run_id, &exam_id).unwrap();
+    let populations: Vec<&str> = samples.iter().map(|s| s.0.as_str()).collect();
+    assert_eq!(populations, ["complaints", "fee_changes", "sars"]);
+    for (population, available, sampled, failed) in &samples {
+        assert!(*sampled <= *available && *failed <= *sampled, "{population}: {samples:?}");
+        if population != "fee_changes" {
+            assert_eq!(*sampled, (*available).min(25), "{population}");
+        }
+    }
+    assert_eq!(engine.store.last_exam_udaap_score(run_id).unwrap(), Some(0.0));
+}
+
+/// A UDAAP rise past the trigger opens a targeted CFPB exam on the next
+/// tick instead of waiting for the scheduled one.
+#[test]
+fn udaap_rise_brings_cfpb_early() {
+    let run_id = "enforcement-cfpb-test";
+    let mut engine = build(run_id);
+    engine.run_ticks(3).unwrap();
+    assert!(engine.store.get_open_exam(run_id).unwrap().is_none());
+
+    // 10 points over 50 customers is 200 per 1,000
+    raise_udaap(&engine, run_id, 3, 10.0);
+    engine.run_ticks(1).unwrap();
+
+    let exam = engine.store.get_open_exam(run_id).unwrap().expect("targeted exam open");
+    assert_eq!((exam.examiner.as_str(), exam.scope.as_str(), exam.tick_started), ("CFPB", "targeted_udaap", 4));
+    engine.run_ticks(5).unwrap();
+
+    let actions = engine.store.enforcement_actions(run_id).unwrap();
+    let udaap: Vec<_> = actions.iter().filter(|a| a.category == "udaap").collect();
+    assert_eq!(udaap.len(), 1, "{actions:?}");
+    assert_eq!((udaap[0].kind.as_str(), udaap[0].tick_issued), ("mra", 9));
+}
+
+/// A major UDAAP finding draws an MRA; the same finding at the next exam,
+/// while the MRA is open, escalates it to a consent order with a penalty
+/// and a complaint lookback.
+#[test]
+fn repeat_finding_escalates_mra_to_consent_order() {
+    let run_id = "enforcement-escalation-test";
+    let mut engine = build(run_id);
+    engine.run_ticks(2).unwrap();
+    // 6 points over 50 customers is 120 per 1,000: major, short of a CFPB visit
+    raise_udaap(&engine, run_id, 2, 6.0);
+    engine.run_ticks(28).unwrap();
+
+    let mra = engine.store.open_enforcement_actions(run_id).unwrap();
+    let mra = mra.iter().find(|a| a.category == "udaap").expect("MRA on the scheduled exam");
+    assert_eq!((mra.kind.as_str(), mra.tick_issued, mra.penalty), ("mra", 26, 0.0));
+    assert_eq!(mra.constraint_kind, None);
+
+    raise_udaap(&engine, run_id, 30, 6.0);
+    engine.run_ticks(10).unwrap();
+
+    let actions = engine.store.enforcement_actions(run_id).unwrap();
+    let order = actions
+        .iter()
+        .find(|a| a.category == "udaap" && a.kind == "consent_order")
+        .expect("consent order on the repeat finding");
+    assert!(order.penalty > 0.0);
+    assert_eq!(order.constraint_kind.as_deref(), Some("complaint_lookback"));
+    let escalated = actions.iter().find(|a| a.action_id == mra.action_id).unwrap();
+    assert_eq!(escalated.status, "escalated");
+    assert_eq!(escalated.superseded_by.as_deref(), Some(order.action_id.as_str()));
+    assert_eq!(escalated.tick_closed, Some(order.tick_issued));
+}
+
+/// A critical UDAAP finding in a cycle that raised fees brings a fee cap:
+/// fees above their soft limit are lowered to it, and a change past it is
+/// rejected while the order stands.
+#[test]
+fn fee_cap_holds_fees_at_soft_limit() {
+    let run_id = "enforcement-fee-cap-test";
+    let mut engine = build(run_id);
+    engine.run_ticks(1).unwrap();
+    engine
+        .submit_command(PlayerCommand::SetProductFee {
+            product_id: "basic_checking".into(),
+            fee_type: "overdraft_fee".into(),
+            new_value: 33.0,
+        })
+        .unwrap();
+    engine.run_ticks(1).unwrap();
+    assert_eq!(engine.store_product_state(run_id, "basic_checking").unwrap().overdraft_fee, 33.0);
+
+    // 20 points over 50 customers is 400 per 1,000: critical
+    raise_udaap(&engine, run_id, 2, 20.0);
+    engine.run_ticks(10).unwrap();
+
+    assert!(engine.store.has_active_constraint(run_id, "fee_cap").unwrap());
+    assert_eq!(engine.store_product_state(run_id, "basic_checking").unwrap().overdraft_fee, 29.0);
+
+    engine
+        .submit_command(PlayerCommand::SetProductFee {
+            product_id: "basic_checking".into(),
+            fee_type: "overdraft_fee".into(),
+            new_value: 31.0,
+        })
+        .unwrap();
+    engine.run_ticks(1).unwrap();
+    assert_eq!(engine.store.count_events_in_range(run_id, 13, 13, "fee_change_rejected").unwrap(), 1);
+    assert_eq!(engine.store_product_state(run_id, "basic_checking").unwrap().overdraft_fee, 29.0);
+}
+
+/// A consent order's lookback is worked through within the review window,
+/// booked as cost, and the order lifted at the first exam past its
+/// remediation period that no longer finds the category.
+#[test]
+fn lookback_worked_then_order_lifted() {
+    let run_id = "enforcement-lookback-test";
+    let mut engine = build(run_id);
+    engine.run_ticks(2).unwrap();
+    raise_udaap(&engine, run_id, 2, 20.0);
+    engine.run_ticks(14).unwrap();
+
+    let actions = engine.store.enforcement_actions(run_id).unwrap();
+    let order = actions.iter().find(|a| a.category == "udaap").expect("consent order").clone();
+    assert_eq!((order.kind.as_str(), order.tick_issued), ("consent_order", 8));
+    assert_eq!(order.constraint_kind.as_deref(), Some("complaint_lookback"));
+    assert!(order.lookback_items > 0, "{order:?}");
+    assert_eq!(order.lookback_reviewed, order.lookback_items);
+    assert_eq!(engine.store.count_events_in_range(run_id, 9, 13, "lookback_completed").unwrap(), 1);
+    let cost = engine.store.enforcement_cost_between(run_id, 8, 8).unwrap();
+    assert!(cost >= order.penalty + order.lookback_items as f64 * 150.0 - 1e-6);
+
+    // The exam closing at 26 is inside the remediation period; 46 is past it
+    engine.run_ticks(34).unwrap();
+    let lifted = engine.store.enforcement_actions(run_id).unwrap();
+    let lifted = lifted.iter().find(|a| a.action_id == order.action_id).unwrap();
+    assert_eq!((lifted.status.as_str(), lifted.tick_closed), ("lifted", Some(46)));
+}
+
+/// With exams on but enforcement off, nothing is sampled, a UDAAP rise
+/// draws no CFPB visit and no action is issued.
+#[test]
+fn enforcement_off_unless_enabled() {
+    let run_id = "enforcement-disabled-test";
+    let mut engine = SimEngine::build_test_with_regulatory_exam(run_id.to_string(), 42).unwrap();
+    engine.run_ticks(2).unwrap();
+    raise_udaap(&engine, run_id, 2, 20.0);
+    engine.run_ticks(20).unwrap();
+
+    let exam = engine.store.get_open_exam(run_id).unwrap().expect("scheduled exam open");
+    assert_ne!(exam.scope, "targeted_udaap");
+    engine.run_ticks(10).unwrap();
+
+    assert!(engine.store.exam_samples(run_id, &exam.exam_id).unwrap().is_empty());
+    assert!(engine.store.enforcement_actions(run_id).unwrap().is_empty());
+    assert_eq!(engine.store.last_exam_udaap_score(run_id).unwrap(), None);
+}
@@ -0,0 +1,135 @@
+//! Engine introspection tests.
+//!
+//! Tests cover: `subsystems()` listing registrations in execution order,
+//! every logged event being declared by the subsystem that emitted it,
+//! declared tables existing in the schema, and the `describe_engine` IPC
+//! message and its reply.
+
+use fincrime_core::{
+    engine::SimEngine,
+    protocol::{EngineDescription, IpcCommand, PROTOCOL_VERSION},
+    snapshot::SNAPSHOT_VERSION,
+    types::RunId,
+};
+use std::collections::BTreeSet;
+
+/// Subsystems are listed once each, in the order they run. Features
+/// switch behaviour inside a subsystem, not its registration, so the
+/// listing is the same whichever are on.
+#[test]
+fn subsystems_listed_in_execution_order() {
+    let engine = SimEngine::build_test("introspect-order-test".into(), 42).unwrap();
+    let subsystems = engine.subsystems();
+    assert!(!subsystems.is_empty());
+
+    let names: BTreeSet<_> = subsystems.iter().map(|s| s.name.as_str()).collect();
+    assert_eq!(names.len(), subsystems.len(), "duplicate subsystem names");
+    for (i, info) in subsystems.iter().enumerate() {
+        assert_eq!(info.order, i);
+        assert!(info.version >= 1);
+    }
+    assert_eq!(subsystems[0].name, "macro");
+    assert_eq!(subsystems[0].tables, ["macro_rate_history"]);
+
+    let incidents = SimEngine::build_test_with_incidents("introspect-incidents-test".into(), 42).unwrap();
+    assert_eq!(incidents.subsystems(), subsystems);
+    assert!(subsystems.iter().any(|s| s.name == "incident"));
+}
+
+/// Every event a subsystem writes to the log is one its manifest says it
+/// produces, across builds that switch on the optional features.
+#[test]
+fn logged_events_are_declared() {
+    type Builder = fn(RunId, u64) -> fincrime_core::error::SimResult<SimEngine>;
+    let builders: &[(&str, Builder)] = &[
+        ("base", SimEngine::build_test),
+        ("incidents", SimEngine::build_test_with_incidents),
+        ("reputation", SimEngine::build_test_with_reputation),
+        ("exam", SimEngine::build_test_with_regulatory_exam),
+        ("enforcement", SimEngine::build_test_with_enforcement),
+        ("branches", SimEngine::build_test_with_branches),
+        ("ach-returns", SimEngine::build_test_with_ach_returns),
+        ("hardship", SimEngine::build_test_with_hardship),
+        ("fatigue", SimEngine::build_test_with_contact_fatigue),
+        ("satisfaction", SimEngine::build_test_with_satisfaction_dynamics),
+        ("remittance", SimEngine::build_test_with_remittance),
+        ("adverse-media", SimEngine::build_test_with_adverse_media),
+        ("correspondent", SimEngine::build_test_with_correspondent_banking),
+        ("cross-border", SimEngine::build_test_with_cross_border),
+        ("deposit-run", SimEngine::build_test_with_deposit_run),
+        ("bec", |run_id, seed| SimEngine::build_test_with_bec(run_id, seed, Some(3))),
+        ("check-fraud", |run_id, seed| SimEngine::build_test_with_check_fraud(run_id, seed, 2)),
+        ("deposit-holds", |run_id, seed| SimEngine::build_test_with_deposit_holds(run_id, seed, 5)),
+        ("evidence-bundles", |run_id, seed| SimEngine::build_test_with_evidence_bundles(run_id, seed, 0.2)),
+        ("alert-grouping", |run_id, seed| SimEngine::build_test_with_alert_grouping(run_id, seed, 14)),
+        ("operational-risk", SimEngine::build_test_with_operational_risk),
+        ("quarter-close", SimEngine::build_test_with_quarter_close),
+        ("opex-allocation", SimEngine::build_test_with_opex_allocation),
+        ("churn-evaluation", SimEngine::build_test_with_churn_evaluation),
+        ("complaint-quality", SimEngine::build_test_with_complaint_quality),
+        ("stress-index", SimEngine::build_test_with_stress_index),
+        ("alert-qa", SimEngine::build_test_with_alert_qa),
+        ("staffing", SimEngine::build_test_with_staffing),
+        ("lending", SimEngine::build_test_with_lending),
+        ("rate-environment", SimEngine::build_test_with_rate_environment),
+        ("alert-triage", SimEngine::build_test_with_alert_triage),
+        ("case-management", SimEngine::build_test_with_case_management),
+        ("channels", SimEngine::build_test_with_channels),
+        ("app-fraud", |run_id, seed| SimEngine::build_test_with_app_fraud(run_id, seed, "vulnerable")),
+        ("atm-network", SimEngine::build_test_with_atm_network),
+        ("merchants", SimEngine::build_test_with_merchants),
+        ("devices", SimEngine::build_test_with_devices),
+    ];
+
+    for (label, build) in builders {
+        let run_id = format!("introspect-events-{label}");
+        let mut engine = build(run_id.clone(), 42).unwrap();
+        engine.run_ticks(45).unwrap();
+        let subsystems = engine.subsystems();
+
+        for (subsystem, event_type) in engine.store.logged_event_sources(&run_id).unwrap() {
+            if subsystem == "engine" {
+                continue;
+            }
+            let info = subsystems
+                .iter()
+                .find(|s| s.name == subsystem)
+                .unwrap_or_else(|| panic!("{label}: unregistered subsystem {subsystem}"));
+            assert!(
+                info.produces.contains(&event_type),
+                "{label}: {subsystem} emitted undeclared {event_type}"
+            );
+        }
+    }
+}
+
+/// Tables a subsystem claims are real tables in the migrated schema.
+#[test]
+fn declared_tables_exist() {
+    let engine = SimEngine::build_test("introspect-tables-test".into(), 42).unwrap();
+    let tables = engine.store.table_names().unwrap();
+    for info in engine.subsystems() {
+        for table in &info.tables {
+            assert!(tables.contains(table), "{} declares missing table {table}", info.name);
+        }
+    }
+}
+
+/// `describe_engine` parses, and its reply carries the build's versions
+/// and subsystems and survives a JSON round trip.
+#[test]
+fn describe_engine_over_ipc() {
+    let cmd: IpcCommand = serde_json::from_str(r#"{ "type": "describe_engine" }"#).unwrap();
+    assert!(matches!(cmd, IpcCommand::DescribeEngine));
+
+    let engine = SimEngine::build_test("introspect-ipc-test".into(), 42).unwrap();
+    let description = EngineDescription::from_engine(&engine);
+    assert_eq!(description.run_id, "introspect-ipc-test");
+    assert_eq!(description.protocol_version, PROTOCOL_VERSION);
+    assert_eq!(description.snapshot_version, SNAPSHOT_VERSION);
+    assert_eq!(description.subsystems, engine.subsystems());
+
+    let json = serde_json::to_string(&description).unwrap();
+    let back: EngineDescription = serde_json::from_str(&json).unwrap();
+    assert_eq!(back, description);
+}
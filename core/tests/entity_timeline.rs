@@ -0,0 +1,177 @@
+//! Entity timeline tests — Phase 4.58.
+//!
+//! Tests cover: a customer's timeline merging openings, transactions,
+//! alerts and SAR filings in order; a case's timeline holding the dispute,
+//! its status changes and documents; an account's timeline leaving out
+//! customer-level items while picking up fraud alerts on its
+//! transactions; and tick windows, unknown ids and the IPC message.
+
+use fincrime_core::{
+    engine::SimEngine,
+    entity_timeline::{EntityTimeline, EntityType, TimelineEntry, TimelineKind},
+    error::SimError,
+    protocol::IpcCommand,
+    transaction_monitoring_subsystem::AMLAlert,
+};
+
+/// A SAR decision run with a structuring alert raised on the first
+/// customer at tick 1, run to `ticks`; returns the customer.
+fn alerted_run(run_id: &str, ticks: u64) -> (SimEngine, String) {
+    let mut engine = SimEngine::build_test_with_sar_decisions(run_id.into(), 42).unwrap();
+    engine.run_ticks(1).unwrap();
+    let customer_id = engine.store.active_customers(run_id).unwrap()[0].customer_id.clone();
+    engine
+        .store
+        .insert_transaction_monitoring_alert(&AMLAlert {
+            alert_id: "A1".into(),
+            run_id: run_id.into(),
+            customer_id: customer_id.clone(),
+            tick: 1,
+            rule_id: "TEST".into(),
+            alert_type: "structuring".into(),
+            alert_score: 90.0,
+            description: "test alert".into(),
+            triggered_amount: Some(9_500.0),
+            transaction_count: Some(3),
+            status: "open".into(),
+        })
+        .unwrap();
+    engine.run_ticks(ticks - 1).unwrap();
+    (engine, customer_id)
+}
+
+fn timeline(engine: &SimEngine, entity_type: EntityType, entity_id: &str) -> Vec<TimelineEntry> {
+    engine.get_entity_timeline(entity_type, entity_id).unwrap().entries
+}
+
+fn accounts(entries: &[TimelineEntry]) -> Vec<String> {
+    entries
+        .iter()
+        .filter(|e| e.source == "account" && e.summary.starts_with("account opened"))
+        .map(|e| e.ref_id.clone())
+        .collect()
+}
+
+/// The customer opens first, then their account; every transaction on
+/// their accounts is there, with the alert and the SAR it led to, all in
+/// tick order.
+#[test]
+fn customer_timeline_merges_in_order() {
+    let (engine, customer_id) = alerted_run("timeline-customer-test", 20);
+    let entries = timeline(&engine, EntityType::Customer, &customer_id);
+
+    assert!(entries.windows(2).all(|w| w[0].tick <= w[1].tick));
+    assert_eq!((entries[0].source.as_str(), entries[0].ref_id.as_str()), ("customer", customer_id.as_str()));
+    assert_eq!(entries[1].source, "account");
+
+    let accounts = accounts(&entries);
+    assert!(!accounts.is_empty());
+    let transactions: i64 = accounts
+        .iter()
+        .map(|a| engine.store.count_account_transactions_in_window(&engine.run_id, a, 0, 20).unwrap())
+        .sum();
+    assert_eq!(entries.iter().filter(|e| e.kind == TimelineKind::Transaction).count() as i64, transactions);
+    assert!(entries
+        .iter()
+        .filter(|e| e.kind == TimelineKind::Transaction)
+        .all(|e| accounts.contains(e.account_id.as_ref().unwrap())));
+
+    let alert = entries.iter().position(|e| e.source == "aml_alert").unwrap();
+    assert_eq!((entries[alert].tick, entries[alert].kind), (1, TimelineKind::Alert));
+    assert_eq!(entries[alert].amount, Some(9_500.0));
+    let sar = entries.iter().position(|e| e.source == "suspicious_activity_report").unwrap();
+    assert_eq!(entries[sar].kind, TimelineKind::StatusChange);
+    assert!(sar > alert && entries[sar].summary.starts_with("SAR filed"), "{:?}", entries[sar]);
+}
+
+/// A case is the dispute filed, its status changes and its documents,
+/// all of which its customer's timeline shows too.
+#[test]
+fn case_timeline_follows_the_dispute() {
+    let run_id = "timeline-case-test";
+    let mut engine = SimEngine::build_test_with_case_documents(run_id.into(), 42, 0.2).unwrap();
+    engine.run_ticks(40).unwrap();
+    let dispute_id = engine.store.documented_cases(run_id, "dispute").unwrap().remove(0);
+    let dispute = engine.store.get_dispute(run_id, &dispute_id).unwrap();
+    let entries = timeline(&engine, EntityType::Case, &dispute_id);
+
+    assert_eq!(entries[0].kind, TimelineKind::Case);
+    assert_eq!(entries[0].tick as i64, dispute.tick_filed);
+    assert_eq!(entries[0].amount, Some(dispute.amount));
+    assert_eq!(entries[0].account_id.as_ref(), Some(&dispute.account_id));
+    assert!(entries.iter().any(|e| e.source == "dispute_timeline" && e.kind == TimelineKind::StatusChange));
+    let documents = engine.store.case_documents(run_id, "dispute", &dispute_id).unwrap();
+    let noted: Vec<&str> = entries.iter().filter(|e| e.source == "case_document").map(|e| e.ref_id.as_str()).collect();
+    assert_eq!(noted, documents.iter().map(|d| d.document_id.as_str()).collect::<Vec<_>>());
+    assert!(entries.iter().all(|e| e.kind != TimelineKind::Transaction));
+
+    let customer = timeline(&engine, EntityType::Customer, &dispute.customer_id);
+    assert!(entries.iter().all(|e| customer.contains(e)));
+}
+
+/// An account's timeline is part of its customer's, without the AML
+/// alerts and SARs filed on the customer.
+#[test]
+fn account_timeline_leaves_out_customer_items() {
+    let (engine, customer_id) = alerted_run("timeline-account-test", 20);
+    let customer = timeline(&engine, EntityType::Customer, &customer_id);
+    let account_id = accounts(&customer).remove(0);
+    let entries = timeline(&engine, EntityType::Account, &account_id);
+
+    assert_eq!(entries[0].source, "account");
+    assert!(entries.iter().all(|e| customer.contains(e)));
+    assert!(entries.iter().all(|e| !["customer", "aml_alert", "suspicious_activity_report", "interaction"]
+        .contains(&e.source.as_str())));
+    let transactions = engine.store.count_account_transactions_in_window(&engine.run_id, &account_id, 0, 20).unwrap();
+    assert_eq!(entries.iter().filter(|e| e.kind == TimelineKind::Transaction).count() as i64, transactions);
+}
+
+/// A fraud alert raised on a transaction lands on the account it was
+/// made on.
+#[test]
+fn transaction_alerts_land_on_the_account() {
+    let run_id = "timeline-fraud-alert-test";
+    let mut engine = SimEngine::build_test_with_check_fraud(run_id.into(), 42, 20).unwrap();
+    engine.run_ticks(15).unwrap();
+    let flagged: Vec<_> = engine.store.check_items(run_id).unwrap().into_iter().filter(|i| i.alert_id.is_some()).collect();
+    assert!(!flagged.is_empty());
+    for item in flagged.iter().take(5) {
+        let entries = timeline(&engine, EntityType::Account, &item.account_id);
+        let alert = entries
+            .iter()
+            .find(|e| e.source == "fraud_alert" && Some(&e.ref_id) == item.alert_id.as_ref())
+            .unwrap_or_else(|| panic!("{item:?}"));
+        assert_eq!(alert.kind, TimelineKind::Alert);
+        assert_eq!(alert.account_id.as_ref(), Some(&item.account_id));
+        assert!(entries.iter().any(|e| e.ref_id == item.txn_id && e.tick <= alert.tick));
+    }
+}
+
+/// A tick window keeps exactly the entries inside it; an unknown id is a
+/// command error; the IPC message's window is optional.
+#[test]
+fn window_unknown_ids_and_ipc() {
+    let (engine, customer_id) = alerted_run("timeline-window-test", 20);
+    let all = timeline(&engine, EntityType::Customer, &customer_id);
+    let window =
+        EntityTimeline::query(&engine.store, &engine.run_id, EntityType::Customer, &customer_id, Some(5), Some(9))
+            .unwrap();
+    let expected: Vec<_> = all.iter().filter(|e| (5..=9).contains(&e.tick)).cloned().collect();
+    assert!(!expected.is_empty());
+    assert_eq!(window.entries, expected);
+    assert_eq!((window.from_tick, window.to_tick), (Some(5), Some(9)));
+
+    for entity_type in [EntityType::Customer, EntityType::Account, EntityType::Case] {
+        let err = engine.get_entity_timeline(entity_type, "nobody").unwrap_err();
+        assert!(matches!(err, SimError::Command { .. }), "{err}");
+        assert!(!err.is_fatal());
+    }
+
+    let parsed: IpcCommand =
+        serde_json::from_str(r#"{"type":"get_entity_timeline","entity_type":"account","entity_id":"a-000001"}"#)
+            .unwrap();
+    let IpcCommand::GetEntityTimeline { entity_type, entity_id, from_tick, to_tick } = parsed else {
+        panic!("{parsed:?}");
+    };
+    assert_eq!((entity_type, entity_id.as_str(), from_tick, to_tick), (EntityType::Account, "a-000001", None, None));
+}
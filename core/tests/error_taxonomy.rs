@@ -0,0 +1,123 @@
+//! Error taxonomy tests.
+//!
+//! Tests cover: a fatal error halting the run so later ticks refuse to
+//! build on it, a recoverable error leaving the run able to continue,
+//! rejected IPC commands coming back as structured recoverable errors,
+//! and the codes and severities clients key off.
+
+use fincrime_core::engine::SimEngine;
+use fincrime_core::error::{Severity, SimError, SimResult};
+use fincrime_core::event::SimEvent;
+use fincrime_core::protocol::{self, ErrorResponse};
+use fincrime_core::rng::{SubsystemRng, SubsystemSlot};
+use fincrime_core::subsystem::SimSubsystem;
+use fincrime_core::types::Tick;
+use std::any::Any;
+
+/// Fails once, at `fail_at`, with the error `make` builds.
+struct FailingSubsystem {
+    fail_at: Tick,
+    make: fn(Tick) -> SimError,
+}
+
+impl SimSubsystem for FailingSubsystem {
+    fn name(&self) -> &'static str {
+        "failing"
+    }
+
+    fn update(&mut self, tick: Tick, _: &[SimEvent], _: &mut SubsystemRng) -> SimResult<Vec<SimEvent>> {
+        if tick == self.fail_at {
+            return Err((self.make)(tick));
+        }
+        Ok(vec![])
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+fn engine_failing_with(run_id: &str, fail_at: Tick, make: fn(Tick) -> SimError) -> SimEngine {
+    let mut engine = SimEngine::build_test(run_id.into(), 42).unwrap();
+    engine.register(SubsystemSlot::Macro, Box::new(FailingSubsystem { fail_at, make }));
+    engine
+}
+
+/// An invariant violation at tick 3 halts the run there; every later
+/// tick fails with `RunHalted` and the clock does not move.
+#[test]
+fn fatal_error_halts_run() {
+    let mut engine = engine_failing_with("err-fatal-test", 3, |tick| SimError::InvariantViolation {
+        tick,
+        invariant: "test".into(),
+    });
+
+    let err = engine.run_ticks(5).unwrap_err();
+    assert!(matches!(err, SimError::InvariantViolation { tick: 3, .. }), "{err}");
+    assert_eq!(engine.halted().map(|(tick, _)| tick), Some(3));
+    assert!(engine.clock.paused, "A failed run_ticks still pauses the clock");
+
+    let again = engine.run_ticks(1).unwrap_err();
+    assert!(matches!(again, SimError::RunHalted { tick: 3, .. }), "{again}");
+    assert!(again.is_fatal());
+    assert_eq!(engine.clock.current_tick, 3);
+}
+
+/// A recoverable error is reported but leaves the run able to tick on.
+#[test]
+fn recoverable_error_does_not_halt() {
+    let mut engine = engine_failing_with("err-recoverable-test", 2, |_| {
+        SimError::command("set_risk_dial", "dial out of range")
+    });
+
+    let err = engine.run_ticks(5).unwrap_err();
+    assert_eq!(err.severity(), Severity::Recoverable);
+    assert!(engine.halted().is_none());
+
+    engine.run_ticks(3).unwrap();
+    assert_eq!(engine.clock.current_tick, 5);
+}
+
+/// Unknown commands and missing or dangling complaint ids are rejected as
+/// recoverable command errors rather than ignored or crashing the session.
+#[test]
+fn rejected_commands_are_structured() {
+    let mut engine = SimEngine::build_test("err-command-test".into(), 42).unwrap();
+    engine.run_ticks(1).unwrap();
+
+    let rejections = [
+        protocol::apply_command(&mut engine, "launch_rocket", &serde_json::json!({})),
+        protocol::apply_command(&mut engine, "resolve_complaint", &serde_json::json!({})),
+        protocol::apply_command(
+            &mut engine,
+            "resolve_complaint",
+            &serde_json::json!({"complaint_id": "cmp-missing"}),
+        ),
+    ];
+    for result in rejections {
+        let err = result.unwrap_err();
+        assert!(matches!(err, SimError::Command { .. }), "{err}");
+        let response = ErrorResponse::from(&err);
+        assert_eq!(response.code.as_deref(), Some("command_error"));
+        assert!(!response.fatal);
+    }
+    engine.run_ticks(1).unwrap();
+}
+
+/// Store failures and unclassified errors are fatal, and the response
+/// says so; a recoverable response omits the flag entirely.
+#[test]
+fn codes_and_severity() {
+    let store = SimError::from(rusqlite::Error::QueryReturnedNoRows);
+    assert_eq!(store.code(), "store_error");
+    assert!(store.is_fatal());
+    assert!(SimError::from(anyhow::anyhow!("boom")).is_fatal());
+    assert!(SimError::generator("customer", "no segments").is_fatal());
+
+    let fatal = serde_json::to_value(ErrorResponse::from(&store)).unwrap();
+    assert_eq!(fatal["fatal"], true);
+    assert_eq!(fatal["code"], "store_error");
+
+    let recoverable = serde_json::to_value(ErrorResponse::from(&SimError::command("pause", "x"))).unwrap();
+    assert!(recoverable.get("fatal").is_none(), "{recoverable}");
+}
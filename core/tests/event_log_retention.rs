@@ -0,0 +1,143 @@
+//! Event log retention tests — Phase 4.28.
+//!
+//! Tests cover: compaction keeping only the trailing window as detailed
+//! rows, reads and counts coming back identical from either tier, the
+//! optional JSON-lines export, a re-compacted tick keeping its entries
+//! in append order, and resume/tournament reads reaching archived ticks.
+
+use fincrime_core::engine::SimEngine;
+use fincrime_core::event::EventLogEntry;
+use fincrime_core::store::SimStore;
+use std::collections::BTreeSet;
+
+/// Ticks past the 15-tick window leave event_log but stay readable.
+#[test]
+fn compaction_keeps_trailing_window_detailed() {
+    let run_id = "retention-window-test";
+    let mut engine = SimEngine::build_test_with_event_log_retention(run_id.into(), 42, 15, None).unwrap();
+    engine.run_ticks(40).unwrap();
+
+    // Compacted at tick 40 through tick 25
+    assert!(!engine.store.archived_events_for_tick(run_id, 25).unwrap().is_empty());
+    assert!(engine.store.archived_events_for_tick(run_id, 26).unwrap().is_empty());
+    let detailed: usize = (26..=40)
+        .map(|tick| engine.store_events_for_tick(run_id, tick).unwrap().len())
+        .sum();
+    assert_eq!(engine.store.detailed_event_count(run_id).unwrap(), detailed as i64);
+    assert!(!engine.store_events_for_tick(run_id, 0).unwrap().is_empty(), "RunInitialized is archived, not lost");
+}
+
+/// Same seed with and without retention: every tick's events and every
+/// event type's range count read back the same.
+#[test]
+fn reads_are_identical_across_tiers() {
+    let mut full = SimEngine::build_test("retention-full-test".into(), 7).unwrap();
+    let mut compacted =
+        SimEngine::build_test_with_event_log_retention("retention-compact-test".into(), 7, 10, None).unwrap();
+    full.run_ticks(35).unwrap();
+    compacted.run_ticks(35).unwrap();
+
+    // Payloads that name the run differ only by run_id
+    let strip = |entries: Vec<EventLogEntry>| -> Vec<(u64, String, String, String)> {
+        entries
+            .into_iter()
+            .map(|e| (e.tick, e.subsystem, e.event_type, e.payload.replace(&e.run_id, "<run>")))
+            .collect()
+    };
+    let mut event_types = BTreeSet::new();
+    for tick in 0..=35 {
+        let expected = strip(full.store_events_for_tick("retention-full-test", tick).unwrap());
+        let actual = strip(compacted.store_events_for_tick("retention-compact-test", tick).unwrap());
+        assert_eq!(actual, expected, "tick {tick}");
+        event_types.extend(expected.into_iter().map(|e| e.2));
+    }
+    for event_type in &event_types {
+        assert_eq!(
+            compacted.store.count_events_in_range("retention-compact-test", 3, 30, event_type).unwrap(),
+            full.store.count_events_in_range("retention-full-test", 3, 30, event_type).unwrap(),
+            "{event_type}",
+        );
+    }
+    assert_eq!(
+        compacted.store.fee_event_count("retention-compact-test").unwrap(),
+        full.store.fee_event_count("retention-full-test").unwrap(),
+    );
+}
+
+/// With an export directory each compacted tick is one JSON line.
+#[test]
+fn export_writes_compacted_ticks_as_json_lines() {
+    let dir = std::env::temp_dir().join(format!("event_export_{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    let run_id = "retention-export-test";
+    let mut engine = SimEngine::build_test_with_event_log_retention(
+        run_id.into(),
+        42,
+        5,
+        Some(dir.to_string_lossy().into_owned()),
+    )
+    .unwrap();
+    engine.run_ticks(20).unwrap();
+
+    let contents = std::fs::read_to_string(dir.join(format!("{run_id}.events.jsonl"))).unwrap();
+    let ticks: Vec<u64> = contents
+        .lines()
+        .map(|line| serde_json::from_str::<serde_json::Value>(line).unwrap()["tick"].as_u64().unwrap())
+        .collect();
+    assert_eq!(ticks, (0..=15).collect::<Vec<_>>(), "Compacted at 10 and 20, once each");
+    let _ = std::fs::remove_dir_all(dir);
+}
+
+/// Events appended to an already archived tick are merged in after the
+/// archived ones on the next pass.
+#[test]
+fn recompacted_tick_keeps_append_order() {
+    let store = SimStore::open(":memory:").unwrap();
+    store.migrate().unwrap();
+    store.insert_run("retention-store-test", 1, "0.1.0-test").unwrap();
+    let append = |event_type: &str| {
+        store
+            .append_event(&EventLogEntry {
+                id: None,
+                run_id: "retention-store-test".into(),
+                tick: 3,
+                subsystem: "test".into(),
+                event_type: event_type.into(),
+                payload: "{}".into(),
+            })
+            .unwrap();
+    };
+
+    append("fee_charged");
+    append("first");
+    let summary = store.compact_event_log("retention-store-test", 3, None).unwrap();
+    assert_eq!((summary.ticks, summary.events), (1, 2));
+    append("fee_charged");
+    store.compact_event_log("retention-store-test", 3, None).unwrap();
+
+    let types: Vec<String> = store
+        .events_for_tick("retention-store-test", 3)
+        .unwrap()
+        .into_iter()
+        .map(|e| e.event_type)
+        .collect();
+    assert_eq!(types, ["fee_charged", "first", "fee_charged"]);
+    assert_eq!(store.fee_event_count("retention-store-test").unwrap(), 2);
+    assert_eq!(store.detailed_event_count("retention-store-test").unwrap(), 0);
+}
+
+/// Resume and tournament reads see ticks that only the archive holds.
+#[test]
+fn logged_event_reads_reach_the_archive() {
+    let run_id = "retention-resume-test";
+    let mut engine = SimEngine::build_test_with_event_log_retention(run_id.into(), 42, 1_000, None).unwrap();
+    engine.run_ticks(12).unwrap();
+    let last_tick = engine.store.last_logged_tick(run_id).unwrap();
+    let fees = engine.store.logged_event_payloads(run_id, "fee_charged").unwrap();
+    assert!(!fees.is_empty());
+
+    engine.store.compact_event_log(run_id, 12, None).unwrap();
+    assert_eq!(engine.store.detailed_event_count(run_id).unwrap(), 0);
+    assert_eq!(engine.store.last_logged_tick(run_id).unwrap(), last_tick);
+    assert_eq!(engine.store.logged_event_payloads(run_id, "fee_charged").unwrap(), fees);
+}
@@ -0,0 +1,150 @@
+//! Streaming event export tests — Phase 4.57.
+//!
+//! Tests cover: every logged event appended as a JSON line in log order,
+//! each tick's lines flushed by the time it completes, rotation by size
+//! keeping only the newest files, a reopened stream appending, and a
+//! named pipe being streamed to without rotation.
+
+use fincrime_core::engine::SimEngine;
+use fincrime_core::event::EventLogEntry;
+use std::path::{Path, PathBuf};
+
+/// A fresh scratch directory for one test.
+fn scratch(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("event_stream_{name}_{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+fn engine(run_id: &str, path: &Path, rotate_bytes: Option<u64>) -> SimEngine {
+    SimEngine::build_test_with_event_stream(run_id.into(), 42, path.to_str().unwrap(), rotate_bytes).unwrap()
+}
+
+fn read_lines(path: &Path) -> Vec<EventLogEntry> {
+    std::fs::read_to_string(path)
+        .unwrap()
+        .lines()
+        .map(|l| serde_json::from_str(l).unwrap_or_else(|e| panic!("{e}: {l}")))
+        .collect()
+}
+
+/// (tick, subsystem, event type, payload) of every logged event.
+fn logged(engine: &SimEngine) -> Vec<(u64, String, String, String)> {
+    (0..=engine.clock.current_tick)
+        .flat_map(|tick| engine.store.events_for_tick(&engine.run_id, tick).unwrap())
+        .map(key)
+        .collect()
+}
+
+fn key(e: EventLogEntry) -> (u64, String, String, String) {
+    (e.tick, e.subsystem, e.event_type, e.payload)
+}
+
+/// The stream holds exactly what event_log does, in the same order.
+#[test]
+fn every_logged_event_streamed_in_order() {
+    let dir = scratch("order");
+    let path = dir.join("events.jsonl");
+    let mut engine = engine("stream-order-test", &path, None);
+    engine.run_ticks(10).unwrap();
+
+    let streamed: Vec<_> = read_lines(&path).into_iter().map(key).collect();
+    assert_eq!(streamed, logged(&engine));
+    assert_eq!(streamed[0].2, "run_initialized");
+    let _ = std::fs::remove_dir_all(dir);
+}
+
+/// After each batch of ticks the file already ends with the last tick's
+/// events, and earlier lines are never rewritten.
+#[test]
+fn ticks_flushed_as_they_complete() {
+    let dir = scratch("flush");
+    let path = dir.join("events.jsonl");
+    let mut engine = engine("stream-flush-test", &path, None);
+    engine.run_ticks(3).unwrap();
+    let first = std::fs::read_to_string(&path).unwrap();
+    assert_eq!(read_lines(&path).len(), logged(&engine).len());
+    assert_eq!(read_lines(&path).last().unwrap().tick, 3);
+
+    engine.run_ticks(2).unwrap();
+    let second = std::fs::read_to_string(&path).unwrap();
+    assert!(second.starts_with(&first) && second.len() > first.len());
+    assert_eq!(read_lines(&path).last().unwrap().tick, 5);
+    let _ = std::fs::remove_dir_all(dir);
+}
+
+/// Past the size limit the file rolls to `.1`, `.1` to `.2`, and the
+/// oldest beyond two is dropped; what is left is the tail of the log.
+#[test]
+fn rotation_by_size_keeps_newest_files() {
+    let dir = scratch("rotate");
+    let path = dir.join("events.jsonl");
+    let mut engine = engine("stream-rotate-test", &path, Some(30_000));
+    engine.run_ticks(10).unwrap();
+
+    let rotated = |n: u32| PathBuf::from(format!("{}.{n}", path.display()));
+    assert!(rotated(1).exists() && rotated(2).exists());
+    assert!(!rotated(3).exists());
+    for file in [rotated(2), rotated(1), path.clone()] {
+        assert!(std::fs::metadata(&file).unwrap().len() <= 30_000, "{}", file.display());
+    }
+
+    let kept: Vec<_> = [rotated(2), rotated(1), path.clone()]
+        .iter()
+        .flat_map(|file| read_lines(file))
+        .map(key)
+        .collect();
+    let all = logged(&engine);
+    assert!(kept.len() < all.len());
+    assert_eq!(kept, all[all.len() - kept.len()..]);
+    let _ = std::fs::remove_dir_all(dir);
+}
+
+/// A second run streaming to the same file appends after the first.
+#[test]
+fn reopened_stream_appends() {
+    let dir = scratch("append");
+    let path = dir.join("events.jsonl");
+    let mut first = engine("stream-append-first-test", &path, None);
+    first.run_ticks(2).unwrap();
+    let mut second = engine("stream-append-second-test", &path, None);
+    second.run_ticks(2).unwrap();
+
+    let runs: Vec<String> = read_lines(&path).into_iter().map(|e| e.run_id).collect();
+    let split = runs.iter().position(|r| r == "stream-append-second-test").unwrap();
+    assert_eq!(split, logged(&first).len());
+    assert!(runs[..split].iter().all(|r| r == "stream-append-first-test"));
+    assert!(runs[split..].iter().all(|r| r == "stream-append-second-test"));
+    let _ = std::fs::remove_dir_all(dir);
+}
+
+/// A reader on a named pipe gets every event; the pipe is never rotated
+/// however small the limit.
+#[cfg(unix)]
+#[test]
+fn named_pipe_streamed_without_rotation() {
+    let dir = scratch("pipe");
+    let path = dir.join("events.pipe");
+    let status = std::process::Command::new("mkfifo").arg(&path).status().unwrap();
+    assert!(status.success());
+
+    let reader = {
+        let path = path.clone();
+        std::thread::spawn(move || std::fs::read_to_string(path).unwrap())
+    };
+    let mut engine = engine("stream-pipe-test", &path, Some(1_000));
+    engine.run_ticks(3).unwrap();
+    let logged = logged(&engine);
+    drop(engine);
+
+    let streamed: Vec<_> = reader
+        .join()
+        .unwrap()
+        .lines()
+        .map(|l| key(serde_json::from_str(l).unwrap()))
+        .collect();
+    assert_eq!(streamed, logged);
+    assert!(!PathBuf::from(format!("{}.1", path.display())).exists());
+    let _ = std::fs::remove_dir_all(dir);
+}
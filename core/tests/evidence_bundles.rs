@@ -0,0 +1,182 @@
+//! Evidence bundle tests — Phase 4.59.
+//!
+//! Tests cover: a SAR's bundle collecting the customer's activity, KYC
+//! record, alerts and prior SARs; a dispute's bundle following the
+//! disputed account and its documents; a filing the activity does not
+//! support being deficient and cited at the next exam; and bundles
+//! staying off unless enabled.
+
+use fincrime_core::{
+    engine::SimEngine,
+    entity_timeline::{EntityType, TimelineKind},
+    event::SimEvent,
+    evidence_bundle::EvidenceBundle,
+    transaction_monitoring_subsystem::AMLAlert,
+};
+
+/// An evidence bundle run with structuring alerts raised on the first
+/// customer at the given ticks for the given amounts, run to `ticks`;
+/// returns the customer.
+fn alerted_run(run_id: &str, alerts: &[(u64, f64)], ticks: u64) -> (SimEngine, String) {
+    let mut engine = SimEngine::build_test_with_evidence_bundles(run_id.into(), 42, 0.0).unwrap();
+    engine.run_ticks(1).unwrap();
+    let customer_id = engine.store.active_customers(run_id).unwrap()[0].customer_id.clone();
+    for (n, &(tick, amount)) in alerts.iter().enumerate() {
+        engine.run_ticks(tick - engine.clock.current_tick).unwrap();
+        engine
+            .store
+            .insert_transaction_monitoring_alert(&AMLAlert {
+                alert_id: format!("A{n}"),
+                run_id: run_id.into(),
+                customer_id: customer_id.clone(),
+                tick,
+                rule_id: "TEST".into(),
+                alert_type: "structuring".into(),
+                alert_score: 90.0,
+                description: "test alert".into(),
+                triggered_amount: Some(amount),
+                transaction_count: Some(3),
+                status: "open".into(),
+            })
+            .unwrap();
+    }
+    engine.run_ticks(ticks - engine.clock.current_tick).unwrap();
+    (engine, customer_id)
+}
+
+/// The bundles of the SARs filed on the customer, in filing order.
+fn sar_bundles(engine: &SimEngine, customer_id: &str) -> Vec<EvidenceBundle> {
+    engine
+        .get_entity_timeline(EntityType::Customer, customer_id)
+        .unwrap()
+        .entries
+        .into_iter()
+        .filter(|e| e.source == "suspicious_activity_report")
+        .map(|e| engine.store.evidence_bundle_for_filing(&engine.run_id, "sar", &e.ref_id).unwrap().unwrap())
+        .collect()
+}
+
+/// A SAR's bundle holds the customer's activity from the lookback before
+/// the alert to filing, largest first, with their KYC record and the
+/// alert; the second SAR on a customer lists the first.
+#[test]
+fn sar_bundle_collects_activity_kyc_and_prior_sars() {
+    let (engine, customer_id) = alerted_run("evidence-sar-test", &[(1, 100.0), (12, 100.0)], 25);
+    let bundles = sar_bundles(&engine, &customer_id);
+    assert_eq!(bundles.len(), 2);
+    let (first, second) = (&bundles[0], &bundles[1]);
+
+    let timeline = engine
+        .get_entity_timeline(EntityType::Customer, &customer_id)
+        .unwrap()
+        .entries;
+    let in_window: Vec<_> = timeline
+        .iter()
+        .filter(|e| e.kind == TimelineKind::Transaction && (first.window_start..=first.tick).contains(&e.tick))
+        .collect();
+    assert_eq!((first.source_id.as_str(), first.window_start), ("A0", 0));
+    assert_eq!(first.transaction_count, in_window.len());
+    let total = in_window.iter().fold(0.0, |sum, e| sum + e.amount.unwrap());
+    assert!((first.transaction_total - total).abs() < 1e-6);
+    assert!(first.transactions.len() <= 25);
+    assert!(first.transactions.iter().all(|t| in_window.iter().any(|e| e.ref_id == t.ref_id)));
+    assert!(first.transactions.windows(2).all(|w| w[0].amount >= w[1].amount));
+
+    let kyc = first.kyc.as_ref().unwrap();
+    assert_eq!(kyc.segment, engine.store.active_customers(&engine.run_id).unwrap()[0].segment);
+    assert!(first.alerts.iter().any(|a| a.ref_id == "A0"));
+    assert_eq!((first.quality, first.gaps.is_empty()), (1.0, true));
+
+    assert!(first.prior_sars.is_empty());
+    assert_eq!(second.prior_sars.len(), 1);
+    assert_eq!(second.prior_sars[0].sar_id, first.filing_id);
+    assert!(second.alerts.iter().any(|a| a.ref_id == "A1"));
+}
+
+/// Each dispute is filed with a bundle of its account's activity and the
+/// case's documents; a document never collected is a gap.
+#[test]
+fn dispute_bundle_follows_account_and_documents() {
+    let run_id = "evidence-dispute-test";
+    let mut engine = SimEngine::build_test_with_evidence_bundles(run_id.into(), 42, 0.2).unwrap();
+    engine.run_ticks(60).unwrap();
+
+    let disputes = engine.store.documented_cases(run_id, "dispute").unwrap();
+    assert!(!disputes.is_empty());
+    let mut gaps = 0;
+    for dispute_id in &disputes {
+        let dispute = engine.store.get_dispute(run_id, dispute_id).unwrap();
+        let bundle = engine.store.evidence_bundle_for_filing(run_id, "dispute", dispute_id).unwrap().unwrap();
+        assert_eq!(bundle.account_id.as_ref(), Some(&dispute.account_id));
+        assert_eq!((bundle.customer_id.as_str(), bundle.tick as i64), (dispute.customer_id.as_str(), dispute.tick_filed));
+        assert!(bundle.transactions.iter().all(|t| t.account_id.as_ref() == Some(&dispute.account_id)));
+
+        let documents = engine.store.case_documents(run_id, "dispute", dispute_id).unwrap();
+        assert!(documents.iter().all(|d| bundle.notes.iter().any(|n| n.ref_id == d.document_id)));
+        let missing = documents.iter().any(|d| d.status == "missing");
+        assert_eq!(bundle.gaps.contains(&"documents_missing".to_string()), missing, "{dispute_id}");
+        gaps += missing as usize;
+    }
+    assert!(gaps > 0);
+}
+
+/// A SAR reporting far more than the customer moved is deficient, and
+/// the exam closing at tick 26 cites it; one the activity supports is not.
+#[test]
+fn unsupported_filing_is_deficient_and_cited() {
+    let (deficient, customer_id) = alerted_run("evidence-deficient-test", &[(1, 1.0e9)], 27);
+    let (supported, _) = alerted_run("evidence-supported-test", &[(1, 100.0)], 27);
+
+    let bundle = &sar_bundles(&deficient, &customer_id)[0];
+    assert_eq!(bundle.gaps, vec!["amount_unsupported".to_string()]);
+    assert!((bundle.quality - 2.0 / 3.0).abs() < 1e-9);
+    assert_eq!(deficient.store.evidence_bundle_counts(&deficient.run_id, 0, 26).unwrap().1, 1);
+    assert_eq!(
+        deficient.store.exam_findings_in_category(&deficient.run_id, "filing_quality").unwrap(),
+        vec![("moderate".to_string(), 50_000.0)]
+    );
+    assert!(supported
+        .store
+        .exam_findings_in_category(&supported.run_id, "filing_quality")
+        .unwrap()
+        .is_empty());
+}
+
+/// Every bundle is announced with the quality and gaps it was kept with.
+#[test]
+fn bundle_events_match_the_kept_bundles() {
+    let run_id = "evidence-events-test";
+    let mut engine = SimEngine::build_test_with_evidence_bundles(run_id.into(), 42, 0.2).unwrap();
+    engine.run_ticks(45).unwrap();
+    let announced: Vec<_> = (1..=45)
+        .flat_map(|tick| engine.store_events_for_tick(run_id, tick).unwrap())
+        .filter_map(|e| match serde_json::from_str::<SimEvent>(&e.payload).ok()? {
+            SimEvent::EvidenceBundleAssembled { filing_type, filing_id, quality, gaps, .. } => {
+                Some((filing_type, filing_id, quality, gaps))
+            }
+            _ => None,
+        })
+        .collect();
+
+    assert!(!announced.is_empty());
+    for (filing_type, filing_id, quality, gaps) in &announced {
+        let bundle = engine.store.evidence_bundle_for_filing(run_id, filing_type, filing_id).unwrap().unwrap();
+        assert_eq!((bundle.quality, &bundle.gaps), (*quality, gaps));
+    }
+    assert_eq!(engine.store.evidence_bundle_counts(run_id, 0, 45).unwrap().0, announced.len() as i64);
+}
+
+/// The test default keeps no bundles.
+#[test]
+fn evidence_bundles_are_opt_in() {
+    let run_id = "evidence-off-test";
+    let mut engine = SimEngine::build_test_with_case_documents(run_id.into(), 42, 0.2).unwrap();
+    engine.run_ticks(45).unwrap();
+
+    assert!(!engine.store.documented_cases(run_id, "dispute").unwrap().is_empty());
+    assert_eq!(engine.store.evidence_bundle_counts(run_id, 0, 45).unwrap(), (0, 0));
+    assert_eq!(
+        engine.store.count_events_in_range(run_id, 0, 45, "evidence_bundle_assembled").unwrap(),
+        0
+    );
+}
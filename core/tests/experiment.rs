@@ -0,0 +1,112 @@
+//! Monte Carlo experiment API tests — Phase 4.20.
+//!
+//! Tests cover: the tidy frame's shape and ordering across scenarios and
+//! seeds, runs staying deterministic per seed whichever thread runs them,
+//! scenario commands reaching the engine, and a failing run being
+//! recorded without stopping the rest.
+
+use fincrime_core::command::PlayerCommand;
+use fincrime_core::config::SimConfig;
+use fincrime_core::engine::SimEngine;
+use fincrime_core::error::SimResult;
+use fincrime_core::experiment::{Experiment, Metrics, Scenario};
+
+const TICKS: u64 = 8;
+
+fn customers_and_txns(engine: &SimEngine) -> SimResult<Metrics> {
+    Ok(vec![
+        ("active_customers".into(), engine.store.customer_count(&engine.run_id, "active")? as f64),
+        ("transactions".into(), engine.store.txn_count_total(&engine.run_id)? as f64),
+    ])
+}
+
+fn baseline() -> Scenario {
+    Scenario::new("baseline", SimConfig::default_test(), TICKS)
+}
+
+/// Two scenarios by three seeds by two metrics come back as twelve rows
+/// in scenario, seed and metric order, with a summary per pair.
+#[test]
+fn frame_is_tidy_and_ordered() {
+    let mut takeovers = SimConfig::default_test();
+    takeovers.ach_origination.enabled = true;
+    takeovers.ach_origination.takeover_daily_rate = 0.01;
+    let frame = Experiment::new(customers_and_txns)
+        .scenario(baseline())
+        .scenario(Scenario::new("takeovers", takeovers, TICKS))
+        .seeds([3, 1, 2])
+        .threads(3)
+        .run();
+
+    assert!(frame.failures.is_empty());
+    assert_eq!(frame.rows.len(), 12);
+    let keys: Vec<_> = frame.rows.iter().map(|r| (r.scenario.as_str(), r.seed)).collect();
+    assert_eq!(keys[0], ("baseline", 1));
+    assert_eq!(keys[6], ("takeovers", 1));
+    assert_eq!(keys[11], ("takeovers", 3));
+    assert_eq!(frame.rows[0].metric, "active_customers");
+    assert_eq!(frame.rows[1].metric, "transactions");
+
+    let summary = frame.summarize();
+    assert_eq!(summary.len(), 4);
+    assert!(summary.iter().all(|s| s.runs == 3 && s.min <= s.mean && s.mean <= s.max));
+    assert!(frame.to_csv().starts_with("scenario,seed,metric,value\nbaseline,1,active_customers,"));
+}
+
+/// The same seed gives the same metrics on one thread or four, and
+/// matches a test engine run directly.
+#[test]
+fn runs_are_deterministic_per_seed() {
+    let serial = Experiment::new(customers_and_txns).scenario(baseline()).seeds([7, 7, 8]).threads(1).run();
+    let parallel = Experiment::new(customers_and_txns).scenario(baseline()).seeds([7, 7, 8]).threads(4).run();
+    assert_eq!(serial.rows, parallel.rows);
+    assert_eq!(serial.values("baseline", "transactions")[0], serial.values("baseline", "transactions")[1]);
+
+    let mut engine = SimEngine::build_test("experiment-direct".into(), 7).unwrap();
+    engine.run_ticks(TICKS).unwrap();
+    let direct = engine.store.txn_count_total("experiment-direct").unwrap() as f64;
+    assert_eq!(serial.values("baseline", "transactions")[0], direct);
+}
+
+/// Commands in the scenario take effect on their tick.
+#[test]
+fn scenario_commands_reach_the_engine() {
+    let mut config = SimConfig::default_test();
+    config.sar_decision.enabled = true;
+    let scenario = Scenario::new("sar-dial", config, TICKS)
+        .command(3, PlayerCommand::SetSarThreshold { threshold: 60.0 });
+    let frame = Experiment::new(|engine: &SimEngine| {
+        let changed = (0..=TICKS)
+            .flat_map(|t| engine.store_events_for_tick(&engine.run_id, t).unwrap())
+            .filter(|e| e.event_type == "sar_threshold_changed")
+            .map(|e| e.tick as f64)
+            .collect::<Vec<_>>();
+        Ok(vec![("changed_at".into(), changed.first().copied().unwrap_or(-1.0))])
+    })
+    .scenario(scenario)
+    .seeds([1])
+    .run();
+
+    assert_eq!(frame.values("sar-dial", "changed_at"), vec![3.0]);
+}
+
+/// A run whose metric extraction fails is listed with its error; the
+/// other seeds still produce rows.
+#[test]
+fn failing_run_is_recorded() {
+    let frame = Experiment::new(|engine: &SimEngine| {
+        if engine.run_id.ends_with("-2") {
+            return Err(fincrime_core::error::SimError::RunNotInitialized);
+        }
+        customers_and_txns(engine)
+    })
+    .scenario(baseline())
+    .seeds([1, 2, 3])
+    .threads(2)
+    .run();
+
+    assert_eq!(frame.failures.len(), 1);
+    assert_eq!(frame.failures[0].seed, 2);
+    assert_eq!(frame.failures[0].error, "Run not initialized");
+    assert_eq!(frame.values("baseline", "transactions").len(), 2);
+}
@@ -0,0 +1,186 @@
+//! Run data export tests — Phase 4.79.
+//!
+//! Tests cover: a CSV export holding every one of the run's rows under a
+//! header of the table's columns; event payloads, which are full of
+//! commas and quotes, surviving the trip as valid JSON; archived event
+//! log ticks exported ahead of the detailed rows; a missing table,
+//! or one with no run to export by, refused; `export_run` writing a file
+//! per export table, empty ones included; and Parquet keeping the rows
+//! and column types when built with it, and being refused up front
+//! when not.
+
+use fincrime_core::{
+    engine::SimEngine,
+    export::{self, ColumnKind, ExportFormat, EXPORT_TABLES},
+};
+use std::path::PathBuf;
+
+fn temp_path(name: &str) -> PathBuf {
+    std::env::temp_dir().join(format!("export_{name}_{}", std::process::id()))
+}
+
+fn run(run_id: &str) -> SimEngine {
+    run_ticks(run_id, 5)
+}
+
+fn run_ticks(run_id: &str, ticks: u64) -> SimEngine {
+    let mut engine = SimEngine::build_test(run_id.into(), 42).unwrap();
+    engine.run_ticks(ticks).unwrap();
+    engine
+}
+
+/// Splits one CSV record, undoing RFC 4180 quoting.
+fn fields(line: &str) -> Vec<String> {
+    let (mut fields, mut field, mut quoted) = (Vec::new(), String::new(), false);
+    let mut chars = line.chars().peekable();
+    while let Some(c) = chars.next() {
+        match (c, quoted) {
+            ('"', true) if chars.peek() == Some(&'"') => {
+                field.push('"');
+                chars.next();
+            }
+            ('"', _) => quoted = !quoted,
+            (',', false) => fields.push(std::mem::take(&mut field)),
+            _ => field.push(c),
+        }
+    }
+    fields.push(field);
+    fields
+}
+
+/// Every transaction of the run comes out, one line each, under the
+/// table's own column names.
+#[test]
+fn csv_export_holds_every_row() {
+    let engine = run("export-csv-test");
+    let path = temp_path("txns.csv");
+    let rows = engine.store.export_table("export-csv-test", "transactions", ExportFormat::Csv, &path).unwrap();
+    assert_eq!(rows as i64, engine.store.txn_count_total("export-csv-test").unwrap());
+    assert!(rows > 0);
+
+    let csv = std::fs::read_to_string(&path).unwrap();
+    let mut lines = csv.lines();
+    let header = fields(lines.next().unwrap());
+    assert_eq!(header[..4], ["txn_id", "run_id", "account_id", "tick"]);
+    assert_eq!(lines.count(), rows);
+    std::fs::remove_file(&path).unwrap();
+}
+
+/// Event payloads are JSON full of commas and quotes; quoting keeps
+/// each one a single field that parses back.
+#[test]
+fn event_payloads_survive_quoting() {
+    let engine = run("export-events-test");
+    let path = temp_path("events.csv");
+    let rows = engine.store.export_table("export-events-test", "event_log", ExportFormat::Csv, &path).unwrap();
+    assert_eq!(rows as i64, engine.store.detailed_event_count("export-events-test").unwrap());
+
+    let csv = std::fs::read_to_string(&path).unwrap();
+    let mut lines = csv.lines();
+    let header = fields(lines.next().unwrap());
+    let payload = header.iter().position(|c| c == "payload").unwrap();
+    for line in lines {
+        let record = fields(line);
+        assert_eq!(record.len(), header.len(), "{line}");
+        serde_json::from_str::<serde_json::Value>(&record[payload]).unwrap();
+    }
+    std::fs::remove_file(&path).unwrap();
+}
+
+/// A run with compacted ticks exports the archived events too, oldest
+/// tick first, as many as a run that kept every row detailed.
+#[test]
+fn event_log_export_includes_archived_ticks() {
+    let mut compacted =
+        SimEngine::build_test_with_event_log_retention("export-archive-test".into(), 42, 5, None).unwrap();
+    compacted.run_ticks(20).unwrap();
+    let full = run_ticks("export-detailed-test", 20);
+    let detailed = full.store.detailed_event_count("export-detailed-test").unwrap();
+    assert!(compacted.store.detailed_event_count("export-archive-test").unwrap() < detailed);
+
+    let path = temp_path("archived_events.csv");
+    let rows = compacted.store.export_table("export-archive-test", "event_log", ExportFormat::Csv, &path).unwrap();
+    assert_eq!(rows as i64, detailed);
+
+    let csv = std::fs::read_to_string(&path).unwrap();
+    let mut lines = csv.lines();
+    let header = fields(lines.next().unwrap());
+    let tick = header.iter().position(|c| c == "tick").unwrap();
+    let ticks: Vec<u64> = lines.map(|line| fields(line)[tick].parse().unwrap()).collect();
+    assert_eq!(ticks.first(), Some(&0));
+    assert!(ticks.windows(2).all(|w| w[0] <= w[1]), "Archived ticks come out ahead of detailed ones");
+    std::fs::remove_file(&path).unwrap();
+}
+
+/// A table that does not exist, or has no run_id to pick the run's rows
+/// by, is refused before any file is written.
+#[test]
+fn tables_without_a_run_are_refused() {
+    let engine = run("export-refused-test");
+    let path = temp_path("refused.csv");
+    let err = engine.store.export_table("export-refused-test", "nope", ExportFormat::Csv, &path).unwrap_err();
+    assert!(err.to_string().contains("no table 'nope'"), "{err}");
+    let err = engine.store.export_table("export-refused-test", "sqlite_sequence", ExportFormat::Csv, &path).unwrap_err();
+    assert!(err.to_string().contains("no run_id column"), "{err}");
+    assert!(!path.exists());
+
+    assert_eq!(ColumnKind::from_declared("INTEGER"), ColumnKind::Integer);
+    assert_eq!(ColumnKind::from_declared("REAL"), ColumnKind::Real);
+    assert_eq!(ColumnKind::from_declared("VARCHAR(20)"), ColumnKind::Text);
+    assert_eq!(ColumnKind::from_declared(""), ColumnKind::Text);
+}
+
+/// `export_run` writes every export table, including those the run has
+/// not reached yet, which get a header and nothing else.
+#[test]
+fn export_run_writes_every_table() {
+    let engine = run("export-run-test");
+    let dir = temp_path("run");
+    let tables = export::export_run(&engine.store, "export-run-test", ExportFormat::Csv, &dir).unwrap();
+    let names: Vec<&str> = tables.iter().map(|t| t.table.as_str()).collect();
+    assert_eq!(names, EXPORT_TABLES);
+    for table in &tables {
+        assert_eq!(table.path, dir.join(format!("export-run-test.{}.csv", table.table)));
+        let lines = std::fs::read_to_string(&table.path).unwrap().lines().count();
+        assert_eq!(lines, table.rows + 1, "{}", table.table);
+    }
+    let pnl = tables.iter().find(|t| t.table == "pnl_snapshot").unwrap();
+    assert_eq!(pnl.rows, 0, "no quarter has closed after 5 ticks");
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+/// Parquet keeps every row and the declared column types.
+#[cfg(feature = "parquet")]
+#[test]
+fn parquet_keeps_rows_and_types() {
+    use parquet::{
+        basic::Type,
+        file::reader::{FileReader, SerializedFileReader},
+    };
+
+    assert_eq!("parquet".parse::<ExportFormat>().unwrap(), ExportFormat::Parquet);
+    let engine = run("export-parquet-test");
+    let path = temp_path("txns.parquet");
+    let rows = engine.store.export_table("export-parquet-test", "transactions", ExportFormat::Parquet, &path).unwrap();
+
+    let reader = SerializedFileReader::new(std::fs::File::open(&path).unwrap()).unwrap();
+    let metadata = reader.metadata();
+    assert_eq!(metadata.file_metadata().num_rows() as usize, rows);
+    let schema = metadata.file_metadata().schema_descr();
+    let physical = |name: &str| {
+        (0..schema.num_columns()).map(|i| schema.column(i)).find(|c| c.name() == name).unwrap().physical_type()
+    };
+    assert_eq!(physical("tick"), Type::INT64);
+    assert_eq!(physical("amount"), Type::DOUBLE);
+    assert_eq!(physical("txn_id"), Type::BYTE_ARRAY);
+    std::fs::remove_file(&path).unwrap();
+}
+
+/// Without the feature, asking for Parquet fails before any run.
+#[cfg(not(feature = "parquet"))]
+#[test]
+fn parquet_needs_the_feature() {
+    let err = "parquet".parse::<ExportFormat>().unwrap_err();
+    assert!(err.to_string().contains("--features parquet"), "{err}");
+    assert!("xlsx".parse::<ExportFormat>().unwrap_err().to_string().contains("unknown export format"));
+}
@@ -0,0 +1,148 @@
+//! External account linking tests — Phase 4.11.
+//!
+//! Tests cover: micro-deposit verification completing after the deposit
+//! delay, external transfers only to verified links, direct deposit
+//! switches bringing payroll in, mule links draining accounts within the
+//! velocity limits, and the strictness dial cutting mule egress.
+
+use fincrime_core::command::PlayerCommand;
+use fincrime_core::engine::SimEngine;
+
+fn build_with_links(run_id: &str, seed: u64) -> SimEngine {
+    SimEngine::build_test_with_external_links(run_id.to_string(), seed)
+        .expect("build test engine with external links")
+}
+
+/// Total drained into mule accounts over a run.
+fn mule_egress(engine: &SimEngine, run_id: &str) -> f64 {
+    engine
+        .store_originated_ach_entries(run_id)
+        .unwrap()
+        .iter()
+        .filter(|e| e.entry_type == "external_transfer_out" && e.status == "submitted")
+        .map(|e| e.amount)
+        .sum()
+}
+
+/// With full strictness every link goes through micro-deposits, resolves
+/// after the deposit delay, and transfers only go to verified links.
+#[test]
+fn micro_deposit_links_verify_after_delay() {
+    let run_id = "external-links-micro-deposit-test";
+    let mut engine = build_with_links(run_id, 0x11AC_0001);
+
+    engine
+        .submit_command(PlayerCommand::SetLinkVerificationStrictness { strictness: 1.0 })
+        .unwrap();
+    engine.run_ticks(20).unwrap();
+
+    let links = engine.store_external_links(run_id).unwrap();
+    let resolved: Vec<_> = links.iter().filter(|l| l.status != "pending").collect();
+    assert!(resolved.iter().any(|l| l.status == "verified"));
+    assert!(resolved.iter().any(|l| l.status == "failed" && !l.fraudulent));
+    for link in links.iter().filter(|l| l.requested_tick > 1) {
+        assert_eq!(link.method, "micro_deposit");
+    }
+    for link in &resolved {
+        assert_eq!(link.resolved_tick, Some(link.requested_tick + 2));
+    }
+
+    let verified_refs: std::collections::HashSet<_> = links
+        .iter()
+        .filter(|l| l.purpose == "transfer" && l.status == "verified")
+        .map(|l| (l.account_id.clone(), l.external_ref.clone()))
+        .collect();
+    let transfers: Vec<_> = engine
+        .store_originated_ach_entries(run_id)
+        .unwrap()
+        .into_iter()
+        .filter(|e| e.entry_type == "external_transfer")
+        .collect();
+    assert!(!transfers.is_empty());
+    for entry in transfers {
+        assert!(verified_refs.contains(&(entry.account_id.clone(), entry.counterparty.clone())));
+    }
+}
+
+/// A verified direct deposit link moves the customer's payroll here.
+#[test]
+fn direct_deposit_switch_brings_payroll() {
+    let run_id = "external-links-dd-switch-test";
+    let mut engine = build_with_links(run_id, 0x11AC_0002);
+
+    engine.run_ticks(15).unwrap();
+
+    let switched: Vec<_> = engine
+        .store_external_links(run_id)
+        .unwrap()
+        .into_iter()
+        .filter(|l| l.purpose == "direct_deposit" && l.status == "verified")
+        .collect();
+    assert!(!switched.is_empty());
+    let payroll_accounts: std::collections::HashSet<_> = engine
+        .store
+        .active_accounts(run_id)
+        .unwrap()
+        .into_iter()
+        .filter(|a| a.has_payroll)
+        .map(|a| a.account_id)
+        .collect();
+    for link in &switched {
+        assert!(payroll_accounts.contains(&link.account_id), "{} not switched", link.account_id);
+    }
+}
+
+/// Verified mule links drain the account for the drain window, never more
+/// than the daily amount limit at a time.
+#[test]
+fn mule_links_drain_within_limits() {
+    let run_id = "external-links-mule-test";
+    let mut engine = build_with_links(run_id, 0x11AC_0003);
+
+    engine
+        .submit_command(PlayerCommand::SetLinkVerificationStrictness { strictness: 0.0 })
+        .unwrap();
+    engine.run_ticks(20).unwrap();
+
+    let mules: Vec<_> = engine
+        .store_external_links(run_id)
+        .unwrap()
+        .into_iter()
+        .filter(|l| l.purpose == "mule" && l.status == "verified")
+        .collect();
+    assert!(!mules.is_empty());
+
+    let drains: Vec<_> = engine
+        .store_originated_ach_entries(run_id)
+        .unwrap()
+        .into_iter()
+        .filter(|e| e.entry_type == "external_transfer_out")
+        .collect();
+    assert!(!drains.is_empty());
+    for entry in &drains {
+        assert!(entry.unauthorized);
+        assert!(entry.amount <= 5_000.0);
+        let link = mules
+            .iter()
+            .find(|l| l.account_id == entry.account_id && l.external_ref == entry.counterparty)
+            .expect("drain goes to a verified mule link");
+        let verified = link.resolved_tick.unwrap();
+        assert!(entry.tick >= verified && entry.tick < verified + 3);
+    }
+}
+
+/// Micro-deposit verification stops most mule links, cutting egress.
+#[test]
+fn strict_verification_cuts_mule_egress() {
+    let mut egress = Vec::new();
+    for (run_id, strictness) in [("external-links-lax-test", 0.0), ("external-links-strict-test", 1.0)] {
+        let mut engine = build_with_links(run_id, 0x11AC_0004);
+        engine
+            .submit_command(PlayerCommand::SetLinkVerificationStrictness { strictness })
+            .unwrap();
+        engine.run_ticks(30).unwrap();
+        egress.push(mule_egress(&engine, run_id));
+    }
+    assert!(egress[0] > 0.0);
+    assert!(egress[1] < egress[0] * 0.5, "Strict {:.0} vs lax {:.0}", egress[1], egress[0]);
+}
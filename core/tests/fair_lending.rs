@@ -0,0 +1,91 @@
+//! Fair lending subsystem tests — Phase 4.4.
+//!
+//! Tests cover: zip demographics generation, the periodic disparity
+//! review, player responses to findings, escalation of unanswered findings.
+
+use fincrime_core::{command::PlayerCommand, engine::SimEngine};
+
+fn build_with_fair_lending(run_id: &str, seed: u64) -> SimEngine {
+    SimEngine::build_test_with_fair_lending(run_id.to_string(), seed)
+        .expect("build test engine with fair lending")
+}
+
+/// Every zip in the customer book gets a demographics row on tick 1.
+#[test]
+fn zip_demographics_generated() {
+    let run_id = "fair-lending-demographics-test";
+    let mut engine = build_with_fair_lending(run_id, 0xFA1E_0001);
+
+    engine.run_ticks(1).unwrap();
+
+    let zips = engine.store_zip_demographics_count(run_id).unwrap();
+    assert!(zips > 0, "Expected demographics for customer zips");
+    assert!(zips <= 50, "At most one zip per test customer, got {zips}");
+}
+
+/// The monthly review records each metric and raises findings over threshold.
+#[test]
+fn review_records_metrics_and_raises_findings() {
+    let run_id = "fair-lending-review-test";
+    let mut engine = build_with_fair_lending(run_id, 0xFA1E_0002);
+
+    engine.run_ticks(30).unwrap();
+
+    let reviews = engine.store_fair_lending_review_count(run_id).unwrap();
+    assert!(reviews > 0, "Expected review rows after the first review period");
+
+    let findings = engine.store_open_fair_lending_findings(run_id).unwrap();
+    assert!(!findings.is_empty(), "Expected at least one finding at the test threshold");
+    assert!(
+        findings.iter().all(|f| f.adverse_ratio > 1.05 && f.response_due_tick == 40),
+        "Findings must be over threshold with a 10-tick response window"
+    );
+}
+
+/// Remediating a finding closes it, is not escalated later, and is counted
+/// toward the quarter's remediation cost.
+#[test]
+fn remediation_resolves_finding() {
+    let run_id = "fair-lending-remediate-test";
+    let mut engine = build_with_fair_lending(run_id, 0xFA1E_0005);
+    engine.run_ticks(30).unwrap();
+
+    let findings = engine.store_open_fair_lending_findings(run_id).unwrap();
+    assert!(!findings.is_empty(), "Expected findings to respond to");
+    for f in &findings {
+        engine
+            .submit_command(PlayerCommand::RespondFairLendingFinding {
+                finding_id: f.finding_id.clone(),
+                response: "remediate".into(),
+            })
+            .unwrap();
+    }
+    engine.run_ticks(15).unwrap();
+
+    assert_eq!(
+        engine.store_fair_lending_finding_count(run_id, "remediated").unwrap(),
+        findings.len() as i64,
+    );
+    assert_eq!(engine.store_fair_lending_finding_count(run_id, "escalated").unwrap(), 0);
+    assert_eq!(
+        engine.store.fair_lending_remediations_between(run_id, 0, 45).unwrap(),
+        findings.len() as i64,
+        "Each remediation is costed in the quarter it happens"
+    );
+}
+
+/// Findings with no response past the deadline are escalated.
+#[test]
+fn unanswered_findings_escalate() {
+    let run_id = "fair-lending-escalate-test";
+    let mut engine = build_with_fair_lending(run_id, 0xFA1E_0004);
+    engine.run_ticks(30).unwrap();
+
+    let raised = engine.store_open_fair_lending_findings(run_id).unwrap().len() as i64;
+    assert!(raised > 0, "Expected findings to go unanswered");
+
+    engine.run_ticks(11).unwrap();
+
+    assert_eq!(engine.store_fair_lending_finding_count(run_id, "escalated").unwrap(), raised);
+    assert_eq!(engine.store_fair_lending_finding_count(run_id, "open").unwrap(), 0);
+}
@@ -0,0 +1,78 @@
+//! Feature flag tests — Phase 4.42.
+//!
+//! Tests cover: a disabled subsystem neither ticking nor logging, flags
+//! that would strand a dependent or switch off the core being refused,
+//! the flags being stored with the run, and every subsystem staying on
+//! unless a scenario disables it.
+
+use fincrime_core::{engine::SimEngine, error::SimError};
+
+fn build(run_id: &str, disabled: &[&str]) -> Result<SimEngine, SimError> {
+    SimEngine::build_test_with_feature_flags(run_id.to_string(), 42, disabled)
+}
+
+fn rejection(disabled: &[&str]) -> String {
+    match build("flags-rejected-test", disabled) {
+        Ok(_) => panic!("{disabled:?} should be refused"),
+        Err(err) => {
+            assert_eq!(err.code(), "generator_error");
+            err.to_string()
+        }
+    }
+}
+
+/// With offers off the run still ticks, and nothing in it comes from the
+/// offer subsystem.
+#[test]
+fn disabled_subsystem_does_not_run() {
+    let run_id = "flags-offer-test";
+    let mut engine = build(run_id, &["offer"]).unwrap();
+    assert!(!engine.subsystems().iter().any(|s| s.name == "offer"));
+    engine.run_ticks(30).unwrap();
+
+    let sources = engine.store.logged_event_sources(run_id).unwrap();
+    assert!(!sources.is_empty());
+    assert!(!sources.iter().any(|(subsystem, _)| subsystem == "offer"));
+    assert_eq!(engine.store.sum_offer_bonuses_paid(run_id, 0, 30).unwrap(), 0.0);
+}
+
+/// Disputes are raised on the payment hub's card rail, so the hub cannot
+/// go while disputes and reconciliation stay; switching all three off is
+/// fine.
+#[test]
+fn dependencies_are_checked() {
+    let err = rejection(&["payment_hub"]);
+    assert!(err.contains("requires 'payment_hub'"), "{err}");
+
+    let mut engine = build("flags-no-cards-test", &["payment_hub", "card_dispute", "reconciliation"]).unwrap();
+    engine.run_ticks(10).unwrap();
+    let names: Vec<_> = engine.subsystems().into_iter().map(|s| s.name).collect();
+    assert!(!names.iter().any(|n| n == "card_dispute" || n == "payment_hub"));
+}
+
+/// Unknown names and core subsystems are refused.
+#[test]
+fn unknown_and_core_subsystems_rejected() {
+    assert!(rejection(&["offers"]).contains("unknown subsystem 'offers'"));
+    assert!(rejection(&["customer"]).contains("core subsystem"));
+}
+
+/// The run records the flags it was built with.
+#[test]
+fn flags_recorded_with_run() {
+    let run_id = "flags-recorded-test";
+    let engine = build(run_id, &["offer", "outreach"]).unwrap();
+    let run = engine.store.describe_run(run_id).unwrap().unwrap();
+    assert_eq!(run.feature_flags.disabled_subsystems, vec!["offer", "outreach"]);
+}
+
+/// Flags are opt-in: by default every subsystem registers and the run
+/// records none disabled.
+#[test]
+fn feature_flags_opt_in() {
+    let run_id = "flags-default-test";
+    let engine = SimEngine::build_test(run_id.to_string(), 42).unwrap();
+    assert_eq!(engine.subsystems().len(), 26);
+    let run = engine.store.describe_run(run_id).unwrap().unwrap();
+    assert!(run.feature_flags.disabled_subsystems.is_empty());
+}
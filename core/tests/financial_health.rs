@@ -0,0 +1,129 @@
+//! Financial health and credit cross-sell tests — Phase 4.34.
+//!
+//! Tests cover: monthly health scoring, the default health floor gating
+//! credit offers, aggressive cross-sell below the floor raising UDAAP
+//! exposure and credit losses, and scoring staying off unless enabled.
+
+use fincrime_core::{command::PlayerCommand, engine::SimEngine};
+
+fn build_with_health(run_id: &str, seed: u64) -> SimEngine {
+    SimEngine::build_test_with_financial_health(run_id.to_string(), seed)
+        .expect("build test engine with financial health")
+}
+
+fn launch_credit_line(engine: &mut SimEngine) {
+    engine
+        .submit_command(PlayerCommand::LaunchOffer {
+            offer_id: "credit_line_1000".into(),
+            exclude_zip_prefixes: vec![],
+        })
+        .unwrap();
+}
+
+/// Every active customer is scored on each monthly pass, within 0..1,
+/// and customers who have been hit with overdraft fees score lower.
+#[test]
+fn health_scored_monthly() {
+    let run_id = "health-score-test";
+    let mut engine = build_with_health(run_id, 42);
+    engine.run_ticks(90).unwrap();
+
+    assert_eq!(engine.store.count_events_in_range(run_id, 0, 90, "financial_health_scored").unwrap(), 3);
+    assert_eq!(
+        engine.store.customer_health_at(run_id, 90).unwrap().len(),
+        engine.store.active_customers(run_id).unwrap().len()
+    );
+
+    // The first pass, before churn thins out the struggling customers
+    let scores = engine.store.customer_health_at(run_id, 30).unwrap();
+    for row in &scores {
+        assert!((0.0..=1.0).contains(&row.score), "{row:?}");
+        assert!((0.0..=1.0).contains(&row.payroll_stability), "{row:?}");
+    }
+
+    let mean = |overdrafts: bool| {
+        let rows: Vec<f64> =
+            scores.iter().filter(|r| (r.overdraft_count > 0) == overdrafts).map(|r| r.score).collect();
+        assert!(!rows.is_empty());
+        rows.iter().sum::<f64>() / rows.len() as f64
+    };
+    assert!(mean(true) < mean(false), "{} vs {}", mean(true), mean(false));
+}
+
+/// Under the default floor a credit offer only opens lines for customers
+/// scoring at or above it, and the launch reports who was held back.
+#[test]
+fn default_floor_gates_credit_offers() {
+    let run_id = "health-gate-test";
+    let mut engine = build_with_health(run_id, 42);
+    engine.run_ticks(20).unwrap();
+    launch_credit_line(&mut engine);
+    engine.run_ticks(1).unwrap();
+
+    let lines = engine.store.credit_lines(run_id).unwrap();
+    assert!(!lines.is_empty());
+    for line in &lines {
+        assert!(line.health_at_open >= 0.5, "{line:?}");
+        assert_eq!((line.credit_limit, line.status.as_str()), (1000.0, "open"));
+    }
+    assert_eq!(engine.store.count_events_in_range(run_id, 21, 21, "credit_offer_screened").unwrap(), 1);
+    assert_eq!(engine.store.count_events_in_range(run_id, 21, 21, "offer_matched").unwrap(), lines.len() as i64);
+}
+
+/// Dropping the floor sells credit to low-health customers: more lines,
+/// UDAAP exposure for each low-health one, and more credit losses than
+/// the same bank with the floor in place. An out-of-range floor is
+/// rejected.
+#[test]
+fn aggressive_cross_sell_raises_losses_and_udaap() {
+    let run = |run_id: &str, floor: Option<f64>| {
+        let mut engine = build_with_health(run_id, 42);
+        engine.run_ticks(20).unwrap();
+        if let Some(min_score) = floor {
+            engine.submit_command(PlayerCommand::SetCreditHealthFloor { min_score: 1.5 }).unwrap();
+            engine.submit_command(PlayerCommand::SetCreditHealthFloor { min_score }).unwrap();
+        }
+        let udaap_before = engine.store_udaap_score(run_id).unwrap();
+        launch_credit_line(&mut engine);
+        engine.run_ticks(1).unwrap();
+        let udaap_delta = engine.store_udaap_score(run_id).unwrap() - udaap_before;
+        engine.run_ticks(340).unwrap();
+        (engine, udaap_delta)
+    };
+    let (gated, gated_udaap) = run("health-gated-test", None);
+    let (aggressive, aggressive_udaap) = run("health-aggressive-test", Some(0.0));
+
+    let gated_lines = gated.store.credit_lines("health-gated-test").unwrap();
+    let aggressive_lines = aggressive.store.credit_lines("health-aggressive-test").unwrap();
+    let low_health = aggressive_lines.iter().filter(|l| l.health_at_open < 0.4).count();
+    assert!(aggressive_lines.len() > gated_lines.len());
+    assert!(low_health > 0);
+    assert!(aggressive_udaap > gated_udaap, "{aggressive_udaap} vs {gated_udaap}");
+
+    let events = |engine: &SimEngine, run_id: &str, kind: &str| {
+        engine.store.count_events_in_range(run_id, 0, 361, kind).unwrap()
+    };
+    assert_eq!(events(&aggressive, "health-aggressive-test", "credit_health_floor_rejected"), 1);
+    assert_eq!(events(&aggressive, "health-aggressive-test", "credit_health_floor_set"), 1);
+
+    let losses = |engine: &SimEngine, run_id: &str| engine.store.sum_credit_losses(run_id, 0, 361).unwrap();
+    let aggressive_losses = losses(&aggressive, "health-aggressive-test");
+    assert!(aggressive_losses > losses(&gated, "health-gated-test"), "{aggressive_losses}");
+    assert_eq!(
+        events(&aggressive, "health-aggressive-test", "credit_line_defaulted"),
+        aggressive_lines.iter().filter(|l| l.status == "defaulted").count() as i64
+    );
+}
+
+/// The test default scores nobody and keeps the credit offer off the
+/// books.
+#[test]
+fn financial_health_is_opt_in() {
+    let run_id = "health-off-test";
+    let mut engine = SimEngine::build_test(run_id.into(), 42).unwrap();
+    engine.run_ticks(60).unwrap();
+
+    assert!(engine.store.customer_health_at(run_id, 30).unwrap().is_empty());
+    assert!(engine.store.credit_lines(run_id).unwrap().is_empty());
+    assert_eq!(engine.store.count_events_in_range(run_id, 0, 60, "financial_health_scored").unwrap(), 0);
+}
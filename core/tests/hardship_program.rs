@@ -0,0 +1,92 @@
+//! Hardship program tests — Phase 4.30.
+//!
+//! Tests cover: enrollment after qualifying life events, overdraft fees
+//! waived only while a customer is enrolled, the quarter's enrollments
+//! and waived fees reported in the segment P&L, and the program staying
+//! off unless enabled.
+
+use fincrime_core::engine::SimEngine;
+
+/// Everyone with a job change at tick 30 enrolls for the configured 90
+/// ticks; the job changes at tick 60 find them already enrolled.
+#[test]
+fn qualifying_life_events_enroll_customers() {
+    let run_id = "hardship-enroll-test";
+    let mut engine = SimEngine::build_test_with_hardship(run_id.into(), 42).unwrap();
+    engine.run_ticks(61).unwrap();
+
+    let enrollments = engine.store.hardship_enrollments(run_id).unwrap();
+    assert!(!enrollments.is_empty());
+    for e in &enrollments {
+        assert_eq!((e.life_event.as_str(), e.tick_enrolled, e.tick_expires), ("job_change", 30, 120));
+    }
+    assert_eq!(
+        engine.store.count_events_in_range(run_id, 30, 30, "life_event_occurred").unwrap(),
+        enrollments.len() as i64
+    );
+    assert_eq!(
+        engine.store.count_events_in_range(run_id, 0, 61, "hardship_enrolled").unwrap(),
+        enrollments.len() as i64
+    );
+    assert!(engine.store.count_events_in_range(run_id, 60, 60, "life_event_occurred").unwrap() > 0);
+}
+
+/// Waivers only go to customers enrolled at the time, for the full
+/// overdraft fee.
+#[test]
+fn overdraft_fees_waived_while_enrolled() {
+    let run_id = "hardship-waiver-test";
+    let mut engine = SimEngine::build_test_with_hardship(run_id.into(), 7).unwrap();
+    engine.run_ticks(90).unwrap();
+
+    let enrollments = engine.store.hardship_enrollments(run_id).unwrap();
+    let waivers = engine.store.hardship_waivers(run_id).unwrap();
+    assert!(!waivers.is_empty(), "Enrolled customers still overdraw");
+    for w in &waivers {
+        assert_eq!((w.fee_type.as_str(), w.amount), ("overdraft", 27.08));
+        assert!(enrollments.iter().any(|e| e.customer_id == w.customer_id
+            && e.tick_enrolled <= w.tick
+            && w.tick < e.tick_expires));
+    }
+}
+
+/// The quarter's segment P&L rows add up to the enrollments and waivers
+/// recorded in ticks 1..=90.
+#[test]
+fn segment_pnl_reports_program_cost() {
+    let run_id = "hardship-pnl-test";
+    let mut engine = SimEngine::build_test_with_hardship(run_id.into(), 7).unwrap();
+    engine.run_ticks(91).unwrap();
+
+    let pnls = engine.store_all_segment_pnls(run_id, 90).unwrap();
+    let enrolled: i64 = pnls.iter().map(|p| p.hardship_enrollments).sum();
+    let active: i64 = pnls.iter().map(|p| p.hardship_active).sum();
+    let waived: f64 = pnls.iter().map(|p| p.hardship_fees_waived).sum();
+
+    let enrollments = engine.store.hardship_enrollments(run_id).unwrap();
+    let in_quarter: Vec<_> = enrollments.iter().filter(|e| (1..=90).contains(&e.tick_enrolled)).collect();
+    assert!(!in_quarter.is_empty());
+    assert_eq!(enrolled, in_quarter.len() as i64);
+    assert!(active > 0 && active <= enrolled, "Only customers still active at tick 90 count");
+
+    let expected: f64 = engine
+        .store
+        .hardship_waivers(run_id)
+        .unwrap()
+        .iter()
+        .filter(|w| (1..=90).contains(&w.tick))
+        .map(|w| w.amount)
+        .sum();
+    assert!((waived - expected).abs() < 0.01, "waived {waived} vs {expected}");
+}
+
+/// The test default leaves the program off: no enrollments or waivers.
+#[test]
+fn program_is_opt_in() {
+    let run_id = "hardship-off-test";
+    let mut engine = SimEngine::build_test(run_id.into(), 42).unwrap();
+    engine.run_ticks(61).unwrap();
+
+    assert!(engine.store.hardship_enrollments(run_id).unwrap().is_empty());
+    assert!(engine.store.hardship_waivers(run_id).unwrap().is_empty());
+}
@@ -0,0 +1,111 @@
+//! Identity verification vendor tests — Phase 4.15.
+//!
+//! Tests cover: every applicant checked with match codes recorded and the
+//! declined ones kept off the books, genuine applicants never declined,
+//! a premium vendor admitting fewer synthetic identities than a budget
+//! one, and each check billed at the vendor's fee.
+
+use fincrime_core::engine::SimEngine;
+
+fn build_with_vendor(run_id: &str, seed: u64, vendor: &str) -> SimEngine {
+    SimEngine::build_test_with_idv_vendor(run_id.to_string(), seed, vendor)
+        .expect("build test engine with IDV vendor")
+}
+
+/// Synthetic identities the vendor passed.
+fn admitted_synthetics(engine: &SimEngine, run_id: &str) -> usize {
+    engine
+        .store_idv_checks(run_id)
+        .unwrap()
+        .iter()
+        .filter(|c| c.decision == "pass")
+        .filter(|c| {
+            engine
+                .store
+                .get_customer_identity(run_id, &c.customer_id)
+                .unwrap()
+                .unwrap()
+                .identity_type
+                == "synthetic"
+        })
+        .count()
+}
+
+/// Every applicant is checked; a no-match element declines them, and
+/// declined applicants never become active customers.
+#[test]
+fn applicants_checked_and_no_matches_declined() {
+    let run_id = "idv-vendor-decline-test";
+    let mut engine = build_with_vendor(run_id, 0x1D7_0001, "standard");
+    engine.run_ticks(1).unwrap();
+
+    let checks = engine.store_idv_checks(run_id).unwrap();
+    assert_eq!(checks.len(), 50);
+    let active = engine.store.active_customers(run_id).unwrap();
+    let declined: Vec<_> = checks.iter().filter(|c| c.decision == "decline").collect();
+    assert!(!declined.is_empty());
+    assert_eq!(active.len(), 50 - declined.len());
+    for check in &checks {
+        assert_eq!(check.vendor, "standard");
+        assert_eq!(check.cost, 1.50);
+        let no_match = check.match_codes().contains(&"no_match");
+        assert_eq!(check.decision == "decline", no_match, "{:?}", check);
+        let is_active = active.iter().any(|c| c.customer_id == check.customer_id);
+        assert_eq!(is_active, check.decision == "pass");
+    }
+}
+
+/// Genuine applicants come back as match or partial, never no match, so
+/// the vendor never declines them.
+#[test]
+fn genuine_applicants_never_declined() {
+    let run_id = "idv-vendor-genuine-test";
+    let mut engine = build_with_vendor(run_id, 0x1D7_0002, "budget");
+    engine.run_ticks(1).unwrap();
+
+    for check in engine.store_idv_checks(run_id).unwrap() {
+        let identity = engine
+            .store
+            .get_customer_identity(run_id, &check.customer_id)
+            .unwrap()
+            .unwrap();
+        if identity.identity_type != "synthetic" {
+            assert_eq!(check.decision, "pass", "{:?}", check);
+            assert!(check.match_codes().iter().all(|c| *c == "match" || *c == "partial"));
+        }
+    }
+}
+
+/// The same applicants run past a premium vendor admit fewer synthetic
+/// identities than past a budget one.
+#[test]
+fn premium_vendor_admits_fewer_synthetics() {
+    let seed = 0x1D7_0003;
+    let mut budget = build_with_vendor("idv-vendor-budget-test", seed, "budget");
+    let mut premium = build_with_vendor("idv-vendor-premium-test", seed, "premium");
+    budget.run_ticks(1).unwrap();
+    premium.run_ticks(1).unwrap();
+
+    let admitted_budget = admitted_synthetics(&budget, "idv-vendor-budget-test");
+    let admitted_premium = admitted_synthetics(&premium, "idv-vendor-premium-test");
+    assert!(
+        admitted_premium < admitted_budget,
+        "premium admitted {admitted_premium}, budget {admitted_budget}"
+    );
+}
+
+/// Each check is billed at the vendor's fee, so the premium vendor's
+/// quarter costs ten times the budget one's.
+#[test]
+fn vendor_fees_booked_per_check() {
+    let seed = 0x1D7_0004;
+    let mut budget = build_with_vendor("idv-vendor-fee-budget-test", seed, "budget");
+    let mut premium = build_with_vendor("idv-vendor-fee-premium-test", seed, "premium");
+    budget.run_ticks(1).unwrap();
+    premium.run_ticks(1).unwrap();
+
+    let budget_cost = budget.store.idv_cost_between("idv-vendor-fee-budget-test", 0, 90).unwrap();
+    let premium_cost = premium.store.idv_cost_between("idv-vendor-fee-premium-test", 0, 90).unwrap();
+    assert!((budget_cost - 50.0 * 0.40).abs() < 1e-9);
+    assert!((premium_cost - 50.0 * 4.00).abs() < 1e-9);
+}
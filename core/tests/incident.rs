@@ -15,13 +15,13 @@ fn build_with_incidents(run_id: &str, seed: u64) -> SimEngine {
         .expect("build test engine with incidents")
 }
 
-/// Migration 019 seeds 10 system components into the database.
+/// Migrations 019 and 091 seed 11 system components into the database.
 #[test]
 fn system_components_seeded() {
     let engine = build("comp-seed-test", 42);
 
     let count = engine.store_system_component_count().unwrap();
-    assert_eq!(count, 10, "Expected 10 seeded system components, got {count}");
+    assert_eq!(count, 11, "Expected 11 seeded system components, got {count}");
 }
 
 /// Running with incident subsystem enabled should generate incidents over time.
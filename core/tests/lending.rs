@@ -0,0 +1,132 @@
+//! Lending subsystem tests — Phase 4.82.
+//!
+//! Tests cover: underwriting against credit score and debt-to-income,
+//! the installment amortization schedule, payments reducing balances and
+//! booking interest, missed payments aging through the delinquency
+//! buckets to a charge-off in credit loss, and lending staying off unless
+//! enabled.
+
+use fincrime_core::{config::SimConfig, engine::SimEngine};
+
+fn build(run_id: &str) -> SimEngine {
+    SimEngine::build_test_with_lending(run_id.to_string(), 42).unwrap()
+}
+
+/// Every loan written clears its product's minimum score and the DTI
+/// limit, and applications that do not are declined.
+#[test]
+fn underwriting_respects_score_and_dti() {
+    let run_id = "lending-underwriting-test";
+    let mut engine = build(run_id);
+    engine.run_ticks(120).unwrap();
+
+    let config = SimConfig::default_test().lending;
+    let loans = engine.store.loans(run_id).unwrap();
+    assert!(!loans.is_empty());
+    for loan in &loans {
+        let product = config.products.iter().find(|p| p.product_id == loan.product_id).unwrap();
+        assert!(loan.credit_score >= product.min_credit_score, "{loan:?}");
+        assert!(loan.dti <= config.max_dti, "{loan:?}");
+        assert!(loan.amount <= product.max_amount, "{loan:?}");
+    }
+    assert!(engine.store.count_events_in_range(run_id, 1, 120, "loan_application_declined").unwrap() > 0);
+    assert_eq!(
+        engine.store.count_events_in_range(run_id, 1, 120, "loan_originated").unwrap(),
+        loans.len() as i64
+    );
+}
+
+/// An installment loan's schedule is written at origination: level
+/// payments a period apart whose principal repays the amount.
+#[test]
+fn installment_schedule_amortizes() {
+    let run_id = "lending-schedule-test";
+    let mut engine = build(run_id);
+    engine.run_ticks(60).unwrap();
+
+    let loans = engine.store.loans(run_id).unwrap();
+    let loan = loans.iter().find(|l| l.kind == "installment").expect("an installment loan");
+    let payments = engine.store.loan_payments(run_id, &loan.loan_id).unwrap();
+    assert_eq!(payments.len(), 36);
+    for (k, p) in payments.iter().enumerate() {
+        assert_eq!(p.due_tick, loan.tick_originated + 30 * (k as u64 + 1));
+        assert!((p.amount_due - payments[0].amount_due).abs() < 0.01, "{p:?}");
+        assert!((p.amount_due - p.principal - p.interest).abs() < 1e-9);
+    }
+    let principal: f64 = payments.iter().map(|p| p.principal).sum();
+    assert!((principal - loan.amount).abs() < 1e-6);
+    assert!(payments.windows(2).all(|w| w[1].interest < w[0].interest));
+}
+
+/// What borrowers pay comes off their balances, and the interest in it is
+/// what lending has earned.
+#[test]
+fn payments_reduce_balance_and_book_interest() {
+    let run_id = "lending-payments-test";
+    let mut engine = build(run_id);
+    engine.run_ticks(150).unwrap();
+
+    let mut interest = 0.0;
+    for loan in engine.store.loans(run_id).unwrap().iter().filter(|l| l.kind == "installment") {
+        let paid: Vec<_> = engine
+            .store
+            .loan_payments(run_id, &loan.loan_id)
+            .unwrap()
+            .into_iter()
+            .filter(|p| p.status == "paid")
+            .collect();
+        let principal: f64 = paid.iter().map(|p| p.principal).sum();
+        assert!((loan.amount - principal - loan.balance).abs() < 1e-6, "{loan:?}");
+        assert!(paid.iter().all(|p| p.tick_paid.unwrap() >= p.due_tick));
+        interest += paid.iter().map(|p| p.interest).sum::<f64>();
+    }
+    let booked: f64 = engine.store.loans(run_id).unwrap().iter().map(|l| l.interest_paid).sum();
+    assert!(interest > 0.0);
+    assert!(booked >= interest - 1e-6);
+    assert!((engine.store.loan_interest_between(run_id, 0, 150).unwrap() - booked).abs() < 1e-6);
+}
+
+/// A borrower whose account cannot cover a payment misses it, and the
+/// loan ages a bucket a month until it is charged off at 120 days past
+/// due; the loss lands in the quarter's credit loss.
+#[test]
+fn missed_payments_age_to_charge_off() {
+    let run_id = "lending-charge-off-test";
+    let mut engine = build(run_id);
+    engine.run_ticks(5).unwrap();
+    let loan = engine.store.loans(run_id).unwrap().into_iter().next().expect("an early loan");
+    engine.store.update_account_balance(run_id, &loan.account_id, -1.0e9).unwrap();
+    engine.run_ticks(175).unwrap();
+
+    let closed = engine.store.loans(run_id).unwrap().into_iter().find(|l| l.loan_id == loan.loan_id).unwrap();
+    let first_due = loan.tick_originated + 30;
+    assert_eq!((closed.status.as_str(), closed.tick_closed), ("charged_off", Some(first_due + 120)));
+    assert!((closed.charge_off_loss - closed.balance * 0.9).abs() < 1e-6);
+
+    let buckets: Vec<(u64, String)> = engine
+        .store
+        .delinquency_history(run_id, &loan.loan_id)
+        .unwrap()
+        .into_iter()
+        .map(|d| (d.tick - first_due, d.bucket))
+        .collect();
+    let expected = [(1, "1_29"), (30, "30_59"), (60, "60_89"), (90, "90_plus")];
+    assert_eq!(buckets, expected.map(|(t, b)| (t, b.to_string())));
+
+    let pnl = engine.store.latest_pnl_snapshot(run_id).unwrap().expect("P&L at tick 180");
+    assert_eq!(pnl.tick, 180);
+    assert!(pnl.credit_loss >= closed.charge_off_loss - 1e-6);
+}
+
+/// The test config leaves lending off: no applications, loans or loan
+/// balances.
+#[test]
+fn lending_off_unless_enabled() {
+    let run_id = "lending-disabled-test";
+    let mut engine = SimEngine::build_test(run_id.to_string(), 42).unwrap();
+    engine.run_ticks(90).unwrap();
+
+    assert!(engine.store.loans(run_id).unwrap().is_empty());
+    assert_eq!(engine.store.count_events_in_range(run_id, 1, 90, "loan_application_declined").unwrap(), 0);
+    assert_eq!(engine.store_latest_pnl(run_id).unwrap().unwrap().avg_loans, 0.0);
+}
@@ -0,0 +1,142 @@
+//! Merchant tests — Phase 4.93.
+//!
+//! Tests cover: no merchants unless enabled, and every card purchase on
+//! one made at a merchant of the run whose category it carries; bad
+//! merchants' purchases disputed far more often than the rest; and weekly
+//! chargeback alerts raised only for merchants over the alert rate.
+
+use fincrime_core::{engine::SimEngine, store::merchant::MerchantRow};
+use serde_json::Value;
+use std::collections::{BTreeMap, BTreeSet};
+
+const TICKS: u64 = 120;
+
+fn engine(run_id: &str) -> SimEngine {
+    let mut engine = SimEngine::build_test_with_merchants(run_id.into(), 42).unwrap();
+    engine.run_ticks(TICKS).unwrap();
+    engine
+}
+
+/// Payloads of every `event_type` event so far, in order.
+fn events(engine: &SimEngine, event_type: &str) -> Vec<Value> {
+    (0..=engine.clock.current_tick)
+        .flat_map(|tick| engine.store.events_for_tick(&engine.run_id, tick).unwrap())
+        .filter(|e| e.event_type == event_type)
+        .map(|e| serde_json::from_str(&e.payload).unwrap())
+        .collect()
+}
+
+fn merchants(engine: &SimEngine) -> BTreeMap<String, MerchantRow> {
+    engine.store.merchants(&engine.run_id).unwrap().into_iter().map(|m| (m.merchant_id.clone(), m)).collect()
+}
+
+/// Off by default; on, each purchase is made at one of the run's
+/// merchants and carries its name and category.
+#[test]
+fn purchases_made_at_merchants() {
+    let mut plain = SimEngine::build_test("merchants-off-test".into(), 42).unwrap();
+    plain.run_ticks(5).unwrap();
+    assert!(plain.store.merchants(&plain.run_id).unwrap().is_empty());
+    for auth in events(&plain, "card_authorization_created") {
+        let id = auth["authorization_id"].as_str().unwrap();
+        assert_eq!(plain.store.get_authorization(&plain.run_id, id).unwrap().merchant_id, None);
+    }
+
+    let mut engine = SimEngine::build_test_with_merchants("merchants-on-test".into(), 42).unwrap();
+    engine.run_ticks(10).unwrap();
+    let merchants = merchants(&engine);
+    assert_eq!(merchants.len(), 20);
+    for m in merchants.values() {
+        let expected = match m.risk_tier.as_str() {
+            "low" => 0.02,
+            "medium" => 0.04,
+            _ => 0.08,
+        };
+        assert_eq!(m.fraud_rate, expected, "{}", m.merchant_id);
+    }
+
+    let auths = events(&engine, "card_authorization_created");
+    assert!(auths.len() > 20, "only {} purchases", auths.len());
+    for auth in &auths {
+        let row = engine.store.get_authorization(&engine.run_id, auth["authorization_id"].as_str().unwrap()).unwrap();
+        let merchant = &merchants[row.merchant_id.as_deref().unwrap()];
+        assert_eq!(row.merchant_name.as_deref(), Some(merchant.name.as_str()));
+        assert_eq!(row.merchant_category.as_deref(), Some(merchant.category.as_str()));
+    }
+}
+
+/// Purchases at a merchant while it is bad are disputed several times as
+/// often as purchases anywhere else.
+#[test]
+fn bad_merchants_spike_disputes() {
+    let engine = engine("merchants-bad-test");
+    let merchants = merchants(&engine);
+    let turned = events(&engine, "merchant_turned_bad");
+    assert!(turned.len() >= 2, "only {} bad merchants", turned.len());
+    for event in &turned {
+        let m = &merchants[event["merchant_id"].as_str().unwrap()];
+        assert_eq!(m.bad_from, event["tick"].as_u64());
+        assert_eq!(m.bad_until, Some(m.bad_from.unwrap() + 20));
+    }
+
+    let disputed: BTreeSet<String> = events(&engine, "dispute_filed")
+        .into_iter()
+        .map(|d| d["authorization_id"].as_str().unwrap().to_string())
+        .collect();
+    // (purchases, disputed) while bad and otherwise, over purchases whose
+    // dispute window has closed
+    let (mut bad, mut rest) = ((0, 0), (0, 0));
+    for auth in events(&engine, "card_authorization_created") {
+        let id = auth["authorization_id"].as_str().unwrap();
+        let row = engine.store.get_authorization(&engine.run_id, id).unwrap();
+        if row.tick_authorized + 62 > TICKS {
+            continue;
+        }
+        let tally = if merchants[row.merchant_id.as_deref().unwrap()].bad_at(row.tick_authorized) {
+            &mut bad
+        } else {
+            &mut rest
+        };
+        tally.0 += 1;
+        tally.1 += disputed.contains(id) as i64;
+    }
+    assert!(bad.1 >= 3, "only {} disputes at bad merchants", bad.1);
+    let bad_rate = bad.1 as f64 / bad.0 as f64;
+    let rest_rate = rest.1 as f64 / rest.0 as f64;
+    assert!(bad_rate > 3.0 * rest_rate, "bad {bad_rate:.3} vs rest {rest_rate:.3}");
+}
+
+/// Each week a merchant over the alert rate with enough disputes raises
+/// an early warning, and only merchants that went bad get there.
+#[test]
+fn chargeback_rates_alerted() {
+    let engine = engine("merchants-alert-test");
+    let merchants = merchants(&engine);
+    let alerts = events(&engine, "merchant_chargeback_alert");
+    assert!(!alerts.is_empty());
+    assert!(engine.store.early_warning_alert_count(&engine.run_id).unwrap() >= alerts.len() as i64);
+
+    for alert in &alerts {
+        let tick = alert["tick"].as_u64().unwrap();
+        let merchant_id = alert["merchant_id"].as_str().unwrap();
+        assert_eq!(tick % 7, 0);
+        assert!(merchants[merchant_id].bad_from.is_some(), "{merchant_id} alerted but never bad");
+        let stats = engine.store.merchant_chargeback_stats(&engine.run_id, tick.saturating_sub(29), tick).unwrap();
+        let stats = stats.iter().find(|s| s.merchant_id == merchant_id).unwrap();
+        assert_eq!(alert["disputes"].as_i64(), Some(stats.disputes));
+        assert!(stats.disputes >= 3 && stats.rate >= 0.15);
+    }
+
+    // No merchant over the line on a review tick goes unalerted
+    for tick in (7..=TICKS).step_by(7) {
+        for stats in engine.store.merchant_chargeback_stats(&engine.run_id, tick.saturating_sub(29), tick).unwrap() {
+            if stats.disputes >= 3 && stats.rate >= 0.15 {
+                assert!(
+                    alerts.iter().any(|a| a["tick"] == tick && a["merchant_id"] == stats.merchant_id.as_str()),
+                    "{} not alerted on {tick}",
+                    stats.merchant_id
+                );
+            }
+        }
+    }
+}
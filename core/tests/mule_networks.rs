@@ -0,0 +1,160 @@
+//! Money mule network tests — Phase 4.87.
+//!
+//! Tests cover: networks recruiting a controller and mules from customers
+//! in no other network, each mule linked to its controller by a
+//! suspicious relationship; mules taking rounds of small credits and
+//! forwarding them, less their cut, to the controller a tick later, who
+//! wires them abroad; networks dissolving at the end of their lifetime;
+//! the daily event matching the legs booked; and the typology staying
+//! off unless enabled.
+
+use fincrime_core::{engine::SimEngine, store::mule_network::MuleTransferRow};
+use serde_json::Value;
+use std::collections::{HashMap, HashSet};
+
+fn build(run_id: &str) -> SimEngine {
+    SimEngine::build_test_with_mule_networks(run_id.into(), 42).unwrap()
+}
+
+fn events(engine: &SimEngine, event_type: &str) -> Vec<Value> {
+    (0..=engine.clock.current_tick)
+        .flat_map(|tick| engine.store.events_for_tick(&engine.run_id, tick).unwrap())
+        .filter(|e| e.event_type == event_type)
+        .map(|e| serde_json::from_str(&e.payload).unwrap())
+        .collect()
+}
+
+fn legs<'a>(transfers: &'a [MuleTransferRow], leg: &str) -> Vec<&'a MuleTransferRow> {
+    transfers.iter().filter(|t| t.leg == leg).collect()
+}
+
+/// Each network has a controller and three to five mules, nobody sits in
+/// two networks, and every mule is linked to its controller.
+#[test]
+fn networks_recruit_controller_and_linked_mules() {
+    let run_id = "mule-seed-test";
+    let mut engine = build(run_id);
+    engine.run_ticks(40).unwrap();
+
+    let networks = engine.store.mule_networks(run_id).unwrap();
+    assert!(!networks.is_empty());
+    let mut seen = HashSet::new();
+    let mut mules = 0;
+    for network in &networks {
+        let members = engine.store.mule_members(run_id, &network.network_id).unwrap();
+        assert!((3..=5).contains(&members.len()), "{}: {} mules", network.network_id, members.len());
+        assert_eq!(members.len() as i64, network.mule_count);
+        assert!(seen.insert(network.controller_id.clone()));
+        for member in &members {
+            assert!(seen.insert(member.customer_id.clone()), "{} recruited twice", member.customer_id);
+            assert_eq!(member.recruited_tick, network.seeded_tick);
+        }
+        mules += members.len() as i64;
+    }
+    assert_eq!(engine.store.suspicious_relationship_count(run_id).unwrap(), mules);
+    let seeded = events(&engine, "mule_network_seeded");
+    assert_eq!(seeded.len(), networks.len());
+    assert_eq!(seeded[0]["controller_id"].as_str(), Some(networks[0].controller_id.as_str()));
+}
+
+/// A round is three credits from distinct fresh senders, none over the
+/// configured amount's upper band. The mule forwards it, less its cut,
+/// the next tick, and the controller wires the day's take abroad.
+#[test]
+fn mules_forward_rounds_to_the_controller() {
+    let run_id = "mule-flow-test";
+    let mut engine = build(run_id);
+    engine.run_ticks(30).unwrap();
+
+    let transfers = engine.store.mule_transfers(run_id).unwrap();
+    let inbound = legs(&transfers, "inbound");
+    assert!(!inbound.is_empty());
+    assert!(inbound.iter().all(|t| t.amount < 1_200.0 * 1.3));
+    let mut rounds: HashMap<(&str, u64), Vec<f64>> = HashMap::new();
+    for t in &inbound {
+        rounds.entry((t.customer_id.as_str(), t.tick)).or_default().push(t.amount);
+    }
+    assert!(rounds.values().all(|r| r.len() == 3));
+    let senders: HashSet<_> = inbound.iter().map(|t| &t.counterparty).collect();
+    assert_eq!(senders.len(), inbound.len());
+
+    let controllers: HashMap<_, _> = engine
+        .store
+        .mule_networks(run_id)
+        .unwrap()
+        .into_iter()
+        .map(|n| (n.network_id.clone(), n))
+        .collect();
+    for forward in legs(&transfers, "forward") {
+        let round: f64 = rounds[&(forward.customer_id.as_str(), forward.tick - 1)].iter().sum();
+        assert!(forward.amount <= round * 0.9 + 1e-6);
+        let network = &controllers[&forward.network_id];
+        assert_eq!(forward.counterparty, network.controller_account_id);
+        assert!(transfers.iter().any(|t| t.leg == "collect"
+            && t.account_id == network.controller_account_id
+            && t.counterparty == forward.account_id
+            && t.tick == forward.tick));
+    }
+    let collected: f64 = legs(&transfers, "collect").iter().map(|t| t.amount).sum();
+    let cashed_out: f64 = legs(&transfers, "cash_out").iter().map(|t| t.amount).sum();
+    assert!(collected > 0.0);
+    assert!(cashed_out <= collected + 1e-6);
+    assert!(legs(&transfers, "cash_out").iter().all(|t| t.customer_id == controllers[&t.network_id].controller_id));
+}
+
+/// A network dissolves its lifetime after it was seeded, and its mules
+/// take no credits from then on.
+#[test]
+fn networks_dissolve_after_their_lifetime() {
+    let run_id = "mule-dissolve-test";
+    let mut engine = build(run_id);
+    engine.run_ticks(80).unwrap();
+
+    let dissolved: Vec<_> =
+        engine.store.mule_networks(run_id).unwrap().into_iter().filter(|n| n.status == "dissolved").collect();
+    assert!(!dissolved.is_empty());
+    let transfers = engine.store.mule_transfers(run_id).unwrap();
+    for network in &dissolved {
+        assert_eq!(network.dissolved_tick, Some(network.seeded_tick + 30));
+        assert!(legs(&transfers, "inbound")
+            .iter()
+            .all(|t| t.network_id != network.network_id || t.tick < network.seeded_tick + 30));
+    }
+    assert!(engine.store.active_mule_networks(run_id).unwrap().len() <= 2);
+    assert_eq!(events(&engine, "mule_network_dissolved").len(), dissolved.len());
+}
+
+/// The day's event totals the legs booked that day.
+#[test]
+fn daily_event_matches_the_legs_booked() {
+    let run_id = "mule-event-test";
+    let mut engine = build(run_id);
+    engine.run_ticks(30).unwrap();
+
+    let transfers = engine.store.mule_transfers(run_id).unwrap();
+    let booked = events(&engine, "mule_transfers_booked");
+    assert!(!booked.is_empty());
+    for event in booked {
+        let tick = event["tick"].as_u64().unwrap();
+        let day: Vec<_> = transfers.iter().filter(|t| t.tick == tick).collect();
+        let total = |leg: &str| day.iter().filter(|t| t.leg == leg).map(|t| t.amount).sum::<f64>();
+        let count = |leg: &str| day.iter().filter(|t| t.leg == leg).count() as i64;
+        assert_eq!(event["inbound_credits"].as_i64(), Some(count("inbound")));
+        assert_eq!(event["forwards"].as_i64(), Some(count("forward")));
+        assert!((event["inflow"].as_f64().unwrap() - total("inbound")).abs() < 1e-6);
+        assert!((event["forwarded"].as_f64().unwrap() - total("forward")).abs() < 1e-6);
+        assert!((event["cashed_out"].as_f64().unwrap() - total("cash_out")).abs() < 1e-6);
+    }
+}
+
+/// With the typology off nobody is recruited and nothing moves.
+#[test]
+fn networks_stay_off_unless_enabled() {
+    let run_id = "mule-off-test";
+    let mut engine = SimEngine::build_test(run_id.into(), 42).unwrap();
+    engine.run_ticks(30).unwrap();
+
+    assert!(engine.store.mule_networks(run_id).unwrap().is_empty());
+    assert!(engine.store.mule_transfers(run_id).unwrap().is_empty());
+    assert_eq!(engine.store.suspicious_relationship_count(run_id).unwrap(), 0);
+}
@@ -0,0 +1,94 @@
+//! Card network invoice reconciliation tests — Phase 4.6.
+//!
+//! Tests cover: monthly invoice lines, network errors opening recon
+//! exceptions, recovery claims, write-off of unclaimed errors.
+
+use fincrime_core::{command::PlayerCommand, engine::SimEngine};
+
+fn build_with_invoices(run_id: &str, seed: u64) -> SimEngine {
+    SimEngine::build_test_with_network_invoices(run_id.to_string(), seed)
+        .expect("build test engine with network invoices")
+}
+
+/// One interchange, assessment and dispute-fee line per invoice period.
+#[test]
+fn invoice_lines_received_monthly() {
+    let run_id = "netinv-monthly-test";
+    let mut engine = build_with_invoices(run_id, 0x4E71_0001);
+
+    engine.run_ticks(29).unwrap();
+    assert_eq!(engine.store_network_invoice_count(run_id, None).unwrap(), 0);
+
+    engine.run_ticks(31).unwrap();
+    assert_eq!(
+        engine.store_network_invoice_count(run_id, None).unwrap(),
+        6,
+        "Expected three lines at tick 30 and three at tick 60"
+    );
+}
+
+/// Network errors beyond tolerance open recon exceptions on the card rail.
+#[test]
+fn network_errors_open_recon_exceptions() {
+    let run_id = "netinv-exception-test";
+    let mut engine = build_with_invoices(run_id, 0x4E71_0002);
+
+    engine.run_ticks(30).unwrap();
+
+    let open = engine.store_open_network_invoice_exceptions(run_id).unwrap();
+    assert!(!open.is_empty(), "Every line errs in the test config");
+    assert!(
+        open.iter().all(|inv| inv.exception_id.is_some() && inv.delta_amount > 1.0),
+        "Each discrepancy must be linked to a recon exception beyond card tolerance"
+    );
+    assert!(
+        open.iter().any(|inv| inv.line_type == "dispute_fee"),
+        "Duplicate dispute fees always exceed tolerance"
+    );
+}
+
+/// Claimed discrepancies are resolved as recovered or denied.
+#[test]
+fn recovery_claims_resolve_discrepancies() {
+    let run_id = "netinv-claim-test";
+    let mut engine = build_with_invoices(run_id, 0x4E71_0003);
+    engine.run_ticks(30).unwrap();
+
+    let open = engine.store_open_network_invoice_exceptions(run_id).unwrap();
+    assert!(!open.is_empty());
+    for inv in &open {
+        engine
+            .submit_command(PlayerCommand::ClaimNetworkRecovery {
+                invoice_id: inv.invoice_id.clone(),
+            })
+            .unwrap();
+    }
+    engine.run_ticks(1).unwrap();
+
+    let recovered = engine.store_network_invoice_count(run_id, Some("recovered")).unwrap();
+    let denied = engine.store_network_invoice_count(run_id, Some("claim_denied")).unwrap();
+    assert_eq!(recovered + denied, open.len() as i64);
+    if recovered > 0 {
+        assert!(engine.store_network_recovered_total(run_id).unwrap() > 0.0);
+    }
+}
+
+/// Discrepancies nobody claims are written off once the window closes.
+#[test]
+fn unclaimed_discrepancies_written_off() {
+    let run_id = "netinv-writeoff-test";
+    let mut engine = build_with_invoices(run_id, 0x4E71_0004);
+    engine.run_ticks(30).unwrap();
+
+    let first_period = engine.store_open_network_invoice_exceptions(run_id).unwrap().len() as i64;
+    assert!(first_period > 0);
+
+    // 60-tick recovery window: tick-30 errors expire at tick 91.
+    engine.run_ticks(61).unwrap();
+
+    assert!(
+        engine.store_network_invoice_count(run_id, Some("written_off")).unwrap() >= first_period,
+        "Tick-30 discrepancies should be written off after the window"
+    );
+    assert_eq!(engine.store_network_recovered_total(run_id).unwrap(), 0.0);
+}
@@ -0,0 +1,93 @@
+//! Offer targeting guardrail tests — Phase 4.5.
+//!
+//! Tests cover: an untargeted launch passing, a policy that excludes only
+//! majority-minority zips being blocked, a disparate-but-mixed policy
+//! launching with a warning, an unassessable zip exclusion failing closed.
+
+use fincrime_core::{command::PlayerCommand, engine::SimEngine};
+
+const CAMPAIGN: &str = "campaign_cash_50";
+
+fn build_with_guardrail(run_id: &str, seed: u64) -> SimEngine {
+    SimEngine::build_test_with_offer_guardrail(run_id.to_string(), seed)
+        .expect("build test engine with offer guardrail")
+}
+
+fn launch(engine: &mut SimEngine, exclude_zip_prefixes: Vec<String>) {
+    engine
+        .submit_command(PlayerCommand::LaunchOffer {
+            offer_id: CAMPAIGN.into(),
+            exclude_zip_prefixes,
+        })
+        .unwrap();
+    engine.run_ticks(1).unwrap();
+}
+
+/// No geographic exclusions: the guardrail passes and the whole book is targeted.
+#[test]
+fn untargeted_launch_passes() {
+    let run_id = "guardrail-pass-test";
+    let mut engine = build_with_guardrail(run_id, 0x6A4D_0001);
+    engine.run_ticks(1).unwrap();
+
+    launch(&mut engine, vec![]);
+
+    assert_eq!(engine.store_offer_guardrail_assessment_count(run_id, "pass").unwrap(), 1);
+    assert!(
+        engine.store_offer_match_count(run_id, CAMPAIGN).unwrap() > 0,
+        "Expected the campaign to reach existing customers"
+    );
+}
+
+/// Excluding every majority-minority zip is redlining: the launch is blocked.
+#[test]
+fn excluding_minority_zips_is_blocked() {
+    let run_id = "guardrail-block-test";
+    let mut engine = build_with_guardrail(run_id, 0x6A4D_0002);
+    engine.run_ticks(1).unwrap();
+
+    let protected = engine.store_zip_codes_by_group(run_id, true).unwrap();
+    assert!(!protected.is_empty(), "Expected majority-minority zips in the book");
+
+    launch(&mut engine, protected);
+
+    assert_eq!(engine.store_offer_guardrail_assessment_count(run_id, "block").unwrap(), 1);
+    assert_eq!(engine.store_offer_match_count(run_id, CAMPAIGN).unwrap(), 0);
+}
+
+/// A policy that excludes protected zips more often than control zips, but
+/// not exclusively, launches over a warning.
+#[test]
+fn disparate_exclusion_warns_but_launches() {
+    let run_id = "guardrail-warn-test";
+    let mut engine = build_with_guardrail(run_id, 0x6A4D_0003);
+    engine.run_ticks(1).unwrap();
+
+    // All protected zips plus roughly two thirds of control zips:
+    // exclusion ratio ≈ 1.0 / 0.65, between warn (1.25) and block (2.0).
+    let mut excluded = engine.store_zip_codes_by_group(run_id, true).unwrap();
+    let control = engine.store_zip_codes_by_group(run_id, false).unwrap();
+    let keep = control.len() * 35 / 100;
+    excluded.extend(control.into_iter().skip(keep));
+
+    launch(&mut engine, excluded);
+
+    assert_eq!(engine.store_offer_guardrail_assessment_count(run_id, "warn").unwrap(), 1);
+    assert!(
+        engine.store_offer_match_count(run_id, CAMPAIGN).unwrap() > 0,
+        "A warned launch still goes out to the included zips"
+    );
+}
+
+/// A zip exclusion submitted before any zip demographics exist cannot be
+/// assessed, so the launch is blocked rather than waved through.
+#[test]
+fn unassessed_exclusion_is_blocked() {
+    let run_id = "guardrail-unassessed-test";
+    let mut engine = build_with_guardrail(run_id, 0x6A4D_0004);
+
+    launch(&mut engine, vec!["1".into(), "2".into()]);
+
+    assert_eq!(engine.store_offer_guardrail_assessment_count(run_id, "unassessed").unwrap(), 1);
+    assert_eq!(engine.store_offer_match_count(run_id, CAMPAIGN).unwrap(), 0);
+}
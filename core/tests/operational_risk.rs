@@ -0,0 +1,192 @@
+//! Operational risk tests — Phase 4.61.
+//!
+//! Tests cover: each loss source landing once under its Basel category,
+//! and losses the bank did not bear left out; exam fines all reaching the
+//! record; the quarterly capital estimate following the standardised
+//! formula and reaching the P&L; a deposit run unable to draw on the
+//! capital held; and the record staying off unless enabled.
+
+use fincrime_core::{
+    config::SimConfig,
+    deposit_run::DepositRun,
+    engine::SimEngine,
+    store::{check_fraud::CheckItemRow, ReconExceptionRow, SimStore},
+};
+
+fn check_item(item_id: &str, tick: u64, loss_bearer: &str, loss: f64) -> CheckItemRow {
+    CheckItemRow {
+        item_id: item_id.into(),
+        account_id: "acct-test".into(),
+        tick: tick - 1,
+        side: "deposited".into(),
+        channel: "atm".into(),
+        drawer: "drawer-test".into(),
+        serial: 1001,
+        payee: "payee-test".into(),
+        amount: loss,
+        typology: "counterfeit".into(),
+        txn_id: format!("txn-{item_id}"),
+        rule: None,
+        alert_id: None,
+        deadline_tick: tick,
+        status: "returned".into(),
+        resolved_tick: Some(tick),
+        loss_bearer: Some(loss_bearer.into()),
+        loss,
+    }
+}
+
+/// Losses booked by fraud and reconciliation are each recorded once,
+/// under their category, however long the run goes on; a loss passed to
+/// another bank is not the bank's.
+#[test]
+fn losses_land_once_under_their_category() {
+    let mut engine = SimEngine::build_test_with_operational_risk("oprisk-sources-test".into(), 42).unwrap();
+    let run_id = engine.run_id.clone();
+    engine.run_ticks(3).unwrap();
+    engine.store.insert_check_item(&run_id, &check_item("CHK-BANK", 3, "bank", 2_500.0)).unwrap();
+    engine.store.insert_check_item(&run_id, &check_item("CHK-OTHER", 3, "paying_bank", 900.0)).unwrap();
+    engine
+        .store
+        .insert_recon_exception(&ReconExceptionRow {
+            exception_id: "EXC-TEST".into(),
+            run_id: run_id.clone(),
+            rail_id: "ACH".into(),
+            tick_detected: 2,
+            tick_resolved: Some(3),
+            status: "written_off".into(),
+            delta_amount: 410.0,
+            internal_total: 10_410.0,
+            external_total: 10_000.0,
+            item_count_delta: None,
+            suspected_cause: Some("unknown".into()),
+            assigned_to: None,
+            resolution_notes: None,
+            resolution_type: Some("write_off".into()),
+            write_off_amount: 410.0,
+        })
+        .unwrap();
+    engine.run_ticks(10).unwrap();
+
+    let losses: Vec<_> = engine
+        .store
+        .loss_events(&run_id)
+        .unwrap()
+        .into_iter()
+        .filter(|l| l.tick == 3)
+        .map(|l| (l.source, l.source_id, l.basel_category, l.amount))
+        .collect();
+    assert_eq!(
+        losses,
+        vec![
+            ("check_item".into(), "CHK-BANK".into(), "external_fraud".into(), 2_500.0),
+            ("recon_exception".into(), "EXC-TEST".into(), "execution_delivery_process_management".into(), 410.0),
+        ]
+    );
+    assert_eq!(
+        engine.store.loss_totals_by_category(&run_id, 3, 3).unwrap(),
+        vec![
+            ("external_fraud".to_string(), 2_500.0),
+            ("execution_delivery_process_management".to_string(), 410.0),
+        ]
+    );
+    let recorded = engine.store.count_events_in_range(&run_id, 0, 13, "operational_loss_recorded").unwrap();
+    assert_eq!(recorded, engine.store.loss_events(&run_id).unwrap().len() as i64);
+}
+
+/// Every exam fine reaches the record as a clients, products and
+/// business practices loss on the tick it was levied.
+#[test]
+fn exam_fines_reach_the_record() {
+    let mut engine = SimEngine::build_test_with_operational_risk("oprisk-exam-test".into(), 42).unwrap();
+    let run_id = engine.run_id.clone();
+    engine.run_ticks(60).unwrap();
+
+    let fines: Vec<_> = engine
+        .store
+        .loss_events(&run_id)
+        .unwrap()
+        .into_iter()
+        .filter(|l| l.source == "exam_finding")
+        .collect();
+    assert!(!fines.is_empty());
+    assert!(fines.iter().all(|l| l.basel_category == "clients_products_business_practices"));
+    let total = fines.iter().fold(0.0, |sum, l| sum + l.amount);
+    assert!((total - engine.store.exam_fine_total(&run_id).unwrap()).abs() < 1e-6);
+}
+
+/// At the quarter's close the capital estimate is the business indicator
+/// component scaled by the internal loss multiplier, and the P&L reports
+/// it with the quarter's losses.
+#[test]
+fn capital_estimate_follows_the_formula() {
+    let mut engine = SimEngine::build_test_with_operational_risk("oprisk-capital-test".into(), 42).unwrap();
+    let run_id = engine.run_id.clone();
+    engine.run_ticks(90).unwrap();
+
+    let pnl = engine.store.latest_pnl_snapshot(&run_id).unwrap().unwrap();
+    let capital = engine.store.latest_op_risk_capital(&run_id).unwrap().unwrap();
+    let losses = engine.store.loss_total_between(&run_id, 0, 89).unwrap();
+    assert!(losses > 0.0);
+
+    let bic = 0.12 * pnl.gross_income * 4.0;
+    let loss_component = 15.0 * losses * 365.0 / 90.0;
+    let ilm = (std::f64::consts::E - 1.0 + (loss_component / bic).powf(0.8)).ln();
+    assert_eq!(capital.tick, 90);
+    assert!((capital.bic - bic).abs() < 1e-6);
+    assert!((capital.loss_component - loss_component).abs() < 1e-6);
+    assert!((capital.ilm - ilm).abs() < 1e-12);
+    assert!((capital.capital - bic * ilm).abs() < 1e-6);
+
+    assert_eq!(pnl.op_risk_capital, capital.capital);
+    assert!((pnl.operational_loss - losses).abs() < 1e-6);
+    assert_eq!(engine.store.count_events_in_range(&run_id, 90, 90, "op_risk_capital_estimated").unwrap(), 1);
+}
+
+/// Each day's liquidity position carries the latest estimate, and the
+/// capital held against operational risk counts for nothing in a run.
+#[test]
+fn deposit_run_cannot_draw_on_op_risk_capital() {
+    let run_id = "oprisk-deposit-run-test";
+    let path = format!("./test_{run_id}.db");
+    let _ = std::fs::remove_file(&path);
+    let store = SimStore::open(&path).unwrap();
+    store.migrate().unwrap();
+    store.insert_run(run_id, 42, "0.1.0-test").unwrap();
+    let mut config = SimConfig::default_test();
+    config.operational_risk.enabled = true;
+    config.regulatory_exam.enabled = true;
+    config.regulatory_exam.exam_interval_ticks = 20;
+    config.regulatory_exam.exam_duration_ticks = 5;
+    config.deposit_run.enabled = true;
+    let deposit_run = DepositRun::new(run_id.into(), config.deposit_run.clone());
+    let mut engine = SimEngine::build_with_config(run_id.into(), 42, &store, config).unwrap();
+    engine.run_ticks(92).unwrap();
+
+    let capital = engine.store.latest_op_risk_capital(run_id).unwrap().unwrap().capital;
+    let positions = engine.store.liquidity_positions(run_id).unwrap();
+    assert!(positions.iter().filter(|p| p.tick <= 90).all(|p| p.op_risk_capital == 0.0));
+    assert!(positions.iter().filter(|p| p.tick > 90).all(|p| p.op_risk_capital == capital));
+
+    let mut position = positions.last().unwrap().clone();
+    position.capital = 0.08 * position.deposits;
+    let held = deposit_run.severity(&position, 50.0);
+    position.op_risk_capital = 0.0;
+    assert!(held > deposit_run.severity(&position, 50.0));
+    drop(engine);
+    let _ = std::fs::remove_file(&path);
+}
+
+/// The test default records no losses and reports no capital.
+#[test]
+fn operational_risk_is_opt_in() {
+    let run_id = "oprisk-off-test";
+    let mut engine = SimEngine::build_test_with_regulatory_exam(run_id.into(), 42).unwrap();
+    engine.run_ticks(90).unwrap();
+
+    assert!(engine.store.exam_fine_total(run_id).unwrap() > 0.0);
+    assert!(engine.store.loss_events(run_id).unwrap().is_empty());
+    assert_eq!(engine.store.latest_op_risk_capital(run_id).unwrap(), None);
+    let pnl = engine.store.latest_pnl_snapshot(run_id).unwrap().unwrap();
+    assert_eq!((pnl.operational_loss, pnl.op_risk_capital), (0.0, 0.0));
+}
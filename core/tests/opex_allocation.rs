@@ -0,0 +1,132 @@
+//! Opex allocation tests — Phase 4.63.
+//!
+//! Tests cover: each pool split over the segments in proportion to its
+//! driver; segment P&L carrying exactly the pools' split; the rules
+//! recorded once and applied every quarter after; bad rules rejected
+//! when the config is checked; and the single customer-share charge
+//! kept while disabled.
+
+use fincrime_core::{config::SimConfig, engine::SimEngine};
+
+/// Every pool is spread in full, each segment's share its driver value
+/// over the total: active customers, the quarter's transactions, or the
+/// desk's heads.
+#[test]
+fn pools_split_by_their_drivers() {
+    let mut engine = SimEngine::build_test_with_opex_allocation("opex-split-test".into(), 42).unwrap();
+    let run_id = engine.run_id.clone();
+    engine.run_ticks(91).unwrap();
+
+    let config = SimConfig::default_test();
+    let rows = engine.store.opex_allocations(&run_id, 90).unwrap();
+    for pool in &config.opex_allocation.pools {
+        let split: Vec<_> = rows.iter().filter(|r| r.pool == pool.pool).collect();
+        assert_eq!(split.len(), config.segments.len(), "{}", pool.pool);
+        let total = split.iter().fold(0.0, |sum, r| sum + r.driver_value);
+        let amount = split.iter().fold(0.0, |sum, r| sum + r.amount);
+        assert!(total > 0.0, "{}", pool.pool);
+        assert!((amount - pool.quarterly_cost).abs() < 1e-6, "{}", pool.pool);
+        for row in split {
+            assert_eq!(row.driver, pool.driver);
+            assert!((row.share - row.driver_value / total).abs() < 1e-12);
+            let expected = match pool.driver.as_str() {
+                "customers" => engine.store.segment_customer_count(&run_id, &row.segment, "active").unwrap() as f64,
+                "transactions" => {
+                    engine.store.segment_activity(&run_id, &row.segment, 1, 90).unwrap().transaction_count as f64
+                }
+                _ => pool.headcount.get(&row.segment).copied().unwrap_or(0.0),
+            };
+            assert_eq!(row.driver_value, expected, "{} {}", pool.pool, row.segment);
+        }
+    }
+}
+
+/// A segment's allocated opex is the sum of its pool shares, and its
+/// total cost carries it.
+#[test]
+fn segment_pnl_carries_the_split() {
+    let mut engine = SimEngine::build_test_with_opex_allocation("opex-segment-test".into(), 42).unwrap();
+    let run_id = engine.run_id.clone();
+    engine.run_ticks(91).unwrap();
+
+    let rows = engine.store.opex_allocations(&run_id, 90).unwrap();
+    let segments = engine.store.segment_pnls_at_tick(&run_id, 90).unwrap();
+    assert!(!segments.is_empty());
+    for seg in segments.iter().filter(|s| s.active_customers > 0) {
+        let split = rows.iter().filter(|r| r.segment == seg.segment).fold(0.0, |sum, r| sum + r.amount);
+        assert!((seg.allocated_opex - split).abs() < 1e-6, "{}", seg.segment);
+        let other = seg.acquisition_cost
+            + seg.servicing_cost
+            + seg.complaint_cost
+            + seg.retention_cost
+            + seg.churn_replacement_cost;
+        assert!((seg.total_cost - other - split).abs() < 1e-6, "{}", seg.segment);
+    }
+}
+
+/// The rules are recorded the first quarter and the second quarter
+/// applies the same ones.
+#[test]
+fn recorded_rules_hold_for_the_run() {
+    let mut engine = SimEngine::build_test_with_opex_allocation("opex-rules-test".into(), 42).unwrap();
+    let run_id = engine.run_id.clone();
+    engine.run_ticks(181).unwrap();
+
+    let config = SimConfig::default_test();
+    let rules = engine.store.opex_allocation_rules(&run_id).unwrap();
+    assert_eq!(rules.iter().map(|(pool, _)| pool.clone()).collect::<Vec<_>>(), config.opex_allocation.pools);
+    assert!(rules.iter().all(|(_, tick)| *tick == 90));
+
+    let drivers = |tick| {
+        let mut d: Vec<(String, String)> = engine
+            .store
+            .opex_allocations(&run_id, tick)
+            .unwrap()
+            .into_iter()
+            .map(|r| (r.pool, r.driver))
+            .collect();
+        d.dedup();
+        d
+    };
+    assert_eq!(drivers(90), drivers(180));
+    assert_eq!(drivers(180).len(), config.opex_allocation.pools.len());
+}
+
+/// Unknown drivers, headcount for segments the scenario lacks and
+/// negative costs are rejected.
+#[test]
+fn bad_rules_are_rejected() {
+    let config = SimConfig::default_test();
+    assert!(config.opex_allocation.validate(&config.segments).is_ok());
+
+    let mut bad = config.opex_allocation.clone();
+    bad.pools[0].driver = "vibes".into();
+    assert!(bad.validate(&config.segments).unwrap_err().to_string().contains("vibes"));
+
+    let mut bad = config.opex_allocation.clone();
+    let desk = bad.pools.iter_mut().find(|p| p.driver == "headcount").unwrap();
+    desk.headcount.insert("martians".into(), 2.0);
+    assert!(bad.validate(&config.segments).unwrap_err().to_string().contains("martians"));
+
+    let mut bad = config.opex_allocation.clone();
+    bad.pools[0].quarterly_cost = -1.0;
+    assert!(bad.validate(&config.segments).is_err());
+}
+
+/// The test default charges each segment its customer share of the
+/// flat allocation and records no split.
+#[test]
+fn opex_allocation_is_opt_in() {
+    let run_id = "opex-off-test";
+    let mut engine = SimEngine::build_test(run_id.into(), 42).unwrap();
+    engine.run_ticks(91).unwrap();
+
+    assert!(engine.store.opex_allocations(run_id, 90).unwrap().is_empty());
+    assert!(engine.store.opex_allocation_rules(run_id).unwrap().is_empty());
+    let segments = engine.store.segment_pnls_at_tick(run_id, 90).unwrap();
+    let total = segments.iter().fold(0.0, |sum, s| sum + s.active_customers as f64);
+    for seg in segments.iter().filter(|s| s.active_customers > 0) {
+        let expected = 45_000.0 * seg.active_customers as f64 / total;
+        assert!((seg.allocated_opex - expected).abs() < 1e-6, "{}", seg.segment);
+    }
+}
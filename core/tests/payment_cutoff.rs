@@ -0,0 +1,111 @@
+//! Payment cutoff tests — Phase 4.8.
+//!
+//! Tests cover: items initiated after a rail's cutoff rolling to the next
+//! processing day, "missing payment" complaints, and the reconciliation
+//! timing difference the roll creates.
+
+use fincrime_core::engine::SimEngine;
+
+fn build_with_cutoffs(run_id: &str, seed: u64) -> SimEngine {
+    SimEngine::build_test_with_payment_cutoffs(run_id.to_string(), seed)
+        .expect("build test engine with payment cutoffs")
+}
+
+/// Only items initiated at or after the 18:00 wire cutoff roll, and they
+/// settle on the next processing day.
+#[test]
+fn late_items_roll_to_next_day() {
+    let run_id = "cutoff-roll-test";
+    let mut engine = build_with_cutoffs(run_id, 0xC07F_0001);
+
+    engine.run_ticks(20).unwrap();
+
+    let rolls = engine.store_cutoff_rolls(run_id, "wire").unwrap();
+    assert!(!rolls.is_empty(), "Expected some wires initiated after the cutoff");
+    for roll in &rolls {
+        assert!(roll.initiated_hour >= 18 && roll.initiated_hour < 22);
+        assert_eq!(roll.processing_tick, roll.booked_tick + 1);
+        if roll.processing_tick <= 20 {
+            assert_eq!(
+                engine.store_transaction_settlement_status(run_id, &roll.txn_id).unwrap(),
+                "settled",
+                "A rolled item settles on the next processing day"
+            );
+        }
+    }
+
+    // Rails without a cutoff process everything same-day.
+    assert!(engine.store_cutoff_rolls(run_id, "RTP").unwrap().is_empty());
+}
+
+/// Only outbound customer payments roll; payroll, cash withdrawals and fees
+/// posted on ACH always settle on the day they are booked.
+#[test]
+fn only_outbound_payments_roll() {
+    let run_id = "cutoff-category-test";
+    let mut engine = build_with_cutoffs(run_id, 0xC07F_0004);
+
+    engine.run_ticks(20).unwrap();
+
+    let rolls = engine.store_cutoff_rolls(run_id, "ACH").unwrap();
+    assert!(!rolls.is_empty(), "Expected some ACH payments initiated after the cutoff");
+    for roll in &rolls {
+        assert_eq!(roll.direction, "debit");
+        assert_eq!(
+            engine.store.transaction_category(run_id, &roll.txn_id).unwrap(),
+            "purchase"
+        );
+    }
+}
+
+/// Customers whose payment rolled complain about a "missing" payment.
+#[test]
+fn rolled_payments_draw_complaints() {
+    let run_id = "cutoff-complaint-test";
+    let mut engine = build_with_cutoffs(run_id, 0xC07F_0002);
+
+    engine.run_ticks(20).unwrap();
+
+    let rolled = engine.store_cutoff_rolls(run_id, "wire").unwrap().len()
+        + engine.store_cutoff_rolls(run_id, "ACH").unwrap().len();
+    let complaints = engine
+        .store
+        .complaint_count_by_category(run_id, "missing_payment", 0, 20)
+        .unwrap();
+    assert!(rolled > 0);
+    assert_eq!(complaints, rolled as i64, "Every roll complains in the test config");
+}
+
+/// A booked-vs-settled gap matching the day's net roll is classified as a
+/// cutoff timing difference; only small ones auto-clear.
+#[test]
+fn rolls_create_clearing_timing_differences() {
+    let run_id = "cutoff-recon-test";
+    let mut engine = build_with_cutoffs(run_id, 0xC07F_0003);
+
+    engine.run_ticks(30).unwrap();
+
+    let cutoff_exceptions: Vec<_> = engine
+        .store
+        .get_recon_exceptions_by_rail(run_id, "wire")
+        .unwrap()
+        .into_iter()
+        .filter(|ex| ex.suspected_cause.as_deref() == Some("cutoff_timing"))
+        .collect();
+    assert!(!cutoff_exceptions.is_empty(), "Expected cutoff timing differences on wires");
+    let threshold = engine.store.get_recon_queue_config("wire").unwrap().auto_clear_threshold;
+    for ex in &cutoff_exceptions {
+        let net_roll = engine
+            .store
+            .net_cutoff_roll(run_id, "wire", ex.tick_detected - 1)
+            .unwrap();
+        assert!((ex.internal_total - ex.external_total - net_roll).abs() < 0.01);
+        if ex.delta_amount >= threshold {
+            assert_ne!(
+                ex.resolution_type.as_deref(),
+                Some("auto_clear"),
+                "Large cutoff differences are left for review"
+            );
+        }
+    }
+}
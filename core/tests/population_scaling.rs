@@ -0,0 +1,81 @@
+//! Population scaling tests — Phase 4.26.
+//!
+//! Tests cover: scaling validating its range and shrinking every
+//! customer-count threshold with the population, the sample keeping the
+//! configured segment mix exactly, a scale of 1.0 replaying the unscaled
+//! run, and a sampled run charging only its share of fixed costs.
+
+use fincrime_core::config::SimConfig;
+use fincrime_core::engine::SimEngine;
+use fincrime_core::protocol::UiState;
+
+/// Out-of-range scales are rejected; in range, customer counts shrink
+/// (never below one) and per-branch costs follow the sample.
+#[test]
+fn scaling_shrinks_count_thresholds() {
+    assert!(SimConfig::default_test().with_population_scale(0.0).is_err());
+    assert!(SimConfig::default_test().with_population_scale(1.5).is_err());
+    assert!(SimConfig::default_test().with_population_scale(f64::NAN).is_err());
+
+    let full = SimConfig::default_test();
+    let sample = SimConfig::default_test().with_population_scale(0.1).unwrap();
+    assert_eq!(sample.population_scale, 0.1);
+    assert_eq!(sample.initial_population, full.initial_population / 10);
+    assert!(sample.branch_network.min_customers_for_branch >= 1);
+    assert!(sample.branch_network.min_customers_for_branch < full.branch_network.min_customers_for_branch);
+    assert!(sample.fair_lending.min_group_size >= 1);
+    assert!(
+        (sample.branch_network.staff_monthly_cost - full.branch_network.staff_monthly_cost * 0.1).abs() < 1e-6
+    );
+}
+
+/// A 20% sample of the 70/20/10 test mix is exactly 7/2/1 customers.
+#[test]
+fn sample_keeps_segment_mix() {
+    let mut engine = SimEngine::build_test_with_population_scale("scale-mix-test".into(), 42, 0.2).unwrap();
+    engine.run_ticks(1).unwrap();
+
+    let count = |segment: &str| engine.store.segment_customer_count("scale-mix-test", segment, "active").unwrap();
+    assert_eq!(count("mass_market"), 7);
+    assert_eq!(count("small_business"), 2);
+    assert_eq!(count("premium"), 1);
+}
+
+/// Scale 1.0 is the unscaled run, draw for draw.
+#[test]
+fn full_scale_matches_unscaled_run() {
+    let mut unscaled = SimEngine::build_test("scale-base-test".into(), 42).unwrap();
+    let mut scaled = SimEngine::build_test_with_population_scale("scale-one-test".into(), 42, 1.0).unwrap();
+    unscaled.run_ticks(10).unwrap();
+    scaled.run_ticks(10).unwrap();
+
+    assert_eq!(scaled.population_scale(), 1.0);
+    assert_eq!(
+        scaled.store.customer_count("scale-one-test", "active").unwrap(),
+        unscaled.store.customer_count("scale-base-test", "active").unwrap(),
+    );
+    assert_eq!(
+        scaled.store.txn_count_total("scale-one-test").unwrap(),
+        unscaled.store.txn_count_total("scale-base-test").unwrap(),
+    );
+}
+
+/// A half-size bank carries about half the head-office cost, so the
+/// quarter's opex scales back up to the full run's; clients see the scale.
+#[test]
+fn sampled_run_charges_its_share_of_fixed_costs() {
+    let mut full = SimEngine::build_test("scale-full-test".into(), 42).unwrap();
+    let mut half = SimEngine::build_test_with_population_scale("scale-half-test".into(), 42, 0.5).unwrap();
+    full.run_ticks(92).unwrap();
+    half.run_ticks(92).unwrap();
+
+    let full_state = UiState::from_engine(&full).unwrap();
+    let half_state = UiState::from_engine(&half).unwrap();
+    assert_eq!(full_state.population_scale, 1.0);
+    assert_eq!(half_state.population_scale, 0.5);
+
+    let full_opex = full_state.pnl_history.last().expect("a quarter closed").opex;
+    let half_opex = half_state.pnl_history.last().expect("a quarter closed").opex;
+    let ratio = half_opex / full_opex;
+    assert!((0.4..0.6).contains(&ratio), "half-scale opex ratio {ratio}");
+}
@@ -0,0 +1,124 @@
+//! Post-run analysis report tests — Phase 4.19.
+//!
+//! Tests cover: takeover cases reconstructed from unauthorized entries
+//! with their losses and counterfactual, mule drains carrying avoidable
+//! loss past their first day, synthetic identities declined at onboarding
+//! counted as caught on the spot, and the report round-tripping as JSON.
+
+use fincrime_core::engine::SimEngine;
+use fincrime_core::post_mortem::{FraudCase, PostMortem};
+
+fn cases_of<'a>(report: &'a PostMortem, typology: &str) -> Vec<&'a FraudCase> {
+    report.cases.iter().filter(|c| c.typology == typology).collect()
+}
+
+/// Each case's loss is its entries that were not declined.
+fn timeline_loss(case: &FraudCase) -> f64 {
+    case.timeline
+        .iter()
+        .filter(|e| !e.description.contains("declined"))
+        .filter_map(|e| e.amount)
+        .sum()
+}
+
+/// Every taken-over customer becomes one case whose timeline lists the
+/// unauthorized pulls; the counterfactual splits the actual loss into
+/// what perfect detection would still have lost and what it would have
+/// saved.
+#[test]
+fn takeovers_reconstructed_with_counterfactual() {
+    let run_id = "post-mortem-takeover-test";
+    let mut engine = SimEngine::build_test_with_ach_takeovers(run_id.to_string(), 0x9057_0001).unwrap();
+    engine.run_ticks(30).unwrap();
+    let report = engine.post_mortem().unwrap();
+    assert_eq!(report.final_tick, engine.clock.current_tick);
+
+    let takeovers = cases_of(&report, "account_takeover");
+    assert!(!takeovers.is_empty(), "Takeovers ran");
+    for case in &takeovers {
+        assert!(case.first_tick <= case.last_tick);
+        assert!((case.loss - timeline_loss(case)).abs() < 1e-6);
+        assert!(case.avoidable_loss <= case.loss);
+        if let Some(detected) = case.detected_tick {
+            assert_eq!(case.latency, Some(detected - case.first_tick));
+        }
+    }
+
+    let cf = &report.counterfactual;
+    let actual: f64 = report.cases.iter().map(|c| c.loss).sum();
+    assert!((cf.actual_loss - actual).abs() < 1e-6);
+    assert!((cf.perfect_detection_loss + cf.avoidable_loss - cf.actual_loss).abs() < 1e-6);
+    assert!(cf.undetected_loss <= cf.actual_loss);
+}
+
+/// A mule link drains the account over several days; everything after
+/// the first day is loss detection on sight would have avoided.
+#[test]
+fn mule_drains_carry_avoidable_loss() {
+    let run_id = "post-mortem-mule-test";
+    let mut engine = SimEngine::build_test_with_external_links(run_id.to_string(), 0x9057_0002).unwrap();
+    engine.run_ticks(40).unwrap();
+    let report = engine.post_mortem().unwrap();
+
+    let drains = cases_of(&report, "mule_drain");
+    assert!(!drains.is_empty(), "Mule links drained accounts");
+    let multi_day = drains.iter().filter(|c| c.last_tick > c.first_tick).collect::<Vec<_>>();
+    assert!(!multi_day.is_empty());
+    for case in multi_day {
+        let first_day: f64 = case
+            .timeline
+            .iter()
+            .filter(|e| e.tick == case.first_tick && !e.description.contains("declined"))
+            .filter_map(|e| e.amount)
+            .sum();
+        assert!((case.avoidable_loss - (case.loss - first_day)).abs() < 1e-6);
+    }
+}
+
+/// Synthetic identities turned away by the IDV vendor are cases caught
+/// at onboarding with zero latency; the latency buckets count every
+/// detected case once.
+#[test]
+fn declined_synthetics_caught_at_onboarding() {
+    let run_id = "post-mortem-synthetic-test";
+    let mut engine =
+        SimEngine::build_test_with_idv_vendor(run_id.to_string(), 0x9057_0003, "premium").unwrap();
+    engine.run_ticks(10).unwrap();
+    let report = engine.post_mortem().unwrap();
+
+    let synthetics = cases_of(&report, "synthetic_identity");
+    let declined: Vec<_> = synthetics
+        .iter()
+        .filter(|c| c.timeline.iter().any(|e| e.description.contains("declined")))
+        .collect();
+    assert!(!declined.is_empty(), "The premium vendor declines synthetics");
+    for case in &declined {
+        assert_eq!(case.latency, Some(0));
+        assert_eq!(case.loss, 0.0);
+    }
+
+    let latency = &report.latency;
+    assert_eq!(latency.detected + latency.undetected, report.cases.len());
+    assert_eq!(latency.buckets.iter().map(|b| b.count).sum::<usize>(), latency.detected);
+    assert!(latency.buckets[0].count >= declined.len(), "Declines land in the same-day bucket");
+    assert_eq!(latency.p50.is_some(), latency.detected > 0);
+}
+
+/// The report serializes to JSON and back intact, and the undetected
+/// cases it lists match the latency distribution's count.
+#[test]
+fn report_round_trips_as_json() {
+    let run_id = "post-mortem-json-test";
+    let mut engine = SimEngine::build_test_with_ach_takeovers(run_id.to_string(), 0x9057_0004).unwrap();
+    engine.run_ticks(20).unwrap();
+    let report = engine.post_mortem().unwrap();
+    assert_eq!(report.undetected().count(), report.latency.undetected);
+
+    let json = serde_json::to_string_pretty(&report).unwrap();
+    let back: PostMortem = serde_json::from_str(&json).unwrap();
+    assert_eq!(back.run_id, run_id);
+    let ids = |r: &PostMortem| r.cases.iter().map(|c| c.case_id.clone()).collect::<Vec<_>>();
+    assert_eq!(ids(&back), ids(&report));
+    assert_eq!(back.latency.buckets.len(), 4);
+    assert!((back.counterfactual.actual_loss - report.counterfactual.actual_loss).abs() < 1e-6);
+}
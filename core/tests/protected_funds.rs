@@ -0,0 +1,96 @@
+//! Benefit payment and protected funds tests — Phase 4.2.
+//!
+//! Tests cover: recipient enrolment and monthly benefit deposits,
+//! enforcement blocking debits against protected funds, violations
+//! feeding the UDAAP score when enforcement is off, determinism.
+
+use fincrime_core::engine::SimEngine;
+
+fn build_with_protection(run_id: &str, seed: u64, enforce: bool) -> SimEngine {
+    SimEngine::build_test_with_protected_funds(run_id.to_string(), seed, enforce)
+        .expect("build test engine with protected funds")
+}
+
+/// Recipients are enrolled on the first tick and paid on the benefit day.
+#[test]
+fn benefit_recipients_receive_monthly_deposits() {
+    let run_id = "pf-deposit-test";
+    let mut engine = build_with_protection(run_id, 0x5EC0_0001, true);
+
+    engine.run_ticks(35).unwrap();
+
+    let recipients = engine.store_benefit_recipient_count(run_id).unwrap();
+    assert!(recipients > 0, "Expected benefit recipients at an 80% share");
+
+    // Benefit days at ticks 3 and 33: at most two deposits per recipient.
+    let deposits = engine.store_benefit_deposit_count(run_id).unwrap();
+    assert!(deposits >= recipients, "Expected every recipient paid at least once");
+    assert!(
+        deposits <= recipients * 2,
+        "Expected at most two deposits per recipient, got {deposits} for {recipients}"
+    );
+}
+
+/// With enforcement on, no debit is ever taken from protected funds.
+#[test]
+fn enforcement_blocks_protected_debits() {
+    let run_id = "pf-enforce-test";
+    let mut engine = build_with_protection(run_id, 0x5EC0_0002, true);
+
+    engine.run_ticks(60).unwrap();
+
+    let violations = engine.store_protected_funds_event_count(run_id, "violation").unwrap();
+    assert_eq!(violations, 0, "Enforced protection must not produce violations");
+
+    let blocked = engine.store_protected_funds_event_count(run_id, "blocked").unwrap()
+        + engine.store_protected_funds_event_count(run_id, "partial").unwrap();
+    assert!(blocked > 0, "Expected some debits blocked or reduced by protection");
+}
+
+/// With enforcement off, debits hit protected funds and raise UDAAP risk.
+#[test]
+fn unenforced_protection_books_violations() {
+    let run_id = "pf-violation-test";
+    let mut engine = build_with_protection(run_id, 0x5EC0_0003, false);
+
+    engine.run_ticks(60).unwrap();
+
+    let violations = engine.store_protected_funds_event_count(run_id, "violation").unwrap();
+    assert!(violations > 0, "Expected protected-funds violations without enforcement");
+
+    let udaap = engine.store_udaap_score(run_id).unwrap();
+    assert!(udaap > 0.0, "Violations should raise the UDAAP score, got {udaap}");
+}
+
+/// Overdraft fees on recipient accounts with no protected deposits in the
+/// lookback window are ordinary fees, not violations.
+#[test]
+fn unprotected_overdraft_fees_are_not_violations() {
+    let run_id = "pf-unprotected-test";
+    let mut engine = build_with_protection(run_id, 0x5EC0_0005, false);
+
+    engine.run_ticks(60).unwrap();
+
+    let (fees, violations) = engine.store.unprotected_debit_counts(run_id, "overdraft_fee").unwrap();
+    assert!(fees > 0, "Expected overdraft fees outside the protected window");
+    assert_eq!(violations, 0, "Nothing was protected, so nothing was violated");
+}
+
+/// Determinism: identical seeds produce identical protected-funds outcomes.
+#[test]
+fn protected_funds_determinism() {
+    const SEED: u64 = 0x5EC0_0004;
+    let run_id = format!("pf-det-{SEED}");
+
+    let mut engine_a = build_with_protection(&run_id, SEED, false);
+    let mut engine_b = build_with_protection(&run_id, SEED, false);
+
+    engine_a.run_ticks(40).unwrap();
+    engine_b.run_ticks(40).unwrap();
+
+    for outcome in ["applied", "partial", "blocked", "violation"] {
+        let a = engine_a.store_protected_funds_event_count(&run_id, outcome).unwrap();
+        let b = engine_b.store_protected_funds_event_count(&run_id, outcome).unwrap();
+        assert_eq!(a, b, "Protected-funds '{outcome}' count diverged: {a} vs {b}");
+    }
+}
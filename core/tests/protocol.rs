@@ -0,0 +1,86 @@
+//! IPC protocol tests — Phase 4.22.
+//!
+//! Tests cover: the published schema matching the one generated from the
+//! message types, the messages existing clients send still parsing,
+//! version checks refusing a mismatched client, and the state a run
+//! produces carrying every field the schema requires.
+
+use fincrime_core::engine::SimEngine;
+use fincrime_core::protocol::{self, IpcCommand, UiState, PROTOCOL_VERSION};
+
+const PUBLISHED_SCHEMA: &str = include_str!("../../docs/ipc_protocol.schema.json");
+
+/// `docs/ipc_protocol.schema.json` is what `schema()` generates; a change
+/// to the message types without regenerating it fails here.
+#[test]
+fn published_schema_matches_generated() {
+    let published: serde_json::Value = serde_json::from_str(PUBLISHED_SCHEMA).unwrap();
+    assert_eq!(
+        published,
+        protocol::schema(),
+        "Regenerate with `sim-runner --print-schema > docs/ipc_protocol.schema.json`",
+    );
+    assert_eq!(published["protocol_version"], PROTOCOL_VERSION);
+}
+
+/// The lines the Godot client sends parse, and a version query may omit
+/// the client's own version.
+#[test]
+fn client_messages_parse() {
+    let parse = |line: &str| serde_json::from_str::<IpcCommand>(line).unwrap();
+    assert!(matches!(parse(r#"{"type":"get_state"}"#), IpcCommand::GetState { summary_only: false }));
+    assert!(matches!(
+        parse(r#"{"type":"tick","count":2}"#),
+        IpcCommand::Tick { count: 2, summary_only: false }
+    ));
+    assert!(matches!(
+        parse(r#"{"type":"command","cmd":"resolve_complaint","payload":{"complaint_id":"c-1"}}"#),
+        IpcCommand::Command { cmd, summary_only: false, .. } if cmd == "resolve_complaint"
+    ));
+    assert!(matches!(parse(r#"{"type":"quit"}"#), IpcCommand::Quit));
+    assert!(matches!(
+        parse(r#"{"type":"get_protocol_version"}"#),
+        IpcCommand::GetProtocolVersion { client_version: None }
+    ));
+    assert!(matches!(
+        parse(r#"{"type":"get_protocol_version","client_version":1}"#),
+        IpcCommand::GetProtocolVersion { client_version: Some(1) }
+    ));
+    assert!(serde_json::from_str::<IpcCommand>(r#"{"type":"launch"}"#).is_err());
+}
+
+/// A matching or unstated client version gets the server's version; a
+/// different one is refused with a code and the version to upgrade to.
+#[test]
+fn mismatched_client_is_refused() {
+    assert_eq!(protocol::check_client_version(None).unwrap().protocol_version, PROTOCOL_VERSION);
+    assert!(protocol::check_client_version(Some(PROTOCOL_VERSION)).is_ok());
+
+    let refusal = protocol::check_client_version(Some(PROTOCOL_VERSION + 1)).unwrap_err();
+    assert_eq!(refusal.code.as_deref(), Some("protocol_mismatch"));
+    assert_eq!(refusal.protocol_version, Some(PROTOCOL_VERSION));
+    let json: serde_json::Value = serde_json::to_value(&refusal).unwrap();
+    assert!(json["error"].as_str().unwrap().contains("mismatch"), "Clients key errors off `error`");
+}
+
+/// A run's state serializes with every field the published schema
+/// requires and nothing it does not describe.
+#[test]
+fn ui_state_matches_schema_fields() {
+    let run_id = "protocol-state-test";
+    let mut engine = SimEngine::build_test(run_id.to_string(), 0x1DC_0001).unwrap();
+    engine.run_ticks(3).unwrap();
+    let state = UiState::from_engine(&engine).unwrap();
+    assert_eq!(state.run_id, run_id);
+    assert_eq!(state.tick, 3);
+
+    let json = serde_json::to_value(&state).unwrap();
+    let schema = protocol::schema();
+    let state_schema = &schema["outbound"]["state"];
+    let mut required: Vec<&str> =
+        state_schema["required"].as_array().unwrap().iter().map(|v| v.as_str().unwrap()).collect();
+    let mut fields: Vec<&str> = json.as_object().unwrap().keys().map(String::as_str).collect();
+    required.sort_unstable();
+    fields.sort_unstable();
+    assert_eq!(fields, required);
+}
@@ -0,0 +1,181 @@
+//! Quarter close tests — Phase 4.62.
+//!
+//! Tests cover: the close's accruals landing in the quarter's P&L; the
+//! P&L signed off only after every other checklist item; an adjustment
+//! on time restating the quarter while a late one waits for the next
+//! close, which also reverses the last one's accruals; a close left
+//! unsigned past its deadline going overdue; and the close staying off
+//! unless enabled.
+
+use fincrime_core::{command::PlayerCommand, engine::SimEngine};
+use serde_json::Value;
+
+fn events(engine: &SimEngine, event_type: &str) -> Vec<Value> {
+    (0..=engine.clock.current_tick)
+        .flat_map(|tick| engine.store.events_for_tick(&engine.run_id, tick).unwrap())
+        .filter(|e| e.event_type == event_type)
+        .map(|e| serde_json::from_str(&e.payload).unwrap())
+        .collect()
+}
+
+fn sign_off(engine: &mut SimEngine, period: &str, item: &str) {
+    engine
+        .submit_command(PlayerCommand::SignOffCloseItem { period: period.into(), item: item.into() })
+        .unwrap();
+}
+
+fn adjust(engine: &mut SimEngine, period: &str, amount: f64) {
+    engine
+        .submit_command(PlayerCommand::PostCloseAdjustment {
+            period: period.into(),
+            amount,
+            reason: "vendor invoice missed at close".into(),
+        })
+        .unwrap();
+}
+
+/// The close reserves against open provisional credit and accrues
+/// unsettled interchange; the quarter's profit is what it would have been
+/// without the close, plus the net the close booked.
+#[test]
+fn accruals_land_in_the_quarter_pnl() {
+    let mut engine = SimEngine::build_test_with_quarter_close("qc-accruals-test".into(), 42).unwrap();
+    let run_id = engine.run_id.clone();
+    engine.run_ticks(91).unwrap();
+    let mut plain = SimEngine::build_test("qc-accruals-plain-test".into(), 42).unwrap();
+    plain.run_ticks(91).unwrap();
+
+    let adjustments = engine.store.close_adjustments(&run_id, "Q1-Y1").unwrap();
+    let kinds: Vec<(&str, bool)> = adjustments.iter().map(|a| (a.kind.as_str(), a.amount > 0.0)).collect();
+    assert_eq!(kinds, [("provisional_credit_reserve", false), ("unposted_interchange", true)]);
+    let total = adjustments.iter().fold(0.0, |sum, a| sum + a.amount);
+
+    let pnl = engine.store.latest_pnl_snapshot(&run_id).unwrap().unwrap();
+    let without = plain.store.latest_pnl_snapshot(&plain.run_id).unwrap().unwrap();
+    assert_eq!(pnl.accrual_adjustment, total);
+    assert!((pnl.pre_tax_profit - (without.pre_tax_profit + total)).abs() < 1e-6);
+    let computed = &events(&engine, "quarterly_pnl_computed")[0];
+    assert_eq!(computed["pre_tax_profit"].as_f64(), Some(pnl.pre_tax_profit));
+
+    let close = engine.store.quarter_close(&run_id, "Q1-Y1").unwrap().unwrap();
+    assert_eq!((close.status.as_str(), close.due_tick, close.adjustment_total), ("open", 105, total));
+    let items: Vec<String> =
+        engine.store.close_checklist(&run_id, "Q1-Y1").unwrap().into_iter().map(|i| i.item).collect();
+    assert_eq!(items, ["accruals", "reconciliation", "disputes", "pnl"]);
+}
+
+/// The P&L cannot be approved while other items are open; once they are
+/// signed, approving it closes the quarter on time.
+#[test]
+fn pnl_is_signed_off_last() {
+    let mut engine = SimEngine::build_test_with_quarter_close("qc-signoff-test".into(), 42).unwrap();
+    let run_id = engine.run_id.clone();
+    engine.run_ticks(91).unwrap();
+
+    sign_off(&mut engine, "Q1-Y1", "pnl");
+    sign_off(&mut engine, "Q1-Y1", "accruals");
+    sign_off(&mut engine, "Q1-Y1", "accruals");
+    engine.run_ticks(1).unwrap();
+    for item in ["reconciliation", "disputes", "pnl"] {
+        sign_off(&mut engine, "Q1-Y1", item);
+    }
+    engine.run_ticks(1).unwrap();
+
+    let reasons: Vec<String> = events(&engine, "quarter_close_command_rejected")
+        .iter()
+        .map(|e| e["reason"].as_str().unwrap().to_string())
+        .collect();
+    assert_eq!(reasons, ["sign off the other items before the P&L", "accruals is already signed off"]);
+    assert_eq!(events(&engine, "close_item_signed_off").len(), 3);
+    let closed = events(&engine, "quarter_closed");
+    assert_eq!(closed.len(), 1);
+    assert_eq!(closed[0]["overdue"].as_bool(), Some(false));
+
+    let close = engine.store.quarter_close(&run_id, "Q1-Y1").unwrap().unwrap();
+    assert_eq!((close.status.as_str(), close.closed_tick), ("closed", Some(93)));
+    let checklist = engine.store.close_checklist(&run_id, "Q1-Y1").unwrap();
+    assert!(checklist.iter().all(|i| i.status == "signed_off"));
+}
+
+/// An adjustment before sign-off restates the quarter; one after waits
+/// for the next close, which books it with the reversal of the last
+/// close's accruals.
+#[test]
+fn late_adjustments_roll_into_the_next_close() {
+    let mut engine = SimEngine::build_test_with_quarter_close("qc-late-test".into(), 42).unwrap();
+    let run_id = engine.run_id.clone();
+    engine.run_ticks(91).unwrap();
+    let before = engine.store.latest_pnl_snapshot(&run_id).unwrap().unwrap();
+
+    adjust(&mut engine, "Q1-Y1", -1_200.0);
+    engine.run_ticks(1).unwrap();
+    let restated = engine.store.latest_pnl_snapshot(&run_id).unwrap().unwrap();
+    assert!((restated.pre_tax_profit - (before.pre_tax_profit - 1_200.0)).abs() < 1e-6);
+    assert!((restated.accrual_adjustment - (before.accrual_adjustment - 1_200.0)).abs() < 1e-6);
+
+    for item in ["accruals", "reconciliation", "disputes", "pnl"] {
+        sign_off(&mut engine, "Q1-Y1", item);
+    }
+    engine.run_ticks(1).unwrap();
+    adjust(&mut engine, "Q1-Y1", 800.0);
+    engine.run_ticks(1).unwrap();
+
+    let after_signoff = engine.store.latest_pnl_snapshot(&run_id).unwrap().unwrap();
+    assert_eq!(after_signoff.pre_tax_profit, restated.pre_tax_profit);
+    let close = engine.store.quarter_close(&run_id, "Q1-Y1").unwrap().unwrap();
+    assert_eq!((close.late_adjustment_count, close.late_adjustment_total), (1, 800.0));
+    let late: Vec<bool> =
+        events(&engine, "close_adjustment_posted").iter().map(|e| e["late"].as_bool().unwrap()).collect();
+    assert_eq!(late, [false, true]);
+
+    engine.run_ticks(88).unwrap();
+    let q1 = engine.store.close_adjustments(&run_id, "Q1-Y1").unwrap();
+    let accrued = q1
+        .iter()
+        .filter(|a| a.kind != "manual")
+        .fold(0.0, |sum, a| sum + a.amount);
+    let q2 = engine.store.close_adjustments(&run_id, "Q2-Y1").unwrap();
+    let reversal = q2.iter().find(|a| a.kind == "accrual_reversal").unwrap();
+    assert!((reversal.amount + accrued).abs() < 1e-9);
+    let rolled = q1.iter().find(|a| a.late).unwrap();
+    assert_eq!(rolled.booked_period.as_deref(), Some("Q2-Y1"));
+
+    let booked = q2.iter().fold(0.0, |sum, a| sum + a.amount) + rolled.amount;
+    let pnl = engine.store.latest_pnl_snapshot(&run_id).unwrap().unwrap();
+    assert_eq!(pnl.period, "Q2-Y1");
+    assert!((pnl.accrual_adjustment - booked).abs() < 1e-6);
+}
+
+/// A close still unsigned past its deadline goes overdue once, and an
+/// adjustment posted to it then is late.
+#[test]
+fn unsigned_close_goes_overdue() {
+    let mut engine = SimEngine::build_test_with_quarter_close("qc-overdue-test".into(), 42).unwrap();
+    let run_id = engine.run_id.clone();
+    engine.run_ticks(91).unwrap();
+    sign_off(&mut engine, "Q1-Y1", "accruals");
+    engine.run_ticks(20).unwrap();
+    adjust(&mut engine, "Q1-Y1", -300.0);
+    engine.run_ticks(1).unwrap();
+
+    let overdue = events(&engine, "quarter_close_overdue");
+    assert_eq!(overdue.len(), 1);
+    assert_eq!((overdue[0]["tick"].as_u64(), overdue[0]["pending_items"].as_u64()), (Some(106), Some(3)));
+    let close = engine.store.quarter_close(&run_id, "Q1-Y1").unwrap().unwrap();
+    assert!(close.overdue);
+    assert_eq!(close.status, "open");
+    assert_eq!((close.late_adjustment_count, close.late_adjustment_total), (1, -300.0));
+}
+
+/// The test default books no accruals and opens no close.
+#[test]
+fn quarter_close_is_opt_in() {
+    let mut engine = SimEngine::build_test("qc-off-test".into(), 42).unwrap();
+    let run_id = engine.run_id.clone();
+    engine.run_ticks(91).unwrap();
+
+    let pnl = engine.store.latest_pnl_snapshot(&run_id).unwrap().unwrap();
+    assert_eq!(pnl.accrual_adjustment, 0.0);
+    assert!(engine.store.quarter_closes(&run_id).unwrap().is_empty());
+    assert!(engine.store.close_adjustments(&run_id, "Q1-Y1").unwrap().is_empty());
+}
@@ -0,0 +1,130 @@
+//! Queue aging heatmap tests — Phase 4.48.
+//!
+//! Tests cover: desk totals agreeing with the paginated queues, complaint
+//! cells bucketed by age and counted overdue, the week-ago column
+//! matching what the matrix said a week earlier, the IPC message, and
+//! the opt-in aged-backlog early warning.
+
+use fincrime_core::{
+    engine::SimEngine,
+    protocol::IpcCommand,
+    queue::{QueueFilter, QueueKind, QueuePage},
+    queue_aging::{QueueAging, AGE_BUCKET_STARTS, TREND_TICKS},
+};
+
+fn run(run_id: &str, ticks: u64) -> SimEngine {
+    let mut engine = SimEngine::build_test(run_id.to_string(), 42).unwrap();
+    engine.run_ticks(ticks).unwrap();
+    engine
+}
+
+fn aging(engine: &SimEngine) -> QueueAging {
+    QueueAging::query(&engine.store, &engine.run_id, engine.clock.current_tick).unwrap()
+}
+
+/// Each desk's open count is its queue's open total, and its cells add
+/// up to it.
+#[test]
+fn desk_totals_match_the_queues() {
+    let engine = run("aging-totals-test", 60);
+    let aging = aging(&engine);
+    assert_eq!(aging.desks.iter().map(|d| d.desk).collect::<Vec<_>>(), QueueKind::ALL);
+    assert_eq!(aging.age_buckets, ["0-2", "3-6", "7-13", "14-29", "30+"]);
+
+    for desk in &aging.desks {
+        let page = QueuePage::query(&engine.store, &engine.run_id, desk.desk, &QueueFilter::default(), 0, Some(1)).unwrap();
+        assert_eq!(desk.open, page.total, "{:?}", desk.desk);
+        let cells: u64 = aging.cells.iter().filter(|c| c.desk == desk.desk).map(|c| c.count).sum();
+        assert_eq!(cells, desk.open, "{:?}", desk.desk);
+    }
+    assert!(aging.desks.iter().filter(|d| d.open > 0).count() > 1, "need more than one busy desk");
+}
+
+/// Complaints land in the bucket of their age and priority, and those
+/// past their SLA due tick are counted overdue.
+#[test]
+fn complaints_bucketed_by_age() {
+    let engine = run("aging-buckets-test", 45);
+    let tick = engine.clock.current_tick;
+    let aging = aging(&engine);
+
+    let bucket = |age: u64| AGE_BUCKET_STARTS.iter().rposition(|start| age >= *start).unwrap();
+    let mut expected = std::collections::BTreeMap::new();
+    for c in engine.store.open_complaints(&engine.run_id).unwrap() {
+        let cell = expected.entry((Some(c.priority.clone()), bucket(tick - c.tick_opened))).or_insert((0, 0));
+        cell.0 += 1;
+        cell.1 += u64::from(c.sla_due_tick < tick);
+    }
+    let actual: std::collections::BTreeMap<_, _> = aging
+        .cells
+        .iter()
+        .filter(|c| c.desk == QueueKind::Complaints && c.count > 0)
+        .map(|c| ((c.priority.clone(), c.age_bucket), (c.count, c.overdue)))
+        .collect();
+    assert_eq!(actual, expected);
+    assert!(expected.len() > 1, "need complaints of several ages");
+    let complaints = &aging.desks[0];
+    assert!(complaints.overdue > 0 && complaints.aged > 0, "{complaints:?}");
+}
+
+/// The week-ago column is what the matrix read a week earlier, and the
+/// delta is the change since.
+#[test]
+fn trend_against_a_week_earlier() {
+    let mut engine = run("aging-trend-test", 40);
+    let then = aging(&engine);
+    engine.run_ticks(TREND_TICKS).unwrap();
+    let now = aging(&engine);
+    assert_eq!(now.week_ago_tick, then.tick);
+
+    for cell in then.cells.iter().filter(|c| c.count > 0) {
+        let current = now
+            .cells
+            .iter()
+            .find(|c| c.desk == cell.desk && c.priority == cell.priority && c.age_bucket == cell.age_bucket)
+            .unwrap_or_else(|| panic!("cell missing a week later: {cell:?}"));
+        assert_eq!(current.week_ago, cell.count, "{cell:?}");
+    }
+    for cell in &now.cells {
+        assert_eq!(cell.delta, cell.count as i64 - cell.week_ago as i64);
+    }
+    for (desk, before) in now.desks.iter().zip(&then.desks) {
+        assert_eq!((desk.open_week_ago, desk.aged_week_ago), (before.open, before.aged), "{:?}", desk.desk);
+    }
+}
+
+/// `get_queue_aging` takes no fields; early in a run the trend compares
+/// against tick zero.
+#[test]
+fn ipc_message_and_early_run() {
+    let parsed = serde_json::from_str::<IpcCommand>(r#"{"type":"get_queue_aging"}"#).unwrap();
+    assert!(matches!(parsed, IpcCommand::GetQueueAging));
+
+    let engine = run("aging-early-test", 3);
+    let aging = aging(&engine);
+    assert_eq!((aging.tick, aging.week_ago_tick), (3, 0));
+    assert!(aging.desks.iter().all(|d| d.aged == 0 && d.aged_week_ago == 0));
+    let json = serde_json::to_value(&aging).unwrap();
+    assert_eq!(json["aged_from_bucket"], 3);
+}
+
+/// With the warning on, a desk whose aged backlog grows week on week
+/// raises an `aged_backlog_growth` warning; off, it never does.
+#[test]
+fn aged_backlog_warning_is_opt_in() {
+    let warnings = |engine: &SimEngine| -> Vec<serde_json::Value> {
+        (1..=engine.clock.current_tick)
+            .flat_map(|tick| engine.store.events_for_tick(&engine.run_id, tick).unwrap())
+            .filter(|e| e.event_type == "complaint_warning_fired")
+            .map(|e| serde_json::from_str::<serde_json::Value>(&e.payload).unwrap())
+            .filter(|p| p["alert_type"] == "aged_backlog_growth")
+            .collect()
+    };
+    assert!(warnings(&run("aging-warning-off-test", 42)).is_empty());
+
+    let mut engine = SimEngine::build_test_with_aged_backlog_warning("aging-warning-on-test".into(), 42, 1).unwrap();
+    engine.run_ticks(42).unwrap();
+    let fired = warnings(&engine);
+    assert!(!fired.is_empty());
+    assert!(fired.iter().all(|p| p["segment"].is_null()));
+}
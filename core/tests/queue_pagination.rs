@@ -0,0 +1,161 @@
+//! Queue pagination tests — Phase 4.43.
+//!
+//! Tests cover: paging a queue end to end in oldest-first order, the
+//! filters, every queue kind being served, page sizes being capped, and
+//! the summary-only state leaving out the unbounded lists.
+
+use fincrime_core::{
+    engine::SimEngine,
+    protocol::{IpcCommand, UiState},
+    queue::{QueueFilter, QueueKind, QueuePage, MAX_PAGE_SIZE},
+    transaction_monitoring_subsystem::AMLAlert,
+};
+
+fn run(run_id: &str, ticks: u64) -> SimEngine {
+    let mut engine = SimEngine::build_test(run_id.to_string(), 42).unwrap();
+    engine.run_ticks(ticks).unwrap();
+    engine
+}
+
+fn page(engine: &SimEngine, queue: QueueKind, filter: &QueueFilter, offset: u64, limit: u64) -> QueuePage {
+    QueuePage::query(&engine.store, &engine.run_id, queue, filter, offset, Some(limit)).unwrap()
+}
+
+/// Following `next_offset` visits every open complaint once, oldest
+/// first, in the order the full state lists them.
+#[test]
+fn pages_cover_the_queue_in_order() {
+    let engine = run("queue-pages-test", 60);
+    let filter = QueueFilter::default();
+
+    let mut ids = Vec::new();
+    let mut offset = Some(0);
+    while let Some(at) = offset {
+        let p = page(&engine, QueueKind::Complaints, &filter, at, 7);
+        assert!(p.items.len() <= 7);
+        ids.extend(p.items.into_iter().map(|i| i.id));
+        offset = p.next_offset;
+    }
+
+    let open = engine.store.open_complaints(&engine.run_id).unwrap();
+    assert!(open.len() > 7, "need more than one page");
+    assert_eq!(page(&engine, QueueKind::Complaints, &filter, 0, 7).total, open.len() as u64);
+    let mut expected = open;
+    expected.sort_by(|a, b| (a.tick_opened, &a.complaint_id).cmp(&(b.tick_opened, &b.complaint_id)));
+    assert_eq!(ids, expected.into_iter().map(|c| c.complaint_id).collect::<Vec<_>>());
+}
+
+/// Status, customer, category and tick-range filters narrow the queue
+/// and the total counts only what matches.
+#[test]
+fn filters_narrow_the_queue() {
+    let engine = run("queue-filter-test", 60);
+    let all = page(&engine, QueueKind::Exceptions, &QueueFilter::default(), 0, MAX_PAGE_SIZE);
+
+    let investigating = QueueFilter { status: Some("investigating".into()), ..Default::default() };
+    let p = page(&engine, QueueKind::Exceptions, &investigating, 0, MAX_PAGE_SIZE);
+    assert!(p.total > 0 && p.total < all.total);
+    assert!(p.items.iter().all(|i| i.status == "investigating"));
+
+    let window = QueueFilter { from_tick: Some(20), to_tick: Some(30), ..Default::default() };
+    let p = page(&engine, QueueKind::Complaints, &window, 0, MAX_PAGE_SIZE);
+    assert!(p.total > 0);
+    assert!(p.items.iter().all(|i| (20..=30).contains(&i.opened_tick)));
+
+    let first = page(&engine, QueueKind::Complaints, &QueueFilter::default(), 0, 1).items.remove(0);
+    let mine = QueueFilter {
+        customer_id: first.customer_id.clone(),
+        category: Some(first.category.clone()),
+        ..Default::default()
+    };
+    let p = page(&engine, QueueKind::Complaints, &mine, 0, MAX_PAGE_SIZE);
+    assert!(p.items.iter().any(|i| i.id == first.id));
+    assert!(p.items.iter().all(|i| i.customer_id == first.customer_id && i.category == first.category));
+}
+
+/// Alerts, exceptions and dispute cases are queues too; a closed alert
+/// leaves the open queue but can still be asked for by status.
+#[test]
+fn every_queue_is_served() {
+    let run_id = "queue-kinds-test";
+    let engine = run(run_id, 45);
+    let customer = engine.store.active_customers(run_id).unwrap().remove(0);
+    for (id, status) in [("Q-1", "open"), ("Q-2", "investigating"), ("Q-3", "resolved")] {
+        engine
+            .store
+            .insert_transaction_monitoring_alert(&AMLAlert {
+                alert_id: id.into(),
+                run_id: run_id.into(),
+                customer_id: customer.customer_id.clone(),
+                tick: 40,
+                rule_id: "TEST".into(),
+                alert_type: "structuring".into(),
+                alert_score: 80.0,
+                description: "test alert".into(),
+                triggered_amount: Some(9_000.0),
+                transaction_count: Some(3),
+                status: status.into(),
+            })
+            .unwrap();
+    }
+
+    let open = QueueFilter::default();
+    let alerts = page(&engine, QueueKind::Alerts, &open, 0, 10);
+    assert_eq!(alerts.items.iter().map(|i| i.id.as_str()).collect::<Vec<_>>(), ["Q-1", "Q-2"]);
+    assert_eq!(alerts.items[0].amount, Some(9_000.0));
+    let resolved = QueueFilter { status: Some("resolved".into()), ..Default::default() };
+    assert_eq!(page(&engine, QueueKind::Alerts, &resolved, 0, 10).total, 1);
+
+    let exceptions = page(&engine, QueueKind::Exceptions, &open, 0, 10);
+    assert!(exceptions.total > 0);
+    assert!(exceptions.items.iter().all(|i| i.customer_id.is_none() && i.amount.is_some()));
+
+    let cases = page(&engine, QueueKind::Cases, &open, 0, 10);
+    assert!(cases.total > 0);
+    assert!(cases.items.iter().all(|i| !i.status.starts_with("resolved") && i.customer_id.is_some()));
+}
+
+/// Page sizes are clamped, and a page past the end is empty with no
+/// next offset.
+#[test]
+fn page_size_is_bounded() {
+    let engine = run("queue-bounds-test", 60);
+    let filter = QueueFilter::default();
+    assert_eq!(page(&engine, QueueKind::Exceptions, &filter, 0, 100_000).limit, MAX_PAGE_SIZE);
+    assert_eq!(page(&engine, QueueKind::Exceptions, &filter, 0, 0).items.len(), 1);
+
+    let defaulted = QueuePage::query(&engine.store, &engine.run_id, QueueKind::Complaints, &filter, 0, None).unwrap();
+    assert_eq!(defaulted.limit, 50);
+
+    let past = page(&engine, QueueKind::Complaints, &filter, 10_000, 10);
+    assert!(past.items.is_empty() && past.next_offset.is_none());
+}
+
+/// A summary-only state carries the same counts and quarter-end P&L as the
+/// full one without the complaint list or P&L history, and clients ask
+/// for it, or for a queue page, over IPC.
+#[test]
+fn summary_only_state_is_bounded() {
+    let engine = run("queue-summary-test", 90);
+    let full = UiState::from_engine(&engine).unwrap();
+    let summary = UiState::summary_from_engine(&engine).unwrap();
+
+    assert!(!full.complaints.is_empty() && !full.pnl_history.is_empty() && !full.summary_only);
+    assert!(summary.complaints.is_empty() && summary.pnl_history.is_empty() && summary.summary_only);
+    assert_eq!(summary.backlog, full.backlog);
+    assert_eq!(summary.complaint_count, full.complaint_count);
+    assert_eq!(summary.nim, full.nim);
+    assert_eq!(summary.pre_tax_profit, full.pre_tax_profit);
+    assert!(serde_json::to_string(&summary).unwrap().len() < 1_024);
+
+    let parse = |line: &str| serde_json::from_str::<IpcCommand>(line).unwrap();
+    assert!(matches!(
+        parse(r#"{"type":"tick","count":1,"summary_only":true}"#),
+        IpcCommand::Tick { count: 1, summary_only: true }
+    ));
+    assert!(matches!(
+        parse(r#"{"type":"query_queue","queue":"cases","filter":{"status":"investigating"},"offset":50}"#),
+        IpcCommand::QueryQueue { queue: QueueKind::Cases, offset: 50, limit: None, filter }
+            if filter.status.as_deref() == Some("investigating")
+    ));
+}
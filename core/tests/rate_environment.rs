@@ -0,0 +1,159 @@
+//! Rate environment tests — Phase 4.83.
+//!
+//! Tests cover: the rate history written at the start and every quarter
+//! with the long end of the curve, the regime's odds setting the rate
+//! path, the curve flattening as rates rise, NIM widening under hikes by
+//! less the higher the deposit betas and the slower assets reprice, and
+//! the old drift and spread when the environment is off.
+
+use fincrime_core::{
+    config::{RateEnvironmentConfig, SimConfig},
+    engine::SimEngine,
+    macro_subsystem::long_rate,
+    store::SimStore,
+};
+
+fn build(run_id: &str, tune: impl FnOnce(&mut RateEnvironmentConfig)) -> SimEngine {
+    let path = format!("./test_{run_id}.db");
+    let _ = std::fs::remove_file(&path);
+    let store = SimStore::open(&path).unwrap();
+    store.migrate().unwrap();
+    store.insert_run(run_id, 42, "0.1.0-test").unwrap();
+    let mut config = SimConfig::default_test();
+    config.rate_environment.enabled = true;
+    // Hold the phase so the regime's odds alone set the path
+    config.macro_regime.phase_ticks = 10_000;
+    tune(&mut config.rate_environment);
+    SimEngine::build_with_config(run_id.into(), 42, &store, config).unwrap()
+}
+
+/// Every quarter's decision is a hike.
+fn always_hike(rates: &mut RateEnvironmentConfig) {
+    for moves in &mut rates.regime_moves {
+        (moves.hike_chance, moves.cut_chance) = (1.0, 0.0);
+    }
+}
+
+/// Every quarter's decision is a hold.
+fn always_hold(rates: &mut RateEnvironmentConfig) {
+    for moves in &mut rates.regime_moves {
+        (moves.hike_chance, moves.cut_chance) = (0.0, 0.0);
+    }
+}
+
+/// NIM of each quarter of a run of `ticks`.
+fn nims(engine: &mut SimEngine, run_id: &str, ticks: u64) -> Vec<f64> {
+    engine.run_ticks(ticks).unwrap();
+    engine.store.all_pnl_snapshots(run_id).unwrap().iter().map(|p| p.nim).collect()
+}
+
+/// The starting rates are written at tick 0, then each quarter's, with
+/// the long end of the curve for each base rate.
+#[test]
+fn rate_history_records_each_quarter() {
+    let run_id = "rates-history-test";
+    let mut engine = SimEngine::build_test_with_rate_environment(run_id.into(), 42).unwrap();
+    engine.run_ticks(270).unwrap();
+
+    let history = engine.store.macro_rate_history(run_id).unwrap();
+    let ticks: Vec<u64> = history.iter().map(|r| r.tick).collect();
+    assert_eq!(ticks, [0, 90, 180, 270]);
+    assert_eq!((history[0].base_rate, history[0].rate_move.as_str()), (0.05, "start"));
+
+    let rates = SimConfig::default_test().rate_environment;
+    for r in &history {
+        assert!((r.long_rate - long_rate(&rates, r.base_rate)).abs() < 1e-12, "{r:?}");
+        assert!(["start", "hike", "cut", "hold"].contains(&r.rate_move.as_str()));
+    }
+    let last = history.last().unwrap();
+    assert_eq!(engine.last_macro_state().unwrap().base_rate, last.base_rate);
+}
+
+/// A regime that always hikes raises the rate a step a quarter; one
+/// that always cuts lowers it.
+#[test]
+fn regime_odds_set_the_rate_path() {
+    let hikes = "rates-hike-path-test";
+    let mut engine = build(hikes, always_hike);
+    engine.run_ticks(270).unwrap();
+    let path: Vec<f64> = engine.store.macro_rate_history(hikes).unwrap().iter().map(|r| r.base_rate).collect();
+    for (q, rate) in path.iter().enumerate() {
+        assert!((rate - (0.05 + 0.0025 * q as f64)).abs() < 1e-12, "{path:?}");
+    }
+
+    let cuts = "rates-cut-path-test";
+    let mut engine = build(cuts, |rates| {
+        for moves in &mut rates.regime_moves {
+            (moves.hike_chance, moves.cut_chance) = (0.0, 1.0);
+        }
+    });
+    engine.run_ticks(270).unwrap();
+    let history = engine.store.macro_rate_history(cuts).unwrap();
+    assert!(history[1..].iter().all(|r| r.rate_move == "cut"));
+    assert!((history[3].base_rate - 0.0425).abs() < 1e-12);
+}
+
+/// The long end follows the base rate only partly: the curve is steep
+/// when rates are low and inverts when they run high.
+#[test]
+fn curve_flattens_as_rates_rise() {
+    let rates = SimConfig::default_test().rate_environment;
+    let spread = |base: f64| long_rate(&rates, base) - base;
+
+    assert!((spread(rates.neutral_rate) - rates.term_spread).abs() < 1e-12);
+    assert!(spread(0.01) > spread(0.05));
+    assert!(spread(0.05) > spread(0.10));
+    assert!(spread(0.10) < 0.0, "inverted at 10%");
+    assert!(long_rate(&rates, 0.06) > long_rate(&rates, 0.05));
+}
+
+/// Under hikes NIM widens on low-beta deposits, and by less when deposits
+/// pass the moves on or when assets are slow to reprice.
+#[test]
+fn nim_widens_with_hikes_less_for_high_betas_and_slow_repricing() {
+    let ticks = 270;
+    let hold = nims(&mut build("rates-nim-hold-test", always_hold), "rates-nim-hold-test", ticks);
+    let hike = nims(&mut build("rates-nim-hike-test", always_hike), "rates-nim-hike-test", ticks);
+    let high_beta = nims(
+        &mut build("rates-nim-beta-test", |rates| {
+            always_hike(rates);
+            rates.deposit_betas.clear();
+            rates.default_deposit_beta = 0.9;
+        }),
+        "rates-nim-beta-test",
+        ticks,
+    );
+    let instant = nims(
+        &mut build("rates-nim-instant-test", |rates| {
+            always_hike(rates);
+            rates.asset_repricing_ticks = 1;
+        }),
+        "rates-nim-instant-test",
+        ticks,
+    );
+
+    assert_eq!(hold.len(), 3);
+    let last = 2;
+    assert!(hike[last] > hold[last], "hike {hike:?} hold {hold:?}");
+    assert!(high_beta[last] < hike[last], "high beta {high_beta:?} hike {hike:?}");
+    assert!(instant[last] > hike[last], "instant {instant:?} lagged {hike:?}");
+}
+
+/// With the environment off the rate drifts a step every quarter and NII
+/// is the old half-rate spread, now on the average rate over the quarter.
+#[test]
+fn environment_off_keeps_drift_and_flat_spread() {
+    let run_id = "rates-disabled-test";
+    let mut engine = SimEngine::build_test(run_id.into(), 42).unwrap();
+    engine.run_ticks(270).unwrap();
+
+    let history = engine.store.macro_rate_history(run_id).unwrap();
+    assert_eq!(history.len(), 4);
+    assert!(history[1..].iter().all(|r| r.rate_move == "hike" || r.rate_move == "cut"));
+
+    for pnl in engine.store.all_pnl_snapshots(run_id).unwrap() {
+        let avg_rate = engine.store.avg_macro_base_rate(run_id, pnl.tick - 89, pnl.tick).unwrap();
+        let expected = avg_rate * 0.5 * (90.0 / 365.0) * 4.0 * 100.0;
+        assert!((pnl.nim - expected).abs() < 1e-9, "{} vs {expected}", pnl.nim);
+    }
+}
@@ -0,0 +1,141 @@
+//! Read-only SQL query tests — Phase 4.67.
+//!
+//! Tests cover: rows returned with their columns and storage classes;
+//! every statement that could write refused before it runs, quoted and
+//! commented semicolons excepted; the row and size limits truncating a
+//! reply; runaway queries stopped by the step budget; and bad SQL or
+//! parameters answered as recoverable command errors.
+
+use fincrime_core::{
+    engine::SimEngine,
+    error::SimError,
+    readonly_query::{check_statement, QueryResult, MAX_ROW_LIMIT},
+};
+use serde_json::{json, Value};
+
+fn engine(run_id: &str) -> SimEngine {
+    let mut engine = SimEngine::build_test(run_id.into(), 42).unwrap();
+    engine.run_ticks(10).unwrap();
+    engine
+}
+
+fn query(engine: &SimEngine, sql: &str, params: &[Value], limit: Option<u64>) -> Result<QueryResult, SimError> {
+    QueryResult::query(&engine.store, sql, params, limit)
+}
+
+fn refused(engine: &SimEngine, sql: &str, params: &[Value]) -> String {
+    let e = query(engine, sql, params, None).unwrap_err();
+    assert_eq!(e.code(), "command_error", "{sql}: {e}");
+    assert!(!e.is_fatal());
+    e.to_string()
+}
+
+/// Each value comes back as its JSON type, and each column is labelled
+/// with the storage class of its first non-NULL value.
+#[test]
+fn rows_come_back_typed() {
+    let engine = engine("sql-typed-test");
+    let run_id = engine.run_id.clone();
+    let result = query(
+        &engine,
+        "SELECT customer_id, satisfaction, open_tick, NULL AS empty, x'00ff' AS raw
+           FROM customer WHERE run_id = ?1 AND segment = ?2 ORDER BY customer_id",
+        &[json!(run_id), json!("mass_market")],
+        Some(3),
+    )
+    .unwrap();
+
+    assert_eq!(result.columns, ["customer_id", "satisfaction", "open_tick", "empty", "raw"]);
+    assert_eq!(result.column_types, ["text", "real", "integer", "null", "blob"]);
+    assert_eq!(result.rows.len(), 3);
+    assert!(result.truncated);
+    let customers = engine.store.active_customers(&run_id).unwrap();
+    let mut ids: Vec<_> = customers.iter().filter(|c| c.segment == "mass_market").map(|c| &c.customer_id).collect();
+    ids.sort();
+    for (row, id) in result.rows.iter().zip(ids) {
+        assert_eq!(row[0], json!(id));
+        assert!(row[1].is_f64() && row[2].is_u64());
+        assert_eq!((&row[3], &row[4]), (&Value::Null, &json!("00ff")));
+    }
+}
+
+/// Only a single SELECT or WITH gets as far as SQLite, and SQLite must
+/// agree it only reads.
+#[test]
+fn writes_are_refused() {
+    let engine = engine("sql-refuse-test");
+    let run_id = engine.run_id.clone();
+    let customers = engine.store.customer_count(&run_id, "active").unwrap();
+
+    for (sql, reason) in [
+        ("DELETE FROM customer", "DELETE is not allowed"),
+        ("UPDATE customer SET status = 'churned'", "UPDATE is not allowed"),
+        ("DROP TABLE customer", "DROP is not allowed"),
+        ("PRAGMA query_only = OFF", "PRAGMA is not allowed"),
+        ("ATTACH DATABASE 'other.db' AS other", "ATTACH is not allowed"),
+        ("  /* comment */ VACUUM", "VACUUM is not allowed"),
+        ("SELECT 1; DELETE FROM customer", "one statement"),
+        ("SELECT 1;; SELECT 2", "one statement"),
+    ] {
+        assert!(refused(&engine, sql, &[]).contains(reason), "{sql}");
+    }
+    let sneaky = "WITH doomed AS (SELECT customer_id FROM customer) DELETE FROM customer WHERE customer_id IN doomed";
+    assert!(refused(&engine, sneaky, &[]).contains("write"));
+    assert_eq!(engine.store.customer_count(&run_id, "active").unwrap(), customers);
+
+    // Semicolons inside strings, identifiers or comments, or trailing
+    for sql in [
+        "SELECT ';' AS \"semi;colon\"",
+        "select 'it''s; fine' -- no; really\n;",
+        "/* lead; */ WITH one AS (SELECT 1) SELECT * FROM one; /* trail */",
+    ] {
+        assert_eq!(check_statement(sql), Ok(()), "{sql}");
+        assert_eq!(query(&engine, sql, &[], None).unwrap().rows.len(), 1, "{sql}");
+    }
+    assert!(check_statement("SELECT 'open").is_err());
+    assert!(check_statement(" -- nothing\n").is_err());
+}
+
+/// A reply stops at the requested rows, never more than the cap, and
+/// before its JSON passes the size limit.
+#[test]
+fn replies_are_capped() {
+    let engine = engine("sql-cap-test");
+    let counting = "WITH RECURSIVE n(i) AS (SELECT 1 UNION ALL SELECT i + 1 FROM n WHERE i < ?1) SELECT i FROM n";
+
+    let few = query(&engine, counting, &[json!(100)], Some(20)).unwrap();
+    assert_eq!((few.rows.len(), few.truncated), (20, true));
+    assert_eq!(few.rows[19], [json!(20)]);
+    let all = query(&engine, counting, &[json!(100)], None).unwrap();
+    assert_eq!((all.rows.len(), all.truncated), (100, false));
+    let capped = query(&engine, counting, &[json!(10_000)], Some(1_000_000)).unwrap();
+    assert_eq!((capped.rows.len() as u64, capped.truncated), (MAX_ROW_LIMIT, true));
+
+    // Each row is 400 KB of hex; the 4 MB limit leaves room for ten
+    let wide = "WITH RECURSIVE n(i) AS (SELECT 1 UNION ALL SELECT i + 1 FROM n WHERE i < 30)
+                SELECT i, hex(zeroblob(200000)) FROM n";
+    let wide = query(&engine, wide, &[], None).unwrap();
+    assert_eq!((wide.rows.len(), wide.truncated), (10, true));
+}
+
+/// A query that would run forever is stopped, and the run carries on.
+#[test]
+fn runaway_queries_are_stopped() {
+    let mut engine = engine("sql-runaway-test");
+    let forever = "WITH RECURSIVE n(i) AS (SELECT 1 UNION ALL SELECT i + 1 FROM n) SELECT count(*) FROM n";
+    assert!(refused(&engine, forever, &[]).contains("steps"));
+
+    engine.run_ticks(1).unwrap();
+    assert_eq!(query(&engine, "SELECT 1", &[], None).unwrap().rows, [[json!(1)]]);
+}
+
+/// Syntax errors, unknown tables and unusable parameters are the
+/// client's mistake, not a broken store.
+#[test]
+fn bad_queries_are_recoverable() {
+    let engine = engine("sql-bad-test");
+    refused(&engine, "SELECT FROM WHERE", &[]);
+    refused(&engine, "SELECT * FROM no_such_table", &[]);
+    refused(&engine, "SELECT ?1", &[json!({ "nested": true })]);
+    refused(&engine, "SELECT ?1, ?2", &[json!(1)]);
+}
@@ -0,0 +1,141 @@
+//! Remittance corridor tests — Phase 4.36.
+//!
+//! Tests cover: international customers profiled onto their home corridor,
+//! legitimate remittances recurring at the corridor interval to a few
+//! family beneficiaries, REMIT_LAYERING alerting layering profiles and
+//! never legitimate ones with scores lifted by corridor risk, and
+//! remittances staying off unless enabled.
+
+use fincrime_core::{engine::SimEngine, event::SimEvent};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+
+fn build_with_remittance(run_id: &str, seed: u64) -> SimEngine {
+    SimEngine::build_test_with_remittance(run_id.to_string(), seed)
+        .expect("build test engine with remittance corridors")
+}
+
+/// (customer_id, alert_score) for every REMIT_LAYERING alert up to `ticks`.
+fn layering_alerts(engine: &SimEngine, run_id: &str, ticks: u64) -> Vec<(String, f64)> {
+    (1..=ticks)
+        .flat_map(|tick| engine.store_events_for_tick(run_id, tick).unwrap())
+        .filter(|e| e.event_type == "transaction_monitoring_alert")
+        .filter_map(|e| match serde_json::from_str(&e.payload).unwrap() {
+            SimEvent::TransactionMonitoringAlert { alert_type, customer_id, alert_score, .. }
+                if alert_type == "remittance_layering" =>
+            {
+                Some((customer_id, alert_score))
+            }
+            _ => None,
+        })
+        .collect()
+}
+
+/// Every international customer is profiled once, on their citizenship
+/// corridor; those without a corridor never remit.
+#[test]
+fn international_customers_profiled_on_home_corridor() {
+    let run_id = "remit-profile-test";
+    let mut engine = build_with_remittance(run_id, 42);
+    engine.run_ticks(10).unwrap();
+
+    let profiles = engine.store.remittance_profiles(run_id).unwrap();
+    assert!(!profiles.is_empty());
+    assert!(profiles.iter().any(|p| p.pattern == "legitimate"));
+    for profile in &profiles {
+        let intl = engine
+            .store
+            .get_customer_international(run_id, &profile.customer_id)
+            .unwrap()
+            .expect("profiled customer is international");
+        assert_eq!(profile.corridor, intl.citizenship_country);
+        let has_corridor = ["MX", "IN", "PH", "CN", "BR", "GB", "CA", "DE", "NG", "AE", "RU"]
+            .contains(&profile.corridor.as_str());
+        assert_eq!(profile.pattern != "none", has_corridor, "{profile:?}");
+    }
+}
+
+/// Legitimate senders remit only down their home corridor, at multiples
+/// of its interval, to no more than their family beneficiaries.
+#[test]
+fn legitimate_remittances_recur_to_family() {
+    let run_id = "remit-legit-test";
+    let mut engine = build_with_remittance(run_id, 42);
+    engine.run_ticks(90).unwrap();
+
+    let profiles: HashMap<String, _> = engine
+        .store
+        .remittance_profiles(run_id)
+        .unwrap()
+        .into_iter()
+        .filter(|p| p.pattern == "legitimate")
+        .map(|p| (p.customer_id.clone(), p))
+        .collect();
+    let mut sends: BTreeMap<String, Vec<_>> = BTreeMap::new();
+    for transfer in engine.store.remittance_transfers(run_id).unwrap() {
+        if profiles.contains_key(&transfer.customer_id) {
+            sends.entry(transfer.customer_id.clone()).or_default().push(transfer);
+        }
+    }
+    assert!(sends.values().any(|s| s.len() >= 2), "some senders remit more than once");
+
+    for (customer_id, transfers) in &sends {
+        let profile = &profiles[customer_id];
+        let beneficiaries: BTreeSet<_> = transfers.iter().map(|t| &t.counterparty).collect();
+        assert!(beneficiaries.len() as u32 <= profile.beneficiaries);
+        for pair in transfers.windows(2) {
+            assert!(pair.iter().all(|t| t.direction == "outbound" && t.corridor == profile.corridor));
+            assert_eq!((pair[1].tick - pair[0].tick) % profile.interval_ticks, 0);
+        }
+    }
+}
+
+/// REMIT_LAYERING alerts layering profiles and never a legitimate sender,
+/// and every alert is lifted by the medium- or high-risk corridors the
+/// funds were fanned out through.
+#[test]
+fn layering_alerts_and_legitimate_remittance_does_not() {
+    let run_id = "remit-layering-test";
+    let mut engine = build_with_remittance(run_id, 42);
+    engine.run_ticks(56).unwrap();
+
+    let patterns: HashMap<String, String> = engine
+        .store
+        .remittance_profiles(run_id)
+        .unwrap()
+        .into_iter()
+        .map(|p| (p.customer_id, p.pattern))
+        .collect();
+    let layering = patterns.values().filter(|p| *p == "layering").count();
+    assert!(layering > 0);
+
+    let alerts = layering_alerts(&engine, run_id, 56);
+    assert!(!alerts.is_empty());
+    for (customer_id, score) in &alerts {
+        assert_eq!(patterns[customer_id], "layering", "{customer_id} alerted");
+        assert!(*score == 65.0 || *score == 80.0, "{score}");
+    }
+    let alerted: BTreeSet<_> = alerts.iter().map(|(c, _)| c).collect();
+    let layered: BTreeSet<_> = engine
+        .store
+        .remittance_transfers(run_id)
+        .unwrap()
+        .into_iter()
+        .filter(|t| t.direction == "inbound" && t.tick <= 49)
+        .map(|t| t.customer_id)
+        .collect();
+    for customer_id in &layered {
+        assert!(alerted.contains(customer_id), "{customer_id} layered undetected");
+    }
+}
+
+/// Remittances are opt-in: the default test build profiles no one and
+/// sends nothing.
+#[test]
+fn remittance_opt_in() {
+    let run_id = "remit-off-test";
+    let mut engine = SimEngine::build_test(run_id.to_string(), 42).unwrap();
+    engine.run_ticks(60).unwrap();
+    assert!(engine.store.remittance_profiles(run_id).unwrap().is_empty());
+    assert!(engine.store.remittance_transfers(run_id).unwrap().is_empty());
+    assert!(layering_alerts(&engine, run_id, 60).is_empty());
+}
@@ -0,0 +1,214 @@
+//! Resuming a stopped run tests — Phase 4.76.
+//!
+//! Tests cover: a run stopped between snapshots and resumed logging the
+//! same events as one that never stopped, player changes made after the
+//! snapshot included; a run stopped on a snapshot tick restoring from it
+//! alone; commands sent after the last tick landing on the next one;
+//! unknown runs refused and runs that never ticked starting from zero;
+//! a breakpoint tripping at the stop tick staying cleared; and a paused
+//! run coming back paused at the speed it was set to.
+
+use fincrime_core::{
+    breakpoint::{Breakpoint, BreakpointCondition},
+    clock::SimSpeed,
+    command::PlayerCommand,
+    config::SimConfig,
+    engine::SimEngine,
+    store::SimStore,
+    transaction_monitoring_subsystem::AMLAlert,
+};
+
+/// A fresh run in its own database file.
+fn open_run(db: &str, run_id: &str, seed: u64) -> SimStore {
+    let _ = std::fs::remove_file(db);
+    let store = SimStore::open(db).unwrap();
+    store.migrate().unwrap();
+    store.insert_run(run_id, seed, "0.1.0-test").unwrap();
+    store
+}
+
+fn payloads(engine: &SimEngine, ticks: std::ops::RangeInclusive<u64>) -> Vec<String> {
+    ticks
+        .flat_map(|tick| engine.store_events_for_tick(&engine.run_id, tick).unwrap())
+        .map(|e| e.payload)
+        .collect()
+}
+
+/// Play to `stop`, lowering the SAR threshold on tick 33, then raise an
+/// alert only the lowered threshold files.
+fn play(engine: &mut SimEngine, stop: u64) -> String {
+    engine.run_ticks(33).unwrap();
+    engine.submit_command(PlayerCommand::SetSarThreshold { threshold: 40.0 }).unwrap();
+    engine
+        .submit_command(PlayerCommand::SetAchOriginationLimits { daily_count: 2, daily_amount: 5_000.0 })
+        .unwrap();
+    engine.run_ticks(stop - 33).unwrap();
+
+    let customer = engine.store.active_customers(&engine.run_id).unwrap().remove(0);
+    engine
+        .store
+        .insert_transaction_monitoring_alert(&AMLAlert {
+            alert_id: "RESUME-1".into(),
+            run_id: engine.run_id.clone(),
+            customer_id: customer.customer_id.clone(),
+            tick: stop,
+            rule_id: "TEST".into(),
+            alert_type: "structuring".into(),
+            alert_score: 50.0,
+            description: "test alert".into(),
+            triggered_amount: Some(9_500.0),
+            transaction_count: Some(3),
+            status: "open".into(),
+        })
+        .unwrap();
+    customer.customer_id
+}
+
+/// Stopped on tick 45, resumed from the tick-30 snapshot plus 15 ticks
+/// of replay, the run carries on as if it had never stopped.
+#[test]
+fn resumed_run_matches_uninterrupted_run() {
+    let run_id = "resume-match-test";
+    let seed = 0x4760_0001;
+
+    let store = open_run("test_resume_match_ref.db", run_id, seed);
+    let mut reference = SimEngine::build_with_config(run_id.into(), seed, &store, SimConfig::default_test()).unwrap();
+    play(&mut reference, 45);
+    reference.run_ticks(50).unwrap();
+
+    let store = open_run("test_resume_match.db", run_id, seed);
+    let mut engine = SimEngine::build_with_config(run_id.into(), seed, &store, SimConfig::default_test()).unwrap();
+    let customer_id = play(&mut engine, 45);
+    drop(engine);
+
+    let mut resumed = SimEngine::resume_with_config(run_id.into(), &store, SimConfig::default_test()).unwrap();
+    assert_eq!(resumed.clock.current_tick, 45);
+    assert!(resumed.clock.paused);
+    resumed.run_ticks(50).unwrap();
+
+    assert_eq!(resumed.clock.current_tick, 95);
+    let (expected, actual) = (payloads(&reference, 46..=95), payloads(&resumed, 46..=95));
+    assert_eq!(expected.len(), actual.len(), "Event counts differ after resuming");
+    for (i, (a, b)) in expected.iter().zip(&actual).enumerate() {
+        assert_eq!(a, b, "Diverged at entry {i} after resuming");
+    }
+    assert_eq!(resumed.store_pnl_count(run_id).unwrap(), reference.store_pnl_count(run_id).unwrap());
+    let filed = resumed.store_events_for_tick(run_id, 49).unwrap();
+    assert!(
+        filed.iter().any(|e| e.event_type == "sar_filed" && e.payload.contains(&customer_id)),
+        "The threshold set before the stop files the alert"
+    );
+}
+
+/// Stopped on a snapshot tick, subsystems restore from the snapshot with
+/// nothing to replay.
+#[test]
+fn stop_on_snapshot_tick_restores_from_snapshot() {
+    let run_id = "resume-snapshot-test";
+    let store = open_run("test_resume_snapshot.db", run_id, 0x4760_0002);
+    let mut engine =
+        SimEngine::build_with_config(run_id.into(), 0x4760_0002, &store, SimConfig::default_test()).unwrap();
+    engine.run_ticks(90).unwrap();
+    let rate = engine.last_macro_state().unwrap().base_rate;
+    let customers = engine.store.active_customers(run_id).unwrap().len();
+    drop(engine);
+
+    let snapshot = SimEngine::resume_with_config(run_id.into(), &store, SimConfig::default_test())
+        .unwrap()
+        .snapshot_at(90)
+        .unwrap()
+        .unwrap();
+    assert_eq!(snapshot.tick, 90);
+    for name in ["macro", "customer", "transaction", "transaction_monitoring", "economics"] {
+        assert!(snapshot.subsystems.contains_key(name), "{name} saved no state");
+    }
+    assert_eq!(snapshot.subsystems["economics"]["quarter_number"], 1);
+
+    let mut resumed = SimEngine::resume_with_config(run_id.into(), &store, SimConfig::default_test()).unwrap();
+    assert_eq!(resumed.clock.current_tick, 90);
+    assert_eq!(resumed.last_macro_state().unwrap().base_rate, rate);
+    resumed.run_ticks(1).unwrap();
+    let after = resumed.store.active_customers(run_id).unwrap().len();
+    assert!(after < customers + customers / 10, "The population is not generated twice: {customers} -> {after}");
+}
+
+/// A command sent after the last tick, before the stop, is applied on
+/// the first tick after resuming.
+#[test]
+fn pending_commands_land_after_resume() {
+    let run_id = "resume-pending-test";
+    let store = open_run("test_resume_pending.db", run_id, 0x4760_0003);
+    let mut engine =
+        SimEngine::build_with_config(run_id.into(), 0x4760_0003, &store, SimConfig::default_test()).unwrap();
+    engine.run_ticks(10).unwrap();
+    engine.submit_command(PlayerCommand::SetSarThreshold { threshold: 70.0 }).unwrap();
+    drop(engine);
+
+    let mut resumed = SimEngine::resume_with_config(run_id.into(), &store, SimConfig::default_test()).unwrap();
+    resumed.run_ticks(1).unwrap();
+    let events = resumed.store_events_for_tick(run_id, 11).unwrap();
+    let changed = events.iter().find(|e| e.event_type == "sar_threshold_changed").expect("threshold changed");
+    assert!(changed.payload.contains("70"), "{}", changed.payload);
+    resumed.run_ticks(1).unwrap();
+    let events = resumed.store_events_for_tick(run_id, 12).unwrap();
+    assert!(!events.iter().any(|e| e.event_type == "sar_threshold_changed"), "Applied once");
+}
+
+/// An unknown run is refused; a run that never ticked resumes at zero.
+#[test]
+fn unknown_and_unplayed_runs() {
+    let store = open_run("test_resume_unknown.db", "resume-unplayed-test", 0x4760_0004);
+    let err = SimEngine::resume_with_config("no-such-run".into(), &store, SimConfig::default_test())
+        .err()
+        .expect("unknown run refused");
+    assert!(err.to_string().contains("no-such-run"), "{err}");
+
+    let mut engine =
+        SimEngine::resume_with_config("resume-unplayed-test".into(), &store, SimConfig::default_test()).unwrap();
+    assert_eq!(engine.clock.current_tick, 0);
+    assert_eq!(engine.seed(), 0x4760_0004);
+    engine.run_ticks(2).unwrap();
+    assert_eq!(engine.clock.current_tick, 2);
+}
+
+/// Breakpoints are not stored: a run stopped by one resumes without it
+/// and plays straight through.
+#[test]
+fn breakpoints_are_not_resumed() {
+    let run_id = "resume-breakpoint-test";
+    let store = open_run("test_resume_breakpoint.db", run_id, 0x4760_0005);
+    let mut engine =
+        SimEngine::build_with_config(run_id.into(), 0x4760_0005, &store, SimConfig::default_test()).unwrap();
+    let condition = BreakpointCondition::AtTick { tick: 7 };
+    engine.set_breakpoint(Breakpoint { id: "day-7".into(), condition, once: false }).unwrap();
+    engine.run_ticks(20).unwrap();
+    assert_eq!(engine.clock.current_tick, 7);
+    drop(engine);
+
+    let mut resumed = SimEngine::resume_with_config(run_id.into(), &store, SimConfig::default_test()).unwrap();
+    assert!(resumed.breakpoints().is_empty());
+    assert!(resumed.breakpoint_hits().is_empty());
+    resumed.run_ticks(5).unwrap();
+    assert_eq!(resumed.clock.current_tick, 12);
+}
+
+/// The player's pause and speed change outlive the process: the resumed
+/// run is still held, at the speed last set.
+#[test]
+fn paused_run_resumes_paused() {
+    let run_id = "resume-held-test";
+    let store = open_run("test_resume_held.db", run_id, 7);
+    let mut engine = SimEngine::build_with_config(run_id.into(), 7, &store, SimConfig::default_test()).unwrap();
+    engine.run_ticks(5).unwrap();
+    engine.submit_command(PlayerCommand::SetSpeed { speed: SimSpeed::FastForward }).unwrap();
+    engine.submit_command(PlayerCommand::Pause).unwrap();
+    drop(engine);
+
+    let mut resumed = SimEngine::resume_with_config(run_id.into(), &store, SimConfig::default_test()).unwrap();
+    assert!(resumed.clock.held);
+    assert_eq!(resumed.clock.speed, SimSpeed::FastForward);
+    assert!(resumed.run_ticks(1).is_err());
+    resumed.submit_command(PlayerCommand::Resume).unwrap();
+    resumed.run_ticks(1).unwrap();
+    assert_eq!(resumed.clock.current_tick, 6);
+}
@@ -0,0 +1,235 @@
+//! Run retention tests — Phase 4.50.
+//!
+//! Tests cover: scrubbing replacing names, tax ids, streets, phone
+//! numbers and login IP addresses in their original formats, shared identities still shared
+//! after scrubbing, other runs and non-personal columns left alone,
+//! purging a run from every table, and unknown runs rejected.
+
+use fincrime_core::{config::SimConfig, engine::SimEngine, store::SimStore};
+use rusqlite::Connection;
+
+const RUNS: [&str; 2] = ["retention-a", "retention-b"];
+
+/// A run with enough synthetic identities to share SSNs, addresses and
+/// phones, plus a second run holding a copy of its rows wherever a
+/// table's key allows two runs to hold the same ids.
+fn two_runs(name: &str) -> (String, SimStore) {
+    let path = std::env::temp_dir().join(format!("retention_{name}_{}.db", std::process::id()));
+    let path = path.to_str().unwrap().to_string();
+    let _ = std::fs::remove_file(&path);
+    let store = SimStore::open(&path).unwrap();
+    store.migrate().unwrap();
+    store.insert_run(RUNS[0], 42, "0.1.0-test").unwrap();
+    let mut config = SimConfig::default_test();
+    config.identity_address.synthetic_identity_rate = 0.30;
+    config.channels.enabled = true;
+    config.devices.enabled = true;
+    let mut engine = SimEngine::build_with_config(RUNS[0].to_string(), 42, &store, config).unwrap();
+    engine.run_ticks(15).unwrap();
+
+    store.insert_run(RUNS[1], 43, "0.1.0-test").unwrap();
+    let conn = Connection::open(&path).unwrap();
+    for table in run_tables(&conn) {
+        let columns: Vec<String> = conn
+            .prepare("SELECT name FROM pragma_table_info(?1) ORDER BY cid")
+            .unwrap()
+            .query_map([&table], |row| row.get(0))
+            .unwrap()
+            .map(Result::unwrap)
+            .collect();
+        let select = columns
+            .iter()
+            .map(|c| if c == "run_id" { "?1".to_string() } else { c.clone() })
+            .collect::<Vec<_>>()
+            .join(", ");
+        conn.execute(
+            &format!("INSERT OR IGNORE INTO {table} SELECT {select} FROM {table} WHERE run_id = ?2"),
+            [RUNS[1], RUNS[0]],
+        )
+        .unwrap();
+    }
+    (path, store)
+}
+
+/// Tables with a `run_id` column, other than `run` itself.
+fn run_tables(conn: &Connection) -> Vec<String> {
+    conn.prepare(
+        "SELECT m.name FROM sqlite_master m WHERE m.type = 'table' AND m.name != 'run'
+         AND EXISTS (SELECT 1 FROM pragma_table_info(m.name) WHERE name = 'run_id') ORDER BY m.name",
+    )
+    .unwrap()
+    .query_map([], |row| row.get(0))
+    .unwrap()
+    .map(Result::unwrap)
+    .collect()
+}
+
+fn strings(conn: &Connection, sql: &str, run_id: &str) -> Vec<String> {
+    let mut stmt = conn.prepare(sql).unwrap();
+    stmt.query_map([run_id], |row| row.get(0)).unwrap().map(Result::unwrap).collect()
+}
+
+fn count(conn: &Connection, sql: &str, run_id: &str) -> u64 {
+    conn.query_row(sql, [run_id], |row| row.get(0)).unwrap()
+}
+
+/// Every name, SSN, street, phone number and login IP address of the run
+/// is replaced, and each keeps the shape of what it replaced.
+#[test]
+fn personal_data_replaced() {
+    let (path, store) = two_runs("replaced");
+    let conn = Connection::open(&path).unwrap();
+    let run = RUNS[0];
+    let queries = [
+        "SELECT name FROM customer WHERE run_id = ?1",
+        "SELECT ssn_full FROM customer_identity WHERE run_id = ?1",
+        "SELECT street_address FROM customer_address WHERE run_id = ?1",
+        "SELECT full_number FROM customer_phone WHERE run_id = ?1",
+        "SELECT ip_address FROM device_session WHERE run_id = ?1",
+    ];
+    let before: Vec<_> = queries.iter().map(|q| strings(&conn, q, run)).collect();
+    assert!(before.iter().all(|values| !values.is_empty()));
+
+    let report = store.scrub_run(run).unwrap();
+    assert_eq!(report.tables["customer"], before[0].len() as u64);
+    assert_eq!(report.tables["customer_phone"], before[3].len() as u64);
+    assert_eq!(report.tables["device_session"], before[4].len() as u64);
+    for (query, old) in queries.iter().zip(&before) {
+        let new = strings(&conn, query, run);
+        assert_eq!(new.len(), old.len());
+        assert!(new.iter().all(|v| !old.contains(v)), "{query}: {new:?}");
+    }
+
+    let malformed = "SELECT COUNT(*) FROM customer_identity WHERE run_id = ?1 AND NOT (
+        ssn_full GLOB '[0-9][0-9][0-9]-[0-9][0-9]-[0-9][0-9][0-9][0-9]'
+        AND ssn_area || '-' || ssn_group || '-' || ssn_serial = ssn_full)";
+    assert_eq!(count(&conn, malformed, run), 0);
+    let phones = "SELECT COUNT(*) FROM customer_phone WHERE run_id = ?1
+        AND full_number != country_code || '-' || area_code || '-' || exchange_code || '-' || subscriber_number";
+    assert_eq!(count(&conn, phones, run), 0);
+    assert!(strings(&conn, queries[0], run).iter().all(|n| n.starts_with("Anon ")));
+    let ips = strings(&conn, queries[4], run);
+    assert!(ips.iter().all(|ip| ip.starts_with("100.") && ip.split('.').all(|o| o.parse::<u8>().is_ok())), "{ips:?}");
+    let _ = std::fs::remove_file(path);
+}
+
+/// Customers who shared an SSN, street or phone still do, distinct
+/// values stay distinct, and an SSN still matches the account tax id and
+/// death master file entry it did.
+#[test]
+fn shared_identities_stay_shared() {
+    let (path, store) = two_runs("linkage");
+    let conn = Connection::open(&path).unwrap();
+    let run = RUNS[0];
+    // Give a second customer the first one's SSN, street and phone, and
+    // report the first one's SSN deceased
+    let first = strings(&conn, "SELECT customer_id FROM customer_identity WHERE run_id = ?1 ORDER BY customer_id", run);
+    let shared = [
+        ("customer_identity", "ssn_full"),
+        ("customer_address", "street_address"),
+        ("customer_phone", "(country_code, area_code, exchange_code, subscriber_number, full_number)"),
+    ];
+    for (table, columns) in shared {
+        conn.execute(
+            &format!(
+                "UPDATE {table} SET {columns} = (SELECT {} FROM {table} WHERE run_id = ?1 AND customer_id = ?2)
+                 WHERE run_id = ?1 AND customer_id = ?3",
+                columns.trim_matches(['(', ')'])
+            ),
+            [run, &first[0], &first[1]],
+        )
+        .unwrap();
+    }
+    conn.execute(
+        "INSERT INTO death_master_file (run_id, ssn, death_year)
+         SELECT run_id, ssn_full, 2020 FROM customer_identity WHERE run_id = ?1 AND customer_id = ?2",
+        [run, &first[0]],
+    )
+    .unwrap();
+
+    let links = [
+        "SELECT COUNT(*) FROM customer_identity a JOIN customer_identity b
+         ON a.run_id = b.run_id AND a.ssn_full = b.ssn_full AND a.rowid < b.rowid WHERE a.run_id = ?1",
+        "SELECT COUNT(*) FROM customer_address a JOIN customer_address b
+         ON a.run_id = b.run_id AND a.street_address = b.street_address AND a.rowid < b.rowid WHERE a.run_id = ?1",
+        "SELECT COUNT(*) FROM customer_phone a JOIN customer_phone b
+         ON a.run_id = b.run_id AND a.full_number = b.full_number AND a.rowid < b.rowid WHERE a.run_id = ?1",
+        "SELECT COUNT(*) FROM account a JOIN customer_identity i
+         ON a.run_id = i.run_id AND a.customer_id = i.customer_id AND a.primary_tax_id = i.ssn_full WHERE a.run_id = ?1",
+        "SELECT COUNT(*) FROM customer_identity i JOIN death_master_file d
+         ON i.run_id = d.run_id AND i.ssn_full = d.ssn WHERE i.run_id = ?1",
+        "SELECT COUNT(DISTINCT ssn_full) FROM customer_identity WHERE run_id = ?1",
+        "SELECT COUNT(DISTINCT name) FROM customer WHERE run_id = ?1",
+    ];
+    let before: Vec<_> = links.iter().map(|q| count(&conn, q, run)).collect();
+    assert!(before.iter().all(|n| *n > 0), "{before:?}");
+
+    store.scrub_run(run).unwrap();
+    let after: Vec<_> = links.iter().map(|q| count(&conn, q, run)).collect();
+    assert_eq!(after, before);
+    let _ = std::fs::remove_file(path);
+}
+
+/// Scrubbing one run leaves the other run, and every column that is not
+/// personal data, exactly as it was.
+#[test]
+fn other_runs_and_columns_untouched() {
+    let (path, store) = two_runs("untouched");
+    let checksums = |run: &str| {
+        [
+            store.table_checksum(run, "customer", &["name"]).unwrap(),
+            store.table_checksum(run, "customer_identity", &["ssn_full", "ssn_area", "ssn_group", "ssn_serial"]).unwrap(),
+            store.table_checksum(run, "customer_address", &["street_address"]).unwrap(),
+            store.table_checksum(run, "transactions", &[]).unwrap(),
+        ]
+    };
+    let full = |run: &str| {
+        ["customer", "customer_identity", "customer_address", "customer_phone"]
+            .map(|table| store.table_checksum(run, table, &[]).unwrap())
+    };
+    let (kept, other) = (checksums(RUNS[0]), full(RUNS[1]));
+    let scrubbed = full(RUNS[0]);
+
+    store.scrub_run(RUNS[0]).unwrap();
+    assert_eq!(checksums(RUNS[0]), kept);
+    assert_eq!(full(RUNS[1]), other);
+    assert!(full(RUNS[0]).iter().zip(&scrubbed).all(|(now, then)| now.rows == then.rows && now != then));
+    let _ = std::fs::remove_file(path);
+}
+
+/// A purge leaves no row of the run in any table, reports what it
+/// deleted, and leaves the other run whole.
+#[test]
+fn purge_removes_run_everywhere() {
+    let (path, store) = two_runs("purge");
+    let conn = Connection::open(&path).unwrap();
+    let tables = run_tables(&conn);
+    let rows = |run: &str| -> Vec<u64> {
+        tables.iter().map(|t| count(&conn, &format!("SELECT COUNT(*) FROM {t} WHERE run_id = ?1"), run)).collect()
+    };
+    let (purged, kept) = (rows(RUNS[0]), rows(RUNS[1]));
+    let other = store.table_checksum(RUNS[1], "transactions", &[]).unwrap();
+
+    let report = store.purge_run(RUNS[0]).unwrap();
+    assert_eq!(report.total(), purged.iter().sum::<u64>() + 1);
+    assert_eq!(report.tables["run"], 1);
+    assert!(rows(RUNS[0]).iter().all(|n| *n == 0));
+    assert_eq!(rows(RUNS[1]), kept);
+    assert_eq!(store.table_checksum(RUNS[1], "transactions", &[]).unwrap(), other);
+    let violations: u64 = conn.query_row("SELECT COUNT(*) FROM pragma_foreign_key_check", [], |r| r.get(0)).unwrap();
+    assert_eq!(violations, 0);
+    let _ = std::fs::remove_file(path);
+}
+
+/// Scrubbing or purging a run that is not there is an error, including
+/// purging one twice.
+#[test]
+fn unknown_run_rejected() {
+    let (path, store) = two_runs("unknown");
+    assert!(store.scrub_run("no-such-run").unwrap_err().to_string().contains("no run no-such-run"));
+    assert!(store.purge_run("no-such-run").is_err());
+    store.purge_run(RUNS[1]).unwrap();
+    assert!(store.purge_run(RUNS[1]).is_err());
+    assert!(store.describe_run(RUNS[0]).unwrap().is_some());
+    let _ = std::fs::remove_file(path);
+}
@@ -0,0 +1,197 @@
+//! Risk-tiered customer lifecycle tests — Phase 4.13.
+//!
+//! Tests cover: limit declines escalating customers up the tiers with the
+//! reason recorded, escalations bringing the EDD review forward to the
+//! new tier's cadence, reviews stepping customers back down once the
+//! behavior subsides, and each review booking the next at the cadence of
+//! the tier the customer leaves with.
+
+use fincrime_core::command::PlayerCommand;
+use fincrime_core::engine::SimEngine;
+use fincrime_core::risk_lifecycle::tier_rank;
+
+fn build_with_lifecycle(run_id: &str, seed: u64) -> SimEngine {
+    SimEngine::build_test_with_risk_lifecycle(run_id.to_string(), seed)
+        .expect("build test engine with risk lifecycle")
+}
+
+/// Set every customer limit type globally to `amount`.
+fn set_all_limits(engine: &mut SimEngine, amount: f64) {
+    for limit_type in ["daily_transfer", "cash_withdrawal", "p2p_send", "wire"] {
+        engine
+            .submit_command(PlayerCommand::SetCustomerLimit {
+                limit_type: limit_type.into(),
+                scope: "global".into(),
+                amount,
+            })
+            .unwrap();
+    }
+}
+
+fn review_interval(tier: &str) -> u64 {
+    match tier {
+        "critical" => 30,
+        "high" => 90,
+        "medium" => 180,
+        _ => 365,
+    }
+}
+
+/// Repeated limit declines push customers up a tier at a time, each move
+/// recorded with its reason and flagged on the customer's risk score.
+#[test]
+fn declines_escalate_tiers_with_reasons() {
+    let run_id = "risk-lifecycle-escalation-test";
+    let mut engine = build_with_lifecycle(run_id, 0x21F3_0001);
+
+    set_all_limits(&mut engine, 1.0);
+    engine.run_ticks(21).unwrap();
+
+    let transitions = engine.store_risk_tier_transitions(run_id).unwrap();
+    assert!(transitions.iter().any(|t| t.to_tier == "medium"));
+    assert!(transitions.iter().any(|t| t.to_tier == "high"));
+    for transition in &transitions {
+        assert_eq!(transition.reason, "behavior_escalation");
+        assert!(tier_rank(&transition.to_tier) > tier_rank(&transition.from_tier));
+        assert!(transition.behavior_risk >= 0.30);
+        assert_eq!(transition.tick % 7, 0, "Escalations happen at re-scores");
+    }
+
+    let last = transitions.last().unwrap();
+    let score = engine
+        .store
+        .get_customer_risk_score(run_id, &last.customer_id)
+        .unwrap()
+        .unwrap();
+    assert_eq!(score.risk_override.as_deref(), Some("escalated"));
+    assert_eq!(score.risk_override_reason.as_deref(), Some("behavior_escalation"));
+    let latest_tier = transitions
+        .iter()
+        .rev()
+        .find(|t| t.customer_id == last.customer_id)
+        .map(|t| t.to_tier.clone())
+        .unwrap();
+    assert_eq!(score.composite_risk, latest_tier);
+    assert_eq!(score.edd_required != 0, matches!(latest_tier.as_str(), "high" | "critical"));
+}
+
+/// Every customer has one review scheduled at their tier's cadence; an
+/// escalation brings it forward to the new tier's.
+#[test]
+fn escalation_brings_review_forward() {
+    let run_id = "risk-lifecycle-schedule-test";
+    let mut engine = build_with_lifecycle(run_id, 0x21F3_0002);
+
+    set_all_limits(&mut engine, 1.0);
+    engine.run_ticks(21).unwrap();
+
+    let transitions = engine.store_risk_tier_transitions(run_id).unwrap();
+    let reviews = engine.store_edd_reviews(run_id).unwrap();
+    assert_eq!(reviews.len(), 50, "One review per customer");
+    for review in &reviews {
+        assert_eq!(review.completed_tick, None);
+        let escalated_at = transitions
+            .iter()
+            .filter(|t| t.customer_id == review.customer_id)
+            .map(|t| t.tick)
+            .max();
+        match escalated_at {
+            Some(tick) => {
+                assert_eq!(review.trigger, "tier_change");
+                assert!(review.due_tick <= tick + review_interval(&review.tier));
+            }
+            None => {
+                assert_eq!(review.trigger, "scheduled");
+                assert_eq!(review.due_tick, 7 + review_interval("low"));
+            }
+        }
+    }
+}
+
+/// Once the declines stop, the EDD review steps the customer back down to
+/// their onboarding tier and schedules the next review at that cadence.
+#[test]
+fn reviews_step_tiers_down_once_behavior_subsides() {
+    let run_id = "risk-lifecycle-downgrade-test";
+    let mut engine = build_with_lifecycle(run_id, 0x21F3_0003);
+
+    set_all_limits(&mut engine, 1.0);
+    engine.run_ticks(14).unwrap();
+    set_all_limits(&mut engine, 1_000_000.0);
+    engine.run_ticks(100).unwrap();
+
+    let reviews = engine.store_edd_reviews(run_id).unwrap();
+    let completed: Vec<_> = reviews.iter().filter(|r| r.completed_tick.is_some()).collect();
+    assert!(!completed.is_empty());
+    for review in &completed {
+        let done = review.completed_tick.unwrap();
+        assert_eq!(review.outcome.as_deref(), Some("downgraded"));
+        assert_eq!(review.cost, 400.0, "Reviewed at the high tier");
+        let score = engine
+            .store
+            .get_customer_risk_score(run_id, &review.customer_id)
+            .unwrap()
+            .unwrap();
+        assert_eq!(score.composite_risk, "low");
+        assert_eq!(score.edd_last_review_tick, Some(done as i64));
+        assert_eq!(score.edd_required, 0);
+        let next = reviews
+            .iter()
+            .find(|r| r.customer_id == review.customer_id && r.completed_tick.is_none())
+            .expect("next review scheduled");
+        assert_eq!(next.due_tick, done + review_interval("low"));
+    }
+    assert!(engine
+        .store_risk_tier_transitions(run_id)
+        .unwrap()
+        .iter()
+        .any(|t| t.reason == "review_downgrade" && t.to_tier == "low"));
+}
+
+/// Reviews fall on their due tick, cost what their tier's review costs,
+/// and book the next one at the cadence of the tier the customer leaves
+/// with: the same cadence when retained, a longer one when downgraded.
+#[test]
+fn reviews_keep_customers_on_their_tier_cadence() {
+    let run_id = "risk-lifecycle-cadence-test";
+    let mut engine = build_with_lifecycle(run_id, 0x21F3_0004);
+
+    set_all_limits(&mut engine, 1.0);
+    engine.run_ticks(114).unwrap();
+
+    let reviews = engine.store_edd_reviews(run_id).unwrap();
+    let completed: Vec<_> = reviews.iter().filter(|r| r.completed_tick.is_some()).collect();
+    assert!(completed
+        .iter()
+        .any(|r| r.tier == "critical" && r.outcome.as_deref() == Some("retained")));
+    for review in &completed {
+        let done = review.completed_tick.unwrap();
+        assert_eq!(done, review.due_tick);
+        let expected_cost = match review.tier.as_str() {
+            "critical" => 750.0,
+            "high" => 400.0,
+            "medium" => 100.0,
+            _ => 50.0,
+        };
+        assert_eq!(review.cost, expected_cost);
+
+        let next = reviews
+            .iter()
+            .find(|r| r.review_id == format!("edd-{}-{done}", review.customer_id))
+            .expect("next review booked");
+        if next.trigger == "tier_change" {
+            continue; // brought forward again by a later escalation
+        }
+        match review.outcome.as_deref() {
+            Some("retained") => {
+                assert_eq!(next.tier, review.tier);
+                assert_eq!(next.due_tick, done + review_interval(&review.tier));
+            }
+            Some("downgraded") => {
+                assert!(tier_rank(&next.tier) < tier_rank(&review.tier));
+                assert!(next.due_tick > done + review_interval(&review.tier));
+            }
+            other => panic!("Unexpected outcome {other:?}"),
+        }
+    }
+}
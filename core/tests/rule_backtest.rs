@@ -0,0 +1,201 @@
+//! Monitoring rule backtest tests — Phase 4.72.
+//!
+//! Tests cover: a large-transaction rule flagging exactly the stored
+//! transactions over its threshold, tick by tick and within a narrowed
+//! window; flagged customers set against the run's own alerts; ground
+//! truth from the post-mortem's fraud cases with the alert list capped;
+//! windows and parameters refused; and the IPC request parsing.
+
+use fincrime_core::{
+    engine::SimEngine,
+    protocol::{IpcCommand, IpcRequest},
+    rule_backtest::{BacktestReport, RuleDefinition, MAX_LISTED_ALERTS},
+    transaction_monitoring_subsystem::AMLAlert,
+};
+use std::collections::{BTreeMap, BTreeSet};
+
+fn backtest(engine: &SimEngine, rule: RuleDefinition, from: Option<u64>, to: Option<u64>) -> BacktestReport {
+    BacktestReport::run(&engine.store, &engine.run_id, engine.clock.current_tick, rule, from, to).unwrap()
+}
+
+fn large_debits(min_amount: f64) -> RuleDefinition {
+    RuleDefinition::LargeTransaction { min_amount, direction: Some("debit".into()) }
+}
+
+fn live_alert(engine: &SimEngine, alert_id: &str, customer_id: &str, tick: u64) -> AMLAlert {
+    AMLAlert {
+        alert_id: alert_id.into(),
+        run_id: engine.run_id.clone(),
+        customer_id: customer_id.into(),
+        tick,
+        rule_id: "STRUCT_9K".into(),
+        alert_type: "structuring".into(),
+        alert_score: 60.0,
+        description: "test alert".into(),
+        triggered_amount: None,
+        transaction_count: None,
+        status: "open".into(),
+    }
+}
+
+/// One alert per customer per tick with a debit over the line, carrying
+/// the total of that customer's qualifying debits.
+#[test]
+fn large_transactions_match_the_stored_ones() {
+    let mut engine = SimEngine::build_test("backtest-large-test".into(), 0xB7E5_0001).unwrap();
+    engine.run_ticks(10).unwrap();
+
+    let mut expected: BTreeMap<(u64, String), (f64, usize)> = BTreeMap::new();
+    for txn in engine.store.get_all_transactions_in_window(&engine.run_id, 1, 10).unwrap() {
+        if txn.txn_type == "debit" && txn.amount.abs() >= 100.0 {
+            let entry = expected.entry((txn.tick, txn.customer_id.clone())).or_default();
+            entry.0 += txn.amount.abs();
+            entry.1 += 1;
+        }
+    }
+    assert!(!expected.is_empty(), "Some debits of 100 or more");
+
+    let report = backtest(&engine, large_debits(100.0), None, None);
+    assert_eq!((report.from_tick, report.to_tick), (1, 10));
+    assert_eq!(report.alert_count, expected.len());
+    assert!(!report.truncated);
+    for alert in &report.alerts {
+        let (amount, count) = expected[&(alert.tick, alert.customer_id.clone())];
+        assert!((alert.amount - amount).abs() < 1e-6);
+        assert_eq!(alert.transaction_count, count);
+    }
+    let customers: BTreeSet<_> = expected.keys().map(|(_, c)| c.clone()).collect();
+    assert_eq!(report.customers_flagged, customers.len());
+
+    let narrowed = backtest(&engine, large_debits(100.0), Some(4), Some(6));
+    assert_eq!(narrowed.alert_count, expected.keys().filter(|(tick, _)| (4..=6).contains(tick)).count());
+    assert!(narrowed.alerts.iter().all(|a| (4..=6).contains(&a.tick)));
+}
+
+/// A live alert on a flagged customer counts for both; one on a customer
+/// the rule passes over counts as live only.
+#[test]
+fn flagged_customers_are_set_against_live_alerts() {
+    let mut engine = SimEngine::build_test("backtest-overlap-test".into(), 0xB7E5_0002).unwrap();
+    engine.run_ticks(8).unwrap();
+    let rule = large_debits(100.0);
+    let before = backtest(&engine, rule.clone(), None, None);
+    let flagged = before.alerts.iter().find(|a| !a.live_alerted).expect("a flagged customer the run did not alert on");
+    assert_eq!(
+        before.overlap.both + before.overlap.rule_only,
+        before.customers_flagged,
+        "Every flagged customer is counted once"
+    );
+
+    let store = &engine.store;
+    let live = store.alerted_customers_between(&engine.run_id, 1, 8).unwrap();
+    let passed_over = store
+        .get_all_transactions_in_window(&engine.run_id, 1, 8)
+        .unwrap()
+        .into_iter()
+        .map(|t| t.customer_id)
+        .find(|c| !live.contains(c) && before.alerts.iter().all(|a| a.customer_id != *c))
+        .expect("a customer the rule and the run both passed over");
+    store.insert_transaction_monitoring_alert(&live_alert(&engine, "bt-a1", &flagged.customer_id, 3)).unwrap();
+    store.insert_transaction_monitoring_alert(&live_alert(&engine, "bt-a2", &passed_over, 3)).unwrap();
+    let after = backtest(&engine, rule.clone(), None, None);
+    assert_eq!(after.overlap.both, before.overlap.both + 1);
+    assert_eq!(after.overlap.rule_only, before.overlap.rule_only - 1);
+    assert_eq!(after.overlap.live_only, before.overlap.live_only + 1);
+    assert!(after.alerts.iter().filter(|a| a.customer_id == flagged.customer_id).all(|a| a.live_alerted));
+
+    // Outside the window the live alerts do not count
+    let later = backtest(&engine, rule, Some(4), None);
+    assert_eq!(
+        later.overlap.live_only,
+        store.alerted_customers_between(&engine.run_id, 4, 8).unwrap().len() - later.overlap.both
+    );
+}
+
+/// A rule that flags everyone who moved money catches every case customer
+/// who transacted; typology counts add up to the totals, and the alert
+/// list stops at its cap while the count goes on.
+#[test]
+fn ground_truth_comes_from_the_fraud_cases() {
+    let mut engine = SimEngine::build_test_with_ach_takeovers("backtest-truth-test".into(), 0xB7E5_0003).unwrap();
+    engine.run_ticks(20).unwrap();
+    let report = backtest(&engine, RuleDefinition::LargeTransaction { min_amount: 0.0, direction: None }, None, None);
+
+    let truth = &report.ground_truth;
+    let cases: BTreeSet<_> = engine.post_mortem().unwrap().cases.into_iter().map(|c| c.customer_id).collect();
+    assert!(truth.customers > 0, "Takeovers ran");
+    assert_eq!(truth.customers, cases.len());
+    assert_eq!(truth.by_typology.iter().map(|t| t.customers).sum::<usize>(), truth.customers);
+    assert_eq!(truth.by_typology.iter().map(|t| t.flagged).sum::<usize>(), truth.flagged);
+    assert_eq!(truth.hit_rate, Some(truth.flagged as f64 / report.customers_flagged as f64));
+    assert_eq!(truth.recall, Some(truth.flagged as f64 / truth.customers as f64));
+    assert!(truth.flagged > 0, "Taken-over customers transacted");
+    for alert in &report.alerts {
+        assert_eq!(alert.ground_truth.is_some(), cases.contains(&alert.customer_id));
+    }
+
+    assert!(report.alert_count > MAX_LISTED_ALERTS);
+    assert!(report.truncated);
+    assert_eq!(report.alerts.len(), MAX_LISTED_ALERTS);
+    assert!(report.alerts.windows(2).all(|w| w[0].tick <= w[1].tick), "Oldest first");
+}
+
+/// Windows outside the ticks played and rules that could never be
+/// deployed are refused without ending the session.
+#[test]
+fn windows_and_parameters_are_validated() {
+    let mut engine = SimEngine::build_test("backtest-validate-test".into(), 0xB7E5_0004).unwrap();
+    engine.run_ticks(3).unwrap();
+    let refused = |rule: RuleDefinition, from: Option<u64>, to: Option<u64>| {
+        let e = BacktestReport::run(&engine.store, &engine.run_id, 3, rule, from, to).unwrap_err();
+        assert_eq!(e.code(), "command_error");
+        assert!(!e.is_fatal());
+    };
+    refused(large_debits(1_000.0), Some(3), Some(2));
+    refused(large_debits(1_000.0), None, Some(4));
+    refused(large_debits(-1.0), None, None);
+    refused(RuleDefinition::LargeTransaction { min_amount: 1.0, direction: Some("sideways".into()) }, None, None);
+    refused(
+        RuleDefinition::Structuring { min_amount: 9_000.0, max_amount: 9_000.0, min_count: 3, lookback_ticks: 7 },
+        None,
+        None,
+    );
+    refused(RuleDefinition::Velocity { min_amount: 50_000.0, min_count: 0, lookback_ticks: 7 }, None, None);
+    refused(RuleDefinition::RapidMovement { min_amount: 5_000.0, window_ticks: 91 }, None, None);
+    refused(RuleDefinition::Velocity { min_amount: f64::NAN, min_count: 10, lookback_ticks: 7 }, None, None);
+
+    let live = RuleDefinition::Structuring { min_amount: 9_000.0, max_amount: 10_000.0, min_count: 3, lookback_ticks: 7 };
+    assert_eq!(backtest(&engine, live, Some(2), None).from_tick, 2);
+}
+
+/// `backtest_rule` names its rule by `kind`; the window is optional.
+#[test]
+fn backtest_requests_parse() {
+    let request: IpcRequest = serde_json::from_str(
+        r#"{"type": "backtest_rule", "rule": {"kind": "velocity", "min_amount": 40000, "min_count": 8, "lookback_ticks": 7}}"#,
+    )
+    .unwrap();
+    assert_eq!(request.command.name(), "backtest_rule");
+    match request.command {
+        IpcCommand::BacktestRule { rule, from_tick, to_tick } => {
+            assert_eq!(rule, RuleDefinition::Velocity { min_amount: 40_000.0, min_count: 8, lookback_ticks: 7 });
+            assert_eq!((from_tick, to_tick), (None, None));
+        }
+        other => panic!("parsed as {other:?}"),
+    }
+
+    let request: IpcRequest = serde_json::from_str(
+        r#"{"type": "backtest_rule", "run_id": "r2", "from_tick": 5, "to_tick": 9,
+            "rule": {"kind": "large_transaction", "min_amount": 10000}}"#,
+    )
+    .unwrap();
+    assert_eq!(request.run_id.as_deref(), Some("r2"));
+    assert!(matches!(
+        request.command,
+        IpcCommand::BacktestRule {
+            rule: RuleDefinition::LargeTransaction { direction: None, .. },
+            from_tick: Some(5),
+            to_tick: Some(9)
+        }
+    ));
+}
@@ -0,0 +1,159 @@
+//! Monitoring rule tuning tests — Phase 4.17.
+//!
+//! Tests cover: suppressions and exclusions silencing the rule they tune,
+//! undocumented suppressions drawing a critical exam finding, changes
+//! made under an approved tuning review passing the exam, and a review of
+//! another rule not counting as documentation until the change is
+//! restored.
+
+use fincrime_core::command::PlayerCommand;
+use fincrime_core::engine::SimEngine;
+
+fn build_with_tuning(run_id: &str, seed: u64) -> SimEngine {
+    SimEngine::build_test_with_rule_tuning(run_id.to_string(), seed)
+        .expect("build test engine with rule tuning")
+}
+
+fn tune(
+    engine: &mut SimEngine,
+    rule_id: &str,
+    change: &str,
+    customer_id: Option<&str>,
+    review_id: Option<&str>,
+) {
+    engine
+        .submit_command(PlayerCommand::TuneMonitoringRule {
+            rule_id: rule_id.into(),
+            change: change.into(),
+            threshold_multiplier: 1.5,
+            customer_id: customer_id.map(String::from),
+            review_id: review_id.map(String::from),
+        })
+        .unwrap();
+}
+
+fn open_review(engine: &mut SimEngine, rule_id: &str) {
+    engine
+        .submit_command(PlayerCommand::OpenTuningReview {
+            rule_id: rule_id.into(),
+            rationale: "alert volume on low-risk payroll customers".into(),
+        })
+        .unwrap();
+}
+
+fn undocumented_findings(engine: &SimEngine, run_id: &str) -> Vec<(String, f64)> {
+    engine
+        .store
+        .exam_findings_in_category(run_id, "undocumented_suppression")
+        .unwrap()
+}
+
+/// Plant three deposits just under $10k on the customer's account at
+/// `tick` and return the customer.
+fn plant_structuring(engine: &SimEngine, run_id: &str, account_id: &str, tick: u64) -> String {
+    for n in 0..3 {
+        let txn_id = format!("struct-{account_id}-{n}");
+        engine
+            .store
+            .insert_transaction(run_id, &txn_id, account_id, tick, 9_500.0, "credit", "cash", None)
+            .unwrap();
+    }
+    engine.store.get_account_customer_id(run_id, account_id).unwrap()
+}
+
+fn structuring_alerts(engine: &SimEngine, run_id: &str, customer_id: &str) -> usize {
+    engine
+        .store
+        .get_alerts_above_threshold(run_id, 0.0, 0, 10)
+        .unwrap()
+        .iter()
+        .filter(|a| a.customer_id == customer_id && a.alert_type == "structuring")
+        .count()
+}
+
+/// A suppressed rule raises nothing, and an excluded customer is skipped
+/// by the rule while everyone else is still monitored.
+#[test]
+fn suppressions_and_exclusions_silence_the_rule() {
+    let run_id = "rule-tuning-silence-test";
+    let mut engine = build_with_tuning(run_id, 0x7E9E_0001);
+    engine.run_ticks(1).unwrap();
+    let excluded = plant_structuring(&engine, run_id, "a-000001", 2);
+    let monitored = plant_structuring(&engine, run_id, "a-000002", 2);
+    tune(&mut engine, "STRUCT_9K", "exclude_customer", Some(&excluded), None);
+    engine.run_ticks(5).unwrap();
+    assert_eq!(structuring_alerts(&engine, run_id, &excluded), 0);
+    assert!(structuring_alerts(&engine, run_id, &monitored) > 0);
+
+    let run_id = "rule-tuning-suppress-test";
+    let mut engine = build_with_tuning(run_id, 0x7E9E_0001);
+    engine.run_ticks(1).unwrap();
+    let customer = plant_structuring(&engine, run_id, "a-000002", 2);
+    tune(&mut engine, "STRUCT_9K", "suppress", None, None);
+    engine.run_ticks(5).unwrap();
+    assert_eq!(structuring_alerts(&engine, run_id, &customer), 0);
+}
+
+/// A suppression with no tuning review behind it is applied, but the
+/// next exam finds it and levies a critical finding.
+#[test]
+fn undocumented_suppression_draws_critical_finding() {
+    let run_id = "rule-tuning-undocumented-test";
+    let mut engine = build_with_tuning(run_id, 0x7E9E_0002);
+    engine.run_ticks(1).unwrap();
+    tune(&mut engine, "VEL_50K_7D", "suppress", None, None);
+    engine.run_ticks(69).unwrap();
+
+    let tunings = engine.store_rule_tunings(run_id).unwrap();
+    assert_eq!(tunings.len(), 1);
+    assert!(!tunings[0].documented);
+    assert!(tunings[0].active);
+    assert_eq!(undocumented_findings(&engine, run_id), vec![("critical".to_string(), 1_000_000.0)]);
+}
+
+/// A change citing an approved review of the same rule is documented,
+/// and the exam has nothing to find.
+#[test]
+fn tuning_under_approved_review_passes_exam() {
+    let run_id = "rule-tuning-documented-test";
+    let mut engine = build_with_tuning(run_id, 0x7E9E_0003);
+    engine.run_ticks(1).unwrap();
+    open_review(&mut engine, "RAPID_MOVE");
+    engine.run_ticks(15).unwrap();
+
+    let reviews = engine.store_tuning_reviews(run_id).unwrap();
+    assert_eq!(reviews.len(), 1);
+    let review = &reviews[0];
+    assert_eq!(review.status, "approved");
+    assert_eq!(review.completed_tick, Some(review.opened_tick + 14));
+    assert_eq!(review.cost, 2_500.0);
+
+    tune(&mut engine, "RAPID_MOVE", "raise_threshold", None, Some(&review.review_id));
+    engine.run_ticks(54).unwrap();
+    let tunings = engine.store_rule_tunings(run_id).unwrap();
+    assert!(tunings[0].documented);
+    assert_eq!(tunings[0].threshold_multiplier, 1.5);
+    assert!(undocumented_findings(&engine, run_id).is_empty());
+}
+
+/// A review of a different rule, or one still in review, does not
+/// document the change; restoring the rule before the exam clears it.
+#[test]
+fn restoring_undocumented_tuning_before_exam_avoids_finding() {
+    let run_id = "rule-tuning-restore-test";
+    let mut engine = build_with_tuning(run_id, 0x7E9E_0004);
+    engine.run_ticks(1).unwrap();
+    open_review(&mut engine, "VEL_50K_7D");
+    engine.run_ticks(1).unwrap();
+    let review_id = engine.store_tuning_reviews(run_id).unwrap()[0].review_id.clone();
+    tune(&mut engine, "VEL_50K_7D", "suppress", None, Some(&review_id));
+    tune(&mut engine, "STRUCT_9K", "suppress", None, Some(&review_id));
+    engine.run_ticks(20).unwrap();
+    assert!(engine.store_rule_tunings(run_id).unwrap().iter().all(|t| !t.documented));
+
+    tune(&mut engine, "VEL_50K_7D", "restore", None, None);
+    tune(&mut engine, "STRUCT_9K", "restore", None, None);
+    engine.run_ticks(48).unwrap();
+    assert!(engine.store_rule_tunings(run_id).unwrap().iter().all(|t| !t.active));
+    assert!(undocumented_findings(&engine, run_id).is_empty());
+}
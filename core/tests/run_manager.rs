@@ -0,0 +1,125 @@
+//! Multi-run hosting tests — Phase 4.70.
+//!
+//! Tests cover: hosted runs stepping independently, each in its own
+//! database; requests routed to the run they name or the default run,
+//! and unknown runs refused; new runs refused without a fresh id;
+//! closing a run, the default included; and `run_id` parsed alongside
+//! any IPC command.
+
+use fincrime_core::{
+    config::SimConfig,
+    engine::SimEngine,
+    error::SimError,
+    protocol::{IpcCommand, IpcRequest},
+    run_manager::{RunManager, RunTemplate},
+};
+use std::path::PathBuf;
+
+fn manager(run_id: &str, db_dir: Option<PathBuf>) -> RunManager {
+    let first = SimEngine::build_test(run_id.into(), 42).unwrap();
+    let template = RunTemplate {
+        db_dir,
+        config: SimConfig::default_test(),
+        version: "0.1.0-test".into(),
+    };
+    RunManager::new(first, template)
+}
+
+fn refused(result: Result<impl std::fmt::Debug, SimError>) -> String {
+    let e = result.unwrap_err();
+    assert_eq!(e.code(), "command_error", "{e}");
+    assert!(!e.is_fatal());
+    e.to_string()
+}
+
+/// Two runs in one database keep their own seed, clock and rows.
+#[test]
+fn runs_step_independently() {
+    let mut runs = manager("runs-a-test", None);
+    runs.create(Some("runs-b-test"), 7).unwrap();
+
+    runs.run_mut("tick", None).unwrap().engine.run_ticks(5).unwrap();
+    runs.run_mut("tick", Some("runs-b-test")).unwrap().engine.run_ticks(2).unwrap();
+
+    let list = runs.list();
+    let summary: Vec<_> = list.runs.iter().map(|r| (r.run_id.as_str(), r.seed, r.tick)).collect();
+    assert_eq!(summary, [("runs-a-test", 42, 5), ("runs-b-test", 7, 2)]);
+    assert_eq!(list.default_run.as_deref(), Some("runs-a-test"));
+
+    let b = &runs.run_mut("get_state", Some("runs-b-test")).unwrap().engine;
+    assert!(!b.store.events_for_tick("runs-b-test", 2).unwrap().is_empty());
+    assert!(b.store.events_for_tick("runs-b-test", 3).unwrap().is_empty());
+    assert!(b.store.describe_run("runs-a-test").unwrap().is_none());
+}
+
+/// A request without a run id goes to the default run; one naming a run
+/// this process does not host is refused without ending the session.
+#[test]
+fn requests_route_by_run_id() {
+    let mut runs = manager("route-a-test", None);
+    runs.create(Some("route-b-test"), 7).unwrap();
+
+    assert_eq!(runs.run_mut("get_state", None).unwrap().engine.run_id, "route-a-test");
+    assert_eq!(runs.run_mut("get_state", Some("route-b-test")).unwrap().engine.run_id, "route-b-test");
+    let message = refused(runs.run_mut("tick", Some("route-c-test")).map(|_| ()));
+    assert!(message.contains("route-c-test"), "{message}");
+}
+
+/// A new run needs an id no hosted run, and no database beside them,
+/// already has.
+#[test]
+fn create_needs_a_fresh_run_id() {
+    let dir = std::env::temp_dir().join(format!("fincrime-runs-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let mut runs = manager("create-a-test", Some(dir.clone()));
+    refused(runs.create(None, 7));
+    refused(runs.create(Some("  "), 7));
+    refused(runs.create(Some("../elsewhere"), 7));
+    let message = refused(runs.create(Some("create-a-test"), 7));
+    assert!(message.contains("already exists"), "{message}");
+
+    runs.create(Some("create-b-test"), 7).unwrap();
+    refused(runs.create(Some("create-b-test"), 8));
+    // A closed run keeps its database, so its id stays taken
+    runs.close(Some("create-b-test")).unwrap();
+    let message = refused(runs.create(Some("create-b-test"), 8));
+    assert!(message.contains("create-b-test.db"), "{message}");
+    assert_eq!(runs.list().runs.len(), 1);
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+/// Closing the default run leaves the others reachable by name only.
+#[test]
+fn closing_the_default_run() {
+    let mut runs = manager("close-a-test", None);
+    runs.create(Some("close-b-test"), 7).unwrap();
+
+    let list = runs.close(None).unwrap();
+    assert_eq!(list.default_run, None);
+    assert_eq!(list.runs.len(), 1);
+    refused(runs.run_mut("tick", None).map(|_| ()));
+    runs.run_mut("tick", Some("close-b-test")).unwrap().engine.run_ticks(1).unwrap();
+    refused(runs.close(Some("close-a-test")));
+
+    runs.close(Some("close-b-test")).unwrap();
+    assert!(runs.is_empty());
+}
+
+/// `run_id` sits beside any command's own fields, and may be left out.
+#[test]
+fn ipc_requests_carry_an_optional_run_id() {
+    let parse = |line: &str| serde_json::from_str::<IpcRequest>(line).unwrap();
+
+    let named = parse(r#"{"type":"tick","count":2,"run_id":"run-7"}"#);
+    assert_eq!(named.run_id.as_deref(), Some("run-7"));
+    assert!(matches!(named.command, IpcCommand::Tick { count: 2, summary_only: false }));
+
+    let default = parse(r#"{"type":"get_state"}"#);
+    assert_eq!(default.run_id, None);
+    assert!(matches!(default.command, IpcCommand::GetState { summary_only: false }));
+
+    let create = parse(r#"{"type":"create_run","run_id":"run-8","seed":8}"#);
+    assert_eq!(create.run_id.as_deref(), Some("run-8"));
+    assert!(matches!(create.command, IpcCommand::CreateRun { seed: 8 }));
+    assert!(matches!(parse(r#"{"type":"list_runs"}"#).command, IpcCommand::ListRuns));
+}
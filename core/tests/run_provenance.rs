@@ -0,0 +1,107 @@
+//! Run provenance tests — Phase 4.24.
+//!
+//! Tests cover: provenance round-tripping through the run table,
+//! runs created without it describing with defaults, the scenario hash
+//! tracking file contents and names but not location, and capture
+//! recording the host and a hash of the shipped data directory.
+
+use fincrime_core::provenance::{self, HostInfo, RunProvenance};
+use fincrime_core::store::SimStore;
+use std::path::PathBuf;
+
+fn store() -> SimStore {
+    let store = SimStore::open(":memory:").unwrap();
+    store.migrate().unwrap();
+    store
+}
+
+/// A scratch scenario directory with two files, one nested.
+fn scenario_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("provenance_{name}_{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(dir.join("segments")).unwrap();
+    std::fs::write(dir.join("segments/segments.json"), r#"{"segments":[]}"#).unwrap();
+    std::fs::write(dir.join("products.json"), "[]").unwrap();
+    dir
+}
+
+/// Everything captured at insert comes back from describe_run.
+#[test]
+fn provenance_round_trips() {
+    let store = store();
+    let provenance = RunProvenance {
+        cli_args: vec!["sim-runner".into(), "--seed".into(), "9".into()],
+        scenario_hash: Some("ab".repeat(32)),
+        scenario: None,
+        git_commit: Some("0123abcd-dirty".into()),
+        features: vec!["grpc".into()],
+        host: Some(HostInfo {
+            os: "linux".into(),
+            arch: "x86_64".into(),
+            hostname: Some("desk-01".into()),
+            cpus: 8,
+        }),
+        started_at: 1_700_000_000,
+    };
+    store
+        .insert_run_with_provenance("prov-run", 9, "0.1.0", &provenance)
+        .unwrap();
+
+    let described = store.describe_run("prov-run").unwrap().unwrap();
+    assert_eq!(described.seed, 9);
+    assert_eq!(described.version, "0.1.0");
+    assert_eq!(described.provenance, provenance);
+}
+
+/// Runs inserted the old way still describe, with empty provenance;
+/// an unknown run is None rather than an error.
+#[test]
+fn runs_without_provenance_describe_with_defaults() {
+    let store = store();
+    store.insert_run("plain-run", 3, "0.1.0").unwrap();
+
+    let described = store.describe_run("plain-run").unwrap().unwrap();
+    assert_eq!(described.provenance, RunProvenance::default());
+    assert!(store.describe_run("no-such-run").unwrap().is_none());
+}
+
+/// Editing or renaming a file changes the hash; the same contents in a
+/// different directory do not.
+#[test]
+fn scenario_hash_tracks_contents_not_location() {
+    let a = scenario_dir("a");
+    let b = scenario_dir("b");
+    let hash = |dir: &PathBuf| provenance::hash_scenario(dir.to_str().unwrap()).unwrap();
+
+    let original = hash(&a);
+    assert_eq!(original.len(), 64);
+    assert_eq!(original, hash(&b), "Location is not part of the hash");
+
+    std::fs::write(b.join("products.json"), "[1]").unwrap();
+    let edited = hash(&b);
+    assert_ne!(original, edited);
+
+    std::fs::rename(b.join("products.json"), b.join("products_v2.json")).unwrap();
+    assert_ne!(edited, hash(&b), "Renaming a file changes the hash");
+
+    let _ = std::fs::remove_dir_all(a);
+    let _ = std::fs::remove_dir_all(b);
+}
+
+/// capture() hashes the data directory and records this host; a data
+/// directory that does not exist is an error, not a silent blank.
+#[test]
+fn capture_records_scenario_and_host() {
+    let data_dir = concat!(env!("CARGO_MANIFEST_DIR"), "/../data");
+    let captured = RunProvenance::capture(vec!["sim-runner".into()], data_dir, Some("abc"), &["grpc"]).unwrap();
+
+    assert_eq!(captured.scenario_hash, Some(provenance::hash_scenario(data_dir).unwrap()));
+    assert_eq!(captured.git_commit.as_deref(), Some("abc"));
+    assert_eq!(captured.features, vec!["grpc".to_string()]);
+    let host = captured.host.unwrap();
+    assert_eq!(host.os, std::env::consts::OS);
+    assert!(host.cpus >= 1);
+    assert!(captured.started_at > 0);
+
+    assert!(RunProvenance::capture(vec![], "/nonexistent/scenario", None, &[]).is_err());
+}
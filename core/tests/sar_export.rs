@@ -0,0 +1,175 @@
+//! SAR e-filing export tests — Phase 4.84.
+//!
+//! Tests cover: a filing carrying the subject's KYC record and accounts,
+//! the FinCEN classification of its activity, the alerts it was filed on
+//! and the subject's largest transactions; the narrative assembled from
+//! them; each batch taking only the SARs not yet exported and marking
+//! them with its id; the XML rendering; and reading alert ids written
+//! both before and after `related_alerts` became valid JSON.
+
+use fincrime_core::{
+    engine::SimEngine,
+    sar_export::{self, activity_classification, related_alert_ids, SarExportFormat, SAR_MAX_TRANSACTIONS},
+    transaction_monitoring_subsystem::AMLAlert,
+};
+use std::path::PathBuf;
+
+fn temp_dir(name: &str) -> PathBuf {
+    std::env::temp_dir().join(format!("sar_export_{name}_{}", std::process::id()))
+}
+
+/// Raise a structuring alert on the first customer at the current tick and
+/// run on to `ticks`; returns the customer.
+fn alert_and_run(engine: &mut SimEngine, alert_id: &str, description: &str, ticks: u64) -> String {
+    let run_id = engine.run_id.clone();
+    let customer_id = engine.store.active_customers(&run_id).unwrap()[0].customer_id.clone();
+    engine
+        .store
+        .insert_transaction_monitoring_alert(&AMLAlert {
+            alert_id: alert_id.into(),
+            run_id: run_id.clone(),
+            customer_id: customer_id.clone(),
+            tick: engine.clock.current_tick,
+            rule_id: "TEST".into(),
+            alert_type: "structuring".into(),
+            alert_score: 95.0,
+            description: description.into(),
+            triggered_amount: Some(9_500.0),
+            transaction_count: Some(3),
+            status: "open".into(),
+        })
+        .unwrap();
+    engine.run_ticks(ticks - engine.clock.current_tick).unwrap();
+    customer_id
+}
+
+fn alerted_run(run_id: &str) -> (SimEngine, String) {
+    let mut engine = SimEngine::build_test(run_id.into(), 42).unwrap();
+    engine.run_ticks(40).unwrap();
+    let customer_id = alert_and_run(&mut engine, "A0", "three deposits of $9,500 & under", 45);
+    (engine, customer_id)
+}
+
+/// A filing on an alerted customer names them with their KYC record and
+/// accounts, classes the activity as structuring over the lookback before
+/// the alert, and lists the alert and the largest transactions.
+#[test]
+fn filing_carries_subject_activity_alerts_and_transactions() {
+    let run_id = "sar-export-filing-test";
+    let (engine, customer_id) = alerted_run(run_id);
+    let sar = engine
+        .store
+        .unexported_sars(run_id)
+        .unwrap()
+        .into_iter()
+        .find(|s| s.subject_id == customer_id)
+        .expect("a SAR on the alerted customer");
+    assert_eq!(related_alert_ids(sar.related_alerts.as_deref()), ["A0"]);
+
+    let filing = sar_export::render_sar(&engine.store, run_id, &sar).unwrap();
+    assert!(filing.subject.kyc.is_some());
+    assert_eq!(filing.subject.accounts, engine.store.customer_account_ids(run_id, &customer_id).unwrap());
+    assert_eq!(
+        (filing.activity.category.as_str(), filing.activity.subcategory.as_str()),
+        activity_classification("structuring")
+    );
+    assert_eq!((filing.activity.from_tick, filing.activity.to_tick), (40 - 30, sar.filing_tick));
+    assert_eq!(filing.alerts.len(), 1);
+    assert_eq!((filing.alerts[0].alert_id.as_str(), filing.alerts[0].tick), ("A0", 40));
+
+    assert!(filing.activity.transaction_count > 0);
+    assert!(filing.transactions.len() <= SAR_MAX_TRANSACTIONS);
+    assert!(filing.transactions.windows(2).all(|w| w[0].amount >= w[1].amount));
+    assert!(filing
+        .transactions
+        .iter()
+        .all(|t| filing.subject.accounts.contains(&t.account_id) && (10..=sar.filing_tick).contains(&t.tick)));
+}
+
+/// The narrative tells who, what and when, lists each alert, sums up the
+/// transactions and ends with the investigator's notes.
+#[test]
+fn narrative_assembled_from_the_filing() {
+    let run_id = "sar-export-narrative-test";
+    let (engine, customer_id) = alerted_run(run_id);
+    let sar = engine
+        .store
+        .unexported_sars(run_id)
+        .unwrap()
+        .into_iter()
+        .find(|s| s.subject_id == customer_id)
+        .unwrap();
+    let filing = sar_export::render_sar(&engine.store, run_id, &sar).unwrap();
+
+    let narrative = &filing.narrative;
+    assert!(narrative.starts_with(&format!("This report concerns customer {customer_id}, a ")));
+    assert!(narrative.contains("Structuring (Transaction(s) below CTR threshold)"));
+    assert!(narrative.contains("- Day 40: TEST (structuring, score 95): three deposits of $9,500 & under"));
+    assert!(narrative.contains(&format!("carried {} transaction(s)", filing.activity.transaction_count)));
+    assert!(narrative.ends_with(&format!("Investigator notes: {}", sar.narrative)));
+}
+
+/// A batch takes every SAR not yet exported and marks it with the batch
+/// id; the next batch takes only those filed since.
+#[test]
+fn batches_take_only_unexported_sars() {
+    let run_id = "sar-export-batch-test";
+    let dir = temp_dir("batch");
+    let (mut engine, _) = alerted_run(run_id);
+    let filed = engine.store.unexported_sars(run_id).unwrap();
+
+    let first = sar_export::export_sars(&engine.store, run_id, SarExportFormat::Json, &dir).unwrap().unwrap();
+    assert_eq!(first.batch_id, format!("sarx-{run_id}-0001"));
+    assert_eq!(first.sars, filed.len());
+    let written: serde_json::Value = serde_json::from_str(&std::fs::read_to_string(&first.path).unwrap()).unwrap();
+    assert_eq!(written["filings"].as_array().unwrap().len(), filed.len());
+    for sar in &filed {
+        assert_eq!(engine.store.sar_export_batch(run_id, &sar.sar_id).unwrap(), Some(first.batch_id.clone()));
+    }
+    assert!(sar_export::export_sars(&engine.store, run_id, SarExportFormat::Json, &dir).unwrap().is_none());
+
+    alert_and_run(&mut engine, "A1", "more deposits", 50);
+    let second = sar_export::export_sars(&engine.store, run_id, SarExportFormat::Json, &dir).unwrap().unwrap();
+    assert_eq!(second.batch_id, format!("sarx-{run_id}-0002"));
+    let written: serde_json::Value = serde_json::from_str(&std::fs::read_to_string(&second.path).unwrap()).unwrap();
+    let filings = written["filings"].as_array().unwrap();
+    assert!(filings.iter().all(|f| !filed.iter().any(|s| s.sar_id == f["sar_id"])));
+    assert!(filings.iter().any(|f| f["alerts"][0]["alert_id"] == "A1"));
+    assert_eq!(engine.store.sar_export_batch_count(run_id).unwrap(), 2);
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+/// The XML batch nests each filing's fields as PascalCase elements, list
+/// items under their list, and escapes text.
+#[test]
+fn xml_batch_is_structured_and_escaped() {
+    let run_id = "sar-export-xml-test";
+    let dir = temp_dir("xml");
+    let (engine, _) = alerted_run(run_id);
+
+    let batch = sar_export::export_sars(&engine.store, run_id, SarExportFormat::Xml, &dir).unwrap().unwrap();
+    assert_eq!(batch.path.extension().unwrap(), "xml");
+    let xml = std::fs::read_to_string(&batch.path).unwrap();
+    assert!(xml.starts_with("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<SARBatch>\n"));
+    assert!(xml.contains(&format!("  <BatchId>{}</BatchId>", batch.batch_id)));
+    assert_eq!(xml.matches("<Filing>").count(), batch.sars);
+    assert!(xml.contains("<Category>Structuring</Category>"));
+    assert!(xml.contains("<Alerts>\n        <Alert>\n          <AlertId>A0</AlertId>"));
+    assert!(xml.contains("<Description>three deposits of $9,500 &amp; under</Description>"));
+    assert!(!xml.contains("$9,500 & under"));
+    assert!(xml.trim_end().ends_with("</SARBatch>"));
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+/// Alert ids are read from valid JSON and from the unterminated arrays
+/// older runs wrote; alert types map to FinCEN categories.
+#[test]
+fn related_alerts_and_classification() {
+    assert_eq!(related_alert_ids(Some(r#"["A1","A2"]"#)), ["A1", "A2"]);
+    assert_eq!(related_alert_ids(Some(r#"["CORR-RESP-PA-01-7-25584]"#)), ["CORR-RESP-PA-01-7-25584"]);
+    assert!(related_alert_ids(None).is_empty());
+
+    assert_eq!(activity_classification("cross_border_funnel"), ("Money Laundering", "Funnel account"));
+    assert_eq!(activity_classification("structuring").0, "Structuring");
+    assert_eq!(activity_classification("something_new"), ("Other Suspicious Activities", "Other"));
+}
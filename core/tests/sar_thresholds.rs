@@ -0,0 +1,151 @@
+//! SAR decision threshold tests — Phase 4.16.
+//!
+//! Tests cover: a low threshold filing defensively at a cost and diluting
+//! SAR quality, a high threshold leaving reportable alerts unfiled until
+//! they are missed and found at the next exam, and lowering the threshold
+//! before the deadline rescuing the filing.
+
+use fincrime_core::command::PlayerCommand;
+use fincrime_core::engine::SimEngine;
+use fincrime_core::transaction_monitoring_subsystem::AMLAlert;
+
+fn build_with_sar_decisions(run_id: &str, seed: u64) -> SimEngine {
+    SimEngine::build_test_with_sar_decisions(run_id.to_string(), seed)
+        .expect("build test engine with SAR decisions")
+}
+
+fn set_threshold(engine: &mut SimEngine, threshold: f64) {
+    engine
+        .submit_command(PlayerCommand::SetSarThreshold { threshold })
+        .unwrap();
+}
+
+/// Onboard the population, then raise an open alert at tick 1 for one
+/// customer per score.
+fn raise_alerts(engine: &mut SimEngine, run_id: &str, alerts: &[(&str, f64)]) {
+    engine.run_ticks(1).unwrap();
+    let customers = engine.store.active_customers(run_id).unwrap();
+    for ((alert_id, score), customer) in alerts.iter().zip(&customers) {
+        raise_alert(engine, run_id, alert_id, &customer.customer_id, *score);
+    }
+}
+
+fn raise_alert(engine: &SimEngine, run_id: &str, alert_id: &str, customer_id: &str, score: f64) {
+    engine
+        .store
+        .insert_transaction_monitoring_alert(&AMLAlert {
+            alert_id: alert_id.into(),
+            run_id: run_id.into(),
+            customer_id: customer_id.into(),
+            tick: 1,
+            rule_id: "TEST".into(),
+            alert_type: "structuring".into(),
+            alert_score: score,
+            description: "test alert".into(),
+            triggered_amount: Some(9_500.0),
+            transaction_count: Some(3),
+            status: "open".into(),
+        })
+        .unwrap();
+}
+
+/// A low threshold files on every alert; those below the reportable score
+/// are defensive, each filing costs analyst time, and the month's SAR
+/// quality drops to the reportable share.
+#[test]
+fn low_threshold_files_defensively() {
+    let run_id = "sar-threshold-defensive-test";
+    let mut engine = build_with_sar_decisions(run_id, 0x5A70_0001);
+    set_threshold(&mut engine, 60.0);
+    raise_alerts(&mut engine, run_id, &[("A1", 65.0), ("A2", 75.0), ("A3", 80.0), ("A4", 90.0)]);
+    engine.run_ticks(30).unwrap();
+
+    let decisions = engine.store_sar_decisions(run_id).unwrap();
+    let ours: Vec<_> = decisions.iter().filter(|d| d.alert_id.starts_with('A')).collect();
+    assert_eq!(ours.len(), 4);
+    for decision in &ours {
+        assert_eq!(decision.tick, 7, "Filed at the first weekly run");
+        assert_eq!(decision.threshold, 60.0);
+        assert_eq!(decision.cost, 250.0);
+        assert!(decision.sar_id.is_some());
+        let expected = if decision.alert_score < 85.0 { "defensive" } else { "filed" };
+        assert_eq!(decision.decision, expected);
+    }
+
+    let metrics = engine.store_sar_quality_metrics(run_id).unwrap();
+    let month = metrics.iter().find(|m| m.tick == 30).unwrap();
+    assert!(month.sars_filed >= 4);
+    assert!(month.defensive_filings >= 3);
+    assert_eq!(month.missed_filings, 0);
+    assert!(month.sar_quality.unwrap() <= 0.25 + 1e-9);
+    assert_eq!(month.filing_cost, 250.0 * month.sars_filed as f64);
+}
+
+/// At the default threshold every filing is reportable and quality holds.
+#[test]
+fn default_threshold_keeps_quality() {
+    let run_id = "sar-threshold-quality-test";
+    let mut engine = build_with_sar_decisions(run_id, 0x5A70_0002);
+    raise_alerts(&mut engine, run_id, &[("A1", 75.0), ("A2", 90.0)]);
+    engine.run_ticks(30).unwrap();
+
+    let decisions = engine.store_sar_decisions(run_id).unwrap();
+    assert!(decisions.iter().all(|d| d.decision == "filed"));
+    assert!(decisions.iter().any(|d| d.alert_id == "A2"));
+    assert!(!decisions.iter().any(|d| d.alert_id == "A1"), "75 is below the threshold");
+
+    let metrics = engine.store_sar_quality_metrics(run_id).unwrap();
+    let month = metrics.iter().find(|m| m.tick == 30).unwrap();
+    assert_eq!(month.defensive_filings, 0);
+    assert_eq!(month.sar_quality, Some(1.0));
+}
+
+/// A threshold above the reportable score leaves the alert unfiled; once
+/// the deadline passes it is a missed filing, and the next exam levies a
+/// major finding for it.
+#[test]
+fn high_threshold_misses_filings_found_at_exam() {
+    let run_id = "sar-threshold-missed-test";
+    let mut engine = build_with_sar_decisions(run_id, 0x5A70_0003);
+    set_threshold(&mut engine, 95.0);
+    raise_alerts(&mut engine, run_id, &[("A1", 90.0)]);
+    engine.run_ticks(35).unwrap();
+
+    let decisions = engine.store_sar_decisions(run_id).unwrap();
+    let missed = decisions.iter().find(|d| d.alert_id == "A1").expect("decision recorded");
+    assert_eq!(missed.decision, "missed");
+    assert_eq!(missed.tick, 35, "Swept at the first weekly run past the deadline");
+    assert_eq!(missed.sar_id, None);
+    assert_eq!(missed.cost, 0.0);
+    let events = engine.store_events_for_tick(run_id, 35).unwrap();
+    assert!(events.iter().any(|e| e.event_type == "sar_filing_missed"));
+
+    engine.run_ticks(35).unwrap();
+    let findings = engine.store.exam_findings_in_category(run_id, "sar_missed_filing").unwrap();
+    assert_eq!(findings, vec![("major".to_string(), 250_000.0)]);
+}
+
+/// Lowering the threshold before the deadline gets the reportable alert
+/// filed instead of missed.
+#[test]
+fn lowering_threshold_before_deadline_rescues_filing() {
+    let run_id = "sar-threshold-rescue-test";
+    let mut engine = build_with_sar_decisions(run_id, 0x5A70_0004);
+    set_threshold(&mut engine, 95.0);
+    raise_alerts(&mut engine, run_id, &[("A1", 90.0)]);
+    engine.run_ticks(9).unwrap();
+    assert!(engine.store_sar_decisions(run_id).unwrap().iter().all(|d| d.alert_id != "A1"));
+
+    set_threshold(&mut engine, 85.0);
+    engine.run_ticks(60).unwrap();
+    let decisions = engine.store_sar_decisions(run_id).unwrap();
+    let filed = decisions.iter().find(|d| d.alert_id == "A1").expect("decision recorded");
+    assert_eq!(filed.decision, "filed");
+    assert_eq!(filed.tick, 14);
+    assert_eq!(filed.threshold, 85.0);
+    assert!(engine
+        .store
+        .exam_findings_in_category(run_id, "sar_missed_filing")
+        .unwrap()
+        .is_empty());
+}
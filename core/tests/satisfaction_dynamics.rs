@@ -0,0 +1,135 @@
+//! Satisfaction dynamics tests — Phase 4.33.
+//!
+//! Tests cover: satisfaction reverting toward the segment baseline,
+//! diminishing returns on repeated goodwill, breached urgent complaints
+//! scarring the baseline, and instantaneous deltas when dynamics are off.
+
+use fincrime_core::{config::SimConfig, engine::SimEngine, satisfaction::SatisfactionDynamics};
+
+fn build_with_dynamics(run_id: &str, seed: u64) -> SimEngine {
+    SimEngine::build_test_with_satisfaction_dynamics(run_id.to_string(), seed)
+        .expect("build test engine with satisfaction dynamics")
+}
+
+fn dynamics(run_id: &str) -> SatisfactionDynamics {
+    let mut config = SimConfig::default_test().satisfaction_dynamics;
+    config.enabled = true;
+    SatisfactionDynamics::new(run_id.into(), config)
+}
+
+/// The monthly pass closes 15% of each customer's gap to the baseline
+/// (less any fee hits that tick), from above and below, and the book
+/// settles toward it over a year.
+#[test]
+fn satisfaction_reverts_toward_segment_baseline() {
+    let run_id = "satisfaction-revert-test";
+    let mut engine = build_with_dynamics(run_id, 42);
+    engine.run_ticks(29).unwrap();
+
+    let customers = engine.store.active_customers(run_id).unwrap();
+    let (low, high) = (&customers[0].customer_id, &customers[1].customer_id);
+    engine.store.update_customer_satisfaction(run_id, low, -1.0).unwrap();
+    engine.store.update_customer_satisfaction(run_id, low, 0.40).unwrap();
+    engine.store.update_customer_satisfaction(run_id, high, 1.0).unwrap();
+    engine.run_ticks(1).unwrap();
+
+    let baseline = dynamics(run_id).baseline(&customers[0].segment, 0.0);
+    assert_eq!(baseline, 0.70);
+    let low_now = engine.store_customer_satisfaction(run_id, low).unwrap();
+    let high_now = engine.store_customer_satisfaction(run_id, high).unwrap();
+    assert!(low_now > 0.42 && low_now <= 0.40 + 0.30 * 0.15 + 1e-9, "{low_now}");
+    assert!(high_now > 0.93 && high_now <= 1.0 - 0.30 * 0.15 + 1e-9, "{high_now}");
+
+    engine.run_ticks(330).unwrap();
+    let active = engine.store.active_customers(run_id).unwrap();
+    let mean = active.iter().map(|c| c.satisfaction).sum::<f64>() / active.len() as f64;
+    assert!((mean - baseline).abs() < 0.03, "{mean}");
+}
+
+/// Each goodwill gesture in the window is worth half the one before;
+/// once the window has passed a gesture counts in full again, and
+/// losses are never discounted.
+#[test]
+fn repeated_goodwill_buys_less() {
+    let run_id = "satisfaction-goodwill-test";
+    let mut engine = build_with_dynamics(run_id, 42);
+    engine.run_ticks(1).unwrap();
+    let customer_id = engine.store.active_customers(run_id).unwrap()[0].customer_id.clone();
+    engine.store.update_customer_satisfaction(run_id, &customer_id, -1.0).unwrap();
+
+    let dynamics = dynamics(run_id);
+    let applied: Vec<f64> = (0..3)
+        .map(|_| dynamics.goodwill(&engine.store, &customer_id, "test", 0.16, 10).unwrap())
+        .collect();
+    assert_eq!(applied, vec![0.16, 0.08, 0.04]);
+    assert_eq!(dynamics.goodwill(&engine.store, &customer_id, "test", -0.02, 10).unwrap(), -0.02);
+    let satisfaction = engine.store_customer_satisfaction(run_id, &customer_id).unwrap();
+    assert!((satisfaction - 0.26).abs() < 1e-9, "{satisfaction}");
+
+    assert_eq!(dynamics.goodwill(&engine.store, &customer_id, "test", 0.16, 190).unwrap(), 0.16);
+    let gestures = engine.store.satisfaction_gestures(run_id).unwrap();
+    assert_eq!(gestures.len(), 4);
+    assert!(gestures.iter().all(|g| g.requested_delta == 0.16));
+
+    // Resolving a complaint with relief is a goodwill gesture too
+    engine.run_ticks(20).unwrap();
+    let complaint = engine.store.first_open_complaint(run_id).unwrap().unwrap();
+    engine
+        .store_close_complaint_direct(run_id, &complaint.complaint_id, 21, "monetary_relief", 50.0)
+        .unwrap();
+    let last = engine.store.satisfaction_gestures(run_id).unwrap().pop().unwrap();
+    assert_eq!((last.source.as_str(), last.customer_id), ("complaint_resolution", complaint.customer_id));
+}
+
+/// Only breaches on urgent complaints scar, and each scar lowers the
+/// customer's baseline by the configured severity.
+#[test]
+fn urgent_breach_scars_baseline() {
+    let run_id = "satisfaction-scar-test";
+    let mut engine = build_with_dynamics(run_id, 42);
+    engine.run_ticks(60).unwrap();
+
+    let scars = engine.store.satisfaction_scars(run_id).unwrap();
+    assert!(!scars.is_empty());
+    assert_eq!(
+        engine.store.count_events_in_range(run_id, 0, 60, "satisfaction_scarred").unwrap(),
+        scars.len() as i64
+    );
+    assert!(engine.store.count_events_in_range(run_id, 0, 60, "sla_breached").unwrap() > scars.len() as i64);
+
+    let dynamics = dynamics(run_id);
+    for scar in &scars {
+        let complaint = engine.store.get_complaint(run_id, &scar.complaint_id).unwrap();
+        assert_eq!((complaint.priority.as_str(), complaint.sla_breached), ("urgent", true));
+        assert_eq!(complaint.customer_id, scar.customer_id);
+
+        let total = engine.store.customer_scar_total(run_id, &scar.customer_id).unwrap();
+        let segment = engine.store.customer_segment(run_id, &scar.customer_id).unwrap();
+        assert!(dynamics.baseline(&segment, total) < dynamics.baseline(&segment, 0.0));
+    }
+}
+
+/// The test default applies deltas as given: no reversion, no discount
+/// on repeat goodwill, no scars.
+#[test]
+fn satisfaction_dynamics_are_opt_in() {
+    let run_id = "satisfaction-off-test";
+    let mut engine = SimEngine::build_test(run_id.into(), 42).unwrap();
+    engine.run_ticks(60).unwrap();
+
+    let complaint = engine.store.first_open_complaint(run_id).unwrap().unwrap();
+    engine.store.update_customer_satisfaction(run_id, &complaint.customer_id, -1.0).unwrap();
+    engine
+        .store_close_complaint_direct(run_id, &complaint.complaint_id, 60, "monetary_relief", 50.0)
+        .unwrap();
+    assert_eq!(engine.store_customer_satisfaction(run_id, &complaint.customer_id).unwrap(), 0.15);
+
+    let off = SatisfactionDynamics::new(run_id.into(), SimConfig::default_test().satisfaction_dynamics);
+    for _ in 0..2 {
+        assert_eq!(off.goodwill(&engine.store, &complaint.customer_id, "test", 0.16, 60).unwrap(), 0.16);
+    }
+
+    assert!(engine.store.satisfaction_gestures(run_id).unwrap().is_empty());
+    assert!(engine.store.satisfaction_scars(run_id).unwrap().is_empty());
+    assert_eq!(engine.store.count_events_in_range(run_id, 0, 60, "satisfaction_scarred").unwrap(), 0);
+}
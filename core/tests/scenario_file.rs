@@ -0,0 +1,171 @@
+//! Scenario file tests — Phase 4.77.
+//!
+//! Tests cover: a TOML file setting the starting population, segment
+//! mix, macro regime and fees of the bank it builds; the same scenario
+//! in JSON reading the same; bad values refused with the field they are
+//! in, and bad syntax with its line, before the config changes; the
+//! enabled subsystems being the only ones besides the core that run;
+//! and a stored scenario changing the run's hash and coming back when
+//! the run is resumed.
+
+use fincrime_core::{
+    config::SimConfig,
+    engine::SimEngine,
+    event::EconomicPhase,
+    provenance::RunProvenance,
+    scenario::ScenarioFile,
+    store::SimStore,
+};
+use std::path::Path;
+
+const RECESSION: &str = r#"
+name = "recession"
+initial_customers = 40
+
+[segment_mix]
+mass_market = 0.5
+small_business = 0.5
+
+[macro_regime]
+economic_phase = "contraction"
+base_rate = 0.02
+phase_ticks = 180
+
+[fees.basic_checking]
+overdraft_fee = 20.0
+monthly_fee = 5.0
+"#;
+
+fn data_dir() -> String {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("../data").to_string_lossy().into_owned()
+}
+
+fn refusal(toml: &str) -> (String, String) {
+    let scenario = match ScenarioFile::from_toml(toml) {
+        Ok(scenario) => scenario,
+        Err(err) => return (err.code().to_string(), err.to_string()),
+    };
+    let mut config = SimConfig::default_test();
+    let err = scenario.apply(&mut config).expect_err("scenario should be refused");
+    assert_eq!(config.initial_population, SimConfig::default_test().initial_population);
+    (err.code().to_string(), err.to_string())
+}
+
+/// Every section of the file reaches the bank that is built from it.
+#[test]
+fn toml_scenario_configures_the_bank() {
+    let scenario = ScenarioFile::from_toml(RECESSION).unwrap();
+    let mut config = SimConfig::default_test();
+    scenario.apply(&mut config).unwrap();
+    assert_eq!(config.initial_population, 40);
+    assert_eq!(config.segments["premium"].population_share, 0.0);
+    assert_eq!(config.products["basic_checking"].overdraft_fee, 20.0);
+
+    let run_id = "scenario-toml-test";
+    let mut engine = SimEngine::build_test_with_scenario(run_id.into(), 42, &scenario).unwrap();
+    let state = engine.last_macro_state().unwrap();
+    assert_eq!(state.economic_phase, EconomicPhase::Contraction);
+    assert_eq!(state.base_rate, 0.02);
+    assert!(state.fraud_multiplier > 1.0);
+
+    engine.run_ticks(1).unwrap();
+    let customers = engine.store.active_customers(run_id).unwrap();
+    assert_eq!(customers.len(), 40);
+    assert!(customers.iter().all(|c| c.segment == "mass_market" || c.segment == "small_business"));
+    let product = engine.store.get_product_state(run_id, "basic_checking").unwrap();
+    assert_eq!((product.overdraft_fee, product.monthly_fee), (20.0, 5.0));
+}
+
+/// The same scenario written as JSON reads the same, and a file is read
+/// as JSON by its extension.
+#[test]
+fn json_scenario_reads_like_toml() {
+    let json = r#"{
+        "name": "recession",
+        "initial_customers": 40,
+        "segment_mix": { "mass_market": 0.5, "small_business": 0.5 },
+        "macro_regime": { "economic_phase": "contraction", "base_rate": 0.02, "phase_ticks": 180 },
+        "fees": { "basic_checking": { "overdraft_fee": 20.0, "monthly_fee": 5.0 } }
+    }"#;
+    let expected = ScenarioFile::from_toml(RECESSION).unwrap();
+    assert_eq!(ScenarioFile::from_json(json).unwrap(), expected);
+
+    let path = std::env::temp_dir().join(format!("scenario_{}.json", std::process::id()));
+    std::fs::write(&path, json).unwrap();
+    assert_eq!(ScenarioFile::load(&path).unwrap(), expected);
+    std::fs::remove_file(&path).unwrap();
+    assert_eq!(ScenarioFile::from_toml("").unwrap(), ScenarioFile::default());
+}
+
+/// Each refusal names the field at fault, or the line for a file that
+/// does not parse, and leaves the config as it was.
+#[test]
+fn invalid_values_name_their_field() {
+    let cases = [
+        ("initial_customers = 0", "initial_customers"),
+        ("[segment_mix]\nmass_market = 0.5\nstudents = 0.5", "segment_mix.students"),
+        ("[segment_mix]\nmass_market = 0.5", "segment_mix:"),
+        ("[macro_regime]\neconomic_phase = \"trough\"\nbase_rate = 0.5\nphase_ticks = 90", "macro_regime.base_rate"),
+        ("[fees.basic_checking]\noverdraft_fee = 50.0", "fees.basic_checking.overdraft_fee"),
+        ("[fees.basic_checking]\nteller_fee = 1.0", "fees.basic_checking.teller_fee"),
+        ("[fees.gold_checking]\nmonthly_fee = 1.0", "fees.gold_checking"),
+    ];
+    for (toml, field) in cases {
+        let (code, message) = refusal(toml);
+        assert_eq!(code, "scenario_invalid", "{message}");
+        assert!(message.contains(&format!("at {field}")), "{toml:?} should name {field}: {message}");
+    }
+
+    let (_, message) = refusal("name = \"x\"\nintial_customers = 10");
+    assert!(message.contains("line 2, column 1") && message.contains("unknown field"), "{message}");
+    let (_, message) = refusal("[macro_regime]\neconomic_phase = \"boom\"\nbase_rate = 0.05\nphase_ticks = 90");
+    assert!(message.contains("line 2") && message.contains("boom"), "{message}");
+}
+
+/// Only the enabled subsystems and the core run; a name the build does
+/// not register is refused with its place in the list.
+#[test]
+fn enabled_subsystems_limit_the_run() {
+    let scenario = ScenarioFile::from_toml(r#"enabled_subsystems = ["complaint", "economics"]"#).unwrap();
+    let engine = SimEngine::build_test_with_scenario("scenario-enabled-test".into(), 42, &scenario).unwrap();
+    let mut running: Vec<String> = engine.subsystems().into_iter().map(|s| s.name).collect();
+    running.sort();
+    assert_eq!(running, ["complaint", "customer", "economics", "macro", "transaction"]);
+
+    let scenario = ScenarioFile::from_toml(r#"enabled_subsystems = ["complaint", "complaints"]"#).unwrap();
+    let err = SimEngine::build_test_with_scenario("scenario-unknown-test".into(), 42, &scenario)
+        .err()
+        .expect("unknown subsystem refused");
+    assert!(err.to_string().contains("enabled_subsystems[1]: unknown subsystem 'complaints'"), "{err}");
+}
+
+/// The scenario is stored with the run, changes its scenario hash, and
+/// is applied again when the run is resumed.
+#[test]
+fn stored_scenario_survives_resume() {
+    let (db, run_id) = ("test_scenario_resume.db", "scenario-resume-test");
+    let _ = std::fs::remove_file(db);
+    let store = SimStore::open(db).unwrap();
+    store.migrate().unwrap();
+    let scenario = ScenarioFile::from_toml(RECESSION).unwrap();
+    let plain = RunProvenance::capture(vec![], &data_dir(), None, &[]).unwrap();
+    let provenance = plain.clone().with_scenario(&scenario).unwrap();
+    assert_ne!(provenance.scenario_hash, plain.scenario_hash);
+    store.insert_run_with_provenance(run_id, 7, "0.1.0-test", &provenance).unwrap();
+
+    let mut engine = SimEngine::build_with_scenario(run_id.into(), 7, &store, &data_dir(), 0.5, &scenario).unwrap();
+    engine.run_ticks(5).unwrap();
+    let applicants = engine.store_events_for_tick(run_id, 1).unwrap();
+    let applied = applicants
+        .iter()
+        .filter(|e| ["customer_onboarded", "applicant_declined"].contains(&e.event_type.as_str()))
+        .count();
+    assert_eq!(applied, 20, "40 applicants at half scale");
+    drop(engine);
+
+    let stored = store.describe_run(run_id).unwrap().unwrap();
+    assert_eq!(stored.provenance.scenario.as_ref(), Some(&scenario));
+    let resumed = SimEngine::resume(run_id.into(), &store, &data_dir(), 0.5).unwrap();
+    assert_eq!(resumed.clock.current_tick, 5);
+    assert_eq!(resumed.last_macro_state().unwrap().economic_phase, EconomicPhase::Contraction);
+}
@@ -0,0 +1,217 @@
+//! Scenario scripting hook tests — Phase 4.68.
+//!
+//! Tests cover: a condition on the KPIs and the tick opening one regulator
+//! inquiry; commands sent through the same checks as an IPC `command` and
+//! queued for the next tick; failing and runaway scripts dropped while the
+//! run and the other scripts carry on; scripts that do not compile or
+//! import modules refused when the engine is built, and calls outside the
+//! sandbox failing at run time; and `once` keys surviving a rebuild.
+
+use fincrime_core::{
+    command::PlayerCommand,
+    config::{ScenarioScript, SimConfig},
+    engine::SimEngine,
+    event::SimEvent,
+    scenario_script::ScenarioScripts,
+    state_mirror::StateMirror,
+    store::SimStore,
+};
+use std::collections::HashMap;
+
+fn open_store(run_id: &str) -> SimStore {
+    let path = format!("./test_{run_id}.db");
+    let _ = std::fs::remove_file(&path);
+    let store = SimStore::open(&path).unwrap();
+    store.migrate().unwrap();
+    store.insert_run(run_id, 42, "0.1.0-test").unwrap();
+    store
+}
+
+fn config(scripts: &[(&str, &str)]) -> SimConfig {
+    let mut config = SimConfig::default_test();
+    config.scenario_scripts.enabled = true;
+    config.scenario_scripts.scripts = scripts
+        .iter()
+        .map(|(name, source)| ScenarioScript { name: name.to_string(), source: source.to_string() })
+        .collect();
+    config
+}
+
+fn build(run_id: &str, scripts: &[(&str, &str)]) -> SimEngine {
+    SimEngine::build_with_config(run_id.into(), 42, &open_store(run_id), config(scripts)).unwrap()
+}
+
+/// Run one tick and return what it logged.
+fn tick(engine: &mut SimEngine) -> Vec<SimEvent> {
+    engine.run_ticks(1).unwrap();
+    let tick = engine.clock.current_tick;
+    let logged = engine.store.events_for_tick(&engine.run_id, tick).unwrap();
+    logged.iter().map(|e| serde_json::from_str(&e.payload).unwrap()).collect()
+}
+
+/// `(tick, script, action, name, detail)` of every script firing in the
+/// next `ticks` ticks.
+fn fired(engine: &mut SimEngine, ticks: u64) -> Vec<(u64, String, String, String, String)> {
+    let mut fired = Vec::new();
+    for _ in 0..ticks {
+        for event in tick(engine) {
+            if let SimEvent::ScenarioScriptFired { tick, script, action, name, detail } = event {
+                fired.push((tick, script, action, name, detail));
+            }
+        }
+    }
+    fired
+}
+
+/// The first tick the condition holds opens an exam; `once` keeps it
+/// from opening another.
+#[test]
+fn backlog_condition_opens_one_inquiry() {
+    let watch = r#"
+        if tick > 10 && state.backlog > 5 && once("inquiry") {
+            regulator_inquiry(`backlog at ${state.backlog}`);
+        }
+    "#;
+    let mut engine = build("script-inquiry-test", &[("backlog_watch", watch)]);
+    let run_id = engine.run_id.clone();
+
+    let mut expected = None;
+    let mut opened = Vec::new();
+    for _ in 0..60 {
+        for event in tick(&mut engine) {
+            if let SimEvent::RegulatoryExamStarted { tick, exam_id, examiner, scope } = event {
+                opened.push((tick, exam_id, examiner, scope));
+            }
+        }
+        let (tick, backlog) = (engine.clock.current_tick, engine.state_mirror().backlog);
+        if expected.is_none() && tick > 10 && backlog > 5 {
+            expected = Some((tick, backlog));
+        }
+    }
+
+    let (tick, backlog) = expected.expect("the backlog never passed 5");
+    let exam_id = format!("exam-inquiry-{tick}");
+    assert_eq!(opened, [(tick, exam_id.clone(), "regulator".into(), "inquiry".into())]);
+    assert_eq!(engine.store.get_open_exam(&run_id).unwrap().unwrap().exam_id, exam_id);
+    let events = engine.store.events_for_tick(&run_id, tick).unwrap();
+    let logged = events.iter().find(|e| e.event_type == "scenario_script_fired").unwrap();
+    assert_eq!(logged.subsystem, "scenario_script");
+    assert!(logged.payload.contains(&format!("backlog at {backlog}")), "{}", logged.payload);
+}
+
+/// A command a script sends is checked like an IPC one; the accepted one
+/// is queued for the next tick and the others say why they were refused.
+#[test]
+fn commands_go_through_the_command_bus() {
+    let desk = r#"
+        if tick == 5 {
+            command("set_sar_threshold", #{ threshold: 60.0 });
+            command("set_sar_threshold", #{ threshold: 500.0 });
+            command("no_such_command");
+        }
+    "#;
+    let mut engine = build("script-command-test", &[("desk", desk)]);
+
+    let fired = fired(&mut engine, 5);
+    let outcomes: Vec<_> = fired.iter().map(|(tick, _, action, name, _)| (*tick, action.as_str(), name.as_str())).collect();
+    assert_eq!(
+        outcomes,
+        [(5, "command", "set_sar_threshold"), (5, "command", "set_sar_threshold"), (5, "command", "no_such_command")],
+    );
+    assert!(fired[0].4.starts_with("queued as "), "{}", fired[0].4);
+    assert!(fired[1].4.contains("threshold: must be between 0 and 100"), "{}", fired[1].4);
+    assert!(fired[2].4.contains("no command 'no_such_command'"), "{}", fired[2].4);
+
+    let queued = fired[0].4.trim_start_matches("queued as ");
+    let command = engine.store.get_player_command(&engine.run_id, queued).unwrap().unwrap();
+    assert!(matches!(command, PlayerCommand::SetSarThreshold { threshold } if threshold == 60.0), "{command:?}");
+}
+
+/// A script that errors or runs past its operation budget is logged once
+/// and dropped; the run and the other scripts carry on.
+#[test]
+fn failing_scripts_are_dropped() {
+    let mut config = config(&[
+        ("divide", "if tick == 3 { let zero = 0; let x = 1 / zero; }"),
+        ("heartbeat", r#"emit("beat", `${tick}`);"#),
+        ("runaway", "if tick >= 2 { loop { } }"),
+    ]);
+    config.scenario_scripts.max_operations = 5_000;
+    let run_id = "script-failure-test";
+    let mut engine = SimEngine::build_with_config(run_id.into(), 42, &open_store(run_id), config).unwrap();
+
+    let mut failures = Vec::new();
+    let mut beats = Vec::new();
+    for _ in 0..10 {
+        for event in tick(&mut engine) {
+            match event {
+                SimEvent::ScenarioScriptFailed { tick, script, error } => failures.push((tick, script, error)),
+                SimEvent::ScenarioScriptFired { tick, detail, .. } => beats.push((tick, detail)),
+                _ => {}
+            }
+        }
+    }
+    let failed: Vec<_> = failures.iter().map(|(tick, script, _)| (*tick, script.as_str())).collect();
+    assert_eq!(failed, [(2, "runaway"), (3, "divide")]);
+    assert!(failures[0].2.to_lowercase().contains("operations"), "{}", failures[0].2);
+    assert!(failures[1].2.to_lowercase().contains("division by zero"), "{}", failures[1].2);
+    assert_eq!(beats, (1..=10).map(|t| (t, t.to_string())).collect::<Vec<_>>());
+    assert_eq!(engine.halted(), None);
+}
+
+/// Bad syntax, unknown variables and imports are refused before the run
+/// starts; a call to the clock or the file system fails the first tick.
+#[test]
+fn scripts_are_checked_and_sandboxed() {
+    for (i, source) in [
+        "if tick > { }",
+        "if backlog > 200 { }",
+        r#"import "std" as std;"#,
+    ]
+    .into_iter()
+    .enumerate()
+    {
+        let run_id = format!("script-compile-test-{i}");
+        let result =
+            SimEngine::build_with_config(run_id.clone(), 42, &open_store(&run_id), config(&[("bad", source)]));
+        let Err(e) = result else { panic!("{source} compiled") };
+        assert!(e.to_string().contains("scenario script 'bad'"), "{e}");
+    }
+
+    // These compile, but there is no clock or file system to call
+    let mut engine = build(
+        "script-sandbox-test",
+        &[("clock", "let t = timestamp();"), ("file", r#"let f = open_file("x");"#)],
+    );
+    let failed: Vec<_> = tick(&mut engine)
+        .into_iter()
+        .filter_map(|e| match e {
+            SimEvent::ScenarioScriptFailed { script, error, .. } => Some((script, error)),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(failed.iter().map(|(script, _)| script.as_str()).collect::<Vec<_>>(), ["clock", "file"]);
+    for (_, error) in &failed {
+        assert!(error.to_lowercase().contains("function not found"), "{error}");
+    }
+}
+
+/// Scripts rebuilt for a resumed run remember which `once` keys were
+/// spent; scripts off by default run nothing.
+#[test]
+fn once_survives_a_rebuild() {
+    let greet = [("greet", r#"if once("hello") { emit("hello", "world"); }"#)];
+    let config = config(&greet);
+    let mut engine = build("script-once-test", &greet);
+    assert_eq!(fired(&mut engine, 5).len(), 1);
+
+    let mut rebuilt = ScenarioScripts::new(&engine.run_id, &config.scenario_scripts, &engine.store).unwrap();
+    let runs = rebuilt.run(6, &StateMirror::default(), HashMap::new());
+    assert_eq!(runs.len(), 1);
+    assert!(runs[0].spent.is_empty());
+    assert_eq!(runs[0].outcome, Ok(Vec::new()));
+
+    let mut off = SimEngine::build_test("script-off-test".into(), 42).unwrap();
+    off.run_ticks(5).unwrap();
+    assert_eq!(off.store.count_events_in_range(&off.run_id, 0, 5, "scenario_script_fired").unwrap(), 0);
+}
@@ -0,0 +1,94 @@
+//! Seed sensitivity report tests — Phase 4.56.
+//!
+//! Tests cover: every outcome summarised across the nearby seeds with
+//! consistent statistics, the report being deterministic for a base
+//! seed, and metrics flagged seed-fragile by their spread against their
+//! mean with a noise band that narrows as seeds are added.
+
+use fincrime_core::config::SimConfig;
+use fincrime_core::experiment::{MetricSummary, Scenario};
+use fincrime_core::seed_sensitivity::{MetricSensitivity, SeedSensitivity, DEFAULT_FRAGILE_CV};
+
+const TICKS: u64 = 8;
+
+fn report(base: u64, count: usize) -> SeedSensitivity {
+    SeedSensitivity::run(
+        Scenario::new("baseline", SimConfig::default_test(), TICKS),
+        SeedSensitivity::nearby_seeds(base, count),
+        2,
+        DEFAULT_FRAGILE_CV,
+    )
+}
+
+fn summary(values: &[f64]) -> MetricSummary {
+    let runs = values.len();
+    let mean = values.iter().sum::<f64>() / runs as f64;
+    let variance = values.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / (runs - 1) as f64;
+    MetricSummary {
+        scenario: "s".into(),
+        metric: "m".into(),
+        runs,
+        mean,
+        std_dev: variance.sqrt(),
+        min: values.iter().copied().fold(f64::INFINITY, f64::min),
+        max: values.iter().copied().fold(f64::NEG_INFINITY, f64::max),
+    }
+}
+
+/// Each outcome gets one summary over the seeds after the base.
+#[test]
+fn outcomes_summarised_across_seeds() {
+    let report = report(40, 3);
+    assert_eq!(report.seeds, [40, 41, 42]);
+    assert_eq!(report.ticks, TICKS);
+    assert!(report.failures.is_empty(), "{:?}", report.failures);
+    let names: Vec<&str> = report.metrics.iter().map(|m| m.metric.as_str()).collect();
+    for metric in ["pre_tax_profit", "fraud_loss", "case_loss", "churned", "complaints"] {
+        assert_eq!(names.iter().filter(|n| **n == metric).count(), 1, "{names:?}");
+    }
+    for m in &report.metrics {
+        assert!(m.runs <= 3 && m.min <= m.mean && m.mean <= m.max, "{m:?}");
+        assert!((m.noise_band - 1.96 * m.std_dev * (2.0 / m.runs as f64).sqrt()).abs() < 1e-9);
+        assert_eq!(m.fragile, report.fragile().any(|f| f.metric == m.metric));
+    }
+    let complaints = report.metrics.iter().find(|m| m.metric == "complaints").unwrap();
+    assert_eq!(complaints.runs, 3);
+    assert!(complaints.mean > 0.0);
+}
+
+/// The same base seed gives the same report; one seed shows no spread.
+#[test]
+fn report_is_deterministic() {
+    assert_eq!(report(7, 2), report(7, 2));
+    let single = report(7, 1);
+    assert!(single.metrics.iter().all(|m| m.std_dev == 0.0 && !m.fragile));
+    assert_eq!(single.fragile().count(), 0);
+}
+
+/// Spread beyond the threshold share of the mean is fragile, as is any
+/// spread around a zero mean; a metric that never moves is not.
+#[test]
+fn fragility_follows_spread_against_mean() {
+    let steady = MetricSensitivity::from_summary(&summary(&[100.0, 102.0, 98.0]), DEFAULT_FRAGILE_CV);
+    assert_eq!(steady.cv, Some(0.02));
+    assert!(!steady.fragile);
+
+    let jumpy = MetricSensitivity::from_summary(&summary(&[10.0, -5.0, 30.0]), DEFAULT_FRAGILE_CV);
+    assert!(jumpy.fragile && jumpy.cv.unwrap() > 1.0);
+    assert!(!MetricSensitivity::from_summary(&summary(&[10.0, -5.0, 30.0]), 2.0).fragile);
+
+    let flat = MetricSensitivity::from_summary(&summary(&[0.0, 0.0, 0.0]), DEFAULT_FRAGILE_CV);
+    assert_eq!((flat.cv, flat.fragile), (None, false));
+    let around_zero = MetricSensitivity::from_summary(&summary(&[-1.0, 0.0, 1.0]), DEFAULT_FRAGILE_CV);
+    assert_eq!((around_zero.cv, around_zero.fragile), (None, true));
+}
+
+/// The same spread over more seeds leaves a narrower band that an
+/// improvement has to clear.
+#[test]
+fn noise_band_narrows_with_more_seeds() {
+    let few = MetricSensitivity::from_summary(&summary(&[90.0, 110.0]), DEFAULT_FRAGILE_CV);
+    let many = MetricSensitivity::from_summary(&summary(&[90.0, 110.0].repeat(8)), DEFAULT_FRAGILE_CV);
+    assert!((few.noise_band - 1.96 * few.std_dev).abs() < 1e-9);
+    assert!(many.noise_band < few.noise_band / 2.0, "{} vs {}", many.noise_band, few.noise_band);
+}
@@ -0,0 +1,97 @@
+//! Settlement account tests — Phase 4.7.
+//!
+//! Tests cover: one account per rail, daily funding positions, shortfall
+//! borrowing rolled into account totals, early warning alerts.
+
+use fincrime_core::engine::SimEngine;
+
+const RAILS: [&str; 4] = ["ACH", "wire", "RTP", "card"];
+
+fn build_with_settlement(run_id: &str, seed: u64) -> SimEngine {
+    SimEngine::build_test_with_settlement_accounts(run_id.to_string(), seed)
+        .expect("build test engine with settlement accounts")
+}
+
+/// A nostro account is opened for every rail once reconciliation starts.
+#[test]
+fn accounts_opened_per_rail() {
+    let run_id = "settlement-open-test";
+    let mut engine = build_with_settlement(run_id, 0x5E77_0001);
+
+    engine.run_ticks(2).unwrap();
+
+    assert_eq!(engine.store_settlement_account_count(run_id).unwrap(), RAILS.len() as i64);
+}
+
+/// Every rail gets daily positions, each funded at the trailing average and
+/// split into either a shortfall or an idle balance.
+#[test]
+fn positions_reconcile_funding_against_actual() {
+    let run_id = "settlement-position-test";
+    let mut engine = build_with_settlement(run_id, 0x5E77_0002);
+
+    engine.run_ticks(30).unwrap();
+
+    for rail in RAILS {
+        let positions = engine.store_settlement_positions(run_id, rail).unwrap();
+        assert!(!positions.is_empty(), "Expected daily settlement positions on {rail}");
+        for pos in positions {
+            assert!(pos.tick >= 2, "No projection is possible on the first tick");
+            assert!(pos.actual_outflow > 0.0 || pos.projected_outflow > 0.0);
+            assert!(pos.shortfall == 0.0 || pos.idle_balance == 0.0);
+            let net = pos.funded_amount + pos.shortfall - pos.idle_balance;
+            assert!((net - pos.actual_outflow).abs() < 1e-6, "Position must balance");
+        }
+    }
+}
+
+/// Shortfalls are borrowed at the penalty cost and rolled into the account.
+#[test]
+fn shortfalls_borrowed_at_penalty_cost() {
+    let run_id = "settlement-borrow-test";
+    let mut engine = build_with_settlement(run_id, 0x5E77_0003);
+
+    engine.run_ticks(30).unwrap();
+
+    for rail in RAILS {
+        let positions = engine.store_settlement_positions(run_id, rail).unwrap();
+        let borrowed: f64 = positions.iter().map(|p| p.shortfall).sum();
+        let cost: f64 = positions.iter().map(|p| p.borrowing_cost).sum();
+        assert!(borrowed > 0.0, "Unbuffered funding should run short on {rail}");
+        for pos in positions.iter().filter(|p| p.shortfall > 0.0) {
+            // 0.0005 penalty rate plus the $150 borrowing fee.
+            let expected = pos.shortfall * 0.0005 + 150.0;
+            assert!((pos.borrowing_cost - expected).abs() < 1e-6);
+        }
+
+        let (total_borrowed, total_cost) =
+            engine.store_settlement_account_totals(run_id, rail).unwrap();
+        assert!((total_borrowed - borrowed).abs() < 1e-6);
+        assert!((total_cost - cost).abs() < 1e-6);
+    }
+}
+
+/// Only shortfalls beyond the alert threshold raise an early warning alert.
+#[test]
+fn large_shortfalls_raise_alerts() {
+    let run_id = "settlement-alert-test";
+    let mut engine = build_with_settlement(run_id, 0x5E77_0004);
+
+    engine.run_ticks(30).unwrap();
+
+    let mut alerts = 0;
+    for rail in RAILS {
+        let positions = engine.store_settlement_positions(run_id, rail).unwrap();
+        for pos in &positions {
+            assert_eq!(
+                pos.alert,
+                pos.shortfall > pos.funded_amount * 0.25,
+                "Alert flag must follow the 25% shortfall threshold"
+            );
+        }
+        let flagged = positions.iter().filter(|p| p.alert).count() as i64;
+        assert_eq!(engine.store_settlement_alert_count(run_id, rail).unwrap(), flagged);
+        alerts += flagged;
+    }
+    assert!(alerts > 0, "Expected some alert-level shortfalls");
+}
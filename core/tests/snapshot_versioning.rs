@@ -0,0 +1,102 @@
+//! Snapshot versioning tests.
+//!
+//! Tests cover: the engine writing snapshots in a versioned envelope that
+//! restores to the same state, unversioned snapshots from before the
+//! envelope upgrading on read, version 2 snapshots reading with no
+//! subsystem state, and snapshots from a newer crate being rejected with
+//! a clear, recoverable error.
+
+use fincrime_core::{
+    clock::SimClock,
+    engine::SimEngine,
+    error::{Severity, SimError},
+    snapshot::{snapshot_version, SimSnapshot, SNAPSHOT_VERSION},
+};
+
+fn snapshot(tick: u64) -> SimSnapshot {
+    let mut clock = SimClock::new("snap-test".into());
+    clock.current_tick = tick;
+    SimSnapshot {
+        run_id: "snap-test".into(),
+        tick,
+        clock,
+        subsystems: Default::default(),
+    }
+}
+
+/// The monthly snapshot the engine takes is stored at the current
+/// version and reads back as the run's clock at that tick.
+#[test]
+fn engine_snapshots_are_versioned() {
+    let run_id = "snap-engine-test";
+    let mut engine = SimEngine::build_test(run_id.to_string(), 42).unwrap();
+    engine.run_ticks(35).unwrap();
+
+    let (tick, json) = engine.store.latest_snapshot_before(run_id, 35).unwrap().unwrap();
+    assert_eq!(tick, 30);
+    assert_eq!(snapshot_version(&json).unwrap(), SNAPSHOT_VERSION);
+
+    let restored = engine.snapshot_at(35).unwrap().unwrap();
+    assert_eq!(restored.tick, 30);
+    assert_eq!(restored.run_id, run_id);
+    assert_eq!(restored.clock.current_tick, 30);
+    assert!(engine.snapshot_at(29).unwrap().is_none());
+}
+
+/// A snapshot round-trips through its envelope unchanged.
+#[test]
+fn envelope_round_trips() {
+    let original = snapshot(60);
+    let json = original.to_json().unwrap();
+    let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+    assert_eq!(value["version"], SNAPSHOT_VERSION);
+    assert_eq!(value["crate_version"], env!("CARGO_PKG_VERSION"));
+    assert_eq!(SimSnapshot::from_json(&json).unwrap(), original);
+}
+
+/// Snapshots saved bare, before the envelope, read as version 1 and
+/// upgrade to the current layout.
+#[test]
+fn unversioned_snapshots_upgrade() {
+    let original = snapshot(90);
+    let legacy = serde_json::to_string(&original).unwrap();
+    assert_eq!(snapshot_version(&legacy).unwrap(), 1);
+    assert_eq!(SimSnapshot::from_json(&legacy).unwrap(), original);
+}
+
+/// Version 2 snapshots, written before subsystem state was saved, read
+/// with none.
+#[test]
+fn version_2_snapshots_upgrade_without_subsystem_state() {
+    let mut state = serde_json::to_value(snapshot(120)).unwrap();
+    state.as_object_mut().unwrap().remove("subsystems");
+    let v2 = serde_json::json!({ "version": 2, "crate_version": "0.1.0", "state": state }).to_string();
+
+    assert_eq!(snapshot_version(&v2).unwrap(), 2);
+    let restored = SimSnapshot::from_json(&v2).unwrap();
+    assert_eq!(restored, snapshot(120));
+    assert!(restored.subsystems.is_empty());
+}
+
+/// A snapshot from a newer crate is refused with an error naming its
+/// version and writer, and nothing is half-read.
+#[test]
+fn newer_snapshots_are_rejected() {
+    let state = serde_json::to_value(snapshot(30)).unwrap();
+    let future = serde_json::json!({
+        "version": SNAPSHOT_VERSION + 1,
+        "crate_version": "99.0.0",
+        "state": state,
+    })
+    .to_string();
+
+    let err = SimSnapshot::from_json(&future).unwrap_err();
+    assert!(
+        matches!(&err, SimError::SnapshotVersion { found, supported, .. }
+            if *found == SNAPSHOT_VERSION + 1 && *supported == SNAPSHOT_VERSION),
+        "{err}"
+    );
+    assert!(err.to_string().contains("99.0.0"), "{err}");
+    assert_eq!(err.severity(), Severity::Recoverable);
+    assert_eq!(err.code(), "snapshot_version_unsupported");
+}
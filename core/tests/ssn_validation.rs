@@ -0,0 +1,115 @@
+//! SSN validation tests — Phase 4.14.
+//!
+//! Tests cover: never-issued ranges and issuance-era mismatches caught by
+//! the validator, synthetic identities flagged at onboarding while genuine
+//! customers pass, deceased SSNs matched on the death master file, and
+//! validation findings raising the synthetic-identity score.
+
+use fincrime_core::engine::SimEngine;
+use fincrime_core::ssn_validation::validate;
+
+fn build_with_validation(run_id: &str, seed: u64) -> SimEngine {
+    SimEngine::build_test_with_ssn_validation(run_id.to_string(), seed)
+        .expect("build test engine with SSN validation")
+}
+
+/// Areas, groups and serials never issued, railroad areas on customers
+/// born after 1963, and post-2011 areas on customers adult by 2011.
+#[test]
+fn validator_catches_impossible_ssns() {
+    assert_eq!(validate(0, 12, 3456, 1980).invalid, vec!["invalid_area"]);
+    assert_eq!(validate(666, 12, 3456, 1980).invalid, vec!["invalid_area"]);
+    assert_eq!(validate(912, 12, 3456, 1980).invalid, vec!["invalid_area"]);
+    assert_eq!(validate(123, 0, 0, 1980).invalid, vec!["invalid_group", "invalid_serial"]);
+
+    assert_eq!(validate(712, 12, 3456, 1985).issuance, vec!["issued_before_birth"]);
+    assert!(validate(712, 12, 3456, 1950).is_clean());
+    assert_eq!(validate(780, 12, 3456, 1970).issuance, vec!["issued_after_adulthood"]);
+    assert!(validate(780, 12, 3456, 2005).is_clean());
+    assert!(validate(123, 45, 6789, 1980).is_clean());
+}
+
+/// Every customer is validated at onboarding; genuine customers pass and
+/// the synthetic identities on implausible SSNs are flagged.
+#[test]
+fn synthetic_identities_flagged_at_onboarding() {
+    let run_id = "ssn-validation-onboarding-test";
+    let mut engine = build_with_validation(run_id, 0x55A1_0001);
+    engine.run_ticks(1).unwrap();
+
+    let validations = engine.store_ssn_validations(run_id).unwrap();
+    assert_eq!(validations.len(), 50);
+    let mut flagged = 0;
+    for validation in &validations {
+        let identity = engine
+            .store
+            .get_customer_identity(run_id, &validation.customer_id)
+            .unwrap()
+            .unwrap();
+        if identity.identity_type == "synthetic" {
+            flagged += usize::from(!validation.issues.is_empty());
+        } else {
+            assert!(validation.issues.is_empty(), "{:?}", validation);
+            assert_eq!(validation.synthetic_score, 0.0);
+        }
+    }
+    assert!(flagged >= 3, "Expected several flagged synthetics, got {flagged}");
+    assert!(validations.iter().any(|v| v.issues.contains(&"invalid_group".to_string())));
+    assert!(validations.iter().any(|v| v.issues.contains(&"issued_after_adulthood".to_string())));
+}
+
+/// Synthetic identities borrowing a deceased person's SSN are matched on
+/// the death master file.
+#[test]
+fn deceased_ssns_matched_on_death_master_file() {
+    let run_id = "ssn-validation-dmf-test";
+    let mut engine = build_with_validation(run_id, 0x55A1_0002);
+    engine.run_ticks(1).unwrap();
+
+    let deceased: Vec<_> = engine
+        .store_ssn_validations(run_id)
+        .unwrap()
+        .into_iter()
+        .filter(|v| v.issues.contains(&"deceased".to_string()))
+        .collect();
+    assert!(!deceased.is_empty());
+    for validation in &deceased {
+        let identity = engine
+            .store
+            .get_customer_identity(run_id, &validation.customer_id)
+            .unwrap()
+            .unwrap();
+        assert_eq!(identity.identity_type, "synthetic");
+        assert!(engine.store.on_death_master_file(run_id, &identity.ssn_full).unwrap());
+        assert!(validation.synthetic_score >= 0.50);
+    }
+}
+
+/// Invalid and deceased SSNs push the synthetic-identity score over the
+/// detection threshold, with the findings listed as an indicator.
+#[test]
+fn validation_findings_raise_synthetic_identity_score() {
+    let run_id = "ssn-validation-score-test";
+    let mut engine = build_with_validation(run_id, 0x55A1_0003);
+    engine.run_ticks(2).unwrap();
+
+    let patterns = engine.store.synthetic_identity_patterns(run_id).unwrap();
+    let strong: Vec<_> = engine
+        .store_ssn_validations(run_id)
+        .unwrap()
+        .into_iter()
+        .filter(|v| v.synthetic_score >= 0.40)
+        .collect();
+    assert!(!strong.is_empty());
+    for validation in &strong {
+        let pattern = patterns
+            .iter()
+            .find(|p| p.customer_id == validation.customer_id)
+            .unwrap_or_else(|| panic!("{} not detected", validation.customer_id));
+        assert!(pattern.confidence_score >= 0.60);
+        assert!(pattern
+            .indicators
+            .iter()
+            .any(|i| i.starts_with("SSN validation:")));
+    }
+}
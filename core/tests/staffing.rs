@@ -0,0 +1,121 @@
+//! Staffing subsystem tests — Phase 4.80.
+//!
+//! Tests cover: queue owners working no more than the staffed capacity,
+//! an unstaffed complaint desk aging into SLA breaches, overtime paid
+//! only for work beyond the regular day, headcount changes accepted or
+//! rejected, and staffing staying off unless enabled.
+
+use fincrime_core::{command::PlayerCommand, engine::SimEngine, queue::QueueKind};
+
+fn build(run_id: &str) -> SimEngine {
+    SimEngine::build_test_with_staffing(run_id.to_string(), 42).unwrap()
+}
+
+fn set_staffing(engine: &mut SimEngine, queue: QueueKind, ftes: f64) {
+    engine.submit_command(PlayerCommand::SetStaffing { queue, ftes }).unwrap();
+}
+
+/// Every staffed queue gets a snapshot each tick after the first, and no
+/// owner works past its capacity.
+#[test]
+fn owners_work_no_more_than_capacity() {
+    let run_id = "staffing-capacity-test";
+    let mut engine = build(run_id);
+    engine.run_ticks(30).unwrap();
+
+    let snapshots = engine.store.staffing_snapshots(run_id).unwrap();
+    assert_eq!(snapshots.len(), 30 * QueueKind::ALL.len());
+    assert!(snapshots.iter().all(|s| s.tick >= 1));
+    for s in &snapshots {
+        assert!(s.worked <= s.capacity, "{} worked {} of {} at tick {}", s.queue, s.worked, s.capacity, s.tick);
+    }
+    let complaints_worked: i64 = snapshots.iter().filter(|s| s.queue == "complaints").map(|s| s.worked).sum();
+    assert!(complaints_worked > 0, "the complaint desk should close complaints");
+}
+
+/// With nobody on the complaint desk, nothing is worked, complaints pile
+/// up and more of them breach their SLA than with the desk staffed.
+#[test]
+fn unstaffed_desk_ages_into_breaches() {
+    let mut staffed = build("staffing-desk-staffed-test");
+    let mut empty = build("staffing-desk-empty-test");
+    set_staffing(&mut empty, QueueKind::Complaints, 0.0);
+    staffed.run_ticks(40).unwrap();
+    empty.run_ticks(40).unwrap();
+
+    let desk: Vec<_> = empty
+        .store
+        .staffing_snapshots("staffing-desk-empty-test")
+        .unwrap()
+        .into_iter()
+        .filter(|s| s.queue == "complaints")
+        .collect();
+    assert!(desk.iter().all(|s| s.capacity == 0 && s.worked == 0 && s.cost == 0.0));
+
+    let open = |engine: &SimEngine, run_id: &str| engine.store.open_complaints(run_id).unwrap().len();
+    assert!(open(&empty, "staffing-desk-empty-test") > open(&staffed, "staffing-desk-staffed-test"));
+    assert!(
+        empty.store_sla_breach_count("staffing-desk-empty-test").unwrap()
+            > staffed.store_sla_breach_count("staffing-desk-staffed-test").unwrap()
+    );
+}
+
+/// A thin desk draws overtime up to a quarter more than its regular day,
+/// and only the items worked beyond that day are paid at the premium.
+#[test]
+fn overtime_paid_for_work_beyond_regular_day() {
+    let run_id = "staffing-overtime-test";
+    let mut engine = build(run_id);
+    set_staffing(&mut engine, QueueKind::Complaints, 0.25);
+    engine.run_ticks(30).unwrap();
+
+    let config = fincrime_core::config::SimConfig::default_test().staffing;
+    let snapshots = engine.store.staffing_snapshots(run_id).unwrap();
+    for s in &snapshots {
+        let overtime = (s.worked as f64 - s.regular_items).max(0.0);
+        assert!((s.overtime_items - overtime).abs() < 1e-9, "{s:?}");
+        let pay = s.ftes * config.fte_daily_cost + overtime * s.overtime_item_cost;
+        assert!((s.cost - pay).abs() < 1e-6, "{s:?}");
+        assert!(s.capacity as f64 <= (s.regular_items * (1.0 + config.max_overtime_share)).ceil(), "{s:?}");
+    }
+    assert!(
+        snapshots.iter().any(|s| s.queue == "complaints" && s.overtime_items > 0.0),
+        "a 0.25 FTE desk should work overtime"
+    );
+}
+
+/// A valid headcount is planned and announced with the one it replaces;
+/// a negative one is rejected and changes nothing.
+#[test]
+fn headcount_changes_accepted_or_rejected() {
+    let run_id = "staffing-command-test";
+    let mut engine = build(run_id);
+    engine.run_ticks(3).unwrap();
+
+    set_staffing(&mut engine, QueueKind::Alerts, 2.5);
+    set_staffing(&mut engine, QueueKind::Cases, -1.0);
+    engine.run_ticks(2).unwrap();
+
+    assert_eq!(engine.store.count_events_in_range(run_id, 3, 5, "staffing_changed").unwrap(), 1);
+    assert_eq!(engine.store.count_events_in_range(run_id, 3, 5, "staffing_rejected").unwrap(), 1);
+    assert_eq!(engine.store.staffing_plan(run_id, QueueKind::Alerts).unwrap(), Some(2.5));
+    assert_eq!(engine.store.staffing_plan(run_id, QueueKind::Cases).unwrap(), None);
+
+    let alerts = engine.store.staffing_snapshots(run_id).unwrap();
+    let ftes_at = |tick| alerts.iter().find(|s| s.queue == "alerts" && s.tick == tick).unwrap().ftes;
+    assert_eq!(ftes_at(3), 1.0);
+    assert_eq!(ftes_at(4), 2.5);
+}
+
+/// The test config leaves staffing off: no snapshots, no pay, and the
+/// queues are worked without limit as before.
+#[test]
+fn staffing_off_unless_enabled() {
+    let run_id = "staffing-disabled-test";
+    let mut engine = SimEngine::build_test(run_id.to_string(), 42).unwrap();
+    engine.run_ticks(20).unwrap();
+
+    assert!(engine.store.staffing_snapshots(run_id).unwrap().is_empty());
+    assert_eq!(engine.store.staffing_cost_between(run_id, 0, 20).unwrap(), 0.0);
+    assert_eq!(engine.store.staffing_capacity(run_id, 10, QueueKind::Complaints).unwrap(), None);
+}
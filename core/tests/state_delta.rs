@@ -0,0 +1,129 @@
+//! Delta state tests — Phase 4.44.
+//!
+//! Tests cover: a delta counting the window's events, carrying those of
+//! the requested types, and its queue changes,
+//! KPIs being sent only when they moved since the client's last state,
+//! closed items showing up, bad windows being refused, and a per-tick
+//! delta being far smaller than the full state.
+
+use fincrime_core::{
+    engine::SimEngine,
+    protocol::{self, IpcCommand, UiState},
+    queue::QueueKind,
+    state_delta::DeltaTracker,
+};
+
+fn run(run_id: &str, ticks: u64) -> SimEngine {
+    let mut engine = SimEngine::build_test(run_id.to_string(), 42).unwrap();
+    engine.run_ticks(ticks).unwrap();
+    engine
+}
+
+/// After one tick the delta counts exactly that tick's events, carries
+/// the ones asked for, and lists the complaints it opened.
+#[test]
+fn delta_carries_the_window() {
+    let mut engine = run("delta-window-test", 20);
+    let mut tracker = DeltaTracker::new();
+    tracker.record(&UiState::from_engine(&engine).unwrap());
+    engine.run_ticks(1).unwrap();
+
+    let wanted = vec!["complaint_filed".to_string()];
+    let delta = tracker.delta(&engine, 20, &wanted).unwrap();
+    assert_eq!((delta.since_tick, delta.tick), (20, 21));
+    let logged = engine.store.events_for_tick(&engine.run_id, 21).unwrap();
+    assert!(!logged.is_empty());
+    assert_eq!(delta.event_counts.values().sum::<u64>(), logged.len() as u64);
+    let filed = logged.iter().filter(|e| e.event_type == "complaint_filed").count();
+    assert!(filed > 0);
+    assert_eq!(delta.events.len(), filed);
+    assert!(delta.events.iter().all(|e| e.tick == 21 && e.event_type == "complaint_filed"));
+    assert!(!delta.truncated);
+
+    let opened_today = engine
+        .store
+        .open_complaints(&engine.run_id)
+        .unwrap()
+        .into_iter()
+        .filter(|c| c.tick_opened == 21)
+        .map(|c| c.complaint_id)
+        .collect::<std::collections::BTreeSet<_>>();
+    let reported = delta
+        .queues
+        .iter()
+        .filter(|q| q.queue == QueueKind::Complaints)
+        .flat_map(|q| q.opened.iter().map(|i| i.id.clone()))
+        .collect::<std::collections::BTreeSet<_>>();
+    assert_eq!(reported, opened_today);
+}
+
+/// KPIs the client already has are left out; a tick the session never
+/// answered for gets all of them.
+#[test]
+fn only_changed_kpis_are_sent() {
+    let mut engine = run("delta-kpi-test", 20);
+    let mut tracker = DeltaTracker::new();
+    tracker.record(&UiState::summary_from_engine(&engine).unwrap());
+
+    let unchanged = tracker.delta(&engine, 20, &[]).unwrap();
+    assert!(unchanged.kpis.is_empty());
+    assert!(unchanged.event_counts.is_empty() && unchanged.queues.is_empty());
+
+    engine.run_ticks(5).unwrap();
+    let moved = tracker.delta(&engine, 20, &[]).unwrap();
+    let state = UiState::summary_from_engine(&engine).unwrap();
+    assert_eq!(moved.kpis.complaint_count, Some(state.complaint_count));
+    assert!(moved.kpis.nim.is_none(), "no P&L has been taken yet");
+
+    let unknown = tracker.delta(&engine, 3, &[]).unwrap();
+    assert_eq!(unknown.kpis.backlog, Some(state.backlog));
+    assert_eq!(unknown.kpis.nim, Some(state.nim));
+}
+
+/// A complaint resolved by the player is reported closed.
+#[test]
+fn closed_items_are_reported() {
+    let mut engine = run("delta-closed-test", 20);
+    let mut tracker = DeltaTracker::new();
+    tracker.record(&UiState::from_engine(&engine).unwrap());
+
+    let complaint = engine.store.open_complaints(&engine.run_id).unwrap().remove(0);
+    engine.run_ticks(1).unwrap();
+    let payload = serde_json::json!({
+        "complaint_id": complaint.complaint_id,
+        "resolution": "explanation_only",
+        "refund": 0.0,
+    });
+    protocol::apply_command(&mut engine, "resolve_complaint", &payload).unwrap();
+
+    let delta = tracker.delta(&engine, 20, &[]).unwrap();
+    let complaints = delta.queues.iter().find(|q| q.queue == QueueKind::Complaints).unwrap();
+    assert!(complaints.closed.iter().any(|i| i.id == complaint.complaint_id && i.status == "closed"));
+}
+
+/// A window starting ahead of the run is refused without ending the
+/// session, and the message parses.
+#[test]
+fn delta_from_the_future_is_refused() {
+    let engine = run("delta-future-test", 5);
+    let err = DeltaTracker::new().delta(&engine, 6, &[]).unwrap_err();
+    assert_eq!(err.code(), "command_error");
+    assert!(!err.is_fatal());
+
+    let cmd: IpcCommand = serde_json::from_str(r#"{"type":"get_state_delta","since_tick":4}"#).unwrap();
+    assert!(matches!(cmd, IpcCommand::GetStateDelta { since_tick: 4, event_types } if event_types.is_empty()));
+}
+
+/// Polling one tick at a time moves a small fraction of the bytes the
+/// full state does.
+#[test]
+fn per_tick_delta_is_small() {
+    let mut engine = run("delta-size-test", 60);
+    let mut tracker = DeltaTracker::new();
+    tracker.record(&UiState::from_engine(&engine).unwrap());
+    engine.run_ticks(1).unwrap();
+
+    let full = serde_json::to_string(&UiState::from_engine(&engine).unwrap()).unwrap();
+    let delta = serde_json::to_string(&tracker.delta(&engine, 60, &[]).unwrap()).unwrap();
+    assert!(delta.len() * 4 < full.len(), "delta {} bytes, full {}", delta.len(), full.len());
+}
@@ -0,0 +1,112 @@
+//! In-memory KPI mirror tests — Phase 4.66.
+//!
+//! Tests cover: the mirror agreeing with the store after every tick, with
+//! bureau disputes, reopened complaints and quarter-end restatements all
+//! moving the figures; state summaries drawn from it; direct closures
+//! between ticks moving the backlog at once; and an engine built over an
+//! existing run loading it from the store.
+
+use fincrime_core::{
+    config::SimConfig,
+    engine::SimEngine,
+    protocol::UiState,
+    state_mirror::StateMirror,
+    store::SimStore,
+};
+
+fn open_store(run_id: &str) -> SimStore {
+    let path = format!("./test_{run_id}.db");
+    let _ = std::fs::remove_file(&path);
+    let store = SimStore::open(&path).unwrap();
+    store.migrate().unwrap();
+    store.insert_run(run_id, 42, "0.1.0-test").unwrap();
+    store
+}
+
+/// Every path that moves a headline figure: bureau disputes file and
+/// close complaints, poor closures reopen, and the quarter close restates
+/// the P&L.
+fn busy_config() -> SimConfig {
+    let mut config = SimConfig::default_test();
+    config.credit_bureau.enabled = true;
+    config.complaint_quality.enabled = true;
+    config.quarter_close.enabled = true;
+    config
+}
+
+#[test]
+fn mirror_matches_the_store_every_tick() {
+    let run_id = "mirror-match-test";
+    let store = open_store(run_id);
+    let mut engine = SimEngine::build_with_config(run_id.into(), 42, &store, busy_config()).unwrap();
+
+    for tick in 1..=200 {
+        engine.run_ticks(1).unwrap();
+        if tick % 10 == 0 {
+            let complaint = engine.store.first_open_complaint(run_id).unwrap().unwrap();
+            engine.store_close_complaint_direct(run_id, &complaint.complaint_id, tick, "explanation_only", 0.0).unwrap();
+        }
+        assert_eq!(engine.state_mirror(), StateMirror::load(&engine.store, run_id).unwrap(), "tick {tick}");
+    }
+    let mirror = engine.state_mirror();
+    assert!(mirror.churned_customers > 0 && mirror.sla_breaches > 0 && mirror.pre_tax_profit != 0.0);
+    assert!(engine.store.count_events_in_range(run_id, 0, 200, "complaint_reopened").unwrap() > 0);
+    assert!(engine.store.count_events_in_range(run_id, 0, 200, "bureau_dispute_filed").unwrap() > 0);
+}
+
+/// Summary and full states carry the mirror's figures; only the full one
+/// adds the history.
+#[test]
+fn states_are_drawn_from_the_mirror() {
+    let mut engine = SimEngine::build_test("mirror-state-test".into(), 42).unwrap();
+    engine.run_ticks(95).unwrap();
+    let mirror = engine.state_mirror();
+
+    for state in [UiState::summary_from_engine(&engine).unwrap(), UiState::from_engine(&engine).unwrap()] {
+        let figures = (state.active_customers, state.churned_customers, state.complaint_count, state.sla_breaches);
+        assert_eq!(
+            figures,
+            (mirror.active_customers, mirror.churned_customers, mirror.complaint_count, mirror.sla_breaches),
+        );
+        assert_eq!(state.backlog, mirror.backlog);
+        assert_eq!((state.nim, state.pre_tax_profit), (mirror.nim, mirror.pre_tax_profit));
+    }
+    let full = UiState::from_engine(&engine).unwrap();
+    assert_eq!(full.pnl_history.last().unwrap().pre_tax_profit, mirror.pre_tax_profit);
+    assert_eq!(full.complaints.len() as i64, mirror.backlog);
+}
+
+/// A closure between ticks moves the backlog before the next tick, and
+/// writes made straight to the store are picked up by a resync.
+#[test]
+fn closures_between_ticks_move_the_backlog() {
+    let mut engine = SimEngine::build_test("mirror-closure-test".into(), 42).unwrap();
+    let run_id = engine.run_id.clone();
+    engine.run_ticks(20).unwrap();
+    let before = engine.state_mirror();
+
+    let open = engine.store.open_complaints(&run_id).unwrap();
+    engine.store_close_complaint_direct(&run_id, &open[0].complaint_id, 20, "monetary_relief", 10.0).unwrap();
+    assert_eq!(engine.state_mirror().backlog, before.backlog - 1);
+
+    engine.store.close_complaint(&run_id, &open[1].complaint_id, 20, "monetary_relief", 10.0).unwrap();
+    assert_eq!(engine.state_mirror().backlog, before.backlog - 1);
+    engine.resync_state_mirror().unwrap();
+    assert_eq!(engine.state_mirror().backlog, before.backlog - 2);
+    assert_eq!(engine.state_mirror(), StateMirror::load(&engine.store, &run_id).unwrap());
+}
+
+/// A second engine over the same run starts from where the first left
+/// off.
+#[test]
+fn rebuilt_engine_loads_the_mirror() {
+    let run_id = "mirror-rebuild-test";
+    let store = open_store(run_id);
+    let mut engine = SimEngine::build_with_config(run_id.into(), 42, &store, SimConfig::default_test()).unwrap();
+    assert_eq!(engine.state_mirror(), StateMirror::default());
+    engine.run_ticks(95).unwrap();
+
+    let rebuilt = SimEngine::build_with_config(run_id.into(), 42, &store, SimConfig::default_test()).unwrap();
+    assert_eq!(rebuilt.state_mirror(), engine.state_mirror());
+    assert!(rebuilt.state_mirror().active_customers > 0);
+}
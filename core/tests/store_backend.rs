@@ -0,0 +1,85 @@
+//! Storage backend trait tests — Phase 4.21.
+//!
+//! The trait tests run the same checks against the SQLite store and the
+//! in-memory backend: the event log keeping append order and snapshots
+//! resolving to the latest at or before a tick. The engine test runs a
+//! build on the in-memory backend.
+
+use fincrime_core::engine::SimEngine;
+use fincrime_core::event::EventLogEntry;
+use fincrime_core::store::backend::{MemoryBackend, StoreBackend};
+use fincrime_core::store::SimStore;
+
+const RUN: &str = "backend-test";
+
+fn backends() -> Vec<(&'static str, Box<dyn StoreBackend>)> {
+    let sqlite = SimStore::in_memory().unwrap();
+    sqlite.migrate().unwrap();
+    sqlite.insert_run(RUN, 7, "test").unwrap();
+    vec![("sqlite", Box::new(sqlite)), ("memory", Box::new(MemoryBackend::new()))]
+}
+
+fn event(tick: u64, event_type: &str) -> EventLogEntry {
+    EventLogEntry {
+        id: None,
+        run_id: RUN.to_string(),
+        tick,
+        subsystem: "test".into(),
+        event_type: event_type.into(),
+        payload: format!("{{\"tick\":{tick}}}"),
+    }
+}
+
+/// Events come back for their tick in append order with ids assigned.
+#[test]
+fn event_log_keeps_order() {
+    for (name, backend) in backends() {
+        for (tick, event_type) in [(1, "a"), (2, "b"), (2, "a"), (3, "a"), (2, "c")] {
+            backend.append_event(&event(tick, event_type)).unwrap();
+        }
+        let tick2 = backend.events_for_tick(RUN, 2).unwrap();
+        let types: Vec<_> = tick2.iter().map(|e| e.event_type.as_str()).collect();
+        assert_eq!(types, ["b", "a", "c"], "{name}");
+        assert!(tick2.iter().all(|e| e.id.is_some()), "{name}");
+        assert!(tick2.windows(2).all(|w| w[0].id < w[1].id), "{name}");
+        assert!(backend.events_for_tick("other-run", 2).unwrap().is_empty(), "{name}");
+        assert!(backend.events_for_tick(RUN, 9).unwrap().is_empty(), "{name}");
+    }
+}
+
+/// The snapshot for a tick is the latest saved at or before it.
+#[test]
+fn snapshots_resolve_latest_before_tick() {
+    for (name, backend) in backends() {
+        assert_eq!(backend.latest_snapshot_before(RUN, 100).unwrap(), None, "{name}");
+        backend.save_snapshot(RUN, 30, "{\"at\":30}").unwrap();
+        backend.save_snapshot(RUN, 60, "{\"at\":60}").unwrap();
+
+        assert_eq!(backend.latest_snapshot_before(RUN, 29).unwrap(), None, "{name}");
+        assert_eq!(
+            backend.latest_snapshot_before(RUN, 59).unwrap(),
+            Some((30, "{\"at\":30}".to_string())),
+            "{name}",
+        );
+        assert_eq!(backend.latest_snapshot_before(RUN, 60).unwrap().map(|s| s.0), Some(60), "{name}");
+    }
+}
+
+/// An engine given the in-memory backend logs and snapshots there, and
+/// the SQLite event log stays empty.
+#[test]
+fn engine_logs_to_the_memory_backend() {
+    let mut engine = SimEngine::build_test("backend-engine-test".into(), 42).unwrap();
+    engine.set_backend(Box::new(MemoryBackend::new()));
+    engine.run_ticks(31).unwrap();
+
+    let init = engine.store_events_for_tick("backend-engine-test", 0).unwrap();
+    assert_eq!(init.len(), 1);
+    assert_eq!(init[0].event_type, "run_initialized");
+    assert!(!engine.store_events_for_tick("backend-engine-test", 31).unwrap().is_empty());
+    assert!(engine.store.events_for_tick("backend-engine-test", 31).unwrap().is_empty());
+
+    let snapshot = engine.snapshot_at(31).unwrap().expect("snapshot at tick 30");
+    assert_eq!(snapshot.tick, 30);
+    assert!(engine.store.latest_snapshot_before("backend-engine-test", 31).unwrap().is_none());
+}
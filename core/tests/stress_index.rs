@@ -0,0 +1,169 @@
+//! Composite stress index tests — Phase 4.69.
+//!
+//! Tests cover: readings mapped onto their bands either way round; the
+//! stress as the weighted mean of the components present, with
+//! resilience and level following from it; components with nothing to
+//! read left out; level changes reported as they happen; and resilience
+//! reaching `UiState` and scenario scripts, and absent unless enabled.
+
+use fincrime_core::{
+    config::{PressureBand, ScenarioScript, SimConfig, StressIndexConfig},
+    engine::SimEngine,
+    protocol::UiState,
+    store::{stress_index::StressIndexRow, SimStore},
+    stress_index::pressure,
+};
+
+fn build(run_id: &str, tune: impl FnOnce(&mut SimConfig)) -> SimEngine {
+    let path = format!("./test_{run_id}.db");
+    let _ = std::fs::remove_file(&path);
+    let store = SimStore::open(&path).unwrap();
+    store.migrate().unwrap();
+    store.insert_run(run_id, 42, "0.1.0-test").unwrap();
+    let mut config = SimConfig::default_test();
+    config.reputation.enabled = true;
+    config.stress_index.enabled = true;
+    tune(&mut config);
+    SimEngine::build_with_config(run_id.into(), 42, &store, config).unwrap()
+}
+
+fn history(engine: &SimEngine) -> Vec<StressIndexRow> {
+    engine.store.stress_index_between(&engine.run_id, 0, engine.clock.current_tick).unwrap()
+}
+
+fn expected_level(config: &StressIndexConfig, resilience: f64) -> &'static str {
+    if resilience < config.critical_below {
+        "critical"
+    } else if resilience < config.strained_below {
+        "strained"
+    } else {
+        "steady"
+    }
+}
+
+#[test]
+fn pressure_maps_bands_either_way() {
+    let rising = PressureBand { calm: 5.0, edge: 40.0 };
+    assert_eq!(pressure(0.0, &rising), 0.0);
+    assert_eq!(pressure(22.5, &rising), 0.5);
+    assert_eq!(pressure(80.0, &rising), 1.0);
+
+    let falling = PressureBand { calm: 80.0, edge: 40.0 };
+    assert_eq!(pressure(95.0, &falling), 0.0);
+    assert_eq!(pressure(60.0, &falling), 0.5);
+    assert_eq!(pressure(10.0, &falling), 1.0);
+
+    assert_eq!(pressure(3.0, &PressureBand { calm: 2.0, edge: 2.0 }), 0.0);
+}
+
+/// Every tick's stress is the weighted mean of the components it could
+/// read; the reputation component reads the score set that tick.
+#[test]
+fn resilience_follows_the_weighted_components() {
+    let mut engine = SimEngine::build_test_with_stress_index("stress-weights-test".into(), 42).unwrap();
+    engine.run_ticks(40).unwrap();
+    let config = SimConfig::default_test().stress_index;
+    let w = &config.weights;
+
+    let rows = history(&engine);
+    assert_eq!(rows.len(), 40);
+    for row in &rows {
+        let parts = [
+            (row.backlog_pressure, w.backlog),
+            (row.capital_pressure, w.capital),
+            (row.reputation_pressure, w.reputation),
+            (row.staffing_pressure, w.staffing),
+            (row.findings_pressure, w.findings),
+        ];
+        let weight: f64 = parts.iter().filter(|(p, _)| p.is_some()).map(|(_, w)| w).sum();
+        let stress = parts.iter().filter_map(|(p, w)| p.map(|p| p * w)).sum::<f64>() / weight;
+        assert!((row.stress - stress).abs() < 1e-9, "tick {}: {} vs {stress}", row.tick, row.stress);
+        assert!((row.resilience - 100.0 * (1.0 - stress)).abs() < 1e-9);
+        assert_eq!(row.level, expected_level(&config, row.resilience));
+    }
+
+    let last = rows.last().unwrap();
+    let score = engine.store_latest_reputation_score(&engine.run_id).unwrap();
+    assert_eq!(last.reputation_pressure, Some(pressure(score, &config.reputation)));
+}
+
+/// With no liquidity model, no branches and reputation off, only the
+/// backlog and findings count.
+#[test]
+fn components_without_a_reading_are_left_out() {
+    let mut engine = build("stress-missing-test", |config| {
+        config.reputation.enabled = false;
+        config.stress_index.weights.findings = 0.0;
+    });
+    engine.run_ticks(20).unwrap();
+
+    for row in history(&engine) {
+        assert_eq!(row.capital_pressure, None);
+        assert_eq!(row.reputation_pressure, None);
+        assert_eq!(row.staffing_pressure, None);
+        // Findings weigh nothing, so the backlog alone sets the stress
+        assert_eq!(row.stress, row.backlog_pressure.unwrap());
+    }
+}
+
+/// Weighing reputation alone, with a band whose edge sits just under
+/// the starting score: every crossing is reported as a level change.
+#[test]
+fn level_changes_are_reported() {
+    let mut engine = build("stress-level-test", |config| {
+        let c = &mut config.stress_index;
+        c.reputation = PressureBand { calm: 75.0, edge: 74.9 };
+        c.weights.backlog = 0.0;
+        c.weights.capital = 0.0;
+        c.weights.staffing = 0.0;
+        c.weights.findings = 0.0;
+    });
+    engine.run_ticks(60).unwrap();
+
+    let rows = history(&engine);
+    assert_eq!(rows[0].level, "steady");
+    let transitions: Vec<_> = rows
+        .windows(2)
+        .filter(|pair| pair[0].level != pair[1].level)
+        .map(|pair| (pair[1].tick, pair[0].level.clone(), pair[1].level.clone()))
+        .collect();
+    assert!(!transitions.is_empty());
+
+    let changes: Vec<_> = (1..=60)
+        .flat_map(|tick| engine.store.events_for_tick(&engine.run_id, tick).unwrap())
+        .filter(|e| e.event_type == "resilience_level_changed")
+        .map(|e| {
+            let e: serde_json::Value = serde_json::from_str(&e.payload).unwrap();
+            (e["tick"].as_u64().unwrap(), e["from"].as_str().unwrap().to_string(), e["to"].as_str().unwrap().to_string())
+        })
+        .collect();
+    assert_eq!(changes, transitions);
+}
+
+/// The latest resilience is in the state summary and the script state;
+/// without the index there is none.
+#[test]
+fn resilience_reaches_ui_state_and_scripts() {
+    let mut engine = build("stress-ui-test", |config| {
+        config.scenario_scripts.enabled = true;
+        config.scenario_scripts.scripts = vec![ScenarioScript {
+            name: "watch".into(),
+            source: r#"emit("resilience", `${state.resilience}`);"#.into(),
+        }];
+    });
+    engine.run_ticks(10).unwrap();
+    let latest = engine.store.latest_stress_index(&engine.run_id).unwrap().unwrap();
+    let state = UiState::summary_from_engine(&engine).unwrap();
+    assert_eq!(state.resilience, Some(latest.resilience));
+
+    let events = engine.store.events_for_tick(&engine.run_id, 10).unwrap();
+    let fired = events.iter().find(|e| e.event_type == "scenario_script_fired").unwrap();
+    let fired: serde_json::Value = serde_json::from_str(&fired.payload).unwrap();
+    let seen: f64 = fired["detail"].as_str().unwrap().parse().unwrap();
+    assert!((seen - latest.resilience).abs() < 1e-6, "{seen} vs {}", latest.resilience);
+
+    let mut off = SimEngine::build_test("stress-off-test".into(), 42).unwrap();
+    off.run_ticks(10).unwrap();
+    assert_eq!(UiState::summary_from_engine(&off).unwrap().resilience, None);
+    assert!(off.store.latest_stress_index(&off.run_id).unwrap().is_none());
+}
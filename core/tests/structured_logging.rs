@@ -0,0 +1,100 @@
+//! Structured logging tests.
+//!
+//! Tests cover: every log event emitted during a tick carrying the run,
+//! tick and subsystem from the engine's spans, and each event attributed
+//! to the subsystem that logged it rather than tagged in its message.
+
+use fincrime_core::engine::SimEngine;
+use std::collections::BTreeMap;
+use std::sync::{Arc, Mutex};
+use tracing::field::{Field, Visit};
+use tracing::span::{Attributes, Id};
+use tracing::{Event, Subscriber};
+use tracing_subscriber::layer::{Context, SubscriberExt};
+use tracing_subscriber::registry::LookupSpan;
+use tracing_subscriber::Layer;
+
+/// Field values by name, rendered as strings.
+#[derive(Debug, Default, Clone)]
+struct Fields(BTreeMap<String, String>);
+
+impl Visit for Fields {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.0.insert(field.name().to_string(), value.to_string());
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+        self.0.insert(field.name().to_string(), format!("{value:?}"));
+    }
+}
+
+/// Records each event with the fields of every span it was logged in.
+#[derive(Clone, Default)]
+struct Capture(Arc<Mutex<Vec<Fields>>>);
+
+impl<S: Subscriber + for<'a> LookupSpan<'a>> Layer<S> for Capture {
+    fn on_new_span(&self, attrs: &Attributes<'_>, id: &Id, ctx: Context<'_, S>) {
+        let mut fields = Fields::default();
+        attrs.record(&mut fields);
+        ctx.span(id).unwrap().extensions_mut().insert(fields);
+    }
+
+    fn on_event(&self, event: &Event<'_>, ctx: Context<'_, S>) {
+        let mut fields = Fields::default();
+        if let Some(scope) = ctx.event_scope(event) {
+            for span in scope.from_root() {
+                if let Some(span_fields) = span.extensions().get::<Fields>() {
+                    fields.0.extend(span_fields.0.clone());
+                }
+            }
+        }
+        event.record(&mut fields);
+        self.0.lock().unwrap().push(fields);
+    }
+}
+
+/// Run `ticks` ticks of a test engine and return everything logged.
+fn capture_run(run_id: &str, seed: u64, ticks: u64) -> Vec<Fields> {
+    let capture = Capture::default();
+    let subscriber = tracing_subscriber::registry().with(capture.clone());
+    tracing::subscriber::with_default(subscriber, || {
+        let mut engine = SimEngine::build_test(run_id.to_string(), seed).unwrap();
+        engine.run_ticks(ticks).unwrap();
+    });
+    let events = capture.0.lock().unwrap().clone();
+    events
+}
+
+/// Events logged inside a tick carry `run_id`, `tick` and `subsystem`,
+/// so a misbehaving tick can be filtered out of a long run.
+#[test]
+fn tick_events_carry_run_tick_and_subsystem() {
+    let run_id = "structured-logging-fields-test";
+    let events = capture_run(run_id, 0x7ACE_0001, 3);
+    let in_ticks: Vec<_> = events.iter().filter(|e| e.0.contains_key("tick")).collect();
+    assert!(!in_ticks.is_empty(), "Subsystems log during ticks");
+    for event in &in_ticks {
+        assert_eq!(event.0["run_id"], run_id);
+        assert!(["1", "2", "3"].contains(&event.0["tick"].as_str()), "{event:?}");
+        assert!(event.0.contains_key("subsystem"), "{event:?}");
+    }
+    let ticks: std::collections::BTreeSet<_> = in_ticks.iter().map(|e| e.0["tick"].clone()).collect();
+    assert_eq!(ticks.len(), 3, "Every tick logs something");
+}
+
+/// The transaction subsystem's daily aggregate line is attributed to it
+/// by the span, and no message repeats the tick in its text.
+#[test]
+fn events_attributed_to_their_subsystem() {
+    let events = capture_run("structured-logging-subsystem-test", 0x7ACE_0002, 2);
+    let daily: Vec<_> = events
+        .iter()
+        .filter(|e| e.0.get("message").is_some_and(|m| m.contains(" txns, vol=$")))
+        .collect();
+    assert_eq!(daily.len(), 2, "One daily aggregate per tick");
+    assert!(daily.iter().all(|e| e.0["subsystem"] == "transaction"));
+    assert!(events
+        .iter()
+        .filter_map(|e| e.0.get("message"))
+        .all(|m| !m.starts_with("tick=")));
+}
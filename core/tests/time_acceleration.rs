@@ -0,0 +1,102 @@
+//! Time acceleration tests — Phase 4.25.
+//!
+//! Tests cover: runs matching a daily run exactly up to the switch tick,
+//! low-risk customers transacting only on step ticks afterwards, tiers
+//! outside the coarse list staying daily, and coarse steps keeping
+//! transaction volume in line with daily simulation.
+
+use fincrime_core::engine::SimEngine;
+
+const SWITCH: u64 = 20;
+const STEP: u64 = 7;
+/// Four full weekly steps past the switch.
+const END: u64 = SWITCH + 4 * STEP;
+
+fn txn_counts(engine: &SimEngine, ticks: std::ops::RangeInclusive<u64>) -> Vec<i64> {
+    ticks
+        .map(|t| engine.store.txn_count_for_tick(&engine.run_id, t).unwrap())
+        .collect()
+}
+
+/// Up to and including the switch tick nothing changes: same seed, same
+/// transactions, tick for tick.
+#[test]
+fn identical_to_daily_before_switch() {
+    let mut daily = SimEngine::build_test("accel-daily-before".into(), 42).unwrap();
+    let mut accel = SimEngine::build_test_with_time_acceleration("accel-before".into(), 42, &["low"]).unwrap();
+    daily.run_ticks(SWITCH).unwrap();
+    accel.run_ticks(SWITCH).unwrap();
+
+    assert_eq!(txn_counts(&daily, 1..=SWITCH), txn_counts(&accel, 1..=SWITCH));
+}
+
+/// After the switch, accounts of low-risk customers only transact on
+/// step ticks.
+#[test]
+fn low_risk_accounts_step_weekly() {
+    let mut engine = SimEngine::build_test_with_time_acceleration("accel-low".into(), 42, &["low"]).unwrap();
+    engine.run_ticks(END).unwrap();
+    let run_id = engine.run_id.clone();
+
+    let tiers = engine.store.customer_risk_tiers(&run_id).unwrap();
+    let low: Vec<_> = engine
+        .store
+        .active_accounts(&run_id)
+        .unwrap()
+        .into_iter()
+        .filter(|a| tiers.get(&a.customer_id).map(String::as_str) == Some("low"))
+        .collect();
+    assert!(!low.is_empty(), "Test population has low-risk customers");
+
+    let mut stepped = 0;
+    for acct in &low {
+        let between_steps: i64 = (SWITCH + 1..=END)
+            .filter(|t| !(t - SWITCH).is_multiple_of(STEP))
+            .map(|t| {
+                engine
+                    .store
+                    .count_account_transactions_in_window(&run_id, &acct.account_id, t as i64, t as i64)
+                    .unwrap()
+            })
+            .sum();
+        if between_steps == 0 {
+            stepped += 1;
+        }
+    }
+    // An open alert keeps a low-risk customer daily, so allow a few
+    assert!(
+        stepped * 10 >= low.len() * 8,
+        "{stepped} of {} low-risk accounts stepped weekly",
+        low.len()
+    );
+}
+
+/// A tier not in the coarse list stays daily: with no coarse tiers the
+/// run matches a daily one past the switch too.
+#[test]
+fn tiers_outside_list_stay_daily() {
+    let mut daily = SimEngine::build_test("accel-daily-tiers".into(), 42).unwrap();
+    let mut accel = SimEngine::build_test_with_time_acceleration("accel-tiers".into(), 42, &[]).unwrap();
+    daily.run_ticks(END).unwrap();
+    accel.run_ticks(END).unwrap();
+
+    assert_eq!(txn_counts(&daily, 1..=END), txn_counts(&accel, 1..=END));
+}
+
+/// Steps cover every day since the last one, so total volume past the
+/// switch stays close to a daily run's.
+#[test]
+fn volume_preserved_across_steps() {
+    let mut daily = SimEngine::build_test("accel-daily-volume".into(), 42).unwrap();
+    let mut accel = SimEngine::build_test_with_time_acceleration("accel-volume".into(), 42, &["low"]).unwrap();
+    daily.run_ticks(END).unwrap();
+    accel.run_ticks(END).unwrap();
+
+    let total = |e: &SimEngine| txn_counts(e, SWITCH + 1..=END).iter().sum::<i64>() as f64;
+    let (daily_total, accel_total) = (total(&daily), total(&accel));
+    let ratio = accel_total / daily_total;
+    assert!(
+        (0.8..=1.2).contains(&ratio),
+        "Accelerated {accel_total} vs daily {daily_total} transactions"
+    );
+}
@@ -0,0 +1,182 @@
+//! Tournament leaderboard tests — Phase 4.71.
+//!
+//! Tests cover: runs placed between the worst and best entry on their
+//! own seed, lower-is-better outcomes included; outcomes a run lacks
+//! left out of its weighting; complete players ranked first and equal
+//! ones sharing a rank; stored runs read from their own databases; and
+//! entries on the wrong seed, scenario or length, or repeated, rejected.
+
+use fincrime_core::{
+    config::SimConfig,
+    engine::SimEngine,
+    provenance::RunProvenance,
+    store::SimStore,
+    tournament::{RunOutcome, ScoreWeights, Tournament, TournamentEntry},
+};
+use std::{collections::BTreeMap, path::Path};
+
+fn tournament(seeds: &[u64], weights: ScoreWeights) -> Tournament {
+    Tournament {
+        name: "spring-cup".into(),
+        seeds: seeds.to_vec(),
+        ticks: 4,
+        scenario_hash: Some("scenario-a".into()),
+        weights,
+        entries: Vec::new(),
+    }
+}
+
+fn only(profit: f64, fraud_loss: f64, detection_rate: f64) -> ScoreWeights {
+    ScoreWeights { pre_tax_profit: profit, fraud_loss, detection_rate, churned: 0.0, complaints: 0.0, resilience: 0.0 }
+}
+
+fn outcome(player: &str, seed: u64, metrics: &[(&str, f64)]) -> RunOutcome {
+    RunOutcome {
+        player: player.into(),
+        run_id: format!("{player}-{seed}"),
+        seed,
+        metrics: metrics.iter().map(|(k, v)| (k.to_string(), *v)).collect(),
+    }
+}
+
+/// Store a run in its own database file, played for `ticks` ticks.
+fn stored_run(run_id: &str, seed: u64, scenario: &str, ticks: u64) -> String {
+    let db = format!("test_{run_id}.db");
+    let _ = std::fs::remove_file(&db);
+    let store = SimStore::open(&db).unwrap();
+    store.migrate().unwrap();
+    let provenance = RunProvenance { scenario_hash: Some(scenario.into()), ..Default::default() };
+    store.insert_run_with_provenance(run_id, seed, "0.1.0-test", &provenance).unwrap();
+    let mut config = SimConfig::default_test();
+    config.reputation.enabled = true;
+    config.stress_index.enabled = true;
+    let mut engine = SimEngine::build_with_config(run_id.into(), seed, &store, config).unwrap();
+    engine.run_ticks(ticks).unwrap();
+    db
+}
+
+fn entry(player: &str, db: &str, run_id: &str) -> TournamentEntry {
+    TournamentEntry { player: player.into(), db: db.into(), run_id: run_id.into() }
+}
+
+/// Each run is placed against its seed alone; a player who missed a seed
+/// ranks after those who played both, whatever their score.
+#[test]
+fn runs_are_placed_against_their_seed() {
+    let cup = tournament(&[1, 2], only(0.5, 0.5, 0.0));
+    let board = cup.rank(vec![
+        outcome("alice", 1, &[("pre_tax_profit", 100.0), ("fraud_loss", 0.0)]),
+        outcome("bob", 1, &[("pre_tax_profit", 50.0), ("fraud_loss", 10.0)]),
+        outcome("carol", 1, &[("pre_tax_profit", 75.0), ("fraud_loss", 5.0)]),
+        outcome("alice", 2, &[("pre_tax_profit", 10.0), ("fraud_loss", 5.0)]),
+        outcome("bob", 2, &[("pre_tax_profit", 10.0), ("fraud_loss", 5.0)]),
+    ]);
+
+    let scores: BTreeMap<(u64, &str), f64> =
+        board.runs.iter().map(|r| ((r.seed, r.player.as_str()), r.score)).collect();
+    assert_eq!(scores[&(1, "alice")], 100.0);
+    assert_eq!(scores[&(1, "bob")], 0.0);
+    assert_eq!(scores[&(1, "carol")], 50.0);
+    // Everyone level on seed 2 is as good as the best
+    assert_eq!(scores[&(2, "bob")], 100.0);
+    assert_eq!(board.runs[0].player, "alice", "best first within a seed");
+
+    let standings: Vec<_> =
+        board.standings.iter().map(|s| (s.rank, s.player.as_str(), s.score, s.complete)).collect();
+    assert_eq!(standings, [(1, "alice", 100.0, true), (2, "bob", 50.0, true), (3, "carol", 50.0, false)]);
+}
+
+/// A run without a detection rate is scored on the outcomes it has.
+#[test]
+fn missing_outcomes_drop_out_of_the_weighting() {
+    let cup = tournament(&[1], only(0.5, 0.0, 0.5));
+    let board = cup.rank(vec![
+        outcome("alice", 1, &[("pre_tax_profit", 50.0), ("detection_rate", 0.9)]),
+        outcome("bob", 1, &[("pre_tax_profit", 100.0)]),
+    ]);
+    let score = |player: &str| board.runs.iter().find(|r| r.player == player).unwrap().score;
+    assert_eq!(score("alice"), 50.0);
+    assert_eq!(score("bob"), 100.0);
+}
+
+/// A definition needs seeds, a length and some weight; everything else
+/// has a default.
+#[test]
+fn definitions_are_validated() {
+    let parsed: Tournament =
+        serde_json::from_str(r#"{ "name": "open", "seeds": [7, 8], "ticks": 90 }"#).unwrap();
+    assert_eq!(parsed.weights, ScoreWeights::default());
+    assert_eq!(parsed.scenario_hash, None);
+    assert!(parsed.entries.is_empty());
+    parsed.validate().unwrap();
+
+    let refused = |cup: Tournament| {
+        let e = cup.validate().unwrap_err();
+        assert_eq!(e.code(), "command_error");
+        assert!(!e.is_fatal());
+    };
+    refused(Tournament { seeds: vec![], ..parsed.clone() });
+    refused(Tournament { ticks: 0, ..parsed.clone() });
+    refused(Tournament { weights: only(0.0, 0.0, 0.0), ..parsed.clone() });
+    refused(Tournament { weights: only(1.0, -0.5, 0.0), ..parsed.clone() });
+}
+
+/// Two players running the same seed the same way tie at the top, each
+/// run read from its own database.
+#[test]
+fn stored_runs_are_scored() {
+    let a = stored_run("cup-alice-42", 42, "scenario-a", 4);
+    let b = stored_run("cup-bob-42", 42, "scenario-a", 4);
+    let mut cup = tournament(&[42], ScoreWeights::default());
+    cup.entries = vec![entry("alice", &a, "cup-alice-42"), entry("bob", &b, "cup-bob-42")];
+
+    let board = cup.leaderboard(Path::new(".")).unwrap();
+    assert!(board.rejected.is_empty(), "{:?}", board.rejected);
+    assert_eq!(board.runs.len(), 2);
+    for run in &board.runs {
+        assert_eq!(run.score, 100.0);
+        for metric in ["pre_tax_profit", "fraud_loss", "churned", "complaints", "resilience"] {
+            assert!(run.metrics.contains_key(metric), "{metric} missing from {:?}", run.metrics);
+        }
+    }
+    assert_eq!(board.runs[0].metrics, board.runs[1].metrics);
+    let ranks: Vec<_> = board.standings.iter().map(|s| (s.rank, s.player.as_str())).collect();
+    assert_eq!(ranks, [(1, "alice"), (1, "bob")]);
+}
+
+/// Entries off the tournament's seeds, scenario or length, a player's
+/// second run on a seed, and runs that cannot be found are listed with
+/// the reason and left out.
+#[test]
+fn entries_that_do_not_fit_are_rejected() {
+    let good = stored_run("fit-good-42", 42, "scenario-a", 4);
+    let seed = stored_run("fit-seed-43", 43, "scenario-a", 4);
+    let scenario = stored_run("fit-scenario-42", 42, "scenario-b", 4);
+    let short = stored_run("fit-short-42", 42, "scenario-a", 3);
+    let mut cup = tournament(&[42], ScoreWeights::default());
+    cup.entries = vec![
+        entry("alice", &good, "fit-good-42"),
+        entry("bob", &seed, "fit-seed-43"),
+        entry("carol", &scenario, "fit-scenario-42"),
+        entry("dave", &short, "fit-short-42"),
+        entry("alice", &good, "fit-good-42"),
+        entry("erin", &good, "fit-missing-42"),
+        entry("frank", "test_fit-nowhere.db", "fit-nowhere-42"),
+    ];
+
+    let board = cup.leaderboard(Path::new(".")).unwrap();
+    assert_eq!(board.standings.len(), 1);
+    assert_eq!(board.standings[0].player, "alice");
+    let reasons: Vec<_> = board.rejected.iter().map(|r| (r.player.as_str(), r.reason.as_str())).collect();
+    assert_eq!(reasons.len(), 6, "{reasons:?}");
+    let reason = |player: &str, fragment: &str| {
+        assert!(reasons.iter().any(|(p, r)| *p == player && r.contains(fragment)), "{player}: {reasons:?}");
+    };
+    reason("bob", "seed 43");
+    reason("carol", "different scenario");
+    reason("dave", "stopped at tick 3");
+    reason("alice", "already entered");
+    reason("erin", "no run 'fit-missing-42'");
+    reason("frank", "no database");
+    assert!(!Path::new("test_fit-nowhere.db").exists());
+}
@@ -0,0 +1,117 @@
+//! Transaction categorization enrichment tests — Phase 4.35.
+//!
+//! Tests cover: merchants categorized at the configured accuracy,
+//! HIGH_RISK_MCC alerts on gambling and crypto spend intensity, high-risk
+//! spend lowering financial health, and enrichment staying off unless
+//! enabled.
+
+use fincrime_core::{engine::SimEngine, event::SimEvent};
+
+fn build_with_enrichment(run_id: &str, seed: u64, accuracy: f64) -> SimEngine {
+    SimEngine::build_test_with_transaction_enrichment(run_id.to_string(), seed, accuracy)
+        .expect("build test engine with transaction enrichment")
+}
+
+/// Every merchant paid is categorized once; a perfect enrichment labels
+/// each with its true category, and a poor one mislabels a share close to
+/// its error rate.
+#[test]
+fn merchants_categorized_at_configured_accuracy() {
+    let mut perfect = build_with_enrichment("enrich-perfect-test", 42, 1.0);
+    perfect.run_ticks(30).unwrap();
+    let merchants = perfect.store.merchant_categories("enrich-perfect-test").unwrap();
+    assert!(!merchants.is_empty());
+    assert!(merchants.iter().all(|m| m.enriched_category == m.true_category));
+    let categorized: i64 = (1..=30)
+        .flat_map(|tick| perfect.store_events_for_tick("enrich-perfect-test", tick).unwrap())
+        .filter(|e| e.event_type == "merchants_categorized")
+        .map(|e| match serde_json::from_str(&e.payload).unwrap() {
+            SimEvent::MerchantsCategorized { merchants, .. } => merchants,
+            other => panic!("unexpected event {other:?}"),
+        })
+        .sum();
+    assert_eq!(categorized, merchants.len() as i64);
+
+    let mut poor = build_with_enrichment("enrich-poor-test", 42, 0.5);
+    poor.run_ticks(30).unwrap();
+    let merchants = poor.store.merchant_categories("enrich-poor-test").unwrap();
+    let wrong = merchants.iter().filter(|m| m.enriched_category != m.true_category).count();
+    let error_rate = wrong as f64 / merchants.len() as f64;
+    assert!((0.35..=0.65).contains(&error_rate), "{error_rate}");
+}
+
+/// With a perfect enrichment, HIGH_RISK_MCC alerts exactly the customers
+/// whose gambling and crypto share of merchant spend crosses the
+/// threshold on the weekly pass.
+#[test]
+fn high_risk_spend_intensity_alerts() {
+    let run_id = "enrich-alert-test";
+    let mut engine = build_with_enrichment(run_id, 42, 1.0);
+    engine.run_ticks(28).unwrap();
+
+    let alerted: Vec<String> = engine
+        .store_events_for_tick(run_id, 28)
+        .unwrap()
+        .into_iter()
+        .filter(|e| e.event_type == "transaction_monitoring_alert")
+        .filter_map(|e| match serde_json::from_str(&e.payload).unwrap() {
+            SimEvent::TransactionMonitoringAlert { alert_type, customer_id, .. }
+                if alert_type == "category_intensity" =>
+            {
+                Some(customer_id)
+            }
+            _ => None,
+        })
+        .collect();
+    assert!(!alerted.is_empty());
+
+    let mut expected = Vec::new();
+    let mut spend = std::collections::BTreeMap::<String, (f64, f64)>::new();
+    for row in engine.store.merchant_spend_by_category(run_id, None, 0, 28).unwrap() {
+        let entry = spend.entry(row.customer_id).or_default();
+        entry.0 += row.amount;
+        if row.category == "gambling" || row.category == "crypto" {
+            entry.1 += row.amount;
+        }
+    }
+    for (customer_id, (total, high_risk)) in spend {
+        if high_risk >= 250.0 && high_risk / total >= 0.30 {
+            expected.push(customer_id);
+        }
+    }
+    let mut alerted = alerted;
+    alerted.sort();
+    assert_eq!(alerted, expected);
+}
+
+/// Gambling and crypto spend costs financial health: heavy high-risk
+/// spenders score below customers with little of it.
+#[test]
+fn high_risk_spend_lowers_financial_health() {
+    let run_id = "enrich-health-test";
+    let mut engine = build_with_enrichment(run_id, 42, 1.0);
+    engine.run_ticks(60).unwrap();
+
+    let scores = engine.store.customer_health_at(run_id, 60).unwrap();
+    let mean = |heavy: bool| {
+        let rows: Vec<f64> = scores
+            .iter()
+            .filter(|r| if heavy { r.high_risk_share >= 0.3 } else { r.high_risk_share < 0.1 })
+            .map(|r| r.score)
+            .collect();
+        assert!(!rows.is_empty());
+        rows.iter().sum::<f64>() / rows.len() as f64
+    };
+    assert!(mean(true) < mean(false), "{} vs {}", mean(true), mean(false));
+}
+
+/// The test default categorizes nothing and raises no intensity alerts.
+#[test]
+fn enrichment_is_opt_in() {
+    let run_id = "enrich-off-test";
+    let mut engine = SimEngine::build_test(run_id.into(), 42).unwrap();
+    engine.run_ticks(30).unwrap();
+
+    assert!(engine.store.merchant_categories(run_id).unwrap().is_empty());
+    assert_eq!(engine.store.count_events_in_range(run_id, 0, 30, "merchants_categorized").unwrap(), 0);
+}
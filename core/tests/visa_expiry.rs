@@ -0,0 +1,154 @@
+//! Visa expiry and documentation refresh tests — Phase 4.37.
+//!
+//! Tests cover: refresh work items opened ahead of expiry and renewals
+//! rolling the expiry forward, lapsed documentation restricted after the
+//! grace period, a full restriction blocking more and drawing more
+//! complaints than an outbound one, the player's policy lifting
+//! restrictions, and visa tracking staying off unless enabled.
+
+use fincrime_core::{command::PlayerCommand, engine::SimEngine, event::SimEvent};
+
+fn build_with_visa(run_id: &str, seed: u64, level: &str) -> SimEngine {
+    SimEngine::build_test_with_visa_expiry(run_id.to_string(), seed, level)
+        .expect("build test engine with visa expiry")
+}
+
+fn events(engine: &SimEngine, run_id: &str, ticks: u64) -> Vec<SimEvent> {
+    (1..=ticks)
+        .flat_map(|tick| engine.store_events_for_tick(run_id, tick).unwrap())
+        .map(|e| serde_json::from_str(&e.payload).unwrap())
+        .collect()
+}
+
+/// Every refresh opens `refresh_lead_ticks` ahead of expiry (or on the
+/// first tick for a visa already inside the window), and verified renewals close
+/// their item `processing_ticks` after submission at its cost. Customers
+/// whose visa is not renewed never submit.
+#[test]
+fn refresh_items_opened_ahead_of_expiry() {
+    let run_id = "visa-refresh-test";
+    let mut engine = build_with_visa(run_id, 42, "outbound");
+    engine.run_ticks(120).unwrap();
+
+    let items = engine.store.visa_refresh_items(run_id).unwrap();
+    assert!(!items.is_empty());
+    let first_tick = items[0].opened_tick;
+    for item in &items {
+        assert!(item.opened_tick == first_tick || item.opened_tick + 20 == item.expiry_tick, "{item:?}");
+        if let Some(completed) = item.completed_tick {
+            assert_eq!(completed, item.submitted_tick.unwrap() + 10);
+            assert_eq!(item.cost, 20.0);
+        }
+    }
+    assert!(items.iter().any(|i| i.completed_tick.is_some()));
+
+    let documents = engine.store.visa_documents(run_id).unwrap();
+    for doc in documents.iter().filter(|d| !d.renews) {
+        assert!(items
+            .iter()
+            .filter(|i| i.customer_id == doc.customer_id)
+            .all(|i| i.submitted_tick.is_none()));
+    }
+    let renewed = events(&engine, run_id, 120)
+        .into_iter()
+        .filter(|e| matches!(e, SimEvent::VisaDocumentsRenewed { .. }))
+        .count();
+    assert_eq!(renewed, items.iter().filter(|i| i.completed_tick.is_some()).count());
+}
+
+/// Lapsed documentation is restricted no sooner than the grace period
+/// after it lapses, and an outbound restriction only blocks wires and
+/// instant payments.
+#[test]
+fn lapsed_documentation_restricted_after_grace() {
+    let run_id = "visa-restrict-test";
+    let mut engine = build_with_visa(run_id, 42, "outbound");
+    engine.run_ticks(120).unwrap();
+
+    let restricted: Vec<_> = engine
+        .store
+        .visa_documents(run_id)
+        .unwrap()
+        .into_iter()
+        .filter(|d| d.restriction.is_some())
+        .collect();
+    assert!(!restricted.is_empty());
+    for doc in &restricted {
+        assert_eq!(doc.restriction.as_deref(), Some("outbound"));
+        assert!(doc.restricted_tick.unwrap() >= doc.lapsed_tick.unwrap() + 5);
+    }
+
+    let all = events(&engine, run_id, 120);
+    assert!(all.iter().any(|e| matches!(e, SimEvent::VisaDocumentationLapsed { .. })));
+    let blocked: Vec<&str> = all
+        .iter()
+        .filter_map(|e| match e {
+            SimEvent::RestrictedTransactionBlocked { channel, .. } => Some(channel.as_str()),
+            _ => None,
+        })
+        .collect();
+    assert!(!blocked.is_empty());
+    assert!(blocked.iter().all(|c| *c == "wire" || *c == "RTP"), "{blocked:?}");
+}
+
+/// On the same run, a full restriction blocks every debit and draws more
+/// complaints than an outbound one.
+#[test]
+fn full_restriction_costs_more_service_than_outbound() {
+    let mut outcomes = Vec::new();
+    for level in ["outbound", "full"] {
+        let run_id = format!("visa-{level}-test");
+        let mut engine = build_with_visa(&run_id, 42, level);
+        engine.run_ticks(120).unwrap();
+        let all = events(&engine, &run_id, 120);
+        let blocked = all
+            .iter()
+            .filter(|e| matches!(e, SimEvent::RestrictedTransactionBlocked { .. }))
+            .count();
+        let complaints = all
+            .iter()
+            .filter(|e| matches!(e, SimEvent::ComplaintFiled { issue, .. }
+                if issue == "account_access" || issue == "service_failure"))
+            .count();
+        outcomes.push((blocked, complaints));
+    }
+    let (outbound, full) = (outcomes[0], outcomes[1]);
+    assert!(full.0 > outbound.0, "blocked: outbound {} full {}", outbound.0, full.0);
+    assert!(full.1 > outbound.1, "complaints: outbound {} full {}", outbound.1, full.1);
+}
+
+/// Setting the policy to "none" lifts every restriction; an unknown level
+/// is rejected.
+#[test]
+fn policy_command_lifts_restrictions() {
+    let run_id = "visa-policy-test";
+    let mut engine = build_with_visa(run_id, 42, "full");
+    engine.run_ticks(100).unwrap();
+    assert!(!engine.store.visa_restrictions(run_id).unwrap().is_empty());
+
+    engine
+        .submit_command(PlayerCommand::SetVisaRestrictionPolicy { level: "frozen".into(), grace_ticks: 0 })
+        .unwrap();
+    engine
+        .submit_command(PlayerCommand::SetVisaRestrictionPolicy { level: "none".into(), grace_ticks: 5 })
+        .unwrap();
+    engine.run_ticks(1).unwrap();
+    assert!(engine.store.visa_restrictions(run_id).unwrap().is_empty());
+
+    let all = events(&engine, run_id, 101);
+    assert!(all.iter().any(|e| matches!(e, SimEvent::VisaRestrictionPolicyRejected { .. })));
+    assert!(all
+        .iter()
+        .any(|e| matches!(e, SimEvent::AccountRestrictionLifted { reason, .. } if reason == "policy")));
+}
+
+/// Visa tracking is opt-in: the default test build opens no refreshes and
+/// restricts no one.
+#[test]
+fn visa_expiry_opt_in() {
+    let run_id = "visa-off-test";
+    let mut engine = SimEngine::build_test(run_id.to_string(), 42).unwrap();
+    engine.run_ticks(60).unwrap();
+    assert!(engine.store.visa_documents(run_id).unwrap().is_empty());
+    assert!(engine.store.visa_refresh_items(run_id).unwrap().is_empty());
+}
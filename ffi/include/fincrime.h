@@ -0,0 +1,42 @@
+/*
+ * fincrime.h — C ABI for embedding the FinCrime: The Desk engine.
+ *
+ * Strings are NUL-terminated UTF-8. Strings returned by the library are
+ * owned by the caller and released with fc_string_free. Failed calls
+ * return NULL or -1; fc_last_error then describes the failure on the
+ * calling thread.
+ */
+#ifndef FINCRIME_H
+#define FINCRIME_H
+
+#include <stdint.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+typedef struct FcEngine FcEngine;
+
+/* Build an engine. db_path is a SQLite file, or NULL for in-memory. */
+FcEngine *fc_engine_new(uint64_t seed, const char *data_dir, const char *db_path);
+
+/* Advance the engine `ticks` ticks. Returns 0, or -1 on failure. */
+int32_t fc_engine_tick(FcEngine *engine, uint64_t ticks);
+
+/* The UI state as a JSON object; free with fc_string_free. */
+char *fc_engine_get_state(FcEngine *engine);
+
+/* Queue a PlayerCommand given as JSON, e.g. {"cmd":"pause"}. */
+int32_t fc_engine_apply_command(FcEngine *engine, const char *command_json);
+
+void fc_engine_free(FcEngine *engine);
+void fc_string_free(char *s);
+
+/* Last failure on this thread, or NULL; valid until the next failure. */
+const char *fc_last_error(void);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif /* FINCRIME_H */
@@ -0,0 +1,187 @@
+//! C ABI for embedding the engine in non-Rust hosts.
+//!
+//! The surface mirrors the sim-runner IPC protocol: build an engine, tick
+//! it, read the UI state as JSON and apply player commands as JSON. Every
+//! string crossing the boundary is NUL-terminated UTF-8. Strings returned
+//! by the library are owned by the caller and released with
+//! `fc_string_free`; engines are released with `fc_engine_free`.
+//!
+//! Calls that fail return null or -1 and leave a message for
+//! `fc_last_error` on the calling thread. Panics never unwind into the
+//! host; they are caught and reported the same way.
+//!
+//! The declarations for C callers live in `include/fincrime.h`.
+
+use anyhow::{anyhow, Context, Result};
+use fincrime_core::{command::PlayerCommand, engine::SimEngine, protocol::UiState, store::SimStore};
+use std::{
+    cell::RefCell,
+    ffi::{c_char, CStr, CString},
+    panic::{catch_unwind, AssertUnwindSafe},
+    ptr,
+};
+
+thread_local! {
+    static LAST_ERROR: RefCell<Option<CString>> = const { RefCell::new(None) };
+}
+
+/// An engine and the store connection that created its run.
+pub struct FcEngine {
+    engine: SimEngine,
+    // Keeps a shared-cache in-memory database alive for the engine's lifetime
+    _store: SimStore,
+}
+
+fn set_last_error(message: String) {
+    let message = CString::new(message.replace('\0', " ")).unwrap_or_default();
+    LAST_ERROR.with(|e| *e.borrow_mut() = Some(message));
+}
+
+/// Run `f`, turning an error or a panic into `fallback` plus a last error.
+fn guard<T>(fallback: T, f: impl FnOnce() -> Result<T>) -> T {
+    match catch_unwind(AssertUnwindSafe(f)) {
+        Ok(Ok(value)) => value,
+        Ok(Err(e)) => {
+            set_last_error(format!("{e:#}"));
+            fallback
+        }
+        Err(panic) => {
+            let message = panic
+                .downcast_ref::<&str>()
+                .map(|s| s.to_string())
+                .or_else(|| panic.downcast_ref::<String>().cloned())
+                .unwrap_or_else(|| "unknown panic".into());
+            set_last_error(format!("panic: {message}"));
+            fallback
+        }
+    }
+}
+
+/// Borrow a C string argument; null is `None`.
+///
+/// # Safety
+/// `s` must be null or point to a NUL-terminated string.
+unsafe fn opt_str<'a>(s: *const c_char, name: &str) -> Result<Option<&'a str>> {
+    if s.is_null() {
+        return Ok(None);
+    }
+    CStr::from_ptr(s)
+        .to_str()
+        .map(Some)
+        .with_context(|| format!("{name} is not valid UTF-8"))
+}
+
+/// # Safety
+/// `engine` must be null or a pointer returned by `fc_engine_new` and not
+/// yet freed.
+unsafe fn engine_mut<'a>(engine: *mut FcEngine) -> Result<&'a mut FcEngine> {
+    engine.as_mut().ok_or_else(|| anyhow!("engine is null"))
+}
+
+fn into_c_string(s: String) -> Result<*mut c_char> {
+    Ok(CString::new(s)?.into_raw())
+}
+
+/// Build an engine for `seed`, loading config from `data_dir`.
+///
+/// `db_path` is a SQLite file to persist the run to, or null for a private
+/// in-memory database. Returns null on failure.
+///
+/// # Safety
+/// `data_dir` must point to a NUL-terminated string; `db_path` must be
+/// null or point to one.
+#[no_mangle]
+pub unsafe extern "C" fn fc_engine_new(
+    seed: u64,
+    data_dir: *const c_char,
+    db_path: *const c_char,
+) -> *mut FcEngine {
+    guard(ptr::null_mut(), || {
+        let data_dir = opt_str(data_dir, "data_dir")?.ok_or_else(|| anyhow!("data_dir is null"))?;
+        let db = match opt_str(db_path, "db_path")? {
+            Some(path) => path.to_string(),
+            None => format!("file:ffi_{}?mode=memory&cache=shared", uuid::Uuid::new_v4()),
+        };
+        let store = SimStore::open(&db)?;
+        store.migrate()?;
+        let run_id = format!("run-{seed}-{}", uuid::Uuid::new_v4());
+        store.insert_run(&run_id, seed, env!("CARGO_PKG_VERSION"))?;
+        let engine = SimEngine::build(run_id, seed, &store, data_dir)?;
+        Ok(Box::into_raw(Box::new(FcEngine { engine, _store: store })))
+    })
+}
+
+/// Advance the engine `ticks` ticks. Returns 0, or -1 on failure.
+///
+/// # Safety
+/// `engine` must be a live pointer from `fc_engine_new`.
+#[no_mangle]
+pub unsafe extern "C" fn fc_engine_tick(engine: *mut FcEngine, ticks: u64) -> i32 {
+    guard(-1, || {
+        engine_mut(engine)?.engine.run_ticks(ticks)?;
+        Ok(0)
+    })
+}
+
+/// The current `UiState` as JSON, the same shape sim-runner sends over
+/// IPC. Free it with `fc_string_free`. Returns null on failure.
+///
+/// # Safety
+/// `engine` must be a live pointer from `fc_engine_new`.
+#[no_mangle]
+pub unsafe extern "C" fn fc_engine_get_state(engine: *mut FcEngine) -> *mut c_char {
+    guard(ptr::null_mut(), || {
+        let state = UiState::from_engine(&engine_mut(engine)?.engine)?;
+        into_c_string(serde_json::to_string(&state)?)
+    })
+}
+
+/// Queue a player command, given as the JSON of a `PlayerCommand`
+/// (e.g. `{"cmd":"pause"}`); it takes effect on the next tick. Returns 0,
+/// or -1 on failure.
+///
+/// # Safety
+/// `engine` must be a live pointer from `fc_engine_new`; `command_json`
+/// must point to a NUL-terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn fc_engine_apply_command(
+    engine: *mut FcEngine,
+    command_json: *const c_char,
+) -> i32 {
+    guard(-1, || {
+        let engine = engine_mut(engine)?;
+        let json = opt_str(command_json, "command_json")?.ok_or_else(|| anyhow!("command_json is null"))?;
+        let command: PlayerCommand = serde_json::from_str(json).context("invalid command")?;
+        engine.engine.submit_command(command)?;
+        Ok(0)
+    })
+}
+
+/// Release an engine. Null is ignored.
+///
+/// # Safety
+/// `engine` must be null or a pointer from `fc_engine_new` not yet freed.
+#[no_mangle]
+pub unsafe extern "C" fn fc_engine_free(engine: *mut FcEngine) {
+    if !engine.is_null() {
+        drop(Box::from_raw(engine));
+    }
+}
+
+/// Release a string returned by the library. Null is ignored.
+///
+/// # Safety
+/// `s` must be null or a string returned by this library not yet freed.
+#[no_mangle]
+pub unsafe extern "C" fn fc_string_free(s: *mut c_char) {
+    if !s.is_null() {
+        drop(CString::from_raw(s));
+    }
+}
+
+/// The message of the last failed call on this thread, or null. The
+/// pointer stays valid until the next failing call on the same thread.
+#[no_mangle]
+pub extern "C" fn fc_last_error() -> *const c_char {
+    LAST_ERROR.with(|e| e.borrow().as_ref().map_or(ptr::null(), |s| s.as_ptr()))
+}
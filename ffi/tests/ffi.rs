@@ -0,0 +1,115 @@
+//! C ABI tests.
+//!
+//! Tests cover: building, ticking and reading state through the C entry
+//! points, applying player commands as JSON, failures surfacing through
+//! `fc_last_error` instead of unwinding, and a file-backed run persisting
+//! where the host can read it back.
+
+use fincrime_core::store::SimStore;
+use fincrime_ffi::*;
+use std::ffi::{c_char, CStr, CString};
+use std::ptr;
+
+const DATA_DIR: &str = "../data";
+
+fn c(s: &str) -> CString {
+    CString::new(s).unwrap()
+}
+
+fn new_engine(seed: u64, db_path: Option<&str>) -> *mut FcEngine {
+    let data_dir = c(DATA_DIR);
+    let db_path = db_path.map(c);
+    let engine = unsafe {
+        fc_engine_new(seed, data_dir.as_ptr(), db_path.as_ref().map_or(ptr::null(), |p| p.as_ptr()))
+    };
+    assert!(!engine.is_null(), "fc_engine_new failed: {}", last_error());
+    engine
+}
+
+fn state(engine: *mut FcEngine) -> serde_json::Value {
+    let raw = unsafe { fc_engine_get_state(engine) };
+    assert!(!raw.is_null(), "fc_engine_get_state failed: {}", last_error());
+    let json = unsafe { CStr::from_ptr(raw) }.to_str().unwrap().to_string();
+    unsafe { fc_string_free(raw) };
+    serde_json::from_str(&json).unwrap()
+}
+
+fn last_error() -> String {
+    let e: *const c_char = fc_last_error();
+    if e.is_null() {
+        return String::new();
+    }
+    unsafe { CStr::from_ptr(e) }.to_string_lossy().into_owned()
+}
+
+/// A host builds an in-memory engine, ticks it and reads back the same
+/// state the IPC protocol sends.
+#[test]
+fn build_tick_and_read_state() {
+    let engine = new_engine(42, None);
+    assert_eq!(unsafe { fc_engine_tick(engine, 5) }, 0, "{}", last_error());
+
+    let state = state(engine);
+    assert_eq!(state["tick"], 5);
+    assert_eq!(state["paused"], true);
+    assert!(state["active_customers"].as_i64().unwrap() > 0);
+    assert!(state["complaints"].is_array());
+    assert!(state["run_id"].as_str().unwrap().starts_with("run-42-"));
+    unsafe { fc_engine_free(engine) };
+}
+
+/// Commands arrive as `PlayerCommand` JSON and are accepted; malformed
+/// ones are rejected with a message.
+#[test]
+fn commands_apply_as_json() {
+    let engine = new_engine(7, None);
+    let fee = c(r#"{"cmd":"set_product_fee","product_id":"basic_checking","fee_type":"monthly_fee","new_value":0.0}"#);
+    assert_eq!(unsafe { fc_engine_apply_command(engine, fee.as_ptr()) }, 0, "{}", last_error());
+    assert_eq!(unsafe { fc_engine_tick(engine, 1) }, 0, "{}", last_error());
+    assert_eq!(state(engine)["tick"], 1);
+
+    let bogus = c(r#"{"cmd":"launch_rocket"}"#);
+    assert_eq!(unsafe { fc_engine_apply_command(engine, bogus.as_ptr()) }, -1);
+    assert!(last_error().starts_with("invalid command"), "{}", last_error());
+    unsafe { fc_engine_free(engine) };
+}
+
+/// Null handles and a bad config directory fail cleanly with a message
+/// rather than crashing the host.
+#[test]
+fn failures_report_through_last_error() {
+    assert_eq!(unsafe { fc_engine_tick(ptr::null_mut(), 1) }, -1);
+    assert_eq!(last_error(), "engine is null");
+    assert!(unsafe { fc_engine_get_state(ptr::null_mut()) }.is_null());
+
+    assert!(unsafe { fc_engine_new(1, ptr::null(), ptr::null()) }.is_null());
+    assert_eq!(last_error(), "data_dir is null");
+
+    let missing = c("./no-such-data-dir");
+    assert!(unsafe { fc_engine_new(1, missing.as_ptr(), ptr::null()) }.is_null());
+    assert!(!last_error().is_empty());
+
+    unsafe {
+        fc_engine_free(ptr::null_mut());
+        fc_string_free(ptr::null_mut());
+    }
+}
+
+/// With a database path the run is written to that file, where the host
+/// can read it back after the engine is gone.
+#[test]
+fn file_backed_run_persists() {
+    let path = std::env::temp_dir().join(format!("fincrime_ffi_{}.db", std::process::id()));
+    let path = path.to_str().unwrap().to_string();
+    let engine = new_engine(99, Some(&path));
+    assert_eq!(unsafe { fc_engine_tick(engine, 3) }, 0, "{}", last_error());
+    let run_id = state(engine)["run_id"].as_str().unwrap().to_string();
+    unsafe { fc_engine_free(engine) };
+
+    let store = SimStore::open(&path).unwrap();
+    assert!(!store.events_for_tick(&run_id, 3).unwrap().is_empty());
+    drop(store);
+    for suffix in ["", "-wal", "-shm"] {
+        let _ = std::fs::remove_file(format!("{path}{suffix}"));
+    }
+}
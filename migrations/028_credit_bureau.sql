@@ -0,0 +1,37 @@
+-- Phase 4.1: Credit bureau furnishing and FCRA disputes
+--
+-- bureau_furnishing: one row per account per monthly furnishing cycle.
+-- bureau_dispute: customer disputes of inaccurate reporting (FCRA §611).
+CREATE TABLE IF NOT EXISTS bureau_furnishing (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    run_id TEXT NOT NULL REFERENCES run(run_id),
+    tick INTEGER NOT NULL,
+    account_id TEXT NOT NULL,
+    customer_id TEXT NOT NULL,
+    actual_status TEXT NOT NULL,
+    -- "current" | "negative_balance"
+    reported_status TEXT NOT NULL,
+    -- "current" | "negative_balance" | "charged_off"
+    balance REAL NOT NULL,
+    is_error INTEGER NOT NULL DEFAULT 0 -- boolean 0/1: reported != actual
+);
+CREATE INDEX IF NOT EXISTS idx_bureau_furnishing_run ON bureau_furnishing(run_id, tick);
+CREATE TABLE IF NOT EXISTS bureau_dispute (
+    dispute_id TEXT PRIMARY KEY,
+    run_id TEXT NOT NULL REFERENCES run(run_id),
+    furnishing_id INTEGER NOT NULL REFERENCES bureau_furnishing(id),
+    customer_id TEXT NOT NULL,
+    account_id TEXT NOT NULL,
+    complaint_id TEXT NOT NULL,
+    -- linked complaint row (issue = "credit_reporting")
+    filed_tick INTEGER NOT NULL,
+    due_tick INTEGER NOT NULL,
+    -- filed_tick + investigation window (30 days under FCRA)
+    status TEXT NOT NULL DEFAULT 'open',
+    -- "open" | "corrected"
+    overdue INTEGER NOT NULL DEFAULT 0,
+    -- boolean 0/1: window elapsed before correction
+    resolved_tick INTEGER,
+    penalty_amount REAL NOT NULL DEFAULT 0.0
+);
+CREATE INDEX IF NOT EXISTS idx_bureau_dispute_run ON bureau_dispute(run_id, status);
@@ -0,0 +1,33 @@
+-- Phase 4.2: Federal benefit deposits and protected-funds enforcement
+--
+-- benefit_recipient: accounts receiving a monthly federal benefit
+--   (Social Security) instead of employer payroll.
+-- protected_funds_event: every bank-initiated debit (overdraft fee,
+--   garnishment, setoff) checked against the protected amount
+--   (benefits deposited within the 31 CFR 212 lookback window).
+CREATE TABLE IF NOT EXISTS benefit_recipient (
+    run_id TEXT NOT NULL REFERENCES run(run_id),
+    account_id TEXT NOT NULL,
+    customer_id TEXT NOT NULL,
+    benefit_type TEXT NOT NULL,
+    -- "social_security"
+    monthly_amount REAL NOT NULL,
+    enrolled_tick INTEGER NOT NULL,
+    PRIMARY KEY (run_id, account_id)
+);
+CREATE TABLE IF NOT EXISTS protected_funds_event (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    run_id TEXT NOT NULL REFERENCES run(run_id),
+    tick INTEGER NOT NULL,
+    account_id TEXT NOT NULL,
+    customer_id TEXT NOT NULL,
+    debit_type TEXT NOT NULL,
+    -- "overdraft_fee" | "garnishment" | "setoff"
+    requested_amount REAL NOT NULL,
+    applied_amount REAL NOT NULL,
+    protected_amount REAL NOT NULL,
+    outcome TEXT NOT NULL,
+    -- "applied" | "partial" | "blocked"
+    violation INTEGER NOT NULL DEFAULT 0 -- boolean 0/1: protected funds were taken
+);
+CREATE INDEX IF NOT EXISTS idx_protected_funds_event_run ON protected_funds_event(run_id, tick);
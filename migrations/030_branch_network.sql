@@ -0,0 +1,42 @@
+-- Phase 4.3: Branch network
+--
+-- branch: physical branches, one market (state) each.
+-- customer_branch: each customer's home branch ("br-DIGITAL" when the
+--   customer's state has no open branch).
+-- branch_performance: monthly per-branch report.
+CREATE TABLE IF NOT EXISTS branch (
+    run_id TEXT NOT NULL REFERENCES run(run_id),
+    branch_id TEXT NOT NULL,
+    state_code TEXT NOT NULL,
+    status TEXT NOT NULL DEFAULT 'open',
+    -- "open" | "closed"
+    staff_count INTEGER NOT NULL,
+    opened_tick INTEGER NOT NULL,
+    closed_tick INTEGER,
+    PRIMARY KEY (run_id, branch_id)
+);
+CREATE INDEX IF NOT EXISTS idx_branch_state ON branch(run_id, state_code, status);
+CREATE TABLE IF NOT EXISTS customer_branch (
+    run_id TEXT NOT NULL REFERENCES run(run_id),
+    customer_id TEXT NOT NULL,
+    branch_id TEXT NOT NULL,
+    assigned_tick INTEGER NOT NULL,
+    PRIMARY KEY (run_id, customer_id)
+);
+CREATE INDEX IF NOT EXISTS idx_customer_branch_branch ON customer_branch(run_id, branch_id);
+CREATE TABLE IF NOT EXISTS branch_performance (
+    run_id TEXT NOT NULL REFERENCES run(run_id),
+    tick INTEGER NOT NULL,
+    branch_id TEXT NOT NULL,
+    customer_count INTEGER NOT NULL,
+    staff_count INTEGER NOT NULL,
+    cash_txn_count INTEGER NOT NULL,
+    cash_volume REAL NOT NULL,
+    complaints_opened INTEGER NOT NULL,
+    complaint_rate REAL NOT NULL,
+    -- complaints per 100 customers over the period
+    churned_count INTEGER NOT NULL,
+    projected_acquisitions REAL NOT NULL,
+    operating_cost REAL NOT NULL,
+    PRIMARY KEY (run_id, tick, branch_id)
+);
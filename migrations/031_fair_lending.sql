@@ -0,0 +1,46 @@
+-- Phase 4.4: Zip-level demographics and fair lending / fair servicing analytics
+--
+-- zip_demographics: census-like attributes for every zip in the customer book.
+-- fair_lending_review: periodic disparity metrics, protected vs control geography.
+-- fair_lending_finding: disparities over threshold that the player must answer.
+CREATE TABLE IF NOT EXISTS zip_demographics (
+    run_id TEXT NOT NULL REFERENCES run(run_id),
+    zip_code TEXT NOT NULL,
+    state_code TEXT NOT NULL,
+    population INTEGER NOT NULL,
+    median_income REAL NOT NULL,
+    minority_share REAL NOT NULL,
+    -- [0.0, 1.0] share of residents in protected racial/ethnic groups
+    lmi_flag INTEGER NOT NULL DEFAULT 0,
+    -- boolean 0/1: median income < 80% of state median (CRA LMI)
+    majority_minority INTEGER NOT NULL DEFAULT 0,
+    -- boolean 0/1: minority_share >= 0.5
+    PRIMARY KEY (run_id, zip_code)
+);
+CREATE TABLE IF NOT EXISTS fair_lending_review (
+    run_id TEXT NOT NULL REFERENCES run(run_id),
+    tick INTEGER NOT NULL,
+    metric TEXT NOT NULL,
+    -- "fee_burden" | "account_closure" | "offer_targeting"
+    protected_value REAL NOT NULL,
+    control_value REAL NOT NULL,
+    adverse_ratio REAL NOT NULL,
+    -- > 1.0 means the protected geography fares worse
+    flagged INTEGER NOT NULL DEFAULT 0,
+    PRIMARY KEY (run_id, tick, metric)
+);
+CREATE TABLE IF NOT EXISTS fair_lending_finding (
+    finding_id TEXT NOT NULL,
+    run_id TEXT NOT NULL REFERENCES run(run_id),
+    tick INTEGER NOT NULL,
+    metric TEXT NOT NULL,
+    adverse_ratio REAL NOT NULL,
+    severity TEXT NOT NULL,
+    -- "moderate" | "major"
+    status TEXT NOT NULL DEFAULT 'open',
+    -- "open" | "remediated" | "justified" | "escalated"
+    response_due_tick INTEGER NOT NULL,
+    responded_tick INTEGER,
+    PRIMARY KEY (run_id, finding_id)
+);
+CREATE INDEX IF NOT EXISTS idx_fair_lending_finding_run ON fair_lending_finding(run_id, status);
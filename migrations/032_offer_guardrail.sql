@@ -0,0 +1,21 @@
+-- Phase 4.5: Marketing discrimination guardrails on offer targeting
+--
+-- offer_guardrail_assessment: one row per proposed offer launch. Compares
+-- how often customers in majority-minority zips are excluded by the
+-- targeting policy against customers in all other zips.
+CREATE TABLE IF NOT EXISTS offer_guardrail_assessment (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    run_id TEXT NOT NULL REFERENCES run(run_id),
+    tick INTEGER NOT NULL,
+    offer_id TEXT NOT NULL,
+    exclude_zip_prefixes TEXT NOT NULL,
+    -- JSON array of excluded zip prefixes
+    protected_exclusion_rate REAL NOT NULL,
+    control_exclusion_rate REAL NOT NULL,
+    disparity_ratio REAL,
+    -- protected / control exclusion rate; NULL when only protected zips are excluded
+    outcome TEXT NOT NULL,
+    -- "pass" | "warn" | "block" | "unassessed"
+    launched INTEGER NOT NULL DEFAULT 0
+);
+CREATE INDEX IF NOT EXISTS idx_offer_guardrail_run ON offer_guardrail_assessment(run_id, offer_id);
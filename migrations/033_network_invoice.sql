@@ -0,0 +1,30 @@
+-- Phase 4.6: Card network invoices (interchange, assessments, dispute fees)
+--
+-- network_invoice: one row per invoice line received from the card network.
+-- internal_amount is what the bank computed from its own authorization and
+-- dispute records; invoiced_amount is what the network billed or remitted.
+-- Lines that disagree beyond tolerance open a recon_exception on the card
+-- rail and can be recovered through a claim inside the recovery window.
+CREATE TABLE IF NOT EXISTS network_invoice (
+    run_id TEXT NOT NULL REFERENCES run(run_id),
+    invoice_id TEXT NOT NULL,
+    tick_received INTEGER NOT NULL,
+    period_start INTEGER NOT NULL,
+    period_end INTEGER NOT NULL,
+    line_type TEXT NOT NULL,
+    -- "interchange" | "assessment" | "dispute_fee"
+    internal_amount REAL NOT NULL,
+    invoiced_amount REAL NOT NULL,
+    delta_amount REAL NOT NULL,
+    -- amount in the network's favor; 0.0 when matched
+    error_type TEXT,
+    -- NULL | "rate_error" | "short_remittance" | "duplicate_dispute_fee"
+    status TEXT NOT NULL DEFAULT 'matched',
+    -- "matched" | "exception" | "recovered" | "claim_denied" | "written_off"
+    exception_id TEXT,
+    recovery_deadline_tick INTEGER,
+    recovered_amount REAL NOT NULL DEFAULT 0.0,
+    tick_resolved INTEGER,
+    PRIMARY KEY (run_id, invoice_id)
+);
+CREATE INDEX IF NOT EXISTS idx_network_invoice_status ON network_invoice(run_id, status);
@@ -0,0 +1,35 @@
+-- Phase 4.7: Nostro / settlement accounts per payment rail
+--
+-- settlement_account: one prefunded settlement account per rail, held at
+-- the rail's operator. Running totals of intraday borrowing.
+-- settlement_position: daily projected vs actual funding position. When
+-- actual settlement outflow exceeds the funded amount the shortfall is
+-- borrowed at a penalty cost; large shortfalls raise an operational alert.
+CREATE TABLE IF NOT EXISTS settlement_account (
+    run_id TEXT NOT NULL REFERENCES run(run_id),
+    rail_id TEXT NOT NULL REFERENCES payment_rail(rail_id),
+    account_id TEXT NOT NULL,
+    counterparty TEXT NOT NULL,
+    opened_tick INTEGER NOT NULL,
+    total_borrowed REAL NOT NULL DEFAULT 0.0,
+    total_borrowing_cost REAL NOT NULL DEFAULT 0.0,
+    shortfall_count INTEGER NOT NULL DEFAULT 0,
+    PRIMARY KEY (run_id, rail_id)
+);
+CREATE TABLE IF NOT EXISTS settlement_position (
+    run_id TEXT NOT NULL REFERENCES run(run_id),
+    rail_id TEXT NOT NULL,
+    tick INTEGER NOT NULL,
+    projected_outflow REAL NOT NULL,
+    -- trailing average of settled outflow
+    funded_amount REAL NOT NULL,
+    -- projected_outflow plus the funding buffer
+    actual_outflow REAL NOT NULL,
+    shortfall REAL NOT NULL DEFAULT 0.0,
+    idle_balance REAL NOT NULL DEFAULT 0.0,
+    -- funded but unused (opportunity cost)
+    borrowing_cost REAL NOT NULL DEFAULT 0.0,
+    alert INTEGER NOT NULL DEFAULT 0,
+    -- boolean 0/1: shortfall above the alert threshold
+    PRIMARY KEY (run_id, rail_id, tick)
+);
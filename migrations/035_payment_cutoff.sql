@@ -0,0 +1,27 @@
+-- Phase 4.8: Cutoff times and processing windows for payment rails
+--
+-- Each rail's cutoff_time_tick is the hour of the processing day after
+-- which newly initiated items miss the day's window. payment_cutoff_roll
+-- records every item that rolled to the next processing day: the core
+-- books it on the initiation day while the rail settles it a day later,
+-- so reconciliation sees a timing difference that reverses next day.
+-- Rolled transactions carry settlement_status 'rolled' until they settle.
+UPDATE payment_rail
+SET cutoff_time_tick = 16
+WHERE rail_id = 'ACH';
+CREATE TABLE IF NOT EXISTS payment_cutoff_roll (
+    run_id TEXT NOT NULL REFERENCES run(run_id),
+    txn_id TEXT NOT NULL,
+    rail_id TEXT NOT NULL REFERENCES payment_rail(rail_id),
+    account_id TEXT NOT NULL,
+    amount REAL NOT NULL,
+    direction TEXT NOT NULL,
+    initiated_hour INTEGER NOT NULL,
+    -- hour of day the customer initiated the payment (0-23)
+    booked_tick INTEGER NOT NULL,
+    -- processing day the item missed (booked internally)
+    processing_tick INTEGER NOT NULL,
+    -- next processing day, when the rail settles it
+    PRIMARY KEY (run_id, txn_id)
+);
+CREATE INDEX IF NOT EXISTS idx_cutoff_roll_processing ON payment_cutoff_roll(run_id, rail_id, processing_tick);
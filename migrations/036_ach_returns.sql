@@ -0,0 +1,54 @@
+-- Phase 4.9: ACH return reason codes
+--
+-- ach_return: every ACH item returned after settlement, with its NACHA
+-- return reason code and the handling that code mandates:
+--   'represent'         — R01/R09: re-presented once, collected or final
+--   'customer_contact'  — R02/R03/R04/R08: account holder must be contacted
+--   'dispute'           — R05/R10/R29: unauthorized claim, opens an ach_dispute
+--   'close_origination' — R07/R16: the originator relationship is closed
+-- ach_dispute: unauthorized-return disputes. After the review window the
+-- originator either accepts or proves authorization and the debit is
+-- reinstated.
+-- ach_origination_block: originator/account pairs closed by a return;
+-- any further entries between them are returned automatically.
+CREATE TABLE IF NOT EXISTS ach_return (
+    run_id TEXT NOT NULL REFERENCES run(run_id),
+    return_id TEXT NOT NULL,
+    txn_id TEXT NOT NULL,
+    account_id TEXT NOT NULL,
+    counterparty TEXT,
+    amount REAL NOT NULL,
+    direction TEXT NOT NULL,
+    return_code TEXT NOT NULL,
+    handling TEXT NOT NULL,
+    tick_returned INTEGER NOT NULL,
+    status TEXT NOT NULL,
+    -- 'returned', 'pending_represent', 'represented', 'final_return',
+    -- 'contact_logged', 'disputed', 'origination_closed'
+    represent_tick INTEGER,
+    resolved_tick INTEGER,
+    PRIMARY KEY (run_id, return_id)
+);
+CREATE INDEX IF NOT EXISTS idx_ach_return_represent ON ach_return(run_id, status, represent_tick);
+CREATE TABLE IF NOT EXISTS ach_origination_block (
+    run_id TEXT NOT NULL REFERENCES run(run_id),
+    account_id TEXT NOT NULL,
+    counterparty TEXT NOT NULL,
+    tick_blocked INTEGER NOT NULL,
+    return_code TEXT NOT NULL,
+    PRIMARY KEY (run_id, account_id, counterparty)
+);
+CREATE TABLE IF NOT EXISTS ach_dispute (
+    run_id TEXT NOT NULL REFERENCES run(run_id),
+    dispute_id TEXT NOT NULL,
+    return_id TEXT NOT NULL,
+    account_id TEXT NOT NULL,
+    customer_id TEXT NOT NULL,
+    amount REAL NOT NULL,
+    return_code TEXT NOT NULL,
+    tick_filed INTEGER NOT NULL,
+    status TEXT NOT NULL DEFAULT 'open',
+    -- 'open', 'customer_upheld', 'originator_upheld'
+    resolved_tick INTEGER,
+    PRIMARY KEY (run_id, dispute_id)
+);
@@ -0,0 +1,41 @@
+-- Phase 4.10: Customer-originated ACH (bill pay and external transfers)
+--
+-- originated_ach: every ACH entry a customer originates, including the
+-- attempts declined by the daily velocity limits:
+--   'bill_pay'          — credit entry paying an external biller; the
+--                         customer's account is debited
+--   'external_transfer' — debit entry pulling funds in from the customer's
+--                         account at another bank
+-- Takeover entries are flagged unauthorized and always come back R10.
+-- odfi_return_review: periodic return-rate reviews of the originated debit
+-- entries against the NACHA unauthorized / administrative / overall
+-- thresholds. A breach puts the bank under network scrutiny and draws a
+-- fine.
+CREATE TABLE IF NOT EXISTS originated_ach (
+    run_id TEXT NOT NULL REFERENCES run(run_id),
+    entry_id TEXT NOT NULL,
+    account_id TEXT NOT NULL,
+    customer_id TEXT NOT NULL,
+    entry_type TEXT NOT NULL,
+    counterparty TEXT NOT NULL,
+    amount REAL NOT NULL,
+    tick INTEGER NOT NULL,
+    unauthorized INTEGER NOT NULL DEFAULT 0,
+    status TEXT NOT NULL,
+    -- 'submitted', 'declined_count', 'declined_amount', 'returned'
+    return_code TEXT,
+    return_tick INTEGER,
+    PRIMARY KEY (run_id, entry_id)
+);
+CREATE INDEX IF NOT EXISTS idx_originated_ach_tick ON originated_ach(run_id, tick);
+CREATE TABLE IF NOT EXISTS odfi_return_review (
+    run_id TEXT NOT NULL REFERENCES run(run_id),
+    tick INTEGER NOT NULL,
+    debit_entries INTEGER NOT NULL,
+    unauthorized_rate REAL NOT NULL,
+    administrative_rate REAL NOT NULL,
+    overall_rate REAL NOT NULL,
+    breached TEXT,
+    network_fine REAL NOT NULL DEFAULT 0,
+    PRIMARY KEY (run_id, tick)
+);
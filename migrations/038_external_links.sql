@@ -0,0 +1,27 @@
+-- Phase 4.11: External account linking and micro-deposit verification
+--
+-- external_account_link: accounts at other banks a customer links for
+-- external transfers or a direct deposit switch. Each link is verified
+-- either instantly or by micro-deposits the customer must confirm:
+--   'pending'  — micro-deposits sent, awaiting confirmation
+--   'verified' — usable for transfers
+--   'failed'   — verification not completed
+-- Mule links opened on taken-over accounts are flagged fraudulent; those
+-- that get through verification drain the account for a few days.
+CREATE TABLE IF NOT EXISTS external_account_link (
+    run_id TEXT NOT NULL REFERENCES run(run_id),
+    link_id TEXT NOT NULL,
+    account_id TEXT NOT NULL,
+    customer_id TEXT NOT NULL,
+    external_ref TEXT NOT NULL,
+    purpose TEXT NOT NULL,
+    -- 'transfer', 'direct_deposit', 'mule'
+    method TEXT NOT NULL,
+    -- 'instant', 'micro_deposit'
+    fraudulent INTEGER NOT NULL DEFAULT 0,
+    status TEXT NOT NULL,
+    requested_tick INTEGER NOT NULL,
+    resolved_tick INTEGER,
+    PRIMARY KEY (run_id, link_id)
+);
+CREATE INDEX IF NOT EXISTS idx_external_link_account ON external_account_link(run_id, account_id, status);
@@ -0,0 +1,27 @@
+-- Phase 4.12: Customer-level limits
+--
+-- customer_limit: limits the player has set, one row per type and scope
+-- ('global', 'tier:<composite_risk>', 'product:<product_id>'). They replace
+-- the configured rule with the same type and scope.
+-- customer_limit_decline: attempts the generators declined because they
+-- would take the customer over a limit.
+CREATE TABLE IF NOT EXISTS customer_limit (
+    run_id TEXT NOT NULL REFERENCES run(run_id),
+    limit_type TEXT NOT NULL,
+    -- 'daily_transfer', 'cash_withdrawal', 'p2p_send', 'wire'
+    scope TEXT NOT NULL,
+    amount REAL NOT NULL,
+    set_tick INTEGER NOT NULL,
+    PRIMARY KEY (run_id, limit_type, scope)
+);
+CREATE TABLE IF NOT EXISTS customer_limit_decline (
+    run_id TEXT NOT NULL REFERENCES run(run_id),
+    decline_id INTEGER PRIMARY KEY AUTOINCREMENT,
+    tick INTEGER NOT NULL,
+    customer_id TEXT NOT NULL,
+    account_id TEXT NOT NULL,
+    limit_type TEXT NOT NULL,
+    amount REAL NOT NULL,
+    limit_amount REAL NOT NULL
+);
+CREATE INDEX IF NOT EXISTS idx_customer_limit_decline_tick ON customer_limit_decline(run_id, tick);
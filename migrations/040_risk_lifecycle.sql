@@ -0,0 +1,41 @@
+-- Phase 4.13: Risk-tiered customer lifecycle
+--
+-- customer_risk_score.base_risk: the tier assigned at onboarding. Behavior
+-- can push a customer above it; reviews never take them below it.
+-- risk_tier_transition: every change to a customer's composite tier, with
+-- the reason and the behavior score behind it:
+--   'behavior_escalation' — recent alerts, limit declines or unauthorized
+--                           entries pushed the customer up a tier
+--   'review_downgrade'    — an EDD review found the behavior had subsided
+-- edd_review: the customer's periodic review. Each customer has one
+-- scheduled review, due after their tier's review interval; moving up a
+-- tier brings it forward.
+ALTER TABLE customer_risk_score ADD COLUMN base_risk TEXT;
+
+CREATE TABLE IF NOT EXISTS risk_tier_transition (
+    run_id TEXT NOT NULL REFERENCES run(run_id),
+    transition_id INTEGER PRIMARY KEY AUTOINCREMENT,
+    tick INTEGER NOT NULL,
+    customer_id TEXT NOT NULL,
+    from_tier TEXT NOT NULL,
+    to_tier TEXT NOT NULL,
+    reason TEXT NOT NULL,
+    behavior_risk REAL NOT NULL
+);
+CREATE TABLE IF NOT EXISTS edd_review (
+    run_id TEXT NOT NULL REFERENCES run(run_id),
+    review_id TEXT NOT NULL,
+    customer_id TEXT NOT NULL,
+    tier TEXT NOT NULL,
+    -- tier when the review was last scheduled
+    review_trigger TEXT NOT NULL,
+    -- 'scheduled', 'tier_change'
+    due_tick INTEGER NOT NULL,
+    completed_tick INTEGER,
+    outcome TEXT,
+    -- 'retained', 'downgraded'
+    cost REAL NOT NULL DEFAULT 0,
+    PRIMARY KEY (run_id, review_id)
+);
+CREATE INDEX IF NOT EXISTS idx_edd_review_due ON edd_review(run_id, completed_tick, due_tick);
+CREATE INDEX IF NOT EXISTS idx_risk_tier_transition_customer ON risk_tier_transition(run_id, customer_id);
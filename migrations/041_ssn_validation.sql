@@ -0,0 +1,25 @@
+-- Phase 4.14: SSN validation against issuance tables and the death master file
+--
+-- death_master_file: SSNs reported deceased, as the SSA publishes them.
+-- ssn_validation: the onboarding check of each customer's SSN. issues is
+-- a comma-separated list of:
+--   'invalid_area', 'invalid_group', 'invalid_serial' — never issued
+--   'issued_before_birth'    — issuance era ended before the birth year
+--   'issued_after_adulthood' — post-2011 area on a customer adult by 2011
+--   'deceased'               — on the death master file
+-- synthetic_score is the validation's contribution to the customer's
+-- synthetic-identity score.
+CREATE TABLE IF NOT EXISTS death_master_file (
+    run_id TEXT NOT NULL REFERENCES run(run_id),
+    ssn TEXT NOT NULL,
+    death_year INTEGER NOT NULL,
+    PRIMARY KEY (run_id, ssn)
+);
+CREATE TABLE IF NOT EXISTS ssn_validation (
+    run_id TEXT NOT NULL REFERENCES run(run_id),
+    customer_id TEXT NOT NULL,
+    tick INTEGER NOT NULL,
+    issues TEXT NOT NULL DEFAULT '',
+    synthetic_score REAL NOT NULL DEFAULT 0,
+    PRIMARY KEY (run_id, customer_id)
+);
@@ -0,0 +1,19 @@
+-- Phase 4.15: Identity verification vendor checks
+--
+-- idv_check: one vendor check per applicant at onboarding. Each element
+-- comes back 'match', 'partial' or 'no_match'; any 'no_match' declines
+-- the applicant, who is never onboarded.
+CREATE TABLE IF NOT EXISTS idv_check (
+    run_id TEXT NOT NULL REFERENCES run(run_id),
+    customer_id TEXT NOT NULL,
+    tick INTEGER NOT NULL,
+    vendor TEXT NOT NULL,
+    name_match TEXT NOT NULL,
+    ssn_match TEXT NOT NULL,
+    dob_match TEXT NOT NULL,
+    address_match TEXT NOT NULL,
+    decision TEXT NOT NULL,
+    -- 'pass', 'decline'
+    cost REAL NOT NULL,
+    PRIMARY KEY (run_id, customer_id)
+);
@@ -0,0 +1,34 @@
+-- Phase 4.16: Configurable SAR decision thresholds
+--
+-- sar_decision: what happened to each alert the SAR threshold was applied
+-- to. 'filed' alerts were reportable, 'defensive' ones were filed below
+-- the reportable score, and 'missed' ones were reportable but left
+-- unfiled past the 30-day deadline.
+-- sar_quality_metrics: monthly volume and quality of filing decisions.
+-- sar_quality is the share of filings that were reportable, NULL when
+-- nothing was filed.
+CREATE TABLE IF NOT EXISTS sar_decision (
+    run_id TEXT NOT NULL REFERENCES run(run_id),
+    alert_id TEXT NOT NULL,
+    tick INTEGER NOT NULL,
+    customer_id TEXT NOT NULL,
+    alert_score REAL NOT NULL,
+    threshold REAL NOT NULL,
+    decision TEXT NOT NULL,
+    -- 'filed', 'defensive', 'missed'
+    sar_id TEXT,
+    cost REAL NOT NULL DEFAULT 0,
+    PRIMARY KEY (run_id, alert_id)
+);
+CREATE INDEX IF NOT EXISTS idx_sar_decision_tick ON sar_decision (run_id, tick);
+CREATE TABLE IF NOT EXISTS sar_quality_metrics (
+    run_id TEXT NOT NULL REFERENCES run(run_id),
+    tick INTEGER NOT NULL,
+    threshold REAL NOT NULL,
+    sars_filed INTEGER NOT NULL,
+    defensive_filings INTEGER NOT NULL,
+    missed_filings INTEGER NOT NULL,
+    sar_quality REAL,
+    filing_cost REAL NOT NULL,
+    PRIMARY KEY (run_id, tick)
+);
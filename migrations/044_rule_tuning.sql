@@ -0,0 +1,35 @@
+-- Phase 4.17: Alert suppression and tuning review workflow
+--
+-- tuning_review: a documented review of a proposed change to one
+-- monitoring rule. It is 'in_review' until due_tick, then 'approved'.
+-- rule_tuning: a change applied to a monitoring rule. change is one of
+-- 'suppress', 'raise_threshold' or 'exclude_customer'; a 'restore'
+-- deactivates the rule's earlier changes. documented is 1 when the change
+-- cited an approved review of the same rule.
+CREATE TABLE IF NOT EXISTS tuning_review (
+    run_id TEXT NOT NULL REFERENCES run(run_id),
+    review_id TEXT NOT NULL,
+    rule_id TEXT NOT NULL,
+    rationale TEXT NOT NULL,
+    opened_tick INTEGER NOT NULL,
+    due_tick INTEGER NOT NULL,
+    status TEXT NOT NULL DEFAULT 'in_review',
+    -- 'in_review', 'approved'
+    completed_tick INTEGER,
+    cost REAL NOT NULL DEFAULT 0,
+    PRIMARY KEY (run_id, review_id)
+);
+CREATE TABLE IF NOT EXISTS rule_tuning (
+    run_id TEXT NOT NULL REFERENCES run(run_id),
+    tuning_id TEXT NOT NULL,
+    tick INTEGER NOT NULL,
+    rule_id TEXT NOT NULL,
+    change TEXT NOT NULL,
+    threshold_multiplier REAL NOT NULL DEFAULT 1.0,
+    customer_id TEXT,
+    review_id TEXT,
+    documented INTEGER NOT NULL,
+    active INTEGER NOT NULL DEFAULT 1,
+    PRIMARY KEY (run_id, tuning_id)
+);
+CREATE INDEX IF NOT EXISTS idx_rule_tuning_active ON rule_tuning (run_id, active, rule_id);
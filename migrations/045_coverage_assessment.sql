@@ -0,0 +1,18 @@
+-- Phase 4.18: Scenario coverage assessment
+--
+-- coverage_assessment: one row per typology per assessment. rules lists
+-- the active rules meant to catch the typology (comma-separated, empty
+-- when none), covered is 1 when there is at least one, and occurrences
+-- is the ground-truth count of the typology running in the window. The
+-- player only ever sees the rule inventory; this table is for the
+-- post-run analysis export.
+CREATE TABLE IF NOT EXISTS coverage_assessment (
+    run_id TEXT NOT NULL REFERENCES run(run_id),
+    tick INTEGER NOT NULL,
+    typology TEXT NOT NULL,
+    -- 'structuring', 'funnel', 'tbml', 'mule', 'ato'
+    rules TEXT NOT NULL DEFAULT '',
+    covered INTEGER NOT NULL,
+    occurrences INTEGER NOT NULL DEFAULT 0,
+    PRIMARY KEY (run_id, tick, typology)
+);
@@ -0,0 +1,16 @@
+-- Phase 4.24: Run provenance
+--
+-- How a run was produced, captured when it is created. cli_args and
+-- features are JSON arrays and host a JSON object; scenario_hash is the
+-- SHA-256 of the data directory the run loaded. All are NULL for runs
+-- created without provenance (tests, the C ABI, experiments).
+ALTER TABLE run
+ADD COLUMN cli_args TEXT;
+ALTER TABLE run
+ADD COLUMN scenario_hash TEXT;
+ALTER TABLE run
+ADD COLUMN git_commit TEXT;
+ALTER TABLE run
+ADD COLUMN features TEXT;
+ALTER TABLE run
+ADD COLUMN host TEXT;
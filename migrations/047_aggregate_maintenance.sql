@@ -0,0 +1,47 @@
+-- Phase 4.27: Incremental aggregate maintenance
+--
+-- segment_activity holds one row per segment per completed tick with the
+-- period-summable inputs to segment P&L and churn aggregates. Quarter-end
+-- and churn reporting sum these rows instead of rescanning the
+-- transaction, customer, complaint and offer tables.
+CREATE TABLE IF NOT EXISTS segment_activity (
+    run_id               TEXT    NOT NULL,
+    tick                 INTEGER NOT NULL,
+    segment              TEXT    NOT NULL,
+    fee_income           REAL    NOT NULL DEFAULT 0.0,
+    new_customers        INTEGER NOT NULL DEFAULT 0,
+    complaints_standard  INTEGER NOT NULL DEFAULT 0,
+    complaints_high      INTEGER NOT NULL DEFAULT 0,
+    complaints_urgent    INTEGER NOT NULL DEFAULT 0,
+    retention_cost       REAL    NOT NULL DEFAULT 0.0,
+    churned_customers    INTEGER NOT NULL DEFAULT 0,
+    fee_driven_churn     INTEGER NOT NULL DEFAULT 0,
+    service_driven_churn INTEGER NOT NULL DEFAULT 0,
+    life_event_churn     INTEGER NOT NULL DEFAULT 0,
+    PRIMARY KEY (run_id, tick, segment)
+);
+
+-- Running count of open complaints and the sum of their open ticks,
+-- kept current by insert_complaint and close_complaint.
+CREATE TABLE IF NOT EXISTS complaint_backlog (
+    run_id          TEXT    PRIMARY KEY,
+    open_count      INTEGER NOT NULL DEFAULT 0,
+    opened_tick_sum INTEGER NOT NULL DEFAULT 0
+);
+INSERT OR IGNORE INTO complaint_backlog (run_id, open_count, opened_tick_sum)
+SELECT run_id, COUNT(*), SUM(tick_opened)
+FROM complaint
+WHERE status = 'open'
+GROUP BY run_id;
+
+-- Folding a single tick must not scan whole tables
+CREATE INDEX IF NOT EXISTS idx_customer_open_tick
+    ON customer (run_id, open_tick);
+CREATE INDEX IF NOT EXISTS idx_customer_close_tick
+    ON customer (run_id, close_tick);
+CREATE INDEX IF NOT EXISTS idx_complaint_tick_opened
+    ON complaint (run_id, tick_opened);
+CREATE INDEX IF NOT EXISTS idx_customer_offer_tick_paid
+    ON customer_offer (run_id, tick_paid);
+CREATE INDEX IF NOT EXISTS idx_churn_cohort_tick
+    ON churn_cohort (run_id, tick_churned);
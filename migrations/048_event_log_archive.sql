@@ -0,0 +1,24 @@
+-- Phase 4.28: Event log retention tiers
+--
+-- Ticks older than the retention window move out of event_log into one
+-- archive row per tick: the tick's entries as a gzip-compressed JSON
+-- array, in append order. event_log_archive_count keeps per-type counts
+-- so range counts never need to decompress.
+CREATE TABLE IF NOT EXISTS event_log_archive (
+    run_id      TEXT    NOT NULL,
+    tick        INTEGER NOT NULL,
+    event_count INTEGER NOT NULL,
+    events      BLOB    NOT NULL,
+    PRIMARY KEY (run_id, tick),
+    FOREIGN KEY (run_id) REFERENCES run(run_id)
+);
+
+CREATE TABLE IF NOT EXISTS event_log_archive_count (
+    run_id      TEXT    NOT NULL,
+    tick        INTEGER NOT NULL,
+    event_type  TEXT    NOT NULL,
+    event_count INTEGER NOT NULL,
+    PRIMARY KEY (run_id, tick, event_type)
+);
+CREATE INDEX IF NOT EXISTS idx_event_log_archive_count_type
+    ON event_log_archive_count (run_id, event_type, tick);
@@ -0,0 +1,22 @@
+-- Phase 4.29: Complaint SLA policy
+--
+-- complaint.channel: how the complaint reached the bank ('phone',
+-- 'branch', 'online', 'regulator'); SLA policy can differ by channel.
+-- complaint_sla_policy: policy changes the player has scheduled. A NULL
+-- priority, product or channel matches every value. Each applies to
+-- complaints opened from effective_tick onward.
+ALTER TABLE complaint
+ADD COLUMN channel TEXT NOT NULL DEFAULT 'phone';
+
+CREATE TABLE IF NOT EXISTS complaint_sla_policy (
+    run_id         TEXT    NOT NULL REFERENCES run(run_id),
+    policy_id      INTEGER PRIMARY KEY AUTOINCREMENT,
+    tick           INTEGER NOT NULL,
+    priority       TEXT,
+    product        TEXT,
+    channel        TEXT,
+    resolve_days   INTEGER NOT NULL,
+    effective_tick INTEGER NOT NULL
+);
+CREATE INDEX IF NOT EXISTS idx_complaint_sla_policy_run
+    ON complaint_sla_policy (run_id, effective_tick);
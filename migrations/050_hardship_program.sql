@@ -0,0 +1,41 @@
+-- Phase 4.30: Customer hardship program
+--
+-- hardship_enrollment: a customer who took up the program after a
+-- qualifying life event, enrolled from tick_enrolled until tick_expires.
+-- hardship_fee_waiver: each fee waived for an enrolled customer — revenue
+-- the bank gave up. Both are folded into segment_activity per tick and
+-- reported quarterly in segment_pnl.
+CREATE TABLE IF NOT EXISTS hardship_enrollment (
+    run_id        TEXT    NOT NULL REFERENCES run(run_id),
+    customer_id   TEXT    NOT NULL,
+    tick_enrolled INTEGER NOT NULL,
+    tick_expires  INTEGER NOT NULL,
+    life_event    TEXT    NOT NULL,
+    PRIMARY KEY (run_id, customer_id, tick_enrolled)
+);
+CREATE INDEX IF NOT EXISTS idx_hardship_enrollment_tick
+    ON hardship_enrollment (run_id, tick_enrolled);
+
+CREATE TABLE IF NOT EXISTS hardship_fee_waiver (
+    run_id      TEXT    NOT NULL REFERENCES run(run_id),
+    waiver_id   INTEGER PRIMARY KEY AUTOINCREMENT,
+    tick        INTEGER NOT NULL,
+    customer_id TEXT    NOT NULL,
+    account_id  TEXT    NOT NULL,
+    fee_type    TEXT    NOT NULL,
+    amount      REAL    NOT NULL
+);
+CREATE INDEX IF NOT EXISTS idx_hardship_fee_waiver_tick
+    ON hardship_fee_waiver (run_id, tick);
+
+ALTER TABLE segment_activity
+ADD COLUMN hardship_enrollments INTEGER NOT NULL DEFAULT 0;
+ALTER TABLE segment_activity
+ADD COLUMN hardship_fees_waived REAL NOT NULL DEFAULT 0.0;
+
+ALTER TABLE segment_pnl
+ADD COLUMN hardship_enrollments INTEGER NOT NULL DEFAULT 0;
+ALTER TABLE segment_pnl
+ADD COLUMN hardship_active INTEGER NOT NULL DEFAULT 0;
+ALTER TABLE segment_pnl
+ADD COLUMN hardship_fees_waived REAL NOT NULL DEFAULT 0.0;
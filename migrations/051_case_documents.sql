@@ -0,0 +1,24 @@
+-- Phase 4.31: Supporting documents for disputes and complaints
+--
+-- case_document: one row per document a case requires, generated when the
+-- case is filed. status is 'on_file' with the document's structured fields
+-- in content (JSON), or 'missing' with content NULL when it was never
+-- collected. Exams count cases with missing documents.
+CREATE TABLE IF NOT EXISTS case_document (
+    run_id       TEXT    NOT NULL REFERENCES run(run_id),
+    document_id  TEXT    NOT NULL,
+    case_type    TEXT    NOT NULL,
+    -- 'dispute' | 'complaint'
+    case_id      TEXT    NOT NULL,
+    customer_id  TEXT    NOT NULL,
+    doc_type     TEXT    NOT NULL,
+    tick_created INTEGER NOT NULL,
+    status       TEXT    NOT NULL,
+    -- 'on_file' | 'missing'
+    content      TEXT,
+    PRIMARY KEY (run_id, document_id)
+);
+CREATE INDEX IF NOT EXISTS idx_case_document_case
+    ON case_document (run_id, case_type, case_id);
+CREATE INDEX IF NOT EXISTS idx_case_document_tick
+    ON case_document (run_id, tick_created, status);
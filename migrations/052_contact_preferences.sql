@@ -0,0 +1,41 @@
+-- Phase 4.32: Contact preferences and communication fatigue
+--
+-- contact_preference: set once per customer at onboarding — whether they
+-- asked not to be contacted and the channel they prefer.
+-- customer_contact: every outreach contact made (offers, surveys,
+-- collections); fatigue and frequency caps count these over a trailing
+-- window. do_not_contact_violated marks contacts made against a
+-- customer's preference.
+-- contact_policy: the player's suppression policy; the latest row wins.
+CREATE TABLE IF NOT EXISTS contact_preference (
+    run_id            TEXT    NOT NULL REFERENCES run(run_id),
+    customer_id       TEXT    NOT NULL,
+    do_not_contact    INTEGER NOT NULL DEFAULT 0,
+    preferred_channel TEXT    NOT NULL,
+    -- 'email' | 'sms' | 'phone' | 'mail'
+    tick_set          INTEGER NOT NULL,
+    PRIMARY KEY (run_id, customer_id)
+);
+
+CREATE TABLE IF NOT EXISTS customer_contact (
+    run_id                  TEXT    NOT NULL REFERENCES run(run_id),
+    contact_id              INTEGER PRIMARY KEY AUTOINCREMENT,
+    tick                    INTEGER NOT NULL,
+    customer_id             TEXT    NOT NULL,
+    campaign_type           TEXT    NOT NULL,
+    -- 'offer' | 'survey' | 'collections'
+    campaign_id             TEXT    NOT NULL,
+    channel                 TEXT    NOT NULL,
+    responded               INTEGER NOT NULL DEFAULT 0,
+    do_not_contact_violated INTEGER NOT NULL DEFAULT 0
+);
+CREATE INDEX IF NOT EXISTS idx_customer_contact_customer
+    ON customer_contact (run_id, customer_id, tick);
+
+CREATE TABLE IF NOT EXISTS contact_policy (
+    run_id                  TEXT    NOT NULL REFERENCES run(run_id),
+    tick                    INTEGER NOT NULL,
+    honor_do_not_contact    INTEGER NOT NULL,
+    max_contacts_per_window INTEGER,
+    PRIMARY KEY (run_id, tick)
+);
@@ -0,0 +1,27 @@
+-- Phase 4.33: Satisfaction dynamics
+--
+-- satisfaction_gesture: every goodwill gesture (a satisfaction gain from
+-- resolving a complaint), with the gain asked for and the gain applied
+-- after diminishing returns on earlier gestures.
+-- satisfaction_scar: a severe incident that permanently lowers the
+-- baseline a customer's satisfaction reverts to.
+CREATE TABLE IF NOT EXISTS satisfaction_gesture (
+    run_id          TEXT    NOT NULL REFERENCES run(run_id),
+    gesture_id      INTEGER PRIMARY KEY AUTOINCREMENT,
+    tick            INTEGER NOT NULL,
+    customer_id     TEXT    NOT NULL,
+    source          TEXT    NOT NULL,
+    requested_delta REAL    NOT NULL,
+    applied_delta   REAL    NOT NULL
+);
+CREATE INDEX IF NOT EXISTS idx_satisfaction_gesture_customer
+    ON satisfaction_gesture (run_id, customer_id, tick);
+
+CREATE TABLE IF NOT EXISTS satisfaction_scar (
+    run_id       TEXT    NOT NULL REFERENCES run(run_id),
+    customer_id  TEXT    NOT NULL,
+    complaint_id TEXT    NOT NULL,
+    tick         INTEGER NOT NULL,
+    severity     REAL    NOT NULL,
+    PRIMARY KEY (run_id, customer_id, complaint_id)
+);
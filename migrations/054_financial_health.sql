@@ -0,0 +1,40 @@
+-- Phase 4.34: Financial health and credit cross-sell
+--
+-- customer_health: monthly financial health score per customer with the
+-- components it was built from.
+-- credit_line: a credit offer taken up; a line defaults at most once.
+-- credit_health_policy: the player's minimum health score for credit
+-- offers; the latest row wins.
+CREATE TABLE IF NOT EXISTS customer_health (
+    run_id             TEXT    NOT NULL REFERENCES run(run_id),
+    customer_id        TEXT    NOT NULL,
+    tick               INTEGER NOT NULL,
+    score              REAL    NOT NULL,
+    balance_volatility REAL    NOT NULL,
+    overdraft_count    INTEGER NOT NULL,
+    payroll_stability  REAL    NOT NULL,
+    PRIMARY KEY (run_id, customer_id, tick)
+);
+
+CREATE TABLE IF NOT EXISTS credit_line (
+    run_id         TEXT    NOT NULL REFERENCES run(run_id),
+    customer_id    TEXT    NOT NULL,
+    offer_id       TEXT    NOT NULL,
+    tick_opened    INTEGER NOT NULL,
+    credit_limit   REAL    NOT NULL,
+    health_at_open REAL    NOT NULL,
+    status         TEXT    NOT NULL DEFAULT 'open',
+    -- 'open' | 'defaulted'
+    tick_defaulted INTEGER,
+    loss_amount    REAL    NOT NULL DEFAULT 0.0,
+    PRIMARY KEY (run_id, customer_id, offer_id)
+);
+CREATE INDEX IF NOT EXISTS idx_credit_line_default
+    ON credit_line (run_id, status, tick_defaulted);
+
+CREATE TABLE IF NOT EXISTS credit_health_policy (
+    run_id    TEXT    NOT NULL REFERENCES run(run_id),
+    tick      INTEGER NOT NULL,
+    min_score REAL    NOT NULL,
+    PRIMARY KEY (run_id, tick)
+);
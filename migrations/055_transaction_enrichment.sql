@@ -0,0 +1,31 @@
+-- Phase 4.35: Transaction categorization enrichment
+--
+-- merchant_category: each merchant counterparty's true spend category and
+-- the category the enrichment labelled it with; they differ when the
+-- enrichment got it wrong.
+-- HIGH_RISK_MCC: gambling and crypto spend intensity, on enriched
+-- categories.
+-- customer_health.high_risk_share: the high-risk spend share behind each
+-- health score.
+CREATE TABLE IF NOT EXISTS merchant_category (
+    run_id             TEXT    NOT NULL REFERENCES run(run_id),
+    counterparty       TEXT    NOT NULL,
+    true_category      TEXT    NOT NULL,
+    enriched_category  TEXT    NOT NULL,
+    tick_first_seen    INTEGER NOT NULL,
+    PRIMARY KEY (run_id, counterparty)
+);
+CREATE INDEX IF NOT EXISTS idx_merchant_category_enriched
+    ON merchant_category (run_id, enriched_category);
+
+-- Counterparty lookups for the enrichment pass and spend-by-category joins
+CREATE INDEX IF NOT EXISTS idx_txn_counterparty
+    ON transactions (run_id, counterparty);
+
+INSERT OR IGNORE INTO transaction_monitoring_rule (
+    rule_id, rule_name, rule_type, threshold_amount, threshold_count,
+    lookback_days, base_alert_score, auto_file_sar, enabled
+) VALUES
+    ('HIGH_RISK_MCC', 'High-Risk Merchant Category Intensity', 'category_intensity', 250.0, 1, 30, 60.0, 0, 1);
+
+ALTER TABLE customer_health ADD COLUMN high_risk_share REAL NOT NULL DEFAULT 0.0;
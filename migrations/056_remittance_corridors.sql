@@ -0,0 +1,44 @@
+-- Phase 4.36: Remittance corridors
+--
+-- remittance_profile: each international customer's remittance behaviour,
+-- decided once. pattern is ground truth: 'legitimate' family remittances
+-- home, 'layering' funds passed through the account, or 'none'.
+-- remittance_transfer: every cross-border wire sent down a corridor, and
+-- the inbound wires that feed layering.
+-- REMIT_LAYERING: fan-out across beneficiaries or corridors while passing
+-- most credits straight back out.
+CREATE TABLE IF NOT EXISTS remittance_profile (
+    run_id         TEXT    NOT NULL REFERENCES run(run_id),
+    customer_id    TEXT    NOT NULL,
+    account_id     TEXT    NOT NULL,
+    corridor       TEXT    NOT NULL,
+    pattern        TEXT    NOT NULL,
+    -- 'legitimate' | 'layering' | 'none'
+    amount         REAL    NOT NULL,
+    interval_ticks INTEGER NOT NULL,
+    beneficiaries  INTEGER NOT NULL,
+    next_tick      INTEGER NOT NULL,
+    tick_enrolled  INTEGER NOT NULL,
+    PRIMARY KEY (run_id, customer_id)
+);
+
+CREATE TABLE IF NOT EXISTS remittance_transfer (
+    txn_id       TEXT    PRIMARY KEY,
+    run_id       TEXT    NOT NULL REFERENCES run(run_id),
+    customer_id  TEXT    NOT NULL,
+    account_id   TEXT    NOT NULL,
+    tick         INTEGER NOT NULL,
+    direction    TEXT    NOT NULL,
+    -- 'outbound' | 'inbound'
+    corridor     TEXT    NOT NULL,
+    counterparty TEXT    NOT NULL,
+    amount       REAL    NOT NULL
+);
+CREATE INDEX IF NOT EXISTS idx_remittance_transfer_tick
+    ON remittance_transfer (run_id, tick);
+
+INSERT OR IGNORE INTO transaction_monitoring_rule (
+    rule_id, rule_name, rule_type, threshold_amount, threshold_count,
+    lookback_days, base_alert_score, auto_file_sar, enabled
+) VALUES
+    ('REMIT_LAYERING', 'Remittance Layering', 'remittance_layering', NULL, 4, 30, 55.0, 0, 1);
@@ -0,0 +1,46 @@
+-- Phase 4.37: Visa expiry and documentation refresh
+--
+-- visa_document: each visa holder's documentation timeline. renews and
+-- planned_submit_tick are ground truth for the customer's behaviour; the
+-- rest is what the bank sees. status: 'valid' | 'refresh_requested' |
+-- 'submitted'. A document is lapsed from lapsed_tick until renewed, and
+-- restriction is the account restriction in force on the customer.
+-- visa_refresh_item: one documentation refresh work item per expiry.
+-- visa_restriction_policy: the player's restriction policy; the latest
+-- row wins.
+CREATE TABLE IF NOT EXISTS visa_document (
+    run_id              TEXT    NOT NULL REFERENCES run(run_id),
+    customer_id         TEXT    NOT NULL,
+    visa_status         TEXT    NOT NULL,
+    expiry_tick         INTEGER NOT NULL,
+    renews              INTEGER NOT NULL,
+    planned_submit_tick INTEGER,
+    status              TEXT    NOT NULL DEFAULT 'valid',
+    submitted_tick      INTEGER,
+    lapsed_tick         INTEGER,
+    restriction         TEXT,
+    -- NULL | 'outbound' | 'full'
+    restricted_tick     INTEGER,
+    PRIMARY KEY (run_id, customer_id)
+);
+
+CREATE TABLE IF NOT EXISTS visa_refresh_item (
+    item_id        TEXT    PRIMARY KEY,
+    run_id         TEXT    NOT NULL REFERENCES run(run_id),
+    customer_id    TEXT    NOT NULL,
+    opened_tick    INTEGER NOT NULL,
+    expiry_tick    INTEGER NOT NULL,
+    submitted_tick INTEGER,
+    completed_tick INTEGER,
+    cost           REAL    NOT NULL DEFAULT 0.0
+);
+CREATE INDEX IF NOT EXISTS idx_visa_refresh_item_customer
+    ON visa_refresh_item (run_id, customer_id);
+
+CREATE TABLE IF NOT EXISTS visa_restriction_policy (
+    run_id      TEXT    NOT NULL REFERENCES run(run_id),
+    tick        INTEGER NOT NULL,
+    level       TEXT    NOT NULL,
+    grace_ticks INTEGER NOT NULL,
+    PRIMARY KEY (run_id, tick)
+);
@@ -0,0 +1,43 @@
+-- Phase 4.38: Adverse media screening
+--
+-- adverse_media_article: the news feed. subject_kind and subject_id are
+-- ground truth — who the story is really about: 'customer' (subject_id a
+-- customer_id), 'counterparty' (a counterparty a customer has paid) or
+-- 'stranger' (NULL). The name and details are what the story prints;
+-- country and birth_year are NULL when it leaves them out.
+-- adverse_media_match: every candidate screening scored against a story.
+-- entity_id is the customer or counterparty screened and customer_id the
+-- customer whose risk it touches. true_match is ground truth.
+CREATE TABLE IF NOT EXISTS adverse_media_article (
+    article_id   TEXT    PRIMARY KEY,
+    run_id       TEXT    NOT NULL REFERENCES run(run_id),
+    tick         INTEGER NOT NULL,
+    subject_kind TEXT    NOT NULL,
+    subject_id   TEXT,
+    subject_type TEXT    NOT NULL,
+    -- 'individual' | 'business'
+    name         TEXT    NOT NULL,
+    country      TEXT,
+    birth_year   INTEGER,
+    category     TEXT    NOT NULL,
+    severity     REAL    NOT NULL
+);
+CREATE INDEX IF NOT EXISTS idx_adverse_media_article_tick
+    ON adverse_media_article (run_id, tick);
+
+CREATE TABLE IF NOT EXISTS adverse_media_match (
+    run_id        TEXT    NOT NULL REFERENCES run(run_id),
+    article_id    TEXT    NOT NULL,
+    entity_kind   TEXT    NOT NULL,
+    -- 'customer' | 'counterparty'
+    entity_id     TEXT    NOT NULL,
+    customer_id   TEXT    NOT NULL,
+    tick          INTEGER NOT NULL,
+    match_score   REAL    NOT NULL,
+    escalated     INTEGER NOT NULL,
+    edd_triggered INTEGER NOT NULL DEFAULT 0,
+    true_match    INTEGER NOT NULL,
+    PRIMARY KEY (run_id, article_id, entity_id, customer_id)
+);
+CREATE INDEX IF NOT EXISTS idx_adverse_media_match_customer
+    ON adverse_media_match (run_id, customer_id);
@@ -0,0 +1,66 @@
+-- Phase 4.39: Correspondent banking
+--
+-- correspondent_relationship: each respondent bank clearing wires through
+-- us. nested_banks is ground truth; nested_disclosed records whether a
+-- due diligence questionnaire has admitted to them. status: 'active' |
+-- 'suspended' (due diligence overdue; wires are rejected).
+-- Each respondent is also booked in customer with segment and status
+-- 'correspondent' so CORR_NESTED alerts have a customer to land on.
+-- correspondent_wire: every wire a respondent sends through us.
+-- ordering_institution and originator_country are what the payment
+-- message shows; nested_bank is ground truth and NULL for the
+-- respondent's own customers. Rejected wires carry no fee.
+-- correspondent_dd_review: one due diligence refresh per cycle.
+-- responds_tick is ground truth (NULL when the respondent never answers);
+-- outcome: 'satisfactory' | 'nested_disclosed' | 'overdue'.
+-- CORR_NESTED: third-party ordering institutions, or more originators than
+-- the respondent declared, in its aggregated wires.
+CREATE TABLE IF NOT EXISTS correspondent_relationship (
+    run_id               TEXT    NOT NULL REFERENCES run(run_id),
+    bank_id              TEXT    NOT NULL,
+    country              TEXT    NOT NULL,
+    risk_rating          TEXT    NOT NULL,
+    declared_originators INTEGER NOT NULL,
+    nested_banks         INTEGER NOT NULL,
+    nested_disclosed     INTEGER NOT NULL DEFAULT 0,
+    status               TEXT    NOT NULL DEFAULT 'active',
+    next_dd_tick         INTEGER NOT NULL,
+    opened_tick          INTEGER NOT NULL,
+    PRIMARY KEY (run_id, bank_id)
+);
+
+CREATE TABLE IF NOT EXISTS correspondent_wire (
+    wire_id              TEXT    PRIMARY KEY,
+    run_id               TEXT    NOT NULL REFERENCES run(run_id),
+    bank_id              TEXT    NOT NULL,
+    tick                 INTEGER NOT NULL,
+    amount               REAL    NOT NULL,
+    originator           TEXT    NOT NULL,
+    ordering_institution TEXT    NOT NULL,
+    originator_country   TEXT    NOT NULL,
+    nested_bank          TEXT,
+    status               TEXT    NOT NULL,
+    -- 'cleared' | 'rejected'
+    fee                  REAL    NOT NULL DEFAULT 0.0
+);
+CREATE INDEX IF NOT EXISTS idx_correspondent_wire_bank
+    ON correspondent_wire (run_id, bank_id, tick);
+
+CREATE TABLE IF NOT EXISTS correspondent_dd_review (
+    review_id      TEXT    PRIMARY KEY,
+    run_id         TEXT    NOT NULL REFERENCES run(run_id),
+    bank_id        TEXT    NOT NULL,
+    requested_tick INTEGER NOT NULL,
+    responds_tick  INTEGER,
+    completed_tick INTEGER,
+    outcome        TEXT,
+    cost           REAL    NOT NULL DEFAULT 0.0
+);
+CREATE INDEX IF NOT EXISTS idx_correspondent_dd_review_bank
+    ON correspondent_dd_review (run_id, bank_id);
+
+INSERT OR IGNORE INTO transaction_monitoring_rule (
+    rule_id, rule_name, rule_type, threshold_amount, threshold_count,
+    lookback_days, base_alert_score, auto_file_sar, enabled
+) VALUES
+    ('CORR_NESTED', 'Correspondent Nested Activity', 'correspondent_nested', NULL, NULL, 30, 50.0, 0, 1);
@@ -0,0 +1,77 @@
+-- Phase 4.40: Cross-border fan-in/fan-out
+--
+-- cross_border_profile: each international customer's cross-border
+-- behaviour, decided once. pattern is ground truth: 'funnel' (collects
+-- wires from many foreign senders and disperses them domestically),
+-- 'inbound' (occasional support from home, some passed on to family) or
+-- 'none'. pending and disperse_tick hold a funnel's collected funds until
+-- they go back out.
+-- cross_border_transfer: every inbound foreign wire and every domestic
+-- transfer out that the typology books. country is 'US' for domestic.
+-- cross_border_finding: one XB_FUNNEL hit over a rolling window, with the
+-- window's totals as the SAR narrative quotes them. high_risk_countries
+-- lists source countries on the high-risk jurisdiction list.
+-- cross_border_evidence: the transactions behind a finding.
+CREATE TABLE IF NOT EXISTS cross_border_profile (
+    run_id        TEXT    NOT NULL REFERENCES run(run_id),
+    customer_id   TEXT    NOT NULL,
+    account_id    TEXT    NOT NULL,
+    home_country  TEXT    NOT NULL,
+    pattern       TEXT    NOT NULL,
+    -- 'funnel' | 'inbound' | 'none'
+    next_tick     INTEGER NOT NULL,
+    disperse_tick INTEGER,
+    pending       REAL    NOT NULL DEFAULT 0.0,
+    tick_enrolled INTEGER NOT NULL,
+    PRIMARY KEY (run_id, customer_id)
+);
+
+CREATE TABLE IF NOT EXISTS cross_border_transfer (
+    txn_id       TEXT    PRIMARY KEY,
+    run_id       TEXT    NOT NULL REFERENCES run(run_id),
+    customer_id  TEXT    NOT NULL,
+    account_id   TEXT    NOT NULL,
+    tick         INTEGER NOT NULL,
+    direction    TEXT    NOT NULL,
+    -- 'inbound' | 'domestic'
+    counterparty TEXT    NOT NULL,
+    country      TEXT    NOT NULL,
+    amount       REAL    NOT NULL
+);
+CREATE INDEX IF NOT EXISTS idx_cross_border_transfer_tick
+    ON cross_border_transfer (run_id, tick);
+
+CREATE TABLE IF NOT EXISTS cross_border_finding (
+    finding_id          TEXT    PRIMARY KEY,
+    run_id              TEXT    NOT NULL REFERENCES run(run_id),
+    customer_id         TEXT    NOT NULL,
+    alert_id            TEXT    NOT NULL,
+    tick                INTEGER NOT NULL,
+    window_start        INTEGER NOT NULL,
+    senders             INTEGER NOT NULL,
+    countries           TEXT    NOT NULL,
+    high_risk_countries TEXT    NOT NULL,
+    inflow              REAL    NOT NULL,
+    payees              INTEGER NOT NULL,
+    outflow             REAL    NOT NULL,
+    alert_score         REAL    NOT NULL
+);
+CREATE INDEX IF NOT EXISTS idx_cross_border_finding_alert
+    ON cross_border_finding (run_id, alert_id);
+
+CREATE TABLE IF NOT EXISTS cross_border_evidence (
+    finding_id   TEXT    NOT NULL REFERENCES cross_border_finding(finding_id),
+    txn_id       TEXT    NOT NULL,
+    tick         INTEGER NOT NULL,
+    direction    TEXT    NOT NULL,
+    counterparty TEXT    NOT NULL,
+    country      TEXT    NOT NULL,
+    amount       REAL    NOT NULL,
+    PRIMARY KEY (finding_id, txn_id)
+);
+
+INSERT OR IGNORE INTO transaction_monitoring_rule (
+    rule_id, rule_name, rule_type, threshold_amount, threshold_count,
+    lookback_days, base_alert_score, auto_file_sar, enabled
+) VALUES
+    ('XB_FUNNEL', 'Cross-Border Funnel', 'cross_border_funnel', NULL, 5, 30, 55.0, 0, 1);
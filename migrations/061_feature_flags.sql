@@ -0,0 +1,7 @@
+-- Phase 4.42: Feature flags
+--
+-- The scenario's feature flags as a JSON object, recorded when the
+-- engine is built so an ablation run can be reproduced. NULL for runs
+-- stored before flags existed.
+ALTER TABLE run
+ADD COLUMN feature_flags TEXT;
@@ -0,0 +1,17 @@
+-- Phase 4.46: Command authorization
+--
+-- One row per command a role gate applied to, allowed or refused: who
+-- sent it, the role it needed and why. Commands no gate covers are not
+-- logged here.
+CREATE TABLE IF NOT EXISTS command_authorization (
+    run_id        TEXT    NOT NULL REFERENCES run(run_id),
+    audit_id      INTEGER PRIMARY KEY AUTOINCREMENT,
+    tick          INTEGER NOT NULL,
+    command       TEXT    NOT NULL,
+    role          TEXT    NOT NULL,
+    required_role TEXT    NOT NULL,
+    allowed       INTEGER NOT NULL,
+    reason        TEXT    NOT NULL
+);
+CREATE INDEX IF NOT EXISTS idx_command_authorization_tick
+    ON command_authorization (run_id, tick);
@@ -0,0 +1,36 @@
+-- Phase 4.50: CTR transaction key
+--
+-- currency_transaction_report declared its transaction key against
+-- transactions(transaction_id), a column that does not exist. SQLite
+-- only rejects such a key when it has to check it, so filing a CTR or
+-- deleting a transaction failed with "foreign key mismatch". Rebuild the
+-- table against transactions(txn_id); its rows and indexes are unchanged.
+CREATE TABLE currency_transaction_report_rebuilt (
+    ctr_id              TEXT NOT NULL,
+    run_id              TEXT NOT NULL,
+    customer_id         TEXT NOT NULL,
+    account_id          TEXT NOT NULL,
+    transaction_id      TEXT NOT NULL,
+    filing_tick         INTEGER NOT NULL,
+    transaction_amount  REAL NOT NULL,
+    transaction_type    TEXT NOT NULL,
+    -- 'cash_deposit', 'cash_withdrawal', 'multiple_related_transactions'
+    filing_deadline     INTEGER NOT NULL,
+    filed_on_time       INTEGER NOT NULL DEFAULT 1,
+    auto_filed          INTEGER NOT NULL DEFAULT 1,
+
+    PRIMARY KEY (run_id, ctr_id),
+    FOREIGN KEY (run_id) REFERENCES run(run_id),
+    FOREIGN KEY (customer_id) REFERENCES customer(customer_id),
+    FOREIGN KEY (account_id) REFERENCES account(account_id),
+    FOREIGN KEY (transaction_id) REFERENCES transactions(txn_id)
+);
+
+INSERT INTO currency_transaction_report_rebuilt SELECT * FROM currency_transaction_report;
+DROP TABLE currency_transaction_report;
+ALTER TABLE currency_transaction_report_rebuilt RENAME TO currency_transaction_report;
+
+CREATE INDEX IF NOT EXISTS idx_ctr_customer
+    ON currency_transaction_report(run_id, customer_id, filing_tick DESC);
+CREATE INDEX IF NOT EXISTS idx_ctr_filing
+    ON currency_transaction_report(run_id, filed_on_time, filing_tick DESC);
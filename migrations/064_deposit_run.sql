@@ -0,0 +1,42 @@
+-- Phase 4.52: Macroprudential deposit run scenario
+--
+-- liquidity_position: the bank's balance sheet at the end of each tick.
+-- liquid_assets is what can meet withdrawals today; emergency_borrowing
+-- is discount window credit drawn by the player and penalty_borrowing
+-- what was borrowed automatically to cover a shortfall. run_rate is the
+-- share of uninsured deposits leaving per tick, 0 outside a run.
+-- premium_cost is interest paid on the deposit rate premium and
+-- liquidity_cost interest on both borrowings, both for the tick.
+-- deposit_run: one row per run, open while ended_tick is NULL.
+-- trigger: 'scheduled' | 'reputation'.
+CREATE TABLE IF NOT EXISTS liquidity_position (
+    run_id              TEXT    NOT NULL REFERENCES run(run_id),
+    tick                INTEGER NOT NULL,
+    deposits            REAL    NOT NULL,
+    uninsured_deposits  REAL    NOT NULL,
+    liquid_assets       REAL    NOT NULL,
+    emergency_borrowing REAL    NOT NULL DEFAULT 0.0,
+    penalty_borrowing   REAL    NOT NULL DEFAULT 0.0,
+    capital             REAL    NOT NULL,
+    rate_premium        REAL    NOT NULL DEFAULT 0.0,
+    run_rate            REAL    NOT NULL DEFAULT 0.0,
+    severity            REAL    NOT NULL DEFAULT 1.0,
+    outflow             REAL    NOT NULL DEFAULT 0.0,
+    premium_cost        REAL    NOT NULL DEFAULT 0.0,
+    liquidity_cost      REAL    NOT NULL DEFAULT 0.0,
+    PRIMARY KEY (run_id, tick)
+);
+
+CREATE TABLE IF NOT EXISTS deposit_run (
+    run_id            TEXT    NOT NULL REFERENCES run(run_id),
+    shock_tick        INTEGER NOT NULL,
+    trigger           TEXT    NOT NULL,
+    reputation_score  REAL    NOT NULL,
+    ended_tick        INTEGER,
+    peak_run_rate     REAL    NOT NULL DEFAULT 0.0,
+    total_outflow     REAL    NOT NULL DEFAULT 0.0,
+    uninsured_outflow REAL    NOT NULL DEFAULT 0.0,
+    emergency_drawn   REAL    NOT NULL DEFAULT 0.0,
+    shortfall         REAL    NOT NULL DEFAULT 0.0,
+    PRIMARY KEY (run_id, shock_tick)
+);
@@ -0,0 +1,33 @@
+-- Phase 4.53: Business email compromise
+--
+-- invoice_wire: every invoice a business customer paid by wire, spoofed
+-- or genuine. The desk never sees `spoofed`; it is what a recall finds
+-- out.
+-- wire_recall: one row per wire the BEC_NEW_PAYEE rule alerted on or the
+-- player recalled. recall_tick is NULL until a recall is sent.
+-- outcome: 'recovered' | 'funds_gone' | 'not_fraud'.
+CREATE TABLE IF NOT EXISTS invoice_wire (
+    txn_id       TEXT    PRIMARY KEY,
+    run_id       TEXT    NOT NULL REFERENCES run(run_id),
+    customer_id  TEXT    NOT NULL,
+    account_id   TEXT    NOT NULL,
+    tick         INTEGER NOT NULL,
+    amount       REAL    NOT NULL,
+    counterparty TEXT    NOT NULL,
+    spoofed      INTEGER NOT NULL DEFAULT 0
+);
+CREATE INDEX IF NOT EXISTS idx_invoice_wire_run ON invoice_wire (run_id, tick);
+
+CREATE TABLE IF NOT EXISTS wire_recall (
+    txn_id       TEXT    PRIMARY KEY,
+    run_id       TEXT    NOT NULL REFERENCES run(run_id),
+    account_id   TEXT    NOT NULL,
+    wire_tick    INTEGER NOT NULL,
+    amount       REAL    NOT NULL,
+    alert_id     TEXT,
+    alerted_tick INTEGER,
+    recall_tick  INTEGER,
+    outcome      TEXT,
+    recovered    REAL    NOT NULL DEFAULT 0.0
+);
+CREATE INDEX IF NOT EXISTS idx_wire_recall_run ON wire_recall (run_id, recall_tick);
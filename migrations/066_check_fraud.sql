@@ -0,0 +1,46 @@
+-- Phase 4.54: Check fraud
+--
+-- Seeds the check rail, cleared in a nightly batch for next-day
+-- settlement.
+INSERT OR IGNORE INTO payment_rail (
+    rail_id, rail_type, latency_type, settlement_delay_ticks, fraud_risk_multiplier,
+    operational_risk_base, batch_window_ticks, cutoff_time_tick
+)
+VALUES ('check', 'check', 'batch', 1, 1.8, 0.002, 1, NULL);
+
+-- check_item: every check that cleared through the bank. side 'presented'
+-- is drawn on a customer and paid out of their account; side 'deposited'
+-- is drawn on another bank and credited to one. The desk never sees
+-- `typology` ('genuine' | 'washed' | 'counterfeit'); a review or the
+-- other bank finds it out.
+-- rule: 'check_duplicate_serial' | 'check_serial_out_of_range' when
+-- flagged.
+-- deadline_tick: the midnight deadline for presented items, the other
+-- bank's return for deposited ones.
+-- status: 'paid' | 'pending_review' | 'cashed_out' | 'cleared'
+--       | 'returned' | 'reimbursed' | 'late_return'.
+-- loss_bearer: 'bank' | 'depositary_bank' | 'paying_bank' once someone
+-- has eaten the loss.
+CREATE TABLE IF NOT EXISTS check_item (
+    item_id       TEXT    PRIMARY KEY,
+    run_id        TEXT    NOT NULL REFERENCES run(run_id),
+    account_id    TEXT    NOT NULL,
+    tick          INTEGER NOT NULL,
+    side          TEXT    NOT NULL,
+    channel       TEXT    NOT NULL,
+    drawer        TEXT    NOT NULL,
+    serial        INTEGER NOT NULL,
+    payee         TEXT    NOT NULL,
+    amount        REAL    NOT NULL,
+    typology      TEXT    NOT NULL,
+    txn_id        TEXT    NOT NULL,
+    rule          TEXT,
+    alert_id      TEXT,
+    deadline_tick INTEGER NOT NULL,
+    status        TEXT    NOT NULL,
+    resolved_tick INTEGER,
+    loss_bearer   TEXT,
+    loss          REAL    NOT NULL DEFAULT 0.0
+);
+CREATE INDEX IF NOT EXISTS idx_check_item_run ON check_item (run_id, tick);
+CREATE INDEX IF NOT EXISTS idx_check_item_serial ON check_item (run_id, drawer, serial);
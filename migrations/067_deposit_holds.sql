@@ -0,0 +1,35 @@
+-- Phase 4.55: Deposit holds
+--
+-- deposit_hold: one row per check deposit with funds on hold. held_amount
+-- is what stays unavailable until release_tick; released_tick is NULL
+-- while the hold is on, and set early if the deposit comes back first.
+-- class: the class that set the hold, 'atm' | 'branch' | 'new_account'
+-- | 'large_item'. pressure: hold ticks times the customer's liquidity
+-- sensitivity.
+CREATE TABLE IF NOT EXISTS deposit_hold (
+    txn_id        TEXT    PRIMARY KEY,
+    run_id        TEXT    NOT NULL REFERENCES run(run_id),
+    account_id    TEXT    NOT NULL,
+    customer_id   TEXT    NOT NULL,
+    tick          INTEGER NOT NULL,
+    class         TEXT    NOT NULL,
+    risk          TEXT    NOT NULL,
+    amount        REAL    NOT NULL,
+    held_amount   REAL    NOT NULL,
+    hold_ticks    INTEGER NOT NULL,
+    release_tick  INTEGER NOT NULL,
+    released_tick INTEGER,
+    pressure      REAL    NOT NULL
+);
+CREATE INDEX IF NOT EXISTS idx_deposit_hold_release ON deposit_hold (run_id, released_tick, release_tick);
+
+-- deposit_hold_policy: hold rules the player has set, in force from
+-- `tick`. NULL class or risk matches all.
+CREATE TABLE IF NOT EXISTS deposit_hold_policy (
+    run_id     TEXT    NOT NULL REFERENCES run(run_id),
+    tick       INTEGER NOT NULL,
+    class      TEXT,
+    risk       TEXT,
+    hold_ticks INTEGER NOT NULL
+);
+CREATE INDEX IF NOT EXISTS idx_deposit_hold_policy_run ON deposit_hold_policy (run_id, tick);
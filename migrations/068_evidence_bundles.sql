@@ -0,0 +1,24 @@
+-- Phase 4.59: Evidence bundles
+--
+-- evidence_bundle: the evidence assembled for a SAR or dispute filing,
+-- one row per filing. filing_type: 'sar' | 'dispute'; filing_id is the
+-- SAR or dispute id and source_id the alert or dispute it came from.
+-- content is the whole bundle as JSON; quality is the share of its
+-- checks it passed, gaps a JSON array of those it failed, and deficient
+-- is set when quality fell below the configured minimum.
+CREATE TABLE IF NOT EXISTS evidence_bundle (
+    run_id      TEXT    NOT NULL REFERENCES run(run_id),
+    bundle_id   TEXT    NOT NULL,
+    filing_type TEXT    NOT NULL,
+    filing_id   TEXT    NOT NULL,
+    source_id   TEXT    NOT NULL,
+    customer_id TEXT    NOT NULL,
+    tick        INTEGER NOT NULL,
+    quality     REAL    NOT NULL,
+    deficient   INTEGER NOT NULL,
+    gaps        TEXT    NOT NULL,
+    content     TEXT    NOT NULL,
+    PRIMARY KEY (run_id, bundle_id)
+);
+CREATE INDEX IF NOT EXISTS idx_evidence_bundle_filing ON evidence_bundle (run_id, filing_type, filing_id);
+CREATE INDEX IF NOT EXISTS idx_evidence_bundle_tick ON evidence_bundle (run_id, tick, deficient);
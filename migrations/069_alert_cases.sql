@@ -0,0 +1,27 @@
+-- Phase 4.60: Alert cases
+--
+-- alert_case: an investigation case grouping AML alerts on one customer
+-- or relationship cluster. customer_id is the customer of the alert that
+-- opened it. last_alert_tick is the newest alert's tick, which decides
+-- whether a new alert still falls in the grouping window. total_amount
+-- sums the alerts' triggered amounts. status: 'open' | 'closed'; a case
+-- closes once none of its alerts is still open or under investigation.
+CREATE TABLE IF NOT EXISTS alert_case (
+    run_id          TEXT    NOT NULL REFERENCES run(run_id),
+    case_id         TEXT    NOT NULL,
+    customer_id     TEXT    NOT NULL,
+    opened_tick     INTEGER NOT NULL,
+    last_alert_tick INTEGER NOT NULL,
+    alert_count     INTEGER NOT NULL,
+    customer_count  INTEGER NOT NULL,
+    total_amount    REAL    NOT NULL,
+    max_score       REAL    NOT NULL,
+    status          TEXT    NOT NULL DEFAULT 'open',
+    closed_tick     INTEGER,
+    PRIMARY KEY (run_id, case_id)
+);
+CREATE INDEX IF NOT EXISTS idx_alert_case_open ON alert_case (run_id, status, last_alert_tick);
+
+-- The case an alert was grouped into; NULL until grouped.
+ALTER TABLE aml_alert ADD COLUMN case_id TEXT;
+CREATE INDEX IF NOT EXISTS idx_aml_alert_case ON aml_alert (run_id, case_id);
@@ -0,0 +1,45 @@
+-- Phase 4.61: Operational risk
+--
+-- loss_event: every operational loss the bank bore, one row per source
+-- record, filed under its Basel event type:
+--   'external_fraud'                        check fraud, unrecovered BEC wires
+--   'clients_products_business_practices'   exam and SAR fines, FCRA
+--                                           penalties, fair lending
+--                                           remediation, network fines
+--   'execution_delivery_process_management' reconciliation write-offs
+-- source names the table the loss came from and source_id its row there.
+-- tick is when the loss was booked.
+CREATE TABLE IF NOT EXISTS loss_event (
+    run_id         TEXT    NOT NULL REFERENCES run(run_id),
+    source         TEXT    NOT NULL,
+    source_id      TEXT    NOT NULL,
+    tick           INTEGER NOT NULL,
+    basel_category TEXT    NOT NULL,
+    amount         REAL    NOT NULL,
+    description    TEXT    NOT NULL,
+    PRIMARY KEY (run_id, source, source_id)
+);
+CREATE INDEX IF NOT EXISTS idx_loss_event_tick ON loss_event (run_id, tick);
+
+-- op_risk_capital: the capital estimate taken each quarter, standardised
+-- approach style. business_indicator is annualised gross income;
+-- annual_loss the average yearly loss over the lookback. capital is the
+-- business indicator component scaled by the internal loss multiplier.
+CREATE TABLE IF NOT EXISTS op_risk_capital (
+    run_id             TEXT    NOT NULL REFERENCES run(run_id),
+    tick               INTEGER NOT NULL,
+    business_indicator REAL    NOT NULL,
+    bic                REAL    NOT NULL,
+    annual_loss        REAL    NOT NULL,
+    loss_component     REAL    NOT NULL,
+    ilm                REAL    NOT NULL,
+    capital            REAL    NOT NULL,
+    PRIMARY KEY (run_id, tick)
+);
+
+-- Capital held against operational risk, which a deposit run cannot
+-- draw on.
+ALTER TABLE liquidity_position ADD COLUMN op_risk_capital REAL NOT NULL DEFAULT 0.0;
+
+ALTER TABLE pnl_snapshot ADD COLUMN operational_loss REAL NOT NULL DEFAULT 0.0;
+ALTER TABLE pnl_snapshot ADD COLUMN op_risk_capital REAL NOT NULL DEFAULT 0.0;
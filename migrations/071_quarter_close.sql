@@ -0,0 +1,58 @@
+-- Phase 4.62: Quarter-end close
+--
+-- quarter_close: one row per quarter closed. due_tick is the last tick
+-- the checklist can be signed off on time; overdue is set once it passes
+-- with items unsigned. adjustment_total is what the close and any
+-- on-time adjustments booked into the quarter's P&L; late adjustments,
+-- posted after sign-off or the deadline, are counted apart.
+CREATE TABLE IF NOT EXISTS quarter_close (
+    run_id                TEXT    NOT NULL REFERENCES run(run_id),
+    period                TEXT    NOT NULL,
+    close_tick            INTEGER NOT NULL,
+    due_tick              INTEGER NOT NULL,
+    status                TEXT    NOT NULL DEFAULT 'open',
+    -- 'open' | 'closed'
+    closed_tick           INTEGER,
+    overdue               INTEGER NOT NULL DEFAULT 0,
+    adjustment_total      REAL    NOT NULL DEFAULT 0.0,
+    late_adjustment_count INTEGER NOT NULL DEFAULT 0,
+    late_adjustment_total REAL    NOT NULL DEFAULT 0.0,
+    PRIMARY KEY (run_id, period)
+);
+
+-- close_adjustment: entries the close books, signed so that a positive
+-- amount raises profit:
+--   'unpaid_bonus'                 bonuses on completed offers, unpaid
+--   'provisional_credit_reserve'   reserve on open disputes' provisional credit
+--   'unposted_interchange'         interchange on cleared, unsettled volume
+--   'accrual_reversal'             the previous close's accruals reversed
+--   'manual'                       posted by the player
+-- booked_period is the quarter whose P&L carries it: its own, or for a
+-- late adjustment the next one closed (NULL until then).
+CREATE TABLE IF NOT EXISTS close_adjustment (
+    run_id        TEXT    NOT NULL REFERENCES run(run_id),
+    adjustment_id TEXT    NOT NULL,
+    period        TEXT    NOT NULL,
+    tick          INTEGER NOT NULL,
+    kind          TEXT    NOT NULL,
+    amount        REAL    NOT NULL,
+    description   TEXT    NOT NULL,
+    late          INTEGER NOT NULL DEFAULT 0,
+    booked_period TEXT,
+    PRIMARY KEY (run_id, adjustment_id)
+);
+CREATE INDEX IF NOT EXISTS idx_close_adjustment_period ON close_adjustment (run_id, period);
+
+-- close_checklist_item: what the player signs off before a close ends.
+CREATE TABLE IF NOT EXISTS close_checklist_item (
+    run_id          TEXT    NOT NULL REFERENCES run(run_id),
+    period          TEXT    NOT NULL,
+    item            TEXT    NOT NULL,
+    detail          TEXT    NOT NULL,
+    status          TEXT    NOT NULL DEFAULT 'pending',
+    -- 'pending' | 'signed_off'
+    signed_off_tick INTEGER,
+    PRIMARY KEY (run_id, period, item)
+);
+
+ALTER TABLE pnl_snapshot ADD COLUMN accrual_adjustment REAL NOT NULL DEFAULT 0.0;
@@ -0,0 +1,35 @@
+-- Phase 4.63: Opex allocation rules
+--
+-- opex_allocation_rule: the pools a run spreads shared opex by, recorded
+-- the first quarter they are applied and applied every quarter after.
+-- driver is 'customers' | 'transactions' | 'headcount'; headcount is a
+-- JSON object of heads per segment, used by the 'headcount' driver.
+CREATE TABLE IF NOT EXISTS opex_allocation_rule (
+    run_id         TEXT    NOT NULL REFERENCES run(run_id),
+    pool           TEXT    NOT NULL,
+    driver         TEXT    NOT NULL,
+    quarterly_cost REAL    NOT NULL,
+    headcount      TEXT    NOT NULL DEFAULT '{}',
+    recorded_tick  INTEGER NOT NULL,
+    PRIMARY KEY (run_id, pool)
+);
+
+-- opex_allocation: each quarter's split, one row per pool and segment.
+-- share is driver_value over the pool's driver total; amount is the
+-- pool's cost times share. A segment's rows sum to its allocated_opex
+-- in segment_pnl.
+CREATE TABLE IF NOT EXISTS opex_allocation (
+    run_id       TEXT    NOT NULL REFERENCES run(run_id),
+    tick         INTEGER NOT NULL,
+    pool         TEXT    NOT NULL,
+    segment      TEXT    NOT NULL,
+    driver       TEXT    NOT NULL,
+    driver_value REAL    NOT NULL,
+    share        REAL    NOT NULL,
+    amount       REAL    NOT NULL,
+    PRIMARY KEY (run_id, tick, pool, segment)
+);
+
+-- Transactions posted, per segment and tick, for the 'transactions'
+-- driver.
+ALTER TABLE segment_activity ADD COLUMN transaction_count INTEGER NOT NULL DEFAULT 0;
@@ -0,0 +1,26 @@
+-- Phase 4.64: Churn prediction evaluation
+--
+-- churn_prediction_eval: how one batch of churn scores fared once its
+-- horizon elapsed, per segment. A customer scored at score_tick counts
+-- as churned if they churned in [score_tick, score_tick + horizon).
+-- calibration_gap is mean_predicted less realized_rate; brier_score the
+-- mean squared error of the predictions; auc the chance a churner was
+-- scored above a stayer (NULL when the segment had only one or the
+-- other). drift is set when the batch scored worse than the segment's
+-- first evaluation at the same horizon by more than the tolerance.
+CREATE TABLE IF NOT EXISTS churn_prediction_eval (
+    run_id          TEXT    NOT NULL REFERENCES run(run_id),
+    score_tick      INTEGER NOT NULL,
+    horizon         INTEGER NOT NULL,
+    segment         TEXT    NOT NULL,
+    eval_tick       INTEGER NOT NULL,
+    customers       INTEGER NOT NULL,
+    churned         INTEGER NOT NULL,
+    mean_predicted  REAL    NOT NULL,
+    realized_rate   REAL    NOT NULL,
+    calibration_gap REAL    NOT NULL,
+    brier_score     REAL    NOT NULL,
+    auc             REAL,
+    drift           INTEGER NOT NULL DEFAULT 0,
+    PRIMARY KEY (run_id, score_tick, horizon, segment)
+);
@@ -0,0 +1,42 @@
+-- Phase 4.65: Complaint response quality
+--
+-- complaint_quality: the quality of each complaint closure, scored the
+-- tick after it closes. A complaint closed again after a reopen is
+-- scored again, so tick_closed is part of the key. time_score is the
+-- handling time over the ticks a thorough response takes, capped at 1;
+-- appropriate is whether the resolution code suits the issue.
+-- outcome is what came of the closure:
+--   'stands'     the closure held
+--   'reopened'   the customer came back and the complaint reopened
+--   'escalated'  the customer went to the regulator, which sent it back
+CREATE TABLE IF NOT EXISTS complaint_quality (
+    run_id          TEXT    NOT NULL REFERENCES run(run_id),
+    complaint_id    TEXT    NOT NULL,
+    tick_closed     INTEGER NOT NULL,
+    scored_tick     INTEGER NOT NULL,
+    resolution_code TEXT    NOT NULL,
+    analyst_skill   REAL    NOT NULL,
+    handling_ticks  INTEGER NOT NULL,
+    time_score      REAL    NOT NULL,
+    appropriate     INTEGER NOT NULL,
+    quality         REAL    NOT NULL,
+    outcome         TEXT    NOT NULL DEFAULT 'stands',
+    PRIMARY KEY (run_id, complaint_id, tick_closed)
+);
+CREATE INDEX IF NOT EXISTS idx_complaint_quality_scored ON complaint_quality (run_id, scored_tick);
+
+-- desk_performance_report: the complaint desk's closures each reporting
+-- period and how good they were. quality_trend is avg_quality less the
+-- previous report's (NULL on the first, or when either had no closures).
+CREATE TABLE IF NOT EXISTS desk_performance_report (
+    run_id             TEXT    NOT NULL REFERENCES run(run_id),
+    tick               INTEGER NOT NULL,
+    closures           INTEGER NOT NULL,
+    avg_quality        REAL,
+    quality_trend      REAL,
+    poor_quality_count INTEGER NOT NULL,
+    reopened_count     INTEGER NOT NULL,
+    escalated_count    INTEGER NOT NULL,
+    avg_handling_ticks REAL,
+    PRIMARY KEY (run_id, tick)
+);
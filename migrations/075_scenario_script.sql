@@ -0,0 +1,12 @@
+-- Phase 4.68: Scenario scripting hooks
+--
+-- scenario_script_once: the keys each scenario script has spent with
+-- once(), so a one-off event does not fire again when the run is rebuilt
+-- over the same store.
+CREATE TABLE IF NOT EXISTS scenario_script_once (
+    run_id   TEXT    NOT NULL REFERENCES run(run_id),
+    script   TEXT    NOT NULL,
+    once_key TEXT    NOT NULL,
+    tick     INTEGER NOT NULL,
+    PRIMARY KEY (run_id, script, once_key)
+);
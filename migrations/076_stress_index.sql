@@ -0,0 +1,21 @@
+-- Phase 4.69: Composite stress index
+--
+-- stress_index: how close the bank ran to the edge each tick. Each
+-- *_pressure column is one component, 0 (calm) to 1 (at the edge), and is
+-- NULL when its source had nothing to go on that tick (no liquidity
+-- position, no branches, reputation off). stress is the weighted mean of
+-- the components present; resilience is 100 × (1 − stress).
+-- level is 'steady' | 'strained' | 'critical'.
+CREATE TABLE IF NOT EXISTS stress_index (
+    run_id              TEXT    NOT NULL REFERENCES run(run_id),
+    tick                INTEGER NOT NULL,
+    backlog_pressure    REAL,
+    capital_pressure    REAL,
+    reputation_pressure REAL,
+    staffing_pressure   REAL,
+    findings_pressure   REAL,
+    stress              REAL    NOT NULL,
+    resilience          REAL    NOT NULL,
+    level               TEXT    NOT NULL,
+    PRIMARY KEY (run_id, tick)
+);
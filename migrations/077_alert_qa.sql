@@ -0,0 +1,55 @@
+-- Phase 4.73: Alert disposition QA
+--
+-- alert_qa_review: one SAR decision re-reviewed by QA. desk_filed is
+-- whether the desk filed ('filed' or 'defensive') or not ('missed');
+-- qa_filed is whether QA judged a filing called for. error_kind is NULL
+-- when they agree, else 'over_filing' (filed, QA would not have) or
+-- 'under_filing' (not filed, QA would have). cost is the QA time spent.
+CREATE TABLE IF NOT EXISTS alert_qa_review (
+    run_id      TEXT    NOT NULL REFERENCES run(run_id),
+    alert_id    TEXT    NOT NULL,
+    cycle_tick  INTEGER NOT NULL,
+    customer_id TEXT    NOT NULL,
+    alert_type  TEXT    NOT NULL,
+    alert_score REAL    NOT NULL,
+    decision    TEXT    NOT NULL,
+    desk_filed  INTEGER NOT NULL,
+    qa_filed    INTEGER NOT NULL,
+    error_kind  TEXT,
+    cost        REAL    NOT NULL,
+    PRIMARY KEY (run_id, alert_id)
+);
+CREATE INDEX IF NOT EXISTS idx_alert_qa_review_cycle ON alert_qa_review (run_id, cycle_tick);
+
+-- alert_qa_cycle: each QA cycle's sample. population is the decisions
+-- made in the cycle, sampled those QA had capacity to re-review.
+-- disagreement_trend is disagreement_rate less the previous cycle's
+-- (NULL on the first, or when either sampled nothing).
+CREATE TABLE IF NOT EXISTS alert_qa_cycle (
+    run_id             TEXT    NOT NULL REFERENCES run(run_id),
+    tick               INTEGER NOT NULL,
+    population         INTEGER NOT NULL,
+    sampled            INTEGER NOT NULL,
+    disagreements      INTEGER NOT NULL,
+    disagreement_rate  REAL,
+    disagreement_trend REAL,
+    PRIMARY KEY (run_id, tick)
+);
+
+-- qa_retraining: a retraining work item opened when QA finds an alert
+-- type dispositioned wrongly too often. error_kind is the error QA found
+-- most ('over_filing' | 'under_filing' | 'mixed'). status is 'open' until
+-- due_tick, then 'completed', when its cost is booked.
+CREATE TABLE IF NOT EXISTS qa_retraining (
+    run_id            TEXT    NOT NULL REFERENCES run(run_id),
+    retraining_id     TEXT    NOT NULL,
+    alert_type        TEXT    NOT NULL,
+    error_kind        TEXT    NOT NULL,
+    disagreement_rate REAL    NOT NULL,
+    opened_tick       INTEGER NOT NULL,
+    due_tick          INTEGER NOT NULL,
+    status            TEXT    NOT NULL DEFAULT 'open',
+    completed_tick    INTEGER,
+    cost              REAL    NOT NULL,
+    PRIMARY KEY (run_id, retraining_id)
+);
@@ -0,0 +1,7 @@
+-- Phase 4.77: Scenario files
+--
+-- The scenario file a run was built with, as a JSON object, so resuming
+-- builds the same bank. NULL for runs built from the data directory
+-- alone.
+ALTER TABLE run
+ADD COLUMN scenario TEXT;
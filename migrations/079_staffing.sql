@@ -0,0 +1,33 @@
+-- Phase 4.80: Staffing and case-worker capacity
+--
+-- staffing_plan: FTEs the player has put on a queue; the latest row for a
+-- queue wins, and a queue with none keeps its configured headcount.
+-- queue is 'complaints' | 'alerts' | 'exceptions' | 'cases'.
+CREATE TABLE IF NOT EXISTS staffing_plan (
+    run_id TEXT    NOT NULL REFERENCES run(run_id),
+    tick   INTEGER NOT NULL,
+    queue  TEXT    NOT NULL,
+    ftes   REAL    NOT NULL,
+    PRIMARY KEY (run_id, tick, queue)
+);
+
+-- staffing_snapshot: one staffed queue on one tick. backlog is the open
+-- items at the start of the tick; regular_items what its staff work in a
+-- day; capacity the items they can work, overtime included; worked what
+-- the owning subsystem got through, and overtime_items the part of it
+-- beyond regular_items. cost is the day's pay, each overtime item at
+-- overtime_item_cost on top.
+CREATE TABLE IF NOT EXISTS staffing_snapshot (
+    run_id             TEXT    NOT NULL REFERENCES run(run_id),
+    tick               INTEGER NOT NULL,
+    queue              TEXT    NOT NULL,
+    ftes               REAL    NOT NULL,
+    backlog            INTEGER NOT NULL,
+    regular_items      REAL    NOT NULL,
+    capacity           INTEGER NOT NULL,
+    worked             INTEGER NOT NULL DEFAULT 0,
+    overtime_items     REAL    NOT NULL DEFAULT 0,
+    overtime_item_cost REAL    NOT NULL,
+    cost               REAL    NOT NULL,
+    PRIMARY KEY (run_id, tick, queue)
+);
@@ -0,0 +1,46 @@
+-- Phase 4.81: Regulatory enforcement
+--
+-- regulatory_exam.udaap_score: the UDAAP risk score when the exam closed,
+-- the baseline the next exam measures the rise from.
+ALTER TABLE regulatory_exam ADD COLUMN udaap_score REAL;
+
+-- exam_sample: what an exam tested. population is 'complaints' (closed in
+-- the window; failed = SLA breached, or a UDAAP complaint closed without
+-- a refund), 'sars' (filed in the window; failed = filed late) or
+-- 'fee_changes' (every change in the cycle; failed = an increase).
+CREATE TABLE IF NOT EXISTS exam_sample (
+    run_id     TEXT    NOT NULL REFERENCES run(run_id),
+    exam_id    TEXT    NOT NULL REFERENCES regulatory_exam(exam_id),
+    population TEXT    NOT NULL,
+    available  INTEGER NOT NULL,
+    sampled    INTEGER NOT NULL,
+    failed     INTEGER NOT NULL,
+    PRIMARY KEY (run_id, exam_id, population)
+);
+
+-- enforcement_action: an MRA or consent order issued on a finding.
+-- constraint_kind is what it imposes until lifted: 'fee_cap' (fees held
+-- at their soft limits), 'complaint_lookback' or 'sar_lookback' (a
+-- re-review of the items in the lookback window), or NULL. status is
+-- 'open' | 'lifted' | 'escalated'; an escalated action is superseded by
+-- the consent order named in superseded_by.
+CREATE TABLE IF NOT EXISTS enforcement_action (
+    action_id            TEXT    PRIMARY KEY,
+    run_id               TEXT    NOT NULL REFERENCES run(run_id),
+    exam_id              TEXT    NOT NULL REFERENCES regulatory_exam(exam_id),
+    finding_id           TEXT    NOT NULL,
+    kind                 TEXT    NOT NULL,
+    -- 'mra' | 'consent_order'
+    category             TEXT    NOT NULL,
+    tick_issued          INTEGER NOT NULL,
+    remediation_due_tick INTEGER NOT NULL,
+    constraint_kind      TEXT,
+    penalty              REAL    NOT NULL DEFAULT 0.0,
+    lookback_items       INTEGER NOT NULL DEFAULT 0,
+    lookback_reviewed    INTEGER NOT NULL DEFAULT 0,
+    lookback_cost        REAL    NOT NULL DEFAULT 0.0,
+    status               TEXT    NOT NULL DEFAULT 'open',
+    tick_closed          INTEGER,
+    superseded_by        TEXT
+);
+CREATE INDEX IF NOT EXISTS idx_enforcement_action_run ON enforcement_action(run_id, status, tick_issued);
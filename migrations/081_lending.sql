@@ -0,0 +1,59 @@
+-- Phase 4.82: Lending
+--
+-- loan: an underwritten installment loan or credit line, paid from the
+-- borrower's account. balance is the principal outstanding; status is
+-- 'open' | 'paid_off' | 'charged_off'; bucket is the delinquency bucket
+-- it sits in now ('current' | '1_29' | '30_59' | '60_89' | '90_plus').
+CREATE TABLE IF NOT EXISTS loan (
+    loan_id          TEXT    PRIMARY KEY,
+    run_id           TEXT    NOT NULL REFERENCES run(run_id),
+    customer_id      TEXT    NOT NULL,
+    account_id       TEXT    NOT NULL,
+    product_id       TEXT    NOT NULL,
+    kind             TEXT    NOT NULL,
+    -- 'installment' | 'line'
+    tick_originated  INTEGER NOT NULL,
+    amount           REAL    NOT NULL,
+    annual_rate      REAL    NOT NULL,
+    credit_score     INTEGER NOT NULL,
+    dti              REAL    NOT NULL,
+    balance          REAL    NOT NULL,
+    interest_paid    REAL    NOT NULL DEFAULT 0.0,
+    status           TEXT    NOT NULL DEFAULT 'open',
+    bucket           TEXT    NOT NULL DEFAULT 'current',
+    tick_closed      INTEGER,
+    charge_off_loss  REAL    NOT NULL DEFAULT 0.0
+);
+CREATE INDEX IF NOT EXISTS idx_loan_run ON loan(run_id, status);
+CREATE INDEX IF NOT EXISTS idx_loan_charge_off ON loan(run_id, status, tick_closed);
+
+-- loan_payment: one scheduled payment. An installment loan's schedule is
+-- written at origination; a line's next payment when it falls due. status
+-- is 'scheduled' | 'paid' | 'missed'; a missed payment is paid later when
+-- the borrower catches up.
+CREATE TABLE IF NOT EXISTS loan_payment (
+    run_id      TEXT    NOT NULL REFERENCES run(run_id),
+    loan_id     TEXT    NOT NULL REFERENCES loan(loan_id),
+    payment_no  INTEGER NOT NULL,
+    due_tick    INTEGER NOT NULL,
+    amount_due  REAL    NOT NULL,
+    principal   REAL    NOT NULL,
+    interest    REAL    NOT NULL,
+    status      TEXT    NOT NULL DEFAULT 'scheduled',
+    tick_paid   INTEGER,
+    PRIMARY KEY (run_id, loan_id, payment_no)
+);
+CREATE INDEX IF NOT EXISTS idx_loan_payment_due ON loan_payment(run_id, due_tick, status);
+CREATE INDEX IF NOT EXISTS idx_loan_payment_paid ON loan_payment(run_id, status, tick_paid);
+
+-- delinquency_status: a loan moving between delinquency buckets, with its
+-- days past due and the amount past due when it moved.
+CREATE TABLE IF NOT EXISTS delinquency_status (
+    run_id          TEXT    NOT NULL REFERENCES run(run_id),
+    loan_id         TEXT    NOT NULL REFERENCES loan(loan_id),
+    tick            INTEGER NOT NULL,
+    days_past_due   INTEGER NOT NULL,
+    bucket          TEXT    NOT NULL,
+    past_due_amount REAL    NOT NULL,
+    PRIMARY KEY (run_id, loan_id, tick)
+);
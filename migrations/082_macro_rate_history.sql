@@ -0,0 +1,15 @@
+-- Phase 4.83: Rate environment
+--
+-- macro_rate_history: the base rate and the long end of the curve each
+-- time the macro subsystem sets them — the starting rates at tick 0, then
+-- every quarter. move is 'start' | 'hike' | 'cut' | 'hold'. A rate is in
+-- force from its tick until the next row.
+CREATE TABLE IF NOT EXISTS macro_rate_history (
+    run_id          TEXT    NOT NULL REFERENCES run(run_id),
+    tick            INTEGER NOT NULL,
+    base_rate       REAL    NOT NULL,
+    long_rate       REAL    NOT NULL,
+    economic_phase  TEXT    NOT NULL,
+    move            TEXT    NOT NULL,
+    PRIMARY KEY (run_id, tick)
+);
@@ -0,0 +1,13 @@
+-- Phase 4.84: SAR e-filing export
+--
+-- sar_export: each SAR sent out in an e-filing batch, and the format the
+-- batch was written in ('json' | 'xml'). A SAR is exported once; SARs
+-- with no row here go in the next batch.
+CREATE TABLE IF NOT EXISTS sar_export (
+    run_id    TEXT NOT NULL REFERENCES run(run_id),
+    sar_id    TEXT NOT NULL,
+    batch_id  TEXT NOT NULL,
+    format    TEXT NOT NULL,
+    PRIMARY KEY (run_id, sar_id)
+);
+CREATE INDEX IF NOT EXISTS idx_sar_export_batch ON sar_export(run_id, batch_id);
@@ -0,0 +1,40 @@
+-- Phase 4.85: Alert triage
+--
+-- How an alert was closed: 'false_positive' | 'suspicious' | 'sar_filed'.
+-- The desk sets it when it files or clears an alert, an analyst when
+-- closing one by hand. NULL while the alert is open.
+ALTER TABLE aml_alert ADD COLUMN disposition TEXT;
+
+-- The tick an analyst escalated the case; NULL if never escalated.
+ALTER TABLE alert_case ADD COLUMN escalated_tick INTEGER;
+
+-- alert_note: the analyst's notes on an alert, one per triage action.
+-- action: 'assign' | 'close' | 'escalate'. analyst is the one assigned
+-- when the note was written, NULL if the alert was unassigned.
+CREATE TABLE IF NOT EXISTS alert_note (
+    run_id   TEXT    NOT NULL REFERENCES run(run_id),
+    alert_id TEXT    NOT NULL,
+    tick     INTEGER NOT NULL,
+    analyst  TEXT,
+    action   TEXT    NOT NULL,
+    note     TEXT    NOT NULL
+);
+CREATE INDEX IF NOT EXISTS idx_alert_note_alert ON alert_note (run_id, alert_id, tick);
+
+-- alert_aging: the alert queue at the end of each tick. Ages are ticks
+-- since the alert was raised, over the alerts still open or under
+-- investigation; aged_alerts are those past the configured age. The
+-- closed_* counts are the alerts closed that tick by disposition.
+CREATE TABLE IF NOT EXISTS alert_aging (
+    run_id                TEXT    NOT NULL REFERENCES run(run_id),
+    tick                  INTEGER NOT NULL,
+    open_alerts           INTEGER NOT NULL,
+    investigating_alerts  INTEGER NOT NULL,
+    avg_age_ticks         REAL    NOT NULL,
+    max_age_ticks         INTEGER NOT NULL,
+    aged_alerts           INTEGER NOT NULL,
+    closed_false_positive INTEGER NOT NULL,
+    closed_suspicious     INTEGER NOT NULL,
+    closed_sar_filed      INTEGER NOT NULL,
+    PRIMARY KEY (run_id, tick)
+);
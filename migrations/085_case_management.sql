@@ -0,0 +1,51 @@
+-- Phase 4.86: Case management
+--
+-- investigation_case: one investigation across the silos, grouping the
+-- AML alerts, fraud alerts, screening hits, disputes and complaints of a
+-- customer or relationship cluster. customer_id is the customer of the
+-- item that opened it; last_activity_tick is when an item last joined.
+-- status: 'open' | 'closed'. disposition, set at closing: 'unfounded' |
+-- 'confirmed' when an analyst closes it, 'worked' when every linked item
+-- was closed on its own desk first.
+CREATE TABLE IF NOT EXISTS investigation_case (
+    run_id             TEXT    NOT NULL REFERENCES run(run_id),
+    case_id            TEXT    NOT NULL,
+    customer_id        TEXT    NOT NULL,
+    opened_tick        INTEGER NOT NULL,
+    last_activity_tick INTEGER NOT NULL,
+    item_count         INTEGER NOT NULL,
+    assigned_to        TEXT,
+    status             TEXT    NOT NULL DEFAULT 'open',
+    disposition        TEXT,
+    closed_tick        INTEGER,
+    PRIMARY KEY (run_id, case_id)
+);
+CREATE INDEX IF NOT EXISTS idx_investigation_case_open ON investigation_case (run_id, status, customer_id);
+
+-- case_item: an item linked into a case. item_type: 'aml_alert' |
+-- 'fraud_alert' | 'screening_hit' | 'dispute' | 'complaint'; item_id is
+-- the row's id in its own table. disposition is what closing the case
+-- set on the item, NULL if the case left it to its own desk.
+CREATE TABLE IF NOT EXISTS case_item (
+    run_id      TEXT    NOT NULL REFERENCES run(run_id),
+    case_id     TEXT    NOT NULL,
+    item_type   TEXT    NOT NULL,
+    item_id     TEXT    NOT NULL,
+    customer_id TEXT    NOT NULL,
+    linked_tick INTEGER NOT NULL,
+    disposition TEXT,
+    PRIMARY KEY (run_id, item_type, item_id)
+);
+CREATE INDEX IF NOT EXISTS idx_case_item_case ON case_item (run_id, case_id);
+
+-- case_note: the analyst's notes on a case, one per command.
+-- action: 'assign' | 'close'.
+CREATE TABLE IF NOT EXISTS case_note (
+    run_id  TEXT    NOT NULL REFERENCES run(run_id),
+    case_id TEXT    NOT NULL,
+    tick    INTEGER NOT NULL,
+    analyst TEXT,
+    action  TEXT    NOT NULL,
+    note    TEXT    NOT NULL
+);
+CREATE INDEX IF NOT EXISTS idx_case_note_case ON case_note (run_id, case_id, tick);
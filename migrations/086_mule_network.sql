@@ -0,0 +1,49 @@
+-- Phase 4.87: Money mule networks
+--
+-- mule_network: ground truth for one network — a controller customer
+-- and the customers recruited as its mules. status: 'active' |
+-- 'dissolved'; a network dissolves lifetime_ticks after it was seeded.
+CREATE TABLE IF NOT EXISTS mule_network (
+    run_id                TEXT    NOT NULL REFERENCES run(run_id),
+    network_id            TEXT    NOT NULL,
+    controller_id         TEXT    NOT NULL,
+    controller_account_id TEXT    NOT NULL,
+    seeded_tick           INTEGER NOT NULL,
+    mule_count            INTEGER NOT NULL,
+    status                TEXT    NOT NULL DEFAULT 'active',
+    dissolved_tick        INTEGER,
+    PRIMARY KEY (run_id, network_id)
+);
+
+-- mule_network_member: a recruited mule. next_tick is the mule's next
+-- round of inbound credits; pending is what it holds to forward on
+-- forward_tick.
+CREATE TABLE IF NOT EXISTS mule_network_member (
+    run_id         TEXT    NOT NULL REFERENCES run(run_id),
+    network_id     TEXT    NOT NULL,
+    customer_id    TEXT    NOT NULL,
+    account_id     TEXT    NOT NULL,
+    recruited_tick INTEGER NOT NULL,
+    next_tick      INTEGER NOT NULL,
+    forward_tick   INTEGER,
+    pending        REAL    NOT NULL DEFAULT 0.0,
+    PRIMARY KEY (run_id, customer_id)
+);
+CREATE INDEX IF NOT EXISTS idx_mule_network_member_network ON mule_network_member (run_id, network_id);
+
+-- mule_transfer: every transaction the typology booked, one row each.
+-- leg: 'inbound' (a credit into a mule from an outside sender) |
+-- 'forward' (a mule's transfer on to the controller) | 'collect' (the
+-- controller's side of it) | 'cash_out' (the controller's wire abroad).
+CREATE TABLE IF NOT EXISTS mule_transfer (
+    txn_id       TEXT    PRIMARY KEY,
+    run_id       TEXT    NOT NULL REFERENCES run(run_id),
+    network_id   TEXT    NOT NULL,
+    customer_id  TEXT    NOT NULL,
+    account_id   TEXT    NOT NULL,
+    tick         INTEGER NOT NULL,
+    leg          TEXT    NOT NULL,
+    counterparty TEXT    NOT NULL,
+    amount       REAL    NOT NULL
+);
+CREATE INDEX IF NOT EXISTS idx_mule_transfer_tick ON mule_transfer (run_id, tick);
@@ -0,0 +1,32 @@
+-- Phase 4.88: Structuring
+--
+-- structuring_label: ground truth, never shown to the player. Each
+-- customer at a risky composite risk level is labeled once, the tick it
+-- is first seen there: 'structurer' | 'benign'. A structurer's next
+-- episode starts at next_tick; while one runs, episode_start is set and
+-- deposits_left counts the deposits still to make.
+CREATE TABLE IF NOT EXISTS structuring_label (
+    run_id        TEXT    NOT NULL REFERENCES run(run_id),
+    customer_id   TEXT    NOT NULL,
+    label         TEXT    NOT NULL,
+    risk_level    TEXT    NOT NULL,
+    labeled_tick  INTEGER NOT NULL,
+    next_tick     INTEGER,
+    episode_start INTEGER,
+    deposits_left INTEGER NOT NULL DEFAULT 0,
+    episodes      INTEGER NOT NULL DEFAULT 0,
+    PRIMARY KEY (run_id, customer_id)
+);
+
+-- structuring_deposit: ground truth, one row per cash deposit a
+-- structurer made, with the episode it belongs to.
+CREATE TABLE IF NOT EXISTS structuring_deposit (
+    txn_id      TEXT    PRIMARY KEY,
+    run_id      TEXT    NOT NULL REFERENCES run(run_id),
+    customer_id TEXT    NOT NULL,
+    account_id  TEXT    NOT NULL,
+    tick        INTEGER NOT NULL,
+    amount      REAL    NOT NULL,
+    episode     INTEGER NOT NULL
+);
+CREATE INDEX IF NOT EXISTS idx_structuring_deposit_customer ON structuring_deposit (run_id, customer_id, tick);
@@ -0,0 +1,15 @@
+-- Phase 4.89: Sanctions name matching
+--
+-- How a screening hit's match_score was reached: the name or alias
+-- matched and whether country and year of birth agreed with the listing.
+ALTER TABLE aml_screening_result ADD COLUMN match_explanation TEXT;
+
+-- Ground truth for sanctions hits, never shown to the player: 1 when the
+-- customer is the listed party, 0 for a namesake. NULL on other
+-- screening types.
+ALTER TABLE aml_screening_result ADD COLUMN true_match INTEGER;
+
+-- Years of birth for the listed individuals, to corroborate name matches.
+UPDATE ofac_watchlist SET date_of_birth = '1968-03-14' WHERE entity_id = 'OFAC-SDN-001';
+UPDATE ofac_watchlist SET date_of_birth = '1975-11-02' WHERE entity_id = 'OFAC-SDN-002';
+UPDATE ofac_watchlist SET date_of_birth = '1981-07-19' WHERE entity_id = 'OFAC-SDN-004';
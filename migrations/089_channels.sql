@@ -0,0 +1,26 @@
+-- Phase 4.90: Service channels
+--
+-- transactions.channel: the channel a customer-initiated debit went
+-- through, 'branch' | 'atm' | 'online' | 'mobile' | 'call_center'; NULL
+-- for card purchases and for what the bank books itself.
+-- complaint.service_channel: the channel a complaint is about, where
+-- known; complaint.channel stays how the complaint reached the bank.
+ALTER TABLE transactions ADD COLUMN channel TEXT;
+ALTER TABLE complaint ADD COLUMN service_channel TEXT;
+
+-- channel_activity: one row per channel per tick. status is the worst
+-- incident open on the channel's component, 'up' | 'degraded' | 'down'.
+-- completed counts what the channel served, diverted and abandoned what
+-- it turned away that went elsewhere or was given up. cost is completed
+-- times the channel's cost to serve, booked as opex.
+CREATE TABLE IF NOT EXISTS channel_activity (
+    run_id    TEXT    NOT NULL REFERENCES run(run_id),
+    tick      INTEGER NOT NULL,
+    channel   TEXT    NOT NULL,
+    status    TEXT    NOT NULL,
+    completed INTEGER NOT NULL DEFAULT 0,
+    diverted  INTEGER NOT NULL DEFAULT 0,
+    abandoned INTEGER NOT NULL DEFAULT 0,
+    cost      REAL    NOT NULL DEFAULT 0.0,
+    PRIMARY KEY (run_id, tick, channel)
+);
@@ -0,0 +1,45 @@
+-- Phase 4.91: Instant payments and authorized push payment fraud
+--
+-- FedNow joins RTP as an instant rail: settled within the tick it is
+-- sent, and irrevocable once it is.
+-- app_scam_payment: every instant payment a customer was talked into
+-- sending a scammer. report_tick is when the victim will report it,
+-- NULL if they never do; claim_status is NULL until then, 'pending'
+-- while the desk decides, then 'reimbursed' or 'declined' under the
+-- policy in force on decided_tick.
+-- pnl_snapshot: reimbursements the bank paid, and what victims whose
+-- claims it decided were left to bear.
+INSERT
+    OR IGNORE INTO payment_rail (
+        rail_id,
+        rail_type,
+        latency_type,
+        settlement_delay_ticks,
+        fraud_risk_multiplier,
+        operational_risk_base,
+        batch_window_ticks,
+        cutoff_time_tick
+    )
+VALUES ('FedNow', 'FedNow', 'instant', 0, 2.0, 0.0015, NULL, NULL);
+
+CREATE TABLE IF NOT EXISTS app_scam_payment (
+    txn_id       TEXT    PRIMARY KEY,
+    run_id       TEXT    NOT NULL REFERENCES run(run_id),
+    customer_id  TEXT    NOT NULL,
+    account_id   TEXT    NOT NULL,
+    tick         INTEGER NOT NULL,
+    amount       REAL    NOT NULL,
+    rail_id      TEXT    NOT NULL,
+    scam_type    TEXT    NOT NULL,
+    payee        TEXT    NOT NULL,
+    vulnerable   INTEGER NOT NULL DEFAULT 0,
+    report_tick  INTEGER,
+    claim_status TEXT,
+    decided_tick INTEGER,
+    policy       TEXT,
+    reimbursed   REAL    NOT NULL DEFAULT 0.0
+);
+CREATE INDEX IF NOT EXISTS idx_app_scam_payment_run ON app_scam_payment (run_id, report_tick);
+
+ALTER TABLE pnl_snapshot ADD COLUMN app_fraud_loss REAL NOT NULL DEFAULT 0.0;
+ALTER TABLE pnl_snapshot ADD COLUMN app_customer_loss REAL NOT NULL DEFAULT 0.0;
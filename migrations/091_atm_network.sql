@@ -0,0 +1,46 @@
+-- Phase 4.92: ATM network
+--
+-- system_component 'atm_fleet': the bank's ATMs. A device fault is an
+-- incident against it naming the ATM; a P0 or P1 takes the whole fleet
+-- out of service.
+-- atm: one row per ATM per run. status is 'in_service' or
+-- 'out_of_service'; while out, incident_id is the fault that took it
+-- out and out_since the tick it did.
+-- transactions.atm_id: the ATM a withdrawal on the ATM channel was made
+-- at; NULL for everything else.
+-- atm_cash_out: every withdrawal made with a cloned card in a cash-out,
+-- with the ATM the crew hit first.
+INSERT
+    OR IGNORE INTO system_component (
+        component_id,
+        label,
+        category,
+        mtbf_days,
+        mttr_hours
+    )
+VALUES ('atm_fleet', 'ATM Fleet', 'channels', 150.0, 24.0);
+
+CREATE TABLE IF NOT EXISTS atm (
+    run_id      TEXT    NOT NULL REFERENCES run(run_id),
+    atm_id      TEXT    NOT NULL,
+    location    TEXT    NOT NULL,
+    status      TEXT    NOT NULL DEFAULT 'in_service',
+    incident_id TEXT,
+    out_since   INTEGER,
+    PRIMARY KEY (run_id, atm_id)
+);
+
+ALTER TABLE transactions ADD COLUMN atm_id TEXT;
+CREATE INDEX IF NOT EXISTS idx_transactions_atm ON transactions (run_id, tick, atm_id);
+
+CREATE TABLE IF NOT EXISTS atm_cash_out (
+    txn_id       TEXT    PRIMARY KEY,
+    run_id       TEXT    NOT NULL REFERENCES run(run_id),
+    cash_out_id  TEXT    NOT NULL,
+    tick         INTEGER NOT NULL,
+    target_atm   TEXT    NOT NULL,
+    atm_id       TEXT    NOT NULL,
+    account_id   TEXT    NOT NULL,
+    amount       REAL    NOT NULL
+);
+CREATE INDEX IF NOT EXISTS idx_atm_cash_out_run ON atm_cash_out (run_id, tick);
@@ -0,0 +1,25 @@
+-- Phase 4.93: Merchants
+--
+-- merchant: one row per merchant per run. fraud_rate is the chance a
+-- settled purchase there is disputed, set by its risk tier. While
+-- bad_from <= tick <= bad_until the merchant has gone bad, and its
+-- purchases are disputed far more often.
+-- authorization.merchant_id, card_dispute.merchant_id: the merchant a
+-- purchase was made at and a dispute is against; NULL when merchants
+-- are off, leaving only the free-text merchant_name.
+CREATE TABLE IF NOT EXISTS merchant (
+    run_id      TEXT    NOT NULL REFERENCES run(run_id),
+    merchant_id TEXT    NOT NULL,
+    name        TEXT    NOT NULL,
+    category    TEXT    NOT NULL,
+    risk_tier   TEXT    NOT NULL,
+    fraud_rate  REAL    NOT NULL,
+    bad_from    INTEGER,
+    bad_until   INTEGER,
+    PRIMARY KEY (run_id, merchant_id)
+);
+
+ALTER TABLE authorization ADD COLUMN merchant_id TEXT;
+ALTER TABLE card_dispute ADD COLUMN merchant_id TEXT;
+CREATE INDEX IF NOT EXISTS idx_auth_merchant ON authorization (run_id, merchant_id, tick_authorized);
+CREATE INDEX IF NOT EXISTS idx_dispute_merchant ON card_dispute (run_id, merchant_id, tick_filed);
@@ -0,0 +1,38 @@
+-- Phase 4.94: Device and IP telemetry
+--
+-- customer_device: every device a customer has logged in from, with
+-- the tick it was first and last seen. shared_count is how many
+-- customers have logged in from the device, kept equal on all of its
+-- rows.
+-- device_session: one row per login, with the device and IP address it
+-- came from. new_device is set on a customer's first login from the
+-- device; takeover on a fraudster's login to a customer's account.
+-- transactions.device_id: the device a payment sent online or in the
+-- mobile app was made from; NULL for everything else.
+CREATE TABLE IF NOT EXISTS customer_device (
+    run_id          TEXT    NOT NULL REFERENCES run(run_id),
+    customer_id     TEXT    NOT NULL,
+    device_id       TEXT    NOT NULL,
+    fingerprint     TEXT    NOT NULL,
+    first_seen_tick INTEGER NOT NULL,
+    last_seen_tick  INTEGER NOT NULL,
+    shared_count    INTEGER NOT NULL DEFAULT 1,
+    PRIMARY KEY (run_id, customer_id, device_id)
+);
+CREATE INDEX IF NOT EXISTS idx_customer_device_device ON customer_device (run_id, device_id);
+
+CREATE TABLE IF NOT EXISTS device_session (
+    run_id      TEXT    NOT NULL REFERENCES run(run_id),
+    session_id  TEXT    NOT NULL,
+    tick        INTEGER NOT NULL,
+    customer_id TEXT    NOT NULL,
+    device_id   TEXT    NOT NULL,
+    ip_address  TEXT    NOT NULL,
+    new_device  INTEGER NOT NULL DEFAULT 0,
+    takeover    INTEGER NOT NULL DEFAULT 0,
+    PRIMARY KEY (run_id, session_id)
+);
+CREATE INDEX IF NOT EXISTS idx_device_session_run ON device_session (run_id, tick);
+
+ALTER TABLE transactions ADD COLUMN device_id TEXT;
+CREATE INDEX IF NOT EXISTS idx_transactions_device ON transactions (run_id, tick, device_id);
@@ -0,0 +1,6 @@
+-- Phase 4.82: Loan tradelines furnished to the credit bureau
+--
+-- bureau_furnishing.tradeline: what the row reports, 'deposit' | 'loan'.
+-- A loan row's account_id is the loan id; its statuses are the loan's
+-- delinquency bucket while open, then 'paid_off' or 'charged_off'.
+ALTER TABLE bureau_furnishing ADD COLUMN tradeline TEXT NOT NULL DEFAULT 'deposit';
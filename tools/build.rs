@@ -0,0 +1,42 @@
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    record_git_commit();
+
+    // The gRPC server is opt-in; without the feature there is nothing to generate
+    #[cfg(feature = "grpc")]
+    {
+        std::env::set_var("PROTOC", protoc_bin_vendored::protoc_bin_path()?);
+        tonic_prost_build::compile_protos("proto/fincrime.proto")?;
+    }
+    Ok(())
+}
+
+/// Expose the commit being built as FINCRIME_GIT_COMMIT for run
+/// provenance. Builds outside a git checkout simply leave it unset.
+fn record_git_commit() {
+    let git = |args: &[&str]| {
+        std::process::Command::new("git")
+            .args(args)
+            .output()
+            .ok()
+            .filter(|o| o.status.success())
+            .map(|o| String::from_utf8_lossy(&o.stdout).trim().to_string())
+    };
+    let Some(commit) = git(&["rev-parse", "HEAD"]) else {
+        return;
+    };
+    let dirty = git(&["status", "--porcelain", "--untracked-files=no"]).is_some_and(|s| !s.is_empty());
+    let suffix = if dirty { "-dirty" } else { "" };
+    println!("cargo:rustc-env=FINCRIME_GIT_COMMIT={commit}{suffix}");
+
+    if let Some(git_dir) = git(&["rev-parse", "--absolute-git-dir"]) {
+        println!("cargo:rerun-if-changed={git_dir}/HEAD");
+        println!("cargo:rerun-if-changed={git_dir}/index");
+        // HEAD only names the branch; a commit moves the branch ref
+        if let Some(branch) = git(&["symbolic-ref", "-q", "HEAD"]) {
+            println!("cargo:rerun-if-changed={git_dir}/{branch}");
+        }
+    }
+    println!("cargo:rerun-if-changed=build.rs");
+    #[cfg(feature = "grpc")]
+    println!("cargo:rerun-if-changed=proto/fincrime.proto");
+}
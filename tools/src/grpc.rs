@@ -0,0 +1,233 @@
+//! gRPC server mode (Phase 4.23).
+//!
+//! Serves `proto/fincrime.proto` with the same semantics as the stdin IPC
+//! loop: Tick, GetState and Command answer with the state after the call,
+//! and a client on another protocol version is refused. StreamEvents
+//! pushes each tick's events to every subscriber as the tick completes,
+//! for dashboards that watch a run someone else is driving.
+//!
+//! Recoverable engine errors come back as INVALID_ARGUMENT, errors that
+//! halted the run as INTERNAL and calls on a halted run as
+//! FAILED_PRECONDITION.
+//!
+//! The engine is not `Sync`, so calls take turns on a mutex and run on
+//! the blocking pool; a long Tick holds the engine until it finishes.
+
+use fincrime_core::{
+    engine::SimEngine,
+    error::{SimError, SimResult},
+    protocol::{self, UiState},
+};
+use std::{
+    net::SocketAddr,
+    pin::Pin,
+    sync::{Arc, Mutex},
+};
+use tokio::sync::broadcast;
+use tokio_stream::{wrappers::BroadcastStream, Stream, StreamExt};
+use tonic::{Request, Response, Status};
+
+pub mod pb {
+    tonic::include_proto!("fincrime.v1");
+}
+
+use pb::simulation_server::{Simulation, SimulationServer};
+
+/// Ticks a subscriber may fall behind before it misses some.
+const EVENT_BUFFER: usize = 256;
+
+pub struct SimService {
+    engine: Arc<Mutex<SimEngine>>,
+    events: broadcast::Sender<pb::TickEvents>,
+}
+
+impl SimService {
+    pub fn new(engine: SimEngine) -> Self {
+        Self {
+            engine: Arc::new(Mutex::new(engine)),
+            events: broadcast::channel(EVENT_BUFFER).0,
+        }
+    }
+
+    pub fn into_server(self) -> SimulationServer<Self> {
+        SimulationServer::new(self)
+    }
+
+    /// Run `f` against the engine on the blocking pool.
+    async fn with_engine<T, F>(&self, f: F) -> Result<T, Status>
+    where
+        T: Send + 'static,
+        F: FnOnce(&mut SimEngine) -> SimResult<T> + Send + 'static,
+    {
+        let engine = Arc::clone(&self.engine);
+        tokio::task::spawn_blocking(move || {
+            let mut engine = engine.lock().unwrap_or_else(|e| e.into_inner());
+            f(&mut engine)
+        })
+        .await
+        .map_err(|e| Status::internal(e.to_string()))?
+        .map_err(|e| to_status(&e))
+    }
+}
+
+/// Recoverable errors are the caller's to fix; a halted run will refuse
+/// every call from now on.
+fn to_status(e: &SimError) -> Status {
+    match e {
+        SimError::RunHalted { .. } => Status::failed_precondition(e.to_string()),
+        _ if e.is_fatal() => Status::internal(e.to_string()),
+        _ => Status::invalid_argument(e.to_string()),
+    }
+}
+
+/// Serve the engine on `addr` until the process is stopped.
+pub async fn serve(engine: SimEngine, addr: SocketAddr) -> Result<(), tonic::transport::Error> {
+    tonic::transport::Server::builder()
+        .add_service(SimService::new(engine).into_server())
+        .serve(addr)
+        .await
+}
+
+#[tonic::async_trait]
+impl Simulation for SimService {
+    async fn get_protocol_version(
+        &self,
+        request: Request<pb::VersionRequest>,
+    ) -> Result<Response<pb::VersionReply>, Status> {
+        protocol::check_client_version(request.into_inner().client_version)
+            .map(|v| {
+                Response::new(pb::VersionReply {
+                    protocol_version: v.protocol_version,
+                })
+            })
+            .map_err(|refusal| Status::failed_precondition(refusal.error))
+    }
+
+    async fn get_state(&self, _: Request<pb::StateRequest>) -> Result<Response<pb::State>, Status> {
+        let state = self.with_engine(|engine| UiState::from_engine(engine)).await?;
+        Ok(Response::new(state.into()))
+    }
+
+    async fn tick(&self, request: Request<pb::TickRequest>) -> Result<Response<pb::State>, Status> {
+        let count = request.into_inner().count;
+        let events = self.events.clone();
+        let state = self
+            .with_engine(move |engine| {
+                for _ in 0..count {
+                    engine.run_ticks(1)?;
+                    let tick = engine.clock.current_tick;
+                    let entries = engine.store_events_for_tick(&engine.run_id, tick)?;
+                    // No subscribers is not an error
+                    let _ = events.send(pb::TickEvents {
+                        tick,
+                        events: entries
+                            .into_iter()
+                            .map(|e| pb::Event {
+                                subsystem: e.subsystem,
+                                event_type: e.event_type,
+                                payload_json: e.payload,
+                            })
+                            .collect(),
+                    });
+                }
+                UiState::from_engine(engine)
+            })
+            .await?;
+        Ok(Response::new(state.into()))
+    }
+
+    async fn command(&self, request: Request<pb::CommandRequest>) -> Result<Response<pb::State>, Status> {
+        let pb::CommandRequest { cmd, payload_json } = request.into_inner();
+        let payload: serde_json::Value = if payload_json.trim().is_empty() {
+            serde_json::json!({})
+        } else {
+            serde_json::from_str(&payload_json)
+                .map_err(|e| Status::invalid_argument(format!("payload_json: {e}")))?
+        };
+        let state = self
+            .with_engine(move |engine| {
+                protocol::apply_command(engine, &cmd, &payload)?;
+                UiState::from_engine(engine)
+            })
+            .await?;
+        Ok(Response::new(state.into()))
+    }
+
+    type StreamEventsStream = Pin<Box<dyn Stream<Item = Result<pb::TickEvents, Status>> + Send>>;
+
+    async fn stream_events(
+        &self,
+        _: Request<pb::StreamRequest>,
+    ) -> Result<Response<Self::StreamEventsStream>, Status> {
+        let stream = BroadcastStream::new(self.events.subscribe()).filter_map(|item| match item {
+            Ok(events) => Some(Ok(events)),
+            Err(e) => {
+                tracing::warn!("event stream subscriber {e}");
+                None
+            }
+        });
+        Ok(Response::new(Box::pin(stream)))
+    }
+}
+
+impl From<UiState> for pb::State {
+    fn from(s: UiState) -> Self {
+        Self {
+            run_id: s.run_id,
+            tick: s.tick,
+            paused: s.paused,
+            active_customers: s.active_customers,
+            churned_customers: s.churned_customers,
+            complaint_count: s.complaint_count,
+            sla_breaches: s.sla_breaches,
+            backlog: s.backlog,
+            nim: s.nim,
+            efficiency_ratio: s.efficiency_ratio,
+            pre_tax_profit: s.pre_tax_profit,
+            pnl_history: s
+                .pnl_history
+                .into_iter()
+                .map(|p| pb::PnlSnapshot {
+                    tick: p.tick,
+                    period: p.period,
+                    nii: p.nii,
+                    fee_income: p.fee_income,
+                    gross_income: p.gross_income,
+                    credit_loss: p.credit_loss,
+                    fraud_loss: p.fraud_loss,
+                    opex: p.opex,
+                    complaint_cost: p.complaint_cost,
+                    pre_tax_profit: p.pre_tax_profit,
+                    nim: p.nim,
+                    efficiency_ratio: p.efficiency_ratio,
+                    avg_deposits: p.avg_deposits,
+                    avg_loans: p.avg_loans,
+                    customer_count: p.customer_count,
+                    active_accounts: p.active_accounts,
+                })
+                .collect(),
+            complaints: s
+                .complaints
+                .into_iter()
+                .map(|c| pb::Complaint {
+                    complaint_id: c.complaint_id,
+                    customer_id: c.customer_id,
+                    account_id: c.account_id,
+                    tick_opened: c.tick_opened,
+                    tick_closed: c.tick_closed,
+                    product: c.product,
+                    issue: c.issue,
+                    priority: c.priority,
+                    status: c.status,
+                    sla_due_tick: c.sla_due_tick,
+                    sla_breached: c.sla_breached,
+                    resolution_code: c.resolution_code,
+                    amount_refunded: c.amount_refunded,
+                    udaap_flag: c.udaap_flag,
+                    channel: c.channel,
+                })
+                .collect(),
+            population_scale: s.population_scale,
+        }
+    }
+}
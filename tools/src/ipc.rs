@@ -0,0 +1,175 @@
+//! The JSON IPC protocol's request handling (Phase 4.75), shared by the
+//! stdin loop and the WebSocket server.
+//!
+//! Each request is answered with exactly one JSON line written to `out`.
+//! Session commands (`get_protocol_version`, `create_run`, `list_runs`,
+//! `close_run`, `quit`) act on the hosted runs; the rest go to the run
+//! the request names, or the default run.
+
+use anyhow::Result;
+use fincrime_core::{
+    breakpoint::BreakpointList,
+    command_result::CommandResult,
+    entity_timeline::EntityTimeline,
+    error::{SimError, SimResult},
+    protocol::{self, EngineDescription, ErrorResponse, IpcCommand, IpcRequest, UiState},
+    queue::QueuePage,
+    queue_aging::QueueAging,
+    readonly_query::QueryResult,
+    rule_backtest::BacktestReport,
+    run_manager::{ManagedRun, RunManager},
+    state_delta::DeltaTracker,
+};
+use std::io::Write;
+
+/// Parse one request and answer it; a line that is not a request is
+/// answered with an `invalid_message` error. Returns false once the
+/// session should end.
+pub fn handle_line(runs: &mut RunManager, line: &str, out: &mut impl Write) -> Result<bool> {
+    match serde_json::from_str(line) {
+        Ok(request) => handle_request(runs, request, out),
+        Err(e) => {
+            writeln!(out, "{}", serde_json::to_string(&ErrorResponse::invalid_message(e))?)?;
+            Ok(true)
+        }
+    }
+}
+
+/// Answer one request. Returns false once the session should end: on
+/// `quit`, a refused protocol version, or the last run halting.
+pub fn handle_request(runs: &mut RunManager, request: IpcRequest, out: &mut impl Write) -> Result<bool> {
+    let IpcRequest { run_id, command } = request;
+    // Phase 4.70: session commands first; the rest go to one run
+    match command {
+        IpcCommand::Quit => return Ok(false),
+        IpcCommand::GetProtocolVersion { client_version } => match protocol::check_client_version(client_version) {
+            Ok(version) => writeln!(out, "{}", serde_json::to_string(&version)?)?,
+            Err(refusal) => {
+                // A client on another protocol version cannot be served
+                writeln!(out, "{}", serde_json::to_string(&refusal)?)?;
+                return Ok(false);
+            }
+        },
+        IpcCommand::CreateRun { seed } => {
+            write_reply(out, runs.create(run_id.as_deref(), seed))?;
+        }
+        IpcCommand::ListRuns => {
+            write_reply(out, Ok(runs.list()))?;
+        }
+        IpcCommand::CloseRun => {
+            write_reply(out, runs.close(run_id.as_deref()))?;
+        }
+        command => match runs.run_mut(command.name(), run_id.as_deref()) {
+            Ok(run) => {
+                if !serve_run(out, run, command)? {
+                    // A run that halts is closed; the session ends with the last one
+                    let halted = run.engine.run_id.clone();
+                    runs.close(Some(&halted))?;
+                    return Ok(!runs.is_empty());
+                }
+            }
+            Err(e) => {
+                write_reply(out, Err::<(), _>(e))?;
+            }
+        },
+    }
+    Ok(true)
+}
+
+/// Answer one request for a hosted run. Returns false once the run hit a
+/// fatal error and should be closed.
+fn serve_run(out: &mut impl Write, run: &mut ManagedRun, command: IpcCommand) -> Result<bool> {
+    let ManagedRun { engine, tracker } = run;
+    match command {
+        IpcCommand::DescribeEngine => {
+            let description = EngineDescription::from_engine(engine);
+            writeln!(out, "{}", serde_json::to_string(&description)?)?;
+            Ok(true)
+        }
+        IpcCommand::Tick { count, summary_only } => {
+            let state = engine.run_steps(count).and_then(|()| UiState::build(engine, summary_only));
+            write_state(out, tracker, state)
+        }
+        IpcCommand::GetState { summary_only } => write_state(out, tracker, UiState::build(engine, summary_only)),
+        IpcCommand::Command { cmd, payload, summary_only, role } => {
+            let result = CommandResult::apply(engine, &cmd, &payload, role)
+                .and_then(|result| result.with_state(engine, summary_only));
+            if let Ok(CommandResult { state: Some(state), .. }) = &result {
+                tracker.record(state);
+            }
+            write_reply(out, result)
+        }
+        IpcCommand::GetStateDelta { since_tick, event_types } => {
+            write_reply(out, tracker.delta(engine, since_tick, &event_types))
+        }
+        IpcCommand::QueryQueue { queue, filter, offset, limit } => {
+            write_reply(out, QueuePage::query(&engine.store, &engine.run_id, queue, &filter, offset, limit))
+        }
+        IpcCommand::GetQueueAging => {
+            write_reply(out, QueueAging::query(&engine.store, &engine.run_id, engine.clock.current_tick))
+        }
+        IpcCommand::GetEntityTimeline { entity_type, entity_id, from_tick, to_tick } => {
+            let timeline =
+                EntityTimeline::query(&engine.store, &engine.run_id, entity_type, &entity_id, from_tick, to_tick);
+            write_reply(out, timeline)
+        }
+        IpcCommand::QueryReadonly { sql, params, limit } => {
+            write_reply(out, QueryResult::query(&engine.store, &sql, &params, limit))
+        }
+        IpcCommand::BacktestRule { rule, from_tick, to_tick } => {
+            let report = BacktestReport::run(
+                &engine.store,
+                &engine.run_id,
+                engine.clock.current_tick,
+                rule,
+                from_tick,
+                to_tick,
+            );
+            write_reply(out, report)
+        }
+        IpcCommand::SetBreakpoint { breakpoint } => {
+            let list = engine.set_breakpoint(breakpoint).map(|()| BreakpointList { breakpoints: engine.breakpoints() });
+            write_reply(out, list)
+        }
+        IpcCommand::ClearBreakpoint { id } => {
+            let list = if engine.clear_breakpoint(&id) {
+                Ok(BreakpointList { breakpoints: engine.breakpoints() })
+            } else {
+                Err(SimError::command("clear_breakpoint", format!("no breakpoint {id:?}")))
+            };
+            write_reply(out, list)
+        }
+        IpcCommand::ListBreakpoints => write_reply(out, Ok(BreakpointList { breakpoints: engine.breakpoints() })),
+        IpcCommand::GetProtocolVersion { .. }
+        | IpcCommand::CreateRun { .. }
+        | IpcCommand::ListRuns
+        | IpcCommand::CloseRun
+        | IpcCommand::Quit => unreachable!("session commands are answered by handle_request"),
+    }
+}
+
+/// Answer with the state, noting it for later deltas, or with a
+/// structured error. Returns false once the error was fatal and the
+/// session should end.
+fn write_state(out: &mut impl Write, tracker: &mut DeltaTracker, state: SimResult<UiState>) -> Result<bool> {
+    if let Ok(state) = &state {
+        tracker.record(state);
+    }
+    write_reply(out, state)
+}
+
+/// Answer with a reply, or with a structured error. Returns false once
+/// the error was fatal and the session should end.
+fn write_reply(out: &mut impl Write, reply: SimResult<impl serde::Serialize>) -> Result<bool> {
+    match reply {
+        Ok(reply) => {
+            writeln!(out, "{}", serde_json::to_string(&reply)?)?;
+            Ok(true)
+        }
+        Err(e) => {
+            writeln!(out, "{}", serde_json::to_string(&ErrorResponse::from(&e))?)?;
+            out.flush()?;
+            Ok(!e.is_fatal())
+        }
+    }
+}
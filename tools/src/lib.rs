@@ -1 +1,7 @@
-// fincrime-tools — placeholder for Phase 1+
+//! fincrime-tools: services sim-runner exposes beyond the stdin IPC loop.
+
+#[cfg(feature = "grpc")]
+pub mod grpc; // Phase 4.23
+pub mod ipc; // Phase 4.75
+#[cfg(feature = "ws")]
+pub mod ws; // Phase 4.75
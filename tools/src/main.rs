@@ -2,50 +2,64 @@
 //!
 //! Usage:
 //!   sim-runner --seed 12345 --ticks 365 --db run.db
+//!   sim-runner --seed 12345 --ticks 365 --report post_mortem.json
 //!   sim-runner --seed 12345 --connect-port 9000
+//!   sim-runner --print-schema > docs/ipc_protocol.schema.json
+//!   sim-runner --db run.db --describe-run run-12345-1700000000
+//!   sim-runner --db run.db --scrub-run run-12345-1700000000
+//!   sim-runner --db run.db --resume run-12345-1700000000 --ticks 365
+//!   sim-runner --seed 12345 --ticks 365 --scenario recession.toml
+//!   sim-runner --seed 12345 --grpc-port 50051   (built with --features grpc)
+//!   sim-runner --seed 12345 --ws-port 8765      (built with --features ws)
+//!   sim-runner --seed 12345 --ticks 3650 --db run.db --event-log-detail-ticks 90
+//!   sim-runner --seed 12345 --ticks 365 --seed-sensitivity 10 --report sensitivity.json
+//!   sim-runner batch --seeds 1..100 --ticks 365 --out results/
+//!   sim-runner --seed 12345 --ticks 365 --export-dir export/ --export-format parquet   (built with --features parquet)
+//!   sim-runner --seed 12345 --ticks 365 --sar-export-dir filings/ --sar-export-format xml
+//!   sim-runner --seed 12345 --ticks 3650 --event-stream events.jsonl --event-stream-rotate-bytes 10000000
+//!   sim-runner --leaderboard tournament.json --report leaderboard.json
 
 use anyhow::Result;
-use fincrime_core::{engine::SimEngine, store::SimStore, types::Tick};
+use fincrime_core::{
+    batch::{self, KPI_FILE},
+    config::{EventLogRetentionConfig, EventStreamConfig, SimConfig},
+    engine::SimEngine,
+    experiment::Scenario,
+    export::{self, ExportFormat},
+    golden::{GoldenDiff, GoldenRun, GOLDEN_POPULATION_SCALE, GOLDEN_SEED, GOLDEN_TICKS},
+    post_mortem::PostMortem,
+    provenance::{self, RunProvenance},
+    protocol,
+    run_manager::{RunManager, RunTemplate},
+    sar_export::{self, SarExportFormat},
+    scenario,
+    seed_sensitivity::{SeedSensitivity, DEFAULT_FRAGILE_CV},
+    store::SimStore,
+    tournament::Tournament,
+};
+use sim_runner::ipc;
 use std::env;
-use std::io::{self, BufRead, BufReader, Write};
-use std::net::TcpStream;
-
-#[derive(serde::Deserialize)]
-#[serde(tag = "type", rename_all = "snake_case")]
-enum IpcCommand {
-    GetState,
-    Tick {
-        count: u64,
-    },
-    Command {
-        cmd: String,
-        payload: serde_json::Value,
-    },
-    Quit,
-}
+use std::io::{self, BufRead, Write};
+use std::path::Path;
 
-#[derive(serde::Serialize)]
-struct UiState {
-    tick: Tick,
-    paused: bool,
-    active_customers: i64,
-    churned_customers: i64,
-    complaint_count: i64,
-    sla_breaches: i64,
-    backlog: i64,
-    nim: f64,
-    efficiency_ratio: f64,
-    pre_tax_profit: f64,
-    pnl_history: Vec<fincrime_core::economics_subsystem::PnLSnapshot>,
-    complaints: Vec<fincrime_core::complaint_subsystem::ComplaintRecord>,
-}
+/// Cargo features this binary was built with, for run provenance.
+const ENABLED_FEATURES: &[&str] = &[
+    #[cfg(feature = "grpc")]
+    "grpc",
+    #[cfg(feature = "ws")]
+    "ws",
+];
 
 fn main() -> Result<()> {
-    env_logger::init();
-
     let args: Vec<String> = env::args().collect();
+    init_logging(args.iter().any(|a| a == "--log-json"));
+    if args.iter().any(|a| a == "--print-schema") {
+        println!("{}", serde_json::to_string_pretty(&protocol::schema())?);
+        return Ok(());
+    }
     let seed = parse_arg(&args, "--seed", 42u64);
     let ticks = parse_arg(&args, "--ticks", 365u64);
+    let population_scale = parse_arg(&args, "--population-scale", 1.0f64);
     let ipc_mode = args.iter().any(|a| a == "--ipc-mode");
     let db = args
         .windows(2)
@@ -57,13 +71,249 @@ fn main() -> Result<()> {
         .find(|w| w[0] == "--data-dir")
         .map(|w| w[1].as_str())
         .unwrap_or("./data");
+    let report_path = args
+        .windows(2)
+        .find(|w| w[0] == "--report")
+        .map(|w| w[1].clone());
+    let describe_run = args
+        .windows(2)
+        .find(|w| w[0] == "--describe-run")
+        .map(|w| w[1].as_str());
+    let scrub_run = args
+        .windows(2)
+        .find(|w| w[0] == "--scrub-run")
+        .map(|w| w[1].as_str());
+    let purge_run = args
+        .windows(2)
+        .find(|w| w[0] == "--purge-run")
+        .map(|w| w[1].as_str());
+    let grpc_port = args
+        .windows(2)
+        .find(|w| w[0] == "--grpc-port")
+        .and_then(|w| w[1].parse::<u16>().ok());
+    let ws_port = args
+        .windows(2)
+        .find(|w| w[0] == "--ws-port")
+        .and_then(|w| w[1].parse::<u16>().ok());
+    let event_log_detail_ticks = args
+        .windows(2)
+        .find(|w| w[0] == "--event-log-detail-ticks")
+        .and_then(|w| w[1].parse::<u64>().ok());
+    let event_log_export = args
+        .windows(2)
+        .find(|w| w[0] == "--event-log-export")
+        .map(|w| w[1].clone());
+    let event_stream = args
+        .windows(2)
+        .find(|w| w[0] == "--event-stream")
+        .map(|w| w[1].clone());
+    let golden_record = args
+        .windows(2)
+        .find(|w| w[0] == "--golden-record")
+        .map(|w| w[1].as_str());
+    let golden_check = args
+        .windows(2)
+        .find(|w| w[0] == "--golden-check")
+        .map(|w| w[1].as_str());
+    let leaderboard = args
+        .windows(2)
+        .find(|w| w[0] == "--leaderboard")
+        .map(|w| w[1].as_str());
+    let seed_sensitivity = args
+        .windows(2)
+        .find(|w| w[0] == "--seed-sensitivity")
+        .and_then(|w| w[1].parse::<usize>().ok());
+    let resume = args
+        .windows(2)
+        .find(|w| w[0] == "--resume")
+        .map(|w| w[1].clone());
+    let scenario_path = args
+        .windows(2)
+        .find(|w| w[0] == "--scenario")
+        .map(|w| w[1].as_str());
+    let export_dir = args
+        .windows(2)
+        .find(|w| w[0] == "--export-dir")
+        .map(|w| w[1].as_str());
+    // Phase 4.79: an unknown or unbuilt format fails before the run
+    let export_format: ExportFormat = args
+        .windows(2)
+        .find(|w| w[0] == "--export-format")
+        .map_or(Ok(ExportFormat::Csv), |w| w[1].parse())?;
+    let sar_export_dir = args
+        .windows(2)
+        .find(|w| w[0] == "--sar-export-dir")
+        .map(|w| w[1].as_str());
+    let sar_export_format: SarExportFormat = args
+        .windows(2)
+        .find(|w| w[0] == "--sar-export-format")
+        .map_or(Ok(SarExportFormat::Json), |w| w[1].parse())?;
+
+    // Reads an existing database; migrating it again would fail
+    if let Some(run_id) = describe_run {
+        let store = SimStore::open(db)?;
+        let description = store
+            .describe_run(run_id)?
+            .ok_or_else(|| anyhow::anyhow!("no run {run_id} in {db}"))?;
+        println!("{}", serde_json::to_string_pretty(&description)?);
+        return Ok(());
+    }
+    // Phase 4.50: retention operations on an existing database
+    if let Some(run_id) = scrub_run {
+        let report = SimStore::open(db)?.scrub_run(run_id)?;
+        println!("{}", serde_json::to_string_pretty(&report)?);
+        return Ok(());
+    }
+    if let Some(run_id) = purge_run {
+        let report = SimStore::open(db)?.purge_run(run_id)?;
+        println!("{}", serde_json::to_string_pretty(&report)?);
+        return Ok(());
+    }
+
+    // Phase 4.71: score the runs a tournament file names
+    if let Some(path) = leaderboard {
+        let mut tournament: Tournament = serde_json::from_str(
+            &std::fs::read_to_string(path).map_err(|e| anyhow::anyhow!("Cannot read {path}: {e}"))?,
+        )?;
+        if tournament.scenario_hash.is_none() {
+            tournament.scenario_hash = Some(provenance::hash_scenario(data_dir)?);
+        }
+        let base_dir = Path::new(path).parent().unwrap_or(Path::new("."));
+        let board = serde_json::to_string_pretty(&tournament.leaderboard(base_dir)?)?;
+        match &report_path {
+            Some(report) => {
+                std::fs::write(report, board + "\n")?;
+                println!("leaderboard for {} written to {report}", tournament.name);
+            }
+            None => println!("{board}"),
+        }
+        return Ok(());
+    }
+
+    // Phase 4.49: golden runs use their own seed, length and database
+    if let Some(path) = golden_record {
+        let golden = GoldenRun::record(data_dir, GOLDEN_SEED, GOLDEN_TICKS, GOLDEN_POPULATION_SCALE)?;
+        std::fs::write(path, serde_json::to_string_pretty(&golden)? + "\n")?;
+        println!("golden run recorded to {path}: seed {}, {} ticks", golden.seed, golden.ticks);
+        return Ok(());
+    }
+    if let Some(path) = golden_check {
+        let recorded: GoldenRun = serde_json::from_str(
+            &std::fs::read_to_string(path).map_err(|e| anyhow::anyhow!("Cannot read {path}: {e}"))?,
+        )?;
+        let diffs = recorded.compare(&recorded.rerun(data_dir)?);
+        for diff in &diffs {
+            println!("  {diff}");
+        }
+        if diffs.iter().any(|d| matches!(d, GoldenDiff::Table { .. })) {
+            anyhow::bail!("golden run differs from {path}; re-record it with --golden-record if the change is intended");
+        }
+        println!("golden run matches {path}");
+        return Ok(());
+    }
+
+    // Phase 4.77: fail on a bad scenario file before doing anything else
+    let scenario_file = scenario_path
+        .map(|path| {
+            let file = scenario::ScenarioFile::load(Path::new(path))
+                .and_then(|file| file.apply(&mut SimConfig::load(data_dir)?).map(|()| file));
+            file.map_err(|e| anyhow::anyhow!("{path}: {e}"))
+        })
+        .transpose()?;
+    if scenario_file.is_some() && resume.is_some() {
+        anyhow::bail!("a resumed run keeps the scenario it was built with; drop --scenario");
+    }
+
+    // Phase 4.78: a database per seed and a KPI line for each
+    if args.get(1).is_some_and(|a| a == "batch") {
+        let seeds = args
+            .windows(2)
+            .find(|w| w[0] == "--seeds")
+            .map(|w| parse_seeds(&w[1]))
+            .ok_or_else(|| anyhow::anyhow!("batch needs --seeds, such as --seeds 1..100"))??;
+        let out_dir = args.windows(2).find(|w| w[0] == "--out").map_or("results", |w| w[1].as_str());
+        let config = load_config(data_dir, scenario_file.as_ref(), population_scale)?;
+        let name = scenario_file.as_ref().and_then(|f| f.name.clone()).unwrap_or_else(|| "batch".to_string());
+        let threads = parse_arg(&args, "--threads", std::thread::available_parallelism().map_or(1, |n| n.get()));
+        let runs = seeds.len();
+        let frame = batch::run_batch(Scenario::new(name, config, ticks), seeds, threads, Path::new(out_dir))?;
+        println!("=== BATCH ===");
+        println!("  runs:           {} of {runs} finished, {ticks} ticks each", runs - frame.failures.len());
+        for s in frame.summarize() {
+            println!("  {:<15} | mean {:>14.2} | sd {:>12.2} | min {:>14.2} | max {:>14.2}", s.metric, s.mean, s.std_dev, s.min, s.max);
+        }
+        for failure in &frame.failures {
+            println!("  seed {} failed: {}", failure.seed, failure.error);
+        }
+        println!("  KPIs:           {}", Path::new(out_dir).join(KPI_FILE).display());
+        if frame.failures.len() == runs {
+            anyhow::bail!("every seed failed");
+        }
+        return Ok(());
+    }
+
+    // Phase 4.56: the same scenario from the seeds after --seed
+    if let Some(count) = seed_sensitivity {
+        let config = load_config(data_dir, scenario_file.as_ref(), population_scale)?;
+        let threads = parse_arg(&args, "--threads", std::thread::available_parallelism().map_or(1, |n| n.get()));
+        let fragile_cv = parse_arg(&args, "--fragile-cv", DEFAULT_FRAGILE_CV);
+        let report = SeedSensitivity::run(
+            Scenario::new("seed-sensitivity", config, ticks),
+            SeedSensitivity::nearby_seeds(seed, count),
+            threads,
+            fragile_cv,
+        );
+        print_seed_sensitivity(&report);
+        if let Some(path) = &report_path {
+            std::fs::write(path, serde_json::to_string_pretty(&report)?)?;
+            println!("  full report:    {path}");
+        }
+        if report.metrics.is_empty() {
+            anyhow::bail!("every seed failed");
+        }
+        return Ok(());
+    }
+
+    // Phase 4.76: carry on a stored run from where it stopped
+    let resumed = match &resume {
+        Some(run_id) => {
+            if db == ":memory:" {
+                anyhow::bail!("--resume needs the --db the run was saved to");
+            }
+            // An existing database; migrating it again would fail
+            let store = SimStore::open(db)?;
+            let description = store
+                .describe_run(run_id)?
+                .ok_or_else(|| anyhow::anyhow!("no run {run_id} in {db}"))?;
+            // The run's own sample unless this invocation overrides it
+            let scale = if args.iter().any(|a| a == "--population-scale") {
+                population_scale
+            } else {
+                parse_arg(&description.provenance.cli_args, "--population-scale", 1.0f64)
+            };
+            let engine = SimEngine::resume(run_id.clone(), &store, data_dir, scale)?;
+            Some((store, engine, scale))
+        }
+        None => None,
+    };
+    let seed = resumed.as_ref().map_or(seed, |(_, engine, _)| engine.seed());
+    let population_scale = resumed.as_ref().map_or(population_scale, |(_, _, scale)| *scale);
 
     if !ipc_mode {
         println!("FinCrime: The Desk — sim-runner");
         println!("  seed:      {seed}");
+        if let Some((_, engine, _)) = &resumed {
+            println!("  resume:    {} from tick {}", engine.run_id, engine.clock.current_tick);
+        }
         println!("  ticks:     {ticks}");
         println!("  db:        {db}");
         println!("  data_dir:  {data_dir}");
+        if let Some(path) = scenario_path {
+            println!("  scenario:  {path}");
+        }
+        if population_scale < 1.0 {
+            println!("  sample:    {:.1}% of the population", population_scale * 100.0);
+        }
         println!();
     }
 
@@ -74,25 +324,108 @@ fn main() -> Result<()> {
     } else {
         db.to_string()
     };
-    let store = SimStore::open(&db_effective)?;
-    store.migrate()?;
+    let (store, mut engine) = match resumed {
+        Some((store, engine, _)) => (store, engine),
+        None => {
+            let store = SimStore::open(&db_effective)?;
+            store.migrate()?;
+
+            let run_id = format!("run-{seed}-{}", chrono_tick());
+            let mut provenance = RunProvenance::capture(
+                args.clone(),
+                data_dir,
+                option_env!("FINCRIME_GIT_COMMIT"),
+                ENABLED_FEATURES,
+            )?;
+            if let Some(file) = &scenario_file {
+                provenance = provenance.with_scenario(file)?;
+            }
+            store.insert_run_with_provenance(&run_id, seed, env!("CARGO_PKG_VERSION"), &provenance)?;
 
-    let run_id = format!("run-{seed}-{}", chrono_tick());
-    store.insert_run(&run_id, seed, env!("CARGO_PKG_VERSION"))?;
+            let file = scenario_file.clone().unwrap_or_default();
+            let engine = SimEngine::build_with_scenario(run_id, seed, &store, data_dir, population_scale, &file)?;
+            (store, engine)
+        }
+    };
+    let run_id = engine.run_id.clone();
+    if event_log_detail_ticks.is_some() || event_log_export.is_some() {
+        engine.set_event_log_retention(EventLogRetentionConfig {
+            enabled: true,
+            detail_ticks: event_log_detail_ticks.unwrap_or(365),
+            compact_interval_ticks: 30,
+            export_dir: event_log_export,
+        });
+    }
+    // Phase 4.57: tail the run's events from outside
+    if let Some(path) = event_stream {
+        engine.set_event_stream(&EventStreamConfig {
+            enabled: true,
+            path,
+            rotate_bytes: args
+                .windows(2)
+                .find(|w| w[0] == "--event-stream-rotate-bytes")
+                .and_then(|w| w[1].parse::<u64>().ok()),
+            keep_files: parse_arg(&args, "--event-stream-keep", 5usize),
+        })?;
+    }
 
-    let mut engine = SimEngine::build(run_id.clone(), seed, &store, data_dir)?;
+    if let Some(port) = grpc_port {
+        return serve_grpc(engine, port);
+    }
 
-    if ipc_mode {
-        run_ipc_loop(&mut engine, &run_id)?;
+    if ipc_mode || ws_port.is_some() {
+        // Runs created over IPC load the same scenario, each into a database beside this one
+        let template = RunTemplate {
+            db_dir: (db != ":memory:").then(|| Path::new(db).parent().unwrap_or(Path::new(".")).to_path_buf()),
+            config: load_config(data_dir, scenario_file.as_ref(), population_scale)?,
+            version: env!("CARGO_PKG_VERSION").to_string(),
+        };
+        let mut runs = RunManager::new(engine, template);
+        match ws_port {
+            Some(port) => serve_ws(runs, port)?,
+            None => run_ipc_loop(&mut runs)?,
+        }
     } else {
         engine.run_ticks(ticks)?;
-        print_summary(&engine, &store, &run_id, ticks)?;
+        print_summary(&engine, &store, &run_id, engine.clock.current_tick)?;
+
+        // Phase 4.19: ground-truth post-mortem, persisted as JSON
+        let report = engine.post_mortem()?;
+        let report_path = report_path.unwrap_or_else(|| format!("post_mortem_{run_id}.json"));
+        std::fs::write(&report_path, serde_json::to_string_pretty(&report)?)?;
+        print_post_mortem(&report, &report_path);
+
+        // Phase 4.79: flat files for analysis outside the engine
+        if let Some(dir) = export_dir {
+            println!("=== EXPORT ({export_format}) ===");
+            for table in export::export_run(&store, &run_id, export_format, Path::new(dir))? {
+                println!("  {:<20} {:>9} rows  {}", table.table, table.rows, table.path.display());
+            }
+        }
+
+        // Phase 4.84: the run's SARs as an e-filing batch
+        if let Some(dir) = sar_export_dir {
+            println!("=== SAR EXPORT ({sar_export_format}) ===");
+            match sar_export::export_sars(&store, &run_id, sar_export_format, Path::new(dir))? {
+                Some(batch) => println!("  {} {:>6} SARs  {}", batch.batch_id, batch.sars, batch.path.display()),
+                None => println!("  no SARs to export"),
+            }
+        }
     }
 
     Ok(())
 }
 
-fn run_ipc_loop(engine: &mut SimEngine, run_id: &str) -> Result<()> {
+/// The data directory's config with the scenario file over it, scaled.
+fn load_config(data_dir: &str, scenario_file: Option<&scenario::ScenarioFile>, scale: f64) -> Result<SimConfig> {
+    let mut config = SimConfig::load(data_dir)?;
+    if let Some(file) = scenario_file {
+        file.apply(&mut config)?;
+    }
+    config.with_population_scale(scale)
+}
+
+fn run_ipc_loop(runs: &mut RunManager) -> Result<()> {
     let stdin = io::stdin();
     let mut stdout = io::stdout();
     let mut handle = stdin.lock();
@@ -104,96 +437,38 @@ fn run_ipc_loop(engine: &mut SimEngine, run_id: &str) -> Result<()> {
         if bytes_read == 0 {
             break; // EOF
         }
-
-        let cmd: IpcCommand = match serde_json::from_str(&buffer) {
-            Ok(c) => c,
-            Err(e) => {
-                let err_json = serde_json::json!({ "error": e.to_string() });
-                writeln!(stdout, "{}", err_json)?;
-                stdout.flush()?;
-                continue;
-            }
-        };
-
-        match cmd {
-            IpcCommand::Quit => break,
-            IpcCommand::Tick { count } => {
-                engine.run_ticks(count)?;
-                let state = build_ui_state(engine, run_id)?;
-                writeln!(stdout, "{}", serde_json::to_string(&state)?)?;
-            }
-            IpcCommand::GetState => {
-                let state = build_ui_state(engine, run_id)?;
-                writeln!(stdout, "{}", serde_json::to_string(&state)?)?;
-            }
-            IpcCommand::Command { cmd, payload } => {
-                handle_command(engine, run_id, &cmd, payload)?;
-                let state = build_ui_state(engine, run_id)?;
-                writeln!(stdout, "{}", serde_json::to_string(&state)?)?;
-            }
-        }
+        let keep_going = ipc::handle_line(runs, &buffer, &mut stdout)?;
         stdout.flush()?;
+        if !keep_going {
+            break;
+        }
     }
     Ok(())
 }
 
-fn handle_command(
-    engine: &mut SimEngine,
-    run_id: &str,
-    cmd: &str,
-    payload: serde_json::Value,
-) -> Result<()> {
-    match cmd {
-        "resolve_complaint" => {
-            let complaint_id = payload["complaint_id"].as_str().unwrap_or_default();
-            let resolution = payload["resolution"].as_str().unwrap_or("explanation_only");
-            let refund = payload["refund"].as_f64().unwrap_or(0.0);
-
-            engine.store_close_complaint_direct(
-                run_id,
-                complaint_id,
-                engine.clock.current_tick,
-                resolution,
-                refund,
-            )?;
-        }
-        _ => log::warn!("Unknown command: {}", cmd),
-    }
+#[cfg(feature = "grpc")]
+fn serve_grpc(engine: SimEngine, port: u16) -> Result<()> {
+    let addr = std::net::SocketAddr::from(([127, 0, 0, 1], port));
+    println!("  grpc:      listening on {addr}");
+    tokio::runtime::Runtime::new()?.block_on(sim_runner::grpc::serve(engine, addr))?;
     Ok(())
 }
 
-fn build_ui_state(engine: &SimEngine, run_id: &str) -> Result<UiState> {
-    // Gather all KPIs
-    let active_customers = engine.store.customer_count(run_id, "active")?;
-    let churned_customers = engine.store_churned_count(run_id)?;
-    let complaint_count = engine.store_complaint_count(run_id)?;
-    let sla_breaches = engine.store_sla_breach_count(run_id)?;
-    let backlog = engine.store_complaint_backlog(run_id)?;
+#[cfg(not(feature = "grpc"))]
+fn serve_grpc(_engine: SimEngine, _port: u16) -> Result<()> {
+    anyhow::bail!("sim-runner was built without gRPC support; rebuild with `--features grpc`")
+}
 
-    // Economics
-    let pnl_snapshots = engine.store_all_pnl_snapshots(run_id)?;
-    let (nim, eff, profit) = if let Some(last) = pnl_snapshots.last() {
-        (last.nim, last.efficiency_ratio, last.pre_tax_profit)
-    } else {
-        (0.0, 0.0, 0.0)
-    };
+#[cfg(feature = "ws")]
+fn serve_ws(runs: RunManager, port: u16) -> Result<()> {
+    let addr = std::net::SocketAddr::from(([127, 0, 0, 1], port));
+    println!("  ws:        listening on ws://{addr}");
+    sim_runner::ws::serve(runs, addr)
+}
 
-    let complaints = engine.store.open_complaints(run_id)?;
-
-    Ok(UiState {
-        tick: engine.clock.current_tick,
-        paused: engine.clock.paused,
-        active_customers,
-        churned_customers,
-        complaint_count,
-        sla_breaches,
-        backlog,
-        nim,
-        efficiency_ratio: eff,
-        pre_tax_profit: profit,
-        pnl_history: pnl_snapshots,
-        complaints,
-    })
+#[cfg(not(feature = "ws"))]
+fn serve_ws(_runs: RunManager, _port: u16) -> Result<()> {
+    anyhow::bail!("sim-runner was built without WebSocket support; rebuild with `--features ws`")
 }
 
 fn print_summary(engine: &SimEngine, store: &SimStore, run_id: &str, ticks: u64) -> Result<()> {
@@ -218,9 +493,23 @@ fn print_summary(engine: &SimEngine, store: &SimStore, run_id: &str, ticks: u64)
     println!("  sla breaches:   {sla_breaches}");
     println!("  backlog:        {backlog}");
 
+    let pnl_snapshots = engine.store_all_pnl_snapshots(run_id).unwrap_or_default();
+    let scale = engine.population_scale();
+    if scale < 1.0 {
+        let full = |count: i64| (count as f64 / scale).round();
+        println!();
+        println!("=== FULL-BANK ESTIMATE ({:.1}% sample) ===", scale * 100.0);
+        println!("  customers:      {:.0}", full(customers));
+        println!("  churned:        {:.0}", full(churned));
+        println!("  total txns:     {:.0}", full(total_txns));
+        println!("  complaints:     {:.0}", full(complaints));
+        if let Some(p) = pnl_snapshots.last() {
+            println!("  {} profit: ${:.0}", p.period, p.pre_tax_profit / scale);
+        }
+    }
+
     println!();
     println!("=== FINANCIAL SUMMARY (Last 4 Quarters) ===");
-    let pnl_snapshots = engine.store_all_pnl_snapshots(run_id).unwrap_or_default();
     if pnl_snapshots.is_empty() {
         println!("  (No quarters completed yet)");
     } else {
@@ -232,9 +521,114 @@ fn print_summary(engine: &SimEngine, store: &SimStore, run_id: &str, ticks: u64)
             );
         }
     }
+
+    println!();
+    println!("=== COVERAGE ASSESSMENT (Ground Truth) ===");
+    let coverage = store.latest_coverage_assessment(run_id).unwrap_or_default();
+    if coverage.is_empty() {
+        println!("  (No assessments completed yet)");
+    } else {
+        println!("  as of tick {}", coverage[0].tick);
+        for c in &coverage {
+            let status = match (c.covered, c.present()) {
+                (false, true) => "UNCOVERED",
+                (true, _) => "covered",
+                (false, false) => "uncovered (absent)",
+            };
+            let rules = if c.rules.is_empty() { "-" } else { c.rules.as_str() };
+            println!(
+                "  {:<12} | {:<18} | occurrences: {:>4} | rules: {}",
+                c.typology, status, c.occurrences, rules
+            );
+        }
+    }
     Ok(())
 }
 
+fn print_post_mortem(report: &PostMortem, path: &str) {
+    let undetected: Vec<_> = report.undetected().collect();
+    let cf = &report.counterfactual;
+
+    println!();
+    println!("=== POST-MORTEM (Ground Truth) ===");
+    println!("  fraud cases:    {}", report.cases.len());
+    println!("  undetected:     {}", undetected.len());
+    if let Some(rate) = cf.detection_rate {
+        println!("  detection rate: {:.1}%", rate * 100.0);
+    }
+    println!("  actual loss:    ${:.0}", cf.actual_loss);
+    println!("  with perfect detection: ${:.0} (avoidable ${:.0})", cf.perfect_detection_loss, cf.avoidable_loss);
+    println!("  undetected loss: ${:.0}", cf.undetected_loss);
+
+    let latency = &report.latency;
+    if let (Some(mean), Some(p50), Some(p90)) = (latency.mean, latency.p50, latency.p90) {
+        println!("  detection latency: mean {mean:.1} | p50 {p50} | p90 {p90} days");
+        for bucket in &latency.buckets {
+            println!("    {:<10} {}", bucket.label, bucket.count);
+        }
+    }
+
+    for score in &report.detection {
+        let pct = |v: Option<f64>| v.map_or("-".to_string(), |v| format!("{:.1}%", v * 100.0));
+        println!(
+            "  {} vs {}: {} of {} flagged | precision {} | recall {}",
+            score.rule_id,
+            score.typology,
+            score.true_positives,
+            score.positives,
+            pct(score.precision),
+            pct(score.recall)
+        );
+    }
+
+    let mut worst = undetected;
+    worst.sort_by(|a, b| b.loss.total_cmp(&a.loss));
+    for case in worst.iter().take(5) {
+        println!(
+            "  missed: {:<18} {} ticks {}-{} | loss ${:.0}",
+            case.typology, case.customer_id, case.first_tick, case.last_tick, case.loss
+        );
+    }
+    println!("  full report:    {path}");
+}
+
+fn print_seed_sensitivity(report: &SeedSensitivity) {
+    let (first, last) = (report.seeds.first().copied().unwrap_or(0), report.seeds.last().copied().unwrap_or(0));
+    println!("=== SEED SENSITIVITY ===");
+    println!("  seeds:          {first}-{last} ({} runs, {} ticks)", report.seeds.len(), report.ticks);
+    for m in &report.metrics {
+        let cv = m.cv.map_or("-".to_string(), |cv| format!("{:.1}%", cv * 100.0));
+        let flag = if m.fragile { "  SEED-FRAGILE" } else { "" };
+        println!(
+            "  {:<15} | mean {:>14.2} | sd {:>12.2} | cv {:>7} | noise band {:>12.2}{flag}",
+            m.metric, m.mean, m.std_dev, cv, m.noise_band
+        );
+    }
+    for failure in &report.failures {
+        println!("  seed {} failed: {}", failure.seed, failure.error);
+    }
+    let fragile: Vec<&str> = report.fragile().map(|m| m.metric.as_str()).collect();
+    if fragile.is_empty() {
+        println!("  no metric varies by more than {:.0}% of its mean", report.fragile_cv * 100.0);
+    } else {
+        println!("  seed-fragile:   {}", fragile.join(", "));
+    }
+}
+
+/// Structured logs to stderr, filtered by RUST_LOG (default: errors only).
+/// Events inside a tick carry `run_id`, `tick` and `subsystem` from the
+/// engine's spans; `--log-json` emits one JSON object per line.
+fn init_logging(json: bool) {
+    let filter = tracing_subscriber::EnvFilter::try_from_default_env()
+        .unwrap_or_else(|_| tracing_subscriber::EnvFilter::new("error"));
+    let builder = tracing_subscriber::fmt().with_env_filter(filter).with_writer(io::stderr);
+    if json {
+        builder.json().with_current_span(false).with_span_list(true).init();
+    } else {
+        builder.init();
+    }
+}
+
 fn parse_arg<T: std::str::FromStr + Copy>(args: &[String], flag: &str, default: T) -> T {
     args.windows(2)
         .find(|w| w[0] == flag)
@@ -242,6 +636,19 @@ fn parse_arg<T: std::str::FromStr + Copy>(args: &[String], flag: &str, default:
         .unwrap_or(default)
 }
 
+/// `A..B` for every seed from A to B inclusive, or a comma-separated list.
+fn parse_seeds(spec: &str) -> Result<Vec<u64>> {
+    let seed = |s: &str| s.trim().parse::<u64>().map_err(|_| anyhow::anyhow!("bad seed '{s}' in --seeds {spec}"));
+    let seeds: Vec<u64> = match spec.split_once("..") {
+        Some((first, last)) => (seed(first)?..=seed(last)?).collect(),
+        None => spec.split(',').map(seed).collect::<Result<_>>()?,
+    };
+    if seeds.is_empty() {
+        anyhow::bail!("--seeds {spec} names no seeds");
+    }
+    Ok(seeds)
+}
+
 fn chrono_tick() -> u64 {
     use std::time::{SystemTime, UNIX_EPOCH};
     SystemTime::now()
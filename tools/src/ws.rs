@@ -0,0 +1,148 @@
+//! WebSocket server mode (Phase 4.75).
+//!
+//! Serves the JSON IPC protocol over WebSocket, for browser dashboards:
+//! each text message is one `IpcRequest` and is answered with one text
+//! message, exactly as the stdin loop answers a line. Any number of
+//! clients may connect and address any hosted run.
+//!
+//! After a `tick` request every other client is sent a `StateUpdate`
+//! with the run's summary state, so dashboards watching a run someone
+//! else is driving stay current. The update goes out once the sender has
+//! its reply; one that cannot be built is logged and skipped. `quit` or a refused protocol version
+//! closes only the sender's connection.
+//!
+//! The runs are not `Sync`, so requests take turns on a mutex; a long
+//! tick holds every client until it finishes. Each connection has its
+//! own thread, which polls its socket so broadcasts queued for it go out
+//! between requests.
+
+use anyhow::Result;
+use fincrime_core::{
+    protocol::{ErrorResponse, IpcCommand, IpcRequest, StateUpdate, UiState},
+    run_manager::RunManager,
+};
+use std::{
+    io::ErrorKind,
+    net::{TcpListener, TcpStream},
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        mpsc, Arc, Mutex,
+    },
+    time::Duration,
+};
+use tungstenite::{Error, Message, WebSocket};
+
+/// How long a connection waits for a request before sending any
+/// broadcasts queued for it.
+const POLL_INTERVAL: Duration = Duration::from_millis(20);
+
+pub struct WsServer {
+    runs: Mutex<RunManager>,
+    clients: Mutex<Vec<(u64, mpsc::Sender<String>)>>,
+    next_client: AtomicU64,
+}
+
+impl WsServer {
+    pub fn new(runs: RunManager) -> Self {
+        Self { runs: Mutex::new(runs), clients: Mutex::new(Vec::new()), next_client: AtomicU64::new(0) }
+    }
+
+    /// Accept clients on `listener` until it fails, each on its own
+    /// thread.
+    pub fn serve(self: Arc<Self>, listener: TcpListener) -> Result<()> {
+        for stream in listener.incoming() {
+            let stream = stream?;
+            let server = Arc::clone(&self);
+            std::thread::spawn(move || {
+                if let Err(e) = server.serve_client(stream) {
+                    tracing::debug!("websocket client dropped: {e}");
+                }
+            });
+        }
+        Ok(())
+    }
+
+    fn serve_client(&self, stream: TcpStream) -> Result<()> {
+        let mut socket = tungstenite::accept(stream).map_err(|e| anyhow::anyhow!("websocket handshake: {e}"))?;
+        socket.get_ref().set_read_timeout(Some(POLL_INTERVAL))?;
+        let id = self.next_client.fetch_add(1, Ordering::Relaxed);
+        let (sender, updates) = mpsc::channel();
+        self.lock_clients().push((id, sender));
+
+        let result = self.client_loop(id, &mut socket, &updates);
+        self.lock_clients().retain(|(client, _)| *client != id);
+        result
+    }
+
+    fn client_loop(&self, id: u64, socket: &mut WebSocket<TcpStream>, updates: &mpsc::Receiver<String>) -> Result<()> {
+        loop {
+            while let Ok(update) = updates.try_recv() {
+                socket.send(Message::text(update))?;
+            }
+            let text = match socket.read() {
+                Ok(Message::Text(text)) => text,
+                Ok(Message::Close(_)) | Err(Error::ConnectionClosed | Error::AlreadyClosed) => return Ok(()),
+                Ok(_) => continue,
+                Err(Error::Io(e)) if matches!(e.kind(), ErrorKind::WouldBlock | ErrorKind::TimedOut) => continue,
+                Err(e) => return Err(e.into()),
+            };
+            let (reply, keep_going, ticked) = self.answer(text.as_str())?;
+            // `quit` has no answer
+            if !reply.is_empty() {
+                socket.send(Message::text(reply))?;
+            }
+            if let Some(run_id) = ticked {
+                // The sender has its reply; a failed update costs the others one refresh
+                if let Err(e) = self.broadcast_state(id, run_id.as_deref()) {
+                    tracing::warn!("state update after tick not sent: {e}");
+                }
+            }
+            if !keep_going {
+                socket.close(None)?;
+                // Send the close frame and wait for the client's
+                while socket.read().is_ok() {}
+                return Ok(());
+            }
+        }
+    }
+
+    /// Answer one message. Returns the reply, false once the client's
+    /// session should end, and the run a `tick` was addressed to.
+    fn answer(&self, text: &str) -> Result<(String, bool, Option<Option<String>>)> {
+        let request: IpcRequest = match serde_json::from_str(text) {
+            Ok(request) => request,
+            Err(e) => return Ok((serde_json::to_string(&ErrorResponse::invalid_message(e))?, true, None)),
+        };
+        let ticked = matches!(request.command, IpcCommand::Tick { .. }).then(|| request.run_id.clone());
+
+        let mut runs = self.runs.lock().unwrap_or_else(|e| e.into_inner());
+        let mut reply = Vec::new();
+        let keep_going = crate::ipc::handle_request(&mut runs, request, &mut reply)?;
+        Ok((String::from_utf8(reply)?.trim_end().to_string(), keep_going, ticked))
+    }
+
+    /// Send every client but `from` the state of the run `from` ticked.
+    fn broadcast_state(&self, from: u64, run_id: Option<&str>) -> Result<()> {
+        let mut runs = self.runs.lock().unwrap_or_else(|e| e.into_inner());
+        if let Ok(run) = runs.run_mut("tick", run_id) {
+            let update = StateUpdate { state_update: UiState::build(&run.engine, true)? };
+            self.broadcast(from, &serde_json::to_string(&update)?);
+        }
+        Ok(())
+    }
+
+    /// Queue `message` for every client but `from`.
+    fn broadcast(&self, from: u64, message: &str) {
+        self.lock_clients().retain(|(client, sender)| *client == from || sender.send(message.to_string()).is_ok());
+    }
+
+    fn lock_clients(&self) -> std::sync::MutexGuard<'_, Vec<(u64, mpsc::Sender<String>)>> {
+        self.clients.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+/// Serve `runs` over WebSocket on `addr` until the listener fails.
+pub fn serve(runs: RunManager, addr: std::net::SocketAddr) -> Result<()> {
+    let listener = TcpListener::bind(addr)?;
+    Arc::new(WsServer::new(runs)).serve(listener)
+}
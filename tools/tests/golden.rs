@@ -0,0 +1,71 @@
+//! Golden-run regression tests.
+//!
+//! Tests cover: the committed fixture matching a fresh run, a changed
+//! table checksum failing the check with the table named, and changed
+//! scenario data alone being reported without failing it.
+
+use std::path::{Path, PathBuf};
+use std::process::{Command, Output};
+
+const FIXTURE: &str = "tools/tests/golden/golden_run.json";
+
+fn root() -> &'static Path {
+    Path::new(env!("CARGO_MANIFEST_DIR")).parent().unwrap()
+}
+
+/// Run `--golden-check` from the workspace root against `fixture`.
+fn golden_check(fixture: &Path) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_sim-runner"))
+        .current_dir(root())
+        .arg("--golden-check")
+        .arg(fixture)
+        .env_remove("RUST_LOG")
+        .output()
+        .expect("run sim-runner")
+}
+
+/// A copy of the committed fixture with `edit` applied.
+fn edited_fixture(name: &str, edit: impl FnOnce(&mut serde_json::Value)) -> PathBuf {
+    let mut fixture: serde_json::Value =
+        serde_json::from_str(&std::fs::read_to_string(root().join(FIXTURE)).unwrap()).unwrap();
+    edit(&mut fixture);
+    let path = std::env::temp_dir().join(format!("golden_{name}_{}.json", std::process::id()));
+    std::fs::write(&path, fixture.to_string()).unwrap();
+    path
+}
+
+/// Re-running the committed fixture's seed reproduces every table. A
+/// change that alters simulation behaviour on purpose re-records it with
+/// `--golden-record`.
+#[test]
+fn committed_fixture_matches() {
+    let output = golden_check(Path::new(FIXTURE));
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(output.status.success(), "{stdout}{}", String::from_utf8_lossy(&output.stderr));
+    assert!(stdout.contains("golden run matches"), "{stdout}");
+}
+
+/// A table that no longer matches fails the check and is named.
+#[test]
+fn table_mismatch_fails() {
+    let fixture = edited_fixture("table", |f| f["tables"]["complaint"]["rows"] = 1.into());
+    let output = golden_check(&fixture);
+    let _ = std::fs::remove_file(&fixture);
+    assert!(!output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("complaint: recorded 1 rows"), "{stdout}");
+    assert!(!stdout.contains("customer:"), "{stdout}");
+    assert!(String::from_utf8_lossy(&output.stderr).contains("--golden-record"));
+}
+
+/// Edited scenario data is reported, but the check passes while every
+/// table still matches.
+#[test]
+fn scenario_change_alone_passes() {
+    let fixture = edited_fixture("scenario", |f| f["scenario_hash"] = "0".repeat(64).into());
+    let output = golden_check(&fixture);
+    let _ = std::fs::remove_file(&fixture);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(output.status.success(), "{stdout}");
+    assert!(stdout.contains("scenario data changed"), "{stdout}");
+}
@@ -0,0 +1,110 @@
+//! gRPC server mode tests — Phase 4.23.
+//!
+//! Tests cover: the version handshake refusing a mismatched client,
+//! Tick and GetState answering with the same state the IPC loop sends,
+//! StreamEvents pushing each tick's events as it completes, and Command
+//! rejecting a malformed payload or an unknown command.
+//!
+//! Run with `cargo test -p sim-runner --features grpc`.
+
+#![cfg(feature = "grpc")]
+
+use fincrime_core::engine::SimEngine;
+use fincrime_core::protocol::PROTOCOL_VERSION;
+use sim_runner::grpc::pb::{self, simulation_client::SimulationClient};
+use sim_runner::grpc::SimService;
+use tokio_stream::wrappers::TcpListenerStream;
+use tonic::transport::Channel;
+
+/// Serve a fresh test engine on a loopback port and connect to it.
+async fn connect(run_id: &str, seed: u64) -> SimulationClient<Channel> {
+    let engine = SimEngine::build_test(run_id.to_string(), seed).expect("build test engine");
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    tokio::spawn(
+        tonic::transport::Server::builder()
+            .add_service(SimService::new(engine).into_server())
+            .serve_with_incoming(TcpListenerStream::new(listener)),
+    );
+    SimulationClient::connect(format!("http://{addr}")).await.unwrap()
+}
+
+/// A client that names the server's version, or none, is told it; one on
+/// another version is refused.
+#[tokio::test]
+async fn version_handshake_refuses_mismatch() {
+    let mut client = connect("grpc-version-test", 0x6290_0001).await;
+    let reply = client.get_protocol_version(pb::VersionRequest { client_version: None }).await.unwrap();
+    assert_eq!(reply.into_inner().protocol_version, PROTOCOL_VERSION);
+    assert!(client
+        .get_protocol_version(pb::VersionRequest { client_version: Some(PROTOCOL_VERSION) })
+        .await
+        .is_ok());
+
+    let refusal = client
+        .get_protocol_version(pb::VersionRequest { client_version: Some(PROTOCOL_VERSION + 1) })
+        .await
+        .unwrap_err();
+    assert_eq!(refusal.code(), tonic::Code::FailedPrecondition);
+    assert!(refusal.message().contains("mismatch"));
+}
+
+/// Tick advances and answers with the state; GetState reads it back
+/// without advancing.
+#[tokio::test]
+async fn tick_and_get_state_match_ipc() {
+    let run_id = "grpc-state-test";
+    let mut client = connect(run_id, 0x6290_0002).await;
+    let state = client.tick(pb::TickRequest { count: 3 }).await.unwrap().into_inner();
+    assert_eq!(state.run_id, run_id);
+    assert_eq!(state.tick, 3);
+    assert!(state.paused, "The engine pauses between calls, as over IPC");
+    assert!(state.active_customers > 0);
+
+    let again = client.get_state(pb::StateRequest {}).await.unwrap().into_inner();
+    assert_eq!(again.tick, 3);
+    assert_eq!(again.active_customers, state.active_customers);
+}
+
+/// A subscriber receives one message per tick advanced after it
+/// subscribed, each carrying that tick's events.
+#[tokio::test]
+async fn stream_pushes_each_tick() {
+    let mut client = connect("grpc-stream-test", 0x6290_0003).await;
+    let mut stream = client.stream_events(pb::StreamRequest {}).await.unwrap().into_inner();
+    client.tick(pb::TickRequest { count: 2 }).await.unwrap();
+
+    for expected in [1, 2] {
+        let tick = stream.message().await.unwrap().expect("tick events");
+        assert_eq!(tick.tick, expected);
+        assert!(!tick.events.is_empty(), "Subsystem events for tick {expected}");
+        assert!(tick.events.iter().all(|e| !e.subsystem.is_empty() && !e.payload_json.is_empty()));
+    }
+}
+
+/// A payload that is not JSON and an unknown command are both invalid
+/// arguments, and neither stops the session.
+#[tokio::test]
+async fn command_validates_payload() {
+    let mut client = connect("grpc-command-test", 0x6290_0004).await;
+    let bad = client
+        .command(pb::CommandRequest {
+            cmd: "resolve_complaint".into(),
+            payload_json: "{not json".into(),
+        })
+        .await
+        .unwrap_err();
+    assert_eq!(bad.code(), tonic::Code::InvalidArgument);
+
+    let unknown = client
+        .command(pb::CommandRequest {
+            cmd: "launch_rocket".into(),
+            payload_json: String::new(),
+        })
+        .await
+        .unwrap_err();
+    assert_eq!(unknown.code(), tonic::Code::InvalidArgument);
+
+    let state = client.get_state(pb::StateRequest {}).await.unwrap().into_inner();
+    assert_eq!(state.tick, 0);
+}
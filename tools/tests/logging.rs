@@ -0,0 +1,56 @@
+//! sim-runner logging tests.
+//!
+//! Tests cover: `--log-json` writing one JSON object per stderr line with
+//! the run, tick and subsystem spans attached, and the default filter
+//! keeping stderr quiet.
+
+use std::path::Path;
+use std::process::{Command, Output};
+
+/// Run sim-runner from the workspace root for `ticks` ticks.
+fn sim_runner(ticks: &str, extra: &[&str], rust_log: Option<&str>) -> Output {
+    let root = Path::new(env!("CARGO_MANIFEST_DIR")).parent().unwrap();
+    let report = std::env::temp_dir().join(format!("sim_runner_logging_{}_{ticks}.json", std::process::id()));
+    let mut cmd = Command::new(env!("CARGO_BIN_EXE_sim-runner"));
+    cmd.current_dir(root)
+        .args(["--seed", "7", "--ticks", ticks, "--report"])
+        .arg(&report)
+        .args(extra)
+        .env_remove("RUST_LOG");
+    if let Some(filter) = rust_log {
+        cmd.env("RUST_LOG", filter);
+    }
+    let output = cmd.output().expect("run sim-runner");
+    let _ = std::fs::remove_file(report);
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    output
+}
+
+/// Every JSON line logged inside a tick lists the tick span with run_id
+/// and tick, then the subsystem span.
+#[test]
+fn log_json_lines_carry_spans() {
+    let output = sim_runner("2", &["--log-json"], Some("debug"));
+    let lines: Vec<serde_json::Value> = String::from_utf8(output.stderr)
+        .unwrap()
+        .lines()
+        .map(|l| serde_json::from_str(l).unwrap_or_else(|e| panic!("{e}: {l}")))
+        .collect();
+    let in_ticks: Vec<_> = lines.iter().filter(|l| l["spans"].is_array()).collect();
+    assert!(!in_ticks.is_empty(), "Debug logging during ticks");
+    for line in in_ticks {
+        let spans = line["spans"].as_array().unwrap();
+        assert_eq!(spans[0]["name"], "tick");
+        assert!(spans[0]["run_id"].as_str().unwrap().starts_with("run-7-"));
+        assert!(spans[0]["tick"].as_u64().is_some_and(|t| (1..=2).contains(&t)));
+        assert!(spans.iter().any(|s| s["name"] == "subsystem" && s["subsystem"].is_string()));
+    }
+}
+
+/// Without RUST_LOG only errors are logged, so a clean run writes
+/// nothing to stderr.
+#[test]
+fn default_filter_is_quiet() {
+    let output = sim_runner("1", &[], None);
+    assert!(output.stderr.is_empty(), "{}", String::from_utf8_lossy(&output.stderr));
+}
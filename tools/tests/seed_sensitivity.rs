@@ -0,0 +1,33 @@
+//! sim-runner seed sensitivity tests.
+//!
+//! Tests cover: `--seed-sensitivity` running the scenario from the seeds
+//! after `--seed`, printing each outcome's spread and writing the JSON
+//! report.
+
+use std::path::Path;
+use std::process::Command;
+
+/// The table lists every outcome and the report names the seeds run.
+#[test]
+fn seed_sensitivity_prints_and_writes_report() {
+    let root = Path::new(env!("CARGO_MANIFEST_DIR")).parent().unwrap();
+    let report = std::env::temp_dir().join(format!("sim_runner_sensitivity_{}.json", std::process::id()));
+    let output = Command::new(env!("CARGO_BIN_EXE_sim-runner"))
+        .current_dir(root)
+        .args(["--seed", "11", "--ticks", "5", "--population-scale", "0.05", "--seed-sensitivity", "3", "--report"])
+        .arg(&report)
+        .env_remove("RUST_LOG")
+        .output()
+        .expect("run sim-runner");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(output.status.success(), "{stdout}{}", String::from_utf8_lossy(&output.stderr));
+    assert!(stdout.contains("seeds:          11-13 (3 runs, 5 ticks)"), "{stdout}");
+    for metric in ["pre_tax_profit", "fraud_loss", "case_loss", "churned", "complaints"] {
+        assert!(stdout.contains(metric), "{metric}: {stdout}");
+    }
+
+    let json: serde_json::Value = serde_json::from_str(&std::fs::read_to_string(&report).unwrap()).unwrap();
+    let _ = std::fs::remove_file(&report);
+    assert_eq!(json["seeds"], serde_json::json!([11, 12, 13]));
+    assert!(json["metrics"].as_array().unwrap().iter().all(|m| m["fragile"].is_boolean()));
+}
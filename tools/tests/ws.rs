@@ -0,0 +1,153 @@
+//! WebSocket server mode tests — Phase 4.75.
+//!
+//! Tests cover: a tick answered with the state and broadcast to the
+//! other clients; the same answers the stdin loop gives, malformed
+//! messages included; broadcasts naming the run that ticked; and `quit`
+//! closing only the sender's connection.
+//!
+//! Run with `cargo test -p sim-runner --features ws`.
+
+#![cfg(feature = "ws")]
+
+use fincrime_core::{
+    config::SimConfig,
+    engine::SimEngine,
+    protocol::PROTOCOL_VERSION,
+    run_manager::{RunManager, RunTemplate},
+};
+use serde_json::{json, Value};
+use sim_runner::{ipc, ws::WsServer};
+use std::{net::TcpStream, sync::Arc};
+use tungstenite::{stream::MaybeTlsStream, Message, WebSocket};
+
+type Client = WebSocket<MaybeTlsStream<TcpStream>>;
+
+fn runs(run_id: &str, seed: u64) -> RunManager {
+    let engine = SimEngine::build_test(run_id.to_string(), seed).expect("build test engine");
+    let template = RunTemplate { db_dir: None, config: SimConfig::default_test(), version: "0.1.0-test".into() };
+    RunManager::new(engine, template)
+}
+
+/// Serve a fresh test run on a loopback port; returns its address.
+fn serve(run_id: &str, seed: u64) -> String {
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = format!("ws://{}", listener.local_addr().unwrap());
+    let server = Arc::new(WsServer::new(runs(run_id, seed)));
+    std::thread::spawn(move || server.serve(listener));
+    addr
+}
+
+fn connect(addr: &str) -> Client {
+    tungstenite::connect(addr).unwrap().0
+}
+
+fn receive(client: &mut Client) -> Value {
+    loop {
+        match client.read().unwrap() {
+            Message::Text(text) => return serde_json::from_str(text.as_str()).unwrap(),
+            Message::Close(_) => panic!("connection closed"),
+            _ => {}
+        }
+    }
+}
+
+fn request(client: &mut Client, message: Value) -> Value {
+    client.send(Message::text(message.to_string())).unwrap();
+    receive(client)
+}
+
+/// The client that ticks gets the full state; every other client is
+/// sent the summary state as a `state_update`.
+#[test]
+fn tick_is_broadcast_to_other_clients() {
+    let addr = serve("ws-broadcast-test", 0x7750_0001);
+    let mut driver = connect(&addr);
+    let mut watchers = [connect(&addr), connect(&addr)];
+    // Both watchers are registered once they have been answered
+    for watcher in &mut watchers {
+        request(watcher, json!({ "type": "get_protocol_version" }));
+    }
+
+    let state = request(&mut driver, json!({ "type": "tick", "count": 2 }));
+    assert_eq!(state["tick"], 2);
+    assert_eq!(state["summary_only"], false);
+    for watcher in &mut watchers {
+        let update = receive(watcher);
+        assert_eq!(update["state_update"]["tick"], 2);
+        assert_eq!(update["state_update"]["run_id"], state["run_id"]);
+        assert_eq!(update["state_update"]["summary_only"], true);
+    }
+
+    // A request that does not tick is answered but not broadcast
+    let watcher = &mut watchers[0];
+    let described = request(watcher, json!({ "type": "get_state", "summary_only": true }));
+    assert_eq!(described["tick"], 2);
+    assert_eq!(described["active_customers"], state["active_customers"]);
+}
+
+/// Each request is answered as the stdin loop answers the same line.
+#[test]
+fn answers_match_the_stdin_loop() {
+    let addr = serve("ws-parity-test", 0x7750_0002);
+    let mut client = connect(&addr);
+    let mut stdin_runs = runs("ws-parity-test", 0x7750_0002);
+
+    for line in [
+        json!({ "type": "get_protocol_version", "client_version": PROTOCOL_VERSION }).to_string(),
+        "not json".to_string(),
+        json!({ "type": "tick", "count": 1, "summary_only": true }).to_string(),
+        json!({ "type": "command", "cmd": "no_such_command", "payload": {} }).to_string(),
+        json!({ "type": "list_breakpoints" }).to_string(),
+    ] {
+        client.send(Message::text(line.clone())).unwrap();
+        let over_ws = receive(&mut client);
+        let mut out = Vec::new();
+        assert!(ipc::handle_line(&mut stdin_runs, &line, &mut out).unwrap());
+        let over_stdin: Value = serde_json::from_slice(&out).unwrap();
+        assert_eq!(over_ws, over_stdin, "{line}");
+    }
+}
+
+/// A tick on a run created over the socket is broadcast under that run.
+#[test]
+fn broadcast_names_the_run_that_ticked() {
+    let addr = serve("ws-runs-test", 0x7750_0003);
+    let mut driver = connect(&addr);
+    let mut watcher = connect(&addr);
+    request(&mut watcher, json!({ "type": "list_runs" }));
+
+    let list = request(&mut driver, json!({ "type": "create_run", "run_id": "table-2", "seed": 7 }));
+    assert_eq!(list["runs"].as_array().unwrap().len(), 2);
+    let state = request(&mut driver, json!({ "type": "tick", "run_id": "table-2", "count": 3 }));
+    assert_eq!((state["run_id"].as_str(), state["tick"].as_u64()), (Some("table-2"), Some(3)));
+
+    let update = receive(&mut watcher);
+    assert_eq!(update["state_update"]["run_id"], "table-2");
+    assert_eq!(update["state_update"]["tick"], 3);
+    let default = request(&mut watcher, json!({ "type": "get_state", "summary_only": true }));
+    assert_eq!(default["tick"], 0, "The default run did not move");
+}
+
+/// `quit` closes the sender's connection; the server and other clients
+/// carry on.
+#[test]
+fn quit_closes_only_the_sender() {
+    let addr = serve("ws-quit-test", 0x7750_0004);
+    let mut leaving = connect(&addr);
+    let mut staying = connect(&addr);
+    request(&mut staying, json!({ "type": "list_runs" }));
+
+    leaving.send(Message::text(json!({ "type": "quit" }).to_string())).unwrap();
+    loop {
+        match leaving.read() {
+            Ok(Message::Close(_)) | Err(_) => break,
+            Ok(message) => assert!(!message.is_text(), "quit has no answer: {message}"),
+        }
+    }
+
+    let state = request(&mut staying, json!({ "type": "tick", "count": 1, "summary_only": true }));
+    assert_eq!(state["tick"], 1);
+    let mut late = connect(&addr);
+    let state = request(&mut late, json!({ "type": "get_state", "summary_only": true }));
+    assert_eq!(state["tick"], 1);
+}